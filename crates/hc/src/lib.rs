@@ -26,6 +26,8 @@ use holochain_cli_sandbox as hc_sandbox;
 use lazy_static::lazy_static;
 
 mod external_subcommands;
+pub mod model;
+pub mod top;
 
 // TODO: change this so it inherits clap's formatting.
 // Clap 3 and 4 format helptext using colours and bold/underline respectively.
@@ -93,6 +95,16 @@ pub enum CliSubcommand {
     Sandbox(hc_sandbox::HcSandbox),
     /// Run a local bootstrap and WebRTC signalling server.
     RunLocalServices(hc_run_local_services::HcRunLocalServices),
+    /// Inspect the databases of a conductor that is not running.
+    State(hc_sandbox::state::HcState),
+    /// Back up and restore the seeds in a conductor's keystore.
+    Keystore(hc_sandbox::keystore::HcKeystore),
+    /// Watch the queues, gossip and ops of a running conductor live.
+    Top(top::HcTop),
+    /// Move the apps of a running conductor, with their source chains, onto another one.
+    Migrate(hc_sandbox::migrate::HcMigrate),
+    /// Check recorded op traces against the model of an op on a conductor.
+    Model(model::HcModel),
     /// Allow redirect of external subcommands (like `hc-scaffold` and `hc-launch`).
     #[command(external_subcommand)]
     External(Vec<String>),
@@ -107,6 +119,11 @@ impl CliSubcommand {
             CliSubcommand::WebApp(cmd) => cmd.run().await?,
             CliSubcommand::Sandbox(cmd) => cmd.run().await?,
             CliSubcommand::RunLocalServices(cmd) => cmd.run().await,
+            CliSubcommand::State(cmd) => cmd.run().await?,
+            CliSubcommand::Keystore(cmd) => cmd.run().await?,
+            CliSubcommand::Top(cmd) => cmd.run().await?,
+            CliSubcommand::Migrate(cmd) => cmd.run().await?,
+            CliSubcommand::Model(cmd) => cmd.run().await?,
             CliSubcommand::External(args) => {
                 let command_suffix = args.first().expect("Missing subcommand name");
                 Command::new(format!("hc-{}", command_suffix))
@@ -0,0 +1,115 @@
+//! Check recorded op traces against the model of an op on a conductor.
+
+use std::path::{Path, PathBuf};
+
+use clap::{Parser, Subcommand};
+use holochain_conductor_api::op_model::{check_op_trace, OpTraceCheck, OpTraceFixture};
+use holochain_conductor_api::OpTraceEvent;
+
+/// The number of events before a violation which are printed with it.
+const CONTEXT_EVENTS: usize = 3;
+
+/// Check recorded op traces against the model of how an op passes through a conductor.
+#[derive(Debug, Parser)]
+pub struct HcModel {
+    /// The model command to run.
+    #[command(subcommand)]
+    pub subcommand: HcModelSubcommand,
+}
+
+/// The model commands.
+#[derive(Debug, Subcommand)]
+pub enum HcModelSubcommand {
+    /// Run op traces through the model and print the events which it doesn't allow.
+    ///
+    /// Each file holds the JSON list of events which `GetOpTrace` returned for one op
+    /// on one conductor. The command fails if any trace has a violation.
+    Check {
+        /// The trace files to check.
+        #[arg(required = true)]
+        traces: Vec<PathBuf>,
+    },
+    /// Make a regression fixture out of the trace of an op on a conductor which received it.
+    ///
+    /// The fixture holds how the op reached the conductor and the state the trace left it in.
+    /// A sweettest can replay it with `replay_op_trace_fixture`, which authors the op again
+    /// with a zome call on fresh conductors and asserts that the op is left in the same state.
+    Fixture {
+        /// The trace file, which must start with the op being received.
+        trace: PathBuf,
+        /// Where to write the fixture, instead of printing it.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+impl HcModel {
+    /// Run this command.
+    pub async fn run(self) -> anyhow::Result<()> {
+        match self.subcommand {
+            HcModelSubcommand::Check { traces } => {
+                let mut violations = 0;
+                for path in traces {
+                    let events = read_trace(&path)?;
+                    let check = check_op_trace(&events);
+                    violations += check.violations.len();
+                    print_check(&path, &events, &check);
+                }
+                if violations > 0 {
+                    anyhow::bail!("Found {} violations of the op model", violations);
+                }
+            }
+            HcModelSubcommand::Fixture { trace, output } => {
+                let fixture = OpTraceFixture::from_trace(&read_trace(&trace)?).map_err(|e| {
+                    anyhow::anyhow!("{} can't be made into a fixture: {}", trace.display(), e)
+                })?;
+                let json = serde_json::to_string_pretty(&fixture)?;
+                match output {
+                    Some(output) => std::fs::write(output, json)?,
+                    None => println!("{}", json),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Read the events of a trace file.
+pub fn read_trace(path: &Path) -> anyhow::Result<Vec<OpTraceEvent>> {
+    let json = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", path.display(), e))?;
+    serde_json::from_str(&json).map_err(|e| {
+        anyhow::anyhow!(
+            "{} is not a JSON list of op trace events: {}",
+            path.display(),
+            e
+        )
+    })
+}
+
+fn print_check(path: &Path, events: &[OpTraceEvent], check: &OpTraceCheck) {
+    println!(
+        "{}: {} events, {} violations",
+        path.display(),
+        events.len(),
+        check.violations.len()
+    );
+    for violation in &check.violations {
+        println!("  event {}: {}", violation.index, violation.reason);
+        let first = violation.index.saturating_sub(CONTEXT_EVENTS);
+        for (index, event) in events
+            .iter()
+            .enumerate()
+            .take(violation.index + 1)
+            .skip(first)
+        {
+            let marker = if index == violation.index { ">" } else { " " };
+            println!(
+                "  {} {:>4} {} {:?}: {}",
+                marker, index, event.timestamp, event.stage, event.detail
+            );
+        }
+        println!("    model before the event: {:?}", violation.before);
+    }
+    println!("  final model: {:?}", check.machine);
+}
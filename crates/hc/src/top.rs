@@ -0,0 +1,445 @@
+//! Watch a running conductor live, in the style of `top`.
+
+use std::io::stdout;
+use std::time::{Duration, Instant};
+
+use clap::Parser;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use crossterm::ExecutableCommand;
+use holochain_cli_sandbox::calls::get_conductor_diagnostics;
+use holochain_cli_sandbox::CmdRunner;
+use holochain_conductor_api::ConductorDiagnostics;
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Paragraph, Row, Table};
+use ratatui::{Frame, Terminal};
+
+/// Watch a running conductor: how many ops are queued for each workflow, the gossip and
+/// fetch pool of each DNA, the status of the ops which each cell authored, and the
+/// connection to each peer.
+///
+/// The view refreshes in place until `q` or `Esc` is pressed.
+#[derive(Debug, Parser)]
+pub struct HcTop {
+    /// The admin port of the running conductor.
+    #[arg(short, long)]
+    port: u16,
+
+    /// How often to refresh the view, in milliseconds.
+    #[arg(long, default_value_t = 1000)]
+    refresh_ms: u64,
+}
+
+impl HcTop {
+    /// Run this command.
+    pub async fn run(self) -> anyhow::Result<()> {
+        let mut cmd = CmdRunner::try_new(self.port).await?;
+
+        stdout().execute(EnterAlternateScreen)?;
+        enable_raw_mode()?;
+        let result = self.watch(&mut cmd).await;
+        disable_raw_mode()?;
+        stdout().execute(LeaveAlternateScreen)?;
+        result
+    }
+
+    async fn watch(&self, cmd: &mut CmdRunner) -> anyhow::Result<()> {
+        let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+        terminal.clear()?;
+        let refresh = Duration::from_millis(self.refresh_ms);
+        let mut last: Option<ConductorDiagnostics> = None;
+        loop {
+            // Keep showing the last snapshot if the conductor stops responding for a moment.
+            let error = match get_conductor_diagnostics(cmd).await {
+                Ok(diagnostics) => {
+                    last = Some(diagnostics);
+                    None
+                }
+                Err(err) => Some(err.to_string()),
+            };
+            terminal.draw(|frame| render(frame, self.port, last.as_ref(), error.as_deref()))?;
+            if tokio::task::spawn_blocking(move || quit_requested(refresh)).await?? {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Wait for up to `timeout` for the user to ask to quit.
+fn quit_requested(timeout: Duration) -> std::io::Result<bool> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() || !event::poll(remaining)? {
+            return Ok(false);
+        }
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press
+                && matches!(key.code, KeyCode::Char('q') | KeyCode::Esc)
+            {
+                return Ok(true);
+            }
+        }
+    }
+}
+
+fn render(
+    frame: &mut Frame,
+    port: u16,
+    diagnostics: Option<&ConductorDiagnostics>,
+    error: Option<&str>,
+) {
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(2),
+            Constraint::Percentage(40),
+            Constraint::Percentage(30),
+            Constraint::Min(0),
+        ])
+        .split(frame.area());
+
+    frame.render_widget(
+        Paragraph::new(status_line(port, diagnostics, error)),
+        layout[0],
+    );
+
+    let header_style = Style::default().add_modifier(Modifier::BOLD);
+    let dnas = Table::new(
+        dna_rows(diagnostics).into_iter().map(Row::new),
+        [
+            Constraint::Min(20),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(10),
+            Constraint::Length(8),
+            Constraint::Length(8),
+            Constraint::Length(8),
+        ],
+    )
+    .header(
+        Row::new([
+            "DNA", "Sys val", "App val", "Integr", "Fetch", "Fetch B", "Gossip", "Rounds", "Errors",
+        ])
+        .style(header_style),
+    )
+    .block(
+        Block::bordered()
+            .title("Workflow queues, fetch pool and gossip (rounds in the last minute)"),
+    );
+    frame.render_widget(dnas, layout[1]);
+
+    let cells = Table::new(
+        cell_rows(diagnostics).into_iter().map(Row::new),
+        [
+            Constraint::Min(20),
+            Constraint::Min(20),
+            Constraint::Length(9),
+            Constraint::Length(11),
+            Constraint::Length(9),
+            Constraint::Length(9),
+        ],
+    )
+    .header(
+        Row::new([
+            "DNA",
+            "Agent",
+            "Authored",
+            "Unpublished",
+            "Withheld",
+            "Receipts",
+        ])
+        .style(header_style),
+    )
+    .block(Block::bordered().title("Authored ops per cell"));
+    frame.render_widget(cells, layout[2]);
+
+    let connections = Table::new(
+        connection_rows(diagnostics).into_iter().map(Row::new),
+        [
+            Constraint::Min(20),
+            Constraint::Length(7),
+            Constraint::Length(9),
+            Constraint::Length(11),
+            Constraint::Length(9),
+            Constraint::Length(11),
+        ],
+    )
+    .header(
+        Row::new([
+            "Peer",
+            "Relayed",
+            "Sent",
+            "Sent B",
+            "Received",
+            "Received B",
+        ])
+        .style(header_style),
+    )
+    .block(Block::bordered().title("Connections to peers"));
+    frame.render_widget(connections, layout[3]);
+}
+
+/// The line at the top of the view, which says when the diagnostics were taken and
+/// why they couldn't be refreshed, if they couldn't.
+fn status_line(
+    port: u16,
+    diagnostics: Option<&ConductorDiagnostics>,
+    error: Option<&str>,
+) -> Line<'static> {
+    let mut status = format!("Conductor on admin port {port} | q to quit");
+    if let Some(diagnostics) = diagnostics {
+        status.push_str(&format!(" | updated {}", diagnostics.taken_at));
+    }
+    match error {
+        Some(error) => Line::styled(
+            format!("{status} | failed to refresh: {error}"),
+            Style::default().fg(Color::Red),
+        ),
+        None => Line::from(status),
+    }
+}
+
+/// The rows of the table of DNAs, with the queues, fetch pool and gossip of each.
+fn dna_rows(diagnostics: Option<&ConductorDiagnostics>) -> Vec<[String; 9]> {
+    diagnostics
+        .map(|diagnostics| diagnostics.dnas.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .map(|dna| {
+            [
+                dna.dna_hash.to_string(),
+                dna.queues.sys_validation.to_string(),
+                dna.queues.app_validation.to_string(),
+                dna.queues.integration.to_string(),
+                dna.fetch_pool.num_ops_to_fetch.to_string(),
+                dna.fetch_pool.op_bytes_to_fetch.to_string(),
+                dna.gossip.current_rounds.to_string(),
+                dna.gossip.recent_rounds.to_string(),
+                dna.gossip.recent_errors.to_string(),
+            ]
+        })
+        .collect()
+}
+
+/// The rows of the table of cells, with the status of the ops which each authored.
+fn cell_rows(diagnostics: Option<&ConductorDiagnostics>) -> Vec<[String; 6]> {
+    diagnostics
+        .map(|diagnostics| diagnostics.cells.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .map(|cell| {
+            [
+                cell.cell_id.dna_hash().to_string(),
+                cell.cell_id.agent_pubkey().to_string(),
+                cell.authored_ops.to_string(),
+                cell.unpublished_ops.to_string(),
+                cell.withheld_ops.to_string(),
+                cell.ops_awaiting_receipts.to_string(),
+            ]
+        })
+        .collect()
+}
+
+/// The rows of the table of connections, with the traffic over each.
+fn connection_rows(diagnostics: Option<&ConductorDiagnostics>) -> Vec<[String; 6]> {
+    diagnostics
+        .map(|diagnostics| diagnostics.connections.as_slice())
+        .unwrap_or_default()
+        .iter()
+        .map(|con| {
+            [
+                con.peer_id.clone(),
+                if con.relayed { "yes" } else { "no" }.to_string(),
+                con.send_message_count.to_string(),
+                con.send_bytes.to_string(),
+                con.recv_message_count.to_string(),
+                con.recv_bytes.to_string(),
+            ]
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_conductor_api::{
+        CellDiagnostics, ClockSkewReport, DnaDiagnostics, GossipActivity, PeerConnectionStats,
+        WorkflowQueueDepths,
+    };
+    use holochain_types::prelude::{AgentPubKey, CellId, DnaHash, Timestamp};
+    use ratatui::backend::TestBackend;
+
+    fn diagnostics() -> ConductorDiagnostics {
+        let mut dna = DnaDiagnostics {
+            dna_hash: DnaHash::from_raw_32(vec![1; 32]),
+            queues: WorkflowQueueDepths {
+                sys_validation: 1,
+                app_validation: 2,
+                integration: 3,
+            },
+            fetch_pool: Default::default(),
+            gossip: GossipActivity {
+                current_rounds: 6,
+                recent_rounds: 7,
+                recent_errors: 8,
+            },
+            blocking_dependencies: vec![],
+        };
+        dna.fetch_pool.num_ops_to_fetch = 4;
+        dna.fetch_pool.op_bytes_to_fetch = 5;
+        ConductorDiagnostics {
+            taken_at: Timestamp::from_micros(0),
+            cells: vec![CellDiagnostics {
+                cell_id: CellId::new(dna.dna_hash.clone(), AgentPubKey::from_raw_32(vec![2; 32])),
+                authored_ops: 10,
+                unpublished_ops: 11,
+                withheld_ops: 12,
+                ops_awaiting_receipts: 13,
+            }],
+            dnas: vec![dna],
+            connections: vec![PeerConnectionStats {
+                peer_id: "peer".to_string(),
+                opened_at: Timestamp::from_micros(0),
+                relayed: true,
+                send_message_count: 20,
+                send_bytes: 21,
+                recv_message_count: 22,
+                recv_bytes: 23,
+            }],
+            clock: ClockSkewReport::default(),
+        }
+    }
+
+    /// The text of each line of the view.
+    fn rendered(diagnostics: Option<&ConductorDiagnostics>, error: Option<&str>) -> Vec<String> {
+        let mut terminal = Terminal::new(TestBackend::new(200, 30)).unwrap();
+        terminal
+            .draw(|frame| render(frame, 1234, diagnostics, error))
+            .unwrap();
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn rows_have_a_column_per_figure() {
+        let diagnostics = diagnostics();
+        let dna_hash = diagnostics.dnas[0].dna_hash.to_string();
+        let agent = diagnostics.cells[0].cell_id.agent_pubkey().to_string();
+
+        assert_eq!(
+            dna_rows(Some(&diagnostics)),
+            vec![[
+                dna_hash.clone(),
+                "1".to_string(),
+                "2".to_string(),
+                "3".to_string(),
+                "4".to_string(),
+                "5".to_string(),
+                "6".to_string(),
+                "7".to_string(),
+                "8".to_string(),
+            ]]
+        );
+        assert_eq!(
+            cell_rows(Some(&diagnostics)),
+            vec![[
+                dna_hash,
+                agent,
+                "10".to_string(),
+                "11".to_string(),
+                "12".to_string(),
+                "13".to_string(),
+            ]]
+        );
+        assert_eq!(
+            connection_rows(Some(&diagnostics)),
+            vec![[
+                "peer".to_string(),
+                "yes".to_string(),
+                "20".to_string(),
+                "21".to_string(),
+                "22".to_string(),
+                "23".to_string(),
+            ]]
+        );
+    }
+
+    #[test]
+    fn no_diagnostics_have_no_rows() {
+        assert!(dna_rows(None).is_empty());
+        assert!(cell_rows(None).is_empty());
+        assert!(connection_rows(None).is_empty());
+    }
+
+    #[test]
+    fn status_line_reports_refresh_failures() {
+        let diagnostics = diagnostics();
+
+        let ok = status_line(1234, Some(&diagnostics), None);
+        assert_eq!(
+            ok.to_string(),
+            format!(
+                "Conductor on admin port 1234 | q to quit | updated {}",
+                diagnostics.taken_at
+            )
+        );
+        assert_eq!(ok.style.fg, None);
+
+        let failed = status_line(1234, None, Some("connection refused"));
+        assert_eq!(
+            failed.to_string(),
+            "Conductor on admin port 1234 | q to quit | failed to refresh: connection refused"
+        );
+        assert_eq!(failed.style.fg, Some(Color::Red));
+    }
+
+    #[test]
+    fn render_shows_every_table() {
+        let diagnostics = diagnostics();
+        let lines = rendered(Some(&diagnostics), None);
+        let row_of = |text: &str| {
+            lines
+                .iter()
+                .find(|line| line.contains(text))
+                .unwrap_or_else(|| panic!("{text} isn't rendered in {lines:#?}"))
+        };
+
+        assert!(row_of("Conductor on admin port 1234").contains("updated"));
+        for header in ["Sys val", "Authored", "Relayed"] {
+            row_of(header);
+        }
+        let dna_row = row_of(&diagnostics.dnas[0].dna_hash.to_string());
+        assert_eq!(
+            dna_row.split_whitespace().skip(1).collect::<Vec<_>>(),
+            ["1", "2", "3", "4", "5", "6", "7", "8", "│"]
+        );
+        let peer_row = row_of("│peer");
+        assert_eq!(
+            peer_row.split_whitespace().collect::<Vec<_>>(),
+            ["│peer", "yes", "20", "21", "22", "23", "│"]
+        );
+    }
+
+    #[test]
+    fn render_keeps_the_tables_when_a_refresh_fails() {
+        let diagnostics = diagnostics();
+        let lines = rendered(Some(&diagnostics), Some("connection refused"));
+
+        assert!(lines[0].contains("failed to refresh: connection refused"));
+        assert!(lines.iter().any(|line| line.contains("│peer")));
+    }
+}
@@ -100,6 +100,20 @@ pub enum HcDnaBundleSubcommand {
 
     /// Print the schema for a DNA manifest
     Schema,
+
+    /// Compare two `.dna` bundle files, listing what changed between them
+    /// and whether each change affects the DnaHash.
+    ///
+    /// e.g.:
+    ///
+    /// $ hc dna diff ./old/my-dna.dna ./new/my-dna.dna
+    Diff {
+        /// The path to the first bundle.
+        a: PathBuf,
+
+        /// The path to the second bundle.
+        b: PathBuf,
+    },
 }
 
 /// Work with Holochain hApp bundles.
@@ -147,6 +161,28 @@ pub enum HcAppBundleSubcommand {
         /// as each of the DNA files specified in the manifest.
         #[arg(short, long)]
         recursive: bool,
+
+        /// Sign the bundle with the publisher key in this signing key file.
+        ///
+        /// Conductors which trust the publisher will accept the bundle, and
+        /// will reject it if it is modified after signing.
+        /// Create a key file with `hc app signing-key`.
+        #[arg(long, value_name = "KEY_FILE")]
+        sign: Option<PathBuf>,
+    },
+
+    /// Create a new key file for signing hApp bundles, and print the publisher
+    /// key it signs as.
+    ///
+    /// e.g.:
+    ///
+    /// $ hc app signing-key ./publisher.key
+    ///
+    /// The printed key is what goes in the `trusted_publishers` list of a
+    /// conductor's `bundle_signing` config. Keep the key file private.
+    SigningKey {
+        /// The path to write the new key file to. It must not exist yet.
+        path: PathBuf,
     },
 
     /// Unpack parts of the `.happ` bundle file into a specific directory.
@@ -343,6 +379,9 @@ impl HcDnaBundleSubcommand {
             Self::Schema => {
                 println!("{}", include_str!("../schema/dna-manifest.schema.json"));
             }
+            Self::Diff { a, b } => {
+                print!("{}", crate::diff::diff_dna_bundles(&a, &b).await?);
+            }
         }
         Ok(())
     }
@@ -359,6 +398,7 @@ impl HcAppBundleSubcommand {
                 path,
                 output,
                 recursive,
+                sign,
             } => {
                 let name = get_app_name(&path).await?;
 
@@ -366,10 +406,19 @@ impl HcAppBundleSubcommand {
                     app_pack_recursive(&path).await?;
                 }
 
-                let (bundle_path, _) =
+                let (bundle_path, bundle) =
                     crate::packing::pack::<AppManifest>(&path, output, name, false).await?;
+                if let Some(key_path) = sign {
+                    let bundle = crate::signing::sign_bundle(bundle, &key_path).await?;
+                    bundle.write_to_file(&bundle_path).await?;
+                }
                 println!("Wrote bundle {}", bundle_path.to_string_lossy());
             }
+            Self::SigningKey { path } => {
+                let publisher = crate::signing::generate_signing_key(&path).await?;
+                println!("Wrote signing key {}", path.to_string_lossy());
+                println!("Publisher key: {}", publisher);
+            }
             Self::Unpack {
                 path,
                 output,
@@ -510,6 +559,7 @@ pub async fn web_app_pack_recursive(web_app_workdir_path: &PathBuf) -> anyhow::R
             path: ffs::canonicalize(app_workdir_location).await?,
             output: None,
             recursive: true,
+            sign: None,
         }
         .run()
         .await?;
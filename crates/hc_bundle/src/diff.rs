@@ -0,0 +1,242 @@
+//! Compare two DNA bundles.
+
+use holochain_types::prelude::*;
+use std::fmt;
+use std::path::Path;
+
+use crate::error::HcBundleResult;
+
+/// The two kinds of zome in a DNA.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ZomeKind {
+    /// An integrity zome, which is part of the DnaHash.
+    Integrity,
+    /// A coordinator zome, which can be swapped without changing the DnaHash.
+    Coordinator,
+}
+
+/// A single difference between two DNAs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DnaChange {
+    /// The DNA was renamed.
+    Name(String, String),
+    /// One of the DNA modifiers changed. The values are debug printed.
+    Modifier(&'static str, String, String),
+    /// The lineage of the DNA changed.
+    Lineage,
+    /// A zome is only in the second DNA.
+    ZomeAdded(ZomeKind, ZomeName),
+    /// A zome is only in the first DNA.
+    ZomeRemoved(ZomeKind, ZomeName),
+    /// A zome's WASM is different.
+    ZomeWasm(ZomeKind, ZomeName, Option<WasmHash>, Option<WasmHash>),
+    /// A zome's dependencies are different.
+    ZomeDependencies(ZomeKind, ZomeName, Vec<ZomeName>, Vec<ZomeName>),
+    /// The same integrity zomes are declared in a different order.
+    IntegrityZomeOrder,
+}
+
+impl DnaChange {
+    /// Whether this change on its own gives the DNA a different DnaHash.
+    pub fn affects_dna_hash(&self) -> bool {
+        match self {
+            DnaChange::Name(..) | DnaChange::Lineage => false,
+            DnaChange::Modifier(..) | DnaChange::IntegrityZomeOrder => true,
+            DnaChange::ZomeAdded(kind, _)
+            | DnaChange::ZomeRemoved(kind, _)
+            | DnaChange::ZomeWasm(kind, ..)
+            | DnaChange::ZomeDependencies(kind, ..) => *kind == ZomeKind::Integrity,
+        }
+    }
+}
+
+impl fmt::Display for ZomeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ZomeKind::Integrity => write!(f, "integrity"),
+            ZomeKind::Coordinator => write!(f, "coordinator"),
+        }
+    }
+}
+
+impl fmt::Display for DnaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let hash = |h: &Option<WasmHash>| match h {
+            Some(h) => h.to_string(),
+            None => "<not wasm>".to_string(),
+        };
+        match self {
+            DnaChange::Name(a, b) => write!(f, "name changed: {a:?} -> {b:?}"),
+            DnaChange::Modifier(field, a, b) => write!(f, "{field} changed: {a} -> {b}"),
+            DnaChange::Lineage => write!(f, "lineage changed"),
+            DnaChange::ZomeAdded(kind, name) => write!(f, "{kind} zome `{name}` was added"),
+            DnaChange::ZomeRemoved(kind, name) => write!(f, "{kind} zome `{name}` was removed"),
+            DnaChange::ZomeWasm(kind, name, a, b) => write!(
+                f,
+                "{kind} zome `{name}` wasm changed: {} -> {}",
+                hash(a),
+                hash(b)
+            ),
+            DnaChange::ZomeDependencies(kind, name, a, b) => {
+                write!(
+                    f,
+                    "{kind} zome `{name}` dependencies changed: {a:?} -> {b:?}"
+                )
+            }
+            DnaChange::IntegrityZomeOrder => write!(f, "integrity zomes were reordered"),
+        }
+    }
+}
+
+/// The differences between two DNAs.
+#[derive(Debug, Clone)]
+pub struct DnaDiff {
+    /// The DnaHash of the first DNA.
+    pub hash_a: DnaHash,
+    /// The DnaHash of the second DNA.
+    pub hash_b: DnaHash,
+    /// Everything that differs, in the order it was found.
+    pub changes: Vec<DnaChange>,
+}
+
+impl fmt::Display for DnaDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "DnaHash a: {}", self.hash_a)?;
+        writeln!(f, "DnaHash b: {}", self.hash_b)?;
+        if self.hash_a == self.hash_b {
+            writeln!(f, "The DnaHash is unchanged")?;
+        } else {
+            writeln!(f, "The DnaHash changed")?;
+        }
+        if self.changes.is_empty() {
+            return writeln!(f, "No differences");
+        }
+        for change in &self.changes {
+            if change.affects_dna_hash() {
+                writeln!(f, "  * {change} (affects DnaHash)")?;
+            } else {
+                writeln!(f, "  * {change}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Read two `.dna` bundles and compare them.
+pub async fn diff_dna_bundles(a: &Path, b: &Path) -> HcBundleResult<DnaDiff> {
+    let (a, hash_a) = DnaBundle::read_from_file(a)
+        .await?
+        .into_dna_file(DnaModifiersOpt::none())
+        .await?;
+    let (b, hash_b) = DnaBundle::read_from_file(b)
+        .await?
+        .into_dna_file(DnaModifiersOpt::none())
+        .await?;
+    Ok(DnaDiff {
+        hash_a,
+        hash_b,
+        changes: diff_dna_defs(a.dna_def(), b.dna_def()),
+    })
+}
+
+/// Compare two DNA definitions.
+pub fn diff_dna_defs(a: &DnaDef, b: &DnaDef) -> Vec<DnaChange> {
+    let mut changes = Vec::new();
+    if a.name != b.name {
+        changes.push(DnaChange::Name(a.name.clone(), b.name.clone()));
+    }
+
+    let (ma, mb) = (&a.modifiers, &b.modifiers);
+    let mut modifier = |field, a: String, b: String| {
+        if a != b {
+            changes.push(DnaChange::Modifier(field, a, b));
+        }
+    };
+    modifier(
+        "network_seed",
+        format!("{:?}", ma.network_seed),
+        format!("{:?}", mb.network_seed),
+    );
+    modifier(
+        "properties",
+        format!("{:?}", ma.properties),
+        format!("{:?}", mb.properties),
+    );
+    modifier(
+        "origin_time",
+        ma.origin_time.to_string(),
+        mb.origin_time.to_string(),
+    );
+    modifier(
+        "quantum_time",
+        format!("{:?}", ma.quantum_time),
+        format!("{:?}", mb.quantum_time),
+    );
+
+    if a.lineage != b.lineage {
+        changes.push(DnaChange::Lineage);
+    }
+
+    let integrity = |dna: &DnaDef| {
+        dna.integrity_zomes
+            .iter()
+            .map(|(n, z)| (n.clone(), z.as_any_zome_def().clone()))
+            .collect::<Vec<_>>()
+    };
+    let (ia, ib) = (integrity(a), integrity(b));
+    diff_zomes(ZomeKind::Integrity, &ia, &ib, &mut changes);
+    let names = |zomes: &[(ZomeName, ZomeDef)]| zomes.iter().map(|(n, _)| n.clone()).collect();
+    let (na, nb): (Vec<_>, Vec<_>) = (names(&ia), names(&ib));
+    // Zome indexes are positional, so the same zomes in another order make
+    // a different DNA.
+    if na != nb && na.iter().all(|n| nb.contains(n)) && nb.iter().all(|n| na.contains(n)) {
+        changes.push(DnaChange::IntegrityZomeOrder);
+    }
+
+    let coordinator = |dna: &DnaDef| {
+        dna.coordinator_zomes
+            .iter()
+            .map(|(n, z)| (n.clone(), z.as_any_zome_def().clone()))
+            .collect::<Vec<_>>()
+    };
+    diff_zomes(
+        ZomeKind::Coordinator,
+        &coordinator(a),
+        &coordinator(b),
+        &mut changes,
+    );
+
+    changes
+}
+
+fn diff_zomes(
+    kind: ZomeKind,
+    a: &[(ZomeName, ZomeDef)],
+    b: &[(ZomeName, ZomeDef)],
+    changes: &mut Vec<DnaChange>,
+) {
+    for (name, zome_a) in a {
+        match b.iter().find(|(n, _)| n == name) {
+            None => changes.push(DnaChange::ZomeRemoved(kind, name.clone())),
+            Some((_, zome_b)) => {
+                let (wasm_a, wasm_b) = (zome_a.wasm_hash(name).ok(), zome_b.wasm_hash(name).ok());
+                if wasm_a != wasm_b {
+                    changes.push(DnaChange::ZomeWasm(kind, name.clone(), wasm_a, wasm_b));
+                }
+                if zome_a.dependencies() != zome_b.dependencies() {
+                    changes.push(DnaChange::ZomeDependencies(
+                        kind,
+                        name.clone(),
+                        zome_a.dependencies().to_vec(),
+                        zome_b.dependencies().to_vec(),
+                    ));
+                }
+            }
+        }
+    }
+    for (name, _) in b {
+        if !a.iter().any(|(n, _)| n == name) {
+            changes.push(DnaChange::ZomeAdded(kind, name.clone()));
+        }
+    }
+}
@@ -42,6 +42,10 @@ pub enum HcBundleError {
 
     #[error(transparent)]
     ModuleCompileError(#[from] CompileError),
+
+    /// A bundle could not be signed
+    #[error("Signing error: {0}")]
+    SigningError(String),
 }
 
 /// HcBundle Result type.
@@ -1,13 +1,17 @@
 mod cli;
+mod diff;
 mod error;
 mod init;
 mod packing;
+mod signing;
 
 pub use cli::{
     app_pack_recursive, bundled_dnas_workdir_locations, get_app_name, get_dna_name,
     get_web_app_name, web_app_pack_recursive, HcAppBundle, HcDnaBundle, HcWebAppBundle,
 };
+pub use diff::{diff_dna_bundles, diff_dna_defs, DnaChange, DnaDiff, ZomeKind};
 pub use packing::{pack, unpack, unpack_raw};
+pub use signing::{generate_signing_key, sign_bundle};
 
 // Temporarily include a fork of wasmer from the git branch 'wamr', until it is officially released in wasmer v5
 #[cfg(feature = "wasmer_wamr")]
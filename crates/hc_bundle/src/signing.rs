@@ -0,0 +1,79 @@
+//! Sign bundles with a publisher key.
+//!
+//! A signing key file holds the 32 byte ed25519 seed of the publisher. The
+//! matching public key is an [`AgentPubKey`], which is what a conductor lists
+//! as a trusted publisher.
+
+use holochain_types::prelude::AgentPubKey;
+use mr_bundle::{Bundle, BundleSignature, Manifest};
+use std::path::Path;
+
+use crate::error::{HcBundleError, HcBundleResult};
+
+/// Create a new signing key file at the given path, returning the publisher key
+/// it signs as. An existing file is never overwritten.
+pub async fn generate_signing_key(path: &Path) -> HcBundleResult<AgentPubKey> {
+    let seed = sodoken::BufWriteSized::<{ sodoken::sign::SEEDBYTES }>::new_no_lock();
+    sodoken::random::bytes_buf(seed.clone())
+        .await
+        .map_err(signing_error)?;
+    let seed = seed.read_lock().to_vec();
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    std::io::Write::write_all(&mut options.open(path)?, &seed)?;
+
+    Ok(load_signing_key(path).await?.0)
+}
+
+/// Sign a bundle with the key in the given signing key file. Any existing
+/// signature is replaced.
+pub async fn sign_bundle<M: Manifest>(
+    bundle: Bundle<M>,
+    key_path: &Path,
+) -> HcBundleResult<Bundle<M>> {
+    let (signer, sec_key) = load_signing_key(key_path).await?;
+    let signature = sodoken::BufWriteSized::<{ sodoken::sign::BYTES }>::new_no_lock();
+    sodoken::sign::detached(signature.clone(), bundle.signing_bytes()?, sec_key)
+        .await
+        .map_err(signing_error)?;
+    let signature = signature.read_lock().to_vec();
+    Ok(bundle.with_signature(BundleSignature {
+        signer: signer.get_raw_39().to_vec(),
+        signature,
+    }))
+}
+
+async fn load_signing_key(
+    path: &Path,
+) -> HcBundleResult<(
+    AgentPubKey,
+    sodoken::BufReadSized<{ sodoken::sign::SECRETKEYBYTES }>,
+)> {
+    let seed: [u8; sodoken::sign::SEEDBYTES] =
+        std::fs::read(path)?.as_slice().try_into().map_err(|_| {
+            HcBundleError::SigningError(format!(
+                "{} is not a signing key file, it must contain a {} byte seed",
+                path.display(),
+                sodoken::sign::SEEDBYTES
+            ))
+        })?;
+    let pub_key = sodoken::BufWriteSized::<{ sodoken::sign::PUBLICKEYBYTES }>::new_no_lock();
+    let sec_key = sodoken::BufWriteSized::<{ sodoken::sign::SECRETKEYBYTES }>::new_mem_locked()
+        .map_err(signing_error)?;
+    sodoken::sign::seed_keypair(
+        pub_key.clone(),
+        sec_key.clone(),
+        sodoken::BufReadSized::from(seed),
+    )
+    .await
+    .map_err(signing_error)?;
+    let pub_key = pub_key.read_lock().to_vec();
+    Ok((AgentPubKey::from_raw_32(pub_key), sec_key.to_read_sized()))
+}
+
+fn signing_error(e: impl std::fmt::Display) -> HcBundleError {
+    HcBundleError::SigningError(e.to_string())
+}
@@ -158,6 +158,68 @@ async fn test_integrity() {
     assert_eq!(integrity_def, coordinator_def,);
 }
 
+#[test]
+fn test_dna_diff() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let pack_dna = |path: &str, name: &str| {
+        let output = tmp_dir.path().join(name);
+        let mut cmd = Command::cargo_bin("hc-dna").unwrap();
+        let cmd = cmd.args(["pack", path, "-o", output.to_str().unwrap()]);
+        cmd.assert().success();
+        output
+    };
+    let integrity_dna = pack_dna("tests/fixtures/my-app/dnas/dna3", "a.dna");
+    let coordinator_dna = pack_dna("tests/fixtures/my-app/dnas/dna4", "b.dna");
+
+    let mut cmd = Command::cargo_bin("hc-dna").unwrap();
+    let cmd = cmd.arg("diff").arg(&integrity_dna).arg(&coordinator_dna);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("The DnaHash is unchanged"))
+        .stdout(predicates::str::contains(
+            "coordinator zome `zome2` was added\n",
+        ));
+
+    let mut cmd = Command::cargo_bin("hc-dna").unwrap();
+    let cmd = cmd.arg("diff").arg(&integrity_dna).arg(&integrity_dna);
+    cmd.assert()
+        .success()
+        .stdout(predicates::str::contains("No differences"));
+}
+
+#[tokio::test]
+async fn test_app_sign() {
+    let tmp_dir = tempfile::tempdir().unwrap();
+    let key_path = tmp_dir.path().join("publisher.key");
+    let app_path = tmp_dir.path().join("signed.happ");
+
+    let mut cmd = Command::cargo_bin("hc-app").unwrap();
+    let cmd = cmd.arg("signing-key").arg(&key_path);
+    let output = cmd.assert().success().get_output().stdout.clone();
+    let publisher = String::from_utf8(output)
+        .unwrap()
+        .lines()
+        .find_map(|line| line.strip_prefix("Publisher key: ").map(str::to_string))
+        .unwrap();
+    let publisher = AgentPubKey::try_from(publisher).unwrap();
+
+    // An existing key is never overwritten
+    let mut cmd = Command::cargo_bin("hc-app").unwrap();
+    let cmd = cmd.arg("signing-key").arg(&key_path);
+    cmd.assert().failure();
+
+    let mut cmd = Command::cargo_bin("hc-app").unwrap();
+    let cmd = cmd
+        .args(["pack", "-r", "tests/fixtures/my-app/", "-o"])
+        .arg(&app_path)
+        .arg("--sign")
+        .arg(&key_path);
+    cmd.assert().success();
+
+    let bundle = read_app(&app_path).unwrap();
+    assert_eq!(bundle.verify_signature().await.unwrap(), Some(publisher));
+}
+
 #[tokio::test]
 #[cfg_attr(target_os = "windows", ignore = "theres a hash mismatch - check crlf?")]
 /// Test that a manifest with multiple integrity zomes and dependencies parses
@@ -216,6 +278,7 @@ async fn test_multi_integrity() {
                 ZomeDef::Wasm(WasmZome {
                     wasm_hash: wasm_hash.clone(),
                     dependencies: vec![],
+                    init_dependencies: Default::default(),
                     preserialized_path: None,
                 })
                 .into(),
@@ -225,6 +288,7 @@ async fn test_multi_integrity() {
                 ZomeDef::Wasm(WasmZome {
                     wasm_hash: wasm_hash.clone(),
                     dependencies: vec![],
+                    init_dependencies: Default::default(),
                     preserialized_path: None,
                 })
                 .into(),
@@ -236,6 +300,7 @@ async fn test_multi_integrity() {
                 ZomeDef::Wasm(WasmZome {
                     wasm_hash: wasm_hash2.clone(),
                     dependencies: vec!["zome1".into()],
+                    init_dependencies: Default::default(),
                     preserialized_path: None,
                 })
                 .into(),
@@ -245,12 +310,17 @@ async fn test_multi_integrity() {
                 ZomeDef::Wasm(WasmZome {
                     wasm_hash: wasm_hash2.clone(),
                     dependencies: vec!["zome1".into(), "zome2".into()],
+                    init_dependencies: Default::default(),
                     preserialized_path: None,
                 })
                 .into(),
             ),
         ],
         lineage: lineage.into_iter().collect(),
+        size_limits: Default::default(),
+        required_validation_receipts: None,
+        interactive_types: Default::default(),
+        fulltext_entry_types: Default::default(),
     };
     assert_eq!(
         dna.dna_def().integrity_zomes[0]
@@ -109,7 +109,26 @@ impl HcRunLocalServices {
     }
 
     pub async fn run_err(self) -> Result<()> {
-        let mut task_list = Vec::new();
+        let services = self.spawn().await?;
+
+        if services.tasks.is_empty() {
+            println!("All Services Disabled - Aborting");
+            return Ok(());
+        }
+
+        services.run().await;
+
+        Ok(())
+    }
+
+    /// Bind the enabled services and return their addresses. The services
+    /// make progress once [`LocalServices::run`] is awaited.
+    pub async fn spawn(self) -> Result<LocalServices> {
+        let mut services = LocalServices {
+            bootstrap_urls: Vec::new(),
+            signal_urls: Vec::new(),
+            tasks: Vec::new(),
+        };
 
         if !self.disable_bootstrap {
             let bs_ip: std::net::IpAddr = self.bootstrap_interface.parse().map_err(Error::other)?;
@@ -118,13 +137,14 @@ impl HcRunLocalServices {
                 .await
                 .map_err(Error::other)?;
             std::mem::forget(shutdown);
-            task_list.push(bs_driver);
+            services.tasks.push(bs_driver);
 
             let mut a_out = AOut::new(&self.bootstrap_address_path).await?;
 
             for addr in tx_addr(bs_addr)? {
                 a_out.write(format!("http://{addr}\n")).await?;
                 println!("# HC BOOTSTRAP - ADDR: http://{addr}");
+                services.bootstrap_urls.push(format!("http://{addr}"));
             }
 
             a_out.close().await?;
@@ -150,7 +170,7 @@ impl HcRunLocalServices {
             let addr_list = sig_hnd.bind_addrs().to_vec();
 
             // there is no real task here... just fake it
-            task_list.push(Box::pin(async move {
+            services.tasks.push(Box::pin(async move {
                 let _sig_hnd = sig_hnd;
                 std::future::pending().await
             }));
@@ -160,6 +180,7 @@ impl HcRunLocalServices {
             for addr in addr_list {
                 a_out.write(format!("ws://{addr}\n")).await?;
                 println!("# HC SIGNAL - ADDR: ws://{addr}");
+                services.signal_urls.push(format!("ws://{addr}"));
             }
 
             a_out.close().await?;
@@ -167,14 +188,25 @@ impl HcRunLocalServices {
             println!("# HC SIGNAL - RUNNING");
         }
 
-        if task_list.is_empty() {
-            println!("All Services Disabled - Aborting");
-            return Ok(());
-        }
+        Ok(services)
+    }
+}
 
-        futures::future::join_all(task_list).await;
+/// Bootstrap and signal servers started by [`HcRunLocalServices::spawn`].
+pub struct LocalServices {
+    /// The urls the bootstrap server can be reached at.
+    pub bootstrap_urls: Vec<String>,
 
-        Ok(())
+    /// The urls the signal server can be reached at.
+    pub signal_urls: Vec<String>,
+
+    tasks: Vec<futures::future::BoxFuture<'static, ()>>,
+}
+
+impl LocalServices {
+    /// Drive the services until they stop, which is normally never.
+    pub async fn run(self) {
+        futures::future::join_all(self.tasks).await;
     }
 }
 
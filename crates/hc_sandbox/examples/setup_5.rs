@@ -66,6 +66,8 @@ async fn main() -> anyhow::Result<()> {
             network_seed: None,
             ignore_genesis_failure: false,
             allow_throwaway_random_agent_key: true,
+            network_profile: None,
+            client_only: false,
         };
 
         let r = AdminRequest::InstallApp(Box::new(payload));
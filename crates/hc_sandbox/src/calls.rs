@@ -12,14 +12,37 @@ use anyhow::anyhow;
 use anyhow::bail;
 use anyhow::ensure;
 use holochain_conductor_api::conductor::paths::ConfigRootPath;
+use holochain_conductor_api::conductor::ConfigReloadReport;
 use holochain_conductor_api::AdminResponse;
+use holochain_conductor_api::AgentKeyInfo;
 use holochain_conductor_api::AppStatusFilter;
+use holochain_conductor_api::AppSummary;
+use holochain_conductor_api::AuthorValidationOutcomes;
+use holochain_conductor_api::ConductorDiagnostics;
+use holochain_conductor_api::HealthReport;
 use holochain_conductor_api::InterfaceDriver;
+use holochain_conductor_api::MembraneProofRecheckReport;
+use holochain_conductor_api::OpCensusReport;
+use holochain_conductor_api::OpMissingReceipts;
+use holochain_conductor_api::OpTraceEvent;
+use holochain_conductor_api::Page;
+use holochain_conductor_api::RegionSetDiagnostics;
+use holochain_conductor_api::SlowOperation;
+use holochain_conductor_api::StartupReport;
 use holochain_conductor_api::{AdminInterfaceConfig, AppInfo};
 use holochain_conductor_api::{AdminRequest, AppInterfaceInfo};
+use holochain_conductor_api::{AppInterfaceConnectionId, AppInterfaceConnectionInfo};
 use holochain_types::app::AppManifest;
+use holochain_types::prelude::ActionHash;
+use holochain_types::prelude::AuditLogEntry;
+use holochain_types::prelude::BackfillProgress;
+use holochain_types::prelude::DhtEpochSummary;
+use holochain_types::prelude::DhtOpArchive;
+use holochain_types::prelude::DhtOpHash;
 use holochain_types::prelude::DnaModifiersOpt;
+use holochain_types::prelude::PublicationProof;
 use holochain_types::prelude::RegisterDnaPayload;
+use holochain_types::prelude::SerializedBytes;
 use holochain_types::prelude::Timestamp;
 use holochain_types::prelude::YamlProperties;
 use holochain_types::prelude::{AgentPubKey, AppBundleSource};
@@ -27,6 +50,7 @@ use holochain_types::prelude::{CellId, InstallAppPayload};
 use holochain_types::prelude::{DnaHash, InstalledAppId};
 use holochain_types::prelude::{DnaSource, NetworkSeed};
 use kitsune_p2p_types::agent_info::AgentInfoSigned;
+use kitsune_p2p_types::dht::arq::ArqPin;
 use std::convert::TryFrom;
 
 use crate::cmds::Existing;
@@ -69,6 +93,9 @@ pub enum AdminRequestCli {
     UninstallApp(UninstallApp),
     /// Calls AdminRequest::ListAppInterfaces.
     ListAppWs,
+    /// Calls AdminRequest::ListAppInterfaceConnections.
+    ListAppConnections,
+    DisconnectAppClient(DisconnectAppClient),
     /// Calls AdminRequest::ListDnas.
     ListDnas,
     /// Calls AdminRequest::GenerateAgentPubKey.
@@ -77,16 +104,52 @@ pub enum AdminRequestCli {
     ListCells,
     /// Calls AdminRequest::ListApps.
     ListApps(ListApps),
+    /// Calls AdminRequest::ListAppSummaries.
+    ListAppSummaries(ListApps),
+    /// Calls AdminRequest::ListAgentKeys.
+    ListAgentKeys,
     EnableApp(EnableApp),
     DisableApp(DisableApp),
     DumpState(DumpState),
     DumpConductorState,
     DumpNetworkMetrics(DumpNetworkMetrics),
     DumpNetworkStats,
+    /// Calls AdminRequest::HealthCheck.
+    HealthCheck,
+    /// Calls AdminRequest::GetStartupReport.
+    GetStartupReport,
+    /// Calls AdminRequest::ListSlowOperations.
+    ListSlowOperations,
+    /// Calls AdminRequest::GetConductorDiagnostics.
+    GetConductorDiagnostics,
+    GetRegionSet(GetRegionSet),
+    GetOpCensus(GetOpCensus),
+    GetValidationOutcomes(GetValidationOutcomes),
+    GetBackfillProgress(GetBackfillProgress),
+    PauseGossip(PauseGossip),
+    ResumeGossip(ResumeGossip),
+    SetArqPin(SetArqPin),
+    RollbackSourceChain(RollbackSourceChain),
+    StartOpTrace(StartOpTrace),
+    GetOpTrace(GetOpTrace),
+    StopOpTrace(StopOpTrace),
+    ListOpsMissingReceipts(ListOpsMissingReceipts),
+    ExportOps(ExportOps),
+    ImportOps(ImportOps),
+    ListDhtEpochs(ListDhtEpochs),
+    ArchiveEpochs(ArchiveEpochs),
+    RecheckMembraneProofs(RecheckMembraneProofs),
+    ExportAuthoredData(ExportAuthoredData),
+    ExportPublicationProof(ExportPublicationProof),
+    ExportAppBundle(ExportAppBundle),
+    SetLogFilter(SetLogFilter),
+    /// Calls AdminRequest::ReloadConfig.
+    ReloadConfig,
     /// Calls AdminRequest::AddAgentInfo.
     /// _Unimplemented_.
     AddAgents,
     ListAgents(ListAgents),
+    ListAuditLog(ListAuditLog),
 }
 
 /// Calls AdminRequest::AddAdminInterfaces
@@ -177,6 +240,11 @@ pub struct InstallApp {
 
     /// Optional network seed override for every DNA in this app
     pub network_seed: Option<NetworkSeed>,
+
+    /// Install the cells of the app as pure clients of the network,
+    /// which hold no data for other agents.
+    #[arg(long)]
+    pub client_only: bool,
 }
 
 /// Calls AdminRequest::UninstallApp
@@ -227,6 +295,129 @@ pub struct DumpState {
     pub agent_key: AgentPubKey,
 }
 
+/// Calls AdminRequest::ListOpsMissingReceipts
+/// and lists the ops authored by a cell which don't have enough validation receipts yet.
+#[derive(Debug, Args, Clone)]
+pub struct ListOpsMissingReceipts {
+    /// The DNA hash half of the cell ID.
+    #[arg(value_parser = parse_dna_hash)]
+    pub dna: DnaHash,
+
+    /// The agent half of the cell ID.
+    #[arg(value_parser = parse_agent_key)]
+    pub agent_key: AgentPubKey,
+}
+
+/// Calls AdminRequest::ExportDhtOpArchive
+/// and writes the ops of a DNA which were integrated before a time to an archive file.
+#[derive(Debug, Args, Clone)]
+pub struct ExportOps {
+    /// The DNA whose ops to export.
+    #[arg(value_parser = parse_dna_hash)]
+    pub dna: DnaHash,
+
+    /// The file to write the archive to.
+    pub path: PathBuf,
+
+    /// Only export ops which were integrated before this time, e.g. `2024-01-01T00:00:00Z`.
+    /// Defaults to now.
+    #[arg(long)]
+    pub integrated_before: Option<Timestamp>,
+}
+
+/// Calls AdminRequest::ImportDhtOpArchive
+/// and imports the ops of an archive file written by `export-ops`,
+/// which are validated again before they are integrated.
+#[derive(Debug, Args, Clone)]
+pub struct ImportOps {
+    /// The archive file to import.
+    pub path: PathBuf,
+}
+
+/// Calls AdminRequest::ListDhtEpochs
+/// and lists the epochs of a DNA, i.e. how many ops were authored in each 30 day period.
+#[derive(Debug, Args, Clone)]
+pub struct ListDhtEpochs {
+    /// The DNA whose epochs to list.
+    #[arg(value_parser = parse_dna_hash)]
+    pub dna: DnaHash,
+}
+
+/// Calls AdminRequest::RecheckMembraneProofs
+/// and reports the agents of a DNA whose membrane proof no longer passes its
+/// genesis self check.
+#[derive(Debug, Args, Clone)]
+pub struct RecheckMembraneProofs {
+    /// The DNA whose agents to check.
+    #[arg(value_parser = parse_dna_hash)]
+    pub dna: DnaHash,
+}
+
+/// Calls AdminRequest::ArchiveDhtEpochs
+/// and writes the ops of the epochs of a DNA which ended before a time to an archive
+/// file, which can be imported with `import-ops`, before deleting them.
+#[derive(Debug, Args, Clone)]
+pub struct ArchiveEpochs {
+    /// The DNA whose epochs to archive.
+    #[arg(value_parser = parse_dna_hash)]
+    pub dna: DnaHash,
+
+    /// The file to write the archive to.
+    pub path: PathBuf,
+
+    /// Only archive the epochs which ended before this time, e.g. `2024-01-01T00:00:00Z`.
+    #[arg(long)]
+    pub before: Timestamp,
+}
+
+/// Calls AdminRequest::ExportAuthoredData
+/// and writes the data which the agent of a cell authored to a JSON file.
+#[derive(Debug, Args, Clone)]
+pub struct ExportAuthoredData {
+    /// The DNA hash half of the cell ID.
+    #[arg(value_parser = parse_dna_hash)]
+    pub dna: DnaHash,
+
+    /// The agent half of the cell ID.
+    #[arg(value_parser = parse_agent_key)]
+    pub agent_key: AgentPubKey,
+
+    /// The file to write the JSON to.
+    pub path: PathBuf,
+}
+
+/// Calls AdminRequest::ExportPublicationProof
+/// and writes a proof that an action of a cell was published to a file,
+/// which can be verified offline with the DNA hash.
+#[derive(Debug, Args, Clone)]
+pub struct ExportPublicationProof {
+    /// The DNA hash half of the cell ID.
+    #[arg(value_parser = parse_dna_hash)]
+    pub dna: DnaHash,
+
+    /// The agent half of the cell ID.
+    #[arg(value_parser = parse_agent_key)]
+    pub agent_key: AgentPubKey,
+
+    /// The action to prove the publication of.
+    #[arg(value_parser = parse_action_hash)]
+    pub action: ActionHash,
+
+    /// The file to write the proof to, as MessagePack.
+    pub path: PathBuf,
+}
+
+/// Calls AdminRequest::ExportAppBundle
+/// and writes the bundle which an app was installed from to a `.happ` file.
+#[derive(Debug, Args, Clone)]
+pub struct ExportAppBundle {
+    /// The app whose bundle to export.
+    pub app_id: String,
+
+    /// The file to write the bundle to.
+    pub path: PathBuf,
+}
+
 /// Arguments for dumping network metrics.
 #[derive(Debug, Args, Clone)]
 pub struct DumpNetworkMetrics {
@@ -235,6 +426,164 @@ pub struct DumpNetworkMetrics {
     pub dna: Option<DnaHash>,
 }
 
+/// Calls AdminRequest::GetRegionSet
+/// and prints the regions of a DNA which historical gossip compares with other nodes.
+#[derive(Debug, Args, Clone)]
+pub struct GetRegionSet {
+    /// The DNA whose regions to print.
+    #[arg(value_parser = parse_dna_hash)]
+    pub dna: DnaHash,
+}
+
+/// Calls AdminRequest::GetOpCensus
+/// and prints the regions of a DNA which neighboring peers hold different ops in.
+#[derive(Debug, Args, Clone)]
+pub struct GetOpCensus {
+    /// The DNA whose ops to compare.
+    #[arg(value_parser = parse_dna_hash)]
+    pub dna: DnaHash,
+
+    /// The most peers to ask.
+    #[arg(long)]
+    pub peers: Option<usize>,
+}
+
+/// Calls AdminRequest::GetValidationOutcomes
+/// and prints how many of the ops of each author of a DNA were found valid or invalid.
+#[derive(Debug, Args, Clone)]
+pub struct GetValidationOutcomes {
+    /// The DNA whose ops to count.
+    #[arg(value_parser = parse_dna_hash)]
+    pub dna: DnaHash,
+}
+
+/// Calls AdminRequest::GetBackfillProgress
+/// and prints how far a cell is with getting the ops of its storage arc.
+#[derive(Debug, Args, Clone)]
+pub struct GetBackfillProgress {
+    /// The DNA hash half of the cell ID.
+    #[arg(value_parser = parse_dna_hash)]
+    pub dna: DnaHash,
+
+    /// The agent half of the cell ID.
+    #[arg(value_parser = parse_agent_key)]
+    pub agent_key: AgentPubKey,
+}
+
+/// Calls AdminRequest::PauseGossip
+/// and stops initiating gossip rounds for a DNA.
+#[derive(Debug, Args, Clone)]
+pub struct PauseGossip {
+    /// The DNA to pause gossip for.
+    #[arg(value_parser = parse_dna_hash)]
+    pub dna: DnaHash,
+
+    /// Also refuse new rounds which remote nodes initiate.
+    #[arg(long)]
+    pub refuse_incoming: bool,
+}
+
+/// Calls AdminRequest::ResumeGossip
+/// and resumes gossip for a DNA which was paused.
+#[derive(Debug, Args, Clone)]
+pub struct ResumeGossip {
+    /// The DNA to resume gossip for.
+    #[arg(value_parser = parse_dna_hash)]
+    pub dna: DnaHash,
+}
+
+/// Calls AdminRequest::SetArqPin
+/// and pins the storage arc of a cell, or lets it be sized dynamically again
+/// if no arc is given.
+#[derive(Debug, Args, Clone)]
+pub struct SetArqPin {
+    /// The DNA hash half of the cell ID.
+    #[arg(value_parser = parse_dna_hash)]
+    pub dna: DnaHash,
+
+    /// The agent half of the cell ID.
+    #[arg(value_parser = parse_agent_key)]
+    pub agent_key: AgentPubKey,
+
+    /// Hold the whole DHT.
+    #[arg(long, conflicts_with_all = &["empty", "start"])]
+    pub full: bool,
+
+    /// Hold nothing.
+    #[arg(long, conflicts_with = "start")]
+    pub empty: bool,
+
+    /// Hold the locations from this one to `--end`.
+    #[arg(long, requires = "end")]
+    pub start: Option<u32>,
+
+    /// The last location to hold, wrapping around the DHT if it's before `--start`.
+    #[arg(long, requires = "start")]
+    pub end: Option<u32>,
+}
+
+/// Calls AdminRequest::RollbackSourceChain
+/// and removes the actions of a cell's chain which come after an earlier one,
+/// if none of them have been published. The app of the cell must be disabled.
+#[derive(Debug, Args, Clone)]
+pub struct RollbackSourceChain {
+    /// The DNA hash half of the cell ID.
+    #[arg(value_parser = parse_dna_hash)]
+    pub dna: DnaHash,
+
+    /// The agent half of the cell ID.
+    #[arg(value_parser = parse_agent_key)]
+    pub agent_key: AgentPubKey,
+
+    /// The action which becomes the head of the chain.
+    #[arg(value_parser = parse_action_hash)]
+    pub to: ActionHash,
+}
+
+/// Calls AdminRequest::StartOpTrace
+/// and records the events of an op as the conductor handles it.
+#[derive(Debug, Args, Clone)]
+pub struct StartOpTrace {
+    /// The hash of the op to trace.
+    #[arg(value_parser = parse_op_hash)]
+    pub op: DhtOpHash,
+}
+
+/// Calls AdminRequest::GetOpTrace
+/// and gets the events which were recorded for a traced op.
+#[derive(Debug, Args, Clone)]
+pub struct GetOpTrace {
+    /// The hash of the traced op.
+    #[arg(value_parser = parse_op_hash)]
+    pub op: DhtOpHash,
+}
+
+/// Calls AdminRequest::StopOpTrace
+/// and stops tracing an op.
+#[derive(Debug, Args, Clone)]
+pub struct StopOpTrace {
+    /// The hash of the traced op.
+    #[arg(value_parser = parse_op_hash)]
+    pub op: DhtOpHash,
+}
+
+/// Calls AdminRequest::DisconnectAppInterfaceClient
+/// and closes a client's connection to an app interface.
+#[derive(Debug, Args, Clone)]
+pub struct DisconnectAppClient {
+    /// The connection ID, as shown by `list-app-connections`.
+    pub connection_id: AppInterfaceConnectionId,
+}
+
+/// Calls AdminRequest::SetLogFilter
+/// and changes which logs the conductor writes.
+#[derive(Debug, Args, Clone)]
+pub struct SetLogFilter {
+    /// Filter directives in the same format as `RUST_LOG`,
+    /// e.g. `warn,kitsune_p2p::gossip=debug`.
+    pub filter: String,
+}
+
 /// Calls AdminRequest::RequestAgentInfo
 /// and pretty prints the agent info on
 /// this conductor.
@@ -249,6 +598,19 @@ pub struct ListAgents {
     pub dna: Option<DnaHash>,
 }
 
+/// Calls AdminRequest::ListAuditLog
+/// and prints the admin requests which changed the state of the conductor.
+#[derive(Debug, Args, Clone)]
+pub struct ListAuditLog {
+    /// Only list entries recorded at or after this time, e.g. `2024-01-01T00:00:00Z`.
+    #[arg(long)]
+    pub since: Option<Timestamp>,
+
+    /// The most entries to list, oldest first.
+    #[arg(long)]
+    pub limit: Option<u32>,
+}
+
 /// Calls AdminRequest::ListApps
 /// and pretty prints the list of apps
 /// installed in this conductor.
@@ -257,6 +619,27 @@ pub struct ListApps {
     /// Optionally request agent info for a particular cell ID.
     #[arg(short, long, value_parser = parse_status_filter)]
     pub status: Option<AppStatusFilter>,
+
+    /// Only list the apps which have a cell of this DNA.
+    #[arg(short, long, value_parser = parse_dna_hash)]
+    pub dna: Option<DnaHash>,
+
+    /// The number of apps to skip.
+    #[arg(long, requires = "limit")]
+    pub offset: Option<usize>,
+
+    /// The most apps to list.
+    #[arg(long)]
+    pub limit: Option<usize>,
+}
+
+impl ListApps {
+    fn page(&self) -> Option<Page> {
+        self.limit.map(|limit| Page {
+            offset: self.offset.unwrap_or(0),
+            limit,
+        })
+    }
 }
 
 #[doc(hidden)]
@@ -357,6 +740,15 @@ async fn call_inner(cmd: &mut CmdRunner, call: AdminRequestCli) -> anyhow::Resul
             let ports = list_app_ws(cmd).await?;
             msg!("Attached app interfaces {:?}", ports);
         }
+        AdminRequestCli::ListAppConnections => {
+            let connections = list_app_connections(cmd).await?;
+            msg!("App interface connections: {:?}", connections);
+        }
+        AdminRequestCli::DisconnectAppClient(args) => {
+            let connection_id = args.connection_id;
+            disconnect_app_client(cmd, args).await?;
+            msg!("Disconnected app client {}", connection_id);
+        }
         AdminRequestCli::RegisterDna(args) => {
             let dnas = register_dna(cmd, args).await?;
             msg!("Registered DNA: {:?}", dnas);
@@ -386,6 +778,14 @@ async fn call_inner(cmd: &mut CmdRunner, call: AdminRequestCli) -> anyhow::Resul
             let apps = list_apps(cmd, args).await?;
             msg!("List apps: {:?}", apps);
         }
+        AdminRequestCli::ListAppSummaries(args) => {
+            let summaries = list_app_summaries(cmd, args).await?;
+            msg!("App summaries: {:?}", summaries);
+        }
+        AdminRequestCli::ListAgentKeys => {
+            let keys = list_agent_keys(cmd).await?;
+            msg!("Agent keys: {:?}", keys);
+        }
         AdminRequestCli::EnableApp(args) => {
             let app_id = args.app_id.clone();
             enable_app(cmd, args).await?;
@@ -414,6 +814,220 @@ async fn call_inner(cmd: &mut CmdRunner, call: AdminRequestCli) -> anyhow::Resul
             // Print without other text so it can be piped
             println!("{}", stats);
         }
+        AdminRequestCli::HealthCheck => {
+            let report = health_check(cmd).await?;
+            msg!("Health report: {:?}", report);
+        }
+        AdminRequestCli::GetStartupReport => {
+            let report = get_startup_report(cmd).await?;
+            msg!("Startup report: {:?}", report);
+        }
+        AdminRequestCli::ListSlowOperations => {
+            let operations = list_slow_operations(cmd).await?;
+            msg!("Slow operations: {:?}", operations);
+        }
+        AdminRequestCli::GetConductorDiagnostics => {
+            let diagnostics = get_conductor_diagnostics(cmd).await?;
+            msg!("Conductor diagnostics: {:?}", diagnostics);
+        }
+        AdminRequestCli::GetRegionSet(args) => {
+            let region_set = get_region_set(cmd, args).await?;
+            msg!(
+                "Regions of {} for agents {:?}:",
+                region_set.dna_hash,
+                region_set.agents
+            );
+            for region in region_set.regions {
+                msg!(
+                    "locations {:?}, times {} to {}: {} ops, {} bytes, hash {}",
+                    region.locations,
+                    region.times.0,
+                    region.times.1,
+                    region.op_count,
+                    region.size,
+                    region.hash
+                );
+            }
+        }
+        AdminRequestCli::GetOpCensus(args) => {
+            let census = get_op_census(cmd, args).await?;
+            msg!(
+                "Op census of {} for agents {:?}: {}",
+                census.dna_hash,
+                census.agents,
+                if census.is_consistent() {
+                    "consistent"
+                } else {
+                    "inconsistent"
+                }
+            );
+            for peer in census.peers {
+                match peer.error {
+                    Some(error) => msg!("{}: failed: {}", peer.agent, error),
+                    None => msg!(
+                        "{}: {} of {} regions differ",
+                        peer.agent,
+                        peer.discrepancies.len(),
+                        peer.regions_compared
+                    ),
+                }
+                for region in peer.discrepancies {
+                    msg!(
+                        "  locations {:?}, times {} to {}: {} ops here, {} ops there",
+                        region.locations,
+                        region.times.0,
+                        region.times.1,
+                        region.local_op_count,
+                        region.peer_op_count
+                    );
+                }
+            }
+        }
+        AdminRequestCli::GetValidationOutcomes(args) => {
+            for outcomes in get_validation_outcomes(cmd, args).await? {
+                msg!(
+                    "{}: {} valid, {} rejected, {} abandoned, {} awaiting validation",
+                    outcomes.author,
+                    outcomes.valid,
+                    outcomes.rejected,
+                    outcomes.abandoned,
+                    outcomes.awaiting
+                );
+            }
+        }
+        AdminRequestCli::GetBackfillProgress(args) => {
+            let progress = get_backfill_progress(cmd, args).await?;
+            let eta = progress
+                .eta_secs
+                .map(|secs| format!("{secs}s"))
+                .unwrap_or_else(|| "unknown".to_string());
+            msg!(
+                "{} of an estimated {} ops integrated ({} pending, {} to fetch), \
+                {} ops per minute, ETA {}, {} gossip rounds, complete: {}",
+                progress.integrated_ops,
+                progress.estimated_total_ops,
+                progress.pending_ops,
+                progress.ops_to_fetch,
+                progress.ops_per_minute,
+                eta,
+                progress.gossip_rounds,
+                progress.complete
+            );
+        }
+        AdminRequestCli::PauseGossip(args) => {
+            let dna = args.dna.clone();
+            pause_gossip(cmd, args).await?;
+            msg!("Paused gossip for {}", dna);
+        }
+        AdminRequestCli::ResumeGossip(args) => {
+            let dna = args.dna.clone();
+            resume_gossip(cmd, args).await?;
+            msg!("Resumed gossip for {}", dna);
+        }
+        AdminRequestCli::SetArqPin(args) => {
+            let cell_id = CellId::new(args.dna.clone(), args.agent_key.clone());
+            match set_arq_pin(cmd, args).await? {
+                Some(pin) => msg!("Pinned the storage arc of {} to {:?}", cell_id, pin),
+                None => msg!("Unpinned the storage arc of {}", cell_id),
+            }
+        }
+        AdminRequestCli::RollbackSourceChain(args) => {
+            let to = args.to.clone();
+            let removed = rollback_source_chain(cmd, args).await?;
+            msg!("Rolled back {} actions to {}", removed.len(), to);
+            for action_hash in removed {
+                msg!("Removed {}", action_hash);
+            }
+        }
+        AdminRequestCli::StartOpTrace(args) => {
+            let op = args.op.clone();
+            start_op_trace(cmd, args).await?;
+            msg!("Tracing op {}", op);
+        }
+        AdminRequestCli::GetOpTrace(args) => {
+            let op = args.op.clone();
+            match get_op_trace(cmd, args).await? {
+                Some(events) => msg!("Events of op {}: {:?}", op, events),
+                None => msg!("Op {} isn't traced", op),
+            }
+        }
+        AdminRequestCli::StopOpTrace(args) => {
+            let op = args.op.clone();
+            stop_op_trace(cmd, args).await?;
+            msg!("Stopped tracing op {}", op);
+        }
+        AdminRequestCli::ListOpsMissingReceipts(args) => {
+            let ops = list_ops_missing_receipts(cmd, args).await?;
+            msg!("Ops missing receipts: {:?}", ops);
+        }
+        AdminRequestCli::ExportOps(args) => {
+            let path = args.path.clone();
+            let archive = export_ops(cmd, args).await?;
+            std::fs::write(&path, archive.to_bytes()?)?;
+            msg!("Exported {} ops to {}", archive.len(), path.display());
+        }
+        AdminRequestCli::ImportOps(args) => {
+            let archive = DhtOpArchive::from_bytes(&std::fs::read(&args.path)?)?;
+            let op_count = import_ops(cmd, archive).await?;
+            msg!("Imported {} ops, which are being validated", op_count);
+        }
+        AdminRequestCli::ListDhtEpochs(args) => {
+            let epochs = list_dht_epochs(cmd, args).await?;
+            msg!("DHT epochs: {:?}", epochs);
+        }
+        AdminRequestCli::ArchiveEpochs(args) => {
+            let path = args.path.clone();
+            let archive = archive_epochs(cmd, args).await?;
+            std::fs::write(&path, archive.to_bytes()?)?;
+            msg!("Archived {} ops to {}", archive.len(), path.display());
+        }
+        AdminRequestCli::RecheckMembraneProofs(args) => {
+            let report = recheck_membrane_proofs(cmd, args).await?;
+            msg!(
+                "Checked {} agents, {} failed: {:?}",
+                report.checked_count,
+                report.failed.len(),
+                report.failed
+            );
+        }
+        AdminRequestCli::ExportAuthoredData(args) => {
+            let path = args.path.clone();
+            let json = export_authored_data(cmd, args).await?;
+            std::fs::write(&path, json)?;
+            msg!("Exported the authored data to {}", path.display());
+        }
+        AdminRequestCli::ExportPublicationProof(args) => {
+            let path = args.path.clone();
+            let proof = export_publication_proof(cmd, args).await?;
+            msg!(
+                "Exported a proof with {} ops and {} receipts to {}",
+                proof.ops.len(),
+                proof.ops.iter().map(|op| op.receipts.len()).sum::<usize>(),
+                path.display()
+            );
+            std::fs::write(&path, SerializedBytes::try_from(proof)?.bytes())?;
+        }
+        AdminRequestCli::ExportAppBundle(args) => {
+            let path = args.path.clone();
+            let bundle = export_app_bundle(cmd, args.app_id).await?;
+            std::fs::write(&path, bundle)?;
+            msg!("Exported the app bundle to {}", path.display());
+        }
+        AdminRequestCli::SetLogFilter(args) => {
+            let filter = args.filter.clone();
+            set_log_filter(cmd, args).await?;
+            msg!("Set log filter: {}", filter);
+        }
+        AdminRequestCli::ReloadConfig => {
+            let report = reload_config(cmd).await?;
+            msg!("Applied settings: {:?}", report.applied);
+            if !report.requires_restart.is_empty() {
+                msg!(
+                    "Skipped settings which need a restart: {:?}",
+                    report.requires_restart
+                );
+            }
+        }
         AdminRequestCli::AddAgents => todo!("Adding agent info via CLI is coming soon"),
         AdminRequestCli::ListAgents(args) => {
             use std::fmt::Write;
@@ -470,6 +1084,18 @@ async fn call_inner(cmd: &mut CmdRunner, call: AdminRequestCli) -> anyhow::Resul
                 msg!("{}\n", out);
             }
         }
+        AdminRequestCli::ListAuditLog(args) => {
+            for entry in list_audit_log(cmd, args).await? {
+                msg!(
+                    "{} {} {} {} {}",
+                    entry.timestamp,
+                    entry.connection,
+                    entry.action,
+                    entry.target.as_deref().unwrap_or("-"),
+                    entry.error.as_deref().unwrap_or("ok")
+                );
+            }
+        }
     }
     Ok(())
 }
@@ -540,6 +1166,7 @@ pub async fn install_app_bundle(cmd: &mut CmdRunner, args: InstallApp) -> anyhow
         agent_key,
         path,
         network_seed,
+        client_only,
     } = args;
 
     let payload = InstallAppPayload {
@@ -551,6 +1178,8 @@ pub async fn install_app_bundle(cmd: &mut CmdRunner, args: InstallApp) -> anyhow
         network_seed,
         ignore_genesis_failure: false,
         allow_throwaway_random_agent_key: true,
+        network_profile: None,
+        client_only,
     };
 
     let r = AdminRequest::InstallApp(Box::new(payload));
@@ -597,6 +1226,36 @@ pub async fn list_app_ws(cmd: &mut CmdRunner) -> anyhow::Result<Vec<AppInterface
     Ok(expect_match!(resp => AdminResponse::AppInterfacesListed, "Failed to list app interfaces"))
 }
 
+/// Calls [`AdminRequest::ListAppInterfaceConnections`].
+pub async fn list_app_connections(
+    cmd: &mut CmdRunner,
+) -> anyhow::Result<Vec<AppInterfaceConnectionInfo>> {
+    let resp = cmd
+        .command(AdminRequest::ListAppInterfaceConnections)
+        .await?;
+    Ok(
+        expect_match!(resp => AdminResponse::AppInterfaceConnectionsListed, "Failed to list app interface connections"),
+    )
+}
+
+/// Calls [`AdminRequest::DisconnectAppInterfaceClient`].
+pub async fn disconnect_app_client(
+    cmd: &mut CmdRunner,
+    args: DisconnectAppClient,
+) -> anyhow::Result<()> {
+    let resp = cmd
+        .command(AdminRequest::DisconnectAppInterfaceClient {
+            connection_id: args.connection_id,
+        })
+        .await?;
+    ensure!(
+        matches!(resp, AdminResponse::AppInterfaceClientDisconnected),
+        "Failed to disconnect app client, got: {:?}",
+        resp
+    );
+    Ok(())
+}
+
 /// Calls [`AdminRequest::ListCellIds`].
 pub async fn list_dnas(cmd: &mut CmdRunner) -> anyhow::Result<Vec<DnaHash>> {
     let resp = cmd.command(AdminRequest::ListDnas).await?;
@@ -613,20 +1272,45 @@ pub async fn generate_agent_pub_key(cmd: &mut CmdRunner) -> anyhow::Result<Agent
 
 /// Calls [`AdminRequest::ListCellIds`].
 pub async fn list_cell_ids(cmd: &mut CmdRunner) -> anyhow::Result<Vec<CellId>> {
-    let resp = cmd.command(AdminRequest::ListCellIds).await?;
+    let resp = cmd.command(AdminRequest::ListCellIds(None)).await?;
     Ok(expect_match!(resp => AdminResponse::CellIdsListed, "Failed to list cell IDs"))
 }
 
 /// Calls [`AdminRequest::ListApps`].
 pub async fn list_apps(cmd: &mut CmdRunner, args: ListApps) -> anyhow::Result<Vec<AppInfo>> {
+    let page = args.page();
     let resp = cmd
         .command(AdminRequest::ListApps {
             status_filter: args.status,
+            dna_hash: args.dna,
+            page,
         })
         .await?;
     Ok(expect_match!(resp => AdminResponse::AppsListed, "Failed to list apps"))
 }
 
+/// Calls [`AdminRequest::ListAppSummaries`].
+pub async fn list_app_summaries(
+    cmd: &mut CmdRunner,
+    args: ListApps,
+) -> anyhow::Result<Vec<AppSummary>> {
+    let page = args.page();
+    let resp = cmd
+        .command(AdminRequest::ListAppSummaries {
+            status_filter: args.status,
+            dna_hash: args.dna,
+            page,
+        })
+        .await?;
+    Ok(expect_match!(resp => AdminResponse::AppSummariesListed, "Failed to list app summaries"))
+}
+
+/// Calls [`AdminRequest::ListAgentKeys`].
+pub async fn list_agent_keys(cmd: &mut CmdRunner) -> anyhow::Result<Vec<AgentKeyInfo>> {
+    let resp = cmd.command(AdminRequest::ListAgentKeys).await?;
+    Ok(expect_match!(resp => AdminResponse::AgentKeysListed, "Failed to list agent keys"))
+}
+
 /// Calls [`AdminRequest::EnableApp`] and activates the installed app.
 pub async fn enable_app(cmd: &mut CmdRunner, args: EnableApp) -> anyhow::Result<()> {
     let resp = cmd
@@ -708,6 +1392,341 @@ async fn dump_network_stats(cmd: &mut CmdRunner) -> anyhow::Result<String> {
     Ok(expect_match!(resp => AdminResponse::NetworkStatsDumped, "Failed to dump network stats"))
 }
 
+/// Calls [`AdminRequest::HealthCheck`].
+pub async fn health_check(cmd: &mut CmdRunner) -> anyhow::Result<HealthReport> {
+    let resp = cmd.command(AdminRequest::HealthCheck).await?;
+    Ok(expect_match!(resp => AdminResponse::HealthChecked, "Failed to check conductor health"))
+}
+
+/// Calls [`AdminRequest::GetStartupReport`].
+pub async fn get_startup_report(cmd: &mut CmdRunner) -> anyhow::Result<Option<StartupReport>> {
+    let resp = cmd.command(AdminRequest::GetStartupReport).await?;
+    Ok(expect_match!(resp => AdminResponse::StartupReport, "Failed to get the startup report"))
+}
+
+/// Calls [`AdminRequest::ListSlowOperations`].
+pub async fn list_slow_operations(cmd: &mut CmdRunner) -> anyhow::Result<Vec<SlowOperation>> {
+    let resp = cmd.command(AdminRequest::ListSlowOperations).await?;
+    Ok(expect_match!(resp => AdminResponse::SlowOperationsListed, "Failed to list slow operations"))
+}
+
+/// Calls [`AdminRequest::GetConductorDiagnostics`].
+pub async fn get_conductor_diagnostics(
+    cmd: &mut CmdRunner,
+) -> anyhow::Result<ConductorDiagnostics> {
+    let resp = cmd.command(AdminRequest::GetConductorDiagnostics).await?;
+    Ok(
+        expect_match!(resp => AdminResponse::ConductorDiagnostics, "Failed to get conductor diagnostics"),
+    )
+}
+
+/// Calls [`AdminRequest::GetRegionSet`].
+pub async fn get_region_set(
+    cmd: &mut CmdRunner,
+    args: GetRegionSet,
+) -> anyhow::Result<RegionSetDiagnostics> {
+    let resp = cmd
+        .command(AdminRequest::GetRegionSet { dna_hash: args.dna })
+        .await?;
+    Ok(expect_match!(resp => AdminResponse::RegionSet, "Failed to get region set"))
+}
+
+/// Calls [`AdminRequest::GetOpCensus`].
+pub async fn get_op_census(
+    cmd: &mut CmdRunner,
+    args: GetOpCensus,
+) -> anyhow::Result<OpCensusReport> {
+    let resp = cmd
+        .command(AdminRequest::GetOpCensus {
+            dna_hash: args.dna,
+            peer_count: args.peers,
+        })
+        .await?;
+    Ok(expect_match!(resp => AdminResponse::OpCensus, "Failed to get op census"))
+}
+
+/// Calls [`AdminRequest::GetValidationOutcomes`].
+pub async fn get_validation_outcomes(
+    cmd: &mut CmdRunner,
+    args: GetValidationOutcomes,
+) -> anyhow::Result<Vec<AuthorValidationOutcomes>> {
+    let resp = cmd
+        .command(AdminRequest::GetValidationOutcomes { dna_hash: args.dna })
+        .await?;
+    Ok(
+        expect_match!(resp => AdminResponse::ValidationOutcomes, "Failed to get validation outcomes"),
+    )
+}
+
+/// Calls [`AdminRequest::GetBackfillProgress`].
+pub async fn get_backfill_progress(
+    cmd: &mut CmdRunner,
+    args: GetBackfillProgress,
+) -> anyhow::Result<BackfillProgress> {
+    let resp = cmd
+        .command(AdminRequest::GetBackfillProgress {
+            cell_id: CellId::new(args.dna, args.agent_key),
+        })
+        .await?;
+    Ok(expect_match!(resp => AdminResponse::BackfillProgress, "Failed to get backfill progress"))
+}
+
+/// Calls [`AdminRequest::PauseGossip`].
+pub async fn pause_gossip(cmd: &mut CmdRunner, args: PauseGossip) -> anyhow::Result<()> {
+    let resp = cmd
+        .command(AdminRequest::PauseGossip {
+            dna_hash: args.dna,
+            refuse_incoming: args.refuse_incoming,
+        })
+        .await?;
+    ensure!(
+        matches!(resp, AdminResponse::GossipPaused),
+        "Failed to pause gossip, got: {:?}",
+        resp
+    );
+    Ok(())
+}
+
+/// Calls [`AdminRequest::ResumeGossip`].
+pub async fn resume_gossip(cmd: &mut CmdRunner, args: ResumeGossip) -> anyhow::Result<()> {
+    let resp = cmd
+        .command(AdminRequest::ResumeGossip { dna_hash: args.dna })
+        .await?;
+    ensure!(
+        matches!(resp, AdminResponse::GossipResumed),
+        "Failed to resume gossip, got: {:?}",
+        resp
+    );
+    Ok(())
+}
+
+/// Calls [`AdminRequest::SetArqPin`] and returns the pin which was set.
+pub async fn set_arq_pin(cmd: &mut CmdRunner, args: SetArqPin) -> anyhow::Result<Option<ArqPin>> {
+    let pin = match (args.full, args.empty, args.start, args.end) {
+        (true, _, _, _) => Some(ArqPin::Full),
+        (_, true, _, _) => Some(ArqPin::Empty),
+        (_, _, Some(start), Some(end)) => Some(ArqPin::Range { start, end }),
+        _ => None,
+    };
+    let resp = cmd
+        .command(AdminRequest::SetArqPin {
+            cell_id: Box::new(CellId::new(args.dna, args.agent_key)),
+            pin: pin.clone(),
+        })
+        .await?;
+    ensure!(
+        matches!(resp, AdminResponse::ArqPinSet),
+        "Failed to set the arc pin, got: {:?}",
+        resp
+    );
+    Ok(pin)
+}
+
+/// Calls [`AdminRequest::RollbackSourceChain`] and returns the actions which were removed.
+pub async fn rollback_source_chain(
+    cmd: &mut CmdRunner,
+    args: RollbackSourceChain,
+) -> anyhow::Result<Vec<ActionHash>> {
+    let resp = cmd
+        .command(AdminRequest::RollbackSourceChain {
+            cell_id: Box::new(CellId::new(args.dna, args.agent_key)),
+            to: args.to,
+        })
+        .await?;
+    Ok(
+        expect_match!(resp => AdminResponse::SourceChainRolledBack, "Failed to roll back the source chain"),
+    )
+}
+
+/// Calls [`AdminRequest::StartOpTrace`].
+pub async fn start_op_trace(cmd: &mut CmdRunner, args: StartOpTrace) -> anyhow::Result<()> {
+    let resp = cmd
+        .command(AdminRequest::StartOpTrace { op_hash: args.op })
+        .await?;
+    ensure!(
+        matches!(resp, AdminResponse::OpTraceStarted),
+        "Failed to start op trace, got: {:?}",
+        resp
+    );
+    Ok(())
+}
+
+/// Calls [`AdminRequest::GetOpTrace`].
+pub async fn get_op_trace(
+    cmd: &mut CmdRunner,
+    args: GetOpTrace,
+) -> anyhow::Result<Option<Vec<OpTraceEvent>>> {
+    let resp = cmd
+        .command(AdminRequest::GetOpTrace { op_hash: args.op })
+        .await?;
+    Ok(expect_match!(resp => AdminResponse::OpTrace, "Failed to get op trace"))
+}
+
+/// Calls [`AdminRequest::StopOpTrace`].
+pub async fn stop_op_trace(cmd: &mut CmdRunner, args: StopOpTrace) -> anyhow::Result<()> {
+    let resp = cmd
+        .command(AdminRequest::StopOpTrace { op_hash: args.op })
+        .await?;
+    ensure!(
+        matches!(resp, AdminResponse::OpTraceStopped),
+        "Failed to stop op trace, got: {:?}",
+        resp
+    );
+    Ok(())
+}
+
+/// Calls [`AdminRequest::ListOpsMissingReceipts`].
+pub async fn list_ops_missing_receipts(
+    cmd: &mut CmdRunner,
+    args: ListOpsMissingReceipts,
+) -> anyhow::Result<Vec<OpMissingReceipts>> {
+    let resp = cmd
+        .command(AdminRequest::ListOpsMissingReceipts {
+            cell_id: Box::new(CellId::new(args.dna, args.agent_key)),
+        })
+        .await?;
+    Ok(
+        expect_match!(resp => AdminResponse::OpsMissingReceiptsListed, "Failed to list ops missing receipts"),
+    )
+}
+
+/// Calls [`AdminRequest::ExportDhtOpArchive`].
+pub async fn export_ops(cmd: &mut CmdRunner, args: ExportOps) -> anyhow::Result<DhtOpArchive> {
+    let resp = cmd
+        .command(AdminRequest::ExportDhtOpArchive {
+            dna_hash: args.dna,
+            integrated_before: args.integrated_before.unwrap_or_else(Timestamp::now),
+        })
+        .await?;
+    Ok(*expect_match!(resp => AdminResponse::DhtOpArchiveExported, "Failed to export ops"))
+}
+
+/// Calls [`AdminRequest::ImportDhtOpArchive`] and returns how many ops the archive had.
+pub async fn import_ops(cmd: &mut CmdRunner, archive: DhtOpArchive) -> anyhow::Result<usize> {
+    let resp = cmd
+        .command(AdminRequest::ImportDhtOpArchive {
+            archive: Box::new(archive),
+        })
+        .await?;
+    match resp {
+        AdminResponse::DhtOpArchiveImported { op_count } => Ok(op_count),
+        _ => Err(anyhow!("Failed to import ops, got: {:?}", resp)),
+    }
+}
+
+/// Calls [`AdminRequest::ListDhtEpochs`].
+pub async fn list_dht_epochs(
+    cmd: &mut CmdRunner,
+    args: ListDhtEpochs,
+) -> anyhow::Result<Vec<DhtEpochSummary>> {
+    let resp = cmd
+        .command(AdminRequest::ListDhtEpochs { dna_hash: args.dna })
+        .await?;
+    Ok(expect_match!(resp => AdminResponse::DhtEpochsListed, "Failed to list DHT epochs"))
+}
+
+/// Calls [`AdminRequest::ArchiveDhtEpochs`].
+pub async fn archive_epochs(
+    cmd: &mut CmdRunner,
+    args: ArchiveEpochs,
+) -> anyhow::Result<DhtOpArchive> {
+    let resp = cmd
+        .command(AdminRequest::ArchiveDhtEpochs {
+            dna_hash: args.dna,
+            before: args.before,
+        })
+        .await?;
+    Ok(*expect_match!(resp => AdminResponse::DhtEpochsArchived, "Failed to archive DHT epochs"))
+}
+
+/// Calls [`AdminRequest::RecheckMembraneProofs`].
+pub async fn recheck_membrane_proofs(
+    cmd: &mut CmdRunner,
+    args: RecheckMembraneProofs,
+) -> anyhow::Result<MembraneProofRecheckReport> {
+    let resp = cmd
+        .command(AdminRequest::RecheckMembraneProofs { dna_hash: args.dna })
+        .await?;
+    Ok(
+        expect_match!(resp => AdminResponse::MembraneProofsRechecked, "Failed to recheck membrane proofs"),
+    )
+}
+
+/// Calls [`AdminRequest::ExportAuthoredData`] and returns the export as JSON.
+pub async fn export_authored_data(
+    cmd: &mut CmdRunner,
+    args: ExportAuthoredData,
+) -> anyhow::Result<String> {
+    let resp = cmd
+        .command(AdminRequest::ExportAuthoredData {
+            cell_id: Box::new(CellId::new(args.dna, args.agent_key)),
+        })
+        .await?;
+    Ok(expect_match!(resp => AdminResponse::AuthoredDataExported, "Failed to export authored data"))
+}
+
+/// Calls [`AdminRequest::ExportPublicationProof`] and returns the proof.
+pub async fn export_publication_proof(
+    cmd: &mut CmdRunner,
+    args: ExportPublicationProof,
+) -> anyhow::Result<PublicationProof> {
+    let resp = cmd
+        .command(AdminRequest::ExportPublicationProof {
+            cell_id: Box::new(CellId::new(args.dna, args.agent_key)),
+            action_hash: args.action,
+        })
+        .await?;
+    Ok(
+        *expect_match!(resp => AdminResponse::PublicationProofExported, "Failed to export publication proof"),
+    )
+}
+
+/// Calls [`AdminRequest::ExportAppBundle`] and returns the encoded bundle.
+pub async fn export_app_bundle(
+    cmd: &mut CmdRunner,
+    installed_app_id: InstalledAppId,
+) -> anyhow::Result<Vec<u8>> {
+    let resp = cmd
+        .command(AdminRequest::ExportAppBundle { installed_app_id })
+        .await?;
+    Ok(expect_match!(resp => AdminResponse::AppBundleExported, "Failed to export app bundle"))
+}
+
+/// Calls [`AdminRequest::SetLogFilter`] and changes the conductor's log filter.
+pub async fn set_log_filter(cmd: &mut CmdRunner, args: SetLogFilter) -> anyhow::Result<()> {
+    let resp = cmd
+        .command(AdminRequest::SetLogFilter {
+            filter: args.filter,
+        })
+        .await?;
+    ensure!(
+        matches!(resp, AdminResponse::LogFilterSet),
+        "Failed to set log filter, got: {:?}",
+        resp
+    );
+    Ok(())
+}
+
+/// Calls [`AdminRequest::ReloadConfig`] and reports which settings changed.
+pub async fn reload_config(cmd: &mut CmdRunner) -> anyhow::Result<ConfigReloadReport> {
+    let resp = cmd.command(AdminRequest::ReloadConfig).await?;
+    Ok(expect_match!(resp => AdminResponse::ConfigReloaded, "Failed to reload config"))
+}
+
+/// Calls [`AdminRequest::ListAuditLog`] and returns the audit log entries.
+pub async fn list_audit_log(
+    cmd: &mut CmdRunner,
+    args: ListAuditLog,
+) -> anyhow::Result<Vec<AuditLogEntry>> {
+    let resp = cmd
+        .command(AdminRequest::ListAuditLog {
+            since: args.since,
+            limit: args.limit,
+        })
+        .await?;
+    Ok(expect_match!(resp => AdminResponse::AuditLogListed, "Failed to list the audit log"))
+}
+
 /// Calls [`AdminRequest::AddAgentInfo`] with and adds the list of agent info.
 pub async fn add_agent_info(cmd: &mut CmdRunner, args: Vec<AgentInfoSigned>) -> anyhow::Result<()> {
     let resp = cmd
@@ -742,6 +1761,14 @@ fn parse_dna_hash(arg: &str) -> anyhow::Result<DnaHash> {
     DnaHash::try_from(arg).map_err(|e| anyhow::anyhow!("{:?}", e))
 }
 
+fn parse_action_hash(arg: &str) -> anyhow::Result<ActionHash> {
+    ActionHash::try_from(arg).map_err(|e| anyhow::anyhow!("{:?}", e))
+}
+
+fn parse_op_hash(arg: &str) -> anyhow::Result<DhtOpHash> {
+    DhtOpHash::try_from(arg).map_err(|e| anyhow::anyhow!("{:?}", e))
+}
+
 fn parse_status_filter(arg: &str) -> anyhow::Result<AppStatusFilter> {
     match arg {
         "active" => Ok(AppStatusFilter::Enabled),
@@ -2,12 +2,14 @@
 
 use crate::cmds::*;
 use clap::{ArgAction, Parser};
+use holochain_cli_run_local_services::HcRunLocalServices;
 use holochain_conductor_api::conductor::paths::ConfigRootPath;
 use holochain_trace::Output;
 use holochain_types::prelude::InstalledAppId;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
 use std::path::PathBuf;
+use url2::Url2;
 
 const DEFAULT_APP_ID: &str = "test-app";
 
@@ -103,6 +105,13 @@ pub enum HcSandboxSubcommand {
 
     /// Create a fresh sandbox with no apps installed.
     Create(Create),
+
+    /// Generate and run a local network of conductors with the same app installed.
+    ///
+    /// A bootstrap server and a WebRTC signal server are started alongside the
+    /// conductors, which are all configured to use them to find each other.
+    /// The admin and app ports of each conductor are printed once it is running.
+    LocalNetwork(LocalNetwork),
 }
 
 /// Options for running a sandbox
@@ -121,6 +130,41 @@ pub struct Run {
     existing: Existing,
 }
 
+/// Options for running a local network of conductors
+#[derive(Debug, Parser)]
+pub struct LocalNetwork {
+    /// Number of conductors to run.
+    #[arg(short, long, default_value = "2")]
+    num_conductors: usize,
+
+    /// ID for the app installed on every conductor.
+    #[arg(short, long, default_value = DEFAULT_APP_ID)]
+    app_id: InstalledAppId,
+
+    /// App interface ports to bind, one per conductor in order.
+    /// For example `hc sandbox local-network -p=9000,9001`.
+    /// By default every conductor gets an app interface on a free port.
+    #[arg(short, long, value_delimiter = ',')]
+    ports: Vec<u16>,
+
+    /// Set a root directory for conductor sandboxes to be placed into.
+    /// Defaults to the system's temp directory.
+    /// This directory must already exist.
+    #[arg(long)]
+    root: Option<PathBuf>,
+
+    /// Launch Holochain with an embedded lair server instead of a standalone process.
+    #[arg(long)]
+    in_process_lair: bool,
+
+    /// A hApp bundle to install.
+    happ: Option<PathBuf>,
+
+    /// Network seed to use when installing the provided hApp.
+    #[arg(long, short = 's')]
+    network_seed: Option<String>,
+}
+
 impl HcSandbox {
     /// Run this command
     pub async fn run(self) -> anyhow::Result<()> {
@@ -179,6 +223,17 @@ impl HcSandbox {
                 crate::save::release_ports(std::env::current_dir()?).await?;
                 return result;
             }
+            HcSandboxSubcommand::LocalNetwork(local_network) => {
+                let result = run_local_network(
+                    &self.holochain_path,
+                    local_network,
+                    self.force_admin_ports,
+                    self.structured,
+                )
+                .await;
+                crate::save::release_ports(std::env::current_dir()?).await?;
+                return result;
+            }
             HcSandboxSubcommand::Call(call) => {
                 crate::calls::call(
                     &self.holochain_path,
@@ -302,6 +357,88 @@ pub async fn run_n(
     Ok(())
 }
 
+/// Perform the `local-network` subcommand
+pub async fn run_local_network(
+    holochain_path: &Path,
+    local_network: LocalNetwork,
+    force_admin_ports: Vec<u16>,
+    structured: Output,
+) -> anyhow::Result<()> {
+    let LocalNetwork {
+        num_conductors,
+        app_id,
+        ports,
+        root,
+        in_process_lair,
+        happ,
+        network_seed,
+    } = local_network;
+
+    let services = HcRunLocalServices::new(
+        None,
+        "127.0.0.1".to_string(),
+        0,
+        false,
+        None,
+        "127.0.0.1".to_string(),
+        0,
+        false,
+    )
+    .spawn()
+    .await?;
+    let bootstrap_url = services
+        .bootstrap_urls
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("The local bootstrap server has no address"))?;
+    let signal_url = services
+        .signal_urls
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("The local signal server has no address"))?
+        .clone();
+    msg!(
+        "Running local services, bootstrap: {}, signal: {}",
+        bootstrap_url,
+        signal_url
+    );
+
+    let create = Create {
+        num_sandboxes: num_conductors,
+        network: Some(NetworkCmd::Network(Network {
+            transport: NetworkType::WebRTC {
+                signal_url,
+                webrtc_config: None,
+            },
+            bootstrap: Some(Url2::try_parse(bootstrap_url)?),
+        })),
+        root,
+        in_process_lair,
+        ..Create::default()
+    };
+    let paths = generate(
+        holochain_path,
+        happ,
+        create,
+        app_id,
+        network_seed,
+        structured.clone(),
+    )
+    .await?;
+    for (port, path) in force_admin_ports.iter().zip(paths.iter()) {
+        crate::force_admin_port(path.clone(), *port)?;
+    }
+
+    let app_ports = if ports.is_empty() {
+        vec![0; paths.len()]
+    } else {
+        ports
+    };
+    tokio::select! {
+        result = tokio::signal::ctrl_c() => result.map_err(anyhow::Error::from),
+        result = run_n(holochain_path, paths, app_ports, force_admin_ports, structured) => result,
+        _ = services.run() => Err(anyhow::anyhow!("The local network services stopped")),
+    }
+}
+
 /// Perform the `generate` subcommand
 pub async fn generate(
     holochain_path: &Path,
@@ -0,0 +1,556 @@
+//! Back up and restore the seeds in the keystore of a conductor that is not running.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::{anyhow, bail};
+use clap::Parser;
+use holochain_conductor_api::conductor::paths::KeystorePath;
+use holochain_conductor_api::config::conductor::ConductorConfig;
+use holochain_conductor_api::config::conductor::KeystoreConfig;
+use holochain_keystore::lair_keystore::spawn_lair_keystore_in_proc;
+use holochain_state::prelude::*;
+use kitsune_p2p_types::dependencies::lair_keystore_api::prelude::{
+    BinData, BinDataSized, LairEntryInfo, SeedInfo, X25519PubKey,
+};
+use kitsune_p2p_types::dependencies::lair_keystore_api::LairClient;
+use serde::{Deserialize, Serialize};
+use sodoken::crypto_box::curve25519xsalsa20poly1305 as crypto_box;
+use sodoken::hash::argon2id;
+
+use crate::state::load_databases;
+
+/// The version of the backup file format written by `hc keystore export`.
+const BACKUP_VERSION: u32 = 1;
+
+/// The tag of the seed that imported seeds are encrypted to, if the keystore
+/// being imported into has no other seed.
+const IMPORT_RECIPIENT_TAG: &str = "hc-keystore-import";
+
+/// Back up and restore the seeds in a conductor's keystore.
+///
+/// The keystore is opened directly, so the conductor and its lair process
+/// must not be running.
+#[derive(Debug, Parser)]
+pub struct HcKeystore {
+    #[command(subcommand)]
+    subcommand: HcKeystoreSubcommand,
+
+    /// Instead of the normal "interactive" passphrase mode,
+    /// collect the passphrases by reading stdin, one per line.
+    /// The conductor passphrase comes first, followed by the backup passphrase.
+    #[arg(long)]
+    piped: bool,
+}
+
+/// The list of subcommands for `hc keystore`.
+#[derive(Debug, Parser)]
+pub enum HcKeystoreSubcommand {
+    /// Export the seeds of a conductor's keystore to a file encrypted with a
+    /// backup passphrase, along with the agent key of each installed app.
+    Export(Export),
+    /// Import the seeds from a backup file into a conductor's keystore,
+    /// for example into a freshly generated sandbox.
+    ///
+    /// The agent keys of the installed apps are not changed. An app's cells are
+    /// identified by its agent key, so an app installed with another key has to be
+    /// uninstalled and installed again with the restored one. The import reports
+    /// which apps use their restored keys and how to install the others.
+    Import(Import),
+}
+
+/// Options for exporting a keystore
+#[derive(Debug, Parser)]
+pub struct Export {
+    /// The conductor's config root directory, e.g. the path of a sandbox.
+    conductor_dir: PathBuf,
+
+    /// The file to write the backup to.
+    #[arg(short, long)]
+    output: PathBuf,
+}
+
+/// Options for importing a keystore backup
+#[derive(Debug, Parser)]
+pub struct Import {
+    /// The config root directory of the conductor to import into.
+    conductor_dir: PathBuf,
+
+    /// The backup file written by `hc keystore export`.
+    #[arg(short, long)]
+    input: PathBuf,
+}
+
+/// The contents of a backup file.
+///
+/// Each seed is encrypted to an x25519 key derived from the backup passphrase,
+/// so the file can't be used without it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct KeystoreBackup {
+    /// The version of the file format.
+    pub version: u32,
+    /// The salt for deriving the encryption key from the backup passphrase.
+    pub salt: BinDataSized<16>,
+    /// The argon2id ops limit for deriving the encryption key.
+    pub ops_limit: u32,
+    /// The argon2id mem limit for deriving the encryption key.
+    pub mem_limit: u32,
+    /// The encrypted seeds.
+    pub seeds: Vec<SeedBackup>,
+    /// The agent key each app was installed with.
+    pub apps: Vec<AppAgentKey>,
+}
+
+/// A single encrypted seed.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SeedBackup {
+    /// The lair tag of the seed.
+    pub tag: String,
+    /// The agent key of the seed.
+    pub agent: AgentPubKey,
+    /// The x25519 key of the keystore entry the seed was encrypted by.
+    pub sender_pub_key: X25519PubKey,
+    /// The nonce of the encrypted seed.
+    pub nonce: BinDataSized<24>,
+    /// The encrypted seed.
+    pub cipher: BinData,
+}
+
+/// The agent key an app was installed with.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppAgentKey {
+    /// The id of the installed app.
+    pub installed_app_id: InstalledAppId,
+    /// The agent key of the app.
+    pub agent_key: AgentPubKey,
+}
+
+impl HcKeystore {
+    /// Run this command
+    pub async fn run(self) -> anyhow::Result<()> {
+        holochain_util::pw::pw_set_piped(self.piped);
+        match self.subcommand {
+            HcKeystoreSubcommand::Export(export) => export_keystore(export).await,
+            HcKeystoreSubcommand::Import(import) => import_keystore(import).await,
+        }
+    }
+}
+
+/// Perform the `export` subcommand
+pub async fn export_keystore(export: Export) -> anyhow::Result<()> {
+    let Export {
+        conductor_dir,
+        output,
+    } = export;
+
+    let (config, databases, key) = load_databases(&conductor_dir).await?;
+    let lair = open_keystore(&config).await?;
+    let passphrase = holochain_util::pw::pw_get_other("# backup passphrase> ")?;
+
+    let salt = {
+        let salt = sodoken::BufWriteSized::<{ argon2id::SALTBYTES }>::new_no_lock();
+        sodoken::random::bytes_buf(salt.clone()).await?;
+        let salt: [u8; argon2id::SALTBYTES] = *salt.read_lock_sized();
+        BinDataSized::from(salt)
+    };
+    let mut backup = KeystoreBackup {
+        version: BACKUP_VERSION,
+        salt,
+        ops_limit: argon2id::OPSLIMIT_MODERATE,
+        mem_limit: argon2id::MEMLIMIT_MODERATE,
+        seeds: Vec::new(),
+        apps: Vec::new(),
+    };
+    let (backup_pub_key, _) = backup_keypair(&backup, passphrase).await?;
+
+    for entry in lair.list_entries().await? {
+        let (tag, seed_info) = match entry {
+            LairEntryInfo::Seed { tag, seed_info } => (tag, seed_info),
+            LairEntryInfo::DeepLockedSeed { tag, .. } => {
+                msg!("Skipping deep locked seed {tag}");
+                continue;
+            }
+            _ => continue,
+        };
+        let agent = agent_key(&seed_info);
+        if !seed_info.exportable {
+            msg!("Skipping seed {tag} for agent {agent}, which lair does not allow to be exported");
+            continue;
+        }
+        // Lair encrypts with the x25519 key of an entry it holds, and the seed's
+        // own key is always available.
+        let (nonce, cipher) = lair
+            .export_seed_by_tag(
+                tag.clone(),
+                seed_info.x25519_pub_key.clone(),
+                backup_pub_key.clone(),
+                None,
+            )
+            .await?;
+        backup.seeds.push(SeedBackup {
+            tag: tag.to_string(),
+            agent,
+            sender_pub_key: seed_info.x25519_pub_key,
+            nonce: nonce.into(),
+            cipher: cipher.into(),
+        });
+    }
+
+    backup.apps = installed_apps(&databases, key)
+        .await?
+        .into_iter()
+        .map(|app| AppAgentKey {
+            installed_app_id: app.id().clone(),
+            agent_key: app.agent_key.clone(),
+        })
+        .collect();
+
+    tokio::fs::write(&output, serde_yaml::to_string(&backup)?).await?;
+    msg!(
+        "Exported {} seed(s) and the agent keys of {} app(s) to {}",
+        backup.seeds.len(),
+        backup.apps.len(),
+        output.display()
+    );
+    lair.shutdown().await?;
+    Ok(())
+}
+
+/// Perform the `import` subcommand
+pub async fn import_keystore(import: Import) -> anyhow::Result<()> {
+    let Import {
+        conductor_dir,
+        input,
+    } = import;
+
+    let backup: KeystoreBackup = serde_yaml::from_str(&tokio::fs::read_to_string(&input).await?)?;
+    if backup.version != BACKUP_VERSION {
+        bail!(
+            "Unsupported keystore backup version {}, expected {BACKUP_VERSION}",
+            backup.version
+        );
+    }
+
+    let (config, databases, key) = load_databases(&conductor_dir).await?;
+    let lair = open_keystore(&config).await?;
+    let passphrase = holochain_util::pw::pw_get_other("# backup passphrase> ")?;
+    let (backup_pub_key, backup_sec_key) = backup_keypair(&backup, passphrase).await?;
+
+    let existing = lair.list_entries().await?;
+    let existing_agents: Vec<AgentPubKey> = existing
+        .iter()
+        .filter_map(|entry| match entry {
+            LairEntryInfo::Seed { seed_info, .. } => Some(agent_key(seed_info)),
+            _ => None,
+        })
+        .collect();
+    let recipient_pub_key = match existing.iter().find_map(|entry| match entry {
+        LairEntryInfo::Seed { seed_info, .. } => Some(seed_info.x25519_pub_key.clone()),
+        _ => None,
+    }) {
+        Some(pub_key) => pub_key,
+        None => {
+            lair.new_seed(IMPORT_RECIPIENT_TAG.into(), None, false)
+                .await?
+                .x25519_pub_key
+        }
+    };
+
+    let mut imported = 0;
+    for seed in &backup.seeds {
+        if existing_agents.contains(&seed.agent) {
+            msg!("Agent {} is already in the keystore", seed.agent);
+            continue;
+        }
+
+        let plain = sodoken::BufWriteSized::<32>::new_mem_locked()?;
+        crypto_box::open_easy(
+            *seed.nonce.cloned_inner(),
+            plain.clone(),
+            seed.cipher.cloned_inner(),
+            seed.sender_pub_key.cloned_inner(),
+            backup_sec_key.clone(),
+        )
+        .await
+        .map_err(|_| {
+            anyhow!(
+                "Could not decrypt seed {}, is the passphrase correct?",
+                seed.tag
+            )
+        })?;
+
+        // Lair only imports seeds encrypted to one of its own entries, so
+        // encrypt the seed again for the keystore being imported into.
+        let nonce = sodoken::BufWriteSized::<{ crypto_box::NONCEBYTES }>::new_no_lock();
+        sodoken::random::bytes_buf(nonce.clone()).await?;
+        let nonce: [u8; crypto_box::NONCEBYTES] = *nonce.read_lock_sized();
+        let cipher = crypto_box::easy(
+            nonce,
+            plain.to_read(),
+            recipient_pub_key.cloned_inner(),
+            backup_sec_key.clone(),
+        )
+        .await?;
+        let cipher: Arc<[u8]> = cipher.read_lock().to_vec().into();
+
+        let seed_info = lair
+            .import_seed(
+                backup_pub_key.clone(),
+                recipient_pub_key.clone(),
+                None,
+                nonce,
+                cipher,
+                seed.tag.clone().into(),
+                true,
+            )
+            .await?;
+        if agent_key(&seed_info) != seed.agent {
+            bail!(
+                "Seed {} was imported as agent {} but was backed up as {}",
+                seed.tag,
+                agent_key(&seed_info),
+                seed.agent
+            );
+        }
+        imported += 1;
+    }
+    msg!(
+        "Imported {imported} seed(s) into {}",
+        conductor_dir.display()
+    );
+    lair.shutdown().await?;
+
+    let installed: Vec<AppAgentKey> = installed_apps(&databases, key)
+        .await?
+        .into_iter()
+        .map(|app| AppAgentKey {
+            installed_app_id: app.id().clone(),
+            agent_key: app.agent_key.clone(),
+        })
+        .collect();
+    for (app, key_match) in match_app_keys(&backup.apps, &installed) {
+        match key_match {
+            AppKeyMatch::Restored => {
+                msg!(
+                    "App {} uses the restored agent {}",
+                    app.installed_app_id,
+                    app.agent_key
+                );
+            }
+            AppKeyMatch::Mismatched { installed } => {
+                msg!(
+                    "App {} is installed with agent {}, but was backed up with agent {}. Uninstall it and install it again with `hc sandbox call install-app --app-id {} --agent-key {} <happ>`",
+                    app.installed_app_id,
+                    installed,
+                    app.agent_key,
+                    app.installed_app_id,
+                    app.agent_key
+                );
+            }
+            AppKeyMatch::NotInstalled => {
+                msg!(
+                    "App {} is not installed. Install it with `hc sandbox call install-app --app-id {} --agent-key {} <happ>`",
+                    app.installed_app_id,
+                    app.installed_app_id,
+                    app.agent_key
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// How an app of a backup matches the apps installed in the conductor imported into.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AppKeyMatch {
+    /// The app is installed with the agent key it was backed up with.
+    Restored,
+    /// The app is installed with another agent key.
+    Mismatched {
+        /// The agent key the app is installed with.
+        installed: AgentPubKey,
+    },
+    /// The app is not installed.
+    NotInstalled,
+}
+
+/// Match the apps of a backup with the installed apps, by app id.
+pub fn match_app_keys<'a>(
+    backup: &'a [AppAgentKey],
+    installed: &[AppAgentKey],
+) -> Vec<(&'a AppAgentKey, AppKeyMatch)> {
+    backup
+        .iter()
+        .map(|app| {
+            let key_match = match installed
+                .iter()
+                .find(|a| a.installed_app_id == app.installed_app_id)
+            {
+                Some(a) if a.agent_key == app.agent_key => AppKeyMatch::Restored,
+                Some(a) => AppKeyMatch::Mismatched {
+                    installed: a.agent_key.clone(),
+                },
+                None => AppKeyMatch::NotInstalled,
+            };
+            (app, key_match)
+        })
+        .collect()
+}
+
+/// Open the lair keystore of a conductor in this process.
+async fn open_keystore(config: &ConductorConfig) -> anyhow::Result<LairClient> {
+    // The sandbox keeps the lair root of a standalone lair server in the
+    // keystore directory of the data root, the same place as an in process one.
+    let lair_root: KeystorePath = match &config.keystore {
+        KeystoreConfig::LairServerInProc {
+            lair_root: Some(lair_root),
+        }
+        | KeystoreConfig::LairEmbedded {
+            lair_root: Some(lair_root),
+            in_memory: false,
+        } => lair_root.clone(),
+        KeystoreConfig::LairServerInProc { lair_root: None }
+        | KeystoreConfig::LairEmbedded {
+            lair_root: None,
+            in_memory: false,
+        }
+        | KeystoreConfig::LairServer { .. } => config
+            .data_root_path
+            .clone()
+            .ok_or_else(|| anyhow!("The conductor config has no data root path"))?
+            .try_into()?,
+        KeystoreConfig::DangerTestKeystore => {
+            bail!("The conductor uses a test keystore, which has no seeds to back up or restore")
+        }
+        KeystoreConfig::LairEmbedded {
+            in_memory: true, ..
+        } => {
+            bail!("The conductor keeps its keystore in memory, which has no seeds to back up or restore")
+        }
+    };
+    let keystore = spawn_lair_keystore_in_proc(
+        &lair_root.as_ref().join("lair-keystore-config.yaml"),
+        holochain_util::pw::pw_get()?,
+    )
+    .await?;
+    Ok(keystore.lair_client())
+}
+
+/// Derive the keypair that seeds are encrypted to from the backup passphrase.
+async fn backup_keypair(
+    backup: &KeystoreBackup,
+    passphrase: sodoken::BufRead,
+) -> anyhow::Result<(X25519PubKey, sodoken::BufReadSized<32>)> {
+    let seed = sodoken::BufWriteSized::<{ crypto_box::SEEDBYTES }>::new_mem_locked()?;
+    argon2id::hash(
+        seed.clone(),
+        passphrase,
+        *backup.salt.cloned_inner(),
+        backup.ops_limit,
+        backup.mem_limit,
+    )
+    .await?;
+    let pub_key = sodoken::BufWriteSized::<{ crypto_box::PUBLICKEYBYTES }>::new_no_lock();
+    let sec_key = sodoken::BufWriteSized::<{ crypto_box::SECRETKEYBYTES }>::new_mem_locked()?;
+    crypto_box::seed_keypair(pub_key.clone(), sec_key.clone(), seed).await?;
+    let pub_key: [u8; crypto_box::PUBLICKEYBYTES] = *pub_key.read_lock_sized();
+    Ok((pub_key.into(), sec_key.to_read_sized()))
+}
+
+/// The apps installed in a conductor, read from its conductor database.
+async fn installed_apps(databases: &Path, key: DbKey) -> anyhow::Result<Vec<InstalledApp>> {
+    #[derive(Debug, Deserialize)]
+    struct InstalledApps {
+        #[serde(default)]
+        installed_apps_and_services: InstalledAppMap,
+    }
+
+    if !databases.join(DbKindConductor.filename()).exists() {
+        return Ok(Vec::new());
+    }
+    let db = DbRead::open_read_only(
+        databases,
+        DbKindConductor,
+        PoolConfig {
+            synchronous_level: DbSyncLevel::default(),
+            key,
+        },
+    )?;
+    let blob: Option<Vec<u8>> = db
+        .read_async(|txn| {
+            DatabaseResult::Ok(
+                txn.query_row("SELECT blob FROM ConductorState WHERE id = 1", [], |row| {
+                    row.get("blob")
+                })
+                .optional()?,
+            )
+        })
+        .await?;
+    Ok(match blob {
+        Some(blob) => from_blob::<InstalledApps>(blob)?
+            .installed_apps_and_services
+            .values()
+            .cloned()
+            .collect(),
+        None => Vec::new(),
+    })
+}
+
+fn agent_key(seed_info: &SeedInfo) -> AgentPubKey {
+    AgentPubKey::from_raw_32(seed_info.ed25519_pub_key.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(id: &str, agent: u8) -> AppAgentKey {
+        AppAgentKey {
+            installed_app_id: id.to_string(),
+            agent_key: AgentPubKey::from_raw_32(vec![agent; 32]),
+        }
+    }
+
+    #[test]
+    fn backed_up_apps_are_matched_with_installed_apps_by_id() {
+        let backup = [app("restored", 1), app("mismatched", 2), app("missing", 3)];
+        let installed = [app("mismatched", 4), app("restored", 1), app("other", 5)];
+        assert_eq!(
+            vec![
+                (&backup[0], AppKeyMatch::Restored),
+                (
+                    &backup[1],
+                    AppKeyMatch::Mismatched {
+                        installed: installed[0].agent_key.clone()
+                    }
+                ),
+                (&backup[2], AppKeyMatch::NotInstalled),
+            ],
+            match_app_keys(&backup, &installed)
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn backup_keypair_is_derived_from_the_passphrase() {
+        let backup = KeystoreBackup {
+            version: BACKUP_VERSION,
+            salt: BinDataSized::from([7; 16]),
+            ops_limit: argon2id::OPSLIMIT_MIN,
+            mem_limit: argon2id::MEMLIMIT_MIN,
+            seeds: Vec::new(),
+            apps: Vec::new(),
+        };
+        let pub_key = |passphrase: &'static [u8]| {
+            let backup = &backup;
+            async move {
+                backup_keypair(backup, sodoken::BufRead::new_no_lock(passphrase))
+                    .await
+                    .unwrap()
+                    .0
+            }
+        };
+        assert_eq!(pub_key(b"passphrase").await, pub_key(b"passphrase").await);
+        assert_ne!(pub_key(b"passphrase").await, pub_key(b"other").await);
+    }
+}
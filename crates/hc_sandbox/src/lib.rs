@@ -44,9 +44,12 @@ pub mod cli;
 pub mod cmds;
 pub mod config;
 pub mod generate;
+pub mod keystore;
+pub mod migrate;
 pub mod run;
 pub mod sandbox;
 pub mod save;
+pub mod state;
 pub use cli::HcSandbox;
 use holochain_trace::Output;
 
@@ -0,0 +1,211 @@
+//! Move the apps of a running conductor onto another running conductor.
+
+use anyhow::{bail, ensure, Context};
+use clap::Parser;
+use holochain_conductor_api::{AdminRequest, AdminResponse, AppInfo, CellInfo};
+use holochain_types::prelude::*;
+
+use crate::calls::{disable_app, enable_app, export_app_bundle, list_apps};
+use crate::calls::{DisableApp, EnableApp, ListApps};
+use crate::expect_match;
+use crate::CmdRunner;
+
+/// Move the apps of a running conductor onto another running conductor, e.g. when
+/// moving to a new host.
+///
+/// Each app is disabled on the source conductor, so that its chains can't move on
+/// while they are copied. It is then installed on the target conductor from the
+/// bundle it was installed from, the source chains of its cells are grafted onto the
+/// target, and it is enabled once the chain heads of the two conductors match.
+/// The apps are left disabled on the source conductor, and an app whose migration
+/// fails can be enabled there again.
+///
+/// Capability grants are entries of the source chains, so they are moved with them.
+/// The agent keys aren't: they must be in the keystore of the target conductor
+/// already, e.g. by moving the seeds with `hc keystore export` and `hc keystore import`.
+/// Clone cells and apps which use the cells of other apps aren't migrated.
+#[derive(Debug, Parser)]
+pub struct HcMigrate {
+    /// The admin port of the conductor to move the apps from.
+    #[arg(long)]
+    source_port: u16,
+
+    /// The admin port of the conductor to move the apps to.
+    #[arg(long)]
+    target_port: u16,
+
+    /// The apps to move. Defaults to all apps of the source conductor.
+    #[arg(long = "app", value_delimiter = ',')]
+    apps: Vec<InstalledAppId>,
+
+    /// Validate the records of the source chains again as they are grafted onto the target.
+    #[arg(long)]
+    validate: bool,
+}
+
+impl HcMigrate {
+    /// Run this command.
+    pub async fn run(self) -> anyhow::Result<()> {
+        let mut source = CmdRunner::try_new(self.source_port).await?;
+        let mut target = CmdRunner::try_new(self.target_port).await?;
+
+        let all_apps = list_apps(
+            &mut source,
+            ListApps {
+                status: None,
+                dna: None,
+                offset: None,
+                limit: None,
+            },
+        )
+        .await?;
+        let apps: Vec<AppInfo> = if self.apps.is_empty() {
+            all_apps
+        } else {
+            self.apps
+                .iter()
+                .map(|app_id| {
+                    all_apps
+                        .iter()
+                        .find(|app| &app.installed_app_id == app_id)
+                        .cloned()
+                        .with_context(|| format!("No app {app_id} on the source conductor"))
+                })
+                .collect::<anyhow::Result<_>>()?
+        };
+
+        for app in apps {
+            migrate_app(&mut source, &mut target, app, self.validate).await?;
+        }
+        Ok(())
+    }
+}
+
+/// Move one app from the source conductor to the target conductor.
+async fn migrate_app(
+    source: &mut CmdRunner,
+    target: &mut CmdRunner,
+    app: AppInfo,
+    validate: bool,
+) -> anyhow::Result<()> {
+    let app_id = app.installed_app_id.clone();
+    disable_app(
+        source,
+        DisableApp {
+            app_id: app_id.clone(),
+        },
+    )
+    .await?;
+
+    let mut chains = Vec::new();
+    let mut membrane_proofs = MemproofMap::new();
+    for (role_name, cells) in &app.cell_info {
+        for cell in cells {
+            let cell_id = match cell {
+                CellInfo::Provisioned(cell) => cell.cell_id.clone(),
+                CellInfo::Cloned(cell) => {
+                    msg!("Skipping clone cell {} of app {app_id}", cell.clone_id);
+                    continue;
+                }
+                CellInfo::Stem(_) => continue,
+            };
+            let records = source_chain(source, &cell_id).await?;
+            // Genesis on the target needs the membrane proof which the agent joined with.
+            let membrane_proof = records.iter().find_map(|record| match record.action() {
+                Action::AgentValidationPkg(pkg) => pkg.membrane_proof.clone(),
+                _ => None,
+            });
+            if let Some(membrane_proof) = membrane_proof {
+                membrane_proofs.insert(role_name.clone(), membrane_proof);
+            }
+            chains.push((cell_id, records));
+        }
+    }
+
+    let bundle = export_app_bundle(source, app_id.clone()).await?;
+    let payload = InstallAppPayload {
+        source: AppBundleSource::Bundle(AppBundle::decode(&bundle)?),
+        agent_key: Some(app.agent_pub_key.clone()),
+        installed_app_id: Some(app_id.clone()),
+        membrane_proofs: Some(membrane_proofs),
+        existing_cells: Default::default(),
+        network_seed: None,
+        ignore_genesis_failure: false,
+        allow_throwaway_random_agent_key: false,
+        network_profile: None,
+        client_only: false,
+    };
+    let resp = target
+        .command(AdminRequest::InstallApp(Box::new(payload)))
+        .await
+        .with_context(|| {
+            format!(
+                "Failed to install app {app_id} on the target conductor, \
+                is agent {} in its keystore?",
+                app.agent_pub_key
+            )
+        })?;
+    expect_match!(resp => AdminResponse::AppInstalled, "Failed to install app");
+
+    for (cell_id, records) in chains {
+        let source_head = records.last().map(|record| record.action_address().clone());
+        let resp = target
+            .command(AdminRequest::GraftRecords {
+                cell_id: cell_id.clone(),
+                validate,
+                records,
+            })
+            .await?;
+        ensure!(
+            matches!(resp, AdminResponse::RecordsGrafted),
+            "Failed to graft records, got: {:?}",
+            resp
+        );
+        let target_head = source_chain(target, &cell_id)
+            .await?
+            .last()
+            .map(|record| record.action_address().clone());
+        if target_head != source_head {
+            bail!(
+                "The chain head of cell {cell_id} of app {app_id} is {target_head:?} on the \
+                target conductor but {source_head:?} on the source conductor"
+            );
+        }
+    }
+
+    enable_app(
+        target,
+        EnableApp {
+            app_id: app_id.clone(),
+        },
+    )
+    .await?;
+    msg!("Migrated app {app_id}, which is disabled on the source conductor");
+    Ok(())
+}
+
+/// The records of the source chain of a cell, in order.
+async fn source_chain(cmd: &mut CmdRunner, cell_id: &CellId) -> anyhow::Result<Vec<Record>> {
+    let resp = cmd
+        .command(AdminRequest::DumpFullState {
+            cell_id: Box::new(cell_id.clone()),
+            // Only the source chain is needed, so skip all of the DHT ops.
+            dht_ops_cursor: Some(u64::MAX),
+        })
+        .await?;
+    let dump = expect_match!(resp => AdminResponse::FullStateDumped, "Failed to dump state");
+    Ok(dump
+        .source_chain_dump
+        .records
+        .into_iter()
+        .map(|record| {
+            Record::new(
+                SignedActionHashed::with_presigned(
+                    ActionHashed::with_pre_hashed(record.action, record.action_address),
+                    record.signature,
+                ),
+                record.entry,
+            )
+        })
+        .collect())
+}
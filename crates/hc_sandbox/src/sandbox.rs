@@ -56,6 +56,7 @@ pub async fn default_with_network(
         agent_key: None,
         path: happ,
         network_seed,
+        client_only: false,
     };
     crate::calls::install_app_bundle(&mut cmd, install_bundle).await?;
     Ok(path)
@@ -0,0 +1,491 @@
+//! Inspect the databases of a conductor that is not running.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use clap::{Parser, ValueEnum};
+use holochain_conductor_api::conductor::paths::ConfigRootPath;
+use holochain_conductor_api::conductor::paths::DatabasesRootPath;
+use holochain_conductor_api::config::conductor::ConductorConfig;
+use holochain_conductor_api::IntegrationStateDump;
+use holochain_p2p::AgentPubKeyExt;
+use holochain_state::prelude::*;
+use serde::Serialize;
+
+use crate::config::read_config;
+
+/// Inspect the state of a conductor from its databases.
+///
+/// The databases are read directly, so the conductor must not be running.
+#[derive(Debug, Parser)]
+pub struct HcState {
+    #[command(subcommand)]
+    subcommand: HcStateSubcommand,
+
+    /// Instead of the normal "interactive" passphrase mode,
+    /// collect the passphrase by reading stdin to the end.
+    #[arg(long)]
+    piped: bool,
+}
+
+/// The list of subcommands for `hc state`.
+#[derive(Debug, Parser)]
+pub enum HcStateSubcommand {
+    /// Print the source chains, op integration status and peer store of a conductor.
+    Dump(Dump),
+}
+
+/// Options for dumping the state of a conductor
+#[derive(Debug, Parser)]
+pub struct Dump {
+    /// The conductor's config root directory, e.g. the path of a sandbox.
+    conductor_dir: PathBuf,
+
+    /// Only dump the cells and spaces of this DNA.
+    #[arg(long, value_parser = parse_hash::<DnaHash>)]
+    dna: Option<DnaHash>,
+
+    /// Only dump the cells of this agent.
+    #[arg(long, value_parser = parse_hash::<AgentPubKey>)]
+    agent: Option<AgentPubKey>,
+
+    /// List each op that is in this status, in addition to the counts.
+    /// For example `hc state dump --op-status=validation-limbo,integration-limbo`.
+    #[arg(long, value_enum, value_delimiter = ',')]
+    op_status: Vec<OpStatus>,
+
+    /// Print the dump as JSON instead of human readable text.
+    #[arg(long)]
+    json: bool,
+}
+
+/// Where an op is in the validation and integration pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum OpStatus {
+    /// Awaiting sys or app validation.
+    ValidationLimbo,
+    /// Validated and waiting to be integrated.
+    IntegrationLimbo,
+    /// Integrated, including rejected ops.
+    Integrated,
+}
+
+impl OpStatus {
+    fn sql_filter(&self) -> &'static str {
+        match self {
+            OpStatus::ValidationLimbo => {
+                "when_integrated IS NULL AND (validation_stage IS NULL OR validation_stage < 3)"
+            }
+            OpStatus::IntegrationLimbo => "when_integrated IS NULL AND validation_stage = 3",
+            OpStatus::Integrated => "when_integrated IS NOT NULL",
+        }
+    }
+}
+
+/// The state of all cells and spaces found in a conductor's databases.
+#[derive(Debug, Serialize)]
+pub struct ConductorStateDump {
+    /// The source chain of every cell.
+    pub cells: Vec<CellStateDump>,
+    /// The DHT and peer store of every space.
+    pub spaces: Vec<SpaceStateDump>,
+}
+
+/// The source chain of one cell.
+#[derive(Debug, Serialize)]
+pub struct CellStateDump {
+    /// The cell this chain belongs to.
+    pub cell_id: CellId,
+    /// The records and publish status of the chain.
+    pub chain: SourceChainDump,
+}
+
+/// The DHT ops and peers held for one DNA.
+#[derive(Debug, Serialize)]
+pub struct SpaceStateDump {
+    /// The DNA of this space.
+    pub dna_hash: DnaHash,
+    /// The number of ops in each status.
+    pub op_counts: IntegrationStateDump,
+    /// The ops in the statuses asked for with `--op-status`.
+    pub ops: Vec<OpDump>,
+    /// The agent infos in the peer store.
+    pub peers: Vec<PeerDump>,
+}
+
+/// A single op in the DHT database.
+#[derive(Debug, Serialize)]
+pub struct OpDump {
+    /// The hash of the op.
+    pub hash: DhtOpHash,
+    /// The type of the op.
+    pub op_type: String,
+    /// The action the op was produced from.
+    pub action_hash: ActionHash,
+    /// Where the op is in the pipeline.
+    pub status: OpStatus,
+    /// The outcome of validation, if it has finished.
+    pub validation_status: Option<ValidationStatus>,
+}
+
+/// A single agent info in the peer store.
+#[derive(Debug, Serialize)]
+pub struct PeerDump {
+    /// The agent the info is for.
+    pub agent: AgentPubKey,
+    /// When the info was signed.
+    pub signed_at: Timestamp,
+    /// When the info expires.
+    pub expires_at: Timestamp,
+    /// The urls the agent can be reached at.
+    pub urls: Vec<String>,
+}
+
+impl HcState {
+    /// Run this command
+    pub async fn run(self) -> anyhow::Result<()> {
+        holochain_util::pw::pw_set_piped(self.piped);
+        match self.subcommand {
+            HcStateSubcommand::Dump(dump) => {
+                let json = dump.json;
+                let state = dump_state(dump).await?;
+                if json {
+                    println!("{}", serde_json::to_string_pretty(&state)?);
+                } else {
+                    print!("{state}");
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Perform the `dump` subcommand
+pub async fn dump_state(dump: Dump) -> anyhow::Result<ConductorStateDump> {
+    let Dump {
+        conductor_dir,
+        dna,
+        agent,
+        op_status,
+        ..
+    } = dump;
+
+    let (_, databases, key) = load_databases(&conductor_dir).await?;
+    dump_databases(&databases, key, dna, agent, op_status).await
+}
+
+/// Dump the cells and spaces found in a databases directory.
+///
+/// The databases are opened read-only and aren't migrated, so that inspecting a
+/// conductor leaves its databases as they were.
+async fn dump_databases(
+    databases: &Path,
+    key: DbKey,
+    dna: Option<DnaHash>,
+    agent: Option<AgentPubKey>,
+    op_status: Vec<OpStatus>,
+) -> anyhow::Result<ConductorStateDump> {
+    let pool_config = || PoolConfig {
+        synchronous_level: DbSyncLevel::default(),
+        key: key.clone(),
+    };
+
+    let mut cells = Vec::new();
+    for cell_id in list_authored_cells(databases)? {
+        if dna.as_ref().is_some_and(|d| d != cell_id.dna_hash())
+            || agent.as_ref().is_some_and(|a| a != cell_id.agent_pubkey())
+        {
+            continue;
+        }
+        let db = DbRead::open_read_only(
+            databases,
+            DbKindAuthored(Arc::new(cell_id.clone())),
+            pool_config(),
+        )?;
+        let chain =
+            holochain_state::source_chain::dump_state(db, cell_id.agent_pubkey().clone()).await?;
+        cells.push(CellStateDump { cell_id, chain });
+    }
+
+    let mut spaces = Vec::new();
+    for dna_hash in list_spaces(databases)? {
+        if dna.as_ref().is_some_and(|d| *d != dna_hash) {
+            continue;
+        }
+        let dht_db = DbRead::open_read_only(
+            databases,
+            DbKindDht(Arc::new(dna_hash.clone())),
+            pool_config(),
+        )?;
+        let (op_counts, ops) = dump_ops(&dht_db, op_status.clone()).await?;
+
+        let space = Arc::new(holochain_p2p::space_holo_to_kit(dna_hash.clone()));
+        let peers = if databases
+            .join(DbKindP2pAgents(space.clone()).filename())
+            .exists()
+        {
+            DbRead::open_read_only(databases, DbKindP2pAgents(space), pool_config())?
+                .p2p_list_agents()
+                .await?
+                .into_iter()
+                .map(|info| PeerDump {
+                    agent: AgentPubKey::from_kitsune(&info.agent),
+                    signed_at: Timestamp::from_micros(info.signed_at_ms as i64 * 1000),
+                    expires_at: Timestamp::from_micros(info.expires_at_ms as i64 * 1000),
+                    urls: info.url_list.iter().map(|u| u.to_string()).collect(),
+                })
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        spaces.push(SpaceStateDump {
+            dna_hash,
+            op_counts,
+            ops,
+            peers,
+        });
+    }
+
+    Ok(ConductorStateDump { cells, spaces })
+}
+
+async fn dump_ops(
+    db: &DbRead<DbKindDht>,
+    op_status: Vec<OpStatus>,
+) -> anyhow::Result<(IntegrationStateDump, Vec<OpDump>)> {
+    Ok(db
+        .read_async(move |txn| {
+            let count = |status: OpStatus| {
+                txn.query_row(
+                    &format!(
+                        "SELECT count(hash) FROM DhtOp WHERE {}",
+                        status.sql_filter()
+                    ),
+                    [],
+                    |row| row.get(0),
+                )
+            };
+            let op_counts = IntegrationStateDump {
+                validation_limbo: count(OpStatus::ValidationLimbo)?,
+                integration_limbo: count(OpStatus::IntegrationLimbo)?,
+                integrated: count(OpStatus::Integrated)?,
+            };
+            let mut ops = Vec::new();
+            for status in op_status {
+                let mut stmt = txn.prepare(&format!(
+                    "
+                    SELECT hash, type, action_hash, validation_status FROM DhtOp
+                    WHERE {}
+                    ORDER BY authored_timestamp ASC
+                    ",
+                    status.sql_filter()
+                ))?;
+                let rows = stmt.query_map([], |row| {
+                    Ok(OpDump {
+                        hash: row.get("hash")?,
+                        op_type: row.get("type")?,
+                        action_hash: row.get("action_hash")?,
+                        status,
+                        validation_status: row.get("validation_status")?,
+                    })
+                })?;
+                for op in rows {
+                    ops.push(op?);
+                }
+            }
+            DatabaseResult::Ok((op_counts, ops))
+        })
+        .await?)
+}
+
+/// Read the config of a conductor and unlock the key of its databases.
+pub(crate) async fn load_databases(
+    conductor_dir: &Path,
+) -> anyhow::Result<(ConductorConfig, DatabasesRootPath, DbKey)> {
+    let config = read_config(ConfigRootPath::from(conductor_dir.to_path_buf()))?
+        .ok_or_else(|| anyhow!("No conductor config found in {}", conductor_dir.display()))?;
+    let databases: DatabasesRootPath = config
+        .data_root_path
+        .clone()
+        .ok_or_else(|| anyhow!("The conductor config has no data root path"))?
+        .try_into()?;
+    let locked = tokio::fs::read_to_string(databases.join("db.key")).await?;
+    let key = DbKey::load(locked, holochain_util::pw::pw_get()?).await?;
+    Ok((config, databases, key))
+}
+
+/// The cells with an authored database, whose file names are `<dna>-<agent>`.
+fn list_authored_cells(databases: &Path) -> anyhow::Result<Vec<CellId>> {
+    let mut cells = Vec::new();
+    for name in list_db_names(&databases.join("authored"))? {
+        // Base64 hashes can contain a `-`, so split on the length of the
+        // DNA hash rather than on the separator.
+        let (Some(dna), Some(agent)) = (
+            name.get(..DNA_HASH_B64_LEN),
+            name.get(DNA_HASH_B64_LEN + 1..),
+        ) else {
+            continue;
+        };
+        if let (Ok(dna), Ok(agent)) = (DnaHash::try_from(dna), AgentPubKey::try_from(agent)) {
+            cells.push(CellId::new(dna, agent));
+        }
+    }
+    Ok(cells)
+}
+
+/// The DNAs with a DHT database.
+fn list_spaces(databases: &Path) -> anyhow::Result<Vec<DnaHash>> {
+    Ok(list_db_names(&databases.join("dht"))?
+        .into_iter()
+        .filter_map(|name| DnaHash::try_from(name.as_str()).ok())
+        .collect())
+}
+
+/// The file names in a database directory, without sqlite's auxiliary files.
+fn list_db_names(dir: &Path) -> anyhow::Result<Vec<String>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut names = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let name = entry?.file_name().to_string_lossy().to_string();
+        if !name.ends_with("-wal") && !name.ends_with("-shm") {
+            names.push(name);
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+/// The length of a base64 encoded DNA hash, including the `u` prefix.
+const DNA_HASH_B64_LEN: usize = 53;
+
+fn parse_hash<T>(arg: &str) -> anyhow::Result<T>
+where
+    for<'a> T: TryFrom<&'a str, Error = HoloHashError>,
+{
+    Ok(T::try_from(arg)?)
+}
+
+impl std::fmt::Display for ConductorStateDump {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for cell in &self.cells {
+            writeln!(f, "--- Cell {} ---", cell.cell_id)?;
+            writeln!(
+                f,
+                "Records authored: {}, Ops published: {}",
+                cell.chain.records.len(),
+                cell.chain.published_ops_count
+            )?;
+            for record in &cell.chain.records {
+                writeln!(
+                    f,
+                    "  #{} {:?} {} at {}",
+                    record.action.action_seq(),
+                    record.action.action_type(),
+                    record.action_address,
+                    record.action.timestamp()
+                )?;
+            }
+        }
+        for space in &self.spaces {
+            writeln!(f, "--- DNA {} ---", space.dna_hash)?;
+            let counts = &space.op_counts;
+            writeln!(
+                f,
+                "Ops in validation limbo: {}, in integration limbo: {}, integrated: {}",
+                counts.validation_limbo, counts.integration_limbo, counts.integrated
+            )?;
+            for op in &space.ops {
+                writeln!(
+                    f,
+                    "  {} {} for {} {:?} {:?}",
+                    op.hash, op.op_type, op.action_hash, op.status, op.validation_status
+                )?;
+            }
+            writeln!(f, "Peers in p2p store: {}", space.peers.len())?;
+            for peer in &space.peers {
+                writeln!(
+                    f,
+                    "  {} signed at {}, expires at {}, urls: {:?}",
+                    peer.agent, peer.signed_at, peer.expires_at, peer.urls
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::prelude::*;
+
+    fn activity_op() -> DhtOpHashed {
+        DhtOpHashed::from_content_sync(ChainOp::RegisterAgentActivity(
+            fixt!(Signature),
+            Action::Create(fixt!(Create)),
+        ))
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn dump_reads_a_sandbox_without_changing_it() {
+        let sandbox = tempfile::tempdir().unwrap();
+        let databases = sandbox.path();
+        let dna_hash = fixt!(DnaHash);
+        let dht_kind = DbKindDht(Arc::new(dna_hash.clone()));
+
+        let ops = [activity_op(), activity_op(), activity_op()];
+        let limbo_hash = ops[1].as_hash().clone();
+        DbWrite::test(databases, dht_kind.clone())
+            .unwrap()
+            .write_async(move |txn| {
+                for op in &ops {
+                    insert_op_dht(txn, op, None)?;
+                }
+                set_validation_stage(txn, ops[1].as_hash(), ValidationStage::AwaitingIntegration)?;
+                set_validation_status(txn, ops[2].as_hash(), ValidationStatus::Valid)?;
+                set_when_integrated(txn, ops[2].as_hash(), Timestamp::now())?;
+                StateMutationResult::Ok(())
+            })
+            .await
+            .unwrap();
+        let dht_file = databases.join(dht_kind.filename());
+        let before = std::fs::read(&dht_file).unwrap();
+
+        let state = dump_databases(
+            databases,
+            DbKey::default(),
+            None,
+            None,
+            vec![OpStatus::IntegrationLimbo],
+        )
+        .await
+        .unwrap();
+        assert!(state.cells.is_empty());
+        assert_eq!(1, state.spaces.len());
+        let space = &state.spaces[0];
+        assert_eq!(dna_hash, space.dna_hash);
+        assert_eq!(1, space.op_counts.validation_limbo);
+        assert_eq!(1, space.op_counts.integration_limbo);
+        assert_eq!(1, space.op_counts.integrated);
+        assert_eq!(
+            vec![limbo_hash],
+            space
+                .ops
+                .iter()
+                .map(|op| op.hash.clone())
+                .collect::<Vec<_>>()
+        );
+        assert!(space.peers.is_empty());
+
+        // - The databases are left as they were, and none are created.
+        assert_eq!(before, std::fs::read(&dht_file).unwrap());
+        let space = Arc::new(holochain_p2p::space_holo_to_kit(dna_hash));
+        assert!(!databases.join(DbKindP2pAgents(space).filename()).exists());
+    }
+}
@@ -3,15 +3,19 @@ use holochain_cli_sandbox::config::read_config;
 use holochain_conductor_api::conductor::ConductorConfig;
 #[cfg(feature = "unstable-dpki")]
 use holochain_conductor_api::conductor::DpkiConfig;
-use holochain_conductor_api::AppResponse;
 use holochain_conductor_api::{AdminRequest, AdminResponse, AppAuthenticationRequest, AppRequest};
+use holochain_conductor_api::{AppInfo, AppInfoStatus, AppResponse, CellInfo};
+use holochain_p2p::AgentPubKeyExt;
 use holochain_types::app::InstalledAppId;
+use holochain_types::prelude::{ActionHash, AgentPubKey, CellId};
 use holochain_types::prelude::{SerializedBytes, SerializedBytesError};
 use holochain_websocket::{
     self as ws, ConnectRequest, WebsocketConfig, WebsocketReceiver, WebsocketResult,
     WebsocketSender,
 };
+use kitsune_p2p_types::config::TransportConfig;
 use matches::assert_matches;
+use std::collections::HashSet;
 use std::future::Future;
 use std::net::ToSocketAddrs;
 use std::path::PathBuf;
@@ -21,6 +25,7 @@ use std::sync::Arc;
 use std::time::Duration;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::process::{Child, Command};
+use url2::Url2;
 
 const WEBSOCKET_TIMEOUT: Duration = Duration::from_secs(3);
 
@@ -395,6 +400,200 @@ async fn create_sandbox_with_custom_dpki_network_seed() {
     assert_eq!(conductor_config.dpki.network_seed, network_seed);
 }
 
+/// Runs a local network of two conductors, which must only be configured with the
+/// bootstrap and signal servers started alongside them, and checks that each
+/// conductor finds the agent of the other one.
+#[tokio::test(flavor = "multi_thread")]
+async fn local_network_conductors_find_each_other() {
+    clean_sandboxes().await;
+    package_fixture_if_not_packaged().await;
+
+    holochain_trace::test_run();
+    let mut cmd = get_sandbox_command();
+    cmd.env("RUST_BACKTRACE", "1")
+        .arg(format!(
+            "--holochain-path={}",
+            get_holochain_bin_path().to_str().unwrap()
+        ))
+        .arg("--piped")
+        .arg("local-network")
+        .arg("--in-process-lair")
+        .arg("--num-conductors=2")
+        .arg("tests/fixtures/my-app/")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .kill_on_drop(true);
+
+    let mut hc_local_network = input_piped_password(&mut cmd).await;
+    let network = get_local_network_info(&mut hc_local_network, 2).await;
+
+    // No conductor is configured with any service other than the local ones.
+    assert_eq!(network.configs.len(), 2);
+    for config in &network.configs {
+        assert_eq!(
+            config.network.bootstrap_service,
+            Some(Url2::parse(&network.bootstrap_url))
+        );
+        assert_eq!(config.network.transport_pool.len(), 1);
+        assert_matches!(
+            &config.network.transport_pool[0],
+            TransportConfig::WebRTC { signal_url, .. } if *signal_url == network.signal_url
+        );
+    }
+    for url in [&network.bootstrap_url, &network.signal_url] {
+        assert!(url.contains("127.0.0.1"), "{url} is not on this host");
+    }
+
+    let mut admins = Vec::new();
+    for launch_info in &network.launch_infos {
+        admins.push(
+            new_websocket_client_for_port::<AdminResponse>(launch_info.admin_port)
+                .await
+                .unwrap(),
+        );
+    }
+
+    let mut agents = Vec::new();
+    for (admin_tx, _) in &admins {
+        let response: AdminResponse =
+            check_timeout(admin_tx.request(AdminRequest::ListCellIds(None))).await;
+        match response {
+            AdminResponse::CellIdsListed(cell_ids) => agents.push(
+                cell_ids
+                    .into_iter()
+                    .map(|cell_id| cell_id.agent_pubkey().clone())
+                    .collect::<HashSet<_>>(),
+            ),
+            _ => panic!("Unexpected response {:?}", response),
+        }
+    }
+
+    // Each conductor learns about the agents of the other one through the local services.
+    for (index, (admin_tx, _)) in admins.iter().enumerate() {
+        let others = &agents[1 - index];
+        tokio::time::timeout(Duration::from_secs(60), async {
+            loop {
+                let response: AdminResponse =
+                    check_timeout(admin_tx.request(AdminRequest::AgentInfo { cell_id: None }))
+                        .await;
+                let known = match response {
+                    AdminResponse::AgentInfo(infos) => infos
+                        .iter()
+                        .map(|info| AgentPubKey::from_kitsune(&info.agent))
+                        .collect::<HashSet<_>>(),
+                    _ => panic!("Unexpected response {:?}", response),
+                };
+                if others.is_subset(&known) {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(500)).await;
+            }
+        })
+        .await
+        .unwrap_or_else(|_| panic!("Conductor {index} never found the other conductor"));
+    }
+}
+
+/// Moves the app of one sandbox onto a freshly created sandbox, which has the seeds of
+/// the first one imported, and checks that the app is running there with the same chain.
+#[tokio::test(flavor = "multi_thread")]
+async fn migrate_app_to_new_sandbox() {
+    clean_sandboxes().await;
+    package_fixture_if_not_packaged().await;
+
+    holochain_trace::test_run();
+    let mut cmd = get_sandbox_command();
+    cmd.arg("--piped")
+        .arg("generate")
+        .arg("--in-process-lair")
+        .arg("tests/fixtures/my-app/");
+    let source_path = run_to_created_sandbox_path(cmd).await;
+
+    let mut cmd = get_sandbox_command();
+    cmd.arg("--piped").arg("create").arg("--in-process-lair");
+    let target_path = run_to_created_sandbox_path(cmd).await;
+
+    let backup = tempfile::tempdir().unwrap();
+    let backup_file = backup.path().join("keystore-backup.json");
+    let mut cmd = get_hc_command();
+    cmd.arg("keystore")
+        .arg("--piped")
+        .arg("export")
+        .arg(&source_path)
+        .arg("--output")
+        .arg(&backup_file);
+    run_with_passphrases(cmd).await;
+    let mut cmd = get_hc_command();
+    cmd.arg("keystore")
+        .arg("--piped")
+        .arg("import")
+        .arg(&target_path)
+        .arg("--input")
+        .arg(&backup_file);
+    run_with_passphrases(cmd).await;
+
+    let mut cmd = get_sandbox_command();
+    cmd.env("RUST_BACKTRACE", "1")
+        .arg(format!(
+            "--holochain-path={}",
+            get_holochain_bin_path().to_str().unwrap()
+        ))
+        .arg("--piped")
+        .arg("run")
+        .arg(format!(
+            "--existing-paths={},{}",
+            source_path.display(),
+            target_path.display()
+        ))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .kill_on_drop(true);
+    let mut hc_run = input_piped_password(&mut cmd).await;
+    let launch_infos = get_launch_infos(&mut hc_run, 2).await;
+    let (source_port, target_port) = (launch_infos[0].admin_port, launch_infos[1].admin_port);
+
+    let (source, _source_poll) = new_websocket_client_for_port::<AdminResponse>(source_port)
+        .await
+        .unwrap();
+    let (target, _target_poll) = new_websocket_client_for_port::<AdminResponse>(target_port)
+        .await
+        .unwrap();
+    let source_app = list_only_app(&source).await;
+    assert_matches!(source_app.status, AppInfoStatus::Running);
+    let cell_id = source_app
+        .cell_info
+        .values()
+        .flatten()
+        .find_map(|cell| match cell {
+            CellInfo::Provisioned(cell) => Some(cell.cell_id.clone()),
+            _ => None,
+        })
+        .unwrap();
+    let source_chain = chain_action_hashes(&source, &cell_id).await;
+
+    let status = get_hc_command()
+        .arg("migrate")
+        .arg(format!("--source-port={source_port}"))
+        .arg(format!("--target-port={target_port}"))
+        .status()
+        .await
+        .unwrap();
+    assert!(status.success());
+
+    // The app is left disabled on the source conductor, so its chain can't move on.
+    assert_matches!(
+        list_only_app(&source).await.status,
+        AppInfoStatus::Disabled { .. }
+    );
+    let target_app = list_only_app(&target).await;
+    assert_eq!(target_app.installed_app_id, source_app.installed_app_id);
+    assert_eq!(target_app.agent_pub_key, source_app.agent_pub_key);
+    assert_matches!(target_app.status, AppInfoStatus::Running);
+    assert_eq!(chain_action_hashes(&target, &cell_id).await, source_chain);
+}
+
 include!(concat!(env!("OUT_DIR"), "/target.rs"));
 
 fn get_target(file: &str) -> std::path::PathBuf {
@@ -466,6 +665,153 @@ async fn get_created_conductor_config(process: &mut Child) -> ConductorConfig {
     panic!("getting created conductor config failed");
 }
 
+/// What a sandbox running a local network reports about itself.
+struct LocalNetworkInfo {
+    bootstrap_url: String,
+    signal_url: String,
+    configs: Vec<ConductorConfig>,
+    launch_infos: Vec<LaunchInfo>,
+}
+
+async fn get_local_network_info(process: &mut Child, num_conductors: usize) -> LocalNetworkInfo {
+    let stdout = process.stdout.take().unwrap();
+    let mut lines = BufReader::new(stdout).lines();
+    let mut services = None;
+    let mut configs = Vec::new();
+    let mut launch_infos = Vec::new();
+    while let Ok(Some(line)) = lines.next_line().await {
+        println!("@@@-{line}-@@@");
+        if let Some(index) = line.find("Running local services, bootstrap: ") {
+            let (bootstrap_url, signal_url) = line[index..]
+                .trim_start_matches("Running local services, bootstrap: ")
+                .split_once(", signal: ")
+                .unwrap();
+            services = Some((
+                bootstrap_url.trim().to_string(),
+                signal_url.trim().to_string(),
+            ));
+        } else if line.contains("Created [ConfigRootPath") {
+            for path in line.split("ConfigRootPath(\"").skip(1) {
+                let path = path.split_once("\")").unwrap().0;
+                configs.push(
+                    read_config(PathBuf::from_str(path).unwrap().into())
+                        .unwrap()
+                        .unwrap(),
+                );
+            }
+        } else if let Some(index) = line.find("Conductor launched #!") {
+            let launch_info_str = line[index..].trim().rsplit_once(' ').unwrap().1;
+            launch_infos.push(serde_json::from_str::<LaunchInfo>(launch_info_str).unwrap());
+            if launch_infos.len() == num_conductors {
+                // Keep reading the output so that the sandbox never blocks on writing it.
+                tokio::spawn(async move { while let Ok(Some(_)) = lines.next_line().await {} });
+                let (bootstrap_url, signal_url) =
+                    services.expect("The local services were never reported");
+                return LocalNetworkInfo {
+                    bootstrap_url,
+                    signal_url,
+                    configs,
+                    launch_infos,
+                };
+            }
+        }
+    }
+    panic!("Unable to find the local network info in sandbox output. See stderr above.")
+}
+
+/// Run a sandbox command which creates a single sandbox to completion and return the
+/// path of the sandbox.
+async fn run_to_created_sandbox_path(mut cmd: Command) -> PathBuf {
+    cmd.stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit());
+    let output = input_piped_password(&mut cmd)
+        .await
+        .wait_with_output()
+        .await
+        .unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let path = stdout
+        .split("ConfigRootPath(\"")
+        .nth(1)
+        .and_then(|path| path.split_once("\")"))
+        .expect("getting created sandbox path failed")
+        .0;
+    PathBuf::from_str(path).unwrap()
+}
+
+/// Run an `hc keystore` command to completion, giving it the conductor passphrase
+/// followed by the backup passphrase.
+async fn run_with_passphrases(mut cmd: Command) {
+    let mut child = cmd
+        .stdin(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+    let mut stdin = child.stdin.take().unwrap();
+    stdin
+        .write_all(b"test-phrase\nbackup-phrase\n")
+        .await
+        .unwrap();
+    drop(stdin);
+    assert!(child.wait().await.unwrap().success());
+}
+
+async fn get_launch_infos(process: &mut Child, num_conductors: usize) -> Vec<LaunchInfo> {
+    let stdout = process.stdout.take().unwrap();
+    let mut lines = BufReader::new(stdout).lines();
+    let mut launch_infos = Vec::new();
+    while let Ok(Some(line)) = lines.next_line().await {
+        println!("@@@@@-{line}-@@@@@");
+        if let Some(index) = line.find("Conductor launched #!") {
+            let launch_info_str = line[index..].trim().rsplit_once(' ').unwrap().1;
+            launch_infos.push(serde_json::from_str::<LaunchInfo>(launch_info_str).unwrap());
+            if launch_infos.len() == num_conductors {
+                // Keep reading the output so that the sandbox never blocks on writing it.
+                tokio::spawn(async move { while let Ok(Some(_)) = lines.next_line().await {} });
+                return launch_infos;
+            }
+        }
+    }
+    panic!("Unable to find launch info in sandbox output. See stderr above.")
+}
+
+/// The one app installed on a conductor.
+async fn list_only_app(admin_tx: &WebsocketSender) -> AppInfo {
+    let response: AdminResponse = check_timeout(admin_tx.request(AdminRequest::ListApps {
+        status_filter: None,
+        dna_hash: None,
+        page: None,
+    }))
+    .await;
+    match response {
+        AdminResponse::AppsListed(mut apps) => {
+            assert_eq!(apps.len(), 1);
+            apps.remove(0)
+        }
+        _ => panic!("Unexpected response {:?}", response),
+    }
+}
+
+/// The action hashes of the source chain of a cell, in order.
+async fn chain_action_hashes(admin_tx: &WebsocketSender, cell_id: &CellId) -> Vec<ActionHash> {
+    let response: AdminResponse = check_timeout(admin_tx.request(AdminRequest::DumpFullState {
+        cell_id: Box::new(cell_id.clone()),
+        dht_ops_cursor: Some(u64::MAX),
+    }))
+    .await;
+    match response {
+        AdminResponse::FullStateDumped(dump) => dump
+            .source_chain_dump
+            .records
+            .into_iter()
+            .map(|record| record.action_address)
+            .collect(),
+        _ => panic!("Unexpected response {:?}", response),
+    }
+}
+
 struct WsPoll(tokio::task::JoinHandle<()>);
 impl Drop for WsPoll {
     fn drop(&mut self) {
@@ -15,12 +15,25 @@ use holo_hash::AgentPubKey;
 pub fn must_get_agent_activity(
     author: AgentPubKey,
     filter: ChainFilter,
+) -> ExternResult<Vec<RegisterAgentActivity>> {
+    must_get_agent_activity_with_options(author, filter, MustGetOptions::default())
+}
+
+/// Same as [`must_get_agent_activity`] but with control over how long to wait
+/// for the network.
+///
+/// See [`MustGetOptions`] for how the timeout and retries are applied.
+pub fn must_get_agent_activity_with_options(
+    author: AgentPubKey,
+    filter: ChainFilter,
+    options: MustGetOptions,
 ) -> ExternResult<Vec<RegisterAgentActivity>> {
     HDI.with(|h| {
         h.borrow()
             .must_get_agent_activity(MustGetAgentActivityInput {
                 author,
                 chain_filter: filter,
+                options,
             })
     })
 }
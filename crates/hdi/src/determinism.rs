@@ -0,0 +1,30 @@
+//! Deterministic sources of randomness and time for validation.
+//!
+//! Every validator must reach the same result for the same op, so validation
+//! callbacks cannot use `random_bytes` or `sys_time`. The functions here are
+//! derived from the op being validated and return the same value on every node.
+
+use crate::prelude::*;
+
+/// Get pseudo-random bytes seeded by the op being validated.
+///
+/// Every validator of the op sees the same bytes. Repeated calls during the
+/// same validation continue the same stream rather than starting over.
+///
+/// The bytes are predictable to anyone who knows the op, so they must never be
+/// used for cryptographic purposes.
+///
+/// Only available in validation callbacks.
+pub fn deterministic_random_bytes(number_of_bytes: u32) -> ExternResult<Bytes> {
+    HDI.with(|h| h.borrow().deterministic_random_bytes(number_of_bytes))
+}
+
+/// Get the timestamp of the action being validated.
+///
+/// Use this instead of the current system time when a validation rule depends
+/// on time, so that validators agree regardless of when they run.
+///
+/// Only available in validation callbacks.
+pub fn validation_timestamp() -> ExternResult<Timestamp> {
+    HDI.with(|h| h.borrow().validation_timestamp(()))
+}
@@ -36,6 +36,19 @@ pub fn must_get_entry(entry_hash: EntryHash) -> ExternResult<EntryHashed> {
     })
 }
 
+/// Same as [`must_get_entry`] but with control over how long to wait for the network.
+///
+/// See [`MustGetOptions`] for how the timeout and retries are applied.
+pub fn must_get_entry_with_options(
+    entry_hash: EntryHash,
+    options: MustGetOptions,
+) -> ExternResult<EntryHashed> {
+    HDI.with(|h| {
+        h.borrow()
+            .must_get_entry_with_options(MustGetEntryWithOptionsInput::new(entry_hash, options))
+    })
+}
+
 /// MUST get a SignedActionHashed at a given ActionHash.
 ///
 /// The SignedActionHashed is NOT guaranteed to be a valid (or even validated) Record.
@@ -63,6 +76,19 @@ pub fn must_get_action(action_hash: ActionHash) -> ExternResult<SignedActionHash
     })
 }
 
+/// Same as [`must_get_action`] but with control over how long to wait for the network.
+///
+/// See [`MustGetOptions`] for how the timeout and retries are applied.
+pub fn must_get_action_with_options(
+    action_hash: ActionHash,
+    options: MustGetOptions,
+) -> ExternResult<SignedActionHashed> {
+    HDI.with(|h| {
+        h.borrow()
+            .must_get_action_with_options(MustGetActionWithOptionsInput::new(action_hash, options))
+    })
+}
+
 /// MUST get a VALID Record at a given ActionHash.
 ///
 /// The Record is guaranteed to be valid.
@@ -109,6 +135,22 @@ pub fn must_get_valid_record(action_hash: ActionHash) -> ExternResult<Record> {
     })
 }
 
+/// Same as [`must_get_valid_record`] but with control over how long to wait for the network.
+///
+/// See [`MustGetOptions`] for how the timeout and retries are applied.
+pub fn must_get_valid_record_with_options(
+    action_hash: ActionHash,
+    options: MustGetOptions,
+) -> ExternResult<Record> {
+    HDI.with(|h| {
+        h.borrow()
+            .must_get_valid_record_with_options(MustGetValidRecordWithOptionsInput::new(
+                action_hash,
+                options,
+            ))
+    })
+}
+
 /// Implements conversion traits to allow a struct to be handled as an app entry.
 /// If you have some need to implement custom serialization logic or metadata injection
 /// you can do so by implementing these traits manually instead.
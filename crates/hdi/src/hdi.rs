@@ -32,6 +32,18 @@ pub trait HdiT: Send + Sync {
         &self,
         must_get_valid_record_input: MustGetValidRecordInput,
     ) -> ExternResult<Record>;
+    fn must_get_entry_with_options(
+        &self,
+        must_get_entry_input: MustGetEntryWithOptionsInput,
+    ) -> ExternResult<EntryHashed>;
+    fn must_get_action_with_options(
+        &self,
+        must_get_action_input: MustGetActionWithOptionsInput,
+    ) -> ExternResult<SignedActionHashed>;
+    fn must_get_valid_record_with_options(
+        &self,
+        must_get_valid_record_input: MustGetValidRecordWithOptionsInput,
+    ) -> ExternResult<Record>;
     fn must_get_agent_activity(
         &self,
         must_get_agent_activity_input: MustGetAgentActivityInput,
@@ -42,6 +54,9 @@ pub trait HdiT: Send + Sync {
     // Info
     fn dna_info(&self, dna_info_input: ()) -> ExternResult<DnaInfo>;
     fn zome_info(&self, zome_info_input: ()) -> ExternResult<ZomeInfo>;
+    // Validation
+    fn deterministic_random_bytes(&self, number_of_bytes: u32) -> ExternResult<Bytes>;
+    fn validation_timestamp(&self, validation_timestamp_input: ()) -> ExternResult<Timestamp>;
     // Trace
     fn trace(&self, trace_msg: TraceMsg) -> ExternResult<()>;
     // XSalsa20Poly1305
@@ -88,6 +103,24 @@ impl HdiT for ErrHdi {
     fn must_get_valid_record(&self, _: MustGetValidRecordInput) -> ExternResult<Record> {
         Self::err("must_get_valid_record")
     }
+    fn must_get_entry_with_options(
+        &self,
+        _: MustGetEntryWithOptionsInput,
+    ) -> ExternResult<EntryHashed> {
+        Self::err("must_get_entry_with_options")
+    }
+    fn must_get_action_with_options(
+        &self,
+        _: MustGetActionWithOptionsInput,
+    ) -> ExternResult<SignedActionHashed> {
+        Self::err("must_get_action_with_options")
+    }
+    fn must_get_valid_record_with_options(
+        &self,
+        _: MustGetValidRecordWithOptionsInput,
+    ) -> ExternResult<Record> {
+        Self::err("must_get_valid_record_with_options")
+    }
     fn must_get_agent_activity(
         &self,
         _: MustGetAgentActivityInput,
@@ -104,6 +137,12 @@ impl HdiT for ErrHdi {
     fn zome_info(&self, _: ()) -> ExternResult<ZomeInfo> {
         Self::err("zome_info")
     }
+    fn deterministic_random_bytes(&self, _: u32) -> ExternResult<Bytes> {
+        Self::err("deterministic_random_bytes")
+    }
+    fn validation_timestamp(&self, _: ()) -> ExternResult<Timestamp> {
+        Self::err("validation_timestamp")
+    }
     // Trace
     fn trace(&self, _: TraceMsg) -> ExternResult<()> {
         Self::err("trace")
@@ -176,6 +215,33 @@ impl HdiT for HostHdi {
             must_get_valid_record_input,
         )
     }
+    fn must_get_entry_with_options(
+        &self,
+        must_get_entry_input: MustGetEntryWithOptionsInput,
+    ) -> ExternResult<EntryHashed> {
+        host_call::<MustGetEntryWithOptionsInput, EntryHashed>(
+            __hc__must_get_entry_with_options_1,
+            must_get_entry_input,
+        )
+    }
+    fn must_get_action_with_options(
+        &self,
+        must_get_action_input: MustGetActionWithOptionsInput,
+    ) -> ExternResult<SignedActionHashed> {
+        host_call::<MustGetActionWithOptionsInput, SignedActionHashed>(
+            __hc__must_get_action_with_options_1,
+            must_get_action_input,
+        )
+    }
+    fn must_get_valid_record_with_options(
+        &self,
+        must_get_valid_record_input: MustGetValidRecordWithOptionsInput,
+    ) -> ExternResult<Record> {
+        host_call::<MustGetValidRecordWithOptionsInput, Record>(
+            __hc__must_get_valid_record_with_options_1,
+            must_get_valid_record_input,
+        )
+    }
     fn must_get_agent_activity(
         &self,
         must_get_agent_activity_input: MustGetAgentActivityInput,
@@ -191,6 +257,12 @@ impl HdiT for HostHdi {
     fn zome_info(&self, _: ()) -> ExternResult<ZomeInfo> {
         host_call::<(), ZomeInfo>(__hc__zome_info_1, ())
     }
+    fn deterministic_random_bytes(&self, number_of_bytes: u32) -> ExternResult<Bytes> {
+        host_call::<u32, Bytes>(__hc__deterministic_random_bytes_1, number_of_bytes)
+    }
+    fn validation_timestamp(&self, _: ()) -> ExternResult<Timestamp> {
+        host_call::<(), Timestamp>(__hc__validation_timestamp_1, ())
+    }
     #[cfg(feature = "unstable-functions")]
     fn is_same_agent(&self, key_1: AgentPubKey, key_2: AgentPubKey) -> ExternResult<bool> {
         return host_call::<(AgentPubKey, AgentPubKey), bool>(
@@ -309,6 +309,13 @@ pub mod ed25519;
 /// - The function call itself
 pub mod info;
 
+/// Deterministic randomness and time for validation callbacks.
+///
+/// Validation must reach the same result on every node, so the host refuses
+/// `random_bytes` and `sys_time` while validating.
+/// The functions in this module are the deterministic replacements.
+pub mod determinism;
+
 #[cfg(feature = "trace")]
 /// Integrates HDI with the Rust tracing crate.
 ///
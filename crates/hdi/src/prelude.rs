@@ -2,11 +2,17 @@
 pub use crate::agent::is_same_agent;
 pub use crate::app_entry;
 pub use crate::chain::must_get_agent_activity;
+pub use crate::chain::must_get_agent_activity_with_options;
+pub use crate::determinism::deterministic_random_bytes;
+pub use crate::determinism::validation_timestamp;
 pub use crate::ed25519::verify_signature;
 pub use crate::ed25519::verify_signature_raw;
 pub use crate::entry::must_get_action;
+pub use crate::entry::must_get_action_with_options;
 pub use crate::entry::must_get_entry;
+pub use crate::entry::must_get_entry_with_options;
 pub use crate::entry::must_get_valid_record;
+pub use crate::entry::must_get_valid_record_with_options;
 pub use crate::entry_types;
 pub use crate::flat_op::*;
 pub use crate::hash::*;
@@ -88,6 +94,8 @@ macro_rules! holochain_externs {
             zome_info:1,
             dna_info:1,
             dna_info:2,
+            deterministic_random_bytes:1,
+            validation_timestamp:1,
             must_get_entry:1,
             must_get_valid_record:1,
             must_get_action:1,
@@ -35,6 +35,27 @@ pub fn set_zome_types(entries: &[(u8, u8)], links: &[(u8, u8)]) {
             todo!()
         }
 
+        fn must_get_entry_with_options(
+            &self,
+            must_get_entry_input: MustGetEntryWithOptionsInput,
+        ) -> ExternResult<EntryHashed> {
+            todo!()
+        }
+
+        fn must_get_action_with_options(
+            &self,
+            must_get_action_input: MustGetActionWithOptionsInput,
+        ) -> ExternResult<SignedActionHashed> {
+            todo!()
+        }
+
+        fn must_get_valid_record_with_options(
+            &self,
+            must_get_valid_record_input: MustGetValidRecordWithOptionsInput,
+        ) -> ExternResult<Record> {
+            todo!()
+        }
+
         #[cfg(feature = "unstable-functions")]
         fn is_same_agent(&self, key_1: AgentPubKey, key_2: AgentPubKey) -> ExternResult<bool> {
             todo!()
@@ -44,6 +65,14 @@ pub fn set_zome_types(entries: &[(u8, u8)], links: &[(u8, u8)]) {
             todo!()
         }
 
+        fn deterministic_random_bytes(&self, number_of_bytes: u32) -> ExternResult<Bytes> {
+            todo!()
+        }
+
+        fn validation_timestamp(&self, validation_timestamp_input: ()) -> ExternResult<Timestamp> {
+            todo!()
+        }
+
         fn zome_info(&self, zome_info_input: ()) -> ExternResult<ZomeInfo> {
             let info = ZomeInfo {
                 name: String::default().into(),
@@ -213,6 +213,8 @@ mod entry_defs_overrides_mod {
         B(A),
         #[entry_type(required_validations = 10, cache_at_agent_activity = true)]
         C(A),
+        #[entry_type(ttl_seconds = 60)]
+        D(A),
     }
 }
 
@@ -238,6 +240,12 @@ fn entry_defs_overrides() {
                 visibility: Default::default(),
                 required_validations: RequiredValidations(10),
                 cache_at_agent_activity: true,
+                ttl: None,
+            },
+            EntryDef {
+                id: "d".into(),
+                ttl: Some(std::time::Duration::from_secs(60)),
+                ..Default::default()
             },
         ]))
     );
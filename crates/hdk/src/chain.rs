@@ -7,6 +7,10 @@ pub use hdi::chain::*;
 /// The agent activity is held by the neighbourhood of the agent's public key, rather than a content hash like the rest of the DHT.
 ///
 /// The agent activity can be filtered with [ `ChainQueryFilter` ] like a local chain query.
+///
+/// The valid warrants against the agent which the authorities of its activity hold are returned
+/// in [ `AgentActivity::warrants` ], for both kinds of [ `ActivityRequest` ], so that an app can
+/// decide how to treat an agent which has been caught misbehaving, e.g. by refusing to interact with it.
 pub fn get_agent_activity(
     agent: AgentPubKey,
     query: ChainQueryFilter,
@@ -18,6 +22,24 @@ pub fn get_agent_activity(
     })
 }
 
+/// Query one page of the _actions_ of a remote agent's chain.
+///
+/// This is [ `get_agent_activity` ] with [ `ActivityRequest::Full` ], except that only the actions
+/// within the [ `AgentActivityPage` ] are returned.
+/// To follow a chain without fetching all of it every time, set `after_seq` on the page to the
+/// highest sequence number that has already been seen.
+pub fn get_agent_activity_page(
+    agent: AgentPubKey,
+    query: ChainQueryFilter,
+    page: AgentActivityPage,
+) -> ExternResult<AgentActivity> {
+    HDK.with(|h| {
+        h.borrow().get_agent_activity(
+            GetAgentActivityInput::new(agent, query, ActivityRequest::Full).with_page(page),
+        )
+    })
+}
+
 /// Walks the source chain in ascending order (oldest to latest) filtering by action and/or entry type
 ///
 /// Given an action and entry type, returns an [ `Vec<Record>` ]
@@ -31,3 +53,14 @@ pub fn get_agent_activity(
 pub fn query(filter: ChainQueryFilter) -> ExternResult<Vec<Record>> {
     HDK.with(|h| h.borrow().query(filter))
 }
+
+/// Query the source chain of another cell of the same agent on this conductor.
+///
+/// This is a read-only alternative to a bridge [ `call` ] for simple lookups
+/// across cells, e.g. of another app the agent has installed. The target cell
+/// must be running and belong to the calling agent.
+///
+/// [ `call` ]: crate::p2p::call
+pub fn query_cell(target: CallTargetCell, filter: ChainQueryFilter) -> ExternResult<Vec<Record>> {
+    HDK.with(|h| h.borrow().query_cell(QueryCellInput::new(target, filter)))
+}
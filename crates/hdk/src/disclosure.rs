@@ -0,0 +1,42 @@
+use crate::prelude::*;
+
+/// Create a proof that the calling agent authored the private entry with this hash.
+///
+/// The proof contains the signed action which created the entry and a signature over the
+/// action hash and the `challenge`, but not the entry itself. It is meant to be returned to an
+/// agent which asked for it, for example from a function which that agent calls with
+/// `call_remote`, so the `challenge` should come from them to show that the proof is fresh.
+///
+/// ```ignore
+/// #[hdk_extern]
+/// fn prove_membership((entry_hash, challenge): (EntryHash, Bytes)) -> ExternResult<PrivateEntryProof> {
+///     create_private_entry_proof(entry_hash, challenge)
+/// }
+/// ```
+///
+/// Fails if there is no private entry with this hash on the agent's source chain.
+pub fn create_private_entry_proof<C>(
+    entry_hash: EntryHash,
+    challenge: C,
+) -> ExternResult<PrivateEntryProof>
+where
+    C: Into<Bytes>,
+{
+    HDK.with(|h| {
+        h.borrow()
+            .create_private_entry_proof(PrivateEntryProofInput {
+                entry_hash,
+                challenge: challenge.into(),
+            })
+    })
+}
+
+/// Check that a [`PrivateEntryProof`] was signed by the author of its action, and that the
+/// action created a private entry.
+///
+/// This only checks the signatures. The caller should also check that the proof answers the
+/// challenge they sent, and that the author, entry hash and entry type are the ones they
+/// asked about.
+pub fn verify_private_entry_proof(proof: PrivateEntryProof) -> ExternResult<bool> {
+    HDK.with(|h| h.borrow().verify_private_entry_proof(proof))
+}
@@ -15,6 +15,105 @@ pub fn create(create_input: CreateInput) -> ExternResult<ActionHash> {
     HDK.with(|h| h.borrow().create(create_input))
 }
 
+/// Create many entries in a single host call.
+///
+/// Behaves like calling [`create`] once per input, in order, and returns the action hashes in the
+/// same order. All the actions are signed together, so bulk imports avoid paying a keystore
+/// round-trip per record.
+///
+/// Countersigned entries can not be created in a batch.
+pub fn create_entries(create_inputs: Vec<CreateInput>) -> ExternResult<Vec<ActionHash>> {
+    HDK.with(|h| h.borrow().create_entries(create_inputs))
+}
+
+/// Create a blob which may be too large for one entry.
+///
+/// The blob is split into [`Chunk`] entries of the chunk type given in the input, which are
+/// created along with a [`ChunkManifest`] entry of the manifest type that lists their hashes.
+/// They're all committed together, and the hash of the action which created the manifest is
+/// returned, to read the blob back with [`get_chunked`].
+///
+/// The integrity zome sees the chunks and the manifest as the serialized [`Chunk`] and
+/// [`ChunkManifest`], so it can validate them as it would any other entry.
+pub fn create_chunked(create_chunked_input: CreateChunkedInput) -> ExternResult<ActionHash> {
+    HDK.with(|h| h.borrow().create_chunked(create_chunked_input))
+}
+
+/// Read a blob which was created with [`create_chunked`], or a range of its bytes.
+///
+/// `None` if the manifest can't be found. The chunks which hold the range are fetched in
+/// parallel, and the call fails if any of them can't be found or isn't the chunk which the
+/// manifest lists, so the bytes which are returned are always those which were created.
+///
+/// See [`ChunkedReader`] to read a large blob piece by piece.
+pub fn get_chunked(get_chunked_input: GetChunkedInput) -> ExternResult<Option<Bytes>> {
+    HDK.with(|h| h.borrow().get_chunked(get_chunked_input))
+}
+
+/// Reads a blob which was created with [`create_chunked`] in pieces of a given size, so that
+/// a zome never holds more of a large blob than it's working on.
+///
+/// Each piece is read with [`get_chunked`], and the reader stops after the last piece.
+pub struct ChunkedReader {
+    manifest_hash: AnyDhtHash,
+    piece_size: u64,
+    get_options: GetOptions,
+    position: u64,
+    done: bool,
+}
+
+impl ChunkedReader {
+    /// Read the blob whose manifest is at `manifest_hash` in pieces of `piece_size` bytes.
+    pub fn new(
+        manifest_hash: impl Into<AnyDhtHash>,
+        piece_size: u64,
+        get_options: GetOptions,
+    ) -> Self {
+        Self {
+            manifest_hash: manifest_hash.into(),
+            piece_size: piece_size.max(1),
+            get_options,
+            position: 0,
+            done: false,
+        }
+    }
+}
+
+impl Iterator for ChunkedReader {
+    type Item = ExternResult<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let mut input = GetChunkedInput::new(self.manifest_hash.clone(), self.get_options.clone());
+        let end = self.position.saturating_add(self.piece_size);
+        input.range = Some(self.position..end);
+        let piece = match get_chunked(input) {
+            Ok(Some(piece)) => piece.into_vec(),
+            Ok(None) => {
+                self.done = true;
+                return Some(Err(wasm_error!(WasmErrorInner::Guest(format!(
+                    "The manifest {} could not be found",
+                    self.manifest_hash
+                )))));
+            }
+            Err(e) => {
+                self.done = true;
+                return Some(Err(e));
+            }
+        };
+        // A short piece is the end of the blob, as is an empty one.
+        self.done = (piece.len() as u64) < self.piece_size;
+        self.position = end;
+        if piece.is_empty() {
+            None
+        } else {
+            Some(Ok(piece))
+        }
+    }
+}
+
 /// General function that can update any entry type.
 ///
 /// This is used under the hood by [`update_entry`], [`update_cap_grant`] and `update_cap_claim`.
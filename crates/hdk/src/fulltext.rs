@@ -0,0 +1,19 @@
+use crate::prelude::*;
+
+/// Query the text of the entries which this cell holds in its local DHT database.
+///
+/// Only the entries of the `fulltext_entry_types` of the DNA manifest are indexed, and
+/// only the ones which the conductor holds, because the cell is an authority for them
+/// or authored them. The network is never asked, so different agents can get different
+/// results for the same query.
+///
+/// The query uses the [FTS5 query syntax](https://www.sqlite.org/fts5.html#full_text_query_syntax)
+/// and the hashes of the best matching entries are returned first. The entries can be
+/// read with [`get`] and [`GetOptions::local`].
+///
+/// ```ignore
+/// let hashes = query_local_fulltext(FullTextQuery::new("peer AND network*").with_limit(10))?;
+/// ```
+pub fn query_local_fulltext(query: FullTextQuery) -> ExternResult<Vec<EntryHash>> {
+    HDK.with(|h| h.borrow().query_local_fulltext(query))
+}
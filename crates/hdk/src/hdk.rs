@@ -27,14 +27,26 @@ pub trait HdkT: HdiT {
         get_agent_activity_input: GetAgentActivityInput,
     ) -> ExternResult<AgentActivity>;
     fn query(&self, filter: ChainQueryFilter) -> ExternResult<Vec<Record>>;
+    fn query_cell(&self, input: QueryCellInput) -> ExternResult<Vec<Record>>;
+    // Full-text
+    fn query_local_fulltext(&self, query: FullTextQuery) -> ExternResult<Vec<EntryHash>>;
     // Ed25519
     fn sign(&self, sign: Sign) -> ExternResult<Signature>;
     fn sign_ephemeral(&self, sign_ephemeral: SignEphemeral) -> ExternResult<EphemeralSignatures>;
+    // Disclosure
+    fn create_private_entry_proof(
+        &self,
+        input: PrivateEntryProofInput,
+    ) -> ExternResult<PrivateEntryProof>;
+    fn verify_private_entry_proof(&self, proof: PrivateEntryProof) -> ExternResult<bool>;
     // Entry
     fn create(&self, create_input: CreateInput) -> ExternResult<ActionHash>;
+    fn create_entries(&self, create_inputs: Vec<CreateInput>) -> ExternResult<Vec<ActionHash>>;
+    fn create_chunked(&self, create_chunked_input: CreateChunkedInput) -> ExternResult<ActionHash>;
     fn update(&self, update_input: UpdateInput) -> ExternResult<ActionHash>;
     fn delete(&self, delete_input: DeleteInput) -> ExternResult<ActionHash>;
     fn get(&self, get_input: Vec<GetInput>) -> ExternResult<Vec<Option<Record>>>;
+    fn get_chunked(&self, get_chunked_input: GetChunkedInput) -> ExternResult<Option<Bytes>>;
     fn get_details(&self, get_input: Vec<GetInput>) -> ExternResult<Vec<Option<Details>>>;
     // CounterSigning
     #[cfg(feature = "unstable-functions")]
@@ -50,6 +62,10 @@ pub trait HdkT: HdiT {
     fn call_info(&self, call_info_input: ()) -> ExternResult<CallInfo>;
     // Link
     fn create_link(&self, create_link_input: CreateLinkInput) -> ExternResult<ActionHash>;
+    fn create_links(
+        &self,
+        create_link_inputs: Vec<CreateLinkInput>,
+    ) -> ExternResult<Vec<ActionHash>>;
     fn delete_link(&self, delete_link_input: DeleteLinkInput) -> ExternResult<ActionHash>;
     fn get_links(&self, get_links_input: Vec<GetLinksInput>) -> ExternResult<Vec<Vec<Link>>>;
     fn get_link_details(
@@ -57,6 +73,7 @@ pub trait HdkT: HdiT {
         get_links_input: Vec<GetLinksInput>,
     ) -> ExternResult<Vec<LinkDetails>>;
     fn count_links(&self, query: LinkQuery) -> ExternResult<usize>;
+    fn get_links_summary(&self, query: LinkQuery) -> ExternResult<LinksSummary>;
     // P2P
     #[cfg(feature = "unstable-functions")]
     fn block_agent(&self, block_agent_input: BlockAgentInput) -> ExternResult<()>;
@@ -64,7 +81,19 @@ pub trait HdkT: HdiT {
     fn unblock_agent(&self, unblock_agent_input: BlockAgentInput) -> ExternResult<()>;
     fn call(&self, call: Vec<Call>) -> ExternResult<Vec<ZomeCallResponse>>;
     fn emit_signal(&self, app_signal: AppSignal) -> ExternResult<()>;
+    // Metrics
+    fn emit_metric(&self, metric: AppMetric) -> ExternResult<()>;
     fn send_remote_signal(&self, remote_signal: RemoteSignal) -> ExternResult<()>;
+    fn send_remote_signal_acked(
+        &self,
+        remote_signal: AckedRemoteSignal,
+    ) -> ExternResult<Vec<RemoteSignalDelivery>>;
+    fn queue_remote_message(&self, message: QueuedRemoteMessage) -> ExternResult<OutboxMessageId>;
+    fn outbox_delivery(
+        &self,
+        ids: Vec<OutboxMessageId>,
+    ) -> ExternResult<Vec<Option<OutboxDelivery>>>;
+    fn get_agent_presence(&self, agent: AgentPubKey) -> ExternResult<Option<AgentPresence>>;
     // Random
     fn random_bytes(&self, number_of_bytes: u32) -> ExternResult<Bytes>;
     // Time
@@ -123,14 +152,25 @@ mockall::mock! {
             get_agent_activity_input: GetAgentActivityInput,
         ) -> ExternResult<AgentActivity>;
         fn query(&self, filter: ChainQueryFilter) -> ExternResult<Vec<Record>>;
+        fn query_cell(&self, input: QueryCellInput) -> ExternResult<Vec<Record>>;
+        fn query_local_fulltext(&self, query: FullTextQuery) -> ExternResult<Vec<EntryHash>>;
         // Ed25519
         fn sign(&self, sign: Sign) -> ExternResult<Signature>;
         fn sign_ephemeral(&self, sign_ephemeral: SignEphemeral) -> ExternResult<EphemeralSignatures>;
+        // Disclosure
+        fn create_private_entry_proof(
+            &self,
+            input: PrivateEntryProofInput,
+        ) -> ExternResult<PrivateEntryProof>;
+        fn verify_private_entry_proof(&self, proof: PrivateEntryProof) -> ExternResult<bool>;
         // Entry
         fn create(&self, create_input: CreateInput) -> ExternResult<ActionHash>;
+        fn create_entries(&self, create_inputs: Vec<CreateInput>) -> ExternResult<Vec<ActionHash>>;
+        fn create_chunked(&self, create_chunked_input: CreateChunkedInput) -> ExternResult<ActionHash>;
         fn update(&self, update_input: UpdateInput) -> ExternResult<ActionHash>;
         fn delete(&self, delete_input: DeleteInput) -> ExternResult<ActionHash>;
         fn get(&self, get_input: Vec<GetInput>) -> ExternResult<Vec<Option<Record>>>;
+        fn get_chunked(&self, get_chunked_input: GetChunkedInput) -> ExternResult<Option<Bytes>>;
         fn get_details(&self, get_input: Vec<GetInput>) -> ExternResult<Vec<Option<Details>>>;
         // CounterSigning
         fn accept_countersigning_preflight_request(
@@ -142,6 +182,10 @@ mockall::mock! {
         fn call_info(&self, call_info_input: ()) -> ExternResult<CallInfo>;
         // Link
         fn create_link(&self, create_link_input: CreateLinkInput) -> ExternResult<ActionHash>;
+        fn create_links(
+            &self,
+            create_link_inputs: Vec<CreateLinkInput>,
+        ) -> ExternResult<Vec<ActionHash>>;
         fn delete_link(&self, delete_link_input: DeleteLinkInput) -> ExternResult<ActionHash>;
         fn get_links(&self, get_links_input: Vec<GetLinksInput>) -> ExternResult<Vec<Vec<Link>>>;
         fn get_link_details(
@@ -149,12 +193,27 @@ mockall::mock! {
             get_links_input: Vec<GetLinksInput>,
         ) -> ExternResult<Vec<LinkDetails>>;
         fn count_links(&self, query: LinkQuery) -> ExternResult<usize>;
+        fn get_links_summary(&self, query: LinkQuery) -> ExternResult<LinksSummary>;
         // P2P
         fn block_agent(&self, block_agent_input: BlockAgentInput) -> ExternResult<()>;
         fn unblock_agent(&self, unblock_agent_input: BlockAgentInput) -> ExternResult<()>;
         fn call(&self, call: Vec<Call>) -> ExternResult<Vec<ZomeCallResponse>>;
         fn emit_signal(&self, app_signal: AppSignal) -> ExternResult<()>;
+        fn emit_metric(&self, metric: AppMetric) -> ExternResult<()>;
         fn send_remote_signal(&self, remote_signal: RemoteSignal) -> ExternResult<()>;
+        fn send_remote_signal_acked(
+            &self,
+            remote_signal: AckedRemoteSignal,
+        ) -> ExternResult<Vec<RemoteSignalDelivery>>;
+        fn queue_remote_message(
+            &self,
+            message: QueuedRemoteMessage,
+        ) -> ExternResult<OutboxMessageId>;
+        fn outbox_delivery(
+            &self,
+            ids: Vec<OutboxMessageId>,
+        ) -> ExternResult<Vec<Option<OutboxDelivery>>>;
+        fn get_agent_presence(&self, agent: AgentPubKey) -> ExternResult<Option<AgentPresence>>;
         // Random
         fn random_bytes(&self, number_of_bytes: u32) -> ExternResult<Bytes>;
         // Time
@@ -208,6 +267,18 @@ mockall::mock! {
             &self,
             must_get_valid_record_input: MustGetValidRecordInput,
         ) -> ExternResult<Record>;
+        fn must_get_entry_with_options(
+            &self,
+            must_get_entry_input: MustGetEntryWithOptionsInput,
+        ) -> ExternResult<EntryHashed>;
+        fn must_get_action_with_options(
+            &self,
+            must_get_action_input: MustGetActionWithOptionsInput,
+        ) -> ExternResult<SignedActionHashed>;
+        fn must_get_valid_record_with_options(
+            &self,
+            must_get_valid_record_input: MustGetValidRecordWithOptionsInput,
+        ) -> ExternResult<Record>;
         fn must_get_agent_activity(
             &self,
             must_get_agent_activity_input: MustGetAgentActivityInput,
@@ -215,6 +286,9 @@ mockall::mock! {
         // Info
         fn dna_info(&self, dna_info_input: ()) -> ExternResult<DnaInfo>;
         fn zome_info(&self, zome_info_input: ()) -> ExternResult<ZomeInfo>;
+        // Validation
+        fn deterministic_random_bytes(&self, number_of_bytes: u32) -> ExternResult<Bytes>;
+        fn validation_timestamp(&self, validation_timestamp_input: ()) -> ExternResult<Timestamp>;
         // Trace
         fn trace(&self, trace_msg: TraceMsg) -> ExternResult<()>;
         // XSalsa20Poly1305
@@ -278,6 +352,27 @@ impl HdiT for ErrHdk {
         Self::err()
     }
 
+    fn must_get_entry_with_options(
+        &self,
+        _must_get_entry_input: MustGetEntryWithOptionsInput,
+    ) -> ExternResult<EntryHashed> {
+        Self::err()
+    }
+
+    fn must_get_action_with_options(
+        &self,
+        _must_get_action_input: MustGetActionWithOptionsInput,
+    ) -> ExternResult<SignedActionHashed> {
+        Self::err()
+    }
+
+    fn must_get_valid_record_with_options(
+        &self,
+        _must_get_valid_record_input: MustGetValidRecordWithOptionsInput,
+    ) -> ExternResult<Record> {
+        Self::err()
+    }
+
     fn must_get_agent_activity(
         &self,
         _: MustGetAgentActivityInput,
@@ -290,6 +385,14 @@ impl HdiT for ErrHdk {
         Self::err()
     }
 
+    fn deterministic_random_bytes(&self, _number_of_bytes: u32) -> ExternResult<Bytes> {
+        Self::err()
+    }
+
+    fn validation_timestamp(&self, _validation_timestamp_input: ()) -> ExternResult<Timestamp> {
+        Self::err()
+    }
+
     fn dna_info(&self, _dna_info_input: ()) -> ExternResult<DnaInfo> {
         Self::err()
     }
@@ -332,15 +435,36 @@ impl HdkT for ErrHdk {
     fn query(&self, _: ChainQueryFilter) -> ExternResult<Vec<Record>> {
         Self::err()
     }
+    fn query_cell(&self, _: QueryCellInput) -> ExternResult<Vec<Record>> {
+        Self::err()
+    }
+    fn query_local_fulltext(&self, _: FullTextQuery) -> ExternResult<Vec<EntryHash>> {
+        Self::err()
+    }
     fn sign(&self, _: Sign) -> ExternResult<Signature> {
         Self::err()
     }
     fn sign_ephemeral(&self, _: SignEphemeral) -> ExternResult<EphemeralSignatures> {
         Self::err()
     }
+    fn create_private_entry_proof(
+        &self,
+        _: PrivateEntryProofInput,
+    ) -> ExternResult<PrivateEntryProof> {
+        Self::err()
+    }
+    fn verify_private_entry_proof(&self, _: PrivateEntryProof) -> ExternResult<bool> {
+        Self::err()
+    }
     fn create(&self, _: CreateInput) -> ExternResult<ActionHash> {
         Self::err()
     }
+    fn create_entries(&self, _: Vec<CreateInput>) -> ExternResult<Vec<ActionHash>> {
+        Self::err()
+    }
+    fn create_chunked(&self, _: CreateChunkedInput) -> ExternResult<ActionHash> {
+        Self::err()
+    }
     fn update(&self, _: UpdateInput) -> ExternResult<ActionHash> {
         Self::err()
     }
@@ -350,6 +474,9 @@ impl HdkT for ErrHdk {
     fn get(&self, _: Vec<GetInput>) -> ExternResult<Vec<Option<Record>>> {
         Self::err()
     }
+    fn get_chunked(&self, _: GetChunkedInput) -> ExternResult<Option<Bytes>> {
+        Self::err()
+    }
     fn get_details(&self, _: Vec<GetInput>) -> ExternResult<Vec<Option<Details>>> {
         Self::err()
     }
@@ -376,6 +503,9 @@ impl HdkT for ErrHdk {
     fn create_link(&self, _: CreateLinkInput) -> ExternResult<ActionHash> {
         Self::err()
     }
+    fn create_links(&self, _: Vec<CreateLinkInput>) -> ExternResult<Vec<ActionHash>> {
+        Self::err()
+    }
     fn delete_link(&self, _: DeleteLinkInput) -> ExternResult<ActionHash> {
         Self::err()
     }
@@ -388,6 +518,9 @@ impl HdkT for ErrHdk {
     fn count_links(&self, _: LinkQuery) -> ExternResult<usize> {
         Self::err()
     }
+    fn get_links_summary(&self, _: LinkQuery) -> ExternResult<LinksSummary> {
+        Self::err()
+    }
     // P2P
     #[cfg(feature = "unstable-functions")]
     fn block_agent(&self, _: BlockAgentInput) -> ExternResult<()> {
@@ -403,9 +536,30 @@ impl HdkT for ErrHdk {
     fn emit_signal(&self, _: AppSignal) -> ExternResult<()> {
         Self::err()
     }
+    fn emit_metric(&self, _: AppMetric) -> ExternResult<()> {
+        Self::err()
+    }
     fn send_remote_signal(&self, _: RemoteSignal) -> ExternResult<()> {
         Self::err()
     }
+    fn send_remote_signal_acked(
+        &self,
+        _: AckedRemoteSignal,
+    ) -> ExternResult<Vec<RemoteSignalDelivery>> {
+        Self::err()
+    }
+    fn queue_remote_message(&self, _: QueuedRemoteMessage) -> ExternResult<OutboxMessageId> {
+        Self::err()
+    }
+    fn outbox_delivery(
+        &self,
+        _: Vec<OutboxMessageId>,
+    ) -> ExternResult<Vec<Option<OutboxDelivery>>> {
+        Self::err()
+    }
+    fn get_agent_presence(&self, _: AgentPubKey) -> ExternResult<Option<AgentPresence>> {
+        Self::err()
+    }
     // Random
     fn random_bytes(&self, _: u32) -> ExternResult<Bytes> {
         Self::err()
@@ -533,6 +687,24 @@ impl HdiT for HostHdk {
     ) -> ExternResult<Record> {
         HostHdi::new().must_get_valid_record(must_get_valid_record_input)
     }
+    fn must_get_entry_with_options(
+        &self,
+        must_get_entry_input: MustGetEntryWithOptionsInput,
+    ) -> ExternResult<EntryHashed> {
+        HostHdi::new().must_get_entry_with_options(must_get_entry_input)
+    }
+    fn must_get_action_with_options(
+        &self,
+        must_get_action_input: MustGetActionWithOptionsInput,
+    ) -> ExternResult<SignedActionHashed> {
+        HostHdi::new().must_get_action_with_options(must_get_action_input)
+    }
+    fn must_get_valid_record_with_options(
+        &self,
+        must_get_valid_record_input: MustGetValidRecordWithOptionsInput,
+    ) -> ExternResult<Record> {
+        HostHdi::new().must_get_valid_record_with_options(must_get_valid_record_input)
+    }
     fn must_get_agent_activity(
         &self,
         must_get_agent_activity_input: MustGetAgentActivityInput,
@@ -549,6 +721,12 @@ impl HdiT for HostHdk {
     fn is_same_agent(&self, key_1: AgentPubKey, key_2: AgentPubKey) -> ExternResult<bool> {
         HostHdi::new().is_same_agent(key_1, key_2)
     }
+    fn deterministic_random_bytes(&self, number_of_bytes: u32) -> ExternResult<Bytes> {
+        HostHdi::new().deterministic_random_bytes(number_of_bytes)
+    }
+    fn validation_timestamp(&self, _: ()) -> ExternResult<Timestamp> {
+        HostHdi::new().validation_timestamp(())
+    }
     fn trace(&self, m: TraceMsg) -> ExternResult<()> {
         HostHdi::new().trace(m)
     }
@@ -590,15 +768,39 @@ impl HdkT for HostHdk {
     fn query(&self, filter: ChainQueryFilter) -> ExternResult<Vec<Record>> {
         host_call::<ChainQueryFilter, Vec<Record>>(__hc__query_1, filter)
     }
+    fn query_cell(&self, input: QueryCellInput) -> ExternResult<Vec<Record>> {
+        host_call::<QueryCellInput, Vec<Record>>(__hc__query_cell_1, input)
+    }
+    fn query_local_fulltext(&self, query: FullTextQuery) -> ExternResult<Vec<EntryHash>> {
+        host_call::<FullTextQuery, Vec<EntryHash>>(__hc__query_local_fulltext_1, query)
+    }
     fn sign(&self, sign: Sign) -> ExternResult<Signature> {
         host_call::<Sign, Signature>(__hc__sign_1, sign)
     }
     fn sign_ephemeral(&self, sign_ephemeral: SignEphemeral) -> ExternResult<EphemeralSignatures> {
         host_call::<SignEphemeral, EphemeralSignatures>(__hc__sign_ephemeral_1, sign_ephemeral)
     }
+    fn create_private_entry_proof(
+        &self,
+        input: PrivateEntryProofInput,
+    ) -> ExternResult<PrivateEntryProof> {
+        host_call::<PrivateEntryProofInput, PrivateEntryProof>(
+            __hc__create_private_entry_proof_1,
+            input,
+        )
+    }
+    fn verify_private_entry_proof(&self, proof: PrivateEntryProof) -> ExternResult<bool> {
+        host_call::<PrivateEntryProof, bool>(__hc__verify_private_entry_proof_1, proof)
+    }
     fn create(&self, create_input: CreateInput) -> ExternResult<ActionHash> {
         host_call::<CreateInput, ActionHash>(__hc__create_1, create_input)
     }
+    fn create_entries(&self, create_inputs: Vec<CreateInput>) -> ExternResult<Vec<ActionHash>> {
+        host_call::<Vec<CreateInput>, Vec<ActionHash>>(__hc__create_entries_1, create_inputs)
+    }
+    fn create_chunked(&self, create_chunked_input: CreateChunkedInput) -> ExternResult<ActionHash> {
+        host_call::<CreateChunkedInput, ActionHash>(__hc__create_chunked_1, create_chunked_input)
+    }
     fn update(&self, update_input: UpdateInput) -> ExternResult<ActionHash> {
         host_call::<UpdateInput, ActionHash>(__hc__update_1, update_input)
     }
@@ -608,6 +810,9 @@ impl HdkT for HostHdk {
     fn get(&self, get_inputs: Vec<GetInput>) -> ExternResult<Vec<Option<Record>>> {
         host_call::<Vec<GetInput>, Vec<Option<Record>>>(__hc__get_1, get_inputs)
     }
+    fn get_chunked(&self, get_chunked_input: GetChunkedInput) -> ExternResult<Option<Bytes>> {
+        host_call::<GetChunkedInput, Option<Bytes>>(__hc__get_chunked_1, get_chunked_input)
+    }
     fn get_details(&self, get_inputs: Vec<GetInput>) -> ExternResult<Vec<Option<Details>>> {
         host_call::<Vec<GetInput>, Vec<Option<Details>>>(__hc__get_details_1, get_inputs)
     }
@@ -636,6 +841,12 @@ impl HdkT for HostHdk {
     fn create_link(&self, create_link_input: CreateLinkInput) -> ExternResult<ActionHash> {
         host_call::<CreateLinkInput, ActionHash>(__hc__create_link_1, create_link_input)
     }
+    fn create_links(
+        &self,
+        create_link_inputs: Vec<CreateLinkInput>,
+    ) -> ExternResult<Vec<ActionHash>> {
+        host_call::<Vec<CreateLinkInput>, Vec<ActionHash>>(__hc__create_links_1, create_link_inputs)
+    }
     fn delete_link(&self, delete_link_input: DeleteLinkInput) -> ExternResult<ActionHash> {
         host_call::<DeleteLinkInput, ActionHash>(__hc__delete_link_1, delete_link_input)
     }
@@ -651,6 +862,9 @@ impl HdkT for HostHdk {
     fn count_links(&self, query: LinkQuery) -> ExternResult<usize> {
         host_call::<LinkQuery, usize>(__hc__count_links_1, query)
     }
+    fn get_links_summary(&self, query: LinkQuery) -> ExternResult<LinksSummary> {
+        host_call::<LinkQuery, LinksSummary>(__hc__get_links_summary_1, query)
+    }
     #[cfg(feature = "unstable-functions")]
     fn block_agent(&self, block_agent_input: BlockAgentInput) -> ExternResult<()> {
         host_call::<BlockAgentInput, ()>(__hc__block_agent_1, block_agent_input)
@@ -665,9 +879,33 @@ impl HdkT for HostHdk {
     fn emit_signal(&self, app_signal: AppSignal) -> ExternResult<()> {
         host_call::<AppSignal, ()>(__hc__emit_signal_1, app_signal)
     }
+    fn emit_metric(&self, metric: AppMetric) -> ExternResult<()> {
+        host_call::<AppMetric, ()>(__hc__emit_metric_1, metric)
+    }
     fn send_remote_signal(&self, remote_signal: RemoteSignal) -> ExternResult<()> {
         host_call::<RemoteSignal, ()>(__hc__send_remote_signal_1, remote_signal)
     }
+    fn send_remote_signal_acked(
+        &self,
+        remote_signal: AckedRemoteSignal,
+    ) -> ExternResult<Vec<RemoteSignalDelivery>> {
+        host_call::<AckedRemoteSignal, Vec<RemoteSignalDelivery>>(
+            __hc__send_remote_signal_acked_1,
+            remote_signal,
+        )
+    }
+    fn queue_remote_message(&self, message: QueuedRemoteMessage) -> ExternResult<OutboxMessageId> {
+        host_call::<QueuedRemoteMessage, OutboxMessageId>(__hc__queue_remote_message_1, message)
+    }
+    fn outbox_delivery(
+        &self,
+        ids: Vec<OutboxMessageId>,
+    ) -> ExternResult<Vec<Option<OutboxDelivery>>> {
+        host_call::<Vec<OutboxMessageId>, Vec<Option<OutboxDelivery>>>(__hc__outbox_delivery_1, ids)
+    }
+    fn get_agent_presence(&self, agent: AgentPubKey) -> ExternResult<Option<AgentPresence>> {
+        host_call::<AgentPubKey, Option<AgentPresence>>(__hc__get_agent_presence_1, agent)
+    }
     fn random_bytes(&self, number_of_bytes: u32) -> ExternResult<Bytes> {
         host_call::<u32, Bytes>(__hc__random_bytes_1, number_of_bytes)
     }
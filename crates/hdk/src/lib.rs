@@ -500,6 +500,14 @@ pub mod chain;
 /// In general it is __not a good idea to reuse signing keys for encryption__ even if the curve is the same, without mathematically translating the keypair, and even then it's dubious to do so.
 pub mod ed25519;
 
+/// Prove authorship of private entries to other agents without revealing their content.
+///
+/// A private entry never leaves its author's source chain, but the action which created it is
+/// published like any other. A proof pairs that signed action with a signature over a challenge
+/// chosen by whoever asked, which makes it possible to answer a remote call, e.g. from
+/// [`call_remote`](crate::p2p::call_remote), with evidence about an entry's hash and type.
+pub mod disclosure;
+
 /// Request contextual information from the Holochain host.
 ///
 /// The Holochain host has additional runtime context that the WASM may find useful and cannot produce for itself including:
@@ -567,6 +575,12 @@ pub mod time;
 /// The host provides the random bytes because any/all WASM implementations of randomness is flawed and insecure.
 pub mod random;
 
+/// Report domain-level measurements to the conductor's metrics pipeline.
+pub mod metric;
+
+/// Search the text of the entries which a cell holds locally.
+pub mod fulltext;
+
 /// The interface between the host and guest is implemented as an `HdkT` trait.
 ///
 /// The `set_hdk` function globally sets a `RefCell` to track the current HDK implementation.
@@ -84,6 +84,15 @@ where
     })
 }
 
+/// Create many links in a single host call.
+///
+/// Behaves like calling [`create_link`] once per input, in order, and returns the action hashes in
+/// the same order. All the actions are signed together, so bulk imports avoid paying a keystore
+/// round-trip per link.
+pub fn create_links(create_link_inputs: Vec<CreateLinkInput>) -> ExternResult<Vec<ActionHash>> {
+    HDK.with(|h| h.borrow().create_links(create_link_inputs))
+}
+
 /// Delete a specific link creation record.
 ///
 /// Links are defined by a [OR-Set CRDT](https://en.wikipedia.org/wiki/Conflict-free_replicated_data_type#OR-Set_(Observed-Remove_Set))
@@ -196,3 +205,21 @@ pub fn get_link_details(
 pub fn count_links(query: LinkQuery) -> ExternResult<usize> {
     HDK.with(|h| h.borrow().count_links(query))
 }
+
+/// Get the creation times of the links matching a [`LinkQuery`], without the links themselves.
+///
+/// The query is filtered in the same way as [ `count_links` ], including by tag prefix,
+/// and the authorities for the base only send back the hash and timestamp of each link.
+/// This is useful for showing recent activity on a base, such as the number of new
+/// messages since a time, without transferring the full set of links.
+///
+/// ```ignore
+/// let summary = get_links_summary(
+///     LinkQuery::new(channel_hash, LinkTypes::ChannelToMessage.try_into_filter()?)
+///         .after(last_read),
+/// )?;
+/// let unread = summary.count();
+/// ```
+pub fn get_links_summary(query: LinkQuery) -> ExternResult<LinksSummary> {
+    HDK.with(|h| h.borrow().get_links_summary(query))
+}
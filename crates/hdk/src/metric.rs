@@ -0,0 +1,16 @@
+use crate::prelude::*;
+
+/// Record an app-defined measurement in the conductor's metrics.
+///
+/// The measurement is recorded in the `hc.app.<name>` histogram. The conductor tags it
+/// with the app, DNA, agent and zome that emitted it, so the same metric name can be
+/// compared across apps and cells.
+///
+/// ```ignore
+/// emit_metric(AppMetric::new("messages_sent", 1.0).with_tag("channel", "general"))?;
+/// ```
+///
+/// Metrics are not available during validation, as validation must not have side effects.
+pub fn emit_metric(metric: AppMetric) -> ExternResult<()> {
+    HDK.with(|h| h.borrow().emit_metric(metric))
+}
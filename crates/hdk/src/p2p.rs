@@ -95,6 +95,48 @@ where
         .unwrap())
 }
 
+/// Wrapper for __call_remote host function, for a remote agent's cell of another DNA.
+///
+/// This is a [ `call_remote` ] to a cell of any DNA, so that the apps of an ecosystem
+/// don't need to put all of their logic in one DNA. The network of the DNA is reached
+/// through the cell which the app of the calling cell has in that DNA, so the app must
+/// have a running cell in the DNA of `cell_id`. The cells of an app are all of the same
+/// agent, so the call is made by the agent of the calling cell, as with [ `call_remote` ].
+/// The remote agent must grant that agent, or a secret it holds, access to the function,
+/// and the call info of the remote call shows that agent as provenance.
+///
+/// ```ignore
+/// ...
+/// let cell_id = CellId::new(other_dna_hash, bob);
+/// let response = call_remote_cell(cell_id, "foo_zome", "do_it", secret, serializable_payload)?;
+/// ...
+/// ```
+pub fn call_remote_cell<I, Z>(
+    cell_id: CellId,
+    zome: Z,
+    fn_name: FunctionName,
+    cap_secret: Option<CapSecret>,
+    payload: I,
+) -> ExternResult<ZomeCallResponse>
+where
+    I: serde::Serialize + std::fmt::Debug,
+    Z: Into<ZomeName>,
+{
+    Ok(HDK
+        .with(|h| {
+            h.borrow().call(vec![Call::new(
+                CallTarget::NetworkCell(cell_id),
+                zome.into(),
+                fn_name,
+                cap_secret,
+                ExternIO::encode(payload).map_err(|e| wasm_error!(e))?,
+            )])
+        })?
+        .into_iter()
+        .next()
+        .unwrap())
+}
+
 /// Emit an app-defined Signal.
 ///
 /// Only clients who have subscribed to signals from this Cell with the proper
@@ -156,3 +198,132 @@ where
         })
     })
 }
+
+/// ## Acknowledged Remote Signal
+/// Send a signal to a list of other agents and wait for each of them to
+/// acknowledge it.
+///
+/// Where [ `send_remote_signal` ] silently drops signals to agents that are
+/// briefly offline, this reports a [ `RemoteSignalDelivery` ] per agent, in
+/// the same order as `agents`. Deliveries that time out or fail to reach
+/// the agent are retried up to `retries` times.
+///
+/// ### Blocking
+/// This call blocks until every agent has either acknowledged the signal
+/// or run out of attempts, so with many unreachable agents it can take up to
+/// `(retries + 1)` times the timeout to return.
+///
+/// ### Usage
+/// The same `recv_remote_signal` extern and unrestricted cap grant as for
+/// [ `send_remote_signal` ] are required on the receiving side.
+/// A recipient that is reached but refuses the call is reported as
+/// [ `RemoteSignalDeliveryStatus::Rejected` ] and is not retried.
+pub fn send_remote_signal_acked<I>(
+    input: I,
+    agents: Vec<AgentPubKey>,
+    retries: u8,
+) -> ExternResult<Vec<RemoteSignalDelivery>>
+where
+    I: serde::Serialize + std::fmt::Debug,
+{
+    HDK.with(|h| {
+        h.borrow().send_remote_signal_acked(
+            AckedRemoteSignal::new(agents, ExternIO::encode(input).map_err(|e| wasm_error!(e))?)
+                .with_retries(retries),
+        )
+    })
+}
+
+/// ## Queued Remote Call
+/// Call a zome function of another agent, even if they are offline right now.
+///
+/// Unlike [ `call_remote` ], which fails with [ `ZomeCallResponse::NetworkError` ]
+/// when the agent can't be reached, the call is queued by the conductor and retried
+/// whenever the agent comes back online, until it is delivered or `ttl` has passed.
+/// This returns as soon as the call is queued. Its delivery, including the response
+/// to the call, can be looked up with [ `outbox_delivery` ].
+///
+/// Queued messages are stored by the conductor, so they are still delivered if it restarts.
+/// The call is made at least once rather than exactly once: if an attempt times out, or
+/// the conductor restarts while waiting for the response, the call is made again even
+/// though the agent may already have run it. Only queue calls to functions which can
+/// safely be run more than once with the same payload.
+pub fn queue_remote_call<I, Z>(
+    agent: AgentPubKey,
+    zome: Z,
+    fn_name: FunctionName,
+    cap_secret: Option<CapSecret>,
+    payload: I,
+    ttl: std::time::Duration,
+) -> ExternResult<OutboxMessageId>
+where
+    I: serde::Serialize + std::fmt::Debug,
+    Z: Into<ZomeName>,
+{
+    HDK.with(|h| {
+        h.borrow().queue_remote_message(QueuedRemoteMessage {
+            agent,
+            message: RemoteMessage::Call {
+                zome_name: zome.into(),
+                fn_name,
+                cap_secret,
+                payload: ExternIO::encode(payload).map_err(|e| wasm_error!(e))?,
+            },
+            ttl_ms: ttl.as_millis() as u64,
+        })
+    })
+}
+
+/// ## Queued Remote Signal
+/// Send a signal to another agent, even if they are offline right now.
+///
+/// Unlike [ `send_remote_signal` ], which drops signals to agents who can't be reached,
+/// the signal is queued by the conductor and retried whenever the agent comes back
+/// online, until it is delivered or `ttl` has passed. The same `recv_remote_signal`
+/// extern and unrestricted cap grant are required on the receiving side.
+///
+/// Queued messages are stored by the conductor, so they are still delivered if it restarts.
+/// Like [ `queue_remote_call` ], a signal may be received more than once.
+pub fn queue_remote_signal<I>(
+    agent: AgentPubKey,
+    input: I,
+    ttl: std::time::Duration,
+) -> ExternResult<OutboxMessageId>
+where
+    I: serde::Serialize + std::fmt::Debug,
+{
+    HDK.with(|h| {
+        h.borrow().queue_remote_message(QueuedRemoteMessage {
+            agent,
+            message: RemoteMessage::Signal(ExternIO::encode(input).map_err(|e| wasm_error!(e))?),
+            ttl_ms: ttl.as_millis() as u64,
+        })
+    })
+}
+
+/// ## Outbox Delivery
+/// Look up the delivery of messages which this agent queued with
+/// [ `queue_remote_call` ] or [ `queue_remote_signal` ], in the same order as `ids`.
+///
+/// A message is `None` if it was never queued by this cell, or if it was delivered,
+/// rejected or expired so long ago that the conductor has forgotten it.
+pub fn outbox_delivery(ids: Vec<OutboxMessageId>) -> ExternResult<Vec<Option<OutboxDelivery>>> {
+    HDK.with(|h| h.borrow().outbox_delivery(ids))
+}
+
+/// ## Agent Presence
+/// Look up when an agent was last seen online.
+///
+/// While an agent is online, its conductor announces its presence to the agent's
+/// neighborhood every minute. The presence is answered from the announcements this
+/// conductor has heard, or else from the agent's neighbors. It is `None` if nobody
+/// who was asked has heard from the agent.
+///
+/// Use [ `AgentPresence::seen_within` ] to tell whether the agent is online now.
+/// ```ignore
+/// let online = get_agent_presence(agent)?
+///     .map_or(false, |presence| presence.seen_within(sys_time()?, Duration::from_secs(180)));
+/// ```
+pub fn get_agent_presence(agent: AgentPubKey) -> ExternResult<Option<AgentPresence>> {
+    HDK.with(|h| h.borrow().get_agent_presence(agent))
+}
@@ -4,10 +4,15 @@ pub use crate::capability::delete_cap_grant;
 pub use crate::capability::generate_cap_secret;
 pub use crate::capability::update_cap_grant;
 pub use crate::chain::get_agent_activity;
+pub use crate::chain::get_agent_activity_page;
 pub use crate::chain::must_get_agent_activity;
+pub use crate::chain::must_get_agent_activity_with_options;
 pub use crate::chain::query;
+pub use crate::chain::query_cell;
 pub use crate::clone::*;
 pub use crate::countersigning::session_times_from_millis;
+pub use crate::disclosure::create_private_entry_proof;
+pub use crate::disclosure::verify_private_entry_proof;
 pub use crate::ed25519::sign;
 pub use crate::ed25519::sign_ephemeral;
 pub use crate::ed25519::sign_ephemeral_raw;
@@ -15,18 +20,26 @@ pub use crate::ed25519::sign_raw;
 pub use crate::ed25519::verify_signature;
 pub use crate::ed25519::verify_signature_raw;
 pub use crate::entry::create;
+pub use crate::entry::create_chunked;
+pub use crate::entry::create_entries;
 pub use crate::entry::create_entry;
 pub use crate::entry::delete;
 pub use crate::entry::delete_entry;
 pub use crate::entry::get;
+pub use crate::entry::get_chunked;
 pub use crate::entry::get_details;
 pub use crate::entry::must_get_action;
+pub use crate::entry::must_get_action_with_options;
 pub use crate::entry::must_get_entry;
+pub use crate::entry::must_get_entry_with_options;
 pub use crate::entry::must_get_valid_record;
+pub use crate::entry::must_get_valid_record_with_options;
 pub use crate::entry::update;
 pub use crate::entry::update_entry;
+pub use crate::entry::ChunkedReader;
 pub use crate::entry_type;
 pub use crate::entry_types;
+pub use crate::fulltext::query_local_fulltext;
 pub use crate::hash::*;
 pub use crate::hash_path::anchor::anchor;
 pub use crate::hash_path::anchor::list_anchor_addresses;
@@ -41,18 +54,27 @@ pub use crate::info::dna_info;
 pub use crate::info::zome_info;
 pub use crate::link::count_links;
 pub use crate::link::create_link;
+pub use crate::link::create_links;
 pub use crate::link::delete_link;
 pub use crate::link::get_link_details;
 pub use crate::link::get_links;
+pub use crate::link::get_links_summary;
 pub use crate::link::GetLinksInputBuilder;
 pub use crate::link::LinkTypeFilterExt;
 pub use crate::map_extern;
 pub use crate::map_extern::ExternResult;
+pub use crate::metric::emit_metric;
 pub use crate::migrate::*;
 pub use crate::p2p::call;
 pub use crate::p2p::call_remote;
+pub use crate::p2p::call_remote_cell;
 pub use crate::p2p::emit_signal;
+pub use crate::p2p::get_agent_presence;
+pub use crate::p2p::outbox_delivery;
+pub use crate::p2p::queue_remote_call;
+pub use crate::p2p::queue_remote_signal;
 pub use crate::p2p::send_remote_signal;
+pub use crate::p2p::send_remote_signal_acked;
 pub use crate::random::*;
 pub use crate::time::sys_time;
 pub use crate::validation_receipt::get_validation_receipts;
@@ -70,6 +92,8 @@ pub use hdi;
 pub use hdi::map_extern_infallible;
 pub use hdi::op::OpHelper;
 pub use hdi::prelude::app_entry;
+pub use hdi::prelude::deterministic_random_bytes;
+pub use hdi::prelude::validation_timestamp;
 pub use hdi::prelude::Anchor;
 pub use hdi::prelude::Component;
 pub use hdi::prelude::Path;
@@ -130,6 +154,8 @@ macro_rules! holochain_externs {
             verify_signature:1,
             sign:1,
             sign_ephemeral:1,
+            create_private_entry_proof:1,
+            verify_private_entry_proof:1,
             zome_info:1,
             call_info:1,
             dna_info:1,
@@ -140,21 +166,33 @@ macro_rules! holochain_externs {
             capability_grants:1,
             capability_info:1,
             get:1,
+            get_chunked:1,
             get_details:1,
             get_links:1,
             get_link_details:1,
             count_links:1,
+            get_links_summary:1,
             get_agent_activity:1,
+            get_agent_presence:1,
             must_get_entry:1,
             must_get_valid_record:1,
             must_get_action:1,
             query:1,
+            query_cell:1,
+            query_local_fulltext:1,
             call_remote:1,
             call:1,
             create:1,
+            create_entries:1,
+            create_chunked:1,
+            emit_metric:1,
             emit_signal:1,
             send_remote_signal:1,
+            send_remote_signal_acked:1,
+            queue_remote_message:1,
+            outbox_delivery:1,
             create_link:1,
+            create_links:1,
             delete_link:1,
             update:1,
             delete:1,
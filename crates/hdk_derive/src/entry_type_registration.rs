@@ -19,6 +19,8 @@ struct VarOpts {
     required_validations: Option<u8>,
     #[darling(default)]
     cache_at_agent_activity: Option<bool>,
+    #[darling(default)]
+    ttl_seconds: Option<u64>,
 }
 
 #[derive(FromDeriveInput)]
@@ -45,6 +47,7 @@ pub fn derive(input: TokenStream) -> TokenStream {
                      visibility,
                      required_validations,
                      cache_at_agent_activity,
+                     ttl_seconds,
                      ..
                  }| {
                     let id = crate::util::to_snake_case(name, &v_ident);
@@ -52,12 +55,19 @@ pub fn derive(input: TokenStream) -> TokenStream {
                     let required_validations =
                         required_validations.unwrap_or_else(|| RequiredValidations::default().0);
                     let cache_at_agent_activity = cache_at_agent_activity.unwrap_or(false);
+                    let ttl = match ttl_seconds {
+                        Some(ttl_seconds) => quote::quote! {
+                            Some(::core::time::Duration::from_secs(#ttl_seconds))
+                        },
+                        None => quote::quote! { None },
+                    };
                     quote::quote! {
                         EntryDef {
                             id: EntryDefId::App(AppEntryName::from_str(#id)),
                             visibility: #visibility,
                             required_validations: RequiredValidations(#required_validations),
                             cache_at_agent_activity: #cache_at_agent_activity,
+                            ttl: #ttl,
                         },
                     }
                 },
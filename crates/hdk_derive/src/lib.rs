@@ -80,6 +80,7 @@ impl Parse for EntryDef {
             visibility,
             required_validations,
             cache_at_agent_activity: false,
+            ttl: None,
         }))
     }
 }
@@ -217,6 +218,8 @@ pub fn derive_to_unit_enum(input: TokenStream) -> TokenStream {
 ///     will not be published anymore (optional). Defaults to 5.
 ///   - visibility: The visibility of this entry. [`public` | `private`].
 ///     Default is `public`.
+///   - ttl_seconds: How long entries of this type are kept on the DHT, which makes them
+///     ephemeral (optional). Default is to keep them forever.
 ///
 /// # Examples
 /// ```ignore
@@ -228,6 +231,8 @@ pub fn derive_to_unit_enum(input: TokenStream) -> TokenStream {
 ///     Msg(Msg),
 ///     #[entry_type(name = "hidden_msg", required_validations = 5, visibility = "private")]
 ///     PrivMsg(PrivMsg),
+///     #[entry_type(ttl_seconds = 3600)]
+///     ChatMsg(ChatMsg),
 /// }
 /// ```
 #[proc_macro_error]
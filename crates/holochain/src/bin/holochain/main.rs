@@ -5,8 +5,10 @@ use holochain::conductor::ConductorHandle;
 use holochain_conductor_api::conductor::paths::DataRootPath;
 use holochain_conductor_api::conductor::process::ERROR_CODE;
 use holochain_conductor_api::conductor::ConductorConfigError;
+use holochain_conductor_api::conductor::LogFormat;
+use holochain_conductor_api::conductor::MetricsConfig;
+use holochain_conductor_api::conductor::TraceExportConfig;
 use holochain_conductor_api::config::conductor::paths::ConfigRootPath;
-use holochain_conductor_api::config::conductor::KeystoreConfig;
 use holochain_trace::Output;
 use holochain_util::tokio_helper;
 #[cfg(unix)]
@@ -83,18 +85,24 @@ async fn async_main() {
 
     let config = load_config(config_path);
 
-    if let Some(t) = &config.tracing_override {
+    let logging = config.logging.clone().unwrap_or_default();
+    if let Some(t) = config.log_filter_directives() {
         std::env::set_var("CUSTOM_FILTER", t);
     }
+    let output = match logging.format {
+        Some(LogFormat::Log) => Output::Log,
+        Some(LogFormat::Compact) => Output::Compact,
+        Some(LogFormat::Json) => Output::Json,
+        None => opt.structured.clone(),
+    };
 
-    holochain_trace::init_fmt(opt.structured.clone()).expect("Failed to start contextual logging");
+    holochain_trace::init_fmt_with_otlp(output, otlp_config(&config))
+        .expect("Failed to start contextual logging");
     debug!("holochain_trace initialized");
 
     let data_root_path: DataRootPath = config.data_root_path_or_die();
 
-    holochain_metrics::HolochainMetricsConfig::new(data_root_path.as_ref())
-        .init()
-        .await;
+    metrics_config(&config, &data_root_path).init().await;
 
     kitsune_p2p_types::metrics::init_sys_info_poll();
 
@@ -104,6 +112,9 @@ async fn async_main() {
 
     info!("Conductor successfully initialized.");
 
+    #[cfg(unix)]
+    reload_config_on_hangup(conductor.clone());
+
     // This println has special meaning. Other processes can detect it and know
     // that the conductor has been initialized, in particular that the admin
     // interfaces are running, and can be connected to.
@@ -125,17 +136,50 @@ async fn async_main() {
     handle_shutdown(shutdown_result);
 }
 
-async fn conductor_handle_from_config(opt: &Opt, config: ConductorConfig) -> ConductorHandle {
-    // read the passphrase to prepare for usage
-    let passphrase = match &config.keystore {
-        KeystoreConfig::DangerTestKeystore => None,
-        KeystoreConfig::LairServer { .. } | KeystoreConfig::LairServerInProc { .. } => {
-            if opt.piped {
-                holochain_util::pw::pw_set_piped(true);
+fn otlp_config(config: &ConductorConfig) -> Option<holochain_trace::otlp::OtlpConfig> {
+    match config.trace_export.clone()? {
+        TraceExportConfig::Otlp {
+            endpoint,
+            service_name,
+            filter,
+        } => Some(holochain_trace::otlp::OtlpConfig {
+            endpoint: endpoint.to_string(),
+            service_name: service_name.unwrap_or_else(|| "holochain".to_string()),
+            filter,
+        }),
+    }
+}
+
+fn metrics_config(
+    config: &ConductorConfig,
+    data_root_path: &DataRootPath,
+) -> holochain_metrics::HolochainMetricsConfig {
+    match &config.metrics {
+        #[cfg(feature = "metrics_prometheus")]
+        Some(MetricsConfig::Prometheus { bind_address }) => {
+            holochain_metrics::HolochainMetricsConfig::Prometheus {
+                bind_address: *bind_address,
             }
+        }
+        #[cfg(not(feature = "metrics_prometheus"))]
+        Some(MetricsConfig::Prometheus { .. }) => {
+            warn!("Prometheus metrics are configured, but this conductor was built without the `metrics_prometheus` feature");
+            holochain_metrics::HolochainMetricsConfig::new(data_root_path.as_ref())
+        }
+        None => holochain_metrics::HolochainMetricsConfig::new(data_root_path.as_ref()),
+    }
+}
 
-            Some(holochain_util::pw::pw_get().unwrap())
+async fn conductor_handle_from_config(opt: &Opt, config: ConductorConfig) -> ConductorHandle {
+    // read the passphrase to prepare for usage
+    let passphrase = if config.keystore.needs_passphrase() {
+        if opt.piped {
+            holochain_util::pw::pw_set_piped(true);
         }
+
+        Some(holochain_util::pw::pw_get().unwrap())
+    } else {
+        None
     };
 
     // Check if database is present
@@ -153,13 +197,14 @@ async fn conductor_handle_from_config(opt: &Opt, config: ConductorConfig) -> Con
     }
 
     // Initialize the Conductor
-    match Conductor::builder()
+    let mut builder = Conductor::builder()
         .config(config)
         .passphrase(passphrase)
-        .danger_print_db_secrets(opt.danger_print_db_secrets)
-        .build()
-        .await
-    {
+        .danger_print_db_secrets(opt.danger_print_db_secrets);
+    if let Some(config_path) = opt.config_path.clone() {
+        builder = builder.config_path(config_path);
+    }
+    match builder.build().await {
         Err(err) => panic!(
             "Could not initialize Conductor from configuration: {:?}",
             err
@@ -168,6 +213,36 @@ async fn conductor_handle_from_config(opt: &Opt, config: ConductorConfig) -> Con
     }
 }
 
+/// Reload the conductor config whenever the process receives `SIGHUP`.
+#[cfg(unix)]
+fn reload_config_on_hangup(conductor: ConductorHandle) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangups = match signal(SignalKind::hangup()) {
+        Ok(hangups) => hangups,
+        Err(e) => {
+            error!(
+                "Could not handle SIGHUP, the config can only be reloaded with an admin call: {:?}",
+                e
+            );
+            return;
+        }
+    };
+    tokio::task::spawn(async move {
+        while hangups.recv().await.is_some() {
+            info!("Received SIGHUP, reloading the conductor config");
+            match conductor.reload_config() {
+                Ok(report) if !report.requires_restart.is_empty() => warn!(
+                    requires_restart = ?report.requires_restart,
+                    "Some changed settings were skipped because they need a restart"
+                ),
+                Ok(_) => (),
+                Err(e) => error!("Could not reload the conductor config: {}", e),
+            }
+        }
+    });
+}
+
 /// Load config, throw friendly error on failure
 fn load_config(maybe_config_root_path: Option<ConfigRootPath>) -> ConductorConfig {
     if let Some(ref config_root_path) = maybe_config_root_path {
@@ -17,6 +17,7 @@ pub mod api;
 mod cell;
 #[cfg(feature = "chc")]
 pub mod chc;
+pub(crate) mod clock;
 #[allow(clippy::module_inception)]
 #[allow(missing_docs)]
 pub mod conductor;
@@ -33,8 +34,10 @@ pub mod p2p_agent_store;
 pub mod paths;
 #[allow(missing_docs)]
 pub mod ribosome_store;
+pub(crate) mod slow_operations;
 pub mod space;
 pub mod state;
+pub(crate) mod workflow_runtimes;
 
 pub use cell::error::CellError;
 pub use cell::Cell;
@@ -6,6 +6,7 @@ use super::error::ConductorApiError;
 use super::error::ConductorApiResult;
 use super::DpkiApi;
 use crate::conductor::conductor::ConductorServices;
+use crate::conductor::error::ConductorError;
 use crate::conductor::error::ConductorResult;
 use crate::conductor::ConductorHandle;
 use crate::core::ribosome::guest_callback::post_commit::PostCommitArgs;
@@ -196,6 +197,17 @@ pub trait CellConductorReadHandleT: Send + Sync {
         expires: Timestamp,
     ) -> ConductorApiResult<WitnessNonceResult>;
 
+    /// Call a zome function of a remote agent's cell of any DNA, through the cell which
+    /// the app of this cell has in that DNA.
+    async fn call_remote_cell(
+        &self,
+        to: CellId,
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        cap_secret: Option<CapSecret>,
+        payload: ExternIO,
+    ) -> ConductorResult<ZomeCallResponse>;
+
     /// Find the first cell ID across all apps the given cell id is in that
     /// is assigned to the given role.
     async fn find_cell_with_role_alongside_cell(
@@ -204,6 +216,13 @@ pub trait CellConductorReadHandleT: Send + Sync {
         role_name: &RoleName,
     ) -> ConductorResult<Option<CellId>>;
 
+    /// Run a read-only query against the source chain of a running cell.
+    async fn query_cell(
+        &self,
+        cell_id: &CellId,
+        filter: ChainQueryFilter,
+    ) -> ConductorResult<Vec<Record>>;
+
     /// Expose block functionality to zomes.
     async fn block(&self, input: Block) -> DatabaseResult<()>;
 
@@ -249,6 +268,16 @@ pub trait CellConductorReadHandleT: Send + Sync {
         cell_id: CellId,
         request: PreflightRequest,
     ) -> ConductorResult<PreflightRequestAcceptance>;
+
+    /// Queue a remote call or signal from a zome of this cell until it can be delivered.
+    async fn queue_remote_message(
+        &self,
+        zome_name: ZomeName,
+        message: QueuedRemoteMessage,
+    ) -> ConductorResult<OutboxMessageId>;
+
+    /// Look up the deliveries of remote messages which this cell queued.
+    fn outbox_delivery(&self, ids: Vec<OutboxMessageId>) -> Vec<Option<OutboxDelivery>>;
 }
 
 #[async_trait]
@@ -295,6 +324,19 @@ impl CellConductorReadHandleT for CellConductorApi {
             .await?)
     }
 
+    async fn call_remote_cell(
+        &self,
+        to: CellId,
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        cap_secret: Option<CapSecret>,
+        payload: ExternIO,
+    ) -> ConductorResult<ZomeCallResponse> {
+        self.conductor_handle
+            .call_remote_cell(&self.cell_id, to, zome_name, fn_name, cap_secret, payload)
+            .await
+    }
+
     async fn find_cell_with_role_alongside_cell(
         &self,
         cell_id: &CellId,
@@ -305,6 +347,16 @@ impl CellConductorReadHandleT for CellConductorApi {
             .await
     }
 
+    async fn query_cell(
+        &self,
+        cell_id: &CellId,
+        filter: ChainQueryFilter,
+    ) -> ConductorResult<Vec<Record>> {
+        self.conductor_handle
+            .query_cell_source_chain(cell_id, filter)
+            .await
+    }
+
     async fn block(&self, input: Block) -> DatabaseResult<()> {
         self.conductor_handle.block(input).await
     }
@@ -375,4 +427,165 @@ impl CellConductorReadHandleT for CellConductorApi {
             .accept_countersigning_session(cell_id, request)
             .await
     }
+
+    async fn queue_remote_message(
+        &self,
+        zome_name: ZomeName,
+        message: QueuedRemoteMessage,
+    ) -> ConductorResult<OutboxMessageId> {
+        self.conductor_handle
+            .queue_remote_message(self.cell_id.clone(), zome_name, message)
+            .await
+    }
+
+    fn outbox_delivery(&self, ids: Vec<OutboxMessageId>) -> Vec<Option<OutboxDelivery>> {
+        self.conductor_handle.outbox_deliveries(&self.cell_id, &ids)
+    }
+}
+
+/// The [`CellConductorReadHandleT`] of a dry-run zome call, which refuses whatever would
+/// reach beyond the call's own cell: calls into other cells, remote calls and messages,
+/// changes to clone cells and countersigning sessions. Blocking and unblocking agents
+/// does nothing. Calls into the other zomes of the same cell share the call's workspace,
+/// so they are made as usual.
+pub struct DryRunCellConductorApi(pub CellConductorReadHandle);
+
+#[async_trait]
+impl CellConductorReadHandleT for DryRunCellConductorApi {
+    fn cell_id(&self) -> &CellId {
+        self.0.cell_id()
+    }
+
+    async fn call_zome(
+        &self,
+        call: ZomeCall,
+        workspace_lock: SourceChainWorkspace,
+    ) -> ConductorApiResult<ZomeCallResult> {
+        if self.0.cell_id() == &call.cell_id {
+            self.0.call_zome(call, workspace_lock).await
+        } else {
+            Err(ConductorError::DryRunRefused("call into another cell").into())
+        }
+    }
+
+    fn get_zome(&self, dna_hash: &DnaHash, zome_name: &ZomeName) -> ConductorApiResult<Zome> {
+        self.0.get_zome(dna_hash, zome_name)
+    }
+
+    fn get_entry_def(&self, key: &EntryDefBufferKey) -> Option<EntryDef> {
+        self.0.get_entry_def(key)
+    }
+
+    fn get_dpki(&self) -> DpkiApi {
+        self.0.get_dpki()
+    }
+
+    async fn witness_nonce_from_calling_agent(
+        &self,
+        agent: AgentPubKey,
+        nonce: Nonce256Bits,
+        expires: Timestamp,
+    ) -> ConductorApiResult<WitnessNonceResult> {
+        self.0
+            .witness_nonce_from_calling_agent(agent, nonce, expires)
+            .await
+    }
+
+    async fn call_remote_cell(
+        &self,
+        _to: CellId,
+        _zome_name: ZomeName,
+        _fn_name: FunctionName,
+        _cap_secret: Option<CapSecret>,
+        _payload: ExternIO,
+    ) -> ConductorResult<ZomeCallResponse> {
+        Err(ConductorError::DryRunRefused("call a remote cell"))
+    }
+
+    async fn find_cell_with_role_alongside_cell(
+        &self,
+        cell_id: &CellId,
+        role_name: &RoleName,
+    ) -> ConductorResult<Option<CellId>> {
+        self.0
+            .find_cell_with_role_alongside_cell(cell_id, role_name)
+            .await
+    }
+
+    async fn query_cell(
+        &self,
+        cell_id: &CellId,
+        filter: ChainQueryFilter,
+    ) -> ConductorResult<Vec<Record>> {
+        self.0.query_cell(cell_id, filter).await
+    }
+
+    async fn block(&self, _input: Block) -> DatabaseResult<()> {
+        Ok(())
+    }
+
+    async fn unblock(&self, _input: Block) -> DatabaseResult<()> {
+        Ok(())
+    }
+
+    async fn is_blocked(&self, input: BlockTargetId, timestamp: Timestamp) -> DatabaseResult<bool> {
+        self.0.is_blocked(input, timestamp).await
+    }
+
+    async fn find_app_containing_cell(
+        &self,
+        cell_id: &CellId,
+    ) -> ConductorResult<Option<InstalledApp>> {
+        self.0.find_app_containing_cell(cell_id).await
+    }
+
+    async fn create_clone_cell(
+        &self,
+        _installed_app_id: &InstalledAppId,
+        _payload: CreateCloneCellPayload,
+    ) -> ConductorResult<ClonedCell> {
+        Err(ConductorError::DryRunRefused("create a clone cell"))
+    }
+
+    async fn disable_clone_cell(
+        &self,
+        _installed_app_id: &InstalledAppId,
+        _payload: DisableCloneCellPayload,
+    ) -> ConductorResult<()> {
+        Err(ConductorError::DryRunRefused("disable a clone cell"))
+    }
+
+    async fn enable_clone_cell(
+        &self,
+        _installed_app_id: &InstalledAppId,
+        _payload: EnableCloneCellPayload,
+    ) -> ConductorResult<ClonedCell> {
+        Err(ConductorError::DryRunRefused("enable a clone cell"))
+    }
+
+    async fn delete_clone_cell(&self, _payload: DeleteCloneCellPayload) -> ConductorResult<()> {
+        Err(ConductorError::DryRunRefused("delete a clone cell"))
+    }
+
+    async fn accept_countersigning_session(
+        &self,
+        _cell_id: CellId,
+        _request: PreflightRequest,
+    ) -> ConductorResult<PreflightRequestAcceptance> {
+        Err(ConductorError::DryRunRefused(
+            "accept a countersigning session",
+        ))
+    }
+
+    async fn queue_remote_message(
+        &self,
+        _zome_name: ZomeName,
+        _message: QueuedRemoteMessage,
+    ) -> ConductorResult<OutboxMessageId> {
+        Err(ConductorError::DryRunRefused("queue a remote message"))
+    }
+
+    fn outbox_delivery(&self, ids: Vec<OutboxMessageId>) -> Vec<Option<OutboxDelivery>> {
+        self.0.outbox_delivery(ids)
+    }
 }
@@ -3,6 +3,7 @@ use holochain_serialized_bytes::prelude::*;
 
 mod admin_interface;
 mod app_interface;
+mod zome_call_streams;
 pub use admin_interface::*;
 pub use app_interface::*;
 use holochain_types::prelude::InstalledAppId;
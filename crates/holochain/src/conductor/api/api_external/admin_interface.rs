@@ -10,6 +10,7 @@ use crate::conductor::ConductorHandle;
 use holochain_serialized_bytes::prelude::*;
 use holochain_types::dna::DnaBundle;
 use holochain_types::prelude::*;
+use kitsune_p2p::GossipPause;
 use mr_bundle::Bundle;
 
 use tracing::*;
@@ -23,12 +24,24 @@ pub use holochain_conductor_api::*;
 pub struct AdminInterfaceApi {
     /// Mutable access to the Conductor
     conductor_handle: ConductorHandle,
+    /// Who is making the requests, as recorded in the audit log
+    connection: Option<String>,
 }
 
 impl AdminInterfaceApi {
     /// Create an admin interface api.
     pub fn new(conductor_handle: ConductorHandle) -> Self {
-        AdminInterfaceApi { conductor_handle }
+        AdminInterfaceApi {
+            conductor_handle,
+            connection: None,
+        }
+    }
+
+    /// Record requests handled by this api in the audit log as made by `connection`,
+    /// rather than by the conductor process itself.
+    pub fn with_connection(mut self, connection: String) -> Self {
+        self.connection = Some(connection);
+        self
     }
 
     /// Handle an [AdminRequest] and return an [AdminResponse].
@@ -53,11 +66,32 @@ impl AdminInterfaceApi {
     pub(crate) async fn handle_admin_request(&self, request: AdminRequest) -> AdminResponse {
         debug!("admin request: {:?}", request);
 
+        let audited = request.audited_action();
         let res = self
             .handle_admin_request_inner(request)
             .await
             .unwrap_or_else(|e| AdminResponse::Error(e.into()));
         debug!("admin response: {:?}", res);
+        if let Some((action, target)) = audited {
+            let error = match &res {
+                AdminResponse::Error(e) => Some(format!("{:?}", e)),
+                _ => None,
+            };
+            let connection = self
+                .connection
+                .clone()
+                .unwrap_or_else(|| "local".to_string());
+            if let Err(err) = self
+                .conductor_handle
+                .record_audit_log_entry(connection, action, target, error)
+                .await
+            {
+                error!(
+                    ?err,
+                    action, "Failed to record an admin request in the audit log"
+                );
+            }
+        }
         res
     }
 
@@ -195,18 +229,51 @@ impl AdminInterfaceApi {
                     .collect();
                 Ok(AdminResponse::AgentKeyRevoked(results))
             }
-            ListCellIds => {
-                let cell_ids = self
+            ListCellIds(filter) => {
+                let filter = filter.unwrap_or_default();
+                let mut cell_ids: Vec<CellId> = self
                     .conductor_handle
                     .running_cell_ids()
                     .into_iter()
+                    .filter(|cell_id| {
+                        filter
+                            .dna_hash
+                            .as_ref()
+                            .map_or(true, |dna_hash| cell_id.dna_hash() == dna_hash)
+                    })
                     .collect();
+                cell_ids.sort();
+                if let Some(page) = filter.page {
+                    cell_ids = page.select(cell_ids);
+                }
                 Ok(AdminResponse::CellIdsListed(cell_ids))
             }
-            ListApps { status_filter } => {
-                let apps = self.conductor_handle.list_apps(status_filter).await?;
+            ListApps {
+                status_filter,
+                dna_hash,
+                page,
+            } => {
+                let apps = self
+                    .conductor_handle
+                    .find_apps(status_filter, dna_hash.as_ref(), page)
+                    .await?;
                 Ok(AdminResponse::AppsListed(apps))
             }
+            ListAppSummaries {
+                status_filter,
+                dna_hash,
+                page,
+            } => {
+                let summaries = self
+                    .conductor_handle
+                    .list_app_summaries(status_filter, dna_hash.as_ref(), page)
+                    .await?;
+                Ok(AdminResponse::AppSummariesListed(summaries))
+            }
+            ListAgentKeys => {
+                let keys = self.conductor_handle.list_agent_keys().await?;
+                Ok(AdminResponse::AgentKeysListed(keys))
+            }
             EnableApp { installed_app_id } => {
                 // Enable app
                 let (app, errors) = self
@@ -289,6 +356,145 @@ impl AdminInterfaceApi {
                 let stats = self.conductor_handle.dump_network_stats().await?;
                 Ok(AdminResponse::NetworkStatsDumped(stats))
             }
+            HealthCheck => Ok(AdminResponse::HealthChecked(
+                self.conductor_handle.health_check().await,
+            )),
+            GetStartupReport => Ok(AdminResponse::StartupReport(
+                self.conductor_handle.startup_report(),
+            )),
+            ListSlowOperations => Ok(AdminResponse::SlowOperationsListed(
+                self.conductor_handle.list_slow_operations(),
+            )),
+            GetConductorDiagnostics => Ok(AdminResponse::ConductorDiagnostics(
+                self.conductor_handle.conductor_diagnostics().await?,
+            )),
+            GetRegionSet { dna_hash } => Ok(AdminResponse::RegionSet(
+                self.conductor_handle
+                    .region_set_diagnostics(dna_hash)
+                    .await?,
+            )),
+            GetOpCensus {
+                dna_hash,
+                peer_count,
+            } => Ok(AdminResponse::OpCensus(
+                self.conductor_handle
+                    .op_census(
+                        dna_hash,
+                        peer_count.unwrap_or(holochain_conductor_api::OP_CENSUS_DEFAULT_PEER_COUNT),
+                    )
+                    .await?,
+            )),
+            GetValidationOutcomes { dna_hash } => Ok(AdminResponse::ValidationOutcomes(
+                self.conductor_handle.validation_outcomes(dna_hash).await?,
+            )),
+            GetBackfillProgress { cell_id } => Ok(AdminResponse::BackfillProgress(
+                self.conductor_handle.backfill_progress(&cell_id).await?,
+            )),
+            PauseGossip {
+                dna_hash,
+                refuse_incoming,
+            } => {
+                let pause = if refuse_incoming {
+                    GossipPause::All
+                } else {
+                    GossipPause::Initiation
+                };
+                self.conductor_handle
+                    .set_gossip_pause(dna_hash, pause)
+                    .await?;
+                Ok(AdminResponse::GossipPaused)
+            }
+            ResumeGossip { dna_hash } => {
+                self.conductor_handle
+                    .set_gossip_pause(dna_hash, GossipPause::Running)
+                    .await?;
+                Ok(AdminResponse::GossipResumed)
+            }
+            SetArqPin { cell_id, pin } => {
+                self.conductor_handle.set_arq_pin(*cell_id, pin).await?;
+                Ok(AdminResponse::ArqPinSet)
+            }
+            StartOpTrace { op_hash } => {
+                self.conductor_handle.start_op_trace(op_hash);
+                Ok(AdminResponse::OpTraceStarted)
+            }
+            GetOpTrace { op_hash } => Ok(AdminResponse::OpTrace(
+                self.conductor_handle.op_trace(&op_hash),
+            )),
+            StopOpTrace { op_hash } => {
+                self.conductor_handle.stop_op_trace(&op_hash);
+                Ok(AdminResponse::OpTraceStopped)
+            }
+            ListOpsMissingReceipts { cell_id } => Ok(AdminResponse::OpsMissingReceiptsListed(
+                self.conductor_handle
+                    .list_ops_missing_receipts(&cell_id)
+                    .await?,
+            )),
+            ExportDhtOpArchive {
+                dna_hash,
+                integrated_before,
+            } => Ok(AdminResponse::DhtOpArchiveExported(Box::new(
+                self.conductor_handle
+                    .export_dht_op_archive(&dna_hash, integrated_before)
+                    .await?,
+            ))),
+            ImportDhtOpArchive { archive } => Ok(AdminResponse::DhtOpArchiveImported {
+                op_count: self
+                    .conductor_handle
+                    .import_dht_op_archive(*archive)
+                    .await?,
+            }),
+            ListDhtEpochs { dna_hash } => Ok(AdminResponse::DhtEpochsListed(
+                self.conductor_handle.list_dht_epochs(&dna_hash).await?,
+            )),
+            ArchiveDhtEpochs { dna_hash, before } => {
+                Ok(AdminResponse::DhtEpochsArchived(Box::new(
+                    self.conductor_handle
+                        .archive_dht_epochs(&dna_hash, before)
+                        .await?,
+                )))
+            }
+            RecheckMembraneProofs { dna_hash } => Ok(AdminResponse::MembraneProofsRechecked(
+                self.conductor_handle
+                    .recheck_membrane_proofs(&dna_hash)
+                    .await?,
+            )),
+            ExportAuthoredData { cell_id } => {
+                let export = self.conductor_handle.export_authored_data(&cell_id).await?;
+                Ok(AdminResponse::AuthoredDataExported(export.to_json()?))
+            }
+            ExportPublicationProof {
+                cell_id,
+                action_hash,
+            } => {
+                let proof = self
+                    .conductor_handle
+                    .export_publication_proof(&cell_id, &action_hash)
+                    .await?;
+                Ok(AdminResponse::PublicationProofExported(Box::new(proof)))
+            }
+            ExportAppBundle { installed_app_id } => {
+                let bundle = self
+                    .conductor_handle
+                    .export_app_bundle(&installed_app_id)
+                    .await?;
+                Ok(AdminResponse::AppBundleExported(bundle.encode()?))
+            }
+            SetLogFilter { filter } => {
+                holochain_trace::set_filter(&filter).map_err(ConductorApiError::other)?;
+                Ok(AdminResponse::LogFilterSet)
+            }
+            ReloadConfig => Ok(AdminResponse::ConfigReloaded(
+                self.conductor_handle.reload_config()?,
+            )),
+            ListAppInterfaceConnections => Ok(AdminResponse::AppInterfaceConnectionsListed(
+                self.conductor_handle.list_app_interface_connections(),
+            )),
+            DisconnectAppInterfaceClient { connection_id } => {
+                self.conductor_handle
+                    .disconnect_app_interface_client(connection_id)?;
+                Ok(AdminResponse::AppInterfaceClientDisconnected)
+            }
             AddAgentInfo { agent_infos } => {
                 self.conductor_handle.add_agent_infos(agent_infos).await?;
                 Ok(AdminResponse::AgentInfoAdded)
@@ -308,6 +514,11 @@ impl AdminInterfaceApi {
                     .await?;
                 Ok(AdminResponse::RecordsGrafted)
             }
+            RollbackSourceChain { cell_id, to } => Ok(AdminResponse::SourceChainRolledBack(
+                self.conductor_handle
+                    .rollback_source_chain(&cell_id, &to)
+                    .await?,
+            )),
             GrantZomeCallCapability(payload) => {
                 self.conductor_handle
                     .clone()
@@ -341,6 +552,9 @@ impl AdminInterfaceApi {
                     .cells_by_dna_lineage(&dna_hash)
                     .await?,
             )),
+            ListAuditLog { since, limit } => Ok(AdminResponse::AuditLogListed(
+                self.conductor_handle.list_audit_log(since, limit).await?,
+            )),
         }
     }
 }
@@ -350,6 +564,7 @@ mod test {
     use super::*;
     use crate::conductor::Conductor;
     use anyhow::Result;
+    use holochain_conductor_api::{ApiErrorCode, ApiErrorSubsystem};
     use holochain_state::prelude::*;
     use holochain_trace;
     use holochain_types::test_utils::fake_dna_zomes;
@@ -487,6 +702,34 @@ mod test {
         Ok(())
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn errors_have_codes() -> Result<()> {
+        holochain_trace::test_run();
+        let env_dir = test_db_dir();
+        let handle = Conductor::builder()
+            .with_data_root_path(env_dir.path().to_path_buf().into())
+            .test(&[])
+            .await?;
+        let admin_api = AdminInterfaceApi::new(handle.clone());
+
+        let response = admin_api
+            .handle_admin_request(AdminRequest::GetRegionSet {
+                dna_hash: DnaHash::from_raw_32(vec![1; 32]),
+            })
+            .await;
+        let AdminResponse::Error(error) = response else {
+            panic!("Expected an error, got {:?}", response);
+        };
+        assert_eq!(ApiErrorCode::DnaMissing, error.code());
+        assert_eq!(ApiErrorSubsystem::Dna, error.subsystem());
+        assert!(!error.is_retryable());
+
+        tokio::time::timeout(std::time::Duration::from_secs(1), handle.shutdown())
+            .await
+            .ok();
+        Ok(())
+    }
+
     // @todo fix test by using new InstallApp call
     // #[tokio::test(flavor = "multi_thread")]
     // async fn install_list_dna_app() {
@@ -580,7 +823,7 @@ mod test {
     // );
 
     // let res = admin_api
-    //     .handle_admin_request(AdminRequest::ListCellIds)
+    //     .handle_admin_request(AdminRequest::ListCellIds(None))
     //     .await;
 
     // assert_matches!(res, AdminResponse::CellIdsListed(v) if v == vec![cell_id2]);
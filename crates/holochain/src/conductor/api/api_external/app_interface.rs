@@ -4,10 +4,15 @@ use crate::conductor::api::error::SerializationError;
 use crate::conductor::interface::error::InterfaceError;
 use crate::conductor::interface::error::InterfaceResult;
 use crate::conductor::ConductorHandle;
+use crate::core::workflow::ZomeCallDryRunOutcome;
+use crate::core::workflow::ZomeCallResult;
+
+use super::zome_call_streams::ZomeCallStreams;
 
 use holochain_serialized_bytes::prelude::*;
 
 use holochain_types::prelude::*;
+use tracing::Instrument;
 
 pub use holochain_conductor_api::*;
 
@@ -16,17 +21,24 @@ pub use holochain_conductor_api::*;
 #[derive(Clone)]
 pub struct AppInterfaceApi {
     conductor_handle: ConductorHandle,
+    zome_call_streams: ZomeCallStreams,
 }
 
 impl AppInterfaceApi {
     /// Create a new instance from a shared Conductor reference
     pub fn new(conductor_handle: ConductorHandle) -> Self {
-        Self { conductor_handle }
+        Self {
+            conductor_handle,
+            zome_call_streams: ZomeCallStreams::default(),
+        }
     }
 
     /// Check an authentication request and return the app that access has been granted
     /// for on success.
-    pub async fn auth(&self, auth: AppAuthentication) -> InterfaceResult<InstalledAppId> {
+    pub async fn auth(
+        &self,
+        auth: AppAuthentication,
+    ) -> InterfaceResult<(InstalledAppId, AppAuthenticationTokenScope)> {
         self.conductor_handle
             .authenticate_app_token(auth.token, auth.installed_app_id)
             .map_err(Box::new)
@@ -59,8 +71,10 @@ impl AppInterfaceApi {
     ) -> AppResponse {
         tracing::debug!("app request: {:?}", request);
 
+        let span = tracing::info_span!("app_request", app_id = %installed_app_id);
         let res = self
             .handle_app_request_inner(installed_app_id, request)
+            .instrument(span)
             .await
             .unwrap_or_else(|e| AppResponse::Error(e.into()));
         tracing::debug!("app response: {:?}", res);
@@ -79,27 +93,46 @@ impl AppInterfaceApi {
                     .get_app_info(&installed_app_id)
                     .await?,
             )),
-            AppRequest::CallZome(call) => {
-                match self.conductor_handle.call_zome(*call.clone()).await? {
-                    Ok(ZomeCallResponse::Ok(output)) => Ok(AppResponse::ZomeCalled(Box::new(output))),
-                    Ok(ZomeCallResponse::Unauthorized(zome_call_authorization, _, zome_name, fn_name, _)) => Ok(AppResponse::Error(
-                        ExternalApiWireError::ZomeCallUnauthorized(format!(
-                            "Call was not authorized with reason {:?}, cap secret {:?} to call the function {} in zome {}",
-                            zome_call_authorization, call.cap_secret, fn_name, zome_name
-                        )),
-                    )),
-                    Ok(ZomeCallResponse::NetworkError(e)) => unreachable!(
-                        "Interface zome calls should never be routed to the network. This is a bug. Got {}",
-                        e
+            AppRequest::CallZome(call) => self.call_zome(&installed_app_id, *call).await,
+            AppRequest::CallZomeStreaming(call) => match self.call_zome(&installed_app_id, *call).await? {
+                AppResponse::ZomeCalled(output) => Ok(AppResponse::ZomeCallResponseChunk(
+                    Box::new(
+                        self.zome_call_streams
+                            .start(installed_app_id, output.into_vec()),
                     ),
-                    Ok(ZomeCallResponse::CountersigningSession(e)) => Ok(AppResponse::Error(
-                        ExternalApiWireError::CountersigningSessionError(format!(
-                            "A countersigning session has failed to start on this zome call because: {}",
-                            e
-                        )),
-                    )),
-                    Err(e) => Ok(AppResponse::Error(e.into())),
-                }
+                )),
+                response => Ok(response),
+            },
+            AppRequest::CallZomeDryRun(call) => {
+                let cap_secret = call.cap_secret;
+                let _turn = self.conductor_handle.zome_call_turn(&installed_app_id).await;
+                let ZomeCallDryRunOutcome {
+                    result,
+                    records,
+                    validation,
+                } = self.conductor_handle.call_zome_dry_run(*call).await?;
+                self.zome_call_response(cap_secret, result, |output| {
+                    AppResponse::ZomeCallDryRun(Box::new(ZomeCallDryRun {
+                        output,
+                        records,
+                        validation,
+                    }))
+                })
+            }
+            AppRequest::NextZomeCallResponseChunk(stream_id) => self
+                .zome_call_streams
+                .next(&installed_app_id, stream_id)
+                .map(|chunk| AppResponse::ZomeCallResponseChunk(Box::new(chunk)))
+                .ok_or_else(|| {
+                    ConductorApiError::other(format!(
+                        "No open zome call response stream with id {}, it may have finished or expired",
+                        stream_id
+                    ))
+                }),
+            AppRequest::CancelZomeCallResponseStream(stream_id) => {
+                self.zome_call_streams
+                    .cancel(&installed_app_id, stream_id);
+                Ok(AppResponse::Ok)
             }
             AppRequest::GetCountersigningSessionState(payload) => {
                 let countersigning_session_state = self
@@ -165,6 +198,14 @@ impl AppInterfaceApi {
                     .await?;
                 Ok(AppResponse::Ok)
             }
+            AppRequest::GetMemproofProgress => Ok(AppResponse::MemproofProgress(
+                self.conductor_handle
+                    .get_memproof_progress(&installed_app_id)
+                    .await?,
+            )),
+            AppRequest::GetZomeCallNonceWindow => Ok(AppResponse::ZomeCallNonceWindow(
+                self.conductor_handle.zome_call_nonce_window()?,
+            )),
             AppRequest::EnableApp => {
                 let status = self
                     .conductor_handle
@@ -198,6 +239,57 @@ impl AppInterfaceApi {
               // }
         }
     }
+
+    /// Call a zome function, turning a call which was refused into an error response.
+    async fn call_zome(
+        &self,
+        installed_app_id: &InstalledAppId,
+        call: ZomeCall,
+    ) -> ConductorApiResult<AppResponse> {
+        let cap_secret = call.cap_secret;
+        // The turn is only held for the call itself, not for the calls which it makes.
+        let _turn = self.conductor_handle.zome_call_turn(installed_app_id).await;
+        let result = self.conductor_handle.call_zome(call).await?;
+        self.zome_call_response(cap_secret, result, |output| {
+            AppResponse::ZomeCalled(Box::new(output))
+        })
+    }
+
+    /// Turn the result of a zome call into a response, with `ok` making the response to
+    /// a call which returned.
+    fn zome_call_response(
+        &self,
+        cap_secret: Option<CapSecret>,
+        result: ZomeCallResult,
+        ok: impl FnOnce(ExternIO) -> AppResponse,
+    ) -> ConductorApiResult<AppResponse> {
+        match result {
+            Ok(ZomeCallResponse::Ok(output)) => Ok(ok(output)),
+            Ok(ZomeCallResponse::Unauthorized(ZomeCallAuthorization::BadNonce(reason), ..)) => {
+                Ok(AppResponse::Error(ExternalApiWireError::ZomeCallNonceRejected {
+                    reason,
+                    window: self.conductor_handle.zome_call_nonce_window()?,
+                }))
+            }
+            Ok(ZomeCallResponse::Unauthorized(zome_call_authorization, _, zome_name, fn_name, _)) => Ok(AppResponse::Error(
+                ExternalApiWireError::ZomeCallUnauthorized(format!(
+                    "Call was not authorized with reason {:?}, cap secret {:?} to call the function {} in zome {}",
+                    zome_call_authorization, cap_secret, fn_name, zome_name
+                )),
+            )),
+            Ok(ZomeCallResponse::NetworkError(e)) => unreachable!(
+                "Interface zome calls should never be routed to the network. This is a bug. Got {}",
+                e
+            ),
+            Ok(ZomeCallResponse::CountersigningSession(e)) => Ok(AppResponse::Error(
+                ExternalApiWireError::CountersigningSessionError(format!(
+                    "A countersigning session has failed to start on this zome call because: {}",
+                    e
+                )),
+            )),
+            Err(e) => Ok(AppResponse::Error(e.into())),
+        }
+    }
 }
 
 /// The payload for authenticating an app interface connection
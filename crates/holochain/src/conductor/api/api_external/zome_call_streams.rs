@@ -0,0 +1,290 @@
+//! Zome call responses which are being streamed to app interface clients in chunks.
+
+use holochain_conductor_api::{
+    ZomeCallResponseChunk, ZomeCallResponseStreamId, ZOME_CALL_RESPONSE_CHUNK_SIZE,
+    ZOME_CALL_RESPONSE_STREAM_TIMEOUT,
+};
+use holochain_types::prelude::InstalledAppId;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Instant;
+
+/// The most streams which can be open at once for an app on an app interface.
+///
+/// When another stream is started for the app, the stream of the app which was read least
+/// recently is closed, so that an app can't close the streams of other apps.
+const MAX_OPEN_STREAMS_PER_APP: usize = 64;
+
+/// A zome call response which hasn't been completely read yet.
+struct ResponseStream {
+    bytes: Vec<u8>,
+    next_index: u32,
+    last_read: Instant,
+}
+
+#[derive(Default)]
+struct ZomeCallStreamsInner {
+    streams: HashMap<(InstalledAppId, ZomeCallResponseStreamId), ResponseStream>,
+}
+
+/// The zome call responses which are being streamed over an app interface.
+///
+/// Streams are kept per app, so a client can only read the responses to calls made through a
+/// connection for the same app. Stream ids are random, so that other connections of the app
+/// can't read a response by guessing the id of its stream.
+#[derive(Clone)]
+pub(crate) struct ZomeCallStreams {
+    inner: Arc<parking_lot::Mutex<ZomeCallStreamsInner>>,
+    chunk_size: usize,
+}
+
+impl Default for ZomeCallStreams {
+    fn default() -> Self {
+        Self::new(ZOME_CALL_RESPONSE_CHUNK_SIZE)
+    }
+}
+
+impl ZomeCallStreams {
+    pub(crate) fn new(chunk_size: usize) -> Self {
+        Self {
+            inner: Default::default(),
+            chunk_size,
+        }
+    }
+
+    /// Start streaming a zome call response, returning its first chunk.
+    ///
+    /// A response which fits in a single chunk is returned as the last chunk, without opening a
+    /// stream.
+    pub(crate) fn start(
+        &self,
+        installed_app_id: InstalledAppId,
+        bytes: Vec<u8>,
+    ) -> ZomeCallResponseChunk {
+        let mut inner = self.inner.lock();
+        inner.remove_expired();
+
+        let stream_id = loop {
+            let stream_id = rand::random();
+            if !inner
+                .streams
+                .contains_key(&(installed_app_id.clone(), stream_id))
+            {
+                break stream_id;
+            }
+        };
+
+        let mut stream = ResponseStream {
+            bytes,
+            next_index: 0,
+            last_read: Instant::now(),
+        };
+        let chunk = stream.next_chunk(stream_id, self.chunk_size);
+        if !chunk.is_last {
+            if inner.open_streams(&installed_app_id) >= MAX_OPEN_STREAMS_PER_APP {
+                inner.remove_least_recently_read(&installed_app_id);
+            }
+            inner.streams.insert((installed_app_id, stream_id), stream);
+        }
+        chunk
+    }
+
+    /// Get the next chunk of a response, or `None` if there is no open stream with this id.
+    pub(crate) fn next(
+        &self,
+        installed_app_id: &InstalledAppId,
+        stream_id: ZomeCallResponseStreamId,
+    ) -> Option<ZomeCallResponseChunk> {
+        let mut inner = self.inner.lock();
+        inner.remove_expired();
+
+        let key = (installed_app_id.clone(), stream_id);
+        let stream = inner.streams.get_mut(&key)?;
+        let chunk = stream.next_chunk(stream_id, self.chunk_size);
+        if chunk.is_last {
+            inner.streams.remove(&key);
+        }
+        Some(chunk)
+    }
+
+    /// Close a stream without reading the rest of it.
+    pub(crate) fn cancel(
+        &self,
+        installed_app_id: &InstalledAppId,
+        stream_id: ZomeCallResponseStreamId,
+    ) {
+        self.inner
+            .lock()
+            .streams
+            .remove(&(installed_app_id.clone(), stream_id));
+    }
+}
+
+impl ZomeCallStreamsInner {
+    fn remove_expired(&mut self) {
+        self.streams
+            .retain(|_, stream| stream.last_read.elapsed() < ZOME_CALL_RESPONSE_STREAM_TIMEOUT);
+    }
+
+    fn open_streams(&self, installed_app_id: &InstalledAppId) -> usize {
+        self.streams
+            .keys()
+            .filter(|(app_id, _)| app_id == installed_app_id)
+            .count()
+    }
+
+    fn remove_least_recently_read(&mut self, installed_app_id: &InstalledAppId) {
+        let oldest = self
+            .streams
+            .iter()
+            .filter(|((app_id, _), _)| app_id == installed_app_id)
+            .min_by_key(|((_, stream_id), stream)| (stream.last_read, *stream_id))
+            .map(|(key, _)| key.clone());
+        if let Some(key) = oldest {
+            tracing::warn!(
+                app_id = %key.0,
+                stream_id = key.1,
+                "Too many zome call response streams are open, closing the least recently read one"
+            );
+            self.streams.remove(&key);
+        }
+    }
+}
+
+impl ResponseStream {
+    fn next_chunk(
+        &mut self,
+        stream_id: ZomeCallResponseStreamId,
+        chunk_size: usize,
+    ) -> ZomeCallResponseChunk {
+        let start = (self.next_index as usize)
+            .saturating_mul(chunk_size)
+            .min(self.bytes.len());
+        let end = start.saturating_add(chunk_size).min(self.bytes.len());
+        let chunk = ZomeCallResponseChunk {
+            stream_id,
+            index: self.next_index,
+            total_size: self.bytes.len() as u64,
+            bytes: self.bytes[start..end].to_vec(),
+            is_last: end == self.bytes.len(),
+        };
+        self.next_index += 1;
+        self.last_read = Instant::now();
+        chunk
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn read_all(
+        streams: &ZomeCallStreams,
+        app: &InstalledAppId,
+        first: ZomeCallResponseChunk,
+    ) -> Vec<ZomeCallResponseChunk> {
+        let mut chunks = vec![first];
+        while !chunks.last().unwrap().is_last {
+            let id = chunks[0].stream_id;
+            chunks.push(streams.next(app, id).unwrap());
+        }
+        chunks
+    }
+
+    #[test]
+    fn small_response_is_a_single_chunk() {
+        let streams = ZomeCallStreams::new(4);
+        let app = "app".to_string();
+
+        let chunk = streams.start(app.clone(), vec![1, 2, 3]);
+        assert!(chunk.is_last);
+        assert_eq!(vec![1, 2, 3], chunk.bytes);
+        assert_eq!(3, chunk.total_size);
+
+        // No stream is kept open for a response which fit in one chunk.
+        assert!(streams.next(&app, chunk.stream_id).is_none());
+    }
+
+    #[test]
+    fn large_response_is_streamed_in_order() {
+        let streams = ZomeCallStreams::new(4);
+        let app = "app".to_string();
+        let bytes = (0..10).collect::<Vec<u8>>();
+
+        let first = streams.start(app.clone(), bytes.clone());
+        let chunks = read_all(&streams, &app, first);
+
+        assert_eq!(
+            vec![0, 1, 2],
+            chunks.iter().map(|c| c.index).collect::<Vec<_>>()
+        );
+        assert!(chunks.iter().all(|c| c.total_size == 10));
+        assert_eq!(
+            bytes,
+            chunks.into_iter().flat_map(|c| c.bytes).collect::<Vec<_>>()
+        );
+
+        // The stream is closed after the last chunk.
+        assert!(streams.next(&app, chunks[0].stream_id).is_none());
+    }
+
+    #[test]
+    fn response_which_fills_its_last_chunk_ends_on_it() {
+        let streams = ZomeCallStreams::new(4);
+        let app = "app".to_string();
+
+        let first = streams.start(app.clone(), vec![0; 8]);
+        let chunks = read_all(&streams, &app, first);
+        assert_eq!(2, chunks.len());
+        assert_eq!(4, chunks[1].bytes.len());
+    }
+
+    #[test]
+    fn streams_are_kept_per_app() {
+        let streams = ZomeCallStreams::new(4);
+        let app = "app".to_string();
+
+        let first = streams.start(app.clone(), vec![0; 10]);
+        assert!(streams
+            .next(&"other".to_string(), first.stream_id)
+            .is_none());
+        assert!(streams.next(&app, first.stream_id).is_some());
+    }
+
+    #[test]
+    fn cancelled_stream_is_closed() {
+        let streams = ZomeCallStreams::new(4);
+        let app = "app".to_string();
+
+        let first = streams.start(app.clone(), vec![0; 10]);
+        streams.cancel(&app, first.stream_id);
+        assert!(streams.next(&app, first.stream_id).is_none());
+    }
+
+    #[test]
+    fn stream_ids_are_not_sequential() {
+        let streams = ZomeCallStreams::new(4);
+        let app = "app".to_string();
+
+        let first = streams.start(app.clone(), vec![0; 10]).stream_id;
+        let second = streams.start(app.clone(), vec![0; 10]).stream_id;
+        assert_ne!(first.wrapping_add(1), second);
+    }
+
+    #[test]
+    fn least_recently_read_stream_of_the_app_is_closed_when_too_many_are_open() {
+        let streams = ZomeCallStreams::new(4);
+        let app = "app".to_string();
+        let other = "other".to_string();
+
+        let other_id = streams.start(other.clone(), vec![0; 10]).stream_id;
+        let ids = (0..=MAX_OPEN_STREAMS_PER_APP)
+            .map(|_| streams.start(app.clone(), vec![0; 10]).stream_id)
+            .collect::<Vec<_>>();
+
+        assert!(streams.next(&app, ids[0]).is_none());
+        assert!(streams.next(&app, ids[MAX_OPEN_STREAMS_PER_APP]).is_some());
+        // - The streams of other apps are kept.
+        assert!(streams.next(&other, other_id).is_some());
+    }
+}
@@ -6,6 +6,7 @@ use crate::core::ribosome::error::RibosomeError;
 use crate::core::workflow::WorkflowError;
 use holo_hash::DnaHash;
 use holochain_chc::ChcError;
+use holochain_conductor_api::{ApiError, ApiErrorCode, ApiErrorSubsystem};
 use holochain_sqlite::error::DatabaseError;
 use holochain_state::source_chain::SourceChainError;
 use holochain_state::workspace::WorkspaceError;
@@ -151,11 +152,188 @@ impl From<ConductorApiError> for ExternalApiWireError {
     fn from(err: ConductorApiError) -> Self {
         match err {
             ConductorApiError::DnaReadError(e) => ExternalApiWireError::DnaReadError(e),
-            e => ExternalApiWireError::internal(e),
+            ConductorApiError::ConductorError(ConductorError::MemproofsRejected(progress)) => {
+                ExternalApiWireError::MemproofsRejected(progress)
+            }
+            e => {
+                let (code, subsystem) = classify_api_error(&e);
+                ExternalApiWireError::ApiError(ApiError::new(code, subsystem, e))
+            }
         }
     }
 }
 
+type ErrorClass = (ApiErrorCode, ApiErrorSubsystem);
+
+/// The code and subsystem which clients see an error as, looking through the errors
+/// which wrap the error which caused it.
+fn classify_api_error(e: &ConductorApiError) -> ErrorClass {
+    use ApiErrorCode as C;
+    use ApiErrorSubsystem as S;
+    match e {
+        ConductorApiError::DnaMissing(_) => (C::DnaMissing, S::Dna),
+        ConductorApiError::ZomeCallCellMismatch { .. } => (C::InvalidRequest, S::Cell),
+        ConductorApiError::ConductorError(e) => classify_conductor_error(e),
+        ConductorApiError::SerializationError(_) => (C::InvalidRequest, S::Interface),
+        ConductorApiError::DatabaseError(e) => classify_database_error(e),
+        ConductorApiError::WorkflowError(e) => classify_workflow_error(e),
+        ConductorApiError::ZomeError(_) | ConductorApiError::RibosomeError(_) => {
+            (C::ZomeCallFailed, S::Ribosome)
+        }
+        ConductorApiError::DnaError(e) => classify_dna_error(e),
+        ConductorApiError::DnaReadError(_) => (C::InvalidDna, S::Dna),
+        ConductorApiError::KeystoreError(_) => (C::Keystore, S::Keystore),
+        ConductorApiError::CellError(e) => classify_cell_error(e),
+        ConductorApiError::AppError(_) => (C::Internal, S::App),
+        ConductorApiError::InterfaceError(_) => (C::Internal, S::Interface),
+        ConductorApiError::SourceChainError(e) => classify_source_chain_error(e),
+        ConductorApiError::AppBundleError(_) | ConductorApiError::MrBundleError(_) => {
+            (C::InvalidBundle, S::App)
+        }
+        ConductorApiError::StateQueryError(_)
+        | ConductorApiError::StateMutationError(_)
+        | ConductorApiError::RusqliteError(_) => (C::Database, S::Database),
+        ConductorApiError::ChcError(_) => (C::Internal, S::Chc),
+        ConductorApiError::WorkspaceError(_)
+        | ConductorApiError::Io(_)
+        | ConductorApiError::JsonDumpError(_)
+        | ConductorApiError::Other(_) => (C::Internal, S::Conductor),
+    }
+}
+
+fn classify_conductor_error(e: &ConductorError) -> ErrorClass {
+    use ApiErrorCode as C;
+    use ApiErrorSubsystem as S;
+    match e {
+        ConductorError::InternalCellError(e) => classify_cell_error(e),
+        ConductorError::AppBundleError(_) | ConductorError::MrBundleError(_) => {
+            (C::InvalidBundle, S::App)
+        }
+        ConductorError::AppHasDependents(..) => (C::AppHasDependents, S::App),
+        ConductorError::AppNotInstalled(_) => (C::AppMissing, S::App),
+        ConductorError::AppAlreadyInstalled(_) => (C::AppAlreadyInstalled, S::App),
+        ConductorError::AppNotRunning(_) => (C::AppNotRunning, S::App),
+        ConductorError::MemproofsRejected(_) => (C::MemproofsRejected, S::App),
+        ConductorError::DatabaseError(e) => classify_database_error(e),
+        ConductorError::StateQueryError(_)
+        | ConductorError::StateMutationError(_)
+        | ConductorError::RusqliteError(_) => (C::Database, S::Database),
+        ConductorError::CellAlreadyExists(_) => (C::CellAlreadyExists, S::Cell),
+        ConductorError::CellNotInitialized | ConductorError::CellDisabled(_) => {
+            (C::CellNotRunning, S::Cell)
+        }
+        ConductorError::CellMissing(_) | ConductorError::RemoteCellUnreachable(_) => {
+            (C::CellMissing, S::Cell)
+        }
+        ConductorError::CloneCellError(_)
+        | ConductorError::ChainRollbackRefused(_)
+        | ConductorError::ActionNotAuthored(..)
+        | ConductorError::DryRunRefused(_) => (C::InvalidRequest, S::Cell),
+        ConductorError::ConductorConfigError(_)
+        | ConductorError::ConfigError(_)
+        | ConductorError::NoDataRootPath
+        | ConductorError::NoConfigPath => (C::InvalidConfig, S::Conductor),
+        ConductorError::CountersigningError(_) => (C::Countersigning, S::Countersigning),
+        ConductorError::DpkiError(_) => (C::Internal, S::Dpki),
+        ConductorError::SerializationError(_) | ConductorError::SerializedBytesError(_) => {
+            (C::InvalidRequest, S::Conductor)
+        }
+        ConductorError::ShuttingDown => (C::ShuttingDown, S::Conductor),
+        ConductorError::ZomeError(_) | ConductorError::RibosomeError(_) => {
+            (C::ZomeCallFailed, S::Ribosome)
+        }
+        ConductorError::DnaError(e) => classify_dna_error(e),
+        ConductorError::WasmMissing | ConductorError::EntryDefStoreError(_) => {
+            (C::InvalidDna, S::Dna)
+        }
+        ConductorError::WorkflowError(e) => classify_workflow_error(e),
+        ConductorError::AppInterfaceIdCollision(_)
+        | ConductorError::AppInterfaceConnectionNotFound(_) => (C::InvalidRequest, S::Interface),
+        ConductorError::InterfaceError(_) => (C::Internal, S::Interface),
+        ConductorError::FailedAuthenticationError(_) => (C::Unauthorized, S::Interface),
+        ConductorError::KeystoreError(_) => (C::Keystore, S::Keystore),
+        ConductorError::SourceChainError(e) => classify_source_chain_error(e),
+        ConductorError::AppError(_) | ConductorError::AppStatusError(_) => (C::Internal, S::App),
+        _ => (C::Internal, S::Conductor),
+    }
+}
+
+fn classify_cell_error(e: &CellError) -> ErrorClass {
+    use ApiErrorCode as C;
+    use ApiErrorSubsystem as S;
+    match e {
+        CellError::CellDisabled(_) => (C::CellNotRunning, S::Cell),
+        CellError::DatabaseError(e) => classify_database_error(e),
+        CellError::DnaError(e) => classify_dna_error(e),
+        CellError::WorkflowError(e) => classify_workflow_error(e),
+        CellError::RibosomeError(_) => (C::ZomeCallFailed, S::Ribosome),
+        CellError::SourceChainError(e) => classify_source_chain_error(e),
+        CellError::ConductorError(e) => classify_conductor_error(e),
+        CellError::ConductorApiError(e) => classify_api_error(e),
+        CellError::HolochainP2pError(_) => (C::Internal, S::Network),
+        CellError::InitTimeout => (C::Timeout, S::Ribosome),
+        _ => (C::Internal, S::Cell),
+    }
+}
+
+fn classify_workflow_error(e: &WorkflowError) -> ErrorClass {
+    use ApiErrorCode as C;
+    use ApiErrorSubsystem as S;
+    match e {
+        WorkflowError::ConductorApi(e) => classify_api_error(e),
+        WorkflowError::CellError(e) => classify_cell_error(e),
+        WorkflowError::SourceChainError(e) => classify_source_chain_error(e),
+        WorkflowError::DatabaseError(e) => classify_database_error(e),
+        WorkflowError::RibosomeError(_) => (C::ZomeCallFailed, S::Ribosome),
+        WorkflowError::CounterSigningError(_) => (C::Countersigning, S::Countersigning),
+        WorkflowError::KeystoreError(_) => (C::Keystore, S::Keystore),
+        WorkflowError::DpkiServiceError(_) => (C::Internal, S::Dpki),
+        WorkflowError::HolochainP2pError(_) => (C::Internal, S::Network),
+        WorkflowError::SqlError(_)
+        | WorkflowError::StateQueryError(_)
+        | WorkflowError::StateMutationError(_) => (C::Database, S::Database),
+        _ => (C::Internal, S::Conductor),
+    }
+}
+
+fn classify_source_chain_error(e: &SourceChainError) -> ErrorClass {
+    use ApiErrorCode as C;
+    use ApiErrorSubsystem as S;
+    match e {
+        SourceChainError::HeadMoved(..) | SourceChainError::ChcHeadMoved(..) => {
+            (C::ChainHeadMoved, S::SourceChain)
+        }
+        SourceChainError::ChainLocked => (C::ChainLocked, S::SourceChain),
+        SourceChainError::LockExpired
+        | SourceChainError::DirtyCounterSigningWrite
+        | SourceChainError::CountersigningWriteWithoutSession
+        | SourceChainError::CounterSigningError(_) => (C::Countersigning, S::Countersigning),
+        SourceChainError::DatabaseError(e) => classify_database_error(e),
+        SourceChainError::KeystoreError(_) => (C::Keystore, S::Keystore),
+        SourceChainError::HolochainP2pError(_) => (C::Internal, S::Network),
+        _ => (C::Internal, S::SourceChain),
+    }
+}
+
+fn classify_database_error(e: &DatabaseError) -> ErrorClass {
+    match e {
+        // - Connections time out when the pool is exhausted, so the database is busy.
+        DatabaseError::Timeout(_) | DatabaseError::DbConnectionPoolError(_) => {
+            (ApiErrorCode::Timeout, ApiErrorSubsystem::Database)
+        }
+        _ => (ApiErrorCode::Database, ApiErrorSubsystem::Database),
+    }
+}
+
+fn classify_dna_error(e: &holochain_types::dna::DnaError) -> ErrorClass {
+    match e {
+        holochain_types::dna::DnaError::DnaMissing(_) => {
+            (ApiErrorCode::DnaMissing, ApiErrorSubsystem::Dna)
+        }
+        _ => (ApiErrorCode::InvalidDna, ApiErrorSubsystem::Dna),
+    }
+}
+
 impl From<SerializationError> for ExternalApiWireError {
     fn from(e: SerializationError) -> Self {
         ExternalApiWireError::Deserialization(format!("{:?}", e))
@@ -8,10 +8,10 @@ use std::hash::Hash;
 use std::hash::Hasher;
 use std::sync::Arc;
 
+use crate::conductor::conductor::SignalSender;
 use futures::future::FutureExt;
 use holochain_serialized_bytes::SerializedBytes;
 use rusqlite::OptionalExtension;
-use tokio::sync::broadcast;
 use tracing::*;
 use tracing_futures::Instrument;
 
@@ -19,6 +19,7 @@ use error::CellError;
 use holo_hash::*;
 use holochain_cascade::authority;
 use holochain_chc::ChcImpl;
+use holochain_conductor_api::OpTraceStage;
 use holochain_conductor_api::ZomeCall;
 use holochain_nonce::fresh_nonce;
 use holochain_p2p::event::CountersigningSessionNegotiationMessage;
@@ -31,12 +32,14 @@ use holochain_types::db_cache::DhtDbQueryCache;
 
 use crate::conductor::api::CellConductorApi;
 use crate::conductor::cell::error::CellResult;
+use crate::core::op_trace::record_op_event;
 use crate::core::queue_consumer::spawn_queue_consumer_tasks;
 use crate::core::queue_consumer::InitialQueueTriggers;
 use crate::core::queue_consumer::QueueTriggers;
 use crate::core::ribosome::guest_callback::init::InitResult;
 use crate::core::ribosome::real_ribosome::RealRibosome;
 use crate::core::ribosome::ZomeCallInvocation;
+use crate::core::workflow::call_zome_dry_run_workflow;
 use crate::core::workflow::call_zome_workflow;
 use crate::core::workflow::countersigning_workflow::countersigning_success;
 use crate::core::workflow::genesis_workflow::genesis_workflow;
@@ -46,12 +49,15 @@ use crate::core::workflow::CallZomeWorkflowArgs;
 use crate::core::workflow::GenesisWorkflowArgs;
 use crate::core::workflow::GenesisWorkspace;
 use crate::core::workflow::InitializeZomesWorkflowArgs;
+use crate::core::workflow::ZomeCallDryRunOutcome;
 use crate::core::workflow::ZomeCallResult;
 use crate::{conductor::api::error::ConductorApiError, core::ribosome::RibosomeT};
 
 use super::api::CellConductorHandle;
+use super::metrics::{create_zome_call_duration_metric, ZomeCallDurationMetric};
 use super::space::Space;
 use super::ConductorHandle;
+use opentelemetry_api::KeyValue;
 
 pub const INIT_MUTEX_TIMEOUT_SECS: u64 = 30;
 
@@ -102,8 +108,9 @@ pub struct Cell {
     space: Space,
     holochain_p2p_cell: HolochainP2pDna,
     queue_triggers: QueueTriggers,
-    signal_tx: broadcast::Sender<Signal>,
+    signal_tx: SignalSender,
     init_mutex: tokio::sync::Mutex<()>,
+    zome_call_duration_metric: ZomeCallDurationMetric,
 }
 
 impl Cell {
@@ -120,7 +127,7 @@ impl Cell {
         conductor_handle: ConductorHandle,
         space: Space,
         holochain_p2p_cell: HolochainP2pDna,
-        signal_tx: broadcast::Sender<Signal>,
+        signal_tx: SignalSender,
     ) -> CellResult<(Self, InitialQueueTriggers)> {
         let conductor_api = Arc::new(CellConductorApi::new(conductor_handle.clone(), id.clone()));
         let authored_db = space.get_or_create_authored_db(id.agent_pubkey().clone())?;
@@ -143,6 +150,7 @@ impl Cell {
             .await
             .map_err(Box::new)?;
 
+            let zome_call_duration_metric = create_zome_call_duration_metric(&id);
             Ok((
                 Self {
                     id,
@@ -153,6 +161,7 @@ impl Cell {
                     queue_triggers,
                     signal_tx,
                     init_mutex: Default::default(),
+                    zome_call_duration_metric,
                 },
                 initial_queue_triggers,
             ))
@@ -205,6 +214,7 @@ impl Cell {
             ribosome,
             dht_db_cache,
             chc,
+            conductor_handle.spaces.clock.clone(),
         );
 
         genesis_workflow(workspace, conductor_api, args)
@@ -392,6 +402,8 @@ impl Cell {
             | QueryAgentInfoSignedNearBasis { .. }
             | QueryPeerDensity { .. }
             | Publish { .. }
+            | HandoffOps { .. }
+            | OpCensus { .. }
             | FetchOpData { .. } => {
                 // These events are aggregated over a set of cells, so need to be handled at the conductor level.
                 unreachable!()
@@ -709,11 +721,7 @@ impl Cell {
     async fn handle_count_links(&self, query: WireLinkQuery) -> CellResult<CountLinksResponse> {
         let db = self.space.dht_db.clone();
         Ok(CountLinksResponse::new(
-            authority::handle_get_links_query(db.into(), query)
-                .await?
-                .into_iter()
-                .map(|l| l.create_link_hash)
-                .collect::<Vec<_>>(),
+            authority::handle_get_links_timestamps(db.into(), query).await?,
         ))
     }
 
@@ -736,10 +744,16 @@ impl Cell {
         author: AgentPubKey,
         filter: holochain_zome_types::chain::ChainFilter,
     ) -> CellResult<MustGetAgentActivityResponse> {
+        let cache = &self.space.dht_query_cache;
+        if let Some(response) = cache.get_chain_subset(&author, &filter) {
+            return Ok(response);
+        }
         let db = self.space.dht_db.clone();
-        authority::handle_must_get_agent_activity(db.into(), author, filter)
-            .await
-            .map_err(Into::into)
+        let response =
+            authority::handle_must_get_agent_activity(db.into(), author.clone(), filter.clone())
+                .await?;
+        cache.put_chain_subset(author, filter, &response);
+        Ok(response)
     }
 
     /// A remote agent is sending us a validation receipt bundle.
@@ -775,36 +789,15 @@ impl Cell {
                 })
                 .await?;
 
-            // If the action has an app entry type get the entry def
-            // from the conductor.
-            let required_receipt_count = match action.as_ref().and_then(|h| h.entry_type()) {
-                Some(EntryType::App(AppEntryDef {
-                    zome_index,
-                    entry_index,
-                    ..
-                })) => {
-                    let ribosome = self.conductor_api.get_this_ribosome().map_err(Box::new)?;
-                    let zome = ribosome.get_integrity_zome(zome_index);
-                    match zome {
-                        Some(zome) => self
-                            .conductor_api
-                            .get_entry_def(&EntryDefBufferKey::new(
-                                zome.into_inner().1,
-                                *entry_index,
-                            ))
-                            .map(|e| u8::from(e.required_validations)),
-                        None => None,
-                    }
-                }
-                _ => None,
-            };
-
-            // If no required receipt count was found then fallback to the default.
-            let required_validation_count = required_receipt_count.unwrap_or(
-                crate::core::workflow::publish_dht_ops_workflow::DEFAULT_RECEIPT_BUNDLE_SIZE,
+            let ribosome = self.conductor_api.get_this_ribosome().map_err(Box::new)?;
+            let required_validation_count = self.conductor_handle.required_validation_receipts(
+                &ribosome,
+                action.as_ref().and_then(|h| h.entry_type()),
             );
 
             let receipt_op_hash = receipt.receipt.dht_op_hash.clone();
+            let receipt_status = receipt.receipt.validation_status;
+            let receipt_validators = receipt.receipt.validators.clone();
 
             let receipt_count = self
                 .space
@@ -843,6 +836,13 @@ impl Cell {
                 })
                 .await?;
 
+            record_op_event(&receipt_op_hash, OpTraceStage::ReceiptReceived, || {
+                format!(
+                    "as {:?}, from {:?}, making {} of {} required",
+                    receipt_status, receipt_validators, receipt_count, required_validation_count
+                )
+            });
+
             // If we have enough receipts then set receipts to complete.
             if receipt_count >= required_validation_count as usize {
                 // Note that the flag is set in the authored db because that's what the publish workflow checks to decide
@@ -891,6 +891,7 @@ impl Cell {
             fn_name,
             nonce,
             expires_at,
+            delegation: Vec::new(),
         };
         // double ? because
         // - ConductorApiResult
@@ -908,6 +909,44 @@ impl Cell {
         &self,
         call: ZomeCall,
         workspace_lock: Option<SourceChainWorkspace>,
+    ) -> CellResult<ZomeCallResult> {
+        let start = std::time::Instant::now();
+        let started_at = Timestamp::now();
+        let zome_name = call.zome_name.clone();
+        let fn_name = call.fn_name.clone();
+        let span = tracing::info_span!(
+            "call_zome",
+            dna_hash = %self.id.dna_hash(),
+            agent = %self.id.agent_pubkey(),
+            zome = %zome_name,
+            fn_name = %fn_name,
+        );
+        let (result, db_wait) = holochain_sqlite::db::measure_db_wait(
+            self.call_zome_inner(call, workspace_lock).instrument(span),
+        )
+        .await;
+        let elapsed = start.elapsed();
+        self.conductor_handle
+            .slow_operations()
+            .zome_call(&self.id, &zome_name, &fn_name, started_at, elapsed, db_wait);
+        self.zome_call_duration_metric.record(
+            elapsed.as_secs_f64(),
+            &[
+                KeyValue::new("zome", zome_name.to_string()),
+                KeyValue::new("fn", fn_name.to_string()),
+                KeyValue::new(
+                    "is_error",
+                    !matches!(result, Ok(Ok(ZomeCallResponse::Ok(_)))),
+                ),
+            ],
+        );
+        result
+    }
+
+    async fn call_zome_inner(
+        &self,
+        call: ZomeCall,
+        workspace_lock: Option<SourceChainWorkspace>,
     ) -> CellResult<ZomeCallResult> {
         // Only check if init has run if this call is not coming from
         // an already running init call.
@@ -931,18 +970,17 @@ impl Cell {
         let is_root_zome_call = workspace_lock.is_none();
         let workspace_lock = match workspace_lock {
             Some(l) => l,
-            None => {
-                SourceChainWorkspace::new(
-                    self.get_or_create_authored_db()?,
-                    self.dht_db().clone(),
-                    self.space.dht_query_cache.clone(),
-                    self.cache().clone(),
-                    keystore.clone(),
-                    self.id.agent_pubkey().clone(),
-                    Arc::new(dna_def),
-                )
-                .await?
-            }
+            None => SourceChainWorkspace::new(
+                self.get_or_create_authored_db()?,
+                self.dht_db().clone(),
+                self.space.dht_query_cache.clone(),
+                self.cache().clone(),
+                keystore.clone(),
+                self.id.agent_pubkey().clone(),
+                Arc::new(dna_def),
+            )
+            .await?
+            .with_clock(self.space.clock.clone()),
         };
         let args = CallZomeWorkflowArgs {
             cell_id: self.id.clone(),
@@ -965,6 +1003,43 @@ impl Cell {
         .map_err(Box::new)?)
     }
 
+    /// Make a zome call without writing or publishing what it commits, and return what
+    /// it would have committed along with whether that would have passed validation.
+    /// The signals which the call emits aren't sent.
+    pub async fn call_zome_dry_run(&self, call: ZomeCall) -> CellResult<ZomeCallDryRunOutcome> {
+        self.check_or_run_zome_init().await?;
+
+        let keystore = self.conductor_api.keystore().clone();
+        let ribosome = self.get_ribosome()?;
+        let invocation =
+            ZomeCallInvocation::try_from_interface_call(self.conductor_api.clone(), call).await?;
+        let dna_def = ribosome.dna_def().as_content().clone();
+        let workspace = SourceChainWorkspace::new(
+            self.get_or_create_authored_db()?,
+            self.dht_db().clone(),
+            self.space.dht_query_cache.clone(),
+            self.cache().clone(),
+            keystore.clone(),
+            self.id.agent_pubkey().clone(),
+            Arc::new(dna_def),
+        )
+        .await?
+        .with_clock(self.space.clock.clone());
+        let args = CallZomeWorkflowArgs {
+            cell_id: self.id.clone(),
+            ribosome,
+            invocation,
+            signal_tx: SignalSender::new(),
+            conductor_handle: self.conductor_handle.clone(),
+            is_root_zome_call: false,
+        };
+        Ok(
+            call_zome_dry_run_workflow(workspace, self.holochain_p2p_cell.clone(), keystore, args)
+                .await
+                .map_err(Box::new)?,
+        )
+    }
+
     /// Check if each Zome's init callback has been run, and if not, run it.
     #[cfg_attr(feature = "instrument", tracing::instrument(skip(self)))]
     pub(crate) async fn check_or_run_zome_init(&self) -> CellResult<()> {
@@ -996,7 +1071,8 @@ impl Cell {
             id.agent_pubkey().clone(),
             Arc::new(dna_def.into_content()),
         )
-        .await?;
+        .await?
+        .with_clock(self.space.clock.clone());
 
         // Check if initialization has run
         if workspace.source_chain().zomes_initialized().await? {
@@ -88,3 +88,67 @@ async fn agent_info_test() {
         assert_eq!(len, 2);
     }
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn paused_gossip_holds_back_ops_until_resumed() {
+    holochain_trace::test_run();
+    let config = SweetConductorConfig::standard().no_publish();
+    let mut conductors = SweetConductorBatch::from_config(2, config).await;
+
+    let (dna_file, _, _) =
+        SweetDnaFile::unique_from_inline_zomes(("zome", simple_create_read_zome())).await;
+
+    let apps = conductors
+        .setup_app("app", &[dna_file.clone()])
+        .await
+        .unwrap();
+    let ((cell_1,), (cell_2,)) = apps.into_tuples();
+    for conductor in conductors.iter() {
+        conductor
+            .raw_handle()
+            .set_gossip_pause(dna_file.dna_hash().clone(), kitsune_p2p::GossipPause::All)
+            .await
+            .unwrap();
+    }
+    conductors.exchange_peer_info().await;
+
+    let _: ActionHash = conductors[0].call(&cell_1.zome("zome"), "create", ()).await;
+
+    // - Without publish or gossip, the op can't reach the other conductor.
+    assert!(await_consistency(5, [&cell_1, &cell_2]).await.is_err());
+
+    for conductor in conductors.iter() {
+        conductor
+            .raw_handle()
+            .set_gossip_pause(
+                dna_file.dna_hash().clone(),
+                kitsune_p2p::GossipPause::Running,
+            )
+            .await
+            .unwrap();
+    }
+    await_consistency(60, [&cell_1, &cell_2]).await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn partitioned_conductors_sync_once_healed() {
+    holochain_trace::test_run();
+    let config = SweetConductorConfig::standard().switchboard("partitioned-conductors");
+    let mut conductors = SweetConductorBatch::from_config(2, config).await;
+    let switchboard = kitsune_p2p::switchboard::Switchboard::named("partitioned-conductors");
+
+    let (dna_file, _, _) =
+        SweetDnaFile::unique_from_inline_zomes(("zome", simple_create_read_zome())).await;
+
+    let apps = conductors.setup_app("app", &[dna_file]).await.unwrap();
+    let ((cell_1,), (cell_2,)) = apps.into_tuples();
+    conductors.exchange_peer_info().await;
+
+    // - Put each conductor in its own partition.
+    switchboard.partition(switchboard.node_urls().into_iter().map(|url| vec![url]));
+    let _: ActionHash = conductors[0].call(&cell_1.zome("zome"), "create", ()).await;
+    assert!(await_consistency(5, [&cell_1, &cell_2]).await.is_err());
+
+    switchboard.heal();
+    await_consistency(60, [&cell_1, &cell_2]).await.unwrap();
+}
@@ -1,3 +1,4 @@
+use crate::conductor::conductor::SignalSender;
 use crate::conductor::space::TestSpaces;
 use crate::conductor::Conductor;
 use crate::core::ribosome::real_ribosome::{ModuleCacheLock, RealRibosome};
@@ -9,7 +10,6 @@ use holochain_state::prelude::*;
 use holochain_wasmer_host::module::ModuleCache;
 use holochain_zome_types::action;
 use std::sync::Arc;
-use tokio::sync::broadcast;
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_cell_handle_publish() {
@@ -72,7 +72,7 @@ async fn test_cell_handle_publish() {
         handle.clone(),
         spaces.test_spaces[&dna].space.clone(),
         holochain_p2p_cell,
-        broadcast::channel(10).0,
+        SignalSender::new(),
     )
     .await
     .unwrap();
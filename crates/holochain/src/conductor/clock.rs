@@ -0,0 +1,111 @@
+//! Estimate how far the clock of the conductor is from the clocks of its peers, warn
+//! when it passes the `clock_skew_threshold` of the [`ConductorTuningParams`], and
+//! correct the [`Clock`] of the conductor for it if `clock_skew_correction` is on.
+
+use holochain_conductor_api::conductor::ConductorTuningParams;
+use holochain_conductor_api::ClockSkewReport;
+use holochain_p2p::AgentPubKeyExt;
+use holochain_types::clock::{Clock, ClockSkewEstimator};
+use holochain_types::prelude::*;
+use kitsune_p2p::agent_store::AgentInfoSigned;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+/// How long the agent infos which a peer published are taken into account, which is
+/// how long agent infos last before they're refreshed.
+const SKEW_WINDOW: Duration = Duration::from_secs(20 * 60);
+
+/// The fewest peers whose agent infos the skew is estimated from.
+const MIN_PEERS: usize = 3;
+
+/// The furthest the skew is estimated to be, so that peers which agree on a wrong
+/// time can't push the clock of the conductor arbitrarily far when it's corrected.
+const MAX_SKEW: Duration = Duration::from_secs(10 * 60);
+
+/// The estimated skew of the clock of the conductor.
+pub(crate) struct ClockSkewMonitor {
+    threshold: Duration,
+    correct: bool,
+    estimator: parking_lot::Mutex<ClockSkewEstimator>,
+    /// The clock of the conductor, which is corrected for the skew.
+    clock: Clock,
+    /// Whether the skew was past the threshold when it was last estimated, so that
+    /// it's only logged when that changes.
+    skewed: AtomicBool,
+}
+
+impl ClockSkewMonitor {
+    pub(crate) fn new(tuning_params: &ConductorTuningParams, clock: Clock) -> Self {
+        Self {
+            threshold: tuning_params.clock_skew_threshold(),
+            correct: tuning_params.clock_skew_correction(),
+            estimator: parking_lot::Mutex::new(ClockSkewEstimator::new(
+                SKEW_WINDOW,
+                MIN_PEERS,
+                MAX_SKEW,
+            )),
+            clock,
+            skewed: AtomicBool::new(false),
+        }
+    }
+
+    /// Take into account when the agent infos which peers published were signed.
+    /// The agent infos of local agents are skipped, as they were signed by this clock.
+    pub(crate) fn observe_agent_infos(
+        &self,
+        agent_infos: &[AgentInfoSigned],
+        local_agents: &HashSet<AgentPubKey>,
+    ) {
+        let estimate = {
+            let mut estimator = self.estimator.lock();
+            for info in agent_infos {
+                let agent = AgentPubKey::from_kitsune(&info.agent);
+                if local_agents.contains(&agent) {
+                    continue;
+                }
+                let signed_at =
+                    Timestamp::from_micros((info.signed_at_ms as i64).saturating_mul(1000));
+                estimator.observe(agent, signed_at);
+            }
+            estimator.estimate()
+        };
+        let Some(skew) = estimate else {
+            return;
+        };
+        let skewed = self.is_past_threshold(skew);
+        if skewed != self.skewed.swap(skewed, Ordering::Relaxed) {
+            if skewed {
+                tracing::warn!(
+                    skew_ms = skew / 1000,
+                    correcting = self.correct,
+                    "The clock of this conductor is skewed from the clocks of its peers, which can make the timestamps of its actions invalid"
+                );
+            } else {
+                tracing::info!(
+                    skew_ms = skew / 1000,
+                    "The clock of this conductor is no longer skewed from the clocks of its peers"
+                );
+            }
+        }
+        if self.correct {
+            self.clock.set_correction(if skewed { skew } else { 0 });
+        }
+    }
+
+    /// How far the clock is estimated to be from the clocks of peers.
+    pub(crate) fn report(&self) -> ClockSkewReport {
+        let mut estimator = self.estimator.lock();
+        let estimated_skew_micros = estimator.estimate();
+        ClockSkewReport {
+            estimated_skew_micros,
+            peers: estimator.peers(),
+            skewed: estimated_skew_micros.map_or(false, |skew| self.is_past_threshold(skew)),
+            correction_micros: self.clock.correction(),
+        }
+    }
+
+    fn is_past_threshold(&self, skew: i64) -> bool {
+        skew.unsigned_abs() as u128 >= self.threshold.as_micros()
+    }
+}
@@ -60,14 +60,24 @@ pub use agent_key_operations::RevokeAgentKeyForAppResult;
 pub use builder::*;
 use holo_hash::DnaHash;
 use holochain_conductor_api::conductor::{DpkiConfig, KeystoreConfig};
+use holochain_conductor_api::AgentKeyDerivation;
+use holochain_conductor_api::AgentKeyInfo;
 use holochain_conductor_api::AppInfo;
 use holochain_conductor_api::AppStatusFilter;
+use holochain_conductor_api::AppSummary;
 use holochain_conductor_api::FullIntegrationStateDump;
 use holochain_conductor_api::FullStateDump;
 use holochain_conductor_api::IntegrationStateDump;
 use holochain_conductor_api::JsonDump;
+use holochain_conductor_api::OpTraceEvent;
+use holochain_conductor_api::Page;
+use holochain_conductor_api::SlowOperation;
+use holochain_conductor_api::StartupReport;
+use holochain_conductor_api::ZomeCallNonceWindow;
+use holochain_conductor_api::{MemproofProgress, RoleGenesisStatus};
 pub use holochain_conductor_services::*;
 use holochain_keystore::lair_keystore::spawn_lair_keystore;
+use holochain_keystore::lair_keystore::spawn_lair_keystore_embedded;
 use holochain_keystore::lair_keystore::spawn_lair_keystore_in_proc;
 use holochain_keystore::MetaLairClient;
 use holochain_p2p::actor::HolochainP2pRefToDna;
@@ -81,18 +91,27 @@ use holochain_state::nonce::WitnessNonceResult;
 use holochain_state::prelude::*;
 use holochain_state::source_chain;
 pub use holochain_types::share;
+use holochain_websocket::AllowedOriginsHandle;
 use holochain_zome_types::prelude::{ClonedCell, Signature, Timestamp};
 use kitsune_p2p::agent_store::AgentInfoSigned;
 
 use crate::conductor::cell::Cell;
+use crate::conductor::clock::ClockSkewMonitor;
 use crate::conductor::conductor::app_auth_token_store::AppAuthTokenStore;
 use crate::conductor::conductor::app_broadcast::AppBroadcast;
+use crate::conductor::conductor::app_connections::AppConnections;
+use crate::conductor::conductor::backfill::BackfillTracker;
+use crate::conductor::conductor::remote_outbox::RemoteOutbox;
+use crate::conductor::conductor::zome_call_scheduler::{ZomeCallPermit, ZomeCallScheduler};
+use crate::conductor::conductor::zome_calls_in_flight::ZomeCallsInFlight;
 use crate::conductor::config::ConductorConfig;
 use crate::conductor::error::ConductorResult;
 use crate::conductor::metrics::create_p2p_event_duration_metric;
 use crate::conductor::p2p_agent_store::get_single_agent_info;
 use crate::conductor::p2p_agent_store::list_all_agent_info;
 use crate::conductor::p2p_agent_store::query_peer_density;
+use crate::conductor::slow_operations::SlowOperationLog;
+use crate::conductor::workflow_runtimes::{WorkflowRuntime, WorkflowRuntimes};
 use crate::core::queue_consumer::InitialQueueTriggers;
 use crate::core::queue_consumer::QueueConsumerMap;
 #[cfg(any(test, feature = "test_utils"))]
@@ -102,6 +121,7 @@ use crate::core::ribosome::guest_callback::post_commit::POST_COMMIT_CHANNEL_BOUN
 use crate::core::ribosome::guest_callback::post_commit::POST_COMMIT_CONCURRENT_LIMIT;
 use crate::core::ribosome::real_ribosome::ModuleCacheLock;
 use crate::core::ribosome::RibosomeT;
+use crate::core::workflow::ZomeCallDryRunOutcome;
 use crate::core::workflow::ZomeCallResult;
 use crate::{
     conductor::api::error::ConductorApiResult, core::ribosome::real_ribosome::RealRibosome,
@@ -110,6 +130,7 @@ use crate::{
 use super::api::AppInterfaceApi;
 use super::api::ZomeCall;
 use super::config::AdminInterfaceConfig;
+use super::config::ConductorTuningParams;
 use super::config::InterfaceDriver;
 use super::entry_def_store::get_entry_defs;
 use super::error::ConductorError;
@@ -136,8 +157,14 @@ mod chc;
 
 mod graft_records_onto_source_chain;
 
+mod health_check;
+pub(crate) use health_check::serve_health_check;
+
 mod app_auth_token_store;
 
+#[cfg(test)]
+mod app_status_model;
+
 /// Operations to manipulate agent keys.
 ///
 /// Agent keys are handled in 2 places in Holochain, on the source chain of a cell and in the
@@ -149,7 +176,76 @@ mod app_auth_token_store;
 /// be reverted or changed.
 mod agent_key_operations;
 
+/// Purging the ops of ephemeral entries from the DHT once they expire.
+mod expired_ops;
+
+/// How many validation receipts authored ops need, and which ops don't have them yet.
+mod validation_receipts;
+
+/// Checking the chain heads and databases of cells when the conductor starts.
+mod startup_check;
+
+/// Prefetching the hashes which cells read most often from the network.
+mod prefetch;
+
+/// Delivering the remote calls and signals which cells queued for other agents.
+mod outbox;
+
+/// Exporting integrated ops into archives, and importing them again.
+mod dht_op_archive;
+
+/// Exporting the data which the agent of a cell authored, for data portability.
+mod data_export;
+
+/// Exporting proofs that actions were published, for audits.
+mod publication_proof;
+
+/// Auditing the membrane proofs of the agents of a DNA against its genesis self check.
+mod membrane_proof_recheck;
+
+/// Handing off the ops which fall outside the storage arcs of local agents to other authorities.
+mod arc_handoff;
+
+/// Pinning the storage arcs of cells, rather than letting them be sized dynamically.
+mod arq_pins;
+
+/// Estimating the redundancy of the regions of the DHT which local agents hold.
+mod redundancy;
+
+/// Publishing authored ops again when a spot check finds that their authorities lost them.
+mod publish_audit;
+
+/// The audit log of admin requests which changed the state of the conductor.
+mod audit_log;
+
+/// Snapshots of the work which the conductor is doing, for watching it live.
+mod diagnostics;
+
+/// Tracking how far newly joined cells are with getting the ops of their storage arcs.
+pub(crate) mod backfill;
+
+/// Refusing incoming ops while the validation backlog or memory usage is too high.
+mod ingest_admission;
+
+/// The cells of apps installed as pure clients of the network.
+mod client_cells;
+
+/// Rolling source chains back to an earlier action before anything after it is published.
+mod chain_rollback;
+
+/// Remote calls to the cells of other DNAs than the calling cell's.
+mod remote_cell_calls;
+
 pub(crate) mod app_broadcast;
+pub use app_broadcast::{SignalReceiver, SignalSender};
+
+pub(crate) mod app_connections;
+
+pub(crate) mod zome_calls_in_flight;
+
+pub(crate) mod zome_call_scheduler;
+
+pub(crate) mod remote_outbox;
 
 #[cfg(test)]
 pub mod tests;
@@ -226,6 +322,13 @@ pub struct Conductor {
     /// The config used to create this Conductor
     pub config: Arc<ConductorConfig>,
 
+    /// The file the config was loaded from, if any.
+    config_path: Option<PathBuf>,
+
+    /// The config with the settings which were changed by reloading the config file
+    /// applied. Settings which need a restart are kept as they were at startup.
+    running_config: RwShare<ConductorConfig>,
+
     /// The map of dna hash spaces.
     pub(crate) spaces: Spaces,
 
@@ -238,6 +341,10 @@ pub struct Conductor {
     /// the dynamically allocated port later.
     admin_websocket_ports: RwShare<Vec<u16>>,
 
+    /// The origins each admin interface accepts connections from, in the order
+    /// the interfaces were added, which is their order in the config.
+    admin_interface_origins: RwShare<Vec<AllowedOriginsHandle>>,
+
     /// The interface to the task manager
     task_manager: TaskManagerClient,
 
@@ -262,6 +369,9 @@ pub struct Conductor {
     pub(crate) running_services: RwShare<ConductorServices>,
 
     /// File system and in-memory cache for wasmer modules.
+    ///
+    /// Modules are keyed by the hash of their wasm, so every cell that runs the same
+    /// zome, including clone cells and cells of other apps, shares one compiled module.
     // Used in ribosomes but kept here as a single instance.
     pub(crate) wasmer_module_cache: Arc<ModuleCacheLock>,
 
@@ -269,6 +379,42 @@ pub struct Conductor {
 
     /// Container to connect app signals to app interfaces, by installed app id.
     app_broadcast: AppBroadcast,
+
+    /// The clients which are connected to app interfaces.
+    app_connections: AppConnections,
+
+    /// The progress of providing memproofs for apps installed with deferred memproofs,
+    /// since the conductor started.
+    memproof_progress: RwShare<HashMap<InstalledAppId, MemproofProgress>>,
+
+    /// Zome calls and workflow runs which took longer than expected.
+    slow_operations: Arc<SlowOperationLog>,
+
+    /// How far the clock is estimated to be from the clocks of peers.
+    clock_skew: ClockSkewMonitor,
+
+    /// The runtimes which the workflows of cells run on, if any
+    workflow_runtimes: Arc<WorkflowRuntimes>,
+
+    /// The zome calls which are in progress, which are waited for on shutdown.
+    zome_calls_in_flight: Arc<ZomeCallsInFlight>,
+
+    /// The turns of the zome calls which apps make through their interfaces.
+    zome_call_scheduler: Arc<ZomeCallScheduler>,
+
+    /// The checks which were run on the cells when the conductor started,
+    /// if `verify_integrity_on_startup` is set.
+    startup_report: RwShare<Option<StartupReport>>,
+
+    /// The remote calls and signals which cells queued for other agents.
+    remote_outbox: RemoteOutbox,
+
+    /// The backfill progress of the running cells, between measurements.
+    backfill_trackers: RwShare<HashMap<CellId, BackfillTracker>>,
+
+    /// The cells of apps installed with `client_only`, as of when cells last joined
+    /// the network.
+    client_cells: RwShare<HashSet<CellId>>,
 }
 
 impl Conductor {
@@ -292,13 +438,14 @@ mod startup_shutdown_impls {
         #[allow(clippy::too_many_arguments)]
         pub(crate) fn new(
             config: Arc<ConductorConfig>,
+            config_path: Option<PathBuf>,
             ribosome_store: RwShare<RibosomeStore>,
             keystore: MetaLairClient,
             holochain_p2p: holochain_p2p::HolochainP2pRef,
             spaces: Spaces,
             post_commit: tokio::sync::mpsc::Sender<PostCommitArgs>,
             outcome_sender: OutcomeSender,
-        ) -> Self {
+        ) -> ConductorResult<Self> {
             let tracing_scope = config.tracing_scope().unwrap_or_default();
             let maybe_data_root_path = config.data_root_path.clone().map(|path| (*path).clone());
 
@@ -309,16 +456,19 @@ mod startup_shutdown_impls {
                 // best effort to ensure the cache dir exists if configured
                 let _ = std::fs::create_dir_all(&path);
             }
+            let clock_skew =
+                ClockSkewMonitor::new(&config.conductor_tuning_params(), spaces.clock.clone());
+            let workflow_runtimes = WorkflowRuntimes::new(&config.conductor_tuning_params())?;
 
-            Self {
+            Ok(Self {
                 spaces,
                 running_cells: RwShare::new(HashMap::new()),
-                config,
                 shutting_down: Arc::new(AtomicBool::new(false)),
                 task_manager: TaskManagerClient::new(outcome_sender, tracing_scope),
                 // Must be initialized later, since it requires an Arc<Conductor>
                 outcomes_task: RwShare::new(None),
                 admin_websocket_ports: RwShare::new(Vec::new()),
+                admin_interface_origins: RwShare::new(Vec::new()),
                 scheduler: Arc::new(parking_lot::Mutex::new(None)),
                 ribosome_store,
                 keystore,
@@ -330,7 +480,23 @@ mod startup_shutdown_impls {
                 ))),
                 app_auth_token_store: RwShare::default(),
                 app_broadcast: AppBroadcast::default(),
-            }
+                app_connections: AppConnections::default(),
+                memproof_progress: RwShare::default(),
+                slow_operations: Arc::new(SlowOperationLog::new(&config.conductor_tuning_params())),
+                clock_skew,
+                workflow_runtimes: Arc::new(workflow_runtimes),
+                zome_calls_in_flight: Arc::default(),
+                zome_call_scheduler: Arc::new(ZomeCallScheduler::new(
+                    config.conductor_tuning_params().zome_call_concurrency(),
+                )),
+                startup_report: RwShare::default(),
+                remote_outbox: RemoteOutbox::default(),
+                backfill_trackers: RwShare::default(),
+                client_cells: RwShare::default(),
+                config_path,
+                running_config: RwShare::new((*config).clone()),
+                config,
+            })
         }
 
         /// A gate to put at the top of public functions to ensure that work is not
@@ -352,19 +518,51 @@ mod startup_shutdown_impls {
             self.outcomes_task.share_mut(|tm| tm.take())
         }
 
-        /// Broadcasts the shutdown signal to all managed tasks
-        /// and returns a future to await for shutdown to complete.
+        /// Stops accepting zome calls and broadcasts the shutdown signal to all
+        /// managed tasks, and returns a future to await for shutdown to complete.
+        ///
+        /// In-flight zome calls, the delivery of the signals they emitted and the
+        /// current runs of workflows are given until the `shutdown_drain_timeout`
+        /// of the tuning params to complete, before the network is shut down.
         pub fn shutdown(&self) -> JoinHandle<TaskManagerResult> {
             self.shutting_down
                 .store(true, std::sync::atomic::Ordering::Relaxed);
 
             use ghost_actor::GhostControlSender;
-            let ghost_shutdown = self.holochain_p2p.ghost_actor_shutdown_immediate();
+            let holochain_p2p = self.holochain_p2p.clone();
             let mut tm = self.task_manager();
             let task = self.detach_task_management().expect("Attempting to shut down after already detaching task management or previous shutdown");
+            let zome_calls_in_flight = self.zome_calls_in_flight.clone();
+            let app_broadcast = self.app_broadcast.clone();
+            let deadline = tokio::time::Instant::now()
+                + self.conductor_tuning_params().shutdown_drain_timeout();
             tokio::task::spawn(async move {
+                tracing::info!("Waiting for in-flight zome calls to complete.");
+                let drained = async {
+                    zome_calls_in_flight.until_idle().await;
+                    app_broadcast.until_delivered().await;
+                };
+                if tokio::time::timeout_at(deadline, drained).await.is_err() {
+                    tracing::warn!(
+                        zome_calls = zome_calls_in_flight.count(),
+                        "Zome calls or signals were still in flight at the shutdown deadline."
+                    );
+                }
+
+                // The network is kept up until the workflows have stopped,
+                // so that they can finish publishing.
                 tracing::info!("Sending shutdown signal to all managed tasks.");
-                let (_, _, r) = futures::join!(ghost_shutdown, tm.shutdown().boxed(), task,);
+                let stopped = futures::future::join(tm.shutdown().boxed(), task);
+                let r = match tokio::time::timeout_at(deadline, stopped).await {
+                    Ok((_, r)) => r,
+                    Err(_) => {
+                        tracing::warn!(
+                            "Managed tasks were still running at the shutdown deadline."
+                        );
+                        Ok(Ok(()))
+                    }
+                };
+                let _ = holochain_p2p.ghost_actor_shutdown_immediate().await;
                 r?
             })
         }
@@ -404,15 +602,73 @@ mod startup_shutdown_impls {
 
             info!("Conductor startup: apps started.");
 
+            if self.conductor_tuning_params().verify_integrity_on_startup() {
+                let report = self.check_cells_on_startup().await;
+                info!(
+                    healthy = report.healthy,
+                    "Conductor startup: cells checked."
+                );
+            }
+
             res
         }
     }
 }
 
+/// Methods related to reloading the conductor config
+mod config_impls {
+    use super::*;
+    use holochain_conductor_api::conductor::ConfigReloadReport;
+
+    impl Conductor {
+        /// Read the config file again and apply the settings which can be changed
+        /// while the conductor is running, see [`ConductorConfig::reload`].
+        ///
+        /// If no log levels are set in the reloaded config, logs are filtered by `RUST_LOG`.
+        pub fn reload_config(&self) -> ConductorResult<ConfigReloadReport> {
+            let config_path = self
+                .config_path
+                .as_ref()
+                .ok_or(ConductorError::NoConfigPath)?;
+            let reloaded = ConductorConfig::load_yaml(config_path)?;
+
+            let report = self.running_config.share_mut(|running| {
+                let reload = running.reload(reloaded);
+                if reload.log_filter_changed {
+                    let directives = reload
+                        .config
+                        .log_filter_directives()
+                        .or_else(|| std::env::var("RUST_LOG").ok())
+                        .unwrap_or_default();
+                    holochain_trace::set_filter(&directives).map_err(ConductorError::other)?;
+                }
+                self.admin_interface_origins.share_ref(|handles| {
+                    for (index, allowed_origins) in &reload.admin_interface_origins {
+                        if let Some(handle) = handles.get(*index) {
+                            handle.set(allowed_origins.clone());
+                        }
+                    }
+                });
+                *running = reload.config;
+                ConductorResult::Ok(reload.report)
+            })?;
+
+            info!(
+                applied = ?report.applied,
+                requires_restart = ?report.requires_restart,
+                "Reloaded the conductor config"
+            );
+            Ok(report)
+        }
+    }
+}
+
 /// Methods related to conductor interfaces
 mod interface_impls {
     use super::*;
-    use holochain_conductor_api::AppInterfaceInfo;
+    use holochain_conductor_api::{
+        AppInterfaceConnectionId, AppInterfaceConnectionInfo, AppInterfaceInfo,
+    };
     use holochain_types::websocket::AllowedOrigins;
 
     impl Conductor {
@@ -425,6 +681,7 @@ mod interface_impls {
         ) -> ConductorResult<Vec<u16>> {
             let admin_api = AdminInterfaceApi::new(self.clone());
             let tm = self.task_manager();
+            let max_message_size = self.conductor_tuning_params().websocket_max_message_size();
 
             // Closure to process each admin config item
             let spawn_from_config = |AdminInterfaceConfig { driver, .. }| {
@@ -436,8 +693,11 @@ mod interface_impls {
                             port,
                             allowed_origins,
                         } => {
-                            let listener = spawn_websocket_listener(port, allowed_origins).await?;
+                            let listener =
+                                spawn_websocket_listener(port, allowed_origins, max_message_size)
+                                    .await?;
                             let port = listener.local_addrs()?[0].port();
+                            let allowed_origins = listener.allowed_origins();
                             spawn_admin_interface_tasks(
                                 tm.clone(),
                                 listener,
@@ -445,7 +705,7 @@ mod interface_impls {
                                 port,
                             );
 
-                            InterfaceResult::Ok(port)
+                            InterfaceResult::Ok((port, allowed_origins))
                         }
                     }
                 }
@@ -453,16 +713,20 @@ mod interface_impls {
 
             // spawn interface tasks, collect their JoinHandles,
             // panic on errors.
-            let ports: Result<Vec<_>, _> =
+            let interfaces: Result<Vec<_>, _> =
                 future::join_all(configs.into_iter().map(spawn_from_config))
                     .await
                     .into_iter()
                     .collect();
             // Exit if the admin interfaces fail to be created
-            let ports = ports.map_err(Box::new)?;
-
-            for p in &ports {
-                self.add_admin_port(*p);
+            let interfaces = interfaces.map_err(Box::new)?;
+
+            let mut ports = Vec::with_capacity(interfaces.len());
+            for (port, allowed_origins) in interfaces {
+                self.add_admin_port(port);
+                self.admin_interface_origins
+                    .share_mut(|origins| origins.push(allowed_origins));
+                ports.push(port);
             }
 
             Ok(ports)
@@ -497,6 +761,8 @@ mod interface_impls {
                 installed_app_id.clone(),
                 app_api,
                 self.app_broadcast.clone(),
+                self.app_connections.clone(),
+                self.conductor_tuning_params().websocket_max_message_size(),
             )
             .await
             .map_err(Box::new)?;
@@ -534,6 +800,25 @@ mod interface_impls {
                 .collect())
         }
 
+        /// The clients which are connected to app interfaces
+        pub fn list_app_interface_connections(&self) -> Vec<AppInterfaceConnectionInfo> {
+            self.app_connections.list()
+        }
+
+        /// Close a client's connection to an app interface
+        pub fn disconnect_app_interface_client(
+            &self,
+            connection_id: AppInterfaceConnectionId,
+        ) -> ConductorResult<()> {
+            if self.app_connections.disconnect(connection_id) {
+                Ok(())
+            } else {
+                Err(ConductorError::AppInterfaceConnectionNotFound(
+                    connection_id,
+                ))
+            }
+        }
+
         /// Start all app interfaces currently in state.
         /// This should only be run at conductor initialization.
         #[allow(irrefutable_let_patterns)]
@@ -689,8 +974,11 @@ mod dna_impls {
             // try to join all the tasks and return the list of dna files
             let wasms = wasms.into_iter().map(|(dna_def, wasms)| async move {
                 let dna_file = DnaFile::new(dna_def.into_content(), wasms).await;
-                let ribosome =
-                    RealRibosome::new(dna_file, self.wasmer_module_cache.clone()).await?;
+                let tuning_params = self.conductor_tuning_params();
+                let ribosome = RealRibosome::new(dna_file, self.wasmer_module_cache.clone())
+                    .await?
+                    .with_metering_limit(tuning_params.wasm_metering_limit)
+                    .with_must_get_limits((&tuning_params).into());
                 ConductorResult::Ok((ribosome.dna_hash().clone(), ribosome))
             });
             let dnas = futures::future::try_join_all(wasms).await?;
@@ -817,7 +1105,11 @@ mod dna_impls {
                 return Ok(());
             }
 
-            let ribosome = RealRibosome::new(dna, self.wasmer_module_cache.clone()).await?;
+            let tuning_params = self.conductor_tuning_params();
+            let ribosome = RealRibosome::new(dna, self.wasmer_module_cache.clone())
+                .await?
+                .with_metering_limit(tuning_params.wasm_metering_limit)
+                .with_must_get_limits((&tuning_params).into());
 
             let entry_defs = self.register_dna_wasm(ribosome.clone()).await?;
 
@@ -896,6 +1188,20 @@ mod network_impls {
             .await?)
         }
 
+        /// A fresh nonce and the expiries which are accepted for zome calls right now.
+        pub fn zome_call_nonce_window(&self) -> ConductorResult<ZomeCallNonceWindow> {
+            let now = Timestamp::now();
+            let (nonce, expires_at) =
+                holochain_nonce::fresh_nonce(now).map_err(ConductorError::Other)?;
+            Ok(ZomeCallNonceWindow {
+                nonce,
+                now,
+                expires_at,
+                latest_expires_at: (now + holochain_state::nonce::WITNESSABLE_EXPIRY_DURATION)
+                    .map_err(ConductorError::other)?,
+            })
+        }
+
         /// Block some target.
         pub async fn block(&self, input: Block) -> DatabaseResult<()> {
             self.spaces.block(input).await
@@ -1072,6 +1378,13 @@ mod network_impls {
                     })
                     .count() as u32;
 
+                let region_redundancy = query_region_redundancy(
+                    db.into(),
+                    dna.to_kitsune(),
+                    std::slice::from_ref(agent_pub_key),
+                )
+                .await?;
+
                 ConductorResult::Ok(NetworkInfo {
                     fetch_pool_info,
                     current_number_of_peers,
@@ -1079,6 +1392,7 @@ mod network_impls {
                     total_network_peers,
                     bytes_since_last_time_queried,
                     completed_rounds_since_last_time_queried,
+                    region_redundancy,
                 })
             }))
             .await
@@ -1179,6 +1493,25 @@ mod network_impls {
                 PutAgentInfoSigned {
                     peer_data, respond, ..
                 } => {
+                    use holochain_p2p::AgentPubKeyExt as _;
+
+                    // Agents publish their agent info when they come online.
+                    self.remote_agents_online(
+                        &peer_data
+                            .iter()
+                            .map(|info| AgentPubKey::from_kitsune(&info.agent))
+                            .collect(),
+                    );
+                    // Agent infos are signed shortly before they're published, so they
+                    // show how far the clocks of their agents are from this one.
+                    self.clock_skew.observe_agent_infos(
+                        &peer_data,
+                        &self
+                            .running_cell_ids()
+                            .into_iter()
+                            .map(|cell_id| cell_id.agent_pubkey().clone())
+                            .collect(),
+                    );
                     let sender = self.p2p_batch_sender(&dna_hash);
                     let (result_sender, response) = tokio::sync::oneshot::channel();
                     let _ = sender
@@ -1285,8 +1618,14 @@ mod network_impls {
                     let signature = to_agent.sign_raw(self.keystore(), data.into()).await?;
                     respond.respond(Ok(async move { Ok(signature) }.boxed().into()));
                 }
-                HolochainP2pEvent::CallRemote { .. }
-                | CountersigningSessionNegotiation { .. }
+                HolochainP2pEvent::CallRemote { ref from_agent, .. } => {
+                    self.remote_agents_online(&[from_agent.clone()].into_iter().collect());
+                    let cell_id =
+                        CellId::new(event.dna_hash().clone(), event.target_agents().clone());
+                    let cell = self.cell_by_id(&cell_id).await?;
+                    cell.handle_holochain_p2p_event(event).await?;
+                }
+                CountersigningSessionNegotiation { .. }
                 | Get { .. }
                 | GetMeta { .. }
                 | GetLinks { .. }
@@ -1323,6 +1662,39 @@ mod network_impls {
                     .instrument(debug_span!("handle_publish"))
                     .await;
                 }
+                HandoffOps {
+                    dna_hash,
+                    to_agent,
+                    respond,
+                    ops,
+                    ..
+                } => {
+                    async {
+                        let res = self
+                            .receive_handed_off_ops(&dna_hash, &to_agent, ops)
+                            .await
+                            .map_err(holochain_p2p::HolochainP2pError::other);
+                        respond.respond(Ok(async move { res }.boxed().into()));
+                    }
+                    .instrument(debug_span!("handle_handoff_ops"))
+                    .await;
+                }
+                OpCensus {
+                    dna_hash,
+                    respond,
+                    arq_set,
+                    ..
+                } => {
+                    async {
+                        let res = self
+                            .op_census_regions(&dna_hash, arq_set)
+                            .await
+                            .map_err(holochain_p2p::HolochainP2pError::other);
+                        respond.respond(Ok(async move { res }.boxed().into()));
+                    }
+                    .instrument(debug_span!("handle_op_census"))
+                    .await;
+                }
                 FetchOpData {
                     respond,
                     query,
@@ -1369,10 +1741,35 @@ mod network_impls {
 
         /// Invoke a zome function on a Cell
         pub async fn call_zome(&self, call: ZomeCall) -> ConductorApiResult<ZomeCallResult> {
+            self.check_running()?;
+            let _in_flight = self.zome_calls_in_flight.enter();
             let cell = self.cell_by_id(&call.cell_id).await?;
             Ok(cell.call_zome(call, None).await?)
         }
 
+        /// Make a zome call without writing or publishing what it commits, and return
+        /// what it would have committed along with whether that would have passed
+        /// validation.
+        pub async fn call_zome_dry_run(
+            &self,
+            call: ZomeCall,
+        ) -> ConductorApiResult<ZomeCallDryRunOutcome> {
+            self.check_running()?;
+            let _in_flight = self.zome_calls_in_flight.enter();
+            let cell = self.cell_by_id(&call.cell_id).await?;
+            Ok(cell.call_zome_dry_run(call).await?)
+        }
+
+        /// Wait for the turn of a zome call which an app makes through its interface,
+        /// if `zome_call_concurrency` limits how many of them run at once. The apps
+        /// which are waiting take turns, so that one app can't hold up the others.
+        pub(crate) async fn zome_call_turn(
+            &self,
+            installed_app_id: &InstalledAppId,
+        ) -> Option<ZomeCallPermit> {
+            self.zome_call_scheduler.enter(installed_app_id).await
+        }
+
         pub(crate) async fn call_zome_with_workspace(
             &self,
             call: ZomeCall,
@@ -1433,6 +1830,10 @@ pub struct InstallAppCommonFlags {
     pub ignore_genesis_failure: bool,
     /// From [`InstallAppPayload::allow_throwaway_random_agent_key`]
     pub allow_throwaway_random_agent_key: bool,
+    /// From [`InstallAppPayload::network_profile`]
+    pub network_profile: Option<NetworkProfile>,
+    /// From [`InstallAppPayload::client_only`]
+    pub client_only: bool,
 }
 
 /// Methods related to app installation and management
@@ -1486,6 +1887,8 @@ mod app_impls {
                         defer_memproofs: false,
                         ignore_genesis_failure: false,
                         allow_throwaway_random_agent_key: true,
+                        network_profile: None,
+                        client_only: false,
                     },
                 )
                 .await?;
@@ -1577,13 +1980,15 @@ mod app_impls {
 
             let app_result = if flags.defer_memproofs {
                 let roles = ops.role_assignments;
-                let app = InstalledAppCommon::new(
+                let mut app = InstalledAppCommon::new(
                     installed_app_id.clone(),
                     agent_key.clone(),
                     roles,
                     manifest,
                     Timestamp::now(),
                 )?;
+                app.network_profile = flags.network_profile;
+                app.client_only = flags.client_only;
 
                 let (_, app) = self
                     .update_state_prime(move |mut state| {
@@ -1598,13 +2003,15 @@ mod app_impls {
 
                 if genesis_result.is_ok() || flags.ignore_genesis_failure {
                     let roles = ops.role_assignments;
-                    let app = InstalledAppCommon::new(
+                    let mut app = InstalledAppCommon::new(
                         installed_app_id.clone(),
                         agent_key.clone(),
                         roles,
                         manifest,
                         Timestamp::now(),
                     )?;
+                    app.network_profile = flags.network_profile;
+                    app.client_only = flags.client_only;
 
                     // Update the db
                     let stopped_app = self.add_disabled_app_to_db(app).await?;
@@ -1677,10 +2084,26 @@ mod app_impls {
                 network_seed,
                 ignore_genesis_failure,
                 allow_throwaway_random_agent_key,
+                network_profile,
+                client_only,
             } = payload;
 
+            // Refuse the app up front if the network config can never satisfy its profile.
+            if let Some(profile) = &network_profile {
+                profile
+                    .restrict(&self.get_config().network)
+                    .map_err(ConductorError::other)?;
+            }
+
             let bundle = {
                 let original_bundle = source.resolve().await?;
+                // The signature covers the bundle as published, so check it
+                // before any overrides are applied to the manifest.
+                let publisher = original_bundle.verify_signature().await?;
+                self.get_config()
+                    .bundle_signing
+                    .check_publisher(publisher.as_ref())
+                    .map_err(AppBundleError::UntrustedPublisher)?;
                 if let Some(network_seed) = network_seed {
                     let mut manifest = original_bundle.manifest().to_owned();
                     manifest.set_network_seed(network_seed);
@@ -1702,6 +2125,8 @@ mod app_impls {
                 defer_memproofs,
                 ignore_genesis_failure,
                 allow_throwaway_random_agent_key,
+                network_profile,
+                client_only,
             };
 
             let membrane_proofs = membrane_proofs.unwrap_or_default();
@@ -1771,6 +2196,9 @@ mod app_impls {
                     .map(|(app_id, _)| app_id.clone())
                     .collect::<HashSet<_>>();
                 self.app_broadcast.retain(installed_app_ids);
+                self.memproof_progress.share_mut(|all_progress| {
+                    all_progress.remove(installed_app_id);
+                });
 
                 Ok(())
             } else {
@@ -1799,52 +2227,163 @@ mod app_impls {
             &self,
             status_filter: Option<AppStatusFilter>,
         ) -> ConductorResult<Vec<AppInfo>> {
-            use AppStatusFilter::*;
+            self.find_apps(status_filter, None, None).await
+        }
+
+        /// List the Apps with a status and a cell of a DNA with their information,
+        /// sorted by their installed_at timestamp, in descending order,
+        /// and select a page of them
+        #[cfg_attr(feature = "instrument", tracing::instrument(skip_all))]
+        pub async fn find_apps(
+            &self,
+            status_filter: Option<AppStatusFilter>,
+            dna_hash: Option<&DnaHash>,
+            page: Option<Page>,
+        ) -> ConductorResult<Vec<AppInfo>> {
             let conductor_state = self.get_state().await?;
+            self.filter_apps(&conductor_state, status_filter, dna_hash, page)
+                .into_iter()
+                .map(|app| {
+                    let dna_definitions = self.get_dna_definitions(app)?;
+                    Ok(AppInfo::from_installed_app(app, &dna_definitions))
+                })
+                .collect()
+        }
 
-            let apps_ids: Vec<&String> = match status_filter {
-                Some(Enabled) => conductor_state
-                    .enabled_apps()
-                    .filter(|(id, _)| is_app(id))
-                    .map(|(id, _)| id)
-                    .collect(),
-                Some(Disabled) => conductor_state
-                    .disabled_apps()
-                    .filter(|(id, _)| is_app(id))
-                    .map(|(id, _)| id)
-                    .collect(),
-                Some(Running) => conductor_state
-                    .running_apps()
-                    .filter(|(id, _)| is_app(id))
-                    .map(|(id, _)| id)
-                    .collect(),
-                Some(Stopped) => conductor_state
-                    .stopped_apps()
-                    .filter(|(id, _)| is_app(id))
-                    .map(|(id, _)| id)
-                    .collect(),
-                Some(Paused) => conductor_state
-                    .paused_apps()
-                    .filter(|(id, _)| is_app(id))
-                    .map(|(id, _)| id)
-                    .collect(),
-                None => conductor_state
-                    .installed_apps_and_services()
-                    .keys()
-                    .filter(|id| is_app(id))
-                    .collect(),
+        /// Summarize the Apps with a status and a cell of a DNA, including the
+        /// storage used by their cells, in the same order as [`Self::find_apps`]
+        #[cfg_attr(feature = "instrument", tracing::instrument(skip_all))]
+        pub async fn list_app_summaries(
+            &self,
+            status_filter: Option<AppStatusFilter>,
+            dna_hash: Option<&DnaHash>,
+            page: Option<Page>,
+        ) -> ConductorResult<Vec<AppSummary>> {
+            let conductor_state = self.get_state().await?;
+            let mut summaries = Vec::new();
+            for app in self.filter_apps(&conductor_state, status_filter, dna_hash, page) {
+                let cell_ids: Vec<CellId> = app.all_cells().collect();
+
+                let mut authored_data_size_on_disk = 0;
+                for cell_id in &cell_ids {
+                    authored_data_size_on_disk += self
+                        .spaces
+                        .get_or_create_authored_db(
+                            cell_id.dna_hash(),
+                            cell_id.agent_pubkey().clone(),
+                        )?
+                        .read_async(get_size_on_disk)
+                        .await?;
+                }
+
+                let dna_hashes: HashSet<&DnaHash> =
+                    cell_ids.iter().map(|cell_id| cell_id.dna_hash()).collect();
+                let mut dna_data_size_on_disk = 0;
+                for dna_hash in dna_hashes {
+                    dna_data_size_on_disk += self
+                        .spaces
+                        .dht_db(dna_hash)?
+                        .read_async(get_size_on_disk)
+                        .await?;
+                    dna_data_size_on_disk += self
+                        .spaces
+                        .cache(dna_hash)?
+                        .read_async(get_size_on_disk)
+                        .await?;
+                }
+
+                summaries.push(AppSummary {
+                    installed_app_id: app.id().clone(),
+                    status: app.status().clone().into(),
+                    enabled: app.status().is_enabled(),
+                    cell_count: cell_ids.len(),
+                    authored_data_size_on_disk,
+                    dna_data_size_on_disk,
+                    installed_at: *app.installed_at(),
+                });
+            }
+            Ok(summaries)
+        }
+
+        /// List the agent keys used by installed apps or derived from the device seed,
+        /// along with the apps and cells which use each key and how it was derived.
+        ///
+        /// Derived keys are listed first, in order of their derivation path.
+        #[cfg_attr(feature = "instrument", tracing::instrument(skip_all))]
+        pub async fn list_agent_keys(&self) -> ConductorResult<Vec<AgentKeyInfo>> {
+            let state = self.get_state().await?;
+            let mut keys: HashMap<AgentPubKey, AgentKeyInfo> = HashMap::new();
+            let new_key_info = |agent_key: &AgentPubKey| AgentKeyInfo {
+                agent_key: agent_key.clone(),
+                apps: vec![],
+                derivation: None,
             };
 
-            let mut app_infos: Vec<AppInfo> = apps_ids
-                .into_iter()
-                .map(|app_id| self.get_app_info_inner(app_id, &conductor_state))
-                .collect::<Result<Vec<_>, _>>()?
-                .into_iter()
-                .flatten()
-                .collect();
-            app_infos.sort_by_key(|app_info| std::cmp::Reverse(app_info.installed_at));
+            for (installed_app_id, app) in state.installed_apps_and_services() {
+                // an app's cells may belong to other agents, e.g. when using
+                // existing cells of another app
+                let mut cells_by_agent: HashMap<AgentPubKey, Vec<CellId>> = HashMap::new();
+                cells_by_agent.entry(app.agent_key().clone()).or_default();
+                for cell_id in app.all_cells() {
+                    cells_by_agent
+                        .entry(cell_id.agent_pubkey().clone())
+                        .or_default()
+                        .push(cell_id);
+                }
+                for (agent_key, cell_ids) in cells_by_agent {
+                    keys.entry(agent_key.clone())
+                        .or_insert_with(|| new_key_info(&agent_key))
+                        .apps
+                        .push((installed_app_id.clone(), cell_ids));
+                }
+            }
 
-            Ok(app_infos)
+            // Derived seeds are found in lair rather than in the conductor state,
+            // so that they can still be found if the conductor state is lost.
+            if let Some(device_seed_lair_tag) = self.get_config().device_seed_lair_tag.clone() {
+                let prefix = format!("{device_seed_lair_tag}.");
+                for entry in self.keystore().lair_client().list_entries().await? {
+                    if let LairEntryInfo::Seed { tag, seed_info } = entry {
+                        // if the tag is "{device_seed_lair_tag}.1.2", the path is [1, 2]
+                        let derivation_path = tag.strip_prefix(&prefix).and_then(|path| {
+                            path.split('.')
+                                .map(|c| c.parse::<u32>().ok())
+                                .collect::<Option<Vec<_>>>()
+                        });
+                        if let Some(derivation_path) = derivation_path {
+                            let agent_key =
+                                AgentPubKey::from_raw_32(seed_info.ed25519_pub_key.0.to_vec());
+                            keys.entry(agent_key.clone())
+                                .or_insert_with(|| new_key_info(&agent_key))
+                                .derivation = Some(AgentKeyDerivation {
+                                device_seed_lair_tag: device_seed_lair_tag.clone(),
+                                derivation_path,
+                                lair_tag: tag.to_string(),
+                            });
+                        }
+                    }
+                }
+            }
+
+            let mut keys: Vec<_> = keys.into_values().collect();
+            for key in keys.iter_mut() {
+                key.apps.sort();
+            }
+            keys.sort_by(|a, b| {
+                let path = |key: &AgentKeyInfo| {
+                    key.derivation
+                        .as_ref()
+                        .map(|derivation| derivation.derivation_path.clone())
+                };
+                match (path(a), path(b)) {
+                    (Some(a_path), Some(b_path)) => a_path.cmp(&b_path),
+                    (Some(_), None) => std::cmp::Ordering::Less,
+                    (None, Some(_)) => std::cmp::Ordering::Greater,
+                    (None, None) => a.agent_key.cmp(&b.agent_key),
+                }
+            });
+
+            Ok(keys)
         }
 
         /// Get the IDs of all active installed Apps which use this Cell
@@ -1913,6 +2452,9 @@ mod app_impls {
         }
 
         /// Run genesis for cells of an app which was installed using `allow_deferred_memproofs`
+        ///
+        /// If genesis fails for any cell, the app keeps awaiting memproofs so that they can be
+        /// provided again, and [`ConductorError::MemproofsRejected`] reports the progress.
         pub async fn provide_memproofs(
             self: Arc<Self>,
             installed_app_id: &InstalledAppId,
@@ -1921,17 +2463,82 @@ mod app_impls {
             let state = self.get_state().await?;
 
             let app = state.get_app(installed_app_id)?;
-            let cells_to_genesis = app
+            if *app.status() != AppStatus::AwaitingMemproofs {
+                return Err(ConductorError::AppStatusError(
+                    "Cannot provide memproofs for an app which is not AwaitingMemproofs"
+                        .to_string(),
+                ));
+            }
+            let roles = app
                 .primary_roles()
                 .map(|(role_name, role)| {
                     (
+                        role_name.clone(),
                         CellId::new(role.dna_hash().clone(), app.agent_key.clone()),
-                        memproofs.remove(role_name),
                     )
                 })
+                .collect::<Vec<_>>();
+
+            self.memproof_progress.share_mut(|all_progress| {
+                let progress = all_progress
+                    .entry(installed_app_id.clone())
+                    .or_insert_with(|| MemproofProgress {
+                        roles: Default::default(),
+                        attempts: 0,
+                    });
+                if progress
+                    .roles
+                    .values()
+                    .any(|status| *status == RoleGenesisStatus::Running)
+                {
+                    return Err(ConductorError::AppStatusError(
+                        "Memproofs are already being provided for this app".to_string(),
+                    ));
+                }
+                progress.attempts += 1;
+                for (role_name, _) in &roles {
+                    let status = progress
+                        .roles
+                        .entry(role_name.clone())
+                        .or_insert(RoleGenesisStatus::AwaitingMemproof);
+                    if *status != RoleGenesisStatus::Complete {
+                        *status = RoleGenesisStatus::Running;
+                    }
+                }
+                Ok(())
+            })?;
+
+            let cells_to_genesis = roles
+                .iter()
+                .map(|(role_name, cell_id)| (cell_id.clone(), memproofs.remove(role_name)))
                 .collect();
 
-            crate::conductor::conductor::genesis_cells(self.clone(), cells_to_genesis).await?;
+            // Genesis is run in its own task so that the progress is recorded
+            // even if the caller stops waiting for it.
+            let result = tokio::task::spawn({
+                let conductor = self.clone();
+                let installed_app_id = installed_app_id.clone();
+                async move {
+                    let result = crate::conductor::conductor::genesis_cells(
+                        conductor.clone(),
+                        cells_to_genesis,
+                    )
+                    .await;
+                    conductor.record_genesis_outcome(&installed_app_id, &roles, &result);
+                    result
+                }
+            })
+            .await?;
+            match result {
+                Ok(()) => (),
+                Err(ConductorError::GenesisFailed { errors }) => {
+                    tracing::warn!(app_id = %installed_app_id, ?errors, "Genesis failed with the provided memproofs");
+                    return Err(ConductorError::MemproofsRejected(
+                        self.get_memproof_progress(installed_app_id).await?,
+                    ));
+                }
+                Err(e) => return Err(e),
+            }
 
             self.update_state({
                 let installed_app_id = installed_app_id.clone();
@@ -1956,6 +2563,74 @@ mod app_impls {
             Ok(())
         }
 
+        /// Update the recorded progress of an app's memproofs after running genesis for
+        /// its `roles`.
+        fn record_genesis_outcome(
+            &self,
+            installed_app_id: &InstalledAppId,
+            roles: &[(RoleName, CellId)],
+            result: &ConductorResult<()>,
+        ) {
+            let failures: HashMap<CellId, String> = match result {
+                Err(ConductorError::GenesisFailed { errors }) => errors
+                    .iter()
+                    .map(|(cell_id, error)| (cell_id.clone(), error.to_string()))
+                    .collect(),
+                _ => HashMap::new(),
+            };
+            self.memproof_progress.share_mut(|all_progress| {
+                let Some(progress) = all_progress.get_mut(installed_app_id) else {
+                    return;
+                };
+                for (role_name, cell_id) in roles {
+                    let status = match (result, failures.get(cell_id)) {
+                        (Ok(()), _) | (Err(ConductorError::GenesisFailed { .. }), None) => {
+                            RoleGenesisStatus::Complete
+                        }
+                        (Err(ConductorError::GenesisFailed { .. }), Some(reason)) => {
+                            RoleGenesisStatus::Failed {
+                                reason: reason.clone(),
+                            }
+                        }
+                        (Err(e), _) => RoleGenesisStatus::Failed {
+                            reason: e.to_string(),
+                        },
+                    };
+                    progress.roles.insert(role_name.clone(), status);
+                }
+            });
+        }
+
+        /// Get the progress of providing memproofs for an app.
+        ///
+        /// Apps which are not awaiting memproofs report genesis as complete for all roles.
+        pub async fn get_memproof_progress(
+            &self,
+            installed_app_id: &InstalledAppId,
+        ) -> ConductorResult<MemproofProgress> {
+            let state = self.get_state().await?;
+            let app = state.get_app(installed_app_id)?;
+            let recorded = self
+                .memproof_progress
+                .share_ref(|all_progress| all_progress.get(installed_app_id).cloned());
+            let default_status = if *app.status() == AppStatus::AwaitingMemproofs {
+                RoleGenesisStatus::AwaitingMemproof
+            } else {
+                RoleGenesisStatus::Complete
+            };
+            let mut progress = recorded.unwrap_or_else(|| MemproofProgress {
+                roles: Default::default(),
+                attempts: 0,
+            });
+            for (role_name, _) in app.primary_roles() {
+                progress
+                    .roles
+                    .entry(role_name.clone())
+                    .or_insert_with(|| default_status.clone());
+            }
+            Ok(progress)
+        }
+
         /// Update the agent key for an installed app
         // TODO: fully implement after DPKI is available
         #[allow(unused)]
@@ -1979,6 +2654,67 @@ mod app_impls {
             unimplemented!("this is a partial implementation for reference only")
         }
 
+        /// The installed apps with a status and a cell of a DNA, sorted by their
+        /// installed_at timestamp, in descending order.
+        fn filter_apps<'a>(
+            &self,
+            state: &'a ConductorState,
+            status_filter: Option<AppStatusFilter>,
+            dna_hash: Option<&DnaHash>,
+            page: Option<Page>,
+        ) -> Vec<&'a InstalledApp> {
+            use AppStatusFilter::*;
+            let apps_ids: Vec<&String> = match status_filter {
+                Some(Enabled) => state
+                    .enabled_apps()
+                    .filter(|(id, _)| is_app(id))
+                    .map(|(id, _)| id)
+                    .collect(),
+                Some(Disabled) => state
+                    .disabled_apps()
+                    .filter(|(id, _)| is_app(id))
+                    .map(|(id, _)| id)
+                    .collect(),
+                Some(Running) => state
+                    .running_apps()
+                    .filter(|(id, _)| is_app(id))
+                    .map(|(id, _)| id)
+                    .collect(),
+                Some(Stopped) => state
+                    .stopped_apps()
+                    .filter(|(id, _)| is_app(id))
+                    .map(|(id, _)| id)
+                    .collect(),
+                Some(Paused) => state
+                    .paused_apps()
+                    .filter(|(id, _)| is_app(id))
+                    .map(|(id, _)| id)
+                    .collect(),
+                None => state
+                    .installed_apps_and_services()
+                    .keys()
+                    .filter(|id| is_app(id))
+                    .collect(),
+            };
+
+            let mut apps: Vec<&InstalledApp> = apps_ids
+                .into_iter()
+                .filter_map(|app_id| state.get_app(app_id).ok())
+                .filter(|app| {
+                    dna_hash.map_or(true, |dna_hash| {
+                        app.all_cells()
+                            .any(|cell_id| cell_id.dna_hash() == dna_hash)
+                    })
+                })
+                .collect();
+            apps.sort_by_key(|app| std::cmp::Reverse(*app.installed_at()));
+
+            match page {
+                Some(page) => page.select(apps),
+                None => apps,
+            }
+        }
+
         fn get_app_info_inner(
             &self,
             app_id: &InstalledAppId,
@@ -2110,6 +2846,27 @@ mod cell_impls {
             }
         }
 
+        /// Run a read-only query against the source chain of a running cell.
+        pub async fn query_cell_source_chain(
+            &self,
+            cell_id: &CellId,
+            filter: ChainQueryFilter,
+        ) -> ConductorResult<Vec<Record>> {
+            // Only running cells may be queried.
+            self.cell_by_id(cell_id).await?;
+            let source_chain = SourceChainRead::new(
+                self.get_or_create_authored_db(cell_id.dna_hash(), cell_id.agent_pubkey().clone())?
+                    .into(),
+                self.get_or_create_dht_db(cell_id.dna_hash())?.into(),
+                self.get_or_create_space(cell_id.dna_hash())?
+                    .dht_query_cache,
+                self.keystore.clone(),
+                cell_id.agent_pubkey().clone(),
+            )
+            .await?;
+            Ok(source_chain.query(filter).await?)
+        }
+
         /// Iterator over cells which are fully "live", meaning they have been
         /// fully initialized and are registered with the kitsune network layer.
         /// Generally used to handle conductor interface requests.
@@ -2479,15 +3236,77 @@ mod app_status_impls {
                 .map(Result::unwrap_err)
                 .collect();
 
+            use holochain_p2p::HolochainP2pSender;
+            use kitsune_p2p_types::dht::{arq::ArqPin, spacetime::SpaceDimension, Arq};
+
+            // The spaces of apps with a network profile must be restricted before they are joined.
+            let network_profiles: HashMap<DnaHash, NetworkProfile> = self
+                .get_state()
+                .await?
+                .installed_apps_and_services()
+                .values()
+                .filter_map(|app| Some((app.all_cells(), app.network_profile.clone()?)))
+                .flat_map(|(cells, profile)| {
+                    cells.map(move |cell_id| (cell_id.dna_hash().clone(), profile.clone()))
+                })
+                .collect();
+            let network_profiles = &network_profiles;
+            let arq_pins = self.get_state().await?.arq_pins;
+            let arq_pins = &arq_pins;
+            let client_cells = self.installed_client_cell_ids().await?;
+            self.client_cells
+                .share_mut(|cells| cells.clone_from(&client_cells));
+            let client_cells = &client_cells;
+            let client_cache_fresh_for = self.conductor_tuning_params().client_cache_fresh_for();
+            for cell_id in client_cells {
+                if let Ok(space) = self.get_or_create_space(cell_id.dna_hash()) {
+                    space
+                        .dht_query_cache
+                        .hot_hashes()
+                        .set_fetched_fresh_for(Some(client_cache_fresh_for));
+                }
+            }
+            let holochain_p2p = &self.holochain_p2p;
+
             // Add agents to local agent store in kitsune
 
             future::join_all(new_cells.iter().enumerate().map(|(i, (cell, _))| {
                 async move {
                     let p2p_agents_db = cell.p2p_agents_db().clone();
                     let cell_id = cell.id().clone();
+                    if let Some(profile) = network_profiles.get(cell_id.dna_hash()) {
+                        if let Err(e) = holochain_p2p
+                            .set_network_profile(cell_id.dna_hash().clone(), profile.clone())
+                            .await
+                        {
+                            tracing::error!(
+                                "The network profile of {cell_id} can't be applied, so it won't join the network. Error: {e:?}"
+                            );
+                            return;
+                        }
+                    }
                     let kagent = cell_id.agent_pubkey().to_kitsune();
+                    // The peer store of the space was persisted when the conductor was last
+                    // running. Drop the peers whose agent infos expired since then, so the
+                    // cell rejoins the network through the peers which are still live.
+                    if let Err(e) = p2p_prune(&p2p_agents_db, vec![kagent.clone()]).await {
+                        tracing::warn!(?e, "The stale peers of {cell_id} couldn't be pruned");
+                    }
+                    match p2p_agents_db.p2p_count_agents().await {
+                        Ok(peers) => {
+                            tracing::info!(%cell_id, peers, "Rejoining the network with the known peers")
+                        }
+                        Err(e) => tracing::warn!(?e, "The peers of {cell_id} couldn't be counted"),
+                    }
                     let maybe_agent_info = p2p_agents_db.p2p_get_agent(&kagent).await.ok().flatten();
-                    let maybe_initial_arq = maybe_agent_info.clone().map(|i| i.storage_arq);
+                    let is_client = client_cells.contains(&cell_id);
+                    // Clients hold nothing from the start, rather than holding their stored arc
+                    // until the pin is applied.
+                    let maybe_initial_arq = if is_client {
+                        Some(Arq::new_empty(SpaceDimension::standard(), kagent.get_loc()))
+                    } else {
+                        maybe_agent_info.clone().map(|i| i.storage_arq)
+                    };
                     let agent_pubkey = cell_id.agent_pubkey().clone();
 
                     let res = tokio::time::timeout(
@@ -2504,8 +3323,25 @@ mod app_status_impls {
                         Ok(r) => {
                             match r {
                                 Ok(_) => {
-                                    // all good
-
+                                    let pin = if is_client {
+                                        Some(&ArqPin::Empty)
+                                    } else {
+                                        arq_pins.get(&cell_id)
+                                    };
+                                    if let Some(pin) = pin {
+                                        if let Err(e) = holochain_p2p
+                                            .set_arq_pin(
+                                                cell_id.dna_hash().clone(),
+                                                cell_id.agent_pubkey().clone(),
+                                                Some(pin.clone()),
+                                            )
+                                            .await
+                                        {
+                                            tracing::error!(
+                                                "The storage arc pin of {cell_id} can't be applied. Error: {e:?}"
+                                            );
+                                        }
+                                    }
                                 }
                                 Err(e) => {
                                     tracing::error!(
@@ -2888,7 +3724,8 @@ mod misc_impls {
                 self.keystore.clone(),
                 cell_id.agent_pubkey().clone(),
             )
-            .await?;
+            .await?
+            .with_clock(self.spaces.clock.clone());
 
             let cap_grant_entry = Entry::CapGrant(cap_grant);
             let entry_hash = EntryHash::with_data_sync(&cap_grant_entry);
@@ -3029,6 +3866,19 @@ mod misc_impls {
                 .map_err(crate::conductor::api::error::ConductorApiError::other)
         }
 
+        /// Pause or resume gossip for a DNA.
+        pub async fn set_gossip_pause(
+            &self,
+            dna_hash: DnaHash,
+            pause: kitsune_p2p::GossipPause,
+        ) -> ConductorApiResult<()> {
+            use holochain_p2p::HolochainP2pSender;
+            self.holochain_p2p()
+                .set_gossip_pause(dna_hash, pause)
+                .await
+                .map_err(crate::conductor::api::error::ConductorApiError::other)
+        }
+
         /// Add signed agent info to the conductor
         pub async fn add_agent_infos(
             &self,
@@ -3091,7 +3941,6 @@ mod misc_impls {
 /// Pure accessor methods
 mod accessor_impls {
     use super::*;
-    use tokio::sync::broadcast;
 
     impl Conductor {
         pub(crate) fn ribosome_store(&self) -> &RwShare<RibosomeStore> {
@@ -3103,10 +3952,7 @@ mod accessor_impls {
         }
 
         /// Get a signal broadcast sender for a cell.
-        pub async fn get_signal_tx(
-            &self,
-            cell_id: &CellId,
-        ) -> ConductorResult<broadcast::Sender<Signal>> {
+        pub async fn get_signal_tx(&self, cell_id: &CellId) -> ConductorResult<SignalSender> {
             let app = self
                 .find_app_containing_cell(cell_id)
                 .await?
@@ -3185,11 +4031,55 @@ mod accessor_impls {
             &self.config
         }
 
+        /// Get the conductor tuning params, including any changes made by reloading the config
+        pub fn conductor_tuning_params(&self) -> ConductorTuningParams {
+            self.running_config
+                .share_ref(|config| config.conductor_tuning_params())
+        }
+
         /// Get a TaskManagerClient
         pub fn task_manager(&self) -> TaskManagerClient {
             self.task_manager.clone()
         }
 
+        /// Get the record of slow zome calls and workflow runs
+        pub(crate) fn slow_operations(&self) -> Arc<SlowOperationLog> {
+            self.slow_operations.clone()
+        }
+
+        /// The runtime which the workflows that the cells of a DNA share run on, if
+        /// the conductor has workflow runtimes
+        pub(crate) fn workflow_runtime(&self, dna_hash: &DnaHash) -> Option<WorkflowRuntime> {
+            self.workflow_runtimes.for_dna(dna_hash)
+        }
+
+        /// The runtime which the workflows of a cell run on, if the conductor has
+        /// workflow runtimes
+        pub(crate) fn cell_workflow_runtime(&self, cell_id: &CellId) -> Option<WorkflowRuntime> {
+            self.workflow_runtimes.for_cell(cell_id)
+        }
+
+        /// The most recent zome calls and workflow runs which took longer
+        /// than their threshold, newest first
+        pub fn list_slow_operations(&self) -> Vec<SlowOperation> {
+            self.slow_operations.recent()
+        }
+
+        /// Start recording the events of an op as it passes through the conductor.
+        pub fn start_op_trace(&self, op_hash: DhtOpHash) {
+            crate::core::op_trace::start_op_trace(op_hash)
+        }
+
+        /// The events which were recorded for a traced op, oldest first.
+        pub fn op_trace(&self, op_hash: &DhtOpHash) -> Option<Vec<OpTraceEvent>> {
+            crate::core::op_trace::op_trace(op_hash)
+        }
+
+        /// Stop tracing an op and drop its events.
+        pub fn stop_op_trace(&self, op_hash: &DhtOpHash) {
+            crate::core::op_trace::stop_op_trace(op_hash)
+        }
+
         /// Find the app which contains the given cell by its [CellId].
         #[cfg_attr(feature = "instrument", tracing::instrument(skip_all))]
         pub async fn find_app_containing_cell(
@@ -3209,7 +4099,8 @@ mod accessor_impls {
 mod authenticate_token_impls {
     use super::*;
     use holochain_conductor_api::{
-        AppAuthenticationToken, AppAuthenticationTokenIssued, IssueAppAuthenticationTokenPayload,
+        AppAuthenticationToken, AppAuthenticationTokenIssued, AppAuthenticationTokenScope,
+        IssueAppAuthenticationTokenPayload,
     };
 
     impl Conductor {
@@ -3223,6 +4114,7 @@ mod authenticate_token_impls {
                     payload.installed_app_id,
                     payload.expiry_seconds,
                     payload.single_use,
+                    payload.scope,
                 )
             });
 
@@ -3248,12 +4140,13 @@ mod authenticate_token_impls {
         /// Authenticate the app interface authentication `token`, optionally requiring the token to
         /// have been issued for a specific `app_id`.
         ///
-        /// Returns the [InstalledAppId] that the token was issued for.
+        /// Returns the [InstalledAppId] that the token was issued for, and the scope of the
+        /// requests which the connection may make.
         pub fn authenticate_app_token(
             &self,
             token: Vec<u8>,
             app_id: Option<InstalledAppId>,
-        ) -> ConductorResult<InstalledAppId> {
+        ) -> ConductorResult<(InstalledAppId, AppAuthenticationTokenScope)> {
             self.app_auth_token_store.share_mut(|app_connection_auth| {
                 app_connection_auth.authenticate_token(token, app_id)
             })
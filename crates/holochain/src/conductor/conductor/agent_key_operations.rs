@@ -25,15 +25,25 @@ impl Conductor {
 
         // Revoke key in DPKI first, if installed, and then in cells' source chains.
         // Call separate function so that in case a part of key revocation fails, the app is still enabled again.
-        let revocation_per_cell_results =
-            Conductor::revoke_agent_key_for_app_inner(self.clone(), agent_key, app_id.clone())
-                .await;
+        let revocation_per_cell_results = Conductor::revoke_agent_key_for_app_inner(
+            self.clone(),
+            agent_key.clone(),
+            app_id.clone(),
+        )
+        .await;
 
         // Enable app again.
         self.clone().enable_app(app_id.clone()).await?;
 
         let revocation_per_cell_results = revocation_per_cell_results?;
 
+        // With DPKI, ops by the revoked key are rejected by every cell, not only the cells of
+        // this app. Recheck the ops which are waiting for validation now rather than when
+        // their validation is next retried.
+        if self.running_services().dpki.is_some() {
+            self.recheck_ops_after_key_revocation(agent_key);
+        }
+
         // Publish 'Delete' actions of cells where successful.
         // Triggering workflow is only possible when cells are enabled.
         let publish_workflow_triggers = revocation_per_cell_results
@@ -65,6 +75,32 @@ impl Conductor {
         Ok(revocation_per_cell_results)
     }
 
+    /// Trigger sys validation of all running cells, so that ops by a revoked key which are
+    /// waiting for validation are checked against the revocation, and deprioritize gossip
+    /// with the agent of the key in every space.
+    ///
+    /// Sys validation rejects ops which were authored by a key after it was revoked in DPKI.
+    /// Gossip is still initiated with the agent when there is no one else to gossip with,
+    /// so that the ops it authored before the revocation keep being held.
+    fn recheck_ops_after_key_revocation(&self, agent_key: AgentPubKey) {
+        self.spaces.add_revoked_agent(agent_key);
+
+        let dna_hashes = self
+            .running_cell_ids()
+            .into_iter()
+            .map(|cell_id| cell_id.dna_hash().clone())
+            .collect::<HashSet<_>>();
+        for dna_hash in dna_hashes {
+            if let Some(trigger) = self
+                .spaces
+                .queue_consumer_map
+                .sys_validation_trigger(Arc::new(dna_hash))
+            {
+                trigger.trigger(&"revoke_agent_key_for_app");
+            }
+        }
+    }
+
     /// Revoke agent key in Deepkey first, if installed, and then write a [`Delete`] of the key to the source chain.
     async fn revoke_agent_key_for_app_inner(
         conductor: Arc<Conductor>,
@@ -135,7 +171,8 @@ impl Conductor {
                     conductor.keystore().clone(),
                     agent_key.clone(),
                 )
-                .await?;
+                .await?
+                .with_clock(conductor.spaces.clock.clone());
 
                 // Insert `Delete` action of agent pub key into source chain
                 source_chain.delete_valid_agent_pub_key().await?;
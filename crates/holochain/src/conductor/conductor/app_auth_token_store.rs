@@ -1,4 +1,5 @@
 use holochain_conductor_api::AppAuthenticationToken;
+use holochain_conductor_api::AppAuthenticationTokenScope;
 use rand::RngCore;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
@@ -22,11 +23,13 @@ impl AppAuthTokenStore {
     /// for use with the specified `installed_app_id` and will expire after `expiry_seconds`.
     ///
     /// If `single_use` is true, the token will be invalidated after the first use, successful or not.
+    /// Connections which authenticate with the token may only make requests which `scope` allows.
     pub fn issue_token(
         &mut self,
         installed_app_id: InstalledAppId,
         expiry_seconds: u64,
         single_use: bool,
+        scope: AppAuthenticationTokenScope,
     ) -> (AppAuthenticationToken, Option<SystemTime>) {
         let mut token = [0u8; 64];
         rand::thread_rng().fill_bytes(&mut token);
@@ -44,6 +47,7 @@ impl AppAuthTokenStore {
                 installed_app_id,
                 expires_at,
                 single_use,
+                scope,
             },
         );
         self.remove_expired_tokens();
@@ -61,7 +65,8 @@ impl AppAuthTokenStore {
         self.issued_tokens.remove(&token);
     }
 
-    /// Authenticate a token and return the `InstalledAppId` that the token was issued for.
+    /// Authenticate a token and return the `InstalledAppId` that the token was issued for,
+    /// along with the scope of the requests which the connection may make.
     ///
     /// If `app_id_restriction` is provided, the token will only be valid for the specified `InstalledAppId`.
     /// This is useful when an app interface is restricted to a single app and tokens that would
@@ -70,7 +75,7 @@ impl AppAuthTokenStore {
         &mut self,
         token: AppAuthenticationToken,
         app_id_restriction: Option<InstalledAppId>,
-    ) -> ConductorResult<InstalledAppId> {
+    ) -> ConductorResult<(InstalledAppId, AppAuthenticationTokenScope)> {
         self.remove_expired_tokens();
 
         match self.issued_tokens.entry(token) {
@@ -92,7 +97,7 @@ impl AppAuthTokenStore {
 
                 let app_id = meta.installed_app_id.clone();
 
-                Ok(app_id)
+                Ok((app_id, meta.scope))
             }
             Entry::Vacant(_) => Err(ConductorError::FailedAuthenticationError(
                 "Invalid token".to_string(),
@@ -145,6 +150,7 @@ struct TokenMeta {
     installed_app_id: InstalledAppId,
     expires_at: Option<SystemTime>,
     single_use: bool,
+    scope: AppAuthenticationTokenScope,
 }
 
 #[cfg(test)]
@@ -155,9 +161,14 @@ mod tests {
     fn issue_and_use_single_use_token() {
         let mut auth = AppAuthTokenStore::new();
         let installed_app_id = "test_app".to_string();
-        let (token, _) = auth.issue_token(installed_app_id.clone(), 10, true);
+        let (token, _) = auth.issue_token(
+            installed_app_id.clone(),
+            10,
+            true,
+            AppAuthenticationTokenScope::Full,
+        );
 
-        let authenticated_for_app = auth.authenticate_token(token.clone(), None).unwrap();
+        let (authenticated_for_app, _) = auth.authenticate_token(token.clone(), None).unwrap();
         assert_eq!(authenticated_for_app, installed_app_id);
 
         let result = auth.authenticate_token(token.clone(), None);
@@ -168,12 +179,17 @@ mod tests {
     fn reuse_token() {
         let mut auth = AppAuthTokenStore::new();
         let installed_app_id = "test_app".to_string();
-        let (token, _) = auth.issue_token(installed_app_id.clone(), 10, false);
+        let (token, _) = auth.issue_token(
+            installed_app_id.clone(),
+            10,
+            false,
+            AppAuthenticationTokenScope::Full,
+        );
 
-        let authenticated_for_app = auth.authenticate_token(token.clone(), None).unwrap();
+        let (authenticated_for_app, _) = auth.authenticate_token(token.clone(), None).unwrap();
         assert_eq!(authenticated_for_app, installed_app_id);
 
-        let authenticated_for_app = auth.authenticate_token(token.clone(), None).unwrap();
+        let (authenticated_for_app, _) = auth.authenticate_token(token.clone(), None).unwrap();
         assert_eq!(authenticated_for_app, installed_app_id);
     }
 
@@ -188,17 +204,27 @@ mod tests {
     fn use_token_with_app_restriction() {
         let mut auth = AppAuthTokenStore::new();
         let installed_app_id = "test_app".to_string();
-        let (token, _) = auth.issue_token(installed_app_id.clone(), 1, true);
+        let (token, _) = auth.issue_token(
+            installed_app_id.clone(),
+            1,
+            true,
+            AppAuthenticationTokenScope::Full,
+        );
 
         let result = auth.authenticate_token(token.clone(), Some(installed_app_id.clone()));
-        assert_eq!(result.unwrap(), installed_app_id);
+        assert_eq!(result.unwrap().0, installed_app_id);
     }
 
     #[test]
     fn use_token_with_app_restriction_mismatch() {
         let mut auth = AppAuthTokenStore::new();
         let installed_app_id = "test_app".to_string();
-        let (token, _) = auth.issue_token(installed_app_id.clone(), 1, true);
+        let (token, _) = auth.issue_token(
+            installed_app_id.clone(),
+            1,
+            true,
+            AppAuthenticationTokenScope::Full,
+        );
 
         let other_app_id = "other_app".to_string();
         let result = auth.authenticate_token(token.clone(), Some(other_app_id));
@@ -213,7 +239,12 @@ mod tests {
     fn use_token_with_app_restriction_mismatch_multi_use() {
         let mut auth = AppAuthTokenStore::new();
         let installed_app_id = "test_app".to_string();
-        let (token, _) = auth.issue_token(installed_app_id.clone(), 1, false);
+        let (token, _) = auth.issue_token(
+            installed_app_id.clone(),
+            1,
+            false,
+            AppAuthenticationTokenScope::Full,
+        );
 
         let other_app_id = "other_app".to_string();
         let result = auth.authenticate_token(token.clone(), Some(other_app_id));
@@ -222,14 +253,19 @@ mod tests {
         // Token was retained through the failed attempt because the caller has used it with a
         // websocket connection that is restricted to another app.
         let result = auth.authenticate_token(token.clone(), Some(installed_app_id.clone()));
-        assert_eq!(result.unwrap(), installed_app_id);
+        assert_eq!(result.unwrap().0, installed_app_id);
     }
 
     #[test]
     fn use_expired_token() {
         let mut auth = AppAuthTokenStore::new();
         let installed_app_id = "test_app".to_string();
-        let (token, _) = auth.issue_token(installed_app_id.clone(), 1, true);
+        let (token, _) = auth.issue_token(
+            installed_app_id.clone(),
+            1,
+            true,
+            AppAuthenticationTokenScope::Full,
+        );
 
         auth.age_tokens();
 
@@ -244,7 +280,12 @@ mod tests {
         let mut auth = AppAuthTokenStore::new();
         let installed_app_id = "test_app".to_string();
         for _ in 0..3 {
-            auth.issue_token(installed_app_id.clone(), 1, true);
+            auth.issue_token(
+                installed_app_id.clone(),
+                1,
+                true,
+                AppAuthenticationTokenScope::Full,
+            );
         }
 
         assert_eq!(3, auth.get_tokens().len());
@@ -254,7 +295,12 @@ mod tests {
 
         // Having this clear out the expired tokens means that even if a client is issuing tokens that
         // don't get used, older tokens will still be dropped.
-        let (token, _) = auth.issue_token(installed_app_id.clone(), 1, true);
+        let (token, _) = auth.issue_token(
+            installed_app_id.clone(),
+            1,
+            true,
+            AppAuthenticationTokenScope::Full,
+        );
 
         assert_eq!(1, auth.get_tokens().len());
         assert_eq!(token, *auth.get_tokens().iter().next().unwrap().0);
@@ -263,7 +309,12 @@ mod tests {
     #[test]
     fn create_token_which_does_not_expire() {
         let mut auth = AppAuthTokenStore::new();
-        let (token, expiry) = auth.issue_token("test_app".to_string(), 0, false);
+        let (token, expiry) = auth.issue_token(
+            "test_app".to_string(),
+            0,
+            false,
+            AppAuthenticationTokenScope::Full,
+        );
         assert!(expiry.is_none());
 
         for _ in 0..3 {
@@ -271,4 +322,22 @@ mod tests {
             assert!(r.is_ok());
         }
     }
+
+    #[test]
+    fn token_scope_is_returned_on_authentication() {
+        let mut auth = AppAuthTokenStore::new();
+        let installed_app_id = "test_app".to_string();
+        let (token, _) = auth.issue_token(
+            installed_app_id.clone(),
+            10,
+            true,
+            AppAuthenticationTokenScope::ReadOnly,
+        );
+
+        let result = auth.authenticate_token(token, None);
+        assert_eq!(
+            result.unwrap(),
+            (installed_app_id, AppAuthenticationTokenScope::ReadOnly)
+        );
+    }
 }
@@ -2,8 +2,11 @@ use holochain_types::app::InstalledAppId;
 use holochain_types::prelude::*;
 use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::broadcast;
+use tokio::sync::broadcast::error::{RecvError, SendError};
+use tokio::sync::mpsc;
 
 // Number of signals in buffer before we start dropping them.
 // 64 gives us a good burst buffer incase multiple threads are
@@ -12,9 +15,123 @@ use tokio::sync::broadcast;
 // the memory usage implications.
 const SIGNAL_BUFFER_SIZE: usize = 64;
 
+/// How often to check whether sent signals have been received.
+const SIGNAL_DELIVERY_POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(10);
+
+/// Sends the signals of an app to each app interface connection of the app, and to
+/// the other subscribers of its signals.
+///
+/// Each interface connection has a queue of its own, so that a client which is slow to
+/// take its signals only drops signals of its own once its queue is full, rather than
+/// holding up the signals of the other clients.
+#[derive(Debug, Clone)]
+pub struct SignalSender {
+    subscribers: broadcast::Sender<Signal>,
+    interfaces: Arc<parking_lot::Mutex<Vec<InterfaceQueue>>>,
+}
+
+/// The queue of signals of one app interface connection.
+#[derive(Debug)]
+struct InterfaceQueue {
+    tx: mpsc::Sender<Signal>,
+    /// How many signals were dropped because the queue was full.
+    dropped: Arc<AtomicU64>,
+}
+
+impl SignalSender {
+    /// Create a sender which no interface is attached to yet.
+    pub fn new() -> Self {
+        Self {
+            subscribers: broadcast::channel(SIGNAL_BUFFER_SIZE).0,
+            interfaces: Arc::default(),
+        }
+    }
+
+    /// Queue a signal for every attached interface and subscriber.
+    ///
+    /// Returns how many of them the signal was queued for, which leaves out the
+    /// interfaces whose queue is full. Like sending on a broadcast channel, this fails
+    /// if there is no interface or subscriber at all.
+    pub fn send(&self, signal: Signal) -> Result<usize, SendError<Signal>> {
+        let mut received = self.subscribers.send(signal.clone()).unwrap_or(0);
+        self.interfaces.lock().retain(|queue| {
+            match queue.tx.try_send(signal.clone()) {
+                Ok(()) => received += 1,
+                Err(mpsc::error::TrySendError::Full(_)) => {
+                    queue.dropped.fetch_add(1, Ordering::Relaxed);
+                }
+                Err(mpsc::error::TrySendError::Closed(_)) => return false,
+            }
+            true
+        });
+        if received == 0 {
+            Err(SendError(signal))
+        } else {
+            Ok(received)
+        }
+    }
+
+    /// Subscribe to the signals which are sent from now on.
+    ///
+    /// Subscribers share one buffer, and a subscriber which falls behind by more than
+    /// the buffer misses the oldest of its signals.
+    pub fn subscribe(&self) -> broadcast::Receiver<Signal> {
+        self.subscribers.subscribe()
+    }
+
+    /// Attach an app interface connection, which gets a queue of signals of its own.
+    pub(crate) fn attach_interface(&self) -> SignalReceiver {
+        let (tx, rx) = mpsc::channel(SIGNAL_BUFFER_SIZE);
+        let dropped = Arc::new(AtomicU64::new(0));
+        self.interfaces.lock().push(InterfaceQueue {
+            tx,
+            dropped: dropped.clone(),
+        });
+        SignalReceiver { rx, dropped }
+    }
+
+    /// Whether every signal which was sent has been received.
+    fn is_delivered(&self) -> bool {
+        self.subscribers.is_empty()
+            && self
+                .interfaces
+                .lock()
+                .iter()
+                .all(|queue| queue.tx.is_closed() || queue.tx.capacity() == SIGNAL_BUFFER_SIZE)
+    }
+}
+
+impl Default for SignalSender {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The receiving end of the queue of signals of one app interface connection.
+#[derive(Debug)]
+pub struct SignalReceiver {
+    rx: mpsc::Receiver<Signal>,
+    dropped: Arc<AtomicU64>,
+}
+
+impl SignalReceiver {
+    /// Receive the next signal.
+    ///
+    /// If signals were dropped because the queue was full since the last call, this
+    /// returns [`RecvError::Lagged`] with how many, and the next call returns the
+    /// next signal.
+    pub async fn recv(&mut self) -> Result<Signal, RecvError> {
+        let dropped = self.dropped.swap(0, Ordering::Relaxed);
+        if dropped > 0 {
+            return Err(RecvError::Lagged(dropped));
+        }
+        self.rx.recv().await.ok_or(RecvError::Closed)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct AppBroadcast {
-    channels: Arc<parking_lot::Mutex<HashMap<InstalledAppId, broadcast::Sender<Signal>>>>,
+    channels: Arc<parking_lot::Mutex<HashMap<InstalledAppId, SignalSender>>>,
 }
 
 impl AppBroadcast {
@@ -28,14 +145,8 @@ impl AppBroadcast {
     ///
     /// The app does not actually need to be installed to call this and it does not need to be
     /// called before subscribing to signals.
-    pub(crate) fn create_send_handle(
-        &self,
-        installed_app_id: InstalledAppId,
-    ) -> broadcast::Sender<Signal> {
-        match self.channels.lock().entry(installed_app_id) {
-            Entry::Occupied(e) => e.get().clone(),
-            Entry::Vacant(e) => e.insert(broadcast::channel(SIGNAL_BUFFER_SIZE).0).clone(),
-        }
+    pub(crate) fn create_send_handle(&self, installed_app_id: InstalledAppId) -> SignalSender {
+        self.sender(installed_app_id)
     }
 
     /// Subscribe to signals for a specific installed app.
@@ -46,14 +157,19 @@ impl AppBroadcast {
         &self,
         installed_app_id: InstalledAppId,
     ) -> broadcast::Receiver<Signal> {
-        match self.channels.lock().entry(installed_app_id) {
-            Entry::Occupied(e) => e.get().subscribe(),
-            Entry::Vacant(e) => {
-                let (tx, rx) = broadcast::channel(SIGNAL_BUFFER_SIZE);
-                e.insert(tx);
+        self.sender(installed_app_id).subscribe()
+    }
 
-                rx
-            }
+    /// Attach an app interface connection to the signals of a specific installed app,
+    /// with a queue of its own.
+    pub(crate) fn attach_interface(&self, installed_app_id: InstalledAppId) -> SignalReceiver {
+        self.sender(installed_app_id).attach_interface()
+    }
+
+    fn sender(&self, installed_app_id: InstalledAppId) -> SignalSender {
+        match self.channels.lock().entry(installed_app_id) {
+            Entry::Occupied(e) => e.get().clone(),
+            Entry::Vacant(e) => e.insert(SignalSender::new()).clone(),
         }
     }
 
@@ -65,6 +181,22 @@ impl AppBroadcast {
             .retain(|k, _| installed_apps.contains(k));
     }
 
+    /// Wait until every signal which has been sent has been received by all subscribers,
+    /// so that signals aren't lost when the app interfaces are closed.
+    pub(crate) async fn until_delivered(&self) {
+        loop {
+            let queued = self
+                .channels
+                .lock()
+                .values()
+                .any(|sender| !sender.is_delivered());
+            if !queued {
+                return;
+            }
+            tokio::time::sleep(SIGNAL_DELIVERY_POLL_INTERVAL).await;
+        }
+    }
+
     #[cfg(test)]
     fn keys(&self) -> Vec<InstalledAppId> {
         self.channels.lock().keys().cloned().collect()
@@ -169,6 +301,66 @@ mod tests {
         assert_eq!(signal_2, signal_2_rcv_2);
     }
 
+    #[tokio::test]
+    async fn wait_for_signals_to_be_delivered() {
+        let app_broadcast = AppBroadcast::new();
+        let installed_app_id: InstalledAppId = "test".into();
+        let tx = app_broadcast.create_send_handle(installed_app_id.clone());
+        let mut rx = app_broadcast.subscribe(installed_app_id);
+
+        // Nothing has been sent yet.
+        app_broadcast.until_delivered().await;
+
+        let signal = Signal::App {
+            cell_id: fixt!(CellId),
+            zome_name: fixt!(ZomeName),
+            signal: AppSignal::new(ExternIO::from(vec![])),
+        };
+        tx.send(signal).unwrap();
+        assert!(tokio::time::timeout(
+            std::time::Duration::from_millis(50),
+            app_broadcast.until_delivered()
+        )
+        .await
+        .is_err());
+
+        rx.recv().await.unwrap();
+        app_broadcast.until_delivered().await;
+    }
+
+    #[tokio::test]
+    async fn slow_interfaces_only_drop_their_own_signals() {
+        let app_broadcast = AppBroadcast::new();
+        let installed_app_id: InstalledAppId = "test".into();
+        let tx = app_broadcast.create_send_handle(installed_app_id.clone());
+        let mut fast = app_broadcast.attach_interface(installed_app_id.clone());
+        let mut slow = app_broadcast.attach_interface(installed_app_id);
+
+        let signal = Signal::App {
+            cell_id: fixt!(CellId),
+            zome_name: fixt!(ZomeName),
+            signal: AppSignal::new(ExternIO::from(vec![])),
+        };
+        // - The slow interface takes none of its signals, so its queue fills up.
+        for _ in 0..SIGNAL_BUFFER_SIZE {
+            assert_eq!(2, tx.send(signal.clone()).unwrap());
+            assert_eq!(signal, fast.recv().await.unwrap());
+        }
+        assert_eq!(1, tx.send(signal.clone()).unwrap());
+        assert_eq!(1, tx.send(signal.clone()).unwrap());
+        assert_eq!(signal, fast.recv().await.unwrap());
+        assert_eq!(signal, fast.recv().await.unwrap());
+
+        assert!(matches!(slow.recv().await, Err(RecvError::Lagged(2))));
+        assert_eq!(signal, slow.recv().await.unwrap());
+
+        // - Interfaces which are gone aren't counted.
+        drop(fast);
+        drop(slow);
+        assert!(tx.send(signal).is_err());
+        app_broadcast.until_delivered().await;
+    }
+
     #[tokio::test]
     async fn clean_up_unused_senders() {
         let app_broadcast = AppBroadcast::new();
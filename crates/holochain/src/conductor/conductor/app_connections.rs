@@ -0,0 +1,240 @@
+//! The clients which are connected to the conductor's app interfaces.
+
+use holochain_conductor_api::signal_subscription::SignalFilterSet;
+use holochain_conductor_api::{AppInterfaceConnectionId, AppInterfaceConnectionInfo};
+use holochain_types::prelude::*;
+use holochain_websocket::WebsocketSender;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::task::AbortHandle;
+
+/// A newly authenticated client connection to an app interface.
+pub(crate) struct NewAppConnection {
+    /// The port of the app interface.
+    pub port: u16,
+    /// The app which the client authenticated for.
+    pub installed_app_id: InstalledAppId,
+    /// The `Origin` header the client sent.
+    pub origin: Option<String>,
+    /// The address of the client.
+    pub peer_addr: std::net::SocketAddr,
+    /// Used to close the connection.
+    pub sender: WebsocketSender,
+}
+
+struct AppConnection {
+    new: NewAppConnection,
+    connected_since: Timestamp,
+    messages_in_flight: Arc<AtomicUsize>,
+    tasks: Vec<AbortHandle>,
+}
+
+#[derive(Default)]
+struct AppConnectionsInner {
+    connections: HashMap<AppInterfaceConnectionId, AppConnection>,
+    next_id: AppInterfaceConnectionId,
+}
+
+/// The clients which are connected to the conductor's app interfaces, by connection id.
+#[derive(Clone, Default)]
+pub(crate) struct AppConnections {
+    inner: Arc<parking_lot::Mutex<AppConnectionsInner>>,
+}
+
+impl AppConnections {
+    /// Register a connection which has been authenticated.
+    ///
+    /// The connection is listed until the returned handle is dropped.
+    pub(crate) fn register(&self, connection: NewAppConnection) -> AppConnectionHandle {
+        let messages_in_flight = Arc::new(AtomicUsize::new(0));
+        let mut inner = self.inner.lock();
+        let id = inner.next_id;
+        inner.next_id += 1;
+        inner.connections.insert(
+            id,
+            AppConnection {
+                new: connection,
+                connected_since: Timestamp::now(),
+                messages_in_flight: messages_in_flight.clone(),
+                tasks: Vec::new(),
+            },
+        );
+        AppConnectionHandle {
+            connections: self.clone(),
+            id,
+            messages_in_flight,
+        }
+    }
+
+    /// Record a task which handles a connection, so that it is stopped when the client
+    /// is disconnected.
+    pub(crate) fn add_task(&self, id: AppInterfaceConnectionId, task: AbortHandle) {
+        match self.inner.lock().connections.get_mut(&id) {
+            Some(connection) => connection.tasks.push(task),
+            // The connection has already been closed.
+            None => task.abort(),
+        }
+    }
+
+    /// List the open connections, ordered by id.
+    pub(crate) fn list(&self) -> Vec<AppInterfaceConnectionInfo> {
+        let mut connections = self
+            .inner
+            .lock()
+            .connections
+            .iter()
+            .map(|(id, connection)| AppInterfaceConnectionInfo {
+                connection_id: *id,
+                port: connection.new.port,
+                installed_app_id: connection.new.installed_app_id.clone(),
+                origin: connection.new.origin.clone(),
+                peer_addr: connection.new.peer_addr.to_string(),
+                connected_since: connection.connected_since,
+                // Connections are sent every signal emitted by their app.
+                signal_filters: SignalFilterSet::allow_all(),
+                messages_in_flight: connection.messages_in_flight.load(Ordering::Relaxed),
+            })
+            .collect::<Vec<_>>();
+        connections.sort_by_key(|c| c.connection_id);
+        connections
+    }
+
+    /// Close a connection and stop the tasks handling it.
+    ///
+    /// Returns false if there is no open connection with this id.
+    pub(crate) fn disconnect(&self, id: AppInterfaceConnectionId) -> bool {
+        let Some(connection) = self.inner.lock().connections.remove(&id) else {
+            return false;
+        };
+        tracing::info!(
+            connection_id = id,
+            app_id = %connection.new.installed_app_id,
+            port = connection.new.port,
+            "Disconnecting app interface client"
+        );
+        connection.new.sender.close();
+        for task in connection.tasks {
+            task.abort();
+        }
+        true
+    }
+
+    fn remove(&self, id: AppInterfaceConnectionId) {
+        self.inner.lock().connections.remove(&id);
+    }
+}
+
+/// Keeps a connection listed in [`AppConnections`] until it is dropped.
+pub(crate) struct AppConnectionHandle {
+    connections: AppConnections,
+    id: AppInterfaceConnectionId,
+    messages_in_flight: Arc<AtomicUsize>,
+}
+
+impl AppConnectionHandle {
+    /// The id of the connection.
+    pub(crate) fn id(&self) -> AppInterfaceConnectionId {
+        self.id
+    }
+
+    /// Count a request from the client as in flight until the returned guard is dropped.
+    pub(crate) fn start_message(&self) -> MessageInFlight {
+        self.messages_in_flight.fetch_add(1, Ordering::Relaxed);
+        MessageInFlight(self.messages_in_flight.clone())
+    }
+}
+
+impl Drop for AppConnectionHandle {
+    fn drop(&mut self) {
+        self.connections.remove(self.id);
+    }
+}
+
+/// A request which is being handled, see [`AppConnectionHandle::start_message`].
+pub(crate) struct MessageInFlight(Arc<AtomicUsize>);
+
+impl Drop for MessageInFlight {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_websocket::{connect, WebsocketConfig, WebsocketListener, WebsocketReceiver};
+
+    /// A connection, and the receivers which must be kept to keep it open.
+    async fn connection() -> (NewAppConnection, [WebsocketReceiver; 2]) {
+        let listener =
+            WebsocketListener::bind(Arc::new(WebsocketConfig::LISTENER_DEFAULT), "localhost:0")
+                .await
+                .unwrap();
+        let addr = listener.local_addrs().unwrap()[0];
+        let (client, accepted) = tokio::join!(
+            connect(Arc::new(WebsocketConfig::CLIENT_DEFAULT), addr),
+            listener.accept()
+        );
+        let (_, client_rx) = client.unwrap();
+        let (sender, rx) = accepted.unwrap();
+        (
+            NewAppConnection {
+                port: addr.port(),
+                installed_app_id: "app".to_string(),
+                origin: Some("origin".to_string()),
+                peer_addr: addr,
+                sender,
+            },
+            [client_rx, rx],
+        )
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn connection_is_listed_until_handle_is_dropped() {
+        let connections = AppConnections::default();
+        let (connection, _receivers) = connection().await;
+
+        let handle = connections.register(connection);
+        let listed = connections.list();
+        assert_eq!(1, listed.len());
+        assert_eq!(handle.id(), listed[0].connection_id);
+        assert_eq!("app", listed[0].installed_app_id);
+        assert_eq!(Some("origin".to_string()), listed[0].origin);
+
+        drop(handle);
+        assert!(connections.list().is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn messages_in_flight_are_counted() {
+        let connections = AppConnections::default();
+        let (connection, _receivers) = connection().await;
+        let handle = connections.register(connection);
+
+        let first = handle.start_message();
+        let second = handle.start_message();
+        assert_eq!(2, connections.list()[0].messages_in_flight);
+
+        drop(first);
+        drop(second);
+        assert_eq!(0, connections.list()[0].messages_in_flight);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn disconnect_stops_connection_tasks() {
+        let connections = AppConnections::default();
+        let (connection, _receivers) = connection().await;
+        let handle = connections.register(connection);
+
+        let task = tokio::task::spawn(std::future::pending::<()>());
+        connections.add_task(handle.id(), task.abort_handle());
+
+        assert!(connections.disconnect(handle.id()));
+        assert!(task.await.unwrap_err().is_cancelled());
+        assert!(connections.list().is_empty());
+
+        // The connection can only be disconnected once.
+        assert!(!connections.disconnect(handle.id()));
+    }
+}
@@ -0,0 +1,574 @@
+//! A model of app statuses and the cells which the conductor runs for them, for checking that
+//! the two stay consistent.
+//!
+//! The model describes the apps and cells of a conductor with small values which can be compared
+//! and hashed: apps and cells are numbered, an app status keeps its reason only where the
+//! conductor treats reasons differently, and a cell is either running or not. Status
+//! transitions are made with the real [`AppStatus::transition`], while the conductor methods
+//! which act on the transitions are mirrored by the model. Creating a cell can be made to fail,
+//! to cover what happens to apps whose cells don't start.
+//!
+//! [`ModelState::project`] maps the conductor state and its running cells onto the model.
+//! [`explore`] visits every state of a small instance of the model and checks invariants on each
+//! of them, including that no running cell belongs to an app which is uninstalled or disabled.
+
+use std::collections::{HashSet, VecDeque};
+
+use super::*;
+
+/// An app, numbered by its position in [`ModelConfig::app_cells`].
+type App = usize;
+
+/// A cell, numbered by its position in the list of cells given to [`ModelState::project`].
+type Cell = usize;
+
+/// The apps which can be installed.
+#[derive(Clone, Debug)]
+struct ModelConfig {
+    /// The cells provisioned for each app.
+    app_cells: Vec<Vec<Cell>>,
+}
+
+impl ModelConfig {
+    fn num_cells(&self) -> usize {
+        self.app_cells.iter().flatten().max().map_or(0, |c| c + 1)
+    }
+}
+
+/// The reason an app is disabled, reduced to the reasons which the conductor treats differently.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum ModelDisabledReason {
+    NeverStarted,
+    NotStartedAfterProvidingMemproofs,
+    /// Disabled by an admin call, either by the user or to delete the agent key.
+    User,
+    Error,
+}
+
+/// The status of an installed app. Mirrors [`AppStatus`] without the descriptions of errors.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum ModelStatus {
+    Running,
+    Paused,
+    Disabled(ModelDisabledReason),
+    AwaitingMemproofs,
+}
+
+impl ModelStatus {
+    fn from_status(status: &AppStatus) -> Self {
+        match status {
+            AppStatus::Running => Self::Running,
+            AppStatus::Paused(_) => Self::Paused,
+            AppStatus::Disabled(reason) => Self::Disabled(match reason {
+                DisabledAppReason::NeverStarted => ModelDisabledReason::NeverStarted,
+                DisabledAppReason::NotStartedAfterProvidingMemproofs => {
+                    ModelDisabledReason::NotStartedAfterProvidingMemproofs
+                }
+                DisabledAppReason::User | DisabledAppReason::DeletingAgentKey => {
+                    ModelDisabledReason::User
+                }
+                DisabledAppReason::Error(_) => ModelDisabledReason::Error,
+            }),
+            AppStatus::AwaitingMemproofs => Self::AwaitingMemproofs,
+        }
+    }
+
+    fn to_status(self) -> AppStatus {
+        match self {
+            Self::Running => AppStatus::Running,
+            Self::Paused => AppStatus::Paused(PausedAppReason::Error(String::new())),
+            Self::Disabled(reason) => AppStatus::Disabled(match reason {
+                ModelDisabledReason::NeverStarted => DisabledAppReason::NeverStarted,
+                ModelDisabledReason::NotStartedAfterProvidingMemproofs => {
+                    DisabledAppReason::NotStartedAfterProvidingMemproofs
+                }
+                ModelDisabledReason::User => DisabledAppReason::User,
+                ModelDisabledReason::Error => DisabledAppReason::Error(String::new()),
+            }),
+            Self::AwaitingMemproofs => AppStatus::AwaitingMemproofs,
+        }
+    }
+
+    /// Apply a transition with the real [`AppStatus::transition`].
+    fn transition(&mut self, transition: AppStatusTransition) -> AppStatusFx {
+        let mut status = self.to_status();
+        let fx = status.transition(transition);
+        *self = Self::from_status(&status);
+        fx
+    }
+}
+
+/// Something which can happen to the apps and cells of a conductor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Action {
+    /// An app is installed, with its memproofs either provided or deferred.
+    Install { app: App, defer_memproofs: bool },
+    /// Memproofs are provided for an app.
+    ProvideMemproofs(App),
+    /// An app is enabled with an admin call.
+    Enable(App),
+    /// An app is enabled with an app call, which is only allowed in some states.
+    EnableFromApp(App),
+    /// An app is disabled with an admin call.
+    Disable(App),
+    /// A paused app is started.
+    Start(App),
+    /// An app is uninstalled.
+    Uninstall(App),
+    /// Creating a cell starts failing.
+    BreakCell(Cell),
+    /// Creating a cell stops failing.
+    FixCell(Cell),
+    /// The conductor restarts, so no cells are running.
+    Restart,
+}
+
+/// The state of the apps and cells of a conductor.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct ModelState {
+    /// The status of each app, or None if it is not installed.
+    apps: Vec<Option<ModelStatus>>,
+    /// Whether each cell is running.
+    running: Vec<bool>,
+    /// Whether creating each cell fails.
+    broken: Vec<bool>,
+}
+
+impl ModelState {
+    fn new(config: &ModelConfig) -> Self {
+        Self {
+            apps: vec![None; config.app_cells.len()],
+            running: vec![false; config.num_cells()],
+            broken: vec![false; config.num_cells()],
+        }
+    }
+
+    /// Whether an action can happen in this state.
+    fn enabled(&self, action: Action) -> bool {
+        match action {
+            Action::Install { app, .. } => self.apps[app].is_none(),
+            Action::ProvideMemproofs(app)
+            | Action::Enable(app)
+            | Action::EnableFromApp(app)
+            | Action::Disable(app)
+            | Action::Start(app)
+            | Action::Uninstall(app) => self.apps[app].is_some(),
+            Action::BreakCell(cell) => !self.broken[cell],
+            Action::FixCell(cell) => self.broken[cell],
+            Action::Restart => true,
+        }
+    }
+
+    /// Apply an action. Errors returned by the conductor are ignored, since the state they
+    /// leave behind is what matters.
+    fn apply(&mut self, config: &ModelConfig, action: Action) {
+        let _ = match action {
+            Action::Install {
+                app,
+                defer_memproofs,
+            } => {
+                self.apps[app] = Some(if defer_memproofs {
+                    ModelStatus::AwaitingMemproofs
+                } else {
+                    ModelStatus::Disabled(ModelDisabledReason::NeverStarted)
+                });
+                Ok(())
+            }
+            Action::ProvideMemproofs(app) => self.provide_memproofs(config, app),
+            Action::Enable(app) => self.transition(config, app, AppStatusTransition::Enable),
+            Action::EnableFromApp(app) => match self.apps[app] {
+                Some(
+                    ModelStatus::Running
+                    | ModelStatus::Disabled(ModelDisabledReason::NotStartedAfterProvidingMemproofs),
+                ) => self.transition(config, app, AppStatusTransition::Enable),
+                _ => Err("app can't be enabled from the app interface".to_string()),
+            },
+            Action::Disable(app) => self.transition(
+                config,
+                app,
+                AppStatusTransition::Disable(DisabledAppReason::User),
+            ),
+            Action::Start(app) => self.transition(config, app, AppStatusTransition::Start),
+            Action::Uninstall(app) => {
+                self.apps[app] = None;
+                self.process_app_status_fx(config, AppStatusFx::SpinDown, None)
+            }
+            Action::BreakCell(cell) => {
+                self.broken[cell] = true;
+                Ok(())
+            }
+            Action::FixCell(cell) => {
+                self.broken[cell] = false;
+                Ok(())
+            }
+            Action::Restart => self.restart(config),
+        };
+    }
+
+    /// Mirrors `Conductor::initialize_conductor`.
+    fn restart(&mut self, config: &ModelConfig) -> Result<(), String> {
+        self.running.iter_mut().for_each(|r| *r = false);
+
+        // Mirrors `Conductor::start_paused_apps`
+        for status in self.apps.iter_mut().flatten() {
+            if *status == ModelStatus::Paused {
+                let _ = status.transition(AppStatusTransition::Start);
+            }
+        }
+        self.process_app_status_fx(config, AppStatusFx::SpinUp, None)
+    }
+
+    /// Mirrors `Conductor::provide_memproofs`.
+    fn provide_memproofs(&mut self, config: &ModelConfig, app: App) -> Result<(), String> {
+        if self.apps[app] != Some(ModelStatus::AwaitingMemproofs) {
+            return Err("app is not awaiting memproofs".to_string());
+        }
+        self.apps[app] = Some(ModelStatus::Disabled(
+            ModelDisabledReason::NotStartedAfterProvidingMemproofs,
+        ));
+        self.create_cells_for_running_apps(config, Some(app));
+        let fx = self.reconcile_app_status_with_cell_status(config, Some(app));
+        self.process_app_status_fx(config, fx, Some(app))
+    }
+
+    /// Mirrors `Conductor::transition_app_status` followed by `Conductor::process_app_status_fx`,
+    /// as used by `Conductor::enable_app`, `Conductor::disable_app` and `Conductor::start_app`.
+    fn transition(
+        &mut self,
+        config: &ModelConfig,
+        app: App,
+        transition: AppStatusTransition,
+    ) -> Result<(), String> {
+        let fx = match &mut self.apps[app] {
+            Some(status) => status.transition(transition),
+            None => return Err("app is not installed".to_string()),
+        };
+        self.process_app_status_fx(config, fx, Some(app))
+    }
+
+    /// Mirrors `Conductor::process_app_status_fx`.
+    fn process_app_status_fx(
+        &mut self,
+        config: &ModelConfig,
+        mut fx: AppStatusFx,
+        app: Option<App>,
+    ) -> Result<(), String> {
+        loop {
+            fx = match fx {
+                AppStatusFx::NoChange => return Ok(()),
+                AppStatusFx::SpinDown => {
+                    self.reconcile_cell_status_with_app_status(config);
+                    AppStatusFx::NoChange
+                }
+                AppStatusFx::SpinUp | AppStatusFx::Both => {
+                    self.reconcile_cell_status_with_app_status(config);
+                    self.reconcile_app_status_with_cell_status(config, app)
+                }
+                AppStatusFx::Error(e) => return Err(e),
+            };
+        }
+    }
+
+    /// Mirrors `Conductor::reconcile_cell_status_with_app_status`.
+    fn reconcile_cell_status_with_app_status(&mut self, config: &ModelConfig) {
+        // Mirrors `Conductor::remove_dangling_cells`
+        let keepers = self.cells_of(config, |status| {
+            matches!(status, ModelStatus::Running | ModelStatus::Paused)
+        });
+        for (cell, running) in self.running.iter_mut().enumerate() {
+            if !keepers.contains(&cell) {
+                *running = false;
+            }
+        }
+
+        self.create_cells_for_running_apps(config, None);
+    }
+
+    /// Mirrors `Conductor::create_and_add_initialized_cells_for_running_apps`.
+    fn create_cells_for_running_apps(&mut self, config: &ModelConfig, app: Option<App>) {
+        let cells = match app {
+            Some(app) if self.apps[app] == Some(ModelStatus::Running) => {
+                config.app_cells[app].iter().copied().collect()
+            }
+            Some(_) => HashSet::new(),
+            None => self.cells_of(config, |status| status == ModelStatus::Running),
+        };
+        for cell in cells {
+            if !self.broken[cell] {
+                self.running[cell] = true;
+            }
+        }
+    }
+
+    /// Mirrors `Conductor::reconcile_app_status_with_cell_status`.
+    fn reconcile_app_status_with_cell_status(
+        &mut self,
+        config: &ModelConfig,
+        only_app: Option<App>,
+    ) -> AppStatusFx {
+        let mut fx = AppStatusFx::NoChange;
+        for (app, status) in self.apps.iter_mut().enumerate() {
+            if only_app.is_some_and(|only| only != app) {
+                continue;
+            }
+            let Some(status) = status else {
+                continue;
+            };
+            let all_running = config.app_cells[app].iter().all(|c| self.running[*c]);
+            let delta = match status {
+                ModelStatus::Running if !all_running => status.transition(
+                    AppStatusTransition::Pause(PausedAppReason::Error(String::new())),
+                ),
+                ModelStatus::Paused if all_running => status.transition(AppStatusTransition::Start),
+                _ => AppStatusFx::NoChange,
+            };
+            fx = fx.combine(delta);
+        }
+        fx
+    }
+
+    /// The cells of every installed app with a status matching the predicate.
+    fn cells_of(&self, config: &ModelConfig, f: impl Fn(ModelStatus) -> bool) -> HashSet<Cell> {
+        self.apps
+            .iter()
+            .enumerate()
+            .filter(|(_, status)| status.is_some_and(&f))
+            .flat_map(|(app, _)| config.app_cells[app].iter().copied())
+            .collect()
+    }
+
+    /// Check the properties which must hold in every state.
+    fn check_invariants(&self, config: &ModelConfig) -> Result<(), String> {
+        let enabled = self.cells_of(config, |status| {
+            matches!(status, ModelStatus::Running | ModelStatus::Paused)
+        });
+        for (cell, running) in self.running.iter().enumerate() {
+            if *running && !enabled.contains(&cell) {
+                return Err(format!(
+                    "cell {cell} is running but doesn't belong to an enabled app"
+                ));
+            }
+        }
+
+        for (app, status) in self.apps.iter().enumerate() {
+            if *status == Some(ModelStatus::Running) {
+                if let Some(cell) = config.app_cells[app].iter().find(|c| !self.running[**c]) {
+                    return Err(format!("app {app} is running but cell {cell} is not"));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that once every cell can be created, a restart gets every enabled app running.
+    fn check_recovers(&self, config: &ModelConfig) -> Result<(), String> {
+        let mut state = self.clone();
+        state.broken.iter_mut().for_each(|b| *b = false);
+        state.apply(config, Action::Restart);
+
+        for (app, status) in state.apps.iter().enumerate() {
+            if *status == Some(ModelStatus::Paused) {
+                return Err(format!(
+                    "app {app} is still paused after a restart: {state:?}"
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ModelConfig {
+    /// The model config for these apps, numbering their cells by their position in `cells`.
+    fn from_apps(apps: &[InstalledAppCommon], cells: &[CellId]) -> Self {
+        Self {
+            app_cells: apps
+                .iter()
+                .map(|app| {
+                    app.required_cells()
+                        .map(|cell_id| cells.iter().position(|c| *c == cell_id).unwrap())
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+}
+
+impl ModelState {
+    /// Map the conductor state and the cells which are running onto the model. `apps` and
+    /// `cells` give the ids which the model numbers, and `broken` the cells which can't be
+    /// created.
+    fn project(
+        state: &ConductorState,
+        apps: &[InstalledAppId],
+        cells: &[CellId],
+        running_cells: &HashSet<CellId>,
+        broken: &HashSet<CellId>,
+    ) -> Self {
+        Self {
+            apps: apps
+                .iter()
+                .map(|id| {
+                    state
+                        .get_app(id)
+                        .ok()
+                        .map(|app| ModelStatus::from_status(app.status()))
+                })
+                .collect(),
+            running: cells.iter().map(|c| running_cells.contains(c)).collect(),
+            broken: cells.iter().map(|c| broken.contains(c)).collect(),
+        }
+    }
+}
+
+fn all_actions(config: &ModelConfig) -> Vec<Action> {
+    let mut actions = vec![Action::Restart];
+    for app in 0..config.app_cells.len() {
+        actions.extend([
+            Action::Install {
+                app,
+                defer_memproofs: false,
+            },
+            Action::Install {
+                app,
+                defer_memproofs: true,
+            },
+            Action::ProvideMemproofs(app),
+            Action::Enable(app),
+            Action::EnableFromApp(app),
+            Action::Disable(app),
+            Action::Start(app),
+            Action::Uninstall(app),
+        ]);
+    }
+    for cell in 0..config.num_cells() {
+        actions.extend([Action::BreakCell(cell), Action::FixCell(cell)]);
+    }
+    actions
+}
+
+/// Visit every state which can be reached from a conductor with no apps installed, checking
+/// the invariants and that a restart recovers every paused app in each of them. Returns the
+/// states visited, or the actions which lead to a failing state.
+fn explore(config: &ModelConfig) -> Result<HashSet<ModelState>, (Vec<Action>, String)> {
+    let actions = all_actions(config);
+    let initial = ModelState::new(config);
+    let mut seen = HashSet::from([initial.clone()]);
+    let mut frontier = VecDeque::from([(initial, vec![])]);
+
+    while let Some((state, trace)) = frontier.pop_front() {
+        state
+            .check_invariants(config)
+            .and_then(|_| state.check_recovers(config))
+            .map_err(|e| (trace.clone(), e))?;
+
+        for action in &actions {
+            if !state.enabled(*action) {
+                continue;
+            }
+            let mut next = state.clone();
+            next.apply(config, *action);
+            if seen.insert(next.clone()) {
+                let mut trace = trace.clone();
+                trace.push(*action);
+                frontier.push_back((next, trace));
+            }
+        }
+    }
+
+    Ok(seen)
+}
+
+mod tests {
+    use super::*;
+    use holochain_types::test_utils::fake_cell_id;
+
+    #[test]
+    fn project_conductor_state() {
+        let cells: Vec<_> = (1..=3).map(fake_cell_id).collect();
+        let apps = [
+            InstalledAppCommon::new_legacy(
+                "app0",
+                [InstalledCell::new(cells[0].clone(), "role".to_string())],
+            )
+            .unwrap(),
+            InstalledAppCommon::new_legacy(
+                "app1",
+                [
+                    InstalledCell::new(cells[1].clone(), "role1".to_string()),
+                    InstalledCell::new(cells[2].clone(), "role2".to_string()),
+                ],
+            )
+            .unwrap(),
+        ];
+        let app_ids: Vec<_> = apps.iter().map(|app| app.id().clone()).collect();
+        let config = ModelConfig::from_apps(&apps, &cells);
+        assert_eq!(vec![vec![0], vec![1, 2]], config.app_cells);
+
+        let mut state = ConductorState::default();
+        state.add_app(apps[1].clone()).unwrap();
+        let _ = state
+            .transition_app_status(&app_ids[1], AppStatusTransition::Enable)
+            .unwrap();
+
+        // The second app is running but one of its cells couldn't be created, which the
+        // conductor would resolve by pausing the app.
+        let running = [cells[1].clone()].into_iter().collect();
+        let broken = [cells[2].clone()].into_iter().collect();
+        let mut model = ModelState::project(&state, &app_ids, &cells, &running, &broken);
+        assert_eq!(
+            ModelState {
+                apps: vec![None, Some(ModelStatus::Running)],
+                running: vec![false, true, false],
+                broken: vec![false, false, true],
+            },
+            model
+        );
+        assert!(model.check_invariants(&config).is_err());
+
+        model.apply(&config, Action::Restart);
+        assert_eq!(
+            vec![None, Some(ModelStatus::Paused)],
+            model.apps,
+            "the app should be paused while its cell can't be created"
+        );
+        model.check_invariants(&config).unwrap();
+    }
+
+    #[test]
+    fn explore_app_lifecycle() {
+        let config = ModelConfig {
+            app_cells: vec![vec![0], vec![1, 2]],
+        };
+        let states = explore(&config).unwrap_or_else(|(trace, e)| panic!("{e}\nafter {trace:?}"));
+
+        // The invariants held with each app in every status which the conductor can leave
+        // it in, including paused because one of its cells can't be created.
+        for app in 0..config.app_cells.len() {
+            for status in [
+                None,
+                Some(ModelStatus::Running),
+                Some(ModelStatus::Paused),
+                Some(ModelStatus::Disabled(ModelDisabledReason::NeverStarted)),
+                Some(ModelStatus::Disabled(
+                    ModelDisabledReason::NotStartedAfterProvidingMemproofs,
+                )),
+                Some(ModelStatus::Disabled(ModelDisabledReason::User)),
+                Some(ModelStatus::AwaitingMemproofs),
+            ] {
+                assert!(
+                    states.iter().any(|state| state.apps[app] == status),
+                    "app {app} is never {status:?}"
+                );
+            }
+            assert!(states.iter().any(|state| {
+                state.apps[app] == Some(ModelStatus::Paused)
+                    && config.app_cells[app].iter().any(|cell| state.broken[*cell])
+            }));
+        }
+        // Both apps run at once.
+        assert!(states.iter().any(|state| state
+            .apps
+            .iter()
+            .all(|status| *status == Some(ModelStatus::Running))));
+    }
+}
@@ -0,0 +1,221 @@
+use super::*;
+use holochain_p2p::dht_arc::DhtArcSet;
+use holochain_state::arc_handoff::*;
+use kitsune_p2p::KitsuneAgent;
+use std::time::Duration;
+
+/// How often the DHT databases are checked for ops which have fallen outside the
+/// storage arcs of the local agents.
+pub(crate) const ARC_HANDOFF_INTERVAL: Duration = Duration::from_secs(60 * 10);
+
+/// The most ops of a DNA which are handed off in one round.
+pub(crate) const ARC_HANDOFF_BATCH_SIZE: usize = 500;
+
+/// How many bases are handed off to at once.
+const ARC_HANDOFF_CONCURRENCY: usize = 10;
+
+impl Conductor {
+    /// Hand off the ops which have fallen outside the storage arcs of the local agents
+    /// of each running DNA, every [`ARC_HANDOFF_INTERVAL`].
+    pub(crate) async fn hand_off_ops_outside_arcs_loop(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(ARC_HANDOFF_INTERVAL);
+        loop {
+            interval.tick().await;
+            let dna_hashes: HashSet<DnaHash> = self
+                .running_cell_ids()
+                .into_iter()
+                .map(|cell_id| cell_id.dna_hash().clone())
+                .collect();
+            for dna_hash in dna_hashes {
+                if let Err(err) = self.hand_off_ops_outside_arcs(&dna_hash).await {
+                    tracing::warn!(?err, ?dna_hash, "Failed to hand off ops outside of arcs");
+                }
+            }
+        }
+    }
+
+    /// Offer ops of a DNA which are outside the storage arcs of all of its local agents
+    /// to the authorities of their basis, and delete the ops which a remote authority
+    /// confirmed that it holds. Ops which no authority confirmed are kept, and offered
+    /// again next time.
+    /// Returns the number of ops which were deleted.
+    pub(crate) async fn hand_off_ops_outside_arcs(
+        &self,
+        dna_hash: &DnaHash,
+    ) -> ConductorResult<usize> {
+        let local_agents: HashSet<AgentPubKey> = self
+            .running_cell_ids()
+            .into_iter()
+            .filter(|cell_id| cell_id.dna_hash() == dna_hash)
+            .map(|cell_id| cell_id.agent_pubkey().clone())
+            .collect();
+        if local_agents.is_empty() {
+            return Ok(0);
+        }
+        // Until every local agent has published its arc, this node's arcs aren't known.
+        let arcs = self
+            .p2p_agents_db(dna_hash)
+            .read_async({
+                let agents = local_agents.clone();
+                let space = dna_hash.to_kitsune();
+                move |txn| -> DatabaseResult<Option<DhtArcSet>> {
+                    let mut arcs = DhtArcSet::new_empty();
+                    for agent in agents {
+                        let kitsune_agent = KitsuneAgent::new(agent.get_raw_36().to_vec());
+                        match txn.p2p_get_agent(space.clone(), &kitsune_agent)? {
+                            Some(info) => {
+                                arcs = arcs
+                                    .union(&DhtArcSet::from_interval(info.storage_arc().inner()))
+                            }
+                            None => return Ok(None),
+                        }
+                    }
+                    Ok(Some(arcs))
+                }
+            })
+            .await?;
+        let Some(arcs) = arcs else {
+            return Ok(0);
+        };
+        if matches!(arcs, DhtArcSet::Full) {
+            return Ok(0);
+        }
+
+        let ops = self
+            .spaces
+            .dht_db(dna_hash)?
+            .read_async(move |txn| ops_outside_arcs(txn, &arcs, ARC_HANDOFF_BATCH_SIZE))
+            .await?;
+        if ops.is_empty() {
+            return Ok(0);
+        }
+        let mut by_basis: HashMap<OpBasis, Vec<DhtOpHashed>> = HashMap::new();
+        for op in ops {
+            by_basis.entry(op.dht_basis()).or_default().push(op);
+        }
+
+        let network = self.holochain_p2p().to_dna(dna_hash.clone(), None);
+        let confirmed: Vec<DhtOpHash> = futures::stream::iter(by_basis)
+            .map(|(basis, ops)| {
+                let network = &network;
+                let local_agents = &local_agents;
+                async move {
+                    let (ops, offered): (Vec<DhtOp>, HashSet<DhtOpHash>) =
+                        ops.into_iter().map(|op| op.into_inner()).unzip();
+                    let held = match network.handoff_ops(basis.clone(), ops).await {
+                        Ok(held) => held,
+                        Err(err) => {
+                            tracing::debug!(?err, ?basis, "Failed to hand off ops");
+                            return Vec::new();
+                        }
+                    };
+                    // Local agents share the database which the ops are deleted from.
+                    held.into_iter()
+                        .filter(|(agent, _)| !local_agents.contains(agent))
+                        .flat_map(|(_, hashes)| hashes)
+                        .filter(|hash| offered.contains(hash))
+                        .collect::<HashSet<_>>()
+                        .into_iter()
+                        .collect::<Vec<_>>()
+                }
+            })
+            .buffer_unordered(ARC_HANDOFF_CONCURRENCY)
+            .concat()
+            .await;
+        if confirmed.is_empty() {
+            return Ok(0);
+        }
+
+        let deleted = self
+            .spaces
+            .dht_db(dna_hash)?
+            .write_async(move |txn| delete_handed_off_ops(txn, &confirmed))
+            .await?;
+        tracing::debug!(?dna_hash, deleted, "Handed off ops outside of arcs");
+        if deleted > 0 {
+            self.get_or_create_space(dna_hash)?
+                .dht_query_cache
+                .queries()
+                .clear();
+        }
+        Ok(deleted)
+    }
+
+    /// Hold the ops which a remote node is handing off to a local agent, if they are
+    /// within the agent's storage arc. Returns the hashes of those ops which are now held.
+    pub(crate) async fn receive_handed_off_ops(
+        &self,
+        dna_hash: &DnaHash,
+        to_agent: &AgentPubKey,
+        ops: Vec<DhtOp>,
+    ) -> ConductorResult<Vec<DhtOpHash>> {
+        let info = self
+            .p2p_agents_db(dna_hash)
+            .read_async({
+                let space = dna_hash.to_kitsune();
+                let agent = KitsuneAgent::new(to_agent.get_raw_36().to_vec());
+                move |txn| txn.p2p_get_agent(space, &agent)
+            })
+            .await?;
+        // Ops outside of the agent's arc would only be handed off again.
+        let Some(info) = info else {
+            return Ok(Vec::new());
+        };
+        let arc = info.storage_arc();
+        let ops: Vec<DhtOp> = ops
+            .into_iter()
+            .filter(|op| arc.contains(op.dht_basis().get_loc()))
+            .collect();
+        if ops.is_empty() {
+            return Ok(Vec::new());
+        }
+        let hashes: Vec<DhtOpHash> = ops.iter().map(DhtOpHash::with_data_sync).collect();
+        self.spaces
+            .handle_publish(dna_hash, false, false, ops)
+            .await?;
+        Ok(self
+            .spaces
+            .dht_db(dna_hash)?
+            .read_async(move |txn| held_op_hashes(txn, hashes))
+            .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sweettest::*;
+    use crate::test_utils::inline_zomes::simple_create_read_zome;
+    use holochain_state::prelude::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ops_are_only_handed_off_outside_of_arcs() {
+        holochain_trace::test_run();
+
+        let (dna_file, _, _) =
+            SweetDnaFile::unique_from_inline_zomes(("zome", simple_create_read_zome())).await;
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let app = conductor.setup_app("app", [&dna_file]).await.unwrap();
+        let (cell,) = app.into_tuple();
+
+        let _: ActionHash = conductor.call(&cell.zome("zome"), "create", ()).await;
+        await_consistency(10, [&cell]).await.unwrap();
+
+        let count_ops = || {
+            cell.dht_db().read_async(move |txn| {
+                DatabaseResult::Ok(txn.query_row("SELECT COUNT(*) FROM DhtOp", [], |row| {
+                    row.get::<_, usize>(0)
+                })?)
+            })
+        };
+        let before = count_ops().await.unwrap();
+
+        // A lone agent holds the full arc, so it has nothing to hand off.
+        let deleted = conductor
+            .raw_handle()
+            .hand_off_ops_outside_arcs(dna_file.dna_hash())
+            .await
+            .unwrap();
+        assert_eq!(0, deleted);
+        assert_eq!(before, count_ops().await.unwrap());
+    }
+}
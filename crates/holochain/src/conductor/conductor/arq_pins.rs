@@ -0,0 +1,85 @@
+use super::*;
+use holochain_p2p::HolochainP2pSender;
+use kitsune_p2p_types::dht::arq::ArqPin;
+
+impl Conductor {
+    /// Pin the storage arc of a cell, so that it holds what the pin says however the
+    /// network changes, or let it be sized dynamically again with `None`.
+    ///
+    /// The pin is kept in the conductor state, and applied again whenever the cell
+    /// joins the network. The new arc is published in the agent info of the cell, which
+    /// peers gossip and publish to it by.
+    pub async fn set_arq_pin(&self, cell_id: CellId, pin: Option<ArqPin>) -> ConductorResult<()> {
+        self.cell_by_id(&cell_id).await?;
+        self.update_state({
+            let cell_id = cell_id.clone();
+            let pin = pin.clone();
+            move |mut state| {
+                match pin {
+                    Some(pin) => state.arq_pins.insert(cell_id, pin),
+                    None => state.arq_pins.remove(&cell_id),
+                };
+                Ok(state)
+            }
+        })
+        .await?;
+        self.holochain_p2p
+            .set_arq_pin(
+                cell_id.dna_hash().clone(),
+                cell_id.agent_pubkey().clone(),
+                pin,
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// The storage arc which a cell is pinned to, if it is.
+    pub async fn arq_pin(&self, cell_id: &CellId) -> ConductorResult<Option<ArqPin>> {
+        Ok(self.get_state().await?.arq_pins.get(cell_id).cloned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sweettest::*;
+    use crate::test_utils::inline_zomes::simple_create_read_zome;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn pinned_arcs_are_published_and_kept_across_restarts() {
+        holochain_trace::test_run();
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let (dna_file, _, _) =
+            SweetDnaFile::unique_from_inline_zomes(("zome", simple_create_read_zome())).await;
+        let app = conductor.setup_app("app", [&dna_file]).await.unwrap();
+        let (alice,) = app.into_tuple();
+        let cell_id = alice.cell_id().clone();
+
+        conductor
+            .raw_handle()
+            .set_arq_pin(cell_id.clone(), Some(ArqPin::Empty))
+            .await
+            .unwrap();
+        tokio::time::timeout(std::time::Duration::from_secs(30), async {
+            loop {
+                let infos = conductor
+                    .get_agent_infos(Some(cell_id.clone()))
+                    .await
+                    .unwrap();
+                if infos.iter().any(|info| info.storage_arq.is_empty()) {
+                    break;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        conductor.shutdown().await;
+        conductor.startup().await;
+        assert_eq!(
+            Some(ArqPin::Empty),
+            conductor.raw_handle().arq_pin(&cell_id).await.unwrap()
+        );
+    }
+}
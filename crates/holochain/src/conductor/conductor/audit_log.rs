@@ -0,0 +1,83 @@
+use super::*;
+use holochain_conductor_api::DEFAULT_AUDIT_LOG_LIMIT;
+
+impl Conductor {
+    /// Append an admin request which changed the state of the conductor to the audit log.
+    pub(crate) async fn record_audit_log_entry(
+        &self,
+        connection: String,
+        action: &str,
+        target: Option<String>,
+        error: Option<String>,
+    ) -> ConductorResult<()> {
+        Ok(holochain_state::audit_log::record_audit_log_entry(
+            &self.spaces.conductor_db,
+            Timestamp::now(),
+            connection,
+            action.to_string(),
+            target,
+            error,
+        )
+        .await?)
+    }
+
+    /// List the audit log, oldest first, from `since` onwards.
+    pub async fn list_audit_log(
+        &self,
+        since: Option<Timestamp>,
+        limit: Option<u32>,
+    ) -> ConductorResult<Vec<AuditLogEntry>> {
+        Ok(holochain_state::audit_log::list_audit_log(
+            &self.spaces.conductor_db,
+            since.unwrap_or(Timestamp::MIN),
+            limit.unwrap_or(DEFAULT_AUDIT_LOG_LIMIT),
+        )
+        .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conductor::api::AdminInterfaceApi;
+    use crate::sweettest::*;
+    use holochain_conductor_api::{AdminRequest, AdminResponse};
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn admin_mutations_are_audited() {
+        holochain_trace::test_run();
+
+        let conductor = SweetConductor::from_standard_config().await;
+        let admin_api = AdminInterfaceApi::new(conductor.raw_handle())
+            .with_connection("127.0.0.1:1234 (http://localhost)".to_string());
+        let request = |request| {
+            let admin_api = admin_api.clone();
+            async move { admin_api.handle_request(Ok(request)).await.unwrap() }
+        };
+
+        request(AdminRequest::GenerateAgentPubKey).await;
+        request(AdminRequest::EnableApp {
+            installed_app_id: "missing".to_string(),
+        })
+        .await;
+        // Requests which only read state aren't audited.
+        request(AdminRequest::ListDnas).await;
+
+        let AdminResponse::AuditLogListed(entries) = request(AdminRequest::ListAuditLog {
+            since: None,
+            limit: None,
+        })
+        .await
+        else {
+            panic!("expected the audit log");
+        };
+        assert_eq!(2, entries.len());
+        assert_eq!("generate_agent_pub_key", entries[0].action);
+        assert_eq!(None, entries[0].error);
+        assert_eq!("enable_app", entries[1].action);
+        assert_eq!(Some("missing".to_string()), entries[1].target);
+        assert!(entries[1].error.is_some());
+        assert!(entries
+            .iter()
+            .all(|entry| entry.connection == "127.0.0.1:1234 (http://localhost)"));
+    }
+}
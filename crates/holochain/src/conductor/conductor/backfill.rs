@@ -0,0 +1,236 @@
+use super::*;
+use holochain_p2p::dht_arc::{DhtArcSet, DhtLocation};
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// How often the backfill progress of the running cells is measured.
+pub(crate) const BACKFILL_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// What is kept about the backfill of a running cell between measurements.
+#[derive(Debug, Clone)]
+pub(crate) struct BackfillTracker {
+    started_at: Instant,
+    /// The integrated ops at the last measurement, and when it was taken.
+    last_measurement: Option<(u64, Instant)>,
+    ops_per_minute: u64,
+    complete: bool,
+}
+
+impl BackfillTracker {
+    fn new() -> Self {
+        Self {
+            started_at: Instant::now(),
+            last_measurement: None,
+            ops_per_minute: 0,
+            complete: false,
+        }
+    }
+}
+
+impl Conductor {
+    /// Measure the backfill progress of each running cell every [`BACKFILL_CHECK_INTERVAL`],
+    /// and emit a [`SystemSignal::BackfillProgress`] to the apps of each cell until its
+    /// backfill is complete.
+    pub(crate) async fn track_backfill_loop(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(BACKFILL_CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            let cell_ids = self.running_cell_ids();
+            self.backfill_trackers
+                .share_mut(|trackers| trackers.retain(|cell_id, _| cell_ids.contains(cell_id)));
+            for cell_id in cell_ids {
+                let complete = self.backfill_trackers.share_ref(|trackers| {
+                    trackers
+                        .get(&cell_id)
+                        .is_some_and(|tracker| tracker.complete)
+                });
+                if complete {
+                    continue;
+                }
+                if let Err(err) = self.update_backfill_progress(&cell_id).await {
+                    tracing::warn!(?err, ?cell_id, "Failed to measure backfill progress");
+                }
+            }
+        }
+    }
+
+    /// How far a running cell is with getting the ops of its storage arc.
+    pub async fn backfill_progress(&self, cell_id: &CellId) -> ConductorResult<BackfillProgress> {
+        if !self.running_cell_ids().contains(cell_id) {
+            return Err(ConductorError::CellMissing(cell_id.clone()));
+        }
+        let tracker = self.backfill_tracker(cell_id);
+        let (integrated_ops, pending_ops, ops_to_fetch, gossip_rounds) =
+            self.measure_backfill(cell_id, tracker.started_at).await?;
+        Ok(BackfillProgress::new(
+            cell_id.clone(),
+            integrated_ops,
+            pending_ops,
+            ops_to_fetch,
+            gossip_rounds,
+            tracker.ops_per_minute,
+        ))
+    }
+
+    /// Measure the backfill progress of a cell, update the integration rate, and
+    /// signal the progress to the apps which use the cell.
+    async fn update_backfill_progress(&self, cell_id: &CellId) -> ConductorResult<()> {
+        let tracker = self.backfill_tracker(cell_id);
+        let (integrated_ops, pending_ops, ops_to_fetch, gossip_rounds) =
+            self.measure_backfill(cell_id, tracker.started_at).await?;
+        let now = Instant::now();
+        let ops_per_minute = match tracker.last_measurement {
+            Some((last_integrated, last_at)) if now > last_at => {
+                let elapsed_ms = now.duration_since(last_at).as_millis().max(1) as u64;
+                integrated_ops.saturating_sub(last_integrated) * 60_000 / elapsed_ms
+            }
+            _ => tracker.ops_per_minute,
+        };
+        let progress = BackfillProgress::new(
+            cell_id.clone(),
+            integrated_ops,
+            pending_ops,
+            ops_to_fetch,
+            gossip_rounds,
+            ops_per_minute,
+        );
+        self.backfill_trackers.share_mut(|trackers| {
+            if let Some(tracker) = trackers.get_mut(cell_id) {
+                tracker.last_measurement = Some((integrated_ops, now));
+                tracker.ops_per_minute = ops_per_minute;
+                tracker.complete = progress.complete;
+            }
+        });
+
+        for app_id in self
+            .list_running_apps_for_dependent_cell_id(cell_id)
+            .await?
+        {
+            // It's fine if no interface is listening.
+            let _ = self
+                .app_broadcast
+                .create_send_handle(app_id)
+                .send(Signal::System(SystemSignal::BackfillProgress(
+                    progress.clone(),
+                )));
+        }
+        Ok(())
+    }
+
+    /// The backfill tracker of a cell, which starts being tracked if it isn't yet.
+    fn backfill_tracker(&self, cell_id: &CellId) -> BackfillTracker {
+        self.backfill_trackers.share_mut(|trackers| {
+            trackers
+                .entry(cell_id.clone())
+                .or_insert_with(BackfillTracker::new)
+                .clone()
+        })
+    }
+
+    /// Count the integrated and pending ops in the storage arc of a cell's agent, the ops
+    /// which are being fetched for its DNA, and the gossip rounds of the DNA which were
+    /// completed since `started_at`.
+    async fn measure_backfill(
+        &self,
+        cell_id: &CellId,
+        started_at: Instant,
+    ) -> ConductorResult<(u64, u64, u64, u32)> {
+        let dna_hash = cell_id.dna_hash();
+        // - Until the agent has joined the network, it has no arc to get ops for.
+        let arcs = self
+            .p2p_agents_db(dna_hash)
+            .p2p_get_agent(&cell_id.agent_pubkey().to_kitsune())
+            .await?
+            .map(|info| DhtArcSet::from_interval(info.storage_arc().inner()))
+            .unwrap_or_else(DhtArcSet::new_empty);
+        let (integrated_ops, pending_ops) = self
+            .get_or_create_dht_db(dna_hash)?
+            .read_async(move |txn| -> DatabaseResult<(u64, u64)> {
+                let mut stmt = txn
+                    .prepare("SELECT storage_center_loc, when_integrated IS NOT NULL FROM DhtOp")?;
+                let mut rows = stmt.query([])?;
+                let (mut integrated, mut pending) = (0, 0);
+                while let Some(row) = rows.next()? {
+                    if !arcs.contains(DhtLocation::new(row.get(0)?)) {
+                        continue;
+                    }
+                    if row.get::<_, bool>(1)? {
+                        integrated += 1;
+                    } else {
+                        pending += 1;
+                    }
+                }
+                Ok((integrated, pending))
+            })
+            .await?;
+
+        let diagnostics = self.holochain_p2p.get_diagnostics(dna_hash.clone()).await?;
+        let ops_to_fetch = diagnostics
+            .fetch_pool
+            .info([dna_hash.to_kitsune()].into_iter().collect())
+            .num_ops_to_fetch as u64;
+        let gossip_rounds = diagnostics
+            .metrics
+            .read()
+            .peer_node_histories()
+            .values()
+            .flat_map(|history| history.completed_rounds.iter())
+            .filter(|round| !round.error && round.end_time >= started_at)
+            .count() as u32;
+        Ok((integrated_ops, pending_ops, ops_to_fetch, gossip_rounds))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sweettest::*;
+    use crate::test_utils::inline_zomes::simple_create_read_zome;
+    use holochain_state::prelude::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn backfill_of_a_newly_joined_cell_completes() {
+        holochain_trace::test_run();
+
+        let mut conductors = SweetConductorBatch::from_standard_config(2).await;
+        let (dna_file, _, _) =
+            SweetDnaFile::unique_from_inline_zomes(("zome", simple_create_read_zome())).await;
+        let alice = conductors[0]
+            .setup_app("app", [&dna_file])
+            .await
+            .unwrap()
+            .into_cells()
+            .remove(0);
+        for _ in 0..3 {
+            let _: ActionHash = conductors[0].call(&alice.zome("zome"), "create", ()).await;
+        }
+
+        // - Bob joins the DHT once Alice has already authored ops.
+        let bob = conductors[1]
+            .setup_app("app", [&dna_file])
+            .await
+            .unwrap()
+            .into_cells()
+            .remove(0);
+        conductors.exchange_peer_info().await;
+
+        let progress = tokio::time::timeout(std::time::Duration::from_secs(60), async {
+            loop {
+                let progress = conductors[1]
+                    .raw_handle()
+                    .backfill_progress(bob.cell_id())
+                    .await
+                    .unwrap();
+                if progress.complete {
+                    return progress;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+            }
+        })
+        .await
+        .unwrap();
+        assert!(progress.gossip_rounds > 0);
+        assert_eq!(progress.integrated_ops, progress.estimated_total_ops);
+        assert_eq!(Some(0), progress.eta_secs);
+        assert!(progress.integrated_ops > 0);
+    }
+}
@@ -7,6 +7,7 @@ use crate::conductor::ribosome_store::RibosomeStore;
 use crate::conductor::ConductorHandle;
 use holochain_conductor_api::conductor::paths::KeystorePath;
 use holochain_p2p::NetworkCompatParams;
+use kitsune_p2p_bootstrap_client::DynBootstrapService;
 
 /// A configurable Builder for Conductor and sometimes ConductorHandle
 #[derive(Default)]
@@ -14,6 +15,10 @@ pub struct ConductorBuilder {
     /// The configuration
     pub config: ConductorConfig,
 
+    /// The file the configuration was loaded from, if any,
+    /// which is read again when the config is reloaded.
+    pub config_path: Option<PathBuf>,
+
     /// The RibosomeStore (mockable)
     pub ribosome_store: RibosomeStore,
 
@@ -38,6 +43,10 @@ pub struct ConductorBuilder {
     #[cfg(any(test, feature = "test_utils"))]
     pub generate_test_device_seed: bool,
 
+    /// Optional bootstrap service, used instead of the bootstrap server of the network config,
+    /// e.g. to let conductors in the same process find each other without a bootstrap server
+    pub bootstrap_service: Option<DynBootstrapService>,
+
     /// Skip printing setup info to stdout
     pub no_print_setup: bool,
 
@@ -62,12 +71,26 @@ impl ConductorBuilder {
         self
     }
 
+    /// Set the file the ConductorConfig was loaded from,
+    /// so that it can be reloaded while the conductor is running
+    pub fn config_path(mut self, config_path: PathBuf) -> Self {
+        self.config_path = Some(config_path);
+        self
+    }
+
     /// Set the passphrase for use in keystore initialization
     pub fn passphrase(mut self, passphrase: Option<sodoken::BufRead>) -> Self {
         self.passphrase = passphrase;
         self
     }
 
+    /// Set the bootstrap service which agent infos are put to and peers are found through,
+    /// instead of the bootstrap server of the network config
+    pub fn with_bootstrap_service(mut self, bootstrap_service: DynBootstrapService) -> Self {
+        self.bootstrap_service = Some(bootstrap_service);
+        self
+    }
+
     /// Set up the builder to skip printing setup
     pub fn no_print_setup(mut self) -> Self {
         self.no_print_setup = true;
@@ -142,6 +165,22 @@ impl ConductorBuilder {
                     Some(p) => Ok(p.to_owned()),
                 }
             };
+            let get_keystore_config_path =
+                |lair_root: &Option<KeystorePath>| -> ConductorResult<std::path::PathBuf> {
+                    let keystore_root_path: KeystorePath = match lair_root {
+                        Some(lair_root) => lair_root.clone(),
+                        None => builder
+                            .config
+                            .data_root_path
+                            .as_ref()
+                            .ok_or(ConductorError::NoDataRootPath)?
+                            .clone()
+                            .try_into()?,
+                    };
+                    Ok(keystore_root_path
+                        .as_ref()
+                        .join("lair-keystore-config.yaml"))
+                };
             match &builder.config.keystore {
                 KeystoreConfig::DangerTestKeystore => {
                     holochain_keystore::spawn_test_keystore().await?
@@ -160,19 +199,7 @@ impl ConductorBuilder {
                 KeystoreConfig::LairServerInProc { lair_root } => {
                     warn_no_encryption();
 
-                    let keystore_root_path: KeystorePath = match lair_root {
-                        Some(lair_root) => lair_root.clone(),
-                        None => builder
-                            .config
-                            .data_root_path
-                            .as_ref()
-                            .ok_or(ConductorError::NoDataRootPath)?
-                            .clone()
-                            .try_into()?,
-                    };
-                    let keystore_config_path = keystore_root_path
-                        .as_ref()
-                        .join("lair-keystore-config.yaml");
+                    let keystore_config_path = get_keystore_config_path(lair_root)?;
                     let passphrase = get_passphrase()?;
 
                     match spawn_lair_keystore_in_proc(&keystore_config_path, passphrase).await {
@@ -183,7 +210,55 @@ impl ConductorBuilder {
                         }
                     }
                 }
+                KeystoreConfig::LairEmbedded {
+                    in_memory: true, ..
+                } => holochain_keystore::spawn_mem_keystore().await?,
+                KeystoreConfig::LairEmbedded {
+                    lair_root,
+                    in_memory: false,
+                } => {
+                    warn_no_encryption();
+
+                    let keystore_config_path = get_keystore_config_path(lair_root)?;
+                    let passphrase = get_passphrase()?;
+
+                    match spawn_lair_keystore_embedded(&keystore_config_path, passphrase).await {
+                        Ok(keystore) => keystore,
+                        Err(err) => {
+                            tracing::error!(?err, "Failed to spawn embedded Lair keystore");
+                            return Err(err.into());
+                        }
+                    }
+                }
+            }
+        };
+
+        #[cfg(feature = "remote_signing")]
+        let keystore = match &builder.config.remote_signing {
+            Some(remote_signing) => {
+                let auth_token = match &remote_signing.auth_token_file {
+                    Some(path) => Some(
+                        tokio::fs::read_to_string(path)
+                            .await
+                            .map_err(|e| ConductorError::Other(e.into()))?
+                            .trim()
+                            .to_string(),
+                    ),
+                    None => None,
+                };
+                let backend = holochain_keystore::remote_signer::HttpSigningBackend::new(
+                    remote_signing.url.clone(),
+                    auth_token,
+                );
+                match keystore.with_signing_backend(Arc::new(backend)).await {
+                    Ok(keystore) => keystore,
+                    Err(err) => {
+                        tracing::error!(?err, "Failed to connect to the remote signing service");
+                        return Err(err.into());
+                    }
+                }
             }
+            None => keystore,
         };
 
         info!("Conductor startup: passphrase obtained.");
@@ -233,6 +308,7 @@ impl ConductorBuilder {
             strat,
             Some(tag_ed),
             Some(keystore.lair_client()),
+            builder.bootstrap_service.clone(),
         );
 
         // TODO: when we make DPKI optional, we can remove the unwrap_or and just let it be None,
@@ -286,13 +362,14 @@ impl ConductorBuilder {
 
         let conductor = Conductor::new(
             config.clone(),
+            builder.config_path.clone(),
             ribosome_store,
             keystore,
             holochain_p2p,
             spaces,
             post_commit_sender,
             outcome_tx,
-        );
+        )?;
 
         let shutting_down = conductor.shutting_down.clone();
 
@@ -408,12 +485,65 @@ impl ConductorBuilder {
             .map(Ok)
         });
 
+        let conductor2 = conductor.clone();
+        tm.add_conductor_task_ignored("purge_expired_ops", move || {
+            conductor2.purge_expired_ops_loop().map(Ok)
+        });
+
+        let conductor2 = conductor.clone();
+        tm.add_conductor_task_ignored("prefetch_hot_hashes", move || {
+            conductor2.prefetch_hot_hashes_loop().map(Ok)
+        });
+
+        conductor.restore_remote_outbox().await?;
+        let conductor2 = conductor.clone();
+        tm.add_conductor_task_ignored("deliver_remote_outbox", move || {
+            conductor2.deliver_remote_outbox_loop().map(Ok)
+        });
+
+        let conductor2 = conductor.clone();
+        tm.add_conductor_task_ignored("hand_off_ops_outside_arcs", move || {
+            conductor2.hand_off_ops_outside_arcs_loop().map(Ok)
+        });
+
+        let conductor2 = conductor.clone();
+        tm.add_conductor_task_ignored("monitor_region_redundancy", move || {
+            conductor2.monitor_region_redundancy_loop().map(Ok)
+        });
+
+        let conductor2 = conductor.clone();
+        tm.add_conductor_task_ignored("audit_published_ops", move || {
+            conductor2.audit_published_ops_loop().map(Ok)
+        });
+
+        let conductor2 = conductor.clone();
+        tm.add_conductor_task_ignored("track_backfill", move || {
+            conductor2.track_backfill_loop().map(Ok)
+        });
+
+        let conductor2 = conductor.clone();
+        tm.add_conductor_task_ignored("ingest_admission", move || {
+            conductor2.ingest_admission_loop().map(Ok)
+        });
+
         let configs = config.admin_interfaces.clone().unwrap_or_default();
         let cell_startup_errors = conductor
             .clone()
             .initialize_conductor(outcome_receiver, configs)
             .await?;
 
+        if let Some(health_check) = &config.health_check {
+            let listener = tokio::net::TcpListener::bind(health_check.bind_address).await?;
+            info!(
+                "Conductor startup: health check listening on {}.",
+                listener.local_addr()?
+            );
+            let conductor2 = conductor.clone();
+            tm.add_conductor_task_ignored("health_check_listener", move || {
+                serve_health_check(conductor2, listener).map(Ok)
+            });
+        }
+
         // TODO: This should probably be emitted over the admin interface
         if !cell_startup_errors.is_empty() {
             error!(
@@ -505,6 +635,7 @@ impl ConductorBuilder {
             strat,
             Some(tag_ed),
             Some(keystore.lair_client()),
+            builder.bootstrap_service.clone(),
         );
 
         // TODO: when we make DPKI optional, we can remove the unwrap_or and just let it be None,
@@ -549,13 +680,14 @@ impl ConductorBuilder {
 
         let conductor = Conductor::new(
             config.clone(),
+            builder.config_path.clone(),
             ribosome_store,
             keystore,
             holochain_p2p,
             spaces,
             post_commit_sender,
             outcome_tx,
-        );
+        )?;
 
         let conductor = Self::update_fake_state(builder.state, conductor).await?;
 
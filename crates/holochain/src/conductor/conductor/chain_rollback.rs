@@ -0,0 +1,331 @@
+use super::*;
+use rusqlite::OptionalExtension;
+
+/// The sequence number of the last action of genesis, which a chain can't be rolled
+/// back past.
+const LAST_GENESIS_SEQ: u32 = 2;
+
+impl Conductor {
+    /// Remove the actions of a cell's source chain which come after `to`, to recover
+    /// from bad commits made while developing an app. Returns the hashes of the actions
+    /// which were removed, newest first.
+    ///
+    /// This is refused unless the removed actions are only known to this conductor:
+    /// the cell must be disabled, so that its chain head can't move, none of the ops of
+    /// the removed actions may have been published, have validation receipts or be
+    /// integrated into the DHT database, from which they could have been gossiped, and
+    /// the chain can't be rolled back past genesis or be held by a chain head
+    /// coordinator. The copies of the ops which are still waiting to be validated in
+    /// the DHT database are removed along with the actions.
+    pub async fn rollback_source_chain(
+        &self,
+        cell_id: &CellId,
+        to: &ActionHash,
+    ) -> ConductorResult<Vec<ActionHash>> {
+        match self.cell_by_id(cell_id).await {
+            Ok(_) => {
+                return Err(ConductorError::ChainRollbackRefused(
+                    "the cell is running, its app must be disabled first".into(),
+                ))
+            }
+            Err(ConductorError::CellDisabled(_)) => {}
+            Err(e) => return Err(e),
+        }
+        if self.get_chc(cell_id).is_some() {
+            return Err(ConductorError::ChainRollbackRefused(
+                "the chain is held by a chain head coordinator".into(),
+            ));
+        }
+
+        let space = self.get_or_create_space(cell_id.dna_hash())?;
+        let author = cell_id.agent_pubkey().clone();
+        let authored_db = space.get_or_create_authored_db(author.clone())?;
+        let (seq, removed, published) = authored_db
+            .read_async({
+                let author = author.clone();
+                let to = to.clone();
+                move |txn| {
+                    let seq: Option<u32> = txn
+                        .query_row(
+                            "SELECT seq FROM Action WHERE hash = :hash AND author = :author",
+                            rusqlite::named_params! { ":hash": to, ":author": author },
+                            |row| row.get(0),
+                        )
+                        .optional()?;
+                    let Some(seq) = seq else {
+                        return DatabaseResult::Ok(None);
+                    };
+                    let removed = txn
+                        .prepare(
+                            "
+                            SELECT hash FROM Action
+                            WHERE author = :author AND seq > :seq
+                            ORDER BY seq DESC
+                            ",
+                        )?
+                        .query_map(
+                            rusqlite::named_params! { ":author": author, ":seq": seq },
+                            |row| row.get(0),
+                        )?
+                        .collect::<Result<Vec<ActionHash>, _>>()?;
+                    let published: usize = txn.query_row(
+                        "
+                        SELECT COUNT(*) FROM DhtOp
+                        JOIN Action ON DhtOp.action_hash = Action.hash
+                        WHERE Action.author = :author AND Action.seq > :seq
+                        AND (
+                            DhtOp.last_publish_time IS NOT NULL
+                            OR DhtOp.receipts_complete IS NOT NULL
+                        )
+                        ",
+                        rusqlite::named_params! { ":author": author, ":seq": seq },
+                        |row| row.get(0),
+                    )?;
+                    Ok(Some((seq, removed, published)))
+                }
+            })
+            .await?
+            .ok_or_else(|| {
+                ConductorError::ChainRollbackRefused(format!(
+                    "{} isn't an action of the chain of {}",
+                    to, author
+                ))
+            })?;
+        if seq < LAST_GENESIS_SEQ {
+            return Err(ConductorError::ChainRollbackRefused(
+                "the chain can't be rolled back past genesis".into(),
+            ));
+        }
+        if published > 0 {
+            return Err(ConductorError::ChainRollbackRefused(format!(
+                "{} ops of the actions after {} have been published",
+                published, to
+            )));
+        }
+        let (receipts, integrated) = space
+            .dht_db
+            .read_async({
+                let author = author.clone();
+                move |txn| {
+                    let receipts: usize = txn.query_row(
+                        "
+                        SELECT COUNT(*) FROM ValidationReceipt
+                        JOIN DhtOp ON ValidationReceipt.op_hash = DhtOp.hash
+                        JOIN Action ON DhtOp.action_hash = Action.hash
+                        WHERE Action.author = :author AND Action.seq > :seq
+                        ",
+                        rusqlite::named_params! { ":author": author, ":seq": seq },
+                        |row| row.get(0),
+                    )?;
+                    // Integrated ops can be gossiped without being published.
+                    let integrated: usize = txn.query_row(
+                        "
+                        SELECT COUNT(*) FROM DhtOp
+                        JOIN Action ON DhtOp.action_hash = Action.hash
+                        WHERE Action.author = :author AND Action.seq > :seq
+                        AND DhtOp.when_integrated IS NOT NULL
+                        ",
+                        rusqlite::named_params! { ":author": author, ":seq": seq },
+                        |row| row.get(0),
+                    )?;
+                    DatabaseResult::Ok((receipts, integrated))
+                }
+            })
+            .await?;
+        if receipts > 0 {
+            return Err(ConductorError::ChainRollbackRefused(format!(
+                "the ops of the actions after {} have {} validation receipts",
+                to, receipts
+            )));
+        }
+        if integrated > 0 {
+            return Err(ConductorError::ChainRollbackRefused(format!(
+                "{} ops of the actions after {} have been integrated into the DHT database",
+                integrated, to
+            )));
+        }
+        if removed.is_empty() {
+            return Ok(removed);
+        }
+
+        // The ops of the actions are deleted along with them, and so are their entries
+        // unless other actions commit them too.
+        authored_db
+            .write_async({
+                let author = author.clone();
+                move |txn| delete_actions_after_seq(txn, &author, seq)
+            })
+            .await?;
+        space
+            .dht_db
+            .write_async(move |txn| delete_actions_after_seq(txn, &author, seq))
+            .await?;
+        space
+            .dht_query_cache
+            .roll_back_activity(cell_id.agent_pubkey(), seq)
+            .await?;
+        space.dht_query_cache.queries().clear();
+        tracing::warn!(?cell_id, %to, removed = removed.len(), "Rolled back a source chain");
+        Ok(removed)
+    }
+}
+
+fn delete_actions_after_seq(
+    txn: &mut Transaction,
+    author: &AgentPubKey,
+    seq: u32,
+) -> StateMutationResult<()> {
+    let entry_hashes = txn
+        .prepare(
+            "
+            SELECT DISTINCT entry_hash FROM Action
+            WHERE author = :author AND seq > :seq AND entry_hash IS NOT NULL
+            ",
+        )?
+        .query_map(
+            rusqlite::named_params! { ":author": author, ":seq": seq },
+            |row| row.get(0),
+        )?
+        .collect::<Result<Vec<EntryHash>, _>>()?;
+    txn.execute(
+        holochain_sqlite::sql::sql_cell::DELETE_ACTIONS_AFTER_SEQ,
+        rusqlite::named_params! { ":author": author, ":seq": seq },
+    )?;
+    delete_unreferenced_entries(txn, &entry_hashes)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sweettest::*;
+    use crate::test_utils::inline_zomes::simple_create_read_zome;
+    use matches::assert_matches;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chains_are_only_rolled_back_before_publishing() {
+        holochain_trace::test_run();
+        let mut conductor = SweetConductorConfig::standard()
+            .no_dpki()
+            .build_conductor()
+            .await;
+        let (dna_file, _, _) =
+            SweetDnaFile::unique_from_inline_zomes(("zome", simple_create_read_zome())).await;
+        let app = conductor.setup_app("app", [&dna_file]).await.unwrap();
+        let (alice,) = app.into_tuple();
+        let cell_id = alice.cell_id().clone();
+        let zome = alice.zome("zome");
+        let authored_db = conductor
+            .get_or_create_authored_db(cell_id.dna_hash(), cell_id.agent_pubkey().clone())
+            .unwrap();
+        let dht_db = conductor.get_or_create_dht_db(cell_id.dna_hash()).unwrap();
+
+        let kept: ActionHash = conductor.call(&zome, "create", ()).await;
+        let removed: ActionHash = conductor.call(&zome, "create", ()).await;
+        await_consistency(10, [&alice]).await.unwrap();
+
+        // - Nothing is rolled back while the cell is running.
+        assert_matches!(
+            conductor.rollback_source_chain(&cell_id, &kept).await,
+            Err(ConductorError::ChainRollbackRefused(_))
+        );
+        conductor
+            .disable_app("app".to_string(), DisabledAppReason::User)
+            .await
+            .unwrap();
+
+        // - Nor once the ops have been published.
+        authored_db
+            .write_async(|txn| {
+                txn.execute("UPDATE DhtOp SET last_publish_time = 1", [])?;
+                DatabaseResult::Ok(())
+            })
+            .await
+            .unwrap();
+        assert_matches!(
+            conductor.rollback_source_chain(&cell_id, &kept).await,
+            Err(ConductorError::ChainRollbackRefused(_))
+        );
+
+        // - Commits which were never published are rolled back.
+        authored_db
+            .write_async(|txn| {
+                txn.execute(
+                    "UPDATE DhtOp SET last_publish_time = NULL, receipts_complete = NULL",
+                    [],
+                )?;
+                DatabaseResult::Ok(())
+            })
+            .await
+            .unwrap();
+        dht_db
+            .write_async(|txn| {
+                txn.execute("DELETE FROM ValidationReceipt", [])?;
+                DatabaseResult::Ok(())
+            })
+            .await
+            .unwrap();
+
+        // - Nor while the ops are integrated into the DHT database, from which
+        //   they may have been gossiped.
+        assert_matches!(
+            conductor.rollback_source_chain(&cell_id, &kept).await,
+            Err(ConductorError::ChainRollbackRefused(reason)) if reason.contains("integrated")
+        );
+        dht_db
+            .write_async(|txn| {
+                txn.execute("UPDATE DhtOp SET when_integrated = NULL", [])?;
+                DatabaseResult::Ok(())
+            })
+            .await
+            .unwrap();
+
+        let rolled_back = conductor
+            .rollback_source_chain(&cell_id, &kept)
+            .await
+            .unwrap();
+        assert_eq!(vec![removed.clone()], rolled_back);
+        assert_eq!(0, ops_of(&authored_db, &removed).await);
+        assert_eq!(0, ops_of(&dht_db, &removed).await);
+        // - The removed action committed the same entry as the kept one, which is kept.
+        let kept_entries: usize = authored_db
+            .read_async({
+                let kept = kept.clone();
+                move |txn| {
+                    txn.query_row(
+                        "
+                        SELECT COUNT(*) FROM Entry
+                        JOIN Action ON Action.entry_hash = Entry.hash
+                        WHERE Action.hash = ?
+                        ",
+                        [kept],
+                        |row| row.get(0),
+                    )
+                    .map_err(DatabaseError::from)
+                }
+            })
+            .await
+            .unwrap();
+        assert_eq!(1, kept_entries);
+
+        // - The chain carries on from where it was rolled back to.
+        conductor.enable_app("app".to_string()).await.unwrap();
+        let next: ActionHash = conductor.call(&zome, "create", ()).await;
+        let record: Option<Record> = conductor.call(&zome, "read", next).await;
+        assert_eq!(&kept, record.unwrap().action().prev_action().unwrap());
+    }
+
+    async fn ops_of<Kind: DbKindT>(db: &DbWrite<Kind>, action_hash: &ActionHash) -> usize {
+        let action_hash = action_hash.clone();
+        db.read_async(move |txn| {
+            txn.query_row(
+                "SELECT COUNT(*) FROM DhtOp WHERE action_hash = ?",
+                [action_hash],
+                |row| row.get(0),
+            )
+            .map_err(DatabaseError::from)
+        })
+        .await
+        .unwrap()
+    }
+}
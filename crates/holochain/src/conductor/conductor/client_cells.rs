@@ -0,0 +1,73 @@
+use super::*;
+
+impl Conductor {
+    /// The cells of the installed apps which were installed with
+    /// [`InstallAppPayload::client_only`].
+    pub(crate) async fn installed_client_cell_ids(&self) -> ConductorResult<HashSet<CellId>> {
+        Ok(self
+            .get_state()
+            .await?
+            .installed_apps_and_services()
+            .values()
+            .filter(|app| app.client_only)
+            .flat_map(|app| app.all_cells())
+            .collect())
+    }
+
+    /// Whether a cell is a pure client of the network, which holds no data for other
+    /// agents.
+    pub fn is_client_cell(&self, cell_id: &CellId) -> bool {
+        self.client_cells.share_ref(|cells| cells.contains(cell_id))
+    }
+
+    /// The running cells which aren't pure clients, and so send validation receipts
+    /// for the ops which they validate.
+    pub(crate) fn running_authority_cell_ids(&self) -> HashSet<CellId> {
+        let mut cell_ids = self.running_cell_ids();
+        self.client_cells
+            .share_ref(|clients| cell_ids.retain(|cell_id| !clients.contains(cell_id)));
+        cell_ids
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sweettest::*;
+    use crate::test_utils::inline_zomes::simple_create_read_zome;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn client_only_cells_hold_an_empty_arc() {
+        holochain_trace::test_run();
+        let conductor = SweetConductor::from_standard_config().await;
+        let (dna_file, _, _) =
+            SweetDnaFile::unique_from_inline_zomes(("zome", simple_create_read_zome())).await;
+        let mut payload =
+            get_install_app_payload_from_dnas("app", None, &[(dna_file, None)], None).await;
+        payload.allow_throwaway_random_agent_key = true;
+        payload.client_only = true;
+        let app = conductor.clone().install_app_bundle(payload).await.unwrap();
+        conductor.enable_app("app".into()).await.unwrap();
+        let cell_id = app.all_cells().next().unwrap();
+
+        assert!(conductor.raw_handle().is_client_cell(&cell_id));
+        assert!(!conductor
+            .raw_handle()
+            .running_authority_cell_ids()
+            .contains(&cell_id));
+        tokio::time::timeout(std::time::Duration::from_secs(30), async {
+            loop {
+                let infos = conductor
+                    .get_agent_infos(Some(cell_id.clone()))
+                    .await
+                    .unwrap();
+                if !infos.is_empty() {
+                    assert!(infos.iter().all(|info| info.storage_arq.is_empty()));
+                    break;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+}
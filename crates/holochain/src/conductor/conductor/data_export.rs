@@ -0,0 +1,137 @@
+use super::*;
+
+impl Conductor {
+    /// Export the actions, app entries and links which the agent of a cell authored,
+    /// including its private entries.
+    pub async fn export_authored_data(
+        &self,
+        cell_id: &CellId,
+    ) -> ConductorApiResult<AuthoredDataExport> {
+        let authored_db = self
+            .cell_by_id(cell_id)
+            .await?
+            .get_or_create_authored_db()?;
+        let dump =
+            source_chain::dump_state(authored_db.into(), cell_id.agent_pubkey().clone()).await?;
+        let records = dump.records.into_iter().map(|record| {
+            Record::new(
+                SignedActionHashed::with_presigned(
+                    ActionHashed::with_pre_hashed(record.action, record.action_address),
+                    record.signature,
+                ),
+                record.entry,
+            )
+        });
+        Ok(AuthoredDataExport::new(cell_id, Timestamp::now(), records))
+    }
+
+    /// Rebuild the bundle which an app was installed from, out of its manifest and the
+    /// DNAs which the conductor holds.
+    ///
+    /// The DNA of each role that the app provisions is bundled with the modifiers it was
+    /// installed with, and the manifest expects its hash. Roles which use the cells of
+    /// another app are left as they are.
+    pub async fn export_app_bundle(
+        &self,
+        installed_app_id: &InstalledAppId,
+    ) -> ConductorApiResult<AppBundle> {
+        let app = self.get_state().await?.get_app(installed_app_id)?.clone();
+        let AppManifest::V1(mut manifest) = app.manifest.clone();
+        let mut resources = Vec::new();
+        for role in &mut manifest.roles {
+            let Some(primary) = app
+                .role_assignments
+                .get(&role.name)
+                .and_then(AppRoleAssignment::as_primary)
+            else {
+                continue;
+            };
+            let dna_hash = primary.base_dna_hash.clone();
+            let dna_file = self
+                .get_dna_file(&dna_hash)
+                .ok_or_else(|| ConductorApiError::DnaMissing(dna_hash.clone()))?;
+            let path = PathBuf::from(format!("{}.dna", role.name));
+            resources.push((path.clone(), DnaBundle::from_dna_file(dna_file)?));
+            role.dna.location = Some(mr_bundle::Location::Bundled(path));
+            role.dna.modifiers = Default::default();
+            role.dna.installed_hash = Some(dna_hash.into());
+        }
+        Ok(AppBundle::new(manifest.into(), resources, PathBuf::from(".")).await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sweettest::*;
+    use crate::test_utils::inline_zomes::simple_create_read_zome_with_visibility;
+    use holochain_state::prelude::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn authored_entries_are_exported_decoded() {
+        holochain_trace::test_run();
+
+        let (dna_file, _, _) = SweetDnaFile::unique_from_inline_zomes((
+            "zome",
+            simple_create_read_zome_with_visibility(EntryVisibility::Private),
+        ))
+        .await;
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let app = conductor.setup_app("app", [&dna_file]).await.unwrap();
+        let (cell,) = app.into_tuple();
+        let action_hash: ActionHash = conductor.call(&cell.zome("zome"), "create", ()).await;
+
+        let export = conductor
+            .raw_handle()
+            .export_authored_data(cell.cell_id())
+            .await
+            .unwrap();
+        // The genesis actions and the create.
+        assert!(export.actions.len() > 3);
+        assert_eq!(1, export.entries.len());
+        assert_eq!(
+            ActionHashB64::from(action_hash),
+            export.entries[0].action_hash
+        );
+        assert_eq!(Some(serde_json::Value::Null), export.entries[0].content);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn exported_app_bundle_installs_the_same_dnas() {
+        use holochain_wasm_test_utils::TestWasm;
+
+        holochain_trace::test_run();
+        let (dna_file, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+        let mut conductor = SweetConductor::from_standard_config().await;
+        conductor.setup_app("app", [&dna_file]).await.unwrap();
+
+        let bundle = conductor
+            .raw_handle()
+            .export_app_bundle(&"app".to_string())
+            .await
+            .unwrap();
+        // The bundle survives being written to a file.
+        let bundle = AppBundle::decode(&bundle.encode().unwrap()).unwrap();
+        let copy = conductor
+            .raw_handle()
+            .install_app_bundle(InstallAppPayload {
+                source: AppBundleSource::Bundle(bundle),
+                agent_key: None,
+                installed_app_id: Some("copy".to_string()),
+                membrane_proofs: None,
+                existing_cells: Default::default(),
+                network_seed: None,
+                ignore_genesis_failure: false,
+                allow_throwaway_random_agent_key: true,
+                network_profile: None,
+                client_only: false,
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            vec![dna_file.dna_hash().clone()],
+            copy.all_cells()
+                .map(|cell_id| cell_id.dna_hash().clone())
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use super::*;
+use holochain_state::epoch::*;
+
+/// How many ops of an archive are handed to the incoming ops workflow at once.
+const IMPORT_BATCH_SIZE: usize = 1000;
+
+impl Conductor {
+    /// Export the ops of a DNA which were integrated before `integrated_before`,
+    /// leaving out private entries.
+    pub async fn export_dht_op_archive(
+        &self,
+        dna_hash: &DnaHash,
+        integrated_before: Timestamp,
+    ) -> ConductorResult<DhtOpArchive> {
+        let ops = self
+            .get_or_create_dht_db(dna_hash)?
+            .read_async(move |txn| {
+                let mut stmt = txn.prepare(
+                    "
+                    SELECT DhtOp.type AS dht_type,
+                    Action.blob AS action_blob,
+                    Entry.blob AS entry_blob
+                    FROM DhtOp
+                    JOIN Action ON DhtOp.action_hash = Action.hash
+                    LEFT JOIN Entry ON Action.entry_hash = Entry.hash
+                    WHERE DhtOp.when_integrated IS NOT NULL
+                    AND DhtOp.when_integrated < :integrated_before
+                    ORDER BY DhtOp.when_integrated
+                    ",
+                )?;
+                let ops = stmt
+                    .query_and_then(
+                        rusqlite::named_params! { ":integrated_before": integrated_before },
+                        |row| holochain_state::query::map_sql_dht_op(false, "dht_type", row),
+                    )?
+                    .collect::<StateQueryResult<Vec<_>>>()?;
+                StateQueryResult::Ok(ops)
+            })
+            .await?;
+        Ok(DhtOpArchive::new(dna_hash.clone(), integrated_before, ops))
+    }
+
+    /// The epochs which the DHT database of a DNA holds ops for or has archived.
+    pub async fn list_dht_epochs(
+        &self,
+        dna_hash: &DnaHash,
+    ) -> ConductorResult<Vec<DhtEpochSummary>> {
+        Ok(self
+            .get_or_create_dht_db(dna_hash)?
+            .read_async(list_dht_epochs)
+            .await?)
+    }
+
+    /// Archive the integrated ops of every epoch of a DNA which ended before `before`,
+    /// and delete them from its DHT database.
+    ///
+    /// The ops of archived epochs aren't taken in again from peers, unless the archive
+    /// is imported. Ops which are still being validated are kept.
+    pub async fn archive_dht_epochs(
+        &self,
+        dna_hash: &DnaHash,
+        before: Timestamp,
+    ) -> ConductorResult<DhtOpArchive> {
+        let before_epoch = dht_op_epoch(before);
+        let archived_at = Timestamp::now();
+        let (ops, deleted) = self
+            .get_or_create_dht_db(dna_hash)?
+            .write_async(move |txn| {
+                let mut stmt = txn.prepare(
+                    "
+                    SELECT DhtOp.type AS dht_type,
+                    Action.blob AS action_blob,
+                    Entry.blob AS entry_blob
+                    FROM DhtOp
+                    JOIN Action ON DhtOp.action_hash = Action.hash
+                    LEFT JOIN Entry ON Action.entry_hash = Entry.hash
+                    WHERE DhtOp.epoch < :before_epoch
+                    AND DhtOp.when_integrated IS NOT NULL
+                    ORDER BY DhtOp.epoch, DhtOp.when_integrated
+                    ",
+                )?;
+                let ops = stmt
+                    .query_and_then(
+                        rusqlite::named_params! { ":before_epoch": before_epoch },
+                        |row| holochain_state::query::map_sql_dht_op(false, "dht_type", row),
+                    )?
+                    .collect::<StateQueryResult<Vec<_>>>()?;
+                drop(stmt);
+                let deleted = delete_dht_epochs(txn, before_epoch, archived_at)?;
+                StateMutationResult::Ok((ops, deleted))
+            })
+            .await?;
+        tracing::info!(?dna_hash, before_epoch, deleted, "Archived DHT epochs");
+        if deleted > 0 {
+            self.get_or_create_space(dna_hash)?
+                .dht_query_cache
+                .queries()
+                .clear();
+        }
+        Ok(DhtOpArchive::new(dna_hash.clone(), archived_at, ops))
+    }
+
+    /// Validate and integrate the ops of an archive again, skipping those which are
+    /// already held. Returns how many ops the archive had.
+    pub async fn import_dht_op_archive(&self, archive: DhtOpArchive) -> ConductorResult<usize> {
+        let dna_hash = archive.dna_hash.clone();
+        // The incoming ops workflow only runs for DNAs with a running cell.
+        if !self
+            .running_cell_ids()
+            .iter()
+            .any(|cell_id| cell_id.dna_hash() == &dna_hash)
+        {
+            return Err(ConductorError::other(format!(
+                "Can't import ops of DNA {dna_hash} without a running cell of it"
+            )));
+        }
+        let ops = archive.into_ops().map_err(ConductorError::other)?;
+        let op_count = ops.len();
+        // The ops of archived epochs would otherwise be refused.
+        let epochs: HashSet<i64> = ops.iter().map(|op| dht_op_epoch(op.timestamp())).collect();
+        self.spaces
+            .dht_db(&dna_hash)?
+            .write_async(move |txn| unarchive_dht_epochs(txn, epochs))
+            .await?;
+        for batch in ops.chunks(IMPORT_BATCH_SIZE) {
+            self.spaces
+                .handle_publish(&dna_hash, false, false, batch.to_vec())
+                .await?;
+        }
+        tracing::info!(?dna_hash, op_count, "Imported a DHT op archive");
+        Ok(op_count)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sweettest::*;
+    use crate::test_utils::inline_zomes::simple_create_read_zome;
+    use holochain_state::prelude::*;
+    use std::time::Duration;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn archived_ops_are_integrated_by_another_conductor() {
+        holochain_trace::test_run();
+
+        let (dna_file, _, _) =
+            SweetDnaFile::unique_from_inline_zomes(("zome", simple_create_read_zome())).await;
+        let dna_hash = dna_file.dna_hash().clone();
+
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let app = conductor.setup_app("app", [&dna_file]).await.unwrap();
+        let (cell,) = app.into_tuple();
+        let action_hash: ActionHash = conductor.call(&cell.zome("zome"), "create", ()).await;
+        await_consistency(10, [&cell]).await.unwrap();
+
+        assert!(conductor
+            .raw_handle()
+            .export_dht_op_archive(&dna_hash, Timestamp::from_micros(0))
+            .await
+            .unwrap()
+            .is_empty());
+        let archive = conductor
+            .raw_handle()
+            .export_dht_op_archive(&dna_hash, Timestamp::now())
+            .await
+            .unwrap();
+        assert!(!archive.is_empty());
+
+        // The other conductor can't get the ops from the network.
+        let mut archival_conductor =
+            SweetConductor::from_config(SweetConductorConfig::standard().no_networking()).await;
+        archival_conductor
+            .setup_app("app", [&dna_file])
+            .await
+            .unwrap();
+        let op_count = archival_conductor
+            .raw_handle()
+            .import_dht_op_archive(archive.clone())
+            .await
+            .unwrap();
+        assert_eq!(archive.len(), op_count);
+
+        let db = archival_conductor
+            .raw_handle()
+            .get_or_create_dht_db(&dna_hash)
+            .unwrap();
+        tokio::time::timeout(Duration::from_secs(30), async {
+            loop {
+                let action_hash = action_hash.clone();
+                let integrated: usize = db
+                    .read_async(move |txn| {
+                        DatabaseResult::Ok(txn.query_row(
+                            "SELECT COUNT(*) FROM DhtOp WHERE action_hash = ? AND when_integrated IS NOT NULL",
+                            [action_hash],
+                            |row| row.get(0),
+                        )?)
+                    })
+                    .await
+                    .unwrap();
+                if integrated > 0 {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        })
+        .await
+        .expect("The archived ops weren't integrated");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn archived_epochs_are_deleted_and_refused() {
+        holochain_trace::test_run();
+
+        let (dna_file, _, _) =
+            SweetDnaFile::unique_from_inline_zomes(("zome", simple_create_read_zome())).await;
+        let dna_hash = dna_file.dna_hash().clone();
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let app = conductor.setup_app("app", [&dna_file]).await.unwrap();
+        let (cell,) = app.into_tuple();
+        let _: ActionHash = conductor.call(&cell.zome("zome"), "create", ()).await;
+        await_consistency(10, [&cell]).await.unwrap();
+
+        let count_ops = || {
+            cell.dht_db().read_async(|txn| {
+                DatabaseResult::Ok(txn.query_row("SELECT COUNT(*) FROM DhtOp", [], |row| {
+                    row.get::<_, usize>(0)
+                })?)
+            })
+        };
+        let held = count_ops().await.unwrap();
+        let epochs = conductor
+            .raw_handle()
+            .list_dht_epochs(&dna_hash)
+            .await
+            .unwrap();
+        assert_eq!(1, epochs.len());
+        assert_eq!(held as u64, epochs[0].op_count);
+
+        // The epoch which is still going on isn't archived.
+        assert!(conductor
+            .raw_handle()
+            .archive_dht_epochs(&dna_hash, Timestamp::now())
+            .await
+            .unwrap()
+            .is_empty());
+        let after_epoch = dht_op_epoch_start(epochs[0].epoch + 1);
+        let archive = conductor
+            .raw_handle()
+            .archive_dht_epochs(&dna_hash, after_epoch)
+            .await
+            .unwrap();
+        assert!(!archive.is_empty());
+        assert_eq!(0, count_ops().await.unwrap());
+        let epochs = conductor
+            .raw_handle()
+            .list_dht_epochs(&dna_hash)
+            .await
+            .unwrap();
+        assert!(epochs[0].archived);
+
+        // Peers can't gossip the ops back, but importing the archive takes them in.
+        conductor
+            .raw_handle()
+            .spaces
+            .handle_publish(&dna_hash, false, false, archive.clone().into_ops().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(0, count_ops().await.unwrap());
+        let op_count = archive.len();
+        conductor
+            .raw_handle()
+            .import_dht_op_archive(archive)
+            .await
+            .unwrap();
+        assert_eq!(op_count, count_ops().await.unwrap());
+    }
+}
@@ -0,0 +1,537 @@
+use super::*;
+use crate::conductor::kitsune_host_impl::{query_region_set, query_region_set_for_coords};
+use crate::conductor::p2p_agent_store::{all_agent_infos, get_single_agent_info};
+use crate::core::metrics::awaited_dependencies;
+use holochain_conductor_api::{
+    BlockingDependency, CellDiagnostics, ConductorDiagnostics, DnaDiagnostics, GossipActivity,
+    OpCensusReport, PeerOpCensus, RegionDiagnostics, RegionDiscrepancy, RegionSetDiagnostics,
+    WorkflowQueueDepths, GOSSIP_ACTIVITY_WINDOW, MAX_BLOCKING_DEPENDENCIES,
+};
+use holochain_p2p::dht::arq::ArqSet;
+use holochain_p2p::dht::prelude::Topology;
+use holochain_p2p::dht::region_set::RegionSetLtcs;
+use holochain_p2p::AgentPubKeyExt;
+use holochain_state::validation_outcomes::validation_outcomes_by_author;
+
+impl Conductor {
+    /// Take a snapshot of the queues, gossip and fetching of each DNA which a running
+    /// cell uses, of the status of the ops which each running cell authored, and of
+    /// the transport connections to other peers.
+    pub async fn conductor_diagnostics(&self) -> ConductorResult<ConductorDiagnostics> {
+        let mut cell_ids: Vec<CellId> = self.running_cell_ids().into_iter().collect();
+        // Sorting by cell keeps the cells of a DNA together.
+        cell_ids.sort();
+        let mut dna_hashes: Vec<DnaHash> = cell_ids
+            .iter()
+            .map(|cell_id| cell_id.dna_hash().clone())
+            .collect();
+        dna_hashes.dedup();
+
+        let mut dnas = Vec::with_capacity(dna_hashes.len());
+        for dna_hash in dna_hashes {
+            dnas.push(self.dna_diagnostics(dna_hash).await?);
+        }
+        let mut cells = Vec::with_capacity(cell_ids.len());
+        for cell_id in cell_ids {
+            cells.push(self.cell_diagnostics(cell_id).await?);
+        }
+        let connections = self.holochain_p2p.get_connection_stats().await?;
+        Ok(ConductorDiagnostics {
+            taken_at: Timestamp::now(),
+            dnas,
+            cells,
+            connections,
+            clock: self.clock_skew.report(),
+        })
+    }
+
+    /// Query the regions which historical gossip compares for a DNA, over the storage
+    /// arcs of the agents of the running cells of the DNA.
+    pub async fn region_set_diagnostics(
+        &self,
+        dna_hash: DnaHash,
+    ) -> ConductorResult<RegionSetDiagnostics> {
+        let topology = self.dna_topology(&dna_hash)?;
+        let (agents, arq_set) = self.local_arq_set(&dna_hash, &topology).await?;
+
+        let region_set = query_region_set(
+            self.get_or_create_dht_db(&dna_hash)?,
+            topology.clone(),
+            &self.get_config().kitsune_tuning_params().to_arq_strat(),
+            Arc::new(arq_set),
+        )
+        .await?;
+        let regions = region_set
+            .regions()
+            .map(|region| {
+                let (locations, times) = region_bounds(&topology, &region.coords);
+                RegionDiagnostics {
+                    locations,
+                    times,
+                    op_count: region.data.count,
+                    size: region.data.size,
+                    hash: region
+                        .data
+                        .hash
+                        .0
+                        .iter()
+                        .map(|byte| format!("{:02x}", byte))
+                        .collect(),
+                }
+            })
+            .collect();
+
+        Ok(RegionSetDiagnostics {
+            dna_hash,
+            taken_at: Timestamp::now(),
+            agents,
+            regions,
+        })
+    }
+
+    /// Ask up to `peer_count` peers whose storage arcs overlap those of the local agents
+    /// of a DNA for the regions of the ops which they hold within the overlap, and
+    /// compare them with the regions of the ops which this conductor holds.
+    pub async fn op_census(
+        &self,
+        dna_hash: DnaHash,
+        peer_count: usize,
+    ) -> ConductorResult<OpCensusReport> {
+        let topology = self.dna_topology(&dna_hash)?;
+        let (agents, arq_set) = self.local_arq_set(&dna_hash, &topology).await?;
+
+        let now = Timestamp::now().as_millis() as u64;
+        let mut peers: Vec<AgentPubKey> = all_agent_infos(self.p2p_agents_db(&dna_hash).into())
+            .await?
+            .into_iter()
+            .filter(|info| info.expires_at_ms > now)
+            .filter(|info| {
+                let arq = ArqSet::single(info.storage_arq.to_bounds(topology.space));
+                !arq_set.intersection(topology.space, &arq).is_empty()
+            })
+            .map(|info| AgentPubKey::from_kitsune(&info.agent))
+            .filter(|agent| !agents.contains(agent))
+            .collect();
+        peers.sort();
+        peers.truncate(peer_count);
+
+        let dht_db = self.get_or_create_dht_db(&dna_hash)?;
+        let peers = futures::future::join_all(peers.into_iter().map(|agent| {
+            let (dna_hash, arq_set, topology, dht_db) = (
+                dna_hash.clone(),
+                arq_set.clone(),
+                topology.clone(),
+                dht_db.clone(),
+            );
+            async move {
+                let census = async {
+                    let theirs = self
+                        .holochain_p2p
+                        .op_census(dna_hash, agent.clone(), arq_set)
+                        .await?;
+                    let ours = query_region_set_for_coords(
+                        dht_db,
+                        topology.clone(),
+                        theirs.coords.clone(),
+                    )
+                    .await?;
+                    ConductorResult::Ok(compare_region_sets(&topology, &ours, &theirs))
+                };
+                match census.await {
+                    Ok((regions_compared, discrepancies)) => PeerOpCensus {
+                        agent,
+                        regions_compared,
+                        discrepancies,
+                        error: None,
+                    },
+                    Err(e) => PeerOpCensus {
+                        agent,
+                        regions_compared: 0,
+                        discrepancies: vec![],
+                        error: Some(e.to_string()),
+                    },
+                }
+            }
+        }))
+        .await;
+
+        Ok(OpCensusReport {
+            dna_hash,
+            taken_at: Timestamp::now(),
+            agents,
+            peers,
+        })
+    }
+
+    /// The regions of the ops of a DNA which this conductor holds within both `arq_set`,
+    /// which a peer taking an op census sent, and the storage arcs of the local agents.
+    pub(crate) async fn op_census_regions(
+        &self,
+        dna_hash: &DnaHash,
+        arq_set: ArqSet,
+    ) -> ConductorResult<RegionSetLtcs> {
+        let topology = self.dna_topology(dna_hash)?;
+        let (_, local) = self.local_arq_set(dna_hash, &topology).await?;
+        // - Normalizing the arcs of the peer keeps them all at one power.
+        let arq_set = ArqSet::new(arq_set.arqs().to_vec());
+        query_region_set(
+            self.get_or_create_dht_db(dna_hash)?,
+            topology.clone(),
+            &self.get_config().kitsune_tuning_params().to_arq_strat(),
+            Arc::new(local.intersection(topology.space, &arq_set)),
+        )
+        .await
+    }
+
+    /// Count the validation outcomes of the ops of a DNA which this conductor holds,
+    /// by the author of each op.
+    pub async fn validation_outcomes(
+        &self,
+        dna_hash: DnaHash,
+    ) -> ConductorResult<Vec<AuthorValidationOutcomes>> {
+        if self.get_dna_def(&dna_hash).is_none() {
+            return Err(DnaError::DnaMissing(dna_hash).into());
+        }
+        Ok(self
+            .get_or_create_dht_db(&dna_hash)?
+            .read_async(|txn| validation_outcomes_by_author(txn))
+            .await?)
+    }
+
+    fn dna_topology(&self, dna_hash: &DnaHash) -> ConductorResult<Topology> {
+        Ok(self
+            .get_dna_def(dna_hash)
+            .ok_or_else(|| DnaError::DnaMissing(dna_hash.clone()))?
+            .topology(
+                self.get_config()
+                    .network
+                    .tuning_params
+                    .danger_gossip_recent_threshold(),
+            ))
+    }
+
+    /// The running local agents of a DNA, and their storage arcs.
+    async fn local_arq_set(
+        &self,
+        dna_hash: &DnaHash,
+        topology: &Topology,
+    ) -> ConductorResult<(Vec<AgentPubKey>, ArqSet)> {
+        let mut agents: Vec<AgentPubKey> = self
+            .running_cell_ids()
+            .into_iter()
+            .filter(|cell_id| cell_id.dna_hash() == dna_hash)
+            .map(|cell_id| cell_id.agent_pubkey().clone())
+            .collect();
+        agents.sort();
+        let mut arqs = Vec::with_capacity(agents.len());
+        for agent in &agents {
+            // - An agent which hasn't joined the network yet has no arc to gossip over.
+            if let Some(info) = get_single_agent_info(
+                self.p2p_agents_db(dna_hash).into(),
+                dna_hash.clone(),
+                agent.clone(),
+            )
+            .await?
+            {
+                arqs.push(info.storage_arq.to_bounds(topology.space));
+            }
+        }
+        Ok((agents, ArqSet::new(arqs)))
+    }
+
+    async fn dna_diagnostics(&self, dna_hash: DnaHash) -> ConductorResult<DnaDiagnostics> {
+        let queues = self
+            .get_or_create_dht_db(&dna_hash)?
+            .read_async(|txn| -> DatabaseResult<WorkflowQueueDepths> {
+                let count = |stages: &str| {
+                    txn.query_row(
+                        &format!(
+                            "
+                            SELECT COUNT(hash) FROM DhtOp
+                            WHERE when_integrated IS NULL
+                            AND {}
+                            ",
+                            stages
+                        ),
+                        [],
+                        |row| row.get(0),
+                    )
+                };
+                Ok(WorkflowQueueDepths {
+                    sys_validation: count("(validation_stage IS NULL OR validation_stage = 0)")?,
+                    app_validation: count("validation_stage IN (1, 2)")?,
+                    integration: count("validation_stage = 3")?,
+                })
+            })
+            .await?;
+
+        let diagnostics = self.holochain_p2p.get_diagnostics(dna_hash.clone()).await?;
+        let fetch_pool = diagnostics
+            .fetch_pool
+            .info([dna_hash.to_kitsune()].into_iter().collect());
+        let gossip = diagnostics
+            .metrics
+            .read()
+            .peer_node_histories()
+            .values()
+            .fold(GossipActivity::default(), |mut gossip, history| {
+                if history.current_round.is_some() {
+                    gossip.current_rounds += 1;
+                }
+                for round in &history.completed_rounds {
+                    if round.end_time.elapsed() < GOSSIP_ACTIVITY_WINDOW {
+                        gossip.recent_rounds += 1;
+                        if round.error {
+                            gossip.recent_errors += 1;
+                        }
+                    }
+                }
+                gossip
+            });
+
+        // The number of ops awaiting each dependency in sys and in app validation.
+        let mut awaited = HashMap::<AnyDhtHash, (usize, usize)>::new();
+        for (hash, blocked_ops) in awaited_dependencies("sys_validation", &dna_hash) {
+            awaited.entry(hash).or_default().0 += blocked_ops;
+        }
+        for (hash, blocked_ops) in awaited_dependencies("app_validation", &dna_hash) {
+            awaited.entry(hash).or_default().1 += blocked_ops;
+        }
+        let mut blocking_dependencies: Vec<_> = awaited
+            .into_iter()
+            .map(|(hash, (sys, app))| BlockingDependency {
+                hash,
+                awaiting_sys_validation: sys,
+                awaiting_app_validation: app,
+            })
+            .collect();
+        blocking_dependencies.sort_by_key(|dependency| std::cmp::Reverse(dependency.blocked_ops()));
+        blocking_dependencies.truncate(MAX_BLOCKING_DEPENDENCIES);
+
+        Ok(DnaDiagnostics {
+            dna_hash,
+            queues,
+            fetch_pool,
+            gossip,
+            blocking_dependencies,
+        })
+    }
+
+    async fn cell_diagnostics(&self, cell_id: CellId) -> ConductorResult<CellDiagnostics> {
+        let (authored_ops, unpublished_ops, withheld_ops, ops_awaiting_receipts) = self
+            .get_or_create_authored_db(cell_id.dna_hash(), cell_id.agent_pubkey().clone())?
+            .read_async(|txn| -> DatabaseResult<(usize, usize, usize, usize)> {
+                Ok(txn.query_row(
+                    "
+                    SELECT
+                    COUNT(hash),
+                    COUNT(CASE WHEN last_publish_time IS NULL AND withhold_publish IS NULL THEN 1 END),
+                    COUNT(CASE WHEN withhold_publish IS NOT NULL THEN 1 END),
+                    COUNT(CASE WHEN require_receipt = 1 AND receipts_complete IS NULL THEN 1 END)
+                    FROM DhtOp
+                    ",
+                    [],
+                    |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+                )?)
+            })
+            .await?;
+        Ok(CellDiagnostics {
+            cell_id,
+            authored_ops,
+            unpublished_ops,
+            withheld_ops,
+            ops_awaiting_receipts,
+        })
+    }
+}
+
+/// The first and last DHT location and time of a region.
+fn region_bounds(
+    topology: &Topology,
+    coords: &holochain_p2p::dht::region::RegionCoords,
+) -> ((u32, u32), (Timestamp, Timestamp)) {
+    let bounds = coords.to_bounds(topology);
+    ((bounds.x.0.as_u32(), bounds.x.1.as_u32()), bounds.t)
+}
+
+/// How many regions two region sets with the same coords have, and the ones which they
+/// hold different ops in.
+fn compare_region_sets(
+    topology: &Topology,
+    ours: &RegionSetLtcs,
+    theirs: &RegionSetLtcs,
+) -> (usize, Vec<RegionDiscrepancy>) {
+    let discrepancies = ours
+        .regions()
+        .zip(theirs.regions())
+        .filter(|(ours, theirs)| ours.data != theirs.data)
+        .map(|(ours, theirs)| {
+            let (locations, times) = region_bounds(topology, &ours.coords);
+            RegionDiscrepancy {
+                locations,
+                times,
+                local_op_count: ours.data.count,
+                peer_op_count: theirs.data.count,
+            }
+        })
+        .collect();
+    (ours.count(), discrepancies)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sweettest::*;
+    use crate::test_utils::inline_zomes::simple_create_read_zome;
+    use holochain_state::prelude::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn diagnostics_cover_each_running_cell() {
+        holochain_trace::test_run();
+
+        let (dna_file, _, _) =
+            SweetDnaFile::unique_from_inline_zomes(("zome", simple_create_read_zome())).await;
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let app = conductor.setup_app("app", [&dna_file]).await.unwrap();
+        let (cell,) = app.into_tuple();
+        let _: ActionHash = conductor.call(&cell.zome("zome"), "create", ()).await;
+        await_consistency(10, [&cell]).await.unwrap();
+
+        let diagnostics = conductor
+            .raw_handle()
+            .conductor_diagnostics()
+            .await
+            .unwrap();
+        assert_eq!(1, diagnostics.dnas.len());
+        assert_eq!(dna_file.dna_hash(), &diagnostics.dnas[0].dna_hash);
+        // Everything has been integrated.
+        assert_eq!(
+            holochain_conductor_api::WorkflowQueueDepths::default(),
+            diagnostics.dnas[0].queues
+        );
+        assert!(diagnostics.dnas[0].blocking_dependencies.is_empty());
+        assert_eq!(1, diagnostics.cells.len());
+        assert_eq!(cell.cell_id(), &diagnostics.cells[0].cell_id);
+        assert!(diagnostics.cells[0].authored_ops > 0);
+
+        let outcomes = conductor
+            .raw_handle()
+            .validation_outcomes(dna_file.dna_hash().clone())
+            .await
+            .unwrap();
+        assert_eq!(1, outcomes.len());
+        assert_eq!(cell.agent_pubkey(), &outcomes[0].author);
+        assert!(outcomes[0].valid > 0);
+        assert_eq!(
+            0,
+            outcomes[0].rejected + outcomes[0].abandoned + outcomes[0].awaiting
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn region_sets_of_two_nodes_match_once_consistent() {
+        holochain_trace::test_run();
+
+        let mut conductors = SweetConductorBatch::from_standard_config(2).await;
+        let (dna_file, _, _) = SweetDnaFile::unique_from_inline_zomes((
+            "zome",
+            crate::test_utils::inline_zomes::simple_create_read_zome(),
+        ))
+        .await;
+        let apps = conductors
+            .setup_app("app", &[dna_file.clone()])
+            .await
+            .unwrap();
+        let ((alice,), (bob,)) = apps.into_tuples();
+        conductors.exchange_peer_info().await;
+
+        let _: ActionHash = conductors[0].call(&alice.zome("zome"), "create", ()).await;
+        await_consistency(60, [&alice, &bob]).await.unwrap();
+
+        let alice_regions = conductors[0]
+            .raw_handle()
+            .region_set_diagnostics(dna_file.dna_hash().clone())
+            .await
+            .unwrap();
+        let bob_regions = conductors[1]
+            .raw_handle()
+            .region_set_diagnostics(dna_file.dna_hash().clone())
+            .await
+            .unwrap();
+        assert_eq!(vec![alice.agent_pubkey().clone()], alice_regions.agents);
+        assert!(!alice_regions.regions.is_empty());
+        // - Both nodes hold the same ops, so they agree on every region they have in common.
+        for region in &alice_regions.regions {
+            if let Some(other) = bob_regions
+                .regions
+                .iter()
+                .find(|other| other.locations == region.locations && other.times == region.times)
+            {
+                assert_eq!(region, other);
+            }
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn op_census_finds_no_discrepancies_once_consistent() {
+        holochain_trace::test_run();
+
+        let mut conductors = SweetConductorBatch::from_standard_config(2).await;
+        let (dna_file, _, _) = SweetDnaFile::unique_from_inline_zomes((
+            "zome",
+            crate::test_utils::inline_zomes::simple_create_read_zome(),
+        ))
+        .await;
+        let apps = conductors
+            .setup_app("app", &[dna_file.clone()])
+            .await
+            .unwrap();
+        let ((alice,), (bob,)) = apps.into_tuples();
+        conductors.exchange_peer_info().await;
+
+        let _: ActionHash = conductors[0].call(&alice.zome("zome"), "create", ()).await;
+        await_consistency(60, [&alice, &bob]).await.unwrap();
+
+        let census = conductors[0]
+            .raw_handle()
+            .op_census(dna_file.dna_hash().clone(), 5)
+            .await
+            .unwrap();
+        assert_eq!(vec![alice.agent_pubkey().clone()], census.agents);
+        // - Only Bob is a peer, Alice isn't asked about her own ops.
+        assert_eq!(1, census.peers.len());
+        assert_eq!(bob.agent_pubkey(), &census.peers[0].agent);
+        assert_eq!(None, census.peers[0].error);
+        assert!(census.peers[0].regions_compared > 0);
+        assert!(census.is_consistent());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn connections_to_peers_are_reported() {
+        holochain_trace::test_run();
+
+        let mut conductors = SweetConductorBatch::from_standard_config(2).await;
+        let (dna_file, _, _) = SweetDnaFile::unique_from_inline_zomes((
+            "zome",
+            crate::test_utils::inline_zomes::simple_create_read_zome(),
+        ))
+        .await;
+        let apps = conductors.setup_app("app", &[dna_file]).await.unwrap();
+        let ((alice,), (bob,)) = apps.into_tuples();
+        conductors.exchange_peer_info().await;
+
+        let _: ActionHash = conductors[0].call(&alice.zome("zome"), "create", ()).await;
+        await_consistency(60, [&alice, &bob]).await.unwrap();
+
+        let diagnostics = conductors[0]
+            .raw_handle()
+            .conductor_diagnostics()
+            .await
+            .unwrap();
+        // - Bob got Alice's ops over a connection, whether it's direct or relayed.
+        assert!(!diagnostics.connections.is_empty());
+        assert!(diagnostics
+            .connections
+            .iter()
+            .any(|con| con.send_bytes > 0 && con.recv_bytes > 0));
+    }
+}
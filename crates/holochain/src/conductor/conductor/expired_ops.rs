@@ -0,0 +1,159 @@
+use super::*;
+use holochain_state::expiry::*;
+
+/// How often the DHT databases are checked for ops of ephemeral entries which have expired.
+pub(crate) const EXPIRED_OPS_PURGE_INTERVAL: std::time::Duration = EXPIRY_QUANTUM;
+
+impl Conductor {
+    /// Purge the expired ops of ephemeral entries from the DHT databases of all running DNAs,
+    /// every [`EXPIRED_OPS_PURGE_INTERVAL`].
+    pub(crate) async fn purge_expired_ops_loop(self: Arc<Self>) {
+        // When the DHT database of each DNA was last checked.
+        let mut last_checked: HashMap<DnaHash, Timestamp> = HashMap::new();
+        let mut interval = tokio::time::interval(EXPIRED_OPS_PURGE_INTERVAL);
+        loop {
+            interval.tick().await;
+            let dna_hashes: HashSet<DnaHash> = self
+                .running_cell_ids()
+                .into_iter()
+                .map(|cell_id| cell_id.dna_hash().clone())
+                .collect();
+            for dna_hash in dna_hashes {
+                let now = Timestamp::now();
+                // Overlap with the last check, so that ops which were integrated while
+                // it was running aren't missed.
+                let since = last_checked
+                    .get(&dna_hash)
+                    .and_then(|t| (*t - EXPIRED_OPS_PURGE_INTERVAL).ok())
+                    .unwrap_or(Timestamp::MIN);
+                match self.purge_expired_ops(&dna_hash, since, now).await {
+                    Ok(()) => {
+                        last_checked.insert(dna_hash, now);
+                    }
+                    Err(err) => {
+                        tracing::warn!(?err, ?dna_hash, "Failed to purge expired ops");
+                    }
+                }
+            }
+        }
+    }
+
+    /// Set when the ops of ephemeral entries which were integrated since `since` expire,
+    /// and delete the ops which have expired by `now` from the DHT database of a DNA.
+    pub(crate) async fn purge_expired_ops(
+        &self,
+        dna_hash: &DnaHash,
+        since: Timestamp,
+        now: Timestamp,
+    ) -> ConductorResult<()> {
+        let ephemeral_types = self.ephemeral_entry_types(dna_hash)?;
+        if ephemeral_types.is_empty() {
+            return Ok(());
+        }
+        let (expiring, purged) = self
+            .spaces
+            .dht_db(dna_hash)?
+            .write_async(move |txn| {
+                let expiring = set_ephemeral_op_expiry(txn, &ephemeral_types, since)?;
+                let purged = delete_expired_ops(txn, expiry_cutoff(now))?;
+                StateMutationResult::Ok((expiring, purged))
+            })
+            .await?;
+        tracing::debug!(?dna_hash, expiring, purged, "Purged expired ops");
+        if purged > 0 {
+            self.get_or_create_space(dna_hash)?
+                .dht_query_cache
+                .queries()
+                .clear();
+        }
+        Ok(())
+    }
+
+    /// The entry types of a DNA which have a TTL.
+    fn ephemeral_entry_types(&self, dna_hash: &DnaHash) -> ConductorResult<EphemeralEntryTypes> {
+        let ribosome = self.get_ribosome(dna_hash)?;
+        let mut ephemeral_types = EphemeralEntryTypes::new();
+        for (zome_index, (_, zome)) in ribosome.dna_def().integrity_zomes.iter().enumerate() {
+            // The entry defs of a zome are stored when its DNA is registered.
+            for entry_index in 0..=u8::MAX {
+                let key = EntryDefBufferKey::new(zome.clone(), entry_index.into());
+                match self.get_entry_def(&key) {
+                    Some(entry_def) => {
+                        if let Some(ttl) = entry_def.ttl {
+                            ephemeral_types
+                                .insert((ZomeIndex(zome_index as u8), entry_index.into()), ttl);
+                        }
+                    }
+                    None => break,
+                }
+            }
+        }
+        Ok(ephemeral_types)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sweettest::*;
+    use holochain_state::expiry::EXPIRY_QUANTUM;
+    use holochain_state::prelude::*;
+    use std::time::Duration;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn expired_ops_are_purged() {
+        holochain_trace::test_run();
+
+        let entry_def = EntryDef::default_from_id("chat").with_ttl(Duration::from_secs(60));
+        let zomes = SweetInlineZomes::new(vec![entry_def], 0).function("create", |api, ()| {
+            let entry = Entry::app(().try_into().unwrap()).unwrap();
+            let hash = api.create(CreateInput::new(
+                InlineZomeSet::get_entry_location(&api, EntryDefIndex(0)),
+                EntryVisibility::Public,
+                entry,
+                ChainTopOrdering::default(),
+            ))?;
+            Ok(hash)
+        });
+        let (dna_file, _, _) = SweetDnaFile::unique_from_inline_zomes(zomes).await;
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let app = conductor.setup_app("app", [&dna_file]).await.unwrap();
+        let (cell,) = app.into_tuple();
+
+        let hash: ActionHash = conductor
+            .call(&cell.zome(SweetInlineZomes::COORDINATOR), "create", ())
+            .await;
+        await_consistency(10, [&cell]).await.unwrap();
+
+        let count_ops = |op_type: ChainOpType| {
+            let hash = hash.clone();
+            cell.dht_db().read_async(move |txn| {
+                DatabaseResult::Ok(txn.query_row(
+                    "SELECT COUNT(*) FROM DhtOp WHERE action_hash = ? AND type = ?",
+                    rusqlite::params![hash, op_type],
+                    |row| row.get::<_, usize>(0),
+                )?)
+            })
+        };
+
+        let dna_hash = dna_file.dna_hash();
+        conductor
+            .raw_handle()
+            .purge_expired_ops(dna_hash, Timestamp::MIN, Timestamp::now())
+            .await
+            .unwrap();
+        assert_eq!(1, count_ops(ChainOpType::StoreEntry).await.unwrap());
+
+        let later = (Timestamp::now() + (Duration::from_secs(60) + EXPIRY_QUANTUM * 2)).unwrap();
+        conductor
+            .raw_handle()
+            .purge_expired_ops(dna_hash, Timestamp::MIN, later)
+            .await
+            .unwrap();
+        assert_eq!(0, count_ops(ChainOpType::StoreEntry).await.unwrap());
+        assert_eq!(0, count_ops(ChainOpType::StoreRecord).await.unwrap());
+        assert_eq!(
+            1,
+            count_ops(ChainOpType::RegisterAgentActivity).await.unwrap()
+        );
+    }
+}
@@ -38,6 +38,13 @@ impl Conductor {
 
         let graft = ChainGraft::new(existing, records).rebalance();
         let chain_top = graft.existing_chain_top();
+        // Records which start a new chain replace all of the existing chain if none of
+        // it is kept, e.g. the genesis records of a cell which a chain is being moved to.
+        let replaces_chain = chain_top.is_none()
+            && graft
+                .incoming()
+                .first()
+                .map_or(false, |record| record.action().prev_action().is_none());
 
         if validate {
             self.clone()
@@ -84,6 +91,14 @@ impl Conductor {
                             },
                         )
                         .map_err(StateMutationError::from)?;
+                    } else if replaces_chain {
+                        txn.execute(
+                            holochain_sqlite::sql::sql_cell::DELETE_ACTIONS,
+                            rusqlite::named_params! {
+                                ":author": cell_id.agent_pubkey(),
+                            },
+                        )
+                        .map_err(StateMutationError::from)?;
                     }
 
                     let mut ops_to_integrate = Vec::new();
@@ -405,4 +420,73 @@ mod tests {
             case.clone().rebalance().rebalance().incoming
         );
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn grafting_a_new_chain_replaces_genesis() {
+        use crate::sweettest::*;
+        use crate::test_utils::inline_zomes::simple_crud_zome;
+
+        holochain_trace::test_run();
+        let (dna_file, _, _) = SweetDnaFile::unique_from_inline_zomes(simple_crud_zome()).await;
+        let config = SweetConductorConfig::standard().no_dpki();
+        let mut source = SweetConductor::from_config(config.clone()).await;
+        // - The conductors share a keystore, so that both can run genesis for the agent.
+        let mut target = SweetConductor::create_with_defaults(
+            config,
+            Some(source.keystore()),
+            None::<DynSweetRendezvous>,
+        )
+        .await;
+        let agent = SweetAgents::one(source.keystore()).await;
+        let (cell,) = source
+            .setup_app_for_agent("app", agent.clone(), [&dna_file])
+            .await
+            .unwrap()
+            .into_tuple();
+        target
+            .setup_app_for_agent("app", agent, [&dna_file])
+            .await
+            .unwrap();
+
+        let chain = |conductor: &SweetConductor| {
+            let handle = conductor.raw_handle();
+            let cell_id = cell.cell_id().clone();
+            async move {
+                handle
+                    .dump_full_cell_state(&cell_id, None)
+                    .await
+                    .unwrap()
+                    .source_chain_dump
+                    .records
+            }
+        };
+        let records: Vec<Record> = chain(&source)
+            .await
+            .into_iter()
+            .map(|record| {
+                Record::new(
+                    SignedActionHashed::with_presigned(
+                        ActionHashed::with_pre_hashed(record.action, record.action_address),
+                        record.signature,
+                    ),
+                    record.entry,
+                )
+            })
+            .collect();
+        let hashes = |records: Vec<SourceChainDumpRecord>| {
+            records
+                .into_iter()
+                .map(|record| record.action_address)
+                .collect::<Vec<_>>()
+        };
+        // - The target ran genesis at a different time, so its chain differs.
+        assert_ne!(hashes(chain(&source).await), hashes(chain(&target).await));
+
+        target
+            .raw_handle()
+            .graft_records_onto_source_chain(cell.cell_id().clone(), false, records)
+            .await
+            .unwrap();
+        assert_eq!(hashes(chain(&source).await), hashes(chain(&target).await));
+    }
 }
@@ -0,0 +1,176 @@
+use holochain_conductor_api::{HealthReport, HealthStatus};
+use warp::filters::BoxedFilter;
+use warp::http::StatusCode;
+use warp::reply::Response;
+use warp::{Filter, Reply};
+
+use super::*;
+
+/// How long each check may take before it is counted as failing.
+const HEALTH_CHECK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Run a check, failing it if it doesn't finish within [`HEALTH_CHECK_TIMEOUT`].
+async fn check<E: std::fmt::Display>(
+    f: impl std::future::Future<Output = Result<(), E>>,
+) -> HealthStatus {
+    match tokio::time::timeout(HEALTH_CHECK_TIMEOUT, f).await {
+        Ok(result) => result.into(),
+        Err(_) => HealthStatus::Failing {
+            reason: format!("timed out after {:?}", HEALTH_CHECK_TIMEOUT),
+        },
+    }
+}
+
+impl Conductor {
+    /// Check that the keystore, the database of each running cell, the
+    /// network transport and the bootstrap service are all working.
+    pub async fn health_check(&self) -> HealthReport {
+        let keystore = check(async {
+            self.keystore()
+                .lair_client()
+                .list_entries()
+                .await
+                .map(|_| ())
+        })
+        .await;
+
+        let mut cells = Vec::new();
+        for cell_id in self.running_cell_ids() {
+            let status = check(async {
+                let db = self.get_or_create_authored_db(
+                    cell_id.dna_hash(),
+                    cell_id.agent_pubkey().clone(),
+                )?;
+                db.write_async(|txn| -> DatabaseResult<()> {
+                    Ok(txn.query_row("SELECT 1", [], |_| Ok(()))?)
+                })
+                .await
+            })
+            .await;
+            cells.push((cell_id, status));
+        }
+
+        let network = check(async { self.dump_network_stats().await.map(|_| ()) }).await;
+
+        let bootstrap = match &self.config.network.bootstrap_service {
+            Some(url) if !self.config.has_rendezvous_bootstrap() => Some(
+                check(async {
+                    kitsune_p2p_bootstrap_client::now(
+                        Some(url.clone()),
+                        kitsune_p2p_bootstrap_client::BootstrapNet::Tx5,
+                    )
+                    .await
+                    .map(|_| ())
+                })
+                .await,
+            ),
+            _ => None,
+        };
+
+        let healthy = keystore.is_ok()
+            && cells.iter().all(|(_, status)| status.is_ok())
+            && network.is_ok()
+            && bootstrap.as_ref().map_or(true, HealthStatus::is_ok);
+
+        HealthReport {
+            healthy,
+            keystore,
+            cells,
+            network,
+            bootstrap,
+        }
+    }
+}
+
+/// Serve the conductor's [`HealthReport`] as JSON on `GET /health`, with a
+/// 200 status if the conductor is healthy and 503 if it is not.
+pub(crate) async fn serve_health_check(
+    conductor: ConductorHandle,
+    listener: tokio::net::TcpListener,
+) {
+    let route = health_check_route(move || {
+        let conductor = conductor.clone();
+        async move { conductor.health_check().await }
+    });
+    if let Err(e) = holochain_metrics::http::serve(listener, route).await {
+        tracing::warn!(?e, "Stopped serving the health check");
+    }
+}
+
+/// The route which answers `GET /health` with the report of `health_check`.
+fn health_check_route<F, Fut>(health_check: F) -> BoxedFilter<(Response,)>
+where
+    F: Fn() -> Fut + Clone + Send + Sync + 'static,
+    Fut: std::future::Future<Output = HealthReport> + Send + 'static,
+{
+    warp::path!("health")
+        .and(warp::get())
+        .then(move || {
+            let report = health_check();
+            async move {
+                let report = report.await;
+                let status = if report.healthy {
+                    StatusCode::OK
+                } else {
+                    StatusCode::SERVICE_UNAVAILABLE
+                };
+                warp::reply::with_status(warp::reply::json(&report), status).into_response()
+            }
+        })
+        .boxed()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(healthy: bool) -> HealthReport {
+        HealthReport {
+            healthy,
+            keystore: HealthStatus::Ok,
+            cells: vec![],
+            network: if healthy {
+                HealthStatus::Ok
+            } else {
+                HealthStatus::Failing {
+                    reason: "no network".to_string(),
+                }
+            },
+            bootstrap: None,
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn health_check_route_serves_the_report() {
+        for healthy in [true, false] {
+            let route = health_check_route(move || async move { report(healthy) });
+            let response = warp::test::request()
+                .path("/health?verbose")
+                .reply(&route)
+                .await;
+            let expected_status = if healthy {
+                StatusCode::OK
+            } else {
+                StatusCode::SERVICE_UNAVAILABLE
+            };
+            assert_eq!(expected_status, response.status());
+            assert_eq!(
+                report(healthy),
+                serde_json::from_slice::<HealthReport>(response.body()).unwrap()
+            );
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn health_check_route_only_answers_get_health() {
+        let route = health_check_route(|| async { report(true) });
+        let response = warp::test::request()
+            .method("POST")
+            .path("/health")
+            .reply(&route)
+            .await;
+        assert_eq!(StatusCode::METHOD_NOT_ALLOWED, response.status());
+        let response = warp::test::request().path("/metrics").reply(&route).await;
+        assert_eq!(StatusCode::NOT_FOUND, response.status());
+    }
+}
@@ -0,0 +1,134 @@
+use super::*;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+/// How often the validation backlog of each space and the memory usage of the
+/// conductor are checked against their limits.
+pub(crate) const INGEST_ADMISSION_INTERVAL: Duration = Duration::from_secs(5);
+
+impl Conductor {
+    /// Check every [`INGEST_ADMISSION_INTERVAL`] whether each space can take in more ops.
+    ///
+    /// While the ops of a space waiting to be validated and integrated are over
+    /// `ingest_max_backlog`, or the memory which the conductor uses is over
+    /// `ingest_max_memory_mb`, no more ops of the space are fetched and new gossip
+    /// rounds are answered with busy, so that peers back off rather than the queues
+    /// growing without bound.
+    pub(crate) async fn ingest_admission_loop(self: Arc<Self>) {
+        let tuning_params = self.conductor_tuning_params();
+        let max_backlog = tuning_params.ingest_max_backlog();
+        let max_memory_mb = tuning_params.ingest_max_memory_mb();
+        if max_backlog == 0 && max_memory_mb == 0 {
+            return;
+        }
+        if max_memory_mb > 0 {
+            kitsune_p2p_types::metrics::init_sys_info_poll();
+        }
+
+        let mut interval = tokio::time::interval(INGEST_ADMISSION_INTERVAL);
+        loop {
+            interval.tick().await;
+            let memory_mb = kitsune_p2p_types::metrics::get_sys_info().used_mem_kb / 1024;
+            let memory_exceeded = max_memory_mb > 0 && memory_mb > max_memory_mb;
+            for space in self.spaces.get_from_spaces(|space| space.clone()) {
+                let backlog = if max_backlog > 0 {
+                    match validation_backlog(&space).await {
+                        Ok(backlog) => backlog,
+                        Err(err) => {
+                            tracing::warn!(
+                                ?err,
+                                dna_hash = ?space.dna_hash,
+                                "Failed to count the validation backlog"
+                            );
+                            continue;
+                        }
+                    }
+                } else {
+                    0
+                };
+                let refuse = memory_exceeded || (max_backlog > 0 && backlog > max_backlog);
+                let was_refused = space.ingest_refused.swap(refuse, Ordering::Relaxed);
+                if refuse && !was_refused {
+                    tracing::warn!(
+                        dna_hash = ?space.dna_hash,
+                        backlog,
+                        max_backlog,
+                        memory_mb,
+                        max_memory_mb,
+                        "Refusing incoming ops until the backlog and memory usage are within limits"
+                    );
+                } else if !refuse && was_refused {
+                    tracing::info!(
+                        dna_hash = ?space.dna_hash,
+                        backlog,
+                        memory_mb,
+                        "Taking in ops again"
+                    );
+                }
+            }
+        }
+    }
+}
+
+/// How many ops of a space wait to be validated or integrated.
+async fn validation_backlog(space: &Space) -> DatabaseResult<usize> {
+    space
+        .dht_db
+        .read_async(|txn| {
+            Ok(txn.query_row(
+                "SELECT COUNT(hash) FROM DhtOp WHERE when_integrated IS NULL",
+                [],
+                |row| row.get(0),
+            )?)
+        })
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sweettest::*;
+    use crate::test_utils::inline_zomes::simple_create_read_zome;
+    use holochain_state::prelude::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ops_are_refused_over_the_memory_limit() {
+        holochain_trace::test_run();
+        // - No conductor runs in a megabyte, so Bob refuses every op.
+        let bob_config = SweetConductorConfig::standard().tune_conductor(|tune| {
+            tune.ingest_max_memory_mb = Some(1);
+        });
+        let mut conductors =
+            SweetConductorBatch::from_configs([SweetConductorConfig::standard(), bob_config]).await;
+        let (dna_file, _, _) =
+            SweetDnaFile::unique_from_inline_zomes(("zome", simple_create_read_zome())).await;
+        let apps = conductors.setup_app("app", &[dna_file]).await.unwrap();
+        let ((alice,), (bob,)) = apps.into_tuples();
+
+        let spaces = conductors[1].raw_handle().spaces.clone();
+        let dna_hash = bob.cell_id().dna_hash().clone();
+        tokio::time::timeout(std::time::Duration::from_secs(30), async {
+            while !spaces.is_ingest_refused(&dna_hash) {
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        conductors.exchange_peer_info().await;
+        let action_hash: ActionHash = conductors[0].call(&alice.zome("zome"), "create", ()).await;
+        tokio::time::sleep(std::time::Duration::from_secs(10)).await;
+
+        let held = bob
+            .dht_db()
+            .read_async(move |txn| {
+                DatabaseResult::Ok(txn.query_row(
+                    "SELECT COUNT(*) FROM DhtOp WHERE action_hash = ?",
+                    [action_hash],
+                    |row| row.get::<_, usize>(0),
+                )?)
+            })
+            .await
+            .unwrap();
+        assert_eq!(0, held);
+    }
+}
@@ -0,0 +1,179 @@
+use super::*;
+use crate::core::ribosome::error::RibosomeResult;
+use crate::core::ribosome::guest_callback::genesis_self_check::v1::GenesisSelfCheckHostAccessV1;
+use crate::core::ribosome::guest_callback::genesis_self_check::v1::GenesisSelfCheckInvocationV1;
+use crate::core::ribosome::guest_callback::genesis_self_check::v2::GenesisSelfCheckHostAccessV2;
+use crate::core::ribosome::guest_callback::genesis_self_check::v2::GenesisSelfCheckInvocationV2;
+use crate::core::ribosome::guest_callback::genesis_self_check::{
+    GenesisSelfCheckHostAccess, GenesisSelfCheckInvocation, GenesisSelfCheckResult,
+};
+use holochain_conductor_api::{MembraneProofFailure, MembraneProofRecheckReport};
+use holochain_types::sql::AsSql;
+use std::collections::HashSet;
+
+impl Conductor {
+    /// Run the genesis self check of a DNA again on the membrane proof of each agent
+    /// whose valid `AgentValidationPkg` its DHT database holds, with the zomes which
+    /// the conductor currently runs for the DNA.
+    ///
+    /// Nothing is done about the agents which fail, they are only reported.
+    pub async fn recheck_membrane_proofs(
+        &self,
+        dna_hash: &DnaHash,
+    ) -> ConductorResult<MembraneProofRecheckReport> {
+        let ribosome = self.get_ribosome(dna_hash)?;
+        let packages = self
+            .get_or_create_dht_db(dna_hash)?
+            .read_async(|txn| {
+                let mut stmt = txn.prepare(
+                    "
+                    SELECT DISTINCT Action.blob AS action_blob
+                    FROM Action
+                    JOIN DhtOp ON DhtOp.action_hash = Action.hash
+                    WHERE Action.type = :type
+                    AND DhtOp.validation_status = :status
+                    ",
+                )?;
+                let packages = stmt
+                    .query_and_then(
+                        rusqlite::named_params! {
+                            ":type": ActionType::AgentValidationPkg.as_sql(),
+                            ":status": ValidationStatus::Valid,
+                        },
+                        |row| from_blob::<SignedAction>(row.get("action_blob")?),
+                    )?
+                    .collect::<StateQueryResult<Vec<_>>>()?;
+                StateQueryResult::Ok(packages)
+            })
+            .await?;
+
+        let mut checked = HashSet::new();
+        let mut failed = Vec::new();
+        for package in packages {
+            let (author, membrane_proof) = match package.action() {
+                Action::AgentValidationPkg(AgentValidationPkg {
+                    author,
+                    membrane_proof,
+                    ..
+                }) => (author.clone(), membrane_proof.clone()),
+                _ => continue,
+            };
+            if !checked.insert(author.clone()) {
+                continue;
+            }
+            let reason = match run_genesis_self_check(&ribosome, &author, membrane_proof).await {
+                Ok(GenesisSelfCheckResult::Valid) => continue,
+                Ok(GenesisSelfCheckResult::Invalid(reason)) => reason,
+                Err(err) => err.to_string(),
+            };
+            failed.push(MembraneProofFailure {
+                agent: author,
+                reason,
+            });
+        }
+        Ok(MembraneProofRecheckReport {
+            checked_count: checked.len(),
+            failed,
+        })
+    }
+}
+
+/// Run the genesis self check of a DNA on the membrane proof of an agent, as genesis does.
+async fn run_genesis_self_check(
+    ribosome: &RealRibosome,
+    agent_key: &AgentPubKey,
+    membrane_proof: Option<MembraneProof>,
+) -> RibosomeResult<GenesisSelfCheckResult> {
+    let DnaDef {
+        name,
+        modifiers: DnaModifiers { properties, .. },
+        integrity_zomes,
+        ..
+    } = &ribosome.dna_def().content;
+    let dna_info = DnaInfoV1 {
+        zome_names: integrity_zomes.iter().map(|(n, _)| n.clone()).collect(),
+        name: name.clone(),
+        hash: ribosome.dna_def().to_hash(),
+        properties: properties.clone(),
+    };
+    ribosome
+        .run_genesis_self_check(
+            GenesisSelfCheckHostAccess {
+                host_access_1: GenesisSelfCheckHostAccessV1,
+                host_access_2: GenesisSelfCheckHostAccessV2,
+            },
+            GenesisSelfCheckInvocation {
+                invocation_1: GenesisSelfCheckInvocationV1 {
+                    payload: Arc::new(GenesisSelfCheckDataV1 {
+                        dna_info,
+                        membrane_proof: membrane_proof.clone(),
+                        agent_key: agent_key.clone(),
+                    }),
+                },
+                invocation_2: GenesisSelfCheckInvocationV2 {
+                    payload: Arc::new(GenesisSelfCheckDataV2 {
+                        membrane_proof,
+                        agent_key: agent_key.clone(),
+                    }),
+                },
+            },
+        )
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::ribosome::guest_callback::genesis_self_check::GenesisSelfCheckResult;
+    use crate::sweettest::*;
+    use ::fixt::prelude::*;
+    use holochain_state::prelude::*;
+    use holochain_types::inline_zome::InlineZomeSet;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn agents_with_failing_membrane_proofs_are_reported() {
+        holochain_trace::test_run();
+        let zome = InlineZomeSet::new_unique_single("integrity", "custom", Vec::new(), 0).function(
+            "integrity",
+            "genesis_self_check",
+            |_api, data: GenesisSelfCheckData| match data.membrane_proof {
+                None => Ok(GenesisSelfCheckResult::Valid),
+                Some(_) => Ok(GenesisSelfCheckResult::Invalid("no memproofs".into())),
+            },
+        );
+        let (dna_file, _, _) = SweetDnaFile::unique_from_inline_zomes(zome).await;
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let app = conductor.setup_app("app", [&dna_file]).await.unwrap();
+        let (alice,) = app.into_tuple();
+        await_consistency(10, [&alice]).await.unwrap();
+
+        // - An agent who joined with a membrane proof, without running the self check.
+        let mut package = fixt!(AgentValidationPkg);
+        package.membrane_proof = Some(std::sync::Arc::new(SerializedBytes::from(
+            UnsafeBytes::from(vec![1]),
+        )));
+        let bob = package.author.clone();
+        let op = DhtOpHashed::from_content_sync(ChainOp::RegisterAgentActivity(
+            fixt!(Signature),
+            Action::AgentValidationPkg(package),
+        ));
+        alice
+            .dht_db()
+            .write_async(move |txn| {
+                insert_op_dht(txn, &op, None)?;
+                set_validation_status(txn, op.as_hash(), ValidationStatus::Valid)?;
+                StateMutationResult::Ok(())
+            })
+            .await
+            .unwrap();
+
+        let report = conductor
+            .raw_handle()
+            .recheck_membrane_proofs(dna_file.dna_hash())
+            .await
+            .unwrap();
+        assert_eq!(2, report.checked_count);
+        assert_eq!(1, report.failed.len());
+        assert_eq!(bob, report.failed[0].agent);
+        assert_eq!("no memproofs", report.failed[0].reason);
+    }
+}
@@ -0,0 +1,196 @@
+use super::remote_outbox::*;
+use super::*;
+use holochain_nonce::fresh_nonce;
+use holochain_state::remote_outbox::{
+    delete_outbox_records_settled_before, load_outbox_records, put_outbox_records,
+};
+use std::time::Duration;
+
+/// How long to wait for the recipient to respond to a single attempt to send a queued message.
+pub(crate) const OUTBOX_ATTEMPT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// How often the outbox is checked for messages whose retry delay has passed.
+pub(crate) const OUTBOX_RETRY_INTERVAL: Duration = Duration::from_secs(1);
+
+impl Conductor {
+    /// Queue a remote call or signal from a zome of a cell until it is delivered
+    /// or its TTL runs out.
+    ///
+    /// The message is stored in the conductor database before this returns,
+    /// so it is still delivered if the conductor restarts.
+    pub(crate) async fn queue_remote_message(
+        &self,
+        from: CellId,
+        zome_name: ZomeName,
+        message: QueuedRemoteMessage,
+    ) -> ConductorResult<OutboxMessageId> {
+        let record = self
+            .remote_outbox
+            .queue(from, zome_name, message, Timestamp::now())
+            .ok_or_else(|| {
+                ConductorError::other(format!(
+                    "A cell can't queue more than {} remote messages at once",
+                    MAX_QUEUED_MESSAGES_PER_CELL
+                ))
+            })?;
+        let id = record.id;
+        if let Err(err) = put_outbox_records(&self.spaces.conductor_db, vec![record]).await {
+            self.remote_outbox.forget(id);
+            return Err(err.into());
+        }
+        self.remote_outbox.release(id);
+        Ok(id)
+    }
+
+    /// Load the remote messages which were queued before the conductor restarted.
+    pub(crate) async fn restore_remote_outbox(&self) -> ConductorResult<()> {
+        let records = load_outbox_records(&self.spaces.conductor_db).await?;
+        self.remote_outbox.restore(records, Timestamp::now());
+        Ok(())
+    }
+
+    /// The deliveries of remote messages which a cell queued.
+    pub(crate) fn outbox_deliveries(
+        &self,
+        from: &CellId,
+        ids: &[OutboxMessageId],
+    ) -> Vec<Option<OutboxDelivery>> {
+        self.remote_outbox.deliveries(from, ids)
+    }
+
+    /// Send the queued remote messages which are due, whenever messages are queued
+    /// or their recipients come online, and at least every [`OUTBOX_RETRY_INTERVAL`].
+    pub(crate) async fn deliver_remote_outbox_loop(self: Arc<Self>) {
+        let mut interval = tokio::time::interval(OUTBOX_RETRY_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = interval.tick() => (),
+                _ = self.remote_outbox.until_due() => (),
+            }
+            let now = Timestamp::now();
+            let due = self.remote_outbox.take_due(now);
+            if let Err(err) = put_outbox_records(&self.spaces.conductor_db, due.expired).await {
+                tracing::warn!(?err, "Failed to store expired remote messages");
+            }
+            if due.forgot_settled {
+                let before = now.saturating_sub(&OUTBOX_SETTLED_RETENTION);
+                if let Err(err) =
+                    delete_outbox_records_settled_before(&self.spaces.conductor_db, before).await
+                {
+                    tracing::warn!(?err, "Failed to delete settled remote messages");
+                }
+            }
+            for attempt in due.attempts {
+                let conductor = self.clone();
+                tokio::spawn(async move {
+                    let id = attempt.id;
+                    let outcome = conductor.attempt_outbox_delivery(attempt).await;
+                    if let OutboxAttemptOutcome::Failed(error) = &outcome {
+                        tracing::debug!(?id, ?error, "Failed to deliver a queued remote message");
+                    }
+                    let Some(record) =
+                        conductor
+                            .remote_outbox
+                            .record_attempt(id, outcome, Timestamp::now())
+                    else {
+                        return;
+                    };
+                    if let Err(err) =
+                        put_outbox_records(&conductor.spaces.conductor_db, vec![record]).await
+                    {
+                        tracing::warn!(
+                            ?id,
+                            ?err,
+                            "Failed to store the delivery of a remote message"
+                        );
+                    }
+                });
+            }
+        }
+    }
+
+    /// Retry the queued messages for these agents straight away, as they are online.
+    pub(crate) fn remote_agents_online(&self, agents: &HashSet<AgentPubKey>) {
+        self.remote_outbox.agents_online(agents, Timestamp::now());
+    }
+
+    async fn attempt_outbox_delivery(&self, attempt: OutboxAttempt) -> OutboxAttemptOutcome {
+        let OutboxAttempt {
+            from,
+            zome_name,
+            agent,
+            message,
+            ..
+        } = attempt;
+        let is_signal = matches!(message, RemoteMessage::Signal(_));
+        let (zome_name, fn_name, cap_secret, payload) = match message {
+            RemoteMessage::Signal(signal) => (zome_name, "recv_remote_signal".into(), None, signal),
+            RemoteMessage::Call {
+                zome_name,
+                fn_name,
+                cap_secret,
+                payload,
+            } => (zome_name, fn_name, cap_secret, payload),
+        };
+        // Every attempt needs its own nonce, otherwise a retry after a late
+        // response would be rejected as a replay.
+        let (nonce, expires_at) = match fresh_nonce(Timestamp::now()) {
+            Ok(nonce) => nonce,
+            Err(err) => return OutboxAttemptOutcome::Failed(err.to_string()),
+        };
+        let zome_call_unsigned = ZomeCallUnsigned {
+            provenance: from.agent_pubkey().clone(),
+            cell_id: CellId::new(from.dna_hash().clone(), agent.clone()),
+            zome_name,
+            fn_name,
+            cap_secret,
+            payload,
+            nonce,
+            expires_at,
+        };
+        let data_to_sign = match zome_call_unsigned.data_to_sign() {
+            Ok(data_to_sign) => data_to_sign,
+            Err(err) => return OutboxAttemptOutcome::Rejected(err.to_string()),
+        };
+        let signature = match from
+            .agent_pubkey()
+            .sign_raw(self.keystore(), data_to_sign)
+            .await
+        {
+            Ok(signature) => signature,
+            Err(err) => return OutboxAttemptOutcome::Failed(err.to_string()),
+        };
+
+        let network = self
+            .holochain_p2p()
+            .to_dna(from.dna_hash().clone(), self.get_chc(&from));
+        let response = match tokio::time::timeout(
+            OUTBOX_ATTEMPT_TIMEOUT,
+            network.call_remote(
+                zome_call_unsigned.provenance,
+                signature,
+                agent,
+                zome_call_unsigned.zome_name,
+                zome_call_unsigned.fn_name,
+                zome_call_unsigned.cap_secret,
+                zome_call_unsigned.payload,
+                zome_call_unsigned.nonce,
+                zome_call_unsigned.expires_at,
+            ),
+        )
+        .await
+        {
+            Ok(Ok(response)) => response,
+            Ok(Err(err)) => return OutboxAttemptOutcome::Failed(err.to_string()),
+            Err(_) => return OutboxAttemptOutcome::Failed("Timed out".into()),
+        };
+        match ZomeCallResponse::try_from(response) {
+            Ok(ZomeCallResponse::Ok(response)) => {
+                OutboxAttemptOutcome::Delivered((!is_signal).then_some(response))
+            }
+            Ok(ZomeCallResponse::NetworkError(err)) => OutboxAttemptOutcome::Failed(err),
+            Ok(response) => OutboxAttemptOutcome::Rejected(response.to_string()),
+            Err(err) => OutboxAttemptOutcome::Rejected(err.to_string()),
+        }
+    }
+}
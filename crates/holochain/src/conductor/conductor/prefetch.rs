@@ -0,0 +1,66 @@
+use super::*;
+use holochain_cascade::CascadeImpl;
+use holochain_p2p::actor::GetOptions as NetworkGetOptions;
+
+impl Conductor {
+    /// Prefetch the hashes which the cells of each running DNA read most often from
+    /// the network, every `prefetch_interval`.
+    pub(crate) async fn prefetch_hot_hashes_loop(self: Arc<Self>) {
+        loop {
+            let tuning_params = self.conductor_tuning_params();
+            let interval = tuning_params.prefetch_interval();
+            tokio::time::sleep(interval).await;
+            let count = tuning_params.prefetch_hot_hashes();
+            if count == 0 {
+                continue;
+            }
+            let dna_hashes: HashSet<DnaHash> = self
+                .running_cell_ids()
+                .into_iter()
+                .map(|cell_id| cell_id.dna_hash().clone())
+                .collect();
+            for dna_hash in dna_hashes {
+                // Prefetched hashes stay fresh until the prefetch after next, so that
+                // a slow prefetch doesn't send their reads back to the network.
+                if let Err(err) = self
+                    .prefetch_hot_hashes(&dna_hash, count, interval * 2)
+                    .await
+                {
+                    tracing::warn!(?err, ?dna_hash, "Failed to prefetch hot hashes");
+                }
+            }
+        }
+    }
+
+    /// Fetch the `count` hashes which the cells of a DNA read most often from the network
+    /// since the last prefetch into its cache database, so that they are read from there
+    /// for the next `fresh_for`.
+    pub(crate) async fn prefetch_hot_hashes(
+        &self,
+        dna_hash: &DnaHash,
+        count: usize,
+        fresh_for: std::time::Duration,
+    ) -> ConductorResult<()> {
+        let space = self.get_or_create_space(dna_hash)?;
+        let hot_hashes = space.dht_query_cache.hot_hashes().clone();
+        let hottest = hot_hashes.take_hottest(count);
+        if hottest.is_empty() {
+            return Ok(());
+        }
+        let network = Arc::new(self.holochain_p2p().to_dna(dna_hash.clone(), None));
+        let cascade = CascadeImpl::empty()
+            .with_network(network, space.cache_db.clone())
+            .with_dht_db_cache(space.dht_query_cache.clone());
+        tracing::debug!(?dna_hash, count = hottest.len(), "Prefetching hot hashes");
+        for hash in hottest {
+            match cascade
+                .fetch_record(hash.clone(), NetworkGetOptions::default())
+                .await
+            {
+                Ok(()) => hot_hashes.set_prefetched(hash, Instant::now() + fresh_for),
+                Err(err) => tracing::debug!(?err, ?hash, "Failed to prefetch a hot hash"),
+            }
+        }
+        Ok(())
+    }
+}
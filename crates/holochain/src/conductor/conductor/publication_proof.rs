@@ -0,0 +1,107 @@
+use super::*;
+use rusqlite::OptionalExtension;
+
+impl Conductor {
+    /// Export a [`PublicationProof`] for an action which the agent of a cell authored:
+    /// the signed action, the ops which were produced from it and the validation
+    /// receipts which have been collected for them so far.
+    pub async fn export_publication_proof(
+        &self,
+        cell_id: &CellId,
+        action_hash: &ActionHash,
+    ) -> ConductorResult<PublicationProof> {
+        let authored_db = self
+            .cell_by_id(cell_id)
+            .await?
+            .get_or_create_authored_db()?;
+        let (action, ops) = authored_db
+            .read_async({
+                let action_hash = action_hash.clone();
+                let author = cell_id.agent_pubkey().clone();
+                move |txn| {
+                    let action = txn
+                        .query_row(
+                            "SELECT blob FROM Action WHERE hash = :hash AND author = :author",
+                            rusqlite::named_params! { ":hash": action_hash, ":author": author },
+                            |row| row.get("blob"),
+                        )
+                        .optional()?
+                        .map(from_blob::<SignedAction>)
+                        .transpose()?;
+                    let ops = txn
+                        .prepare("SELECT hash, type FROM DhtOp WHERE action_hash = ?")?
+                        .query_map([&action_hash], |row| Ok((row.get(0)?, row.get(1)?)))?
+                        .collect::<Result<Vec<(DhtOpHash, ChainOpType)>, _>>()?;
+                    StateQueryResult::Ok((action, ops))
+                }
+            })
+            .await?;
+        let action = action.ok_or_else(|| {
+            ConductorError::ActionNotAuthored(action_hash.clone(), cell_id.clone())
+        })?;
+
+        let ops = self
+            .get_or_create_dht_db(cell_id.dna_hash())?
+            .read_async(move |txn| {
+                ops.into_iter()
+                    .map(|(op_hash, op_type)| {
+                        Ok(PublishedOp {
+                            op_type,
+                            receipts: list_receipts(txn, &op_hash)?,
+                            op_hash,
+                        })
+                    })
+                    .collect::<StateQueryResult<Vec<_>>>()
+            })
+            .await?;
+        Ok(PublicationProof {
+            dna_hash: cell_id.dna_hash().clone(),
+            action: SignedActionHashed::from_content_sync(action),
+            ops,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sweettest::*;
+    use crate::test_utils::inline_zomes::simple_create_read_zome;
+    use ::fixt::prelude::*;
+    use matches::assert_matches;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn publication_proofs_verify_with_the_dna_hash() {
+        holochain_trace::test_run();
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let (dna_file, _, _) =
+            SweetDnaFile::unique_from_inline_zomes(("zome", simple_create_read_zome())).await;
+        let app = conductor.setup_app("app", [&dna_file]).await.unwrap();
+        let (alice,) = app.into_tuple();
+        let action_hash: ActionHash = conductor.call(&alice.zome("zome"), "create", ()).await;
+
+        let proof = conductor
+            .raw_handle()
+            .export_publication_proof(alice.cell_id(), &action_hash)
+            .await
+            .unwrap();
+        // - The proof survives being sent to a third party.
+        let proof: PublicationProof = SerializedBytes::try_from(proof)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let verified = proof.verify(dna_file.dna_hash()).await.unwrap();
+        assert_eq!(action_hash, verified.action_hash);
+        assert_eq!(alice.agent_pubkey(), &verified.author);
+        // - A create produces a record, an entry and an activity op.
+        assert_eq!(3, verified.ops.len());
+
+        assert_matches!(
+            conductor
+                .raw_handle()
+                .export_publication_proof(alice.cell_id(), &fixt!(ActionHash))
+                .await,
+            Err(ConductorError::ActionNotAuthored(..))
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use super::*;
+use crate::conductor::metrics::{create_republished_ops_metric, RepublishedOpsMetric};
+use holochain_p2p::actor::GetOptions as NetworkGetOptions;
+use holochain_state::publish_audit::*;
+use std::time::Duration;
+
+/// How often a sample of the published actions of each running cell is checked with
+/// the authorities of the network.
+pub(crate) const PUBLISH_AUDIT_INTERVAL: Duration = Duration::from_secs(60 * 30);
+
+/// How many actions of a cell are checked in one audit.
+pub(crate) const PUBLISH_AUDIT_SAMPLE_SIZE: usize = 10;
+
+impl Conductor {
+    /// Audit the published ops of each running cell, every [`PUBLISH_AUDIT_INTERVAL`].
+    pub(crate) async fn audit_published_ops_loop(self: Arc<Self>) {
+        let metric = create_republished_ops_metric();
+        let mut interval = tokio::time::interval(PUBLISH_AUDIT_INTERVAL);
+        loop {
+            interval.tick().await;
+            if self.get_config().network.tuning_params.disable_publish {
+                continue;
+            }
+            for cell_id in self.running_cell_ids() {
+                if let Err(err) = self.audit_published_ops(&cell_id, &metric).await {
+                    tracing::warn!(?err, ?cell_id, "Failed to audit published ops");
+                }
+            }
+        }
+    }
+
+    /// Get a random sample of the actions whose ops the agent of a cell has finished
+    /// publishing from the network, and publish the ops of the actions which no
+    /// authority holds any more again.
+    /// Returns the number of ops which will be published again.
+    pub(crate) async fn audit_published_ops(
+        &self,
+        cell_id: &CellId,
+        metric: &RepublishedOpsMetric,
+    ) -> ConductorResult<usize> {
+        let cell = self.cell_by_id(cell_id).await?;
+        let authored_db = cell.get_or_create_authored_db()?;
+        let actions = authored_db
+            .read_async({
+                let author = cell_id.agent_pubkey().clone();
+                move |txn| actions_to_audit(txn, &author, PUBLISH_AUDIT_SAMPLE_SIZE)
+            })
+            .await?;
+        if actions.is_empty() {
+            return Ok(0);
+        }
+
+        let network = self
+            .holochain_p2p()
+            .to_dna(cell_id.dna_hash().clone(), None);
+        let options = NetworkGetOptions {
+            follow_redirects: false,
+            ..Default::default()
+        };
+        let mut missing = Vec::new();
+        for action_hash in actions {
+            match network
+                .get(action_hash.clone().into(), options.clone())
+                .await
+            {
+                // When no authority answers, there's no telling whether they hold it.
+                Ok(responses) if responses.is_empty() => (),
+                Ok(responses) => {
+                    let held = responses.iter().any(|response| match response {
+                        WireOps::Record(record) => record.action.is_some(),
+                        // A warrant means that the action is held, and found invalid.
+                        WireOps::Warrant(_) => true,
+                        WireOps::Entry(_) => false,
+                    });
+                    if !held {
+                        missing.push(action_hash);
+                    }
+                }
+                Err(err) => {
+                    tracing::debug!(?err, ?action_hash, "Failed to audit a published action");
+                }
+            }
+        }
+        if missing.is_empty() {
+            return Ok(0);
+        }
+
+        let republished = authored_db
+            .write_async({
+                let missing = missing.clone();
+                move |txn| {
+                    let mut republished = 0;
+                    for action_hash in &missing {
+                        republished += republish_action_ops(txn, action_hash)?;
+                    }
+                    StateMutationResult::Ok(republished)
+                }
+            })
+            .await?;
+        tracing::info!(
+            ?cell_id,
+            ?missing,
+            republished,
+            "Publishing the ops of actions which their authorities no longer hold again"
+        );
+        metric.add(
+            republished as u64,
+            &[opentelemetry_api::KeyValue::new(
+                "dna_hash",
+                format!("{:?}", cell_id.dna_hash()),
+            )],
+        );
+        cell.triggers()
+            .publish_dht_ops
+            .trigger(&"audit_published_ops");
+        Ok(republished)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sweettest::*;
+    use crate::test_utils::inline_zomes::simple_create_read_zome;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ops_which_authorities_lost_are_published_again() {
+        holochain_trace::test_run();
+
+        let mut conductors = SweetConductorBatch::from_standard_config(2).await;
+        let (dna_file, _, _) =
+            SweetDnaFile::unique_from_inline_zomes(("zome", simple_create_read_zome())).await;
+        let apps = conductors
+            .setup_app("app", &[dna_file.clone()])
+            .await
+            .unwrap();
+        let ((alice,), (bob,)) = apps.into_tuples();
+        conductors.exchange_peer_info().await;
+
+        let action_hash: ActionHash = conductors[0].call(&alice.zome("zome"), "create", ()).await;
+        await_consistency(60, [&alice, &bob]).await.unwrap();
+        // Two nodes can't collect a full bundle of receipts, so pretend that they have.
+        alice
+            .authored_db()
+            .write_async(|txn| {
+                txn.execute("UPDATE DhtOp SET receipts_complete = 1", [])?;
+                DatabaseResult::Ok(())
+            })
+            .await
+            .unwrap();
+
+        let conductor = conductors[0].raw_handle();
+        let metric = create_republished_ops_metric();
+        let audit = || conductor.audit_published_ops(alice.cell_id(), &metric);
+        // - Nothing is published again while the authorities hold the ops.
+        assert_eq!(0, audit().await.unwrap());
+
+        // - Once every authority has lost the action, its ops are published again.
+        for cell in [&alice, &bob] {
+            let action_hash = action_hash.clone();
+            cell.dht_db()
+                .write_async(move |txn| {
+                    txn.execute("DELETE FROM DhtOp WHERE action_hash = ?", [&action_hash])?;
+                    txn.execute("DELETE FROM Action WHERE hash = ?", [&action_hash])?;
+                    DatabaseResult::Ok(())
+                })
+                .await
+                .unwrap();
+        }
+        // A create produces a record, an entry and an activity op.
+        assert_eq!(3, audit().await.unwrap());
+        // - The action isn't audited again until its ops have been published again.
+        assert_eq!(0, audit().await.unwrap());
+    }
+}
@@ -0,0 +1,104 @@
+use super::*;
+use crate::conductor::metrics::{create_region_redundancy_metric, RegionRedundancyMetric};
+use std::time::Duration;
+
+/// How often the redundancy of the regions which local agents hold is estimated.
+pub(crate) const REDUNDANCY_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 5);
+
+impl Conductor {
+    /// Estimate the redundancy of the regions which the local agents of each running DNA
+    /// hold, every [`REDUNDANCY_CHECK_INTERVAL`].
+    ///
+    /// When `min_region_redundancy` is set, a [`SystemSignal::LowRedundancy`] is emitted
+    /// to the apps of a DNA when one of its regions falls below it.
+    pub(crate) async fn monitor_region_redundancy_loop(self: Arc<Self>) {
+        let metric = create_region_redundancy_metric();
+        let mut low_regions: HashMap<DnaHash, HashSet<u32>> = HashMap::new();
+        let mut interval = tokio::time::interval(REDUNDANCY_CHECK_INTERVAL);
+        loop {
+            interval.tick().await;
+            let mut agents_by_dna: HashMap<DnaHash, Vec<AgentPubKey>> = HashMap::new();
+            for cell_id in self.running_cell_ids() {
+                let (dna_hash, agent) = cell_id.into_dna_and_agent();
+                agents_by_dna.entry(dna_hash).or_default().push(agent);
+            }
+            low_regions.retain(|dna_hash, _| agents_by_dna.contains_key(dna_hash));
+            for (dna_hash, agents) in agents_by_dna {
+                let low = low_regions.entry(dna_hash.clone()).or_default();
+                if let Err(err) = self
+                    .check_region_redundancy(&dna_hash, &agents, &metric, low)
+                    .await
+                {
+                    tracing::warn!(?err, ?dna_hash, "Failed to check region redundancy");
+                }
+            }
+        }
+    }
+
+    /// Record the redundancy of each region of a DNA which `agents` hold, and warn about
+    /// the regions which have newly fallen below `min_region_redundancy`.
+    /// `low` holds the start locations of the regions which were already low.
+    async fn check_region_redundancy(
+        &self,
+        dna_hash: &DnaHash,
+        agents: &[AgentPubKey],
+        metric: &RegionRedundancyMetric,
+        low: &mut HashSet<u32>,
+    ) -> ConductorResult<()> {
+        let regions = query_region_redundancy(
+            self.p2p_agents_db(dna_hash).into(),
+            dna_hash.to_kitsune(),
+            agents,
+        )
+        .await?;
+        for region in &regions {
+            metric.record(
+                region.estimated_redundancy as u64,
+                &[
+                    opentelemetry_api::KeyValue::new("dna_hash", format!("{:?}", dna_hash)),
+                    opentelemetry_api::KeyValue::new("region_start", region.start_loc as i64),
+                ],
+            );
+        }
+
+        let min_redundancy = self.conductor_tuning_params().min_region_redundancy();
+        let newly_low: Vec<RegionRedundancy> = regions
+            .into_iter()
+            .filter(|region| region.estimated_redundancy < min_redundancy)
+            .collect();
+        let still_low: HashSet<u32> = newly_low.iter().map(|region| region.start_loc).collect();
+        let newly_low: Vec<RegionRedundancy> = newly_low
+            .into_iter()
+            .filter(|region| !low.contains(&region.start_loc))
+            .collect();
+        *low = still_low;
+        if newly_low.is_empty() {
+            return Ok(());
+        }
+
+        let app_ids = self
+            .list_running_apps_for_dependent_dna_hash(dna_hash)
+            .await?;
+        for region in newly_low {
+            tracing::warn!(
+                ?dna_hash,
+                start_loc = region.start_loc,
+                end_loc = region.end_loc,
+                estimated_redundancy = region.estimated_redundancy,
+                min_redundancy,
+                "The estimated redundancy of a held region of the DHT is low"
+            );
+            for app_id in &app_ids {
+                // It's fine if no interface is listening.
+                let _ = self
+                    .app_broadcast
+                    .create_send_handle(app_id.clone())
+                    .send(Signal::System(SystemSignal::LowRedundancy {
+                        dna_hash: dna_hash.clone(),
+                        region: region.clone(),
+                    }));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use super::*;
+use holochain_nonce::fresh_nonce;
+
+impl Conductor {
+    /// Call a zome function of a remote agent's cell of any DNA, on behalf of a cell
+    /// of an app on this conductor.
+    ///
+    /// The network of the DNA is reached through the cell which the app of the calling
+    /// cell has in that DNA. Every cell of an app is of the app's agent, so that cell is
+    /// of the same agent as the calling cell, and the call is signed by the agent of the
+    /// calling cell as any of its remote calls are. No other agent's identity is used:
+    /// a cell of the DNA which is of another agent, such as a cell of another app which
+    /// the app depends on, is never called through. The remote cell must grant the agent
+    /// access to the function. Network errors are returned as
+    /// [`ZomeCallResponse::NetworkError`], as they are for remote calls within a DNA.
+    pub(crate) async fn call_remote_cell(
+        &self,
+        from: &CellId,
+        to: CellId,
+        zome_name: ZomeName,
+        fn_name: FunctionName,
+        cap_secret: Option<CapSecret>,
+        payload: ExternIO,
+    ) -> ConductorResult<ZomeCallResponse> {
+        let via = self
+            .find_cell_of_dna_alongside_cell(from, to.dna_hash())
+            .await?
+            .ok_or_else(|| ConductorError::RemoteCellUnreachable(to.dna_hash().clone()))?;
+        let network = self.cell_by_id(&via).await?.holochain_p2p_dna().clone();
+        let provenance = via.agent_pubkey().clone();
+        let (nonce, expires_at) = fresh_nonce(Timestamp::now()).map_err(ConductorError::other)?;
+        let zome_call_unsigned = ZomeCallUnsigned {
+            provenance: provenance.clone(),
+            cell_id: to,
+            zome_name,
+            fn_name,
+            cap_secret,
+            payload,
+            nonce,
+            expires_at,
+        };
+        let signature = provenance
+            .sign_raw(self.keystore(), zome_call_unsigned.data_to_sign()?)
+            .await?;
+        match network
+            .call_remote(
+                provenance,
+                signature,
+                zome_call_unsigned.cell_id.agent_pubkey().clone(),
+                zome_call_unsigned.zome_name,
+                zome_call_unsigned.fn_name,
+                zome_call_unsigned.cap_secret,
+                zome_call_unsigned.payload,
+                zome_call_unsigned.nonce,
+                zome_call_unsigned.expires_at,
+            )
+            .await
+        {
+            Ok(response) => Ok(ZomeCallResponse::try_from(response)?),
+            Err(e) => Ok(ZomeCallResponse::NetworkError(e.to_string())),
+        }
+    }
+
+    /// Find the cell of a DNA and of the same agent in the running app which the given
+    /// cell is in.
+    async fn find_cell_of_dna_alongside_cell(
+        &self,
+        cell_id: &CellId,
+        dna_hash: &DnaHash,
+    ) -> ConductorResult<Option<CellId>> {
+        Ok(self
+            .get_state()
+            .await?
+            .running_apps()
+            .find(|(_, running_app)| running_app.all_cells().any(|i| i == *cell_id))
+            .and_then(|(_, running_app)| {
+                running_app.all_cells().find(|cell| {
+                    cell.dna_hash() == dna_hash && cell.agent_pubkey() == cell_id.agent_pubkey()
+                })
+            }))
+    }
+}
@@ -0,0 +1,441 @@
+//! The remote calls and signals which cells have queued for other agents,
+//! which are retried until they are delivered or expire.
+//!
+//! Every change to a message is returned as an [`OutboxRecord`], which the
+//! conductor stores in its database, so that the outbox can be restored
+//! with [`RemoteOutbox::restore`] when the conductor restarts.
+
+use holochain_state::remote_outbox::OutboxRecord;
+use holochain_types::prelude::*;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use tokio::sync::Notify;
+
+/// The most messages which a cell may have waiting in the outbox at once.
+pub(crate) const MAX_QUEUED_MESSAGES_PER_CELL: usize = 1000;
+
+/// The longest a queued message may be kept.
+pub(crate) const MAX_OUTBOX_TTL: Duration = Duration::from_secs(60 * 60 * 24 * 7);
+
+/// How long delivered, rejected and expired messages are kept, so that their
+/// delivery can be looked up.
+pub(crate) const OUTBOX_SETTLED_RETENTION: Duration = Duration::from_secs(60 * 60);
+
+/// How long to wait after the first failed attempt before retrying a message.
+/// The delay doubles with every failed attempt.
+pub(crate) const OUTBOX_MIN_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// The longest delay between two attempts, unless the recipient comes online before it.
+pub(crate) const OUTBOX_MAX_RETRY_DELAY: Duration = Duration::from_secs(60 * 5);
+
+/// A message which is due to be sent.
+#[derive(Clone, Debug)]
+pub(crate) struct OutboxAttempt {
+    pub(crate) id: OutboxMessageId,
+    /// The cell which queued the message.
+    pub(crate) from: CellId,
+    /// The zome which queued the message, whose `recv_remote_signal` receives signals.
+    pub(crate) zome_name: ZomeName,
+    pub(crate) agent: AgentPubKey,
+    pub(crate) message: RemoteMessage,
+}
+
+/// The outcome of an attempt to send a message.
+#[derive(Clone, Debug)]
+pub(crate) enum OutboxAttemptOutcome {
+    Delivered(Option<ExternIO>),
+    Rejected(String),
+    Failed(String),
+}
+
+/// The messages which cells have queued for other agents.
+#[derive(Default)]
+pub(crate) struct RemoteOutbox {
+    state: parking_lot::Mutex<OutboxState>,
+    /// Notified when messages become due before their retry delay.
+    due: Notify,
+}
+
+#[derive(Default)]
+struct OutboxState {
+    next_id: u64,
+    messages: HashMap<OutboxMessageId, OutboxEntry>,
+}
+
+struct OutboxEntry {
+    record: OutboxRecord,
+    next_attempt: Timestamp,
+    /// Set while the message is being sent, or hasn't been stored yet.
+    in_progress: bool,
+}
+
+impl OutboxEntry {
+    fn attempts(&self) -> u32 {
+        match &self.record.delivery {
+            OutboxDelivery::Pending { attempts, .. }
+            | OutboxDelivery::Delivered { attempts, .. }
+            | OutboxDelivery::Expired { attempts } => *attempts,
+            OutboxDelivery::Rejected(_) => 0,
+        }
+    }
+
+    fn settle(&mut self, delivery: OutboxDelivery, now: Timestamp) {
+        self.record.delivery = delivery;
+        self.record.settled_at = Some(now);
+        self.record.revision += 1;
+    }
+}
+
+/// The messages which [`RemoteOutbox::take_due`] found due or expired.
+#[derive(Debug, Default)]
+pub(crate) struct DueMessages {
+    /// The messages to send.
+    pub(crate) attempts: Vec<OutboxAttempt>,
+    /// The records of the messages which expired.
+    pub(crate) expired: Vec<OutboxRecord>,
+    /// Whether any settled messages were forgotten, which should also be
+    /// deleted from the database.
+    pub(crate) forgot_settled: bool,
+}
+
+impl RemoteOutbox {
+    /// Restore the messages which were stored before the conductor restarted.
+    /// The pending messages are sent again straight away.
+    pub(crate) fn restore(&self, records: Vec<OutboxRecord>, now: Timestamp) {
+        let mut state = self.state.lock();
+        for record in records {
+            state.next_id = state.next_id.max(record.id.0 + 1);
+            state.messages.insert(
+                record.id,
+                OutboxEntry {
+                    record,
+                    next_attempt: now,
+                    in_progress: false,
+                },
+            );
+        }
+        drop(state);
+        self.due.notify_one();
+    }
+
+    /// Queue a message from a zome of a cell.
+    ///
+    /// The message isn't sent until it is [`released`](Self::release), so that
+    /// it can be stored first. Returns `None` if the cell already has
+    /// [`MAX_QUEUED_MESSAGES_PER_CELL`] messages waiting.
+    pub(crate) fn queue(
+        &self,
+        from: CellId,
+        zome_name: ZomeName,
+        message: QueuedRemoteMessage,
+        now: Timestamp,
+    ) -> Option<OutboxRecord> {
+        let mut state = self.state.lock();
+        let pending = state
+            .messages
+            .values()
+            .filter(|entry| entry.record.from == from && entry.record.delivery.is_pending())
+            .count();
+        if pending >= MAX_QUEUED_MESSAGES_PER_CELL {
+            return None;
+        }
+        let id = OutboxMessageId(state.next_id);
+        state.next_id += 1;
+        let ttl = Duration::from_millis(message.ttl_ms).min(MAX_OUTBOX_TTL);
+        let record = OutboxRecord {
+            id,
+            from,
+            zome_name,
+            agent: message.agent,
+            message: message.message,
+            delivery: OutboxDelivery::Pending {
+                attempts: 0,
+                last_error: None,
+            },
+            expires_at: now.saturating_add(&ttl),
+            settled_at: None,
+            revision: 0,
+        };
+        state.messages.insert(
+            id,
+            OutboxEntry {
+                record: record.clone(),
+                next_attempt: now,
+                in_progress: true,
+            },
+        );
+        Some(record)
+    }
+
+    /// Start sending a queued message, now that it has been stored.
+    pub(crate) fn release(&self, id: OutboxMessageId) {
+        if let Some(entry) = self.state.lock().messages.get_mut(&id) {
+            entry.in_progress = false;
+        }
+        self.due.notify_one();
+    }
+
+    /// Drop a queued message which couldn't be stored.
+    pub(crate) fn forget(&self, id: OutboxMessageId) {
+        self.state.lock().messages.remove(&id);
+    }
+
+    /// Make the messages for these agents due straight away, as they are online.
+    pub(crate) fn agents_online(&self, agents: &HashSet<AgentPubKey>, now: Timestamp) {
+        let mut state = self.state.lock();
+        let mut any = false;
+        for entry in state.messages.values_mut() {
+            if entry.record.delivery.is_pending() && agents.contains(&entry.record.agent) {
+                entry.next_attempt = now;
+                any = true;
+            }
+        }
+        drop(state);
+        if any {
+            self.due.notify_one();
+        }
+    }
+
+    /// Wait until messages may have become due before their retry delay.
+    pub(crate) async fn until_due(&self) {
+        self.due.notified().await
+    }
+
+    /// Take the messages which are due, so that they can be sent.
+    ///
+    /// This also expires the messages whose TTL has run out, and forgets
+    /// the messages which were settled more than [`OUTBOX_SETTLED_RETENTION`] ago.
+    pub(crate) fn take_due(&self, now: Timestamp) -> DueMessages {
+        let mut state = self.state.lock();
+        let mut due = DueMessages::default();
+        state.messages.retain(|_, entry| {
+            let keep = entry.record.settled_at.map_or(true, |settled_at| {
+                now < settled_at.saturating_add(&OUTBOX_SETTLED_RETENTION)
+            });
+            due.forgot_settled |= !keep;
+            keep
+        });
+        for (id, entry) in state.messages.iter_mut() {
+            if !entry.record.delivery.is_pending() || entry.in_progress {
+                continue;
+            }
+            if entry.record.expires_at <= now {
+                let attempts = entry.attempts();
+                entry.settle(OutboxDelivery::Expired { attempts }, now);
+                due.expired.push(entry.record.clone());
+            } else if entry.next_attempt <= now {
+                entry.in_progress = true;
+                due.attempts.push(OutboxAttempt {
+                    id: *id,
+                    from: entry.record.from.clone(),
+                    zome_name: entry.record.zome_name.clone(),
+                    agent: entry.record.agent.clone(),
+                    message: entry.record.message.clone(),
+                });
+            }
+        }
+        due
+    }
+
+    /// Record the outcome of an attempt to send a message.
+    /// Returns the changed record of the message.
+    pub(crate) fn record_attempt(
+        &self,
+        id: OutboxMessageId,
+        outcome: OutboxAttemptOutcome,
+        now: Timestamp,
+    ) -> Option<OutboxRecord> {
+        let mut state = self.state.lock();
+        let entry = state.messages.get_mut(&id)?;
+        entry.in_progress = false;
+        let attempts = entry.attempts() + 1;
+        match outcome {
+            OutboxAttemptOutcome::Delivered(response) => {
+                entry.settle(OutboxDelivery::Delivered { response, attempts }, now);
+            }
+            OutboxAttemptOutcome::Rejected(reason) => {
+                entry.settle(OutboxDelivery::Rejected(reason), now);
+            }
+            OutboxAttemptOutcome::Failed(error) => {
+                entry.record.delivery = OutboxDelivery::Pending {
+                    attempts,
+                    last_error: Some(error),
+                };
+                entry.record.revision += 1;
+                let delay = OUTBOX_MIN_RETRY_DELAY
+                    .saturating_mul(2u32.saturating_pow(attempts - 1))
+                    .min(OUTBOX_MAX_RETRY_DELAY);
+                entry.next_attempt = now.saturating_add(&delay);
+            }
+        }
+        Some(entry.record.clone())
+    }
+
+    /// The deliveries of messages which a cell queued, in the same order as `ids`.
+    pub(crate) fn deliveries(
+        &self,
+        from: &CellId,
+        ids: &[OutboxMessageId],
+    ) -> Vec<Option<OutboxDelivery>> {
+        let state = self.state.lock();
+        ids.iter()
+            .map(|id| {
+                state
+                    .messages
+                    .get(id)
+                    .filter(|entry| &entry.record.from == from)
+                    .map(|entry| entry.record.delivery.clone())
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::prelude::*;
+    use hdk::prelude::AgentPubKeyFixturator;
+    use hdk::prelude::CellIdFixturator;
+
+    fn signal(agent: AgentPubKey, ttl: Duration) -> QueuedRemoteMessage {
+        QueuedRemoteMessage {
+            agent,
+            message: RemoteMessage::Signal(ExternIO::encode(()).unwrap()),
+            ttl_ms: ttl.as_millis() as u64,
+        }
+    }
+
+    fn later(now: Timestamp, duration: Duration) -> Timestamp {
+        now.saturating_add(&duration)
+    }
+
+    #[test]
+    fn messages_are_retried_until_they_are_delivered() {
+        let outbox = RemoteOutbox::default();
+        let from = fixt!(CellId);
+        let agent = fixt!(AgentPubKey);
+        let now = Timestamp::now();
+        let id = outbox
+            .queue(
+                from.clone(),
+                "zome".into(),
+                signal(agent.clone(), Duration::from_secs(60)),
+                now,
+            )
+            .unwrap()
+            .id;
+
+        // A message isn't sent until it has been stored.
+        assert!(outbox.take_due(now).attempts.is_empty());
+        outbox.release(id);
+        let due = outbox.take_due(now);
+        assert_eq!(1, due.attempts.len());
+        // An attempt in progress isn't taken again.
+        assert!(outbox.take_due(now).attempts.is_empty());
+
+        let record = outbox
+            .record_attempt(id, OutboxAttemptOutcome::Failed("offline".into()), now)
+            .unwrap();
+        assert_eq!(1, record.revision);
+        assert!(outbox.take_due(now).attempts.is_empty());
+        assert_eq!(
+            1,
+            outbox
+                .take_due(later(now, OUTBOX_MIN_RETRY_DELAY))
+                .attempts
+                .len()
+        );
+        outbox.record_attempt(id, OutboxAttemptOutcome::Failed("offline".into()), now);
+
+        // The message is sent again as soon as the agent is online.
+        outbox.agents_online(&[agent].into_iter().collect(), now);
+        assert_eq!(1, outbox.take_due(now).attempts.len());
+        let record = outbox
+            .record_attempt(id, OutboxAttemptOutcome::Delivered(None), now)
+            .unwrap();
+        assert_eq!(Some(now), record.settled_at);
+        assert_eq!(3, record.revision);
+
+        assert_eq!(
+            vec![Some(OutboxDelivery::Delivered {
+                response: None,
+                attempts: 3
+            })],
+            outbox.deliveries(&from, &[id])
+        );
+        // Other cells can't see the delivery.
+        assert_eq!(vec![None], outbox.deliveries(&fixt!(CellId), &[id]));
+
+        assert!(
+            outbox
+                .take_due(later(now, OUTBOX_SETTLED_RETENTION))
+                .forgot_settled
+        );
+        assert_eq!(vec![None], outbox.deliveries(&from, &[id]));
+    }
+
+    #[test]
+    fn messages_expire() {
+        let outbox = RemoteOutbox::default();
+        let from = fixt!(CellId);
+        let now = Timestamp::now();
+        let id = outbox
+            .queue(
+                from.clone(),
+                "zome".into(),
+                signal(fixt!(AgentPubKey), Duration::from_secs(1)),
+                now,
+            )
+            .unwrap()
+            .id;
+        outbox.release(id);
+
+        let due = outbox.take_due(later(now, Duration::from_secs(1)));
+        assert!(due.attempts.is_empty());
+        assert_eq!(
+            vec![id],
+            due.expired.iter().map(|r| r.id).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![Some(OutboxDelivery::Expired { attempts: 0 })],
+            outbox.deliveries(&from, &[id])
+        );
+    }
+
+    #[test]
+    fn restored_messages_are_sent_again_and_keep_their_ids() {
+        let outbox = RemoteOutbox::default();
+        let from = fixt!(CellId);
+        let now = Timestamp::now();
+        let record = outbox
+            .queue(
+                from.clone(),
+                "zome".into(),
+                signal(fixt!(AgentPubKey), Duration::from_secs(60)),
+                now,
+            )
+            .unwrap();
+
+        // The conductor restarts while the message is pending.
+        let outbox = RemoteOutbox::default();
+        outbox.restore(vec![record.clone()], now);
+        assert_eq!(
+            vec![record.id],
+            outbox
+                .take_due(now)
+                .attempts
+                .iter()
+                .map(|a| a.id)
+                .collect::<Vec<_>>()
+        );
+        let next = outbox
+            .queue(
+                from,
+                "zome".into(),
+                signal(fixt!(AgentPubKey), Duration::from_secs(60)),
+                now,
+            )
+            .unwrap();
+        assert!(next.id > record.id);
+    }
+}
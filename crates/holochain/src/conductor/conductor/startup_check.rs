@@ -0,0 +1,110 @@
+use super::*;
+use holochain_conductor_api::CellStartupHealth;
+use holochain_sqlite::schema::check_schema_version;
+use holochain_state::source_chain::{verify_chain_head, SourceChainResult};
+
+impl Conductor {
+    /// Check the chain head, the database schema versions and the DHT query cache
+    /// of each running cell, and keep the report for [`Conductor::startup_report`].
+    pub(crate) async fn check_cells_on_startup(&self) -> StartupReport {
+        let mut cells = Vec::new();
+        for cell_id in self.running_cell_ids() {
+            let health = self.check_cell_on_startup(cell_id).await;
+            if !health.is_ok() {
+                tracing::error!(?health, "A cell failed its startup checks");
+            }
+            cells.push(health);
+        }
+        let report = StartupReport {
+            healthy: cells.iter().all(CellStartupHealth::is_ok),
+            checked_at: Timestamp::now(),
+            cells,
+        };
+        self.startup_report
+            .share_mut(|startup_report| *startup_report = Some(report.clone()));
+        report
+    }
+
+    async fn check_cell_on_startup(&self, cell_id: CellId) -> CellStartupHealth {
+        let chain_head: SourceChainResult<()> = async {
+            let author = Arc::new(cell_id.agent_pubkey().clone());
+            self.get_or_create_authored_db(cell_id.dna_hash(), cell_id.agent_pubkey().clone())?
+                .read_async(move |txn| verify_chain_head(txn, author))
+                .await
+        }
+        .await;
+
+        let schema: DatabaseResult<()> =
+            async {
+                check_db_schema(&self.get_or_create_authored_db(
+                    cell_id.dna_hash(),
+                    cell_id.agent_pubkey().clone(),
+                )?)
+                .await?;
+                check_db_schema(&self.get_or_create_dht_db(cell_id.dna_hash())?).await?;
+                check_db_schema(&self.get_or_create_cache_db(cell_id.dna_hash())?).await
+            }
+            .await;
+
+        let dht_query_cache: DatabaseResult<()> = async {
+            self.get_or_create_space(cell_id.dna_hash())?
+                .dht_query_cache
+                .init()
+                .await
+        }
+        .await;
+
+        CellStartupHealth {
+            cell_id,
+            chain_head: chain_head.into(),
+            schema: schema.into(),
+            dht_query_cache: dht_query_cache.into(),
+        }
+    }
+
+    /// The report of the checks which were run on the cells when the conductor
+    /// started, if `verify_integrity_on_startup` is set.
+    pub fn startup_report(&self) -> Option<StartupReport> {
+        self.startup_report.share_ref(|report| report.clone())
+    }
+}
+
+async fn check_db_schema<Kind: DbKindT>(db: &DbWrite<Kind>) -> DatabaseResult<()> {
+    let db_kind = db.kind().kind();
+    db.read_async(move |txn| check_schema_version(txn, &db_kind))
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sweettest::*;
+    use holochain_conductor_api::HealthStatus;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn cells_are_checked_on_startup() {
+        holochain_trace::test_run();
+
+        let mut config = SweetConductorConfig::standard();
+        config
+            .tuning_params
+            .get_or_insert_with(Default::default)
+            .verify_integrity_on_startup = Some(true);
+        let mut conductor = SweetConductor::from_config(config).await;
+        let (dna_file, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+        let app = conductor.setup_app("app", [&dna_file]).await.unwrap();
+        let (cell,) = app.into_tuple();
+
+        // The cell wasn't running when the conductor started.
+        assert!(conductor.startup_report().unwrap().cells.is_empty());
+
+        conductor.shutdown().await;
+        conductor.startup().await;
+
+        let report = conductor.startup_report().unwrap();
+        assert!(report.healthy);
+        assert_eq!(1, report.cells.len());
+        assert_eq!(cell.cell_id(), &report.cells[0].cell_id);
+        assert_eq!(HealthStatus::Ok, report.cells[0].chain_head);
+    }
+}
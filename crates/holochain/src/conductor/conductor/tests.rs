@@ -14,6 +14,7 @@ use crate::{
 use ::fixt::prelude::*;
 use holochain_conductor_api::AppInfoStatus;
 use holochain_conductor_api::CellInfo;
+use holochain_conductor_api::DryRunValidation;
 use holochain_keystore::crude_mock_keystore::*;
 use holochain_keystore::test_keystore;
 use holochain_types::inline_zome::InlineZomeSet;
@@ -53,13 +54,15 @@ async fn can_update_state() {
     .unwrap();
     let conductor = Conductor::new(
         config.into(),
+        None,
         ribosome_store,
         keystore,
         holochain_p2p,
         spaces,
         post_commit_sender,
         outcome_tx,
-    );
+    )
+    .unwrap();
     let state = conductor.get_state().await.unwrap();
     let mut expect_state = ConductorState::default();
     expect_state.set_tag(state.tag().clone());
@@ -109,13 +112,15 @@ async fn app_ids_are_unique() {
     .unwrap();
     let conductor = Conductor::new(
         config.into(),
+        None,
         ribosome_store,
         test_keystore(),
         holochain_p2p,
         spaces,
         post_commit_sender,
         outcome_tx,
-    );
+    )
+    .unwrap();
 
     let cell_id = fake_cell_id(1);
 
@@ -894,6 +899,128 @@ async fn test_bad_entry_validation_after_genesis_returns_zome_call_error() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn dry_run_zome_calls_commit_nothing() {
+    holochain_trace::test_run();
+    let (dna, _, _) = mk_dna(simple_crud_zome()).await;
+    let mut conductor = SweetConductorConfig::standard()
+        .no_dpki()
+        .build_conductor()
+        .await;
+    let app = conductor.setup_app("app", [&dna]).await.unwrap();
+    let (cell,) = app.into_tuple();
+    let zome = cell.zome(SweetInlineZomes::COORDINATOR);
+
+    let (nonce, expires_at) = holochain_nonce::fresh_nonce(Timestamp::now()).unwrap();
+    let call = ZomeCall::try_from_unsigned_zome_call(
+        conductor.keystore(),
+        ZomeCallUnsigned {
+            cell_id: cell.cell_id().clone(),
+            zome_name: zome.name().clone(),
+            fn_name: "create_unit".into(),
+            cap_secret: None,
+            provenance: cell.agent_pubkey().clone(),
+            payload: ExternIO::encode(()).unwrap(),
+            nonce,
+            expires_at,
+        },
+    )
+    .await
+    .unwrap();
+    let outcome = conductor
+        .raw_handle()
+        .call_zome_dry_run(call)
+        .await
+        .unwrap();
+
+    assert_matches!(outcome.result, Ok(ZomeCallResponse::Ok(_)));
+    assert_eq!(DryRunValidation::Valid, outcome.validation);
+    assert_eq!(1, outcome.records.len());
+
+    // - The action which would have been committed is the one which is committed next.
+    let hash: ActionHash = conductor.call(&zome, "create_unit", ()).await;
+    let record: Option<Record> = conductor.call(&zome, "read", hash).await;
+    assert_eq!(
+        outcome.records[0].action().action_seq(),
+        record.unwrap().action().action_seq()
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn dry_run_zome_calls_reach_no_other_agents_or_cells() {
+    holochain_trace::test_run();
+    let touched = Arc::new(AtomicU32::new(0));
+    let touched_clone = touched.clone();
+    let zome_1 = SweetInlineZomes::new(vec![], 0).function("touch", move |_, _: ()| {
+        touched_clone.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    });
+    let (dna_1, _, _) = SweetDnaFile::unique_from_inline_zomes(zome_1).await;
+    let zome_2 = SweetInlineZomes::new(vec![], 0).function(
+        "signal_and_bridge",
+        |api, (agent, cell_id): (AgentPubKey, CellId)| {
+            let signal_refused = api
+                .send_remote_signal(RemoteSignal {
+                    agents: vec![agent],
+                    signal: ExternIO::encode(()).unwrap(),
+                })
+                .is_err();
+            let bridge_refused = api
+                .call(vec![Call {
+                    target: CallTarget::ConductorCell(CallTargetCell::OtherCell(cell_id)),
+                    zome_name: SweetInlineZomes::COORDINATOR.into(),
+                    fn_name: "touch".into(),
+                    cap_secret: None,
+                    payload: ExternIO::encode(()).unwrap(),
+                }])
+                .is_err();
+            Ok((signal_refused, bridge_refused))
+        },
+    );
+    let (dna_2, _, _) = SweetDnaFile::unique_from_inline_zomes(zome_2).await;
+    let mut conductor = SweetConductorConfig::standard()
+        .no_dpki()
+        .build_conductor()
+        .await;
+    let app = conductor.setup_app("app", [&dna_1, &dna_2]).await.unwrap();
+    let (cell_1, cell_2) = app.into_tuple();
+    let zome = cell_2.zome(SweetInlineZomes::COORDINATOR);
+    let other_agent = SweetAgents::one(conductor.keystore()).await;
+    let payload = (other_agent, cell_1.cell_id().clone());
+
+    let (nonce, expires_at) = holochain_nonce::fresh_nonce(Timestamp::now()).unwrap();
+    let call = ZomeCall::try_from_unsigned_zome_call(
+        conductor.keystore(),
+        ZomeCallUnsigned {
+            cell_id: cell_2.cell_id().clone(),
+            zome_name: zome.name().clone(),
+            fn_name: "signal_and_bridge".into(),
+            cap_secret: None,
+            provenance: cell_2.agent_pubkey().clone(),
+            payload: ExternIO::encode(payload.clone()).unwrap(),
+            nonce,
+            expires_at,
+        },
+    )
+    .await
+    .unwrap();
+    let outcome = conductor
+        .raw_handle()
+        .call_zome_dry_run(call)
+        .await
+        .unwrap();
+
+    // - Neither the remote signal nor the call into the other cell was made.
+    let response = assert_matches!(outcome.result, Ok(ZomeCallResponse::Ok(response)) => response);
+    assert_eq!((true, true), response.decode::<(bool, bool)>().unwrap());
+    assert_eq!(0, touched.load(Ordering::SeqCst));
+
+    // - Whereas the same call made for real reaches both.
+    let refused: (bool, bool) = conductor.call(&zome, "signal_and_bridge", payload).await;
+    assert_eq!((false, false), refused);
+    assert_eq!(1, touched.load(Ordering::SeqCst));
+}
+
 // NB: currently the pre-genesis and post-genesis handling of panics is the same.
 //   If we implement [ B-04188 ], then this test will be made more possible.
 //   Otherwise, we have to devise a way to discover whether a panic happened
@@ -1221,6 +1348,8 @@ async fn test_deferred_memproof_provisioning() {
             network_seed: None,
             ignore_genesis_failure: false,
             allow_throwaway_random_agent_key: true,
+            network_profile: None,
+            client_only: false,
         })
         .await
         .unwrap();
@@ -1310,6 +1439,15 @@ async fn test_deferred_memproof_provisioning() {
     let app_info = conductor.get_app_info(&app_id).await.unwrap().unwrap();
     assert_eq!(app_info.status, AppInfoStatus::Running);
 
+    //- Memproofs can't be provided again once the app is running
+    let r = conductor
+        .clone()
+        .provide_memproofs(&app_id, MemproofMap::new())
+        .await;
+    assert_matches!(r, Err(ConductorError::AppStatusError(_)));
+    let app_info = conductor.get_app_info(&app_id).await.unwrap().unwrap();
+    assert_eq!(app_info.status, AppInfoStatus::Running);
+
     //- And now we can make a zome call successfully
     let _: String = conductor.call(&cell.zome("foo"), "foo", ()).await;
 
@@ -1350,6 +1488,8 @@ async fn test_deferred_memproof_provisioning_uninstall() {
             network_seed: None,
             ignore_genesis_failure: false,
             allow_throwaway_random_agent_key: true,
+            network_profile: None,
+            client_only: false,
         })
         .await
         .unwrap();
@@ -1363,6 +1503,101 @@ async fn test_deferred_memproof_provisioning_uninstall() {
     assert_eq!(conductor.list_apps(None).await.unwrap().len(), 0);
 }
 
+/// A memproof which fails genesis can be provided again without reinstalling the app
+#[tokio::test(flavor = "multi_thread")]
+async fn test_deferred_memproof_retry_after_genesis_failure() {
+    holochain_trace::test_run();
+    let zome = InlineZomeSet::new_unique_single("integrity", "custom", Vec::new(), 0).function(
+        "integrity",
+        "genesis_self_check",
+        |_api, data: GenesisSelfCheckData| match data.membrane_proof {
+            Some(proof) if proof.bytes().as_slice() == [1] => Ok(GenesisSelfCheckResult::Valid),
+            _ => Ok(GenesisSelfCheckResult::Invalid("bad memproof".into())),
+        },
+    );
+    let (dna, _, _) = SweetDnaFile::unique_from_inline_zomes(zome).await;
+    let conductor = SweetConductor::from_standard_config().await;
+    let app_id = "app-id".to_string();
+    let role_name = "role".to_string();
+    let bundle = app_bundle_from_dnas(&[(role_name.clone(), dna)], true, None).await;
+
+    conductor
+        .clone()
+        .install_app_bundle(InstallAppPayload {
+            source: AppBundleSource::Bundle(bundle),
+            agent_key: None,
+            installed_app_id: Some(app_id.clone()),
+            membrane_proofs: Default::default(),
+            existing_cells: Default::default(),
+            network_seed: None,
+            ignore_genesis_failure: false,
+            allow_throwaway_random_agent_key: true,
+            network_profile: None,
+            client_only: false,
+        })
+        .await
+        .unwrap();
+
+    let progress = conductor.get_memproof_progress(&app_id).await.unwrap();
+    assert_eq!(0, progress.attempts);
+    assert_eq!(
+        Some(&RoleGenesisStatus::AwaitingMemproof),
+        progress.roles.get(&role_name)
+    );
+
+    let memproof = |byte: u8| -> MemproofMap {
+        [(
+            role_name.clone(),
+            Arc::new(SerializedBytes::from(UnsafeBytes::from(vec![byte]))),
+        )]
+        .into_iter()
+        .collect()
+    };
+
+    //- A bad memproof is rejected, with the reason reported for its role
+    let err = conductor
+        .clone()
+        .provide_memproofs(&app_id, memproof(0))
+        .await
+        .unwrap_err();
+    let ConductorError::MemproofsRejected(progress) = err else {
+        panic!("expected the memproofs to be rejected, got {:?}", err);
+    };
+    assert_eq!(1, progress.attempts);
+    assert_matches!(
+        progress.roles.get(&role_name),
+        Some(RoleGenesisStatus::Failed { reason }) if reason.contains("bad memproof")
+    );
+    assert_eq!(
+        progress,
+        conductor.get_memproof_progress(&app_id).await.unwrap()
+    );
+
+    //- The app is still awaiting memproofs
+    let app_info = conductor.get_app_info(&app_id).await.unwrap().unwrap();
+    assert_eq!(app_info.status, AppInfoStatus::AwaitingMemproofs);
+
+    //- A corrected memproof completes genesis
+    conductor
+        .clone()
+        .provide_memproofs(&app_id, memproof(1))
+        .await
+        .unwrap();
+    let progress = conductor.get_memproof_progress(&app_id).await.unwrap();
+    assert_eq!(2, progress.attempts);
+    assert_eq!(
+        Some(&RoleGenesisStatus::Complete),
+        progress.roles.get(&role_name)
+    );
+    let app_info = conductor.get_app_info(&app_id).await.unwrap().unwrap();
+    assert_eq!(
+        app_info.status,
+        AppInfoStatus::Disabled {
+            reason: DisabledAppReason::NotStartedAfterProvidingMemproofs
+        }
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn test_list_apps_sorted_consistently() {
     holochain_trace::test_run();
@@ -1460,3 +1695,280 @@ async fn test_app_info_cells_sorted_consistently() {
         ]
     );
 }
+
+/// With a signing policy, only bundles signed by a trusted publisher can be installed
+#[tokio::test(flavor = "multi_thread")]
+async fn test_install_app_bundle_signing_policy() {
+    holochain_trace::test_run();
+    let keystore = holochain_keystore::spawn_test_keystore().await.unwrap();
+    let publisher = AgentPubKey::new_random(&keystore).await.unwrap();
+    let other = AgentPubKey::new_random(&keystore).await.unwrap();
+
+    let mut config = SweetConductorConfig::standard();
+    config.bundle_signing.trusted_publishers = vec![publisher.clone()];
+    config.bundle_signing.require_trusted_signature = true;
+    let conductor = SweetConductor::from_config(config).await;
+
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Foo]).await;
+    let bundle = || app_bundle_from_dnas(&[("role".to_string(), dna.clone())], false, None);
+    let sign = |bundle: AppBundle, signer: AgentPubKey| {
+        let keystore = keystore.clone();
+        async move {
+            let signature = signer
+                .sign_raw(&keystore, bundle.signing_bytes().unwrap().into())
+                .await
+                .unwrap();
+            AppBundle::from(
+                bundle
+                    .into_inner()
+                    .with_signature(mr_bundle::BundleSignature {
+                        signer: signer.get_raw_39().to_vec(),
+                        signature: signature.0.to_vec(),
+                    }),
+            )
+        }
+    };
+    let install = |bundle: AppBundle, app_id: &str| {
+        conductor.clone().install_app_bundle(InstallAppPayload {
+            source: AppBundleSource::Bundle(bundle),
+            agent_key: None,
+            installed_app_id: Some(app_id.to_string()),
+            membrane_proofs: Default::default(),
+            existing_cells: Default::default(),
+            network_seed: None,
+            ignore_genesis_failure: false,
+            allow_throwaway_random_agent_key: true,
+            network_profile: None,
+            client_only: false,
+        })
+    };
+
+    assert_matches!(
+        install(bundle().await, "unsigned").await,
+        Err(ConductorError::AppBundleError(
+            AppBundleError::UntrustedPublisher(_)
+        ))
+    );
+    assert_matches!(
+        install(sign(bundle().await, other).await, "untrusted").await,
+        Err(ConductorError::AppBundleError(
+            AppBundleError::UntrustedPublisher(_)
+        ))
+    );
+
+    // A trusted signature no longer matches once the bundle is altered.
+    let signed = sign(bundle().await, publisher.clone()).await;
+    let signature = signed.signature().unwrap().clone();
+    let mut manifest = signed.manifest().clone();
+    manifest.set_network_seed("tampered".to_string());
+    let tampered = AppBundle::from(
+        signed
+            .into_inner()
+            .update_manifest(manifest)
+            .unwrap()
+            .with_signature(signature),
+    );
+    assert_matches!(
+        install(tampered, "tampered").await,
+        Err(ConductorError::AppBundleError(
+            AppBundleError::InvalidSignature(_)
+        ))
+    );
+
+    install(sign(bundle().await, publisher).await, "trusted")
+        .await
+        .unwrap();
+}
+
+/// A conductor can run lair embedded in the process, either in memory or
+/// with the store in its data root
+#[tokio::test(flavor = "multi_thread")]
+async fn test_embedded_lair_keystore() {
+    holochain_trace::test_run();
+    for in_memory in [true, false] {
+        let db_dir = holochain_state::test_utils::test_db_dir();
+        let mut config: ConductorConfig = SweetConductorConfig::standard().no_dpki().into();
+        config.data_root_path = Some(db_dir.path().to_path_buf().into());
+        config.keystore = KeystoreConfig::LairEmbedded {
+            lair_root: None,
+            in_memory,
+        };
+        let conductor = Conductor::builder()
+            .config(config)
+            .passphrase(Some(sodoken::BufRead::new_no_lock(b"passphrase")))
+            .build()
+            .await
+            .unwrap();
+
+        conductor
+            .keystore()
+            .new_sign_keypair_random()
+            .await
+            .unwrap();
+        assert_eq!(
+            db_dir
+                .path()
+                .join("ks")
+                .join("lair-keystore-config.yaml")
+                .exists(),
+            !in_memory
+        );
+
+        conductor.shutdown().await.unwrap().unwrap();
+    }
+}
+
+/// Keys derived from the device seed are listed with their derivation path and the apps using them,
+/// and are still listed after the apps using them are uninstalled
+#[tokio::test(flavor = "multi_thread")]
+async fn test_list_agent_keys() {
+    holochain_trace::test_run();
+    let mut conductor =
+        SweetConductor::from_config(SweetConductorConfig::standard().no_dpki()).await;
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Foo]).await;
+
+    let app1 = conductor.setup_app("app1", [&dna]).await.unwrap();
+    let app2 = conductor.setup_app("app2", [&dna]).await.unwrap();
+    let random_agent = SweetAgents::one(conductor.keystore()).await;
+    let app3 = conductor
+        .setup_app_for_agent("app3", random_agent.clone(), [&dna])
+        .await
+        .unwrap();
+
+    let keys = conductor.raw_handle().list_agent_keys().await.unwrap();
+    assert_eq!(keys.len(), 3);
+
+    let device_seed_lair_tag = conductor.get_config().device_seed_lair_tag.clone().unwrap();
+    for (key, app, app_index) in [(&keys[0], &app1, 0), (&keys[1], &app2, 1)] {
+        assert_eq!(&key.agent_key, app.agent());
+        assert_eq!(
+            key.apps,
+            vec![(
+                app.installed_app_id().clone(),
+                vec![app.cells()[0].cell_id().clone()]
+            )]
+        );
+        assert_eq!(
+            key.derivation,
+            Some(AgentKeyDerivation {
+                device_seed_lair_tag: device_seed_lair_tag.clone(),
+                derivation_path: vec![app_index, 0],
+                lair_tag: format!("{device_seed_lair_tag}.{app_index}.0"),
+            })
+        );
+    }
+    assert_eq!(keys[2].agent_key, random_agent);
+    assert_eq!(keys[2].apps[0].0, *app3.installed_app_id());
+    assert_eq!(keys[2].derivation, None);
+
+    conductor
+        .raw_handle()
+        .uninstall_app(&"app1".to_string(), false)
+        .await
+        .unwrap();
+    let keys = conductor.raw_handle().list_agent_keys().await.unwrap();
+    assert_eq!(keys.len(), 3);
+    assert_eq!(&keys[0].agent_key, app1.agent());
+    assert!(keys[0].apps.is_empty());
+    assert!(keys[0].derivation.is_some());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn list_apps_by_dna_and_page() {
+    holochain_trace::test_run();
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let dna1 = SweetDnaFile::unique_empty().await;
+    let dna2 = SweetDnaFile::unique_empty().await;
+    conductor.setup_app("app1", [&dna1]).await.unwrap();
+    conductor.setup_app("app2", [&dna1, &dna2]).await.unwrap();
+    conductor
+        .disable_app("app2".to_string(), DisabledAppReason::User)
+        .await
+        .unwrap();
+
+    let app_ids = |apps: Vec<AppInfo>| -> Vec<InstalledAppId> {
+        apps.into_iter().map(|app| app.installed_app_id).collect()
+    };
+    assert_eq!(
+        vec!["app2".to_string(), "app1".to_string()],
+        app_ids(
+            conductor
+                .find_apps(None, Some(dna1.dna_hash()), None)
+                .await
+                .unwrap()
+        )
+    );
+    assert_eq!(
+        vec!["app2".to_string()],
+        app_ids(
+            conductor
+                .find_apps(None, Some(dna2.dna_hash()), None)
+                .await
+                .unwrap()
+        )
+    );
+    let page = Page {
+        offset: 1,
+        limit: 5,
+    };
+    assert_eq!(
+        vec!["app1".to_string()],
+        app_ids(conductor.find_apps(None, None, Some(page)).await.unwrap())
+    );
+
+    let summaries = conductor
+        .list_app_summaries(Some(AppStatusFilter::Disabled), None, None)
+        .await
+        .unwrap();
+    assert_eq!(1, summaries.len());
+    assert_eq!("app2", summaries[0].installed_app_id);
+    assert_eq!(2, summaries[0].cell_count);
+    assert!(!summaries[0].enabled);
+    assert!(summaries[0].authored_data_size_on_disk > 0);
+    assert!(summaries[0].dna_data_size_on_disk > 0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn stale_peers_are_pruned_when_cells_rejoin() {
+    use holochain_p2p::{AgentPubKeyExt, DnaHashExt};
+    use kitsune_p2p::agent_store::AgentInfoSigned;
+    use kitsune_p2p::dht::arq::ArqSize;
+    use kitsune_p2p::{fixt::*, KitsuneAgent, KitsuneSignature};
+
+    holochain_trace::test_run();
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let (dna_file, _, _) =
+        SweetDnaFile::unique_from_inline_zomes(("zome", simple_crud_zome())).await;
+    let app = conductor.setup_app("app", [&dna_file]).await.unwrap();
+    let (cell,) = app.into_tuple();
+
+    let now = std::time::UNIX_EPOCH.elapsed().unwrap().as_millis() as u64;
+    let peer = |expires_at_ms: u64| {
+        AgentInfoSigned::sign(
+            dna_file.dna_hash().to_kitsune(),
+            Arc::new(fixt!(KitsuneAgent)),
+            ArqSize::from_half_len(u32::MAX / 2),
+            vec![],
+            expires_at_ms - 1000,
+            expires_at_ms,
+            |_| async move { Ok(Arc::new(fixt!(KitsuneSignature, Predictable))) },
+        )
+    };
+    let stale = peer(now - 1000).await.unwrap();
+    let live = peer(now + 1000 * 60 * 60).await.unwrap();
+    let db = conductor.spaces.p2p_agents_db(dna_file.dna_hash()).unwrap();
+    p2p_put_all(&db, [&stale, &live].into_iter()).await.unwrap();
+
+    conductor.shutdown().await;
+    conductor.startup().await;
+
+    // - The peer store was reloaded, without the peer which went stale.
+    let db = conductor.spaces.p2p_agents_db(dna_file.dna_hash()).unwrap();
+    assert!(db.p2p_get_agent(&stale.agent).await.unwrap().is_none());
+    assert!(db.p2p_get_agent(&live.agent).await.unwrap().is_some());
+    assert!(db
+        .p2p_get_agent(&cell.agent_pubkey().to_kitsune())
+        .await
+        .unwrap()
+        .is_some());
+}
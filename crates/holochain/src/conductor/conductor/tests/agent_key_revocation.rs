@@ -15,10 +15,15 @@ use rusqlite::Row;
 #[cfg(feature = "unstable-dpki")]
 use {
     crate::core::SysValidationError,
+    ::fixt::prelude::*,
     holochain_conductor_services::{DpkiServiceError, KeyRevocation, KeyState, RevokeKeyInput},
     holochain_keystore::AgentPubKeyExt,
+    holochain_state::mutations::insert_op_dht,
     holochain_types::deepkey_roundtrip_backward,
+    holochain_types::dht_op::{ChainOp, DhtOpHashed},
+    holochain_zome_types::action::Action,
     holochain_zome_types::dependencies::holochain_integrity_types::Signature,
+    holochain_zome_types::fixt::{CreateFixturator, SignatureFixturator},
     holochain_zome_types::timestamp::Timestamp,
     holochain_zome_types::validate::ValidationStatus,
     rusqlite::named_params,
@@ -165,6 +170,65 @@ mod single_conductor {
         assert_matches!(result, ConductorError::DpkiError(DpkiServiceError::DpkiAgentInvalid(invalid_key, _timestamp)) if invalid_key == agent_key);
     }
 
+    #[cfg(feature = "unstable-dpki")]
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ops_by_revoked_key_are_rechecked_and_gossip_with_agent_is_deprioritized() {
+        holochain_trace::test_run();
+        let TestCase {
+            conductor,
+            dna_file_1,
+            app,
+            agent_key,
+            ..
+        } = TestCase::dpki().await;
+
+        revoke_agent_key_in_dpki(&conductor, agent_key.clone()).await;
+
+        // An op by the revoked key which is waiting for sys validation.
+        let mut create = fixt!(Create);
+        create.author = agent_key.clone();
+        create.timestamp = Timestamp::now();
+        let op = DhtOpHashed::from_content_sync(ChainOp::RegisterAgentActivity(
+            fixt!(Signature),
+            Action::Create(create),
+        ));
+        let op_hash = op.as_hash().clone();
+        let dht_db = conductor.get_dht_db(dna_file_1.dna_hash()).unwrap();
+        dht_db.test_write(move |txn| insert_op_dht(txn, &op, None).unwrap());
+        assert!(!conductor.spaces.is_revoked_agent(&agent_key));
+
+        // The key is already revoked in DPKI, so this only deletes it from the source chains
+        // and rechecks the ops.
+        conductor
+            .clone()
+            .revoke_agent_key_for_app(agent_key.clone(), app.installed_app_id().clone())
+            .await
+            .unwrap();
+        assert!(conductor.spaces.is_revoked_agent(&agent_key));
+
+        tokio::time::timeout(std::time::Duration::from_secs(30), async {
+            loop {
+                let status: Option<ValidationStatus> = dht_db.test_read({
+                    let op_hash = op_hash.clone();
+                    move |txn| {
+                        txn.query_row(
+                            "SELECT validation_status FROM DhtOp WHERE hash = :hash",
+                            named_params! { ":hash": op_hash },
+                            |row| row.get(0),
+                        )
+                        .unwrap()
+                    }
+                });
+                if status == Some(ValidationStatus::Rejected) {
+                    break;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+            }
+        })
+        .await
+        .expect("op by the revoked key was not rejected");
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn revoke_agent_key_without_dpki() {
         let TestCase {
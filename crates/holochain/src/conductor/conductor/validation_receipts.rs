@@ -0,0 +1,139 @@
+use super::*;
+use holochain_conductor_api::OpMissingReceipts;
+
+impl Conductor {
+    /// How many validation receipts the ops of an action authored on a DNA need
+    /// before they stop being republished.
+    ///
+    /// The count set in the DNA manifest takes precedence, then the `required_validations`
+    /// of the entry def for app entries, then the conductor's default.
+    pub(crate) fn required_validation_receipts(
+        &self,
+        ribosome: &RealRibosome,
+        entry_type: Option<&EntryType>,
+    ) -> u8 {
+        if let Some(count) = ribosome.dna_def().required_validation_receipts {
+            return count;
+        }
+        let entry_def_count = match entry_type {
+            Some(EntryType::App(AppEntryDef {
+                zome_index,
+                entry_index,
+                ..
+            })) => ribosome
+                .get_integrity_zome(zome_index)
+                .and_then(|zome| {
+                    self.get_entry_def(&EntryDefBufferKey::new(zome.into_inner().1, *entry_index))
+                })
+                .map(|entry_def| u8::from(entry_def.required_validations)),
+            _ => None,
+        };
+        entry_def_count.unwrap_or_else(|| {
+            self.conductor_tuning_params()
+                .default_required_validation_receipts()
+        })
+    }
+
+    /// The ops authored by a cell which haven't received enough validation receipts yet,
+    /// oldest first.
+    pub async fn list_ops_missing_receipts(
+        &self,
+        cell_id: &CellId,
+    ) -> ConductorResult<Vec<OpMissingReceipts>> {
+        let ribosome = self.get_ribosome(cell_id.dna_hash())?;
+        let author = cell_id.agent_pubkey().clone();
+        let ops = self
+            .get_or_create_authored_db(cell_id.dna_hash(), author.clone())?
+            .read_async(move |txn| ops_awaiting_receipts(txn, &author))
+            .await?;
+
+        // Receipts are stored in the DHT database when they are received.
+        let op_hashes: Vec<DhtOpHash> = ops.iter().map(|op| op.op_hash.clone()).collect();
+        let receipt_counts = self
+            .get_or_create_dht_db(cell_id.dna_hash())?
+            .read_async(move |txn| {
+                op_hashes
+                    .iter()
+                    .map(|op_hash| count_valid(txn, op_hash))
+                    .collect::<DatabaseResult<Vec<_>>>()
+            })
+            .await?;
+
+        Ok(ops
+            .into_iter()
+            .zip(receipt_counts)
+            .map(|(op, receipt_count)| OpMissingReceipts {
+                required_receipt_count: self
+                    .required_validation_receipts(&ribosome, op.action.action().entry_type()),
+                action_hash: ActionHash::with_data_sync(op.action.action()),
+                authored_at: op.action.action().timestamp(),
+                op_hash: op.op_hash,
+                op_type: op.op_type,
+                last_published_at: op.last_publish_time,
+                receipt_count,
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::ribosome::real_ribosome::RealRibosome;
+    use crate::sweettest::*;
+    use crate::test_utils::inline_zomes::simple_create_read_zome;
+    use holochain_state::prelude::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn the_dna_sets_how_many_receipts_ops_need() {
+        holochain_trace::test_run();
+
+        let (dna_file, _, _) =
+            SweetDnaFile::unique_from_inline_zomes(("zome", simple_create_read_zome())).await;
+        let (dna, code) = dna_file.into_parts();
+        let mut dna_def = dna.into_content();
+        dna_def.required_validation_receipts = Some(2);
+        let dna_file = DnaFile::from_parts(DnaDefHashed::from_content_sync(dna_def), code);
+
+        // A single conductor can't receive receipts from anyone else.
+        let mut config = SweetConductorConfig::standard();
+        config
+            .tuning_params
+            .get_or_insert_with(Default::default)
+            .default_required_validation_receipts = Some(7);
+        let mut conductor = SweetConductor::from_config(config).await;
+        let app = conductor.setup_app("app", [&dna_file]).await.unwrap();
+        let (cell,) = app.into_tuple();
+
+        let action_hash: ActionHash = conductor.call(&cell.zome("zome"), "create", ()).await;
+
+        let ops = conductor
+            .raw_handle()
+            .list_ops_missing_receipts(cell.cell_id())
+            .await
+            .unwrap();
+        let entry_ops: Vec<_> = ops
+            .iter()
+            .filter(|op| op.action_hash == action_hash)
+            .collect();
+        assert!(!entry_ops.is_empty());
+        for op in entry_ops {
+            assert_eq!(0, op.receipt_count);
+            assert_eq!(2, op.required_receipt_count);
+        }
+
+        // Without a count in the DNA, ops which aren't for an app entry use the conductor's default.
+        let (dna, code) = dna_file.into_parts();
+        let mut dna_def = dna.into_content();
+        dna_def.required_validation_receipts = None;
+        let ribosome = RealRibosome::empty(DnaFile::from_parts(
+            DnaDefHashed::from_content_sync(dna_def),
+            code,
+        ));
+        assert_eq!(
+            7,
+            conductor
+                .raw_handle()
+                .required_validation_receipts(&ribosome, None)
+        );
+    }
+}
@@ -0,0 +1,169 @@
+//! Sharing the zome calls which may run at once fairly between apps, so that an app
+//! which makes many calls can't keep the calls of other apps waiting behind its own.
+
+use holochain_types::app::InstalledAppId;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use tokio::sync::oneshot;
+
+/// Lets at most `limit` zome calls run at once. The calls which are waiting to run
+/// are queued by app, and the apps take turns, so that the calls of an app only wait
+/// behind one call of each other app which is waiting.
+pub(crate) struct ZomeCallScheduler {
+    limit: usize,
+    state: parking_lot::Mutex<SchedulerState>,
+}
+
+#[derive(Default)]
+struct SchedulerState {
+    /// How many zome calls are running.
+    running: usize,
+    /// The zome calls which are waiting to run, by app, with the app whose turn is
+    /// next at the front.
+    waiting: VecDeque<(InstalledAppId, VecDeque<oneshot::Sender<ZomeCallPermit>>)>,
+}
+
+impl ZomeCallScheduler {
+    /// Let at most `limit` zome calls run at once, or any number of them with 0.
+    pub(crate) fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            state: Default::default(),
+        }
+    }
+
+    /// Wait for the turn of a zome call of an app, which runs until the returned permit
+    /// is dropped. If there is no limit, no permit is needed.
+    pub(crate) async fn enter(self: &Arc<Self>, app_id: &InstalledAppId) -> Option<ZomeCallPermit> {
+        if self.limit == 0 {
+            return None;
+        }
+        let rx = {
+            let mut state = self.state.lock();
+            if state.running < self.limit && state.waiting.is_empty() {
+                state.running += 1;
+                return Some(ZomeCallPermit(Some(self.clone())));
+            }
+            let (tx, rx) = oneshot::channel();
+            match state.waiting.iter_mut().find(|(id, _)| id == app_id) {
+                Some((_, calls)) => calls.push_back(tx),
+                None => state
+                    .waiting
+                    .push_back((app_id.clone(), VecDeque::from([tx]))),
+            }
+            rx
+        };
+        // The permit is only dropped unsent along with the scheduler.
+        rx.await.ok()
+    }
+
+    /// The number of zome calls which are waiting for their turn.
+    #[cfg(test)]
+    fn waiting(&self) -> usize {
+        self.state
+            .lock()
+            .waiting
+            .iter()
+            .map(|(_, calls)| calls.len())
+            .sum()
+    }
+
+    /// Hand the slot of a zome call which finished to the next call of the app whose
+    /// turn it is, or free it if no call is waiting.
+    fn release(self: &Arc<Self>) {
+        let mut state = self.state.lock();
+        while let Some((app_id, mut calls)) = state.waiting.pop_front() {
+            let next = calls.pop_front();
+            if !calls.is_empty() {
+                state.waiting.push_back((app_id, calls));
+            }
+            if let Some(tx) = next {
+                match tx.send(ZomeCallPermit(Some(self.clone()))) {
+                    Ok(()) => return,
+                    // The call stopped waiting, so the slot goes to the next one.
+                    Err(mut permit) => {
+                        permit.0.take();
+                    }
+                }
+            }
+        }
+        state.running -= 1;
+    }
+}
+
+/// The turn of a zome call, which lasts until this is dropped.
+pub(crate) struct ZomeCallPermit(Option<Arc<ZomeCallScheduler>>);
+
+impl Drop for ZomeCallPermit {
+    fn drop(&mut self) {
+        if let Some(scheduler) = self.0.take() {
+            scheduler.release();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn apps_take_turns() {
+        let scheduler = Arc::new(ZomeCallScheduler::new(1));
+        let chatty: InstalledAppId = "chatty".into();
+        let quiet: InstalledAppId = "quiet".into();
+        let running = scheduler.enter(&chatty).await.unwrap();
+
+        let (order_tx, mut order_rx) = tokio::sync::mpsc::unbounded_channel();
+        let mut calls = Vec::new();
+        for app_id in [&chatty, &chatty, &chatty, &quiet] {
+            let app_id = app_id.clone();
+            let order_tx = order_tx.clone();
+            calls.push(tokio::spawn({
+                let scheduler = scheduler.clone();
+                async move {
+                    let _permit = scheduler.enter(&app_id).await;
+                    order_tx.send(app_id).unwrap();
+                }
+            }));
+            // - Queue the calls in order.
+            while scheduler.waiting() < calls.len() {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        }
+        drop(running);
+        for call in calls {
+            call.await.unwrap();
+        }
+        drop(order_tx);
+
+        let mut order = Vec::new();
+        while let Some(app_id) = order_rx.recv().await {
+            order.push(app_id);
+        }
+        // - The quiet app's call runs after one chatty call, rather than after all of them.
+        assert_eq!(vec![chatty.clone(), quiet, chatty.clone(), chatty], order);
+    }
+
+    #[tokio::test]
+    async fn calls_which_stop_waiting_give_up_their_turn() {
+        let scheduler = Arc::new(ZomeCallScheduler::new(1));
+        let app_id: InstalledAppId = "app".into();
+        let running = scheduler.enter(&app_id).await.unwrap();
+
+        assert!(
+            tokio::time::timeout(Duration::from_millis(10), scheduler.enter(&app_id))
+                .await
+                .is_err()
+        );
+        drop(running);
+        let _permit = tokio::time::timeout(Duration::from_secs(1), scheduler.enter(&app_id))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(0, scheduler.waiting());
+
+        let unlimited = Arc::new(ZomeCallScheduler::new(0));
+        assert!(unlimited.enter(&app_id).await.is_none());
+    }
+}
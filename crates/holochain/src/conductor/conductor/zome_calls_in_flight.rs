@@ -0,0 +1,80 @@
+//! The zome calls which are in progress, so that the conductor can let them
+//! complete before it shuts down.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use tokio::sync::Notify;
+
+/// Counts the zome calls which are in progress.
+#[derive(Default)]
+pub(crate) struct ZomeCallsInFlight {
+    count: AtomicUsize,
+    idle: Notify,
+}
+
+impl ZomeCallsInFlight {
+    /// Count a zome call as in progress until the returned guard is dropped.
+    pub(crate) fn enter(self: &Arc<Self>) -> ZomeCallInFlight {
+        self.count.fetch_add(1, Ordering::AcqRel);
+        ZomeCallInFlight(self.clone())
+    }
+
+    /// The number of zome calls in progress.
+    pub(crate) fn count(&self) -> usize {
+        self.count.load(Ordering::Acquire)
+    }
+
+    /// Wait until no zome calls are in progress.
+    pub(crate) async fn until_idle(&self) {
+        loop {
+            // Register for the notification before checking the count, so that
+            // it can't be missed.
+            let idle = self.idle.notified();
+            if self.count() == 0 {
+                return;
+            }
+            idle.await;
+        }
+    }
+}
+
+/// A zome call which is in progress.
+pub(crate) struct ZomeCallInFlight(Arc<ZomeCallsInFlight>);
+
+impl Drop for ZomeCallInFlight {
+    fn drop(&mut self) {
+        if self.0.count.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.0.idle.notify_waiters();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn wait_for_zome_calls_to_complete() {
+        let in_flight = Arc::new(ZomeCallsInFlight::default());
+        in_flight.until_idle().await;
+
+        let first = in_flight.enter();
+        let second = in_flight.enter();
+        assert_eq!(2, in_flight.count());
+
+        let waiting = tokio::spawn({
+            let in_flight = in_flight.clone();
+            async move { in_flight.until_idle().await }
+        });
+        drop(first);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        assert!(!waiting.is_finished());
+
+        drop(second);
+        tokio::time::timeout(Duration::from_secs(1), waiting)
+            .await
+            .unwrap()
+            .unwrap();
+    }
+}
@@ -80,6 +80,9 @@ pub enum ConductorError {
     #[error("Attempted to add two app interfaces with the same id: {0:?}")]
     AppInterfaceIdCollision(AppInterfaceId),
 
+    #[error("No client is connected to an app interface with connection id {0}")]
+    AppInterfaceConnectionNotFound(holochain_conductor_api::AppInterfaceConnectionId),
+
     // Box is to avoid cycle in error definition
     #[error(transparent)]
     InterfaceError(#[from] Box<InterfaceError>),
@@ -87,6 +90,9 @@ pub enum ConductorError {
     #[error("Failed to run genesis on the following cells in the app: {errors:?}")]
     GenesisFailed { errors: Vec<(CellId, CellError)> },
 
+    #[error("Genesis failed with the provided memproofs: {0:?}")]
+    MemproofsRejected(holochain_conductor_api::MemproofProgress),
+
     #[error(transparent)]
     SerializedBytesError(#[from] holochain_serialized_bytes::SerializedBytesError),
 
@@ -156,6 +162,18 @@ pub enum ConductorError {
 
     #[error("A required trigger is missing: {0}")]
     MissingTrigger(String),
+
+    #[error("The source chain can't be rolled back: {0}")]
+    ChainRollbackRefused(String),
+
+    #[error("The app of the calling cell has no cell of DNA {0}, so it can't reach the network of that DNA")]
+    RemoteCellUnreachable(DnaHash),
+
+    #[error("{0} isn't an action which the agent of the cell {1:?} authored")]
+    ActionNotAuthored(ActionHash, CellId),
+
+    #[error("A dry-run zome call can't {0}, since that would reach beyond its own cell")]
+    DryRunRefused(&'static str),
 }
 
 impl ConductorError {
@@ -3,6 +3,10 @@
 
 use super::error::InterfaceResult;
 use crate::conductor::conductor::app_broadcast::AppBroadcast;
+use crate::conductor::conductor::app_connections::{
+    AppConnectionHandle, AppConnections, NewAppConnection,
+};
+use crate::conductor::conductor::SignalReceiver;
 use crate::conductor::manager::TaskManagerClient;
 use holochain_serialized_bytes::SerializedBytes;
 use holochain_types::signal::Signal;
@@ -15,13 +19,14 @@ use std::net::{Ipv4Addr, Ipv6Addr, SocketAddrV4, SocketAddrV6};
 
 use crate::conductor::api::{AdminInterfaceApi, AppAuthentication, AppInterfaceApi};
 use holochain_conductor_api::{
-    AdminRequest, AdminResponse, AppAuthenticationRequest, AppRequest, AppResponse,
+    AdminRequest, AdminResponse, AppAuthenticationRequest, AppAuthenticationTokenScope, AppRequest,
+    AppResponse, ExternalApiWireError,
 };
 use holochain_types::app::InstalledAppId;
 use holochain_types::websocket::AllowedOrigins;
 use std::sync::Arc;
 use tokio::pin;
-use tokio::sync::broadcast;
+use tokio::task::AbortHandle;
 use tokio::task::JoinHandle;
 use tracing::*;
 
@@ -34,15 +39,25 @@ const CONCURRENCY_COUNT: usize = 128;
 /// The maximum number of connections allowed to the admin interface
 pub const MAX_CONNECTIONS: usize = 400;
 
+/// The config for an interface's WebsocketListener.
+fn listener_config(allowed_origins: AllowedOrigins, max_message_size: usize) -> WebsocketConfig {
+    let mut config = WebsocketConfig::LISTENER_DEFAULT;
+    config.allowed_origins = Some(allowed_origins);
+    // Messages are sent as a single frame, so the frame size must match.
+    config.max_message_size = max_message_size;
+    config.max_frame_size = max_message_size;
+    config
+}
+
 /// Create a WebsocketListener to be used in interfaces
 pub async fn spawn_websocket_listener(
     port: u16,
     allowed_origins: AllowedOrigins,
+    max_message_size: usize,
 ) -> InterfaceResult<WebsocketListener> {
     trace!("Initializing Admin interface");
 
-    let mut config = WebsocketConfig::LISTENER_DEFAULT;
-    config.allowed_origins = Some(allowed_origins);
+    let config = listener_config(allowed_origins, max_message_size);
 
     let listener = WebsocketListener::dual_bind(
         Arc::new(config),
@@ -99,8 +114,13 @@ pub fn spawn_admin_interface_tasks(
                             continue;
                         };
                         debug!("Accepting new connection with number of existing connections {}", conn_count);
+                        let connection = format!(
+                            "{} ({})",
+                            rx_from_iface.peer_addr(),
+                            rx_from_iface.origin().unwrap_or("no origin")
+                        );
                         task_list.0.lock().push(tokio::task::spawn(recv_incoming_admin_msgs(
-                            api.clone(),
+                            api.clone().with_connection(connection),
                             rx_from_iface,
                         )));
                     }
@@ -114,19 +134,21 @@ pub fn spawn_admin_interface_tasks(
 }
 
 /// Create an App Interface, which includes the ability to receive signals
-/// from Cells via a broadcast channel
-pub async fn spawn_app_interface_task(
+/// from Cells, with a queue of signals for each connection
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn spawn_app_interface_task(
     tm: TaskManagerClient,
     port: u16,
     allowed_origins: AllowedOrigins,
     installed_app_id: Option<InstalledAppId>,
     api: AppInterfaceApi,
     app_broadcast: AppBroadcast,
+    app_connections: AppConnections,
+    max_message_size: usize,
 ) -> InterfaceResult<u16> {
     trace!("Initializing App interface");
 
-    let mut config = WebsocketConfig::LISTENER_DEFAULT;
-    config.allowed_origins = Some(allowed_origins);
+    let config = listener_config(allowed_origins, max_message_size);
 
     let listener = WebsocketListener::dual_bind(
         Arc::new(config),
@@ -150,6 +172,7 @@ pub async fn spawn_app_interface_task(
                             api.clone(),
                             rx_from_iface,
                             app_broadcast.clone(),
+                            app_connections.clone(),
                             tx_to_iface,
                             installed_app_id.clone(),
                             port,
@@ -212,11 +235,13 @@ async fn recv_incoming_admin_msgs(api: AdminInterfaceApi, rx_from_iface: Websock
 /// connection is dropped.
 /// If the authentication succeeds, then message handling tasks are spawned to handle normal
 /// communication with the client.
+#[allow(clippy::too_many_arguments)]
 fn authenticate_incoming_app_connection(
     task_list: TaskListInner,
     api: AppInterfaceApi,
     mut rx_from_iface: WebsocketReceiver,
     app_broadcast: AppBroadcast,
+    app_connections: AppConnections,
     tx_to_iface: WebsocketSender,
     installed_app_id: Option<InstalledAppId>,
     port: u16,
@@ -268,24 +293,38 @@ fn authenticate_incoming_app_connection(
                         })
                         .await
                     {
-                        Ok(installed_app_id) => {
+                        Ok((installed_app_id, scope)) => {
                             // Once authentication passes we know which app this connection is for,
-                            // so we can subscribe to app signals now.
-                            let rx_from_cell = app_broadcast.subscribe(installed_app_id.clone());
+                            // so we can subscribe to app signals now, with a queue of its own.
+                            let rx_from_cell =
+                                app_broadcast.attach_interface(installed_app_id.clone());
 
-                            spawn_app_signals_handler(
+                            let connection = app_connections.register(NewAppConnection {
+                                port,
+                                installed_app_id: installed_app_id.clone(),
+                                origin: rx_from_iface.origin().map(ToString::to_string),
+                                peer_addr: rx_from_iface.peer_addr(),
+                                sender: tx_to_iface.clone(),
+                            });
+                            let connection_id = connection.id();
+
+                            let signals_task = spawn_app_signals_handler(
                                 task_list.clone(),
                                 rx_from_cell,
                                 tx_to_iface.clone(),
                                 port,
                                 installed_app_id.clone(),
                             );
-                            spawn_recv_incoming_app_msgs(
+                            app_connections.add_task(connection_id, signals_task);
+                            let recv_task = spawn_recv_incoming_app_msgs(
                                 task_list,
                                 api,
                                 rx_from_iface,
                                 installed_app_id,
+                                scope,
+                                connection,
                             );
+                            app_connections.add_task(connection_id, recv_task);
                         }
                         Err(e) => {
                             warn!("Connection to Holochain app port {port} failed to authenticate: {e}. Dropping connection.");
@@ -304,11 +343,11 @@ fn authenticate_incoming_app_connection(
 /// the connected client via `tx_to_iface`.
 fn spawn_app_signals_handler(
     task_list: TaskListInner,
-    rx_from_cell: broadcast::Receiver<Signal>,
+    rx_from_cell: SignalReceiver,
     tx_to_iface: WebsocketSender,
     port: u16,
     installed_app_id: InstalledAppId,
-) {
+) -> AbortHandle {
     use futures::stream::StreamExt;
 
     let rx_from_cell = futures::stream::unfold(rx_from_cell, move |mut rx_from_cell| {
@@ -318,7 +357,7 @@ fn spawn_app_signals_handler(
                 match rx_from_cell.recv().await {
                     // We missed some signals, but the channel is still open
                     Err(tokio::sync::broadcast::error::RecvError::Lagged(dropped)) => {
-                        warn!("Holochain app port {port} dropped {dropped} signals. The app '{installed_app_id}' is emitting signals too fast.");
+                        warn!("Holochain app port {port} dropped {dropped} signals. The app '{installed_app_id}' is emitting signals faster than the client takes them.");
                         continue;
                     }
                     Ok(item) => return Some((item, rx_from_cell)),
@@ -331,7 +370,7 @@ fn spawn_app_signals_handler(
         }
     });
 
-    task_list.lock().push(tokio::task::spawn(async move {
+    let task = tokio::task::spawn(async move {
         pin!(rx_from_cell);
         loop {
             if let Some(signal) = rx_from_cell.next().await {
@@ -351,18 +390,25 @@ fn spawn_app_signals_handler(
                 break;
             }
         }
-    }));
+    });
+    let abort_handle = task.abort_handle();
+    task_list.lock().push(task);
+    abort_handle
 }
 
 /// Starts a task that listens for messages coming from the external client on `rx_from_iface`
 /// and calls the provided `api` to handle them. Responses from the `api` are sent back to the
 /// client via `tx_to_iface`.
+///
+/// The `connection` stays registered until the client disconnects.
 fn spawn_recv_incoming_app_msgs(
     task_list: TaskListInner,
     api: AppInterfaceApi,
     rx_from_iface: WebsocketReceiver,
     installed_app_id: InstalledAppId,
-) {
+    scope: AppAuthenticationTokenScope,
+    connection: AppConnectionHandle,
+) -> AbortHandle {
     use futures::stream::StreamExt;
 
     trace!("CONNECTION: {}", rx_from_iface.peer_addr());
@@ -389,21 +435,27 @@ fn spawn_recv_incoming_app_msgs(
         });
 
     // TODO - metrics to indicate if we're getting overloaded here.
-    task_list
-        .lock()
-        .push(tokio::task::spawn(rx_from_iface.for_each_concurrent(
-            CONCURRENCY_COUNT,
-            move |msg| {
+    let task = tokio::task::spawn(async move {
+        rx_from_iface
+            .for_each_concurrent(CONCURRENCY_COUNT, |msg| {
                 let installed_app_id = installed_app_id.clone();
                 let api = api.clone();
+                let in_flight = connection.start_message();
                 async move {
-                    if let Err(err) = handle_incoming_app_message(msg, installed_app_id, api).await
+                    let _in_flight = in_flight;
+                    if let Err(err) =
+                        handle_incoming_app_message(msg, installed_app_id, scope, api).await
                     {
                         error!(?err, "error handling app websocket message");
                     }
                 }
-            },
-        )));
+            })
+            .await;
+        drop(connection);
+    });
+    let abort_handle = task.abort_handle();
+    task_list.lock().push(task);
+    abort_handle
 }
 
 /// Handles messages on admin interfaces
@@ -435,7 +487,11 @@ async fn handle_incoming_admin_message(
                 }
             }
             let result = Cnv(result);
-            respond.respond(result).await?;
+            respond
+                .respond_or(result, |e| {
+                    Cnv(AdminResponse::Error(ExternalApiWireError::internal(e)).try_into())
+                })
+                .await?;
             Ok(())
         }
     }
@@ -445,6 +501,7 @@ async fn handle_incoming_admin_message(
 async fn handle_incoming_app_message(
     ws_msg: ReceiveMessage<AppRequest>,
     installed_app_id: InstalledAppId,
+    scope: AppAuthenticationTokenScope,
     api: AppInterfaceApi,
 ) -> InterfaceResult<()> {
     match ws_msg {
@@ -458,7 +515,14 @@ async fn handle_incoming_app_message(
         }
         ReceiveMessage::Request(data, respond) => {
             use holochain_serialized_bytes::SerializedBytesError;
-            let result: AppResponse = api.handle_request(installed_app_id, Ok(data)).await?;
+            let result: AppResponse = if scope.allows(&data) {
+                api.handle_request(installed_app_id, Ok(data)).await?
+            } else {
+                AppResponse::Error(ExternalApiWireError::RequestOutOfScope(format!(
+                    "The token this connection authenticated with has the {:?} scope",
+                    scope
+                )))
+            };
             // Have to jump through some hoops, because our response type
             // only implements try_into, but the responder needs try_from.
             let result = result.try_into();
@@ -471,7 +535,11 @@ async fn handle_incoming_app_message(
                 }
             }
             let result = Cnv(result);
-            respond.respond(result).await?;
+            respond
+                .respond_or(result, |e| {
+                    Cnv(AppResponse::Error(ExternalApiWireError::internal(e)).try_into())
+                })
+                .await?;
             Ok(())
         }
     }
@@ -581,6 +649,8 @@ pub mod test {
             network_seed: None,
             ignore_genesis_failure: false,
             allow_throwaway_random_agent_key: true,
+            network_profile: None,
+            client_only: false,
         }));
         let response: AdminResponse = admin_tx.request(request).await.unwrap();
         let app_info = match response {
@@ -863,6 +933,13 @@ pub mod test {
                         total_network_peers: 1,
                         bytes_since_last_time_queried: 1838,
                         completed_rounds_since_last_time_queried: 0,
+                        region_redundancy: redundancy_regions()
+                            .map(|(start_loc, end_loc)| RegionRedundancy {
+                                start_loc,
+                                end_loc,
+                                estimated_redundancy: 1,
+                            })
+                            .collect(),
                     }]
                 )
             }
@@ -1230,6 +1307,10 @@ pub mod test {
                     .map(|z| z.coordinator.into_inner())
                     .collect(),
                 lineage: Default::default(),
+                size_limits: Default::default(),
+                required_validation_receipts: None,
+                interactive_types: Default::default(),
+                fulltext_entry_types: Default::default(),
             },
             zomes.into_iter().flat_map(Vec::<DnaWasm>::from),
         )
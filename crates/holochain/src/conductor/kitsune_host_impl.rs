@@ -4,9 +4,10 @@ mod query_region_op_hashes;
 mod query_region_set;
 mod query_size_limited_regions;
 use holochain_conductor_api::conductor::ConductorConfig;
-use kitsune_p2p_bin_data::KitsuneSpace;
+use kitsune_p2p_bin_data::{KitsuneAgent, KitsuneSignature, KitsuneSpace};
+use kitsune_p2p_bootstrap_client::DynBootstrapService;
 pub use query_region_op_hashes::query_region_op_hashes;
-pub use query_region_set::query_region_set;
+pub use query_region_set::{query_region_set, query_region_set_for_coords};
 pub use query_size_limited_regions::query_size_limited_regions;
 
 use std::sync::Arc;
@@ -16,11 +17,11 @@ use futures::FutureExt;
 use holo_hash::DnaHash;
 use holochain_p2p::{
     dht::{spacetime::Topology, ArqStrat},
-    DhtOpHashExt, DnaHashExt, FetchContextExt,
+    AgentPubKeyExt, DhtOpHashExt, DnaHashExt, FetchContextExt,
 };
 use holochain_sqlite::prelude::{AsP2pMetricStoreTxExt, AsP2pStateReadExt, AsP2pStateWriteExt};
 use holochain_types::{
-    prelude::{DhtOpHash, DnaError},
+    prelude::{AgentPubKey, DhtOpHash, DnaError, Signature},
     share::RwShare,
 };
 use holochain_zome_types::prelude::Timestamp;
@@ -44,6 +45,7 @@ pub struct KitsuneHostImpl {
     strat: ArqStrat,
     lair_tag: Option<Arc<str>>,
     lair_client: Option<lair_keystore_api::LairClient>,
+    bootstrap_service: Option<DynBootstrapService>,
 }
 
 /// Manual Debug implementation to skip non debuggable fields.
@@ -65,6 +67,7 @@ impl KitsuneHostImpl {
         strat: ArqStrat,
         lair_tag: Option<Arc<str>>,
         lair_client: Option<lair_keystore_api::LairClient>,
+        bootstrap_service: Option<DynBootstrapService>,
     ) -> Arc<Self> {
         Arc::new(Self {
             spaces,
@@ -73,6 +76,7 @@ impl KitsuneHostImpl {
             strat,
             lair_tag,
             lair_client,
+            bootstrap_service,
         })
     }
 }
@@ -288,6 +292,16 @@ impl KitsuneHost for KitsuneHostImpl {
         .into()
     }
 
+    fn is_overloaded(&self, space: &KitsuneSpace) -> bool {
+        self.spaces
+            .is_ingest_refused(&DnaHash::from_kitsune_raw(space.clone()))
+    }
+
+    fn is_deprioritized(&self, _space: &KitsuneSpace, agent: &KitsuneAgent) -> bool {
+        self.spaces
+            .is_revoked_agent(&AgentPubKey::from_kitsune_raw(agent.clone()))
+    }
+
     fn handle_op_hash_received(
         &self,
         _space: &KitsuneSpace,
@@ -304,6 +318,28 @@ impl KitsuneHost for KitsuneHostImpl {
     ) {
     }
 
+    fn verify_network_data(
+        &self,
+        agent: Arc<KitsuneAgent>,
+        data: Vec<u8>,
+        signature: Arc<KitsuneSignature>,
+    ) -> KitsuneHostResult<bool> {
+        async move {
+            let Ok(signature) = <[u8; 64]>::try_from(signature.0.as_slice()) else {
+                return Ok(false);
+            };
+            let agent = AgentPubKey::from_kitsune(&agent);
+            Ok(holochain_keystore::AgentPubKeyExt::verify_signature_raw(
+                &agent,
+                &Signature(signature),
+                data.into(),
+            )
+            .await?)
+        }
+        .boxed()
+        .into()
+    }
+
     fn lair_tag(&self) -> Option<Arc<str>> {
         self.lair_tag.clone()
     }
@@ -311,4 +347,8 @@ impl KitsuneHost for KitsuneHostImpl {
     fn lair_client(&self) -> Option<lair_keystore_api::LairClient> {
         self.lair_client.clone()
     }
+
+    fn bootstrap_service(&self) -> Option<DynBootstrapService> {
+        self.bootstrap_service.clone()
+    }
 }
@@ -1,6 +1,7 @@
 use holo_hash::DhtOpHash;
 use holochain_p2p::{dht::prelude::*, DhtOpHashExt};
 use holochain_sqlite::prelude::*;
+use holochain_state::expiry::expiry_cutoff;
 use kitsune_p2p::dependencies::kitsune_p2p_fetch::OpHashSized;
 use rusqlite::named_params;
 
@@ -25,6 +26,7 @@ pub async fn query_region_op_hashes(
                         ":storage_end_loc": x1,
                         ":timestamp_min": t0,
                         ":timestamp_max": t1,
+                        ":expiry_cutoff": expiry_cutoff(Timestamp::now()),
                     },
                     |row| {
                         let hash: DhtOpHash = row.get("hash")?;
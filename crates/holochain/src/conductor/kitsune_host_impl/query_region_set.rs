@@ -2,6 +2,7 @@ use std::sync::Arc;
 
 use holochain_p2p::dht::prelude::*;
 use holochain_sqlite::prelude::*;
+use holochain_state::expiry::expiry_cutoff;
 use rusqlite::named_params;
 
 use crate::conductor::error::ConductorResult;
@@ -22,7 +23,16 @@ pub async fn query_region_set(
 ) -> ConductorResult<RegionSetLtcs> {
     let times = TelescopingTimes::historical(&topology);
     let coords = RegionCoordSetLtcs::new(times, (*arq_set).clone());
+    query_region_set_for_coords(db, topology, coords).await
+}
 
+/// Query the data of each region of a set of region coords, e.g. the coords of a
+/// region set which a peer sent, so that the two sets line up region by region.
+pub async fn query_region_set_for_coords(
+    db: DbWrite<DbKindDht>,
+    topology: Topology,
+    coords: RegionCoordSetLtcs,
+) -> ConductorResult<RegionSetLtcs> {
     let region_set = db
         .read_async(move |txn| {
             let sql = holochain_sqlite::sql::sql_cell::FETCH_OP_REGION;
@@ -50,6 +60,7 @@ pub(super) fn query_region_data(
             ":storage_end_loc": x1,
             ":timestamp_min": t0,
             ":timestamp_max": t1,
+            ":expiry_cutoff": expiry_cutoff(Timestamp::now()),
         },
         |row| {
             let total_action_size: f64 = row.get("total_action_size")?;
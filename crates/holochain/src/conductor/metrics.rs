@@ -1,7 +1,12 @@
-use opentelemetry_api::{global::meter_with_version, metrics::*};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use holochain_types::prelude::CellId;
+use opentelemetry_api::{global::meter_with_version, metrics::*, KeyValue};
 
 pub type P2pEventDurationMetric = Histogram<f64>;
 pub type PostCommitDurationMetric = Histogram<f64>;
+pub type ZomeCallDurationMetric = Histogram<f64>;
 
 pub fn create_p2p_event_duration_metric() -> P2pEventDurationMetric {
     meter_with_version(
@@ -28,3 +33,111 @@ pub fn create_post_commit_duration_metric() -> P2pEventDurationMetric {
     .with_description("The time spent executing a post commit")
     .init()
 }
+
+pub fn create_zome_call_duration_metric(cell_id: &CellId) -> ZomeCallDurationMetric {
+    meter_with_version(
+        "hc.conductor",
+        None::<&'static str>,
+        None::<&'static str>,
+        Some(vec![
+            KeyValue::new("dna_hash", format!("{:?}", cell_id.dna_hash())),
+            KeyValue::new("agent", format!("{:?}", cell_id.agent_pubkey())),
+        ]),
+    )
+    .f64_histogram("hc.conductor.zome_call.duration")
+    .with_unit(Unit::new("s"))
+    .with_description("The time spent running a zome call")
+    .init()
+}
+
+pub type SlowOperationCountMetric = Counter<u64>;
+
+pub fn create_slow_operation_count_metric() -> SlowOperationCountMetric {
+    meter_with_version(
+        "hc.conductor",
+        None::<&'static str>,
+        None::<&'static str>,
+        Some(vec![]),
+    )
+    .u64_counter("hc.conductor.slow_operation.count")
+    .with_description(
+        "The number of zome calls and workflow runs which took longer than their threshold",
+    )
+    .init()
+}
+
+pub type WorkflowRuntimeWaitDurationMetric = Histogram<f64>;
+
+pub fn create_workflow_runtime_wait_duration_metric(
+    runtime: usize,
+) -> WorkflowRuntimeWaitDurationMetric {
+    meter_with_version(
+        "hc.conductor",
+        None::<&'static str>,
+        None::<&'static str>,
+        Some(vec![KeyValue::new("runtime", runtime as i64)]),
+    )
+    .f64_histogram("hc.conductor.workflow_runtime.wait.duration")
+    .with_unit(Unit::new("s"))
+    .with_description("The time a workflow run waited for a thread of its workflow runtime")
+    .init()
+}
+
+/// Report the number of workflow runs which are currently running or waiting on a workflow runtime.
+pub fn create_workflow_runtime_active_metric(runtime: usize) -> Arc<AtomicU64> {
+    let active = Arc::new(AtomicU64::new(0));
+    let meter = meter_with_version(
+        "hc.conductor",
+        None::<&'static str>,
+        None::<&'static str>,
+        Some(vec![KeyValue::new("runtime", runtime as i64)]),
+    );
+
+    let gauge = meter
+        .u64_observable_gauge("hc.conductor.workflow_runtime.active")
+        .with_description(
+            "The number of workflow runs which are running or waiting on a workflow runtime",
+        )
+        .init();
+
+    let observed_active = active.clone();
+    let registration_result = meter.register_callback(&[gauge.as_any()], move |observer| {
+        observer.observe_u64(&gauge, observed_active.load(Ordering::Relaxed), &[])
+    });
+    if let Err(e) = registration_result {
+        tracing::error!("Failed to register callback for metric: {:?}", e);
+    }
+    active
+}
+
+pub type RegionRedundancyMetric = Histogram<u64>;
+
+pub fn create_region_redundancy_metric() -> RegionRedundancyMetric {
+    meter_with_version(
+        "hc.conductor",
+        None::<&'static str>,
+        None::<&'static str>,
+        Some(vec![]),
+    )
+    .u64_histogram("hc.conductor.dht.region_redundancy")
+    .with_description(
+        "The estimated number of authorities of each region of the DHT which a local agent holds",
+    )
+    .init()
+}
+
+pub type RepublishedOpsMetric = Counter<u64>;
+
+pub fn create_republished_ops_metric() -> RepublishedOpsMetric {
+    meter_with_version(
+        "hc.conductor",
+        None::<&'static str>,
+        None::<&'static str>,
+        Some(vec![]),
+    )
+    .u64_counter("hc.conductor.publish.republished_ops")
+    .with_description(
+        "The number of authored ops which were published again because their authorities no longer held them",
+    )
+    .init()
+}
@@ -8,6 +8,7 @@ use holochain_p2p::dht::spacetime::Topology;
 use holochain_p2p::dht::PeerStrat;
 use holochain_p2p::dht::PeerView;
 use holochain_p2p::dht_arc::DhtArc;
+use holochain_p2p::dht_arc::DhtArcSet;
 use holochain_p2p::kitsune_p2p::agent_store::AgentInfoSigned;
 use holochain_p2p::AgentPubKeyExt;
 use holochain_sqlite::prelude::*;
@@ -267,6 +268,54 @@ pub async fn query_peer_density(
     Ok(strat.view(topology, arqs.as_slice()))
 }
 
+/// Estimate the redundancy of each region of the DHT which is held by any of `agents`,
+/// from the storage arcs of the unexpired agents in the peer store.
+///
+/// Returns no regions until one of `agents` is in the peer store.
+pub async fn query_region_redundancy(
+    env: DbRead<DbKindP2pAgents>,
+    kitsune_space: Arc<kitsune_p2p::KitsuneSpace>,
+    agents: &[AgentPubKey],
+) -> ConductorResult<Vec<RegionRedundancy>> {
+    let now = now();
+    let infos = env.p2p_list_agents().await?;
+    let mut held = DhtArcSet::new_empty();
+    let mut arcs = Vec::with_capacity(infos.len());
+    for info in infos {
+        if info.space != kitsune_space || is_expired(now, &info) {
+            continue;
+        }
+        let arc = DhtArcSet::from_interval(info.storage_arc().inner());
+        if agents.contains(&AgentPubKey::from_kitsune(&info.agent)) {
+            held = held.union(&arc);
+        }
+        arcs.push(arc);
+    }
+    Ok(estimate_region_redundancy(&held, &arcs))
+}
+
+/// The redundancy of each region which overlaps `held`, which is the number of `arcs`
+/// which cover the whole region.
+fn estimate_region_redundancy(held: &DhtArcSet, arcs: &[DhtArcSet]) -> Vec<RegionRedundancy> {
+    redundancy_regions()
+        .filter_map(|(start_loc, end_loc)| {
+            let region = DhtArcSet::from_bounds(start_loc.into(), end_loc.into());
+            if !held.overlap(&region) {
+                return None;
+            }
+            let estimated_redundancy = arcs
+                .iter()
+                .filter(|arc| arc.intersection(&region) == region)
+                .count() as u32;
+            Some(RegionRedundancy {
+                start_loc,
+                end_loc,
+                estimated_redundancy,
+            })
+        })
+        .collect()
+}
+
 fn now() -> u64 {
     std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
@@ -356,6 +405,30 @@ mod tests {
     use holochain_state::test_utils::test_p2p_agents_db;
     use kitsune_p2p_types::fixt::*;
 
+    #[test]
+    fn region_redundancy_counts_arcs_covering_held_regions() {
+        let regions: Vec<_> = redundancy_regions().collect();
+        let (first_start, first_end) = regions[0];
+        let (_, second_end) = regions[1];
+        // Only the first region is held.
+        let held = DhtArcSet::from_bounds(first_start.into(), (first_start + 1).into());
+        let arcs = vec![
+            DhtArcSet::new_full(),
+            DhtArcSet::from_bounds(first_start.into(), second_end.into()),
+            // Doesn't cover the whole of the first region.
+            DhtArcSet::from_bounds((first_start + 1).into(), first_end.into()),
+        ];
+
+        assert_eq!(
+            vec![RegionRedundancy {
+                start_loc: first_start,
+                end_loc: first_end,
+                estimated_redundancy: 2,
+            }],
+            estimate_region_redundancy(&held, &arcs)
+        );
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_store_agent_info_signed() {
         holochain_trace::test_run();
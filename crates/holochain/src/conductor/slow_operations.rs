@@ -0,0 +1,184 @@
+//! Keep a record of zome calls and workflow runs which take longer than the
+//! thresholds in the [`ConductorTuningParams`], so that the cause of a slow
+//! conductor can be found without attaching a profiler.
+
+use super::metrics::{create_slow_operation_count_metric, SlowOperationCountMetric};
+use holochain_conductor_api::conductor::ConductorTuningParams;
+use holochain_conductor_api::{SlowOperation, SlowOperationTarget};
+use holochain_types::prelude::*;
+use opentelemetry_api::KeyValue;
+use std::collections::VecDeque;
+use std::time::Duration;
+
+/// The most slow operations to keep.
+const MAX_SLOW_OPERATIONS: usize = 100;
+
+/// The most recent slow operations in the conductor.
+pub(crate) struct SlowOperationLog {
+    zome_call_threshold: Duration,
+    workflow_threshold: Duration,
+    recent: parking_lot::Mutex<VecDeque<SlowOperation>>,
+    count_metric: SlowOperationCountMetric,
+}
+
+impl SlowOperationLog {
+    pub(crate) fn new(tuning_params: &ConductorTuningParams) -> Self {
+        Self {
+            zome_call_threshold: tuning_params.slow_zome_call_threshold(),
+            workflow_threshold: tuning_params.slow_workflow_threshold(),
+            recent: Default::default(),
+            count_metric: create_slow_operation_count_metric(),
+        }
+    }
+
+    /// Record a zome call if it was slow.
+    pub(crate) fn zome_call(
+        &self,
+        cell_id: &CellId,
+        zome_name: &ZomeName,
+        fn_name: &FunctionName,
+        started_at: Timestamp,
+        duration: Duration,
+        db_wait: Duration,
+    ) {
+        if duration < self.zome_call_threshold {
+            return;
+        }
+        self.count_metric.add(
+            1,
+            &[
+                KeyValue::new("kind", "zome_call"),
+                KeyValue::new("dna_hash", cell_id.dna_hash().to_string()),
+                KeyValue::new("target", format!("{}/{}", zome_name, fn_name)),
+            ],
+        );
+        self.record(
+            SlowOperationTarget::ZomeCall {
+                cell_id: cell_id.clone(),
+                zome_name: zome_name.clone(),
+                fn_name: fn_name.clone(),
+            },
+            started_at,
+            duration,
+            db_wait,
+        );
+    }
+
+    /// Record a workflow run if it was slow.
+    pub(crate) fn workflow(
+        &self,
+        workflow: &str,
+        dna_hash: &DnaHash,
+        agent: Option<&AgentPubKey>,
+        started_at: Timestamp,
+        duration: Duration,
+        db_wait: Duration,
+    ) {
+        if duration < self.workflow_threshold {
+            return;
+        }
+        self.count_metric.add(
+            1,
+            &[
+                KeyValue::new("kind", "workflow"),
+                KeyValue::new("dna_hash", dna_hash.to_string()),
+                KeyValue::new("target", workflow.to_string()),
+            ],
+        );
+        self.record(
+            SlowOperationTarget::Workflow {
+                workflow: workflow.to_string(),
+                dna_hash: dna_hash.clone(),
+                agent: agent.cloned(),
+            },
+            started_at,
+            duration,
+            db_wait,
+        );
+    }
+
+    fn record(
+        &self,
+        target: SlowOperationTarget,
+        started_at: Timestamp,
+        duration: Duration,
+        db_wait: Duration,
+    ) {
+        tracing::warn!(?target, ?duration, ?db_wait, "Slow operation");
+        let mut recent = self.recent.lock();
+        recent.push_front(SlowOperation {
+            target,
+            started_at,
+            duration_micros: duration.as_micros() as u64,
+            db_wait_micros: db_wait.as_micros() as u64,
+        });
+        recent.truncate(MAX_SLOW_OPERATIONS);
+    }
+
+    /// The most recent slow operations, newest first.
+    pub(crate) fn recent(&self) -> Vec<SlowOperation> {
+        self.recent.lock().iter().cloned().collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_only_slow_operations() {
+        let log = SlowOperationLog::new(&ConductorTuningParams {
+            slow_zome_call_threshold: Some(Duration::from_millis(100)),
+            slow_workflow_threshold: Some(Duration::from_secs(1)),
+            ..ConductorTuningParams::new()
+        });
+        let cell_id = CellId::new(
+            DnaHash::from_raw_32(vec![1; 32]),
+            AgentPubKey::from_raw_32(vec![2; 32]),
+        );
+        let zome_call = |duration| {
+            log.zome_call(
+                &cell_id,
+                &"zome".into(),
+                &"fn".into(),
+                Timestamp::now(),
+                duration,
+                Duration::from_millis(5),
+            )
+        };
+
+        zome_call(Duration::from_millis(50));
+        log.workflow(
+            "sys_validation_consumer",
+            cell_id.dna_hash(),
+            None,
+            Timestamp::now(),
+            Duration::from_millis(500),
+            Duration::ZERO,
+        );
+        assert!(log.recent().is_empty());
+
+        zome_call(Duration::from_millis(150));
+        log.workflow(
+            "sys_validation_consumer",
+            cell_id.dna_hash(),
+            None,
+            Timestamp::now(),
+            Duration::from_secs(2),
+            Duration::ZERO,
+        );
+        let recent = log.recent();
+        assert_eq!(recent.len(), 2);
+        assert!(matches!(
+            recent[0].target,
+            SlowOperationTarget::Workflow { .. }
+        ));
+        assert_eq!(recent[1].duration_micros, 150_000);
+        assert_eq!(recent[1].db_wait_micros, 5_000);
+
+        for _ in 0..MAX_SLOW_OPERATIONS {
+            zome_call(Duration::from_secs(1));
+        }
+        assert_eq!(log.recent().len(), MAX_SLOW_OPERATIONS);
+    }
+}
@@ -3,8 +3,11 @@
 //! Multiple [`Cell`](crate::conductor::Cell)'s could share the same space.
 use std::{
     cell::Cell,
-    collections::{hash_map, HashMap},
-    sync::Arc,
+    collections::{hash_map, HashMap, HashSet},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
     time::Duration,
 };
 
@@ -14,6 +17,7 @@ use super::{
     p2p_agent_store::{self, P2pBatch},
 };
 use crate::conductor::{error::ConductorError, state::ConductorState};
+use crate::core::op_trace::record_op_event;
 use crate::core::workflow::countersigning_workflow::CountersigningWorkspace;
 use crate::core::{
     queue_consumer::QueueConsumerMap,
@@ -27,6 +31,7 @@ use crate::core::{
 use holo_hash::{AgentPubKey, DhtOpHash, DnaHash};
 use holochain_conductor_api::conductor::paths::DatabasesRootPath;
 use holochain_conductor_api::conductor::ConductorConfig;
+use holochain_conductor_api::OpTraceStage;
 use holochain_keystore::MetaLairClient;
 use holochain_p2p::AgentPubKeyExt;
 use holochain_p2p::DnaHashExt;
@@ -41,11 +46,13 @@ use holochain_sqlite::prelude::{
     PoolConfig, ReadAccess,
 };
 use holochain_state::{
+    expiry::expiry_cutoff,
     host_fn_workspace::SourceChainWorkspace,
     mutations,
     prelude::*,
     query::{map_sql_dht_op_common, StateQueryError},
 };
+use holochain_types::clock::Clock;
 use holochain_util::timed;
 use kitsune_p2p::event::{TimeWindow, TimeWindowInclusive};
 use kitsune_p2p_block::NodeId;
@@ -70,6 +77,11 @@ pub struct Spaces {
     pub(crate) conductor_db: DbWrite<DbKindConductor>,
     pub(crate) wasm_db: DbWrite<DbKindWasm>,
     db_key: DbKey,
+    /// The agents whose keys were revoked in DPKI on this conductor, which gossip
+    /// is only initiated with when there is no one else to gossip with.
+    revoked_agents: RwShare<HashSet<AgentPubKey>>,
+    /// The clock of the conductor, which every space shares.
+    pub(crate) clock: Clock,
 }
 
 #[derive(Clone)]
@@ -121,6 +133,14 @@ pub struct Space {
     /// Incoming ops batch for this space.
     pub incoming_ops_batch: IncomingOpsBatch,
 
+    /// Whether incoming ops are refused for now, because the validation backlog of this
+    /// space or the memory usage of the conductor is over its limit.
+    pub ingest_refused: Arc<AtomicBool>,
+
+    /// The clock of the conductor, which the source chains of this space take the
+    /// timestamps of new actions from.
+    pub clock: Clock,
+
     root_db_dir: Arc<PathBuf>,
     db_key: DbKey,
 }
@@ -215,6 +235,8 @@ impl Spaces {
             conductor_db,
             wasm_db,
             db_key,
+            revoked_agents: RwShare::new(HashSet::new()),
+            clock: Clock::default(),
         })
     }
 
@@ -375,6 +397,30 @@ impl Spaces {
             .share_ref(|spaces| spaces.values().map(f).collect())
     }
 
+    /// Whether incoming ops are refused for a space. Spaces which don't exist yet
+    /// aren't created and don't refuse ops.
+    pub fn is_ingest_refused(&self, dna_hash: &DnaHash) -> bool {
+        self.map.share_ref(|spaces| {
+            spaces
+                .get(dna_hash)
+                .is_some_and(|space| space.ingest_refused.load(Ordering::Relaxed))
+        })
+    }
+
+    /// Record that the key of an agent was revoked, so that gossip with the agent
+    /// is deprioritized in every space.
+    pub fn add_revoked_agent(&self, agent: AgentPubKey) {
+        self.revoked_agents.share_mut(|agents| {
+            agents.insert(agent);
+        });
+    }
+
+    /// Whether the key of an agent was revoked on this conductor.
+    pub fn is_revoked_agent(&self, agent: &AgentPubKey) -> bool {
+        self.revoked_agents
+            .share_ref(|agents| agents.contains(agent))
+    }
+
     /// Get the space if it exists or create it if it doesn't.
     pub fn get_or_create_space(&self, dna_hash: &DnaHash) -> DatabaseResult<Space> {
         self.get_or_create_space_ref(dna_hash, |s| s.clone())
@@ -396,6 +442,7 @@ impl Spaces {
                             self.db_dir.to_path_buf(),
                             self.config.db_sync_strategy,
                             self.db_key.clone(),
+                            self.clock.clone(),
                         )?;
 
                         let r = f(&space);
@@ -523,6 +570,7 @@ impl Spaces {
                             ":from": start,
                             ":to": end,
                             ":limit": max_ops,
+                            ":expiry_cutoff": expiry_cutoff(Timestamp::now()),
                         },
                         |row| row.get("hash"),
                     )?
@@ -557,19 +605,25 @@ impl Spaces {
         dna_hash: &DnaHash,
         query: FetchOpDataQuery,
     ) -> ConductorResult<Vec<(holo_hash::DhtOpHash, holochain_types::dht_op::DhtOp)>> {
-        match query {
+        let ops = match query {
             FetchOpDataQuery::Hashes {
                 op_hash_list,
                 include_limbo,
             } => {
                 self.handle_fetch_op_data_by_hashes(dna_hash, op_hash_list, include_limbo)
-                    .await
+                    .await?
             }
             FetchOpDataQuery::Regions(regions) => {
                 self.handle_fetch_op_data_by_regions(dna_hash, regions)
-                    .await
+                    .await?
             }
+        };
+        for (hash, _) in &ops {
+            record_op_event(hash, OpTraceStage::Served, || {
+                "to a remote node fetching it".to_string()
+            });
         }
+        Ok(ops)
     }
 
     #[cfg_attr(feature = "instrument", tracing::instrument(skip(self, regions)))]
@@ -595,6 +649,7 @@ impl Spaces {
                                 ":storage_end_loc": x1,
                                 ":timestamp_min": t0,
                                 ":timestamp_max": t1,
+                                ":expiry_cutoff": expiry_cutoff(Timestamp::now()),
                             },
                             |row| {
                                 let hash: DhtOpHash =
@@ -749,6 +804,7 @@ impl Space {
         root_db_dir: PathBuf,
         db_sync_strategy: DbSyncStrategy,
         db_key: DbKey,
+        clock: Clock,
     ) -> DatabaseResult<Self> {
         let space = dna_hash.to_kitsune();
         let db_sync_level = match db_sync_strategy {
@@ -824,6 +880,8 @@ impl Space {
             witnessing_workspace,
             incoming_op_hashes,
             incoming_ops_batch,
+            ingest_refused: Default::default(),
+            clock,
             dht_query_cache,
             conductor_db,
             root_db_dir: Arc::new(root_db_dir),
@@ -838,14 +896,15 @@ impl Space {
         keystore: MetaLairClient,
         author: AgentPubKey,
     ) -> SourceChainResult<SourceChain> {
-        SourceChain::raw_empty(
+        Ok(SourceChain::raw_empty(
             self.get_or_create_authored_db(author.clone())?,
             self.dht_db.clone(),
             self.dht_query_cache.clone(),
             keystore,
             author,
         )
-        .await
+        .await?
+        .with_clock(self.clock.clone()))
     }
 
     /// Create a SourceChainWorkspace from this Space
@@ -864,7 +923,8 @@ impl Space {
             author,
             dna_def,
         )
-        .await?)
+        .await?
+        .with_clock(self.clock.clone()))
     }
 
     /// Get or create the authored database for an agent in this space
@@ -975,6 +1035,7 @@ impl TestSpace {
                 temp_dir.path().to_path_buf(),
                 Default::default(),
                 Default::default(),
+                Default::default(),
             )
             .unwrap(),
             _temp_dir: temp_dir,
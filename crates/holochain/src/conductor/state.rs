@@ -8,6 +8,7 @@ use holochain_conductor_services::DPKI_APP_ID;
 use holochain_p2p::NetworkCompatParams;
 use holochain_types::prelude::*;
 use holochain_types::websocket::AllowedOrigins;
+use kitsune_p2p_types::dht::arq::ArqPin;
 use serde::Deserialize;
 use serde::Serialize;
 use std::collections::HashMap;
@@ -64,6 +65,10 @@ pub struct ConductorState {
     /// List of interfaces any UI can use to access zome functions.
     #[serde(default)]
     pub(crate) app_interfaces: HashMap<AppInterfaceId, AppInterfaceConfig>,
+
+    /// The storage arcs which cells are pinned to, applied whenever they join the network.
+    #[serde(default)]
+    pub(crate) arq_pins: HashMap<CellId, ArqPin>,
 }
 
 /// A unique identifier used to refer to an App Interface internally.
@@ -56,6 +56,8 @@ async fn clone_only_provisioning_creates_no_cell_and_allows_cloning() {
             existing_cells: Default::default(),
             ignore_genesis_failure: false,
             allow_throwaway_random_agent_key: true,
+            network_profile: None,
+            client_only: false,
         }
     }
 
@@ -178,6 +180,8 @@ async fn reject_duplicate_app_for_same_agent() {
             existing_cells: Default::default(),
             ignore_genesis_failure: false,
             allow_throwaway_random_agent_key: true,
+            network_profile: None,
+            client_only: false,
         })
         .await
         .unwrap();
@@ -199,6 +203,8 @@ async fn reject_duplicate_app_for_same_agent() {
             existing_cells: Default::default(),
             ignore_genesis_failure: false,
             allow_throwaway_random_agent_key: true,
+            network_profile: None,
+            client_only: false,
             network_seed: None,
         })
         .await;
@@ -224,6 +230,8 @@ async fn reject_duplicate_app_for_same_agent() {
             existing_cells: Default::default(),
             ignore_genesis_failure: false,
             allow_throwaway_random_agent_key: true,
+            network_profile: None,
+            client_only: false,
             network_seed: None,
         })
         .await;
@@ -246,6 +254,8 @@ async fn reject_duplicate_app_for_same_agent() {
             existing_cells: Default::default(),
             ignore_genesis_failure: false,
             allow_throwaway_random_agent_key: true,
+            network_profile: None,
+            client_only: false,
             network_seed: Some("network".into()),
         })
         .await;
@@ -298,6 +308,8 @@ async fn can_install_app_a_second_time_using_nothing_but_the_manifest_from_app_i
             existing_cells: Default::default(),
             ignore_genesis_failure: false,
             allow_throwaway_random_agent_key: true,
+            network_profile: None,
+            client_only: false,
         })
         .await
         .unwrap();
@@ -343,6 +355,8 @@ async fn can_install_app_a_second_time_using_nothing_but_the_manifest_from_app_i
             existing_cells: Default::default(),
             ignore_genesis_failure: false,
             allow_throwaway_random_agent_key: true,
+            network_profile: None,
+            client_only: false,
         })
         .await
         .unwrap();
@@ -539,6 +553,8 @@ async fn use_existing_integration() {
             existing_cells: Default::default(),
             ignore_genesis_failure: false,
             allow_throwaway_random_agent_key: true,
+            network_profile: None,
+            client_only: false,
         })
         .await
         .unwrap();
@@ -556,6 +572,8 @@ async fn use_existing_integration() {
                 existing_cells: Default::default(),
                 ignore_genesis_failure: false,
                 allow_throwaway_random_agent_key: true,
+                network_profile: None,
+                client_only: false,
             })
             .await
             .unwrap_err();
@@ -578,6 +596,8 @@ async fn use_existing_integration() {
                 existing_cells: Default::default(),
                 ignore_genesis_failure: false,
                 allow_throwaway_random_agent_key: true,
+                network_profile: None,
+                client_only: false,
             })
             .await
             .unwrap_err();
@@ -612,6 +632,8 @@ async fn use_existing_integration() {
             },
             ignore_genesis_failure: false,
             allow_throwaway_random_agent_key: true,
+            network_profile: None,
+            client_only: false,
         })
         .await
         .unwrap();
@@ -694,3 +716,35 @@ async fn use_existing_integration() {
         .await
         .unwrap();
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn network_profile_is_enforced_at_install() {
+    holochain_trace::test_run();
+
+    let conductor = SweetConductor::from_standard_config().await;
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+
+    // - The conductor's signal server isn't one which the profile allows.
+    let mut payload =
+        get_install_app_payload_from_dnas("app_1", None, &[(dna.clone(), None)], None).await;
+    payload.allow_throwaway_random_agent_key = true;
+    payload.network_profile = Some(NetworkProfile {
+        signal_servers: Some(vec!["wss://signal.example.com".to_string()]),
+        ..Default::default()
+    });
+    assert_matches!(
+        conductor.clone().install_app_bundle(payload).await,
+        Err(ConductorError::Other(_))
+    );
+
+    // - The conductor's signal server is on the local network.
+    let mut payload = get_install_app_payload_from_dnas("app_2", None, &[(dna, None)], None).await;
+    payload.allow_throwaway_random_agent_key = true;
+    payload.network_profile = Some(NetworkProfile {
+        lan_only: true,
+        ..Default::default()
+    });
+    let app = conductor.clone().install_app_bundle(payload).await.unwrap();
+    assert!(app.network_profile.as_ref().unwrap().lan_only);
+    conductor.enable_app("app_2".into()).await.unwrap();
+}
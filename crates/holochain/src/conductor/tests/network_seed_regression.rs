@@ -69,6 +69,8 @@ async fn network_seed_regression() {
             existing_cells: Default::default(),
             ignore_genesis_failure: false,
             allow_throwaway_random_agent_key: true,
+            network_profile: None,
+            client_only: false,
         })
         .await
         .unwrap();
@@ -84,6 +86,8 @@ async fn network_seed_regression() {
             existing_cells: Default::default(),
             ignore_genesis_failure: false,
             allow_throwaway_random_agent_key: true,
+            network_profile: None,
+            client_only: false,
         })
         .await
         .unwrap();
@@ -319,6 +323,8 @@ impl TestCase {
                 existing_cells: Default::default(),
                 ignore_genesis_failure: false,
                 allow_throwaway_random_agent_key: true,
+                network_profile: None,
+                client_only: false,
             })
             .await
             .unwrap();
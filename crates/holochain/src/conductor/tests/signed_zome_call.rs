@@ -268,3 +268,219 @@ async fn signed_zome_call_wildcard() {
         .unwrap();
     assert_matches!(response, ZomeCallResponse::Ok(_));
 }
+
+#[tokio::test(flavor = "multi_thread")]
+#[cfg(feature = "test_utils")]
+async fn delegated_zome_call() {
+    use holochain_conductor_api::{sign_zome_call_delegation, ZomeCall};
+    use holochain_keystore::AgentPubKeyExt;
+    use holochain_nonce::fresh_nonce;
+    use matches::assert_matches;
+
+    let zome = TestWasm::Create;
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![zome, TestWasm::Capability]).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", [&dna]).await.unwrap();
+    let cell = app.cells()[0].clone();
+    let cell_id = cell.cell_id().clone();
+    let agent_pub_key = app.agent().clone();
+    let keystore = conductor.keystore();
+    let server_key = AgentPubKey::new_random(&keystore).await.unwrap();
+    let worker_key = AgentPubKey::new_random(&keystore).await.unwrap();
+    let expires_at = (Timestamp::now() + std::time::Duration::from_secs(60)).unwrap();
+
+    // The agent backs each delegation with a cap grant to its delegate.
+    let grant = |secret: u8, delegate: &AgentPubKey, functions: GrantedFunctions| {
+        let cap_secret = CapSecret::from([secret; CAP_SECRET_BYTES]);
+        let payload = GrantZomeCallCapabilityPayload {
+            cell_id: cell_id.clone(),
+            cap_grant: ZomeCallCapGrant {
+                tag: "delegation".into(),
+                functions,
+                access: CapAccess::Assigned {
+                    secret: cap_secret,
+                    assignees: [delegate.clone()].into(),
+                },
+            },
+        };
+        let conductor = &conductor;
+        async move {
+            let action_hash = conductor.grant_zome_call_capability(payload).await.unwrap();
+            (cap_secret, action_hash)
+        }
+    };
+    let worker_functions =
+        GrantedFunctions::Listed([(zome.coordinator_zome_name(), "get_entry".into())].into());
+    let (server_secret, server_grant) = grant(1, &server_key, GrantedFunctions::All).await;
+    let (worker_secret, _) = grant(2, &worker_key, worker_functions.clone()).await;
+
+    // The agent delegates to a server, which delegates on to one of its workers.
+    let delegation = vec![
+        sign_zome_call_delegation(
+            &keystore,
+            &agent_pub_key,
+            server_key.clone(),
+            cell_id.clone(),
+            GrantedFunctions::All,
+            server_secret,
+            expires_at,
+        )
+        .await
+        .unwrap(),
+        sign_zome_call_delegation(
+            &keystore,
+            &server_key,
+            worker_key.clone(),
+            cell_id.clone(),
+            worker_functions,
+            worker_secret,
+            expires_at,
+        )
+        .await
+        .unwrap(),
+    ];
+    let call = |fn_name: &str| {
+        let (nonce, expires_at) = fresh_nonce(Timestamp::now()).unwrap();
+        ZomeCallUnsigned {
+            provenance: agent_pub_key.clone(),
+            cell_id: cell_id.clone(),
+            zome_name: zome.coordinator_zome_name(),
+            fn_name: fn_name.into(),
+            cap_secret: None,
+            payload: ExternIO::encode(()).unwrap(),
+            nonce,
+            expires_at,
+        }
+    };
+
+    // The worker can make calls which every delegation of the chain allows.
+    let response = conductor
+        .call_zome(
+            ZomeCall::try_from_delegated_zome_call(
+                &keystore,
+                call("get_entry"),
+                delegation.clone(),
+            )
+            .await
+            .unwrap(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_matches!(response, ZomeCallResponse::Ok(_));
+
+    // The worker may only call the functions which the server delegated to it.
+    let response = conductor
+        .call_zome(
+            ZomeCall::try_from_delegated_zome_call(
+                &keystore,
+                call("create_entry"),
+                delegation.clone(),
+            )
+            .await
+            .unwrap(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_matches!(
+        response,
+        ZomeCallResponse::Unauthorized(ZomeCallAuthorization::BadDelegation(_), ..)
+    );
+
+    // The server can't skip the agent's delegation.
+    let response = conductor
+        .call_zome(
+            ZomeCall::try_from_delegated_zome_call(
+                &keystore,
+                call("get_entry"),
+                delegation[1..].to_vec(),
+            )
+            .await
+            .unwrap(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_matches!(
+        response,
+        ZomeCallResponse::Unauthorized(ZomeCallAuthorization::BadDelegation(_), ..)
+    );
+
+    // Deleting the grant to the server revokes every delegation after it.
+    let _: ActionHash = conductor
+        .call(
+            &cell.zome(TestWasm::Capability),
+            "delete_cap_grant",
+            server_grant,
+        )
+        .await;
+    let response = conductor
+        .call_zome(
+            ZomeCall::try_from_delegated_zome_call(&keystore, call("get_entry"), delegation)
+                .await
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+    assert_matches!(
+        response,
+        ZomeCallResponse::Unauthorized(ZomeCallAuthorization::BadDelegation(_), ..)
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+#[cfg(feature = "test_utils")]
+async fn reused_nonce_is_rejected_with_a_fresh_window() {
+    use crate::conductor::api::AppInterfaceApi;
+    use holochain_conductor_api::{AppRequest, AppResponse, ExternalApiWireError, ZomeCall};
+    use matches::assert_matches;
+
+    let zome = TestWasm::Create;
+    let (dna, _, _) = SweetDnaFile::unique_from_test_wasms(vec![zome]).await;
+    let mut conductor = SweetConductor::from_standard_config().await;
+    let app = conductor.setup_app("app", [&dna]).await.unwrap();
+    let cell_id = app.cells()[0].cell_id().clone();
+    let api = AppInterfaceApi::new(conductor.raw_handle());
+
+    let request = |request| api.handle_request("app".into(), Ok(request));
+    let window = match request(AppRequest::GetZomeCallNonceWindow).await.unwrap() {
+        AppResponse::ZomeCallNonceWindow(window) => window,
+        response => panic!("unexpected response {response:?}"),
+    };
+    assert!(window.now < window.expires_at);
+    assert!(window.expires_at <= window.latest_expires_at);
+
+    let call = ZomeCall::try_from_unsigned_zome_call(
+        &conductor.keystore(),
+        ZomeCallUnsigned {
+            provenance: cell_id.agent_pubkey().clone(),
+            cell_id: cell_id.clone(),
+            zome_name: zome.coordinator_zome_name(),
+            fn_name: "get_entry".into(),
+            cap_secret: None,
+            payload: ExternIO::encode(()).unwrap(),
+            nonce: window.nonce,
+            expires_at: window.expires_at,
+        },
+    )
+    .await
+    .unwrap();
+    assert_matches!(
+        request(AppRequest::CallZome(Box::new(call.clone())))
+            .await
+            .unwrap(),
+        AppResponse::ZomeCalled(_)
+    );
+    match request(AppRequest::CallZome(Box::new(call))).await.unwrap() {
+        AppResponse::Error(ExternalApiWireError::ZomeCallNonceRejected {
+            reason,
+            window: retry,
+        }) => {
+            assert_eq!(NonceRejection::Duplicate, reason);
+            assert_ne!(window.nonce, retry.nonce);
+        }
+        response => panic!("unexpected response {response:?}"),
+    }
+}
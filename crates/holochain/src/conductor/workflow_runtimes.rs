@@ -0,0 +1,293 @@
+//! Separate tokio runtimes for the workflows of cells, so that heavy validation
+//! and integration on one DNA can't starve the zome calls of other apps on the
+//! same conductor.
+//!
+//! The number of runtimes is set by the `workflow_runtime_count` in the
+//! [`ConductorTuningParams`]. The cells of a DNA all run their workflows on the same
+//! runtime, which is chosen by the location of the DNA hash, unless the
+//! `workflow_runtime_assignments` put the DNA or one of its cells on another one.
+//! With no workflow runtimes, workflows run on the same runtime as zome calls.
+
+use super::error::{ConductorError, ConductorResult};
+use super::metrics::{
+    create_workflow_runtime_active_metric, create_workflow_runtime_wait_duration_metric,
+    WorkflowRuntimeWaitDurationMetric,
+};
+use futures::Future;
+use holochain_conductor_api::conductor::ConductorTuningParams;
+use holochain_types::prelude::*;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+use tokio::runtime::{Handle, Runtime};
+use tokio::task::JoinError;
+
+/// The runtimes which the workflows of cells run on.
+pub(crate) struct WorkflowRuntimes {
+    runtimes: Vec<Runtime>,
+    handles: Vec<WorkflowRuntime>,
+    /// The runtimes which the `workflow_runtime_assignments` put DNAs on.
+    dnas: HashMap<DnaHash, usize>,
+    /// The runtimes which the `workflow_runtime_assignments` put cells on.
+    cells: HashMap<CellId, usize>,
+}
+
+impl WorkflowRuntimes {
+    /// Start the workflow runtimes.
+    ///
+    /// Fails if a runtime can't be started or an assignment names a runtime
+    /// which doesn't exist, so that the conductor doesn't start without them.
+    pub(crate) fn new(tuning_params: &ConductorTuningParams) -> ConductorResult<Self> {
+        let count = tuning_params.workflow_runtime_count();
+        let mut dnas = HashMap::new();
+        let mut cells = HashMap::new();
+        for assignment in tuning_params.workflow_runtime_assignments() {
+            if assignment.runtime >= count {
+                return Err(ConductorError::ConfigError(format!(
+                    "The workflows of DNA {} are assigned to workflow runtime {}, but the workflow_runtime_count is {}",
+                    assignment.dna_hash, assignment.runtime, count
+                )));
+            }
+            let dna_hash = DnaHash::from(assignment.dna_hash);
+            match assignment.agent {
+                Some(agent) => {
+                    cells.insert(CellId::new(dna_hash, agent.into()), assignment.runtime);
+                }
+                None => {
+                    dnas.insert(dna_hash, assignment.runtime);
+                }
+            }
+        }
+
+        let threads = tuning_params.workflow_runtime_threads().max(1);
+        let mut runtimes = Vec::with_capacity(count);
+        for index in 0..count {
+            match tokio::runtime::Builder::new_multi_thread()
+                .worker_threads(threads)
+                .thread_name(format!("hc-workflow-{}", index))
+                .enable_all()
+                .build()
+            {
+                Ok(runtime) => runtimes.push(runtime),
+                Err(err) => {
+                    tracing::error!(?err, index, "Failed to start a workflow runtime");
+                    for runtime in runtimes {
+                        runtime.shutdown_background();
+                    }
+                    return Err(err.into());
+                }
+            }
+        }
+        let handles = runtimes
+            .iter()
+            .enumerate()
+            .map(|(index, runtime)| WorkflowRuntime::new(index, runtime.handle().clone()))
+            .collect();
+        Ok(Self {
+            runtimes,
+            handles,
+            dnas,
+            cells,
+        })
+    }
+
+    /// The runtime which the workflows that the cells of a DNA share run on, if there
+    /// are any workflow runtimes.
+    pub(crate) fn for_dna(&self, dna_hash: &DnaHash) -> Option<WorkflowRuntime> {
+        if self.handles.is_empty() {
+            return None;
+        }
+        let index = match self.dnas.get(dna_hash) {
+            Some(index) => *index,
+            None => dna_hash.get_loc().as_u32() as usize % self.handles.len(),
+        };
+        Some(self.handles[index].clone())
+    }
+
+    /// The runtime which the workflows of a cell run on, if there are any workflow
+    /// runtimes. Without an assignment of its own, that's the runtime of its DNA.
+    pub(crate) fn for_cell(&self, cell_id: &CellId) -> Option<WorkflowRuntime> {
+        match self.cells.get(cell_id) {
+            Some(index) => Some(self.handles[*index].clone()),
+            None => self.for_dna(cell_id.dna_hash()),
+        }
+    }
+}
+
+impl Drop for WorkflowRuntimes {
+    fn drop(&mut self) {
+        // The conductor may be dropped from within an async context,
+        // where a runtime can't block while it shuts down.
+        for runtime in self.runtimes.drain(..) {
+            runtime.shutdown_background();
+        }
+    }
+}
+
+/// One of the [`WorkflowRuntimes`].
+#[derive(Clone)]
+pub(crate) struct WorkflowRuntime {
+    index: usize,
+    handle: Handle,
+    active: Arc<AtomicU64>,
+    wait_metric: WorkflowRuntimeWaitDurationMetric,
+}
+
+impl WorkflowRuntime {
+    fn new(index: usize, handle: Handle) -> Self {
+        Self {
+            index,
+            handle,
+            active: create_workflow_runtime_active_metric(index),
+            wait_metric: create_workflow_runtime_wait_duration_metric(index),
+        }
+    }
+
+    /// Run a future on this runtime and wait for its result.
+    ///
+    /// Returns an error if the runtime shut down before the future completed.
+    /// If the future panics, the panic is resumed in the caller.
+    pub(crate) async fn run<T: 'static + Send>(
+        &self,
+        fut: impl 'static + Send + Future<Output = T>,
+    ) -> Result<T, JoinError> {
+        let active = ActiveGuard::new(self.active.clone());
+        let wait_metric = self.wait_metric.clone();
+        let queued_at = Instant::now();
+        let result = self
+            .handle
+            .spawn(async move {
+                wait_metric.record(queued_at.elapsed().as_secs_f64(), &[]);
+                let _active = active;
+                fut.await
+            })
+            .await;
+        match result {
+            Err(err) if err.is_panic() => std::panic::resume_unwind(err.into_panic()),
+            result => result,
+        }
+    }
+
+    /// The index of this runtime among the [`WorkflowRuntimes`].
+    pub(crate) fn index(&self) -> usize {
+        self.index
+    }
+}
+
+/// Counts a workflow run as active on its runtime until it is dropped.
+struct ActiveGuard(Arc<AtomicU64>);
+
+impl ActiveGuard {
+    fn new(active: Arc<AtomicU64>) -> Self {
+        active.fetch_add(1, Ordering::Relaxed);
+        Self(active)
+    }
+}
+
+impl Drop for ActiveGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::prelude::*;
+    use holo_hash::fixt::{AgentPubKeyFixturator, DnaHashFixturator};
+    use holochain_conductor_api::conductor::WorkflowRuntimeAssignment;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn workflows_run_on_the_runtime_of_their_dna() {
+        let tuning_params = ConductorTuningParams {
+            workflow_runtime_count: Some(2),
+            workflow_runtime_threads: Some(1),
+            ..ConductorTuningParams::new()
+        };
+        let runtimes = WorkflowRuntimes::new(&tuning_params).unwrap();
+
+        let dna_hash = fixt!(DnaHash);
+        let runtime = runtimes.for_dna(&dna_hash).unwrap();
+        assert_eq!(
+            runtime.index(),
+            runtimes.for_dna(&dna_hash).unwrap().index()
+        );
+
+        let thread_name = runtime
+            .run(async { std::thread::current().name().map(String::from) })
+            .await
+            .unwrap();
+        assert_eq!(
+            Some(format!("hc-workflow-{}", runtime.index())),
+            thread_name
+        );
+        assert_eq!(0, runtime.active.load(Ordering::Relaxed));
+
+        // Without workflow runtimes, workflows run on the conductor's runtime.
+        let runtimes = WorkflowRuntimes::new(&ConductorTuningParams::new()).unwrap();
+        assert!(runtimes.for_dna(&dna_hash).is_none());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn assigned_dnas_and_cells_run_on_their_runtimes() {
+        let dna_hash = fixt!(DnaHash);
+        let alice = fixt!(AgentPubKey);
+        let bob = fixt!(AgentPubKey);
+        let by_location = dna_hash.get_loc().as_u32() as usize % 2;
+        let other = 1 - by_location;
+
+        let tuning_params = ConductorTuningParams {
+            workflow_runtime_count: Some(2),
+            workflow_runtime_threads: Some(1),
+            workflow_runtime_assignments: Some(vec![
+                WorkflowRuntimeAssignment {
+                    dna_hash: dna_hash.clone().into(),
+                    agent: None,
+                    runtime: other,
+                },
+                WorkflowRuntimeAssignment {
+                    dna_hash: dna_hash.clone().into(),
+                    agent: Some(alice.clone().into()),
+                    runtime: by_location,
+                },
+            ]),
+            ..ConductorTuningParams::new()
+        };
+        let runtimes = WorkflowRuntimes::new(&tuning_params).unwrap();
+
+        assert_eq!(other, runtimes.for_dna(&dna_hash).unwrap().index());
+        // Cells without an assignment of their own follow their DNA.
+        assert_eq!(
+            other,
+            runtimes
+                .for_cell(&CellId::new(dna_hash.clone(), bob))
+                .unwrap()
+                .index()
+        );
+        assert_eq!(
+            by_location,
+            runtimes
+                .for_cell(&CellId::new(dna_hash, alice))
+                .unwrap()
+                .index()
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn assignments_to_missing_runtimes_fail() {
+        let tuning_params = ConductorTuningParams {
+            workflow_runtime_count: Some(1),
+            workflow_runtime_assignments: Some(vec![WorkflowRuntimeAssignment {
+                dna_hash: fixt!(DnaHash).into(),
+                agent: Some(fixt!(AgentPubKey).into()),
+                runtime: 1,
+            }]),
+            ..ConductorTuningParams::new()
+        };
+        assert!(matches!(
+            WorkflowRuntimes::new(&tuning_params),
+            Err(ConductorError::ConfigError(_))
+        ));
+    }
+}
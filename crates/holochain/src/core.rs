@@ -10,6 +10,7 @@ mod validation;
 pub mod workflow;
 
 mod metrics;
+pub(crate) mod op_trace;
 mod sys_validate;
 
 pub use sys_validate::*;
@@ -1,6 +1,10 @@
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use std::time::Duration;
 
-use holo_hash::{AgentPubKey, DnaHash};
+use holo_hash::{AgentPubKey, AnyDhtHash, DnaHash};
+use once_cell::sync::Lazy;
 use opentelemetry_api::{global::meter_with_version, metrics::*, KeyValue};
 
 pub type WorkflowDurationMetric = Histogram<f64>;
@@ -30,3 +34,141 @@ pub fn create_workflow_duration_metric(
     .with_description("The time spent running a workflow")
     .init()
 }
+
+/// The latest queue depth of each workflow, for each DNA.
+static WORKFLOW_QUEUE_DEPTHS: Lazy<
+    parking_lot::Mutex<HashMap<(&'static str, DnaHash), Arc<AtomicU64>>>,
+> = Lazy::new(Default::default);
+
+/// Report the number of ops a workflow found waiting to be processed when it started.
+pub fn record_workflow_queue_depth(workflow_name: &'static str, dna_hash: &DnaHash, depth: usize) {
+    let mut depths = WORKFLOW_QUEUE_DEPTHS.lock();
+    let depth_value = depths
+        .entry((workflow_name, dna_hash.clone()))
+        .or_insert_with(|| create_workflow_queue_depth_metric(workflow_name, dna_hash));
+    depth_value.store(depth as u64, Ordering::Relaxed);
+}
+
+fn create_workflow_queue_depth_metric(
+    workflow_name: &'static str,
+    dna_hash: &DnaHash,
+) -> Arc<AtomicU64> {
+    let depth = Arc::new(AtomicU64::new(0));
+    let meter = meter_with_version(
+        "hc.conductor",
+        None::<&'static str>,
+        None::<&'static str>,
+        Some(vec![
+            KeyValue::new("workflow", workflow_name),
+            KeyValue::new("dna_hash", format!("{:?}", dna_hash)),
+        ]),
+    );
+
+    let gauge = meter
+        .u64_observable_gauge("hc.conductor.workflow.queue.depth")
+        .with_description("The number of ops waiting to be processed by a workflow")
+        .init();
+
+    let observed_depth = depth.clone();
+    let registration_result = meter.register_callback(&[gauge.as_any()], move |observer| {
+        observer.observe_u64(&gauge, observed_depth.load(Ordering::Relaxed), &[])
+    });
+    if let Err(e) = registration_result {
+        tracing::error!("Failed to register callback for metric: {:?}", e);
+    }
+    depth
+}
+
+/// The dependencies which the ops in validation limbo were waiting for when each
+/// workflow last ran, with how many ops were waiting for each, for each DNA.
+static AWAITED_DEPENDENCIES: Lazy<
+    parking_lot::Mutex<HashMap<(&'static str, DnaHash), HashMap<AnyDhtHash, usize>>>,
+> = Lazy::new(Default::default);
+
+/// Report the dependencies which the ops a workflow couldn't validate are waiting for,
+/// replacing what the workflow reported when it last ran.
+pub fn record_awaited_dependencies(
+    workflow_name: &'static str,
+    dna_hash: &DnaHash,
+    awaited: HashMap<AnyDhtHash, usize>,
+) {
+    let mut dependencies = AWAITED_DEPENDENCIES.lock();
+    if awaited.is_empty() {
+        dependencies.remove(&(workflow_name, dna_hash.clone()));
+    } else {
+        dependencies.insert((workflow_name, dna_hash.clone()), awaited);
+    }
+}
+
+/// The dependencies which the ops of a DNA were waiting for when a workflow last ran,
+/// with how many ops were waiting for each.
+pub fn awaited_dependencies(
+    workflow_name: &'static str,
+    dna_hash: &DnaHash,
+) -> HashMap<AnyDhtHash, usize> {
+    AWAITED_DEPENDENCIES
+        .lock()
+        .get(&(workflow_name, dna_hash.clone()))
+        .cloned()
+        .unwrap_or_default()
+}
+
+pub type AppMetricHistogram = Histogram<f64>;
+
+/// The histogram of each metric which apps have emitted, by name.
+static APP_METRIC_HISTOGRAMS: Lazy<parking_lot::Mutex<HashMap<String, AppMetricHistogram>>> =
+    Lazy::new(Default::default);
+
+/// Record a measurement which an app emitted through the emit_metric host function,
+/// as the `hc.app.<name>` histogram.
+pub fn record_app_metric(name: &str, value: f64, attributes: &[KeyValue]) {
+    let mut histograms = APP_METRIC_HISTOGRAMS.lock();
+    if let Some(histogram) = histograms.get(name) {
+        histogram.record(value, attributes);
+        return;
+    }
+    histograms
+        .entry(name.to_string())
+        .or_insert_with(|| create_app_metric_histogram(name))
+        .record(value, attributes);
+}
+
+fn create_app_metric_histogram(name: &str) -> AppMetricHistogram {
+    meter_with_version(
+        "hc.app",
+        None::<&'static str>,
+        None::<&'static str>,
+        Some(vec![]),
+    )
+    .f64_histogram(format!("hc.app.{}", name))
+    .with_description("A measurement emitted by an app through the emit_metric host function")
+    .init()
+}
+
+pub type IncomingOpsInsertRateMetric = Histogram<f64>;
+
+static INCOMING_OPS_INSERT_RATE: Lazy<IncomingOpsInsertRateMetric> = Lazy::new(|| {
+    meter_with_version(
+        "hc.conductor",
+        None::<&'static str>,
+        None::<&'static str>,
+        Some(vec![]),
+    )
+    .f64_histogram("hc.conductor.incoming_ops.insert_rate")
+    .with_unit(Unit::new("ops/s"))
+    .with_description(
+        "How many incoming ops are inserted per second by each batch of the incoming ops workflow",
+    )
+    .init()
+});
+
+/// Report how long a batch of the incoming ops workflow took to insert its ops.
+pub fn record_incoming_ops_inserted(dna_hash: &DnaHash, inserted: usize, elapsed: Duration) {
+    if inserted == 0 || elapsed.is_zero() {
+        return;
+    }
+    INCOMING_OPS_INSERT_RATE.record(
+        inserted as f64 / elapsed.as_secs_f64(),
+        &[KeyValue::new("dna_hash", format!("{:?}", dna_hash))],
+    );
+}
@@ -0,0 +1,199 @@
+//! Follow individual ops through the conductor.
+//!
+//! Once an op is traced with [`start_op_trace`], every part of the conductor which
+//! handles it records an event, which is logged and kept until the trace is stopped.
+//! Traces are shared by all conductors in the process.
+
+use holo_hash::DhtOpHash;
+use holochain_conductor_api::{OpTraceEvent, OpTraceStage};
+use holochain_zome_types::prelude::Timestamp;
+use once_cell::sync::Lazy;
+use std::collections::{HashMap, VecDeque};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// The most events which are kept for each traced op. Older events are dropped.
+const MAX_OP_TRACE_EVENTS: usize = 1000;
+
+/// The events of each traced op.
+static OP_TRACES: Lazy<parking_lot::Mutex<HashMap<DhtOpHash, VecDeque<OpTraceEvent>>>> =
+    Lazy::new(Default::default);
+
+/// Whether any op is traced, so that untraced ops don't take the lock.
+static ANY_OP_TRACED: AtomicBool = AtomicBool::new(false);
+
+/// Start tracing an op. The events of an op which is already traced are kept.
+pub(crate) fn start_op_trace(op_hash: DhtOpHash) {
+    let mut traces = OP_TRACES.lock();
+    traces.entry(op_hash).or_default();
+    ANY_OP_TRACED.store(true, Ordering::Relaxed);
+}
+
+/// Stop tracing an op and drop its events.
+pub(crate) fn stop_op_trace(op_hash: &DhtOpHash) {
+    let mut traces = OP_TRACES.lock();
+    traces.remove(op_hash);
+    ANY_OP_TRACED.store(!traces.is_empty(), Ordering::Relaxed);
+}
+
+/// The events of a traced op, oldest first, or `None` if it isn't traced.
+pub(crate) fn op_trace(op_hash: &DhtOpHash) -> Option<Vec<OpTraceEvent>> {
+    OP_TRACES
+        .lock()
+        .get(op_hash)
+        .map(|events| events.iter().cloned().collect())
+}
+
+/// The ops which are traced.
+pub(crate) fn traced_ops() -> Vec<DhtOpHash> {
+    if !ANY_OP_TRACED.load(Ordering::Relaxed) {
+        return Vec::new();
+    }
+    OP_TRACES.lock().keys().cloned().collect()
+}
+
+/// Record that an op passed through a stage, if it is traced.
+/// `detail` is only called for traced ops.
+pub(crate) fn record_op_event(
+    op_hash: &DhtOpHash,
+    stage: OpTraceStage,
+    detail: impl FnOnce() -> String,
+) {
+    if !ANY_OP_TRACED.load(Ordering::Relaxed) {
+        return;
+    }
+    let mut traces = OP_TRACES.lock();
+    let Some(events) = traces.get_mut(op_hash) else {
+        return;
+    };
+    let detail = detail();
+    tracing::info!(target: "op_trace", ?op_hash, ?stage, %detail, "Traced op event");
+    if events.len() == MAX_OP_TRACE_EVENTS {
+        events.pop_front();
+    }
+    events.push_back(OpTraceEvent {
+        timestamp: Timestamp::now(),
+        stage,
+        detail,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_traced_ops_record_events() {
+        let traced = DhtOpHash::from_raw_36(vec![1; 36]);
+        let untraced = DhtOpHash::from_raw_36(vec![2; 36]);
+        start_op_trace(traced.clone());
+
+        record_op_event(&traced, OpTraceStage::Received, || "by publish".to_string());
+        record_op_event(&untraced, OpTraceStage::Received, || {
+            panic!("untraced ops aren't described")
+        });
+        record_op_event(&traced, OpTraceStage::SysValidated, || {
+            "Accepted".to_string()
+        });
+
+        let events = op_trace(&traced).unwrap();
+        assert_eq!(
+            vec![OpTraceStage::Received, OpTraceStage::SysValidated],
+            events.iter().map(|event| event.stage).collect::<Vec<_>>()
+        );
+        assert_eq!(None, op_trace(&untraced));
+
+        stop_op_trace(&traced);
+        assert_eq!(None, op_trace(&traced));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn gossiped_op_is_traced_until_integrated() {
+        use crate::sweettest::*;
+        use crate::test_utils::inline_zomes::simple_create_read_zome;
+        use holochain_state::prelude::*;
+
+        holochain_trace::test_run();
+        let config = SweetConductorConfig::standard().no_publish();
+        let mut conductors = SweetConductorBatch::from_config(2, config).await;
+        let (dna_file, _, _) =
+            SweetDnaFile::unique_from_inline_zomes(("zome", simple_create_read_zome())).await;
+        let apps = conductors.setup_app("app", &[dna_file]).await.unwrap();
+        let ((alice,), (bob,)) = apps.into_tuples();
+
+        let action_hash: ActionHash = conductors[0].call(&alice.zome("zome"), "create", ()).await;
+        let op_hash = alice
+            .authored_db()
+            .read_async(move |txn| {
+                DatabaseResult::Ok(txn.query_row(
+                    "SELECT hash FROM DhtOp WHERE action_hash = :action_hash AND type = :type",
+                    named_params! {
+                        ":action_hash": action_hash,
+                        ":type": ChainOpType::StoreRecord,
+                    },
+                    |row| row.get::<_, DhtOpHash>(0),
+                )?)
+            })
+            .await
+            .unwrap();
+        conductors[1].raw_handle().start_op_trace(op_hash.clone());
+
+        // - Without publish, Bob can only get the op by gossip once the peers know each other.
+        conductors.exchange_peer_info().await;
+        await_consistency(60, [&alice, &bob]).await.unwrap();
+
+        let stages: Vec<OpTraceStage> = conductors[1]
+            .raw_handle()
+            .op_trace(&op_hash)
+            .unwrap()
+            .into_iter()
+            .map(|event| event.stage)
+            .collect();
+        for stage in [
+            OpTraceStage::Received,
+            OpTraceStage::SysValidated,
+            OpTraceStage::AppValidated,
+            OpTraceStage::Integrated,
+        ] {
+            assert!(
+                stages.contains(&stage),
+                "{:?} is missing from {:?}",
+                stage,
+                stages
+            );
+        }
+        conductors[1].raw_handle().stop_op_trace(&op_hash);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn gossiped_op_fixture_is_replayed() {
+        use crate::sweettest::*;
+        use crate::test_utils::inline_zomes::simple_create_read_zome;
+        use holochain_conductor_api::op_model::*;
+        use holochain_state::prelude::*;
+
+        holochain_trace::test_run();
+        let fixture = OpTraceFixture {
+            arrival: OpArrival::Gossip,
+            terminal: OpHolderState {
+                received: true,
+                sys_validated: Some(OpStageOutcome::Accepted),
+                app_validated: Some(OpStageOutcome::Accepted),
+                integrated: Some(ValidationStatus::Valid),
+                receipt_sent: None,
+            },
+        };
+        let (dna_file, _, _) =
+            SweetDnaFile::unique_from_inline_zomes(("zome", simple_create_read_zome())).await;
+        let check = replay_op_trace_fixture(
+            &fixture,
+            dna_file,
+            "zome",
+            "create",
+            (),
+            ChainOpType::StoreRecord,
+            std::time::Duration::from_secs(60),
+        )
+        .await;
+        assert!(check.machine.from_arrival);
+    }
+}
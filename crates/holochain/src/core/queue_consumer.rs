@@ -31,8 +31,9 @@ use super::workflow::app_validation_workflow::AppValidationWorkspace;
 use super::workflow::sys_validation_workflow::SysValidationWorkspace;
 use super::workflow::{WorkflowError, WorkflowResult};
 use crate::conductor::conductor::{RwShare, StopReceiver};
-use crate::conductor::manager::TaskManagerClient;
+use crate::conductor::slow_operations::SlowOperationLog;
 use crate::conductor::space::Space;
+use crate::conductor::workflow_runtimes::WorkflowRuntime;
 use crate::conductor::ConductorHandle;
 use crate::conductor::{error::ConductorError, manager::ManagedTaskResult};
 use derive_more::Display;
@@ -40,6 +41,7 @@ use futures::future::Either;
 use futures::{Future, Stream, StreamExt};
 use holochain_p2p::HolochainP2pDna;
 use holochain_p2p::*;
+use holochain_sqlite::db::measure_db_wait;
 use holochain_types::prelude::*;
 use publish_dht_ops_consumer::*;
 use std::collections::HashMap;
@@ -48,6 +50,7 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::broadcast;
+use tracing::Instrument;
 use witnessing_consumer::*;
 
 // MAYBE: move these to workflow mod
@@ -116,6 +119,10 @@ pub async fn spawn_queue_consumer_tasks(
         )
     });
 
+    let dna_def = conductor
+        .get_dna_def(&dna_hash)
+        .expect("Dna must be in store");
+
     // Integration
     // One per space.
     let tx_integration = queue_consumer_map.spawn_once_integration(dna_hash.clone(), || {
@@ -123,16 +130,13 @@ pub async fn spawn_queue_consumer_tasks(
             dna_hash.clone(),
             dht_db.clone(),
             dht_query_cache.clone(),
-            conductor.task_manager(),
+            conductor.clone(),
             tx_receipt.clone(),
             network.clone(),
+            Arc::new(dna_def.clone()),
         )
     });
 
-    let dna_def = conductor
-        .get_dna_def(&dna_hash)
-        .expect("Dna must be in store");
-
     // App validation
     // One per space.
     let tx_app = queue_consumer_map.spawn_once_app_validation(dna_hash.clone(), || {
@@ -170,7 +174,6 @@ pub async fn spawn_queue_consumer_tasks(
                 Arc::new(dna_def),
                 conductor.running_services().dpki.clone(),
                 conductor
-                    .get_config()
                     .conductor_tuning_params()
                     .sys_validation_retry_delay(),
             ),
@@ -190,11 +193,9 @@ pub async fn spawn_queue_consumer_tasks(
             .or_insert_with(|| {
                 Arc::new(CountersigningWorkspace::new(
                     conductor
-                        .config
                         .conductor_tuning_params()
                         .countersigning_resolution_retry_delay(),
                     conductor
-                        .config
                         .conductor_tuning_params()
                         .countersigning_resolution_retry_limit,
                 ))
@@ -213,7 +214,7 @@ pub async fn spawn_queue_consumer_tasks(
     let tx_witnessing = queue_consumer_map.spawn_once_witnessing(dna_hash, || {
         spawn_witnessing_consumer(
             space.clone(),
-            conductor.task_manager(),
+            conductor.clone(),
             network.clone(),
             tx_sys.clone(),
         )
@@ -421,6 +422,22 @@ pub struct TriggerSender {
     reset_back_off: Option<Arc<AtomicBool>>,
     /// Pause / resume the back off loop if there is one.
     pause_back_off: Option<Arc<AtomicBool>>,
+    /// The spans which sent a trigger since the workflow last started,
+    /// so that the workflow's span can be linked to what caused it to run.
+    trigger_spans: Arc<parking_lot::Mutex<Vec<tracing::Span>>>,
+}
+
+/// The most trigger spans to keep between workflow runs.
+const MAX_TRIGGER_SPANS: usize = 16;
+
+tokio::task_local! {
+    /// The span which caused the running workflow to be triggered.
+    ///
+    /// Triggers sent by a workflow pass this span on rather than the
+    /// workflow's own span, so that every workflow which runs as a result
+    /// of e.g. a zome call is a child of the zome call's span, instead of
+    /// forming an ever growing chain of workflow spans.
+    static TRIGGER_ORIGIN: tracing::Span;
 }
 
 /// The receiving end of a queue trigger channel
@@ -456,6 +473,7 @@ impl TriggerSender {
                 trigger: tx,
                 reset_back_off: None,
                 pause_back_off: None,
+                trigger_spans: Default::default(),
             },
             TriggerReceiver {
                 rx,
@@ -485,6 +503,7 @@ impl TriggerSender {
                 trigger: tx,
                 reset_back_off: Some(reset_back_off.clone()),
                 pause_back_off: Some(pause_back_off.clone()),
+                trigger_spans: Default::default(),
             },
             TriggerReceiver {
                 rx,
@@ -497,6 +516,15 @@ impl TriggerSender {
     /// Lazily nudge the consumer task, ignoring the case where the consumer
     /// already has a pending trigger signal
     pub fn trigger(&self, context: &'static &'static str) {
+        let span = TRIGGER_ORIGIN
+            .try_with(Clone::clone)
+            .unwrap_or_else(|_| tracing::Span::current());
+        if !span.is_disabled() {
+            let mut spans = self.trigger_spans.lock();
+            if spans.len() < MAX_TRIGGER_SPANS {
+                spans.push(span);
+            }
+        }
         if self.trigger.send(context).is_err() {
             tracing::warn!(
                 "Queue consumer trigger was sent while Cell is shutting down: ignoring."
@@ -504,6 +532,11 @@ impl TriggerSender {
         };
     }
 
+    /// Take the spans which sent a trigger since this was last called.
+    fn take_trigger_spans(&self) -> Vec<tracing::Span> {
+        std::mem::take(&mut *self.trigger_spans.lock())
+    }
+
     /// Reset the back off to the lowest duration.
     /// If no back off is set this is a no-op.
     pub fn reset_back_off(&self) {
@@ -721,14 +754,42 @@ async fn queue_consumer_main_task_impl<
     agent: Option<AgentPubKey>,
     (tx, rx): (TriggerSender, TriggerReceiver),
     stop: StopReceiver,
+    slow_operations: Arc<SlowOperationLog>,
+    workflow_runtime: Option<WorkflowRuntime>,
     mut fut: impl 'static + Send + FnMut() -> Fut,
 ) -> ManagedTaskResult {
     let mut triggers = trigger_stream(rx, stop);
     let duration_metric = create_workflow_duration_metric(name.clone(), dna_hash, agent);
     loop {
         if let Some(()) = triggers.next().await {
+            let (span, origin) =
+                workflow_span(&name, &dna_hash, agent.as_ref(), tx.take_trigger_spans());
             let start = Instant::now();
-            match fut().await {
+            let started_at = Timestamp::now();
+            let run = measure_db_wait(TRIGGER_ORIGIN.scope(origin, fut().instrument(span)));
+            let (result, db_wait) = match &workflow_runtime {
+                Some(workflow_runtime) => match workflow_runtime.run(run).await {
+                    Ok(run) => run,
+                    Err(_) => {
+                        tracing::info!(
+                            "Workflow runtime {} has shut down: stopping queue consumer '{}'",
+                            workflow_runtime.index(),
+                            name
+                        );
+                        break;
+                    }
+                },
+                None => run.await,
+            };
+            slow_operations.workflow(
+                &name,
+                &dna_hash,
+                agent.as_ref(),
+                started_at,
+                start.elapsed(),
+                db_wait,
+            );
+            match result {
                 Ok(WorkComplete::Incomplete(delay)) => {
                     tracing::debug!("Work incomplete, re-triggering workflow - {}.", name);
                     if let Some(dly) = delay {
@@ -754,20 +815,62 @@ async fn queue_consumer_main_task_impl<
     ManagedTaskResult::Ok(())
 }
 
+/// Create the span for one run of a workflow, along with the span to pass on
+/// to the workflows it triggers. Its parent is the first span which triggered
+/// the workflow, and it follows from any other triggers, so that the work
+/// which led to this run can be traced.
+fn workflow_span(
+    name: &str,
+    dna_hash: &DnaHash,
+    agent: Option<&AgentPubKey>,
+    trigger_spans: Vec<tracing::Span>,
+) -> (tracing::Span, tracing::Span) {
+    let mut trigger_spans = trigger_spans.into_iter();
+    let origin = trigger_spans.next();
+    let span = tracing::info_span!(
+        parent: origin.as_ref().and_then(|s| s.id()),
+        "workflow",
+        workflow = %name,
+        dna_hash = %dna_hash,
+        agent = tracing::field::Empty,
+    );
+    if let Some(agent) = agent {
+        span.record("agent", tracing::field::display(agent));
+    }
+    for trigger_span in trigger_spans {
+        span.follows_from(&trigger_span);
+    }
+    let origin = origin.unwrap_or_else(|| span.clone());
+    (span, origin)
+}
+
 fn queue_consumer_dna_bound<Fut: 'static + Send + Future<Output = WorkflowResult<WorkComplete>>>(
     name: &str,
     dna_hash: Arc<DnaHash>,
-    tm: TaskManagerClient,
+    conductor: ConductorHandle,
     (tx, rx): (TriggerSender, TriggerReceiver),
     fut: impl 'static + Send + FnMut() -> Fut,
 ) {
     let workflow_name = name.to_string();
     let task_dna_hash = dna_hash.clone();
-    tm.add_dna_task_critical(name, dna_hash, {
-        move |stop| {
-            queue_consumer_main_task_impl(workflow_name, task_dna_hash, None, (tx, rx), stop, fut)
-        }
-    });
+    let slow_operations = conductor.slow_operations();
+    let workflow_runtime = conductor.workflow_runtime(&dna_hash);
+    conductor
+        .task_manager()
+        .add_dna_task_critical(name, dna_hash, {
+            move |stop| {
+                queue_consumer_main_task_impl(
+                    workflow_name,
+                    task_dna_hash,
+                    None,
+                    (tx, rx),
+                    stop,
+                    slow_operations,
+                    workflow_runtime,
+                    fut,
+                )
+            }
+        });
 }
 
 fn queue_consumer_cell_bound<
@@ -775,25 +878,31 @@ fn queue_consumer_cell_bound<
 >(
     name: &str,
     cell_id: CellId,
-    tm: TaskManagerClient,
+    conductor: ConductorHandle,
     (tx, rx): (TriggerSender, TriggerReceiver),
     fut: impl 'static + Send + FnMut() -> Fut,
 ) {
     let workflow_name = name.to_string();
     let dna_hash = cell_id.dna_hash().clone();
     let agent = cell_id.agent_pubkey().clone();
-    tm.add_cell_task_critical(name, cell_id, {
-        move |stop| {
-            queue_consumer_main_task_impl(
-                workflow_name,
-                Arc::new(dna_hash),
-                Some(agent),
-                (tx, rx),
-                stop,
-                fut,
-            )
-        }
-    });
+    let slow_operations = conductor.slow_operations();
+    let workflow_runtime = conductor.cell_workflow_runtime(&cell_id);
+    conductor
+        .task_manager()
+        .add_cell_task_critical(name, cell_id, {
+            move |stop| {
+                queue_consumer_main_task_impl(
+                    workflow_name,
+                    Arc::new(dna_hash),
+                    Some(agent),
+                    (tx, rx),
+                    stop,
+                    slow_operations,
+                    workflow_runtime,
+                    fut,
+                )
+            }
+        });
 }
 
 /// Does nothing.
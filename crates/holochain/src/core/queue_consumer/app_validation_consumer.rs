@@ -32,7 +32,7 @@ pub fn spawn_app_validation_consumer(
     queue_consumer_dna_bound(
         "app_validation_consumer",
         dna_hash.clone(),
-        conductor.task_manager(),
+        conductor.clone(),
         (tx.clone(), rx),
         move || {
             app_validation_workflow(
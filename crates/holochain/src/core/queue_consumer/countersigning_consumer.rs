@@ -20,7 +20,7 @@ pub(crate) fn spawn_countersigning_consumer(
     queue_consumer_cell_bound(
         "countersigning_consumer",
         cell_id.clone(),
-        conductor.task_manager(),
+        conductor.clone(),
         (tx.clone(), rx),
         move || {
             countersigning_workflow_fn(
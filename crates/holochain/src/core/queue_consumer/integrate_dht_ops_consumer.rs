@@ -1,29 +1,31 @@
 //! The workflow and queue consumer for DhtOp integration
 
 use super::*;
-use crate::conductor::manager::TaskManagerClient;
 use crate::core::workflow::integrate_dht_ops_workflow::integrate_dht_ops_workflow;
+use crate::core::workflow::integrate_dht_ops_workflow::FullTextIndexing;
 use holochain_types::db_cache::DhtDbQueryCache;
 
 /// Spawn the QueueConsumer for DhtOpIntegration workflow
 #[cfg_attr(
     feature = "instrument",
-    tracing::instrument(skip(env, trigger_receipt, tm, network, dht_query_cache))
+    tracing::instrument(skip(env, trigger_receipt, conductor, network, dht_query_cache, dna_def))
 )]
 pub fn spawn_integrate_dht_ops_consumer(
     dna_hash: Arc<DnaHash>,
     env: DbWrite<DbKindDht>,
     dht_query_cache: DhtDbQueryCache,
-    tm: TaskManagerClient,
+    conductor: ConductorHandle,
     trigger_receipt: TriggerSender,
     network: HolochainP2pDna,
+    dna_def: Arc<DnaDef>,
 ) -> TriggerSender {
     let (tx, rx) = TriggerSender::new();
+    let fulltext = FullTextIndexing::new(dna_def);
 
     super::queue_consumer_dna_bound(
         "integrate_dht_ops_consumer",
         dna_hash,
-        tm,
+        conductor,
         (tx.clone(), rx),
         move || {
             integrate_dht_ops_workflow(
@@ -31,6 +33,7 @@ pub fn spawn_integrate_dht_ops_consumer(
                 dht_query_cache.clone(),
                 trigger_receipt.clone(),
                 network.clone(),
+                fulltext.clone(),
             )
         },
     );
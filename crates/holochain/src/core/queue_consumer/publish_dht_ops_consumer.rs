@@ -24,7 +24,7 @@ pub fn spawn_publish_dht_ops_consumer(
     super::queue_consumer_cell_bound(
         "publish_dht_ops_consumer",
         cell_id.clone(),
-        conductor.task_manager(),
+        conductor.clone(),
         (tx.clone(), rx),
         move || {
             let conductor = conductor.clone();
@@ -32,10 +32,7 @@ pub fn spawn_publish_dht_ops_consumer(
             let env = env.clone();
             let agent = cell_id.agent_pubkey().clone();
             let network = network.clone();
-            let min_publish_interval = conductor
-                .get_config()
-                .conductor_tuning_params()
-                .min_publish_interval();
+            let min_publish_interval = conductor.conductor_tuning_params().min_publish_interval();
             async move {
                 if conductor.get_config().network.tuning_params.disable_publish {
                     Ok(WorkComplete::Complete)
@@ -29,7 +29,7 @@ pub fn spawn_sys_validation_consumer(
     super::queue_consumer_dna_bound(
         "sys_validation_consumer",
         space.dna_hash.clone(),
-        conductor.task_manager(),
+        conductor.clone(),
         (tx.clone(), rx),
         move || {
             if let Some(representative_agent) =
@@ -21,7 +21,7 @@ pub fn spawn_validation_receipt_consumer(
     super::queue_consumer_dna_bound(
         "validation_receipt_consumer",
         dna_hash.clone(),
-        conductor.task_manager(),
+        conductor.clone(),
         (tx.clone(), rx),
         move || {
             validation_receipt_workflow(
@@ -29,7 +29,7 @@ pub fn spawn_validation_receipt_consumer(
                 env.clone(),
                 network.clone(),
                 keystore.clone(),
-                conductor.running_cell_ids(),
+                conductor.running_authority_cell_ids(),
                 {
                     let conductor = conductor.clone();
                     move |block| {
@@ -1,7 +1,6 @@
 //! The queue consumer for the witnessing workflow.
 
 use super::*;
-use crate::conductor::manager::TaskManagerClient;
 use crate::core::workflow::witnessing_workflow::witnessing_workflow;
 use tracing::*;
 
@@ -9,7 +8,7 @@ use tracing::*;
 #[instrument(skip_all)]
 pub(crate) fn spawn_witnessing_consumer(
     space: Space,
-    task_manager: TaskManagerClient,
+    conductor: ConductorHandle,
     dna_network: HolochainP2pDna,
     trigger_sys: TriggerSender,
 ) -> TriggerSender {
@@ -18,7 +17,7 @@ pub(crate) fn spawn_witnessing_consumer(
     queue_consumer_dna_bound(
         "witnessing_consumer",
         space.dna_hash.clone(),
-        task_manager,
+        conductor,
         (tx.clone(), rx),
         move || witnessing_workflow(space.clone(), dna_network.clone(), trigger_sys.clone()),
     );
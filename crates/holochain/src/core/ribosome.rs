@@ -54,12 +54,14 @@ pub mod guest_callback;
 pub mod host_fn;
 pub mod real_ribosome;
 
+mod blocked_agents;
 mod check_clone_access;
 
 use crate::conductor::api::CellConductorHandle;
 use crate::conductor::api::CellConductorReadHandle;
 use crate::conductor::api::DpkiApi;
 use crate::conductor::api::ZomeCall;
+use crate::conductor::conductor::SignalSender;
 use crate::core::ribosome::guest_callback::entry_defs::EntryDefsResult;
 use crate::core::ribosome::guest_callback::genesis_self_check::v1::GenesisSelfCheckHostAccessV1;
 use crate::core::ribosome::guest_callback::genesis_self_check::v2::GenesisSelfCheckHostAccessV2;
@@ -69,7 +71,6 @@ use crate::core::ribosome::guest_callback::post_commit::PostCommitInvocation;
 use crate::core::ribosome::guest_callback::validate::ValidateInvocation;
 use crate::core::ribosome::guest_callback::validate::ValidateResult;
 use crate::core::ribosome::guest_callback::CallStream;
-use derive_more::Constructor;
 use error::RibosomeResult;
 use ghost_actor::dependencies::must_future::MustBoxFuture;
 use guest_callback::entry_defs::EntryDefsHostAccess;
@@ -77,6 +78,7 @@ use guest_callback::init::InitHostAccess;
 use guest_callback::post_commit::PostCommitHostAccess;
 use guest_callback::validate::ValidateHostAccess;
 use holo_hash::AgentPubKey;
+use holochain_conductor_api::conductor::ConductorTuningParams;
 use holochain_conductor_services::DpkiImpl;
 use holochain_keystore::MetaLairClient;
 use holochain_nonce::*;
@@ -92,7 +94,6 @@ use holochain_zome_types::block::BlockTargetId;
 use mockall::automock;
 use std::iter::Iterator;
 use std::sync::Arc;
-use tokio::sync::broadcast;
 
 use self::guest_callback::{
     entry_defs::EntryDefsInvocation, genesis_self_check::GenesisSelfCheckResult,
@@ -186,6 +187,21 @@ impl HostContext {
         }
     }
 
+    /// The clock which new timestamps are taken from: the clock of the source chain
+    /// of the call, which the conductor corrects for its skew, or else the system clock.
+    pub fn clock(&self) -> holochain_types::clock::Clock {
+        match self {
+            Self::ZomeCall(ZomeCallHostAccess { workspace, .. })
+            | Self::Init(InitHostAccess { workspace, .. })
+            | Self::PostCommit(PostCommitHostAccess { workspace, .. }) => workspace
+                .source_chain()
+                .as_ref()
+                .map(|source_chain| source_chain.clock().clone())
+                .unwrap_or_default(),
+            _ => Default::default(),
+        }
+    }
+
     /// Get the DPKI service if installed.
     pub fn maybe_dpki(&self) -> DpkiApi {
         match self.clone() {
@@ -235,7 +251,7 @@ impl HostContext {
     }
 
     /// Get the signal sender, panics if none was provided
-    pub fn signal_tx(&mut self) -> &mut broadcast::Sender<Signal> {
+    pub fn signal_tx(&mut self) -> &mut SignalSender {
         match self {
             Self::ZomeCall(ZomeCallHostAccess { signal_tx, .. })
             | Self::Init(InitHostAccess { signal_tx, .. })
@@ -260,6 +276,19 @@ impl HostContext {
             ),
         }
     }
+
+    /// Get the call zome handle, if one was provided
+    pub fn maybe_call_zome_handle(&self) -> Option<&CellConductorReadHandle> {
+        match self {
+            Self::ZomeCall(ZomeCallHostAccess {
+                call_zome_handle, ..
+            })
+            | Self::Init(InitHostAccess {
+                call_zome_handle, ..
+            }) => Some(call_zome_handle),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -304,7 +333,7 @@ impl FnComponents {
 pub enum ZomesToInvoke {
     /// All the integrity zomes.
     AllIntegrity,
-    /// All integrity and coordinator zomes.
+    /// All integrity and coordinator zomes, in init order.
     All,
     /// A single zome of unknown type.
     One(Zome),
@@ -369,14 +398,21 @@ pub trait Invocation: Clone + Send + Sync {
 }
 
 impl ZomeCallInvocation {
+    /// The call must be signed by its provenance, or by the last delegate of its
+    /// delegation chain, see [`ZomeCallDelegation`].
     pub async fn verify_signature(&self) -> RibosomeResult<ZomeCallAuthorization> {
+        let unsigned = ZomeCallUnsigned::from(ZomeCall::from(self.clone()));
+        let signer = if self.delegation.is_empty() {
+            &self.provenance
+        } else {
+            match self.verify_delegation(&unsigned).await? {
+                Ok(signer) => signer,
+                Err(reason) => return Ok(ZomeCallAuthorization::BadDelegation(reason)),
+            }
+        };
         Ok(
-            if self
-                .provenance
-                .verify_signature_raw(
-                    &self.signature,
-                    ZomeCallUnsigned::from(ZomeCall::from(self.clone())).data_to_sign()?,
-                )
+            if signer
+                .verify_signature_raw(&self.signature, unsigned.data_to_sign()?)
                 .await?
             {
                 ZomeCallAuthorization::Authorized
@@ -386,6 +422,42 @@ impl ZomeCallInvocation {
         )
     }
 
+    /// Check that each delegation of the chain allows the call and was signed by the
+    /// provenance or the delegate before it, and return the key which must have signed the call.
+    async fn verify_delegation(
+        &self,
+        unsigned: &ZomeCallUnsigned,
+    ) -> RibosomeResult<Result<&AgentPubKey, String>> {
+        let signer = match ZomeCallDelegation::allowed_signer(
+            &self.delegation,
+            unsigned,
+            Timestamp::now(),
+        ) {
+            Ok(signer) => signer,
+            Err(reason) => return Ok(Err(reason)),
+        };
+        let mut delegator = &self.provenance;
+        for (i, delegation) in self.delegation.iter().enumerate() {
+            let data = ZomeCallDelegation::data_to_sign(
+                &delegation.delegate,
+                &delegation.cell_id,
+                &delegation.functions,
+                &delegation.cap_secret,
+                delegation.expires_at,
+            )?;
+            if !delegator
+                .verify_signature_raw(&delegation.signature, data)
+                .await?
+            {
+                return Ok(Err(format!(
+                    "Delegation {i} of the chain wasn't signed by its delegator {delegator}"
+                )));
+            }
+            delegator = &delegation.delegate;
+        }
+        Ok(Ok(signer))
+    }
+
     /// to decide if a zome call grant is authorized:
     /// - we need to find a live (committed and not deleted) cap grant that matches the secret
     /// - if the live cap grant is for the current author the call is ALWAYS authorized ELSE
@@ -411,6 +483,38 @@ impl ZomeCallInvocation {
         })
     }
 
+    /// Every delegation of a delegated call must be backed by a live cap grant on the
+    /// source chain of the called cell which allows its delegate to make the call, so
+    /// that deleting the grant revokes the delegation.
+    pub async fn verify_delegation_grants(
+        &self,
+        host_access: &ZomeCallHostAccess,
+    ) -> RibosomeResult<ZomeCallAuthorization> {
+        let source_chain = host_access
+            .workspace
+            .source_chain()
+            .as_ref()
+            .expect("Must have source chain to make zome calls");
+        let check_function = (self.zome.zome_name().clone(), self.fn_name.clone());
+        for (i, delegation) in self.delegation.iter().enumerate() {
+            if source_chain
+                .valid_cap_grant(
+                    check_function.clone(),
+                    delegation.delegate.clone(),
+                    Some(delegation.cap_secret),
+                )
+                .await?
+                .is_none()
+            {
+                return Ok(ZomeCallAuthorization::BadDelegation(format!(
+                    "Delegation {i} of the chain has no live cap grant for its delegate {}",
+                    delegation.delegate
+                )));
+            }
+        }
+        Ok(ZomeCallAuthorization::Authorized)
+    }
+
     pub async fn verify_nonce(
         &self,
         host_access: &ZomeCallHostAccess,
@@ -427,7 +531,15 @@ impl ZomeCallInvocation {
                 .map_err(Box::new)?
             {
                 WitnessNonceResult::Fresh => ZomeCallAuthorization::Authorized,
-                nonce_result => ZomeCallAuthorization::BadNonce(format!("{:?}", nonce_result)),
+                WitnessNonceResult::Duplicate => {
+                    ZomeCallAuthorization::BadNonce(NonceRejection::Duplicate)
+                }
+                WitnessNonceResult::Expired => {
+                    ZomeCallAuthorization::BadNonce(NonceRejection::Expired)
+                }
+                WitnessNonceResult::Future => {
+                    ZomeCallAuthorization::BadNonce(NonceRejection::Future)
+                }
             },
         )
     }
@@ -457,6 +569,7 @@ impl ZomeCallInvocation {
     /// - the signature must be valid
     /// - the nonce must not have already been seen
     /// - the grant must be valid
+    /// - each delegation of the call must be backed by a live grant
     /// - the provenance must not have any active blocks against them right now
     ///
     /// The checks MUST be done in this order as witnessing the nonce is a write operation,
@@ -470,7 +583,12 @@ impl ZomeCallInvocation {
             ZomeCallAuthorization::Authorized => match self.verify_nonce(host_access).await? {
                 ZomeCallAuthorization::Authorized => match self.verify_grant(host_access).await? {
                     ZomeCallAuthorization::Authorized => {
-                        self.verify_blocked_provenance(host_access).await?
+                        match self.verify_delegation_grants(host_access).await? {
+                            ZomeCallAuthorization::Authorized => {
+                                self.verify_blocked_provenance(host_access).await?
+                            }
+                            unauthorized => unauthorized,
+                        }
                     }
                     unauthorized => unauthorized,
                 },
@@ -522,6 +640,9 @@ pub struct ZomeCallInvocation {
     pub nonce: Nonce256Bits,
     /// This call MUST NOT be respected after this time, in the opinion of the callee.
     pub expires_at: Timestamp,
+    /// The chain of delegations which allows the call to be signed by a key other
+    /// than the provenance. Empty if the provenance signed the call.
+    pub delegation: Vec<ZomeCallDelegation>,
 }
 
 impl Invocation for ZomeCallInvocation {
@@ -554,6 +675,7 @@ impl ZomeCallInvocation {
             signature,
             nonce,
             expires_at,
+            delegation,
         } = call;
         let zome = conductor_api
             .get_zome(cell_id.dna_hash(), &zome_name)
@@ -568,6 +690,7 @@ impl ZomeCallInvocation {
             signature,
             nonce,
             expires_at,
+            delegation,
         })
     }
 }
@@ -584,6 +707,7 @@ impl From<ZomeCallInvocation> for ZomeCall {
             signature,
             nonce,
             expires_at,
+            delegation,
         } = inv;
         Self {
             cell_id,
@@ -595,18 +719,49 @@ impl From<ZomeCallInvocation> for ZomeCall {
             signature,
             nonce,
             expires_at,
+            delegation,
         }
     }
 }
 
-#[derive(Clone, Constructor)]
+#[derive(Clone)]
 pub struct ZomeCallHostAccess {
     pub workspace: HostFnWorkspace,
     pub keystore: MetaLairClient,
     pub dpki: Option<DpkiImpl>,
     pub network: HolochainP2pDna,
-    pub signal_tx: broadcast::Sender<Signal>,
+    pub signal_tx: SignalSender,
     pub call_zome_handle: CellConductorReadHandle,
+    /// A dry-run call mustn't reach other agents, so the host functions which
+    /// write to the network are denied to it.
+    pub dry_run: bool,
+}
+
+impl ZomeCallHostAccess {
+    pub fn new(
+        workspace: HostFnWorkspace,
+        keystore: MetaLairClient,
+        dpki: Option<DpkiImpl>,
+        network: HolochainP2pDna,
+        signal_tx: SignalSender,
+        call_zome_handle: CellConductorReadHandle,
+    ) -> Self {
+        Self {
+            workspace,
+            keystore,
+            dpki,
+            network,
+            signal_tx,
+            call_zome_handle,
+            dry_run: false,
+        }
+    }
+
+    /// Deny the host functions which write to the network, for a dry-run call.
+    pub fn dry_run(mut self) -> Self {
+        self.dry_run = true;
+        self
+    }
 }
 
 impl std::fmt::Debug for ZomeCallHostAccess {
@@ -622,8 +777,49 @@ impl From<ZomeCallHostAccess> for HostContext {
 }
 
 impl From<&ZomeCallHostAccess> for HostFnAccess {
-    fn from(_: &ZomeCallHostAccess) -> Self {
-        Self::all()
+    fn from(access: &ZomeCallHostAccess) -> Self {
+        let mut permissions = Self::all();
+        if access.dry_run {
+            permissions.write_network = Permission::Deny;
+        }
+        permissions
+    }
+}
+
+/// The most that a `must_get_*` host function will wait for the network,
+/// whatever the zome asks for.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MustGetLimits {
+    /// The longest timeout allowed for a single attempt.
+    pub max_timeout: std::time::Duration,
+    /// The most retries allowed after the first attempt.
+    pub max_retries: u8,
+}
+
+impl MustGetLimits {
+    /// The timeout for each attempt, and how many attempts to make, for the
+    /// options a zome asked for.
+    pub fn bound(&self, options: MustGetOptions) -> (Option<std::time::Duration>, u8) {
+        let timeout = options
+            .timeout_ms
+            .map(|ms| std::time::Duration::from_millis(ms).min(self.max_timeout));
+        let attempts = options.retries.min(self.max_retries).saturating_add(1);
+        (timeout, attempts)
+    }
+}
+
+impl From<&ConductorTuningParams> for MustGetLimits {
+    fn from(tuning_params: &ConductorTuningParams) -> Self {
+        Self {
+            max_timeout: tuning_params.must_get_max_timeout(),
+            max_retries: tuning_params.must_get_max_retries(),
+        }
+    }
+}
+
+impl Default for MustGetLimits {
+    fn default() -> Self {
+        (&ConductorTuningParams::new()).into()
     }
 }
 
@@ -638,6 +834,9 @@ pub trait RibosomeT: Sized + std::fmt::Debug + Send + Sync {
 
     fn dna_file(&self) -> &DnaFile;
 
+    /// The conductor's caps on `must_get_*` network timeouts and retries.
+    fn must_get_limits(&self) -> MustGetLimits;
+
     async fn zome_info(&self, zome: Zome) -> RibosomeResult<ZomeInfo>;
 
     fn zomes_to_invoke(&self, zomes_to_invoke: ZomesToInvoke) -> Vec<Zome> {
@@ -648,11 +847,24 @@ pub trait RibosomeT: Sized + std::fmt::Debug + Send + Sync {
                 .iter()
                 .map(|(n, d)| (n.clone(), d.clone().erase_type()).into())
                 .collect(),
-            ZomesToInvoke::All => self
-                .dna_def()
-                .all_zomes()
-                .map(|(n, d)| (n.clone(), d.clone()).into())
-                .collect(),
+            // Only init invokes all zomes, so honour the declared init order.
+            // The order was checked when the ribosome was built.
+            ZomesToInvoke::All => match self.dna_def().init_order() {
+                Ok(zomes) => zomes
+                    .into_iter()
+                    .map(|(n, d)| (n.clone(), d.clone()).into())
+                    .collect(),
+                Err(err) => {
+                    tracing::warn!(
+                        ?err,
+                        "The init order of the DNA is invalid, so its zomes are invoked in declaration order"
+                    );
+                    self.dna_def()
+                        .all_zomes()
+                        .map(|(n, d)| (n.clone(), d.clone()).into())
+                        .collect()
+                }
+            },
             ZomesToInvoke::One(zome) => vec![zome],
             ZomesToInvoke::OneIntegrity(zome) => vec![zome.erase_type()],
             ZomesToInvoke::OneCoordinator(zome) => vec![zome.erase_type()],
@@ -864,6 +1076,32 @@ pub mod wasm_test {
         assert_eq!(fn_components.into_iter().collect::<Vec<String>>(), expected,);
     }
 
+    #[test]
+    fn must_get_options_are_capped_by_limits() {
+        let limits = crate::core::ribosome::MustGetLimits {
+            max_timeout: Duration::from_secs(10),
+            max_retries: 2,
+        };
+
+        assert_eq!(limits.bound(MustGetOptions::default()), (None, 1));
+        assert_eq!(
+            limits.bound(
+                MustGetOptions::default()
+                    .with_timeout_ms(500)
+                    .with_retries(1)
+            ),
+            (Some(Duration::from_millis(500)), 2)
+        );
+        assert_eq!(
+            limits.bound(
+                MustGetOptions::default()
+                    .with_timeout_ms(60_000)
+                    .with_retries(u8::MAX)
+            ),
+            (Some(Duration::from_secs(10)), 3)
+        );
+    }
+
     pub struct RibosomeTestFixture {
         pub conductor: SweetConductor,
         pub alice_pubkey: AgentPubKey,
@@ -0,0 +1,273 @@
+use crate::conductor::api::CellConductorReadHandle;
+use crate::core::ribosome::HostContext;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::{wasm_error, WasmError, WasmErrorInner, WasmHostError};
+use holochain_zome_types::block::BlockTargetId;
+use std::collections::HashMap;
+use wasmer::RuntimeError;
+
+/// Leaves the content of agents which are blocked out of the results of gets and
+/// link queries, for apps which set `filter_blocked_agents` in their manifest.
+///
+/// Whether an author is blocked is only looked up once, so that a single filter
+/// can be used for all the results of a host function call.
+pub struct BlockedAgentFilter {
+    conductor_handle: CellConductorReadHandle,
+    now: Timestamp,
+    blocked: HashMap<AgentPubKey, bool>,
+}
+
+impl BlockedAgentFilter {
+    /// Create a filter which checks authors against the block list of the conductor.
+    pub fn new(conductor_handle: CellConductorReadHandle) -> Self {
+        Self {
+            conductor_handle,
+            now: Timestamp::now(),
+            blocked: HashMap::new(),
+        }
+    }
+
+    /// Get the filter for the app of the calling cell.
+    ///
+    /// Returns `None` if the app doesn't filter blocked agents, or if the host
+    /// context has no access to the conductor, as in post commit callbacks.
+    pub async fn for_host_context(
+        host_context: &HostContext,
+    ) -> Result<Option<Self>, RuntimeError> {
+        let conductor_handle = match host_context.maybe_call_zome_handle() {
+            Some(conductor_handle) => conductor_handle,
+            None => return Ok(None),
+        };
+        let installed_app = conductor_handle
+            .find_app_containing_cell(conductor_handle.cell_id())
+            .await
+            .map_err(|conductor_error| -> RuntimeError {
+                wasm_error!(WasmErrorInner::Host(conductor_error.to_string())).into()
+            })?;
+        Ok(installed_app
+            .filter(|installed_app| installed_app.manifest().filter_blocked_agents())
+            .map(|_| Self::new(conductor_handle.clone())))
+    }
+
+    /// Whether the cell of an agent on the calling cell's DNA is currently blocked.
+    async fn is_blocked(&mut self, agent: &AgentPubKey) -> Result<bool, RuntimeError> {
+        if let Some(blocked) = self.blocked.get(agent) {
+            return Ok(*blocked);
+        }
+        let cell_id = CellId::new(
+            self.conductor_handle.cell_id().dna_hash().clone(),
+            agent.clone(),
+        );
+        let blocked = self
+            .conductor_handle
+            .is_blocked(BlockTargetId::Cell(cell_id), self.now)
+            .await
+            .map_err(|database_error| -> RuntimeError {
+                wasm_error!(WasmErrorInner::Host(database_error.to_string())).into()
+            })?;
+        self.blocked.insert(agent.clone(), blocked);
+        Ok(blocked)
+    }
+
+    /// Keep the items which weren't authored by a blocked agent.
+    async fn retain<T>(
+        &mut self,
+        items: Vec<T>,
+        author: impl Fn(&T) -> &AgentPubKey,
+    ) -> Result<Vec<T>, RuntimeError> {
+        let mut retained = Vec::with_capacity(items.len());
+        for item in items {
+            if !self.is_blocked(author(&item)).await? {
+                retained.push(item);
+            }
+        }
+        Ok(retained)
+    }
+
+    /// Leave out the records which were authored by blocked agents.
+    pub async fn filter_records(
+        &mut self,
+        records: Vec<Option<Record>>,
+    ) -> Result<Vec<Option<Record>>, RuntimeError> {
+        let mut filtered = Vec::with_capacity(records.len());
+        for record in records {
+            filtered.push(match record {
+                Some(record) if self.is_blocked(record.action().author()).await? => None,
+                record => record,
+            });
+        }
+        Ok(filtered)
+    }
+
+    /// Leave out the actions of blocked agents from the details of records and entries.
+    ///
+    /// The details of a record which was authored by a blocked agent, and of an entry
+    /// which was only created by blocked agents, are left out altogether.
+    pub async fn filter_details(
+        &mut self,
+        details: Vec<Option<Details>>,
+    ) -> Result<Vec<Option<Details>>, RuntimeError> {
+        let mut filtered = Vec::with_capacity(details.len());
+        for details in details {
+            filtered.push(self.details(details).await?);
+        }
+        Ok(filtered)
+    }
+
+    async fn details(&mut self, details: Option<Details>) -> Result<Option<Details>, RuntimeError> {
+        match details {
+            Some(Details::Record(mut record_details)) => {
+                if self
+                    .is_blocked(record_details.record.action().author())
+                    .await?
+                {
+                    return Ok(None);
+                }
+                record_details.deletes = self
+                    .retain(record_details.deletes, |a| a.action().author())
+                    .await?;
+                record_details.updates = self
+                    .retain(record_details.updates, |a| a.action().author())
+                    .await?;
+                Ok(Some(Details::Record(record_details)))
+            }
+            Some(Details::Entry(mut entry_details)) => {
+                let created =
+                    !entry_details.actions.is_empty() || !entry_details.rejected_actions.is_empty();
+                entry_details.actions = self
+                    .retain(entry_details.actions, |a| a.action().author())
+                    .await?;
+                entry_details.rejected_actions = self
+                    .retain(entry_details.rejected_actions, |a| a.action().author())
+                    .await?;
+                if created
+                    && entry_details.actions.is_empty()
+                    && entry_details.rejected_actions.is_empty()
+                {
+                    return Ok(None);
+                }
+                entry_details.deletes = self
+                    .retain(entry_details.deletes, |a| a.action().author())
+                    .await?;
+                entry_details.updates = self
+                    .retain(entry_details.updates, |a| a.action().author())
+                    .await?;
+                Ok(Some(Details::Entry(entry_details)))
+            }
+            None => Ok(None),
+        }
+    }
+
+    /// Leave out the links which were created by blocked agents.
+    pub async fn filter_links(
+        &mut self,
+        links: Vec<Vec<Link>>,
+    ) -> Result<Vec<Vec<Link>>, RuntimeError> {
+        let mut filtered = Vec::with_capacity(links.len());
+        for links in links {
+            filtered.push(self.retain(links, |link| &link.author).await?);
+        }
+        Ok(filtered)
+    }
+
+    /// Leave out the links which were created by blocked agents, and the deletes
+    /// of blocked agents on the remaining links.
+    pub async fn filter_link_details(
+        &mut self,
+        link_details: Vec<LinkDetails>,
+    ) -> Result<Vec<LinkDetails>, RuntimeError> {
+        let mut filtered = Vec::with_capacity(link_details.len());
+        for link_details in link_details {
+            let creates = self
+                .retain(link_details.into_inner(), |(create, _)| {
+                    create.action().author()
+                })
+                .await?;
+            let mut kept = Vec::with_capacity(creates.len());
+            for (create, deletes) in creates {
+                let deletes = self.retain(deletes, |a| a.action().author()).await?;
+                kept.push((create, deletes));
+            }
+            filtered.push(kept.into());
+        }
+        Ok(filtered)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conductor::api::MockCellConductorReadHandleT;
+    use ::fixt::prelude::*;
+    use futures::FutureExt;
+    use holochain_types::fixt::RecordFixturator;
+    use std::sync::Arc;
+
+    /// A filter for a conductor which has blocked a single agent, and expects
+    /// the block list to be checked `lookups` times.
+    fn filter(blocked: AgentPubKey, lookups: usize) -> BlockedAgentFilter {
+        let mut conductor_handle = MockCellConductorReadHandleT::new();
+        conductor_handle
+            .expect_cell_id()
+            .return_const(fixt!(CellId));
+        conductor_handle
+            .expect_is_blocked()
+            .times(lookups)
+            .returning(move |target_id, _| {
+                let is_blocked = matches!(
+                    target_id,
+                    BlockTargetId::Cell(cell_id) if *cell_id.agent_pubkey() == blocked
+                );
+                async move { Ok(is_blocked) }.boxed()
+            });
+        BlockedAgentFilter::new(Arc::new(conductor_handle))
+    }
+
+    fn link(author: AgentPubKey) -> Link {
+        Link {
+            author,
+            base: fixt!(AnyLinkableHash),
+            target: fixt!(AnyLinkableHash),
+            timestamp: Timestamp::now(),
+            zome_index: ZomeIndex(0),
+            link_type: LinkType(0),
+            tag: LinkTag::new(vec![]),
+            create_link_hash: fixt!(ActionHash),
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn records_of_blocked_agents_are_filtered() {
+        let blocked_record = fixt!(Record);
+        let record = fixt!(Record);
+        let mut filter = filter(blocked_record.action().author().clone(), 2);
+
+        // The block list is only checked once for each author.
+        let records = filter
+            .filter_records(vec![
+                Some(blocked_record.clone()),
+                Some(record.clone()),
+                None,
+                Some(blocked_record),
+            ])
+            .await
+            .unwrap();
+        assert_eq!(vec![None, Some(record), None, None], records);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn links_of_blocked_agents_are_filtered() {
+        let blocked = fixt!(AgentPubKey);
+        let kept = link(fixt!(AgentPubKey));
+        let mut filter = filter(blocked.clone(), 2);
+
+        let links = filter
+            .filter_links(vec![
+                vec![link(blocked.clone()), kept.clone()],
+                vec![link(blocked)],
+            ])
+            .await
+            .unwrap();
+        assert_eq!(vec![vec![kept], vec![]], links);
+    }
+}
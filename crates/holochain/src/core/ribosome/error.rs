@@ -31,6 +31,10 @@ pub enum RibosomeError {
     #[error("Referenced a zome that doesn't exist: Zome: {0}")]
     ZomeNotExists(ZomeName),
 
+    /// A zome call ran out of metering points and was terminated.
+    #[error("Zome call {0}:{1} was terminated after exceeding its execution budget of {2} wasm operations")]
+    MeteringLimitExceeded(ZomeName, FunctionName, u64),
+
     /// A ZomeFn was called by name that doesn't exist
     #[error("Attempted to call a zome function that doesn't exist: Zome: {0} Fn {1}")]
     ZomeFnNotExists(ZomeName, FunctionName),
@@ -94,6 +98,10 @@ pub enum RibosomeError {
     #[error("Host function {2} cannot be called from zome function {1} in zome {0}")]
     HostFnPermissions(ZomeName, FunctionName, String),
 
+    /// A validation callback called a Host function whose result differs between nodes.
+    #[error("Host function {2} is not available to validation callback {1} in zome {0} because it is non-deterministic")]
+    NonDeterministicHostFn(ZomeName, FunctionName, String),
+
     /// An attempt to was made to perform a clone operation on a cell that is not provisioned or belongs to another app.
     #[error("Invalid request to modify a cell which belongs to another app")]
     InvalidCloneTarget,
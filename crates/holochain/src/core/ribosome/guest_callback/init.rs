@@ -1,4 +1,5 @@
 use crate::conductor::api::CellConductorReadHandle;
+use crate::conductor::conductor::SignalSender;
 use crate::core::ribosome::FnComponents;
 use crate::core::ribosome::HostContext;
 use crate::core::ribosome::Invocation;
@@ -11,7 +12,6 @@ use holochain_p2p::HolochainP2pDna;
 use holochain_serialized_bytes::prelude::*;
 use holochain_state::host_fn_workspace::HostFnWorkspace;
 use holochain_types::prelude::*;
-use tokio::sync::broadcast;
 
 #[derive(Debug, Clone)]
 pub struct InitInvocation {
@@ -30,7 +30,7 @@ pub struct InitHostAccess {
     pub keystore: MetaLairClient,
     pub dpki: Option<DpkiImpl>,
     pub network: HolochainP2pDna,
-    pub signal_tx: broadcast::Sender<Signal>,
+    pub signal_tx: SignalSender,
     pub call_zome_handle: CellConductorReadHandle,
 }
 
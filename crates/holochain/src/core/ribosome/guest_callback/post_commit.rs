@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use crate::conductor::conductor::SignalSender;
 use crate::conductor::ConductorHandle;
 use crate::core::ribosome::FnComponents;
 use crate::core::ribosome::HostContext;
@@ -13,7 +14,6 @@ use holochain_serialized_bytes::prelude::*;
 use holochain_state::host_fn_workspace::HostFnWorkspace;
 use holochain_state::host_fn_workspace::SourceChainWorkspace;
 use holochain_types::prelude::*;
-use tokio::sync::broadcast;
 
 pub const POST_COMMIT_CHANNEL_BOUND: usize = 100;
 pub const POST_COMMIT_CONCURRENT_LIMIT: usize = 5;
@@ -35,7 +35,7 @@ pub struct PostCommitHostAccess {
     pub workspace: HostFnWorkspace,
     pub keystore: MetaLairClient,
     pub network: HolochainP2pDna,
-    pub signal_tx: broadcast::Sender<Signal>,
+    pub signal_tx: SignalSender,
 }
 
 impl std::fmt::Debug for PostCommitHostAccess {
@@ -91,7 +91,7 @@ pub async fn send_post_commit(
     keystore: MetaLairClient,
     actions: Vec<SignedActionHashed>,
     zomes: Vec<CoordinatorZome>,
-    signal_tx: broadcast::Sender<Signal>,
+    signal_tx: SignalSender,
 ) -> Result<(), tokio::sync::mpsc::error::SendError<()>> {
     let cell_id = workspace.source_chain().cell_id();
 
@@ -10,6 +10,8 @@ use holochain_serialized_bytes::prelude::*;
 use holochain_state::host_fn_workspace::HostFnWorkspaceRead;
 use holochain_types::prelude::*;
 use holochain_zome_types::op::Op;
+use std::sync::atomic::AtomicU64;
+use std::sync::atomic::Ordering;
 use std::sync::Arc;
 
 #[derive(Clone, Debug)]
@@ -19,16 +21,72 @@ pub struct ValidateInvocation {
     zomes_to_invoke: ZomesToInvoke,
     /// The serialized arguments to the callback function.
     data: Arc<ExternIO>,
+    /// The timestamp of the action the op was produced from.
+    action_timestamp: Timestamp,
+    /// A seed derived from the op, the same for every validator.
+    random_seed: [u8; 32],
 }
 
 impl ValidateInvocation {
     pub fn new(zomes_to_invoke: ZomesToInvoke, data: &Op) -> Result<Self, SerializedBytesError> {
+        let action_timestamp = data.timestamp();
         let data = Arc::new(ExternIO::encode(data)?);
+        let mut random_seed = [0; 32];
+        random_seed.copy_from_slice(&holo_hash::encode::blake2b_256(data.as_bytes()));
         Ok(Self {
             zomes_to_invoke,
             data,
+            action_timestamp,
+            random_seed,
         })
     }
+
+    /// The deterministic inputs to hand to the validate callback for this op.
+    pub fn validation_context(&self) -> ValidationContext {
+        ValidationContext::new(self.action_timestamp, self.random_seed)
+    }
+}
+
+/// Inputs to the validate callback that every node validating the same op agrees on.
+///
+/// These back the `validation_timestamp` and `deterministic_random_bytes` host functions,
+/// which replace `sys_time` and `random_bytes` during validation.
+#[derive(Clone, Debug)]
+pub struct ValidationContext {
+    /// The timestamp of the action being validated.
+    pub action_timestamp: Timestamp,
+    /// The seed for deterministic random bytes.
+    pub random_seed: [u8; 32],
+    /// The number of blocks of random bytes handed out so far, so that
+    /// repeated calls in the same callback return different bytes.
+    random_blocks: Arc<AtomicU64>,
+}
+
+impl ValidationContext {
+    pub fn new(action_timestamp: Timestamp, random_seed: [u8; 32]) -> Self {
+        Self {
+            action_timestamp,
+            random_seed,
+            random_blocks: Default::default(),
+        }
+    }
+
+    /// Produce the next `len` pseudo-random bytes.
+    ///
+    /// Each 32 byte block is the hash of the seed and the block's index, so a
+    /// callback that makes the same calls always receives the same bytes.
+    pub fn random_bytes(&self, len: usize) -> Vec<u8> {
+        let blocks = len.div_ceil(32) as u64;
+        let first_block = self.random_blocks.fetch_add(blocks, Ordering::Relaxed);
+        let mut bytes = Vec::with_capacity(blocks as usize * 32);
+        for block in first_block..first_block + blocks {
+            let mut input = self.random_seed.to_vec();
+            input.extend_from_slice(&block.to_le_bytes());
+            bytes.extend(holo_hash::encode::blake2b_256(&input));
+        }
+        bytes.truncate(len);
+        bytes
+    }
 }
 
 #[derive(Clone, Constructor)]
@@ -37,6 +95,7 @@ pub struct ValidateHostAccess {
     pub network: GenericNetwork,
     pub dpki: DpkiApi,
     pub is_inline: bool,
+    pub validation_context: ValidationContext,
 }
 
 impl std::fmt::Debug for ValidateHostAccess {
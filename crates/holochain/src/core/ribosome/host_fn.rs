@@ -1,5 +1,7 @@
 use super::CallContext;
+use super::HostContext;
 use super::RibosomeT;
+use super::ValidateHostAccess;
 use holochain_types::prelude::*;
 use holochain_wasmer_host::prelude::*;
 use std::sync::Arc;
@@ -19,6 +21,54 @@ impl KeyRefExt for XSalsa20Poly1305KeyRef {
     }
 }
 
+/// The timeout per attempt and the number of attempts that a `must_get_*`
+/// call should make, given the options the zome asked for.
+///
+/// Validation that only reads local data gets a single attempt without a
+/// timeout, as waiting longer can not make anything appear.
+pub(crate) fn must_get_bounds(
+    ribosome: &impl RibosomeT,
+    call_context: &CallContext,
+    options: MustGetOptions,
+) -> (Option<std::time::Duration>, u8) {
+    match call_context.host_context {
+        HostContext::Validate(ValidateHostAccess {
+            is_inline: false, ..
+        }) => (None, 1),
+        _ => ribosome.must_get_limits().bound(options),
+    }
+}
+
+/// Call `lookup` until `found` accepts its result, at most `attempts` times,
+/// cutting every attempt short after `timeout`.
+///
+/// Returns the last result if nothing was accepted, or `None` if the last
+/// attempt timed out.
+pub(crate) async fn must_get_with_retries<T, Fut>(
+    (timeout, attempts): (Option<std::time::Duration>, u8),
+    found: impl Fn(&T) -> bool,
+    mut lookup: impl FnMut() -> Fut,
+) -> Result<Option<T>, wasmer::RuntimeError>
+where
+    Fut: std::future::Future<Output = Result<T, wasmer::RuntimeError>>,
+{
+    let mut result = None;
+    for attempt in 1..=attempts {
+        result = match timeout {
+            Some(timeout) => match tokio::time::timeout(timeout, lookup()).await {
+                Ok(result) => Some(result?),
+                Err(_) => None,
+            },
+            None => Some(lookup().await?),
+        };
+        if result.as_ref().is_some_and(&found) {
+            break;
+        }
+        tracing::debug!(attempt, attempts, "must_get dependency not found yet");
+    }
+    Ok(result)
+}
+
 pub struct HostFnApi<Ribosome: RibosomeT> {
     ribosome: Arc<Ribosome>,
     call_context: Arc<CallContext>,
@@ -100,6 +150,13 @@ host_fn_api_impls! {
     // Returns ActionHash of the newly created record.
     fn create (zt::entry::CreateInput) -> holo_hash::ActionHash;
 
+    // Create many records with one signing round-trip to the keystore.
+    fn create_entries (Vec<zt::entry::CreateInput>) -> Vec<holo_hash::ActionHash>;
+
+    // Split a blob into chunk entries and create them with the manifest entry which
+    // lists them. Returns the ActionHash of the manifest.
+    fn create_chunked (zt::entry::CreateChunkedInput) -> holo_hash::ActionHash;
+
     fn x_salsa20_poly1305_shared_secret_create_random(
         Option<zt::x_salsa20_poly1305::key_ref::XSalsa20Poly1305KeyRef>
     ) -> zt::x_salsa20_poly1305::key_ref::XSalsa20Poly1305KeyRef;
@@ -134,15 +191,27 @@ host_fn_api_impls! {
     // Recipient, Sender, Encrypted data.
     fn ed_25519_x_salsa20_poly1305_decrypt (holochain_zome_types::x_salsa20_poly1305::Ed25519XSalsa20Poly1305Decrypt) -> holochain_zome_types::x_salsa20_poly1305::data::XSalsa20Poly1305Data;
 
+    // Prove authorship of a private entry without revealing its content.
+    fn create_private_entry_proof (zt::disclosure::PrivateEntryProofInput) -> zt::disclosure::PrivateEntryProof;
+
     // Create a link between two entries.
     fn create_link (zt::link::CreateLinkInput) -> holo_hash::ActionHash;
 
+    // Create many links with one signing round-trip to the keystore.
+    fn create_links (Vec<zt::link::CreateLinkInput>) -> Vec<holo_hash::ActionHash>;
+
     // Delete an entry.
     fn delete (zt::entry::DeleteInput) -> holo_hash::ActionHash;
 
     // Delete a CreateLink record.
     fn delete_link (zt::link::DeleteLinkInput) -> holo_hash::ActionHash;
 
+    // Pseudo-random bytes seeded by the op being validated, the same for every validator.
+    fn deterministic_random_bytes (u32) -> zt::bytes::Bytes;
+
+    // Record an app-defined measurement in the conductor's metrics.
+    fn emit_metric (zt::metric::AppMetric) -> ();
+
     // Action hash of the newly committed record.
     // Emit a Signal::App to subscribers on the interface
     fn emit_signal (zt::signal::AppSignal) -> ();
@@ -154,8 +223,15 @@ host_fn_api_impls! {
     // Attempt to get a live entry from the cascade.
     fn get (Vec<zt::entry::GetInput>) -> Vec<Option<zt::record::Record>>;
 
+    // Read a blob, or a range of it, from its chunk entries, which are fetched in
+    // parallel and checked against the hashes in its manifest.
+    fn get_chunked (zt::entry::GetChunkedInput) -> Option<zt::bytes::Bytes>;
+
     fn get_agent_activity (zt::agent_activity::GetAgentActivityInput) -> zt::query::AgentActivity;
 
+    // When an agent last announced its presence to its neighborhood.
+    fn get_agent_presence (AgentPubKey) -> Option<zt::presence::AgentPresence>;
+
     // Query agent key lineage for the provided key.
     fn get_agent_key_lineage (AgentPubKey) -> Vec<AgentPubKey>;
 
@@ -168,6 +244,9 @@ host_fn_api_impls! {
 
     fn count_links(zt::query::LinkQuery) -> usize;
 
+    // Get the creation times of links without the links themselves.
+    fn get_links_summary(zt::query::LinkQuery) -> zt::query::LinksSummary;
+
     // Hash data on the host.
     fn hash (zt::hash::HashInput) -> zt::hash::HashOutput;
 
@@ -179,23 +258,47 @@ host_fn_api_impls! {
     // Retreive a record from the DHT or short circuit.
     fn must_get_valid_record (zt::entry::MustGetValidRecordInput) -> Record;
 
+    // Same as must_get_valid_record, with control over how long to wait for the network.
+    fn must_get_valid_record_with_options (zt::entry::MustGetValidRecordWithOptionsInput) -> Record;
+
     // Retreive a entry from the DHT or short circuit.
     fn must_get_entry (zt::entry::MustGetEntryInput) -> EntryHashed;
 
+    // Same as must_get_entry, with control over how long to wait for the network.
+    fn must_get_entry_with_options (zt::entry::MustGetEntryWithOptionsInput) -> EntryHashed;
+
     // Retrieve an action from the DHT or short circuit.
     fn must_get_action (zt::entry::MustGetActionInput) -> SignedActionHashed;
 
+    // Same as must_get_action, with control over how long to wait for the network.
+    fn must_get_action_with_options (zt::entry::MustGetActionWithOptionsInput) -> SignedActionHashed;
+
     fn must_get_agent_activity (zt::chain::MustGetAgentActivityInput) -> Vec<zt::op::RegisterAgentActivity>;
 
     // Query the source chain for data.
     fn query (zt::query::ChainQueryFilter) -> Vec<Record>;
 
+    // Query the source chain of another cell of the same agent.
+    fn query_cell (zt::call::QueryCellInput) -> Vec<Record>;
+
+    // Query the text of the entries held locally.
+    fn query_local_fulltext (zt::fulltext::FullTextQuery) -> Vec<holo_hash::EntryHash>;
+
     // the length of random bytes to create
     fn random_bytes (u32) -> zt::bytes::Bytes;
 
     // Remotely signal many agents without waiting for responses
     fn send_remote_signal (zt::signal::RemoteSignal) -> ();
 
+    // Remotely signal many agents and report delivery status per agent
+    fn send_remote_signal_acked (zt::signal::AckedRemoteSignal) -> Vec<zt::signal::RemoteSignalDelivery>;
+
+    // Queue a remote call or signal until the recipient can be reached
+    fn queue_remote_message (zt::outbox::QueuedRemoteMessage) -> zt::outbox::OutboxMessageId;
+
+    // Look up the delivery of queued remote messages
+    fn outbox_delivery (Vec<zt::outbox::OutboxMessageId>) -> Vec<Option<zt::outbox::OutboxDelivery>>;
+
     // @todo
     #[cfg(feature = "unstable-functions")]
     fn schedule (String) -> ();
@@ -217,6 +320,9 @@ host_fn_api_impls! {
     // Current system time, in the opinion of the host, as a `Duration`.
     fn sys_time (()) -> zt::timestamp::Timestamp;
 
+    // The timestamp of the action being validated.
+    fn validation_timestamp (()) -> zt::timestamp::Timestamp;
+
     // Unblock some previously blocked agent.
     #[cfg(feature = "unstable-functions")]
     fn unblock_agent (zt::block::BlockAgentInput) -> ();
@@ -226,6 +332,9 @@ host_fn_api_impls! {
 
     fn verify_signature (zt::signature::VerifySignature) -> bool;
 
+    // Check the signatures of a proof about a private entry.
+    fn verify_private_entry_proof (zt::disclosure::PrivateEntryProof) -> bool;
+
     // The zome and agent info are constants specific to the current zome and chain.
     // All the information is provided by core so there is no input value.
     // These are constant for the lifetime of a zome call.
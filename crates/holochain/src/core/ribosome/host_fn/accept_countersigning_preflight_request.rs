@@ -29,7 +29,7 @@ pub fn accept_countersigning_preflight_request<'a>(
                 return Ok(PreflightRequestAcceptance::Invalid(e.to_string()));
             }
             tokio_helper::block_forever_on(async move {
-                if (Timestamp::now() + SESSION_TIME_FUTURE_MAX).unwrap_or(Timestamp::MAX)
+                if (call_context.host_context.clock().now() + SESSION_TIME_FUTURE_MAX).unwrap_or(Timestamp::MAX)
                     < *input.session_times.start()
                 {
                     return Ok(PreflightRequestAcceptance::UnacceptableFutureStart);
@@ -135,6 +135,7 @@ pub mod wasm_test {
                     agent_pubkey: alice_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    page: None,
                 },
             )
             .await;
@@ -146,6 +147,7 @@ pub mod wasm_test {
                     agent_pubkey: alice_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    page: None,
                 },
             )
             .await;
@@ -157,6 +159,7 @@ pub mod wasm_test {
                     agent_pubkey: bob_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    page: None,
                 },
             )
             .await;
@@ -168,6 +171,7 @@ pub mod wasm_test {
                     agent_pubkey: bob_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    page: None,
                 },
             )
             .await;
@@ -242,6 +246,7 @@ pub mod wasm_test {
                     agent_pubkey: alice_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    page: None,
                 },
             )
             .await;
@@ -253,6 +258,7 @@ pub mod wasm_test {
                     agent_pubkey: alice_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    page: None,
                 },
             )
             .await;
@@ -264,6 +270,7 @@ pub mod wasm_test {
                     agent_pubkey: bob_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    page: None,
                 },
             )
             .await;
@@ -275,6 +282,7 @@ pub mod wasm_test {
                     agent_pubkey: bob_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    page: None,
                 },
             )
             .await;
@@ -673,6 +681,7 @@ pub mod wasm_test {
                     agent_pubkey: alice_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    page: None,
                 },
             )
             .await;
@@ -754,6 +763,7 @@ pub mod wasm_test {
                     agent_pubkey: alice_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    page: None,
                 },
             )
             .await;
@@ -776,6 +786,7 @@ pub mod wasm_test {
                     agent_pubkey: bob_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    page: None,
                 },
             )
             .await;
@@ -984,6 +995,7 @@ pub mod wasm_test {
                     agent_pubkey: alice_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    page: None,
                 },
             )
             .await;
@@ -1065,6 +1077,7 @@ pub mod wasm_test {
                     agent_pubkey: alice_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    page: None,
                 },
             )
             .await;
@@ -1087,6 +1100,7 @@ pub mod wasm_test {
                     agent_pubkey: bob_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    page: None,
                 },
             )
             .await;
@@ -1138,6 +1152,7 @@ pub mod wasm_test {
                         agent_pubkey: bob_pubkey.clone(),
                         chain_query_filter: ChainQueryFilter::new(),
                         activity_request: ActivityRequest::Full,
+                        page: None,
                     },
                 )
                 .await;
@@ -1204,6 +1219,7 @@ pub mod wasm_test {
                     agent_pubkey: alice_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    page: None,
                 },
             )
             .await;
@@ -1216,6 +1232,7 @@ pub mod wasm_test {
                     agent_pubkey: bob_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    page: None,
                 },
             )
             .await;
@@ -1242,6 +1259,7 @@ pub mod wasm_test {
                     agent_pubkey: alice_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    page: None,
                 },
             )
             .await;
@@ -1254,6 +1272,7 @@ pub mod wasm_test {
                     agent_pubkey: bob_pubkey.clone(),
                     chain_query_filter: ChainQueryFilter::new(),
                     activity_request: ActivityRequest::Full,
+                    page: None,
                 },
             )
             .await;
@@ -1314,6 +1333,7 @@ pub mod wasm_test {
                         agent_pubkey: alice_pubkey.clone(),
                         chain_query_filter: ChainQueryFilter::new(),
                         activity_request: ActivityRequest::Full,
+                        page: None,
                     },
                 )
                 .await;
@@ -1326,6 +1346,7 @@ pub mod wasm_test {
                         agent_pubkey: bob_pubkey.clone(),
                         chain_query_filter: ChainQueryFilter::new(),
                         activity_request: ActivityRequest::Full,
+                        page: None,
                     },
                 )
                 .await;
@@ -1408,6 +1429,7 @@ pub mod wasm_test {
                         agent_pubkey: alice_pubkey.clone(),
                         chain_query_filter: ChainQueryFilter::new(),
                         activity_request: ActivityRequest::Full,
+                        page: None,
                     },
                 )
                 .await;
@@ -1421,6 +1443,7 @@ pub mod wasm_test {
                         agent_pubkey: bob_pubkey.clone(),
                         chain_query_filter: ChainQueryFilter::new(),
                         activity_request: ActivityRequest::Full,
+                        page: None,
                     },
                 )
                 .await;
@@ -1507,6 +1530,7 @@ pub mod wasm_test {
                         agent_pubkey: alice_pubkey.clone(),
                         chain_query_filter: ChainQueryFilter::new(),
                         activity_request: ActivityRequest::Full,
+                        page: None,
                     },
                 )
                 .await;
@@ -1519,6 +1543,7 @@ pub mod wasm_test {
                         agent_pubkey: bob_pubkey.clone(),
                         chain_query_filter: ChainQueryFilter::new(),
                         activity_request: ActivityRequest::Full,
+                        page: None,
                     },
                 )
                 .await;
@@ -1542,6 +1567,7 @@ pub mod wasm_test {
                         agent_pubkey: alice_pubkey.clone(),
                         chain_query_filter: ChainQueryFilter::new(),
                         activity_request: ActivityRequest::Full,
+                        page: None,
                     },
                 )
                 .await;
@@ -1554,6 +1580,7 @@ pub mod wasm_test {
                         agent_pubkey: bob_pubkey.clone(),
                         chain_query_filter: ChainQueryFilter::new(),
                         activity_request: ActivityRequest::Full,
+                        page: None,
                     },
                 )
                 .await;
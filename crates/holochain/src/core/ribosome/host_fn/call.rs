@@ -39,7 +39,7 @@ pub fn call(
                         },
                     )
                     | (
-                        CallTarget::NetworkAgent(_),
+                        CallTarget::NetworkAgent(_) | CallTarget::NetworkCell(_),
                         HostFnAccess {
                             write_network: Permission::Allow,
                             agent_info: Permission::Allow,
@@ -111,6 +111,21 @@ pub fn call(
                                     Err(e) => Ok(ZomeCallResponse::NetworkError(e.to_string())),
                                 }
                             }
+                            // The conductor makes the call, as it's made through another cell.
+                            CallTarget::NetworkCell(target_cell) => call_context
+                                .host_context()
+                                .call_zome_handle()
+                                .call_remote_cell(
+                                    target_cell,
+                                    zome_name,
+                                    fn_name,
+                                    cap_secret,
+                                    payload,
+                                )
+                                .await
+                                .map_err(|e| -> RuntimeError {
+                                    wasm_error!(WasmErrorInner::Host(e.to_string())).into()
+                                }),
                             CallTarget::ConductorCell(target_cell) => {
                                 let cell_id_result: Result<CellId, RuntimeError> = match target_cell
                                 {
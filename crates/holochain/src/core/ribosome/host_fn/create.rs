@@ -49,18 +49,7 @@ pub fn create<'a>(
                     let entry_hash = EntryHash::with_data_sync(&entry);
 
                     // extract the entry defs for a zome
-                    let entry_type = match entry_location {
-                        EntryDefLocation::App(AppEntryDefLocation {
-                            zome_index,
-                            entry_def_index,
-                        }) => {
-                            let app_entry_def =
-                                AppEntryDef::new(entry_def_index, zome_index, entry_visibility);
-                            EntryType::App(app_entry_def)
-                        }
-                        EntryDefLocation::CapGrant => EntryType::CapGrant,
-                        EntryDefLocation::CapClaim => EntryType::CapClaim,
-                    };
+                    let entry_type = entry_type(entry_location, entry_visibility);
 
                     // build an action for the entry being committed
                     let action_builder = builder::Create {
@@ -102,6 +91,25 @@ pub fn create<'a>(
     }
 }
 
+/// The entry type an action should record for an entry created at this location.
+pub(crate) fn entry_type(
+    entry_location: EntryDefLocation,
+    entry_visibility: EntryVisibility,
+) -> EntryType {
+    match entry_location {
+        EntryDefLocation::App(AppEntryDefLocation {
+            zome_index,
+            entry_def_index,
+        }) => EntryType::App(AppEntryDef::new(
+            entry_def_index,
+            zome_index,
+            entry_visibility,
+        )),
+        EntryDefLocation::CapGrant => EntryType::CapGrant,
+        EntryDefLocation::CapClaim => EntryType::CapClaim,
+    }
+}
+
 #[cfg(test)]
 #[cfg(feature = "slow_tests")]
 pub mod wasm_test {
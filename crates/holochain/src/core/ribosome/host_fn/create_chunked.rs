@@ -0,0 +1,169 @@
+use super::create::entry_type;
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+/// split a blob into chunk entries and create them with the manifest which lists them
+pub fn create_chunked(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: CreateChunkedInput,
+) -> Result<ActionHash, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            write_workspace: Permission::Allow,
+            ..
+        } => {
+            let CreateChunkedInput {
+                chunk_location,
+                manifest_location,
+                entry_visibility,
+                bytes,
+                chunk_size,
+                chain_top_ordering,
+            } = input;
+            if chunk_size == 0 || chunk_size > MAX_CHUNK_SIZE {
+                return Err(wasm_error!(WasmErrorInner::Guest(format!(
+                    "Chunks must be between 1 and {} bytes",
+                    MAX_CHUNK_SIZE
+                )))
+                .into());
+            }
+
+            let app_entry = |entry: Result<Entry, EntryError>| {
+                entry.map_err(|e| wasm_error!(WasmErrorInner::Guest(e.to_string())))
+            };
+            let mut items = Vec::new();
+            let mut chunks = Vec::new();
+            for chunk in bytes.chunks(chunk_size as usize) {
+                let entry = app_entry(Entry::app_fancy(Chunk(chunk.to_vec())))?;
+                let entry_hash = EntryHash::with_data_sync(&entry);
+                chunks.push(entry_hash.clone());
+                items.push((
+                    builder::Create {
+                        entry_type: entry_type(chunk_location.clone(), entry_visibility),
+                        entry_hash,
+                    },
+                    Some(entry),
+                    chain_top_ordering,
+                ));
+            }
+            let manifest = app_entry(Entry::app_fancy(ChunkManifest {
+                size: bytes.len() as u64,
+                chunk_size,
+                chunks,
+            }))?;
+            items.push((
+                builder::Create {
+                    entry_type: entry_type(manifest_location, entry_visibility),
+                    entry_hash: EntryHash::with_data_sync(&manifest),
+                },
+                Some(manifest),
+                chain_top_ordering,
+            ));
+
+            // The chunks and the manifest are committed together, so a manifest is
+            // never committed without its chunks.
+            let mut hashes = tokio_helper::block_forever_on(async move {
+                call_context
+                    .host_context
+                    .workspace_write()
+                    .source_chain()
+                    .as_ref()
+                    .expect("Must have source chain if write_workspace access is given")
+                    .put_weightless_batch(items)
+                    .await
+                    .map_err(|source_chain_error| -> RuntimeError {
+                        wasm_error!(WasmErrorInner::Host(source_chain_error.to_string())).into()
+                    })
+            })?;
+            Ok(hashes.pop().expect("The manifest is always created"))
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "create_chunked".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sweettest::*;
+    use hdk::prelude::*;
+    use holochain_types::inline_zome::InlineZomeSet;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn blobs_are_read_back_from_their_chunks() {
+        holochain_trace::test_run();
+
+        let zome = InlineIntegrityZome::new_unique(
+            vec![
+                EntryDef::default_from_id("chunk"),
+                EntryDef::default_from_id("manifest"),
+            ],
+            0,
+        )
+        .function("create_blob", |api, bytes: Bytes| {
+            let mut input = CreateChunkedInput::new(
+                InlineZomeSet::get_entry_location(&api, EntryDefIndex(0)),
+                InlineZomeSet::get_entry_location(&api, EntryDefIndex(1)),
+                EntryVisibility::Public,
+                bytes.into_vec(),
+            );
+            input.chunk_size = 10;
+            Ok(api.create_chunked(input)?)
+        })
+        .function(
+            "read_blob",
+            |api, (hash, range): (ActionHash, Option<std::ops::Range<u64>>)| {
+                let mut input = GetChunkedInput::new(hash, GetOptions::default());
+                input.range = range;
+                Ok(api.get_chunked(input)?)
+            },
+        )
+        .function("query", |api, ()| Ok(api.query(ChainQueryFilter::new())?));
+        let (dna_file, _, _) = SweetDnaFile::unique_from_inline_zomes(("zome", zome)).await;
+
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let app = conductor.setup_app("app", [&dna_file]).await.unwrap();
+        let (cell,) = app.into_tuple();
+        let zome = cell.zome("zome");
+
+        let blob: Vec<u8> = (0..25).collect();
+        let manifest: ActionHash = conductor
+            .call(&zome, "create_blob", Bytes::from(blob.clone()))
+            .await;
+        // - Three chunks and the manifest are committed.
+        let records: Vec<Record> = conductor.call(&zome, "query", ()).await;
+        assert_eq!(&manifest, records.last().unwrap().action_address());
+        assert_eq!(
+            4,
+            records
+                .iter()
+                .filter(|r| matches!(r.action().entry_type(), Some(EntryType::App(_))))
+                .count()
+        );
+
+        for (range, expected) in [
+            (None, blob.clone()),
+            (Some(8..22), blob[8..22].to_vec()),
+            (Some(20..100), blob[20..].to_vec()),
+            (Some(30..40), vec![]),
+        ] {
+            let read: Option<Bytes> = conductor
+                .call(&zome, "read_blob", (manifest.clone(), range))
+                .await;
+            assert_eq!(Some(expected), read.map(Bytes::into_vec));
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use super::create::entry_type;
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+/// create many records with a single signing round-trip
+pub fn create_entries(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    inputs: Vec<CreateInput>,
+) -> Result<Vec<ActionHash>, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            write_workspace: Permission::Allow,
+            ..
+        } => {
+            let items = inputs
+                .into_iter()
+                .map(|input| {
+                    let CreateInput {
+                        entry_location,
+                        entry_visibility,
+                        entry,
+                        chain_top_ordering,
+                    } = input;
+
+                    // A countersigned entry has to be the only write in its zome call.
+                    if let Entry::CounterSign(_, _) = entry {
+                        return Err(wasm_error!(WasmErrorInner::Host(
+                            "Countersigned entries can not be created in a batch".to_string()
+                        )));
+                    }
+
+                    let action_builder = builder::Create {
+                        entry_type: entry_type(entry_location, entry_visibility),
+                        entry_hash: EntryHash::with_data_sync(&entry),
+                    };
+                    Ok((action_builder, Some(entry), chain_top_ordering))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+
+            // note that validation is handled by the workflow
+            // if the validation fails this commit will be rolled back by virtue of the DB transaction
+            // being atomic
+            tokio_helper::block_forever_on(async move {
+                call_context
+                    .host_context
+                    .workspace_write()
+                    .source_chain()
+                    .as_ref()
+                    .expect("Must have source chain if write_workspace access is given")
+                    .put_weightless_batch(items)
+                    .await
+                    .map_err(|source_chain_error| -> RuntimeError {
+                        wasm_error!(WasmErrorInner::Host(source_chain_error.to_string())).into()
+                    })
+            })
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "create_entries".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sweettest::*;
+    use crate::test_utils::inline_zomes::AppString;
+    use hdk::prelude::*;
+    use holochain_types::inline_zome::InlineZomeSet;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn create_entries_appends_in_order() {
+        holochain_trace::test_run();
+
+        let zome = InlineIntegrityZome::new_unique(vec![EntryDef::default_from_id("string")], 0)
+            .function("create_batch", |api, strings: Vec<String>| {
+                let inputs = strings
+                    .into_iter()
+                    .map(|s| {
+                        CreateInput::new(
+                            InlineZomeSet::get_entry_location(&api, EntryDefIndex(0)),
+                            EntryVisibility::Public,
+                            Entry::app(AppString::new(s).try_into().unwrap()).unwrap(),
+                            ChainTopOrdering::default(),
+                        )
+                    })
+                    .collect();
+                Ok(api.create_entries(inputs)?)
+            })
+            .function("query", |api, ()| Ok(api.query(ChainQueryFilter::new())?));
+        let (dna_file, _, _) = SweetDnaFile::unique_from_inline_zomes(("zome", zome)).await;
+
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let app = conductor.setup_app("app", [&dna_file]).await.unwrap();
+        let (cell,) = app.into_tuple();
+        let zome = cell.zome("zome");
+
+        let strings = vec!["a".to_string(), "b".to_string(), "c".to_string()];
+        let hashes: Vec<ActionHash> = conductor.call(&zome, "create_batch", strings).await;
+        assert_eq!(hashes.len(), 3);
+
+        let records: Vec<Record> = conductor.call(&zome, "query", ()).await;
+        let tail = &records[records.len() - 3..];
+        assert_eq!(
+            tail.iter()
+                .map(|r| r.action_address().clone())
+                .collect::<Vec<_>>(),
+            hashes
+        );
+        for pair in tail.windows(2) {
+            assert_eq!(
+                pair[1].action().prev_action(),
+                Some(pair[0].action_address())
+            );
+            assert_eq!(
+                pair[1].action().action_seq(),
+                pair[0].action().action_seq() + 1
+            );
+        }
+    }
+}
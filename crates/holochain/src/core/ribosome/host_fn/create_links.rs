@@ -0,0 +1,117 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_wasmer_host::prelude::*;
+
+use holochain_types::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+pub fn create_links(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    inputs: Vec<CreateLinkInput>,
+) -> Result<Vec<ActionHash>, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            write_workspace: Permission::Allow,
+            ..
+        } => {
+            let items = inputs
+                .into_iter()
+                .map(|input| {
+                    let CreateLinkInput {
+                        base_address,
+                        target_address,
+                        zome_index,
+                        link_type,
+                        tag,
+                        chain_top_ordering,
+                    } = input;
+                    let action_builder = builder::CreateLink::new(
+                        base_address,
+                        target_address,
+                        zome_index,
+                        link_type,
+                        tag,
+                    );
+                    (action_builder, None, chain_top_ordering)
+                })
+                .collect::<Vec<_>>();
+
+            // note that validation is handled by the workflow
+            // if the validation fails this commit will be rolled back by virtue of the DB transaction
+            // being atomic
+            tokio_helper::block_forever_on(async move {
+                call_context
+                    .host_context
+                    .workspace_write()
+                    .source_chain()
+                    .as_ref()
+                    .expect("Must have source chain if write_workspace access is given")
+                    .put_weightless_batch(items)
+                    .await
+                    .map_err(|source_chain_error| -> RuntimeError {
+                        wasm_error!(WasmErrorInner::Host(source_chain_error.to_string())).into()
+                    })
+            })
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "create_links".into()
+            )
+            .to_string()
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sweettest::*;
+    use hdk::prelude::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn create_links_appends_in_order() {
+        holochain_trace::test_run();
+
+        let zome = InlineIntegrityZome::new_unique(vec![], 1)
+            .function("link_batch", |api, base: AgentPubKey| {
+                let inputs = (0..3u8)
+                    .map(|i| {
+                        CreateLinkInput::new(
+                            base.clone().into(),
+                            base.clone().into(),
+                            ZomeIndex(0),
+                            LinkType(0),
+                            vec![i].into(),
+                            ChainTopOrdering::default(),
+                        )
+                    })
+                    .collect();
+                Ok(api.create_links(inputs)?)
+            })
+            .function("query", |api, ()| Ok(api.query(ChainQueryFilter::new())?));
+        let (dna_file, _, _) = SweetDnaFile::unique_from_inline_zomes(("zome", zome)).await;
+
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let app = conductor.setup_app("app", [&dna_file]).await.unwrap();
+        let (cell,) = app.into_tuple();
+        let zome = cell.zome("zome");
+
+        let hashes: Vec<ActionHash> = conductor
+            .call(&zome, "link_batch", cell.agent_pubkey().clone())
+            .await;
+        assert_eq!(hashes.len(), 3);
+
+        let records: Vec<Record> = conductor.call(&zome, "query", ()).await;
+        let tail = &records[records.len() - 3..];
+        for (record, hash) in tail.iter().zip(&hashes) {
+            assert_eq!(record.action_address(), hash);
+            assert!(matches!(record.action(), Action::CreateLink(_)));
+        }
+    }
+}
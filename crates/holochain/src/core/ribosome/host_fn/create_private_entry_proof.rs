@@ -0,0 +1,130 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+pub fn create_private_entry_proof(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: PrivateEntryProofInput,
+) -> Result<PrivateEntryProof, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            read_workspace: Permission::Allow,
+            keystore: Permission::Allow,
+            ..
+        } => tokio_helper::block_forever_on(async move {
+            let PrivateEntryProofInput {
+                entry_hash,
+                challenge,
+            } = input;
+            let records = call_context
+                .host_context
+                .workspace()
+                .source_chain()
+                .as_ref()
+                .expect("Must have source chain to prove authorship of a private entry")
+                .query(ChainQueryFilter::new().entry_hashes([entry_hash.clone()].into()))
+                .await
+                .map_err(|source_chain_error| -> RuntimeError {
+                    wasm_error!(WasmErrorInner::Host(source_chain_error.to_string())).into()
+                })?;
+
+            // Only private entries can be proven, a public entry can simply be fetched.
+            let signed_action = records
+                .into_iter()
+                .map(|record| record.signed_action)
+                .find(|signed_action| {
+                    signed_action
+                        .action()
+                        .entry_type()
+                        .map_or(false, |entry_type| {
+                            *entry_type.visibility() == EntryVisibility::Private
+                        })
+                })
+                .ok_or_else(|| -> RuntimeError {
+                    wasm_error!(WasmErrorInner::Guest(format!(
+                        "No private entry with hash {} on this source chain",
+                        entry_hash
+                    )))
+                    .into()
+                })?;
+
+            let data = PrivateEntryProof::challenge_data(signed_action.as_hash(), &challenge)
+                .map_err(|e| -> RuntimeError { wasm_error!(e).into() })?;
+            let challenge_signature = call_context
+                .host_context
+                .keystore()
+                .sign(signed_action.action().author().clone(), data.into())
+                .await
+                .map_err(|keystore_error| -> RuntimeError {
+                    wasm_error!(WasmErrorInner::Host(keystore_error.to_string())).into()
+                })?;
+
+            Ok(PrivateEntryProof {
+                signed_action,
+                challenge,
+                challenge_signature,
+            })
+        }),
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "create_private_entry_proof".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+pub mod wasm_test {
+    use crate::core::ribosome::wasm_test::RibosomeTestFixture;
+    use hdk::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ribosome_private_entry_proof_test() {
+        holochain_trace::test_run();
+        let RibosomeTestFixture {
+            conductor,
+            alice,
+            alice_pubkey,
+            bob,
+            ..
+        } = RibosomeTestFixture::new(TestWasm::Create).await;
+
+        let _: ActionHash = conductor.call(&alice, "create_priv_msg", ()).await;
+        let challenge = Bytes::from(vec![1, 2, 3]);
+
+        let proof: PrivateEntryProof = conductor
+            .call(&alice, "prove_priv_msg", challenge.clone())
+            .await;
+        assert_eq!(&alice_pubkey, proof.author());
+        assert!(proof.entry_hash().is_some());
+        assert_eq!(challenge, proof.challenge);
+
+        // Anyone can check the proof without seeing the entry.
+        let valid: bool = conductor
+            .call(&bob, "verify_priv_msg_proof", proof.clone())
+            .await;
+        assert!(valid);
+
+        // A proof can't be reused to answer another challenge.
+        let replayed = PrivateEntryProof {
+            challenge: Bytes::from(vec![4, 5, 6]),
+            ..proof
+        };
+        let valid: bool = conductor
+            .call(&bob, "verify_priv_msg_proof", replayed)
+            .await;
+        assert!(!valid);
+    }
+}
@@ -0,0 +1,108 @@
+use crate::core::ribosome::guest_callback::validate::ValidateHostAccess;
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostContext;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+/// Return n pseudo-random bytes that every validator of the current op will agree on.
+pub fn deterministic_random_bytes(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: u32,
+) -> Result<holochain_types::prelude::Bytes, RuntimeError> {
+    match &call_context.host_context() {
+        HostContext::Validate(ValidateHostAccess {
+            validation_context, ..
+        }) => Ok(holochain_types::prelude::Bytes::from(
+            validation_context.random_bytes(input as _),
+        )),
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "deterministic_random_bytes".into()
+            )
+            .to_string()
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+pub mod wasm_test {
+    use super::deterministic_random_bytes;
+    use crate::core::ribosome::guest_callback::validate::ValidationContext;
+    use crate::core::ribosome::CallContext;
+    use crate::core::ribosome::HostContext;
+    use crate::fixt::CallContextFixturator;
+    use crate::fixt::RealRibosomeFixturator;
+    use crate::fixt::ValidateHostAccessFixturator;
+    use crate::fixt::ZomeCallHostAccessFixturator;
+    use ::fixt::prelude::*;
+    use holochain_types::prelude::*;
+    use std::sync::Arc;
+
+    fn validate_call_context(validation_context: ValidationContext) -> Arc<CallContext> {
+        let mut call_context = CallContextFixturator::new(::fixt::Unpredictable)
+            .next()
+            .unwrap();
+        let mut host_access = fixt!(ValidateHostAccess);
+        host_access.validation_context = validation_context;
+        call_context.host_context = HostContext::Validate(host_access);
+        Arc::new(call_context)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    /// validators of the same op get the same bytes
+    async fn deterministic_random_bytes_test() {
+        let ribosome = Arc::new(
+            RealRibosomeFixturator::new(crate::fixt::curve::Zomes(vec![]))
+                .next()
+                .unwrap(),
+        );
+        let timestamp = Timestamp::now();
+        let validator_1 = validate_call_context(ValidationContext::new(timestamp, [1; 32]));
+        let validator_2 = validate_call_context(ValidationContext::new(timestamp, [1; 32]));
+        let other_op = validate_call_context(ValidationContext::new(timestamp, [2; 32]));
+
+        let first = deterministic_random_bytes(ribosome.clone(), validator_1.clone(), 40).unwrap();
+        let second = deterministic_random_bytes(ribosome.clone(), validator_1, 40).unwrap();
+        assert_eq!(40, first.len());
+        // Repeated calls hand out fresh bytes.
+        assert_ne!(first, second);
+
+        // Another validator making the same calls gets the same bytes.
+        assert_eq!(
+            first,
+            deterministic_random_bytes(ribosome.clone(), validator_2.clone(), 40).unwrap()
+        );
+        assert_eq!(
+            second,
+            deterministic_random_bytes(ribosome.clone(), validator_2, 40).unwrap()
+        );
+
+        // Validating a different op gives different bytes.
+        assert_ne!(
+            first,
+            deterministic_random_bytes(ribosome, other_op, 40).unwrap()
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    /// outside of validation there is no op to seed from
+    async fn deterministic_random_bytes_outside_validation_test() {
+        let ribosome = RealRibosomeFixturator::new(crate::fixt::curve::Zomes(vec![]))
+            .next()
+            .unwrap();
+        let mut call_context = CallContextFixturator::new(::fixt::Unpredictable)
+            .next()
+            .unwrap();
+        call_context.host_context = HostContext::ZomeCall(fixt!(ZomeCallHostAccess));
+
+        assert!(deterministic_random_bytes(Arc::new(ribosome), Arc::new(call_context), 1).is_err());
+    }
+}
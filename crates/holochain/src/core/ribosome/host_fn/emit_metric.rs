@@ -0,0 +1,234 @@
+use crate::core::metrics::record_app_metric;
+use crate::core::ribosome::guest_callback::init::InitHostAccess;
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use crate::core::ribosome::ZomeCallHostAccess;
+use holochain_types::prelude::*;
+use holochain_util::tokio_helper;
+use holochain_wasmer_host::prelude::*;
+use opentelemetry_api::KeyValue;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+/// Tag keys the host sets on every app metric, which apps may not override.
+const RESERVED_TAGS: [&str; 4] = ["app", "dna", "agent", "zome"];
+
+pub fn emit_metric(
+    ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: AppMetric,
+) -> Result<(), RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            non_determinism: Permission::Allow,
+            ..
+        } => {
+            let AppMetric { name, value, tags } = input;
+            if !is_valid_metric_name(&name) {
+                return Err(wasm_error!(WasmErrorInner::Guest(format!(
+                    "App metric names must start with a letter and contain only ASCII letters, digits, '_' and '.', but got '{}'",
+                    name
+                )))
+                .into());
+            }
+            if let Some((key, _)) = tags
+                .iter()
+                .find(|(key, _)| RESERVED_TAGS.contains(&key.as_str()))
+            {
+                return Err(wasm_error!(WasmErrorInner::Guest(format!(
+                    "The tag '{}' is reserved for the conductor and cannot be set on app metric '{}'",
+                    key, name
+                )))
+                .into());
+            }
+
+            let mut attributes = vec![
+                KeyValue::new("dna", ribosome.dna_def().as_hash().to_string()),
+                KeyValue::new("zome", call_context.zome.zome_name().to_string()),
+            ];
+            if let Some(app_id) = installed_app_id(&call_context.host_context) {
+                attributes.push(KeyValue::new("app", app_id));
+            }
+            if let Some(agent) = call_context
+                .host_context
+                .maybe_workspace()
+                .and_then(|workspace| {
+                    workspace
+                        .source_chain()
+                        .as_ref()
+                        .map(|source_chain| source_chain.agent_pubkey().to_string())
+                })
+            {
+                attributes.push(KeyValue::new("agent", agent));
+            }
+            attributes.extend(
+                tags.into_iter()
+                    .map(|(key, value)| KeyValue::new(key, value)),
+            );
+
+            record_app_metric(&name, value, &attributes);
+            Ok(())
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "emit_metric".into()
+            )
+            .to_string()
+        ))
+        .into()),
+    }
+}
+
+/// Whether a name can be used for the `hc.app.<name>` histogram.
+fn is_valid_metric_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphabetic())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+}
+
+/// The id of the app which the emitting cell belongs to, if the host context can
+/// reach the conductor to look it up.
+fn installed_app_id(host_context: &HostContext) -> Option<InstalledAppId> {
+    let handle = match host_context {
+        HostContext::ZomeCall(ZomeCallHostAccess {
+            call_zome_handle, ..
+        })
+        | HostContext::Init(InitHostAccess {
+            call_zome_handle, ..
+        }) => call_zome_handle.clone(),
+        _ => return None,
+    };
+    tokio_helper::block_forever_on(async move {
+        handle.find_app_containing_cell(handle.cell_id()).await
+    })
+    .map_err(|err| tracing::warn!(?err, "Failed to find the app of a cell emitting a metric"))
+    .ok()
+    .flatten()
+    .map(|app| app.id().clone())
+}
+
+#[cfg(test)]
+pub mod wasm_test {
+    use super::emit_metric;
+    use crate::conductor::api::MockCellConductorReadHandleT;
+    use crate::core::ribosome::HostContext;
+    use crate::core::ribosome::ZomeCallHostAccess;
+    use crate::fixt::CallContextFixturator;
+    use crate::fixt::RealRibosomeFixturator;
+    use crate::fixt::ValidateHostAccessFixturator;
+    use crate::fixt::ZomeCallHostAccessFixturator;
+    use ::fixt::prelude::*;
+    use futures::FutureExt;
+    use holochain_metrics::prometheus::PrometheusMeterProvider;
+    use holochain_types::prelude::*;
+    use std::sync::Arc;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn emit_metric_test() {
+        // - The histogram of the metric is only created when it is first emitted,
+        //   so it is recorded by this provider.
+        let metrics = PrometheusMeterProvider::new();
+        opentelemetry_api::global::set_meter_provider(metrics.clone());
+
+        let ribosome = Arc::new(
+            RealRibosomeFixturator::new(crate::fixt::curve::Zomes(vec![]))
+                .next()
+                .unwrap(),
+        );
+        let cell_id = fixt!(CellId);
+        let mut conductor_handle = MockCellConductorReadHandleT::new();
+        conductor_handle
+            .expect_cell_id()
+            .return_const(cell_id.clone());
+        conductor_handle
+            .expect_find_app_containing_cell()
+            .returning(move |_| {
+                let app = InstalledAppCommon::new_legacy(
+                    "test-app",
+                    [InstalledCell::new(cell_id.clone(), "role".into())],
+                )
+                .unwrap();
+                async move { Ok(Some(InstalledApp::new_fresh(app))) }.boxed()
+            });
+        let mut call_context = CallContextFixturator::new(::fixt::Unpredictable)
+            .next()
+            .unwrap();
+        call_context.host_context = HostContext::ZomeCall(ZomeCallHostAccess {
+            call_zome_handle: Arc::new(conductor_handle),
+            ..fixt!(ZomeCallHostAccess)
+        });
+        let call_context = Arc::new(call_context);
+
+        for value in [3.0, 4.0] {
+            emit_metric(
+                ribosome.clone(),
+                call_context.clone(),
+                AppMetric::new("emit_metric_test.messages_sent", value)
+                    .with_tag("channel", "general"),
+            )
+            .unwrap();
+        }
+
+        let encoded = metrics.encode();
+        let recorded = |suffix: &str| {
+            encoded
+                .lines()
+                .find(|line| {
+                    line.starts_with(&format!("hc_app_emit_metric_test_messages_sent_{}{{", suffix))
+                })
+                .unwrap_or_else(|| panic!("no {} in {}", suffix, encoded))
+                .to_string()
+        };
+        let sum = recorded("sum");
+        assert!(sum.ends_with("} 7"), "{}", sum);
+        assert!(sum.contains("app=\"test-app\""), "{}", sum);
+        assert!(sum.contains("channel=\"general\""), "{}", sum);
+        assert!(
+            sum.contains(&format!("dna=\"{}\"", ribosome.dna_def().as_hash())),
+            "{}",
+            sum
+        );
+        // - Both measurements were recorded by the same cached histogram.
+        assert!(recorded("count").ends_with("} 2"));
+
+        // The conductor's own tags can't be overridden.
+        assert!(emit_metric(
+            ribosome.clone(),
+            call_context.clone(),
+            AppMetric::new("messages_sent", 3.0).with_tag("dna", "other"),
+        )
+        .is_err());
+
+        assert!(emit_metric(
+            ribosome.clone(),
+            call_context.clone(),
+            AppMetric::new("", 3.0)
+        )
+        .is_err());
+        assert!(emit_metric(ribosome, call_context, AppMetric::new("messages sent", 3.0)).is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn emit_metric_in_validation_test() {
+        let ribosome = RealRibosomeFixturator::new(crate::fixt::curve::Zomes(vec![]))
+            .next()
+            .unwrap();
+        let mut call_context = CallContextFixturator::new(::fixt::Unpredictable)
+            .next()
+            .unwrap();
+        call_context.host_context = HostContext::Validate(fixt!(ValidateHostAccess));
+
+        assert!(emit_metric(
+            Arc::new(ribosome),
+            Arc::new(call_context),
+            AppMetric::new("messages_sent", 1.0),
+        )
+        .is_err());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::core::ribosome::blocked_agents::BlockedAgentFilter;
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::HostFnAccess;
 use crate::core::ribosome::RibosomeError;
@@ -23,7 +24,7 @@ pub fn get(
             ..
         } => {
             let results: Vec<Result<Option<Record>, _>> =
-                tokio_helper::block_forever_on(async move {
+                tokio_helper::block_forever_on(async {
                     futures::stream::iter(inputs.into_iter().map(|input| async {
                         let GetInput {
                             any_dht_hash,
@@ -52,6 +53,12 @@ pub fn get(
                 })
                 .collect();
             let results = results?;
+            let results = tokio_helper::block_forever_on(async {
+                match BlockedAgentFilter::for_host_context(&call_context.host_context).await? {
+                    Some(mut filter) => filter.filter_records(results).await,
+                    None => Ok(results),
+                }
+            })?;
             tracing::debug!(
                 "Ending with {} out of {} results and {} total responses.",
                 results.iter().filter(|r| r.is_some()).count(),
@@ -23,17 +23,23 @@ pub fn get_agent_activity(
                 agent_pubkey,
                 chain_query_filter,
                 activity_request,
+                page,
             } = input;
             let options = match activity_request {
+                // Warrants are included either way, as a warranted agent's chain may
+                // look valid to the authorities which haven't seen the invalid action.
                 ActivityRequest::Status => GetActivityOptions {
                     include_valid_activity: false,
                     include_rejected_activity: false,
+                    include_warrants: true,
                     get_options: GetOptions::local(),
                     ..Default::default()
                 },
                 ActivityRequest::Full => GetActivityOptions {
                     include_valid_activity: true,
                     include_rejected_activity: true,
+                    include_warrants: true,
+                    page,
                     get_options: GetOptions::local(),
                     ..Default::default()
                 },
@@ -0,0 +1,43 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+#[cfg_attr(
+    feature = "instrument",
+    tracing::instrument(skip(_ribosome, call_context))
+)]
+pub fn get_agent_presence(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: AgentPubKey,
+) -> Result<Option<AgentPresence>, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            read_workspace: Permission::Allow,
+            ..
+        } => {
+            let network = call_context.host_context.network().clone();
+            // timeouts must be handled by the network
+            tokio_helper::block_forever_on(async move {
+                network
+                    .get_agent_presence(input)
+                    .await
+                    .map_err(|e| -> RuntimeError { wasm_error!(e.to_string()).into() })
+            })
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "get_agent_presence".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
@@ -0,0 +1,52 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_cascade::CascadeImpl;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+#[cfg_attr(feature = "instrument", tracing::instrument(skip(_ribosome, call_context), fields(?call_context.zome, function = ?call_context.function_name)))]
+pub fn get_chunked(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: GetChunkedInput,
+) -> Result<Option<Bytes>, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            read_workspace: Permission::Allow,
+            ..
+        } => {
+            let GetChunkedInput {
+                manifest_hash,
+                range,
+                get_options,
+            } = input;
+            tokio_helper::block_forever_on(async {
+                CascadeImpl::from_workspace_and_network(
+                    &call_context.host_context.workspace(),
+                    call_context.host_context.network().clone(),
+                )
+                .get_chunked(manifest_hash, range, get_options)
+                .await
+            })
+            .map(|bytes| bytes.map(Bytes::from))
+            .map_err(|cascade_error| {
+                wasm_error!(WasmErrorInner::Host(cascade_error.to_string())).into()
+            })
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "get_chunked".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
+
+// The round trip through create_chunked is tested there.
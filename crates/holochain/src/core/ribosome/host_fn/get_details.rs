@@ -1,3 +1,4 @@
+use crate::core::ribosome::blocked_agents::BlockedAgentFilter;
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::HostFnAccess;
 use crate::core::ribosome::RibosomeError;
@@ -20,7 +21,7 @@ pub fn get_details(
             ..
         } => {
             let results: Vec<Result<Option<Details>, _>> =
-                tokio_helper::block_forever_on(async move {
+                tokio_helper::block_forever_on(async {
                     join_all(inputs.into_iter().map(|input| async {
                         let GetInput {
                             any_dht_hash,
@@ -43,7 +44,14 @@ pub fn get_details(
                     })
                 })
                 .collect();
-            Ok(results?)
+            let results = results?;
+            let results = tokio_helper::block_forever_on(async {
+                match BlockedAgentFilter::for_host_context(&call_context.host_context).await? {
+                    Some(mut filter) => filter.filter_details(results).await,
+                    None => Ok(results),
+                }
+            })?;
+            Ok(results)
         }
         _ => Err(wasm_error!(WasmErrorInner::Host(
             RibosomeError::HostFnPermissions(
@@ -1,3 +1,4 @@
+use crate::core::ribosome::blocked_agents::BlockedAgentFilter;
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::HostFnAccess;
 use crate::core::ribosome::RibosomeError;
@@ -21,7 +22,7 @@ pub fn get_link_details(
             ..
         } => {
             let results: Vec<Result<Vec<_>, RibosomeError>> =
-                tokio_helper::block_forever_on(async move {
+                tokio_helper::block_forever_on(async {
                     join_all(inputs.into_iter().map(|input| async {
                         let GetLinksInput {
                             base_address,
@@ -63,7 +64,14 @@ pub fn get_link_details(
                     }
                 })
                 .collect();
-            Ok(results?)
+            let results = results?;
+            let results = tokio_helper::block_forever_on(async {
+                match BlockedAgentFilter::for_host_context(&call_context.host_context).await? {
+                    Some(mut filter) => filter.filter_link_details(results).await,
+                    None => Ok(results),
+                }
+            })?;
+            Ok(results)
         }
         _ => Err(wasm_error!(WasmErrorInner::Host(
             RibosomeError::HostFnPermissions(
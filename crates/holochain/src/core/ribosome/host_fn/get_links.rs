@@ -1,3 +1,4 @@
+use crate::core::ribosome::blocked_agents::BlockedAgentFilter;
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::HostFnAccess;
 use crate::core::ribosome::RibosomeError;
@@ -24,7 +25,7 @@ pub fn get_links(
             ..
         } => {
             let results: Vec<Result<Vec<Link>, RibosomeError>> =
-                tokio_helper::block_forever_on(async move {
+                tokio_helper::block_forever_on(async {
                     let call_context_iter = std::iter::from_fn(|| Some(call_context.clone()));
                     futures::stream::iter(
                         std::iter::zip(inputs.into_iter(), call_context_iter).map(
@@ -78,6 +79,12 @@ pub fn get_links(
                 })
                 .collect();
             let results = results?;
+            let results = tokio_helper::block_forever_on(async {
+                match BlockedAgentFilter::for_host_context(&call_context.host_context).await? {
+                    Some(mut filter) => filter.filter_links(results).await,
+                    None => Ok(results),
+                }
+            })?;
             tracing::debug!(
                 "Ending with {} out of {} results, {} total links and {} total responses.",
                 results.iter().filter(|r| !r.is_empty()).count(),
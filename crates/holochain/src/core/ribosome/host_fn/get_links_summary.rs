@@ -0,0 +1,89 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_cascade::CascadeImpl;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+/// Get the creation times of links
+#[allow(clippy::extra_unused_lifetimes)]
+#[cfg_attr(feature = "instrument", tracing::instrument(skip(_ribosome, call_context), fields(? call_context.zome, function = ? call_context.function_name)))]
+pub fn get_links_summary<'a>(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    query: LinkQuery,
+) -> Result<LinksSummary, RuntimeError> {
+    tracing::debug!(msg = "Summarising links", ?query);
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            read_workspace: Permission::Allow,
+            ..
+        } => tokio_helper::block_forever_on(async move {
+            let wire_query = WireLinkQuery {
+                base: query.base,
+                link_type: query.link_type,
+                tag_prefix: query.tag_prefix,
+                before: query.before,
+                after: query.after,
+                author: query.author,
+            };
+
+            CascadeImpl::from_workspace_and_network(
+                &call_context.host_context.workspace(),
+                call_context.host_context.network().to_owned(),
+            )
+            .dht_links_summary(wire_query)
+            .await
+            .map_err(|cascade_error| {
+                wasm_error!(WasmErrorInner::Host(cascade_error.to_string())).into()
+            })
+        }),
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "get_links_summary".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+#[cfg(feature = "slow_tests")]
+mod tests {
+    use crate::core::ribosome::wasm_test::RibosomeTestFixture;
+    use hdk::prelude::*;
+    use holochain_wasm_test_utils::TestWasm;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn get_links_summary() {
+        holochain_trace::test_run();
+        let RibosomeTestFixture {
+            conductor,
+            alice,
+            bob,
+            ..
+        } = RibosomeTestFixture::new(TestWasm::Link).await;
+
+        let base: AnyLinkableHash = conductor.call(&alice, "get_base_hash", ()).await;
+        let query = LinkQuery::new(base, LinkTypeFilter::Dependencies(vec![ZomeIndex(0)]));
+
+        let summary: LinksSummary = conductor
+            .call(&alice, "get_summary", query.clone())
+            .await;
+        assert_eq!(LinksSummary::default(), summary);
+
+        let _: ActionHash = conductor.call(&alice, "create_link", ()).await;
+        let _: ActionHash = conductor.call(&bob, "create_link", ()).await;
+
+        let summary: LinksSummary = conductor.call(&alice, "get_summary", query).await;
+        assert_eq!(2, summary.count());
+        let links: Vec<Link> = conductor.call(&alice, "get_links", ()).await;
+        assert_eq!(links.iter().map(|l| l.timestamp).max(), summary.latest());
+    }
+}
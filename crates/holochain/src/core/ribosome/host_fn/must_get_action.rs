@@ -1,107 +1,14 @@
+use super::must_get_action_with_options::must_get_action_with_options;
 use crate::core::ribosome::CallContext;
-use crate::core::ribosome::HostContext;
-use crate::core::ribosome::RibosomeError;
 use crate::core::ribosome::RibosomeT;
-use holochain_cascade::{Cascade, CascadeImpl};
-use holochain_p2p::actor::GetOptions as NetworkGetOptions;
 use holochain_types::prelude::*;
-use holochain_wasmer_host::prelude::*;
 use std::sync::Arc;
 use wasmer::RuntimeError;
 
-#[cfg_attr(
-    feature = "instrument",
-    tracing::instrument(skip(_ribosome, call_context))
-)]
 pub fn must_get_action(
-    _ribosome: Arc<impl RibosomeT>,
+    ribosome: Arc<impl RibosomeT>,
     call_context: Arc<CallContext>,
     input: MustGetActionInput,
 ) -> Result<SignedActionHashed, RuntimeError> {
-    tracing::debug!("begin must_get_action");
-    let ret = match HostFnAccess::from(&call_context.host_context()) {
-        HostFnAccess {
-            read_workspace_deterministic: Permission::Allow,
-            ..
-        } => {
-            let action_hash = input.into_inner();
-
-            // timeouts must be handled by the network
-            tokio_helper::block_forever_on(async move {
-                let workspace = call_context.host_context.workspace();
-                use crate::core::ribosome::ValidateHostAccess;
-                let cascade = match call_context.host_context {
-                    HostContext::Validate(ValidateHostAccess { is_inline, .. }) => {
-                        if is_inline {
-                            CascadeImpl::from_workspace_and_network(
-                                &workspace,
-                                call_context.host_context.network().clone(),
-                            )
-                        } else {
-                            CascadeImpl::from_workspace_stores(workspace.stores(), None)
-                        }
-                    }
-                    _ => CascadeImpl::from_workspace_and_network(
-                        &workspace,
-                        call_context.host_context.network().clone(),
-                    ),
-                };
-                match cascade
-                    .retrieve_action(action_hash.clone(), NetworkGetOptions::must_get_options())
-                    .await
-                    .map_err(|cascade_error| -> RuntimeError {
-                        wasm_error!(WasmErrorInner::Host(cascade_error.to_string())).into()
-                    })? {
-                    Some((action, _)) => Ok(action),
-                    None => match call_context.host_context {
-                        HostContext::EntryDefs(_)
-                        | HostContext::GenesisSelfCheckV1(_)
-                        | HostContext::GenesisSelfCheckV2(_)
-                        | HostContext::PostCommit(_)
-                        | HostContext::ZomeCall(_) => Err(wasm_error!(WasmErrorInner::Host(
-                            format!("Failed to get SignedActionHashed {}", action_hash)
-                        ))
-                        .into()),
-                        HostContext::Init(_) => Err(wasm_error!(WasmErrorInner::HostShortCircuit(
-                            holochain_serialized_bytes::encode(
-                                &ExternIO::encode(InitCallbackResult::UnresolvedDependencies(
-                                    UnresolvedDependencies::Hashes(vec![action_hash.into()],)
-                                ))
-                                .map_err(|e| -> RuntimeError { wasm_error!(e).into() })?,
-                            )
-                            .map_err(|e| -> RuntimeError { wasm_error!(e).into() })?
-                        ))
-                        .into()),
-                        HostContext::Validate(_) => {
-                            Err(wasm_error!(WasmErrorInner::HostShortCircuit(
-                                holochain_serialized_bytes::encode(
-                                    &ExternIO::encode(
-                                        ValidateCallbackResult::UnresolvedDependencies(
-                                            UnresolvedDependencies::Hashes(
-                                                vec![action_hash.into()],
-                                            )
-                                        )
-                                    )
-                                    .map_err(|e| -> RuntimeError { wasm_error!(e).into() })?,
-                                )
-                                .map_err(|e| -> RuntimeError { wasm_error!(e).into() })?
-                            ))
-                            .into())
-                        }
-                    },
-                }
-            })
-        }
-        _ => Err(wasm_error!(WasmErrorInner::Host(
-            RibosomeError::HostFnPermissions(
-                call_context.zome.zome_name().clone(),
-                call_context.function_name().clone(),
-                "must_get_action".into(),
-            )
-            .to_string(),
-        ))
-        .into()),
-    };
-    tracing::debug!(?ret);
-    ret
+    must_get_action_with_options(ribosome, call_context, input.into())
 }
@@ -1,3 +1,5 @@
+use super::must_get_bounds;
+use super::must_get_with_retries;
 use crate::core::ribosome::CallContext;
 use crate::core::ribosome::HostContext;
 use crate::core::ribosome::HostFnAccess;
@@ -12,10 +14,10 @@ use wasmer::RuntimeError;
 
 #[cfg_attr(
     feature = "instrument",
-    tracing::instrument(skip(_ribosome, call_context))
+    tracing::instrument(skip(ribosome, call_context))
 )]
 pub fn must_get_agent_activity(
-    _ribosome: Arc<impl RibosomeT>,
+    ribosome: Arc<impl RibosomeT>,
     call_context: Arc<CallContext>,
     input: MustGetAgentActivityInput,
 ) -> Result<Vec<RegisterAgentActivity>, RuntimeError> {
@@ -28,7 +30,9 @@ pub fn must_get_agent_activity(
             let MustGetAgentActivityInput {
                 author,
                 chain_filter,
+                options,
             } = input;
+            let bounds = must_get_bounds(ribosome.as_ref(), &call_context, options);
 
             // timeouts must be handled by the network
             tokio_helper::block_forever_on(async move {
@@ -50,12 +54,30 @@ pub fn must_get_agent_activity(
                         call_context.host_context.network().clone(),
                     ),
                 };
-                let result = cascade
-                    .must_get_agent_activity(author.clone(), chain_filter.clone())
-                    .await
-                    .map_err(|cascade_error| -> RuntimeError {
-                        wasm_error!(WasmErrorInner::Host(cascade_error.to_string())).into()
-                    })?;
+                let lookup = {
+                    let cascade = &cascade;
+                    let author = &author;
+                    let chain_filter = &chain_filter;
+                    move || async move {
+                        cascade
+                            .must_get_agent_activity(author.clone(), chain_filter.clone())
+                            .await
+                            .map_err(|cascade_error| -> RuntimeError {
+                                wasm_error!(WasmErrorInner::Host(cascade_error.to_string())).into()
+                            })
+                    }
+                };
+                // Only a chain that has not been fully fetched yet is worth asking for again.
+                let is_fetched = |response: &MustGetAgentActivityResponse| {
+                    !matches!(
+                        response,
+                        MustGetAgentActivityResponse::IncompleteChain
+                            | MustGetAgentActivityResponse::ChainTopNotFound(_)
+                    )
+                };
+                let result = must_get_with_retries(bounds, is_fetched, lookup)
+                    .await?
+                    .unwrap_or(MustGetAgentActivityResponse::IncompleteChain);
 
                 use MustGetAgentActivityResponse::*;
 
@@ -0,0 +1,41 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_types::access::Permission;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+#[cfg_attr(
+    feature = "instrument",
+    tracing::instrument(skip(_ribosome, call_context))
+)]
+pub fn outbox_delivery(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: Vec<OutboxMessageId>,
+) -> Result<Vec<Option<OutboxDelivery>>, RuntimeError> {
+    match (
+        HostFnAccess::from(&call_context.host_context()),
+        call_context.host_context().maybe_call_zome_handle(),
+    ) {
+        (
+            HostFnAccess {
+                agent_info: Permission::Allow,
+                ..
+            },
+            Some(call_zome_handle),
+        ) => Ok(call_zome_handle.outbox_delivery(input)),
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "outbox_delivery".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
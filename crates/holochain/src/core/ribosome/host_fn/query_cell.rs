@@ -0,0 +1,116 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+pub fn query_cell(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: QueryCellInput,
+) -> Result<Vec<Record>, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            read_workspace: Permission::Allow,
+            agent_info: Permission::Allow,
+            ..
+        } => tokio_helper::block_forever_on(async move {
+            let QueryCellInput { target, filter } = input;
+            let handle = call_context.host_context().call_zome_handle();
+            let this_cell_id = handle.cell_id().clone();
+            let cell_id = match target {
+                CallTargetCell::OtherRole(role_name) => handle
+                    .find_cell_with_role_alongside_cell(&this_cell_id, &role_name)
+                    .await
+                    .map_err(|e| -> RuntimeError {
+                        wasm_error!(WasmErrorInner::Host(e.to_string())).into()
+                    })?
+                    .ok_or_else(|| -> RuntimeError {
+                        wasm_error!(WasmErrorInner::Host(format!("Role not found: {role_name}")))
+                            .into()
+                    })?,
+                CallTargetCell::OtherCell(cell_id) => cell_id,
+                CallTargetCell::Local => this_cell_id.clone(),
+            };
+            // A bridge call between cells of the same agent is authorized by
+            // the author grant, so that is the only case a query is allowed.
+            if cell_id.agent_pubkey() != this_cell_id.agent_pubkey() {
+                return Err(wasm_error!(WasmErrorInner::Host(format!(
+                    "Cannot query cell {cell_id:?} which belongs to another agent"
+                )))
+                .into());
+            }
+            handle
+                .query_cell(&cell_id, filter)
+                .await
+                .map_err(|e| -> RuntimeError {
+                    wasm_error!(WasmErrorInner::Host(e.to_string())).into()
+                })
+        }),
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "query_cell".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sweettest::*;
+    use hdk::prelude::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn query_cell_reads_other_cells_of_the_same_agent() {
+        holochain_trace::test_run();
+
+        let zome = || {
+            InlineIntegrityZome::new_unique(vec![], 0).function(
+                "query_other",
+                |api, cell_id: CellId| {
+                    Ok(api.query_cell(QueryCellInput::new(
+                        CallTargetCell::OtherCell(cell_id),
+                        ChainQueryFilter::new(),
+                    ))?)
+                },
+            )
+        };
+        let (dna_a, _, _) = SweetDnaFile::unique_from_inline_zomes(("zome", zome())).await;
+        let (dna_b, _, _) = SweetDnaFile::unique_from_inline_zomes(("zome", zome())).await;
+
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let app = conductor.setup_app("app", [&dna_a, &dna_b]).await.unwrap();
+        let (cell_a, cell_b) = app.into_tuple();
+        let (other_cell,) = conductor
+            .setup_app("other", [&dna_b])
+            .await
+            .unwrap()
+            .into_tuple();
+
+        let records: Vec<Record> = conductor
+            .call(&cell_a.zome("zome"), "query_other", cell_b.cell_id().clone())
+            .await;
+        assert!(!records.is_empty());
+        assert!(records
+            .iter()
+            .all(|r| r.action().author() == cell_b.agent_pubkey()));
+        assert_eq!(records[0].action().action_type(), ActionType::Dna);
+
+        // Cells of other agents can't be queried, even on the same conductor.
+        let result: Result<Vec<Record>, _> = conductor
+            .call_fallible(
+                &cell_a.zome("zome"),
+                "query_other",
+                other_cell.cell_id().clone(),
+            )
+            .await;
+        assert!(result.is_err());
+    }
+}
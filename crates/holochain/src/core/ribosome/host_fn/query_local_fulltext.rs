@@ -0,0 +1,184 @@
+use crate::core::ribosome::error::RibosomeError;
+use crate::core::ribosome::{CallContext, RibosomeT};
+use holochain_sqlite::db::DbKindDht;
+use holochain_sqlite::prelude::DbRead;
+use holochain_state::fulltext::query_fulltext;
+use holochain_types::access::{HostFnAccess, Permission};
+use holochain_util::tokio_helper;
+use holochain_wasmer_host::prelude::{wasm_error, WasmError, WasmErrorInner, WasmHostError};
+use holochain_zome_types::prelude::{EntryHash, FullTextQuery};
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+/// Query the text of the entries of the full-text entry types of the DNA which are held
+/// in the local DHT database. The network is never asked.
+#[cfg_attr(feature = "instrument", tracing::instrument(skip(_ribosome, call_context), fields(?call_context.zome, function = ?call_context.function_name)))]
+pub fn query_local_fulltext(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: FullTextQuery,
+) -> Result<Vec<EntryHash>, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            read_workspace: Permission::Allow,
+            ..
+        } => {
+            let FullTextQuery { query, limit } = input;
+            if query.trim().is_empty() {
+                return Err(wasm_error!(WasmErrorInner::Guest(
+                    "Full-text queries must not be empty".to_string()
+                ))
+                .into());
+            }
+            let results = tokio_helper::block_forever_on(async move {
+                let dht_db: DbRead<DbKindDht> = call_context.host_context.workspace().databases().1;
+
+                dht_db
+                    .read_async(move |txn| query_fulltext(txn, &query, limit as usize))
+                    .await
+            })
+            .map_err(|e| wasm_error!(WasmErrorInner::Host(e.to_string())))?;
+
+            Ok(results)
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "query_local_fulltext".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::query_local_fulltext;
+    use crate::core::ribosome::HostContext;
+    use crate::fixt::ValidateHostAccessFixturator;
+    use crate::fixt::ZomeCallHostAccessFixturator;
+    use crate::fixt::{CallContextFixturator, RealRibosomeFixturator};
+    use crate::sweettest::*;
+    use ::fixt::prelude::*;
+    use holochain_zome_types::prelude::*;
+    use std::sync::Arc;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn query_local_fulltext_of_an_empty_db() {
+        let ribosome = Arc::new(
+            RealRibosomeFixturator::new(crate::fixt::curve::Zomes(vec![]))
+                .next()
+                .unwrap(),
+        );
+        let mut call_context = CallContextFixturator::new(Unpredictable).next().unwrap();
+        call_context.host_context = fixt!(ZomeCallHostAccess, Predictable).into();
+        let call_context = Arc::new(call_context);
+
+        let found = query_local_fulltext(
+            ribosome.clone(),
+            call_context.clone(),
+            FullTextQuery::new("peer"),
+        )
+        .unwrap();
+        assert!(found.is_empty());
+
+        assert!(
+            query_local_fulltext(ribosome.clone(), call_context.clone(), FullTextQuery::new(" "))
+                .is_err()
+        );
+        // - Queries which aren't valid FTS5 queries fail.
+        assert!(
+            query_local_fulltext(ribosome, call_context, FullTextQuery::new("\"unterminated"))
+                .is_err()
+        );
+    }
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes)]
+    struct Post {
+        text: String,
+    }
+
+    fn post_entry(text: &str) -> Entry {
+        let post = Post {
+            text: text.to_string(),
+        };
+        Entry::app(post.try_into().unwrap()).unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn entries_of_fulltext_types_are_found_once_integrated() {
+        holochain_trace::test_run();
+
+        let entry_defs = vec![
+            EntryDef::default_from_id("post"),
+            EntryDef::default_from_id("comment"),
+        ];
+        let zomes = SweetInlineZomes::new(entry_defs, 0)
+            .function("create", |api, (entry_index, text): (u8, String)| {
+                let entry = post_entry(&text);
+                let hash = api.create(CreateInput::new(
+                    InlineZomeSet::get_entry_location(&api, EntryDefIndex(entry_index)),
+                    EntryVisibility::Public,
+                    entry,
+                    ChainTopOrdering::default(),
+                ))?;
+                Ok(hash)
+            })
+            .function("search", |api, query: String| {
+                Ok(api.query_local_fulltext(FullTextQuery::new(query))?)
+            });
+        let (dna_file, _, _) = SweetDnaFile::unique_from_inline_zomes(zomes).await;
+        let (mut dna_def, wasms): (DnaDef, Vec<_>) = dna_file.into();
+        // - Only posts are indexed.
+        dna_def.fulltext_entry_types = vec![ZomeTypeIndex {
+            zome: SweetInlineZomes::INTEGRITY.into(),
+            index: 0,
+        }];
+        let dna_file = DnaFile::new(dna_def, wasms).await;
+
+        let mut conductor = SweetConductor::from_standard_config().await;
+        let app = conductor.setup_app("app", [&dna_file]).await.unwrap();
+        let (cell,) = app.into_tuple();
+        let zome = cell.zome(SweetInlineZomes::COORDINATOR);
+
+        let _: ActionHash = conductor
+            .call(&zome, "create", (0u8, "peer to peer networks".to_string()))
+            .await;
+        let _: ActionHash = conductor
+            .call(&zome, "create", (1u8, "a peer review".to_string()))
+            .await;
+        await_consistency(10, [&cell]).await.unwrap();
+        let post_hash = EntryHash::with_data_sync(&post_entry("peer to peer networks"));
+
+        // Entries are indexed on the next integration after their ops were integrated.
+        let mut found: Vec<EntryHash> = vec![];
+        for _ in 0..50 {
+            found = conductor.call(&zome, "search", "peer".to_string()).await;
+            if !found.is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+        }
+        assert_eq!(vec![post_hash], found);
+        let found: Vec<EntryHash> = conductor.call(&zome, "search", "review".to_string()).await;
+        assert!(found.is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn query_local_fulltext_in_validation() {
+        let ribosome = RealRibosomeFixturator::new(crate::fixt::curve::Zomes(vec![]))
+            .next()
+            .unwrap();
+        let mut call_context = CallContextFixturator::new(Unpredictable).next().unwrap();
+        call_context.host_context = HostContext::Validate(fixt!(ValidateHostAccess));
+
+        assert!(query_local_fulltext(
+            Arc::new(ribosome),
+            Arc::new(call_context),
+            FullTextQuery::new("peer"),
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,49 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_types::access::Permission;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+#[cfg_attr(
+    feature = "instrument",
+    tracing::instrument(skip(_ribosome, call_context, input))
+)]
+pub fn queue_remote_message(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: QueuedRemoteMessage,
+) -> Result<OutboxMessageId, RuntimeError> {
+    match (
+        HostFnAccess::from(&call_context.host_context()),
+        call_context.host_context().maybe_call_zome_handle(),
+    ) {
+        (
+            HostFnAccess {
+                write_network: Permission::Allow,
+                agent_info: Permission::Allow,
+                ..
+            },
+            Some(call_zome_handle),
+        ) => tokio_helper::block_forever_on(async {
+            call_zome_handle
+                .queue_remote_message(call_context.zome().zome_name().clone(), input)
+                .await
+                .map_err(|e| -> RuntimeError {
+                    wasm_error!(WasmErrorInner::Host(e.to_string())).into()
+                })
+        }),
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "queue_remote_message".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
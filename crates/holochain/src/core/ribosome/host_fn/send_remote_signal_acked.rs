@@ -0,0 +1,253 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use futures::future::join_all;
+use holochain_keystore::AgentPubKeyExt;
+use holochain_keystore::MetaLairClient;
+use holochain_nonce::fresh_nonce;
+use holochain_p2p::GenericNetwork;
+use holochain_types::access::Permission;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+use std::time::Duration;
+use wasmer::RuntimeError;
+
+/// How long to wait for a recipient to acknowledge a signal if the caller
+/// does not specify a timeout.
+pub const DEFAULT_ACKED_REMOTE_SIGNAL_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The longest a caller may wait for a single delivery attempt.
+pub const MAX_ACKED_REMOTE_SIGNAL_TIMEOUT: Duration = Duration::from_secs(60);
+
+#[cfg_attr(
+    feature = "instrument",
+    tracing::instrument(skip(_ribosome, call_context, input))
+)]
+pub fn send_remote_signal_acked(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: AckedRemoteSignal,
+) -> Result<Vec<RemoteSignalDelivery>, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            write_network: Permission::Allow,
+            agent_info: Permission::Allow,
+            ..
+        } => {
+            let from_agent = super::agent_info::agent_info(_ribosome, call_context.clone(), ())?
+                .agent_latest_pubkey;
+            let network = call_context.host_context().network().clone();
+            let keystore = call_context.host_context.keystore().clone();
+            let zome_name = call_context.zome().zome_name().clone();
+            let AckedRemoteSignal {
+                agents,
+                signal,
+                retries,
+                timeout_ms,
+            } = input;
+            let timeout = timeout_ms
+                .map(Duration::from_millis)
+                .unwrap_or(DEFAULT_ACKED_REMOTE_SIGNAL_TIMEOUT)
+                .min(MAX_ACKED_REMOTE_SIGNAL_TIMEOUT);
+            let max_attempts = retries.saturating_add(1);
+
+            tokio_helper::block_forever_on(join_all(agents.into_iter().map(|agent| {
+                deliver(
+                    network.clone(),
+                    keystore.clone(),
+                    from_agent.clone(),
+                    agent,
+                    zome_name.clone(),
+                    signal.clone(),
+                    timeout,
+                    max_attempts,
+                )
+            })))
+            .into_iter()
+            .collect()
+        }
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "send_remote_signal_acked".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
+
+/// Call `recv_remote_signal` on a single agent until it is acknowledged,
+/// rejected, or `max_attempts` is reached.
+#[allow(clippy::too_many_arguments)]
+async fn deliver(
+    network: GenericNetwork,
+    keystore: MetaLairClient,
+    from_agent: AgentPubKey,
+    agent: AgentPubKey,
+    zome_name: ZomeName,
+    signal: ExternIO,
+    timeout: Duration,
+    max_attempts: u8,
+) -> Result<RemoteSignalDelivery, RuntimeError> {
+    const FN_NAME: &str = "recv_remote_signal";
+    let mut attempts = 0;
+    loop {
+        attempts += 1;
+        // Every attempt needs its own nonce, otherwise a retry after a late
+        // acknowledgement would be rejected as a replay.
+        let (nonce, expires_at) = fresh_nonce(Timestamp::now()).map_err(|e| -> RuntimeError {
+            wasm_error!(WasmErrorInner::Host(e.to_string())).into()
+        })?;
+        let zome_call_unsigned = ZomeCallUnsigned {
+            provenance: from_agent.clone(),
+            cell_id: CellId::new(network.dna_hash(), agent.clone()),
+            zome_name: zome_name.clone(),
+            fn_name: FN_NAME.into(),
+            cap_secret: None,
+            payload: signal.clone(),
+            nonce,
+            expires_at,
+        };
+        let signature = from_agent
+            .sign_raw(
+                &keystore,
+                zome_call_unsigned
+                    .data_to_sign()
+                    .map_err(|e| -> RuntimeError { wasm_error!(e.to_string()).into() })?,
+            )
+            .await
+            .map_err(|e| -> RuntimeError {
+                wasm_error!(WasmErrorInner::Host(e.to_string())).into()
+            })?;
+
+        let status = match tokio::time::timeout(
+            timeout,
+            network.call_remote(
+                from_agent.clone(),
+                signature,
+                agent.clone(),
+                zome_call_unsigned.zome_name,
+                zome_call_unsigned.fn_name,
+                zome_call_unsigned.cap_secret,
+                zome_call_unsigned.payload,
+                zome_call_unsigned.nonce,
+                zome_call_unsigned.expires_at,
+            ),
+        )
+        .await
+        {
+            Err(_) => RemoteSignalDeliveryStatus::TimedOut,
+            Ok(Err(e)) => RemoteSignalDeliveryStatus::Unreachable(e.to_string()),
+            Ok(Ok(serialized_bytes)) => match ZomeCallResponse::try_from(serialized_bytes) {
+                Ok(ZomeCallResponse::Ok(_)) => RemoteSignalDeliveryStatus::Delivered,
+                Ok(response) => RemoteSignalDeliveryStatus::Rejected(format!("{:?}", response)),
+                Err(e) => RemoteSignalDeliveryStatus::Rejected(e.to_string()),
+            },
+        };
+
+        // Only network failures are worth retrying, a rejection will be
+        // rejected again.
+        let retryable = matches!(
+            status,
+            RemoteSignalDeliveryStatus::TimedOut | RemoteSignalDeliveryStatus::Unreachable(_)
+        );
+        if !retryable || attempts >= max_attempts {
+            return Ok(RemoteSignalDelivery {
+                agent,
+                status,
+                attempts,
+            });
+        }
+        tracing::debug!(?agent, ?status, attempts, "Retrying remote signal");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sweettest::*;
+    use hdk::prelude::*;
+
+    fn test_zome(agents: Vec<AgentPubKey>) -> InlineIntegrityZome {
+        InlineIntegrityZome::new_unique(vec![], 0)
+            .function("signal_others", move |api, ()| {
+                let signal = ExternIO::encode("Hey").unwrap();
+                let deliveries = api.send_remote_signal_acked(
+                    AckedRemoteSignal::new(agents.clone(), signal)
+                        .with_retries(1)
+                        .with_timeout_ms(2_000),
+                )?;
+                Ok(deliveries)
+            })
+            .function("recv_remote_signal", move |api, signal: ExternIO| {
+                api.emit_signal(AppSignal::new(signal)).map_err(Into::into)
+            })
+            .function("init", move |api, ()| {
+                let mut fns = BTreeSet::new();
+                fns.insert((api.zome_info(()).unwrap().name, "recv_remote_signal".into()));
+                let functions = GrantedFunctions::Listed(fns);
+                let cap_grant_entry = CapGrantEntry {
+                    tag: "".into(),
+                    // empty access converts to unrestricted
+                    access: ().into(),
+                    functions,
+                };
+                api.create(CreateInput::new(
+                    EntryDefLocation::CapGrant,
+                    EntryVisibility::Private,
+                    Entry::CapGrant(cap_grant_entry),
+                    ChainTopOrdering::default(),
+                ))
+                .unwrap();
+
+                Ok(InitCallbackResult::Pass)
+            })
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[cfg(feature = "test_utils")]
+    async fn acked_remote_signal_reports_delivery_per_agent() {
+        holochain_trace::test_run();
+
+        let config = SweetConductorConfig::standard().no_dpki();
+        let mut conductors = SweetConductorBatch::from_config(2, config).await;
+
+        let agents =
+            futures::future::join_all(conductors.iter().map(|c| SweetAgents::one(c.keystore())))
+                .await;
+        // An agent that no conductor is running, so it can never acknowledge.
+        let absent_agent = AgentPubKey::from_raw_32(vec![0xdb; 32]);
+
+        let zome = test_zome(vec![agents[1].clone(), absent_agent.clone()]);
+        let (dna_file, _, _) = SweetDnaFile::unique_from_inline_zomes(("zome", zome)).await;
+
+        let apps = conductors
+            .setup_app_for_zipped_agents("app", &agents, &[dna_file.clone()])
+            .await
+            .unwrap();
+
+        conductors.exchange_peer_info().await;
+
+        let cells = apps.cells_flattened();
+        let mut signals = conductors[1].subscribe_to_app_signals("app".to_string());
+
+        let deliveries: Vec<RemoteSignalDelivery> = conductors[0]
+            .call(&cells[0].zome("zome"), "signal_others", ())
+            .await;
+
+        assert_eq!(deliveries.len(), 2);
+        assert_eq!(deliveries[0].agent, agents[1]);
+        assert_eq!(deliveries[0].status, RemoteSignalDeliveryStatus::Delivered);
+        assert_eq!(deliveries[0].attempts, 1);
+
+        assert_eq!(deliveries[1].agent, absent_agent);
+        assert!(!deliveries[1].is_delivered());
+        // The first attempt plus one retry.
+        assert_eq!(deliveries[1].attempts, 2);
+
+        signals.recv().await.expect("Failed to recv signal");
+    }
+}
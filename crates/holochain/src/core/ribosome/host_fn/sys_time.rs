@@ -17,7 +17,7 @@ pub fn sys_time(
         HostFnAccess {
             non_determinism: Permission::Allow,
             ..
-        } => Ok(holochain_zome_types::prelude::Timestamp::now()),
+        } => Ok(call_context.host_context().clock().now()),
         _ => Err(wasm_error!(WasmErrorInner::Host(
             RibosomeError::HostFnPermissions(
                 call_context.zome.zome_name().clone(),
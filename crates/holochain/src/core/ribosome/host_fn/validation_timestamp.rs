@@ -0,0 +1,31 @@
+use crate::core::ribosome::guest_callback::validate::ValidateHostAccess;
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostContext;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_wasmer_host::prelude::*;
+use holochain_zome_types::prelude::Timestamp;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+/// The timestamp of the action being validated, to use instead of `sys_time` in validation.
+pub fn validation_timestamp(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    _input: (),
+) -> Result<Timestamp, RuntimeError> {
+    match &call_context.host_context() {
+        HostContext::Validate(ValidateHostAccess {
+            validation_context, ..
+        }) => Ok(validation_context.action_timestamp),
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "validation_timestamp".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
@@ -0,0 +1,154 @@
+use crate::core::ribosome::CallContext;
+use crate::core::ribosome::HostFnAccess;
+use crate::core::ribosome::RibosomeError;
+use crate::core::ribosome::RibosomeT;
+use holochain_keystore::AgentPubKeyExt;
+use holochain_types::prelude::*;
+use holochain_wasmer_host::prelude::*;
+use std::sync::Arc;
+use wasmer::RuntimeError;
+
+pub fn verify_private_entry_proof(
+    _ribosome: Arc<impl RibosomeT>,
+    call_context: Arc<CallContext>,
+    input: PrivateEntryProof,
+) -> Result<bool, RuntimeError> {
+    match HostFnAccess::from(&call_context.host_context()) {
+        HostFnAccess {
+            keystore_deterministic: Permission::Allow,
+            ..
+        } => tokio_helper::block_forever_on(async move {
+            let is_private = input.entry_type().map_or(false, |entry_type| {
+                *entry_type.visibility() == EntryVisibility::Private
+            });
+            if !is_private {
+                return Ok(false);
+            }
+            if input.signed_action.verify_signature().await.is_err() {
+                return Ok(false);
+            }
+            let data =
+                PrivateEntryProof::challenge_data(input.signed_action.as_hash(), &input.challenge)
+                    .map_err(|e| -> RuntimeError { wasm_error!(e).into() })?;
+            input
+                .author()
+                .verify_signature_raw(&input.challenge_signature, data.into())
+                .await
+                .map_err(|e| wasmer::RuntimeError::user(Box::new(e)))
+        }),
+        _ => Err(wasm_error!(WasmErrorInner::Host(
+            RibosomeError::HostFnPermissions(
+                call_context.zome.zome_name().clone(),
+                call_context.function_name().clone(),
+                "verify_private_entry_proof".into(),
+            )
+            .to_string(),
+        ))
+        .into()),
+    }
+}
+
+#[cfg(test)]
+pub mod wasm_test {
+    use super::verify_private_entry_proof;
+    use crate::core::ribosome::HostContext;
+    use crate::fixt::CallContextFixturator;
+    use crate::fixt::RealRibosomeFixturator;
+    use crate::fixt::ValidateHostAccessFixturator;
+    use crate::fixt::ZomeCallHostAccessFixturator;
+    use ::fixt::prelude::*;
+    use holochain_keystore::AgentPubKeyExt;
+    use holochain_keystore::MetaLairClient;
+    use holochain_types::prelude::*;
+    use std::sync::Arc;
+
+    async fn proof(
+        keystore: &MetaLairClient,
+        visibility: EntryVisibility,
+        challenge: Bytes,
+    ) -> PrivateEntryProof {
+        let author = keystore.new_sign_keypair_random().await.unwrap();
+        let mut create = fixt!(Create);
+        create.author = author.clone();
+        create.entry_type = EntryType::App(AppEntryDef {
+            entry_index: 0.into(),
+            zome_index: 0.into(),
+            visibility,
+        });
+        let signed_action =
+            SignedActionHashed::sign(keystore, ActionHashed::from_content_sync(create.into()))
+                .await
+                .unwrap();
+        let data =
+            PrivateEntryProof::challenge_data(signed_action.as_hash(), &challenge).unwrap();
+        let challenge_signature = author.sign_raw(keystore, data.into()).await.unwrap();
+        PrivateEntryProof {
+            signed_action,
+            challenge,
+            challenge_signature,
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn verify_private_entry_proof_test() {
+        let ribosome = Arc::new(
+            RealRibosomeFixturator::new(crate::fixt::curve::Zomes(vec![]))
+                .next()
+                .unwrap(),
+        );
+        let mut call_context = CallContextFixturator::new(::fixt::Unpredictable)
+            .next()
+            .unwrap();
+        call_context.host_context = HostContext::ZomeCall(fixt!(ZomeCallHostAccess));
+        let keystore = call_context.host_context.keystore().clone();
+        let call_context = Arc::new(call_context);
+        let challenge = Bytes::from(vec![1, 2, 3]);
+
+        let valid = proof(&keystore, EntryVisibility::Private, challenge.clone()).await;
+        assert!(
+            verify_private_entry_proof(ribosome.clone(), call_context.clone(), valid.clone())
+                .unwrap()
+        );
+
+        // The challenge signature doesn't cover another challenge.
+        let replayed = PrivateEntryProof {
+            challenge: Bytes::from(vec![4, 5, 6]),
+            ..valid.clone()
+        };
+        assert!(
+            !verify_private_entry_proof(ribosome.clone(), call_context.clone(), replayed).unwrap()
+        );
+
+        // The challenge must be signed by the author of the action.
+        let other = proof(&keystore, EntryVisibility::Private, challenge.clone()).await;
+        let wrong_signer = PrivateEntryProof {
+            challenge_signature: other.challenge_signature,
+            ..valid
+        };
+        assert!(
+            !verify_private_entry_proof(ribosome.clone(), call_context.clone(), wrong_signer)
+                .unwrap()
+        );
+
+        // Public entries are not proven this way.
+        let public = proof(&keystore, EntryVisibility::Public, challenge).await;
+        assert!(!verify_private_entry_proof(ribosome, call_context, public).unwrap());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn verify_private_entry_proof_in_validation_test() {
+        let ribosome = Arc::new(
+            RealRibosomeFixturator::new(crate::fixt::curve::Zomes(vec![]))
+                .next()
+                .unwrap(),
+        );
+        let mut call_context = CallContextFixturator::new(::fixt::Unpredictable)
+            .next()
+            .unwrap();
+        let keystore = holochain_keystore::test_keystore();
+        call_context.host_context = HostContext::Validate(fixt!(ValidateHostAccess));
+
+        let proof = proof(&keystore, EntryVisibility::Private, Bytes::from(vec![1])).await;
+        assert!(verify_private_entry_proof(ribosome, Arc::new(call_context), proof).unwrap());
+    }
+}
@@ -33,33 +33,52 @@ use crate::core::ribosome::host_fn::capability_claims::capability_claims;
 use crate::core::ribosome::host_fn::capability_grants::capability_grants;
 use crate::core::ribosome::host_fn::capability_info::capability_info;
 use crate::core::ribosome::host_fn::create::create;
+use crate::core::ribosome::host_fn::create_chunked::create_chunked;
 use crate::core::ribosome::host_fn::create_clone_cell::create_clone_cell;
+use crate::core::ribosome::host_fn::create_entries::create_entries;
 use crate::core::ribosome::host_fn::create_link::create_link;
+use crate::core::ribosome::host_fn::create_links::create_links;
+use crate::core::ribosome::host_fn::create_private_entry_proof::create_private_entry_proof;
 use crate::core::ribosome::host_fn::create_x25519_keypair::create_x25519_keypair;
 use crate::core::ribosome::host_fn::delete::delete;
 use crate::core::ribosome::host_fn::delete_link::delete_link;
+use crate::core::ribosome::host_fn::deterministic_random_bytes::deterministic_random_bytes;
 use crate::core::ribosome::host_fn::dna_info_1::dna_info_1;
 use crate::core::ribosome::host_fn::dna_info_2::dna_info_2;
 use crate::core::ribosome::host_fn::ed_25519_x_salsa20_poly1305_decrypt::ed_25519_x_salsa20_poly1305_decrypt;
 use crate::core::ribosome::host_fn::ed_25519_x_salsa20_poly1305_encrypt::ed_25519_x_salsa20_poly1305_encrypt;
+use crate::core::ribosome::host_fn::emit_metric::emit_metric;
 use crate::core::ribosome::host_fn::emit_signal::emit_signal;
 use crate::core::ribosome::host_fn::get::get;
+use crate::core::ribosome::host_fn::get_agent_presence::get_agent_presence;
+use crate::core::ribosome::host_fn::get_chunked::get_chunked;
 use crate::core::ribosome::host_fn::get_details::get_details;
 use crate::core::ribosome::host_fn::get_link_details::get_link_details;
 use crate::core::ribosome::host_fn::get_links::get_links;
+use crate::core::ribosome::host_fn::get_links_summary::get_links_summary;
 use crate::core::ribosome::host_fn::hash::hash;
 use crate::core::ribosome::host_fn::must_get_action::must_get_action;
+use crate::core::ribosome::host_fn::must_get_action_with_options::must_get_action_with_options;
 use crate::core::ribosome::host_fn::must_get_agent_activity::must_get_agent_activity;
 use crate::core::ribosome::host_fn::must_get_entry::must_get_entry;
+use crate::core::ribosome::host_fn::must_get_entry_with_options::must_get_entry_with_options;
 use crate::core::ribosome::host_fn::must_get_valid_record::must_get_valid_record;
+use crate::core::ribosome::host_fn::must_get_valid_record_with_options::must_get_valid_record_with_options;
+use crate::core::ribosome::host_fn::outbox_delivery::outbox_delivery;
 use crate::core::ribosome::host_fn::query::query;
+use crate::core::ribosome::host_fn::query_cell::query_cell;
+use crate::core::ribosome::host_fn::query_local_fulltext::query_local_fulltext;
+use crate::core::ribosome::host_fn::queue_remote_message::queue_remote_message;
 use crate::core::ribosome::host_fn::random_bytes::random_bytes;
 use crate::core::ribosome::host_fn::send_remote_signal::send_remote_signal;
+use crate::core::ribosome::host_fn::send_remote_signal_acked::send_remote_signal_acked;
 use crate::core::ribosome::host_fn::sign::sign;
 use crate::core::ribosome::host_fn::sign_ephemeral::sign_ephemeral;
 use crate::core::ribosome::host_fn::sys_time::sys_time;
 use crate::core::ribosome::host_fn::trace::trace;
 use crate::core::ribosome::host_fn::update::update;
+use crate::core::ribosome::host_fn::validation_timestamp::validation_timestamp;
+use crate::core::ribosome::host_fn::verify_private_entry_proof::verify_private_entry_proof;
 use crate::core::ribosome::host_fn::verify_signature::verify_signature;
 use crate::core::ribosome::host_fn::version::version;
 use crate::core::ribosome::host_fn::x_25519_x_salsa20_poly1305_decrypt::x_25519_x_salsa20_poly1305_decrypt;
@@ -74,6 +93,7 @@ use crate::core::ribosome::CallContext;
 use crate::core::ribosome::GenesisSelfCheckHostAccessV1;
 use crate::core::ribosome::GenesisSelfCheckHostAccessV2;
 use crate::core::ribosome::Invocation;
+use crate::core::ribosome::MustGetLimits;
 use crate::core::ribosome::RibosomeT;
 use crate::core::ribosome::ZomeCallInvocation;
 use futures::FutureExt;
@@ -160,6 +180,13 @@ pub struct RealRibosome {
     /// File system and in-memory cache for wasm modules.
     pub wasmer_module_cache: Arc<ModuleCacheLock>,
 
+    /// The maximum number of wasm operations per zome call.
+    /// The limit of the wasm engine is used if this is not set.
+    pub metering_limit: Option<u64>,
+
+    /// Caps on the network timeouts and retries of `must_get_*` calls.
+    pub must_get_limits: MustGetLimits,
+
     #[cfg(test)]
     /// Wasm cache for Deepkey wasm in a temporary directory to be shared across all tests.
     pub shared_test_module_cache: Arc<ModuleCacheLock>,
@@ -174,11 +201,17 @@ static CONTEXT_MAP: ContextMap = Lazy::new(Default::default);
 // host fn calls.
 static CONTEXT_KEY: AtomicU64 = AtomicU64::new(0);
 
+// Host functions whose results differ between nodes. Instances built for
+// validation link these to a stub that always errors, so validation can only
+// use the deterministic alternatives.
+const NON_DETERMINISTIC_HOST_FNS: [&str; 2] = ["__hc__random_bytes_1", "__hc__sys_time_1"];
+
 struct HostFnBuilder {
     store: Arc<Mutex<Store>>,
     function_env: FunctionEnv<Env>,
     ribosome_arc: Arc<RealRibosome>,
     context_key: u64,
+    deterministic: bool,
 }
 
 impl HostFnBuilder {
@@ -194,6 +227,9 @@ impl HostFnBuilder {
     {
         let ribosome_arc = Arc::clone(&self.ribosome_arc);
         let context_key = self.context_key;
+        let banned = (self.deterministic
+            && NON_DETERMINISTIC_HOST_FNS.contains(&host_function_name))
+        .then(|| host_function_name.trim_start_matches("__hc__").to_string());
         {
             let mut store_lock = self.store.lock();
             let mut store_mut = store_lock.as_store_mut();
@@ -217,7 +253,18 @@ impl HostFnBuilder {
                         };
                         let (env, mut store_mut) = function_env_mut.data_and_store_mut();
                         let result = match env.consume_bytes_from_guest(&mut store_mut, guest_ptr, len) {
-                            Ok(input) => host_function(Arc::clone(&ribosome_arc), context_arc, input),
+                            Ok(input) => match &banned {
+                                Some(name) => Err(wasm_error!(WasmErrorInner::Host(
+                                    RibosomeError::NonDeterministicHostFn(
+                                        context_arc.zome.zome_name().clone(),
+                                        context_arc.function_name().clone(),
+                                        name.clone(),
+                                    )
+                                    .to_string(),
+                                ))
+                                .into()),
+                                None => host_function(Arc::clone(&ribosome_arc), context_arc, input),
+                            },
                             Err(runtime_error) => Result::<_, RuntimeError>::Err(runtime_error),
                         };
                         Ok(u64::from_le_bytes(
@@ -279,12 +326,21 @@ impl RealRibosome {
             zome_dependencies: Default::default(),
             usage_meter: Self::standard_usage_meter(),
             wasmer_module_cache,
+            metering_limit: None,
+            must_get_limits: Default::default(),
             #[cfg(test)]
             shared_test_module_cache: Arc::new(ModuleCacheLock::new(ModuleCache::new(
                 _shared_test_module_cache,
             ))),
         };
 
+        // Reject DNAs whose init dependencies can never be satisfied before
+        // any callback gets the chance to run.
+        ribosome
+            .dna_def()
+            .init_order()
+            .map_err(|e| RibosomeError::DnaError(DnaError::ZomeError(e)))?;
+
         // Collect the number of entry and link types
         // for each integrity zome.
         // TODO: should this be in parallel? Are they all beholden to the same lock?
@@ -370,6 +426,18 @@ impl RealRibosome {
         Ok(ribosome)
     }
 
+    /// Limit the number of wasm operations each zome call may execute.
+    pub fn with_metering_limit(mut self, metering_limit: Option<u64>) -> Self {
+        self.metering_limit = metering_limit;
+        self
+    }
+
+    /// Cap the network timeouts and retries that `must_get_*` calls may ask for.
+    pub fn with_must_get_limits(mut self, must_get_limits: MustGetLimits) -> Self {
+        self.must_get_limits = must_get_limits;
+        self
+    }
+
     #[cfg(any(test, feature = "test_utils"))]
     pub fn empty(dna_file: DnaFile) -> Self {
         Self {
@@ -378,6 +446,8 @@ impl RealRibosome {
             zome_dependencies: Default::default(),
             usage_meter: Self::standard_usage_meter(),
             wasmer_module_cache: Arc::new(ModuleCacheLock::new(ModuleCache::new(None))),
+            metering_limit: None,
+            must_get_limits: Default::default(),
             #[cfg(test)]
             shared_test_module_cache: Arc::new(ModuleCacheLock::new(ModuleCache::new(None))),
         }
@@ -434,10 +504,17 @@ impl RealRibosome {
         module: Arc<Module>,
         context_key: u64,
         name: &str,
+        deterministic: bool,
     ) -> RibosomeResult<Arc<InstanceWithStore>> {
         let store = Arc::new(Mutex::new(Store::default()));
         let function_env = FunctionEnv::new(&mut store.lock().as_store_mut(), Env::default());
-        let (function_env, imports) = Self::imports(self, context_key, store.clone(), function_env);
+        let (function_env, imports) = Self::imports(
+            self,
+            context_key,
+            store.clone(),
+            function_env,
+            deterministic,
+        );
         let instance;
         {
             let mut store = store.lock();
@@ -504,6 +581,10 @@ impl RealRibosome {
             integrity_zomes: Default::default(),
             coordinator_zomes: Default::default(),
             lineage: Default::default(),
+            size_limits: Default::default(),
+            required_validation_receipts: None,
+            interactive_types: Default::default(),
+            fulltext_entry_types: Default::default(),
         };
         let empty_dna_file = DnaFile::new(empty_dna_def, vec![]).await;
         let empty_ribosome = RealRibosome::new(
@@ -516,8 +597,12 @@ impl RealRibosome {
         // We just leave this Env uninitialized as default because we never make it
         // to an instance that needs to run on this code path.
         let function_env = FunctionEnv::new(&mut store.as_store_mut(), Env::default());
-        let (_function_env, imports) =
-            empty_ribosome.imports(context_key, Arc::new(Mutex::new(store)), function_env);
+        let (_function_env, imports) = empty_ribosome.imports(
+            context_key,
+            Arc::new(Mutex::new(store)),
+            function_env,
+            false,
+        );
         let mut imports: Vec<String> = imports.into_iter().map(|((_ns, name), _)| name).collect();
         imports.sort();
         Ok(imports)
@@ -528,6 +613,7 @@ impl RealRibosome {
         context_key: u64,
         store: Arc<Mutex<Store>>,
         function_env: FunctionEnv<Env>,
+        deterministic: bool,
     ) -> (FunctionEnv<Env>, Imports) {
         let mut imports = wasmer::imports! {};
         let mut ns = Exports::new();
@@ -540,6 +626,7 @@ impl RealRibosome {
             function_env,
             ribosome_arc,
             context_key,
+            deterministic,
         };
 
         host_fn_builder
@@ -548,6 +635,11 @@ impl RealRibosome {
             .with_host_function(&mut ns, "__hc__hash_1", hash)
             .with_host_function(&mut ns, "__hc__version_1", version)
             .with_host_function(&mut ns, "__hc__verify_signature_1", verify_signature)
+            .with_host_function(
+                &mut ns,
+                "__hc__verify_private_entry_proof_1",
+                verify_private_entry_proof,
+            )
             .with_host_function(&mut ns, "__hc__sign_1", sign)
             .with_host_function(&mut ns, "__hc__sign_ephemeral_1", sign_ephemeral)
             .with_host_function(
@@ -606,15 +698,28 @@ impl RealRibosome {
             .with_host_function(&mut ns, "__hc__call_info_1", call_info)
             .with_host_function(&mut ns, "__hc__random_bytes_1", random_bytes)
             .with_host_function(&mut ns, "__hc__sys_time_1", sys_time)
+            .with_host_function(
+                &mut ns,
+                "__hc__deterministic_random_bytes_1",
+                deterministic_random_bytes,
+            )
+            .with_host_function(
+                &mut ns,
+                "__hc__validation_timestamp_1",
+                validation_timestamp,
+            )
             .with_host_function(&mut ns, "__hc__capability_claims_1", capability_claims)
             .with_host_function(&mut ns, "__hc__capability_grants_1", capability_grants)
             .with_host_function(&mut ns, "__hc__capability_info_1", capability_info)
             .with_host_function(&mut ns, "__hc__get_1", get)
+            .with_host_function(&mut ns, "__hc__get_chunked_1", get_chunked)
             .with_host_function(&mut ns, "__hc__get_details_1", get_details)
             .with_host_function(&mut ns, "__hc__get_links_1", get_links)
             .with_host_function(&mut ns, "__hc__get_link_details_1", get_link_details)
             .with_host_function(&mut ns, "__hc__count_links_1", count_links)
+            .with_host_function(&mut ns, "__hc__get_links_summary_1", get_links_summary)
             .with_host_function(&mut ns, "__hc__get_agent_activity_1", get_agent_activity)
+            .with_host_function(&mut ns, "__hc__get_agent_presence_1", get_agent_presence)
             .with_host_function(&mut ns, "__hc__must_get_entry_1", must_get_entry)
             .with_host_function(&mut ns, "__hc__must_get_action_1", must_get_action)
             .with_host_function(
@@ -622,17 +727,58 @@ impl RealRibosome {
                 "__hc__must_get_valid_record_1",
                 must_get_valid_record,
             )
+            .with_host_function(
+                &mut ns,
+                "__hc__must_get_entry_with_options_1",
+                must_get_entry_with_options,
+            )
+            .with_host_function(
+                &mut ns,
+                "__hc__must_get_action_with_options_1",
+                must_get_action_with_options,
+            )
+            .with_host_function(
+                &mut ns,
+                "__hc__must_get_valid_record_with_options_1",
+                must_get_valid_record_with_options,
+            )
             .with_host_function(
                 &mut ns,
                 "__hc__must_get_agent_activity_1",
                 must_get_agent_activity,
             )
             .with_host_function(&mut ns, "__hc__query_1", query)
+            .with_host_function(&mut ns, "__hc__query_cell_1", query_cell)
+            .with_host_function(
+                &mut ns,
+                "__hc__query_local_fulltext_1",
+                query_local_fulltext,
+            )
+            .with_host_function(
+                &mut ns,
+                "__hc__create_private_entry_proof_1",
+                create_private_entry_proof,
+            )
             .with_host_function(&mut ns, "__hc__send_remote_signal_1", send_remote_signal)
+            .with_host_function(
+                &mut ns,
+                "__hc__send_remote_signal_acked_1",
+                send_remote_signal_acked,
+            )
+            .with_host_function(
+                &mut ns,
+                "__hc__queue_remote_message_1",
+                queue_remote_message,
+            )
+            .with_host_function(&mut ns, "__hc__outbox_delivery_1", outbox_delivery)
             .with_host_function(&mut ns, "__hc__call_1", call)
             .with_host_function(&mut ns, "__hc__create_1", create)
+            .with_host_function(&mut ns, "__hc__create_entries_1", create_entries)
+            .with_host_function(&mut ns, "__hc__create_chunked_1", create_chunked)
             .with_host_function(&mut ns, "__hc__emit_signal_1", emit_signal)
+            .with_host_function(&mut ns, "__hc__emit_metric_1", emit_metric)
             .with_host_function(&mut ns, "__hc__create_link_1", create_link)
+            .with_host_function(&mut ns, "__hc__create_links_1", create_links)
             .with_host_function(&mut ns, "__hc__delete_link_1", delete_link)
             .with_host_function(&mut ns, "__hc__update_1", update)
             .with_host_function(&mut ns, "__hc__delete_1", delete)
@@ -852,8 +998,15 @@ impl RealRibosome {
                 if module.info().exports.contains_key(fn_name.as_ref()) {
                     // there is a corresponding zome fn
                     let context_key = Self::next_context_key();
-                    let instance_with_store =
-                        self.build_instance_with_store(module, context_key, &zome.name.0)?;
+                    // Validation must reach the same result on every node.
+                    let deterministic =
+                        matches!(call_context.host_context, HostContext::Validate(_));
+                    let instance_with_store = self.build_instance_with_store(
+                        module,
+                        context_key,
+                        &zome.name.0,
+                        deterministic,
+                    )?;
                     // add call context to map for the following call
                     {
                         CONTEXT_MAP
@@ -862,23 +1015,36 @@ impl RealRibosome {
                     }
 
                     // Reset available metering points to the maximum allowed per zome call
-                    reset_metering_points(instance_with_store.clone());
+                    reset_metering_points(instance_with_store.clone(), self.metering_limit);
 
                     // be aware of this clone!
                     // the whole invocation is cloned!
                     // @todo - is this a problem for large payloads like entries?
                     let input = invocation.clone().host_input()?;
                     let instance_with_store_clone = instance_with_store.clone();
-                    let result = tokio::task::spawn_blocking(move || {
-                        Self::call_zome_fn(input, zome, fn_name, instance_with_store_clone)
+                    let zome_name = zome.zome_name().clone();
+                    let call_fn_name = fn_name.clone();
+                    let mut result = tokio::task::spawn_blocking(move || {
+                        Self::call_zome_fn(input, zome, call_fn_name, instance_with_store_clone)
                             .map(Some)
                     })
                     .await?;
 
                     // Get metering points consumed in zome call and save to usage_meter
-                    let points_used = get_used_metering_points(instance_with_store.clone());
+                    let points_used =
+                        get_used_metering_points(instance_with_store.clone(), self.metering_limit);
                     self.usage_meter.add(points_used, &otel_info);
 
+                    // A call that ran out of points was trapped by the engine, so report
+                    // that instead of the opaque runtime error.
+                    if result.is_err() && metering_points_exhausted(instance_with_store.clone()) {
+                        result = Err(RibosomeError::MeteringLimitExceeded(
+                            zome_name,
+                            fn_name,
+                            points_used,
+                        ));
+                    }
+
                     // remove context from map after call
                     {
                         CONTEXT_MAP.lock().remove(&context_key);
@@ -1011,7 +1177,7 @@ impl RibosomeT for RealRibosome {
                     // create a new key for the context map.
                     let context_key = Self::next_context_key();
                     let instance_with_store =
-                        self.build_instance_with_store(module, context_key, &zome.name.0)?;
+                        self.build_instance_with_store(module, context_key, &zome.name.0, false)?;
 
                     // add call context to map for following call
                     {
@@ -1154,6 +1320,10 @@ impl RibosomeT for RealRibosome {
         &self.dna_file
     }
 
+    fn must_get_limits(&self) -> MustGetLimits {
+        self.must_get_limits
+    }
+
     fn get_integrity_zome(&self, zome_index: &ZomeIndex) -> Option<IntegrityZome> {
         self.dna_file
             .dna_def()
@@ -1359,40 +1529,57 @@ pub mod wasm_test {
                 "__hc__close_chain_1",
                 "__hc__count_links_1",
                 "__hc__create_1",
+                "__hc__create_chunked_1",
                 "__hc__create_clone_cell_1",
+                "__hc__create_entries_1",
                 "__hc__create_link_1",
+                "__hc__create_links_1",
+                "__hc__create_private_entry_proof_1",
                 "__hc__create_x25519_keypair_1",
                 "__hc__delete_1",
                 "__hc__delete_clone_cell_1",
                 "__hc__delete_link_1",
+                "__hc__deterministic_random_bytes_1",
                 "__hc__disable_clone_cell_1",
                 "__hc__dna_info_1",
                 "__hc__dna_info_2",
                 "__hc__ed_25519_x_salsa20_poly1305_decrypt_1",
                 "__hc__ed_25519_x_salsa20_poly1305_encrypt_1",
+                "__hc__emit_metric_1",
                 "__hc__emit_signal_1",
                 "__hc__enable_clone_cell_1",
                 "__hc__get_1",
                 "__hc__get_agent_activity_1",
                 #[cfg(feature = "unstable-functions")]
                 "__hc__get_agent_key_lineage_1",
+                "__hc__get_agent_presence_1",
+                "__hc__get_chunked_1",
                 "__hc__get_details_1",
                 "__hc__get_link_details_1",
                 "__hc__get_links_1",
+                "__hc__get_links_summary_1",
                 "__hc__get_validation_receipts_1",
                 "__hc__hash_1",
                 #[cfg(feature = "unstable-functions")]
                 "__hc__is_same_agent_1",
                 "__hc__must_get_action_1",
+                "__hc__must_get_action_with_options_1",
                 "__hc__must_get_agent_activity_1",
                 "__hc__must_get_entry_1",
+                "__hc__must_get_entry_with_options_1",
                 "__hc__must_get_valid_record_1",
+                "__hc__must_get_valid_record_with_options_1",
                 "__hc__open_chain_1",
+                "__hc__outbox_delivery_1",
                 "__hc__query_1",
+                "__hc__query_cell_1",
+                "__hc__query_local_fulltext_1",
+                "__hc__queue_remote_message_1",
                 "__hc__random_bytes_1",
                 #[cfg(feature = "unstable-functions")]
                 "__hc__schedule_1",
                 "__hc__send_remote_signal_1",
+                "__hc__send_remote_signal_acked_1",
                 "__hc__sign_1",
                 "__hc__sign_ephemeral_1",
                 #[cfg(feature = "unstable-functions")]
@@ -1402,6 +1589,8 @@ pub mod wasm_test {
                 #[cfg(feature = "unstable-functions")]
                 "__hc__unblock_agent_1",
                 "__hc__update_1",
+                "__hc__validation_timestamp_1",
+                "__hc__verify_private_entry_proof_1",
                 "__hc__verify_signature_1",
                 "__hc__version_1",
                 "__hc__x_25519_x_salsa20_poly1305_decrypt_1",
@@ -1420,6 +1609,24 @@ pub mod wasm_test {
         );
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn clone_dnas_share_module_cache_keys() {
+        let (dna_file, _, _) = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::Create]).await;
+        let clone = dna_file.clone().with_network_seed("clone".into()).await;
+        assert_ne!(dna_file.dna_hash(), clone.dna_hash());
+
+        let ribosome = super::RealRibosome::empty(dna_file);
+        let clone_ribosome = super::RealRibosome::empty(clone);
+
+        // Different DNAs with the same wasm must look up the same compiled module.
+        for (zome_name, _) in ribosome.dna_file.dna_def().all_zomes() {
+            assert_eq!(
+                ribosome.get_module_cache_key(zome_name).unwrap(),
+                clone_ribosome.get_module_cache_key(zome_name).unwrap()
+            );
+        }
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     #[ignore]
     async fn the_incredible_halt_test() {
@@ -1446,4 +1653,34 @@ pub mod wasm_test {
         .await;
         assert!(create_result.unwrap().is_err());
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    #[cfg(feature = "wasmer_sys")]
+    async fn zome_call_exceeding_metering_limit_is_terminated() {
+        holochain_trace::test_run();
+
+        let config = SweetConductorConfig::standard().tune_conductor(|c| {
+            c.wasm_metering_limit = Some(1_000_000);
+        });
+        let mut conductor = SweetConductor::from_config(config).await;
+        let (dna_file, _, _) =
+            SweetDnaFile::unique_from_test_wasms(vec![TestWasm::TheIncredibleHalt]).await;
+        let app = conductor.setup_app("app", [&dna_file]).await.unwrap();
+        let zome = app.cells()[0].zome(TestWasm::TheIncredibleHalt);
+
+        // The call loops forever, so it must be stopped by the much lower limit.
+        let result: Result<(), _> = tokio::time::timeout(
+            std::time::Duration::from_secs(60),
+            conductor.call_fallible(&zome, "smash", ()),
+        )
+        .await
+        .unwrap();
+
+        let error = result.unwrap_err().to_string();
+        assert!(
+            error.contains("exceeding its execution budget of 1000000 wasm operations"),
+            "{}",
+            error
+        );
+    }
 }
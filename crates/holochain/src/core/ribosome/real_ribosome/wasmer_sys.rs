@@ -9,26 +9,43 @@ use std::sync::Arc;
 use wasmer::{AsStoreMut, Module};
 use wasmer_middlewares::metering::{get_remaining_points, set_remaining_points, MeteringPoints};
 
-pub fn reset_metering_points(instance_with_store: Arc<InstanceWithStore>) {
+/// Reset the available metering points to `limit`, or to the engine's limit if not set.
+pub fn reset_metering_points(instance_with_store: Arc<InstanceWithStore>, limit: Option<u64>) {
     let mut store_lock = instance_with_store.store.lock();
     let mut store_mut = store_lock.as_store_mut();
     set_remaining_points(
         &mut store_mut,
         instance_with_store.instance.as_ref(),
-        WASM_METERING_LIMIT,
+        limit.unwrap_or(WASM_METERING_LIMIT),
     );
 }
 
-pub fn get_used_metering_points(instance_with_store: Arc<InstanceWithStore>) -> u64 {
+/// Get the metering points used since they were reset to `limit`.
+pub fn get_used_metering_points(
+    instance_with_store: Arc<InstanceWithStore>,
+    limit: Option<u64>,
+) -> u64 {
+    let limit = limit.unwrap_or(WASM_METERING_LIMIT);
     let mut store_lock = instance_with_store.store.lock();
     let mut store_mut = store_lock.as_store_mut();
 
     match get_remaining_points(&mut store_mut, instance_with_store.instance.as_ref()) {
-        MeteringPoints::Remaining(points) => WASM_METERING_LIMIT - points,
-        MeteringPoints::Exhausted => WASM_METERING_LIMIT,
+        MeteringPoints::Remaining(points) => limit.saturating_sub(points),
+        MeteringPoints::Exhausted => limit,
     }
 }
 
+/// Check whether the instance was stopped because it ran out of metering points.
+pub fn metering_points_exhausted(instance_with_store: Arc<InstanceWithStore>) -> bool {
+    let mut store_lock = instance_with_store.store.lock();
+    let mut store_mut = store_lock.as_store_mut();
+
+    matches!(
+        get_remaining_points(&mut store_mut, instance_with_store.instance.as_ref()),
+        MeteringPoints::Exhausted
+    )
+}
+
 /// DEPRECATED: Bundling precompiled and preserialized wasm for iOS is deprecated. Please use the wasm interpreter instead.
 pub fn get_prebuilt_module(wasm_zome: &WasmZome) -> RibosomeResult<Option<Arc<Module>>> {
     match &wasm_zome.preserialized_path {
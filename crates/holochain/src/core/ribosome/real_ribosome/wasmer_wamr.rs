@@ -8,13 +8,21 @@ use tracing::warn;
 use wasmer::Module;
 
 // Metering is not supported in wasmer_wamr feature. This is a no-op.
-pub fn reset_metering_points(_instance_with_store: Arc<InstanceWithStore>) {}
+pub fn reset_metering_points(_instance_with_store: Arc<InstanceWithStore>, _limit: Option<u64>) {}
 
 // Metering is not supported in wasmer_wamr feature. This is a no-op.
-pub fn get_used_metering_points(_instance_with_store: Arc<InstanceWithStore>) -> u64 {
+pub fn get_used_metering_points(
+    _instance_with_store: Arc<InstanceWithStore>,
+    _limit: Option<u64>,
+) -> u64 {
     0
 }
 
+// Metering is not supported in wasmer_wamr feature, so points are never exhausted.
+pub fn metering_points_exhausted(_instance_with_store: Arc<InstanceWithStore>) -> bool {
+    false
+}
+
 // Use of precompiled and serialized modules is not supported in wasmer_wamr feature.
 // If a preserialized_path is specified for the zome, it is ignored.
 pub fn get_prebuilt_module(wasm_zome: &WasmZome) -> RibosomeResult<Option<Arc<Module>>> {
@@ -464,15 +464,32 @@ pub fn check_new_entry_action(action: &Action) -> SysValidationResult<()> {
     }
 }
 
-/// Check the entry size is under the MAX_ENTRY_SIZE
-pub fn check_entry_size(entry: &Entry) -> SysValidationResult<()> {
+/// The largest entry which can be committed to a DNA with these limits.
+/// This is never more than the MAX_ENTRY_SIZE.
+pub fn max_entry_size(size_limits: &PayloadSizeLimits) -> usize {
+    size_limits
+        .max_entry_size
+        .map_or(MAX_ENTRY_SIZE, |max| MAX_ENTRY_SIZE.min(max as usize))
+}
+
+/// The largest link tag which can be committed to a DNA with these limits.
+/// This is never more than the MAX_TAG_SIZE.
+pub fn max_tag_size(size_limits: &PayloadSizeLimits) -> usize {
+    size_limits
+        .max_link_tag_size
+        .map_or(MAX_TAG_SIZE, |max| MAX_TAG_SIZE.min(max as usize))
+}
+
+/// Check the entry size is under the limit for the DNA
+pub fn check_entry_size(entry: &Entry, size_limits: &PayloadSizeLimits) -> SysValidationResult<()> {
     match entry {
         Entry::App(bytes) | Entry::CounterSign(_, bytes) => {
             let size = std::mem::size_of_val(&bytes.bytes()[..]);
-            if size <= MAX_ENTRY_SIZE {
+            let max = max_entry_size(size_limits);
+            if size <= max {
                 Ok(())
             } else {
-                Err(ValidationOutcome::EntryTooLarge(size).into())
+                Err(ValidationOutcome::EntryTooLarge(size, max).into())
             }
         }
         _ => {
@@ -482,13 +499,14 @@ pub fn check_entry_size(entry: &Entry) -> SysValidationResult<()> {
     }
 }
 
-/// Check the link tag size is under the MAX_TAG_SIZE
-pub fn check_tag_size(tag: &LinkTag) -> SysValidationResult<()> {
+/// Check the link tag size is under the limit for the DNA
+pub fn check_tag_size(tag: &LinkTag, size_limits: &PayloadSizeLimits) -> SysValidationResult<()> {
     let size = std::mem::size_of_val(&tag.0[..]);
-    if size <= MAX_TAG_SIZE {
+    let max = max_tag_size(size_limits);
+    if size <= max {
         Ok(())
     } else {
-        Err(ValidationOutcome::TagTooLarge(size).into())
+        Err(ValidationOutcome::TagTooLarge(size, max).into())
     }
 }
 
@@ -2,7 +2,6 @@ use holochain_conductor_services::DpkiServiceError;
 use std::convert::TryFrom;
 
 use super::SourceChainError;
-use super::MAX_ENTRY_SIZE;
 use crate::conductor::api::error::ConductorApiError;
 use crate::conductor::entry_def_store::error::EntryDefStoreError;
 use crate::core::validation::OutcomeOrError;
@@ -126,20 +125,14 @@ pub enum ValidationOutcome {
     EntryDefId(AppEntryDef),
     #[error("The entry has a different hash to the action's entry hash")]
     EntryHash,
-    #[error(
-        "The entry size {0} was larger than the MAX_ENTRY_SIZE {}",
-        MAX_ENTRY_SIZE
-    )]
-    EntryTooLarge(usize),
+    #[error("The entry size {0} was larger than the limit of {1} for this DNA")]
+    EntryTooLarge(usize, usize),
     #[error("The entry has a different type to the action's entry type")]
     EntryTypeMismatch,
     #[error("The visibility for {0:?} didn't match the zome")]
     EntryVisibility(AppEntryDef),
-    #[error(
-        "The link tag size {0} was larger than the MAX_TAG_SIZE {}",
-        super::MAX_TAG_SIZE
-    )]
-    TagTooLarge(usize),
+    #[error("The link tag size {0} was larger than the limit of {1} for this DNA")]
+    TagTooLarge(usize, usize),
     #[error("An op with non-private entry type is missing its entry data. Action: {0:?}, Op type: {1:?} Reason: {2}")]
     MalformedDhtOp(Box<Action>, ChainOpType, String),
     #[error("The action with {0:?} was expected to be a link add action")]
@@ -233,7 +233,7 @@ async fn record_with_cascade(
 async fn assert_valid_action(keystore: &MetaLairClient, action: Action) {
     let (record, deps) = record_with_deps(keystore, action).await;
     let cascade = Arc::new(MockCascade::with_records(deps.clone()));
-    let result = sys_validate_record(&record, cascade).await;
+    let result = sys_validate_record(&record, &Default::default(), cascade).await;
     if result.is_err() {
         dbg!(&deps, &record);
         result.unwrap();
@@ -269,10 +269,10 @@ async fn verify_action_signature_test() {
         SignedActionHashed::new_unchecked(record_valid.action().clone(), wrong_signature);
     let record_invalid = Record::new(action_invalid, None);
 
-    sys_validate_record(&record_valid, cascade.clone())
+    sys_validate_record(&record_valid, &Default::default(), cascade.clone())
         .await
         .unwrap();
-    sys_validate_record(&record_invalid, cascade)
+    sys_validate_record(&record_invalid, &Default::default(), cascade)
         .await
         .unwrap_err();
 }
@@ -312,6 +312,7 @@ async fn check_previous_action() {
 
         let actual = sys_validate_record(
             &sign_record(&keystore, action, None).await,
+            &Default::default(),
             Arc::new(cascade),
         )
         .await
@@ -430,15 +431,23 @@ async fn check_previous_timestamp() {
     let (record, mut deps) = record_with_deps(&keystore, action).await;
     *deps[0].as_action_mut().timestamp_mut() = before;
 
-    sys_validate_record(&record, Arc::new(MockCascade::with_records(deps.clone())))
-        .await
-        .unwrap();
+    sys_validate_record(
+        &record,
+        &Default::default(),
+        Arc::new(MockCascade::with_records(deps.clone())),
+    )
+    .await
+    .unwrap();
 
     *deps[0].as_action_mut().timestamp_mut() = after;
-    let r = sys_validate_record(&record, Arc::new(MockCascade::with_records(deps.clone())))
-        .await
-        .unwrap_err()
-        .into_outcome();
+    let r = sys_validate_record(
+        &record,
+        &Default::default(),
+        Arc::new(MockCascade::with_records(deps.clone())),
+    )
+    .await
+    .unwrap_err()
+    .into_outcome();
 
     assert_matches!(
         r,
@@ -462,18 +471,24 @@ async fn check_previous_seq() {
     // *record.as_action_mut().action_seq_mut().unwrap() = 2;
     *deps[0].as_action_mut().action_seq_mut().unwrap() = 1;
 
-    assert!(
-        sys_validate_record(&record, Arc::new(MockCascade::with_records(deps.clone())))
-            .await
-            .is_ok()
-    );
+    assert!(sys_validate_record(
+        &record,
+        &Default::default(),
+        Arc::new(MockCascade::with_records(deps.clone()))
+    )
+    .await
+    .is_ok());
 
     *deps[0].as_action_mut().action_seq_mut().unwrap() = 2;
     assert_matches!(
-        sys_validate_record(&record, Arc::new(MockCascade::with_records(deps.clone())))
-            .await
-            .unwrap_err()
-            .into_outcome(),
+        sys_validate_record(
+            &record,
+            &Default::default(),
+            Arc::new(MockCascade::with_records(deps.clone()))
+        )
+        .await
+        .unwrap_err()
+        .into_outcome(),
         Some(ValidationOutcome::PrevActionError(PrevActionError {
             source: PrevActionErrorKind::InvalidSeq(2, 2),
             ..
@@ -482,10 +497,14 @@ async fn check_previous_seq() {
 
     *deps[0].as_action_mut().action_seq_mut().unwrap() = 3;
     assert_matches!(
-        sys_validate_record(&record, Arc::new(MockCascade::with_records(deps.clone())))
-            .await
-            .unwrap_err()
-            .into_outcome(),
+        sys_validate_record(
+            &record,
+            &Default::default(),
+            Arc::new(MockCascade::with_records(deps.clone()))
+        )
+        .await
+        .unwrap_err()
+        .into_outcome(),
         Some(ValidationOutcome::PrevActionError(PrevActionError {
             source: PrevActionErrorKind::InvalidSeq(2, 3),
             ..
@@ -496,10 +515,14 @@ async fn check_previous_seq() {
     let record = rebuild_record(record, &keystore).await;
     *deps[0].as_action_mut().action_seq_mut().unwrap() = 0;
     assert_matches!(
-        sys_validate_record(&record, Arc::new(MockCascade::with_records(deps.clone())))
-            .await
-            .unwrap_err()
-            .into_outcome(),
+        sys_validate_record(
+            &record,
+            &Default::default(),
+            Arc::new(MockCascade::with_records(deps.clone()))
+        )
+        .await
+        .unwrap_err()
+        .into_outcome(),
         Some(ValidationOutcome::PrevActionError(PrevActionError {
             source: PrevActionErrorKind::InvalidRoot,
             ..
@@ -587,7 +610,9 @@ async fn check_entry_size_test() {
         EntryType::App(AppEntryDef::arbitrary(&mut g).unwrap());
     *record.as_entry_mut() = RecordEntry::Present(tiny_entry);
     let mut record = rebuild_record(record, &keystore).await;
-    sys_validate_record(&record, cascade.clone()).await.unwrap();
+    sys_validate_record(&record, &Default::default(), cascade.clone())
+        .await
+        .unwrap();
 
     let huge_entry = Entry::App(AppEntryBytes(SerializedBytes::from(UnsafeBytes::from(
         (0..5_000_000).map(|_| 0u8).collect::<Vec<_>>(),
@@ -596,11 +621,11 @@ async fn check_entry_size_test() {
     let record = rebuild_record(record, &keystore).await;
 
     assert_eq!(
-        sys_validate_record(&record, cascade)
+        sys_validate_record(&record, &Default::default(), cascade)
             .await
             .unwrap_err()
             .into_outcome(),
-        Some(ValidationOutcome::EntryTooLarge(5_000_000))
+        Some(ValidationOutcome::EntryTooLarge(5_000_000, MAX_ENTRY_SIZE))
     );
 }
 
@@ -644,7 +669,7 @@ async fn check_update_reference_test() {
     let record = rebuild_record(record, &keystore).await;
 
     assert_eq!(
-        sys_validate_record(&record, cascade)
+        sys_validate_record(&record, &Default::default(), cascade)
             .await
             .unwrap_err()
             .into_outcome(),
@@ -672,14 +697,77 @@ async fn check_link_tag_size_test() {
     let (record, cascade) = record_with_cascade(&keystore, action.into()).await;
 
     assert_eq!(
-        sys_validate_record(&record, cascade)
+        sys_validate_record(&record, &Default::default(), cascade)
             .await
             .unwrap_err()
             .into_outcome(),
-        Some(ValidationOutcome::TagTooLarge(super::MAX_TAG_SIZE + 1))
+        Some(ValidationOutcome::TagTooLarge(
+            super::MAX_TAG_SIZE + 1,
+            super::MAX_TAG_SIZE
+        ))
     );
 }
 
+/// A DNA can declare smaller size limits than Holochain's
+#[tokio::test(flavor = "multi_thread")]
+async fn check_dna_size_limits_test() {
+    let mut g = random_generator();
+
+    let keystore = test_keystore();
+
+    let size_limits = PayloadSizeLimits {
+        max_entry_size: Some(100),
+        max_link_tag_size: Some(10),
+    };
+
+    let action = contrafact::brute("app entry create", |a: &Create| {
+        matches!(a.entry_type, EntryType::App(_))
+    })
+    .build(&mut g);
+    let (mut record, cascade) = record_with_cascade(&keystore, action.into()).await;
+    *record.as_action_mut().entry_data_mut().unwrap().1 =
+        EntryType::App(AppEntryDef::arbitrary(&mut g).unwrap());
+    *record.as_entry_mut() = RecordEntry::Present(Entry::App(AppEntryBytes(
+        SerializedBytes::from(UnsafeBytes::from(vec![0u8; 101])),
+    )));
+    let record = rebuild_record(record, &keystore).await;
+
+    sys_validate_record(&record, &Default::default(), cascade.clone())
+        .await
+        .unwrap();
+    assert_eq!(
+        sys_validate_record(&record, &size_limits, cascade)
+            .await
+            .unwrap_err()
+            .into_outcome(),
+        Some(ValidationOutcome::EntryTooLarge(101, 100))
+    );
+
+    let mut action = CreateLink::arbitrary(&mut g).unwrap();
+    action.tag = LinkTag(vec![0u8; 11]);
+    let (record, cascade) = record_with_cascade(&keystore, action.into()).await;
+
+    assert_eq!(
+        sys_validate_record(&record, &size_limits, cascade)
+            .await
+            .unwrap_err()
+            .into_outcome(),
+        Some(ValidationOutcome::TagTooLarge(11, 10))
+    );
+}
+
+/// A DNA can't raise the size limits above Holochain's
+#[test]
+fn dna_size_limits_are_capped() {
+    let size_limits = PayloadSizeLimits {
+        max_entry_size: Some(u32::MAX),
+        max_link_tag_size: Some(u32::MAX),
+    };
+    assert_eq!(MAX_ENTRY_SIZE, max_entry_size(&size_limits));
+    assert_eq!(MAX_TAG_SIZE, max_tag_size(&size_limits));
+    assert_eq!(MAX_TAG_SIZE, max_tag_size(&Default::default()));
+}
+
 /// Check that StoreEntry does not have a private entry type
 #[tokio::test(flavor = "multi_thread")]
 async fn incoming_ops_filters_private_entry() {
@@ -890,5 +978,7 @@ async fn valid_chain_fact_test() {
 
     let cascade = MockCascade::with_records(chain);
 
-    sys_validate_record(&last, Arc::new(cascade)).await.unwrap();
+    sys_validate_record(&last, &Default::default(), Arc::new(cascade))
+        .await
+        .unwrap();
 }
@@ -98,6 +98,8 @@ use crate::conductor::api::DpkiApi;
 use crate::conductor::entry_def_store::get_entry_def;
 use crate::conductor::Conductor;
 use crate::conductor::ConductorHandle;
+use crate::core::metrics::{record_awaited_dependencies, record_workflow_queue_depth};
+use crate::core::op_trace::record_op_event;
 use crate::core::queue_consumer::TriggerSender;
 use crate::core::queue_consumer::WorkComplete;
 use crate::core::ribosome::guest_callback::validate::ValidateHostAccess;
@@ -116,6 +118,7 @@ pub use types::Outcome;
 use holo_hash::DhtOpHash;
 use holochain_cascade::Cascade;
 use holochain_cascade::CascadeImpl;
+use holochain_conductor_api::OpTraceStage;
 use holochain_keystore::MetaLairClient;
 use holochain_p2p::actor::GetOptions as NetworkGetOptions;
 use holochain_p2p::GenericNetwork;
@@ -126,7 +129,7 @@ use holochain_state::host_fn_workspace::HostFnWorkspaceRead;
 use holochain_state::prelude::*;
 
 use parking_lot::Mutex;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::atomic::AtomicUsize;
 use std::sync::atomic::Ordering;
 use std::sync::Arc;
@@ -209,8 +212,10 @@ async fn app_validation_workflow_inner(
     dht_query_cache: DhtDbQueryCache,
 ) -> WorkflowResult<OutcomeSummary> {
     let db = workspace.dht_db.clone().into();
-    let sorted_dht_ops = validation_query::get_ops_to_app_validate(&db).await?;
+    let mut sorted_dht_ops = validation_query::get_ops_to_app_validate(&db).await?;
+    validation_query::put_interactive_ops_first(&mut sorted_dht_ops, &workspace.dna_def);
     let num_ops_to_validate = sorted_dht_ops.len();
+    record_workflow_queue_depth("app_validation", &dna_hash, num_ops_to_validate);
 
     let cascade = Arc::new(workspace.full_cascade(network.clone()));
     let accepted_ops = Arc::new(AtomicUsize::new(0));
@@ -219,8 +224,12 @@ async fn app_validation_workflow_inner(
     let warranted_ops = Arc::new(AtomicUsize::new(0));
     let failed_ops = Arc::new(Mutex::new(HashSet::new()));
     let mut agent_activity = vec![];
+    // The bases of the ops which were integrated as soon as they were validated.
+    let mut integrated_bases = vec![];
     #[cfg(feature = "unstable-warrants")]
     let mut warrant_op_hashes = vec![];
+    // How many ops are waiting for each dependency which couldn't be found yet.
+    let mut awaited_dependencies = HashMap::<AnyDhtHash, usize>::new();
 
     // Validate ops sequentially
     for sorted_dht_op in sorted_dht_ops.into_iter() {
@@ -235,6 +244,7 @@ async fn app_validation_workflow_inner(
         let op_type = chain_op.get_type();
         let action = chain_op.action();
         let dht_op_lite = chain_op.to_lite();
+        let basis = chain_op.dht_basis();
 
         // If this is agent activity, track it for the cache.
         let activity = matches!(op_type, ChainOpType::RegisterAgentActivity).then(|| {
@@ -261,6 +271,9 @@ async fn app_validation_workflow_inner(
 
         match validation_outcome {
             Ok(outcome) => {
+                record_op_event(&dht_op_hash, OpTraceStage::AppValidated, || {
+                    format!("{:?}", outcome)
+                });
                 // Collect all agent activity.
                 if let Some(activity) = activity {
                     // If the activity is accepted or rejected then it's ready to integrate.
@@ -271,6 +284,11 @@ async fn app_validation_workflow_inner(
                 if let Outcome::AwaitingDeps(_) | Outcome::Rejected(_) = &outcome {
                     warn!(?outcome, ?dht_op_lite, "DhtOp has failed app validation");
                 }
+                if let Outcome::AwaitingDeps(hashes) = &outcome {
+                    for hash in hashes {
+                        *awaited_dependencies.entry(hash.clone()).or_default() += 1;
+                    }
+                }
 
                 let accepted_ops = accepted_ops.clone();
                 let awaiting_ops = awaiting_ops.clone();
@@ -300,6 +318,14 @@ async fn app_validation_workflow_inner(
                     warranted_ops.fetch_add(1, Ordering::SeqCst);
                 }
 
+                let integrated = matches!(outcome, Outcome::Accepted) && deps.is_empty();
+                // Ops without dependencies are integrated right away.
+                let integrated_status = match &outcome {
+                    Outcome::Accepted if deps.is_empty() => Some(ValidationStatus::Valid),
+                    Outcome::Rejected(_) if deps.is_empty() => Some(ValidationStatus::Rejected),
+                    _ => None,
+                };
+                let traced_op_hash = dht_op_hash.clone();
                 let write_result = workspace
                     .dht_db
                     .write_async(move|txn| match outcome {
@@ -337,6 +363,15 @@ async fn app_validation_workflow_inner(
                     .await;
                 if let Err(err) = write_result {
                     tracing::error!(?chain_op, ?err, "Error updating dht op in database.");
+                } else {
+                    if let Some(status) = integrated_status {
+                        record_op_event(&traced_op_hash, OpTraceStage::Integrated, || {
+                            format!("as {:?}, right after app validation", status)
+                        });
+                    }
+                    if integrated {
+                        integrated_bases.push(basis);
+                    }
                 }
             }
             Err(err) => {
@@ -345,10 +380,14 @@ async fn app_validation_workflow_inner(
                     ?err,
                     "App validation error when validating dht op."
                 );
+                record_op_event(&dht_op_hash, OpTraceStage::AppValidated, || {
+                    format!("failed to validate: {:?}", err)
+                });
                 failed_ops.lock().insert(dht_op_hash);
             }
         }
     }
+    record_awaited_dependencies("app_validation", &dna_hash, awaited_dependencies);
 
     // "self-publish" warrants, i.e. insert them into the DHT db as if they were published to us by another node
     #[cfg(feature = "unstable-warrants")]
@@ -361,6 +400,8 @@ async fn app_validation_workflow_inner(
     )
     .await?;
 
+    dht_query_cache.queries().invalidate(integrated_bases);
+
     // Once the database transaction is committed, add agent activity to the cache
     // that is ready for integration.
     for (author, seq, has_no_dependency) in agent_activity {
@@ -751,7 +792,13 @@ async fn run_validation_callback(
 ) -> AppValidationResult<Outcome> {
     let validate_result = ribosome
         .run_validate(
-            ValidateHostAccess::new(workspace.clone(), network.clone(), dpki, is_inline),
+            ValidateHostAccess::new(
+                workspace.clone(),
+                network.clone(),
+                dpki,
+                is_inline,
+                invocation.validation_context(),
+            ),
             invocation.clone(),
         )
         .await?;
@@ -896,6 +943,7 @@ impl AppValidationWorkspace {
         CascadeImpl::empty()
             .with_authored(self.authored_db.clone().into())
             .with_dht(self.dht_db.clone().into())
+            .with_dht_db_cache(self.dht_db_cache.clone())
             .with_network(Arc::new(network), self.cache.clone())
     }
 }
@@ -238,6 +238,7 @@ async fn validation_callback_awaiting_deps_agent_activity() {
                 let result = api.must_get_agent_activity(MustGetAgentActivityInput {
                     author: delete.hashed.author.clone(),
                     chain_filter: chain_filter.clone(),
+                    options: Default::default(),
                 });
                 if result.is_ok() {
                     Ok(ValidateCallbackResult::Valid)
@@ -256,6 +256,7 @@ async fn validate_ops_in_sequence_must_get_agent_activity() {
                 let result = api.must_get_agent_activity(MustGetAgentActivityInput {
                     author: agent.clone(),
                     chain_filter: chain_filter.clone(),
+                    options: Default::default(),
                 });
                 if result.is_ok() {
                     Ok(ValidateCallbackResult::Valid)
@@ -862,6 +863,10 @@ async fn check_app_entry_def_test() {
                 .coordinator
                 .into_inner()],
             lineage: Default::default(),
+            size_limits: Default::default(),
+            required_validation_receipts: None,
+            interactive_types: Default::default(),
+            fulltext_entry_types: Default::default(),
         },
         [integrity, coordinator],
     )
@@ -960,6 +965,7 @@ async fn app_validation_produces_warrants() {
                 agent_pubkey,
                 chain_query_filter: Default::default(),
                 activity_request: ActivityRequest::Full,
+                page: None,
             })?)
         });
 
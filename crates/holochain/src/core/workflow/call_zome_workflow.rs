@@ -6,6 +6,8 @@ use super::sys_validation_workflow::sys_validate_record;
 use crate::conductor::api::CellConductorApi;
 use crate::conductor::api::CellConductorApiT;
 use crate::conductor::api::DpkiApi;
+use crate::conductor::api::DryRunCellConductorApi;
+use crate::conductor::conductor::SignalSender;
 use crate::conductor::ConductorHandle;
 use crate::core::check_dpki_agent_validity_for_record;
 use crate::core::queue_consumer::TriggerSender;
@@ -15,6 +17,7 @@ use crate::core::ribosome::RibosomeT;
 use crate::core::ribosome::ZomeCallHostAccess;
 use crate::core::ribosome::ZomeCallInvocation;
 use crate::core::workflow::WorkflowError;
+use holochain_conductor_api::DryRunValidation;
 use holochain_keystore::MetaLairClient;
 use holochain_p2p::HolochainP2pDna;
 use holochain_state::host_fn_workspace::SourceChainWorkspace;
@@ -23,7 +26,6 @@ use holochain_state::source_chain::SourceChainError;
 use holochain_types::prelude::*;
 use holochain_zome_types::record::Record;
 use std::sync::Arc;
-use tokio::sync::broadcast;
 
 #[cfg(test)]
 mod validation_test;
@@ -34,7 +36,7 @@ pub type ZomeCallResult = RibosomeResult<ZomeCallResponse>;
 pub struct CallZomeWorkflowArgs<RibosomeT> {
     pub ribosome: RibosomeT,
     pub invocation: ZomeCallInvocation,
-    pub signal_tx: broadcast::Sender<Signal>,
+    pub signal_tx: SignalSender,
     pub conductor_handle: ConductorHandle,
     pub is_root_zome_call: bool,
     pub cell_id: CellId,
@@ -131,6 +133,76 @@ where
     Ok(result)
 }
 
+/// What a zome call would have done, from [`call_zome_dry_run_workflow`].
+pub struct ZomeCallDryRunOutcome {
+    /// What the zome function returned, or why it couldn't be called.
+    pub result: ZomeCallResult,
+    /// The records which the call would have committed, in order.
+    pub records: Vec<Record>,
+    /// Whether the records would have passed validation.
+    pub validation: DryRunValidation,
+}
+
+/// Call a zome function as [`call_zome_workflow`] does, but without writing or
+/// publishing what it commits, and return what it would have committed along with
+/// whether that would have passed validation.
+///
+/// The workspace is never flushed, whatever `args.is_root_zome_call` says, and the
+/// signals which the call emits should go to a [`SignalSender`] which nothing listens to.
+/// Nothing the call does reaches other agents or cells: the host functions which
+/// write to the network are denied, and so are calls into other cells, remote
+/// messages and changes to clone cells, through a [`DryRunCellConductorApi`].
+#[cfg_attr(feature = "instrument", tracing::instrument(skip_all))]
+pub async fn call_zome_dry_run_workflow<Ribosome>(
+    workspace: SourceChainWorkspace,
+    network: HolochainP2pDna,
+    keystore: MetaLairClient,
+    args: CallZomeWorkflowArgs<Ribosome>,
+) -> WorkflowResult<ZomeCallDryRunOutcome>
+where
+    Ribosome: RibosomeT + 'static,
+{
+    let CallZomeWorkflowArgs {
+        ribosome,
+        invocation,
+        signal_tx,
+        conductor_handle,
+        cell_id,
+        ..
+    } = args;
+
+    let call_zome_handle = Arc::new(DryRunCellConductorApi(
+        CellConductorApi::new(conductor_handle.clone(), cell_id).into_call_zome_handle(),
+    ));
+    let host_access = ZomeCallHostAccess::new(
+        workspace.clone().into(),
+        keystore,
+        conductor_handle.running_services().dpki,
+        network.clone(),
+        signal_tx,
+        call_zome_handle,
+    )
+    .dry_run();
+    let (ribosome, result) =
+        call_zome_function_authorized(ribosome, host_access, invocation).await?;
+    let records = workspace.source_chain().scratch_records()?;
+    let validation = match inline_validation(workspace, network, conductor_handle, ribosome).await {
+        Ok(()) => DryRunValidation::Valid,
+        Err(WorkflowError::SourceChainError(SourceChainError::InvalidCommit(reason))) => {
+            DryRunValidation::Invalid(reason)
+        }
+        Err(WorkflowError::SourceChainError(SourceChainError::IncompleteCommit(
+            IncompleteCommitReason::DepMissingFromDht(deps),
+        ))) => DryRunValidation::MissingDependencies(deps),
+        Err(err) => return Err(err),
+    };
+    Ok(ZomeCallDryRunOutcome {
+        result,
+        records,
+        validation,
+    })
+}
+
 async fn call_zome_workflow_inner<Ribosome>(
     workspace: SourceChainWorkspace,
     dpki: DpkiApi,
@@ -284,7 +356,7 @@ where
         let mut to_app_validate: Vec<Record> = Vec::with_capacity(scratch_records.len());
         // Loop forwards through all the new records
         for record in scratch_records {
-            sys_validate_record(&record, cascade.clone())
+            sys_validate_record(&record, &ribosome.dna_def().size_limits, cascade.clone())
                 .await
                 // If the was en error exit
                 // If the validation failed, exit with an InvalidCommit
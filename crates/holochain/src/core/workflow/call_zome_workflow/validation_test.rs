@@ -33,6 +33,10 @@ async fn direct_validation_test() {
             integrity_zomes: vec![TestZomes::from(TestWasm::Update).integrity.into_inner()],
             coordinator_zomes: vec![TestZomes::from(TestWasm::Update).coordinator.into_inner()],
             lineage: Default::default(),
+            size_limits: Default::default(),
+            required_validation_receipts: None,
+            interactive_types: Default::default(),
+            fulltext_entry_types: Default::default(),
         },
         [integrity, coordinator],
     )
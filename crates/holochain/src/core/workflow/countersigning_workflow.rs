@@ -1,6 +1,7 @@
 //! Countersigning workflow to maintain countersigning session state.
 
 use super::error::WorkflowResult;
+use crate::conductor::conductor::SignalSender;
 use crate::conductor::space::Space;
 use crate::core::queue_consumer::{TriggerSender, WorkComplete};
 use holo_hash::AgentPubKey;
@@ -10,7 +11,6 @@ use holochain_state::prelude::*;
 use kitsune_p2p_types::tx_utils::Share;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::broadcast::Sender;
 use tokio::task::AbortHandle;
 
 /// Accept handler for starting countersigning sessions.
@@ -28,6 +28,9 @@ mod refresh;
 /// Success handler for receiving signature bundles from the network.
 mod success;
 
+#[cfg(test)]
+mod model;
+
 #[cfg(test)]
 mod tests;
 
@@ -143,7 +146,7 @@ pub(crate) async fn countersigning_workflow(
     network: Arc<impl HolochainP2pDnaT>,
     keystore: MetaLairClient,
     cell_id: CellId,
-    signal_tx: Sender<Signal>,
+    signal_tx: SignalSender,
     self_trigger: TriggerSender,
     integration_trigger: TriggerSender,
     publish_trigger: TriggerSender,
@@ -302,7 +305,7 @@ pub(crate) async fn countersigning_workflow(
                 )
                 .await?;
             } else if force_publish {
-                complete::force_publish_countersigning_session(
+                let published = complete::force_publish_countersigning_session(
                     space.clone(),
                     network.clone(),
                     keystore.clone(),
@@ -312,7 +315,21 @@ pub(crate) async fn countersigning_workflow(
                     preflight_request.clone(),
                 )
                 .await?;
-                completed = true;
+
+                if published {
+                    completed = true;
+                } else {
+                    // There is no committed session to publish, so the session can only be
+                    // abandoned. Otherwise the chain would stay locked.
+                    force_abandon_session(
+                        space.clone(),
+                        cell_id.agent_pubkey(),
+                        &preflight_request,
+                        workspace.clone(),
+                        &signal_tx,
+                    )
+                    .await?;
+                }
             }
         }
     }
@@ -375,7 +392,7 @@ async fn try_recover_failed_session(
     workspace: Arc<CountersigningWorkspace>,
     network: Arc<impl HolochainP2pDnaT + Sized>,
     cell_id: &CellId,
-    signal_tx: &Sender<Signal>,
+    signal_tx: &SignalSender,
 ) -> WorkflowResult<()> {
     let maybe_session_in_unknown_state = workspace
         .inner
@@ -563,7 +580,7 @@ async fn apply_timeout(
     space: &Space,
     workspace: Arc<CountersigningWorkspace>,
     cell_id: &CellId,
-    signal_tx: Sender<Signal>,
+    signal_tx: SignalSender,
 ) -> WorkflowResult<()> {
     let preflight_request = workspace
         .inner
@@ -688,7 +705,7 @@ async fn force_abandon_session(
     author: &AgentPubKey,
     preflight_request: &PreflightRequest,
     workspace: Arc<CountersigningWorkspace>,
-    signal_tx: &Sender<Signal>,
+    signal_tx: &SignalSender,
 ) -> SourceChainResult<()> {
     let authored_db = space.get_or_create_authored_db(author.clone())?;
 
@@ -93,6 +93,13 @@ pub async fn accept_countersigning_request(
     if put_accepted_result.is_err() {
         // This really shouldn't happen. The chain lock is the primary state and that should be in place here.
         tracing::error!("Failed to store accepted session in workspace");
+
+        // The chain was locked for this session, which is being refused, so the lock must be
+        // released. Nothing else would release it before the session end time.
+        if let Err(unlock_error) = source_chain.unlock_chain().await {
+            tracing::error!(?unlock_error);
+        }
+
         return Ok(PreflightRequestAcceptance::AnotherSessionIsInProgress);
     };
 
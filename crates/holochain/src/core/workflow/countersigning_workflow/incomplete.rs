@@ -80,7 +80,9 @@ pub async fn inner_countersigning_session_incomplete(
         .filter(|a| **a != author)
         .collect::<Vec<_>>();
 
-    let cascade = CascadeImpl::empty().with_network(network, space.cache_db.clone());
+    let cascade = CascadeImpl::empty()
+        .with_network(network, space.cache_db.clone())
+        .with_dht_db_cache(space.dht_query_cache.clone());
 
     let get_activity_options = GetActivityOptions {
         include_warrants: false, // TODO document that apps should consider checking for warrants before completing preflight
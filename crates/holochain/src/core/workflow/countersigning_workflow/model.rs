@@ -0,0 +1,808 @@
+//! A model of countersigning sessions, for checking the countersigning workflow.
+//!
+//! The model describes the state of countersigning for one cell with small values which can be
+//! compared and hashed: sessions are numbered in the order they were accepted, and the source
+//! chain is reduced to the session which the chain is locked for and the countersigning entry at
+//! the head of the chain, if any. Signature bundles are reduced to whether they would complete
+//! the session, and the network is reduced to the decision reached when resolving a session.
+//!
+//! [`ModelState::project`] maps the workspace session and the source chain onto the model.
+//! [`explore`] visits every state of a small instance of the model and checks invariants on each
+//! of them, including that the chain lock is always eventually released.
+
+use std::collections::{HashSet, VecDeque};
+
+use super::*;
+
+/// A countersigning session, numbered in the order the sessions were accepted.
+type SessionId = u8;
+
+/// The limits for the model.
+#[derive(Clone, Debug)]
+struct ModelConfig {
+    /// Mirrors `CountersigningWorkspace::countersigning_resolution_retry_limit`.
+    retry_limit: Option<usize>,
+    /// How many sessions can be accepted.
+    max_sessions: SessionId,
+    /// How many signature bundles can be waiting in the workspace.
+    max_bundles: usize,
+}
+
+/// The decision reached by an attempt to resolve a session in the unknown state.
+///
+/// Mirrors `SessionCompletionDecision`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Decision {
+    Complete,
+    Abandoned,
+    Indeterminate,
+    Failed,
+}
+
+/// Something which can happen to a countersigning session.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Action {
+    /// A preflight request for a new session is accepted.
+    Accept,
+    /// The countersigning entry for the session the chain is locked for is committed.
+    /// If it fails validation then the chain is unlocked instead.
+    Commit { valid: bool },
+    /// Something other than a countersigning entry is committed.
+    CommitOther,
+    /// A signature bundle for the session in the workspace is received.
+    ReceiveSignatures { valid: bool },
+    /// The end time of the oldest session which hasn't ended yet passes.
+    SessionEnds,
+    /// The countersigning workflow runs, reaching this decision if it tries to resolve a session.
+    RunWorkflow(Decision),
+    /// The user asks for the unresolved session to be abandoned.
+    ForceAbandon,
+    /// The user asks for the unresolved session to be published.
+    ForcePublish,
+    /// The conductor restarts, losing the workspace.
+    Restart,
+}
+
+/// The state of the workspace session. Mirrors `CountersigningSessionState`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum ModelSession {
+    Accepted(SessionId),
+    SignaturesCollected {
+        id: SessionId,
+        /// Whether each bundle would complete the session if the chain is in the right state.
+        bundles: Vec<bool>,
+        /// The number of resolution attempts, if the signatures came from resolving the session.
+        resolution: Option<usize>,
+    },
+    Unknown {
+        id: SessionId,
+        attempts: usize,
+        force_abandon: bool,
+        force_publish: bool,
+    },
+}
+
+impl ModelSession {
+    fn id(&self) -> SessionId {
+        match self {
+            Self::Accepted(id)
+            | Self::SignaturesCollected { id, .. }
+            | Self::Unknown { id, .. } => *id,
+        }
+    }
+}
+
+/// The countersigning entry at the head of the source chain.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct ModelHead {
+    id: SessionId,
+    /// Whether the session has been completed and its ops published.
+    revealed: bool,
+}
+
+/// The state of countersigning for one cell.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+struct ModelState {
+    /// The session in the workspace.
+    session: Option<ModelSession>,
+    /// The session the chain is locked for.
+    lock: Option<SessionId>,
+    /// The countersigning entry at the head of the chain, if the head is one.
+    head: Option<ModelHead>,
+    /// How many sessions have been accepted.
+    started: SessionId,
+    /// How many sessions have passed their end time.
+    ended: SessionId,
+    /// Whether the workflow has been triggered.
+    triggered: bool,
+    /// Whether a completed session has been chosen to be abandoned. This must never happen:
+    /// the database refuses to remove a published session entry, so the workflow fails
+    /// and the chain stays locked.
+    abandoned_completed: bool,
+}
+
+impl ModelState {
+    /// Whether an action can happen in this state.
+    fn enabled(&self, config: &ModelConfig, action: Action) -> bool {
+        match action {
+            Action::Accept => self.started < config.max_sessions,
+            Action::Commit { .. } => match self.lock {
+                Some(id) => id >= self.ended && self.head.as_ref().map(|h| h.id) != Some(id),
+                None => false,
+            },
+            Action::CommitOther => self.lock.is_none() && self.head.is_some(),
+            Action::ReceiveSignatures { valid } => match &self.session {
+                // A valid bundle includes our own action, so we must have committed
+                Some(session) => {
+                    let bundles = match session {
+                        ModelSession::SignaturesCollected { bundles, .. } => bundles.len(),
+                        _ => 0,
+                    };
+                    bundles < config.max_bundles
+                        && (!valid
+                            || self.head
+                                == Some(ModelHead {
+                                    id: session.id(),
+                                    revealed: false,
+                                }))
+                }
+                None => false,
+            },
+            Action::SessionEnds => self.ended < self.started,
+            Action::RunWorkflow(_) => self.triggered,
+            Action::ForceAbandon | Action::ForcePublish => {
+                matches!(self.session, Some(ModelSession::Unknown { attempts, .. }) if attempts >= 1)
+            }
+            Action::Restart => true,
+        }
+    }
+
+    fn apply(&mut self, config: &ModelConfig, action: Action) {
+        match action {
+            Action::Accept => self.accept(),
+            Action::Commit { valid } => {
+                let id = self.lock.expect("Commit is only enabled when locked");
+                if valid {
+                    self.head = Some(ModelHead {
+                        id,
+                        revealed: false,
+                    });
+                } else {
+                    // The call zome workflow unlocks the chain when validation fails
+                    self.lock = None;
+                    self.triggered = true;
+                }
+            }
+            Action::CommitOther => self.head = None,
+            Action::ReceiveSignatures { valid } => self.receive_signatures(vec![valid], None),
+            Action::SessionEnds => self.ended += 1,
+            Action::RunWorkflow(decision) => self.run_workflow(config, decision),
+            Action::ForceAbandon | Action::ForcePublish => {
+                if let Some(ModelSession::Unknown {
+                    force_abandon,
+                    force_publish,
+                    ..
+                }) = &mut self.session
+                {
+                    if action == Action::ForceAbandon {
+                        *force_abandon = true;
+                    } else {
+                        *force_publish = true;
+                    }
+                    self.triggered = true;
+                }
+            }
+            Action::Restart => {
+                self.session = None;
+                self.triggered = true;
+            }
+        }
+    }
+
+    /// Mirrors `accept_countersigning_request`.
+    fn accept(&mut self) {
+        let id = self.started;
+        self.started += 1;
+
+        if self.lock.is_some() {
+            return;
+        }
+        self.lock = Some(id);
+
+        if self.session.is_some() {
+            // The previous session is still in the workspace, so this one is refused
+            self.lock = None;
+            return;
+        }
+        self.session = Some(ModelSession::Accepted(id));
+        self.triggered = true;
+    }
+
+    /// Mirrors `countersigning_success`.
+    fn receive_signatures(&mut self, bundles: Vec<bool>, resolution: Option<usize>) {
+        let Some(session) = &mut self.session else {
+            return;
+        };
+        let id = session.id();
+        *session = match session {
+            ModelSession::Accepted(_) => ModelSession::SignaturesCollected {
+                id,
+                bundles,
+                resolution: None,
+            },
+            ModelSession::SignaturesCollected {
+                bundles: existing,
+                resolution,
+                ..
+            } => ModelSession::SignaturesCollected {
+                id,
+                bundles: existing.iter().copied().chain(bundles).collect(),
+                resolution: *resolution,
+            },
+            ModelSession::Unknown { attempts, .. } => ModelSession::SignaturesCollected {
+                id,
+                bundles,
+                resolution: Some(resolution.unwrap_or(*attempts)),
+            },
+        };
+        self.triggered = true;
+    }
+
+    /// Mirrors `countersigning_workflow`.
+    fn run_workflow(&mut self, config: &ModelConfig, decision: Decision) {
+        self.triggered = false;
+
+        self.refresh();
+        self.apply_timeout();
+        self.try_recover(config, decision);
+
+        let mut completed = false;
+        if let Some(ModelSession::SignaturesCollected {
+            id,
+            bundles,
+            resolution,
+        }) = &mut self.session
+        {
+            let id = *id;
+            let resolution = *resolution;
+            for valid in std::mem::take(bundles) {
+                if self.complete(id, valid) {
+                    completed = true;
+                    break;
+                }
+            }
+            if !completed {
+                if let Some(attempts) = resolution {
+                    self.session = Some(ModelSession::Unknown {
+                        id,
+                        attempts,
+                        force_abandon: false,
+                        force_publish: false,
+                    });
+                }
+            }
+        } else if let Some(ModelSession::Unknown {
+            id,
+            force_abandon,
+            force_publish,
+            ..
+        }) = self.session
+        {
+            if force_abandon {
+                self.force_abandon(id);
+            } else if force_publish {
+                if self.force_publish(id) {
+                    completed = true;
+                } else {
+                    self.force_abandon(id);
+                }
+            }
+        }
+
+        if completed {
+            self.session = None;
+        }
+
+        // Reschedule while there is a session
+        if self.session.is_some() {
+            self.triggered = true;
+        }
+    }
+
+    /// Mirrors `refresh::refresh_workspace_state`.
+    fn refresh(&mut self) {
+        let registered = self.session.is_some();
+        let mut locked = false;
+
+        if let Some(lock) = self.lock {
+            locked = true;
+            // Only a session entry which matches the lock is the current session
+            let current = self.head.as_ref().map(|h| h.id).filter(|id| *id == lock);
+            match current {
+                None if !registered => {
+                    self.lock = None;
+                    locked = false;
+                }
+                Some(id) if !registered => {
+                    self.session = Some(ModelSession::Unknown {
+                        id,
+                        attempts: 0,
+                        force_abandon: false,
+                        force_publish: false,
+                    });
+                }
+                _ => {}
+            }
+        }
+
+        if !locked {
+            self.session = None;
+        }
+    }
+
+    /// Mirrors `apply_timeout`.
+    fn apply_timeout(&mut self) {
+        let Some(session) = &mut self.session else {
+            return;
+        };
+        let id = session.id();
+        let ended = id < self.ended;
+        let has_committed = self.head.as_ref().map(|h| h.id) == Some(id);
+
+        let unknown = ModelSession::Unknown {
+            id,
+            attempts: 0,
+            force_abandon: false,
+            force_publish: false,
+        };
+        let mut timed_out = false;
+        match session {
+            ModelSession::Accepted(_) if ended => {
+                if has_committed {
+                    *session = unknown;
+                } else {
+                    timed_out = true;
+                }
+            }
+            ModelSession::SignaturesCollected {
+                bundles,
+                resolution: None,
+                ..
+            } if ended && bundles.is_empty() => *session = unknown,
+            _ => {}
+        }
+
+        if timed_out {
+            self.force_abandon(id);
+        }
+    }
+
+    /// Mirrors `try_recover_failed_session` and `inner_countersigning_session_incomplete`.
+    fn try_recover(&mut self, config: &ModelConfig, decision: Decision) {
+        let Some(ModelSession::Unknown { id, .. }) = self.session else {
+            return;
+        };
+
+        // No decision can be made without our own session entry to look up
+        let decision = if self.head.as_ref().map(|h| h.id) == Some(id) {
+            decision
+        } else {
+            Decision::Indeterminate
+        };
+
+        match decision {
+            Decision::Complete => {
+                let attempts = self.add_attempt(true);
+                self.receive_signatures(vec![true], Some(attempts));
+                if let Some(ModelSession::SignaturesCollected { resolution, .. }) =
+                    &mut self.session
+                {
+                    *resolution = Some(attempts);
+                }
+            }
+            Decision::Abandoned => {
+                self.abandon_session();
+                self.session = None;
+            }
+            Decision::Indeterminate => {
+                let attempts = self.add_attempt(true);
+                let limit = config.retry_limit.unwrap_or(0);
+                if config.retry_limit.is_none() || (limit > 0 && attempts >= limit) {
+                    self.force_abandon(id);
+                }
+            }
+            Decision::Failed => {
+                self.add_attempt(false);
+            }
+        }
+    }
+
+    /// Mirrors `update_last_attempted`. Returns the number of attempts made.
+    fn add_attempt(&mut self, count: bool) -> usize {
+        match &mut self.session {
+            Some(ModelSession::Unknown { attempts, .. }) => {
+                if count {
+                    *attempts += 1;
+                }
+                *attempts
+            }
+            _ => 0,
+        }
+    }
+
+    /// Mirrors `complete::inner_countersigning_session_complete`. Returns whether the session
+    /// was completed.
+    fn complete(&mut self, id: SessionId, valid: bool) -> bool {
+        let current = self.head.as_ref().map(|h| h.id) == Some(id) && self.lock == Some(id);
+        if valid && current {
+            self.reveal();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Mirrors `complete::force_publish_countersigning_session`. Returns whether the session was
+    /// published.
+    fn force_publish(&mut self, id: SessionId) -> bool {
+        let current = self.head.as_ref().map(|h| h.id) == Some(id) && self.lock == Some(id);
+        if current {
+            self.reveal();
+        }
+        current
+    }
+
+    fn reveal(&mut self) {
+        self.lock = None;
+        if let Some(head) = &mut self.head {
+            head.revealed = true;
+        }
+    }
+
+    /// Mirrors `force_abandon_session`.
+    fn force_abandon(&mut self, id: SessionId) {
+        if self.head.as_ref().map(|h| h.id) == Some(id) {
+            self.abandon_session();
+        } else if self.lock == Some(id) {
+            self.lock = None;
+        }
+        self.session = None;
+    }
+
+    /// Mirrors `abandon_session`.
+    fn abandon_session(&mut self) {
+        if let Some(head) = self.head.take() {
+            self.abandoned_completed |= head.revealed;
+        }
+        self.lock = None;
+    }
+
+    /// Check the properties which must hold in every state.
+    fn check_invariants(&self) -> Result<(), String> {
+        if self.abandoned_completed {
+            return Err("a completed session was chosen to be abandoned".to_string());
+        }
+        if let Some(head) = &self.head {
+            if !head.revealed && self.lock != Some(head.id) {
+                return Err(format!(
+                    "the chain is not locked for session {} which was committed but not completed",
+                    head.id
+                ));
+            }
+        }
+        if let Some(session) = &self.session {
+            if session.id() >= self.started {
+                return Err(format!("session {} was never accepted", session.id()));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that the chain lock is eventually released and the session removed from the
+    /// workspace when no signatures arrive, no decision can be reached about the session,
+    /// and the workflow only runs when it is triggered.
+    fn check_lock_released(&self, config: &ModelConfig) -> Result<(), String> {
+        let mut state = self.clone();
+        for _ in 0..MAX_RELEASE_ROUNDS {
+            while state.ended < state.started {
+                state.apply(config, Action::SessionEnds);
+            }
+            if !state.triggered {
+                break;
+            }
+            state.apply(config, Action::RunWorkflow(Decision::Indeterminate));
+        }
+
+        if state.lock.is_some() || state.session.is_some() {
+            Err(format!("the chain lock was not released: {state:?}"))
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl ModelState {
+    /// Map the state of countersigning for a cell onto the model.
+    ///
+    /// `sessions` are the preflight requests which have been accepted, in the order they were
+    /// accepted, and the first `ended` of them have passed their end time. `head` is the request
+    /// for the countersigning entry at the head of the chain and whether it has been published.
+    /// `valid_bundle` tells whether a signature bundle would complete its session.
+    fn project(
+        sessions: &[PreflightRequest],
+        ended: usize,
+        workspace_session: Option<&CountersigningSessionState>,
+        lock_subject: Option<&[u8]>,
+        head: Option<(&PreflightRequest, bool)>,
+        triggered: bool,
+        valid_bundle: impl Fn(&[SignedAction]) -> bool,
+    ) -> Self {
+        let fingerprints = sessions
+            .iter()
+            .map(|request| request.fingerprint().unwrap())
+            .collect::<Vec<_>>();
+        let id_of_fingerprint = |fingerprint: &[u8]| {
+            fingerprints
+                .iter()
+                .position(|f| f == fingerprint)
+                .expect("every session must have been accepted") as SessionId
+        };
+        let id_of = |request: &PreflightRequest| id_of_fingerprint(&request.fingerprint().unwrap());
+
+        let session = workspace_session.map(|session| match session {
+            CountersigningSessionState::Accepted(request) => ModelSession::Accepted(id_of(request)),
+            CountersigningSessionState::SignaturesCollected {
+                preflight_request,
+                signature_bundles,
+                resolution,
+            } => ModelSession::SignaturesCollected {
+                id: id_of(preflight_request),
+                bundles: signature_bundles
+                    .iter()
+                    .map(|bundle| valid_bundle(bundle))
+                    .collect(),
+                resolution: resolution.as_ref().map(|r| r.attempts),
+            },
+            CountersigningSessionState::Unknown {
+                preflight_request,
+                resolution,
+                force_abandon,
+                force_publish,
+            } => ModelSession::Unknown {
+                id: id_of(preflight_request),
+                attempts: resolution.attempts,
+                force_abandon: *force_abandon,
+                force_publish: *force_publish,
+            },
+        });
+
+        Self {
+            session,
+            lock: lock_subject.map(id_of_fingerprint),
+            head: head.map(|(request, revealed)| ModelHead {
+                id: id_of(request),
+                revealed,
+            }),
+            started: sessions.len() as SessionId,
+            ended: ended as SessionId,
+            triggered,
+            abandoned_completed: false,
+        }
+    }
+}
+
+/// How many workflow runs releasing a chain lock may take, beyond which the lock is considered
+/// to be held forever.
+const MAX_RELEASE_ROUNDS: usize = 100;
+
+fn all_actions() -> Vec<Action> {
+    let mut actions = vec![
+        Action::Accept,
+        Action::Commit { valid: true },
+        Action::Commit { valid: false },
+        Action::CommitOther,
+        Action::ReceiveSignatures { valid: true },
+        Action::ReceiveSignatures { valid: false },
+        Action::SessionEnds,
+        Action::ForceAbandon,
+        Action::ForcePublish,
+        Action::Restart,
+    ];
+    for decision in [
+        Decision::Complete,
+        Decision::Abandoned,
+        Decision::Indeterminate,
+        Decision::Failed,
+    ] {
+        actions.push(Action::RunWorkflow(decision));
+    }
+    actions
+}
+
+/// Visit every state which can be reached from a cell with no session, checking the invariants
+/// and that the chain lock is eventually released in each of them. Returns the states visited,
+/// or the actions which lead to a failing state.
+fn explore(config: &ModelConfig) -> Result<HashSet<ModelState>, (Vec<Action>, String)> {
+    let actions = all_actions();
+    let mut seen = HashSet::new();
+    let mut frontier = VecDeque::from([(ModelState::default(), vec![])]);
+    seen.insert(ModelState::default());
+
+    while let Some((state, trace)) = frontier.pop_front() {
+        state
+            .check_invariants()
+            .and_then(|_| state.check_lock_released(config))
+            .map_err(|e| (trace.clone(), e))?;
+
+        for action in &actions {
+            if !state.enabled(config, *action) {
+                continue;
+            }
+            let mut next = state.clone();
+            next.apply(config, *action);
+            if seen.insert(next.clone()) {
+                let mut trace = trace.clone();
+                trace.push(*action);
+                frontier.push_back((next, trace));
+            }
+        }
+    }
+
+    Ok(seen)
+}
+
+mod tests {
+    use super::*;
+    use fixt::prelude::*;
+    use hdk::prelude::{CounterSigningSessionTimes, CreateBase, EntryTypeFixturator, Timestamp};
+    use holo_hash::fixt::{AgentPubKeyFixturator, EntryHashFixturator};
+    use std::ops::Add;
+
+    fn preflight_request() -> PreflightRequest {
+        PreflightRequest::try_new(
+            fixt!(EntryHash),
+            vec![(fixt!(AgentPubKey), vec![]), (fixt!(AgentPubKey), vec![])],
+            vec![],
+            0,
+            false,
+            CounterSigningSessionTimes {
+                start: Timestamp::now(),
+                end: Timestamp::now()
+                    .add(std::time::Duration::from_secs(60))
+                    .unwrap(),
+            },
+            ActionBase::Create(CreateBase::new(fixt!(EntryType))),
+            PreflightBytes(vec![]),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn project_session_state() {
+        let sessions = [preflight_request(), preflight_request()];
+        let lock_subject = sessions[1].fingerprint().unwrap();
+
+        // The first session completed, and the second is being resolved after its entry was
+        // committed and the conductor restarted.
+        let state = ModelState::project(
+            &sessions,
+            2,
+            Some(&CountersigningSessionState::Unknown {
+                preflight_request: sessions[1].clone(),
+                resolution: SessionResolutionSummary {
+                    attempts: 2,
+                    ..Default::default()
+                },
+                force_abandon: false,
+                force_publish: true,
+            }),
+            Some(&lock_subject),
+            Some((&sessions[1], false)),
+            true,
+            |_| true,
+        );
+
+        assert_eq!(
+            ModelState {
+                session: Some(ModelSession::Unknown {
+                    id: 1,
+                    attempts: 2,
+                    force_abandon: false,
+                    force_publish: true,
+                }),
+                lock: Some(1),
+                head: Some(ModelHead {
+                    id: 1,
+                    revealed: false
+                }),
+                started: 2,
+                ended: 2,
+                triggered: true,
+                abandoned_completed: false,
+            },
+            state
+        );
+        state.check_invariants().unwrap();
+
+        let config = ModelConfig {
+            retry_limit: Some(3),
+            max_sessions: 2,
+            max_bundles: 2,
+        };
+        state.check_lock_released(&config).unwrap();
+    }
+
+    #[test]
+    fn explore_sessions() {
+        for retry_limit in [None, Some(1), Some(3)] {
+            let config = ModelConfig {
+                retry_limit,
+                max_sessions: 3,
+                max_bundles: 2,
+            };
+            let states = explore(&config)
+                .unwrap_or_else(|(trace, e)| panic!("{retry_limit:?}: {e}\nafter {trace:?}"));
+            let reached = |f: &dyn Fn(&ModelState) -> bool| states.iter().any(f);
+
+            // The invariants held in every state of a session: accepted, committed with the
+            // chain locked, with signatures collected directly or by resolving it, being
+            // resolved, and completed.
+            assert!(reached(&|s| matches!(
+                s.session,
+                Some(ModelSession::Accepted(_))
+            )));
+            assert!(reached(&|s| {
+                s.lock.is_some()
+                    && s.head
+                        .as_ref()
+                        .is_some_and(|h| !h.revealed && Some(h.id) == s.lock)
+            }));
+            assert!(reached(&|s| matches!(
+                s.session,
+                Some(ModelSession::SignaturesCollected {
+                    resolution: None,
+                    ..
+                })
+            )));
+            assert!(reached(&|s| matches!(
+                s.session,
+                Some(ModelSession::SignaturesCollected {
+                    resolution: Some(_),
+                    ..
+                })
+            )));
+            assert!(reached(&|s| matches!(
+                s.session,
+                Some(ModelSession::Unknown { attempts: 0, .. })
+            )));
+            // A session is only left unresolved after an attempt to resolve it, so that the
+            // user can force it either way, if the retry limit allows more than one attempt.
+            let retried = retry_limit.is_some_and(|limit| limit > 1);
+            assert_eq!(
+                retried,
+                reached(&|s| matches!(
+                    s.session,
+                    Some(ModelSession::Unknown {
+                        force_abandon: true,
+                        ..
+                    })
+                )),
+                "{retry_limit:?}"
+            );
+            assert_eq!(
+                retried,
+                reached(&|s| matches!(
+                    s.session,
+                    Some(ModelSession::Unknown {
+                        force_publish: true,
+                        ..
+                    })
+                )),
+                "{retry_limit:?}"
+            );
+            assert!(reached(&|s| s.head.as_ref().is_some_and(|h| h.revealed)));
+            // Every session the config allows was accepted.
+            assert!(reached(&|s| s.started == config.max_sessions));
+        }
+    }
+}
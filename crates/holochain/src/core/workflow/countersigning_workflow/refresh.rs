@@ -1,3 +1,4 @@
+use crate::conductor::conductor::SignalSender;
 use crate::conductor::space::Space;
 use crate::core::workflow::countersigning_workflow::{
     CountersigningSessionState, CountersigningWorkspace, ResolutionRequiredReason,
@@ -12,7 +13,6 @@ use holochain_state::prelude::{
 use holochain_types::prelude::{Signal, SystemSignal};
 use holochain_zome_types::cell::CellId;
 use std::sync::Arc;
-use tokio::sync::broadcast::Sender;
 
 /// Resolves the various states that the system can find itself in when operating a countersigning session.
 ///
@@ -29,7 +29,7 @@ pub async fn refresh_workspace_state(
     space: &Space,
     workspace: Arc<CountersigningWorkspace>,
     cell_id: CellId,
-    signal: Sender<Signal>,
+    signal: SignalSender,
 ) {
     tracing::debug!(
         "Refreshing countersigning workspace state for {:?}",
@@ -56,8 +56,9 @@ pub async fn refresh_workspace_state(
             .flatten();
 
         // If the chain is locked, then we need to check the session state.
-        if lock.is_some() {
+        if let Some(lock) = lock {
             locked_for_agent = true;
+            let lock_subject = lock.subject().to_vec();
 
             // Try to retrieve the current countersigning session. If we can't then we have lost
             // the state of the session and need to unlock the chain.
@@ -71,6 +72,19 @@ pub async fn refresh_workspace_state(
                                 current_countersigning_session(txn, Arc::new(agent.clone()))?;
                             tracing::trace!("Current session: {:?}", maybe_current_session);
 
+                            // A session entry which doesn't match the chain lock belongs to an
+                            // earlier session which has already completed, so it isn't the
+                            // session that the chain is locked for.
+                            let maybe_current_session = match maybe_current_session {
+                                Some((_, _, ref session_data))
+                                    if session_data.preflight_request.fingerprint()?
+                                        != lock_subject =>
+                                {
+                                    None
+                                }
+                                other => other,
+                            };
+
                             // If we've not made a commit and the entry hasn't been committed then
                             // there is no way to recover the session.
                             // We also can't have published a signature yet, so it's safe to unlock
@@ -1,3 +1,4 @@
+use crate::conductor::conductor::SignalSender;
 use crate::conductor::space::TestSpace;
 use crate::core::queue_consumer::{TriggerReceiver, TriggerSender};
 use crate::core::ribosome::weigh_placeholder;
@@ -48,7 +49,7 @@ use std::ops::Add;
 use std::sync::atomic::AtomicUsize;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::broadcast::{Receiver, Sender};
+use tokio::sync::broadcast::Receiver;
 
 #[tokio::test(flavor = "multi_thread")]
 async fn accept_countersigning_request_creates_state() {
@@ -1783,7 +1784,7 @@ struct TestHarness {
     test_space: TestSpace,
     workspace: Arc<CountersigningWorkspace>,
     network: Arc<MockHolochainP2pDnaT>,
-    signal_tx: Sender<Signal>,
+    signal_tx: SignalSender,
     signal_rx: Receiver<Signal>,
     keystore: MetaLairClient,
     author: AgentPubKey,
@@ -1801,7 +1802,8 @@ impl TestHarness {
     async fn new(dna_hash: DnaHash, retry_limit: Option<usize>) -> Self {
         let test_space = TestSpace::new(dna_hash.clone());
         let network = MockHolochainP2pDnaT::new();
-        let signal = tokio::sync::broadcast::channel::<Signal>(1);
+        let signal_tx = SignalSender::new();
+        let signal_rx = signal_tx.subscribe();
         let keystore = holochain_keystore::test_keystore();
         let author = keystore.new_sign_keypair_random().await.unwrap();
         let countersigning_trigger = TriggerSender::new();
@@ -1840,8 +1842,8 @@ impl TestHarness {
             test_space,
             workspace,
             network: Arc::new(network),
-            signal_tx: signal.0,
-            signal_rx: signal.1,
+            signal_tx,
+            signal_rx,
             keystore,
             author,
             countersigning_tx: countersigning_trigger.0,
@@ -20,6 +20,7 @@ use holochain_chc::ChcImpl;
 use holochain_sqlite::prelude::*;
 use holochain_state::source_chain;
 use holochain_state::workspace::WorkspaceResult;
+use holochain_types::clock::Clock;
 use holochain_types::db_cache::DhtDbQueryCache;
 use holochain_types::prelude::*;
 use rusqlite::named_params;
@@ -36,6 +37,8 @@ where
     ribosome: Ribosome,
     dht_db_cache: DhtDbQueryCache,
     chc: Option<ChcImpl>,
+    /// The clock of the conductor, which the genesis actions are timestamped with.
+    clock: Clock,
 }
 
 // #[cfg_attr(feature = "instrument", tracing::instrument(skip(workspace, api, args)))]
@@ -66,6 +69,7 @@ where
         ribosome,
         dht_db_cache,
         chc,
+        clock,
     } = args;
 
     if workspace.has_genesis(agent_pubkey.clone()).await? {
@@ -117,7 +121,7 @@ where
     // NOTE: we could check the key against DPKI state here, but the key hasn't even been
     //       registered at this point, so we can't.
 
-    source_chain::genesis(
+    source_chain::genesis_with_clock(
         workspace.vault.clone(),
         workspace.dht_db.clone(),
         &dht_db_cache,
@@ -126,6 +130,7 @@ where
         agent_pubkey,
         membrane_proof,
         chc,
+        &clock,
     )
     .await?;
 
@@ -233,6 +238,7 @@ mod tests {
                 ribosome,
                 dht_db_cache: dht_db_cache.clone(),
                 chc: None,
+                clock: Default::default(),
             };
             let _: () = genesis_workflow(workspace, api, args).await.unwrap();
         }
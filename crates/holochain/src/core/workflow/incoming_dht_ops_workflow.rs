@@ -2,10 +2,14 @@
 
 use super::sys_validation_workflow::counterfeit_check_action;
 use super::{error::WorkflowResult, sys_validation_workflow::counterfeit_check_warrant};
+use crate::core::metrics::record_incoming_ops_inserted;
+use crate::core::op_trace::record_op_event;
 use crate::{conductor::space::Space, core::queue_consumer::TriggerSender};
 use holo_hash::DhtOpHash;
+use holochain_conductor_api::OpTraceStage;
 use holochain_sqlite::error::DatabaseResult;
 use holochain_sqlite::prelude::*;
+use holochain_state::epoch::archived_dht_epochs;
 use holochain_state::prelude::*;
 use incoming_ops_batch::InOpBatchEntry;
 use std::{collections::HashSet, sync::Arc};
@@ -72,21 +76,11 @@ fn batch_process_entry(
     txn: &mut Txn<DbKindDht>,
     request_validation_receipt: bool,
     ops: Vec<DhtOpHashed>,
-) -> WorkflowResult<()> {
+) -> WorkflowResult<usize> {
     // add incoming ops to the validation limbo
-    let mut to_pending = Vec::with_capacity(ops.len());
-    for op in ops {
-        if !op_exists_inner(txn, &op.hash)? {
-            to_pending.push(op);
-        } else if request_validation_receipt {
-            set_require_receipt(txn, &op.hash, true)?;
-        }
-    }
-
-    tracing::debug!("Inserting {} ops", to_pending.len());
-    add_to_pending(txn, &to_pending, request_validation_receipt)?;
-
-    Ok(())
+    let inserted = insert_ops_dht_batch(txn, &ops, request_validation_receipt)?;
+    tracing::debug!("Inserted {} ops", inserted);
+    Ok(inserted)
 }
 
 #[derive(Default, Clone)]
@@ -106,14 +100,24 @@ pub async fn incoming_dht_ops_workflow(
         incoming_op_hashes,
         incoming_ops_batch,
         dht_db,
+        dna_hash,
         ..
     } = space;
 
     // Compute hashes for all the ops
-    let ops = ops
+    let ops: Vec<DhtOpHashed> = ops
         .into_iter()
         .map(DhtOpHashed::from_content_sync)
         .collect();
+    for op in &ops {
+        record_op_event(op.as_hash(), OpTraceStage::Received, || {
+            if request_validation_receipt {
+                "by publish, with a validation receipt requested".to_string()
+            } else {
+                "by gossip or fetch".to_string()
+            }
+        });
+    }
 
     // Filter out ops that are already being tracked, to avoid doing duplicate work
     let (_claim, ops) = OpsClaim::acquire(incoming_op_hashes, ops);
@@ -146,6 +150,7 @@ pub async fn incoming_dht_ops_workflow(
         // Filter the list of ops to only include those that are not already in the database.
         filter_ops = filter_existing_ops(&dht_db, filter_ops).await?;
     }
+    filter_ops = filter_archived_ops(&dht_db, filter_ops).await?;
 
     // Check again whether everything has been filtered out and avoid launching a Tokio task if so
     if filter_ops.is_empty() {
@@ -167,26 +172,34 @@ pub async fn incoming_dht_ops_workflow(
                 while let Some(entries) = maybe_batch {
                     let senders = Arc::new(parking_lot::Mutex::new(Vec::new()));
                     let senders2 = senders.clone();
-                    if let Err(err) = dht_db
+                    let started = std::time::Instant::now();
+                    match dht_db
                         .write_async(move |txn| {
+                            let mut inserted = 0;
                             for entry in entries {
                                 let InOpBatchEntry {
                                     snd,
                                     request_validation_receipt,
                                     ops,
                                 } = entry;
-                                let res = batch_process_entry(txn, request_validation_receipt, ops);
+                                let res = batch_process_entry(txn, request_validation_receipt, ops)
+                                    .map(|count| {
+                                        inserted += count;
+                                    });
 
                                 // we can't send the results here...
                                 // we haven't committed
                                 senders2.lock().push((snd, res));
                             }
 
-                            WorkflowResult::Ok(())
+                            WorkflowResult::Ok(inserted)
                         })
                         .await
                     {
-                        tracing::error!(?err, "incoming_dht_ops_workflow error");
+                        Ok(inserted) => {
+                            record_incoming_ops_inserted(&dna_hash, inserted, started.elapsed())
+                        }
+                        Err(err) => tracing::error!(?err, "incoming_dht_ops_workflow error"),
                     }
 
                     for (snd, res) in senders.lock().drain(..) {
@@ -223,19 +236,6 @@ async fn should_keep(op: &DhtOp) -> WorkflowResult<()> {
     Ok(())
 }
 
-fn add_to_pending(
-    txn: &mut Txn<DbKindDht>,
-    ops: &[DhtOpHashed],
-    request_validation_receipt: bool,
-) -> StateMutationResult<()> {
-    for op in ops {
-        insert_op_dht(txn, op, todo_no_cache_transfer_data())?;
-        set_require_receipt(txn, op.as_hash(), request_validation_receipt)?;
-    }
-
-    Ok(())
-}
-
 fn op_exists_inner(txn: &rusqlite::Transaction<'_>, hash: &DhtOpHash) -> DatabaseResult<bool> {
     Ok(txn.query_row(
         "
@@ -271,3 +271,16 @@ pub async fn filter_existing_ops(
         })
         .await
 }
+
+/// Filter out the ops of epochs which have been archived, so that peers which still
+/// hold them can't gossip them back.
+pub async fn filter_archived_ops(
+    vault: &DbWrite<DbKindDht>,
+    mut ops: Vec<DhtOpHashed>,
+) -> WorkflowResult<Vec<DhtOpHashed>> {
+    let archived = vault.read_async(archived_dht_epochs).await?;
+    if !archived.is_empty() {
+        ops.retain(|op| !archived.contains(&dht_op_epoch(op.timestamp())));
+    }
+    Ok(ops)
+}
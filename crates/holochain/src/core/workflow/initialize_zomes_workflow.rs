@@ -1,6 +1,7 @@
 use super::error::WorkflowResult;
 use crate::conductor::api::CellConductorApi;
 use crate::conductor::api::CellConductorApiT;
+use crate::conductor::conductor::SignalSender;
 use crate::conductor::ConductorHandle;
 use crate::core::queue_consumer::TriggerSender;
 use crate::core::ribosome::guest_callback::init::InitHostAccess;
@@ -14,7 +15,6 @@ use holochain_p2p::HolochainP2pDna;
 use holochain_state::host_fn_workspace::SourceChainWorkspace;
 use holochain_types::prelude::*;
 use holochain_zome_types::action::builder;
-use tokio::sync::broadcast;
 
 #[derive(Constructor)]
 pub struct InitializeZomesWorkflowArgs<Ribosome>
@@ -23,7 +23,7 @@ where
 {
     pub ribosome: Ribosome,
     pub conductor_handle: ConductorHandle,
-    pub signal_tx: broadcast::Sender<Signal>,
+    pub signal_tx: SignalSender,
     pub cell_id: CellId,
     pub integrate_dht_ops_trigger: TriggerSender,
 }
@@ -222,7 +222,7 @@ mod tests {
         let args = InitializeZomesWorkflowArgs {
             ribosome,
             conductor_handle,
-            signal_tx: broadcast::channel(1).0,
+            signal_tx: SignalSender::new(),
             cell_id: CellId::new(dna_def_hashed.to_hash(), author.clone()),
             integrate_dht_ops_trigger: integrate_dht_ops_trigger.0.clone(),
         };
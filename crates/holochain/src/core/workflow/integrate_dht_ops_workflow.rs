@@ -1,32 +1,70 @@
 //! The workflow and queue consumer for DhtOp integration
 
 use super::*;
+use crate::core::op_trace::{record_op_event, traced_ops};
 use crate::core::queue_consumer::TriggerSender;
 use crate::core::queue_consumer::WorkComplete;
+use holochain_conductor_api::OpTraceStage;
 use holochain_p2p::HolochainP2pDna;
 use holochain_p2p::HolochainP2pDnaT;
+use holochain_state::fulltext::index_fulltext_entries;
 use holochain_state::prelude::*;
+use std::sync::Arc;
 
 #[cfg(test)]
 mod query_tests;
 #[cfg(test)]
 mod tests;
 
+/// The full-text entry types of a DNA, and since when the ops integrated into its DHT
+/// database haven't been checked for entries to index yet.
+///
+/// Ops are integrated by the validation workflows as well as this one, so each run of
+/// this workflow indexes the entries of all the ops integrated since the last run.
+///
+/// The ops which another transaction stamped as integrated just before a run, but which
+/// were only committed after it, would be missed by the next run if it started at the
+/// time of this one. So each run also rechecks the ops integrated within
+/// [`FULLTEXT_SINCE_OVERLAP`] of the last run; entries are only ever indexed once.
+#[derive(Clone)]
+pub struct FullTextIndexing {
+    dna_def: Arc<DnaDef>,
+    since: Arc<parking_lot::Mutex<Timestamp>>,
+}
+
+/// How long before the start of the last run the next run starts checking integrated ops.
+pub const FULLTEXT_SINCE_OVERLAP: std::time::Duration = std::time::Duration::from_secs(60);
+
+impl FullTextIndexing {
+    /// Index the entries of the full-text entry types of a DNA, starting from the
+    /// ops which were integrated before the conductor started.
+    pub fn new(dna_def: Arc<DnaDef>) -> Self {
+        Self {
+            dna_def,
+            since: Arc::new(parking_lot::Mutex::new(Timestamp::MIN)),
+        }
+    }
+}
+
 #[cfg_attr(
     feature = "instrument",
-    tracing::instrument(skip(vault, trigger_receipt, network, dht_query_cache))
+    tracing::instrument(skip(vault, trigger_receipt, network, dht_query_cache, fulltext))
 )]
 pub async fn integrate_dht_ops_workflow(
     vault: DbWrite<DbKindDht>,
     dht_query_cache: DhtDbQueryCache,
     trigger_receipt: TriggerSender,
     network: HolochainP2pDna,
+    fulltext: FullTextIndexing,
 ) -> WorkflowResult<WorkComplete> {
     let start = std::time::Instant::now();
     let time = holochain_zome_types::prelude::Timestamp::now();
+    let fulltext_since = *fulltext.since.lock();
+    let fulltext_dna_def = fulltext.dna_def.clone();
     // Get any activity from the cache that is ready to be integrated.
     let activity_to_integrate = dht_query_cache.get_activity_to_integrate().await?;
-    let (changed, activity_integrated) = vault
+    let traced = traced_ops();
+    let (changed, activity_integrated, integrated_bases, integrated_traced, indexed) = vault
         .write_async(move |txn| {
             let mut total = 0;
             if !activity_to_integrate.is_empty() {
@@ -83,14 +121,75 @@ pub async fn integrate_dht_ops_workflow(
 
                 })?;
             total += changed;
-            WorkflowResult::Ok((total, activity_to_integrate))
+            // The bases of the integrated ops whose records and links may be cached.
+            let integrated_bases = if total > 0 {
+                txn.prepare_cached(holochain_sqlite::sql::sql_cell::SELECT_INTEGRATED_BASES)?
+                    .query_map(
+                        named_params! {
+                            ":when_integrated": time,
+                            ":store_record": ChainOpType::StoreRecord,
+                            ":store_entry": ChainOpType::StoreEntry,
+                            ":updated_content": ChainOpType::RegisterUpdatedContent,
+                            ":updated_record": ChainOpType::RegisterUpdatedRecord,
+                            ":deleted_by": ChainOpType::RegisterDeletedBy,
+                            ":deleted_entry_action": ChainOpType::RegisterDeletedEntryAction,
+                            ":create_link": ChainOpType::RegisterAddLink,
+                            ":delete_link": ChainOpType::RegisterRemoveLink,
+                        },
+                        |row| row.get::<_, OpBasis>(0),
+                    )?
+                    .collect::<Result<Vec<_>, _>>()?
+            } else {
+                Vec::new()
+            };
+            // Integration is done in bulk, so traced ops are looked up one at a time.
+            let mut integrated_traced = Vec::new();
+            if total > 0 {
+                let mut stmt = txn.prepare_cached(
+                    "SELECT validation_status FROM DhtOp
+                    WHERE hash = :hash AND when_integrated = :when_integrated",
+                )?;
+                for hash in traced {
+                    let status = stmt
+                        .query_row(
+                            named_params! {
+                                ":hash": hash,
+                                ":when_integrated": time,
+                            },
+                            |row| row.get::<_, Option<ValidationStatus>>(0),
+                        )
+                        .optional()?;
+                    if let Some(status) = status {
+                        integrated_traced.push((hash, status));
+                    }
+                }
+            }
+            let indexed = index_fulltext_entries(txn, &fulltext_dna_def, fulltext_since)?;
+            WorkflowResult::Ok((
+                total,
+                activity_to_integrate,
+                integrated_bases,
+                integrated_traced,
+                indexed,
+            ))
         })
         .await?;
+    // The ops integrated from shortly before now are checked on the next run.
+    *fulltext.since.lock() = time.saturating_sub(&FULLTEXT_SINCE_OVERLAP);
+    if indexed > 0 {
+        tracing::debug!(indexed, "Indexed the text of entries");
+    }
+    for (hash, status) in integrated_traced {
+        record_op_event(&hash, OpTraceStage::Integrated, || {
+            format!("as {:?}, once its dependencies were integrated", status)
+        });
+    }
     // Once the database transaction is committed, update the cache with the
-    // integrated activity.
+    // integrated activity, and drop the cached queries on the integrated bases.
     dht_query_cache
         .set_all_activity_to_integrated(activity_integrated)
         .await?;
+    dht_query_cache.queries().invalidate(integrated_bases);
     let ops_ps = changed as f64 / start.elapsed().as_micros() as f64 * 1_000_000.0;
     tracing::debug!(?changed, %ops_ps);
     if changed > 0 {
@@ -99,9 +99,15 @@ async fn integrate_query() {
     let (qt, _rx) = TriggerSender::new();
     let test_network = test_network(None, None).await;
     let holochain_p2p_cell = test_network.dna_network();
-    integrate_dht_ops_workflow(db.to_db(), db.to_db().into(), qt, holochain_p2p_cell)
-        .await
-        .unwrap();
+    integrate_dht_ops_workflow(
+        db.to_db(),
+        db.to_db().into(),
+        qt,
+        holochain_p2p_cell,
+        FullTextIndexing::new(Arc::new(fixt!(DnaDef))),
+    )
+    .await
+    .unwrap();
     let hashes = db
         .write_async(move |txn| -> DatabaseResult<HashSet<DhtOpHash>> {
             let mut stmt =
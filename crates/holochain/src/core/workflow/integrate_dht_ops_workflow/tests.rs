@@ -353,9 +353,15 @@ async fn call_workflow<'env>(env: DbWrite<DbKindDht>) {
     let (qt, _rx) = TriggerSender::new();
     let test_network = test_network(None, None).await;
     let holochain_p2p_cell = test_network.dna_network();
-    integrate_dht_ops_workflow(env.clone(), env.clone().into(), qt, holochain_p2p_cell)
-        .await
-        .unwrap();
+    integrate_dht_ops_workflow(
+        env.clone(),
+        env.clone().into(),
+        qt,
+        holochain_p2p_cell,
+        FullTextIndexing::new(Arc::new(fixt!(DnaDef))),
+    )
+    .await
+    .unwrap();
 }
 
 // Need to clear the data from the previous test
@@ -555,3 +561,77 @@ async fn test_ops_state() {
         .await;
     }
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn ops_integrated_just_before_a_run_are_indexed_by_the_next_run() {
+    holochain_trace::test_run();
+    let test_db = test_dht_db();
+    let env = test_db.to_db();
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes)]
+    struct Post(String);
+
+    let mut dna_def = fixt!(DnaDef);
+    dna_def.integrity_zomes = vec![("integrity".into(), fixt!(IntegrityZomeDef))];
+    dna_def.fulltext_entry_types = vec![ZomeTypeIndex {
+        zome: "integrity".into(),
+        index: 0,
+    }];
+    let fulltext = FullTextIndexing::new(Arc::new(dna_def));
+
+    let entry = Entry::App(AppEntryBytes(
+        Post("peer to peer networks".to_string())
+            .try_into()
+            .unwrap(),
+    ));
+    let entry_hash = EntryHash::with_data_sync(&entry);
+    let mut create = fixt!(Create);
+    create.entry_type = EntryType::App(AppEntryDef::new(
+        0.into(),
+        0.into(),
+        EntryVisibility::Public,
+    ));
+    create.entry_hash = entry_hash.clone();
+    let op = DhtOpHashed::from_content_sync(ChainOp::StoreEntry(
+        fixt!(Signature),
+        NewEntryAction::Create(create),
+        entry,
+    ));
+
+    let run = |fulltext: FullTextIndexing| {
+        let env = env.clone();
+        async move {
+            let (qt, _rx) = TriggerSender::new();
+            let test_network = test_network(None, None).await;
+            integrate_dht_ops_workflow(
+                env.clone(),
+                env.clone().into(),
+                qt,
+                test_network.dna_network(),
+                fulltext,
+            )
+            .await
+            .unwrap();
+        }
+    };
+    run(fulltext.clone()).await;
+
+    // - The op was stamped as integrated before the last run started, but was only
+    //   committed after it.
+    let integrated_at = (Timestamp::now() - std::time::Duration::from_secs(30)).unwrap();
+    env.write_async(move |txn| -> StateMutationResult<()> {
+        mutations::insert_op_dht(txn, &op, None)?;
+        mutations::set_validation_status(txn, op.as_hash(), ValidationStatus::Valid)?;
+        mutations::set_when_integrated(txn, op.as_hash(), integrated_at)?;
+        Ok(())
+    })
+    .await
+    .unwrap();
+    run(fulltext).await;
+
+    let found = env
+        .read_async(|txn| holochain_state::fulltext::query_fulltext(txn, "peer", 10))
+        .await
+        .unwrap();
+    assert_eq!(vec![entry_hash], found);
+}
@@ -12,9 +12,12 @@
 //!
 
 use super::error::WorkflowResult;
+use crate::core::op_trace::record_op_event;
 use crate::core::queue_consumer::TriggerSender;
 use crate::core::queue_consumer::WorkComplete;
 use holo_hash::*;
+use holochain_conductor_api::OpTraceStage;
+use holochain_p2p::DhtOpHashExt;
 use holochain_p2p::HolochainP2pDnaT;
 use holochain_state::prelude::*;
 use kitsune_p2p::dependencies::kitsune_p2p_fetch::OpHashSized;
@@ -30,7 +33,8 @@ pub use publish_query::{get_ops_to_publish, num_still_needing_publish};
 #[cfg(test)]
 mod unit_tests;
 
-/// Default redundancy factor for validation receipts
+/// Default redundancy factor for validation receipts, used when neither the DNA,
+/// the entry def nor the conductor's tuning params set one.
 pub const DEFAULT_RECEIPT_BUNDLE_SIZE: u8 = 5;
 
 #[cfg_attr(
@@ -58,18 +62,28 @@ pub async fn publish_dht_ops_workflow(
     let mut success = Vec::with_capacity(to_publish.len());
     for (basis, list) in to_publish {
         let (op_hash_list, op_data_list): (Vec<_>, Vec<_>) = list.into_iter().unzip();
-        match network
+        let result = network
             .publish(
                 true,
                 false,
-                basis,
+                basis.clone(),
                 agent.clone(),
                 op_hash_list.clone(),
                 None,
                 Some(op_data_list),
             )
-            .await
-        {
+            .await;
+        for hash in &op_hash_list {
+            record_op_event(
+                &DhtOpHash::from_kitsune(hash.data_ref()),
+                OpTraceStage::Published,
+                || match &result {
+                    Ok(()) => format!("to the authorities of {:?}", basis),
+                    Err(e) => format!("failed to publish to {:?}: {:?}", basis, e),
+                },
+            );
+        }
+        match result {
             Err(e) => {
                 // If we get a routing error it means the space hasn't started yet and we should try publishing again.
                 if let holochain_p2p::HolochainP2pError::RoutingDnaError(_) = e {
@@ -92,7 +106,6 @@ pub async fn publish_dht_ops_workflow(
     let continue_publish = db
         .write_async(move |txn| {
             for hash in success {
-                use holochain_p2p::DhtOpHashExt;
                 let hash = DhtOpHash::from_kitsune(hash.data_ref());
                 set_last_publish_time(txn, &hash, now)?;
             }
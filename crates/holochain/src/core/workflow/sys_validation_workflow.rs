@@ -35,7 +35,7 @@
 //! - For a [`ChainOp::RegisterDeletedEntryAction`]
 //!    - The [`Delete::deletes_address`] reference to the [`Action`] being deleted must point to an [`Action`] that can be found locally. The action being deleted must be a [`Action::Create`] or [`Action::Update`].
 //! - For a [`ChainOp::RegisterAddLink`]
-//!   - The size of the [`CreateLink::tag`] must be less than or equal to the maximum size that is accepted for this link tag. This is specified in the constant [`MAX_TAG_SIZE`].
+//!   - The size of the [`CreateLink::tag`] must be less than or equal to the maximum size that is accepted for this link tag. This is specified in the constant [`MAX_TAG_SIZE`], or by a smaller `max_link_tag_size` in the [`PayloadSizeLimits`] of the DNA.
 //! - For a [`ChainOp::RegisterRemoveLink`]
 //!   - The [`DeleteLink::link_add_address`] reference to the [`Action`] of the link being deleted must point to an [`Action`] that can be found locally. That action being deleted must also
 //!     be a [`Action::CreateLink`].
@@ -56,7 +56,7 @@
 //!
 //! - The entry type specified in the [`Action`] must match the entry type specified in the [`Entry`].
 //! - The entry hash specified in the [`Action`] must match the entry hash specified in the [`Entry`], which will be hashed as part of the check to obtain a value that is deterministic.
-//! - The size of the [`Entry`] must be less than or equal to the maximum size that is accepted for this entry type. This is specified in the constant [`MAX_ENTRY_SIZE`].
+//! - The size of the [`Entry`] must be less than or equal to the maximum size that is accepted for this entry type. This is specified in the constant [`MAX_ENTRY_SIZE`], or by a smaller `max_entry_size` in the [`PayloadSizeLimits`] of the DNA.
 //! - If the [`Action`] is an [`Action::Update`], then the [`Update::original_action_address`] reference to the [`Action`] being updated must point to an [`Action`] that can be found locally. Once the [`Action`] address has been resolved, the [`Update::original_entry_address`] is checked against the entry address that the referenced [`Action`] specified.
 //! - If the [`Entry`] is an [`Entry::CounterSign`], then the pre-flight response signatures are checked.
 //!
@@ -76,6 +76,8 @@
 //!
 
 use crate::conductor::Conductor;
+use crate::core::metrics::{record_awaited_dependencies, record_workflow_queue_depth};
+use crate::core::op_trace::record_op_event;
 use crate::core::queue_consumer::TriggerSender;
 use crate::core::queue_consumer::WorkComplete;
 use crate::core::sys_validate::*;
@@ -86,6 +88,7 @@ use futures::StreamExt;
 use holo_hash::DhtOpHash;
 use holochain_cascade::Cascade;
 use holochain_cascade::CascadeImpl;
+use holochain_conductor_api::OpTraceStage;
 use holochain_conductor_services::DpkiImpl;
 use holochain_keystore::MetaLairClient;
 use holochain_p2p::GenericNetwork;
@@ -94,6 +97,7 @@ use holochain_sqlite::prelude::*;
 use holochain_sqlite::sql::sql_cell::ACTION_HASH_BY_PREV;
 use holochain_state::prelude::*;
 use rusqlite::Transaction;
+use std::collections::HashMap;
 use std::convert::TryInto;
 use std::sync::Arc;
 use std::time::Duration;
@@ -238,7 +242,10 @@ async fn sys_validation_workflow_inner(
     _representative_agent: AgentPubKey,
 ) -> WorkflowResult<OutcomeSummary> {
     let db = workspace.dht_db.clone();
-    let sorted_ops = validation_query::get_ops_to_sys_validate(&db).await?;
+    let dna_def = DnaDefHashed::from_content_sync((*workspace.dna_def()).clone());
+    let mut sorted_ops = validation_query::get_ops_to_sys_validate(&db).await?;
+    validation_query::put_interactive_ops_first(&mut sorted_ops, &workspace.dna_def());
+    record_workflow_queue_depth("sys_validation", dna_def.as_hash(), sorted_ops.len());
 
     // Forget what dependencies are currently in use
     current_validation_dependencies
@@ -256,6 +263,7 @@ async fn sys_validation_workflow_inner(
             .same_dht
             .lock()
             .purge_held_deps();
+        record_awaited_dependencies("sys_validation", dna_def.as_hash(), HashMap::new());
 
         return Ok(OutcomeSummary::new());
     }
@@ -264,7 +272,6 @@ async fn sys_validation_workflow_inner(
     tracing::debug!("Sys validating {} ops", num_ops_to_validate);
 
     let cascade = Arc::new(workspace.local_cascade());
-    let dna_def = DnaDefHashed::from_content_sync((*workspace.dna_def()).clone());
 
     retrieve_previous_actions_for_ops(
         current_validation_dependencies.clone(),
@@ -280,6 +287,8 @@ async fn sys_validation_workflow_inner(
         .purge_held_deps();
 
     let mut validation_outcomes = Vec::with_capacity(sorted_ops.len());
+    // How many ops are waiting for each dependency which isn't held yet.
+    let mut awaited_dependencies = HashMap::<AnyDhtHash, usize>::new();
     for hashed_op in sorted_ops {
         let dpki = workspace
             .dpki
@@ -297,13 +306,31 @@ async fn sys_validation_workflow_inner(
         .await;
 
         match r {
-            Ok(outcome) => validation_outcomes.push((hashed_op, outcome)),
+            Ok(outcome) => {
+                record_op_event(hashed_op.as_hash(), OpTraceStage::SysValidated, || {
+                    format!("{:?}", outcome)
+                });
+                if let Outcome::MissingDhtDep = outcome {
+                    let mut deps = current_validation_dependencies.same_dht.lock();
+                    for dep in hashed_op.as_content().sys_validation_dependencies() {
+                        if !deps.has(&dep) {
+                            *awaited_dependencies.entry(dep.into()).or_default() += 1;
+                        }
+                    }
+                }
+                validation_outcomes.push((hashed_op, outcome))
+            }
             Err(e) => {
+                record_op_event(hashed_op.as_hash(), OpTraceStage::SysValidated, || {
+                    format!("failed to validate: {:?}", e)
+                });
                 tracing::error!(error = ?e, "Error validating op");
             }
         }
     }
 
+    record_awaited_dependencies("sys_validation", dna_def.as_hash(), awaited_dependencies);
+
     // Allow unused mutable, because it's mutated when feature unstable-warrants is enabled.
     #[allow(unused_mut)]
     let (mut summary, _invalid_ops, _forked_pairs) = workspace
@@ -361,6 +388,9 @@ async fn sys_validation_workflow_inner(
                         summary.rejected += 1;
                         if deps.is_empty() {
                             put_integrated(txn, &op_hash, ValidationStatus::Rejected)?;
+                            record_op_event(&op_hash, OpTraceStage::Integrated, || {
+                                "as Rejected, right after sys validation".to_string()
+                            });
                         } else {
                             put_integration_limbo(txn, &op_hash, ValidationStatus::Rejected)?;
                         }
@@ -723,6 +753,7 @@ async fn validate_chain_op(
                         .try_into()
                         .map_err(|_| ValidationOutcome::NotNewEntry(action.clone()))?,
                     entry,
+                    &dna_def.size_limits,
                     validation_dependencies,
                 )
                 .await?;
@@ -749,7 +780,13 @@ async fn validate_chain_op(
             }
 
             check_prev_action(&action.clone().into())?;
-            store_entry(action.into(), entry, validation_dependencies.clone()).await
+            store_entry(
+                action.into(),
+                entry,
+                &dna_def.size_limits,
+                validation_dependencies.clone(),
+            )
+            .await
         }
         ChainOp::RegisterAgentActivity(_, action) => {
             register_agent_activity(action, validation_dependencies.clone(), dna_def)?;
@@ -761,6 +798,7 @@ async fn validate_chain_op(
                 store_entry(
                     NewEntryActionRef::Update(action),
                     entry,
+                    &dna_def.size_limits,
                     validation_dependencies,
                 )
                 .await?;
@@ -774,6 +812,7 @@ async fn validate_chain_op(
                 store_entry(
                     NewEntryActionRef::Update(action),
                     entry,
+                    &dna_def.size_limits,
                     validation_dependencies,
                 )
                 .await?;
@@ -787,7 +826,7 @@ async fn validate_chain_op(
         ChainOp::RegisterDeletedEntryAction(_, action) => {
             register_deleted_entry_action(action, validation_dependencies)
         }
-        ChainOp::RegisterAddLink(_, action) => register_add_link(action),
+        ChainOp::RegisterAddLink(_, action) => register_add_link(action, &dna_def.size_limits),
         ChainOp::RegisterRemoveLink(_, action) => {
             register_delete_link(action, validation_dependencies)
         }
@@ -912,9 +951,10 @@ async fn validate_warrant_op(
 /// it is intended to be used for validation of records which have been authored locally so we should always be able to check the previous action.
 pub async fn sys_validate_record(
     record: &Record,
+    size_limits: &PayloadSizeLimits,
     cascade: Arc<impl Cascade + Send + Sync>,
 ) -> SysValidationOutcome<()> {
-    match sys_validate_record_inner(record, cascade).await {
+    match sys_validate_record_inner(record, size_limits, cascade).await {
         // Validation succeeded
         Ok(_) => Ok(()),
         // Validation failed so exit with that outcome
@@ -933,6 +973,7 @@ pub async fn sys_validate_record(
 
 async fn sys_validate_record_inner(
     record: &Record,
+    size_limits: &PayloadSizeLimits,
     cascade: Arc<impl Cascade + Send + Sync>,
 ) -> SysValidationResult<()> {
     let signature = record.signature();
@@ -943,6 +984,7 @@ async fn sys_validate_record_inner(
     async fn validate(
         action: &Action,
         maybe_entry: Option<&Entry>,
+        size_limits: &PayloadSizeLimits,
         cascade: Arc<impl Cascade + Send + Sync>,
     ) -> SysValidationResult<()> {
         let validation_dependencies = SysValDeps::default();
@@ -973,6 +1015,7 @@ async fn sys_validate_record_inner(
                     .try_into()
                     .map_err(|_| ValidationOutcome::NotNewEntry(action.clone()))?,
                 maybe_entry,
+                size_limits,
                 validation_dependencies.clone(),
             )
             .await?;
@@ -984,7 +1027,7 @@ async fn sys_validate_record_inner(
             Action::Delete(action) => {
                 register_deleted_entry_action(action, validation_dependencies.clone())
             }
-            Action::CreateLink(action) => register_add_link(action),
+            Action::CreateLink(action) => register_add_link(action, size_limits),
             Action::DeleteLink(action) => {
                 register_delete_link(action, validation_dependencies.clone())
             }
@@ -997,15 +1040,15 @@ async fn sys_validate_record_inner(
             if let Some(weight) = action.entry_rate_data() {
                 let entry_hash = EntryHash::with_data_sync(maybe_entry.unwrap());
                 for action in session.build_action_set(entry_hash, weight)? {
-                    validate(&action, maybe_entry, cascade.clone()).await?;
+                    validate(&action, maybe_entry, size_limits, cascade.clone()).await?;
                 }
                 Ok(())
             } else {
                 tracing::error!("Got countersigning entry without rate assigned. This should be impossible. But, let's see what happens.");
-                validate(action, maybe_entry, cascade.clone()).await
+                validate(action, maybe_entry, size_limits, cascade.clone()).await
             }
         }
-        _ => validate(action, maybe_entry, cascade).await,
+        _ => validate(action, maybe_entry, size_limits, cascade).await,
     }
 }
 
@@ -1084,6 +1127,7 @@ fn store_record(action: &Action, validation_dependencies: SysValDeps) -> SysVali
 async fn store_entry(
     action: NewEntryActionRef<'_>,
     entry: &Entry,
+    size_limits: &PayloadSizeLimits,
     validation_dependencies: SysValDeps,
 ) -> SysValidationResult<()> {
     // Get data ready to validate
@@ -1093,7 +1137,7 @@ async fn store_entry(
     // Checks
     check_entry_type(entry_type, entry)?;
     check_entry_hash(entry_hash, entry)?;
-    check_entry_size(entry)?;
+    check_entry_size(entry, size_limits)?;
 
     // Additional checks if this is an Update
     if let NewEntryActionRef::Update(entry_update) = action {
@@ -1188,8 +1232,11 @@ fn register_deleted_entry_action(
     check_new_entry_action(action)
 }
 
-fn register_add_link(link_add: &CreateLink) -> SysValidationResult<()> {
-    check_tag_size(&link_add.tag)
+fn register_add_link(
+    link_add: &CreateLink,
+    size_limits: &PayloadSizeLimits,
+) -> SysValidationResult<()> {
+    check_tag_size(&link_add.tag, size_limits)
 }
 
 fn register_delete_link(
@@ -1366,7 +1413,12 @@ impl SysValidationWorkspace {
     }
 
     pub fn network_and_cache_cascade(&self, network: GenericNetwork) -> CascadeImpl {
-        CascadeImpl::empty().with_network(network, self.cache.clone())
+        let cascade = CascadeImpl::empty().with_network(network, self.cache.clone());
+        // The cached queries on the bases of fetched ops are dropped.
+        match &self.dht_query_cache {
+            Some(dht_query_cache) => cascade.with_dht_db_cache(dht_query_cache.clone()),
+            None => cascade,
+        }
     }
 
     /// Get a reference to the sys validation workspace's dna def.
@@ -1,8 +1,10 @@
 use super::sys_validation_workflow;
 use super::validation_deps::SysValDeps;
 use super::validation_query::get_ops_to_app_validate;
+use super::validation_query::get_ops_to_sys_validate;
 use super::SysValidationWorkspace;
 use crate::conductor::space::TestSpace;
+use crate::core::metrics::awaited_dependencies;
 use crate::core::queue_consumer::TriggerReceiver;
 use crate::core::queue_consumer::TriggerSender;
 use crate::core::queue_consumer::WorkComplete;
@@ -219,9 +221,99 @@ async fn validate_op_with_dependency_not_found_on_the_dht() {
     let ops_to_app_validate = test_case.get_ops_pending_app_validation().await;
     assert!(ops_to_app_validate.is_empty());
 
+    // The missing previous action is reported as what the op is waiting for.
+    let awaited = awaited_dependencies("sys_validation", &test_case.dna_hash());
+    assert_eq!(
+        Some(&1),
+        awaited.get(&previous_action.as_hash().clone().into())
+    );
+
     test_case.expect_app_validation_not_triggered().await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn op_awaiting_a_missing_dependency_is_validated_once_it_arrives() {
+    holochain_trace::test_run();
+
+    let mut test_case = TestCase::new().await;
+
+    // Previous op, which nobody has yet
+    let mut prev_create_action = fixt!(Create);
+    prev_create_action.author = test_case.agent.clone();
+    prev_create_action.action_seq = 10;
+    prev_create_action.entry_type = EntryType::App(AppEntryDef {
+        entry_index: 0.into(),
+        zome_index: 0.into(),
+        visibility: EntryVisibility::Public,
+    });
+    let previous_action = test_case
+        .sign_action(Action::Create(prev_create_action.clone()))
+        .await;
+
+    // Op to validate, to go in the dht database
+    let mut create_action = fixt!(Create);
+    create_action.author = previous_action.action().author().clone();
+    create_action.action_seq = previous_action.action().action_seq() + 1;
+    create_action.prev_action = previous_action.as_hash().clone();
+    create_action.timestamp = Timestamp::now();
+    create_action.entry_type = EntryType::App(AppEntryDef {
+        entry_index: 0.into(),
+        zome_index: 0.into(),
+        visibility: EntryVisibility::Public,
+    });
+    let op = ChainOp::RegisterAgentActivity(fixt!(Signature), Action::Create(create_action)).into();
+    let op_hash = test_case
+        .save_op_to_db(test_case.dht_db_handle(), op)
+        .await
+        .unwrap();
+
+    let mut network = MockHolochainP2pDnaT::new();
+    let response = WireOps::Record(WireRecordOps::new());
+    network
+        .expect_get()
+        .return_once(move |_, _| Ok(vec![response]));
+
+    // - The op stays in limbo, waiting for the previous action, and the workflow
+    //   asks to be run again later.
+    let work_complete = test_case.with_network_behaviour(network).run().await;
+    assert_eq!(
+        WorkComplete::Incomplete(Some(std::time::Duration::from_secs(10))),
+        work_complete
+    );
+    assert!(test_case
+        .get_ops_pending_sys_validation()
+        .await
+        .contains(&op_hash));
+    assert!(test_case.get_ops_pending_app_validation().await.is_empty());
+    let awaited = awaited_dependencies("sys_validation", &test_case.dna_hash());
+    assert_eq!(
+        Some(&1),
+        awaited.get(&previous_action.as_hash().clone().into())
+    );
+    test_case.expect_app_validation_not_triggered().await;
+
+    // - The previous action arrives.
+    let previous_op =
+        ChainOp::RegisterAgentActivity(fixt!(Signature), Action::Create(prev_create_action)).into();
+    test_case
+        .save_op_to_db(test_case.cache_db_handle(), previous_op)
+        .await
+        .unwrap();
+
+    // - The op is validated when the workflow runs again, and passed on to app validation.
+    assert_eq!(WorkComplete::Complete, test_case.run().await);
+    assert!(!test_case
+        .get_ops_pending_sys_validation()
+        .await
+        .contains(&op_hash));
+    assert!(test_case
+        .get_ops_pending_app_validation()
+        .await
+        .contains(&op_hash));
+    assert!(awaited_dependencies("sys_validation", &test_case.dna_hash()).is_empty());
+    test_case.expect_app_validation_triggered().await;
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn validate_op_with_wrong_sequence_number_rejected_and_not_forwarded_to_app_validation() {
     holochain_trace::test_run();
@@ -401,6 +493,16 @@ impl TestCase {
         self.run().await
     }
 
+    /// The ops which are still waiting to be sys validated
+    async fn get_ops_pending_sys_validation(&self) -> HashSet<DhtOpHash> {
+        get_ops_to_sys_validate(&self.dht_db_handle().into())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|op_hashed| op_hashed.hash)
+            .collect()
+    }
+
     /// This provides a quick and reliable way to check that ops have been sys validated
     async fn get_ops_pending_app_validation(&self) -> HashSet<DhtOpHash> {
         get_ops_to_app_validate(&self.dht_db_handle().into())
@@ -7,6 +7,8 @@ use crate::core::workflow::sys_validation_workflow::types::Outcome;
 use crate::core::workflow::sys_validation_workflow::validate_op;
 use crate::core::workflow::WorkflowResult;
 use crate::core::ValidationOutcome;
+use crate::core::MAX_ENTRY_SIZE;
+use crate::core::MAX_TAG_SIZE;
 use crate::prelude::*;
 use ::fixt::prelude::*;
 use futures::FutureExt;
@@ -1075,7 +1077,7 @@ async fn validate_store_record_with_large_entry() {
     let outcome = test_case.with_op(op).run().await.unwrap();
 
     assert_eq!(
-        Outcome::Rejected(ValidationOutcome::EntryTooLarge(5_000_011).to_string()),
+        Outcome::Rejected(ValidationOutcome::EntryTooLarge(5_000_011, MAX_ENTRY_SIZE).to_string()),
         outcome,
     );
 }
@@ -1457,7 +1459,7 @@ async fn validate_store_entry_with_large_entry() {
     let outcome = test_case.with_op(op).run().await.unwrap();
 
     assert_eq!(
-        Outcome::Rejected(ValidationOutcome::EntryTooLarge(5_000_011).to_string()),
+        Outcome::Rejected(ValidationOutcome::EntryTooLarge(5_000_011, MAX_ENTRY_SIZE).to_string()),
         outcome,
     );
 }
@@ -2214,7 +2216,28 @@ async fn validate_add_link_tag_too_large() {
     let outcome = test_case.with_op(op).run().await.unwrap();
 
     assert_eq!(
-        Outcome::Rejected(ValidationOutcome::TagTooLarge(2_000).to_string()),
+        Outcome::Rejected(ValidationOutcome::TagTooLarge(2_000, MAX_TAG_SIZE).to_string()),
+        outcome
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn validate_add_link_tag_too_large_for_dna() {
+    holochain_trace::test_run();
+
+    let mut test_case = TestCase::new().await;
+    test_case.dna_def_mut().size_limits.max_link_tag_size = Some(10);
+
+    // Op to validate
+    let mut create_link_action = fixt!(CreateLink);
+    create_link_action.tag = vec![0; 11].into();
+    create_link_action.timestamp = Timestamp::now();
+    let op = ChainOp::RegisterAddLink(fixt!(Signature), create_link_action).into();
+
+    let outcome = test_case.with_op(op).run().await.unwrap();
+
+    assert_eq!(
+        Outcome::Rejected(ValidationOutcome::TagTooLarge(11, 10).to_string()),
         outcome
     );
 }
@@ -19,6 +19,18 @@ pub async fn get_ops_to_sys_validate(db: &DbRead<DbKindDht>) -> WorkflowResult<V
     get_ops_to_validate(db, true).await
 }
 
+/// Move the ops of the [`InteractiveTypes`] of the DNA ahead of the other ops to
+/// validate, otherwise keeping their order.
+pub fn put_interactive_ops_first(ops: &mut [DhtOpHashed], dna_def: &DnaDef) {
+    if dna_def.interactive_types.is_empty() {
+        return;
+    }
+    ops.sort_by_cached_key(|op| match op.as_content() {
+        DhtOp::ChainOp(chain_op) => !dna_def.is_interactive(&chain_op.action()),
+        DhtOp::WarrantOp(_) => true,
+    });
+}
+
 async fn get_ops_to_validate(
     db: &DbRead<DbKindDht>,
     system: bool,
@@ -170,6 +182,46 @@ mod tests {
         assert!(overlap.is_empty());
     }
 
+    #[test]
+    fn interactive_ops_go_first() {
+        let mut dna_def = fixt!(DnaDef);
+        dna_def.integrity_zomes = vec![(
+            "integrity".into(),
+            ZomeDef::from_hash(WasmHash::from_raw_32(vec![0; 32])).into(),
+        )];
+        dna_def.interactive_types.entry_types.push(ZomeTypeIndex {
+            zome: "integrity".into(),
+            index: 0,
+        });
+        let background: Vec<_> = (0..3)
+            .map(|_| {
+                DhtOpHashed::from_content_sync(ChainOp::RegisterAgentActivity(
+                    fixt!(Signature),
+                    Action::CreateLink(fixt!(CreateLink)),
+                ))
+            })
+            .collect();
+        let mut create = fixt!(Create);
+        create.entry_type = EntryType::App(AppEntryDef::new(
+            0.into(),
+            0.into(),
+            EntryVisibility::Public,
+        ));
+        let interactive = DhtOpHashed::from_content_sync(ChainOp::RegisterAgentActivity(
+            fixt!(Signature),
+            Action::Create(create),
+        ));
+
+        let mut ops = background.clone();
+        ops.push(interactive.clone());
+        put_interactive_ops_first(&mut ops, &dna_def);
+
+        // - The interactive op goes first, and the others keep their order.
+        let mut expected = vec![interactive];
+        expected.extend(background);
+        assert_eq!(expected, ops);
+    }
+
     async fn create_test_data(db: &DbWrite<DbKindDht>) -> Expected {
         let mut to_sys_validate = Vec::with_capacity(40);
         let mut to_app_validate = Vec::with_capacity(40);
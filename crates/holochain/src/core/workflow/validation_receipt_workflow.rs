@@ -10,7 +10,9 @@ use holochain_state::prelude::*;
 use tracing::*;
 
 use super::error::WorkflowResult;
+use crate::core::op_trace::record_op_event;
 use crate::core::queue_consumer::WorkComplete;
+use holochain_conductor_api::OpTraceStage;
 use holochain_zome_types::block::Block;
 use holochain_zome_types::block::BlockTarget;
 use holochain_zome_types::block::CellBlockReason;
@@ -176,14 +178,30 @@ where
         );
     }
 
+    let receipt_statuses: Vec<(DhtOpHash, ValidationStatus)> = receipts
+        .iter()
+        .map(|receipt| {
+            (
+                receipt.receipt.dht_op_hash.clone(),
+                receipt.receipt.validation_status,
+            )
+        })
+        .collect();
+
     // Send it and don't wait for response.
-    if let Err(e) = holochain_p2p::HolochainP2pDnaT::send_validation_receipts(
+    let result = holochain_p2p::HolochainP2pDnaT::send_validation_receipts(
         network,
         op_author.clone(),
         receipts.into(),
     )
-    .await
-    {
+    .await;
+    for (op_hash, status) in receipt_statuses {
+        record_op_event(&op_hash, OpTraceStage::ReceiptSent, || match &result {
+            Ok(()) => format!("as {:?}, to {}", status, op_author),
+            Err(e) => format!("as {:?}, failed to send to {}: {:?}", status, op_author, e),
+        });
+    }
+    if let Err(e) = result {
         // No one home, they will need to publish again.
         info!(failed_send_receipt = ?e);
     }
@@ -3,6 +3,7 @@ pub mod curve;
 use crate::conductor::api::CellConductorReadHandle;
 use crate::conductor::api::DpkiApi;
 use crate::conductor::api::MockCellConductorReadHandleT;
+use crate::conductor::conductor::SignalSender;
 use crate::core::ribosome::guest_callback::entry_defs::EntryDefsHostAccess;
 use crate::core::ribosome::guest_callback::entry_defs::EntryDefsInvocation;
 use crate::core::ribosome::guest_callback::init::InitHostAccess;
@@ -10,6 +11,7 @@ use crate::core::ribosome::guest_callback::init::InitInvocation;
 use crate::core::ribosome::guest_callback::post_commit::PostCommitHostAccess;
 use crate::core::ribosome::guest_callback::post_commit::PostCommitInvocation;
 use crate::core::ribosome::guest_callback::validate::ValidateHostAccess;
+use crate::core::ribosome::guest_callback::validate::ValidationContext;
 use crate::core::ribosome::real_ribosome::ModuleCacheLock;
 use crate::core::ribosome::real_ribosome::RealRibosome;
 use crate::core::ribosome::CallContext;
@@ -39,7 +41,6 @@ use rand::Rng;
 use std::collections::BTreeMap;
 use std::sync::Arc;
 use strum::IntoEnumIterator;
-use tokio::sync::broadcast;
 
 pub use holochain_types::fixt::*;
 
@@ -352,8 +353,9 @@ fixturator!(
         dpki: None,
         keystore: MetaLairClientFixturator::new(Empty).next().unwrap(),
         network: HolochainP2pDnaFixturator::new(Empty).next().unwrap(),
-        signal_tx: broadcast::channel(50).0,
+        signal_tx: SignalSender::new(),
         call_zome_handle: CellConductorReadHandleFixturator::new(Empty).next().unwrap(),
+        dry_run: false,
     };
     curve Unpredictable ZomeCallHostAccess {
         workspace: HostFnWorkspaceFixturator::new(Unpredictable).next().unwrap(),
@@ -361,8 +363,9 @@ fixturator!(
         dpki: None,
         keystore: MetaLairClientFixturator::new(Unpredictable).next().unwrap(),
         network: HolochainP2pDnaFixturator::new(Unpredictable).next().unwrap(),
-        signal_tx: broadcast::channel(50).0,
+        signal_tx: SignalSender::new(),
         call_zome_handle: CellConductorReadHandleFixturator::new(Unpredictable).next().unwrap(),
+        dry_run: false,
     };
     curve Predictable ZomeCallHostAccess {
         workspace: HostFnWorkspaceFixturator::new_indexed(Predictable, get_fixt_index!())
@@ -376,10 +379,11 @@ fixturator!(
         network: HolochainP2pDnaFixturator::new_indexed(Predictable, get_fixt_index!())
             .next()
             .unwrap(),
-        signal_tx: broadcast::channel(50).0,
+        signal_tx: SignalSender::new(),
         call_zome_handle: CellConductorReadHandleFixturator::new_indexed(Predictable, get_fixt_index!())
             .next()
             .unwrap(),
+        dry_run: false,
     };
 );
 
@@ -406,7 +410,7 @@ fixturator!(
         // DPKI cannot be fixturated.
         dpki: None,
         network: HolochainP2pDnaFixturator::new(Empty).next().unwrap(),
-        signal_tx: broadcast::channel(50).0,
+        signal_tx: SignalSender::new(),
         call_zome_handle: CellConductorReadHandleFixturator::new(Empty).next().unwrap(),
     };
     curve Unpredictable InitHostAccess {
@@ -415,7 +419,7 @@ fixturator!(
         // DPKI cannot be fixturated.
         dpki: None,
         network: HolochainP2pDnaFixturator::new(Unpredictable).next().unwrap(),
-        signal_tx: broadcast::channel(50).0,
+        signal_tx: SignalSender::new(),
         call_zome_handle: CellConductorReadHandleFixturator::new(Unpredictable).next().unwrap(),
     };
     curve Predictable InitHostAccess {
@@ -430,7 +434,7 @@ fixturator!(
         network: HolochainP2pDnaFixturator::new_indexed(Predictable, get_fixt_index!())
             .next()
             .unwrap(),
-        signal_tx: broadcast::channel(50).0,
+        signal_tx: SignalSender::new(),
         call_zome_handle: CellConductorReadHandleFixturator::new_indexed(Predictable, get_fixt_index!())
             .next()
             .unwrap(),
@@ -448,13 +452,13 @@ fixturator!(
         workspace: HostFnWorkspaceFixturator::new(Empty).next().unwrap(),
         keystore: MetaLairClientFixturator::new(Empty).next().unwrap(),
         network: HolochainP2pDnaFixturator::new(Empty).next().unwrap(),
-        signal_tx: broadcast::channel(50).0,
+        signal_tx: SignalSender::new(),
     };
     curve Unpredictable PostCommitHostAccess {
         workspace: HostFnWorkspaceFixturator::new(Unpredictable).next().unwrap(),
         keystore: MetaLairClientFixturator::new(Unpredictable).next().unwrap(),
         network: HolochainP2pDnaFixturator::new(Unpredictable).next().unwrap(),
-        signal_tx: broadcast::channel(50).0,
+        signal_tx: SignalSender::new(),
     };
     curve Predictable PostCommitHostAccess {
         workspace: HostFnWorkspaceFixturator::new_indexed(Predictable, get_fixt_index!())
@@ -466,7 +470,7 @@ fixturator!(
         network: HolochainP2pDnaFixturator::new_indexed(Predictable, get_fixt_index!())
             .next()
             .unwrap(),
-        signal_tx: broadcast::channel(50).0,
+        signal_tx: SignalSender::new(),
     };
 );
 
@@ -484,9 +488,14 @@ fixturator!(
     curve Predictable None;
 );
 
+fixturator!(
+    ValidationContext;
+    constructor fn new(Timestamp, ThirtyTwoBytes);
+);
+
 fixturator!(
     ValidateHostAccess;
-    constructor fn new(HostFnWorkspace, HolochainP2pDna, DpkiApi, bool);
+    constructor fn new(HostFnWorkspace, HolochainP2pDna, DpkiApi, bool, ValidationContext);
 );
 
 fixturator!(
@@ -522,6 +531,7 @@ fixturator!(
         signature: SignatureFixturator::new(Empty).next().unwrap(),
         nonce: Nonce256Bits::from(ThirtyTwoBytesFixturator::new(Empty).next().unwrap()),
         expires_at: TimestampFixturator::new(Empty).next().unwrap(),
+        delegation: Vec::new(),
     };
     curve Unpredictable ZomeCallInvocation {
         cell_id: CellIdFixturator::new(Unpredictable).next().unwrap(),
@@ -534,6 +544,7 @@ fixturator!(
         nonce: Nonce256Bits::from(ThirtyTwoBytesFixturator::new(Unpredictable).next().unwrap()),
         // @todo should this be less predictable?
         expires_at: (Timestamp::now() + std::time::Duration::from_secs(30)).unwrap(),
+        delegation: Vec::new(),
     };
     curve Predictable ZomeCallInvocation {
         cell_id: CellIdFixturator::new_indexed(Predictable, get_fixt_index!())
@@ -558,6 +569,7 @@ fixturator!(
         nonce: Nonce256Bits::from(ThirtyTwoBytesFixturator::new_indexed(Predictable, get_fixt_index!()).next().unwrap()),
         // @todo should this be more predictable?
         expires_at: (Timestamp::now() + std::time::Duration::from_secs(30)).unwrap(),
+        delegation: Vec::new(),
     };
 );
 
@@ -82,5 +82,7 @@ pub async fn get_install_app_payload_from_dnas(
         existing_cells: Default::default(),
         ignore_genesis_failure: false,
         allow_throwaway_random_agent_key: false,
+        network_profile: None,
+        client_only: false,
     }
 }
@@ -1,11 +1,15 @@
 use super::{SweetAppBatch, SweetConductor, SweetConductorConfig};
 use crate::conductor::api::error::ConductorApiResult;
+use crate::conductor::config::ConductorConfig;
+use crate::conductor::Conductor;
 use crate::sweettest::*;
 use ::fixt::prelude::StdRng;
 use futures::future;
 use hdk::prelude::*;
 use holochain_types::prelude::*;
+use kitsune_p2p_bootstrap_client::{DynBootstrapService, LocalBootstrap};
 use std::path::PathBuf;
+use std::sync::Arc;
 
 /// A collection of SweetConductors, with methods for operating on the entire collection
 #[derive(derive_more::Into, derive_more::IntoIterator, derive_more::Deref)]
@@ -87,6 +91,39 @@ impl SweetConductorBatch {
         conductors
     }
 
+    /// Create SweetConductors from the given ConductorConfigs, each with its own new TestEnvironments,
+    /// which find each other through a [`LocalBootstrap`] shared in this process rather than
+    /// a bootstrap server. They still connect to each other through the signal server of a
+    /// [`SweetLocalRendezvous`], so the configs shouldn't set a bootstrap service.
+    ///
+    /// Also await consistency for DPKI cells, if DPKI is enabled.
+    pub async fn from_configs_local_bootstrap<C, I>(configs: I) -> SweetConductorBatch
+    where
+        C: Into<SweetConductorConfig>,
+        I: IntoIterator<Item = C>,
+    {
+        let rendezvous = SweetLocalRendezvous::new().await;
+        let bootstrap: DynBootstrapService = Arc::new(LocalBootstrap::new());
+        let conductors = Self::new(
+            future::join_all(configs.into_iter().map(|config| {
+                let config: SweetConductorConfig = config.into();
+                let config: ConductorConfig = config.apply_rendezvous(&rendezvous).into();
+                let builder = Conductor::builder()
+                    .config(config)
+                    .with_bootstrap_service(bootstrap.clone());
+                SweetConductor::from_builder_rendezvous(builder, rendezvous.clone())
+            }))
+            .await,
+        );
+
+        let dpki_cells = conductors.dpki_cells();
+        if !dpki_cells.is_empty() {
+            await_consistency(15, dpki_cells.as_slice()).await.unwrap();
+        }
+
+        conductors
+    }
+
     /// Create the given number of new SweetConductors, each with its own new TestEnvironments
     pub async fn from_config<C: Clone + Into<SweetConductorConfig>>(
         num: usize,
@@ -47,6 +47,27 @@ impl From<KitsuneP2pConfig> for SweetConductorConfig {
                 countersigning_resolution_retry_delay: Some(std::time::Duration::from_secs(3)),
                 countersigning_resolution_retry_limit: None,
                 min_publish_interval: None,
+                wasm_metering_limit: None,
+                must_get_max_timeout: None,
+                must_get_max_retries: None,
+                slow_zome_call_threshold: None,
+                slow_workflow_threshold: None,
+                websocket_max_message_size: None,
+                default_required_validation_receipts: None,
+                workflow_runtime_count: None,
+                workflow_runtime_threads: None,
+                workflow_runtime_assignments: None,
+                shutdown_drain_timeout: None,
+                verify_integrity_on_startup: None,
+                prefetch_hot_hashes: None,
+                prefetch_interval: None,
+                client_cache_fresh_for: None,
+                min_region_redundancy: None,
+                ingest_max_backlog: None,
+                ingest_max_memory_mb: None,
+                zome_call_concurrency: None,
+                clock_skew_threshold: None,
+                clock_skew_correction: None,
             }),
             ..Default::default()
         }
@@ -174,6 +195,17 @@ impl SweetConductorConfig {
         self
     }
 
+    /// Connect over the in-memory switchboard with this name instead, on which a test can
+    /// delay, drop and partition the messages between conductors with
+    /// [`kitsune_p2p::switchboard::Switchboard::named`].
+    pub fn switchboard(mut self, name: &str) -> Self {
+        self.network.transport_pool =
+            vec![kitsune_p2p_types::config::TransportConfig::Switchboard {
+                name: name.to_string(),
+            }];
+        self
+    }
+
     /// Completely disable networking
     pub fn no_networking(mut self) -> Self {
         self.network = self.network.clone().tune(|mut tp| {
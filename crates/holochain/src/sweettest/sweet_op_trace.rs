@@ -0,0 +1,108 @@
+//! Replay the regression fixtures which `hc model fixture` makes out of op traces.
+
+use super::*;
+use holochain_conductor_api::op_model::{
+    check_op_trace, is_holder_event, OpArrival, OpTraceCheck, OpTraceFixture, OpTraceViolation,
+};
+use holochain_state::prelude::*;
+use std::time::Duration;
+
+/// Replay an [`OpTraceFixture`] on two fresh conductors, and assert that the op is left
+/// in the same state on the conductor which receives it as the trace left it in.
+///
+/// A trace doesn't record what authored the op, so the op is authored again by calling
+/// `fn_name` on the first conductor, which must return the [`ActionHash`] of the action
+/// whose `op_type` op was traced. The op then reaches the second conductor the same way
+/// as it reached the traced conductor, by publish or by gossip, and the trace of it there
+/// is checked against the model until it reaches the state of the fixture, or `timeout`
+/// passes.
+///
+/// The conductors of a test share their op traces, so the op is only traced once the
+/// author has integrated it, and the events which the author records are left out.
+pub async fn replay_op_trace_fixture<P>(
+    fixture: &OpTraceFixture,
+    dna_file: DnaFile,
+    zome_name: &str,
+    fn_name: &str,
+    payload: P,
+    op_type: ChainOpType,
+    timeout: Duration,
+) -> OpTraceCheck
+where
+    P: serde::Serialize + std::fmt::Debug,
+{
+    let config = match fixture.arrival {
+        OpArrival::Publish => SweetConductorConfig::standard().tune(|tp| {
+            tp.disable_recent_gossip = true;
+            tp.disable_historical_gossip = true;
+        }),
+        OpArrival::Gossip => SweetConductorConfig::standard().no_publish(),
+    };
+    let mut conductors = SweetConductorBatch::from_config(2, config).await;
+    let apps = conductors.setup_app("app", &[dna_file]).await.unwrap();
+    let ((author,), _) = apps.into_tuples();
+
+    // - The peers don't know each other yet, so the op can't reach the holder
+    //   before it is traced.
+    let action_hash: ActionHash = conductors[0]
+        .call(&author.zome(zome_name), fn_name, payload)
+        .await;
+    await_consistency(timeout.as_secs(), [&author])
+        .await
+        .unwrap();
+    let op_hash = author
+        .authored_db()
+        .read_async(move |txn| {
+            DatabaseResult::Ok(txn.query_row(
+                "SELECT hash FROM DhtOp WHERE action_hash = :action_hash AND type = :type",
+                named_params! {
+                    ":action_hash": action_hash,
+                    ":type": op_type,
+                },
+                |row| row.get::<_, DhtOpHash>(0),
+            )?)
+        })
+        .await
+        .unwrap();
+    conductors[1].raw_handle().start_op_trace(op_hash.clone());
+
+    conductors.exchange_peer_info().await;
+    if fixture.arrival == OpArrival::Publish {
+        conductors.force_all_publish_dht_ops().await;
+    }
+
+    let holder_trace = || {
+        let events = conductors[1]
+            .raw_handle()
+            .op_trace(&op_hash)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(is_holder_event)
+            .collect::<Vec<_>>();
+        check_op_trace(&events)
+    };
+    let check = tokio::time::timeout(timeout, async {
+        loop {
+            let check = holder_trace();
+            if check.machine.holder_state() == fixture.terminal {
+                return check;
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    })
+    .await
+    .unwrap_or_else(|_| holder_trace());
+    conductors[1].raw_handle().stop_op_trace(&op_hash);
+
+    assert_eq!(
+        Vec::<OpTraceViolation>::new(),
+        check.violations,
+        "the replayed trace has violations"
+    );
+    assert_eq!(
+        fixture.terminal,
+        check.machine.holder_state(),
+        "the replayed op didn't reach the state of the fixture"
+    );
+    check
+}
@@ -955,6 +955,7 @@ where
         signature,
         nonce,
         expires_at,
+        delegation,
         ..
     } = new_zome_call(keystore, cell_id, func, payload, zome.clone().into()).await?;
     Ok(ZomeCallInvocation {
@@ -967,6 +968,7 @@ where
         signature,
         nonce,
         expires_at,
+        delegation,
     })
 }
 
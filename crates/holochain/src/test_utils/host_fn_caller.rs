@@ -3,6 +3,7 @@
 use crate::conductor::api::CellConductorApi;
 use crate::conductor::api::CellConductorApiT;
 use crate::conductor::api::CellConductorReadHandle;
+use crate::conductor::conductor::SignalSender;
 use crate::conductor::ConductorHandle;
 use crate::core::ribosome::host_fn;
 use crate::core::ribosome::real_ribosome::RealRibosome;
@@ -27,7 +28,6 @@ use holochain_types::db_cache::DhtDbQueryCache;
 use holochain_types::prelude::*;
 use holochain_wasm_test_utils::TestWasmPair;
 use std::sync::Arc;
-use tokio::sync::broadcast;
 use unwrap_to::unwrap_to;
 
 // Commit entry types //
@@ -95,7 +95,7 @@ pub struct HostFnCaller {
     pub zome_path: ZomePath,
     pub network: HolochainP2pDna,
     pub keystore: MetaLairClient,
-    pub signal_tx: broadcast::Sender<Signal>,
+    pub signal_tx: SignalSender,
     pub call_zome_handle: CellConductorReadHandle,
 }
 
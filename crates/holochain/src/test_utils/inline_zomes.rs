@@ -9,12 +9,18 @@ use crate::sweettest::SweetInlineZomes;
 
 /// An InlineZome with simple Create and Read operations
 pub fn simple_create_read_zome() -> InlineIntegrityZome {
+    simple_create_read_zome_with_visibility(EntryVisibility::Public)
+}
+
+/// An InlineZome with simple Create and Read operations, which creates entries
+/// with the given visibility
+pub fn simple_create_read_zome_with_visibility(visibility: EntryVisibility) -> InlineIntegrityZome {
     InlineIntegrityZome::new_unique(InlineEntryTypes::entry_defs(), 0)
         .function("create", move |api, ()| {
             let entry = Entry::app(().try_into().unwrap()).unwrap();
             let hash = api.create(CreateInput::new(
                 InlineZomeSet::get_entry_location(&api, InlineEntryTypes::A),
-                EntryVisibility::Public,
+                visibility,
                 entry,
                 ChainTopOrdering::default(),
             ))?;
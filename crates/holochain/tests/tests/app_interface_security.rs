@@ -1,8 +1,9 @@
 use either::Either;
 use holochain::sweettest::{websocket_client_by_port, SweetConductor, SweetDnaFile, WsPollRecv};
 use holochain_conductor_api::{
-    AdminRequest, AdminResponse, AppAuthenticationRequest, AppAuthenticationToken, AppRequest,
-    AppResponse, IssueAppAuthenticationTokenPayload,
+    AdminRequest, AdminResponse, AppAuthenticationRequest, AppAuthenticationToken,
+    AppAuthenticationTokenScope, AppRequest, AppResponse, ExternalApiWireError,
+    IssueAppAuthenticationTokenPayload,
 };
 use holochain_types::prelude::InstalledAppId;
 use holochain_types::websocket::AllowedOrigins;
@@ -553,6 +554,58 @@ async fn revoke_app_auth_token() {
     assert_matches!(err, WebsocketError::Close(_));
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn requests_are_restricted_by_token_scope() {
+    holochain_trace::test_run();
+
+    let conductor = SweetConductor::from_standard_config().await;
+
+    let app_port = conductor
+        .clone()
+        .add_app_interface(Either::Left(0), AllowedOrigins::Any, None)
+        .await
+        .unwrap();
+
+    let (admin_tx, _admin_rx) = conductor.admin_ws_client::<AdminResponse>().await;
+    let issued: AdminResponse = admin_tx
+        .request(AdminRequest::IssueAppAuthenticationToken(
+            IssueAppAuthenticationTokenPayload::for_installed_app_id("test-app".into())
+                .scope(AppAuthenticationTokenScope::ReadOnly),
+        ))
+        .await
+        .unwrap();
+    let token = match issued {
+        AdminResponse::AppAuthenticationTokenIssued(issued) => issued.token,
+        _ => panic!("Unexpected response"),
+    };
+
+    let (app_tx, app_rx) = websocket_client_by_port(app_port).await.unwrap();
+    let _app_rx = WsPollRecv::new::<AppResponse>(app_rx);
+    app_tx
+        .authenticate(AppAuthenticationRequest { token })
+        .await
+        .unwrap();
+
+    // Reading is allowed.
+    let listed: AppResponse = app_tx
+        .request(AppRequest::ListWasmHostFunctions)
+        .await
+        .unwrap();
+    assert!(matches!(listed, AppResponse::ListWasmHostFunctions(_)));
+
+    // Managing the app isn't, but the connection stays open.
+    let enabled: AppResponse = app_tx.request(AppRequest::EnableApp).await.unwrap();
+    assert_matches!(
+        enabled,
+        AppResponse::Error(ExternalApiWireError::RequestOutOfScope(_))
+    );
+    let listed: AppResponse = app_tx
+        .request(AppRequest::ListWasmHostFunctions)
+        .await
+        .unwrap();
+    assert!(matches!(listed, AppResponse::ListWasmHostFunctions(_)));
+}
+
 async fn check_app_port(port: u16, origin: &str, token: AppAuthenticationToken) {
     let (client, rx) = connect(
         Arc::new(WebsocketConfig::CLIENT_DEFAULT),
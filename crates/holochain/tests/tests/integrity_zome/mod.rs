@@ -89,6 +89,7 @@ async fn test_coordinator_zome_update_multi_integrity() {
         ZomeDef::Wasm(WasmZome {
             wasm_hash,
             mut dependencies,
+            init_dependencies,
             preserialized_path,
         }) => {
             dependencies.clear();
@@ -99,6 +100,7 @@ async fn test_coordinator_zome_update_multi_integrity() {
                 ZomeDef::Wasm(WasmZome {
                     wasm_hash,
                     dependencies,
+                    init_dependencies,
                     preserialized_path,
                 })
                 .into(),
@@ -181,6 +183,7 @@ async fn test_coordinator_zome_update_multi_integrity() {
     let new_coordinator: CoordinatorZomeDef = ZomeDef::Wasm(WasmZome {
         wasm_hash,
         dependencies: vec!["2".into()],
+        init_dependencies: Default::default(),
         preserialized_path: None,
     })
     .into();
@@ -238,6 +241,7 @@ async fn test_update_admin_interface() {
             dependencies: Some(vec![ZomeDependency {
                 name: TestIntegrityWasm::IntegrityZome.into(),
             }]),
+            init_dependencies: None,
         }],
     };
 
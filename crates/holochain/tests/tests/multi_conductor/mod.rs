@@ -346,6 +346,117 @@ async fn private_entries_dont_leak() {
     check_for_private_entries(conductors[1].get_cache_db(bobbo.cell_id()).await.unwrap()).await;
 }
 
+/// Test that a cell can call a remote agent's cell of another DNA, through the cell
+/// which its app has in that DNA, as its own agent and only where the remote cell
+/// grants that agent access.
+#[cfg(feature = "test_utils")]
+#[tokio::test(flavor = "multi_thread")]
+async fn remote_calls_to_cells_of_other_dnas() {
+    use holochain::sweettest::SweetInlineZomes;
+
+    holochain_trace::test_run();
+    let caller = SweetInlineZomes::new(vec![], 0).function(
+        "call_remote_cell",
+        |api, (cell_id, fn_name): (CellId, String)| {
+            Ok(api
+                .call(vec![Call::new(
+                    CallTarget::NetworkCell(cell_id),
+                    SweetInlineZomes::COORDINATOR.into(),
+                    fn_name.into(),
+                    None,
+                    ExternIO::encode(()).unwrap(),
+                )])?
+                .pop()
+                .unwrap())
+        },
+    );
+    let callee = SweetInlineZomes::new(vec![], 0)
+        .function("init", |api, ()| {
+            let functions = GrantedFunctions::Listed(
+                [(SweetInlineZomes::COORDINATOR.into(), "whoami".into())]
+                    .into_iter()
+                    .collect(),
+            );
+            api.create(CreateInput::new(
+                EntryDefLocation::CapGrant,
+                EntryVisibility::Private,
+                Entry::CapGrant(CapGrantEntry {
+                    tag: "".into(),
+                    // empty access converts to unrestricted
+                    access: ().into(),
+                    functions,
+                }),
+                ChainTopOrdering::default(),
+            ))?;
+            Ok(InitCallbackResult::Pass)
+        })
+        .function("whoami", |api, ()| Ok(api.call_info(())?.provenance))
+        .function("ungranted_whoami", |api, ()| {
+            Ok(api.call_info(())?.provenance)
+        });
+    let (caller_dna, _, _) = SweetDnaFile::unique_from_inline_zomes(caller).await;
+    let (callee_dna, _, _) = SweetDnaFile::unique_from_inline_zomes(callee).await;
+    let unknown_dna = SweetDnaFile::unique_empty().await;
+
+    let mut conductors = SweetConductorBatch::from_standard_config_rendezvous(2).await;
+    let (alice_caller, alice_callee) = conductors[0]
+        .setup_app("app", [&caller_dna, &callee_dna])
+        .await
+        .unwrap()
+        .into_tuple();
+    let (bob,) = conductors[1]
+        .setup_app("app", [&callee_dna])
+        .await
+        .unwrap()
+        .into_tuple();
+    conductors.exchange_peer_info().await;
+
+    // - Bob grants access to `whoami` in init.
+    let _: AgentPubKey = conductors[1]
+        .call(&bob.zome(SweetInlineZomes::COORDINATOR), "whoami", ())
+        .await;
+
+    let response: ZomeCallResponse = conductors[0]
+        .call(
+            &alice_caller.zome(SweetInlineZomes::COORDINATOR),
+            "call_remote_cell",
+            (bob.cell_id().clone(), "whoami"),
+        )
+        .await;
+    let provenance: AgentPubKey = match response {
+        ZomeCallResponse::Ok(output) => output.decode().unwrap(),
+        other => panic!("Remote call failed: {:?}", other),
+    };
+    // - The call was made through alice's cell of the DNA of bob's cell, which is of
+    //   the same agent as the calling cell, so no other identity is borrowed.
+    assert_eq!(alice_callee.agent_pubkey(), alice_caller.agent_pubkey());
+    assert_eq!(alice_caller.agent_pubkey(), &provenance);
+
+    // - Bob's grant decides what may be called, as for any remote call.
+    let response: ZomeCallResponse = conductors[0]
+        .call(
+            &alice_caller.zome(SweetInlineZomes::COORDINATOR),
+            "call_remote_cell",
+            (bob.cell_id().clone(), "ungranted_whoami"),
+        )
+        .await;
+    assert!(
+        matches!(response, ZomeCallResponse::Unauthorized(..)),
+        "{response:?}"
+    );
+
+    // - A DNA which the app has no cell of can't be reached.
+    let unreachable = CellId::new(unknown_dna.dna_hash().clone(), bob.agent_pubkey().clone());
+    assert!(conductors[0]
+        .call_fallible::<_, ZomeCallResponse>(
+            &alice_caller.zome(SweetInlineZomes::COORDINATOR),
+            "call_remote_cell",
+            (unreachable, "whoami"),
+        )
+        .await
+        .is_err());
+}
+
 #[cfg_attr(feature = "instrument", tracing::instrument(skip_all))]
 async fn check_for_private_entries<Kind: DbKindT>(env: DbWrite<Kind>) {
     let count: usize = env.read_async(move |txn| -> DatabaseResult<usize> {
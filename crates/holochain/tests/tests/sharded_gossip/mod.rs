@@ -128,6 +128,42 @@ async fn bad_network_gossip() {
     );
 }
 
+#[cfg(feature = "test_utils")]
+#[tokio::test(flavor = "multi_thread")]
+#[cfg_attr(target_os = "macos", ignore = "flaky")]
+async fn gossip_with_a_local_bootstrap_service() {
+    holochain_trace::test_run();
+
+    // - The conductors only share a signal server and a bootstrap service in this process,
+    //   there's no bootstrap server.
+    let config = SweetConductorConfig::rendezvous(false).no_dpki();
+    assert!(config.network.bootstrap_service.is_none());
+    let mut conductors =
+        SweetConductorBatch::from_configs_local_bootstrap([config.clone(), config]).await;
+
+    let (dna_file, _, _) =
+        SweetDnaFile::unique_from_inline_zomes(("simple", simple_create_read_zome())).await;
+    let mut cells = Vec::new();
+    for conductor in conductors.iter_mut() {
+        let (cell,) = conductor
+            .setup_app("app", [&dna_file])
+            .await
+            .unwrap()
+            .into_tuple();
+        cells.push(cell);
+    }
+
+    let hash: ActionHash = conductors[0]
+        .call(&cells[0].zome("simple"), "create", ())
+        .await;
+    await_consistency(60, &cells).await.unwrap();
+
+    let record: Option<Record> = conductors[1]
+        .call(&cells[1].zome("simple"), "read", hash)
+        .await;
+    assert!(record.is_some());
+}
+
 #[cfg(feature = "test_utils")]
 #[tokio::test(flavor = "multi_thread")]
 #[cfg_attr(target_os = "macos", ignore = "flaky")]
@@ -218,6 +218,7 @@ where
         nonce: zome_call_unsigned.nonce,
         expires_at: zome_call_unsigned.expires_at,
         signature: Signature::from(signature.to_bytes()),
+        delegation: Vec::new(),
     };
     let request = AppRequest::CallZome(Box::new(call));
     let response = app_tx.request(request);
@@ -376,6 +377,8 @@ pub async fn register_and_install_dna_named(
         existing_cells: Default::default(),
         ignore_genesis_failure: false,
         allow_throwaway_random_agent_key: true,
+        network_profile: None,
+        client_only: false,
     };
     let request = AdminRequest::InstallApp(Box::new(payload));
     let response = client.request(request);
@@ -17,6 +17,7 @@ use holochain::{
 use std::net::{Ipv4Addr, Ipv6Addr, ToSocketAddrs};
 
 use either::Either;
+use holochain_conductor_api::conductor::ConductorTuningParams;
 use holochain_conductor_api::{AdminInterfaceConfig, AppRequest, InterfaceDriver};
 use holochain_types::websocket::AllowedOrigins;
 use holochain_types::{
@@ -885,7 +886,7 @@ async fn holochain_websockets_listen_on_ipv4_and_ipv6() {
     let _rx4 = WsPollRecv::new::<AdminResponse>(rx);
 
     let response: AdminResponse = ipv4_admin_sender
-        .request(AdminRequest::ListCellIds)
+        .request(AdminRequest::ListCellIds(None))
         .await
         .unwrap();
     match response {
@@ -902,7 +903,7 @@ async fn holochain_websockets_listen_on_ipv4_and_ipv6() {
     let _rx6 = WsPollRecv::new::<AdminResponse>(rx);
 
     let response: AdminResponse = ipv6_admin_sender
-        .request(AdminRequest::ListCellIds)
+        .request(AdminRequest::ListCellIds(None))
         .await
         .unwrap();
     match response {
@@ -1009,6 +1010,70 @@ async fn emit_signal_after_app_connection_closed() {
     //      Can't currently do that with TaskMotel which I think is the right thing to query here.
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn list_and_disconnect_app_interface_clients() {
+    holochain_trace::test_run();
+
+    let mut conductor = SweetConductor::from_standard_config().await;
+
+    let dna_file = SweetDnaFile::unique_from_test_wasms(vec![TestWasm::EmitSignal])
+        .await
+        .0;
+    let installed_app_id: InstalledAppId = "app".into();
+    conductor
+        .setup_app(&installed_app_id, &[dna_file])
+        .await
+        .unwrap();
+
+    let port = conductor
+        .clone()
+        .add_app_interface(Either::Left(0), AllowedOrigins::Any, None)
+        .await
+        .expect("Couldn't create app interface");
+    let (tx, rx) = websocket_client_by_port(port).await.unwrap();
+    let _rx = WsPollRecv::new::<AppResponse>(rx);
+
+    authenticate_app_ws_client(
+        tx.clone(),
+        conductor
+            .get_arbitrary_admin_websocket_port()
+            .expect("No admin ports on this conductor"),
+        installed_app_id.clone(),
+    )
+    .await;
+
+    // The connection is registered once the conductor has handled the authentication.
+    let connection = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let Some(connection) = conductor.list_app_interface_connections().pop() {
+                break connection;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("Timed out waiting for the connection to be listed");
+    assert_eq!(port, connection.port);
+    assert_eq!(installed_app_id, connection.installed_app_id);
+    assert_eq!(Some("holochain_websocket".to_string()), connection.origin);
+    assert_eq!(0, connection.messages_in_flight);
+
+    conductor
+        .disconnect_app_interface_client(connection.connection_id)
+        .unwrap();
+    assert!(conductor.list_app_interface_connections().is_empty());
+
+    // The client can no longer make requests.
+    tx.request::<_, AppResponse>(AppRequest::AppInfo)
+        .await
+        .unwrap_err();
+
+    assert_matches!(
+        conductor.disconnect_app_interface_client(connection.connection_id),
+        Err(ConductorError::AppInterfaceConnectionNotFound(_))
+    );
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn filter_messages_that_do_not_deserialize() {
     holochain_trace::test_run();
@@ -1092,3 +1157,103 @@ async fn filter_messages_that_do_not_deserialize() {
         }
     }
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn reload_config_applies_changeable_settings() {
+    holochain_trace::test_run();
+    let tmp_dir = TempDir::new().unwrap();
+    let config = create_config(0, tmp_dir.path().to_path_buf().into());
+    let conductor_handle = Conductor::builder()
+        .config(config)
+        .config_path(tmp_dir.path().join("conductor_config.yml"))
+        .with_test_device_seed()
+        .build()
+        .await
+        .unwrap();
+    let port = admin_port(&conductor_handle).await;
+    let (client, rx) = websocket_client(&conductor_handle).await.unwrap();
+    let _rx = WsPollRecv::new::<AdminResponse>(rx);
+
+    // Start from the config the conductor is running with,
+    // which includes the generated device seed.
+    let mut config = conductor_handle.get_config().clone();
+    config.admin_interfaces = Some(vec![AdminInterfaceConfig {
+        driver: InterfaceDriver::Websocket {
+            port: 0,
+            allowed_origins: AllowedOrigins::Origins(
+                ["http://example.com".to_string()].into_iter().collect(),
+            ),
+        },
+    }]);
+    config.tuning_params = Some(ConductorTuningParams {
+        sys_validation_retry_delay: Some(Duration::from_secs(7)),
+        ..ConductorTuningParams::new()
+    });
+    config.network.tracing_scope = Some("reloaded".to_string());
+    write_config(tmp_dir.path().to_path_buf(), &config);
+
+    let response = client.request(AdminRequest::ReloadConfig).await.unwrap();
+    let AdminResponse::ConfigReloaded(report) = response else {
+        panic!("unexpected response: {:?}", response);
+    };
+    assert_eq!(
+        vec![
+            "tuning_params".to_string(),
+            "admin_interfaces.allowed_origins".to_string()
+        ],
+        report.applied
+    );
+    assert_eq!(vec!["network".to_string()], report.requires_restart);
+    assert_eq!(
+        Duration::from_secs(7),
+        conductor_handle
+            .conductor_tuning_params()
+            .sys_validation_retry_delay()
+    );
+
+    // The connection which was already open keeps working.
+    let response = client.request(AdminRequest::ListDnas).await.unwrap();
+    assert_matches!(response, AdminResponse::DnasListed(_));
+
+    let connect_with_origin = |origin: &'static str| {
+        connect(
+            Arc::new(WebsocketConfig::CLIENT_DEFAULT),
+            ConnectRequest::new(
+                format!("localhost:{port}")
+                    .to_socket_addrs()
+                    .unwrap()
+                    .next()
+                    .unwrap(),
+            )
+            .try_set_header("Origin", origin)
+            .unwrap(),
+        )
+    };
+    assert!(connect_with_origin("http://other.org").await.is_err());
+    let (_client, _rx) = connect_with_origin("http://example.com").await.unwrap();
+
+    // Reloading again without changes applies nothing.
+    let response = client.request(AdminRequest::ReloadConfig).await.unwrap();
+    assert_matches!(
+        response,
+        AdminResponse::ConfigReloaded(report) if report.applied.is_empty() && report.requires_restart == vec!["network".to_string()]
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn reload_config_fails_without_config_file() {
+    holochain_trace::test_run();
+    let tmp_dir = TempDir::new().unwrap();
+    let config = create_config(0, tmp_dir.path().to_path_buf().into());
+    let conductor_handle = Conductor::builder()
+        .config(config)
+        .with_test_device_seed()
+        .build()
+        .await
+        .unwrap();
+
+    assert_matches!(
+        conductor_handle.reload_config(),
+        Err(ConductorError::NoConfigPath)
+    );
+}
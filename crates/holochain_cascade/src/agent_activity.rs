@@ -56,7 +56,12 @@ fn merge_activity_responses(
             continue;
         }
 
-        warrants.extend(these_warrants);
+        // Authorities which hold the same warrant each return it.
+        for warrant in these_warrants {
+            if !warrants.contains(&warrant) {
+                warrants.push(warrant);
+            }
+        }
 
         match (merged_highest_observed.take(), highest_observed) {
             (None, None) => {}
@@ -215,6 +220,17 @@ fn merge_activity_responses(
     }
 }
 
+/// Apply a page to activity that may have been merged from many authorities.
+pub(crate) fn apply_page(items: ChainItems, page: &AgentActivityPage) -> ChainItems {
+    match items {
+        ChainItems::Full(records) => ChainItems::Full(page.apply(records)),
+        // Only the sequence numbers are known, the authorities have already
+        // applied the time range.
+        ChainItems::Hashes(hashes) => ChainItems::Hashes(page.apply_seq_bounds(hashes)),
+        ChainItems::NotRequested => ChainItems::NotRequested,
+    }
+}
+
 pub(crate) fn compute_chain_status<T: ActionSequenceAndHash>(
     valid: impl Iterator<Item = T>,
     rejected: impl Iterator<Item = T>,
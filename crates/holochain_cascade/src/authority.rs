@@ -13,6 +13,7 @@ use crate::authority::get_agent_activity_query::records::GetAgentActivityRecords
 use holo_hash::ActionHash;
 use holo_hash::AgentPubKey;
 use holochain_state::query::link::GetLinksQuery;
+use holochain_state::query::link_count::LinkTimestampsQuery;
 use holochain_state::query::CascadeTxnWrapper;
 use holochain_state::query::{Query, Store};
 use holochain_types::prelude::*;
@@ -66,8 +67,13 @@ pub async fn handle_get_agent_activity(
         .read_async(move |txn| -> CascadeResult<AgentActivityResponse> {
             let txn = CascadeTxnWrapper::from(txn);
 
-            let warrants =
-                txn.get_warrants_for_basis(&AnyLinkableHash::from(agent.clone()), true)?;
+            // Only the warrants which have been found valid are given out, rather than
+            // every warrant which the activity query comes across.
+            let warrants = if options.include_warrants {
+                txn.get_warrants_for_basis(&AnyLinkableHash::from(agent.clone()), true)?
+            } else {
+                Vec::new()
+            };
 
             let mut activity_response = if options.include_full_records {
                 // If the caller wanted records, prioritise giving those back.
@@ -77,11 +83,7 @@ pub async fn handle_get_agent_activity(
                 GetAgentActivityHashesQuery::new(agent, query, options).run(txn)?
             };
 
-            if !warrants.is_empty() {
-                // TODO why did we retrieve warrants in the activity query if we're going to overwrite them here?
-                activity_response.warrants =
-                    warrants.into_iter().map(|w| w.into_warrant()).collect();
-            }
+            activity_response.warrants = warrants.into_iter().map(|w| w.into_warrant()).collect();
 
             Ok(activity_response)
         })
@@ -145,3 +147,13 @@ pub async fn handle_get_links_query(
         .read_async(move |txn| get_links_query.run(CascadeTxnWrapper::from(txn)))
         .await?)
 }
+
+/// Handler for counting links, which only reads the hashes and times of the links
+#[cfg_attr(feature = "instrument", tracing::instrument(skip(db)))]
+pub async fn handle_get_links_timestamps(
+    db: DbRead<DbKindDht>,
+    query: WireLinkQuery,
+) -> CascadeResult<Vec<(ActionHash, Timestamp)>> {
+    let query = LinkTimestampsQuery::new(query);
+    Ok(db.read_async(move |txn| query.run(txn)).await?)
+}
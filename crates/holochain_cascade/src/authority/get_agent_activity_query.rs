@@ -147,6 +147,10 @@ where
 
     let valid_activity = if options.include_valid_activity {
         let valid = filter.filter_actions(valid);
+        let valid = match &options.page {
+            Some(page) => page.apply(valid),
+            None => valid,
+        };
         valid.to_chain_items()
     } else {
         ChainItems::NotRequested
@@ -154,6 +158,10 @@ where
 
     let rejected_activity = if options.include_rejected_activity {
         let rejected = filter.filter_actions(rejected);
+        let rejected = match &options.page {
+            Some(page) => page.apply(rejected),
+            None => rejected,
+        };
         rejected.to_chain_items()
     } else {
         ChainItems::NotRequested
@@ -295,4 +295,19 @@ async fn get_agent_activity() {
     .unwrap();
 
     pretty_assertions::assert_eq!(result, expected);
+
+    // Warrants are left out when they aren't asked for.
+    let options = actor::GetActivityOptions {
+        include_warrants: false,
+        ..options
+    };
+    let result = handle_get_agent_activity(
+        db.to_db().into(),
+        td.agent.clone(),
+        QueryFilter::new(),
+        (&options).into(),
+    )
+    .await
+    .unwrap();
+    assert!(result.warrants.is_empty());
 }
@@ -0,0 +1,89 @@
+//! Reading blobs which were split into [`Chunk`] entries, from the [`ChunkManifest`]
+//! entry which lists them.
+
+use super::*;
+use crate::error::CascadeError;
+use futures::stream::StreamExt;
+use futures::stream::TryStreamExt;
+use std::ops::Range;
+
+/// How many chunks are fetched at once. Each fetch can make several connections.
+const PARALLEL_CHUNK_FETCHES: usize = 8;
+
+impl CascadeImpl {
+    /// Read the bytes of `range` of the blob whose manifest is at `manifest_hash`, or
+    /// the whole blob if there's no range. `None` if the manifest can't be found.
+    ///
+    /// Only the chunks which hold the range are fetched, several at once, and each
+    /// of them is rejected unless it has the hash and the size which the manifest
+    /// lists for it, as the chunks aren't validated against the manifest when
+    /// they're retrieved.
+    pub async fn get_chunked(
+        &self,
+        manifest_hash: AnyDhtHash,
+        range: Option<Range<u64>>,
+        options: GetOptions,
+    ) -> CascadeResult<Option<Vec<u8>>> {
+        let Some(record) = self.dht_get(manifest_hash.clone(), options.clone()).await? else {
+            return Ok(None);
+        };
+        let manifest = match record.entry().as_option() {
+            Some(Entry::App(bytes)) => ChunkManifest::try_from(bytes.clone().into_sb())
+                .map_err(|_| CascadeError::InvalidChunkManifest(manifest_hash.clone()))?,
+            _ => return Err(CascadeError::InvalidChunkManifest(manifest_hash)),
+        };
+        if !manifest.is_consistent() {
+            return Err(CascadeError::InvalidChunkManifest(manifest_hash));
+        }
+
+        let range = range.unwrap_or(0..manifest.size);
+        let end = range.end.min(manifest.size);
+        let start = range.start.min(end);
+        let indexes = manifest.chunks_for(&(start..end));
+        let offset = (start - indexes.start as u64 * manifest.chunk_size as u64) as usize;
+        let network_options: NetworkGetOptions = options.into();
+        let manifest = &manifest;
+        let chunks: Vec<Vec<u8>> = futures::stream::iter(indexes.map(|index| {
+            let network_options = network_options.clone();
+            async move {
+                let hash = manifest.chunks[index].clone();
+                let (entry, _) = self
+                    .retrieve_entry(hash.clone(), network_options)
+                    .await?
+                    .ok_or_else(|| CascadeError::ChunkMissing(hash.clone()))?;
+                verify_chunk(manifest, index, &hash, entry)
+            }
+        }))
+        // The chunks come back in order, so each is verified as it arrives.
+        .buffered(PARALLEL_CHUNK_FETCHES)
+        .try_collect()
+        .await?;
+
+        let mut bytes = chunks.concat();
+        bytes.drain(..offset.min(bytes.len()));
+        bytes.truncate((end - start) as usize);
+        Ok(Some(bytes))
+    }
+}
+
+/// The bytes of the chunk at `index` of the manifest, if the entry which was retrieved
+/// for it is that chunk.
+fn verify_chunk(
+    manifest: &ChunkManifest,
+    index: usize,
+    hash: &EntryHash,
+    entry: EntryHashed,
+) -> CascadeResult<Vec<u8>> {
+    let invalid = || CascadeError::InvalidChunk(hash.clone());
+    if entry.as_hash() != hash {
+        return Err(invalid());
+    }
+    let Entry::App(bytes) = entry.into_content() else {
+        return Err(invalid());
+    };
+    let Chunk(bytes) = Chunk::try_from(bytes.into_sb()).map_err(|_| invalid())?;
+    if bytes.len() as u64 != manifest.chunk_len(index) {
+        return Err(invalid());
+    }
+    Ok(bytes)
+}
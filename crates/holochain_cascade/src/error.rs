@@ -1,6 +1,6 @@
 #![allow(missing_docs)]
 
-use holo_hash::{ActionHash, AnyDhtHash};
+use holo_hash::{ActionHash, AnyDhtHash, EntryHash};
 use holochain_p2p::HolochainP2pError;
 use holochain_serialized_bytes::SerializedBytesError;
 use holochain_sqlite::error::DatabaseError;
@@ -32,6 +32,20 @@ pub enum CascadeError {
     #[error("Got an invalid response from an authority for the request hash: {0:?}")]
     InvalidResponse(AnyDhtHash),
 
+    #[error("Only {responses} of the {required} authorities required for a quorum responded")]
+    QuorumNotReached { required: u8, responses: usize },
+
+    #[error("{0:?} isn't a manifest of chunks which add up to its size")]
+    InvalidChunkManifest(AnyDhtHash),
+
+    #[error("The chunk {0} couldn't be found")]
+    ChunkMissing(EntryHash),
+
+    #[error(
+        "The entry which was retrieved for the chunk {0} isn't the chunk which its manifest lists"
+    )]
+    InvalidChunk(EntryHash),
+
     #[error(transparent)]
     JoinError(#[from] JoinError),
 
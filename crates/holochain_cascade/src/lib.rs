@@ -49,9 +49,11 @@ use holochain_state::query::DbScratch;
 use holochain_state::query::PrivateDataQuery;
 use holochain_state::scratch::SyncScratch;
 use metrics::create_cascade_duration_metric;
+use metrics::create_query_cache_hit_metric;
+use metrics::create_query_cache_miss_metric;
 use metrics::CascadeDurationMetric;
+use opentelemetry_api::KeyValue;
 use std::collections::HashMap;
-use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::Instant;
 use tracing::*;
@@ -63,6 +65,7 @@ pub mod authority;
 pub mod error;
 
 mod agent_activity;
+mod chunks;
 mod metrics;
 
 #[cfg(feature = "test_utils")]
@@ -101,6 +104,7 @@ pub enum CascadeSource {
 pub struct CascadeImpl {
     authored: Option<DbRead<DbKindAuthored>>,
     dht: Option<DbRead<DbKindDht>>,
+    dht_db_cache: Option<DhtDbQueryCache>,
     cache: Option<DbWrite<DbKindCache>>,
     scratch: Option<SyncScratch>,
     network: Option<GenericNetwork>,
@@ -133,6 +137,16 @@ impl CascadeImpl {
         }
     }
 
+    /// Add the dht query cache to the cascade, which holds the chain
+    /// subsets found by [`CascadeImpl::must_get_agent_activity`] and the
+    /// results of record and link queries.
+    pub fn with_dht_db_cache(self, dht_db_cache: DhtDbQueryCache) -> Self {
+        Self {
+            dht_db_cache: Some(dht_db_cache),
+            ..self
+        }
+    }
+
     /// Add the cache to the cascade.
     pub fn with_cache(self, cache: DbWrite<DbKindCache>) -> Self {
         Self {
@@ -158,6 +172,7 @@ impl CascadeImpl {
         CascadeImpl {
             authored: self.authored,
             dht: self.dht,
+            dht_db_cache: self.dht_db_cache,
             scratch: self.scratch,
             private_data: self.private_data,
             cache: Some(cache_db),
@@ -171,6 +186,7 @@ impl CascadeImpl {
         Self {
             authored: None,
             dht: None,
+            dht_db_cache: None,
             network: None,
             cache: None,
             scratch: None,
@@ -191,6 +207,7 @@ impl CascadeImpl {
         let HostFnStores {
             authored,
             dht,
+            dht_db_cache,
             cache,
             scratch,
         } = workspace.stores();
@@ -198,6 +215,7 @@ impl CascadeImpl {
         CascadeImpl {
             authored: Some(authored),
             dht: Some(dht),
+            dht_db_cache: Some(dht_db_cache),
             cache: Some(cache),
             private_data,
             scratch,
@@ -211,12 +229,14 @@ impl CascadeImpl {
         let HostFnStores {
             authored,
             dht,
+            dht_db_cache,
             cache,
             scratch,
         } = stores;
         Self {
             authored: Some(authored),
             dht: Some(dht),
+            dht_db_cache: Some(dht_db_cache),
             cache: Some(cache),
             scratch,
             network: None,
@@ -542,14 +562,55 @@ impl CascadeImpl {
     ) -> CascadeResult<()> {
         let network = some_or_return!(self.network.as_ref());
         let results = network
-            .get(hash, options.clone())
+            .get(hash.clone(), options.clone())
             .instrument(debug_span!("fetch_record::network_get"))
             .await?;
 
         self.merge_ops_into_cache(results).await?;
+        self.invalidate_cached_queries(hash.into());
         Ok(())
     }
 
+    /// Fetch a Record from the network for a read, unless it was prefetched recently
+    /// enough to be read from the cache.
+    ///
+    /// The read is counted, so that the hashes which are read most often are prefetched.
+    /// For the DNAs of client-only cells, whatever is fetched stays fresh for a while too.
+    #[cfg_attr(feature = "instrument", tracing::instrument(skip(self, options)))]
+    async fn fetch_record_for_read(
+        &self,
+        hash: AnyDhtHash,
+        options: NetworkGetOptions,
+    ) -> CascadeResult<()> {
+        if let Some(dht_db_cache) = &self.dht_db_cache {
+            let hot_hashes = dht_db_cache.hot_hashes();
+            hot_hashes.record_read(&hash);
+            if hot_hashes.is_prefetched(&hash) {
+                return Ok(());
+            }
+            self.fetch_record(hash.clone(), options).await?;
+            hot_hashes.record_fetch(&hash);
+            return Ok(());
+        }
+        self.fetch_record(hash, options).await
+    }
+
+    /// Fetch a Record from `quorum` authorities at once, caching the results.
+    /// Fails if fewer authorities respond.
+    #[cfg_attr(feature = "instrument", tracing::instrument(skip(self)))]
+    async fn fetch_record_quorum(&self, hash: AnyDhtHash, quorum: u8) -> CascadeResult<()> {
+        let network = some_or_return!(self.network.as_ref());
+        let results = network
+            .get(hash.clone(), GetOptions::quorum(quorum).into())
+            .instrument(debug_span!("fetch_record_quorum::network_get"))
+            .await?;
+        let responses = results.len();
+
+        self.merge_ops_into_cache(results).await?;
+        self.invalidate_cached_queries(hash.into());
+        check_quorum(quorum, responses)
+    }
+
     #[cfg_attr(feature = "instrument", tracing::instrument(skip(self, options)))]
     async fn fetch_links(
         &self,
@@ -557,11 +618,17 @@ impl CascadeImpl {
         options: GetLinksOptions,
     ) -> CascadeResult<()> {
         let network = some_or_return!(self.network.as_ref());
+        let strategy = options.get_options.strategy;
         let results = network.get_links(link_key.clone(), options).await?;
+        let responses = results.len();
 
         self.merge_link_ops_into_cache(results, link_key.clone())
             .await?;
-        Ok(())
+        self.invalidate_cached_queries(link_key.base);
+        match strategy {
+            GetStrategy::Quorum(quorum) => check_quorum(quorum, responses),
+            _ => Ok(()),
+        }
     }
 
     #[cfg_attr(feature = "instrument", tracing::instrument(skip(self, options)))]
@@ -641,6 +708,82 @@ impl CascadeImpl {
         Ok(results)
     }
 
+    /// Run a query through the query cache of the DHT database, if the result
+    /// of this cascade can be cached.
+    async fn cascading_cached<Q>(
+        &self,
+        cached_query: CachedQuery,
+        query: Q,
+    ) -> CascadeResult<Q::Output>
+    where
+        Q: Query<Item = Judged<SignedActionHashed>> + Send + 'static,
+        <Q as Query>::Output: Clone + Into<CachedResult> + TryFrom<CachedResult> + Send + 'static,
+    {
+        let attributes = [KeyValue::new(
+            "query",
+            match cached_query {
+                CachedQuery::Record(_) => "record",
+                CachedQuery::Links(_) => "links",
+            },
+        )];
+        let (query_cache, key) = match self.query_cache_key(cached_query)? {
+            Some(cached) => cached,
+            None => return self.cascading(query).await,
+        };
+
+        // The generation must be read before the databases are.
+        let generation = query_cache.generation();
+        if let Some(output) = query_cache
+            .get(&key)
+            .and_then(|result| result.try_into().ok())
+        {
+            create_query_cache_hit_metric().add(1, &attributes);
+            return Ok(output);
+        }
+        create_query_cache_miss_metric().add(1, &attributes);
+
+        let output = self.cascading(query).await?;
+        query_cache.put(key, output.clone().into(), generation);
+        Ok(output)
+    }
+
+    /// The query cache and the key to cache a query under, if the results of this
+    /// cascade can be cached.
+    ///
+    /// Only the results of a cascade over all the databases of a cell, with nothing
+    /// in its scratch, are cached.
+    #[allow(clippy::result_large_err)] // TODO - investigate this lint
+    fn query_cache_key(
+        &self,
+        query: CachedQuery,
+    ) -> CascadeResult<Option<(&CascadeQueryCache, CachedQueryKey)>> {
+        let (Some(dht_db_cache), Some(authored), Some(_), Some(_)) =
+            (&self.dht_db_cache, &self.authored, &self.dht, &self.cache)
+        else {
+            return Ok(None);
+        };
+        if let Some(scratch) = &self.scratch {
+            if !scratch.apply(|scratch| scratch.is_empty())? {
+                return Ok(None);
+            }
+        }
+        Ok(Some((
+            dht_db_cache.queries(),
+            CachedQueryKey {
+                cell_id: authored.kind().0.clone(),
+                private_data: self.private_data.clone(),
+                query,
+            },
+        )))
+    }
+
+    /// Drop the cached results of queries on a basis, as ops on it were written to the cache.
+    fn invalidate_cached_queries(&self, basis: AnyLinkableHash) {
+        if let Some(dht_db_cache) = &self.dht_db_cache {
+            dht_db_cache.queries().invalidate([basis]);
+        }
+    }
+
     /// Search through the stores and return the first non-none result.
     async fn find_map<F, T>(&self, mut f: F) -> CascadeResult<Option<T>>
     where
@@ -712,6 +855,12 @@ impl CascadeImpl {
             return self.cascading(query.clone()).await;
         }
 
+        if let GetStrategy::Quorum(quorum) = options.strategy {
+            // Always ask the authorities for the freshest data.
+            self.fetch_record_quorum(entry_hash.into(), quorum).await?;
+            return self.cascading(query).await;
+        }
+
         // If we are not in the process of authoring this hash or its
         // authority we need a network call.
         let authoring = self.am_i_authoring(&entry_hash.clone().into())?;
@@ -745,6 +894,12 @@ impl CascadeImpl {
             return self.cascading(query.clone()).await;
         }
 
+        if let GetStrategy::Quorum(quorum) = options.strategy {
+            // Always ask the authorities for the freshest data.
+            self.fetch_record_quorum(action_hash.into(), quorum).await?;
+            return self.cascading(query).await;
+        }
+
         // If we are not in the process of authoring this hash or its
         // authority we need a network call.
         let authoring = self.am_i_authoring(&action_hash.clone().into())?;
@@ -769,6 +924,7 @@ impl CascadeImpl {
         options: GetOptions,
     ) -> CascadeResult<Option<Record>> {
         let query: GetLiveRecordQuery = self.construct_query_with_data_access(action_hash.clone());
+        let cached_query = CachedQuery::Record(action_hash.clone().into());
 
         // DESIGN: we can short circuit if we have any local deletes on an action.
         // Is this bad because we will not go back to the network until our
@@ -776,7 +932,13 @@ impl CascadeImpl {
 
         if let GetStrategy::Local = options.strategy {
             // Only return what is in the database.
-            return self.cascading(query.clone()).await;
+            return self.cascading_cached(cached_query, query).await;
+        }
+
+        if let GetStrategy::Quorum(quorum) = options.strategy {
+            // Always ask the authorities for the freshest data.
+            self.fetch_record_quorum(action_hash.into(), quorum).await?;
+            return self.cascading(query).await;
         }
 
         // If we are not in the process of authoring this hash or its
@@ -784,12 +946,12 @@ impl CascadeImpl {
         let authoring = self.am_i_authoring(&action_hash.clone().into())?;
         let authority = self.am_i_an_authority(action_hash.clone().into()).await?;
         if !(authoring || authority) {
-            self.fetch_record(action_hash.into(), options.into())
+            self.fetch_record_for_read(action_hash.into(), options.into())
                 .await?;
         }
 
         // Check if we have the data now after the network call.
-        self.cascading(query).await
+        self.cascading_cached(cached_query, query).await
     }
 
     #[cfg_attr(feature = "instrument", tracing::instrument(skip(self, options)))]
@@ -801,10 +963,17 @@ impl CascadeImpl {
         options: GetOptions,
     ) -> CascadeResult<Option<Record>> {
         let query: GetLiveEntryQuery = self.construct_query_with_data_access(entry_hash.clone());
+        let cached_query = CachedQuery::Record(entry_hash.clone().into());
 
         if let GetStrategy::Local = options.strategy {
             // Only return what is in the database.
-            return self.cascading(query.clone()).await;
+            return self.cascading_cached(cached_query, query).await;
+        }
+
+        if let GetStrategy::Quorum(quorum) = options.strategy {
+            // Always ask the authorities for the freshest data.
+            self.fetch_record_quorum(entry_hash.into(), quorum).await?;
+            return self.cascading(query).await;
         }
 
         // If we are not in the process of authoring this hash or its
@@ -812,11 +981,12 @@ impl CascadeImpl {
         let authoring = self.am_i_authoring(&entry_hash.clone().into())?;
         let authority = self.am_i_an_authority(entry_hash.clone().into()).await?;
         if !(authoring || authority) {
-            self.fetch_record(entry_hash.into(), options.into()).await?;
+            self.fetch_record_for_read(entry_hash.into(), options.into())
+                .await?;
         }
 
         // Check if we have the data now after the network call.
-        self.cascading(query).await
+        self.cascading_cached(cached_query, query).await
     }
 
     /// Perform a concurrent `get` on multiple hashes simultaneously, returning
@@ -886,14 +1056,19 @@ impl CascadeImpl {
         options: GetLinksOptions,
     ) -> CascadeResult<Vec<Link>> {
         // only fetch links from network if i am not an authority and
-        // GetStrategy is Latest
-        if let GetStrategy::Network = options.get_options.strategy {
-            let authority = self.am_i_an_authority(key.base.clone()).await?;
-            if !authority {
-                self.fetch_links(key.clone(), options).await?;
+        // GetStrategy is Latest, or always if GetStrategy is Quorum
+        match options.get_options.strategy {
+            GetStrategy::Network => {
+                let authority = self.am_i_an_authority(key.base.clone()).await?;
+                if !authority {
+                    self.fetch_links(key.clone(), options).await?;
+                }
             }
+            GetStrategy::Quorum(_) => self.fetch_links(key.clone(), options).await?,
+            GetStrategy::Local => {}
         }
 
+        let cached_query = CachedQuery::Links(key.clone());
         let query = GetLinksQuery::new(
             key.base,
             key.type_query,
@@ -905,7 +1080,7 @@ impl CascadeImpl {
             },
         );
 
-        self.cascading(query).await
+        self.cascading_cached(cached_query, query).await
     }
 
     #[cfg_attr(feature = "instrument", tracing::instrument(skip(self, key, options)))]
@@ -917,12 +1092,16 @@ impl CascadeImpl {
         options: GetLinksOptions,
     ) -> CascadeResult<Vec<(SignedActionHashed, Vec<SignedActionHashed>)>> {
         // only fetch link details from network if i am not an authority and
-        // GetStrategy is Network
-        if let GetStrategy::Network = options.get_options.strategy {
-            let authority = self.am_i_an_authority(key.base.clone()).await?;
-            if !authority {
-                self.fetch_links(key.clone(), options).await?;
+        // GetStrategy is Network, or always if GetStrategy is Quorum
+        match options.get_options.strategy {
+            GetStrategy::Network => {
+                let authority = self.am_i_an_authority(key.base.clone()).await?;
+                if !authority {
+                    self.fetch_links(key.clone(), options).await?;
+                }
             }
+            GetStrategy::Quorum(_) => self.fetch_links(key.clone(), options).await?,
+            GetStrategy::Local => {}
         }
         let query = GetLinkDetailsQuery::new(key.base, key.type_query, key.tag);
         self.cascading(query).await
@@ -931,14 +1110,35 @@ impl CascadeImpl {
     /// Count the number of links matching the `query`.
     #[cfg_attr(feature = "instrument", tracing::instrument(skip(self, query)))]
     pub async fn dht_count_links(&self, query: WireLinkQuery) -> CascadeResult<usize> {
-        let mut links = HashSet::<ActionHash>::new();
+        Ok(self.dht_link_timestamps(query).await?.len())
+    }
+
+    /// Get the creation times of the links matching the `query`, oldest first.
+    #[cfg_attr(feature = "instrument", tracing::instrument(skip(self, query)))]
+    pub async fn dht_links_summary(&self, query: WireLinkQuery) -> CascadeResult<LinksSummary> {
+        let mut timestamps = self
+            .dht_link_timestamps(query)
+            .await?
+            .into_values()
+            .collect::<Vec<_>>();
+        timestamps.sort();
+        Ok(LinksSummary { timestamps })
+    }
+
+    /// The creation times of the links matching the `query`, by the hash of
+    /// their create link action.
+    async fn dht_link_timestamps(
+        &self,
+        query: WireLinkQuery,
+    ) -> CascadeResult<HashMap<ActionHash, Timestamp>> {
+        let mut links = HashMap::new();
         if !self.am_i_an_authority(query.base.clone()).await? {
             if let Some(network) = &self.network {
                 links.extend(
                     network
                         .count_links(query.clone())
                         .await?
-                        .create_link_actions(),
+                        .into_create_link_timestamps(),
                 );
             }
         }
@@ -954,10 +1154,10 @@ impl CascadeImpl {
             self.cascading(get_links_query)
                 .await?
                 .into_iter()
-                .map(|l| l.create_link_hash),
+                .map(|l| (l.create_link_hash, l.timestamp)),
         );
 
-        Ok(links.len())
+        Ok(links)
     }
 
     /// Request a hash bounded chain query.
@@ -966,6 +1166,18 @@ impl CascadeImpl {
         author: AgentPubKey,
         filter: ChainFilter,
     ) -> CascadeResult<MustGetAgentActivityResponse> {
+        // Chain subsets which include unpublished actions from the scratch aren't cached,
+        // as those actions may never be committed.
+        let dht_db_cache = self
+            .dht_db_cache
+            .as_ref()
+            .filter(|_| self.scratch.is_none());
+        if let Some(response) =
+            dht_db_cache.and_then(|cache| cache.get_chain_subset(&author, &filter))
+        {
+            return Ok(response);
+        }
+
         // Get the available databases.
         let mut txn_guards = self.get_txn_guards().await?;
         let scratch = self.scratch.clone();
@@ -1007,6 +1219,9 @@ impl CascadeImpl {
 
         // Short circuit if we have a result.
         if matches!(result, MustGetAgentActivityResponse::Activity { .. }) {
+            if let Some(cache) = dht_db_cache {
+                cache.put_chain_subset(author, filter, &result);
+            }
             return Ok(result);
         }
 
@@ -1017,9 +1232,13 @@ impl CascadeImpl {
             // this point then the chain is incomplete for this request.
             Ok(MustGetAgentActivityResponse::IncompleteChain)
         } else {
-            Ok(self
-                .fetch_must_get_agent_activity(author.clone(), filter)
-                .await?)
+            let result = self
+                .fetch_must_get_agent_activity(author.clone(), filter.clone())
+                .await?;
+            if let Some(cache) = dht_db_cache {
+                cache.put_chain_subset(author, filter, &result);
+            }
+            Ok(result)
         }
     }
 
@@ -1039,6 +1258,7 @@ impl CascadeImpl {
     /// - [include_full_records](GetActivityOptions::include_full_records) will fetch the full records for each action matching the query.
     ///   This is only effective if [include_valid_activity](GetActivityOptions::include_valid_activity) or [include_rejected_activity](GetActivityOptions::include_rejected_activity) is true.
     ///   Even when this is set, entries will only be fetched if [include_entries](ChainQueryFilter::include_entries) is also true.
+    /// - [page](GetActivityOptions::page) will only return a window of the matching activity.
     #[cfg_attr(
         feature = "instrument",
         tracing::instrument(skip(self, agent, query, options))
@@ -1081,8 +1301,15 @@ impl CascadeImpl {
             let results = self
                 .fetch_agent_activity(agent.clone(), query.clone(), options.clone())
                 .await?;
-            let merged_response: AgentActivityResponse =
+            let mut merged_response: AgentActivityResponse =
                 agent_activity::merge_activities(agent.clone(), &options, results)?;
+            if options.include_warrants {
+                for warrant in self.held_warrants(&agent).await? {
+                    if !merged_response.warrants.contains(&warrant) {
+                        merged_response.warrants.push(warrant);
+                    }
+                }
+            }
             merged_response
         };
 
@@ -1105,6 +1332,13 @@ impl CascadeImpl {
             warrants,
         } = merged_response;
 
+        // Every authority applies the page to its own view of the chain, so the
+        // merged activity can hold more than one page of actions.
+        if let Some(page) = &options.page {
+            valid_activity = agent_activity::apply_page(valid_activity, page);
+            rejected_activity = agent_activity::apply_page(rejected_activity, page);
+        }
+
         // If records were requested then the activity authority might not have had all the entries.
         // That becomes more likely for new records as the number of agents on a network increases.
         // So we need to fill in the missing entries.
@@ -1130,6 +1364,22 @@ impl CascadeImpl {
         Ok(r)
     }
 
+    /// The valid warrants against an agent which the DHT database holds, which it may
+    /// do without being an authority for the agent's activity, e.g. for the warrants
+    /// which this conductor issued.
+    async fn held_warrants(&self, agent: &AgentPubKey) -> CascadeResult<Vec<Warrant>> {
+        let dht = some_or_return!(self.dht.clone(), Vec::new());
+        let basis = AnyLinkableHash::from(agent.clone());
+        dht.read_async(move |txn| -> CascadeResult<Vec<Warrant>> {
+            Ok(CascadeTxnWrapper::from(txn)
+                .get_warrants_for_basis(&basis, true)?
+                .into_iter()
+                .map(|warrant| warrant.into_warrant())
+                .collect())
+        })
+        .await
+    }
+
     /// Looks through a [ChainItems] object and fills in any missing entry data.
     ///
     /// For any [RecordEntry::NotStored] entries, this function will attempt to fetch the entry data
@@ -1221,6 +1471,18 @@ impl CascadeImpl {
     }
 }
 
+/// Check that enough authorities responded to a request for a quorum.
+#[allow(clippy::result_large_err)] // TODO - investigate this lint
+fn check_quorum(required: u8, responses: usize) -> CascadeResult<()> {
+    if responses < required as usize {
+        return Err(error::CascadeError::QuorumNotReached {
+            required,
+            responses,
+        });
+    }
+    Ok(())
+}
+
 #[cfg(feature = "test_utils")]
 impl MockCascade {
     /// Construct a mock which acts as if the given records were part of local storage
@@ -19,3 +19,37 @@ pub fn create_cascade_duration_metric() -> &'static CascadeDurationMetric {
         .init()
     })
 }
+
+pub type CascadeQueryCacheMetric = Counter<u64>;
+
+static QUERY_CACHE_HIT_METRIC: OnceLock<CascadeQueryCacheMetric> = OnceLock::new();
+
+static QUERY_CACHE_MISS_METRIC: OnceLock<CascadeQueryCacheMetric> = OnceLock::new();
+
+pub fn create_query_cache_hit_metric() -> &'static CascadeQueryCacheMetric {
+    QUERY_CACHE_HIT_METRIC.get_or_init(|| {
+        meter_with_version(
+            "hc.cascade",
+            None::<&'static str>,
+            None::<&'static str>,
+            Some(vec![]),
+        )
+        .u64_counter("hc.cascade.query_cache.hits")
+        .with_description("The number of cascade queries which were answered by the query cache")
+        .init()
+    })
+}
+
+pub fn create_query_cache_miss_metric() -> &'static CascadeQueryCacheMetric {
+    QUERY_CACHE_MISS_METRIC.get_or_init(|| {
+        meter_with_version(
+            "hc.cascade",
+            None::<&'static str>,
+            None::<&'static str>,
+            Some(vec![]),
+        )
+        .u64_counter("hc.cascade.query_cache.misses")
+        .with_description("The number of cascade queries which had to be run on the databases")
+        .init()
+    })
+}
@@ -22,7 +22,7 @@ use holochain_types::test_utils::chain::TestChainItem;
 use kitsune_p2p::agent_store::AgentInfoSigned;
 use kitsune_p2p::dependencies::kitsune_p2p_fetch::OpHashSized;
 use kitsune_p2p::dht::Arq;
-use std::collections::HashSet;
+use std::collections::HashMap;
 use std::sync::Arc;
 use QueryFilter;
 use Signature;
@@ -121,20 +121,16 @@ impl HolochainP2pDnaT for PassThroughNetwork {
         &self,
         query: WireLinkQuery,
     ) -> actor::HolochainP2pResult<CountLinksResponse> {
-        let mut out = HashSet::new();
+        let mut out = HashMap::new();
 
         for db in &self.envs {
-            let r = authority::handle_get_links_query(db.clone(), query.clone())
+            let r = authority::handle_get_links_timestamps(db.clone(), query.clone())
                 .await
                 .map_err(|e| HolochainP2pError::Other(e.into()))?;
             out.extend(r);
         }
 
-        Ok(CountLinksResponse::new(
-            out.into_iter()
-                .map(|l| l.create_link_hash)
-                .collect::<Vec<_>>(),
-        ))
+        Ok(CountLinksResponse::new(out.into_iter().collect()))
     }
 
     async fn get_agent_activity(
@@ -184,6 +180,21 @@ impl HolochainP2pDnaT for PassThroughNetwork {
         Ok(self.authority)
     }
 
+    async fn get_agent_presence(
+        &self,
+        _agent: AgentPubKey,
+    ) -> actor::HolochainP2pResult<Option<AgentPresence>> {
+        todo!()
+    }
+
+    async fn handoff_ops(
+        &self,
+        _basis: holo_hash::OpBasis,
+        _ops: Vec<DhtOp>,
+    ) -> actor::HolochainP2pResult<Vec<(AgentPubKey, Vec<holo_hash::DhtOpHash>)>> {
+        todo!()
+    }
+
     fn dna_hash(&self) -> holo_hash::DnaHash {
         todo!()
     }
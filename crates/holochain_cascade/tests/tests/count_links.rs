@@ -85,6 +85,44 @@ async fn count_links_authority() {
     assert_eq!(count, 0);
 }
 
+// Checks that the creation times of links can be fetched from an authority without the links
+#[tokio::test(flavor = "multi_thread")]
+async fn links_summary_not_authority() {
+    holochain_trace::test_run();
+
+    // Environments
+    let cache = test_cache_db();
+    let authority = test_dht_db();
+
+    // Data
+    let td = EntryTestData::create();
+    fill_db(&authority.to_db(), td.store_entry_op.clone()).await;
+    fill_db(&authority.to_db(), td.create_link_op.clone()).await;
+
+    // Network
+    let network = PassThroughNetwork::authority_for_nothing(vec![authority.to_db().clone().into()]);
+
+    // Cascade
+    let cascade = CascadeImpl::empty().with_network(network, cache.to_db());
+
+    let summary = cascade
+        .dht_links_summary(td.link_query.clone())
+        .await
+        .unwrap();
+
+    assert_eq!(td.links.len(), summary.count());
+    assert_eq!(td.links.iter().map(|l| l.timestamp).max(), summary.latest());
+
+    fill_db(&authority.to_db(), td.delete_link_op.clone()).await;
+
+    let summary = cascade
+        .dht_links_summary(td.link_query.clone())
+        .await
+        .unwrap();
+
+    assert_eq!(LinksSummary::default(), summary);
+}
+
 // Checks that locally authored data that hasn't yet been published to the network is included in the link count
 // seen by the agent doing the publish
 #[tokio::test(flavor = "multi_thread")]
@@ -113,8 +151,12 @@ async fn count_links_authoring() {
     // Network
     let mut mock = MockHolochainP2pDnaT::new();
     mock.expect_authority_for_hash().returning(|_| Ok(false));
-    mock.expect_count_links()
-        .returning(|_| Ok(CountLinksResponse::new(vec![action_hash(&[1, 2, 3])])));
+    mock.expect_count_links().returning(|_| {
+        Ok(CountLinksResponse::new(vec![(
+            action_hash(&[1, 2, 3]),
+            Timestamp::now(),
+        )]))
+    });
     let mock = Arc::new(mock);
 
     // Cascade
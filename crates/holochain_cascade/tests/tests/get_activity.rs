@@ -77,6 +77,56 @@ async fn get_activity() {
     assert_agent_activity_responses_eq!(expected, r);
 }
 
+/// Check that an agent's activity can be fetched one page at a time
+#[tokio::test(flavor = "multi_thread")]
+async fn get_activity_page() {
+    holochain_trace::test_run();
+
+    let test_data = ActivityTestData::valid_chain_scenario(false);
+
+    let scenario = GetActivityTestScenario::new(test_data.clone())
+        .include_agent_activity_ops_in_dht_db()
+        .await
+        .include_agent_activity_noise_ops_in_dht_db()
+        .await;
+
+    let all_hashes = match &test_data.valid_hashes {
+        ChainItems::Hashes(hashes) => hashes.clone(),
+        _ => unreachable!(),
+    };
+
+    let mut paged_hashes = Vec::new();
+    let mut after_seq = None;
+    loop {
+        let mut page = AgentActivityPage::new(20);
+        page.after_seq = after_seq;
+        let options = GetActivityOptions {
+            include_valid_activity: true,
+            include_rejected_activity: false,
+            page: Some(page),
+            ..Default::default()
+        };
+
+        let r = scenario.query_authority(options).await.unwrap();
+
+        // The highest observed action is for the whole chain, not just the page.
+        assert_eq!(Some(test_data.highest_observed.clone()), r.highest_observed);
+
+        let hashes = match r.valid_activity {
+            ChainItems::Hashes(hashes) => hashes,
+            other => panic!("Expected hashes, got {:?}", other),
+        };
+        assert!(hashes.len() <= 20);
+        let Some(last) = hashes.last() else {
+            break;
+        };
+        after_seq = Some(last.0);
+        paged_hashes.extend(hashes);
+    }
+
+    assert_eq!(all_hashes, paged_hashes);
+}
+
 /// Check that the different options for getting chain items will return the same records
 #[tokio::test(flavor = "multi_thread")]
 async fn get_activity_chain_items_parity() {
@@ -232,6 +232,48 @@ async fn entry_not_authority_or_authoring() {
     assert_can_get(&td_entry, &td_record, &cascade, GetOptions::network()).await;
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn entry_quorum() {
+    holochain_trace::test_run();
+
+    // Environments
+    let cache = test_cache_db();
+    let authority = test_dht_db();
+    let other_authority = test_dht_db();
+
+    // Data
+    // - Only one of the authorities holds the data.
+    let td_entry = EntryTestData::create();
+    let td_record = RecordTestData::create();
+    fill_db(&authority.to_db(), td_entry.store_entry_op.clone()).await;
+    fill_db(&authority.to_db(), td_record.any_store_record_op.clone()).await;
+
+    // Network
+    // - The authorities are asked even though this node is an authority too.
+    let network = PassThroughNetwork::authority_for_all(vec![
+        authority.to_db().clone().into(),
+        other_authority.to_db().clone().into(),
+    ]);
+
+    // Cascade
+    let cascade = CascadeImpl::empty().with_network(network, cache.to_db());
+
+    assert_can_get(&td_entry, &td_record, &cascade, GetOptions::quorum(2)).await;
+
+    // - Fewer authorities respond than are required.
+    let err = cascade
+        .dht_get(td_entry.hash.clone().into(), GetOptions::quorum(3))
+        .await
+        .unwrap_err();
+    assert!(matches!(
+        err,
+        holochain_cascade::error::CascadeError::QuorumNotReached {
+            required: 3,
+            responses: 2
+        }
+    ));
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn entry_authoring() {
     holochain_trace::test_run();
@@ -494,3 +536,43 @@ async fn test_pending_data_isnt_returned() {
 
     assert_can_retrieve(&td_entry, &cascade, GetOptions::network()).await;
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn records_are_cached_until_their_basis_is_invalidated() {
+    holochain_trace::test_run();
+
+    // Environments
+    let cache = test_cache_db();
+    let dht = test_dht_db();
+    let vault = test_authored_db();
+    let dht_db_cache = DhtDbQueryCache::new(dht.to_db().into());
+
+    // Data
+    let td_entry = EntryTestData::create();
+    let td_record = RecordTestData::create();
+
+    // Cascade
+    let cascade = CascadeImpl::empty()
+        .with_authored(vault.to_db().into())
+        .with_dht(dht.to_db().into())
+        .with_cache(cache.to_db())
+        .with_dht_db_cache(dht_db_cache.clone());
+
+    assert_is_none(&td_entry, &td_record, &cascade, GetOptions::local()).await;
+    assert!(!dht_db_cache.queries().is_empty());
+
+    // The cached results are returned until the ops are integrated.
+    fill_db(&dht.to_db(), td_entry.store_entry_op.clone()).await;
+    fill_db(&dht.to_db(), td_record.any_store_record_op.clone()).await;
+    assert!(cascade
+        .dht_get(td_entry.hash.clone().into(), GetOptions::local())
+        .await
+        .unwrap()
+        .is_none());
+
+    dht_db_cache.queries().invalidate([
+        td_entry.hash.clone().into(),
+        td_record.any_action_hash.clone().into(),
+    ]);
+    assert_can_get(&td_entry, &td_record, &cascade, GetOptions::local()).await;
+}
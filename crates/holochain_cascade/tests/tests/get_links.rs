@@ -1,5 +1,6 @@
 use holochain_cascade::test_utils::*;
 use holochain_cascade::CascadeImpl;
+use holochain_p2p::actor::GetLinksOptions;
 use holochain_p2p::MockHolochainP2pDnaT;
 use holochain_state::prelude::*;
 use std::sync::Arc;
@@ -60,6 +61,69 @@ async fn links_not_authority() {
     );
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn links_quorum() {
+    holochain_trace::test_run();
+
+    // Environments
+    let cache = test_cache_db();
+    let authority = test_dht_db();
+    let other_authority = test_dht_db();
+
+    // Data
+    // - Only one of the authorities has seen the link being deleted.
+    let td = EntryTestData::create();
+    fill_db(&authority.to_db(), td.store_entry_op.clone()).await;
+    fill_db(&authority.to_db(), td.create_link_op.clone()).await;
+    fill_db(&other_authority.to_db(), td.store_entry_op.clone()).await;
+    fill_db(&other_authority.to_db(), td.create_link_op.clone()).await;
+    fill_db(&other_authority.to_db(), td.delete_link_op.clone()).await;
+
+    // Network
+    let network = PassThroughNetwork::authority_for_all(vec![
+        authority.to_db().clone().into(),
+        other_authority.to_db().clone().into(),
+    ]);
+
+    // Cascade
+    let cascade = CascadeImpl::empty().with_network(network, cache.to_db());
+
+    let options = GetLinksOptions {
+        get_options: GetOptions::quorum(2),
+        ..Default::default()
+    };
+
+    // - The responses of both authorities are merged.
+    let r = cascade
+        .dht_get_links(td.link_key_tag.clone(), options.clone())
+        .await
+        .unwrap();
+
+    assert!(r.is_empty());
+
+    let r = cascade
+        .get_link_details(td.link_key_tag.clone(), options)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        r,
+        vec![(
+            td.create_link_action.clone(),
+            vec![td.delete_link_action.clone()]
+        ),]
+    );
+
+    let options = GetLinksOptions {
+        get_options: GetOptions::quorum(3),
+        ..Default::default()
+    };
+    assert!(cascade
+        .dht_get_links(td.link_key_tag.clone(), options)
+        .await
+        .is_err());
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn links_authority() {
     holochain_trace::test_run();
@@ -5,8 +5,18 @@ use holochain_types::prelude::*;
 use holochain_types::websocket::AllowedOrigins;
 use holochain_zome_types::cell::CellId;
 use kitsune_p2p_types::agent_info::AgentInfoSigned;
+use kitsune_p2p_types::dht::arq::ArqPin;
+use kitsune_p2p_types::fetch_pool::FetchPoolInfo;
 
-use crate::{AppInfo, FullStateDump, RevokeAgentKeyPayload, StorageInfo};
+pub use holochain_state_types::AuthorValidationOutcomes;
+pub use kitsune_p2p_types::metrics::PeerConnectionStats;
+
+use crate::conductor::ConfigReloadReport;
+use crate::signal_subscription::SignalFilterSet;
+use crate::{
+    AppInfo, AppInfoStatus, AppRequest, FullStateDump, MemproofProgress, RevokeAgentKeyPayload,
+    StorageInfo, ZomeCallNonceWindow,
+};
 
 /// Represents the available conductor functions to call over an admin interface.
 ///
@@ -126,10 +136,13 @@ pub enum AdminRequest {
 
     /// List the IDs of all live cells currently running in the conductor.
     ///
+    /// Results are sorted by DNA hash and then agent key. The filter can restrict
+    /// the cells to those of a DNA, and select a page of the results.
+    ///
     /// # Returns
     ///
     /// [`AdminResponse::CellIdsListed`]
-    ListCellIds,
+    ListCellIds(Option<CellIdsFilter>),
 
     /// List the apps and their information that are installed in the conductor.
     ///
@@ -143,8 +156,46 @@ pub enum AdminRequest {
     ListApps {
         /// An optional status to filter the list of apps by
         status_filter: Option<AppStatusFilter>,
+        /// Only list the apps which have a cell of this DNA
+        #[serde(default)]
+        dna_hash: Option<DnaHash>,
+        /// Only list a page of the sorted apps
+        #[serde(default)]
+        page: Option<Page>,
     },
 
+    /// List a summary of each app installed in the conductor, with the number of cells
+    /// and the storage it uses, without the full [`AppInfo`].
+    ///
+    /// The apps are filtered and sorted in the same way as for [`AdminRequest::ListApps`].
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::AppSummariesListed`]
+    ListAppSummaries {
+        /// An optional status to filter the list of apps by
+        status_filter: Option<AppStatusFilter>,
+        /// Only list the apps which have a cell of this DNA
+        #[serde(default)]
+        dna_hash: Option<DnaHash>,
+        /// Only list a page of the sorted apps
+        #[serde(default)]
+        page: Option<Page>,
+    },
+
+    /// List the agent keys known to the conductor, along with the apps and
+    /// cells which use each key and how the key was derived.
+    ///
+    /// Keys which were derived from the device seed are found in lair, so
+    /// they are listed even if no installed app uses them, e.g. after the
+    /// conductor database has been lost. Such a key can be reused by passing
+    /// it as the `agent_key` when installing the app again.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::AgentKeysListed`]
+    ListAgentKeys,
+
     /// Changes the specified app from a disabled to an enabled state in the conductor.
     ///
     /// It is likely to want to call this after calling [`AdminRequest::InstallApp`], since a freshly
@@ -282,6 +333,385 @@ pub enum AdminRequest {
     /// Dump raw json network statistics from the backend networking lib.
     DumpNetworkStats,
 
+    /// Check that the conductor is able to do its work: that the keystore
+    /// responds, that the database of each running cell can be written to,
+    /// that the network transport responds and that the bootstrap service
+    /// can be reached.
+    ///
+    /// The same report is served over HTTP if
+    /// [`ConductorConfig::health_check`](crate::conductor::ConductorConfig::health_check) is set.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::HealthChecked`]
+    HealthCheck,
+
+    /// Get the report of the integrity checks which the conductor ran on its cells
+    /// when it started: that the chain head of each cell is the last action which was
+    /// committed, that its databases have the expected schema versions and that its
+    /// DHT query cache can be built.
+    ///
+    /// The checks are only run if
+    /// [`ConductorTuningParams::verify_integrity_on_startup`](crate::conductor::ConductorTuningParams::verify_integrity_on_startup)
+    /// is set.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::StartupReport`]
+    GetStartupReport,
+
+    /// List the most recent zome calls and workflow runs which took longer
+    /// than the thresholds set in the conductor's tuning params, newest first.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::SlowOperationsListed`]
+    ListSlowOperations,
+
+    /// Get a snapshot of the work which the conductor is doing: how many ops are queued
+    /// for each workflow, the state of gossip and fetching for each DNA, the status of
+    /// the ops which each cell authored, and the transport connection to each peer.
+    ///
+    /// The connection stats tell connectivity problems apart from gossip problems,
+    /// e.g. a peer which gossip can't reach may only be reachable through a relay.
+    ///
+    /// This is cheap enough to be polled, e.g. by `hc top`.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::ConductorDiagnostics`]
+    GetConductorDiagnostics,
+
+    /// Get the regions of the ops of a DNA which historical gossip compares with
+    /// other nodes, over the storage arcs of the local agents.
+    ///
+    /// When two nodes keep gossiping about the same regions without converging,
+    /// comparing the region sets of the two nodes shows which regions they disagree on.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::RegionSet`]
+    GetRegionSet {
+        /// The DNA whose regions to get.
+        dna_hash: DnaHash,
+    },
+
+    /// Take a census of the ops of a DNA: ask the peers whose storage arcs overlap those
+    /// of the local agents for the regions of the ops which they hold, and compare them
+    /// with the regions of the ops which this node holds.
+    ///
+    /// Only the op counts and fingerprints of the regions are exchanged, so this is much
+    /// cheaper than syncing to find out whether the nodes hold the same ops. Peers are
+    /// only asked when this is called.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::OpCensus`]
+    GetOpCensus {
+        /// The DNA whose ops to compare.
+        dna_hash: DnaHash,
+        /// The most peers to ask, [`OP_CENSUS_DEFAULT_PEER_COUNT`] if not given.
+        peer_count: Option<usize>,
+    },
+
+    /// Count the outcomes of validating the ops of a DNA which this conductor holds,
+    /// grouped by the agent who authored them.
+    ///
+    /// Agents which author many rejected ops are either misbehaving or running a buggy app,
+    /// which stands out here without going through the ops one by one.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::ValidationOutcomes`], with the authors of the most rejected ops first.
+    GetValidationOutcomes {
+        /// The DNA whose ops to count.
+        dna_hash: DnaHash,
+    },
+
+    /// Get how far a running cell is with getting the ops of its storage arc, which
+    /// shows whether a cell which joined an existing DHT is still syncing.
+    ///
+    /// Apps also receive the progress of their cells as a
+    /// [`SystemSignal::BackfillProgress`](holochain_types::signal::SystemSignal::BackfillProgress)
+    /// until backfill is complete.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::BackfillProgress`]
+    GetBackfillProgress {
+        /// The cell whose progress to get.
+        cell_id: CellId,
+    },
+
+    /// Stop initiating gossip rounds for a DNA, to find out whether an issue is
+    /// driven by gossip without restarting the conductor with gossip disabled.
+    ///
+    /// Rounds which are in progress still finish. The pause lasts until
+    /// [`AdminRequest::ResumeGossip`] is called or the conductor restarts.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::GossipPaused`]
+    PauseGossip {
+        /// The DNA to pause gossip for.
+        dna_hash: DnaHash,
+        /// Also refuse new rounds which remote nodes initiate, rather than only
+        /// not initiating any.
+        #[serde(default)]
+        refuse_incoming: bool,
+    },
+
+    /// Resume gossip for a DNA which was paused with [`AdminRequest::PauseGossip`].
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::GossipResumed`]
+    ResumeGossip {
+        /// The DNA to resume gossip for.
+        dna_hash: DnaHash,
+    },
+
+    /// Pin the storage arc of a cell to hold the whole DHT, nothing, or a range of
+    /// locations, rather than letting it be sized by the network conditions, e.g. for
+    /// an archival node or a client which shouldn't hold data for others.
+    ///
+    /// The pin is kept across restarts. The new arc is published in the agent info of
+    /// the cell, which peers gossip and publish to it by.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::ArqPinSet`]
+    SetArqPin {
+        /// The cell whose arc to pin.
+        cell_id: Box<CellId>,
+        /// The arc to pin the cell to, or `None` to let it be sized dynamically again.
+        pin: Option<ArqPin>,
+    },
+
+    /// Start tracing an op: from now on, each time it is published, served, received,
+    /// validated or integrated, or a validation receipt for it is sent or received,
+    /// an event is logged and kept for [`AdminRequest::GetOpTrace`].
+    ///
+    /// Tracing an op which is already traced keeps its events. Traces are kept in
+    /// memory until [`AdminRequest::StopOpTrace`] is called or the conductor restarts.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::OpTraceStarted`]
+    StartOpTrace {
+        /// The op to trace.
+        op_hash: DhtOpHash,
+    },
+
+    /// Get the events which were recorded for a traced op, oldest first.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::OpTrace`]
+    GetOpTrace {
+        /// The traced op.
+        op_hash: DhtOpHash,
+    },
+
+    /// Stop tracing an op and drop the events which were recorded for it.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::OpTraceStopped`]
+    StopOpTrace {
+        /// The traced op.
+        op_hash: DhtOpHash,
+    },
+
+    /// List the ops authored by a cell which haven't received enough validation
+    /// receipts yet, oldest first, along with how many receipts they have and
+    /// how many they need.
+    ///
+    /// These ops keep being republished. An op which stays in this list long
+    /// after it was authored may need more receipts than the network can provide,
+    /// see [`ConductorTuningParams::default_required_validation_receipts`](crate::conductor::ConductorTuningParams::default_required_validation_receipts).
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::OpsMissingReceiptsListed`]
+    ListOpsMissingReceipts {
+        /// The cell whose authored ops to list.
+        cell_id: Box<CellId>,
+    },
+
+    /// Export the ops of a DNA which this conductor integrated before a point in time
+    /// into a [`DhtOpArchive`], e.g. to keep a cold-storage backup of the data of a
+    /// community or to seed an archival node.
+    ///
+    /// Private entries aren't exported.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::DhtOpArchiveExported`]
+    ExportDhtOpArchive {
+        /// The DNA whose ops to export.
+        dna_hash: DnaHash,
+        /// Only ops which were integrated before this time are exported.
+        integrated_before: Timestamp,
+    },
+
+    /// Import the ops of a [`DhtOpArchive`] into the DHT database of its DNA.
+    ///
+    /// The ops are validated again, in the same way as ops published by other agents,
+    /// and integrated once they are valid. Ops which this conductor already holds are skipped.
+    /// A cell of the DNA must be running.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::DhtOpArchiveImported`]
+    ImportDhtOpArchive {
+        /// The archive to import.
+        archive: Box<DhtOpArchive>,
+    },
+
+    /// List the epochs which the DHT database of a DNA holds ops for or has archived.
+    ///
+    /// The ops of a DNA are partitioned into epochs by the time they were authored, see
+    /// [`dht_op_epoch`](holochain_types::prelude::dht_op_epoch).
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::DhtEpochsListed`]
+    ListDhtEpochs {
+        /// The DNA whose epochs to list.
+        dna_hash: DnaHash,
+    },
+
+    /// Archive the integrated ops of every epoch of a DNA which ended before a point in
+    /// time into a [`DhtOpArchive`], and delete them from the DHT database.
+    ///
+    /// The ops of archived epochs aren't taken in again from peers, unless the archive
+    /// is imported with [`AdminRequest::ImportDhtOpArchive`].
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::DhtEpochsArchived`]
+    ArchiveDhtEpochs {
+        /// The DNA whose epochs to archive.
+        dna_hash: DnaHash,
+        /// Only the epochs which ended before this time are archived.
+        before: Timestamp,
+    },
+
+    /// Run the genesis self check of a DNA again on the membrane proofs of the agents
+    /// whose `AgentValidationPkg` the conductor holds, and report the agents whose
+    /// membrane proof doesn't pass it.
+    ///
+    /// This is an audit only: agents which fail aren't blocked or warranted. As each
+    /// agent only runs the self check on its own membrane proof when it joins, this also
+    /// finds agents which joined without running it.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::MembraneProofsRechecked`]
+    RecheckMembraneProofs {
+        /// The DNA whose agents to check.
+        dna_hash: DnaHash,
+    },
+
+    /// Export the data which the agent of a cell authored, i.e. its actions, app entries
+    /// and links, as a portable [`AuthoredDataExport`] in JSON, e.g. to hand a user their
+    /// data when they ask for it.
+    ///
+    /// Unlike [`AdminRequest::DumpState`], hashes are written as base64 strings and app entries
+    /// are decoded, so the export can be read without Holochain.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::AuthoredDataExported`]
+    ExportAuthoredData {
+        /// The cell whose authored data to export.
+        cell_id: Box<CellId>,
+    },
+
+    /// Export a [`PublicationProof`] for an action which the agent of a cell authored: the
+    /// signed action, its ops and the validation receipts which have been collected for them.
+    ///
+    /// A third party can check the proof offline with [`PublicationProof::verify`], given
+    /// only the DNA hash, to see which validators vouched for the ops of the action.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::PublicationProofExported`]
+    ExportPublicationProof {
+        /// The cell which authored the action.
+        cell_id: Box<CellId>,
+        /// The action to prove the publication of.
+        action_hash: ActionHash,
+    },
+
+    /// Rebuild the bundle which an app was installed from, out of its manifest and the
+    /// DNAs which the conductor holds, e.g. to install the app on another conductor.
+    ///
+    /// Each DNA is bundled with the modifiers it was installed with, and the manifest
+    /// expects the installed DNA hashes, so installing the bundle creates the same cells.
+    /// Roles which use the cells of another app are left as they are in the manifest,
+    /// and clone cells aren't included.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::AppBundleExported`]
+    ExportAppBundle {
+        /// The app whose bundle to export.
+        installed_app_id: InstalledAppId,
+    },
+
+    /// Change which logs the conductor writes, without restarting it.
+    ///
+    /// The new filter replaces the levels set on startup from `RUST_LOG`
+    /// or the conductor config, until the conductor is restarted.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::LogFilterSet`]
+    SetLogFilter {
+        /// Filter directives in the same format as `RUST_LOG`,
+        /// e.g. `warn,kitsune_p2p::gossip=debug`.
+        filter: String,
+    },
+
+    /// Read the conductor config file again and apply the settings which can be
+    /// changed without restarting the conductor.
+    ///
+    /// Log levels, conductor tuning params and the allowed origins of admin interfaces
+    /// are applied. Other settings, including the network config and its tuning params,
+    /// are only read on startup, so changes to them are reported and skipped.
+    /// The conductor also reloads its config when it receives `SIGHUP`.
+    ///
+    /// Fails if the conductor was not started from a config file.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::ConfigReloaded`]
+    ReloadConfig,
+
+    /// List the clients which are connected to the conductor's app interfaces.
+    ///
+    /// Only connections which have authenticated are listed.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::AppInterfaceConnectionsListed`]
+    ListAppInterfaceConnections,
+
+    /// Disconnect a client from an app interface.
+    ///
+    /// The connection is closed, and any requests the client has in flight are abandoned.
+    /// The client can connect again if it has a valid app authentication token.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::AppInterfaceClientDisconnected`]
+    DisconnectAppInterfaceClient {
+        /// The connection to close, as listed by [`AdminRequest::ListAppInterfaceConnections`].
+        connection_id: AppInterfaceConnectionId,
+    },
+
     /// Add a list of agents to this conductor's peer store.
     ///
     /// This is a way of shortcutting peer discovery and is useful for testing.
@@ -367,6 +797,26 @@ pub enum AdminRequest {
         records: Vec<Record>,
     },
 
+    /// Remove the actions of a cell's source chain which come after an earlier action,
+    /// to recover from bad commits made while developing an app.
+    ///
+    /// This is refused unless nothing after that action has left the conductor: the
+    /// app of the cell must be disabled with [`AdminRequest::DisableApp`] first, none
+    /// of the ops of the removed actions may have been published, have validation
+    /// receipts or be integrated into the DHT database, from which they could have
+    /// been gossiped, and genesis can't be removed. The ops of the removed actions
+    /// which are still waiting to be validated are removed too.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::SourceChainRolledBack`]
+    RollbackSourceChain {
+        /// The cell whose chain to roll back.
+        cell_id: Box<CellId>,
+        /// The action which becomes the head of the chain.
+        to: ActionHash,
+    },
+
     /// Request capability grant for making zome calls.
     ///
     /// # Returns
@@ -402,6 +852,85 @@ pub enum AdminRequest {
     /// Find installed cells which use a DNA that's forward-compatible with the given DNA hash.
     /// Namely, this finds cells with DNAs whose manifest lists the given DNA hash in its `lineage` field.
     GetCompatibleCells(DnaHash),
+
+    /// List the audit log of admin requests which changed the state of the conductor,
+    /// such as installing apps, generating keys and granting capabilities.
+    ///
+    /// Every such request is recorded once it has been handled, along with the connection
+    /// which made it and whether it failed. The log is never pruned.
+    ///
+    /// # Returns
+    ///
+    /// [`AdminResponse::AuditLogListed`]
+    ListAuditLog {
+        /// Only list entries which were recorded at or after this time.
+        #[serde(default)]
+        since: Option<Timestamp>,
+        /// The most entries to list, oldest first. Defaults to [`DEFAULT_AUDIT_LOG_LIMIT`].
+        #[serde(default)]
+        limit: Option<u32>,
+    },
+}
+
+/// How many entries [`AdminRequest::ListAuditLog`] lists when no limit is given.
+pub const DEFAULT_AUDIT_LOG_LIMIT: u32 = 1000;
+
+impl AdminRequest {
+    /// The type of the request and what it acts on, if it changes the state of the
+    /// conductor and so is recorded in the audit log.
+    ///
+    /// Secrets, such as the cap secrets of grants, are never part of the target.
+    pub fn audited_action(&self) -> Option<(&'static str, Option<String>)> {
+        use AdminRequest::*;
+        let audited = match self {
+            AddAdminInterfaces(configs) => ("add_admin_interfaces", Some(format!("{:?}", configs))),
+            RegisterDna(_) => ("register_dna", None),
+            UpdateCoordinators(payload) => {
+                ("update_coordinators", Some(payload.dna_hash.to_string()))
+            }
+            InstallApp(payload) => ("install_app", payload.installed_app_id.clone()),
+            UninstallApp {
+                installed_app_id, ..
+            } => ("uninstall_app", Some(installed_app_id.clone())),
+            GenerateAgentPubKey => ("generate_agent_pub_key", None),
+            RevokeAgentKey(payload) => ("revoke_agent_key", Some(payload.agent_key.to_string())),
+            EnableApp { installed_app_id } => ("enable_app", Some(installed_app_id.clone())),
+            DisableApp { installed_app_id } => ("disable_app", Some(installed_app_id.clone())),
+            AttachAppInterface { port, .. } => {
+                ("attach_app_interface", port.map(|port| port.to_string()))
+            }
+            PauseGossip { dna_hash, .. } => ("pause_gossip", Some(dna_hash.to_string())),
+            ResumeGossip { dna_hash } => ("resume_gossip", Some(dna_hash.to_string())),
+            SetArqPin { cell_id, .. } => ("set_arq_pin", Some(cell_id.to_string())),
+            ImportDhtOpArchive { archive } => {
+                ("import_dht_op_archive", Some(archive.dna_hash.to_string()))
+            }
+            ArchiveDhtEpochs { dna_hash, .. } => ("archive_dht_epochs", Some(dna_hash.to_string())),
+            SetLogFilter { filter } => ("set_log_filter", Some(filter.clone())),
+            ReloadConfig => ("reload_config", None),
+            DisconnectAppInterfaceClient { connection_id } => (
+                "disconnect_app_interface_client",
+                Some(connection_id.to_string()),
+            ),
+            AddAgentInfo { .. } => ("add_agent_info", None),
+            GraftRecords { cell_id, .. } => ("graft_records", Some(cell_id.to_string())),
+            RollbackSourceChain { cell_id, .. } => {
+                ("rollback_source_chain", Some(cell_id.to_string()))
+            }
+            GrantZomeCallCapability(payload) => (
+                "grant_zome_call_capability",
+                Some(payload.cell_id.to_string()),
+            ),
+            DeleteCloneCell(payload) => ("delete_clone_cell", Some(payload.app_id.clone())),
+            IssueAppAuthenticationToken(payload) => (
+                "issue_app_authentication_token",
+                Some(payload.installed_app_id.clone()),
+            ),
+            RevokeAppAuthenticationToken(_) => ("revoke_app_authentication_token", None),
+            _ => return None,
+        };
+        Some(audited)
+    }
 }
 
 /// Represents the possible responses to an [`AdminRequest`]
@@ -474,6 +1003,14 @@ pub enum AdminResponse {
     /// Contains a list of the `InstalledAppInfo` of the installed apps in the conductor.
     AppsListed(Vec<AppInfo>),
 
+    /// The successful response to an [`AdminRequest::ListAppSummaries`].
+    AppSummariesListed(Vec<AppSummary>),
+
+    /// The successful response to an [`AdminRequest::ListAgentKeys`].
+    ///
+    /// Contains the agent keys in the conductor and which apps use them.
+    AgentKeysListed(Vec<AgentKeyInfo>),
+
     /// The successful response to an [`AdminRequest::AttachAppInterface`].
     ///
     /// Contains the port number of the attached app interface.
@@ -532,6 +1069,104 @@ pub enum AdminResponse {
     /// networking library.
     NetworkStatsDumped(String),
 
+    /// The successful response to an [`AdminRequest::HealthCheck`].
+    ///
+    /// This is returned whether or not the conductor is healthy.
+    HealthChecked(HealthReport),
+
+    /// The successful response to an [`AdminRequest::GetStartupReport`].
+    ///
+    /// `None` if the conductor didn't check its cells when it started.
+    StartupReport(Option<StartupReport>),
+
+    /// The successful response to an [`AdminRequest::ListSlowOperations`].
+    SlowOperationsListed(Vec<SlowOperation>),
+
+    /// The successful response to an [`AdminRequest::GetConductorDiagnostics`].
+    ConductorDiagnostics(ConductorDiagnostics),
+
+    /// The successful response to an [`AdminRequest::GetRegionSet`].
+    RegionSet(RegionSetDiagnostics),
+
+    /// The successful response to an [`AdminRequest::GetOpCensus`].
+    OpCensus(OpCensusReport),
+
+    /// The successful response to an [`AdminRequest::GetValidationOutcomes`].
+    ValidationOutcomes(Vec<AuthorValidationOutcomes>),
+
+    /// The successful response to an [`AdminRequest::GetBackfillProgress`].
+    BackfillProgress(BackfillProgress),
+
+    /// The successful response to an [`AdminRequest::PauseGossip`].
+    GossipPaused,
+
+    /// The successful response to an [`AdminRequest::ResumeGossip`].
+    GossipResumed,
+
+    /// The successful response to an [`AdminRequest::SetArqPin`].
+    ArqPinSet,
+
+    /// The successful response to an [`AdminRequest::StartOpTrace`].
+    OpTraceStarted,
+
+    /// The successful response to an [`AdminRequest::GetOpTrace`].
+    ///
+    /// `None` if the op isn't traced.
+    OpTrace(Option<Vec<OpTraceEvent>>),
+
+    /// The successful response to an [`AdminRequest::StopOpTrace`].
+    OpTraceStopped,
+
+    /// The successful response to an [`AdminRequest::ListOpsMissingReceipts`].
+    OpsMissingReceiptsListed(Vec<OpMissingReceipts>),
+
+    /// The successful response to an [`AdminRequest::ExportDhtOpArchive`].
+    DhtOpArchiveExported(Box<DhtOpArchive>),
+
+    /// The successful response to an [`AdminRequest::ImportDhtOpArchive`].
+    ///
+    /// The ops have been queued for validation, and are integrated once they are valid.
+    DhtOpArchiveImported {
+        /// How many ops the archive had, including any which were already held.
+        op_count: usize,
+    },
+
+    /// The successful response to an [`AdminRequest::ListDhtEpochs`].
+    DhtEpochsListed(Vec<DhtEpochSummary>),
+
+    /// The successful response to an [`AdminRequest::ArchiveDhtEpochs`].
+    DhtEpochsArchived(Box<DhtOpArchive>),
+
+    /// The successful response to an [`AdminRequest::RecheckMembraneProofs`].
+    MembraneProofsRechecked(MembraneProofRecheckReport),
+
+    /// The successful response to an [`AdminRequest::ExportAuthoredData`].
+    ///
+    /// The [`AuthoredDataExport`] as pretty printed JSON.
+    AuthoredDataExported(String),
+
+    /// The successful response to an [`AdminRequest::ExportPublicationProof`].
+    PublicationProofExported(Box<PublicationProof>),
+
+    /// The successful response to an [`AdminRequest::ExportAppBundle`].
+    ///
+    /// The encoded bundle, which can be written to a `.happ` file.
+    AppBundleExported(#[serde(with = "serde_bytes")] Vec<u8>),
+
+    /// The successful response to an [`AdminRequest::SetLogFilter`].
+    LogFilterSet,
+
+    /// The successful response to an [`AdminRequest::ReloadConfig`].
+    ///
+    /// Lists the settings which changed, and which of them need a restart.
+    ConfigReloaded(ConfigReloadReport),
+
+    /// The successful response to an [`AdminRequest::ListAppInterfaceConnections`].
+    AppInterfaceConnectionsListed(Vec<AppInterfaceConnectionInfo>),
+
+    /// The successful response to an [`AdminRequest::DisconnectAppInterfaceClient`].
+    AppInterfaceClientDisconnected,
+
     /// The successful response to an [`AdminRequest::AddAgentInfo`].
     ///
     /// This means the agent info was successfully added to the peer store.
@@ -545,6 +1180,11 @@ pub enum AdminResponse {
     /// The successful response to an [`AdminRequest::GraftRecords`].
     RecordsGrafted,
 
+    /// The successful response to an [`AdminRequest::RollbackSourceChain`].
+    ///
+    /// These are the hashes of the actions which were removed, newest first.
+    SourceChainRolledBack(Vec<ActionHash>),
+
     /// The successful response to an [`AdminRequest::GrantZomeCallCapability`].
     ZomeCallCapabilityGranted,
 
@@ -562,6 +1202,9 @@ pub enum AdminResponse {
 
     /// The successful response to an [`AdminRequest::GetCompatibleCells`].
     CompatibleCells(CompatibleCells),
+
+    /// The successful response to an [`AdminRequest::ListAuditLog`].
+    AuditLogListed(Vec<AuditLogEntry>),
 }
 
 pub type CompatibleCells = BTreeSet<(InstalledAppId, BTreeSet<CellId>)>;
@@ -587,8 +1230,22 @@ pub enum ExternalApiWireError {
     ActivateApp(String),
     /// The zome call is unauthorized.
     ZomeCallUnauthorized(String),
+    /// The request isn't allowed by the scope of the token which the client authenticated with.
+    RequestOutOfScope(String),
+    /// The zome call was rejected because of its nonce or expiry.
+    ZomeCallNonceRejected {
+        /// Why the nonce was rejected.
+        reason: NonceRejection,
+        /// A fresh nonce and the expiries which the conductor accepts, to retry the call with.
+        window: ZomeCallNonceWindow,
+    },
     /// A countersigning session has failed.
     CountersigningSessionError(String),
+    /// Genesis failed for some of an app's roles with the memproofs which were provided,
+    /// see [`AppRequest::ProvideMemproofs`](crate::AppRequest::ProvideMemproofs).
+    MemproofsRejected(MemproofProgress),
+    /// The request failed with an error which clients can branch on by its code.
+    ApiError(ApiError),
 }
 
 impl ExternalApiWireError {
@@ -598,6 +1255,176 @@ impl ExternalApiWireError {
         // this version intended for users.
         ExternalApiWireError::InternalError(e.to_string())
     }
+
+    /// The code of the error, which is stable across conductor versions.
+    pub fn code(&self) -> ApiErrorCode {
+        match self {
+            Self::InternalError(_) | Self::ActivateApp(_) => ApiErrorCode::Internal,
+            Self::Deserialization(_) => ApiErrorCode::InvalidRequest,
+            Self::DnaReadError(_) => ApiErrorCode::InvalidDna,
+            Self::RibosomeError(_) => ApiErrorCode::ZomeCallFailed,
+            Self::ZomeCallUnauthorized(_) => ApiErrorCode::Unauthorized,
+            Self::RequestOutOfScope(_) => ApiErrorCode::RequestOutOfScope,
+            Self::ZomeCallNonceRejected { .. } => ApiErrorCode::NonceRejected,
+            Self::CountersigningSessionError(_) => ApiErrorCode::Countersigning,
+            Self::MemproofsRejected(_) => ApiErrorCode::MemproofsRejected,
+            Self::ApiError(e) => e.code,
+        }
+    }
+
+    /// The part of the conductor which the error came from.
+    pub fn subsystem(&self) -> ApiErrorSubsystem {
+        match self {
+            Self::InternalError(_) => ApiErrorSubsystem::Conductor,
+            Self::Deserialization(_)
+            | Self::RequestOutOfScope(_)
+            | Self::ZomeCallNonceRejected { .. } => ApiErrorSubsystem::Interface,
+            Self::DnaReadError(_) => ApiErrorSubsystem::Dna,
+            Self::RibosomeError(_) | Self::ZomeCallUnauthorized(_) => ApiErrorSubsystem::Ribosome,
+            Self::ActivateApp(_) | Self::MemproofsRejected(_) => ApiErrorSubsystem::App,
+            Self::CountersigningSessionError(_) => ApiErrorSubsystem::Countersigning,
+            Self::ApiError(e) => e.subsystem,
+        }
+    }
+
+    /// Whether the same request may succeed if it is made again.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            Self::ApiError(e) => e.retryable,
+            e => e.code().is_retryable(),
+        }
+    }
+}
+
+/// An error which a request over an admin or app interface failed with.
+///
+/// Unlike the message, the code and subsystem are stable, so that clients can tell
+/// errors apart without parsing messages, e.g. to retry a zome call whose commit
+/// lost a race with another call writing to the same source chain.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+pub struct ApiError {
+    /// What went wrong.
+    pub code: ApiErrorCode,
+    /// The part of the conductor which the error came from.
+    pub subsystem: ApiErrorSubsystem,
+    /// Whether the same request may succeed if it is made again.
+    pub retryable: bool,
+    /// A description of the error for people to read, which may change between versions.
+    pub message: String,
+}
+
+impl ApiError {
+    /// An error whose retryability follows from its code.
+    pub fn new(code: ApiErrorCode, subsystem: ApiErrorSubsystem, message: impl ToString) -> Self {
+        Self {
+            code,
+            subsystem,
+            retryable: code.is_retryable(),
+            message: message.to_string(),
+        }
+    }
+}
+
+/// What went wrong in a request, see [`ApiError`].
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiErrorCode {
+    /// An error which clients can't do anything about, other than report it.
+    Internal,
+    /// The request or its payload couldn't be decoded, or isn't valid.
+    InvalidRequest,
+    /// The conductor configuration, or a change to it, isn't valid.
+    InvalidConfig,
+    /// The DNA isn't installed in the conductor.
+    DnaMissing,
+    /// The DNA couldn't be read or isn't valid.
+    InvalidDna,
+    /// The app or DNA bundle couldn't be read or isn't valid.
+    InvalidBundle,
+    /// The app isn't installed.
+    AppMissing,
+    /// An app with the same id is already installed.
+    AppAlreadyInstalled,
+    /// The app isn't running, e.g. because it's disabled or paused.
+    AppNotRunning,
+    /// The app can't be removed or disabled, because other apps use its cells.
+    AppHasDependents,
+    /// The cell doesn't exist in the conductor.
+    CellMissing,
+    /// The cell exists but isn't running, e.g. because it's disabled or still starting.
+    CellNotRunning,
+    /// A cell with the same id already exists.
+    CellAlreadyExists,
+    /// The zome call failed, e.g. because the zome function returned an error.
+    ZomeCallFailed,
+    /// The zome call isn't authorized by a capability grant.
+    Unauthorized,
+    /// The request isn't allowed by the scope of the token which the client authenticated with.
+    RequestOutOfScope,
+    /// The nonce or expiry of a zome call was rejected.
+    NonceRejected,
+    /// Another write to the source chain was committed first.
+    ChainHeadMoved,
+    /// The source chain is locked by a countersigning session.
+    ChainLocked,
+    /// A countersigning session failed or doesn't exist.
+    Countersigning,
+    /// Genesis failed with the memproofs which were provided.
+    MemproofsRejected,
+    /// The keystore failed, e.g. because a key is missing from it.
+    Keystore,
+    /// A database failed.
+    Database,
+    /// An operation took too long, e.g. because a database is busy.
+    Timeout,
+    /// The conductor is shutting down.
+    ShuttingDown,
+}
+
+impl ApiErrorCode {
+    /// Whether a request which failed with this code may succeed if it is made again.
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            Self::CellNotRunning
+                | Self::NonceRejected
+                | Self::ChainHeadMoved
+                | Self::ChainLocked
+                | Self::Timeout
+        )
+    }
+}
+
+/// The part of the conductor which an error came from, see [`ApiError`].
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiErrorSubsystem {
+    /// The admin or app interface.
+    Interface,
+    /// The conductor itself.
+    Conductor,
+    /// An app or its bundle.
+    App,
+    /// A DNA or its bundle.
+    Dna,
+    /// A cell.
+    Cell,
+    /// The zomes of a cell.
+    Ribosome,
+    /// A source chain.
+    SourceChain,
+    /// Countersigning.
+    Countersigning,
+    /// The databases.
+    Database,
+    /// The keystore.
+    Keystore,
+    /// The network.
+    Network,
+    /// A chain head coordinator.
+    Chc,
+    /// The DPKI service.
+    Dpki,
 }
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, Clone)]
@@ -620,6 +1447,66 @@ pub enum AppStatusFilter {
     Paused,
 }
 
+/// Which of the sorted results of a listing to return.
+#[derive(
+    Debug, serde::Serialize, serde::Deserialize, SerializedBytes, Clone, Copy, PartialEq, Eq,
+)]
+pub struct Page {
+    /// The number of results to skip.
+    pub offset: usize,
+    /// The most results to return.
+    pub limit: usize,
+}
+
+impl Page {
+    /// Select the results on this page.
+    pub fn select<T>(&self, items: impl IntoIterator<Item = T>) -> Vec<T> {
+        items
+            .into_iter()
+            .skip(self.offset)
+            .take(self.limit)
+            .collect()
+    }
+}
+
+/// Filter for [`AdminRequest::ListCellIds`].
+#[derive(Debug, Default, serde::Serialize, serde::Deserialize, SerializedBytes, Clone)]
+pub struct CellIdsFilter {
+    /// Only list the cells of this DNA.
+    #[serde(default)]
+    pub dna_hash: Option<DnaHash>,
+    /// Only list a page of the sorted cell IDs.
+    #[serde(default)]
+    pub page: Option<Page>,
+}
+
+/// A summary of an installed app, returned by [`AdminRequest::ListAppSummaries`].
+#[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, Clone, PartialEq)]
+pub struct AppSummary {
+    /// The ID of the app.
+    pub installed_app_id: InstalledAppId,
+
+    /// The app's current status.
+    pub status: AppInfoStatus,
+
+    /// Whether the app is enabled, which includes paused apps.
+    pub enabled: bool,
+
+    /// The number of cells of the app, including clone cells.
+    pub cell_count: usize,
+
+    /// The bytes on disk of the source chains of the app's cells.
+    pub authored_data_size_on_disk: usize,
+
+    /// The bytes on disk of the DHT and cache databases of the app's DNAs.
+    ///
+    /// These databases are shared by all the apps which have a cell of the same DNA.
+    pub dna_data_size_on_disk: usize,
+
+    /// When the app was installed.
+    pub installed_at: Timestamp,
+}
+
 /// Informational response for listing app interfaces.
 #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, Clone)]
 pub struct AppInterfaceInfo {
@@ -633,6 +1520,509 @@ pub struct AppInterfaceInfo {
     pub installed_app_id: Option<InstalledAppId>,
 }
 
+/// Identifies a client connection to an app interface.
+///
+/// Ids are assigned by the conductor when a connection is authenticated, and are not reused
+/// until the conductor restarts.
+pub type AppInterfaceConnectionId = u64;
+
+/// A client which is connected to an app interface.
+#[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, Clone, PartialEq)]
+pub struct AppInterfaceConnectionInfo {
+    /// The id of this connection.
+    pub connection_id: AppInterfaceConnectionId,
+
+    /// The port of the app interface which the client is connected to.
+    pub port: u16,
+
+    /// The app which the client authenticated for.
+    pub installed_app_id: InstalledAppId,
+
+    /// The `Origin` header the client sent when it connected.
+    pub origin: Option<String>,
+
+    /// The address the client connected from.
+    pub peer_addr: String,
+
+    /// When the client connected.
+    pub connected_since: Timestamp,
+
+    /// Which of the app's signals are sent to the client.
+    pub signal_filters: SignalFilterSet,
+
+    /// How many requests from the client are currently being handled.
+    pub messages_in_flight: usize,
+}
+
+/// Informational response for listing agent keys.
+#[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, Clone, PartialEq, Eq)]
+pub struct AgentKeyInfo {
+    /// The agent key.
+    pub agent_key: AgentPubKey,
+
+    /// The installed apps which use this key, with the cells of each app
+    /// which use it.
+    pub apps: Vec<(InstalledAppId, Vec<CellId>)>,
+
+    /// How the key was derived from the device seed, if it was.
+    pub derivation: Option<AgentKeyDerivation>,
+}
+
+/// Where a key derived from the device seed can be found.
+///
+/// The conductor derives keys from the seed at
+/// [`ConductorConfig::device_seed_lair_tag`](crate::conductor::ConductorConfig::device_seed_lair_tag)
+/// with the following derivation paths:
+/// - `[0]` for the DPKI agent key
+/// - `[app_index, key_index]` for app agent keys, where `app_index` counts the
+///   apps installed without a given agent key, starting from 0, and
+///   `key_index` is 0 for the first key of the app
+///
+/// Each derived seed is stored in lair with the tag
+/// `{device_seed_lair_tag}.{path}`, with the path components joined by dots,
+/// e.g. `device-seed.3.0`.
+#[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, Clone, PartialEq, Eq)]
+pub struct AgentKeyDerivation {
+    /// The lair tag of the device seed the key was derived from.
+    pub device_seed_lair_tag: String,
+
+    /// The derivation path from the device seed.
+    pub derivation_path: Vec<u32>,
+
+    /// The lair tag the derived seed is stored under.
+    pub lair_tag: String,
+}
+
+/// The result of [`AdminRequest::HealthCheck`].
+#[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, Clone, PartialEq, Eq)]
+pub struct HealthReport {
+    /// Whether every check passed.
+    pub healthy: bool,
+
+    /// Whether the keystore responds.
+    pub keystore: HealthStatus,
+
+    /// Whether a write transaction can be run on the source chain database
+    /// of each running cell.
+    pub cells: Vec<(CellId, HealthStatus)>,
+
+    /// Whether the network transport responds.
+    pub network: HealthStatus,
+
+    /// Whether the bootstrap service can be reached,
+    /// or `None` if no bootstrap service is configured.
+    pub bootstrap: Option<HealthStatus>,
+}
+
+/// The outcome of one check in a [`HealthReport`].
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", tag = "status")]
+pub enum HealthStatus {
+    /// The check passed.
+    Ok,
+    /// The check failed.
+    Failing {
+        /// Why the check failed.
+        reason: String,
+    },
+}
+
+impl HealthStatus {
+    /// Whether the check passed.
+    pub fn is_ok(&self) -> bool {
+        matches!(self, Self::Ok)
+    }
+}
+
+impl<E: std::fmt::Display> From<Result<(), E>> for HealthStatus {
+    fn from(result: Result<(), E>) -> Self {
+        match result {
+            Ok(()) => Self::Ok,
+            Err(e) => Self::Failing {
+                reason: e.to_string(),
+            },
+        }
+    }
+}
+
+/// The result of [`AdminRequest::GetStartupReport`].
+#[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, Clone, PartialEq, Eq)]
+pub struct StartupReport {
+    /// Whether every check passed for every cell.
+    pub healthy: bool,
+
+    /// When the checks were run.
+    pub checked_at: Timestamp,
+
+    /// The outcome of the checks for each cell which was started.
+    pub cells: Vec<CellStartupHealth>,
+}
+
+/// The outcome of the startup checks of one cell in a [`StartupReport`].
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+pub struct CellStartupHealth {
+    /// The cell which was checked.
+    pub cell_id: CellId,
+
+    /// Whether the chain head is the last action which was committed, with
+    /// every action before it present.
+    pub chain_head: HealthStatus,
+
+    /// Whether the authored, DHT and cache databases have the expected schema versions.
+    pub schema: HealthStatus,
+
+    /// Whether the DHT query cache could be built from the DHT database.
+    pub dht_query_cache: HealthStatus,
+}
+
+impl CellStartupHealth {
+    /// Whether every check passed for this cell.
+    pub fn is_ok(&self) -> bool {
+        self.chain_head.is_ok() && self.schema.is_ok() && self.dht_query_cache.is_ok()
+    }
+}
+
+/// The result of [`AdminRequest::GetConductorDiagnostics`].
+#[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, Clone, PartialEq, Eq)]
+pub struct ConductorDiagnostics {
+    /// When the snapshot was taken.
+    pub taken_at: Timestamp,
+
+    /// The diagnostics of each DNA which a running cell uses.
+    pub dnas: Vec<DnaDiagnostics>,
+
+    /// The status of the ops which each running cell authored.
+    pub cells: Vec<CellDiagnostics>,
+
+    /// The statistics of each open transport connection to a remote peer.
+    #[serde(default)]
+    pub connections: Vec<PeerConnectionStats>,
+
+    /// How far the clock of the conductor is estimated to be from the clocks of its peers.
+    #[serde(default)]
+    pub clock: ClockSkewReport,
+}
+
+/// How far the clock of a conductor is estimated to be from the clocks of its peers,
+/// in a [`ConductorDiagnostics`].
+///
+/// The skew is estimated from when the agent infos which peers publish were signed,
+/// as the median over the peers of the freshest agent info of each.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClockSkewReport {
+    /// How many microseconds the clocks of peers are ahead of the clock of the
+    /// conductor, or behind it if negative. Missing until enough peers are known.
+    pub estimated_skew_micros: Option<i64>,
+    /// How many peers the skew was estimated from.
+    pub peers: usize,
+    /// Whether the skew is past the `clock_skew_threshold` of the conductor.
+    pub skewed: bool,
+    /// The microseconds which are added to the system clock for the timestamps of
+    /// new actions, if `clock_skew_correction` is on.
+    pub correction_micros: i64,
+}
+
+/// The diagnostics of a DNA in a [`ConductorDiagnostics`].
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+pub struct DnaDiagnostics {
+    /// The DNA.
+    pub dna_hash: DnaHash,
+
+    /// How many ops of the DNA are queued for each workflow.
+    pub queues: WorkflowQueueDepths,
+
+    /// The ops which are waiting to be fetched from other nodes.
+    pub fetch_pool: FetchPoolInfo,
+
+    /// What gossip has been doing recently.
+    pub gossip: GossipActivity,
+
+    /// The dependencies which the most ops of the DNA in validation limbo are waiting
+    /// for, most blocking first, at most [`MAX_BLOCKING_DEPENDENCIES`] of them.
+    #[serde(default)]
+    pub blocking_dependencies: Vec<BlockingDependency>,
+}
+
+/// A dependency which ops in validation limbo are waiting for, as counted when the
+/// validation workflows last ran.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+pub struct BlockingDependency {
+    /// The missing dependency.
+    pub hash: AnyDhtHash,
+    /// How many ops are waiting for it to be sys validated.
+    pub awaiting_sys_validation: usize,
+    /// How many ops are waiting for it to be app validated.
+    pub awaiting_app_validation: usize,
+}
+
+impl BlockingDependency {
+    /// How many ops are waiting for the dependency in total.
+    pub fn blocked_ops(&self) -> usize {
+        self.awaiting_sys_validation + self.awaiting_app_validation
+    }
+}
+
+/// How many of the dependencies which block the most ops are reported in a
+/// [`DnaDiagnostics`].
+pub const MAX_BLOCKING_DEPENDENCIES: usize = 20;
+
+/// How many ops are queued for each of the workflows which validate and integrate
+/// the ops of a DNA.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct WorkflowQueueDepths {
+    /// Ops awaiting sys validation, including those waiting for their dependencies.
+    pub sys_validation: usize,
+    /// Ops awaiting app validation, including those waiting for their dependencies.
+    pub app_validation: usize,
+    /// Validated ops awaiting integration.
+    pub integration: usize,
+}
+
+/// The gossip activity of a DNA.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GossipActivity {
+    /// Rounds which are currently in progress with other nodes.
+    pub current_rounds: usize,
+    /// Rounds which ended within the last [`GOSSIP_ACTIVITY_WINDOW`].
+    pub recent_rounds: usize,
+    /// How many of the recent rounds ended in an error.
+    pub recent_errors: usize,
+}
+
+/// How far back completed gossip rounds are counted in a [`GossipActivity`].
+pub const GOSSIP_ACTIVITY_WINDOW: std::time::Duration = std::time::Duration::from_secs(60);
+
+/// The status of the ops which a cell authored, in a [`ConductorDiagnostics`].
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+pub struct CellDiagnostics {
+    /// The cell.
+    pub cell_id: CellId,
+    /// Every op which the cell authored.
+    pub authored_ops: usize,
+    /// Authored ops which haven't been published yet.
+    pub unpublished_ops: usize,
+    /// Authored ops which are withheld from publishing, e.g. during countersigning.
+    pub withheld_ops: usize,
+    /// Authored ops which still need more validation receipts.
+    pub ops_awaiting_receipts: usize,
+}
+
+/// The regions of the ops of a DNA, see [`AdminRequest::GetRegionSet`].
+#[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, Clone, PartialEq, Eq)]
+pub struct RegionSetDiagnostics {
+    /// The DNA.
+    pub dna_hash: DnaHash,
+
+    /// When the regions were queried.
+    pub taken_at: Timestamp,
+
+    /// The local agents whose storage arcs the regions cover.
+    pub agents: Vec<AgentPubKey>,
+
+    /// Every region, including the empty ones, so that the regions of two nodes
+    /// with the same arcs line up.
+    pub regions: Vec<RegionDiagnostics>,
+}
+
+/// A region in a [`RegionSetDiagnostics`].
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+pub struct RegionDiagnostics {
+    /// The first and last DHT location of the region.
+    pub locations: (u32, u32),
+    /// The first and last time of the region, which ops are placed in by when they
+    /// were authored.
+    pub times: (Timestamp, Timestamp),
+    /// How many ops are in the region.
+    pub op_count: u32,
+    /// The total size of the ops in the region, in bytes.
+    pub size: u32,
+    /// The fingerprint of the region, the XOR of the hashes of its ops, in hex.
+    /// Two nodes hold the same ops in the region if their fingerprints match.
+    pub hash: String,
+}
+
+/// How many peers an [`AdminRequest::GetOpCensus`] asks unless it says otherwise.
+pub const OP_CENSUS_DEFAULT_PEER_COUNT: usize = 5;
+
+/// A comparison of the ops of a DNA which this node holds with those which its
+/// neighbors hold, see [`AdminRequest::GetOpCensus`].
+#[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, Clone, PartialEq, Eq)]
+pub struct OpCensusReport {
+    /// The DNA.
+    pub dna_hash: DnaHash,
+
+    /// When the census was taken.
+    pub taken_at: Timestamp,
+
+    /// The local agents whose storage arcs the census covers.
+    pub agents: Vec<AgentPubKey>,
+
+    /// Each peer which was asked.
+    pub peers: Vec<PeerOpCensus>,
+}
+
+impl OpCensusReport {
+    /// Whether every peer answered and holds the same ops as this node, in every
+    /// region which both hold.
+    pub fn is_consistent(&self) -> bool {
+        self.peers
+            .iter()
+            .all(|peer| peer.error.is_none() && peer.discrepancies.is_empty())
+    }
+}
+
+/// The answer of a peer to an op census, in an [`OpCensusReport`].
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+pub struct PeerOpCensus {
+    /// An agent of the peer whose storage arc overlaps those of the local agents.
+    pub agent: AgentPubKey,
+    /// How many regions both nodes hold, which were compared.
+    pub regions_compared: usize,
+    /// The regions which the two nodes hold different ops in.
+    pub discrepancies: Vec<RegionDiscrepancy>,
+    /// Why the peer couldn't be asked, if it couldn't.
+    pub error: Option<String>,
+}
+
+/// A region which this node and a peer hold different ops in, in a [`PeerOpCensus`].
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+pub struct RegionDiscrepancy {
+    /// The first and last DHT location of the region.
+    pub locations: (u32, u32),
+    /// The first and last time of the region.
+    pub times: (Timestamp, Timestamp),
+    /// How many ops this node holds in the region.
+    pub local_op_count: u32,
+    /// How many ops the peer holds in the region. The counts can match while the
+    /// ops don't, if the fingerprints of the region differ.
+    pub peer_op_count: u32,
+}
+
+/// Something which happened to a traced op, see [`AdminRequest::StartOpTrace`].
+#[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, Clone, PartialEq, Eq)]
+pub struct OpTraceEvent {
+    /// When it happened.
+    pub timestamp: Timestamp,
+    /// Which part of the conductor the op passed through.
+    pub stage: OpTraceStage,
+    /// What happened there, e.g. the outcome of validation.
+    pub detail: String,
+}
+
+/// The parts of the conductor which a traced op can pass through.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OpTraceStage {
+    /// The op was published to the authorities of its basis.
+    Published,
+    /// The op was sent to a remote node which fetched it.
+    Served,
+    /// The op was received from a remote node, by publish or by fetching it.
+    Received,
+    /// The op was sys validated.
+    SysValidated,
+    /// The op was app validated.
+    AppValidated,
+    /// The op was integrated into the DHT.
+    Integrated,
+    /// A validation receipt for the op was sent to its author.
+    ReceiptSent,
+    /// A validation receipt for the op was received from a validator.
+    ReceiptReceived,
+}
+
+/// A zome call or workflow run which took longer than the configured threshold.
+///
+/// See [`ConductorTuningParams::slow_zome_call_threshold`](crate::conductor::ConductorTuningParams::slow_zome_call_threshold)
+/// and [`ConductorTuningParams::slow_workflow_threshold`](crate::conductor::ConductorTuningParams::slow_workflow_threshold).
+#[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, Clone, PartialEq, Eq)]
+pub struct SlowOperation {
+    /// What was slow.
+    pub target: SlowOperationTarget,
+
+    /// When the operation started.
+    pub started_at: Timestamp,
+
+    /// How long the operation took, in microseconds.
+    pub duration_micros: u64,
+
+    /// How much of that time was spent waiting for a database connection,
+    /// in microseconds.
+    pub db_wait_micros: u64,
+}
+
+/// An authored op which hasn't received as many validation receipts as it needs,
+/// so it is still being republished.
+#[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, Clone, PartialEq, Eq)]
+pub struct OpMissingReceipts {
+    /// The hash of the op.
+    pub op_hash: DhtOpHash,
+
+    /// The type of the op.
+    pub op_type: ChainOpType,
+
+    /// The hash of the action the op was produced from.
+    pub action_hash: ActionHash,
+
+    /// When the action was authored.
+    pub authored_at: Timestamp,
+
+    /// When the op was last published, if it has been.
+    pub last_published_at: Option<Timestamp>,
+
+    /// How many validation receipts the op has received.
+    pub receipt_count: usize,
+
+    /// How many validation receipts the op needs.
+    pub required_receipt_count: u8,
+}
+
+/// The outcome of running the genesis self check of a DNA again on the membrane proofs
+/// of the agents which joined it.
+#[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, Clone, PartialEq, Eq)]
+pub struct MembraneProofRecheckReport {
+    /// How many agents had their membrane proof checked.
+    pub checked_count: usize,
+
+    /// The agents whose membrane proof doesn't pass the check.
+    pub failed: Vec<MembraneProofFailure>,
+}
+
+/// An agent whose membrane proof doesn't pass the genesis self check of its DNA.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+pub struct MembraneProofFailure {
+    /// The agent.
+    pub agent: AgentPubKey,
+
+    /// Why the membrane proof doesn't pass, as given by the check, or the error which
+    /// the check failed with.
+    pub reason: String,
+}
+
+/// The zome call or workflow which a [`SlowOperation`] was for.
+#[derive(Debug, serde::Serialize, serde::Deserialize, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case", tag = "type")]
+pub enum SlowOperationTarget {
+    /// A zome call.
+    ZomeCall {
+        /// The cell which was called.
+        cell_id: CellId,
+        /// The zome which was called.
+        zome_name: ZomeName,
+        /// The function which was called.
+        fn_name: FunctionName,
+    },
+    /// A run of a workflow.
+    Workflow {
+        /// The name of the workflow.
+        workflow: String,
+        /// The DNA the workflow ran for.
+        dna_hash: DnaHash,
+        /// The agent the workflow ran for, if the workflow runs per cell.
+        agent: Option<AgentPubKey>,
+    },
+}
+
 /// Request payload for [AdminRequest::IssueAppAuthenticationToken].
 #[derive(Debug, serde::Serialize, serde::Deserialize)]
 pub struct IssueAppAuthenticationTokenPayload {
@@ -655,6 +2045,11 @@ pub struct IssueAppAuthenticationTokenPayload {
     /// Set this to `false` to allow the token to be used multiple times.
     #[serde(default = "default_single_use")]
     pub single_use: bool,
+
+    /// What a client which connects with the token may request. By default it may make
+    /// any request in the context of the app.
+    #[serde(default)]
+    pub scope: AppAuthenticationTokenScope,
 }
 
 fn default_expiry_seconds() -> u64 {
@@ -684,6 +2079,12 @@ impl IssueAppAuthenticationTokenPayload {
         self.single_use = single_use;
         self
     }
+
+    /// Set what a client which connects with the token may request.
+    pub fn scope(mut self, scope: AppAuthenticationTokenScope) -> Self {
+        self.scope = scope;
+        self
+    }
 }
 
 impl From<InstalledAppId> for IssueAppAuthenticationTokenPayload {
@@ -693,6 +2094,7 @@ impl From<InstalledAppId> for IssueAppAuthenticationTokenPayload {
             installed_app_id,
             expiry_seconds: 30,
             single_use: true,
+            scope: AppAuthenticationTokenScope::default(),
         }
     }
 }
@@ -700,6 +2102,61 @@ impl From<InstalledAppId> for IssueAppAuthenticationTokenPayload {
 /// A token issued by the conductor that can be used to authenticate a connection to an app interface.
 pub type AppAuthenticationToken = Vec<u8>;
 
+/// What a client which authenticated with an app authentication token may request over
+/// the app interface. Requests are always made in the context of the app which the token
+/// was issued for, and signals of the app are received with any scope.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AppAuthenticationTokenScope {
+    /// Any request.
+    #[default]
+    Full,
+    /// Any request except those which manage the app, i.e. enabling the app, providing
+    /// memproofs and creating, enabling or disabling clone cells.
+    NoAdmin,
+    /// Only requests which read the state of the app, such as [`AppRequest::AppInfo`].
+    ///
+    /// Zome calls can't be made, as any zome function may write to the source chain.
+    ReadOnly,
+    /// No requests at all, so that the client only receives the signals of the app.
+    SignalOnly,
+}
+
+impl AppAuthenticationTokenScope {
+    /// Whether a client with this scope may make the request.
+    ///
+    /// Every request is matched by name, so that a new request has to be given a scope.
+    pub fn allows(&self, request: &AppRequest) -> bool {
+        match request {
+            // Requests which read the state of the app.
+            AppRequest::AppInfo
+            | AppRequest::GetCountersigningSessionState(_)
+            | AppRequest::NetworkInfo(_)
+            | AppRequest::ListWasmHostFunctions
+            | AppRequest::GetMemproofProgress
+            | AppRequest::GetZomeCallNonceWindow => {
+                matches!(self, Self::Full | Self::NoAdmin | Self::ReadOnly)
+            }
+            // Requests which use the app, and may write to its source chains.
+            AppRequest::CallZome(_)
+            | AppRequest::CallZomeStreaming(_)
+            | AppRequest::NextZomeCallResponseChunk(_)
+            | AppRequest::CancelZomeCallResponseStream(_)
+            | AppRequest::CallZomeDryRun(_)
+            | AppRequest::AbandonCountersigningSession(_)
+            | AppRequest::PublishCountersigningSession(_) => {
+                matches!(self, Self::Full | Self::NoAdmin)
+            }
+            // Requests which manage the app.
+            AppRequest::CreateCloneCell(_)
+            | AppRequest::DisableCloneCell(_)
+            | AppRequest::EnableCloneCell(_)
+            | AppRequest::ProvideMemproofs(_)
+            | AppRequest::EnableApp => matches!(self, Self::Full),
+        }
+    }
+}
+
 /// Response payload for [AdminResponse::AppAuthenticationTokenIssued].
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct AppAuthenticationTokenIssued {
@@ -772,4 +2229,50 @@ mod tests {
 
         assert_eq!(json_actual, json_expected);
     }
+
+    #[test]
+    fn list_requests_without_filters_are_deserialized() {
+        let request: AdminRequest = serde_json::from_str(r#"{"type":"list_cell_ids"}"#).unwrap();
+        assert!(matches!(request, AdminRequest::ListCellIds(None)));
+
+        let request: AdminRequest =
+            serde_json::from_str(r#"{"type":"list_apps","data":{"status_filter":null}}"#).unwrap();
+        assert!(matches!(
+            request,
+            AdminRequest::ListApps {
+                status_filter: None,
+                dna_hash: None,
+                page: None,
+            }
+        ));
+    }
+
+    #[test]
+    fn api_errors_are_serialized_with_their_code() {
+        let error = ExternalApiWireError::ApiError(ApiError::new(
+            ApiErrorCode::ChainHeadMoved,
+            ApiErrorSubsystem::SourceChain,
+            "head moved",
+        ));
+        let json = serde_json::to_value(&error).unwrap();
+        assert_eq!(
+            serde_json::json!({
+                "type": "api_error",
+                "data": {
+                    "code": "chain_head_moved",
+                    "subsystem": "source_chain",
+                    "retryable": true,
+                    "message": "head moved",
+                },
+            }),
+            json
+        );
+        assert!(error.is_retryable());
+
+        // Errors which predate the codes have codes too.
+        let error = ExternalApiWireError::RequestOutOfScope("signals only".to_string());
+        assert_eq!(ApiErrorCode::RequestOutOfScope, error.code());
+        assert_eq!(ApiErrorSubsystem::Interface, error.subsystem());
+        assert!(!error.is_retryable());
+    }
 }
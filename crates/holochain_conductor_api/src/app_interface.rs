@@ -5,6 +5,7 @@ use holochain_keystore::MetaLairClient;
 use holochain_types::prelude::*;
 use indexmap::IndexMap;
 use kitsune_p2p_types::fetch_pool::FetchPoolInfo;
+use std::collections::BTreeMap;
 
 /// Represents the available conductor functions to call over an app interface
 /// and will result in a corresponding [`AppResponse`] message being sent back over the
@@ -33,6 +34,53 @@ pub enum AppRequest {
     /// [`AppResponse::ZomeCalled`]
     CallZome(Box<ZomeCall>),
 
+    /// Call a zome function, and stream the response back in chunks.
+    ///
+    /// This is the same as [`AppRequest::CallZome`], except that the response is split into
+    /// chunks of at most [`ZOME_CALL_RESPONSE_CHUNK_SIZE`] bytes, so that a large response can be
+    /// received and consumed incrementally rather than as a single message. The first chunk is
+    /// returned in response to this request, and the rest are requested with
+    /// [`AppRequest::NextZomeCallResponseChunk`]. Concatenating the bytes of every chunk gives
+    /// the bytes of the [`ExternIO`] which [`AppRequest::CallZome`] would have returned.
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::ZomeCallResponseChunk`]
+    CallZomeStreaming(Box<ZomeCall>),
+
+    /// Get the next chunk of a zome call response which is being streamed.
+    ///
+    /// A stream is closed once its last chunk has been returned, or if no chunk has been
+    /// requested from it for [`ZOME_CALL_RESPONSE_STREAM_TIMEOUT`].
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::ZomeCallResponseChunk`]
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if there is no open stream with the given id for this app.
+    NextZomeCallResponseChunk(ZomeCallResponseStreamId),
+
+    /// Close a zome call response stream without reading the rest of its chunks.
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::Ok`]
+    CancelZomeCallResponseStream(ZomeCallResponseStreamId),
+
+    /// Call a zome function without committing anything, to preview what it would commit.
+    ///
+    /// The call runs against a scratch copy of the source chain, and what it commits is
+    /// validated as it would be, but nothing is written or published, and the signals
+    /// which it emits aren't sent. The host functions which would reach other cells or
+    /// agents, such as remote signals, remote calls and calls into other cells, fail.
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::ZomeCallDryRun`]
+    CallZomeDryRun(Box<ZomeCall>),
+
     /// Get the state of a countersigning session.
     ///
     /// # Returns
@@ -165,11 +213,24 @@ pub enum AppRequest {
     /// using `allow_deferred_memproofs` and memproofs were not provided at
     /// installation time.
     ///
+    /// Genesis is run for each of the app's cells with the memproof for its role.
+    /// If genesis fails for any role then the app keeps awaiting memproofs, and
+    /// [`ExternalApiWireError::MemproofsRejected`] reports which roles failed and why.
+    /// Memproofs can then be provided again. Roles which completed genesis keep
+    /// their cells, so only the roles which failed need a corrected memproof.
+    ///
     /// # Returns
     ///
     /// [`AppResponse::Ok`]
     ProvideMemproofs(MemproofMap),
 
+    /// Get the progress of providing memproofs for this app.
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::MemproofProgress`]
+    GetMemproofProgress,
+
     /// Enable the app, only in special circumstances.
     /// Can only be called while the app is in the `Disabled(NotStartedAfterProvidingMemproofs)` state.
     /// Cannot be used to enable the app if it's in any other state, or Disabled for any other reason.
@@ -178,6 +239,17 @@ pub enum AppRequest {
     ///
     /// [`AppResponse::Ok`]
     EnableApp,
+
+    /// Get a fresh nonce and the expiries which this conductor accepts for zome calls,
+    /// for clients which sign zome calls themselves.
+    ///
+    /// Expiries are checked against the conductor's clock, so using the returned `now`
+    /// rather than the client's clock avoids having calls rejected because of clock drift.
+    ///
+    /// # Returns
+    ///
+    /// [`AppResponse::ZomeCallNonceWindow`]
+    GetZomeCallNonceWindow,
     //
     // TODO: implement after DPKI lands
     // /// Replace the agent key associated with this app with a new one.
@@ -216,6 +288,13 @@ pub enum AppResponse {
     /// [msgpack]: https://msgpack.org/
     ZomeCalled(Box<ExternIO>),
 
+    /// The successful response to an [`AppRequest::CallZomeStreaming`] or an
+    /// [`AppRequest::NextZomeCallResponseChunk`].
+    ZomeCallResponseChunk(Box<ZomeCallResponseChunk>),
+
+    /// The successful response to an [`AppRequest::CallZomeDryRun`].
+    ZomeCallDryRun(Box<ZomeCallDryRun>),
+
     /// The successful response to an [`AppRequest::GetCountersigningSessionState`].
     CountersigningSessionState(Box<Option<CountersigningSessionState>>),
 
@@ -247,6 +326,12 @@ pub enum AppResponse {
     /// All the wasm host functions supported by this conductor.
     ListWasmHostFunctions(Vec<String>),
 
+    /// The successful response to an [`AppRequest::GetMemproofProgress`].
+    MemproofProgress(MemproofProgress),
+
+    /// The successful response to an [`AppRequest::GetZomeCallNonceWindow`].
+    ZomeCallNonceWindow(ZomeCallNonceWindow),
+
     /// The app agent key as been rotated, and the new key is returned.
     AppAgentKeyRotated(AgentPubKey),
 
@@ -254,6 +339,105 @@ pub enum AppResponse {
     Ok,
 }
 
+/// The largest number of bytes in a single [`ZomeCallResponseChunk`].
+pub const ZOME_CALL_RESPONSE_CHUNK_SIZE: usize = 1 << 20;
+
+/// How long a zome call response stream stays open without a chunk being requested from it.
+pub const ZOME_CALL_RESPONSE_STREAM_TIMEOUT: std::time::Duration =
+    std::time::Duration::from_secs(60);
+
+/// Identifies a zome call response which is being streamed in chunks. Stream ids are random.
+pub type ZomeCallResponseStreamId = u64;
+
+/// One chunk of a zome call response which is being streamed, from an
+/// [`AppRequest::CallZomeStreaming`].
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct ZomeCallResponseChunk {
+    /// The stream which this chunk belongs to, used to request the next chunk.
+    pub stream_id: ZomeCallResponseStreamId,
+    /// The position of this chunk in the response, starting from 0.
+    pub index: u32,
+    /// The size in bytes of the whole response.
+    pub total_size: u64,
+    /// The bytes of the response in this chunk.
+    #[serde(with = "serde_bytes")]
+    pub bytes: Vec<u8>,
+    /// Whether this is the last chunk of the response, after which the stream is closed.
+    pub is_last: bool,
+}
+
+/// What a zome call would have committed, from an [`AppRequest::CallZomeDryRun`].
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ZomeCallDryRun {
+    /// What the zome function returned.
+    pub output: ExternIO,
+    /// The records which the call would have committed to the source chain, in order.
+    /// The links which it would have created or deleted are among them, as
+    /// `CreateLink` and `DeleteLink` actions.
+    pub records: Vec<Record>,
+    /// Whether the records would have passed validation.
+    pub validation: DryRunValidation,
+}
+
+/// The outcome of validating the records of a [`ZomeCallDryRun`].
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type", content = "data")]
+pub enum DryRunValidation {
+    /// The records are valid, so the call would have committed them.
+    Valid,
+    /// A record is invalid, so the call would have failed.
+    Invalid(String),
+    /// The records couldn't be validated yet, because these dependencies couldn't be
+    /// fetched, so the call would have failed for now.
+    MissingDependencies(Vec<AnyDhtHash>),
+}
+
+/// A fresh nonce and the expiries which a conductor accepts for zome calls,
+/// from an [`AppRequest::GetZomeCallNonceWindow`].
+///
+/// A nonce may only be used once by each provenance until the call expires.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ZomeCallNonceWindow {
+    /// A nonce which hasn't been used yet.
+    pub nonce: Nonce256Bits,
+    /// The time according to the conductor's clock.
+    pub now: Timestamp,
+    /// The expiry which the conductor gives to the calls it signs itself.
+    pub expires_at: Timestamp,
+    /// The latest expiry which the conductor accepts. Calls which expire after this
+    /// are rejected with [`NonceRejection::Future`].
+    pub latest_expires_at: Timestamp,
+}
+
+/// The progress of setting up an app which was installed with deferred memproofs,
+/// from an [`AppRequest::GetMemproofProgress`].
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct MemproofProgress {
+    /// The genesis status of each of the app's roles which create a cell when the
+    /// app is installed.
+    pub roles: BTreeMap<RoleName, RoleGenesisStatus>,
+    /// How many times memproofs have been provided for the app.
+    pub attempts: u32,
+}
+
+/// The genesis status of one role of an app, see [`MemproofProgress`].
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case", tag = "type", content = "data")]
+pub enum RoleGenesisStatus {
+    /// Waiting for a memproof to be provided.
+    AwaitingMemproof,
+    /// Genesis is running with the memproof that was provided.
+    Running,
+    /// Genesis completed and the role's cell was created.
+    Complete,
+    /// Genesis failed with the memproof that was provided, so a memproof
+    /// needs to be provided again.
+    Failed {
+        /// Why genesis failed.
+        reason: String,
+    },
+}
+
 /// The data provided over an app interface in order to make a zome call
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct ZomeCall {
@@ -272,11 +456,18 @@ pub struct ZomeCall {
     /// via a `CapGrant`. Otherwise it will be necessary to provide a `CapSecret` for every call.
     pub cap_secret: Option<CapSecret>,
     /// The provenance (source) of the call
-    /// MUST match the signature.
+    /// MUST match the signature, unless the call is delegated.
     pub provenance: AgentPubKey,
     pub signature: Signature,
     pub nonce: Nonce256Bits,
     pub expires_at: Timestamp,
+    /// The chain of delegations which allows the last delegate to sign the call on behalf
+    /// of the provenance, e.g. so that a server-side component can make calls for a user
+    /// without holding the user's agent key. Empty if the provenance signed the call.
+    ///
+    /// See [`ZomeCallDelegation`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub delegation: Vec<ZomeCallDelegation>,
 }
 
 impl From<ZomeCall> for ZomeCallUnsigned {
@@ -318,6 +509,41 @@ impl ZomeCall {
             nonce: unsigned_zome_call.nonce,
             expires_at: unsigned_zome_call.expires_at,
             signature,
+            delegation: Vec::new(),
+        })
+    }
+
+    /// Sign a call on behalf of its provenance with the key of the last delegate of
+    /// a delegation chain.
+    pub async fn try_from_delegated_zome_call(
+        keystore: &MetaLairClient,
+        unsigned_zome_call: ZomeCallUnsigned,
+        delegation: Vec<ZomeCallDelegation>,
+    ) -> LairResult<Self> {
+        let delegate = delegation
+            .last()
+            .ok_or_else(|| "A delegated zome call needs a delegation".to_string())?
+            .delegate
+            .clone();
+        let signature = delegate
+            .sign_raw(
+                keystore,
+                unsigned_zome_call
+                    .data_to_sign()
+                    .map_err(|e| e.to_string())?,
+            )
+            .await?;
+        Ok(Self {
+            cell_id: unsigned_zome_call.cell_id,
+            zome_name: unsigned_zome_call.zome_name,
+            fn_name: unsigned_zome_call.fn_name,
+            payload: unsigned_zome_call.payload,
+            cap_secret: unsigned_zome_call.cap_secret,
+            provenance: unsigned_zome_call.provenance,
+            nonce: unsigned_zome_call.nonce,
+            expires_at: unsigned_zome_call.expires_at,
+            signature,
+            delegation,
         })
     }
 
@@ -340,6 +566,36 @@ impl ZomeCall {
     }
 }
 
+/// Let `delegate` sign calls to some functions of a cell on behalf of `delegator`,
+/// until `expires_at`.
+///
+/// `delegator` is the provenance of the calls for the first delegation of a chain,
+/// and the delegate of the delegation before it otherwise. `cap_secret` is the secret
+/// of a cap grant on the source chain of the cell which is assigned to `delegate`,
+/// and deleting that grant revokes the delegation.
+pub async fn sign_zome_call_delegation(
+    keystore: &MetaLairClient,
+    delegator: &AgentPubKey,
+    delegate: AgentPubKey,
+    cell_id: CellId,
+    functions: GrantedFunctions,
+    cap_secret: CapSecret,
+    expires_at: Timestamp,
+) -> LairResult<ZomeCallDelegation> {
+    let data =
+        ZomeCallDelegation::data_to_sign(&delegate, &cell_id, &functions, &cap_secret, expires_at)
+            .map_err(|e| e.to_string())?;
+    let signature = delegator.sign_raw(keystore, data).await?;
+    Ok(ZomeCallDelegation {
+        delegate,
+        cell_id,
+        functions,
+        cap_secret,
+        expires_at,
+        signature,
+    })
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum CellInfo {
@@ -575,6 +831,9 @@ pub struct NetworkInfo {
     pub total_network_peers: u32,
     pub bytes_since_last_time_queried: u64,
     pub completed_rounds_since_last_time_queried: u32,
+    /// The estimated redundancy of each region of the DHT which the agent holds.
+    #[serde(default)]
+    pub region_redundancy: Vec<RegionRedundancy>,
 }
 
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
@@ -54,28 +54,44 @@
 //! ```
 
 use crate::conductor::process::ERROR_CODE;
-use holochain_types::prelude::DbSyncStrategy;
+use holochain_types::prelude::{AgentPubKeyB64, DbSyncStrategy, DnaHashB64};
 use kitsune_p2p_types::config::{KitsuneP2pConfig, KitsuneP2pTuningParams};
 use serde::de::DeserializeOwned;
 use serde::Deserialize;
 use serde::Serialize;
 
 mod admin_interface_config;
+mod bundle_signing_config;
+mod config_reload;
 mod dpki_config;
 #[allow(missing_docs)]
 mod error;
+mod health_check_config;
 mod keystore_config;
+mod logging_config;
+mod metrics_config;
 /// Defines subdirectories of the config directory.
 pub mod paths;
 pub mod process;
+#[cfg(feature = "remote_signing")]
+mod remote_signing_config;
+mod trace_export_config;
 //mod logger_config;
 //mod signal_config;
 
 pub use super::*;
+pub use bundle_signing_config::BundleSigningConfig;
+pub use config_reload::{ConfigReload, ConfigReloadReport};
 pub use dpki_config::DpkiConfig;
 //pub use logger_config::LoggerConfig;
 pub use error::*;
+pub use health_check_config::HealthCheckConfig;
 pub use keystore_config::KeystoreConfig;
+pub use logging_config::{LogFormat, LoggingConfig};
+pub use metrics_config::MetricsConfig;
+#[cfg(feature = "remote_signing")]
+pub use remote_signing_config::RemoteSigningConfig;
+pub use trace_export_config::TraceExportConfig;
 //pub use signal_config::SignalConfig;
 use std::path::Path;
 
@@ -89,6 +105,11 @@ pub struct ConductorConfig {
     #[serde(default)]
     pub tracing_override: Option<String>,
 
+    /// Optional log output configuration.
+    /// Any `tracing_override` takes precedence over the levels set here.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logging: Option<LoggingConfig>,
+
     /// The path to the data root for this conductor;
     /// This can be `None` while building up the config programatically but MUST
     /// be set by the time the config is used to build a conductor.
@@ -98,6 +119,9 @@ pub struct ConductorConfig {
     /// The lair tag used to refer to the "device seed" which was used to generate
     /// the AgentPubKey for the DPKI cell.
     ///
+    /// Apps installed without an agent key get a key derived from this seed too.
+    /// See [`AgentKeyDerivation`](crate::AgentKeyDerivation) for the derivation paths.
+    ///
     /// This must not be changed once the conductor has been started for the first time.
     pub device_seed_lair_tag: Option<String>,
 
@@ -122,6 +146,10 @@ pub struct ConductorConfig {
     #[serde(default)]
     pub dpki: DpkiConfig,
 
+    /// Which app bundles may be installed, based on who signed them.
+    #[serde(default)]
+    pub bundle_signing: BundleSigningConfig,
+
     /// Setup admin interfaces to control this conductor through a websocket connection.
     pub admin_interfaces: Option<Vec<AdminInterfaceConfig>>,
 
@@ -136,6 +164,27 @@ pub struct ConductorConfig {
     #[cfg(feature = "chc")]
     pub chc_url: Option<url2::Url2>,
 
+    /// Optional remote signing service which holds some or all of the agent keys.
+    /// If set, signing with any key the service holds is forwarded to it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[cfg(feature = "remote_signing")]
+    pub remote_signing: Option<RemoteSigningConfig>,
+
+    /// Optional listener for collecting conductor metrics.
+    /// If not set, metrics are only reported if enabled through the
+    /// environment variables described in the `holochain_metrics` crate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub metrics: Option<MetricsConfig>,
+
+    /// Optional HTTP listener serving the conductor's health report.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub health_check: Option<HealthCheckConfig>,
+
+    /// Optional destination to export tracing spans to,
+    /// in addition to logging them.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace_export: Option<TraceExportConfig>,
+
     /// Override the default database synchronous strategy.
     ///
     /// See [sqlite documentation] for information about database sync levels.
@@ -243,6 +292,143 @@ pub struct ConductorTuningParams {
     ///
     /// Default: 5 minutes
     pub min_publish_interval: Option<std::time::Duration>,
+    /// The maximum number of wasm operations that a single zome call or callback may execute.
+    ///
+    /// A call that runs out of operations is terminated and fails with an error saying that
+    /// its execution budget was exceeded. This protects a conductor that is shared by many apps
+    /// from a zome that accidentally loops forever.
+    ///
+    /// Default: the limit of the wasm engine, which is 100 billion operations
+    pub wasm_metering_limit: Option<u64>,
+    /// The longest that a `must_get_*` host function may ask to wait for a single network attempt.
+    ///
+    /// Zomes that ask for a longer timeout are given this one instead.
+    ///
+    /// Default: 60 seconds
+    pub must_get_max_timeout: Option<std::time::Duration>,
+    /// The largest number of retries that a `must_get_*` host function may ask for.
+    ///
+    /// Zomes that ask for more retries are given this many instead.
+    ///
+    /// Default: 3
+    pub must_get_max_retries: Option<u8>,
+    /// Zome calls which take longer than this are recorded as slow operations.
+    ///
+    /// Default: 5 seconds
+    pub slow_zome_call_threshold: Option<std::time::Duration>,
+    /// Workflow runs which take longer than this are recorded as slow operations.
+    ///
+    /// Default: 30 seconds
+    pub slow_workflow_threshold: Option<std::time::Duration>,
+    /// The largest message, in bytes, which the admin and app websocket interfaces will send
+    /// or receive.
+    ///
+    /// A client which sends a larger message is disconnected with a `1009` (message too big)
+    /// close frame. A response which would be larger is replaced with an error response.
+    ///
+    /// Default: 16 MiB
+    pub websocket_max_message_size: Option<usize>,
+    /// How many validation receipts an authored op needs before it stops being republished,
+    /// for DNAs which don't set `required_validation_receipts` in their manifest.
+    ///
+    /// The ops of app entries use the `required_validations` of their entry def instead.
+    ///
+    /// Default: 5
+    pub default_required_validation_receipts: Option<u8>,
+    /// How many tokio runtimes to run workflows on, apart from the runtime which handles
+    /// zome calls and interface requests.
+    ///
+    /// The DNAs of the conductor are spread across these runtimes, so that heavy validation
+    /// and integration for one DNA only competes with the DNAs on the same runtime, and never
+    /// with zome calls. The cells of a DNA share a runtime, since they share its validation
+    /// and integration workflows, unless the `workflow_runtime_assignments` say otherwise.
+    ///
+    /// Only read when the conductor starts.
+    ///
+    /// Default: 0, which runs workflows on the same runtime as zome calls
+    pub workflow_runtime_count: Option<usize>,
+    /// How many worker threads each of the `workflow_runtime_count` runtimes has.
+    ///
+    /// Only read when the conductor starts.
+    ///
+    /// Default: 2
+    pub workflow_runtime_threads: Option<usize>,
+    /// Runs the workflows of particular DNAs or cells on the workflow runtime of
+    /// one's choosing, instead of the one chosen by the location of the DNA hash.
+    ///
+    /// The conductor fails to start if an assignment names a runtime beyond the
+    /// `workflow_runtime_count`.
+    ///
+    /// Only read when the conductor starts.
+    ///
+    /// Default: none
+    pub workflow_runtime_assignments: Option<Vec<WorkflowRuntimeAssignment>>,
+    /// How long the conductor waits, when it shuts down, for in-flight zome calls and
+    /// workflow runs to complete before it stops the network and closes its databases.
+    ///
+    /// Default: 10 seconds
+    pub shutdown_drain_timeout: Option<std::time::Duration>,
+    /// Whether the conductor checks the chain heads and databases of its cells when it starts,
+    /// so that problems are reported in its startup report rather than at the first use of a cell.
+    ///
+    /// Default: false
+    pub verify_integrity_on_startup: Option<bool>,
+    /// How many of the hashes which are read most often from the network are prefetched
+    /// for each DNA every `prefetch_interval`.
+    ///
+    /// A hash is only prefetched if it was read more than once since the last prefetch.
+    /// Until the next prefetch, reads of a prefetched hash are answered from the cache
+    /// without waiting on the network.
+    ///
+    /// Default: 0, which doesn't prefetch
+    pub prefetch_hot_hashes: Option<usize>,
+    /// How often the hashes which are read most often from the network are prefetched.
+    ///
+    /// Default: 30 seconds
+    pub prefetch_interval: Option<std::time::Duration>,
+    /// How long the records which the cells of apps installed with `client_only` fetch
+    /// from the network are read from the cache before they are fetched again.
+    ///
+    /// Default: 5 minutes
+    pub client_cache_fresh_for: Option<std::time::Duration>,
+    /// How many authorities a region of the DHT which a local agent holds should be
+    /// estimated to have. When a region has fewer, a warning is logged and a
+    /// [`SystemSignal::LowRedundancy`](holochain_types::signal::SystemSignal::LowRedundancy)
+    /// is sent to the apps with a cell of the DNA.
+    ///
+    /// Default: 0, which doesn't warn
+    pub min_region_redundancy: Option<u32>,
+    /// How many ops of a DNA may wait to be validated and integrated before the conductor
+    /// stops taking in more ops of the DNA from publish and gossip. Peers are told that
+    /// the conductor is busy and try again later.
+    ///
+    /// Default: 0, which doesn't limit
+    pub ingest_max_backlog: Option<usize>,
+    /// How many megabytes of memory the conductor may use before it stops taking in ops
+    /// from publish and gossip until its usage drops again.
+    ///
+    /// Default: 0, which doesn't limit
+    pub ingest_max_memory_mb: Option<u64>,
+    /// How many zome calls from app interfaces may run at once. Further calls wait
+    /// for their turn, and the apps with waiting calls take turns, so that an app which
+    /// makes many calls doesn't keep the calls of other apps waiting behind its own.
+    ///
+    /// The calls which zome calls make to other cells, and calls from remote agents,
+    /// don't wait for a turn.
+    ///
+    /// Default: 0, which doesn't limit
+    pub zome_call_concurrency: Option<usize>,
+    /// How far the clock of this conductor may be estimated to be from the clocks of its
+    /// peers before a warning is logged. The skew is estimated from when the agent
+    /// infos which peers publish were signed.
+    ///
+    /// Default: 30 seconds
+    pub clock_skew_threshold: Option<std::time::Duration>,
+    /// Whether to correct the timestamps of new actions, and the time which zomes are
+    /// given, by the estimated skew once it passes `clock_skew_threshold`.
+    ///
+    /// Default: false
+    pub clock_skew_correction: Option<bool>,
 }
 
 impl ConductorTuningParams {
@@ -253,6 +439,27 @@ impl ConductorTuningParams {
             countersigning_resolution_retry_delay: None,
             countersigning_resolution_retry_limit: None,
             min_publish_interval: None,
+            wasm_metering_limit: None,
+            must_get_max_timeout: None,
+            must_get_max_retries: None,
+            slow_zome_call_threshold: None,
+            slow_workflow_threshold: None,
+            websocket_max_message_size: None,
+            default_required_validation_receipts: None,
+            workflow_runtime_count: None,
+            workflow_runtime_threads: None,
+            workflow_runtime_assignments: None,
+            shutdown_drain_timeout: None,
+            verify_integrity_on_startup: None,
+            prefetch_hot_hashes: None,
+            prefetch_interval: None,
+            client_cache_fresh_for: None,
+            min_region_redundancy: None,
+            ingest_max_backlog: None,
+            ingest_max_memory_mb: None,
+            zome_call_concurrency: None,
+            clock_skew_threshold: None,
+            clock_skew_correction: None,
         }
     }
 
@@ -273,6 +480,131 @@ impl ConductorTuningParams {
         self.min_publish_interval
             .unwrap_or_else(|| std::time::Duration::from_secs(60 * 5))
     }
+
+    /// Get the current value of `must_get_max_timeout` or its default value.
+    pub fn must_get_max_timeout(&self) -> std::time::Duration {
+        self.must_get_max_timeout
+            .unwrap_or_else(|| std::time::Duration::from_secs(60))
+    }
+
+    /// Get the current value of `must_get_max_retries` or its default value.
+    pub fn must_get_max_retries(&self) -> u8 {
+        self.must_get_max_retries.unwrap_or(3)
+    }
+
+    /// Get the current value of `slow_zome_call_threshold` or its default value.
+    pub fn slow_zome_call_threshold(&self) -> std::time::Duration {
+        self.slow_zome_call_threshold
+            .unwrap_or_else(|| std::time::Duration::from_secs(5))
+    }
+
+    /// Get the current value of `slow_workflow_threshold` or its default value.
+    pub fn slow_workflow_threshold(&self) -> std::time::Duration {
+        self.slow_workflow_threshold
+            .unwrap_or_else(|| std::time::Duration::from_secs(30))
+    }
+
+    /// Get the current value of `websocket_max_message_size` or its default value.
+    pub fn websocket_max_message_size(&self) -> usize {
+        self.websocket_max_message_size.unwrap_or(16 << 20)
+    }
+
+    /// Get the current value of `default_required_validation_receipts` or its default value.
+    pub fn default_required_validation_receipts(&self) -> u8 {
+        self.default_required_validation_receipts.unwrap_or(5)
+    }
+
+    /// Get the current value of `workflow_runtime_count` or its default value.
+    pub fn workflow_runtime_count(&self) -> usize {
+        self.workflow_runtime_count.unwrap_or(0)
+    }
+
+    /// Get the current value of `workflow_runtime_threads` or its default value.
+    pub fn workflow_runtime_threads(&self) -> usize {
+        self.workflow_runtime_threads.unwrap_or(2)
+    }
+
+    /// Get the current value of `workflow_runtime_assignments` or its default value.
+    pub fn workflow_runtime_assignments(&self) -> Vec<WorkflowRuntimeAssignment> {
+        self.workflow_runtime_assignments
+            .clone()
+            .unwrap_or_default()
+    }
+
+    /// Get the current value of `shutdown_drain_timeout` or its default value.
+    pub fn shutdown_drain_timeout(&self) -> std::time::Duration {
+        self.shutdown_drain_timeout
+            .unwrap_or_else(|| std::time::Duration::from_secs(10))
+    }
+
+    /// Get the current value of `verify_integrity_on_startup` or its default value.
+    pub fn verify_integrity_on_startup(&self) -> bool {
+        self.verify_integrity_on_startup.unwrap_or(false)
+    }
+
+    /// Get the current value of `prefetch_hot_hashes` or its default value.
+    pub fn prefetch_hot_hashes(&self) -> usize {
+        self.prefetch_hot_hashes.unwrap_or(0)
+    }
+
+    /// Get the current value of `prefetch_interval` or its default value.
+    pub fn prefetch_interval(&self) -> std::time::Duration {
+        self.prefetch_interval
+            .unwrap_or_else(|| std::time::Duration::from_secs(30))
+    }
+
+    /// Get the current value of `client_cache_fresh_for` or its default value.
+    pub fn client_cache_fresh_for(&self) -> std::time::Duration {
+        self.client_cache_fresh_for
+            .unwrap_or_else(|| std::time::Duration::from_secs(5 * 60))
+    }
+
+    /// Get the current value of `min_region_redundancy` or its default value.
+    pub fn min_region_redundancy(&self) -> u32 {
+        self.min_region_redundancy.unwrap_or(0)
+    }
+
+    /// Get the current value of `ingest_max_backlog` or its default value.
+    pub fn ingest_max_backlog(&self) -> usize {
+        self.ingest_max_backlog.unwrap_or(0)
+    }
+
+    /// Get the current value of `ingest_max_memory_mb` or its default value.
+    pub fn ingest_max_memory_mb(&self) -> u64 {
+        self.ingest_max_memory_mb.unwrap_or(0)
+    }
+
+    /// Get the current value of `zome_call_concurrency` or its default value.
+    pub fn zome_call_concurrency(&self) -> usize {
+        self.zome_call_concurrency.unwrap_or(0)
+    }
+
+    /// Get the current value of `clock_skew_threshold` or its default value.
+    pub fn clock_skew_threshold(&self) -> std::time::Duration {
+        self.clock_skew_threshold
+            .unwrap_or_else(|| std::time::Duration::from_secs(30))
+    }
+
+    /// Get the current value of `clock_skew_correction` or its default value.
+    pub fn clock_skew_correction(&self) -> bool {
+        self.clock_skew_correction.unwrap_or(false)
+    }
+}
+
+/// Runs the workflows of a DNA, or of one of its cells, on one of the workflow runtimes.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct WorkflowRuntimeAssignment {
+    /// The DNA whose workflows are assigned.
+    pub dna_hash: DnaHashB64,
+    /// Only assign the workflows of the cell of this agent.
+    ///
+    /// Without an agent, the workflows which the cells of the DNA share, such as
+    /// app validation and integration, are assigned along with those of every cell
+    /// that has no assignment of its own.
+    #[serde(default)]
+    pub agent: Option<AgentPubKeyB64>,
+    /// The index of the runtime, from 0 to one less than the `workflow_runtime_count`.
+    pub runtime: usize,
 }
 
 impl Default for ConductorTuningParams {
@@ -285,6 +617,27 @@ impl Default for ConductorTuningParams {
             ),
             countersigning_resolution_retry_limit: None,
             min_publish_interval: None,
+            wasm_metering_limit: None,
+            must_get_max_timeout: None,
+            must_get_max_retries: None,
+            slow_zome_call_threshold: None,
+            slow_workflow_threshold: None,
+            websocket_max_message_size: None,
+            default_required_validation_receipts: None,
+            workflow_runtime_count: None,
+            workflow_runtime_threads: None,
+            workflow_runtime_assignments: None,
+            shutdown_drain_timeout: None,
+            verify_integrity_on_startup: None,
+            prefetch_hot_hashes: None,
+            prefetch_interval: None,
+            client_cache_fresh_for: None,
+            min_region_redundancy: None,
+            ingest_max_backlog: None,
+            ingest_max_memory_mb: None,
+            zome_call_concurrency: None,
+            clock_skew_threshold: None,
+            clock_skew_correction: None,
         }
     }
 }
@@ -331,16 +684,23 @@ mod tests {
             result,
             ConductorConfig {
                 tracing_override: None,
+                logging: None,
                 data_root_path: Some(PathBuf::from("/path/to/env").into()),
                 device_seed_lair_tag: None,
                 danger_generate_throwaway_device_seed: false,
                 network: KitsuneP2pConfig::mem(),
+                bundle_signing: Default::default(),
                 dpki: DpkiConfig::default(),
                 keystore: KeystoreConfig::DangerTestKeystore,
                 admin_interfaces: None,
                 db_sync_strategy: DbSyncStrategy::default(),
                 #[cfg(feature = "chc")]
                 chc_url: None,
+                #[cfg(feature = "remote_signing")]
+                remote_signing: None,
+                metrics: None,
+                health_check: None,
+                trace_export: None,
                 tuning_params: None,
             }
         );
@@ -427,9 +787,11 @@ mod tests {
             result.unwrap(),
             ConductorConfig {
                 tracing_override: None,
+                logging: None,
                 data_root_path: Some(PathBuf::from("/path/to/env").into()),
                 device_seed_lair_tag: None,
                 danger_generate_throwaway_device_seed: false,
+                bundle_signing: Default::default(),
                 dpki: DpkiConfig::disabled(),
                 keystore: KeystoreConfig::LairServerInProc { lair_root: None },
                 admin_interfaces: Some(vec![AdminInterfaceConfig {
@@ -442,6 +804,11 @@ mod tests {
                 db_sync_strategy: DbSyncStrategy::Fast,
                 #[cfg(feature = "chc")]
                 chc_url: None,
+                #[cfg(feature = "remote_signing")]
+                remote_signing: None,
+                metrics: None,
+                health_check: None,
+                trace_export: None,
                 tuning_params: None,
             }
         );
@@ -528,9 +895,11 @@ mod tests {
             result.unwrap(),
             ConductorConfig {
                 tracing_override: None,
+                logging: None,
                 data_root_path: Some(PathBuf::from("/path/to/env").into()),
                 device_seed_lair_tag: None,
                 danger_generate_throwaway_device_seed: false,
+                bundle_signing: Default::default(),
                 dpki: DpkiConfig::production(Some("path/to/dna.dna".into())),
                 keystore: KeystoreConfig::LairServerInProc { lair_root: None },
                 admin_interfaces: Some(vec![AdminInterfaceConfig {
@@ -543,6 +912,11 @@ mod tests {
                 db_sync_strategy: DbSyncStrategy::Fast,
                 #[cfg(feature = "chc")]
                 chc_url: None,
+                #[cfg(feature = "remote_signing")]
+                remote_signing: None,
+                metrics: None,
+                health_check: None,
+                trace_export: None,
                 tuning_params: None,
             }
         );
@@ -565,10 +939,12 @@ mod tests {
             result.unwrap(),
             ConductorConfig {
                 tracing_override: None,
+                logging: None,
                 data_root_path: Some(PathBuf::from("/path/to/env").into()),
                 device_seed_lair_tag: None,
                 danger_generate_throwaway_device_seed: false,
                 network: KitsuneP2pConfig::mem(),
+                bundle_signing: Default::default(),
                 dpki: Default::default(),
                 keystore: KeystoreConfig::LairServer {
                     connection_url: url2::url2!("unix:///var/run/lair-keystore/socket?k=EcRDnP3xDIZ9Rk_1E-egPE0mGZi5CcszeRxVkb2QXXQ"),
@@ -577,11 +953,52 @@ mod tests {
                 db_sync_strategy: DbSyncStrategy::Resilient,
                 #[cfg(feature = "chc")]
                 chc_url: None,
+                #[cfg(feature = "remote_signing")]
+                remote_signing: None,
+                metrics: None,
+                health_check: None,
+                trace_export: None,
                 tuning_params: None,
             }
         );
     }
 
+    #[test]
+    fn test_config_prometheus_metrics() {
+        let yaml = r#"---
+    data_root_path: /path/to/env
+    metrics:
+      type: prometheus
+      bind_address: "127.0.0.1:9100"
+    "#;
+        let result: ConductorConfig = config_from_yaml(yaml).unwrap();
+        assert_eq!(
+            result.metrics,
+            Some(MetricsConfig::Prometheus {
+                bind_address: "127.0.0.1:9100".parse().unwrap(),
+            })
+        );
+    }
+
+    #[test]
+    fn test_config_otlp_trace_export() {
+        let yaml = r#"---
+    data_root_path: /path/to/env
+    trace_export:
+      type: otlp
+      endpoint: "http://localhost:4318"
+    "#;
+        let result: ConductorConfig = config_from_yaml(yaml).unwrap();
+        assert_eq!(
+            result.trace_export,
+            Some(TraceExportConfig::Otlp {
+                endpoint: url2::url2!("http://localhost:4318"),
+                service_name: None,
+                filter: None,
+            })
+        );
+    }
+
     #[test]
     #[cfg(not(feature = "unstable-sharding"))]
     fn test_config_default_network_config_no_sharding() {
@@ -0,0 +1,36 @@
+use holo_hash::AgentPubKey;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Define which app bundles the conductor will install, based on the
+/// publisher signatures embedded in them by `hc app pack --sign`.
+///
+/// A bundle with a signature which doesn't match its contents is always
+/// rejected. With the default settings, unsigned bundles are accepted.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct BundleSigningConfig {
+    /// The public keys of the publishers whose signatures are trusted.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trusted_publishers: Vec<AgentPubKey>,
+
+    /// Only install app bundles which are signed by one of the
+    /// `trusted_publishers`. Unsigned bundles and bundles signed by
+    /// anyone else are rejected by `InstallApp`.
+    #[serde(default)]
+    pub require_trusted_signature: bool,
+}
+
+impl BundleSigningConfig {
+    /// Check the publisher of a bundle against this policy, where `signer` is
+    /// the key that validly signed the bundle, if any.
+    pub fn check_publisher(&self, signer: Option<&AgentPubKey>) -> Result<(), String> {
+        if !self.require_trusted_signature {
+            return Ok(());
+        }
+        match signer {
+            None => Err("the bundle is not signed".to_string()),
+            Some(signer) if self.trusted_publishers.contains(signer) => Ok(()),
+            Some(signer) => Err(format!("{signer} is not a trusted publisher")),
+        }
+    }
+}
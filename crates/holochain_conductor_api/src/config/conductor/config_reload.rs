@@ -0,0 +1,437 @@
+use super::{AdminInterfaceConfig, ConductorConfig, LoggingConfig};
+use crate::config::InterfaceDriver;
+use holochain_types::websocket::AllowedOrigins;
+use serde::Deserialize;
+use serde::Serialize;
+
+/// Which settings changed when the conductor config file was reloaded with
+/// [`AdminRequest::ReloadConfig`](crate::AdminRequest::ReloadConfig).
+///
+/// Settings are named by their key in the config file, e.g. `tuning_params`.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct ConfigReloadReport {
+    /// The settings which changed and have been applied.
+    pub applied: Vec<String>,
+
+    /// The settings which changed but were skipped,
+    /// because the conductor has to be restarted to change them.
+    pub requires_restart: Vec<String>,
+}
+
+/// The changes to make to a running conductor when its config file is reloaded.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConfigReload {
+    /// The running config, with every setting which can be changed
+    /// without a restart taken from the reloaded config.
+    pub config: ConductorConfig,
+
+    /// Whether the log filter directives changed.
+    pub log_filter_changed: bool,
+
+    /// The new allowed origins of admin interfaces which changed,
+    /// by their position in `admin_interfaces`.
+    pub admin_interface_origins: Vec<(usize, AllowedOrigins)>,
+
+    /// Which settings changed.
+    pub report: ConfigReloadReport,
+}
+
+impl ConductorConfig {
+    /// The log filter directives set by `tracing_override`, or otherwise by `logging`,
+    /// in the same format as `RUST_LOG`.
+    pub fn log_filter_directives(&self) -> Option<String> {
+        self.tracing_override.clone().or_else(|| {
+            self.logging
+                .as_ref()
+                .and_then(LoggingConfig::filter_directives)
+        })
+    }
+
+    /// Work out which settings of a `reloaded` config can be applied to a conductor
+    /// which is running with this config.
+    ///
+    /// Log levels, conductor tuning params and the allowed origins of admin interfaces
+    /// can be changed. Everything else, including the network config, is only read
+    /// on startup, and so are the tuning params of the workflow runtimes, the slow
+    /// operation thresholds, the clock skew monitor and `zome_call_concurrency`.
+    pub fn reload(&self, reloaded: ConductorConfig) -> ConfigReload {
+        // Destructured so that new settings have to be sorted into one of the groups below.
+        let ConductorConfig {
+            tracing_override,
+            logging,
+            data_root_path,
+            device_seed_lair_tag,
+            danger_generate_throwaway_device_seed,
+            keystore,
+            dpki,
+            bundle_signing,
+            admin_interfaces,
+            network,
+            #[cfg(feature = "chc")]
+            chc_url,
+            #[cfg(feature = "remote_signing")]
+            remote_signing,
+            metrics,
+            health_check,
+            trace_export,
+            db_sync_strategy,
+            tuning_params,
+        } = reloaded;
+
+        let mut config = self.clone();
+        let mut report = ConfigReloadReport::default();
+
+        if tracing_override != self.tracing_override {
+            report.applied.push("tracing_override".to_string());
+            config.tracing_override = tracing_override;
+        }
+
+        let running_logging = self.logging.clone().unwrap_or_default();
+        let logging = logging.unwrap_or_default();
+        if logging.format != running_logging.format {
+            report.requires_restart.push("logging.format".to_string());
+        }
+        if (&logging.level, &logging.targets) != (&running_logging.level, &running_logging.targets)
+        {
+            report.applied.push("logging".to_string());
+            config.logging = Some(LoggingConfig {
+                format: running_logging.format,
+                ..logging
+            });
+        }
+
+        // Some tuning params are only read on startup: the workflow runtimes, the slow
+        // operation log, the clock skew monitor and the zome call scheduler are created
+        // once. The running values of those are kept.
+        let running = self.conductor_tuning_params();
+        let running_set = self.tuning_params.clone().unwrap_or_default();
+        let mut tuning_params = tuning_params;
+        if let Some(reloaded) = &mut tuning_params {
+            let mut startup_only = |name: &str, changed: bool| {
+                if changed {
+                    report
+                        .requires_restart
+                        .push(format!("tuning_params.{name}"));
+                }
+                changed
+            };
+            if startup_only(
+                "workflow_runtimes",
+                (
+                    reloaded.workflow_runtime_count(),
+                    reloaded.workflow_runtime_threads(),
+                    reloaded.workflow_runtime_assignments(),
+                ) != (
+                    running.workflow_runtime_count(),
+                    running.workflow_runtime_threads(),
+                    running.workflow_runtime_assignments(),
+                ),
+            ) {
+                reloaded.workflow_runtime_count = running_set.workflow_runtime_count;
+                reloaded.workflow_runtime_threads = running_set.workflow_runtime_threads;
+                reloaded.workflow_runtime_assignments =
+                    running_set.workflow_runtime_assignments.clone();
+            }
+            if startup_only(
+                "slow_zome_call_threshold",
+                reloaded.slow_zome_call_threshold() != running.slow_zome_call_threshold(),
+            ) {
+                reloaded.slow_zome_call_threshold = running_set.slow_zome_call_threshold;
+            }
+            if startup_only(
+                "slow_workflow_threshold",
+                reloaded.slow_workflow_threshold() != running.slow_workflow_threshold(),
+            ) {
+                reloaded.slow_workflow_threshold = running_set.slow_workflow_threshold;
+            }
+            if startup_only(
+                "clock_skew_threshold",
+                reloaded.clock_skew_threshold() != running.clock_skew_threshold(),
+            ) {
+                reloaded.clock_skew_threshold = running_set.clock_skew_threshold;
+            }
+            if startup_only(
+                "clock_skew_correction",
+                reloaded.clock_skew_correction() != running.clock_skew_correction(),
+            ) {
+                reloaded.clock_skew_correction = running_set.clock_skew_correction;
+            }
+            if startup_only(
+                "zome_call_concurrency",
+                reloaded.zome_call_concurrency() != running.zome_call_concurrency(),
+            ) {
+                reloaded.zome_call_concurrency = running_set.zome_call_concurrency;
+            }
+        }
+        if tuning_params != self.tuning_params {
+            report.applied.push("tuning_params".to_string());
+            config.tuning_params = tuning_params;
+        }
+
+        let mut admin_interface_origins = Vec::new();
+        if admin_interfaces != self.admin_interfaces {
+            match changed_origins(
+                self.admin_interfaces.as_deref().unwrap_or_default(),
+                admin_interfaces.as_deref().unwrap_or_default(),
+            ) {
+                Some(changed) => {
+                    if !changed.is_empty() {
+                        report
+                            .applied
+                            .push("admin_interfaces.allowed_origins".to_string());
+                    }
+                    admin_interface_origins = changed;
+                    config.admin_interfaces = admin_interfaces;
+                }
+                None => report.requires_restart.push("admin_interfaces".to_string()),
+            }
+        }
+
+        #[allow(unused_mut)]
+        let mut startup_only = vec![
+            ("data_root_path", data_root_path == self.data_root_path),
+            (
+                "device_seed_lair_tag",
+                device_seed_lair_tag == self.device_seed_lair_tag,
+            ),
+            (
+                "danger_generate_throwaway_device_seed",
+                danger_generate_throwaway_device_seed == self.danger_generate_throwaway_device_seed,
+            ),
+            ("keystore", keystore == self.keystore),
+            ("dpki", dpki == self.dpki),
+            ("bundle_signing", bundle_signing == self.bundle_signing),
+            ("network", network == self.network),
+            ("metrics", metrics == self.metrics),
+            ("health_check", health_check == self.health_check),
+            ("trace_export", trace_export == self.trace_export),
+            (
+                "db_sync_strategy",
+                db_sync_strategy == self.db_sync_strategy,
+            ),
+        ];
+        #[cfg(feature = "chc")]
+        startup_only.push(("chc_url", chc_url == self.chc_url));
+        #[cfg(feature = "remote_signing")]
+        startup_only.push(("remote_signing", remote_signing == self.remote_signing));
+        report.requires_restart.extend(
+            startup_only
+                .into_iter()
+                .filter(|(_, unchanged)| !unchanged)
+                .map(|(name, _)| name.to_string()),
+        );
+
+        ConfigReload {
+            log_filter_changed: config.log_filter_directives() != self.log_filter_directives(),
+            config,
+            admin_interface_origins,
+            report,
+        }
+    }
+}
+
+/// The admin interfaces whose allowed origins changed, or `None` if any other part of
+/// the admin interfaces changed.
+fn changed_origins(
+    running: &[AdminInterfaceConfig],
+    reloaded: &[AdminInterfaceConfig],
+) -> Option<Vec<(usize, AllowedOrigins)>> {
+    if running.len() != reloaded.len() {
+        return None;
+    }
+    let mut changed = Vec::new();
+    for (index, (running, reloaded)) in running.iter().zip(reloaded).enumerate() {
+        let (
+            InterfaceDriver::Websocket {
+                port: running_port,
+                allowed_origins: running_origins,
+            },
+            InterfaceDriver::Websocket {
+                port: reloaded_port,
+                allowed_origins: reloaded_origins,
+            },
+        ) = (&running.driver, &reloaded.driver);
+        if running_port != reloaded_port {
+            return None;
+        }
+        if running_origins != reloaded_origins {
+            changed.push((index, reloaded_origins.clone()));
+        }
+    }
+    Some(changed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::conductor::ConductorTuningParams;
+    use crate::config::conductor::LogFormat;
+
+    fn admin_interface(port: u16, allowed_origins: &str) -> AdminInterfaceConfig {
+        AdminInterfaceConfig {
+            driver: InterfaceDriver::Websocket {
+                port,
+                allowed_origins: allowed_origins.to_string().into(),
+            },
+        }
+    }
+
+    #[test]
+    fn unchanged_config_reloads_nothing() {
+        let config = ConductorConfig {
+            admin_interfaces: Some(vec![admin_interface(1234, "*")]),
+            ..Default::default()
+        };
+        let reload = config.reload(config.clone());
+        assert_eq!(ConfigReloadReport::default(), reload.report);
+        assert!(!reload.log_filter_changed);
+        assert!(reload.admin_interface_origins.is_empty());
+        assert_eq!(config, reload.config);
+    }
+
+    #[test]
+    fn changeable_settings_are_applied() {
+        let running = ConductorConfig {
+            admin_interfaces: Some(vec![
+                admin_interface(1234, "*"),
+                admin_interface(1235, "http://localhost:3000"),
+            ]),
+            ..Default::default()
+        };
+        let reloaded = ConductorConfig {
+            logging: Some(LoggingConfig {
+                level: Some("debug".to_string()),
+                ..Default::default()
+            }),
+            tuning_params: Some(ConductorTuningParams {
+                sys_validation_retry_delay: Some(std::time::Duration::from_secs(5)),
+                ..ConductorTuningParams::new()
+            }),
+            admin_interfaces: Some(vec![
+                admin_interface(1234, "*"),
+                admin_interface(1235, "http://localhost:3001"),
+            ]),
+            ..Default::default()
+        };
+
+        let reload = running.reload(reloaded.clone());
+        assert_eq!(
+            vec![
+                "logging".to_string(),
+                "tuning_params".to_string(),
+                "admin_interfaces.allowed_origins".to_string(),
+            ],
+            reload.report.applied
+        );
+        assert!(reload.report.requires_restart.is_empty());
+        assert!(reload.log_filter_changed);
+        assert_eq!(
+            vec![(1, "http://localhost:3001".to_string().into())],
+            reload.admin_interface_origins
+        );
+        assert_eq!(reloaded, reload.config);
+    }
+
+    #[test]
+    fn startup_only_settings_are_skipped() {
+        let running = ConductorConfig {
+            logging: Some(LoggingConfig {
+                level: Some("info".to_string()),
+                ..Default::default()
+            }),
+            admin_interfaces: Some(vec![admin_interface(1234, "*")]),
+            tuning_params: Some(ConductorTuningParams::new()),
+            ..Default::default()
+        };
+        let mut reloaded = running.clone();
+        reloaded.logging = Some(LoggingConfig {
+            format: Some(LogFormat::Json),
+            level: Some("info".to_string()),
+            ..Default::default()
+        });
+        reloaded.admin_interfaces = Some(vec![admin_interface(4321, "http://localhost:3000")]);
+        reloaded.network.bootstrap_service = Some(url2::url2!("https://bootstrap.example"));
+        reloaded.device_seed_lair_tag = Some("device-seed".to_string());
+        reloaded.tuning_params = Some(ConductorTuningParams {
+            workflow_runtime_count: Some(4),
+            ..ConductorTuningParams::new()
+        });
+
+        let reload = running.reload(reloaded);
+        assert!(reload.report.applied.is_empty());
+        assert_eq!(
+            vec![
+                "logging.format".to_string(),
+                "tuning_params.workflow_runtimes".to_string(),
+                "admin_interfaces".to_string(),
+                "device_seed_lair_tag".to_string(),
+                "network".to_string(),
+            ],
+            reload.report.requires_restart
+        );
+        assert!(!reload.log_filter_changed);
+        assert!(reload.admin_interface_origins.is_empty());
+        // The running config is kept for settings which need a restart.
+        assert_eq!(running, reload.config);
+    }
+
+    #[test]
+    fn tuning_params_read_on_startup_are_skipped() {
+        let running = ConductorConfig {
+            tuning_params: Some(ConductorTuningParams::new()),
+            ..Default::default()
+        };
+        let reloaded = ConductorConfig {
+            tuning_params: Some(ConductorTuningParams {
+                slow_zome_call_threshold: Some(std::time::Duration::from_millis(10)),
+                zome_call_concurrency: Some(4),
+                min_publish_interval: Some(std::time::Duration::from_secs(30)),
+                ..ConductorTuningParams::new()
+            }),
+            ..Default::default()
+        };
+
+        let reload = running.reload(reloaded);
+        assert_eq!(vec!["tuning_params".to_string()], reload.report.applied);
+        assert_eq!(
+            vec![
+                "tuning_params.slow_zome_call_threshold".to_string(),
+                "tuning_params.zome_call_concurrency".to_string(),
+            ],
+            reload.report.requires_restart
+        );
+        // Only the tuning params which are read as they're used are changed.
+        assert_eq!(
+            Some(ConductorTuningParams {
+                min_publish_interval: Some(std::time::Duration::from_secs(30)),
+                ..ConductorTuningParams::new()
+            }),
+            reload.config.tuning_params
+        );
+    }
+
+    #[test]
+    fn tracing_override_takes_precedence_over_logging_levels() {
+        let running = ConductorConfig {
+            tracing_override: Some("warn".to_string()),
+            ..Default::default()
+        };
+        let reloaded = ConductorConfig {
+            tracing_override: Some("warn".to_string()),
+            logging: Some(LoggingConfig {
+                level: Some("debug".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let reload = running.reload(reloaded);
+        assert_eq!(vec!["logging".to_string()], reload.report.applied);
+        // The levels changed, but the overridden filter didn't.
+        assert!(!reload.log_filter_changed);
+        assert_eq!(
+            Some("warn".to_string()),
+            reload.config.log_filter_directives()
+        );
+    }
+}
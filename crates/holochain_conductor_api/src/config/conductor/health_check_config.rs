@@ -0,0 +1,17 @@
+use serde::Deserialize;
+use serde::Serialize;
+use std::net::SocketAddr;
+
+/// Serve the conductor's health report over HTTP, for process supervisors
+/// such as systemd or Kubernetes to poll.
+///
+/// `GET /health` responds with the [`HealthReport`](crate::HealthReport) as
+/// JSON, with status 200 if the conductor is healthy and 503 if it is not.
+///
+/// The listener has no authentication, so it should only be bound to
+/// an address which is not reachable from untrusted networks.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct HealthCheckConfig {
+    /// The address to listen on, e.g. `127.0.0.1:8889`.
+    pub bind_address: SocketAddr,
+}
@@ -33,6 +33,37 @@ pub enum KeystoreConfig {
         #[serde(default, skip_serializing_if = "Option::is_none")]
         lair_root: Option<KeystorePath>,
     },
+
+    /// Run lair embedded in the conductor process, calling into it directly
+    /// rather than through a lair server and its IPC socket. Nothing outside
+    /// this process can connect to the keystore. Good for tests and for
+    /// applications which embed a conductor.
+    LairEmbedded {
+        /// The "lair_root" path, i.e. the directory containing the
+        /// "lair-keystore-config.yaml" file. This is compatible with the
+        /// `lair_server_in_proc` keystore, so a conductor can switch between them.
+        /// If not specified, will default to the ConductorConfig
+        /// `[environment_path]/ks`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        lair_root: Option<KeystorePath>,
+
+        /// Keep all keys in memory, so that they are lost when the conductor
+        /// shuts down. Nothing is written to the `lair_root` and no
+        /// passphrase is needed.
+        #[serde(default)]
+        in_memory: bool,
+    },
+}
+
+impl KeystoreConfig {
+    /// Whether a passphrase is needed to unlock this keystore.
+    pub fn needs_passphrase(&self) -> bool {
+        match self {
+            KeystoreConfig::DangerTestKeystore => false,
+            KeystoreConfig::LairEmbedded { in_memory, .. } => !in_memory,
+            KeystoreConfig::LairServer { .. } | KeystoreConfig::LairServerInProc { .. } => true,
+        }
+    }
 }
 
 impl Default for KeystoreConfig {
@@ -0,0 +1,87 @@
+use serde::Deserialize;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Configure the conductor's log output.
+///
+/// Levels set here can be changed while the conductor is running with
+/// [`AdminRequest::SetLogFilter`](crate::AdminRequest::SetLogFilter), or by
+/// editing the config file and reloading it with
+/// [`AdminRequest::ReloadConfig`](crate::AdminRequest::ReloadConfig).
+/// The format can only be changed by restarting the conductor.
+#[derive(Deserialize, Serialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct LoggingConfig {
+    /// The format to write logs in.
+    /// If set, this takes precedence over the `--structured` command line flag.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub format: Option<LogFormat>,
+
+    /// The level to log at, for targets which don't have a level in `targets`,
+    /// e.g. `info`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+
+    /// Levels for individual targets, keyed by target (usually a module path),
+    /// e.g. `kitsune_p2p::gossip: debug`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub targets: BTreeMap<String, String>,
+}
+
+impl LoggingConfig {
+    /// The filter directives for these levels, in the same format as `RUST_LOG`.
+    /// Returns `None` if no levels are set.
+    pub fn filter_directives(&self) -> Option<String> {
+        let directives: Vec<String> = self
+            .level
+            .iter()
+            .cloned()
+            .chain(
+                self.targets
+                    .iter()
+                    .map(|(target, level)| format!("{target}={level}")),
+            )
+            .collect();
+        if directives.is_empty() {
+            None
+        } else {
+            Some(directives.join(","))
+        }
+    }
+}
+
+/// The format of the conductor's log output.
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum LogFormat {
+    /// Human readable lines.
+    Log,
+    /// More compact human readable lines.
+    Compact,
+    /// One JSON object per line, for log collectors to parse.
+    Json,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn filter_directives() {
+        assert_eq!(LoggingConfig::default().filter_directives(), None);
+
+        let config = LoggingConfig {
+            format: Some(LogFormat::Json),
+            level: Some("warn".to_string()),
+            targets: [
+                ("kitsune_p2p".to_string(), "info".to_string()),
+                ("holochain::core".to_string(), "debug".to_string()),
+            ]
+            .into_iter()
+            .collect(),
+        };
+        assert_eq!(
+            config.filter_directives().unwrap(),
+            "warn,holochain::core=debug,kitsune_p2p=info"
+        );
+    }
+}
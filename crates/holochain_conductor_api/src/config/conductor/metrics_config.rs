@@ -0,0 +1,18 @@
+use serde::Deserialize;
+use serde::Serialize;
+use std::net::SocketAddr;
+
+/// Define how the conductor exposes its metrics to be collected.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum MetricsConfig {
+    /// Serve the metrics over HTTP in the Prometheus text format,
+    /// for a Prometheus server to scrape from the `/metrics` path.
+    ///
+    /// The listener has no authentication, so it should only be bound to
+    /// an address which is not reachable from untrusted networks.
+    Prometheus {
+        /// The address to listen on, e.g. `127.0.0.1:9100`.
+        bind_address: SocketAddr,
+    },
+}
@@ -0,0 +1,20 @@
+use serde::Deserialize;
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Define a remote signing service, such as an HSM or key management service,
+/// which holds agent keys so that they never have to be on the conductor host.
+///
+/// The conductor signs with the service for every key it holds, and with
+/// its own keystore for every other key.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct RemoteSigningConfig {
+    /// The base URL of the signing service's HTTP API.
+    /// This should be an `https` URL.
+    pub url: url2::Url2,
+
+    /// A file containing the token which the conductor authenticates to the
+    /// signing service with. It is sent as a bearer token with every request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_token_file: Option<PathBuf>,
+}
@@ -0,0 +1,30 @@
+use serde::Deserialize;
+use serde::Serialize;
+use url2::Url2;
+
+/// Define where the conductor exports its tracing spans to.
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum TraceExportConfig {
+    /// Send spans to an OpenTelemetry collector using OTLP over HTTP.
+    ///
+    /// Spans are tagged with the DNA, agent and app they are running for,
+    /// and the spans of workflows are linked to the span which triggered
+    /// them, so that a zome call can be followed from the interface call
+    /// through publishing, validation and integration.
+    Otlp {
+        /// The base URL of the collector, e.g. `http://localhost:4318`.
+        /// Only `http` URLs are supported.
+        endpoint: Url2,
+
+        /// The `service.name` to report spans under.
+        /// Defaults to `holochain`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        service_name: Option<String>,
+
+        /// Which spans to export, in the same format as `RUST_LOG`.
+        /// Defaults to `info`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        filter: Option<String>,
+    },
+}
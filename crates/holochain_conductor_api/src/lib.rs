@@ -27,6 +27,7 @@
 mod admin_interface;
 mod app_interface;
 pub mod config;
+pub mod op_model;
 pub mod signal_subscription;
 pub mod state_dump;
 pub mod storage_info;
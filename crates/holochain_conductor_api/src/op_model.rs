@@ -0,0 +1,522 @@
+//! A model of the life of an op on one conductor, checked against the traces which
+//! [`AdminRequest::StartOpTrace`](crate::AdminRequest::StartOpTrace) records.
+//!
+//! [`OpNetworkMachine`] follows an op through the stages which the conductor traces: it is
+//! received, sys validated, app validated, integrated, and a validation receipt is sent to its
+//! author, while its author publishes it, serves it to fetching nodes and receives the receipts.
+//! Each [`OpTraceEvent`] of a trace is applied to the machine in turn, and an event which can't
+//! happen in the state the machine is in is an [`OpTraceViolation`].
+//!
+//! The outcomes of validation and integration are read from the details which the conductor
+//! records with the events.
+//!
+//! A trace can be started at any time. If the first event of a trace is the op being received,
+//! every stage the op then passes through must be traced. Otherwise the op was authored by the
+//! conductor, or reached it before the trace was started, and only the stages which follow a
+//! traced stage are checked.
+//!
+//! An [`OpTraceFixture`] keeps the state which a trace left an op in on a conductor which
+//! received it, so that a test can replay how the op was authored and check that the op is
+//! left in the same state again.
+
+use crate::{OpTraceEvent, OpTraceStage};
+use holochain_zome_types::prelude::ValidationStatus;
+
+/// The latest outcome of a validation stage.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OpStageOutcome {
+    /// The op will be validated again, e.g. because a dependency is missing.
+    Pending,
+    /// The op passed the stage.
+    Accepted,
+    /// The op was rejected by the stage.
+    Rejected,
+}
+
+impl OpStageOutcome {
+    fn from_detail(detail: &str) -> Self {
+        if detail.starts_with("Accepted") {
+            Self::Accepted
+        } else if detail.starts_with("Rejected") {
+            Self::Rejected
+        } else {
+            Self::Pending
+        }
+    }
+
+    fn is_final(self) -> bool {
+        !matches!(self, Self::Pending)
+    }
+}
+
+/// The model of an op on one conductor.
+#[derive(Clone, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct OpNetworkMachine {
+    /// Whether any event has been applied.
+    pub started: bool,
+    /// Whether the trace was started before the op was received, so that every stage the op
+    /// passed through was traced.
+    pub from_arrival: bool,
+    /// Whether the op was received from a remote node.
+    pub received: bool,
+    /// The latest outcome of sys validation.
+    pub sys_validated: Option<OpStageOutcome>,
+    /// The latest outcome of app validation.
+    pub app_validated: Option<OpStageOutcome>,
+    /// The status the op was integrated with.
+    pub integrated: Option<ValidationStatus>,
+    /// Whether the op was published to the authorities of its basis.
+    pub published: bool,
+    /// Whether the op was served to a remote node fetching it.
+    pub served: bool,
+    /// The status of the validation receipt sent to the author of the op.
+    pub receipt_sent: Option<ValidationStatus>,
+    /// Whether a validation receipt was received from a validator.
+    pub receipt_received: bool,
+}
+
+impl OpNetworkMachine {
+    /// Apply an event, or return why the event can't happen in this state.
+    ///
+    /// The event is applied even if it can't happen, so that the events after it are
+    /// checked against what the conductor did.
+    pub fn apply(&mut self, event: &OpTraceEvent) -> Result<(), String> {
+        if !self.started {
+            self.started = true;
+            self.from_arrival = event.stage == OpTraceStage::Received;
+        }
+        // Once a validation stage has been traced, every later stage must be traced too.
+        let validation_traced =
+            self.from_arrival || self.sys_validated.is_some() || self.app_validated.is_some();
+
+        let mut violation = None;
+        match event.stage {
+            OpTraceStage::Received => self.received = true,
+            OpTraceStage::Published => self.published = true,
+            OpTraceStage::Served => self.served = true,
+            OpTraceStage::ReceiptReceived => self.receipt_received = true,
+            OpTraceStage::SysValidated => {
+                let outcome = OpStageOutcome::from_detail(&event.detail);
+                violation = if let Some(status) = self.integrated {
+                    Some(format!(
+                        "the op was sys validated after it was integrated as {:?}",
+                        status
+                    ))
+                } else {
+                    match self.sys_validated {
+                        Some(previous) if previous.is_final() => Some(format!(
+                            "the op was sys validated again after it was {:?}",
+                            previous
+                        )),
+                        _ => None,
+                    }
+                };
+                self.sys_validated = Some(outcome);
+            }
+            OpTraceStage::AppValidated => {
+                let outcome = OpStageOutcome::from_detail(&event.detail);
+                violation = if let Some(status) = self.integrated {
+                    Some(format!(
+                        "the op was app validated after it was integrated as {:?}",
+                        status
+                    ))
+                } else if let Some(previous) = self.app_validated.filter(|o| o.is_final()) {
+                    Some(format!(
+                        "the op was app validated again after it was {:?}",
+                        previous
+                    ))
+                } else {
+                    match self.sys_validated {
+                        Some(OpStageOutcome::Accepted) => None,
+                        Some(OpStageOutcome::Rejected) => {
+                            Some("the op was app validated after sys validation rejected it".into())
+                        }
+                        Some(OpStageOutcome::Pending) => Some(
+                            "the op was app validated before sys validation accepted it".into(),
+                        ),
+                        None if self.from_arrival => {
+                            Some("the op was app validated before it was sys validated".into())
+                        }
+                        None => None,
+                    }
+                };
+                self.app_validated = Some(outcome);
+            }
+            OpTraceStage::Integrated => {
+                let status = status_from_detail(&event.detail)?;
+                violation = if let Some(previous) = self.integrated {
+                    Some(format!(
+                        "the op was integrated as {:?} after it was already integrated as {:?}",
+                        status, previous
+                    ))
+                } else if !validation_traced {
+                    None
+                } else {
+                    match status {
+                        ValidationStatus::Valid
+                            if self.app_validated != Some(OpStageOutcome::Accepted) =>
+                        {
+                            Some(
+                                "the op was integrated as Valid before app validation accepted it"
+                                    .into(),
+                            )
+                        }
+                        ValidationStatus::Rejected
+                            if self.sys_validated != Some(OpStageOutcome::Rejected)
+                                && self.app_validated != Some(OpStageOutcome::Rejected) =>
+                        {
+                            Some(
+                                "the op was integrated as Rejected but validation didn't reject it"
+                                    .into(),
+                            )
+                        }
+                        _ => None,
+                    }
+                };
+                self.integrated = Some(status);
+            }
+            OpTraceStage::ReceiptSent => {
+                let status = status_from_detail(&event.detail)?;
+                violation = match self.integrated {
+                    Some(integrated) if integrated != status => Some(format!(
+                        "a receipt was sent as {:?} for an op integrated as {:?}",
+                        status, integrated
+                    )),
+                    None if validation_traced => {
+                        Some("a receipt was sent before the op was integrated".into())
+                    }
+                    _ => None,
+                };
+                self.receipt_sent = Some(status);
+            }
+        }
+        violation.map_or(Ok(()), Err)
+    }
+
+    /// Whether the op has been integrated, so that it won't pass through any more
+    /// validation stages on this conductor.
+    pub fn is_integrated(&self) -> bool {
+        self.integrated.is_some()
+    }
+
+    /// The parts of the state which were changed by the conductor holding the op,
+    /// rather than by its author.
+    pub fn holder_state(&self) -> OpHolderState {
+        OpHolderState {
+            received: self.received,
+            sys_validated: self.sys_validated,
+            app_validated: self.app_validated,
+            integrated: self.integrated,
+            receipt_sent: self.receipt_sent,
+        }
+    }
+}
+
+/// Read the status from the detail of an integration or receipt event,
+/// which starts with e.g. "as Valid".
+fn status_from_detail(detail: &str) -> Result<ValidationStatus, String> {
+    let status = detail
+        .strip_prefix("as ")
+        .and_then(|rest| rest.split(|c: char| !c.is_alphanumeric()).next());
+    match status {
+        Some("Valid") => Ok(ValidationStatus::Valid),
+        Some("Rejected") => Ok(ValidationStatus::Rejected),
+        Some("Abandoned") => Ok(ValidationStatus::Abandoned),
+        _ => Err(format!("the status can't be read from {:?}", detail)),
+    }
+}
+
+/// An event of a trace which can't happen in the state the model was in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpTraceViolation {
+    /// The position of the event in the trace.
+    pub index: usize,
+    /// The event.
+    pub event: OpTraceEvent,
+    /// The state of the model before the event.
+    pub before: OpNetworkMachine,
+    /// Why the event can't happen.
+    pub reason: String,
+}
+
+/// A trace which was run through the model.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OpTraceCheck {
+    /// The state of the model after the last event.
+    pub machine: OpNetworkMachine,
+    /// The events which can't happen in the state the model was in.
+    pub violations: Vec<OpTraceViolation>,
+}
+
+/// Run the events of a trace through the model, in order.
+pub fn check_op_trace(events: &[OpTraceEvent]) -> OpTraceCheck {
+    let mut machine = OpNetworkMachine::default();
+    let mut violations = Vec::new();
+    for (index, event) in events.iter().enumerate() {
+        let before = machine.clone();
+        if let Err(reason) = machine.apply(event) {
+            violations.push(OpTraceViolation {
+                index,
+                event: event.clone(),
+                before,
+                reason,
+            });
+        }
+    }
+    OpTraceCheck {
+        machine,
+        violations,
+    }
+}
+
+/// Whether an event is recorded by a conductor holding the op, rather than by its author.
+pub fn is_holder_event(event: &OpTraceEvent) -> bool {
+    !matches!(
+        event.stage,
+        OpTraceStage::Published | OpTraceStage::Served | OpTraceStage::ReceiptReceived
+    )
+}
+
+/// The state of an op on a conductor which received it from its author.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct OpHolderState {
+    /// Whether the op was received.
+    pub received: bool,
+    /// The latest outcome of sys validation.
+    pub sys_validated: Option<OpStageOutcome>,
+    /// The latest outcome of app validation.
+    pub app_validated: Option<OpStageOutcome>,
+    /// The status the op was integrated with.
+    pub integrated: Option<ValidationStatus>,
+    /// The status of the validation receipt sent to the author of the op.
+    pub receipt_sent: Option<ValidationStatus>,
+}
+
+/// How an op first reached the conductor which received it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OpArrival {
+    /// The author published the op to the conductor.
+    Publish,
+    /// The conductor got the op by gossip, or by fetching it.
+    Gossip,
+}
+
+/// A regression fixture made from the trace of an op on a conductor which received it.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct OpTraceFixture {
+    /// How the op first reached the conductor.
+    pub arrival: OpArrival,
+    /// The state the trace left the op in.
+    pub terminal: OpHolderState,
+}
+
+impl OpTraceFixture {
+    /// Make a fixture from a trace which starts with the op being received.
+    ///
+    /// A trace with violations can't be made into a fixture, since replaying it would
+    /// assert that the conductor does what the model doesn't allow.
+    pub fn from_trace(events: &[OpTraceEvent]) -> Result<Self, String> {
+        let events = events
+            .iter()
+            .filter(|event| is_holder_event(event))
+            .cloned()
+            .collect::<Vec<_>>();
+        let arrival = match events.first() {
+            Some(event) if event.stage == OpTraceStage::Received => {
+                if event.detail.starts_with("by publish") {
+                    OpArrival::Publish
+                } else {
+                    OpArrival::Gossip
+                }
+            }
+            _ => return Err("the trace doesn't start with the op being received".to_string()),
+        };
+        let check = check_op_trace(&events);
+        if let Some(violation) = check.violations.first() {
+            return Err(format!(
+                "event {} of the trace is a violation: {}",
+                violation.index, violation.reason
+            ));
+        }
+        Ok(Self {
+            arrival,
+            terminal: check.machine.holder_state(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holochain_zome_types::prelude::Timestamp;
+
+    fn trace(events: &[(OpTraceStage, &str)]) -> Vec<OpTraceEvent> {
+        events
+            .iter()
+            .enumerate()
+            .map(|(i, (stage, detail))| OpTraceEvent {
+                timestamp: Timestamp(i as i64),
+                stage: *stage,
+                detail: detail.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn received_op_is_validated_integrated_and_receipted() {
+        use OpTraceStage::*;
+        let check = check_op_trace(&trace(&[
+            (Received, "by publish, with a validation receipt requested"),
+            (SysValidated, "MissingDhtDep"),
+            (SysValidated, "Accepted"),
+            (AppValidated, "AwaitingDeps([])"),
+            (AppValidated, "Accepted"),
+            (Integrated, "as Valid, right after app validation"),
+            (Received, "by gossip or fetch"),
+            (ReceiptSent, "as Valid, to author"),
+        ]));
+        assert_eq!(Vec::<OpTraceViolation>::new(), check.violations);
+        assert_eq!(
+            OpNetworkMachine {
+                started: true,
+                from_arrival: true,
+                received: true,
+                sys_validated: Some(OpStageOutcome::Accepted),
+                app_validated: Some(OpStageOutcome::Accepted),
+                integrated: Some(ValidationStatus::Valid),
+                receipt_sent: Some(ValidationStatus::Valid),
+                ..Default::default()
+            },
+            check.machine
+        );
+    }
+
+    #[test]
+    fn authored_op_is_integrated_without_being_validated() {
+        use OpTraceStage::*;
+        let check = check_op_trace(&trace(&[
+            (Published, "to the authorities of basis"),
+            (
+                Integrated,
+                "as Valid, once its dependencies were integrated",
+            ),
+            (Served, "to a remote node fetching it"),
+            (
+                ReceiptReceived,
+                "as Valid, from validators, making 1 of 5 required",
+            ),
+        ]));
+        assert!(check.violations.is_empty());
+        assert!(check.machine.is_integrated());
+        assert!(!check.machine.from_arrival);
+    }
+
+    #[test]
+    fn events_out_of_order_are_violations() {
+        use OpTraceStage::*;
+        let check = check_op_trace(&trace(&[
+            (Received, "by gossip or fetch"),
+            (AppValidated, "Accepted"),
+            (SysValidated, "Rejected(\"bad\")"),
+            (Integrated, "as Valid, right after app validation"),
+            (ReceiptSent, "as Rejected, to author"),
+            (
+                Integrated,
+                "as Valid, once its dependencies were integrated",
+            ),
+        ]));
+        assert_eq!(
+            vec![
+                (1, "the op was app validated before it was sys validated"),
+                (
+                    4,
+                    "a receipt was sent as Rejected for an op integrated as Valid"
+                ),
+                (
+                    5,
+                    "the op was integrated as Valid after it was already integrated as Valid"
+                ),
+            ],
+            check
+                .violations
+                .iter()
+                .map(|v| (v.index, v.reason.as_str()))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Some(OpStageOutcome::Accepted),
+            check.violations[1].before.app_validated
+        );
+    }
+
+    #[test]
+    fn fixture_is_the_holder_state_a_trace_leaves() {
+        use OpTraceStage::*;
+        let fixture = OpTraceFixture::from_trace(&trace(&[
+            (Published, "to the authorities of basis"),
+            (Received, "by publish, with a validation receipt requested"),
+            (SysValidated, "Accepted"),
+            (AppValidated, "Rejected(\"bad\")"),
+            (Integrated, "as Rejected, right after app validation"),
+            (
+                ReceiptReceived,
+                "as Rejected, from validators, making 1 of 5 required",
+            ),
+        ]))
+        .unwrap();
+        assert_eq!(
+            OpTraceFixture {
+                arrival: OpArrival::Publish,
+                terminal: OpHolderState {
+                    received: true,
+                    sys_validated: Some(OpStageOutcome::Accepted),
+                    app_validated: Some(OpStageOutcome::Rejected),
+                    integrated: Some(ValidationStatus::Rejected),
+                    receipt_sent: None,
+                },
+            },
+            fixture
+        );
+
+        assert!(OpTraceFixture::from_trace(&trace(&[(
+            Integrated,
+            "as Valid, once its dependencies were integrated"
+        )]))
+        .is_err());
+        assert!(OpTraceFixture::from_trace(&trace(&[
+            (Received, "by gossip or fetch"),
+            (Integrated, "as Valid, right after app validation"),
+        ]))
+        .is_err());
+    }
+
+    #[test]
+    fn only_stages_after_a_traced_stage_are_checked() {
+        use OpTraceStage::*;
+        // - The trace was started after the op was validated.
+        let check = check_op_trace(&trace(&[
+            (
+                Integrated,
+                "as Rejected, once its dependencies were integrated",
+            ),
+            (ReceiptSent, "as Rejected, to author"),
+        ]));
+        assert!(check.violations.is_empty());
+
+        // - Once sys validation is traced, integration needs app validation.
+        let check = check_op_trace(&trace(&[
+            (SysValidated, "Accepted"),
+            (Integrated, "as Valid, right after app validation"),
+        ]));
+        assert_eq!(
+            vec!["the op was integrated as Valid before app validation accepted it"],
+            check
+                .violations
+                .iter()
+                .map(|v| v.reason.as_str())
+                .collect::<Vec<_>>()
+        );
+    }
+}
@@ -98,6 +98,9 @@ pub struct MustGetAgentActivityInput {
     pub author: AgentPubKey,
     /// The filter on the chains activity.
     pub chain_filter: ChainFilter,
+    /// How hard to try fetching the activity from the network.
+    #[serde(default)]
+    pub options: crate::entry::MustGetOptions,
 }
 
 impl<H: Eq + Ord + std::hash::Hash> ChainFilter<H> {
@@ -22,8 +22,10 @@ use holo_hash::HashableContentBytes;
 use holochain_serialized_bytes::prelude::*;
 
 mod app_entry_bytes;
+mod chunk;
 mod error;
 pub use app_entry_bytes::*;
+pub use chunk::*;
 pub use error::*;
 
 /// Entries larger than this number of bytes cannot be created
@@ -184,6 +186,35 @@ impl HashableContent for Entry {
     }
 }
 
+/// How hard a `must_get_*` call should try to fetch data from the network
+/// before giving up.
+///
+/// Both values are capped by the conductor's own limits. They have no effect
+/// when the host only looks at local data, which is the case for validation
+/// that was not triggered by an authoring agent.
+#[derive(Serialize, Deserialize, Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub struct MustGetOptions {
+    /// Give up on an attempt after this many milliseconds.
+    /// `None` waits as long as the network does.
+    pub timeout_ms: Option<u64>,
+    /// How many further attempts to make when the first one finds nothing.
+    pub retries: u8,
+}
+
+impl MustGetOptions {
+    /// Fail fast: make a single attempt with this timeout.
+    pub fn with_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = Some(timeout_ms);
+        self
+    }
+
+    /// Keep trying this many more times if nothing is found.
+    pub fn with_retries(mut self, retries: u8) -> Self {
+        self.retries = retries;
+        self
+    }
+}
+
 /// Zome input for must_get_valid_record.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct MustGetValidRecordInput(pub ActionHash);
@@ -200,6 +231,31 @@ impl MustGetValidRecordInput {
     }
 }
 
+/// Zome input for must_get_valid_record_with_options.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct MustGetValidRecordWithOptionsInput {
+    /// The action of the record to get.
+    pub action_hash: ActionHash,
+    /// How hard to try fetching the record from the network.
+    pub options: MustGetOptions,
+}
+
+impl MustGetValidRecordWithOptionsInput {
+    /// Constructor.
+    pub fn new(action_hash: ActionHash, options: MustGetOptions) -> Self {
+        Self {
+            action_hash,
+            options,
+        }
+    }
+}
+
+impl From<MustGetValidRecordInput> for MustGetValidRecordWithOptionsInput {
+    fn from(input: MustGetValidRecordInput) -> Self {
+        Self::new(input.0, MustGetOptions::default())
+    }
+}
+
 /// Zome input for must_get_entry.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct MustGetEntryInput(pub EntryHash);
@@ -216,6 +272,31 @@ impl MustGetEntryInput {
     }
 }
 
+/// Zome input for must_get_entry_with_options.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct MustGetEntryWithOptionsInput {
+    /// The hash of the entry to get.
+    pub entry_hash: EntryHash,
+    /// How hard to try fetching the entry from the network.
+    pub options: MustGetOptions,
+}
+
+impl MustGetEntryWithOptionsInput {
+    /// Constructor.
+    pub fn new(entry_hash: EntryHash, options: MustGetOptions) -> Self {
+        Self {
+            entry_hash,
+            options,
+        }
+    }
+}
+
+impl From<MustGetEntryInput> for MustGetEntryWithOptionsInput {
+    fn from(input: MustGetEntryInput) -> Self {
+        Self::new(input.0, MustGetOptions::default())
+    }
+}
+
 /// Zome input for must_get_action.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct MustGetActionInput(pub ActionHash);
@@ -231,3 +312,28 @@ impl MustGetActionInput {
         self.0
     }
 }
+
+/// Zome input for must_get_action_with_options.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct MustGetActionWithOptionsInput {
+    /// The hash of the action to get.
+    pub action_hash: ActionHash,
+    /// How hard to try fetching the action from the network.
+    pub options: MustGetOptions,
+}
+
+impl MustGetActionWithOptionsInput {
+    /// Constructor.
+    pub fn new(action_hash: ActionHash, options: MustGetOptions) -> Self {
+        Self {
+            action_hash,
+            options,
+        }
+    }
+}
+
+impl From<MustGetActionInput> for MustGetActionWithOptionsInput {
+    fn from(input: MustGetActionInput) -> Self {
+        Self::new(input.0, MustGetOptions::default())
+    }
+}
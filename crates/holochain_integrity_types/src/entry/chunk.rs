@@ -0,0 +1,104 @@
+use super::ENTRY_SIZE_LIMIT;
+use holo_hash::EntryHash;
+use holochain_serialized_bytes::prelude::*;
+use std::ops::Range;
+
+/// The size of the chunks which a blob is split into unless another size is given.
+pub const DEFAULT_CHUNK_SIZE: u32 = 1_000_000;
+
+/// The largest chunk which fits in an entry, leaving room for the serialization of
+/// the [`Chunk`] around it.
+pub const MAX_CHUNK_SIZE: u32 = ENTRY_SIZE_LIMIT as u32 - 64;
+
+/// A piece of a blob which is too large to be one entry.
+#[derive(Clone, Serialize, Deserialize, SerializedBytes, PartialEq, Eq)]
+pub struct Chunk(#[serde(with = "serde_bytes")] pub Vec<u8>);
+
+impl std::fmt::Debug for Chunk {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Chunk({} bytes)", self.0.len())
+    }
+}
+
+/// The entry which a blob that was split into [`Chunk`] entries is read from.
+///
+/// Every chunk but the last is `chunk_size` bytes, so the chunks which hold any range
+/// of the blob are known without fetching the others.
+#[derive(Clone, Debug, Serialize, Deserialize, SerializedBytes, PartialEq, Eq)]
+pub struct ChunkManifest {
+    /// The size of the blob in bytes.
+    pub size: u64,
+    /// The size of every chunk but the last.
+    pub chunk_size: u32,
+    /// The hashes of the chunk entries, in order.
+    pub chunks: Vec<EntryHash>,
+}
+
+impl ChunkManifest {
+    /// Whether the number and size of the chunks add up to the size of the blob.
+    pub fn is_consistent(&self) -> bool {
+        self.chunk_size > 0
+            && self.chunk_size <= MAX_CHUNK_SIZE
+            && self.chunks.len() as u64 == self.size.div_ceil(self.chunk_size as u64)
+    }
+
+    /// The size of the chunk at `index`.
+    pub fn chunk_len(&self, index: usize) -> u64 {
+        let start = index as u64 * self.chunk_size as u64;
+        self.size.saturating_sub(start).min(self.chunk_size as u64)
+    }
+
+    /// The indexes of the chunks which hold the bytes of `range`, which is cut to the
+    /// size of the blob.
+    pub fn chunks_for(&self, range: &Range<u64>) -> Range<usize> {
+        let end = range.end.min(self.size);
+        let start = range.start.min(end);
+        if start == end {
+            return 0..0;
+        }
+        let chunk_size = self.chunk_size as u64;
+        (start / chunk_size) as usize..end.div_ceil(chunk_size) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manifest_of(size: u64, chunk_size: u32) -> ChunkManifest {
+        let chunks = size.div_ceil(chunk_size as u64) as usize;
+        ChunkManifest {
+            size,
+            chunk_size,
+            chunks: vec![EntryHash::from_raw_36(vec![0; 36]); chunks],
+        }
+    }
+
+    #[test]
+    fn ranges_map_to_the_chunks_which_hold_them() {
+        let manifest = manifest_of(25, 10);
+        assert!(manifest.is_consistent());
+        assert_eq!(
+            vec![10, 10, 5, 0],
+            (0..4).map(|i| manifest.chunk_len(i)).collect::<Vec<_>>()
+        );
+
+        assert_eq!(0..3, manifest.chunks_for(&(0..u64::MAX)));
+        assert_eq!(0..1, manifest.chunks_for(&(0..10)));
+        assert_eq!(0..2, manifest.chunks_for(&(9..11)));
+        assert_eq!(2..3, manifest.chunks_for(&(20..100)));
+        assert_eq!(0..0, manifest.chunks_for(&(30..40)));
+        assert_eq!(0..0, manifest.chunks_for(&(5..5)));
+
+        assert!(manifest_of(0, 10).is_consistent());
+        let mut missing_chunk = manifest;
+        missing_chunk.chunks.pop();
+        assert!(!missing_chunk.is_consistent());
+        assert!(!ChunkManifest {
+            size: 0,
+            chunk_size: 0,
+            chunks: vec![],
+        }
+        .is_consistent());
+    }
+}
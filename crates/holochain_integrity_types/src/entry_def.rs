@@ -1,5 +1,6 @@
 use std::borrow::Borrow;
 use std::borrow::Cow;
+use std::time::Duration;
 
 use holochain_serialized_bytes::prelude::*;
 
@@ -75,6 +76,12 @@ pub struct EntryDef {
     /// Note this will result in more storage being used on the DHT.
     /// Defaults to false.
     pub cache_at_agent_activity: bool,
+    /// How long entries of this type are kept on the DHT after they are authored.
+    /// Once expired, the ops which store an ephemeral entry are no longer gossiped
+    /// and are purged by the authorities which hold them.
+    /// Entries of this type are kept forever if no TTL is set.
+    #[serde(default)]
+    pub ttl: Option<Duration>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
@@ -162,9 +169,21 @@ impl EntryDef {
             visibility,
             required_validations,
             cache_at_agent_activity,
+            ttl: None,
         }
     }
 
+    /// Make entries of this type ephemeral, so that they expire after the given TTL.
+    pub fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Whether entries of this type expire.
+    pub fn is_ephemeral(&self) -> bool {
+        self.ttl.is_some()
+    }
+
     #[cfg(any(test, feature = "test_utils"))]
     pub fn default_from_id<I: Into<EntryDefId>>(id: I) -> Self {
         EntryDef {
@@ -251,6 +270,7 @@ impl Default for EntryDef {
             visibility: Default::default(),
             required_validations: Default::default(),
             cache_at_agent_activity: false,
+            ttl: None,
         }
     }
 }
@@ -12,6 +12,7 @@
 
 #[allow(missing_docs)]
 pub mod action;
+pub mod bytes;
 pub mod capability;
 pub mod chain;
 pub mod countersigning;
@@ -2,6 +2,7 @@
 
 pub use crate::action::conversions::*;
 pub use crate::action::*;
+pub use crate::bytes::*;
 pub use crate::capability::*;
 pub use crate::chain::*;
 pub use crate::countersigning::*;
@@ -24,6 +24,8 @@ where
             CrudeMockKeystore(Arc::new(err_fn)),
         )))),
         s,
+        None,
+        Default::default(),
     )
 }
 
@@ -51,6 +53,8 @@ where
         MetaLairClient(
             Arc::new(parking_lot::Mutex::new(LairClient(Arc::new(mock)))),
             s,
+            None,
+            Default::default(),
         ),
         control,
     ))
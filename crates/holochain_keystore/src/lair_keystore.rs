@@ -38,6 +38,44 @@ pub async fn spawn_lair_keystore_in_proc(
     spawn_lair_keystore(connection_url.into(), passphrase).await
 }
 
+/// Spawn a keystore backed by lair_keystore running embedded in this process.
+/// Unlike [`spawn_lair_keystore_in_proc`], no lair server is started, so there
+/// is no IPC socket between the client and the keystore.
+/// The store is kept in the same lair root as for an in-process server, so
+/// a conductor can switch between the two.
+/// @param config_path - path to the lair config yaml file
+pub async fn spawn_lair_keystore_embedded(
+    config_path: &PathBuf,
+    passphrase: sodoken::BufRead,
+) -> LairResult<MetaLairClient> {
+    let config = get_config(config_path, passphrase.clone()).await?;
+
+    // make sure no standalone lair server is using this lair root
+    {
+        let config = config.clone();
+        tokio::task::spawn_blocking(move || ::lair_keystore::pid_check::pid_check(&config))
+            .await
+            .map_err(one_err::OneErr::new)??;
+    }
+
+    let store_factory = ::lair_keystore::store_sqlite::create_sql_pool_factory(
+        &config.store_file,
+        &config.database_salt,
+    );
+    let keystore =
+        lair_keystore_api::in_proc_keystore::InProcKeystore::new(config, store_factory, passphrase)
+            .await?;
+
+    let client = keystore.new_client().await?;
+    let (s, _) = tokio::sync::mpsc::unbounded_channel();
+    Ok(MetaLairClient(
+        Arc::new(parking_lot::Mutex::new(client)),
+        s,
+        None,
+        Default::default(),
+    ))
+}
+
 async fn get_config(
     config_path: &PathBuf,
     passphrase: sodoken::BufRead,
@@ -42,6 +42,10 @@ pub use agent_pubkey_ext::*;
 
 pub mod lair_keystore;
 
+pub mod remote_signer;
+
+mod sign_cache;
+
 pub mod paths;
 
 mod test_keystore;
@@ -80,5 +84,10 @@ pub async fn spawn_mem_keystore() -> LairResult<MetaLairClient> {
     // return the client
     let client = keystore.new_client().await?;
     let (s, _) = tokio::sync::mpsc::unbounded_channel();
-    Ok(MetaLairClient(Arc::new(parking_lot::Mutex::new(client)), s))
+    Ok(MetaLairClient(
+        Arc::new(parking_lot::Mutex::new(client)),
+        s,
+        None,
+        Default::default(),
+    ))
 }
@@ -6,6 +6,9 @@ use parking_lot::Mutex;
 use std::future::Future;
 use std::sync::Arc;
 
+use crate::remote_signer::RemoteSigner;
+use crate::sign_cache::SignatureCache;
+
 pub use kitsune_p2p_types::dependencies::lair_keystore_api::LairResult;
 
 const TIME_CHECK_FREQ: std::time::Duration = std::time::Duration::from_secs(5);
@@ -16,8 +19,15 @@ const RECON_MAX_MS: u64 = 5000;
 type Esnd = tokio::sync::mpsc::UnboundedSender<()>;
 
 /// Abstraction around runtime switching/upgrade of lair keystore / client.
+/// Signing may be forwarded to a [`SigningBackend`](crate::remote_signer::SigningBackend)
+/// for the keys it holds.
 #[derive(Clone)]
-pub struct MetaLairClient(pub(crate) Arc<Mutex<LairClient>>, pub(crate) Esnd);
+pub struct MetaLairClient(
+    pub(crate) Arc<Mutex<LairClient>>,
+    pub(crate) Esnd,
+    pub(crate) Option<Arc<RemoteSigner>>,
+    pub(crate) Arc<SignatureCache>,
+);
 
 /// A lair error could indicate a connection problem or user error.
 /// If we get any error state, we send a signal to our connection validation
@@ -138,7 +148,12 @@ impl MetaLairClient {
             });
         }
 
-        Ok(MetaLairClient(inner, c_check_send))
+        Ok(MetaLairClient(
+            inner,
+            c_check_send,
+            None,
+            Default::default(),
+        ))
     }
 
     /// Get the raw underlying lair client instance.
@@ -177,19 +192,40 @@ impl MetaLairClient {
         data: Arc<[u8]>,
     ) -> impl Future<Output = LairResult<Signature>> + 'static + Send {
         let (client, esnd) = self.cli();
-        async move {
+        let remote = self.2.clone();
+        let cache = self.3.clone();
+        let (cache_key, cache_data) = (pub_key.clone(), data.clone());
+        let sign = async move {
             tokio::time::timeout(std::time::Duration::from_secs(30), async move {
+                if let Some(remote) = &remote {
+                    if remote.holds(&pub_key) {
+                        return remote.sign(pub_key, data).await;
+                    }
+                }
                 let mut pub_key_2 = [0; 32];
                 pub_key_2.copy_from_slice(pub_key.get_raw_32());
-                let sig = echk!(
-                    esnd,
-                    client.sign_by_pub_key(pub_key_2.into(), None, data).await
-                );
-                Ok(Signature(*sig.0))
+                match client
+                    .sign_by_pub_key(pub_key_2.into(), None, data.clone())
+                    .await
+                {
+                    Ok(sig) => Ok(Signature(*sig.0)),
+                    Err(err) => {
+                        // the key may have been added to the signing
+                        // service since we last asked which keys it holds
+                        if let Some(remote) = remote {
+                            if remote.refresh().await.is_ok() && remote.holds(&pub_key) {
+                                return remote.sign(pub_key, data).await;
+                            }
+                        }
+                        let _ = esnd.send(());
+                        Err(err)
+                    }
+                }
             })
             .await
             .map_err(one_err::OneErr::new)?
-        }
+        };
+        cache.sign(cache_key, cache_data, sign)
     }
 
     /// Construct a new randomized shared secret, associated with given tag
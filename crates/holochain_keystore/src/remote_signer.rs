@@ -0,0 +1,201 @@
+//! Sign with agent keys which are held by a remote signing service, such as
+//! an HSM or a key management service, rather than by lair.
+//!
+//! A [`MetaLairClient`] with a [`SigningBackend`] attached forwards signing
+//! requests for the keys the backend holds to the backend. Everything else,
+//! including signing with any other key, is still handled by lair.
+//!
+//! Keys held by the backend are created by the signing service, not by the
+//! conductor, and are passed as the `agent_key` when installing an app.
+//! Only signing is supported for these keys, so they can't be used with
+//! DPKI or for `crypto_box` encryption.
+
+use crate::*;
+use futures::future::BoxFuture;
+use holo_hash::AgentPubKey;
+use holochain_zome_types::prelude::Signature;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+#[cfg(feature = "remote_signing")]
+mod http;
+#[cfg(feature = "remote_signing")]
+pub use http::*;
+
+/// A service which holds agent keys and signs with them on request.
+pub trait SigningBackend: 'static + Send + Sync {
+    /// List the agent keys this backend can sign with.
+    fn list_keys(&self) -> BoxFuture<'static, LairResult<Vec<AgentPubKey>>>;
+
+    /// Sign data with one of the keys this backend holds.
+    fn sign(
+        &self,
+        agent: AgentPubKey,
+        data: Arc<[u8]>,
+    ) -> BoxFuture<'static, LairResult<Signature>>;
+}
+
+/// A [`SigningBackend`] along with the keys it is known to hold.
+pub(crate) struct RemoteSigner {
+    backend: Arc<dyn SigningBackend>,
+    keys: parking_lot::RwLock<HashSet<AgentPubKey>>,
+}
+
+impl RemoteSigner {
+    pub(crate) async fn new(backend: Arc<dyn SigningBackend>) -> LairResult<Self> {
+        let signer = Self {
+            backend,
+            keys: Default::default(),
+        };
+        signer.refresh().await?;
+        Ok(signer)
+    }
+
+    /// Whether the backend is known to hold this key.
+    pub(crate) fn holds(&self, agent: &AgentPubKey) -> bool {
+        self.keys.read().contains(agent)
+    }
+
+    /// Fetch the list of keys from the backend again, e.g. because keys
+    /// have been added to the signing service since it was last fetched.
+    pub(crate) async fn refresh(&self) -> LairResult<()> {
+        let keys = self.backend.list_keys().await?;
+        *self.keys.write() = keys.into_iter().collect();
+        Ok(())
+    }
+
+    /// Sign with the backend, checking the signature it returns before
+    /// handing it out.
+    pub(crate) async fn sign(&self, agent: AgentPubKey, data: Arc<[u8]>) -> LairResult<Signature> {
+        let signature = self.backend.sign(agent.clone(), data.clone()).await?;
+        let valid = agent
+            .verify_signature_raw(&signature, data)
+            .await
+            .map_err(one_err::OneErr::new)?;
+        if !valid {
+            return Err(one_err::OneErr::new(format!(
+                "The signing service returned an invalid signature for {agent}"
+            )));
+        }
+        Ok(signature)
+    }
+}
+
+impl MetaLairClient {
+    /// Attach a signing backend to this keystore, so that it signs with the
+    /// backend for every key the backend holds.
+    pub async fn with_signing_backend(self, backend: Arc<dyn SigningBackend>) -> LairResult<Self> {
+        let signer = RemoteSigner::new(backend).await?;
+        Ok(MetaLairClient(
+            self.0,
+            self.1,
+            Some(Arc::new(signer)),
+            self.3,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::FutureExt;
+
+    /// A signing backend which holds its keys in a separate test keystore.
+    struct TestBackend {
+        keystore: MetaLairClient,
+        keys: Arc<parking_lot::Mutex<Vec<AgentPubKey>>>,
+        corrupt: bool,
+    }
+
+    impl SigningBackend for TestBackend {
+        fn list_keys(&self) -> BoxFuture<'static, LairResult<Vec<AgentPubKey>>> {
+            let keys = self.keys.lock().clone();
+            async move { Ok(keys) }.boxed()
+        }
+
+        fn sign(
+            &self,
+            agent: AgentPubKey,
+            data: Arc<[u8]>,
+        ) -> BoxFuture<'static, LairResult<Signature>> {
+            let keystore = self.keystore.clone();
+            let corrupt = self.corrupt;
+            async move {
+                let mut signature = keystore.sign(agent, data).await?;
+                if corrupt {
+                    signature.0[0] ^= 1;
+                }
+                Ok(signature)
+            }
+            .boxed()
+        }
+    }
+
+    async fn setup(
+        corrupt: bool,
+    ) -> (
+        MetaLairClient,
+        MetaLairClient,
+        Arc<parking_lot::Mutex<Vec<AgentPubKey>>>,
+    ) {
+        let remote = spawn_test_keystore().await.unwrap();
+        let keys = Arc::new(parking_lot::Mutex::new(vec![remote
+            .new_sign_keypair_random()
+            .await
+            .unwrap()]));
+        let backend = TestBackend {
+            keystore: remote.clone(),
+            keys: keys.clone(),
+            corrupt,
+        };
+        let keystore = spawn_test_keystore()
+            .await
+            .unwrap()
+            .with_signing_backend(Arc::new(backend))
+            .await
+            .unwrap();
+        (keystore, remote, keys)
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn signs_with_remote_and_local_keys() {
+        let (keystore, remote, keys) = setup(false).await;
+        let data: Arc<[u8]> = b"signature test data".to_vec().into();
+
+        let remote_key = keys.lock()[0].clone();
+        let signature = remote_key.sign_raw(&keystore, data.clone()).await.unwrap();
+        assert!(remote_key
+            .verify_signature_raw(&signature, data.clone())
+            .await
+            .unwrap());
+
+        // keys added to the signing service later are found too
+        let new_remote_key = remote.new_sign_keypair_random().await.unwrap();
+        keys.lock().push(new_remote_key.clone());
+        let signature = new_remote_key
+            .sign_raw(&keystore, data.clone())
+            .await
+            .unwrap();
+        assert!(new_remote_key
+            .verify_signature_raw(&signature, data.clone())
+            .await
+            .unwrap());
+
+        let local_key = keystore.new_sign_keypair_random().await.unwrap();
+        let signature = local_key.sign_raw(&keystore, data.clone()).await.unwrap();
+        assert!(local_key
+            .verify_signature_raw(&signature, data)
+            .await
+            .unwrap());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn rejects_invalid_remote_signature() {
+        let (keystore, _remote, keys) = setup(true).await;
+        let remote_key = keys.lock()[0].clone();
+        assert!(remote_key
+            .sign_raw(&keystore, b"data".to_vec().into())
+            .await
+            .is_err());
+    }
+}
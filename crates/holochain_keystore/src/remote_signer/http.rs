@@ -0,0 +1,124 @@
+//! A [`SigningBackend`] which talks to a signing service over HTTP.
+//!
+//! The service must implement the following endpoints:
+//!
+//! ## `GET /keys`
+//!
+//! Lists the agent keys the service can sign with.
+//!
+//! Response:
+//! - 200: msgpack-encoded `Vec<AgentPubKey>`
+//! - other: error message as plaintext string
+//!
+//! ## `POST /sign`
+//!
+//! Signs data with one of the service's keys.
+//!
+//! Body: msgpack-encoded [`SignRequest`]
+//! Response:
+//! - 200: msgpack-encoded `Signature`
+//! - other: error message as plaintext string
+//!
+//! ## Notes (for both endpoints)
+//!
+//! Every request carries an `Authorization: Bearer {token}` header with the
+//! token the backend was configured with, and the service should reject any
+//! request without a valid token. Use an `https` URL so that the token and
+//! the data being signed are protected in transit.
+//!
+//! Signatures returned by the service are checked against the agent key
+//! before the conductor uses them.
+
+use super::*;
+use kitsune_p2p_types::dependencies::url2::Url2;
+
+/// The body of a request to the `/sign` endpoint.
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+pub struct SignRequest {
+    /// The key to sign with.
+    pub agent: AgentPubKey,
+    /// The data to sign.
+    #[serde(with = "serde_bytes")]
+    pub data: Vec<u8>,
+}
+
+/// A [`SigningBackend`] for a remote signing service with an HTTP API.
+pub struct HttpSigningBackend {
+    base_url: Url2,
+    auth_token: Option<String>,
+    client: reqwest::Client,
+}
+
+impl HttpSigningBackend {
+    /// Constructor. The `auth_token` is sent as a bearer token with
+    /// every request.
+    pub fn new(mut base_url: Url2, auth_token: Option<String>) -> Self {
+        // paths are joined onto the base URL, which only keeps the
+        // last path segment if it ends with a slash
+        if !base_url.path().ends_with('/') {
+            let path = format!("{}/", base_url.path());
+            base_url.set_path(&path);
+        }
+        Self {
+            base_url,
+            auth_token,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> LairResult<reqwest::RequestBuilder> {
+        let url = self.base_url.join(path).map_err(one_err::OneErr::new)?;
+        let request = self.client.request(method, url);
+        Ok(match &self.auth_token {
+            Some(token) => request.bearer_auth(token),
+            None => request,
+        })
+    }
+}
+
+async fn decode_response<T>(response: reqwest::Response) -> LairResult<T>
+where
+    T: serde::de::DeserializeOwned + std::fmt::Debug,
+{
+    let status = response.status().as_u16();
+    if status == 200 {
+        let bytes = response.bytes().await.map_err(one_err::OneErr::new)?;
+        Ok(holochain_serialized_bytes::decode(&bytes).map_err(one_err::OneErr::new)?)
+    } else {
+        let msg = response.text().await.map_err(one_err::OneErr::new)?;
+        Err(one_err::OneErr::new(format!(
+            "signing service error, code: {status}, msg: {msg}"
+        )))
+    }
+}
+
+impl SigningBackend for HttpSigningBackend {
+    fn list_keys(&self) -> BoxFuture<'static, LairResult<Vec<AgentPubKey>>> {
+        let request = self.request(reqwest::Method::GET, "keys");
+        Box::pin(async move {
+            let response = request?.send().await.map_err(one_err::OneErr::new)?;
+            decode_response(response).await
+        })
+    }
+
+    fn sign(
+        &self,
+        agent: AgentPubKey,
+        data: Arc<[u8]>,
+    ) -> BoxFuture<'static, LairResult<Signature>> {
+        let request = self.request(reqwest::Method::POST, "sign");
+        Box::pin(async move {
+            let body = holochain_serialized_bytes::encode(&SignRequest {
+                agent,
+                data: data.to_vec(),
+            })
+            .map_err(one_err::OneErr::new)?;
+            let response = request?
+                .body(body)
+                .send()
+                .await
+                .map_err(one_err::OneErr::new)?;
+            decode_response(response).await
+        })
+    }
+}
@@ -0,0 +1,195 @@
+//! Share signatures between identical signing requests.
+//!
+//! The same data is often signed by the same agent several times in quick
+//! succession, e.g. by concurrent workflows. Ed25519 signatures are
+//! deterministic, so rather than sending every one of these requests to lair,
+//! requests for a (key, data) pair which is already being signed wait for
+//! that signature, and completed signatures are kept for a short window.
+//!
+//! Requests are keyed by a hash of the key and data, so that the cache doesn't
+//! hold on to the data which was signed. Requests for different data are still
+//! sent to lair one by one, since lair has no request to sign several payloads.
+
+use futures::future::{BoxFuture, FutureExt, Shared};
+use holo_hash::AgentPubKey;
+use holochain_zome_types::prelude::Signature;
+use kitsune_p2p_types::dependencies::lair_keystore_api::LairResult;
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How long a signature is kept for reuse.
+const SIGNATURE_CACHE_TTL: Duration = Duration::from_secs(5);
+
+/// The most signatures which are kept at once. The least recently used ones are
+/// dropped to make room for new ones.
+const SIGNATURE_CACHE_MAX: NonZeroUsize = match NonZeroUsize::new(1024) {
+    Some(max) => max,
+    None => unreachable!(),
+};
+
+type SharedSignature = Shared<BoxFuture<'static, LairResult<Signature>>>;
+
+/// The hash of a key and the data which it signs.
+type SignatureKey = Vec<u8>;
+
+/// Signing requests which are in flight or recently completed.
+pub(crate) struct SignatureCache(
+    parking_lot::Mutex<LruCache<SignatureKey, (Instant, SharedSignature)>>,
+);
+
+impl Default for SignatureCache {
+    fn default() -> Self {
+        Self(parking_lot::Mutex::new(LruCache::new(SIGNATURE_CACHE_MAX)))
+    }
+}
+
+impl SignatureCache {
+    /// Get the signature for this key and data, only calling `sign` if there
+    /// is no matching request in flight or completed within the window.
+    /// Failed requests are not kept, so the next request tries again.
+    pub(crate) fn sign<F>(
+        &self,
+        pub_key: AgentPubKey,
+        data: Arc<[u8]>,
+        sign: F,
+    ) -> impl std::future::Future<Output = LairResult<Signature>> + 'static + Send
+    where
+        F: std::future::Future<Output = LairResult<Signature>> + 'static + Send,
+    {
+        let now = Instant::now();
+        let key = holo_hash::blake2b_256(&[pub_key.get_raw_39(), &data].concat());
+        let mut cache = self.0.lock();
+        if let Some((created, signature)) = cache.get(&key) {
+            if now.duration_since(*created) < SIGNATURE_CACHE_TTL
+                && !matches!(signature.peek(), Some(Err(_)))
+            {
+                return signature.clone();
+            }
+        }
+        let signature = sign.boxed().shared();
+        cache.put(key, (now, signature.clone()));
+        signature
+    }
+
+    #[cfg(test)]
+    fn len(&self) -> usize {
+        self.0.lock().len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    async fn counting_signer(
+        calls: Arc<AtomicUsize>,
+        result: LairResult<Signature>,
+    ) -> LairResult<Signature> {
+        calls.fetch_add(1, Ordering::SeqCst);
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        result
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn identical_requests_share_a_signature() {
+        let cache = SignatureCache::default();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let agent = AgentPubKey::from_raw_32(vec![1; 32]);
+        let data: Arc<[u8]> = b"data".to_vec().into();
+
+        let requests = (0..10).map(|_| {
+            cache.sign(
+                agent.clone(),
+                data.clone(),
+                counting_signer(calls.clone(), Ok(Signature([2; 64]))),
+            )
+        });
+        for signature in futures::future::join_all(requests).await {
+            assert_eq!(signature.unwrap(), Signature([2; 64]));
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // a completed signature is reused too
+        cache
+            .sign(
+                agent.clone(),
+                data,
+                counting_signer(calls.clone(), Ok(Signature([2; 64]))),
+            )
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        // but different data is signed separately
+        cache
+            .sign(
+                agent,
+                b"other data".to_vec().into(),
+                counting_signer(calls.clone(), Ok(Signature([3; 64]))),
+            )
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn least_recently_used_signatures_are_dropped_when_full() {
+        let cache = SignatureCache::default();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let agent = AgentPubKey::from_raw_32(vec![1; 32]);
+        let sign = |cache: &SignatureCache, i: usize| {
+            let calls = calls.clone();
+            cache.sign(agent.clone(), i.to_le_bytes().to_vec().into(), async move {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Ok(Signature([2; 64]))
+            })
+        };
+
+        sign(&cache, 0).await.unwrap();
+        for i in 1..SIGNATURE_CACHE_MAX.get() {
+            sign(&cache, i).await.unwrap();
+        }
+        // - The first signature is used again, so it's the most recently used.
+        sign(&cache, 0).await.unwrap();
+        assert_eq!(SIGNATURE_CACHE_MAX.get(), calls.load(Ordering::SeqCst));
+
+        // - A new signature is still cached once the cache is full, in place of
+        //   the least recently used one.
+        sign(&cache, SIGNATURE_CACHE_MAX.get()).await.unwrap();
+        assert_eq!(SIGNATURE_CACHE_MAX.get(), cache.len());
+        sign(&cache, SIGNATURE_CACHE_MAX.get()).await.unwrap();
+        sign(&cache, 0).await.unwrap();
+        assert_eq!(SIGNATURE_CACHE_MAX.get() + 1, calls.load(Ordering::SeqCst));
+        sign(&cache, 1).await.unwrap();
+        assert_eq!(SIGNATURE_CACHE_MAX.get() + 2, calls.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn failed_requests_are_not_kept() {
+        let cache = SignatureCache::default();
+        let calls = Arc::new(AtomicUsize::new(0));
+        let agent = AgentPubKey::from_raw_32(vec![1; 32]);
+        let data: Arc<[u8]> = b"data".to_vec().into();
+
+        assert!(cache
+            .sign(
+                agent.clone(),
+                data.clone(),
+                counting_signer(calls.clone(), Err("lair error".into())),
+            )
+            .await
+            .is_err());
+        cache
+            .sign(
+                agent,
+                data,
+                counting_signer(calls.clone(), Ok(Signature([2; 64]))),
+            )
+            .await
+            .unwrap();
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
@@ -75,7 +75,12 @@ pub async fn spawn_test_keystore() -> LairResult<MetaLairClient> {
     // return the client
     let client = keystore.new_client().await?;
     let (s, _) = tokio::sync::mpsc::unbounded_channel();
-    Ok(MetaLairClient(Arc::new(parking_lot::Mutex::new(client)), s))
+    Ok(MetaLairClient(
+        Arc::new(parking_lot::Mutex::new(client)),
+        s,
+        None,
+        Default::default(),
+    ))
 }
 
 /// Generate a test keystore pre-populated with a couple test keypairs.
@@ -0,0 +1,36 @@
+use holochain_keystore::lair_keystore::*;
+
+#[tokio::test(flavor = "multi_thread")]
+async fn embedded_keystore_persists_seeds() {
+    let tmp = tempdir::TempDir::new("lair embedded test").unwrap();
+    let config_path = tmp.path().join("lair-keystore-config.yaml");
+    let passphrase = sodoken::BufRead::from(&b"passphrase"[..]);
+
+    let keystore = spawn_lair_keystore_embedded(&config_path, passphrase.clone())
+        .await
+        .unwrap();
+    let seed = keystore
+        .lair_client()
+        .new_seed("test-seed".into(), None, false)
+        .await
+        .unwrap();
+    keystore.shutdown().await.unwrap();
+
+    // the seed is still there when the keystore is opened again
+    let keystore = spawn_lair_keystore_embedded(&config_path, passphrase)
+        .await
+        .unwrap();
+    let entry = keystore
+        .lair_client()
+        .get_entry("test-seed".into())
+        .await
+        .unwrap();
+    match entry {
+        kitsune_p2p_types::dependencies::lair_keystore_api::prelude::LairEntryInfo::Seed {
+            seed_info,
+            ..
+        } => assert_eq!(seed_info.ed25519_pub_key, seed.ed25519_pub_key),
+        other => panic!("unexpected entry: {other:?}"),
+    }
+    keystore.shutdown().await.unwrap();
+}
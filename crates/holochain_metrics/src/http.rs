@@ -0,0 +1,103 @@
+//! The HTTP server which the conductor's HTTP endpoints, such as the
+//! [`prometheus`](crate::prometheus) metrics, are served with.
+
+use std::time::Duration;
+use warp::filters::BoxedFilter;
+use warp::reply::Response;
+
+/// How long a client may take to send the headers of a request before its
+/// connection is closed, so that clients which send slowly can't hold on to
+/// connections.
+pub const HEADER_READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Answer requests on the listener with the `routes`, until the listener fails.
+/// Requests which no route matches are answered with a 404.
+pub async fn serve(
+    listener: tokio::net::TcpListener,
+    routes: BoxedFilter<(Response,)>,
+) -> std::io::Result<()> {
+    serve_with_header_read_timeout(listener, routes, HEADER_READ_TIMEOUT).await
+}
+
+async fn serve_with_header_read_timeout(
+    listener: tokio::net::TcpListener,
+    routes: BoxedFilter<(Response,)>,
+    header_read_timeout: Duration,
+) -> std::io::Result<()> {
+    let service = warp::service(routes);
+    let make_service = hyper::service::make_service_fn(move |_| {
+        let service = service.clone();
+        async move { Ok::<_, std::convert::Infallible>(service) }
+    });
+    let incoming = hyper::server::conn::AddrIncoming::from_listener(listener)
+        .map_err(std::io::Error::other)?;
+    hyper::Server::builder(incoming)
+        .http1_header_read_timeout(header_read_timeout)
+        .serve(make_service)
+        .await
+        .map_err(std::io::Error::other)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use warp::{Filter, Reply};
+
+    async fn spawn_server(header_read_timeout: Duration) -> std::net::SocketAddr {
+        let routes = warp::path!("hello")
+            .and(warp::get())
+            .map(|| "hello".into_response())
+            .boxed();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::task::spawn(serve_with_header_read_timeout(
+            listener,
+            routes,
+            header_read_timeout,
+        ));
+        addr
+    }
+
+    async fn get(addr: std::net::SocketAddr, path: &str) -> String {
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(
+                format!("GET {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+                    .as_bytes(),
+            )
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn serves_routes() {
+        let addr = spawn_server(HEADER_READ_TIMEOUT).await;
+
+        let response = get(addr, "/hello").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{response}");
+        assert!(response.ends_with("hello"), "{response}");
+
+        let response = get(addr, "/other").await;
+        assert!(
+            response.starts_with("HTTP/1.1 404 Not Found\r\n"),
+            "{response}"
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn closes_connections_which_send_headers_too_slowly() {
+        let addr = spawn_server(Duration::from_millis(100)).await;
+
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream.write_all(b"GET /hello HTTP/1.1\r\n").await.unwrap();
+        let mut response = Vec::new();
+        tokio::time::timeout(Duration::from_secs(5), stream.read_to_end(&mut response))
+            .await
+            .expect("the connection was not closed")
+            .unwrap();
+    }
+}
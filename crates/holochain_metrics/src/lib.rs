@@ -31,6 +31,11 @@
 //!   - The influxdb auth token must have permission to write to all buckets
 //!   - Metrics will be set up to report to this already running InfluxDB.
 //!
+//! Metrics can also be served over HTTP for a Prometheus server to scrape,
+//! with the [`HolochainMetricsConfig::Prometheus`] config. The conductor
+//! uses this when the `metrics` section of its config is set, see the
+//! [`prometheus`] module for how metric names are converted.
+//!
 //! ## Metric Naming Conventions
 //!
 //! We will largely attempt to follow the guidelines for metric naming
@@ -89,6 +94,8 @@
 //! | `kitsune.peer.send.byte.count` | `u64_histogram` | `By` | When kitsune sends data to a remote peer. |- `remote_id`: the base64 remote peer id.<br />- `is_error`: if the send failed. |
 //! | `kitsune.gossip.generate_op_blooms.duration` | `f64_histogram` | `s` | The time taken to generate op blooms for gossip. | - `space`: The space (dna_hash representation) that gossip is being performed for.<br />- `batch_size`: The number of ops that were included in the bloom batch for this observation. |
 //! | `kitsune.gossip.generate_op_region_set.duration` | `f64_histogram` | `s` | The time taken to generate op region sets for gossip. | - `space`: The space (dna_hash representation) that gossip is being performed for. |
+//! | `kitsune.gossip.byte.count` | `u64_counter` | `By` | Bytes of gossip messages sent and received. | - `space`: The space (dna_hash representation) that gossip is being performed for.<br />- `gossip_type`: `recent` or `historical`.<br />- `direction`: `send` or `recv`. |
+//! | `kitsune.fetch_pool.size` | `u64_observable_gauge` | | The number of items waiting to be fetched. | |
 //! | `tx5.conn.ice.send` | `u64_observable_counter` | `By` | Bytes sent on ice channel. |- `remote_id`: the base64 remote peer id.<br />- `state_uniq`: endpoint identifier.<br />- `conn_uniq`: connection identifier. |
 //! | `tx5.conn.ice.recv` | `u64_observable_counter` | `By` | Bytes received on ice channel. |- `remote_id`: the base64 remote peer id.<br />- `state_uniq`: endpoint identifier.<br />- `conn_uniq`: connection identifier. |
 //! | `tx5.conn.data.send` | `u64_observable_counter` | `By` | Bytes sent on data channel. |- `remote_id`: the base64 remote peer id.<br />- `state_uniq`: endpoint identifier.<br />- `conn_uniq`: connection identifier. |
@@ -96,12 +103,27 @@
 //! | `tx5.conn.data.send.message.count` | `u64_observable_counter` | | Message count sent on data channel. |- `remote_id`: the base64 remote peer id.<br />- `state_uniq`: endpoint identifier.<br />- `conn_uniq`: connection identifier. |
 //! | `tx5.conn.data.recv.message.count` | `u64_observable_counter` | | Message count received on data channel. |- `remote_id`: the base64 remote peer id.<br />- `state_uniq`: endpoint identifier.<br />- `conn_uniq`: connection identifier. |
 //! | `hc.conductor.p2p_event.duration`  | `f64_histogram` | `s` | The time spent processing a p2p event. |- `dna_hash`: The DNA hash that this event is being sent on behalf of. |
+//! | `hc.conductor.zome_call.duration` | `f64_histogram` | `s` | The time taken to handle a zome call. |- `dna_hash`: The DNA hash of the cell being called.<br />- `agent`: The agent of the cell being called.<br />- `zome`: The zome being called.<br />- `fn`: The function being called.<br />- `is_error`: if the call did not return successfully. |
+//! | `hc.conductor.slow_operation.count` | `u64_counter` | | Zome calls and workflow runs which took longer than their threshold in the conductor tuning params. |- `kind`: `zome_call` or `workflow`.<br />- `dna_hash`: The DNA hash the operation ran for.<br />- `target`: `{zome}/{fn}` for zome calls, or the name of the workflow. |
+//! | `hc.conductor.workflow.queue.depth` | `u64_observable_gauge` | | The number of ops waiting to be processed by a workflow. |- `workflow`: The name of the workflow.<br />- `dna_hash`: The DNA hash that this workflow is running for. |
 //! | `hc.conductor.post_commit.duration` | `f64_histogram` | `s` | The time spent executing a post commit. |- `dna_hash`: The DNA hash that this post commit is running for.<br />- `agent`: The agent running the post commit. |
+//! | `hc.conductor.incoming_ops.insert_rate` | `f64_histogram` | `ops/s` | How many incoming ops were inserted per second by each batch of the incoming ops workflow. |- `dna_hash`: The DNA hash that the ops were inserted for. |
 //! | `hc.conductor.workflow.duration` | `f64_histogram` | `s` | The time spent running a workflow. |- `workflow`: The name of the workflow.<br />- `dna_hash`: The DNA hash that this workflow is running for.<br />- `agent`: (optional) The agent that this workflow is running for if the workflow is cell bound. |
+//! | `hc.conductor.workflow_runtime.active` | `u64_observable_gauge` | | The number of workflow runs which are running or waiting on a workflow runtime. |- `runtime`: The index of the workflow runtime. |
+//! | `hc.conductor.workflow_runtime.wait.duration` | `f64_histogram` | `s` | The time a workflow run waited for a thread of its workflow runtime. |- `runtime`: The index of the workflow runtime. |
 //! | `hc.cascade.duration` | `f64_histogram` | `s` | The time taken to execute a cascade query. | |
+//! | `hc.cascade.query_cache.hits` | `u64_counter` | | The number of cascade queries which were answered by the query cache. |- `query`: The kind of query, `record` or `links`. |
+//! | `hc.cascade.query_cache.misses` | `u64_counter` | | The number of cascade queries which had to be run on the databases. |- `query`: The kind of query, `record` or `links`. |
 //! | `hc.db.pool.utilization` | `f64_gauge` | | The utilisation of connections in the pool. |- `kind`: The kind of database such as Conductor, Wasm or Dht etc.<br />- `id`: The unique identifier for this database if multiple instances can exist, such as a Dht database. |
 //! | `hc.db.connections.use_time` | `f64_histogram` | `s` | The time between borrowing a connection and returning it to the pool. |- `kind`: The kind of database such as Conductor, Wasm or Dht etc.<br />- `id`: The unique identifier for this database if multiple instances can exist, such as a Dht database. |
 //! | `hc.ribosome.wasm.usage` | `u64_counter` | | The metered usage of a wasm ribosome. | - `dna`: The DNA hash that this wasm is metered for.<br />- `zome`: The zome that this wasm is metered for.<br />- `fn`: The function that this wasm is metered for.<br />- `agent`: The agent that this wasm is metered for (if there is one). |
+//! | `hc.app.metric` | `f64_histogram` | | A measurement emitted by an app through the `emit_metric` host function. | - `name`: The app-defined metric name.<br />- `dna`: The DNA hash of the cell that emitted the metric.<br />- `zome`: The zome that emitted the metric.<br />- `agent`: The agent of the cell that emitted the metric (if there is one).<br />- Any tags set by the app. |
+
+#[cfg(feature = "http")]
+pub mod http;
+
+#[cfg(feature = "prometheus")]
+pub mod prometheus;
 
 #[cfg(feature = "influxive")]
 const DASH_NETWORK_STATS: &[u8] = include_bytes!("dashboards/networkstats.json");
@@ -147,6 +169,13 @@ pub enum HolochainMetricsConfig {
         /// The meter provider config for setting up opentelemetry.
         otel_config: influxive::InfluxiveMeterProviderConfig,
     },
+
+    #[cfg(feature = "prometheus")]
+    /// Serve metrics over HTTP for a Prometheus server to scrape.
+    Prometheus {
+        /// The address to listen for scrape requests on.
+        bind_address: std::net::SocketAddr,
+    },
 }
 
 impl HolochainMetricsConfig {
@@ -239,6 +268,36 @@ impl HolochainMetricsConfig {
             } => {
                 Self::init_influxive_child_svc(*child_svc_config, otel_config).await;
             }
+            #[cfg(feature = "prometheus")]
+            Self::Prometheus { bind_address } => {
+                Self::init_prometheus(bind_address).await;
+            }
+        }
+    }
+
+    #[cfg(feature = "prometheus")]
+    async fn init_prometheus(bind_address: std::net::SocketAddr) {
+        tracing::info!(%bind_address, "initializing holochain_metrics");
+
+        match tokio::net::TcpListener::bind(bind_address).await {
+            Ok(listener) => {
+                let meter_provider = prometheus::PrometheusMeterProvider::new();
+
+                // setup opentelemetry to use our metrics collector
+                opentelemetry_api::global::set_meter_provider(meter_provider.clone());
+
+                if let Ok(addr) = listener.local_addr() {
+                    tracing::info!(%addr, "prometheus metrics listener running");
+                }
+                tokio::task::spawn(async move {
+                    if let Err(err) = prometheus::serve_prometheus(listener, meter_provider).await {
+                        tracing::error!(?err, "prometheus metrics listener failed");
+                    }
+                });
+            }
+            Err(err) => {
+                tracing::warn!(?err, "unable to start prometheus metrics listener");
+            }
         }
     }
 
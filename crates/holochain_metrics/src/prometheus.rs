@@ -0,0 +1,838 @@
+//! Serve metrics for Prometheus to scrape.
+//!
+//! The [`PrometheusMeterProvider`] keeps the current value of every metric
+//! in memory, and [`metrics_route`] answers HTTP requests to `/metrics` with
+//! them, encoded by the [`prometheus`](::prometheus) crate in the text
+//! exposition format. [`serve_prometheus`] serves the route with the
+//! [`http`](crate::http) server.
+//!
+//! Metric names are converted to Prometheus names by replacing the dots with
+//! underscores and appending the unit, so `hc.conductor.workflow.duration`
+//! with the unit `s` becomes `hc_conductor_workflow_duration_seconds`.
+//! Counters also get the `_total` suffix. Attributes become labels.
+
+use ::prometheus::proto;
+use opentelemetry_api::metrics::*;
+use opentelemetry_api::KeyValue;
+use std::borrow::Cow;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use warp::filters::BoxedFilter;
+use warp::reply::Response;
+use warp::{Filter, Reply};
+
+/// Histogram bucket bounds for metrics measured in seconds.
+const SECONDS_BUCKETS: &[f64] = &[
+    0.001, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0,
+];
+
+/// Histogram bucket bounds for metrics measured in bytes.
+const BYTES_BUCKETS: &[f64] = &[
+    64.0, 256.0, 1024.0, 4096.0, 16384.0, 65536.0, 262144.0, 1048576.0, 4194304.0, 16777216.0,
+];
+
+/// Histogram bucket bounds for metrics with any other unit.
+const DEFAULT_BUCKETS: &[f64] = &[
+    1.0, 2.0, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0, 5000.0, 10000.0,
+];
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kind {
+    Counter,
+    Gauge,
+    Histogram,
+}
+
+impl Kind {
+    fn metric_type(&self) -> proto::MetricType {
+        match self {
+            Kind::Counter => proto::MetricType::COUNTER,
+            Kind::Gauge => proto::MetricType::GAUGE,
+            Kind::Histogram => proto::MetricType::HISTOGRAM,
+        }
+    }
+}
+
+/// How an instrument changes the series it reports to.
+#[derive(Clone, Copy)]
+enum Update {
+    /// Add to the value, for counters.
+    Add,
+    /// Replace the value, for observed instruments.
+    Set,
+    /// Count the value in the histogram buckets.
+    Record,
+}
+
+type Labels = Vec<(String, String)>;
+
+enum Series {
+    Value(f64),
+    Histogram {
+        /// Cumulative counts for each of the family's bucket bounds.
+        buckets: Vec<u64>,
+        sum: f64,
+        count: u64,
+    },
+}
+
+/// All the series of one metric.
+struct Family {
+    kind: Kind,
+    help: Option<String>,
+    bounds: &'static [f64],
+    series: Mutex<BTreeMap<Labels, Series>>,
+}
+
+impl Family {
+    fn update(&self, update: Update, labels: Labels, value: f64) {
+        let mut series = self.series.lock().unwrap();
+        match update {
+            Update::Add => {
+                if let Series::Value(total) = series.entry(labels).or_insert(Series::Value(0.0)) {
+                    *total += value;
+                }
+            }
+            Update::Set => {
+                series.insert(labels, Series::Value(value));
+            }
+            Update::Record => {
+                let entry = series.entry(labels).or_insert_with(|| Series::Histogram {
+                    buckets: vec![0; self.bounds.len()],
+                    sum: 0.0,
+                    count: 0,
+                });
+                if let Series::Histogram {
+                    buckets,
+                    sum,
+                    count,
+                } = entry
+                {
+                    for (bound, bucket) in self.bounds.iter().zip(buckets.iter_mut()) {
+                        if value <= *bound {
+                            *bucket += 1;
+                        }
+                    }
+                    *sum += value;
+                    *count += 1;
+                }
+            }
+        }
+    }
+
+    fn collect(&self, name: &str) -> Option<proto::MetricFamily> {
+        let series = self.series.lock().unwrap();
+        if series.is_empty() {
+            return None;
+        }
+        let metrics: Vec<proto::Metric> = series
+            .iter()
+            .map(|(labels, series)| {
+                let mut metric = proto::Metric::default();
+                let labels: Vec<proto::LabelPair> = labels
+                    .iter()
+                    .map(|(name, value)| {
+                        let mut label = proto::LabelPair::default();
+                        label.set_name(name.clone());
+                        label.set_value(value.clone());
+                        label
+                    })
+                    .collect();
+                metric.set_label(labels.into());
+                match (self.kind, series) {
+                    (Kind::Counter, Series::Value(value)) => {
+                        let mut counter = proto::Counter::default();
+                        counter.set_value(*value);
+                        metric.set_counter(counter);
+                    }
+                    (_, Series::Value(value)) => {
+                        let mut gauge = proto::Gauge::default();
+                        gauge.set_value(*value);
+                        metric.set_gauge(gauge);
+                    }
+                    (
+                        _,
+                        Series::Histogram {
+                            buckets,
+                            sum,
+                            count,
+                        },
+                    ) => {
+                        let mut histogram = proto::Histogram::default();
+                        let buckets: Vec<proto::Bucket> = self
+                            .bounds
+                            .iter()
+                            .zip(buckets)
+                            .map(|(bound, count)| {
+                                let mut bucket = proto::Bucket::default();
+                                bucket.set_upper_bound(*bound);
+                                bucket.set_cumulative_count(*count);
+                                bucket
+                            })
+                            .collect();
+                        histogram.set_bucket(buckets.into());
+                        histogram.set_sample_sum(*sum);
+                        histogram.set_sample_count(*count);
+                        metric.set_histogram(histogram);
+                    }
+                }
+                metric
+            })
+            .collect();
+
+        let mut family = proto::MetricFamily::default();
+        family.set_name(name.to_string());
+        if let Some(help) = &self.help {
+            family.set_help(help.clone());
+        }
+        family.set_field_type(self.kind.metric_type());
+        family.set_metric(metrics.into());
+        Some(family)
+    }
+}
+
+/// Replace the characters which can't be used in a Prometheus name.
+fn sanitize(name: &str) -> String {
+    let mut name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    name
+}
+
+fn prometheus_name(name: &str, unit: Option<&Unit>, kind: Kind) -> String {
+    let mut name = sanitize(name);
+    match unit.map(|unit| unit.as_str()) {
+        None | Some("") | Some("1") => {}
+        Some("s") => name.push_str("_seconds"),
+        Some("ms") => name.push_str("_milliseconds"),
+        Some("By") => name.push_str("_bytes"),
+        Some(unit) => {
+            name.push('_');
+            name.push_str(&sanitize(unit));
+        }
+    }
+    if kind == Kind::Counter {
+        name.push_str("_total");
+    }
+    name
+}
+
+type Callback = Arc<dyn Fn() + 'static + Send + Sync>;
+
+#[derive(Default)]
+struct Registry {
+    families: Mutex<BTreeMap<String, Arc<Family>>>,
+    callbacks: Mutex<HashMap<u64, Callback>>,
+}
+
+impl Registry {
+    /// Get the family for a metric, creating it if this is the first
+    /// instrument for this metric.
+    fn family(
+        &self,
+        name: &str,
+        description: Option<Cow<'static, str>>,
+        unit: Option<&Unit>,
+        kind: Kind,
+    ) -> Arc<Family> {
+        let new_family = || {
+            Arc::new(Family {
+                kind,
+                help: description.as_ref().map(|d| d.to_string()),
+                bounds: match unit.map(|unit| unit.as_str()) {
+                    Some("s") => SECONDS_BUCKETS,
+                    Some("By") => BYTES_BUCKETS,
+                    _ => DEFAULT_BUCKETS,
+                },
+                series: Default::default(),
+            })
+        };
+        let name = prometheus_name(name, unit, kind);
+        let mut families = self.families.lock().unwrap();
+        let family = families.entry(name.clone()).or_insert_with(&new_family);
+        if family.kind == kind {
+            family.clone()
+        } else {
+            tracing::warn!(%name, ?kind, existing = ?family.kind, "metric already exists with a different type, it will not be reported");
+            new_family()
+        }
+    }
+
+    fn push_callback(&self, callback: Callback) -> u64 {
+        static ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
+        let id = ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        self.callbacks.lock().unwrap().insert(id, callback);
+        id
+    }
+
+    fn encode(&self) -> String {
+        // update the observed instruments first, without holding the lock
+        // so that callbacks may create new instruments
+        let callbacks: Vec<Callback> = self.callbacks.lock().unwrap().values().cloned().collect();
+        for callback in callbacks {
+            callback();
+        }
+
+        let families: Vec<(String, Arc<Family>)> = self
+            .families
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(name, family)| (name.clone(), family.clone()))
+            .collect();
+        let families: Vec<proto::MetricFamily> = families
+            .iter()
+            .filter_map(|(name, family)| family.collect(name))
+            .collect();
+        ::prometheus::TextEncoder::new()
+            .encode_to_string(&families)
+            .unwrap_or_else(|err| {
+                tracing::warn!(?err, "failed to encode metrics");
+                String::new()
+            })
+    }
+}
+
+/// Values which instruments report.
+trait MetricValue: 'static + Send + Sync {
+    fn to_f64(self) -> f64;
+}
+
+impl MetricValue for f64 {
+    fn to_f64(self) -> f64 {
+        self
+    }
+}
+
+impl MetricValue for u64 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl MetricValue for i64 {
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+struct Instrument {
+    this: std::sync::Weak<Self>,
+    family: Arc<Family>,
+    update: Update,
+    attributes: Option<Arc<[KeyValue]>>,
+}
+
+impl Instrument {
+    fn report(&self, value: f64, attributes: &[KeyValue]) {
+        let mut labels: Labels = self
+            .attributes
+            .iter()
+            .flat_map(|meter_attributes| meter_attributes.iter())
+            .chain(attributes)
+            .map(|kv| (sanitize(kv.key.as_str()), kv.value.to_string()))
+            .collect();
+        labels.sort_by(|a, b| a.0.cmp(&b.0));
+        labels.dedup_by(|a, b| a.0 == b.0);
+        self.family.update(self.update, labels, value);
+    }
+}
+
+impl<T: MetricValue> SyncCounter<T> for Instrument {
+    fn add(&self, value: T, attributes: &[KeyValue]) {
+        self.report(value.to_f64(), attributes)
+    }
+}
+
+impl<T: MetricValue> SyncUpDownCounter<T> for Instrument {
+    fn add(&self, value: T, attributes: &[KeyValue]) {
+        self.report(value.to_f64(), attributes)
+    }
+}
+
+impl<T: MetricValue> SyncHistogram<T> for Instrument {
+    fn record(&self, value: T, attributes: &[KeyValue]) {
+        self.report(value.to_f64(), attributes)
+    }
+}
+
+impl<T: MetricValue> AsyncInstrument<T> for Instrument {
+    fn observe(&self, measurement: T, attributes: &[KeyValue]) {
+        self.report(measurement.to_f64(), attributes)
+    }
+
+    fn as_any(&self) -> Arc<dyn std::any::Any> {
+        // the instrument is only ever used through the Arc it was created in
+        self.this.upgrade().unwrap()
+    }
+}
+
+struct PrometheusInstrumentProvider {
+    registry: Arc<Registry>,
+    attributes: Option<Arc<[KeyValue]>>,
+}
+
+impl PrometheusInstrumentProvider {
+    fn instrument(
+        &self,
+        name: Cow<'static, str>,
+        description: Option<Cow<'static, str>>,
+        unit: Option<Unit>,
+        kind: Kind,
+        update: Update,
+    ) -> Arc<Instrument> {
+        let family = self
+            .registry
+            .family(&name, description, unit.as_ref(), kind);
+        Arc::new_cyclic(|this| Instrument {
+            this: this.clone(),
+            family,
+            update,
+            attributes: self.attributes.clone(),
+        })
+    }
+
+    fn observable<T: MetricValue>(
+        &self,
+        name: Cow<'static, str>,
+        description: Option<Cow<'static, str>>,
+        unit: Option<Unit>,
+        kind: Kind,
+        callbacks: Vec<opentelemetry_api::metrics::Callback<T>>,
+    ) -> Arc<Instrument> {
+        let instrument = self.instrument(name, description, unit, kind, Update::Set);
+        if !callbacks.is_empty() {
+            let observed = instrument.clone();
+            self.registry.push_callback(Arc::new(move || {
+                for callback in callbacks.iter() {
+                    callback(&*observed);
+                }
+            }));
+        }
+        instrument
+    }
+}
+
+impl InstrumentProvider for PrometheusInstrumentProvider {
+    fn u64_counter(
+        &self,
+        name: Cow<'static, str>,
+        description: Option<Cow<'static, str>>,
+        unit: Option<Unit>,
+    ) -> Result<Counter<u64>> {
+        Ok(Counter::new(self.instrument(
+            name,
+            description,
+            unit,
+            Kind::Counter,
+            Update::Add,
+        )))
+    }
+
+    fn f64_counter(
+        &self,
+        name: Cow<'static, str>,
+        description: Option<Cow<'static, str>>,
+        unit: Option<Unit>,
+    ) -> Result<Counter<f64>> {
+        Ok(Counter::new(self.instrument(
+            name,
+            description,
+            unit,
+            Kind::Counter,
+            Update::Add,
+        )))
+    }
+
+    fn u64_observable_counter(
+        &self,
+        name: Cow<'static, str>,
+        description: Option<Cow<'static, str>>,
+        unit: Option<Unit>,
+        callbacks: Vec<opentelemetry_api::metrics::Callback<u64>>,
+    ) -> Result<ObservableCounter<u64>> {
+        Ok(ObservableCounter::new(self.observable(
+            name,
+            description,
+            unit,
+            Kind::Counter,
+            callbacks,
+        )))
+    }
+
+    fn f64_observable_counter(
+        &self,
+        name: Cow<'static, str>,
+        description: Option<Cow<'static, str>>,
+        unit: Option<Unit>,
+        callbacks: Vec<opentelemetry_api::metrics::Callback<f64>>,
+    ) -> Result<ObservableCounter<f64>> {
+        Ok(ObservableCounter::new(self.observable(
+            name,
+            description,
+            unit,
+            Kind::Counter,
+            callbacks,
+        )))
+    }
+
+    fn i64_up_down_counter(
+        &self,
+        name: Cow<'static, str>,
+        description: Option<Cow<'static, str>>,
+        unit: Option<Unit>,
+    ) -> Result<UpDownCounter<i64>> {
+        Ok(UpDownCounter::new(self.instrument(
+            name,
+            description,
+            unit,
+            Kind::Gauge,
+            Update::Add,
+        )))
+    }
+
+    fn f64_up_down_counter(
+        &self,
+        name: Cow<'static, str>,
+        description: Option<Cow<'static, str>>,
+        unit: Option<Unit>,
+    ) -> Result<UpDownCounter<f64>> {
+        Ok(UpDownCounter::new(self.instrument(
+            name,
+            description,
+            unit,
+            Kind::Gauge,
+            Update::Add,
+        )))
+    }
+
+    fn i64_observable_up_down_counter(
+        &self,
+        name: Cow<'static, str>,
+        description: Option<Cow<'static, str>>,
+        unit: Option<Unit>,
+        callbacks: Vec<opentelemetry_api::metrics::Callback<i64>>,
+    ) -> Result<ObservableUpDownCounter<i64>> {
+        Ok(ObservableUpDownCounter::new(self.observable(
+            name,
+            description,
+            unit,
+            Kind::Gauge,
+            callbacks,
+        )))
+    }
+
+    fn f64_observable_up_down_counter(
+        &self,
+        name: Cow<'static, str>,
+        description: Option<Cow<'static, str>>,
+        unit: Option<Unit>,
+        callbacks: Vec<opentelemetry_api::metrics::Callback<f64>>,
+    ) -> Result<ObservableUpDownCounter<f64>> {
+        Ok(ObservableUpDownCounter::new(self.observable(
+            name,
+            description,
+            unit,
+            Kind::Gauge,
+            callbacks,
+        )))
+    }
+
+    fn u64_observable_gauge(
+        &self,
+        name: Cow<'static, str>,
+        description: Option<Cow<'static, str>>,
+        unit: Option<Unit>,
+        callbacks: Vec<opentelemetry_api::metrics::Callback<u64>>,
+    ) -> Result<ObservableGauge<u64>> {
+        Ok(ObservableGauge::new(self.observable(
+            name,
+            description,
+            unit,
+            Kind::Gauge,
+            callbacks,
+        )))
+    }
+
+    fn i64_observable_gauge(
+        &self,
+        name: Cow<'static, str>,
+        description: Option<Cow<'static, str>>,
+        unit: Option<Unit>,
+        callbacks: Vec<opentelemetry_api::metrics::Callback<i64>>,
+    ) -> Result<ObservableGauge<i64>> {
+        Ok(ObservableGauge::new(self.observable(
+            name,
+            description,
+            unit,
+            Kind::Gauge,
+            callbacks,
+        )))
+    }
+
+    fn f64_observable_gauge(
+        &self,
+        name: Cow<'static, str>,
+        description: Option<Cow<'static, str>>,
+        unit: Option<Unit>,
+        callbacks: Vec<opentelemetry_api::metrics::Callback<f64>>,
+    ) -> Result<ObservableGauge<f64>> {
+        Ok(ObservableGauge::new(self.observable(
+            name,
+            description,
+            unit,
+            Kind::Gauge,
+            callbacks,
+        )))
+    }
+
+    fn f64_histogram(
+        &self,
+        name: Cow<'static, str>,
+        description: Option<Cow<'static, str>>,
+        unit: Option<Unit>,
+    ) -> Result<Histogram<f64>> {
+        Ok(Histogram::new(self.instrument(
+            name,
+            description,
+            unit,
+            Kind::Histogram,
+            Update::Record,
+        )))
+    }
+
+    fn u64_histogram(
+        &self,
+        name: Cow<'static, str>,
+        description: Option<Cow<'static, str>>,
+        unit: Option<Unit>,
+    ) -> Result<Histogram<u64>> {
+        Ok(Histogram::new(self.instrument(
+            name,
+            description,
+            unit,
+            Kind::Histogram,
+            Update::Record,
+        )))
+    }
+
+    fn i64_histogram(
+        &self,
+        name: Cow<'static, str>,
+        description: Option<Cow<'static, str>>,
+        unit: Option<Unit>,
+    ) -> Result<Histogram<i64>> {
+        Ok(Histogram::new(self.instrument(
+            name,
+            description,
+            unit,
+            Kind::Histogram,
+            Update::Record,
+        )))
+    }
+
+    fn register_callback(
+        &self,
+        _instruments: &[Arc<dyn std::any::Any>],
+        callback: Box<dyn Fn(&dyn Observer) + Send + Sync>,
+    ) -> Result<Box<dyn CallbackRegistration>> {
+        struct O;
+        impl Observer for O {
+            fn observe_f64(
+                &self,
+                inst: &dyn AsyncInstrument<f64>,
+                measurement: f64,
+                attrs: &[KeyValue],
+            ) {
+                inst.observe(measurement, attrs);
+            }
+
+            fn observe_u64(
+                &self,
+                inst: &dyn AsyncInstrument<u64>,
+                measurement: u64,
+                attrs: &[KeyValue],
+            ) {
+                inst.observe(measurement, attrs);
+            }
+
+            fn observe_i64(
+                &self,
+                inst: &dyn AsyncInstrument<i64>,
+                measurement: i64,
+                attrs: &[KeyValue],
+            ) {
+                inst.observe(measurement, attrs);
+            }
+        }
+
+        let id = self.registry.push_callback(Arc::new(move || callback(&O)));
+
+        struct Unregister(u64, Arc<Registry>);
+
+        impl CallbackRegistration for Unregister {
+            fn unregister(&mut self) -> Result<()> {
+                self.1.callbacks.lock().unwrap().remove(&self.0);
+                Ok(())
+            }
+        }
+
+        Ok(Box::new(Unregister(id, self.registry.clone())))
+    }
+}
+
+/// An opentelemetry meter provider which keeps metrics to be scraped
+/// by Prometheus.
+#[derive(Clone, Default)]
+pub struct PrometheusMeterProvider(Arc<Registry>);
+
+impl PrometheusMeterProvider {
+    /// Constructor.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Encode the current values of all metrics in the Prometheus
+    /// text exposition format.
+    pub fn encode(&self) -> String {
+        self.0.encode()
+    }
+}
+
+impl MeterProvider for PrometheusMeterProvider {
+    fn versioned_meter(
+        &self,
+        _name: impl Into<Cow<'static, str>>,
+        _version: Option<impl Into<Cow<'static, str>>>,
+        _schema_url: Option<impl Into<Cow<'static, str>>>,
+        attributes: Option<Vec<KeyValue>>,
+    ) -> Meter {
+        Meter::new(Arc::new(PrometheusInstrumentProvider {
+            registry: self.0.clone(),
+            attributes: attributes.map(|a| a.into_boxed_slice().into()),
+        }))
+    }
+}
+
+/// The route which answers `GET /metrics` with the metrics of the provider.
+pub fn metrics_route(provider: PrometheusMeterProvider) -> BoxedFilter<(Response,)> {
+    warp::path!("metrics")
+        .and(warp::get())
+        .map(move || {
+            warp::reply::with_header(provider.encode(), "content-type", ::prometheus::TEXT_FORMAT)
+                .into_response()
+        })
+        .boxed()
+}
+
+/// Answer scrape requests on the listener with the metrics of the provider,
+/// until the listener fails.
+pub async fn serve_prometheus(
+    listener: tokio::net::TcpListener,
+    provider: PrometheusMeterProvider,
+) -> std::io::Result<()> {
+    crate::http::serve(listener, metrics_route(provider)).await
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn encodes_metrics() {
+        let provider = PrometheusMeterProvider::new();
+        let meter = provider.versioned_meter(
+            "test",
+            None::<&'static str>,
+            None::<&'static str>,
+            Some(vec![KeyValue::new("dna_hash", "abc")]),
+        );
+
+        let counter = meter
+            .u64_counter("test.request.count")
+            .with_description("Requests")
+            .init();
+        counter.add(2, &[]);
+        counter.add(3, &[]);
+
+        let histogram = meter
+            .f64_histogram("test.request.duration")
+            .with_unit(Unit::new("s"))
+            .init();
+        histogram.record(0.02, &[KeyValue::new("zome", "a\"b")]);
+        histogram.record(2.0, &[KeyValue::new("zome", "a\"b")]);
+
+        let gauge = meter.u64_observable_gauge("test.queue.size").init();
+        meter
+            .register_callback(&[gauge.as_any()], move |observer| {
+                observer.observe_u64(&gauge, 7, &[])
+            })
+            .unwrap();
+
+        let encoded = provider.encode();
+        for line in [
+            "# HELP test_request_count_total Requests",
+            "# TYPE test_request_count_total counter",
+            "test_request_count_total{dna_hash=\"abc\"} 5",
+            "# TYPE test_request_duration_seconds histogram",
+            "test_request_duration_seconds_bucket{dna_hash=\"abc\",zome=\"a\\\"b\",le=\"0.01\"} 0",
+            "test_request_duration_seconds_bucket{dna_hash=\"abc\",zome=\"a\\\"b\",le=\"0.025\"} 1",
+            "test_request_duration_seconds_bucket{dna_hash=\"abc\",zome=\"a\\\"b\",le=\"+Inf\"} 2",
+            "test_request_duration_seconds_sum{dna_hash=\"abc\",zome=\"a\\\"b\"} 2.02",
+            "test_request_duration_seconds_count{dna_hash=\"abc\",zome=\"a\\\"b\"} 2",
+            "# TYPE test_queue_size gauge",
+            "test_queue_size{dna_hash=\"abc\"} 7",
+        ] {
+            assert!(
+                encoded.lines().any(|l| l == line),
+                "missing line {line} in:\n{encoded}"
+            );
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn serves_metrics() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let provider = PrometheusMeterProvider::new();
+        provider
+            .meter("test")
+            .u64_counter("test.count")
+            .init()
+            .add(1, &[]);
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::task::spawn(serve_prometheus(listener, provider));
+
+        let get = |path: &'static str| async move {
+            let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+            stream
+                .write_all(
+                    format!("GET {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+                        .as_bytes(),
+                )
+                .await
+                .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            response
+        };
+
+        let response = get("/metrics").await;
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"), "{response}");
+        assert!(response.ends_with("test_count_total 1\n"), "{response}");
+
+        let response = get("/other").await;
+        assert!(
+            response.starts_with("HTTP/1.1 404 Not Found\r\n"),
+            "{response}"
+        );
+    }
+}
@@ -155,6 +155,20 @@ pub trait HolochainP2pDnaT: Send + Sync + 'static {
         basis: holo_hash::OpBasis,
     ) -> actor::HolochainP2pResult<bool>;
 
+    /// When an agent last announced its presence to its neighborhood.
+    async fn get_agent_presence(
+        &self,
+        agent: AgentPubKey,
+    ) -> actor::HolochainP2pResult<Option<AgentPresence>>;
+
+    /// Offer ops which have fallen outside the storage arcs of this node's agents
+    /// to the authorities of their basis, returning the ops which each authority holds.
+    async fn handoff_ops(
+        &self,
+        basis: holo_hash::OpBasis,
+        ops: Vec<holochain_types::dht_op::DhtOp>,
+    ) -> actor::HolochainP2pResult<Vec<(AgentPubKey, Vec<holo_hash::DhtOpHash>)>>;
+
     /// Messages between agents driving a countersigning session.
     async fn countersigning_session_negotiation(
         &self,
@@ -396,6 +410,25 @@ impl HolochainP2pDnaT for HolochainP2pDna {
             .await
     }
 
+    async fn get_agent_presence(
+        &self,
+        agent: AgentPubKey,
+    ) -> actor::HolochainP2pResult<Option<AgentPresence>> {
+        self.sender
+            .get_agent_presence((*self.dna_hash).clone(), agent)
+            .await
+    }
+
+    async fn handoff_ops(
+        &self,
+        basis: holo_hash::OpBasis,
+        ops: Vec<holochain_types::dht_op::DhtOp>,
+    ) -> actor::HolochainP2pResult<Vec<(AgentPubKey, Vec<holo_hash::DhtOpHash>)>> {
+        self.sender
+            .handoff_ops((*self.dna_hash).clone(), basis, ops)
+            .await
+    }
+
     async fn countersigning_session_negotiation(
         &self,
         agents: Vec<AgentPubKey>,
@@ -14,6 +14,7 @@ use kitsune_p2p::KitsuneOpData;
 use kitsune_p2p::PreflightUserData;
 use kitsune_p2p_fetch::FetchContext;
 
+use crate::presence::*;
 use crate::types::AgentPubKeyExt;
 
 use ghost_actor::dependencies::tracing;
@@ -273,6 +274,36 @@ impl WrapEvtSender {
         )
     }
 
+    fn handoff_ops(
+        &self,
+        dna_hash: DnaHash,
+        to_agent: AgentPubKey,
+        ops: Vec<holochain_types::dht_op::DhtOp>,
+    ) -> impl Future<Output = HolochainP2pResult<Vec<holo_hash::DhtOpHash>>> + 'static + Send {
+        let op_count = ops.len();
+        timing_trace!(
+            true,
+            { self.0.handoff_ops(dna_hash, to_agent, ops) },
+            %op_count,
+            a = "recv_handoff_ops",
+        )
+    }
+
+    fn op_census(
+        &self,
+        dna_hash: DnaHash,
+        to_agent: AgentPubKey,
+        arq_set: kitsune_p2p::dht::arq::ArqSet,
+    ) -> impl Future<Output = HolochainP2pResult<kitsune_p2p::dht::region_set::RegionSetLtcs>>
+           + 'static
+           + Send {
+        timing_trace!(
+            true,
+            { self.0.op_census(dna_hash, to_agent, arq_set) },
+            a = "recv_op_census",
+        )
+    }
+
     fn validation_receipts_received(
         &self,
         dna_hash: DnaHash,
@@ -364,6 +395,9 @@ pub(crate) struct HolochainP2pActor {
     evt_sender: WrapEvtSender,
     kitsune_p2p: ghost_actor::GhostSender<kitsune_p2p::actor::KitsuneP2p>,
     host: kitsune_p2p::HostApi,
+    presence: PresenceStore,
+    /// The tasks which announce the presence of each joined agent.
+    presence_announcers: HashMap<(DnaHash, AgentPubKey), tokio::task::AbortHandle>,
 }
 
 impl ghost_actor::GhostControlHandler for HolochainP2pActor {
@@ -371,6 +405,9 @@ impl ghost_actor::GhostControlHandler for HolochainP2pActor {
         self,
     ) -> ghost_actor::dependencies::must_future::MustBoxFuture<'static, ()> {
         use ghost_actor::GhostControlSender;
+        for announcer in self.presence_announcers.values() {
+            announcer.abort();
+        }
         async move {
             let _ = self.kitsune_p2p.ghost_actor_shutdown_immediate().await;
         }
@@ -433,6 +470,8 @@ impl HolochainP2pActor {
             evt_sender: WrapEvtSender(evt_sender),
             kitsune_p2p,
             host,
+            presence: PresenceStore::default(),
+            presence_announcers: HashMap::new(),
         })
     }
 
@@ -600,6 +639,40 @@ impl HolochainP2pActor {
         .into())
     }
 
+    /// receiving ops which a remote node is handing off to us
+    fn handle_incoming_handoff_ops(
+        &mut self,
+        dna_hash: DnaHash,
+        to_agent: AgentPubKey,
+        ops: Vec<holochain_types::dht_op::DhtOp>,
+    ) -> kitsune_p2p::actor::KitsuneP2pHandlerResult<Vec<u8>> {
+        let evt_sender = self.evt_sender.clone();
+        Ok(async move {
+            let res = evt_sender.handoff_ops(dna_hash, to_agent, ops).await;
+            res.and_then(|held| Ok(holochain_serialized_bytes::encode(&held)?))
+                .map_err(kitsune_p2p::KitsuneP2pError::from)
+        }
+        .boxed()
+        .into())
+    }
+
+    /// receiving a request from a remote node for the regions of the ops we hold
+    fn handle_incoming_op_census(
+        &mut self,
+        dna_hash: DnaHash,
+        to_agent: AgentPubKey,
+        arq_set: kitsune_p2p::dht::arq::ArqSet,
+    ) -> kitsune_p2p::actor::KitsuneP2pHandlerResult<Vec<u8>> {
+        let evt_sender = self.evt_sender.clone();
+        Ok(async move {
+            let res = evt_sender.op_census(dna_hash, to_agent, arq_set).await;
+            res.and_then(|regions| Ok(holochain_serialized_bytes::encode(&regions)?))
+                .map_err(kitsune_p2p::KitsuneP2pError::from)
+        }
+        .boxed()
+        .into())
+    }
+
     /// receiving an incoming publish from a remote node
     fn handle_incoming_publish(
         &mut self,
@@ -887,6 +960,23 @@ impl kitsune_p2p::event::KitsuneP2pEventHandler for HolochainP2pActor {
                 )
                 .into())
             }
+            crate::wire::WireMessage::Presence { .. } => {
+                Err(HolochainP2pError::invalid_p2p_message(
+                    "invalid: presence announcements are broadcast, not requests".to_string(),
+                )
+                .into())
+            }
+            crate::wire::WireMessage::GetAgentPresence { agent } => {
+                let response = holochain_serialized_bytes::encode(&self.presence.get(&space, &agent))
+                    .map_err(HolochainP2pError::from)?;
+                Ok(async move { Ok(response) }.boxed().into())
+            }
+            crate::wire::WireMessage::HandoffOps { ops } => {
+                self.handle_incoming_handoff_ops(space, to_agent, ops)
+            }
+            crate::wire::WireMessage::OpCensus { arq_set } => {
+                self.handle_incoming_op_census(space, to_agent, arq_set)
+            }
         }
     }
 
@@ -914,7 +1004,10 @@ impl kitsune_p2p::event::KitsuneP2pEventHandler for HolochainP2pActor {
             | crate::wire::WireMessage::GetLinks { .. }
             | crate::wire::WireMessage::CountLinks { .. }
             | crate::wire::WireMessage::GetAgentActivity { .. }
-            | crate::wire::WireMessage::MustGetAgentActivity { .. } => {
+            | crate::wire::WireMessage::MustGetAgentActivity { .. }
+            | crate::wire::WireMessage::GetAgentPresence { .. }
+            | crate::wire::WireMessage::HandoffOps { .. }
+            | crate::wire::WireMessage::OpCensus { .. } => {
                 Err(HolochainP2pError::invalid_p2p_message(
                     "invalid call type message in a notify".to_string(),
                 )
@@ -980,6 +1073,17 @@ impl kitsune_p2p::event::KitsuneP2pEventHandler for HolochainP2pActor {
             crate::wire::WireMessage::PublishCountersign { flag, op } => {
                 self.handle_incoming_publish(space, false, flag, vec![op])
             }
+            crate::wire::WireMessage::Presence { announcement } => {
+                let presence = self.presence.clone();
+                Ok(async move {
+                    if announcement.is_valid(&space).await {
+                        presence.record(&space, announcement, Timestamp::now());
+                    }
+                    Ok(())
+                }
+                .boxed()
+                .into())
+            }
         }
     }
 
@@ -1150,8 +1254,23 @@ impl HolochainP2pHandler for HolochainP2pActor {
         maybe_agent_info: Option<AgentInfoSigned>,
         initial_arq: Option<Arq>,
     ) -> HolochainP2pHandlerResult<()> {
-        let space = dna_hash.into_kitsune();
-        let agent = agent_pub_key.into_kitsune();
+        let space = dna_hash.to_kitsune();
+        let agent = agent_pub_key.to_kitsune();
+
+        let announcer = tokio::spawn(announce_presence_loop(
+            self.evt_sender.clone(),
+            self.kitsune_p2p.clone(),
+            self.config.tuning_params.clone(),
+            self.presence.clone(),
+            dna_hash.clone(),
+            agent_pub_key.clone(),
+        ));
+        if let Some(previous) = self
+            .presence_announcers
+            .insert((dna_hash, agent_pub_key), announcer.abort_handle())
+        {
+            previous.abort();
+        }
 
         let kitsune_p2p = self.kitsune_p2p.clone();
         Ok(async move {
@@ -1172,8 +1291,12 @@ impl HolochainP2pHandler for HolochainP2pActor {
         dna_hash: DnaHash,
         agent_pub_key: AgentPubKey,
     ) -> HolochainP2pHandlerResult<()> {
-        let space = dna_hash.into_kitsune();
-        let agent = agent_pub_key.into_kitsune();
+        let space = dna_hash.to_kitsune();
+        let agent = agent_pub_key.to_kitsune();
+
+        if let Some(announcer) = self.presence_announcers.remove(&(dna_hash, agent_pub_key)) {
+            announcer.abort();
+        }
 
         let kitsune_p2p = self.kitsune_p2p.clone();
         Ok(async move { Ok(kitsune_p2p.leave(space, agent).await?) }
@@ -1402,8 +1525,12 @@ impl HolochainP2pHandler for HolochainP2pActor {
         let tuning_params = self.config.tuning_params.clone();
         timing_trace_out!(
             async move {
-                let input =
+                let mut input =
                     kitsune_p2p::actor::RpcMulti::new(&tuning_params, space, basis, payload);
+                if let Some(remote_agent_count) = options.remote_agent_count {
+                    input.max_remote_agent_count = remote_agent_count;
+                }
+                input.as_race = options.as_race;
                 let result = kitsune_p2p
                     .rpc_multi(input)
                     .instrument(tracing::debug_span!("rpc_multi"))
@@ -1479,10 +1606,17 @@ impl HolochainP2pHandler for HolochainP2pActor {
             async move {
                 let mut input =
                     kitsune_p2p::actor::RpcMulti::new(&tuning_params, space, basis, payload);
-                // NOTE - We're just targeting a single remote node for now
-                //        without doing any pagination / etc...
-                //        Setting up RpcMulti to act like RpcSingle
-                input.max_remote_agent_count = 1;
+                if let holochain_zome_types::entry::GetStrategy::Quorum(authorities) =
+                    options.get_options.strategy
+                {
+                    input.max_remote_agent_count = authorities;
+                    input.as_race = false;
+                } else {
+                    // NOTE - We're just targeting a single remote node for now
+                    //        without doing any pagination / etc...
+                    //        Setting up RpcMulti to act like RpcSingle
+                    input.max_remote_agent_count = 1;
+                }
                 let result = kitsune_p2p.rpc_multi(input).await?;
 
                 let mut out = Vec::new();
@@ -1680,6 +1814,123 @@ impl HolochainP2pHandler for HolochainP2pActor {
         )
     }
 
+    #[cfg_attr(
+        feature = "instrument",
+        tracing::instrument(skip(self), level = "trace")
+    )]
+    fn handle_get_agent_presence(
+        &mut self,
+        dna_hash: DnaHash,
+        agent: AgentPubKey,
+    ) -> HolochainP2pHandlerResult<Option<AgentPresence>> {
+        let presence = self.presence.clone();
+        // An announcement heard since the last one was due is as recent as any
+        // which the agent's neighbors could have.
+        if let Some(local) = presence.get(&dna_hash, &agent) {
+            let local = local.presence();
+            if local.seen_within(Timestamp::now(), PRESENCE_ANNOUNCE_INTERVAL * 2) {
+                return Ok(async move { Ok(Some(local)) }.boxed().into());
+            }
+        }
+
+        let space = dna_hash.to_kitsune();
+        let basis = OpBasis::from(agent.clone()).to_kitsune();
+        let payload = crate::wire::WireMessage::get_agent_presence(agent.clone()).encode()?;
+
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        let tuning_params = self.config.tuning_params.clone();
+        timing_trace_out!(
+            async move {
+                let input =
+                    kitsune_p2p::actor::RpcMulti::new(&tuning_params, space, basis, payload);
+                let result = kitsune_p2p
+                    .rpc_multi(input)
+                    .instrument(tracing::debug_span!("rpc_multi"))
+                    .await?;
+
+                for item in result {
+                    let kitsune_p2p::actor::RpcMultiResponse { response, .. } = item;
+                    let announcement: Option<PresenceAnnouncement> =
+                        holochain_serialized_bytes::decode(&response)?;
+                    if let Some(announcement) = announcement {
+                        if announcement.agent == agent && announcement.is_valid(&dna_hash).await {
+                            presence.record(&dna_hash, announcement, Timestamp::now());
+                        }
+                    }
+                }
+
+                Ok(presence
+                    .get(&dna_hash, &agent)
+                    .map(|announcement| announcement.presence()))
+            },
+            a = "send_get_agent_presence"
+        )
+    }
+
+    #[cfg_attr(
+        feature = "instrument",
+        tracing::instrument(skip(self, ops), level = "trace")
+    )]
+    fn handle_handoff_ops(
+        &mut self,
+        dna_hash: DnaHash,
+        basis: OpBasis,
+        ops: Vec<holochain_types::dht_op::DhtOp>,
+    ) -> HolochainP2pHandlerResult<Vec<(AgentPubKey, Vec<holo_hash::DhtOpHash>)>> {
+        let space = dna_hash.into_kitsune();
+        let basis = basis.to_kitsune();
+        let payload = crate::wire::WireMessage::handoff_ops(ops).encode()?;
+
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        let tuning_params = self.config.tuning_params.clone();
+        timing_trace_out!(
+            async move {
+                let input =
+                    kitsune_p2p::actor::RpcMulti::new(&tuning_params, space, basis, payload);
+                let result = kitsune_p2p
+                    .rpc_multi(input)
+                    .instrument(tracing::debug_span!("rpc_multi"))
+                    .await?;
+
+                let mut out = Vec::new();
+                for item in result {
+                    let kitsune_p2p::actor::RpcMultiResponse { agent, response } = item;
+                    let held: Vec<holo_hash::DhtOpHash> =
+                        holochain_serialized_bytes::decode(&response)?;
+                    out.push((AgentPubKey::from_kitsune(&agent), held));
+                }
+                Ok(out)
+            },
+            a = "send_handoff_ops"
+        )
+    }
+
+    #[cfg_attr(
+        feature = "instrument",
+        tracing::instrument(skip(self, arq_set), level = "trace")
+    )]
+    fn handle_op_census(
+        &mut self,
+        dna_hash: DnaHash,
+        to_agent: AgentPubKey,
+        arq_set: kitsune_p2p::dht::arq::ArqSet,
+    ) -> HolochainP2pHandlerResult<kitsune_p2p::dht::region_set::RegionSetLtcs> {
+        let space = dna_hash.into_kitsune();
+        let to_agent = to_agent.into_kitsune();
+        let payload = crate::wire::WireMessage::op_census(arq_set).encode()?;
+
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        timing_trace_out!(
+            async move {
+                let response = kitsune_p2p
+                    .rpc_single(space, to_agent, payload, None)
+                    .await?;
+                Ok(holochain_serialized_bytes::decode(&response)?)
+            },
+            a = "send_op_census"
+        )
+    }
+
     #[cfg_attr(
         feature = "instrument",
         tracing::instrument(skip(self), level = "trace")
@@ -1733,6 +1984,15 @@ impl HolochainP2pHandler for HolochainP2pActor {
         .into())
     }
 
+    fn handle_get_connection_stats(
+        &mut self,
+    ) -> HolochainP2pHandlerResult<Vec<kitsune_p2p_types::metrics::PeerConnectionStats>> {
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        Ok(async move { Ok(kitsune_p2p.get_connection_stats().await?) }
+            .boxed()
+            .into())
+    }
+
     fn handle_get_diagnostics(
         &mut self,
         dna_hash: DnaHash,
@@ -1748,4 +2008,126 @@ impl HolochainP2pHandler for HolochainP2pActor {
         .boxed()
         .into())
     }
+
+    fn handle_set_gossip_pause(
+        &mut self,
+        dna_hash: DnaHash,
+        pause: kitsune_p2p::GossipPause,
+    ) -> HolochainP2pHandlerResult<()> {
+        let space = dna_hash.into_kitsune();
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        Ok(
+            async move { Ok(kitsune_p2p.set_gossip_pause(space, pause).await?) }
+                .boxed()
+                .into(),
+        )
+    }
+
+    fn handle_set_arq_pin(
+        &mut self,
+        dna_hash: DnaHash,
+        agent_pub_key: AgentPubKey,
+        pin: Option<kitsune_p2p_types::dht::arq::ArqPin>,
+    ) -> HolochainP2pHandlerResult<()> {
+        let space = dna_hash.into_kitsune();
+        let agent = agent_pub_key.into_kitsune();
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        Ok(
+            async move { Ok(kitsune_p2p.set_arq_pin(space, agent, pin).await?) }
+                .boxed()
+                .into(),
+        )
+    }
+
+    fn handle_get_coverage_gaps(
+        &mut self,
+        dna_hash: DnaHash,
+        agent_pub_key: AgentPubKey,
+    ) -> HolochainP2pHandlerResult<Vec<kitsune_p2p_types::dht::arq::CoverageGap>> {
+        let space = dna_hash.into_kitsune();
+        let agent = agent_pub_key.into_kitsune();
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        Ok(
+            async move { Ok(kitsune_p2p.get_coverage_gaps(space, agent).await?) }
+                .boxed()
+                .into(),
+        )
+    }
+
+    fn handle_set_network_profile(
+        &mut self,
+        dna_hash: DnaHash,
+        profile: kitsune_p2p_types::config::NetworkProfile,
+    ) -> HolochainP2pHandlerResult<()> {
+        let space = dna_hash.into_kitsune();
+        let kitsune_p2p = self.kitsune_p2p.clone();
+        Ok(
+            async move { Ok(kitsune_p2p.set_network_profile(space, profile).await?) }
+                .boxed()
+                .into(),
+        )
+    }
+}
+
+/// Announce the presence of a joined agent to its neighborhood every
+/// [`PRESENCE_ANNOUNCE_INTERVAL`], until the task is aborted when it leaves.
+async fn announce_presence_loop(
+    evt_sender: WrapEvtSender,
+    kitsune_p2p: ghost_actor::GhostSender<kitsune_p2p::actor::KitsuneP2p>,
+    tuning_params: kitsune_p2p_types::config::KitsuneP2pTuningParams,
+    presence: PresenceStore,
+    dna_hash: DnaHash,
+    agent: AgentPubKey,
+) {
+    let mut interval = tokio::time::interval(PRESENCE_ANNOUNCE_INTERVAL);
+    loop {
+        interval.tick().await;
+        let timeout = tuning_params.implicit_timeout();
+        if let Err(err) = announce_presence(
+            &evt_sender,
+            &kitsune_p2p,
+            timeout,
+            &presence,
+            &dna_hash,
+            &agent,
+        )
+        .await
+        {
+            tracing::debug!(?err, ?dna_hash, ?agent, "Failed to announce presence");
+        }
+    }
+}
+
+async fn announce_presence(
+    evt_sender: &WrapEvtSender,
+    kitsune_p2p: &ghost_actor::GhostSender<kitsune_p2p::actor::KitsuneP2p>,
+    timeout: KitsuneTimeout,
+    presence: &PresenceStore,
+    dna_hash: &DnaHash,
+    agent: &AgentPubKey,
+) -> HolochainP2pResult<()> {
+    let at = Timestamp::now();
+    let data = PresenceAnnouncement::data_to_sign(dna_hash, agent, at)?;
+    let signature = evt_sender
+        .sign_network_data(dna_hash.clone(), agent.clone(), data)
+        .await?;
+    let announcement = PresenceAnnouncement {
+        agent: agent.clone(),
+        at,
+        signature,
+    };
+    presence.record(dna_hash, announcement.clone(), at);
+
+    // The agent's neighborhood is the authorities for its own hash.
+    let basis = OpBasis::from(agent.clone()).to_kitsune();
+    let payload = crate::wire::WireMessage::presence(announcement).encode()?;
+    kitsune_p2p
+        .broadcast(
+            dna_hash.to_kitsune(),
+            basis,
+            timeout,
+            BroadcastData::User(payload),
+        )
+        .await?;
+    Ok(())
 }
@@ -162,6 +162,29 @@ impl HolochainP2pHandler for StubNetwork {
     ) -> HolochainP2pHandlerResult<bool> {
         Err("stub".into())
     }
+    fn handle_get_agent_presence(
+        &mut self,
+        dna_hash: DnaHash,
+        agent: AgentPubKey,
+    ) -> HolochainP2pHandlerResult<Option<AgentPresence>> {
+        Err("stub".into())
+    }
+    fn handle_handoff_ops(
+        &mut self,
+        dna_hash: DnaHash,
+        basis: OpBasis,
+        ops: Vec<DhtOp>,
+    ) -> HolochainP2pHandlerResult<Vec<(AgentPubKey, Vec<DhtOpHash>)>> {
+        Err("stub".into())
+    }
+    fn handle_op_census(
+        &mut self,
+        dna_hash: DnaHash,
+        to_agent: AgentPubKey,
+        arq_set: kitsune_p2p::dht::arq::ArqSet,
+    ) -> HolochainP2pHandlerResult<kitsune_p2p::dht::region_set::RegionSetLtcs> {
+        Err("stub".into())
+    }
     fn handle_countersigning_session_negotiation(
         &mut self,
         dna_hash: DnaHash,
@@ -182,12 +205,51 @@ impl HolochainP2pHandler for StubNetwork {
         Err("stub".into())
     }
 
+    fn handle_get_connection_stats(
+        &mut self,
+    ) -> HolochainP2pHandlerResult<Vec<kitsune_p2p_types::metrics::PeerConnectionStats>> {
+        Err("stub".into())
+    }
+
     fn handle_get_diagnostics(
         &mut self,
         dna_hash: DnaHash,
     ) -> HolochainP2pHandlerResult<kitsune_p2p::gossip::sharded_gossip::KitsuneDiagnostics> {
         Err("stub".into())
     }
+
+    fn handle_set_gossip_pause(
+        &mut self,
+        dna_hash: DnaHash,
+        pause: kitsune_p2p::GossipPause,
+    ) -> HolochainP2pHandlerResult<()> {
+        Err("stub".into())
+    }
+
+    fn handle_set_arq_pin(
+        &mut self,
+        dna_hash: DnaHash,
+        agent_pub_key: AgentPubKey,
+        pin: Option<kitsune_p2p_types::dht::arq::ArqPin>,
+    ) -> HolochainP2pHandlerResult<()> {
+        Err("stub".into())
+    }
+
+    fn handle_get_coverage_gaps(
+        &mut self,
+        dna_hash: DnaHash,
+        agent_pub_key: AgentPubKey,
+    ) -> HolochainP2pHandlerResult<Vec<kitsune_p2p_types::dht::arq::CoverageGap>> {
+        Err("stub".into())
+    }
+
+    fn handle_set_network_profile(
+        &mut self,
+        dna_hash: DnaHash,
+        profile: kitsune_p2p_types::config::NetworkProfile,
+    ) -> HolochainP2pHandlerResult<()> {
+        Err("stub".into())
+    }
 }
 
 /// Spawn a stub network that doesn't respond to any messages.
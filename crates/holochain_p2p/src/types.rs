@@ -110,6 +110,7 @@ pub fn space_holo_to_kit(d: holo_hash::DnaHash) -> kitsune_p2p::KitsuneSpace {
 
 pub mod actor;
 pub mod event;
+pub mod presence;
 
 pub(crate) mod wire;
 
@@ -135,8 +135,15 @@ impl GetOptions {
 }
 
 impl From<holochain_zome_types::entry::GetOptions> for GetOptions {
-    fn from(_: holochain_zome_types::entry::GetOptions) -> Self {
-        Self::default()
+    fn from(options: holochain_zome_types::entry::GetOptions) -> Self {
+        match options.strategy {
+            holochain_zome_types::entry::GetStrategy::Quorum(authorities) => Self {
+                remote_agent_count: Some(authorities),
+                as_race: false,
+                ..Self::default()
+            },
+            _ => Self::default(),
+        }
     }
 }
 
@@ -234,6 +241,8 @@ pub struct GetActivityOptions {
     pub include_warrants: bool,
     /// Include the full signed records in the response, instead of just the hashes.
     pub include_full_records: bool,
+    /// Only return a window of the activity, see [`AgentActivityPage`](holochain_zome_types::agent_activity::AgentActivityPage).
+    pub page: Option<holochain_zome_types::agent_activity::AgentActivityPage>,
     /// Configure how the data should be fetched.
     pub get_options: holochain_zome_types::entry::GetOptions,
 }
@@ -247,6 +256,7 @@ impl Default for GetActivityOptions {
             include_rejected_activity: false,
             include_warrants: true,
             include_full_records: false,
+            page: None,
             get_options: Default::default(),
         }
     }
@@ -365,6 +375,27 @@ ghost_actor::ghost_chan! {
         /// Check if any local agent in this space is an authority for a hash.
         fn authority_for_hash(dna_hash: DnaHash, basis: OpBasis) -> bool;
 
+        /// When an agent last announced its presence to its neighborhood, as heard
+        /// by this node or, if that isn't recent, by the agent's neighbors.
+        fn get_agent_presence(dna_hash: DnaHash, agent: AgentPubKey) -> Option<AgentPresence>;
+
+        /// Offer ops which have fallen outside the storage arcs of this node's agents
+        /// to the authorities of their basis.
+        /// Returns the hashes of the ops which each authority confirmed that it holds.
+        fn handoff_ops(
+            dna_hash: DnaHash,
+            basis: OpBasis,
+            ops: Vec<holochain_types::dht_op::DhtOp>,
+        ) -> Vec<(AgentPubKey, Vec<holo_hash::DhtOpHash>)>;
+
+        /// Ask a peer for the regions of the ops which it holds within both
+        /// `arq_set` and its own storage arcs, to compare them with our own.
+        fn op_census(
+            dna_hash: DnaHash,
+            to_agent: AgentPubKey,
+            arq_set: crate::dht::arq::ArqSet,
+        ) -> crate::dht::region_set::RegionSetLtcs;
+
         /// Messages between agents negotiation a countersigning session.
         fn countersigning_session_negotiation(
             dna_hash: DnaHash,
@@ -380,8 +411,36 @@ ghost_actor::ghost_chan! {
         /// Dump network stats.
         fn dump_network_stats() -> String;
 
+        /// Get the statistics of each open transport connection to a remote peer.
+        fn get_connection_stats() -> Vec<kitsune_p2p_types::metrics::PeerConnectionStats>;
+
         /// Get struct for diagnostic data
         fn get_diagnostics(dna_hash: DnaHash) -> KitsuneDiagnostics;
+
+        /// Pause or resume gossip for a DNA.
+        fn set_gossip_pause(dna_hash: DnaHash, pause: kitsune_p2p::GossipPause) -> ();
+
+        /// Pin the storage arc of a local agent of a DNA, or let it be sized by dynamic
+        /// arcs again with `None`.
+        fn set_arq_pin(
+            dna_hash: DnaHash,
+            agent_pub_key: AgentPubKey,
+            pin: Option<kitsune_p2p_types::dht::arq::ArqPin>,
+        ) -> ();
+
+        /// Get the segments of the storage arc of a local agent of a DNA, from the one
+        /// which the fewest known peers cover to the one which the most cover.
+        fn get_coverage_gaps(
+            dna_hash: DnaHash,
+            agent_pub_key: AgentPubKey,
+        ) -> Vec<kitsune_p2p_types::dht::arq::CoverageGap>;
+
+        /// Restrict the network infrastructure which a DNA may use.
+        /// This must be set before the DNA is first joined.
+        fn set_network_profile(
+            dna_hash: DnaHash,
+            profile: kitsune_p2p_types::config::NetworkProfile,
+        ) -> ();
     }
 }
 
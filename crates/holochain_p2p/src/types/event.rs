@@ -75,6 +75,9 @@ pub struct GetActivityOptions {
     pub include_warrants: bool,
     /// Include the full records, instead of just the hashes.
     pub include_full_records: bool,
+    /// Only return a window of the activity.
+    #[serde(default)]
+    pub page: Option<holochain_zome_types::agent_activity::AgentActivityPage>,
 }
 
 impl Default for GetActivityOptions {
@@ -84,6 +87,7 @@ impl Default for GetActivityOptions {
             include_warrants: true,
             include_rejected_activity: false,
             include_full_records: false,
+            page: None,
         }
     }
 }
@@ -95,6 +99,7 @@ impl From<&actor::GetActivityOptions> for GetActivityOptions {
             include_warrants: a.include_warrants,
             include_rejected_activity: a.include_rejected_activity,
             include_full_records: a.include_full_records,
+            page: a.page.clone(),
         }
     }
 }
@@ -243,6 +248,24 @@ ghost_actor::ghost_chan! {
             filter: holochain_zome_types::chain::ChainFilter,
         ) -> MustGetAgentActivityResponse;
 
+        /// A remote node is handing off ops which have fallen outside its storage arc,
+        /// so that we hold them instead.
+        /// Returns the hashes of the ops which we now hold.
+        fn handoff_ops(
+            dna_hash: DnaHash,
+            to_agent: AgentPubKey,
+            ops: Vec<holochain_types::dht_op::DhtOp>,
+        ) -> Vec<holo_hash::DhtOpHash>;
+
+        /// A remote node is taking a census of the ops which its neighbors hold.
+        /// Returns the regions of the ops which we hold within both the given arcs
+        /// and our own storage arcs.
+        fn op_census(
+            dna_hash: DnaHash,
+            to_agent: AgentPubKey,
+            arq_set: kitsune_p2p::dht::arq::ArqSet,
+        ) -> kitsune_p2p::dht::region_set::RegionSetLtcs;
+
         /// A remote node has sent us a validation receipt.
         fn validation_receipts_received(
             dna_hash: DnaHash,
@@ -298,6 +321,8 @@ macro_rules! match_p2p_evt {
             HolochainP2pEvent::CountLinks { $i, .. } => { $($t)* }
             HolochainP2pEvent::GetAgentActivity { $i, .. } => { $($t)* }
             HolochainP2pEvent::MustGetAgentActivity { $i, .. } => { $($t)* }
+            HolochainP2pEvent::HandoffOps { $i, .. } => { $($t)* }
+            HolochainP2pEvent::OpCensus { $i, .. } => { $($t)* }
             HolochainP2pEvent::ValidationReceiptsReceived { $i, .. } => { $($t)* }
             HolochainP2pEvent::SignNetworkData { $i, .. } => { $($t)* }
             HolochainP2pEvent::CountersigningSessionNegotiation { $i, .. } => { $($t)* }
@@ -0,0 +1,148 @@
+//! Presence announcements, which each joined agent broadcasts to its neighborhood
+//! while it is online, so that other agents can tell when it was last seen.
+
+use holo_hash::{AgentPubKey, DnaHash};
+use holochain_serialized_bytes::SerializedBytesError;
+use holochain_zome_types::prelude::*;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// How often each joined agent announces its presence.
+pub const PRESENCE_ANNOUNCE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How far ahead of the local clock an announcement may be before it is ignored.
+pub const MAX_PRESENCE_CLOCK_DRIFT: Duration = Duration::from_secs(60 * 5);
+
+/// The most agents whose presence is kept for each DNA.
+pub const MAX_PRESENCE_RECORDS: usize = 10_000;
+
+/// An agent's signed statement that it was online at a point in time.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PresenceAnnouncement {
+    /// The agent which is online.
+    pub agent: AgentPubKey,
+    /// When the announcement was made.
+    pub at: Timestamp,
+    /// The agent's signature of [`PresenceAnnouncement::data_to_sign`].
+    pub signature: Signature,
+}
+
+impl PresenceAnnouncement {
+    /// The bytes which an agent signs to announce its presence in a DNA.
+    pub fn data_to_sign(
+        dna_hash: &DnaHash,
+        agent: &AgentPubKey,
+        at: Timestamp,
+    ) -> Result<Vec<u8>, SerializedBytesError> {
+        holochain_serialized_bytes::encode(&("presence", dna_hash, agent, at))
+    }
+
+    /// Check that the announcement was signed by its agent for this DNA.
+    pub async fn is_valid(&self, dna_hash: &DnaHash) -> bool {
+        let Ok(data) = Self::data_to_sign(dna_hash, &self.agent, self.at) else {
+            return false;
+        };
+        holochain_keystore::AgentPubKeyExt::verify_signature_raw(
+            &self.agent,
+            &self.signature,
+            data.into(),
+        )
+        .await
+        .unwrap_or(false)
+    }
+
+    /// The presence which this announcement tells of.
+    pub fn presence(&self) -> AgentPresence {
+        AgentPresence {
+            agent: self.agent.clone(),
+            last_seen: self.at,
+        }
+    }
+}
+
+/// The latest presence announcement of each agent which this node has heard from.
+#[derive(Clone, Default)]
+pub struct PresenceStore(Arc<Mutex<HashMap<DnaHash, HashMap<AgentPubKey, PresenceAnnouncement>>>>);
+
+impl PresenceStore {
+    /// Keep an announcement which has already been checked with
+    /// [`PresenceAnnouncement::is_valid`], if it is newer than the one already kept.
+    ///
+    /// Announcements made more than [`MAX_PRESENCE_CLOCK_DRIFT`] after `now` are ignored,
+    /// as are announcements of new agents once [`MAX_PRESENCE_RECORDS`] are kept.
+    /// Returns whether the announcement was kept.
+    pub fn record(
+        &self,
+        dna_hash: &DnaHash,
+        announcement: PresenceAnnouncement,
+        now: Timestamp,
+    ) -> bool {
+        let drift = now
+            .as_micros()
+            .saturating_add(MAX_PRESENCE_CLOCK_DRIFT.as_micros() as i64);
+        if announcement.at.as_micros() > drift {
+            return false;
+        }
+        let mut store = self.0.lock().expect("presence store poisoned");
+        let agents = store.entry(dna_hash.clone()).or_default();
+        match agents.get_mut(&announcement.agent) {
+            Some(kept) if kept.at >= announcement.at => false,
+            Some(kept) => {
+                *kept = announcement;
+                true
+            }
+            None if agents.len() >= MAX_PRESENCE_RECORDS => false,
+            None => {
+                agents.insert(announcement.agent.clone(), announcement);
+                true
+            }
+        }
+    }
+
+    /// The latest announcement of an agent in a DNA.
+    pub fn get(&self, dna_hash: &DnaHash, agent: &AgentPubKey) -> Option<PresenceAnnouncement> {
+        self.0
+            .lock()
+            .expect("presence store poisoned")
+            .get(dna_hash)
+            .and_then(|agents| agents.get(agent))
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::prelude::*;
+    use holo_hash::fixt::*;
+
+    fn announcement(agent: AgentPubKey, at: i64) -> PresenceAnnouncement {
+        PresenceAnnouncement {
+            agent,
+            at: Timestamp::from_micros(at),
+            signature: Signature([0; 64]),
+        }
+    }
+
+    #[test]
+    fn the_latest_announcement_is_kept() {
+        let store = PresenceStore::default();
+        let dna_hash = fixt!(DnaHash);
+        let agent = fixt!(AgentPubKey);
+        let now = Timestamp::from_micros(1_000_000_000);
+
+        assert!(store.record(&dna_hash, announcement(agent.clone(), 100), now));
+        assert!(!store.record(&dna_hash, announcement(agent.clone(), 50), now));
+        assert!(store.record(&dna_hash, announcement(agent.clone(), 200), now));
+        assert_eq!(
+            Some(Timestamp::from_micros(200)),
+            store.get(&dna_hash, &agent).map(|a| a.at)
+        );
+        // Announcements from the far future are ignored.
+        let future = now.as_micros() + MAX_PRESENCE_CLOCK_DRIFT.as_micros() as i64 + 1;
+        assert!(!store.record(&dna_hash, announcement(agent.clone(), future), now));
+        // Presence is kept per DNA.
+        assert_eq!(None, store.get(&fixt!(DnaHash), &agent));
+    }
+}
@@ -82,6 +82,18 @@ pub enum WireMessage {
         flag: bool,
         op: DhtOp,
     },
+    Presence {
+        announcement: presence::PresenceAnnouncement,
+    },
+    GetAgentPresence {
+        agent: AgentPubKey,
+    },
+    HandoffOps {
+        ops: Vec<DhtOp>,
+    },
+    OpCensus {
+        arq_set: kitsune_p2p::dht::arq::ArqSet,
+    },
 }
 
 #[allow(missing_docs)]
@@ -189,6 +201,22 @@ impl WireMessage {
         Self::MustGetAgentActivity { agent, filter }
     }
 
+    pub fn presence(announcement: presence::PresenceAnnouncement) -> WireMessage {
+        Self::Presence { announcement }
+    }
+
+    pub fn get_agent_presence(agent: AgentPubKey) -> WireMessage {
+        Self::GetAgentPresence { agent }
+    }
+
+    pub fn handoff_ops(ops: Vec<DhtOp>) -> WireMessage {
+        Self::HandoffOps { ops }
+    }
+
+    pub fn op_census(arq_set: kitsune_p2p::dht::arq::ArqSet) -> WireMessage {
+        Self::OpCensus { arq_set }
+    }
+
     pub fn countersigning_session_negotiation(
         message: event::CountersigningSessionNegotiationMessage,
     ) -> WireMessage {
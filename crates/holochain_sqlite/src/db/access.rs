@@ -3,7 +3,8 @@ use crate::db::databases::DATABASE_HANDLES;
 use crate::db::guard::{PConnGuard, PTxnGuard};
 use crate::db::kind::{DbKind, DbKindT};
 use crate::db::pool::{
-    initialize_connection, new_connection_pool, num_read_threads, ConnectionPool, PoolConfig,
+    initialize_connection, new_connection_pool, new_read_only_connection_pool, num_read_threads,
+    ConnectionPool, PoolConfig,
 };
 use crate::error::{DatabaseError, DatabaseResult};
 use derive_more::Into;
@@ -239,6 +240,45 @@ impl<Kind: DbKindT> DbRead<Kind> {
     }
 }
 
+impl<Kind: DbKindT + Send + Sync + 'static> DbRead<Kind> {
+    /// Open an existing database for reading only, e.g. to inspect the databases
+    /// of a conductor which isn't running.
+    ///
+    /// Unlike [`DbWrite::open_with_pool_config`], the database isn't created,
+    /// wiped or migrated, and the connections can't write to it, so opening it
+    /// leaves the database as it was.
+    pub fn open_read_only(
+        path_prefix: &Path,
+        kind: Kind,
+        pool_config: PoolConfig,
+    ) -> DatabaseResult<Self> {
+        let path = path_prefix.join(kind.filename());
+        if !path.is_file() {
+            return Err(DatabaseError::DatabaseMissing(path));
+        }
+        let connection_pool = new_read_only_connection_pool(&path, pool_config);
+        // Prove that the database can be read with the key.
+        connection_pool
+            .get()?
+            .query_row("SELECT count(*) FROM sqlite_master", [], |row| {
+                row.get::<_, usize>(0)
+            })?;
+
+        Ok(DbRead {
+            write_semaphore: DbWrite::<Kind>::get_write_semaphore(kind.kind()),
+            read_semaphore: DbWrite::<Kind>::get_read_semaphore(kind.kind()),
+            long_read_semaphore: DbWrite::<Kind>::get_long_read_semaphore(kind.kind()),
+            max_readers: num_read_threads() * 2,
+            num_readers: Arc::new(AtomicUsize::new(0)),
+            use_time_metric: create_connection_use_time_metric(kind.kind()),
+            kind,
+            path,
+            connection_pool,
+            statement_trace_fn: None,
+        })
+    }
+}
+
 /// The canonical representation of a (singleton) database.
 /// The wrapper contains methods for managing transactions
 /// and database connections,
@@ -546,11 +586,13 @@ async fn acquire_semaphore_permit(
 ) -> DatabaseResult<OwnedSemaphorePermit> {
     let id = nanoid::nanoid!(7);
     tracing::trace!(?id, "???     acquire semaphore permit");
+    let start = Instant::now();
     let permit = tokio::time::timeout(
         std::time::Duration::from_millis(ACQUIRE_TIMEOUT_MS.load(Ordering::Acquire)),
         semaphore.acquire_owned(),
     )
     .await;
+    super::wait_time::record_db_wait(start.elapsed());
     tracing::trace!(?id, ?permit, "    !!! semaphore permit obtained");
     match permit {
         Ok(Ok(s)) => Ok(s),
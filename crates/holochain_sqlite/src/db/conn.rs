@@ -29,6 +29,12 @@ impl<'e> PConn {
         Self { inner }
     }
 
+    /// Work to do once a transaction is over, whether it succeeded or not.
+    fn finish_txn(&self) {
+        #[cfg(feature = "slow_query_log")]
+        crate::slow_query_log::log_slow_queries(self);
+    }
+
     #[cfg_attr(feature = "instrument", tracing::instrument(skip_all))]
     pub(super) fn execute_in_read_txn<E, R, F>(&'e mut self, f: F) -> Result<R, E>
     where
@@ -42,7 +48,10 @@ impl<'e> PConn {
         );
 
         // TODO It would be possible to prevent the transaction from calling commit here if we passed a reference instead of a move.
-        timed!([10, 20, 50], "running closure", { f(txn) })
+        let result = timed!([10, 20, 50], "running closure", { f(txn) });
+
+        self.finish_txn();
+        result
     }
 
     /// Run a closure, passing in a mutable reference to a read-write
@@ -57,19 +66,24 @@ impl<'e> PConn {
     {
         tracing::trace!("entered execute_in_exclusive_rw_txn");
 
-        let mut txn = timed!([10, 100, 1000], "getting exclusive r/w transaction", {
-            self.transaction_with_behavior(TransactionBehavior::Exclusive)
-                .map_err(DatabaseError::from)?
-        });
+        let result = (|| {
+            let mut txn = timed!([10, 100, 1000], "getting exclusive r/w transaction", {
+                self.transaction_with_behavior(TransactionBehavior::Exclusive)
+                    .map_err(DatabaseError::from)?
+            });
 
-        let result = timed!([10, 100, 1000], "running closure", f(&mut txn)?);
+            let result = timed!([10, 100, 1000], "running closure", f(&mut txn)?);
 
-        timed!(
-            [10, 100, 1000],
-            "comitting transaction",
-            txn.commit().map_err(DatabaseError::from)?
-        );
+            timed!(
+                [10, 100, 1000],
+                "comitting transaction",
+                txn.commit().map_err(DatabaseError::from)?
+            );
+
+            Ok(result)
+        })();
 
-        Ok(result)
+        self.finish_txn();
+        result
     }
 }
@@ -8,6 +8,7 @@ mod key;
 mod kind;
 mod metrics;
 mod pool;
+mod wait_time;
 
 #[cfg(all(test, not(loom)))]
 mod tests;
@@ -20,6 +21,7 @@ pub use kind::{
     DbKindP2pMetrics, DbKindT, DbKindWasm,
 };
 pub use pool::{DbSyncLevel, DbSyncStrategy, PoolConfig};
+pub use wait_time::measure_db_wait;
 
 #[cfg(feature = "test_utils")]
 pub use access::set_acquire_timeout;
@@ -66,6 +66,24 @@ pub(super) fn new_connection_pool(path: Option<&Path>, config: PoolConfig) -> Co
         Some(path) => SqliteConnectionManager::file(path),
         None => SqliteConnectionManager::memory(),
     };
+    build_connection_pool(manager, config)
+}
+
+/// A pool of connections which can only read the database file at `path`,
+/// and can't create it.
+pub(super) fn new_read_only_connection_pool(path: &Path, config: PoolConfig) -> ConnectionPool {
+    let manager = r2d2_sqlite::SqliteConnectionManager::file(path).with_flags(
+        OpenFlags::SQLITE_OPEN_READ_ONLY
+            | OpenFlags::SQLITE_OPEN_URI
+            | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    );
+    build_connection_pool(manager, config)
+}
+
+fn build_connection_pool(
+    manager: r2d2_sqlite::SqliteConnectionManager,
+    config: PoolConfig,
+) -> ConnectionPool {
     let customizer = Box::new(ConnCustomizer { config });
 
     /*
@@ -126,6 +144,9 @@ pub(super) fn initialize_connection(conn: &mut Connection, config: &PoolConfig)
 
     add_custom_functions(conn)?;
 
+    #[cfg(feature = "slow_query_log")]
+    crate::slow_query_log::watch_connection(conn);
+
     Ok(())
 }
 
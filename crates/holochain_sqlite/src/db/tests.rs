@@ -1,9 +1,9 @@
 use tempfile::TempDir;
 
-use crate::prelude::{DatabaseResult, DbKindWasm};
+use crate::prelude::{DatabaseError, DatabaseResult, DbKindT, DbKindWasm};
 
-use super::pool::num_read_threads;
-use super::DbWrite;
+use super::pool::{initialize_connection, num_read_threads, PoolConfig};
+use super::{DbRead, DbWrite};
 
 /// This test does prove that making all transactions
 /// synchronous fixes the db timeout issue but it's slow
@@ -135,3 +135,68 @@ async fn db_connection_doesnt_timeout() {
     // without taking permits.
     assert!(result.is_err());
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn read_only_db_reads_without_writing() {
+    let td = TempDir::new().unwrap();
+    let db = DbWrite::test(td.path(), DbKindWasm).unwrap();
+    db.write_async(|txn| {
+        txn.execute("INSERT INTO Wasm (hash, blob) VALUES(?, ?)", [[0], [1]])?;
+        DatabaseResult::Ok(())
+    })
+    .await
+    .unwrap();
+    drop(db);
+
+    let db = DbRead::open_read_only(td.path(), DbKindWasm, PoolConfig::default()).unwrap();
+    let (count, insert) = db
+        .read_async(|txn| {
+            let count: usize =
+                txn.query_row("SELECT COUNT(rowid) FROM Wasm", [], |row| row.get(0))?;
+            let insert = txn.execute("INSERT INTO Wasm (hash, blob) VALUES(?, ?)", [[2], [3]]);
+            DatabaseResult::Ok((count, insert))
+        })
+        .await
+        .unwrap();
+    assert_eq!(1, count);
+    assert!(matches!(
+        insert,
+        Err(rusqlite::Error::SqliteFailure(err, _))
+            if err.code == rusqlite::ErrorCode::ReadOnly
+    ));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn read_only_db_is_not_migrated() {
+    let td = TempDir::new().unwrap();
+    let path = td.path().join(DbKindWasm.filename());
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    {
+        let mut conn = rusqlite::Connection::open(&path).unwrap();
+        initialize_connection(&mut conn, &PoolConfig::default()).unwrap();
+        conn.execute("CREATE TABLE Old (id INTEGER)", []).unwrap();
+    }
+
+    let db = DbRead::open_read_only(td.path(), DbKindWasm, PoolConfig::default()).unwrap();
+    let (version, tables) = db
+        .read_async(|txn| {
+            let version: u32 = txn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+            let tables = txn
+                .prepare("SELECT name FROM sqlite_master WHERE type = 'table'")?
+                .query_map([], |row| row.get::<_, String>(0))?
+                .collect::<Result<Vec<_>, _>>()?;
+            DatabaseResult::Ok((version, tables))
+        })
+        .await
+        .unwrap();
+    assert_eq!(0, version);
+    assert_eq!(vec!["Old".to_string()], tables);
+}
+
+#[test]
+fn read_only_db_is_not_created() {
+    let td = TempDir::new().unwrap();
+    let result = DbRead::open_read_only(td.path(), DbKindWasm, PoolConfig::default());
+    assert!(matches!(result, Err(DatabaseError::DatabaseMissing(_))));
+    assert!(!td.path().join(DbKindWasm.filename()).exists());
+}
@@ -0,0 +1,47 @@
+//! Measure how long a task spends waiting for database connections.
+
+use std::cell::Cell;
+use std::future::Future;
+use std::time::Duration;
+
+tokio::task_local! {
+    static DB_WAIT: Cell<Duration>;
+}
+
+/// Run a future, adding up the time it spends waiting for database
+/// permits before it can run transactions.
+///
+/// Only waits in the task running the future are counted, not those in any
+/// tasks or threads it spawns.
+pub async fn measure_db_wait<F: Future>(f: F) -> (F::Output, Duration) {
+    DB_WAIT
+        .scope(Cell::new(Duration::ZERO), async move {
+            let output = f.await;
+            (output, DB_WAIT.with(Cell::get))
+        })
+        .await
+}
+
+/// Add a wait to the measurement of the current task, if it is being measured.
+pub(super) fn record_db_wait(wait: Duration) {
+    let _ = DB_WAIT.try_with(|total| total.set(total.get() + wait));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn adds_up_waits_in_the_task() {
+        let ((), wait) = measure_db_wait(async {
+            record_db_wait(Duration::from_millis(3));
+            tokio::task::yield_now().await;
+            record_db_wait(Duration::from_millis(4));
+        })
+        .await;
+        assert_eq!(wait, Duration::from_millis(7));
+
+        // outside of a measurement this is a no-op
+        record_db_wait(Duration::from_millis(1));
+    }
+}
@@ -62,6 +62,13 @@ pub enum DatabaseError {
 
     #[error(transparent)]
     Timeout(tokio::time::error::Elapsed),
+
+    #[error("The {db_kind} database has {found} migrations applied, but {expected} were expected")]
+    SchemaVersionMismatch {
+        db_kind: String,
+        expected: usize,
+        found: usize,
+    },
 }
 
 impl From<TimestampError> for DatabaseError {
@@ -93,7 +93,10 @@ pub mod functions;
 #[cfg(not(loom))]
 pub mod nonce;
 pub mod prelude;
+pub mod query_plan;
 pub mod schema;
+#[cfg(feature = "slow_query_log")]
+pub mod slow_query_log;
 #[cfg(not(loom))]
 pub mod sql;
 pub mod stats;
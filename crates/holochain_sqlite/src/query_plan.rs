@@ -0,0 +1,25 @@
+//! Inspecting the plans which SQLite chooses to run statements with, so that queries
+//! which regress into table scans can be caught.
+
+use rusqlite::Connection;
+
+/// The steps of the plan which SQLite chooses for a statement, as described by
+/// `EXPLAIN QUERY PLAN`, e.g. `SEARCH DhtOp USING INDEX DhtOp_type_dep_idx (type=?)`.
+///
+/// The statement isn't run, and its parameters don't need to be bound.
+pub fn explain_query_plan(conn: &Connection, sql: &str) -> rusqlite::Result<Vec<String>> {
+    let mut stmt = conn.prepare(&format!("EXPLAIN QUERY PLAN {}", sql))?;
+    // Unlike `query`, this doesn't insist on every parameter being bound.
+    let mut rows = stmt.raw_query();
+    let mut steps = Vec::new();
+    while let Some(row) = rows.next()? {
+        steps.push(row.get(3)?);
+    }
+    Ok(steps)
+}
+
+/// Whether a step of a query plan reads every row of a table rather than searching
+/// an index. Scans of an index, e.g. to count its entries, aren't table scans.
+pub fn is_table_scan(step: &str) -> bool {
+    step.starts_with("SCAN ") && !step.contains(" USING ") && !step.contains("CONSTANT ROW")
+}
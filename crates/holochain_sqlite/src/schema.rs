@@ -17,6 +17,7 @@ use once_cell::sync::Lazy;
 use rusqlite::{Connection, Transaction};
 
 use crate::db::DbKind;
+use crate::error::{DatabaseError, DatabaseResult};
 
 pub static SCHEMA_CELL: Lazy<Schema> = Lazy::new(|| Schema {
     migrations: vec![
@@ -37,6 +38,22 @@ pub static SCHEMA_CELL: Lazy<Schema> = Lazy::new(|| Schema {
             forward: include_str!("sql/cell/schema/4-up.sql").into(),
             _schema: include_str!("sql/cell/schema/4.sql").into(),
         },
+        M {
+            forward: include_str!("sql/cell/schema/5-up.sql").into(),
+            _schema: include_str!("sql/cell/schema/5.sql").into(),
+        },
+        M {
+            forward: include_str!("sql/cell/schema/6-up.sql").into(),
+            _schema: include_str!("sql/cell/schema/6.sql").into(),
+        },
+        M {
+            forward: include_str!("sql/cell/schema/7-up.sql").into(),
+            _schema: include_str!("sql/cell/schema/7.sql").into(),
+        },
+        M {
+            forward: include_str!("sql/cell/schema/8-up.sql").into(),
+            _schema: include_str!("sql/cell/schema/8.sql").into(),
+        },
     ],
 });
 
@@ -47,6 +64,14 @@ pub static SCHEMA_CONDUCTOR: Lazy<Schema> = Lazy::new(|| Schema {
             forward: include_str!("sql/conductor/schema/1-up.sql").into(),
             _schema: "".into(),
         },
+        M {
+            forward: include_str!("sql/conductor/schema/2-up.sql").into(),
+            _schema: "".into(),
+        },
+        M {
+            forward: include_str!("sql/conductor/schema/3-up.sql").into(),
+            _schema: "".into(),
+        },
     ],
 });
 
@@ -118,6 +143,26 @@ impl Schema {
     }
 }
 
+/// Check that all the migrations of a database's schema have been applied to it.
+pub fn check_schema_version(txn: &Transaction, db_kind: &DbKind) -> DatabaseResult<()> {
+    let schema = match crate::table::schema_for_kind(db_kind) {
+        Some(schema) => schema,
+        None => return Ok(()),
+    };
+    let user_version: u16 = txn.pragma_query_value(None, "user_version", |row| row.get(0))?;
+    let found = user_version as usize;
+    let expected = schema.migrations.len();
+    if found == expected {
+        Ok(())
+    } else {
+        Err(DatabaseError::SchemaVersionMismatch {
+            db_kind: db_kind.to_string(),
+            expected,
+            found,
+        })
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Migration {
     _schema: Sql,
@@ -174,6 +219,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn schema_version_is_checked() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        {
+            let txn = conn.transaction().unwrap();
+            assert_eq!(
+                Err(DatabaseError::SchemaVersionMismatch {
+                    db_kind: DbKind::Wasm.to_string(),
+                    expected: SCHEMA_WASM.migrations.len(),
+                    found: 0,
+                }),
+                check_schema_version(&txn, &DbKind::Wasm)
+            );
+        }
+
+        SCHEMA_WASM
+            .initialize(&mut conn, Some(DbKind::Wasm))
+            .unwrap();
+        let txn = conn.transaction().unwrap();
+        check_schema_version(&txn, &DbKind::Wasm).unwrap();
+    }
+
     #[test]
     fn test_migrations_sequential() {
         let mut schema = Schema {
@@ -0,0 +1,145 @@
+//! Logging of the statements which take longer than a threshold to run, with their
+//! parameters and query plan.
+//!
+//! Statements are timed with the trace hook of each connection, so they aren't timed
+//! on databases which are opened with a statement trace function of their own.
+//! The parameters are logged as they were bound, so the log can hold secrets such as
+//! the secrets of capability grants. This is meant for debugging and is only compiled
+//! in with the `slow_query_log` feature.
+
+use crate::query_plan::explain_query_plan;
+use rusqlite::{ffi, Connection};
+use std::cell::RefCell;
+use std::ffi::CStr;
+use std::os::raw::{c_int, c_uint, c_void};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+
+/// Statements which take at least this long are logged. Defaults to 100ms.
+static THRESHOLD_MICROS: AtomicU64 = AtomicU64::new(100_000);
+
+/// The most slow statements which are kept per thread until they're logged.
+const MAX_PENDING: usize = 32;
+
+/// A statement which took at least the threshold to run.
+struct SlowQuery {
+    sql: String,
+    /// The statement with its parameters filled in.
+    expanded_sql: Option<String>,
+    duration: Duration,
+}
+
+thread_local! {
+    /// The slow statements which ran on this thread and haven't been logged yet.
+    static SLOW: RefCell<Vec<SlowQuery>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Log the statements which take at least this long to run.
+pub fn set_slow_query_threshold(threshold: Duration) {
+    THRESHOLD_MICROS.store(threshold.as_micros() as u64, Ordering::Relaxed);
+}
+
+/// Time every statement which runs on a connection.
+pub(crate) fn watch_connection(conn: &mut Connection) {
+    // rusqlite only wraps the legacy profile hook, which doesn't give the statement,
+    // so its parameters couldn't be filled in.
+    // SAFETY: the hook doesn't use the connection, and is replaced rather than
+    // called once the connection is closed.
+    unsafe {
+        ffi::sqlite3_trace_v2(
+            conn.handle(),
+            ffi::SQLITE_TRACE_PROFILE as c_uint,
+            Some(statement_finished),
+            std::ptr::null_mut(),
+        );
+    }
+}
+
+/// Log the slow statements which ran on this thread since the last call, with the
+/// plans which SQLite chooses for them on `conn`.
+///
+/// The plans can't be explained from within the hook, as the connection is busy
+/// running the statement then, so this is called once a transaction is over.
+pub(crate) fn log_slow_queries(conn: &Connection) {
+    for query in SLOW.with(|slow| std::mem::take(&mut *slow.borrow_mut())) {
+        let plan = explain_query_plan(conn, &query.sql);
+        tracing::warn!(
+            duration = ?query.duration,
+            sql = %query.sql,
+            expanded_sql = ?query.expanded_sql,
+            ?plan,
+            "Slow query"
+        );
+    }
+}
+
+/// The `SQLITE_TRACE_PROFILE` hook, which is given the statement which finished and
+/// how many nanoseconds it took.
+unsafe extern "C" fn statement_finished(
+    _event: c_uint,
+    _ctx: *mut c_void,
+    stmt: *mut c_void,
+    nanos: *mut c_void,
+) -> c_int {
+    let duration = Duration::from_nanos(*(nanos as *const i64) as u64);
+    if duration.as_micros() < THRESHOLD_MICROS.load(Ordering::Relaxed) as u128 {
+        return 0;
+    }
+    let stmt = stmt as *mut ffi::sqlite3_stmt;
+    let sql = ffi::sqlite3_sql(stmt);
+    if sql.is_null() {
+        return 0;
+    }
+    let sql = CStr::from_ptr(sql).to_string_lossy().into_owned();
+    let expanded = ffi::sqlite3_expanded_sql(stmt);
+    let expanded_sql = if expanded.is_null() {
+        None
+    } else {
+        let expanded_sql = CStr::from_ptr(expanded).to_string_lossy().into_owned();
+        ffi::sqlite3_free(expanded as *mut c_void);
+        Some(expanded_sql)
+    };
+    SLOW.with(|slow| {
+        let mut slow = slow.borrow_mut();
+        if slow.len() < MAX_PENDING {
+            slow.push(SlowQuery {
+                sql,
+                expanded_sql,
+                duration,
+            });
+        }
+    });
+    0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slow_queries_are_kept_with_their_parameters() {
+        set_slow_query_threshold(Duration::ZERO);
+        let mut conn = Connection::open_in_memory().unwrap();
+        watch_connection(&mut conn);
+        conn.execute_batch("CREATE TABLE Thing (id INTEGER PRIMARY KEY, name TEXT)")
+            .unwrap();
+        SLOW.with(|slow| slow.borrow_mut().clear());
+
+        conn.execute("INSERT INTO Thing (name) VALUES (?)", ["box"])
+            .unwrap();
+
+        SLOW.with(|slow| {
+            let slow = slow.borrow();
+            assert_eq!(1, slow.len());
+            assert_eq!("INSERT INTO Thing (name) VALUES (?)", slow[0].sql);
+            assert_eq!(
+                Some("INSERT INTO Thing (name) VALUES ('box')"),
+                slow[0].expanded_sql.as_deref()
+            );
+        });
+        // - Explaining the plan is a statement too, which shouldn't be kept.
+        set_slow_query_threshold(Duration::from_secs(60));
+        log_slow_queries(&conn);
+        SLOW.with(|slow| assert!(slow.borrow().is_empty()));
+    }
+}
@@ -1,4 +1,14 @@
+#[cfg(test)]
+mod tests;
+
 pub mod sql_cell {
+    /// How many microseconds of authored time each epoch of DHT ops covers, i.e. 30 days.
+    ///
+    /// Ops are partitioned into epochs by their authored timestamp, so that gossip
+    /// queries and purges only read the epochs which they cover. This must match the
+    /// `epoch` column of the `DhtOp` table.
+    pub const DHT_OP_EPOCH_MICROS: i64 = 2_592_000_000_000;
+
     pub const UPDATE_INTEGRATE_DEP_ACTIVITY: &str =
         include_str!("sql/cell/update_dep_activity.sql");
     pub const ACTIVITY_INTEGRATED_UPPER_BOUND: &str =
@@ -8,6 +18,9 @@ pub mod sql_cell {
     pub const ALL_READY_ACTIVITY: &str = include_str!("sql/cell/all_ready_activity.sql");
     pub const DELETE_ACTIONS_AFTER_SEQ: &str =
         include_str!("sql/cell/delete_actions_after_seq.sql");
+    pub const DELETE_ACTIONS: &str = include_str!("sql/cell/delete_actions.sql");
+    pub const DELETE_UNREFERENCED_ENTRY: &str =
+        include_str!("sql/cell/delete_unreferenced_entry.sql");
     pub const UPDATE_INTEGRATE_DEP_STORE_RECORD: &str =
         include_str!("sql/cell/update_dep_store_record.sql");
     pub const UPDATE_INTEGRATE_DEP_STORE_ENTRY: &str =
@@ -16,6 +29,7 @@ pub mod sql_cell {
         include_str!("sql/cell/update_dep_store_entry_basis.sql");
     pub const UPDATE_INTEGRATE_DEP_CREATE_LINK: &str =
         include_str!("sql/cell/update_dep_create_link.sql");
+    pub const SELECT_INTEGRATED_BASES: &str = include_str!("sql/cell/select_integrated_bases.sql");
 
     pub const SELECT_VALID_AGENT_PUB_KEY: &str =
         include_str!("sql/cell/select_valid_agent_pub_key.sql");
@@ -67,6 +81,14 @@ pub mod sql_conductor {
     pub const FROM_BLOCK_SPAN_WHERE_OVERLAPPING: &str =
         include_str!("sql/conductor/from_block_span_where_overlapping.sql");
     pub const IS_BLOCKED: &str = include_str!("sql/conductor/is_blocked.sql");
+    pub const INSERT_AUDIT_LOG_ENTRY: &str =
+        include_str!("sql/conductor/insert_audit_log_entry.sql");
+    pub const SELECT_AUDIT_LOG: &str = include_str!("sql/conductor/select_audit_log.sql");
+    pub const UPSERT_OUTBOX_MESSAGE: &str = include_str!("sql/conductor/upsert_outbox_message.sql");
+    pub const SELECT_OUTBOX_MESSAGES: &str =
+        include_str!("sql/conductor/select_outbox_messages.sql");
+    pub const DELETE_SETTLED_OUTBOX_MESSAGES: &str =
+        include_str!("sql/conductor/delete_settled_outbox_messages.sql");
     pub const SELECT_VALID_CAP_GRANT_FOR_CAP_SECRET: &str =
         include_str!("sql/conductor/select_valid_cap_grant_for_cap_secret.sql");
     pub const SELECT_VALID_UNRESTRICTED_CAP_GRANT: &str =
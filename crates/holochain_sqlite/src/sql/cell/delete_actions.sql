@@ -0,0 +1,4 @@
+DELETE FROM
+  Action
+WHERE
+  author = :author
@@ -0,0 +1,12 @@
+DELETE FROM
+  Entry
+WHERE
+  hash = :hash
+  AND NOT EXISTS (
+    SELECT
+      1
+    FROM
+      Action
+    WHERE
+      Action.entry_hash = :hash
+  )
@@ -5,3 +5,17 @@ FROM
 WHERE
   DhtOp.authored_timestamp >= :from
   AND DhtOp.authored_timestamp < :to
+  -- only the epochs which overlap the time window are read
+  AND DhtOp.epoch BETWEEN :from / 2592000000000
+  AND :to / 2592000000000
+  -- archived epochs aren't gossiped, even for the ops which are still held
+  AND DhtOp.epoch NOT IN (
+    SELECT
+      epoch
+    FROM
+      ArchivedDhtEpoch
+  )
+  AND (
+    DhtOp.expires_at IS NULL
+    OR DhtOp.expires_at > :expiry_cutoff
+  )
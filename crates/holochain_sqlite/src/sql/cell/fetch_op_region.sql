@@ -37,4 +37,18 @@ WHERE
   AND (
     authored_timestamp >= :timestamp_min
     AND authored_timestamp <= :timestamp_max
+  ) -- only the epochs which overlap the temporal bounds are read
+  AND DhtOp.epoch BETWEEN :timestamp_min / 2592000000000
+  AND :timestamp_max / 2592000000000
+  -- archived epochs aren't gossiped, even for the ops which are still held
+  AND DhtOp.epoch NOT IN (
+    SELECT
+      epoch
+    FROM
+      ArchivedDhtEpoch
+  )
+  -- ops of ephemeral entries are excluded once they expire
+  AND (
+    DhtOp.expires_at IS NULL
+    OR DhtOp.expires_at > :expiry_cutoff
   )
@@ -0,0 +1,5 @@
+-- no-sql-format --
+
+ALTER TABLE DhtOp ADD COLUMN  expires_at  INTEGER  NULL;  -- DATETIME. Only set for the ops of ephemeral entries.
+
+CREATE INDEX DhtOp_expires_at_idx ON DhtOp ( expires_at );
@@ -0,0 +1,13 @@
+-- no-sql-format --
+
+-- The epoch which an op was authored in, as 30 day partitions of the authored timestamp,
+-- so that gossip queries and purges only touch the epochs which they cover.
+ALTER TABLE DhtOp ADD COLUMN  epoch  INTEGER  GENERATED ALWAYS AS (authored_timestamp / 2592000000000) VIRTUAL;
+
+CREATE INDEX DhtOp_epoch_idx ON DhtOp ( epoch, storage_center_loc );
+
+-- The epochs whose ops have been archived and deleted, so that they aren't taken in again.
+CREATE TABLE IF NOT EXISTS ArchivedDhtEpoch (
+    epoch           INTEGER     PRIMARY KEY ON CONFLICT IGNORE,
+    archived_at     INTEGER     NOT NULL  -- DATETIME
+);
@@ -0,0 +1,5 @@
+-- no-sql-format --
+
+-- Entries are stored once for all the actions which commit them, so whether any
+-- action still refers to an entry is checked before the entry is deleted.
+CREATE INDEX Action_entry_hash_idx ON Action ( entry_hash );
@@ -0,0 +1,18 @@
+-- no-sql-format --
+
+-- The text content of the entries of the types which a DNA indexes for full-text
+-- queries, and the FTS5 index over it. The text is dropped with its entry.
+CREATE TABLE IF NOT EXISTS EntryText (
+    entry_hash       BLOB           PRIMARY KEY ON CONFLICT IGNORE,
+    content          TEXT           NOT NULL
+);
+CREATE VIRTUAL TABLE IF NOT EXISTS EntryFullText USING fts5 ( content, content = 'EntryText' );
+CREATE TRIGGER IF NOT EXISTS EntryText_insert AFTER INSERT ON EntryText BEGIN
+    INSERT INTO EntryFullText ( rowid, content ) VALUES ( new.rowid, new.content );
+END;
+CREATE TRIGGER IF NOT EXISTS EntryText_delete AFTER DELETE ON EntryText BEGIN
+    INSERT INTO EntryFullText ( EntryFullText, rowid, content ) VALUES ( 'delete', old.rowid, old.content );
+END;
+CREATE TRIGGER IF NOT EXISTS Entry_delete_text AFTER DELETE ON Entry BEGIN
+    DELETE FROM EntryText WHERE entry_hash = old.hash;
+END;
@@ -0,0 +1,16 @@
+SELECT
+  DISTINCT basis_hash
+FROM
+  DhtOp
+WHERE
+  DhtOp.type IN (
+    :store_record,
+    :store_entry,
+    :updated_content,
+    :updated_record,
+    :deleted_by,
+    :deleted_entry_action,
+    :create_link,
+    :delete_link
+  )
+  AND when_integrated = :when_integrated
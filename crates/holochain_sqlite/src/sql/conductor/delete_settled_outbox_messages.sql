@@ -0,0 +1,5 @@
+-- the messages which were settled before a time
+DELETE FROM
+  RemoteOutbox
+WHERE
+  settled_at_us < :before_us
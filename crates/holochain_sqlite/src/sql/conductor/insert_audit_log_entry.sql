@@ -0,0 +1,10 @@
+INSERT INTO
+  AuditLog (timestamp_us, connection, action, target, error)
+VALUES
+  (
+    :timestamp_us,
+    :connection,
+    :action,
+    :target,
+    :error
+  )
@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS AuditLog (
+  -- Never reused, so that entries can't be silently replaced.
+  id INTEGER PRIMARY KEY AUTOINCREMENT,
+  -- literal integer from Timestamp in rust
+  timestamp_us INTEGER NOT NULL,
+  connection TEXT NOT NULL,
+  action TEXT NOT NULL,
+  target TEXT NULL,
+  error TEXT NULL
+);
+
+CREATE INDEX IF NOT EXISTS audit_log_timestamp_us_idx ON AuditLog(timestamp_us);
@@ -0,0 +1,12 @@
+CREATE TABLE IF NOT EXISTS RemoteOutbox (
+  -- The OutboxMessageId
+  id INTEGER PRIMARY KEY,
+  -- The queued message and its delivery
+  blob BLOB NOT NULL,
+  -- Incremented on every change to the message
+  revision INTEGER NOT NULL,
+  -- literal integer from Timestamp in rust
+  settled_at_us INTEGER NULL
+);
+
+CREATE INDEX IF NOT EXISTS remote_outbox_settled_at_us_idx ON RemoteOutbox(settled_at_us);
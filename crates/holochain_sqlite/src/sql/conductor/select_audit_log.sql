@@ -0,0 +1,16 @@
+-- the entries at or after a time, oldest first
+SELECT
+  id,
+  timestamp_us,
+  connection,
+  action,
+  target,
+  error
+FROM
+  AuditLog
+WHERE
+  timestamp_us >= :since_us
+ORDER BY
+  id ASC
+LIMIT
+  :limit
@@ -0,0 +1,6 @@
+SELECT
+  blob
+FROM
+  RemoteOutbox
+ORDER BY
+  id ASC
@@ -0,0 +1,13 @@
+-- a change made after this one may already be stored, so it is only kept
+-- if it's newer
+INSERT INTO
+  RemoteOutbox (id, blob, revision, settled_at_us)
+VALUES
+  (:id, :blob, :revision, :settled_at_us) ON CONFLICT (id) DO
+UPDATE
+SET
+  blob = excluded.blob,
+  revision = excluded.revision,
+  settled_at_us = excluded.settled_at_us
+WHERE
+  excluded.revision > RemoteOutbox.revision
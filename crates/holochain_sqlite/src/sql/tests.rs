@@ -0,0 +1,122 @@
+use super::sql_cell::*;
+use crate::prelude::*;
+use crate::query_plan::{explain_query_plan, is_table_scan};
+use holo_hash::DnaHash;
+use std::sync::Arc;
+
+/// Check that each statement is planned to use the given indexes, in order, and never
+/// scans a table.
+async fn assert_index_usage(cases: Vec<(&'static str, &'static str, Vec<&'static str>)>) {
+    let db = DbWrite::test_in_mem(DbKindDht(Arc::new(DnaHash::from_raw_36(vec![0; 36])))).unwrap();
+    db.read_async(move |txn| {
+        for (name, sql, indexes) in cases {
+            let plan = explain_query_plan(txn, sql)?;
+            assert!(
+                !plan.iter().any(|step| is_table_scan(step)),
+                "{} scans a table: {:#?}",
+                name,
+                plan
+            );
+            let used: Vec<&str> = plan
+                .iter()
+                .filter_map(|step| step.split(" INDEX ").nth(1))
+                .filter_map(|rest| rest.split_whitespace().next())
+                .collect();
+            assert_eq!(indexes, used, "{} uses other indexes: {:#?}", name, plan);
+        }
+        DatabaseResult::Ok(())
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn integration_updates_use_indexes() {
+    assert_index_usage(vec![
+        (
+            "UPDATE_INTEGRATE_DEP_ACTIVITY",
+            UPDATE_INTEGRATE_DEP_ACTIVITY,
+            vec!["DhtOp_stage_type_status_idx", "Action_author"],
+        ),
+        (
+            "UPDATE_INTEGRATE_DEP_STORE_RECORD",
+            UPDATE_INTEGRATE_DEP_STORE_RECORD,
+            vec!["DhtOp_stage_type_status_idx", "DhtOp_action_hash_idx"],
+        ),
+        (
+            "UPDATE_INTEGRATE_DEP_STORE_ENTRY",
+            UPDATE_INTEGRATE_DEP_STORE_ENTRY,
+            vec!["DhtOp_stage_type_status_idx", "DhtOp_action_hash_idx"],
+        ),
+        (
+            "UPDATE_INTEGRATE_DEP_STORE_ENTRY_BASIS",
+            UPDATE_INTEGRATE_DEP_STORE_ENTRY_BASIS,
+            vec!["DhtOp_stage_type_status_idx", "DhtOp_basis_hash_idx"],
+        ),
+        (
+            "UPDATE_INTEGRATE_DEP_CREATE_LINK",
+            UPDATE_INTEGRATE_DEP_CREATE_LINK,
+            vec!["DhtOp_stage_type_status_idx", "DhtOp_action_hash_idx"],
+        ),
+    ])
+    .await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn entry_references_are_checked_by_index() {
+    assert_index_usage(vec![(
+        "DELETE_UNREFERENCED_ENTRY",
+        DELETE_UNREFERENCED_ENTRY,
+        vec!["sqlite_autoindex_Entry_1", "Action_entry_hash_idx"],
+    )])
+    .await;
+}
+
+#[test]
+fn table_scans_are_told_apart_from_index_scans() {
+    assert!(is_table_scan("SCAN DhtOp"));
+    assert!(!is_table_scan(
+        "SCAN DhtOp USING COVERING INDEX DhtOp_type_dep_idx"
+    ));
+    assert!(!is_table_scan(
+        "SEARCH DhtOp USING INDEX DhtOp_action_hash_idx (action_hash=?)"
+    ));
+}
+
+#[test]
+fn epoch_queries_match_the_epoch_length() {
+    let epoch = format!("/ {}", DHT_OP_EPOCH_MICROS);
+    for sql in [
+        FETCH_OP_HASHES_P1,
+        FETCH_OP_REGION,
+        FETCH_OPS_BY_REGION,
+        FETCH_REGION_OP_HASHES,
+        include_str!("cell/schema/6-up.sql"),
+    ] {
+        assert!(sql.contains(&epoch), "{}", sql);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn region_queries_read_bounded_epochs() {
+    let db = DbWrite::test_in_mem(DbKindDht(Arc::new(DnaHash::from_raw_36(vec![0; 36])))).unwrap();
+    db.read_async(move |txn| {
+        for (name, sql) in [
+            ("FETCH_OP_REGION", FETCH_OP_REGION),
+            ("FETCH_OPS_BY_REGION", FETCH_OPS_BY_REGION),
+            ("FETCH_REGION_OP_HASHES", FETCH_REGION_OP_HASHES),
+        ] {
+            let plan = explain_query_plan(txn, sql)?;
+            assert!(
+                plan.iter()
+                    .any(|step| step.contains("INDEX DhtOp_epoch_idx (epoch>? AND epoch<?)")),
+                "{} doesn't read by epoch: {:#?}",
+                name,
+                plan
+            );
+        }
+        DatabaseResult::Ok(())
+    })
+    .await
+    .unwrap();
+}
@@ -3,35 +3,27 @@
 use rusqlite::Connection;
 
 use crate::db::DbKind;
+use crate::schema::*;
 
 /// Enumeration of all databases needed by Holochain
-pub(crate) fn initialize_database(conn: &mut Connection, db_kind: DbKind) -> rusqlite::Result<()> {
+pub(crate) fn schema_for_kind(db_kind: &DbKind) -> Option<&'static Schema> {
     match db_kind {
-        DbKind::Dht(_) => {
-            crate::schema::SCHEMA_CELL.initialize(conn, Some(db_kind))?;
-        }
-        DbKind::Authored(_) => {
-            crate::schema::SCHEMA_CELL.initialize(conn, Some(db_kind))?;
-        }
-        DbKind::Conductor => {
-            crate::schema::SCHEMA_CONDUCTOR.initialize(conn, Some(db_kind))?;
-        }
-        DbKind::Wasm => {
-            crate::schema::SCHEMA_WASM.initialize(conn, Some(db_kind))?;
-        }
-        DbKind::P2pAgentStore(_) => {
-            crate::schema::SCHEMA_P2P_STATE.initialize(conn, Some(db_kind))?;
-        }
-        DbKind::P2pMetrics(_) => {
-            crate::schema::SCHEMA_P2P_METRICS.initialize(conn, Some(db_kind))?;
-        }
-        DbKind::Cache(_) => {
-            crate::schema::SCHEMA_CELL.initialize(conn, Some(db_kind))?;
-        }
+        DbKind::Dht(_) => Some(&SCHEMA_CELL),
+        DbKind::Authored(_) => Some(&SCHEMA_CELL),
+        DbKind::Conductor => Some(&SCHEMA_CONDUCTOR),
+        DbKind::Wasm => Some(&SCHEMA_WASM),
+        DbKind::P2pAgentStore(_) => Some(&SCHEMA_P2P_STATE),
+        DbKind::P2pMetrics(_) => Some(&SCHEMA_P2P_METRICS),
+        DbKind::Cache(_) => Some(&SCHEMA_CELL),
         #[cfg(feature = "test_utils")]
-        DbKind::Test(_) => {
-            // Nothing to do
-        }
+        DbKind::Test(_) => None,
+    }
+}
+
+/// Run the migrations of a database's schema which haven't been run yet.
+pub(crate) fn initialize_database(conn: &mut Connection, db_kind: DbKind) -> rusqlite::Result<()> {
+    if let Some(schema) = schema_for_kind(&db_kind) {
+        schema.initialize(conn, Some(db_kind))?;
     }
     Ok(())
 }
@@ -0,0 +1,154 @@
+//! Handing off the ops which fall outside the storage arcs of a node's agents,
+//! after the arcs have shrunk.
+//!
+//! Ops outside the arcs are offered to the authorities of their basis, and are only
+//! deleted once one of them has confirmed that it holds them, so that shrinking an arc
+//! doesn't reduce how many authorities hold an op.
+
+use crate::mutations::delete_unreferenced_actions_and_entries;
+use crate::mutations::StateMutationResult;
+use crate::query::map_sql_dht_op_common;
+use crate::query::StateQueryResult;
+use holo_hash::DhtOpHash;
+use holochain_sqlite::prelude::*;
+use holochain_sqlite::rusqlite::named_params;
+use holochain_types::prelude::*;
+use kitsune_p2p::dht_arc::{DhtArcSet, DhtLocation};
+
+/// Up to `limit` integrated chain ops whose storage location is outside of `arcs`.
+///
+/// Ops of private entries are never handed off, as they are only held by their author.
+pub fn ops_outside_arcs(
+    txn: &Transaction,
+    arcs: &DhtArcSet,
+    limit: usize,
+) -> StateQueryResult<Vec<DhtOpHashed>> {
+    let mut stmt = txn.prepare(
+        "
+        SELECT DhtOp.hash, DhtOp.storage_center_loc
+        FROM DhtOp
+        JOIN Action ON DhtOp.action_hash = Action.hash
+        WHERE DhtOp.when_integrated IS NOT NULL
+        ",
+    )?;
+    let mut rows = stmt.query([])?;
+    let mut hashes = Vec::new();
+    while hashes.len() < limit {
+        let Some(row) = rows.next()? else {
+            break;
+        };
+        let loc: u32 = row.get("storage_center_loc")?;
+        if !arcs.contains(DhtLocation::new(loc)) {
+            hashes.push(row.get::<_, DhtOpHash>("hash")?);
+        }
+    }
+
+    let mut stmt = txn.prepare_cached(
+        "
+        SELECT DhtOp.type AS dht_type,
+        Action.blob AS action_blob,
+        Entry.blob AS entry_blob
+        FROM DhtOp
+        JOIN Action ON DhtOp.action_hash = Action.hash
+        LEFT JOIN Entry ON Action.entry_hash = Entry.hash
+        WHERE DhtOp.hash = :hash
+        ",
+    )?;
+    let mut ops = Vec::with_capacity(hashes.len());
+    for hash in hashes {
+        let op = stmt
+            .query_and_then(named_params! { ":hash": hash }, |row| {
+                map_sql_dht_op_common(false, false, "dht_type", row)
+            })?
+            .next()
+            .transpose()?
+            .flatten();
+        if let Some(op) = op {
+            ops.push(DhtOpHashed::with_pre_hashed(op, hash));
+        }
+    }
+    Ok(ops)
+}
+
+/// The hashes of `hashes` which are held in the DHT database, whether or not
+/// they have been validated yet.
+pub fn held_op_hashes(
+    txn: &Transaction,
+    hashes: impl IntoIterator<Item = DhtOpHash>,
+) -> StateQueryResult<Vec<DhtOpHash>> {
+    let mut stmt = txn.prepare_cached("SELECT 1 FROM DhtOp WHERE hash = :hash")?;
+    let mut held = Vec::new();
+    for hash in hashes {
+        if stmt.exists(named_params! { ":hash": hash })? {
+            held.push(hash);
+        }
+    }
+    Ok(held)
+}
+
+/// Delete ops which other authorities have confirmed that they hold, as well as any
+/// entries and actions which are no longer referred to by an op.
+/// Returns the number of ops which were deleted.
+pub fn delete_handed_off_ops(
+    txn: &mut Txn<DbKindDht>,
+    hashes: &[DhtOpHash],
+) -> StateMutationResult<usize> {
+    let mut count = 0;
+    for hash in hashes {
+        count += txn.execute(
+            "DELETE FROM DhtOp WHERE hash = :hash",
+            named_params! { ":hash": hash },
+        )?;
+    }
+    if count > 0 {
+        delete_unreferenced_actions_and_entries(txn)?;
+    }
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mutations::insert_op_dht;
+    use crate::mutations::set_when_integrated;
+    use crate::test_utils::test_dht_db;
+    use ::fixt::prelude::*;
+    use kitsune_p2p::dht_arc::DhtArcRange;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn ops_outside_arcs_are_handed_off() {
+        let db = test_dht_db().to_db();
+
+        let create_link = fixt!(CreateLink);
+        let ops = vec![
+            DhtOpHashed::from_content_sync(ChainOp::RegisterAgentActivity(
+                fixt!(Signature),
+                create_link.clone().into(),
+            )),
+            DhtOpHashed::from_content_sync(ChainOp::RegisterAddLink(fixt!(Signature), create_link)),
+        ];
+        // Only hold the location of the first op.
+        let held_loc = ops[0].dht_basis().get_loc();
+        let arcs = DhtArcSet::from(DhtArcRange::from_bounds(held_loc, held_loc));
+        let outside = ops[1].to_hash();
+
+        let (found, deleted, held) = db
+            .write_async(move |txn| {
+                for op in &ops {
+                    insert_op_dht(txn, op, None)?;
+                    set_when_integrated(txn, op.as_hash(), Timestamp::now())?;
+                }
+                let found = ops_outside_arcs(txn, &arcs, 10)?;
+                let hashes: Vec<_> = found.iter().map(|op| op.to_hash()).collect();
+                let deleted = delete_handed_off_ops(txn, &hashes)?;
+                let held = held_op_hashes(txn, ops.iter().map(|op| op.to_hash()))?;
+                StateMutationResult::Ok((hashes, deleted, held))
+            })
+            .await
+            .unwrap();
+        assert_eq!(vec![outside.clone()], found);
+        assert_eq!(1, deleted);
+        assert!(!held.contains(&outside));
+        assert_eq!(1, held.len());
+    }
+}
@@ -0,0 +1,106 @@
+//! The append-only log of administrative actions taken on the conductor.
+
+use holochain_sqlite::prelude::DatabaseResult;
+use holochain_sqlite::prelude::DbWrite;
+use holochain_sqlite::rusqlite::named_params;
+use holochain_sqlite::sql::sql_conductor;
+use holochain_types::prelude::AuditLogEntry;
+use holochain_types::prelude::DbKindConductor;
+use holochain_zome_types::prelude::Timestamp;
+
+/// Append an entry to the audit log. Entries are never updated or deleted.
+#[cfg_attr(feature = "instrument", tracing::instrument(skip_all))]
+pub async fn record_audit_log_entry(
+    db: &DbWrite<DbKindConductor>,
+    timestamp: Timestamp,
+    connection: String,
+    action: String,
+    target: Option<String>,
+    error: Option<String>,
+) -> DatabaseResult<()> {
+    db.write_async(move |txn| {
+        txn.execute(
+            sql_conductor::INSERT_AUDIT_LOG_ENTRY,
+            named_params! {
+                ":timestamp_us": timestamp,
+                ":connection": connection,
+                ":action": action,
+                ":target": target,
+                ":error": error,
+            },
+        )?;
+        Ok(())
+    })
+    .await
+}
+
+/// Up to `limit` entries of the audit log which were recorded at or after `since`,
+/// oldest first.
+pub async fn list_audit_log(
+    db: &DbWrite<DbKindConductor>,
+    since: Timestamp,
+    limit: u32,
+) -> DatabaseResult<Vec<AuditLogEntry>> {
+    db.read_async(move |txn| {
+        let mut stmt = txn.prepare(sql_conductor::SELECT_AUDIT_LOG)?;
+        let entries = stmt
+            .query_map(
+                named_params! {
+                    ":since_us": since,
+                    ":limit": limit,
+                },
+                |row| {
+                    Ok(AuditLogEntry {
+                        id: row.get("id")?,
+                        timestamp: row.get("timestamp_us")?,
+                        connection: row.get("connection")?,
+                        action: row.get("action")?,
+                        target: row.get("target")?,
+                        error: row.get("error")?,
+                    })
+                },
+            )?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(entries)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::test_conductor_db;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn audit_log_is_listed_in_order_since_a_time() {
+        let db = test_conductor_db();
+        for (us, action) in [(1, "install_app"), (2, "enable_app"), (3, "uninstall_app")] {
+            record_audit_log_entry(
+                &db,
+                Timestamp(us),
+                "127.0.0.1:1234".into(),
+                action.into(),
+                Some("app".into()),
+                (action == "uninstall_app").then(|| "not installed".into()),
+            )
+            .await
+            .unwrap();
+        }
+
+        let entries = list_audit_log(&db, Timestamp(2), 10).await.unwrap();
+        assert_eq!(
+            vec!["enable_app", "uninstall_app"],
+            entries
+                .iter()
+                .map(|entry| entry.action.as_str())
+                .collect::<Vec<_>>()
+        );
+        assert!(entries[0].id < entries[1].id);
+        assert_eq!(None, entries[0].error);
+        assert_eq!(Some("not installed".to_string()), entries[1].error);
+
+        let entries = list_audit_log(&db, Timestamp(0), 1).await.unwrap();
+        assert_eq!(1, entries.len());
+        assert_eq!("install_app", entries[0].action);
+    }
+}
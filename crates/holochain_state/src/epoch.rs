@@ -0,0 +1,210 @@
+//! Epochs of the DHT database, which partition its ops by the time they were authored.
+//!
+//! Gossip queries only read the epochs which overlap the time window they cover, and
+//! whole epochs of integrated ops can be archived and deleted at once. The epochs which
+//! have been archived are recorded, so that their ops aren't taken in again when peers
+//! gossip them back.
+
+use crate::mutations::delete_unreferenced_actions_and_entries;
+use crate::mutations::StateMutationResult;
+use crate::query::StateQueryResult;
+use holochain_sqlite::prelude::*;
+use holochain_sqlite::rusqlite::named_params;
+use holochain_types::prelude::*;
+use std::collections::BTreeMap;
+use std::collections::HashSet;
+
+/// The epochs which the DHT database holds ops for or has archived, oldest first.
+pub fn list_dht_epochs(txn: &Txn<DbKindDht>) -> StateQueryResult<Vec<DhtEpochSummary>> {
+    let mut epochs: BTreeMap<i64, DhtEpochSummary> = BTreeMap::new();
+    let mut stmt = txn.prepare("SELECT epoch, COUNT(hash) FROM DhtOp GROUP BY epoch")?;
+    let held = stmt.query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, u64>(1)?)))?;
+    for held in held {
+        let (epoch, op_count) = held?;
+        epochs.insert(epoch, epoch_summary(epoch, op_count, false));
+    }
+    for epoch in archived_dht_epochs(txn)? {
+        epochs
+            .entry(epoch)
+            .or_insert_with(|| epoch_summary(epoch, 0, true))
+            .archived = true;
+    }
+    Ok(epochs.into_values().collect())
+}
+
+/// The epochs whose ops have been archived.
+pub fn archived_dht_epochs(txn: &Txn<DbKindDht>) -> StateQueryResult<HashSet<i64>> {
+    let mut stmt = txn.prepare_cached("SELECT epoch FROM ArchivedDhtEpoch")?;
+    let epochs = stmt
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<_, _>>()?;
+    Ok(epochs)
+}
+
+/// Delete the integrated ops of every epoch before `before_epoch`, as well as any
+/// entries and actions which are no longer referred to by an op, and record the
+/// epochs as archived. Ops which are still being validated are kept.
+/// Returns the number of ops which were deleted.
+pub fn delete_dht_epochs(
+    txn: &mut Txn<DbKindDht>,
+    before_epoch: i64,
+    archived_at: Timestamp,
+) -> StateMutationResult<usize> {
+    txn.execute(
+        "
+        INSERT INTO ArchivedDhtEpoch (epoch, archived_at)
+        SELECT DISTINCT epoch, :archived_at FROM DhtOp
+        WHERE epoch < :before_epoch AND when_integrated IS NOT NULL
+        ",
+        named_params! {
+            ":before_epoch": before_epoch,
+            ":archived_at": archived_at,
+        },
+    )?;
+    let count = txn.execute(
+        "DELETE FROM DhtOp WHERE epoch < :before_epoch AND when_integrated IS NOT NULL",
+        named_params! {
+            ":before_epoch": before_epoch,
+        },
+    )?;
+    if count > 0 {
+        delete_unreferenced_actions_and_entries(txn)?;
+    }
+    Ok(count)
+}
+
+/// Stop treating epochs as archived, e.g. when their archive is imported again.
+pub fn unarchive_dht_epochs(
+    txn: &mut Txn<DbKindDht>,
+    epochs: impl IntoIterator<Item = i64>,
+) -> StateMutationResult<()> {
+    for epoch in epochs {
+        txn.execute(
+            "DELETE FROM ArchivedDhtEpoch WHERE epoch = :epoch",
+            named_params! {
+                ":epoch": epoch,
+            },
+        )?;
+    }
+    Ok(())
+}
+
+fn epoch_summary(epoch: i64, op_count: u64, archived: bool) -> DhtEpochSummary {
+    DhtEpochSummary {
+        epoch,
+        starts_at: dht_op_epoch_start(epoch),
+        op_count,
+        archived,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mutations::insert_op_dht;
+    use crate::mutations::set_when_integrated;
+    use crate::test_utils::test_dht_db;
+    use ::fixt::prelude::*;
+    use holochain_sqlite::sql::sql_cell::DHT_OP_EPOCH_MICROS;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn whole_epochs_are_deleted() {
+        let db = test_dht_db().to_db();
+
+        let op_at = |micros: i64| {
+            let mut create = fixt!(Create);
+            create.timestamp = Timestamp::from_micros(micros);
+            DhtOpHashed::from_content_sync(ChainOp::RegisterAgentActivity(
+                fixt!(Signature),
+                create.into(),
+            ))
+        };
+        let old = op_at(DHT_OP_EPOCH_MICROS + 1);
+        let old_pending = op_at(DHT_OP_EPOCH_MICROS + 2);
+        let current = op_at(2 * DHT_OP_EPOCH_MICROS);
+
+        let (deleted, epochs) = db
+            .write_async(move |txn| {
+                for op in [&old, &old_pending, &current] {
+                    insert_op_dht(txn, op, None)?;
+                }
+                for op in [&old, &current] {
+                    set_when_integrated(txn, op.as_hash(), Timestamp::now())?;
+                }
+                let deleted = delete_dht_epochs(txn, 2, Timestamp::now())?;
+                let epochs = list_dht_epochs(txn)?;
+                StateMutationResult::Ok((deleted, epochs))
+            })
+            .await
+            .unwrap();
+        assert_eq!(1, deleted);
+        assert_eq!(
+            vec![(1, 1, true), (2, 1, false)],
+            epochs
+                .iter()
+                .map(|epoch| (epoch.epoch, epoch.op_count, epoch.archived))
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn archived_epochs_are_left_out_of_gossip() {
+        use holochain_sqlite::sql::sql_cell::{FETCH_OP_HASHES_P1, FETCH_OP_REGION};
+
+        let db = test_dht_db().to_db();
+
+        let op_at = |micros: i64| {
+            let mut create = fixt!(Create);
+            create.timestamp = Timestamp::from_micros(micros);
+            DhtOpHashed::from_content_sync(ChainOp::RegisterAgentActivity(
+                fixt!(Signature),
+                create.into(),
+            ))
+        };
+        let old = op_at(DHT_OP_EPOCH_MICROS + 1);
+        let old_pending = op_at(DHT_OP_EPOCH_MICROS + 2);
+        let current = op_at(2 * DHT_OP_EPOCH_MICROS);
+        let current_hash = current.as_hash().clone();
+
+        let (hashes, region_count) = db
+            .write_async(move |txn| {
+                for op in [&old, &old_pending, &current] {
+                    insert_op_dht(txn, op, None)?;
+                }
+                for op in [&old, &current] {
+                    set_when_integrated(txn, op.as_hash(), Timestamp::now())?;
+                }
+                delete_dht_epochs(txn, 2, Timestamp::now())?;
+
+                // - The op of the archived epoch which is still being validated is held,
+                //   but neither its op hash nor its region is gossiped.
+                let hashes = txn
+                    .prepare(FETCH_OP_HASHES_P1)?
+                    .query_map(
+                        named_params! {
+                            ":from": Timestamp::from_micros(0),
+                            ":to": Timestamp::from_micros(3 * DHT_OP_EPOCH_MICROS),
+                            ":expiry_cutoff": Timestamp::now(),
+                        },
+                        |row| row.get::<_, DhtOpHash>(0),
+                    )?
+                    .collect::<Result<Vec<_>, _>>()?;
+                let region_count: u32 = txn.query_row(
+                    FETCH_OP_REGION,
+                    named_params! {
+                        ":storage_start_loc": 0,
+                        ":storage_end_loc": u32::MAX,
+                        ":timestamp_min": Timestamp::from_micros(0),
+                        ":timestamp_max": Timestamp::from_micros(3 * DHT_OP_EPOCH_MICROS),
+                        ":expiry_cutoff": Timestamp::now(),
+                    },
+                    |row| row.get("count"),
+                )?;
+                StateMutationResult::Ok((hashes, region_count))
+            })
+            .await
+            .unwrap();
+        assert_eq!(vec![current_hash], hashes);
+        assert_eq!(1, region_count);
+    }
+}
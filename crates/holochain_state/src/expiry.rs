@@ -0,0 +1,230 @@
+//! Expiry of the ops of ephemeral entries, which are entries whose type has a TTL.
+//!
+//! Only the ops which store an ephemeral entry expire, which are the
+//! [`ChainOpType::StoreEntry`] and [`ChainOpType::StoreRecord`] ops. The agent activity
+//! of the action which created the entry is kept, so that the author's source chain
+//! can still be validated.
+//!
+//! Expiry times are rounded up to a multiple of the [`EXPIRY_QUANTUM`], and ops are
+//! excluded from gossip once the current time rounded down to the quantum has passed them.
+//! That way authorities whose clocks differ by less than the quantum agree on which ops
+//! have expired, so that expired ops don't cause their gossip regions to mismatch.
+
+use crate::mutations::delete_unreferenced_actions_and_entries;
+use crate::mutations::StateMutationResult;
+use crate::query::from_blob;
+use holo_hash::DhtOpHash;
+use holochain_sqlite::prelude::*;
+use holochain_sqlite::rusqlite::named_params;
+use holochain_types::prelude::*;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// The granularity of the expiry of the ops of ephemeral entries.
+pub const EXPIRY_QUANTUM: Duration = Duration::from_secs(5 * 60);
+
+/// The TTLs of the ephemeral entry types of a DNA.
+pub type EphemeralEntryTypes = HashMap<(ZomeIndex, EntryDefIndex), Duration>;
+
+/// When an op of an ephemeral entry which was authored at `timestamp` expires.
+pub fn op_expiry(timestamp: Timestamp, ttl: Duration) -> Timestamp {
+    let quantum = EXPIRY_QUANTUM.as_micros() as i64;
+    let expires_at = timestamp
+        .as_micros()
+        .saturating_add(i64::try_from(ttl.as_micros()).unwrap_or(i64::MAX));
+    match expires_at.rem_euclid(quantum) {
+        0 => Timestamp::from_micros(expires_at),
+        rem => Timestamp::from_micros(expires_at.saturating_add(quantum - rem)),
+    }
+}
+
+/// The ops which expire at or before the returned time have expired at `now`.
+pub fn expiry_cutoff(now: Timestamp) -> Timestamp {
+    let quantum = EXPIRY_QUANTUM.as_micros() as i64;
+    let now = now.as_micros();
+    Timestamp::from_micros(now - now.rem_euclid(quantum))
+}
+
+/// Set when the ops of ephemeral entries which were integrated at or after `since` expire.
+/// Returns the number of ops which were found to be ephemeral.
+pub fn set_ephemeral_op_expiry(
+    txn: &mut Txn<DbKindDht>,
+    ephemeral_types: &EphemeralEntryTypes,
+    since: Timestamp,
+) -> StateMutationResult<usize> {
+    if ephemeral_types.is_empty() {
+        return Ok(0);
+    }
+    let ops = txn
+        .prepare_cached(
+            "
+            SELECT DhtOp.hash, Action.blob
+            FROM DhtOp
+            JOIN Action ON DhtOp.action_hash = Action.hash
+            WHERE DhtOp.type IN (:store_entry, :store_record)
+            AND DhtOp.when_integrated >= :since
+            AND DhtOp.expires_at IS NULL
+            AND Action.entry_type IS NOT NULL
+            ",
+        )?
+        .query_map(
+            named_params! {
+                ":store_entry": ChainOpType::StoreEntry,
+                ":store_record": ChainOpType::StoreRecord,
+                ":since": since,
+            },
+            |row| {
+                let hash: DhtOpHash = row.get("hash")?;
+                let blob: Vec<u8> = row.get("blob")?;
+                Ok((hash, blob))
+            },
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut count = 0;
+    for (hash, blob) in ops {
+        let action = from_blob::<SignedAction>(blob)?;
+        let ttl = match action.action().entry_type() {
+            Some(EntryType::App(app_entry_def)) => {
+                ephemeral_types.get(&(app_entry_def.zome_index, app_entry_def.entry_index))
+            }
+            _ => None,
+        };
+        if let Some(ttl) = ttl {
+            txn.execute(
+                "UPDATE DhtOp SET expires_at = :expires_at WHERE hash = :hash",
+                named_params! {
+                    ":expires_at": op_expiry(action.action().timestamp(), *ttl),
+                    ":hash": hash,
+                },
+            )?;
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Delete the ops which have expired by `cutoff`, as well as any entries and actions
+/// which are no longer referred to by an op.
+/// Returns the number of ops which were deleted.
+pub fn delete_expired_ops(
+    txn: &mut Txn<DbKindDht>,
+    cutoff: Timestamp,
+) -> StateMutationResult<usize> {
+    let count = txn.execute(
+        "DELETE FROM DhtOp WHERE expires_at <= :cutoff",
+        named_params! {
+            ":cutoff": cutoff,
+        },
+    )?;
+    if count == 0 {
+        return Ok(0);
+    }
+
+    delete_unreferenced_actions_and_entries(txn)?;
+    Ok(count)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mutations::insert_op_dht;
+    use crate::mutations::set_when_integrated;
+    use crate::test_utils::test_dht_db;
+    use ::fixt::prelude::*;
+
+    #[test]
+    fn expiry_is_quantized() {
+        let quantum = EXPIRY_QUANTUM.as_micros() as i64;
+        let authored = Timestamp::from_micros(10 * quantum + 1);
+
+        let expires_at = op_expiry(authored, Duration::from_secs(1));
+        assert_eq!(Timestamp::from_micros(11 * quantum), expires_at);
+        assert_eq!(
+            expires_at,
+            op_expiry(
+                Timestamp::from_micros(11 * quantum - 1_000_000),
+                Duration::from_secs(1)
+            )
+        );
+
+        // The op is only excluded once the cutoff has passed its expiry.
+        assert!(expiry_cutoff(Timestamp::from_micros(11 * quantum - 1)) < expires_at);
+        assert_eq!(
+            expires_at,
+            expiry_cutoff(Timestamp::from_micros(11 * quantum + 1))
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn expired_ops_are_deleted() {
+        let db = test_dht_db().to_db();
+
+        let entry_def = AppEntryDef::new(0.into(), 0.into(), EntryVisibility::Public);
+        let ephemeral_types: EphemeralEntryTypes = [(
+            (entry_def.zome_index, entry_def.entry_index),
+            Duration::from_secs(60),
+        )]
+        .into();
+
+        let entry = Entry::App(fixt!(AppEntryBytes));
+        let mut ephemeral = fixt!(Create);
+        ephemeral.entry_type = EntryType::App(entry_def.clone());
+        ephemeral.entry_hash = EntryHash::with_data_sync(&entry);
+        let mut kept = fixt!(Create);
+        kept.entry_type = EntryType::App(AppEntryDef::new(
+            1.into(),
+            0.into(),
+            EntryVisibility::Public,
+        ));
+        let kept_entry = Entry::App(fixt!(AppEntryBytes));
+        kept.entry_hash = EntryHash::with_data_sync(&kept_entry);
+
+        let ephemeral_op = DhtOpHashed::from_content_sync(ChainOp::StoreEntry(
+            fixt!(Signature),
+            NewEntryAction::Create(ephemeral.clone()),
+            entry.clone(),
+        ));
+        let activity_op = DhtOpHashed::from_content_sync(ChainOp::RegisterAgentActivity(
+            fixt!(Signature),
+            ephemeral.clone().into(),
+        ));
+        let kept_op = DhtOpHashed::from_content_sync(ChainOp::StoreEntry(
+            fixt!(Signature),
+            NewEntryAction::Create(kept),
+            kept_entry,
+        ));
+
+        let expires_at = op_expiry(ephemeral.timestamp, Duration::from_secs(60));
+        let (tagged, deleted_early, deleted) = db
+            .write_async(move |txn| {
+                for op in [&ephemeral_op, &activity_op, &kept_op] {
+                    insert_op_dht(txn, op, None)?;
+                    set_when_integrated(txn, op.as_hash(), Timestamp::now())?;
+                }
+                let tagged = set_ephemeral_op_expiry(txn, &ephemeral_types, Timestamp::MIN)?;
+                let deleted_early = delete_expired_ops(txn, expiry_cutoff(ephemeral.timestamp))?;
+                let deleted = delete_expired_ops(txn, expires_at)?;
+                StateMutationResult::Ok((tagged, deleted_early, deleted))
+            })
+            .await
+            .unwrap();
+        assert_eq!(1, tagged);
+        assert_eq!(0, deleted_early);
+        assert_eq!(1, deleted);
+
+        let (ops, entries) = db
+            .read_async(|txn| {
+                let ops: usize =
+                    txn.query_row("SELECT COUNT(*) FROM DhtOp", [], |row| row.get(0))?;
+                let entries: usize =
+                    txn.query_row("SELECT COUNT(*) FROM Entry", [], |row| row.get(0))?;
+                DatabaseResult::Ok((ops, entries))
+            })
+            .await
+            .unwrap();
+        // The agent activity of the ephemeral entry and the other entry are kept.
+        assert_eq!(2, ops);
+        assert_eq!(1, entries);
+    }
+}
@@ -0,0 +1,313 @@
+//! Full-text indexing of the content of the entries which a cell holds.
+//!
+//! Only the entries of the [`DnaDef::fulltext_entry_types`] of a DNA are indexed, once
+//! their [`ChainOpType::StoreEntry`] op has been integrated as valid. The text of an
+//! entry is every string in its serialized content, so that apps don't have to build
+//! inverted indices out of links to search the data they hold.
+//!
+//! The index only covers the entries held in the local DHT database. The text of an
+//! entry is removed from the index when the entry is deleted from the database.
+
+use crate::mutations::StateMutationResult;
+use crate::query::from_blob;
+use crate::query::StateQueryResult;
+use holo_hash::EntryHash;
+use holochain_sqlite::prelude::*;
+use holochain_sqlite::rusqlite::named_params;
+use holochain_types::prelude::*;
+use serde::de::{DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::Deserialize;
+use std::fmt;
+
+/// Index the content of the entries of the full-text entry types of a DNA whose
+/// store entry ops were integrated as valid at or after `since`.
+/// Returns the number of entries whose text was added to the index.
+pub fn index_fulltext_entries(
+    txn: &mut Txn<DbKindDht>,
+    dna_def: &DnaDef,
+    since: Timestamp,
+) -> StateMutationResult<usize> {
+    if dna_def.fulltext_entry_types.is_empty() {
+        return Ok(0);
+    }
+    let entries = txn
+        .prepare_cached(
+            "
+            SELECT Entry.hash, Entry.blob AS entry_blob, Action.blob AS action_blob
+            FROM DhtOp
+            JOIN Action ON DhtOp.action_hash = Action.hash
+            JOIN Entry ON Action.entry_hash = Entry.hash
+            WHERE DhtOp.type = :store_entry
+            AND DhtOp.when_integrated >= :since
+            AND DhtOp.validation_status = :valid
+            AND NOT EXISTS (SELECT 1 FROM EntryText WHERE EntryText.entry_hash = Entry.hash)
+            ",
+        )?
+        .query_map(
+            named_params! {
+                ":store_entry": ChainOpType::StoreEntry,
+                ":since": since,
+                ":valid": ValidationStatus::Valid,
+            },
+            |row| {
+                let hash: EntryHash = row.get("hash")?;
+                let entry: Vec<u8> = row.get("entry_blob")?;
+                let action: Vec<u8> = row.get("action_blob")?;
+                Ok((hash, entry, action))
+            },
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut count = 0;
+    for (hash, entry, action) in entries {
+        let action = from_blob::<SignedAction>(action)?;
+        let indexed = match action.action().entry_type() {
+            Some(EntryType::App(app_entry_def)) => dna_def.is_fulltext_indexed(app_entry_def),
+            _ => false,
+        };
+        if !indexed {
+            continue;
+        }
+        let Entry::App(bytes) = from_blob::<Entry>(entry)? else {
+            continue;
+        };
+        let text = match entry_text(bytes.as_ref().bytes()) {
+            Ok(text) => text,
+            Err(err) => {
+                tracing::warn!(?err, ?hash, "Failed to read the text of an entry");
+                continue;
+            }
+        };
+        count += txn.execute(
+            "INSERT INTO EntryText (entry_hash, content) VALUES (:entry_hash, :content)",
+            named_params! {
+                ":entry_hash": hash,
+                ":content": text,
+            },
+        )?;
+    }
+    Ok(count)
+}
+
+/// The hashes of up to `limit` of the indexed entries which match an FTS5 full-text
+/// `query`, best matches first.
+pub fn query_fulltext(
+    txn: &Transaction,
+    query: &str,
+    limit: usize,
+) -> StateQueryResult<Vec<EntryHash>> {
+    let hashes = txn
+        .prepare_cached(
+            "
+            SELECT EntryText.entry_hash
+            FROM EntryFullText
+            JOIN EntryText ON EntryText.rowid = EntryFullText.rowid
+            WHERE EntryFullText MATCH :query
+            ORDER BY EntryFullText.rank
+            LIMIT :limit
+            ",
+        )?
+        .query_map(
+            named_params! {
+                ":query": query,
+                ":limit": limit,
+            },
+            |row| row.get(0),
+        )?
+        .collect::<Result<Vec<EntryHash>, _>>()?;
+    Ok(hashes)
+}
+
+/// All the strings in the serialized content of an app entry, one per line.
+/// Map keys are left out, since they are the field names of the entry type.
+fn entry_text(bytes: &[u8]) -> Result<String, SerializedBytesError> {
+    let EntryText(text) = holochain_serialized_bytes::decode(bytes)?;
+    Ok(text)
+}
+
+/// The text which was collected from the serialized content of an entry.
+#[derive(Debug)]
+struct EntryText(String);
+
+impl<'de> Deserialize<'de> for EntryText {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut text = String::new();
+        TextSeed(&mut text).deserialize(deserializer)?;
+        Ok(EntryText(text))
+    }
+}
+
+/// Appends every string of a value to the text.
+struct TextSeed<'a>(&'a mut String);
+
+impl<'de> DeserializeSeed<'de> for TextSeed<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> Visitor<'de> for TextSeed<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("any value")
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<(), E> {
+        if !v.is_empty() {
+            if !self.0.is_empty() {
+                self.0.push('\n');
+            }
+            self.0.push_str(v);
+        }
+        Ok(())
+    }
+
+    fn visit_bool<E>(self, _: bool) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_i64<E>(self, _: i64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_u64<E>(self, _: u64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_f64<E>(self, _: f64) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_bytes<E>(self, _: &[u8]) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_unit<E>(self) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_none<E>(self) -> Result<(), E> {
+        Ok(())
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        self.deserialize(deserializer)
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        self.deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while seq.next_element_seed(TextSeed(self.0))?.is_some() {}
+        Ok(())
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        while map.next_key::<IgnoredAny>()?.is_some() {
+            map.next_value_seed(TextSeed(self.0))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mutations::{delete_unreferenced_actions_and_entries, insert_op_dht};
+    use crate::mutations::{set_validation_status, set_when_integrated};
+    use crate::test_utils::test_dht_db;
+    use ::fixt::prelude::*;
+
+    #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes)]
+    struct Post {
+        title: String,
+        tags: Vec<String>,
+        likes: u32,
+    }
+
+    #[test]
+    fn entry_text_is_the_strings_of_the_entry() {
+        let post = Post {
+            title: "Holochain".to_string(),
+            tags: vec!["p2p".to_string(), "rust".to_string()],
+            likes: 3,
+        };
+        let bytes = SerializedBytes::try_from(post).unwrap();
+        assert_eq!("Holochain\np2p\nrust", entry_text(bytes.bytes()).unwrap());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn entries_of_fulltext_types_are_indexed_and_queried() {
+        let db = test_dht_db().to_db();
+        let mut dna_def = fixt!(DnaDef);
+        dna_def.integrity_zomes = vec![("integrity".into(), fixt!(IntegrityZomeDef))];
+        dna_def.fulltext_entry_types = vec![ZomeTypeIndex {
+            zome: "integrity".into(),
+            index: 0,
+        }];
+
+        let store_entry = |entry_index: u8, title: &str| {
+            let entry = Entry::App(AppEntryBytes(
+                Post {
+                    title: title.to_string(),
+                    tags: vec![],
+                    likes: 0,
+                }
+                .try_into()
+                .unwrap(),
+            ));
+            let mut create = fixt!(Create);
+            create.entry_type = EntryType::App(AppEntryDef::new(
+                entry_index.into(),
+                0.into(),
+                EntryVisibility::Public,
+            ));
+            let entry_hash = EntryHash::with_data_sync(&entry);
+            create.entry_hash = entry_hash.clone();
+            let op = DhtOpHashed::from_content_sync(ChainOp::StoreEntry(
+                fixt!(Signature),
+                NewEntryAction::Create(create),
+                entry,
+            ));
+            (op, entry_hash)
+        };
+        let (indexed, indexed_hash) = store_entry(0, "peer to peer networks");
+        let (other_type, _) = store_entry(1, "peer review");
+
+        let (count, count_again, found, not_found) = db
+            .write_async(move |txn| {
+                for op in [&indexed, &other_type] {
+                    insert_op_dht(txn, op, None)?;
+                    set_validation_status(txn, op.as_hash(), ValidationStatus::Valid)?;
+                    set_when_integrated(txn, op.as_hash(), Timestamp::now())?;
+                }
+                let count = index_fulltext_entries(txn, &dna_def, Timestamp::MIN)?;
+                // - Entries are only indexed once.
+                let count_again = index_fulltext_entries(txn, &dna_def, Timestamp::MIN)?;
+                let found = query_fulltext(txn, "peer", 10)?;
+                let not_found = query_fulltext(txn, "review", 10)?;
+                StateMutationResult::Ok((count, count_again, found, not_found))
+            })
+            .await
+            .unwrap();
+        assert_eq!(1, count);
+        assert_eq!(0, count_again);
+        assert_eq!(vec![indexed_hash], found);
+        assert!(not_found.is_empty());
+
+        // - The text of an entry is dropped with the entry.
+        let found = db
+            .write_async(|txn| {
+                txn.execute("DELETE FROM DhtOp", [])?;
+                delete_unreferenced_actions_and_entries(txn)?;
+                query_fulltext(txn, "peer", 10)
+            })
+            .await
+            .unwrap();
+        assert!(found.is_empty());
+    }
+}
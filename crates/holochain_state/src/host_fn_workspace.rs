@@ -13,6 +13,7 @@ pub struct HostFnWorkspace<
     source_chain: Option<SourceChain<SourceChainDb, SourceChainDht>>,
     authored: DbRead<DbKindAuthored>,
     dht: DbRead<DbKindDht>,
+    dht_db_cache: DhtDbQueryCache,
     cache: DbWrite<DbKindCache>,
     dna_def: Arc<DnaDef>,
     /// Did the root call that started this call chain
@@ -32,6 +33,7 @@ pub struct SourceChainWorkspace {
 pub struct HostFnStores {
     pub authored: DbRead<DbKindAuthored>,
     pub dht: DbRead<DbKindDht>,
+    pub dht_db_cache: DhtDbQueryCache,
     pub cache: DbWrite<DbKindCache>,
     pub scratch: Option<SyncScratch>,
 }
@@ -63,7 +65,15 @@ impl SourceChainWorkspace {
             author,
         )
         .await?;
-        Self::new_inner(authored, dht, cache, source_chain, dna_def, false)
+        Self::new_inner(
+            authored,
+            dht,
+            dht_db_cache,
+            cache,
+            source_chain,
+            dna_def,
+            false,
+        )
     }
 
     /// Create a source chain workspace where the root caller is the init callback.
@@ -84,7 +94,15 @@ impl SourceChainWorkspace {
             author,
         )
         .await?;
-        Self::new_inner(authored, dht, cache, source_chain, dna_def, true)
+        Self::new_inner(
+            authored,
+            dht,
+            dht_db_cache,
+            cache,
+            source_chain,
+            dna_def,
+            true,
+        )
     }
 
     /// Create a source chain with a blank chain head.
@@ -108,12 +126,21 @@ impl SourceChainWorkspace {
             author,
         )
         .await?;
-        Self::new_inner(authored, dht, cache, source_chain, dna_def, false)
+        Self::new_inner(
+            authored,
+            dht,
+            dht_db_cache,
+            cache,
+            source_chain,
+            dna_def,
+            false,
+        )
     }
 
     fn new_inner(
         authored: DbWrite<DbKindAuthored>,
         dht: DbWrite<DbKindDht>,
+        dht_db_cache: DhtDbQueryCache,
         cache: DbWrite<DbKindCache>,
         source_chain: SourceChain,
         dna_def: Arc<DnaDef>,
@@ -124,6 +151,7 @@ impl SourceChainWorkspace {
                 source_chain: Some(source_chain.clone()),
                 authored: authored.into(),
                 dht: dht.into(),
+                dht_db_cache,
                 dna_def,
                 cache,
                 init_is_root,
@@ -170,6 +198,7 @@ where
             source_chain,
             authored: authored.into(),
             dht: dht.into(),
+            dht_db_cache,
             cache,
             dna_def,
             init_is_root: false,
@@ -188,6 +217,7 @@ where
         HostFnStores {
             authored: self.authored.clone(),
             dht: self.dht.clone(),
+            dht_db_cache: self.dht_db_cache.clone(),
             cache: self.cache.clone(),
             scratch: self.source_chain.as_ref().map(|sc| sc.scratch()),
         }
@@ -208,6 +238,16 @@ impl SourceChainWorkspace {
     pub fn source_chain(&self) -> &SourceChain {
         &self.source_chain
     }
+
+    /// Take the timestamps of new actions from `clock`, see [`SourceChain::with_clock`].
+    pub fn with_clock(mut self, clock: holochain_types::clock::Clock) -> Self {
+        self.inner.source_chain = self
+            .inner
+            .source_chain
+            .map(|source_chain| source_chain.with_clock(clock.clone()));
+        self.source_chain = self.source_chain.with_clock(clock);
+        self
+    }
 }
 
 impl From<HostFnWorkspace> for HostFnWorkspaceRead {
@@ -216,6 +256,7 @@ impl From<HostFnWorkspace> for HostFnWorkspaceRead {
             source_chain: workspace.source_chain.map(|sc| sc.into()),
             authored: workspace.authored,
             dht: workspace.dht,
+            dht_db_cache: workspace.dht_db_cache,
             cache: workspace.cache,
             dna_def: workspace.dna_def,
             init_is_root: workspace.init_is_root,
@@ -235,6 +276,7 @@ impl From<SourceChainWorkspace> for HostFnWorkspaceRead {
             source_chain: Some(workspace.source_chain.into()),
             authored: workspace.inner.authored,
             dht: workspace.inner.dht,
+            dht_db_cache: workspace.inner.dht_db_cache,
             cache: workspace.inner.cache,
             dna_def: workspace.inner.dna_def,
             init_is_root: workspace.inner.init_is_root,
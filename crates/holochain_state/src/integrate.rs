@@ -60,6 +60,7 @@ pub async fn authored_ops_to_dht_db_without_check(
             StateMutationResult::Ok(ops)
         })
         .await?;
+    let bases: Vec<_> = ops.iter().map(|op| op.dht_basis()).collect();
     let mut activity = Vec::new();
     let activity = dht_db
         .write_async(|txn| {
@@ -71,6 +72,7 @@ pub async fn authored_ops_to_dht_db_without_check(
             StateMutationResult::Ok(activity)
         })
         .await?;
+    dht_db_cache.queries().invalidate(bases);
     for op in activity {
         let deps = op.sys_validation_dependencies();
 
@@ -27,24 +27,32 @@
 // TODO - address the underlying issue:
 #![allow(clippy::result_large_err)]
 
+pub mod arc_handoff;
+pub mod audit_log;
 #[allow(missing_docs)]
 pub mod block;
 pub mod chain_lock;
 #[allow(missing_docs)]
 pub mod dna_def;
 pub mod entry_def;
+pub mod epoch;
+pub mod expiry;
+pub mod fulltext;
 pub mod host_fn_workspace;
 pub mod integrate;
 pub mod mutations;
 pub mod nonce;
 #[allow(missing_docs)]
 pub mod prelude;
+pub mod publish_audit;
 pub mod query;
+pub mod remote_outbox;
 pub mod schedule;
 pub mod scratch;
 #[allow(missing_docs)]
 pub mod source_chain;
 pub mod validation_db;
+pub mod validation_outcomes;
 pub mod validation_receipts;
 #[allow(missing_docs)]
 pub mod wasm;
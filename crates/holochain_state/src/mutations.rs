@@ -17,8 +17,10 @@ use holochain_types::sql::AsSql;
 use kitsune_p2p::dependencies::kitsune_p2p_fetch::TransferMethod;
 use std::str::FromStr;
 
+pub use batch::*;
 pub use error::*;
 
+mod batch;
 mod error;
 
 #[macro_export]
@@ -1084,11 +1086,66 @@ pub fn remove_countersigning_session(
         [cs_action.to_hash()],
     )?;
     tracing::debug!("Removed {} ops from the authored DHT", count);
-    let count = txn.execute("DELETE FROM Entry WHERE hash = ?", [cs_entry_hash])?;
-    tracing::debug!("Removed {} entries", count);
     let count = txn.execute("DELETE FROM Action WHERE hash = ?", [cs_action.to_hash()])?;
     tracing::debug!("Removed {} actions", count);
+    // Another action may have committed the same entry.
+    let count = delete_unreferenced_entries(txn, &[cs_entry_hash])?;
+    tracing::debug!("Removed {} entries", count);
+
+    Ok(())
+}
 
+/// Delete the given entries unless an action still refers to them, after actions have
+/// been deleted. An entry is stored once however many actions commit it, so it's only
+/// deleted along with the last of them. Returns how many entries were deleted.
+///
+/// Only the actions of the same database are counted: the other databases of the
+/// DNA keep their own copies of the entry.
+pub fn delete_unreferenced_entries(
+    txn: &mut Transaction,
+    entry_hashes: &[EntryHash],
+) -> StateMutationResult<usize> {
+    let mut stmt =
+        txn.prepare_cached(holochain_sqlite::sql::sql_cell::DELETE_UNREFERENCED_ENTRY)?;
+    let mut count = 0;
+    for hash in entry_hashes {
+        count += stmt.execute(named_params! { ":hash": hash })?;
+    }
+    Ok(count)
+}
+
+/// Delete the entries and actions of the DHT database which are no longer referred to
+/// by an op, after ops have been deleted.
+pub fn delete_unreferenced_actions_and_entries(
+    txn: &mut Txn<DbKindDht>,
+) -> StateMutationResult<()> {
+    // An entry can be stored by the ops of several actions, which may not all have been deleted.
+    txn.execute(
+        "
+        DELETE FROM Entry
+        WHERE NOT EXISTS (
+            SELECT 1 FROM DhtOp
+            JOIN Action ON DhtOp.action_hash = Action.hash
+            WHERE Action.entry_hash = Entry.hash
+            AND DhtOp.type IN (:store_entry, :store_record, :updated_content, :updated_record)
+        )
+        ",
+        named_params! {
+            ":store_entry": ChainOpType::StoreEntry,
+            ":store_record": ChainOpType::StoreRecord,
+            ":updated_content": ChainOpType::RegisterUpdatedContent,
+            ":updated_record": ChainOpType::RegisterUpdatedRecord,
+        },
+    )?;
+    txn.execute(
+        "
+        DELETE FROM Action
+        WHERE NOT EXISTS (
+            SELECT 1 FROM DhtOp WHERE DhtOp.action_hash = Action.hash
+        )
+        ",
+        [],
+    )?;
     Ok(())
 }
 
@@ -0,0 +1,221 @@
+//! Inserting many ops into the DHT database at once, e.g. the ops which were fetched
+//! from a peer.
+
+use super::*;
+use holochain_sqlite::rusqlite::params_from_iter;
+use holochain_sqlite::rusqlite::ToSql;
+use std::collections::HashSet;
+
+/// How many ops are looked up or written by each statement, which keeps the number of
+/// bound parameters within SQLite's default limit of 999.
+const OPS_PER_STATEMENT: usize = 64;
+
+/// The columns of the `DhtOp` table which are written for each op.
+const OP_COLUMNS: &str = "hash, type, storage_center_loc, authored_timestamp, when_stored, \
+    basis_hash, action_hash, require_receipt, op_order, dependency";
+
+/// The row of the `DhtOp` table which is written for a chain op.
+struct OpRow<'a> {
+    hash: &'a DhtOpHash,
+    op_type: DhtOpType,
+    basis: OpBasis,
+    authored_timestamp: Timestamp,
+    action_hash: ActionHash,
+    order: OpOrder,
+    dependency: Option<ActionHash>,
+}
+
+/// Insert the ops which the DHT database doesn't already hold, as
+/// [`insert_op_dht`] does for each op, but with the rows of many ops written by each
+/// statement. Each action and entry is written once, however many of the ops were
+/// produced from it.
+///
+/// If `require_receipt` is set, the ops which are already held require a validation
+/// receipt too. Returns how many ops were inserted.
+pub fn insert_ops_dht_batch(
+    txn: &mut Txn<DbKindDht>,
+    ops: &[DhtOpHashed],
+    require_receipt: bool,
+) -> StateMutationResult<usize> {
+    let held = held_op_hashes(txn, ops)?;
+    let mut inserted: HashSet<&DhtOpHash> = HashSet::new();
+    let mut actions: HashSet<ActionHash> = HashSet::new();
+    let mut entries: HashSet<EntryHash> = HashSet::new();
+    let mut rows = Vec::with_capacity(ops.len());
+    let when_stored = Timestamp::now();
+    for op in ops {
+        let hash = op.as_hash();
+        if held.contains(hash) {
+            if require_receipt {
+                set_require_receipt(txn, hash, true)?;
+            }
+            continue;
+        }
+        if !inserted.insert(hash) {
+            continue;
+        }
+        match op.as_content() {
+            DhtOp::ChainOp(chain_op) => {
+                let action = chain_op.action();
+                if let Some(entry) = chain_op.entry().into_option() {
+                    let entry_hash = action
+                        .entry_hash()
+                        .ok_or_else(|| DhtOpError::ActionWithoutEntry(action.clone()))?;
+                    if entries.insert(entry_hash.clone()) {
+                        insert_entry(txn, entry_hash, entry)?;
+                    }
+                }
+                let action_hashed = ActionHashed::from_content_sync(action);
+                let action_hash = action_hashed.as_hash().clone();
+                if actions.insert(action_hash.clone()) {
+                    insert_action(
+                        txn,
+                        &SignedActionHashed::with_presigned(
+                            action_hashed,
+                            chain_op.signature().clone(),
+                        ),
+                    )?;
+                }
+                let op_type = op.get_type();
+                rows.push(OpRow {
+                    hash,
+                    op_type,
+                    basis: op.dht_basis(),
+                    authored_timestamp: op.timestamp(),
+                    action_hash,
+                    order: OpOrder::new(op_type, op.timestamp()),
+                    dependency: op.sys_validation_dependencies().into_iter().next(),
+                });
+            }
+            // Warrants are rare, so they're inserted one at a time.
+            DhtOp::WarrantOp(_) => {
+                insert_op_dht(txn, op, None)?;
+                set_require_receipt(txn, hash, require_receipt)?;
+            }
+        }
+    }
+
+    for chunk in rows.chunks(OPS_PER_STATEMENT) {
+        let sql = format!(
+            "INSERT INTO DhtOp ({}) VALUES {}",
+            OP_COLUMNS,
+            vec!["(?, ?, ?, ?, ?, ?, ?, ?, ?, ?)"; chunk.len()].join(", ")
+        );
+        let locs: Vec<_> = chunk.iter().map(|row| row.basis.get_loc()).collect();
+        let mut params: Vec<&dyn ToSql> = Vec::with_capacity(chunk.len() * 10);
+        for (row, loc) in chunk.iter().zip(&locs) {
+            let values: [&dyn ToSql; 10] = [
+                row.hash,
+                &row.op_type,
+                loc,
+                &row.authored_timestamp,
+                &when_stored,
+                &row.basis,
+                &row.action_hash,
+                &require_receipt,
+                &row.order,
+                &row.dependency,
+            ];
+            params.extend(values);
+        }
+        txn.prepare_cached(&sql)?
+            .execute(params_from_iter(params))?;
+    }
+    Ok(inserted.len())
+}
+
+/// The ops which the DHT database already holds.
+fn held_op_hashes(
+    txn: &Txn<DbKindDht>,
+    ops: &[DhtOpHashed],
+) -> StateMutationResult<HashSet<DhtOpHash>> {
+    let mut held = HashSet::new();
+    for chunk in ops.chunks(OPS_PER_STATEMENT) {
+        let sql = format!(
+            "SELECT hash FROM DhtOp WHERE hash IN ({})",
+            vec!["?"; chunk.len()].join(", ")
+        );
+        let mut stmt = txn.prepare_cached(&sql)?;
+        let hashes = stmt.query_map(
+            params_from_iter(chunk.iter().map(|op| op.as_hash())),
+            |row| row.get(0),
+        )?;
+        for hash in hashes {
+            held.insert(hash?);
+        }
+    }
+    Ok(held)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::test_dht_db;
+    use ::fixt::prelude::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn batched_ops_are_inserted_once() {
+        let db = test_dht_db().to_db();
+
+        let entry = Entry::App(fixt!(AppEntryBytes));
+        let mut create = fixt!(Create);
+        create.entry_type = EntryType::App(AppEntryDef::new(
+            0.into(),
+            0.into(),
+            EntryVisibility::Public,
+        ));
+        create.entry_hash = EntryHash::with_data_sync(&entry);
+        let signature = fixt!(Signature);
+        let store_entry = DhtOpHashed::from_content_sync(ChainOp::StoreEntry(
+            signature.clone(),
+            NewEntryAction::Create(create.clone()),
+            entry.clone(),
+        ));
+        let store_record = DhtOpHashed::from_content_sync(ChainOp::StoreRecord(
+            signature,
+            Action::Create(create),
+            RecordEntry::Present(entry),
+        ));
+        // Far more ops than fit in one statement.
+        let others: Vec<DhtOpHashed> = (0..OPS_PER_STATEMENT * 2 + 1)
+            .map(|_| {
+                DhtOpHashed::from_content_sync(ChainOp::RegisterAgentActivity(
+                    fixt!(Signature),
+                    Action::CreateLink(fixt!(CreateLink)),
+                ))
+            })
+            .collect();
+
+        let (first, second) = db
+            .write_async(move |txn| {
+                let mut ops = vec![store_entry.clone(), store_record, store_entry.clone()];
+                ops.extend(others);
+                let first = insert_ops_dht_batch(txn, &ops, false)?;
+                let second = insert_ops_dht_batch(txn, &[store_entry.clone()], true)?;
+                let require_receipt: bool = txn.query_row(
+                    "SELECT require_receipt FROM DhtOp WHERE hash = ?",
+                    [store_entry.as_hash()],
+                    |row| row.get(0),
+                )?;
+                assert!(require_receipt);
+                StateMutationResult::Ok((first, second))
+            })
+            .await
+            .unwrap();
+        assert_eq!(2 + OPS_PER_STATEMENT * 2 + 1, first);
+        assert_eq!(0, second);
+
+        let (actions, entries) = db
+            .read_async(|txn| {
+                let actions: usize =
+                    txn.query_row("SELECT COUNT(*) FROM Action", [], |row| row.get(0))?;
+                let entries: usize =
+                    txn.query_row("SELECT COUNT(*) FROM Entry", [], |row| row.get(0))?;
+                DatabaseResult::Ok((actions, entries))
+            })
+            .await
+            .unwrap();
+        assert_eq!(1 + OPS_PER_STATEMENT * 2 + 1, actions);
+        assert_eq!(1, entries);
+    }
+}
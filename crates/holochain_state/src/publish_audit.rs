@@ -0,0 +1,123 @@
+//! Auditing whether the authorities of the ops which an agent authored still hold them.
+//!
+//! Ops stop being published once they have collected enough validation receipts, so
+//! ops which were lost after that, e.g. because the authorities which held them left
+//! during a partition, would never be published again. A sample of the actions whose
+//! ops are complete is checked with the network now and then, and the ops of the
+//! actions which no authority holds any more are marked to be published again.
+
+use crate::mutations::StateMutationResult;
+use crate::query::StateQueryResult;
+use holo_hash::{ActionHash, AgentPubKey};
+use holochain_sqlite::prelude::*;
+use holochain_sqlite::rusqlite::named_params;
+
+/// Up to `limit` actions of `author`, picked at random, whose ops have all collected
+/// enough validation receipts and aren't being withheld from publishing.
+pub fn actions_to_audit(
+    txn: &Transaction,
+    author: &AgentPubKey,
+    limit: usize,
+) -> StateQueryResult<Vec<ActionHash>> {
+    let mut stmt = txn.prepare(
+        "
+        SELECT Action.hash
+        FROM Action
+        WHERE Action.author = :author
+        AND EXISTS (SELECT 1 FROM DhtOp WHERE DhtOp.action_hash = Action.hash)
+        AND NOT EXISTS (
+            SELECT 1 FROM DhtOp
+            WHERE DhtOp.action_hash = Action.hash
+            AND (DhtOp.receipts_complete IS NULL OR DhtOp.withhold_publish IS NOT NULL)
+        )
+        ORDER BY RANDOM()
+        LIMIT :limit
+        ",
+    )?;
+    let hashes = stmt
+        .query_map(
+            named_params! { ":author": author, ":limit": limit },
+            |row| row.get(0),
+        )?
+        .collect::<Result<Vec<ActionHash>, _>>()?;
+    Ok(hashes)
+}
+
+/// Mark the ops of an action to be published again, as if they had never collected
+/// any receipts. Returns the number of ops which were marked.
+pub fn republish_action_ops(
+    txn: &mut Txn<DbKindAuthored>,
+    action_hash: &ActionHash,
+) -> StateMutationResult<usize> {
+    Ok(txn.execute(
+        "
+        UPDATE DhtOp
+        SET receipts_complete = NULL, last_publish_time = NULL
+        WHERE action_hash = :action_hash
+        ",
+        named_params! { ":action_hash": action_hash },
+    )?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mutations::{insert_op_authored, set_receipts_complete};
+    use crate::test_utils::test_authored_db;
+    use ::fixt::prelude::*;
+    use holo_hash::HasHash;
+    use holochain_types::prelude::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn only_actions_with_complete_ops_are_audited() {
+        let db = test_authored_db().to_db();
+        let author = fixt!(AgentPubKey);
+        let action = |author: &AgentPubKey| {
+            let mut create = fixt!(Create);
+            create.author = author.clone();
+            Action::Create(create)
+        };
+        let ops = |action: &Action| {
+            [
+                DhtOpHashed::from_content_sync(ChainOp::RegisterAgentActivity(
+                    fixt!(Signature),
+                    action.clone(),
+                )),
+                DhtOpHashed::from_content_sync(ChainOp::StoreRecord(
+                    fixt!(Signature),
+                    action.clone(),
+                    RecordEntry::NA,
+                )),
+            ]
+        };
+        let complete = action(&author);
+        let incomplete = action(&author);
+        let complete_hash = ActionHash::with_data_sync(&complete);
+
+        let (audited, republished, audited_after) = db
+            .write_async({
+                let author = author.clone();
+                let complete_hash = complete_hash.clone();
+                move |txn| {
+                    for (action, all_complete) in [(complete, true), (incomplete, false)] {
+                        for (i, op) in ops(&action).iter().enumerate() {
+                            insert_op_authored(txn, op)?;
+                            if all_complete || i == 0 {
+                                set_receipts_complete(txn, op.as_hash(), true)?;
+                            }
+                        }
+                    }
+                    let audited = actions_to_audit(txn, &author, 10)?;
+                    let republished = republish_action_ops(txn, &complete_hash)?;
+                    let audited_after = actions_to_audit(txn, &author, 10)?;
+                    StateMutationResult::Ok((audited, republished, audited_after))
+                }
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(vec![complete_hash], audited);
+        assert_eq!(2, republished);
+        assert!(audited_after.is_empty());
+    }
+}
@@ -89,12 +89,22 @@ impl LinksQuery {
         type_query: &LinkTypeFilter,
         tag: Option<String>,
         filter: &GetLinksFilter,
+    ) -> String {
+        Self::filtered_create_query_string("Action.blob AS action_blob", type_query, tag, filter)
+    }
+
+    fn filtered_create_query_string(
+        columns: &str,
+        type_query: &LinkTypeFilter,
+        tag: Option<String>,
+        filter: &GetLinksFilter,
     ) -> String {
         let mut s = format!(
             "
-            SELECT Action.blob AS action_blob FROM DhtOp
+            SELECT {} FROM DhtOp
             {}
             ",
+            columns,
             Self::common_query_string()
         );
         s = Self::add_type_query(s, type_query);
@@ -172,6 +182,35 @@ impl LinksQuery {
         delete_query
     }
 
+    /// The query for the action hashes and timestamps of the links which haven't been
+    /// deleted, which reads neither the link actions nor the actions of the deletes.
+    pub fn timestamps_query(&self) -> String {
+        let create_query = Self::filtered_create_query_string(
+            "Action.hash AS hash, DhtOp.authored_timestamp AS authored_timestamp",
+            &self.type_query,
+            self.tag.clone(),
+            &self.filter,
+        );
+        format!(
+            "
+            {}
+            AND NOT EXISTS (
+                SELECT 1 FROM DhtOp AS DeleteOp
+                JOIN Action AS DeleteAction ON DeleteOp.action_hash = DeleteAction.hash
+                WHERE DeleteOp.type = :delete
+                AND
+                DeleteAction.create_link_hash = Action.hash
+                AND
+                DeleteOp.validation_status = :status
+                AND
+                DeleteOp.when_integrated IS NOT NULL
+            )
+            ORDER BY DhtOp.authored_timestamp
+            ",
+            create_query
+        )
+    }
+
     pub fn params(&self) -> Vec<Params> {
         {
             named_params! {
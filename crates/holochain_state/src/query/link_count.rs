@@ -1,7 +1,12 @@
-use crate::query::link::GetLinksFilter;
+use crate::query::link::{GetLinksFilter, LinksQuery};
+use crate::query::StateQueryResult;
+use holo_hash::ActionHash;
+use holochain_sqlite::rusqlite::Transaction;
 use holochain_types::link::WireLinkQuery;
+use holochain_zome_types::prelude::Timestamp;
 
-// Note that link_count uses `GetLinksQuery`, so there is no query implemented here
+// Note that link_count uses `GetLinksQuery` to count the links held locally, while
+// authorities answer with `LinkTimestampsQuery` so that the links needn't be sent.
 
 impl From<WireLinkQuery> for GetLinksFilter {
     fn from(value: WireLinkQuery) -> Self {
@@ -12,3 +17,35 @@ impl From<WireLinkQuery> for GetLinksFilter {
         }
     }
 }
+
+/// Get the action hashes and timestamps of the links matching a [`WireLinkQuery`]
+/// from a single database, oldest first.
+#[derive(Debug, Clone)]
+pub struct LinkTimestampsQuery {
+    query: LinksQuery,
+}
+
+impl LinkTimestampsQuery {
+    /// Create the query.
+    pub fn new(query: WireLinkQuery) -> Self {
+        Self {
+            query: LinksQuery::new(
+                query.base.clone(),
+                query.link_type.clone(),
+                query.tag_prefix.clone(),
+                query.into(),
+            ),
+        }
+    }
+
+    /// Run the query on a database.
+    pub fn run(&self, txn: &Transaction) -> StateQueryResult<Vec<(ActionHash, Timestamp)>> {
+        let mut stmt = txn.prepare_cached(&self.query.timestamps_query())?;
+        let links = stmt
+            .query_map(&self.query.params()[..], |row| {
+                Ok((row.get("hash")?, row.get("authored_timestamp")?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(links)
+    }
+}
@@ -2,8 +2,10 @@ use super::*;
 use crate::here;
 use crate::prelude::mutations_helpers::insert_valid_integrated_op;
 use crate::prelude::*;
+use crate::query::link_count::LinkTimestampsQuery;
 use holochain_trace;
 use holochain_types::db::DbWrite;
+use holochain_types::link::WireLinkQuery;
 use holochain_types::record::SignedActionHashedExt;
 use std::vec::IntoIter;
 
@@ -1074,3 +1076,48 @@ async fn link_type_ranges() {
     )
     .await;
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn link_timestamps() {
+    let test_db = test_dht_db();
+    let arc = test_db.to_db();
+
+    let td = fixtures(arc.clone(), 1).into_iter().next().unwrap();
+
+    let timestamps = |tag_prefix: LinkTag| {
+        let query = LinkTimestampsQuery::new(WireLinkQuery {
+            base: td.base_hash.clone().into(),
+            link_type: LinkTypeFilter::single_type(td.zome_index, td.link_type),
+            tag_prefix: Some(tag_prefix),
+            before: None,
+            after: None,
+            author: None,
+        });
+        arc.read_async(move |txn| query.run(txn))
+    };
+    let tag_len = td.tag.0.len();
+    let half_tag = if tag_len > 1 { tag_len / 2 } else { tag_len };
+    let half_tag = LinkTag::new(&td.tag.0[..half_tag]);
+    let other_tag = LinkTag::new(
+        td.tag
+            .0
+            .iter()
+            .map(|b| b.wrapping_add(1))
+            .collect::<Vec<_>>(),
+    );
+
+    assert!(timestamps(td.tag.clone()).await.unwrap().is_empty());
+
+    td.add_link().await;
+    let expected = vec![(
+        td.expected_link.create_link_hash.clone(),
+        td.expected_link.timestamp,
+    )];
+    assert_eq!(expected, timestamps(td.tag.clone()).await.unwrap());
+    // The tag is matched by prefix.
+    assert_eq!(expected, timestamps(half_tag).await.unwrap());
+    assert!(timestamps(other_tag).await.unwrap().is_empty());
+
+    td.delete_link().await;
+    assert!(timestamps(td.tag.clone()).await.unwrap().is_empty());
+}
@@ -0,0 +1,144 @@
+//! The remote calls and signals which cells have queued for other agents,
+//! kept in the conductor database so that they are still delivered after
+//! the conductor restarts.
+
+use crate::mutations::StateMutationResult;
+use crate::query::from_blob;
+use crate::query::to_blob;
+use crate::query::StateQueryResult;
+use holochain_sqlite::prelude::DatabaseResult;
+use holochain_sqlite::prelude::DbWrite;
+use holochain_sqlite::rusqlite::named_params;
+use holochain_sqlite::sql::sql_conductor;
+use holochain_types::prelude::*;
+
+/// A queued message and the state of its delivery.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct OutboxRecord {
+    /// The id the message was queued under.
+    pub id: OutboxMessageId,
+    /// The cell which queued the message.
+    pub from: CellId,
+    /// The zome which queued the message, whose `recv_remote_signal` receives signals.
+    pub zome_name: ZomeName,
+    /// The agent the message is sent to.
+    pub agent: AgentPubKey,
+    /// The message.
+    pub message: RemoteMessage,
+    /// The state of the delivery.
+    pub delivery: OutboxDelivery,
+    /// When the message expires if it hasn't been delivered.
+    pub expires_at: Timestamp,
+    /// When the message was delivered, rejected or expired.
+    pub settled_at: Option<Timestamp>,
+    /// Incremented on every change to the record, so that a record which is
+    /// stored late can't overwrite a later change.
+    pub revision: u32,
+}
+
+/// Store the records, replacing older revisions of them.
+pub async fn put_outbox_records(
+    db: &DbWrite<DbKindConductor>,
+    records: Vec<OutboxRecord>,
+) -> StateMutationResult<()> {
+    if records.is_empty() {
+        return Ok(());
+    }
+    db.write_async(move |txn| {
+        for record in records {
+            txn.execute(
+                sql_conductor::UPSERT_OUTBOX_MESSAGE,
+                named_params! {
+                    ":id": record.id.0,
+                    ":blob": to_blob(&record)?,
+                    ":revision": record.revision,
+                    ":settled_at_us": record.settled_at,
+                },
+            )?;
+        }
+        StateMutationResult::Ok(())
+    })
+    .await
+}
+
+/// All the stored records, in the order they were queued.
+pub async fn load_outbox_records(
+    db: &DbWrite<DbKindConductor>,
+) -> StateQueryResult<Vec<OutboxRecord>> {
+    db.read_async(move |txn| {
+        let blobs = txn
+            .prepare(sql_conductor::SELECT_OUTBOX_MESSAGES)?
+            .query_map([], |row| row.get::<_, Vec<u8>>("blob"))?
+            .collect::<Result<Vec<_>, _>>()?;
+        blobs.into_iter().map(from_blob).collect()
+    })
+    .await
+}
+
+/// Delete the records which were settled before a time.
+/// Returns the number of records deleted.
+pub async fn delete_outbox_records_settled_before(
+    db: &DbWrite<DbKindConductor>,
+    before: Timestamp,
+) -> DatabaseResult<usize> {
+    db.write_async(move |txn| {
+        Ok(txn.execute(
+            sql_conductor::DELETE_SETTLED_OUTBOX_MESSAGES,
+            named_params! { ":before_us": before },
+        )?)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::test_conductor_db;
+    use ::fixt::prelude::*;
+
+    fn record(id: u64, revision: u32, settled_at: Option<Timestamp>) -> OutboxRecord {
+        OutboxRecord {
+            id: OutboxMessageId(id),
+            from: fixt!(CellId),
+            zome_name: "zome".into(),
+            agent: fixt!(AgentPubKey),
+            message: RemoteMessage::Signal(ExternIO::encode(()).unwrap()),
+            delivery: OutboxDelivery::Pending {
+                attempts: revision,
+                last_error: None,
+            },
+            expires_at: Timestamp(100),
+            settled_at,
+            revision,
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn outbox_records_are_stored_and_only_replaced_by_later_revisions() {
+        let db = test_conductor_db();
+        let first = record(1, 1, None);
+        let second = record(2, 0, Some(Timestamp(10)));
+        put_outbox_records(&db, vec![first.clone(), second.clone()])
+            .await
+            .unwrap();
+
+        let newer = OutboxRecord {
+            revision: 2,
+            ..first.clone()
+        };
+        put_outbox_records(&db, vec![newer.clone()]).await.unwrap();
+        put_outbox_records(&db, vec![first]).await.unwrap();
+        assert_eq!(
+            vec![newer.clone(), second],
+            load_outbox_records(&db).await.unwrap()
+        );
+
+        assert_eq!(
+            1,
+            delete_outbox_records_settled_before(&db, Timestamp(11))
+                .await
+                .unwrap()
+        );
+        assert_eq!(vec![newer], load_outbox_records(&db).await.unwrap());
+    }
+}
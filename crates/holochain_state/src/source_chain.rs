@@ -23,6 +23,7 @@ use holochain_sqlite::sql::sql_cell::SELECT_VALID_AGENT_PUB_KEY;
 use holochain_sqlite::sql::sql_conductor::SELECT_VALID_CAP_GRANT_FOR_CAP_SECRET;
 use holochain_sqlite::sql::sql_conductor::SELECT_VALID_UNRESTRICTED_CAP_GRANT;
 use holochain_state_types::SourceChainDumpRecord;
+use holochain_types::clock::Clock;
 use holochain_types::sql::AsSql;
 
 use crate::prelude::*;
@@ -45,6 +46,7 @@ pub struct SourceChain<AuthorDb = DbWrite<DbKindAuthored>, DhtDb = DbWrite<DbKin
     head_info: Option<HeadInfo>,
     public_only: bool,
     zomes_initialized: Arc<AtomicBool>,
+    clock: Clock,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -204,7 +206,7 @@ impl SourceChain {
             //       we are willing to accept, beyond which we emit an error
             //       rather than bumping the timestamp
             timestamp: std::cmp::max(
-                Timestamp::now(),
+                self.clock.now(),
                 (chain_head_timestamp + std::time::Duration::from_micros(1))?,
             ),
             action_seq,
@@ -235,6 +237,91 @@ impl SourceChain {
         .await
     }
 
+    /// Put many new records at the end of the source chain, in order, using
+    /// the specified weight for each.
+    ///
+    /// Every action is built against the head left by the one before it, then
+    /// all of them are signed concurrently. The keystore has no request to sign
+    /// several actions at once, so each action is still its own keystore
+    /// request, but the batch waits on the slowest of them rather than on each
+    /// in turn.
+    pub async fn put_weighed_batch<W, U: ActionUnweighed<Weight = W>, B: ActionBuilder<U>>(
+        &self,
+        items: Vec<(B, Option<Entry>, ChainTopOrdering, W)>,
+    ) -> SourceChainResult<Vec<ActionHash>> {
+        let HeadInfo {
+            action: mut prev_action,
+            seq: mut action_seq,
+            mut timestamp,
+        } = self.chain_head_nonempty()?;
+
+        let mut actions = Vec::with_capacity(items.len());
+        let mut entries = Vec::with_capacity(items.len());
+        for (action_builder, maybe_entry, chain_top_ordering, weight) in items {
+            action_seq += 1;
+            // Same timestamp rule as `put_weighed`, applied to the batch's own head.
+            timestamp = std::cmp::max(
+                self.clock.now(),
+                (timestamp + std::time::Duration::from_micros(1))?,
+            );
+            let common = ActionBuilderCommon {
+                author: (*self.author).clone(),
+                timestamp,
+                action_seq,
+                prev_action: prev_action.clone(),
+            };
+            let action = ActionHashed::from_content_sync(
+                action_builder.build(common).weighed(weight).into(),
+            );
+            prev_action = action.as_hash().clone();
+            actions.push(action);
+            entries.push((maybe_entry, chain_top_ordering));
+        }
+
+        let actions = futures::future::try_join_all(
+            actions
+                .into_iter()
+                .map(|action| SignedActionHashed::sign(&self.keystore, action)),
+        )
+        .await?;
+        let hashes = actions.iter().map(|a| a.as_hash().clone()).collect();
+        self.scratch.apply(|scratch| {
+            for (action, (maybe_entry, chain_top_ordering)) in actions.into_iter().zip(entries) {
+                insert_record_scratch(
+                    scratch,
+                    Record::new(action, maybe_entry),
+                    chain_top_ordering,
+                );
+            }
+        })?;
+        Ok(hashes)
+    }
+
+    /// Put many new records at the end of the source chain with a default weight.
+    pub async fn put_weightless_batch<
+        W: Default,
+        U: ActionUnweighed<Weight = W>,
+        B: ActionBuilder<U>,
+    >(
+        &self,
+        items: Vec<(B, Option<Entry>, ChainTopOrdering)>,
+    ) -> SourceChainResult<Vec<ActionHash>> {
+        self.put_weighed_batch(
+            items
+                .into_iter()
+                .map(|(action_builder, maybe_entry, chain_top_ordering)| {
+                    (
+                        action_builder,
+                        maybe_entry,
+                        chain_top_ordering,
+                        Default::default(),
+                    )
+                })
+                .collect(),
+        )
+        .await
+    }
+
     #[async_recursion]
     #[cfg_attr(feature = "instrument", tracing::instrument(skip(self, network)))]
     pub async fn flush(
@@ -440,6 +527,11 @@ impl SourceChain {
             Ok((actions, permit)) => {
                 drop(permit);
 
+                // The cached queries of this cell read its authored data.
+                self.dht_db_cache
+                    .queries()
+                    .invalidate(ops_to_integrate.iter().map(|(_, basis)| basis.clone()));
+
                 authored_ops_to_dht_db(
                     network,
                     ops_to_integrate,
@@ -547,6 +639,7 @@ where
             head_info,
             public_only: false,
             zomes_initialized: Arc::new(AtomicBool::new(false)),
+            clock: Clock::default(),
         })
     }
 
@@ -579,6 +672,7 @@ where
             head_info,
             public_only: false,
             zomes_initialized: Arc::new(AtomicBool::new(false)),
+            clock: Clock::default(),
         })
     }
 
@@ -586,6 +680,18 @@ where
         self.public_only = true;
     }
 
+    /// Take the timestamps of new actions from `clock`, e.g. the clock of the
+    /// conductor which corrects for its skew, rather than the system clock.
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// The clock which the timestamps of new actions are taken from.
+    pub fn clock(&self) -> &Clock {
+        &self.clock
+    }
+
     pub fn keystore(&self) -> &MetaLairClient {
         &self.keystore
     }
@@ -1091,7 +1197,6 @@ async fn rebase_actions_on(
 }
 
 #[allow(clippy::too_many_arguments)]
-#[cfg_attr(feature = "instrument", tracing::instrument(skip_all))]
 pub async fn genesis(
     authored: DbWrite<DbKindAuthored>,
     dht_db: DbWrite<DbKindDht>,
@@ -1101,10 +1206,39 @@ pub async fn genesis(
     agent_pubkey: AgentPubKey,
     membrane_proof: Option<MembraneProof>,
     chc: Option<ChcImpl>,
+) -> SourceChainResult<()> {
+    genesis_with_clock(
+        authored,
+        dht_db,
+        dht_db_cache,
+        keystore,
+        dna_hash,
+        agent_pubkey,
+        membrane_proof,
+        chc,
+        &Clock::default(),
+    )
+    .await
+}
+
+/// [`genesis`], with the timestamps of the genesis actions taken from `clock`, which
+/// should be the clock that the source chain will take later timestamps from.
+#[allow(clippy::too_many_arguments)]
+#[cfg_attr(feature = "instrument", tracing::instrument(skip_all))]
+pub async fn genesis_with_clock(
+    authored: DbWrite<DbKindAuthored>,
+    dht_db: DbWrite<DbKindDht>,
+    dht_db_cache: &DhtDbQueryCache,
+    keystore: MetaLairClient,
+    dna_hash: DnaHash,
+    agent_pubkey: AgentPubKey,
+    membrane_proof: Option<MembraneProof>,
+    chc: Option<ChcImpl>,
+    clock: &Clock,
 ) -> SourceChainResult<()> {
     let dna_action = Action::Dna(Dna {
         author: agent_pubkey.clone(),
-        timestamp: Timestamp::now(),
+        timestamp: clock.now(),
         hash: dna_hash,
     });
     let dna_action = ActionHashed::from_content_sync(dna_action);
@@ -1117,7 +1251,7 @@ pub async fn genesis(
     // create the agent validation entry and add it directly to the store
     let agent_validation_action = Action::AgentValidationPkg(AgentValidationPkg {
         author: agent_pubkey.clone(),
-        timestamp: Timestamp::now(),
+        timestamp: clock.now(),
         action_seq: 1,
         prev_action: dna_action_address,
         membrane_proof,
@@ -1133,7 +1267,7 @@ pub async fn genesis(
     // create a agent chain record and add it directly to the store
     let agent_action = Action::Create(Create {
         author: agent_pubkey.clone(),
-        timestamp: Timestamp::now(),
+        timestamp: clock.now(),
         action_seq: 2,
         prev_action: avh_addr,
         entry_type: EntryType::AgentPubKey,
@@ -1254,6 +1388,47 @@ pub fn chain_head_db_nonempty(
     chain_head_db(txn, author)?.ok_or(SourceChainError::ChainEmpty)
 }
 
+/// Check that the chain head of an author is the last action which was committed to
+/// their chain, and that every sequence number up to the head has exactly one action.
+pub fn verify_chain_head(txn: &Transaction, author: Arc<AgentPubKey>) -> SourceChainResult<()> {
+    let head = match chain_head_db(txn, author.clone())? {
+        Some(head) => head,
+        None => return Ok(()),
+    };
+    let last_committed: ActionHash = txn
+        .query_row(
+            "SELECT hash FROM Action WHERE author = :author ORDER BY rowid DESC LIMIT 1",
+            named_params! { ":author": author },
+            |row| row.get(0),
+        )
+        .map_err(DatabaseError::from)?;
+    if last_committed != head.action {
+        return Err(SourceChainError::InvalidStructure(
+            ChainInvalidReason::HeadNotLastCommitted {
+                head: head.action,
+                last_committed,
+            },
+        ));
+    }
+    let (action_count, distinct_seqs): (u32, u32) = txn
+        .query_row(
+            "SELECT COUNT(*), COUNT(DISTINCT seq) FROM Action WHERE author = :author",
+            named_params! { ":author": author },
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .map_err(DatabaseError::from)?;
+    if action_count != head.seq + 1 || distinct_seqs != head.seq + 1 {
+        return Err(SourceChainError::InvalidStructure(
+            ChainInvalidReason::IncompleteChain {
+                head_seq: head.seq,
+                action_count,
+                distinct_seqs,
+            },
+        ));
+    }
+    Ok(())
+}
+
 pub type CurrentCountersigningSessionOpt = Option<(Record, EntryHash, CounterSigningSessionData)>;
 
 /// Check if there is a current countersigning session and if so, return the
@@ -1423,6 +1598,7 @@ impl From<SourceChain> for SourceChainRead {
             head_info: chain.head_info,
             public_only: chain.public_only,
             zomes_initialized: Arc::new(AtomicBool::new(false)),
+            clock: chain.clock,
         }
     }
 }
@@ -1711,6 +1887,99 @@ mod tests {
         assert_matches!(result, SourceChainError::InvalidAgentKey(invalid_key, cell_id) if invalid_key == *chain.author && cell_id == *chain.cell_id());
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn actions_are_timestamped_by_the_clock_of_the_chain() {
+        let authored_db = test_authored_db().to_db();
+        let dht_db = test_dht_db().to_db();
+        let dht_db_cache = DhtDbQueryCache::new(dht_db.clone().into());
+        let keystore = test_keystore();
+        let agent_key = keystore.new_sign_keypair_random().await.unwrap();
+        let clock = Clock::default();
+        clock.set_correction(-3_600_000_000);
+        let an_hour_ago = (Timestamp::now() - std::time::Duration::from_secs(3600)).unwrap();
+
+        source_chain::genesis_with_clock(
+            authored_db.clone(),
+            dht_db.clone(),
+            &dht_db_cache,
+            keystore.clone(),
+            fake_dna_hash(1),
+            agent_key.clone(),
+            None,
+            None,
+            &clock,
+        )
+        .await
+        .unwrap();
+        let chain = SourceChain::new(authored_db, dht_db, dht_db_cache, keystore, agent_key)
+            .await
+            .unwrap()
+            .with_clock(clock);
+        let genesis_timestamp = chain.chain_head_nonempty().unwrap().timestamp;
+        chain.delete_valid_agent_pub_key().await.unwrap();
+        let timestamp = *chain.scratch_records().unwrap()[0].action().timestamp();
+
+        // - Both the genesis actions and the later ones are an hour behind the system clock.
+        let a_minute_later = (an_hour_ago + std::time::Duration::from_secs(60)).unwrap();
+        assert!(an_hour_ago <= genesis_timestamp && genesis_timestamp < a_minute_later);
+        assert!(genesis_timestamp < timestamp && timestamp < a_minute_later);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn chain_head_is_verified() {
+        let authored_db = test_authored_db().to_db();
+        let dht_db = test_dht_db().to_db();
+        let dht_db_cache = DhtDbQueryCache::new(dht_db.clone().into());
+        let keystore = test_keystore();
+        let agent_key = keystore.new_sign_keypair_random().await.unwrap();
+
+        source_chain::genesis(
+            authored_db.clone(),
+            dht_db.clone(),
+            &dht_db_cache,
+            keystore.clone(),
+            fake_dna_hash(1),
+            agent_key.clone(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+
+        let author = Arc::new(agent_key);
+        authored_db
+            .read_async({
+                let author = author.clone();
+                move |txn| verify_chain_head(txn, author)
+            })
+            .await
+            .unwrap();
+
+        // Lose the agent key action from the middle of the chain.
+        let result = authored_db
+            .write_async(move |txn| {
+                txn.execute(
+                    "DELETE FROM DhtOp WHERE action_hash IN (SELECT hash FROM Action WHERE seq = 1)",
+                    [],
+                )
+                .map_err(DatabaseError::from)?;
+                txn.execute("DELETE FROM Action WHERE seq = 1", [])
+                    .map_err(DatabaseError::from)?;
+                verify_chain_head(txn, author)
+            })
+            .await;
+        assert_matches!(
+            result,
+            Err(SourceChainError::InvalidStructure(
+                ChainInvalidReason::IncompleteChain {
+                    head_seq: 2,
+                    action_count: 2,
+                    distinct_seqs: 2,
+                }
+            ))
+        );
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_get_cap_grant() -> SourceChainResult<()> {
         let test_db = test_authored_db();
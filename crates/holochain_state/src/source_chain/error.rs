@@ -172,6 +172,19 @@ pub enum ChainInvalidReason {
 
     #[error("Content was expected to definitely exist at this address, but didn't: {0}")]
     MissingData(EntryHash),
+
+    #[error("The chain head {head} isn't the last action which was committed, {last_committed}")]
+    HeadNotLastCommitted {
+        head: ActionHash,
+        last_committed: ActionHash,
+    },
+
+    #[error("The chain head is at seq {head_seq}, but the chain has {action_count} actions at {distinct_seqs} sequence numbers")]
+    IncompleteChain {
+        head_seq: u32,
+        action_count: u32,
+        distinct_seqs: u32,
+    },
 }
 
 /// The reason that a commit could not be completed.
@@ -0,0 +1,118 @@
+//! Summaries of the validation outcomes of the ops which a node holds, by author,
+//! so that agents which author many invalid ops stand out.
+
+use crate::query::StateQueryResult;
+use holochain_sqlite::rusqlite::named_params;
+use holochain_sqlite::rusqlite::Transaction;
+use holochain_state_types::AuthorValidationOutcomes;
+use holochain_types::prelude::*;
+
+/// Count the validation outcomes of the ops in a DHT database by the author of their
+/// action, with the authors of the most rejected ops first.
+///
+/// Warrants are left out, as they aren't validated like the ops of a source chain.
+pub fn validation_outcomes_by_author(
+    txn: &Transaction,
+) -> StateQueryResult<Vec<AuthorValidationOutcomes>> {
+    let outcomes = txn
+        .prepare_cached(
+            "
+            SELECT
+            Action.author,
+            COUNT(CASE WHEN DhtOp.validation_status = :valid THEN 1 END) AS valid,
+            COUNT(CASE WHEN DhtOp.validation_status = :rejected THEN 1 END) AS rejected,
+            COUNT(CASE WHEN DhtOp.validation_status = :abandoned THEN 1 END) AS abandoned,
+            COUNT(CASE WHEN DhtOp.validation_status IS NULL THEN 1 END) AS awaiting
+            FROM DhtOp
+            JOIN Action ON DhtOp.action_hash = Action.hash
+            GROUP BY Action.author
+            ORDER BY rejected DESC, Action.author
+            ",
+        )?
+        .query_map(
+            named_params! {
+                ":valid": ValidationStatus::Valid,
+                ":rejected": ValidationStatus::Rejected,
+                ":abandoned": ValidationStatus::Abandoned,
+            },
+            |row| {
+                Ok(AuthorValidationOutcomes {
+                    author: row.get("author")?,
+                    valid: row.get("valid")?,
+                    rejected: row.get("rejected")?,
+                    abandoned: row.get("abandoned")?,
+                    awaiting: row.get("awaiting")?,
+                })
+            },
+        )?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(outcomes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mutations::insert_op_dht;
+    use crate::mutations::set_validation_status;
+    use crate::mutations::StateMutationResult;
+    use crate::test_utils::test_dht_db;
+    use ::fixt::prelude::*;
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn outcomes_are_counted_by_author() {
+        let db = test_dht_db().to_db();
+        let alice = AgentPubKey::from_raw_32(vec![1; 32]);
+        let bob = AgentPubKey::from_raw_32(vec![2; 32]);
+
+        let op = |author: &AgentPubKey| {
+            let mut create = fixt!(Create);
+            create.author = author.clone();
+            DhtOpHashed::from_content_sync(ChainOp::RegisterAgentActivity(
+                fixt!(Signature),
+                create.into(),
+            ))
+        };
+        let ops = [
+            (op(&alice), Some(ValidationStatus::Valid)),
+            (op(&alice), None),
+            (op(&bob), Some(ValidationStatus::Valid)),
+            (op(&bob), Some(ValidationStatus::Rejected)),
+            (op(&bob), Some(ValidationStatus::Abandoned)),
+        ];
+        db.write_async(move |txn| {
+            for (op, status) in &ops {
+                insert_op_dht(txn, op, None)?;
+                if let Some(status) = status {
+                    set_validation_status(txn, op.as_hash(), *status)?;
+                }
+            }
+            StateMutationResult::Ok(())
+        })
+        .await
+        .unwrap();
+
+        let outcomes = db
+            .read_async(|txn| validation_outcomes_by_author(txn))
+            .await
+            .unwrap();
+        assert_eq!(
+            vec![
+                AuthorValidationOutcomes {
+                    author: bob,
+                    valid: 1,
+                    rejected: 1,
+                    abandoned: 1,
+                    awaiting: 0,
+                },
+                AuthorValidationOutcomes {
+                    author: alice,
+                    valid: 1,
+                    rejected: 0,
+                    abandoned: 0,
+                    awaiting: 1,
+                },
+            ],
+            outcomes
+        );
+    }
+}
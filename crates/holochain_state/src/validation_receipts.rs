@@ -7,7 +7,9 @@ use holochain_sqlite::rusqlite::OptionalExtension;
 use holochain_sqlite::rusqlite::Transaction;
 use holochain_sqlite::rusqlite::{named_params, Params, Statement};
 use holochain_types::dht_op::DhtOpType;
-use holochain_types::prelude::{SignedValidationReceipt, ValidationReceipt};
+use holochain_types::prelude::{
+    ChainOpType, SignedAction, SignedValidationReceipt, Timestamp, ValidationReceipt,
+};
 use holochain_zome_types::prelude::{ValidationReceiptInfo, ValidationReceiptSet};
 use mutations::StateMutationResult;
 use std::collections::HashMap;
@@ -96,6 +98,73 @@ pub fn get_pending_validation_receipts(
     Ok(ops)
 }
 
+/// An op authored by an agent which is still being published to get validation receipts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OpAwaitingReceipts {
+    /// The hash of the op.
+    pub op_hash: DhtOpHash,
+    /// The type of the op.
+    pub op_type: ChainOpType,
+    /// The action the op was produced from.
+    pub action: SignedAction,
+    /// When the op was last published, if it has been.
+    pub last_publish_time: Option<Timestamp>,
+}
+
+/// Find the ops authored by `author` which haven't received enough validation receipts
+/// yet, oldest first. Ops which aren't published, like those of private entries or
+/// those withheld for countersigning, are left out.
+pub fn ops_awaiting_receipts(
+    txn: &Transaction,
+    author: &AgentPubKey,
+) -> StateQueryResult<Vec<OpAwaitingReceipts>> {
+    let mut stmt = txn.prepare(
+        "
+        SELECT
+          DhtOp.hash as op_hash,
+          DhtOp.type as op_type,
+          DhtOp.last_publish_time as last_publish_time,
+          Action.blob as action_blob
+        FROM
+          DhtOp
+          JOIN Action ON DhtOp.action_hash = Action.hash
+        WHERE
+          Action.author = :author
+          AND (DhtOp.type != :store_entry OR Action.private_entry = 0)
+          AND DhtOp.withhold_publish IS NULL
+          AND DhtOp.receipts_complete IS NULL
+        ORDER BY
+          DhtOp.authored_timestamp ASC
+        ",
+    )?;
+    let ops = stmt
+        .query_and_then(
+            named_params! {
+                ":author": author,
+                ":store_entry": ChainOpType::StoreEntry,
+            },
+            |row| {
+                let op_hash: DhtOpHash = row.get("op_hash")?;
+                let op_type: DhtOpType = row.get("op_type")?;
+                // Stored in seconds since the UNIX epoch.
+                let last_publish_time: Option<i64> = row.get("last_publish_time")?;
+                let action = from_blob::<SignedAction>(row.get("action_blob")?)?;
+                StateQueryResult::Ok(match op_type {
+                    DhtOpType::Chain(op_type) => Some(OpAwaitingReceipts {
+                        op_hash,
+                        op_type,
+                        action,
+                        last_publish_time: last_publish_time
+                            .map(|secs| Timestamp::from_micros(secs.saturating_mul(1_000_000))),
+                    }),
+                    _ => None,
+                })
+            },
+        )?
+        .collect::<StateQueryResult<Vec<_>>>()?;
+    Ok(ops.into_iter().flatten().collect())
+}
+
 /// Finds [DhtOp]s for the given [ActionHash] and returns the associated [ValidationReceiptSet]s.
 ///
 /// Each [ValidationReceiptSet] contains the validation receipts we have received for a single [DhtOp].
@@ -456,6 +525,60 @@ mod tests {
         check_receipt_sets(receipt_sets, test_op_hash, vr1, vr2);
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn authored_ops_awaiting_receipts() {
+        let env = crate::test_utils::test_authored_db().to_db();
+
+        let author = fixt!(AgentPubKey);
+        let authored_op = |author: &AgentPubKey| {
+            let mut action = fixt!(Dna);
+            action.author = author.clone();
+            DhtOpHashed::from_content_sync(ChainOp::RegisterAgentActivity(
+                fixt!(Signature),
+                action.into(),
+            ))
+        };
+        let awaiting = authored_op(&author);
+        let published = authored_op(&author);
+        let complete = authored_op(&author);
+        let other_author = authored_op(&fixt!(AgentPubKey));
+
+        let awaiting_hash = awaiting.as_hash().clone();
+        let published_hash = published.as_hash().clone();
+        env.write_async({
+            let published_hash = published_hash.clone();
+            let complete_hash = complete.as_hash().clone();
+            move |txn| -> StateMutationResult<()> {
+                for op in [&awaiting, &published, &complete, &other_author] {
+                    insert_op_authored(txn, op)?;
+                }
+                set_last_publish_time(txn, &published_hash, std::time::Duration::from_secs(10))?;
+                set_receipts_complete(txn, &complete_hash, true)
+            }
+        })
+        .await
+        .unwrap();
+
+        let ops = env
+            .read_async(move |txn| ops_awaiting_receipts(txn, &author))
+            .await
+            .unwrap();
+        let mut hashes: Vec<_> = ops.iter().map(|op| op.op_hash.clone()).collect();
+        hashes.sort();
+        let mut expected = vec![awaiting_hash.clone(), published_hash.clone()];
+        expected.sort();
+        assert_eq!(expected, hashes);
+
+        let published = ops.iter().find(|op| op.op_hash == published_hash).unwrap();
+        assert_eq!(ChainOpType::RegisterAgentActivity, published.op_type);
+        assert_eq!(
+            Some(Timestamp::from_micros(10_000_000)),
+            published.last_publish_time
+        );
+        let awaiting = ops.iter().find(|op| op.op_hash == awaiting_hash).unwrap();
+        assert_eq!(None, awaiting.last_publish_time);
+    }
+
     fn check_receipt_sets(
         receipt_sets: Vec<ValidationReceiptSet>,
         test_op_hash: DhtOpHash,
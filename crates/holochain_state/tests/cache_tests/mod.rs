@@ -411,3 +411,33 @@ async fn check_none_integrated_with_awaiting_deps() {
     let to_integrate = cache.get_activity_to_integrate().await.unwrap();
     assert!(to_integrate.is_empty());
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn chain_subsets_are_invalidated_by_new_activity() {
+    let mut u = Unstructured::new(&NOISE);
+    let author = AgentPubKey::arbitrary(&mut u).unwrap();
+    let db = test_in_mem_db(DbKindDht(Arc::new(DnaHash::from_raw_32(vec![0; 32]))));
+    let cache = DhtDbQueryCache::new(db.clone().into());
+    let filter = ChainFilter::new(ActionHash::arbitrary(&mut u).unwrap());
+    let activity = MustGetAgentActivityResponse::Activity {
+        activity: vec![RegisterAgentActivity::arbitrary(&mut u).unwrap()],
+        warrants: vec![],
+    };
+
+    // Incomplete chains may be completed later, so they aren't cached.
+    cache.put_chain_subset(
+        author.clone(),
+        filter.clone(),
+        &MustGetAgentActivityResponse::IncompleteChain,
+    );
+    assert_eq!(None, cache.get_chain_subset(&author, &filter));
+
+    cache.put_chain_subset(author.clone(), filter.clone(), &activity);
+    assert_eq!(Some(activity), cache.get_chain_subset(&author, &filter));
+
+    cache
+        .set_activity_ready_to_integrate(&author, Some(0))
+        .await
+        .unwrap();
+    assert_eq!(None, cache.get_chain_subset(&author, &filter));
+}
@@ -1,4 +1,4 @@
-use holo_hash::ActionHash;
+use holo_hash::{ActionHash, AgentPubKey};
 use holochain_integrity_types::{Action, Entry, Signature};
 use serde::{Deserialize, Serialize};
 
@@ -18,6 +18,19 @@ pub struct SourceChainDumpRecord {
     pub entry: Option<Entry>,
 }
 
+/// How many of the ops of an author which a node holds were found valid or invalid,
+/// and how many are still being validated.
+#[derive(Serialize, Debug, Clone, Deserialize, PartialEq, Eq)]
+pub struct AuthorValidationOutcomes {
+    pub author: AgentPubKey,
+    pub valid: usize,
+    pub rejected: usize,
+    /// Ops whose validation was given up on, e.g. because their dependencies couldn't be fetched.
+    pub abandoned: usize,
+    /// Ops which haven't been validated yet.
+    pub awaiting: usize,
+}
+
 pub mod prelude {
     pub use crate::*;
 }
@@ -82,11 +82,13 @@ use tracing_subscriber::{
     },
     layer::SubscriberExt,
     registry::LookupSpan,
+    reload,
     util::SubscriberInitExt,
     Layer, Registry,
 };
 
 use derive_more::Display;
+use once_cell::sync::OnceCell;
 use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 
@@ -96,6 +98,7 @@ use fmt::*;
 mod flames;
 mod fmt;
 pub mod metrics;
+pub mod otlp;
 mod writer;
 
 mod open;
@@ -190,9 +193,11 @@ pub fn test_run_timed_flame() -> Result<Option<Box<impl Drop>>, errors::TracingE
     let buffer = Arc::new(Mutex::new(Vec::new()));
     let writer_handle = InMemoryWriter::new(buffer.clone());
 
-    init_fmt_with_opts(Output::FlameTimed, move || {
-        InMemoryWriter::new(buffer.clone())
-    })?;
+    init_fmt_with_opts(
+        Output::FlameTimed,
+        move || InMemoryWriter::new(buffer.clone()),
+        None,
+    )?;
     Ok(Some(Box::new(FlameTimed::new(writer_handle))))
 }
 
@@ -205,9 +210,11 @@ pub fn test_run_timed_ice() -> Result<Option<Box<impl Drop>>, errors::TracingErr
     let buffer = Arc::new(Mutex::new(Vec::new()));
     let writer_handle = InMemoryWriter::new(buffer.clone());
 
-    init_fmt_with_opts(Output::IceTimed, move || {
-        InMemoryWriter::new(buffer.clone())
-    })?;
+    init_fmt_with_opts(
+        Output::IceTimed,
+        move || InMemoryWriter::new(buffer.clone()),
+        None,
+    )?;
     Ok(Some(Box::new(FlameTimed::new(writer_handle))))
 }
 
@@ -254,17 +261,59 @@ where
     Ok(standard_layer_unfiltered(writer)?.with_filter(filter))
 }
 
+type ReloadFilter = Box<dyn Fn(EnvFilter) -> Result<(), reload::Error> + Send + Sync>;
+
+/// Swaps the filter of the logging output set up by [init_fmt].
+static RELOAD_FILTER: OnceCell<ReloadFilter> = OnceCell::new();
+
+/// Wrap the filter for the logging output so that it can be replaced
+/// later with [set_filter].
+fn reloadable_filter<S>(filter: EnvFilter) -> reload::Layer<EnvFilter, S>
+where
+    S: Subscriber + 'static,
+{
+    let (filter, handle) = reload::Layer::new(filter);
+    let _ = RELOAD_FILTER.set(Box::new(move |f| handle.reload(f)));
+    filter
+}
+
+/// Replace the filter of the logging output set up by [init_fmt], e.g. to
+/// change log levels without restarting.
+/// The directives are in the same format as `RUST_LOG`.
+pub fn set_filter(directives: &str) -> Result<(), errors::TracingError> {
+    let filter = EnvFilter::try_new(directives)?;
+    let reload = RELOAD_FILTER
+        .get()
+        .ok_or(errors::TracingError::FilterNotReloadable)?;
+    reload(filter).map_err(errors::TracingError::from)
+}
+
 /// This checks RUST_LOG for a filter but doesn't complain if there is none or it doesn't parse.
 /// It then checks for CUSTOM_FILTER which if set will output an error if it doesn't parse.
 pub fn init_fmt(output: Output) -> Result<(), errors::TracingError> {
-    init_fmt_with_opts(output, std::io::stderr)
+    init_fmt_with_opts(output, std::io::stderr, None)
+}
+
+/// Same as [init_fmt], and also export spans to an OpenTelemetry collector
+/// if an [otlp::OtlpConfig] is given.
+pub fn init_fmt_with_otlp(
+    output: Output,
+    otlp: Option<otlp::OtlpConfig>,
+) -> Result<(), errors::TracingError> {
+    init_fmt_with_opts(output, std::io::stderr, otlp)
 }
 
-fn init_fmt_with_opts<W>(output: Output, writer: W) -> Result<(), errors::TracingError>
+fn init_fmt_with_opts<W>(
+    output: Output,
+    writer: W,
+    otlp: Option<otlp::OtlpConfig>,
+) -> Result<(), errors::TracingError>
 where
     W: for<'writer> MakeWriter<'writer> + Send + Sync + 'static,
 {
     let filter = standard_filter()?;
+    let exports_spans = otlp.is_some();
+    let registry = Registry::default().with(otlp.map(|c| c.layer()).transpose()?);
 
     println!(
         "Initialising log output formatting with option {:?}",
@@ -272,66 +321,72 @@ where
     );
 
     match output {
-        Output::Json => Registry::default()
+        Output::Json => registry
             .with(
                 standard_layer_unfiltered(writer)?
                     .with_timer(UtcTime::rfc_3339())
                     .json()
                     .event_format(FormatEvent)
-                    .with_filter(filter),
+                    .with_filter(reloadable_filter(filter)),
             )
             .init(),
 
-        Output::JsonTimed => Registry::default()
+        Output::JsonTimed => registry
             .with(
                 standard_layer_unfiltered(writer)?
                     .with_span_events(FmtSpan::CLOSE)
                     .with_timer(UtcTime::rfc_3339())
                     .json()
                     .event_format(FormatEvent)
-                    .with_filter(filter),
+                    .with_filter(reloadable_filter(filter)),
             )
             .init(),
 
-        Output::Log => Registry::default().with(standard_layer(writer)?).init(),
+        Output::Log => registry
+            .with(standard_layer_unfiltered(writer)?.with_filter(reloadable_filter(filter)))
+            .init(),
 
-        Output::LogTimed => Registry::default()
+        Output::LogTimed => registry
             .with(
                 standard_layer_unfiltered(writer)?
                     .with_span_events(FmtSpan::FULL)
-                    .with_filter(filter),
+                    .with_filter(reloadable_filter(filter)),
             )
             .init(),
 
-        Output::FlameTimed => Registry::default()
+        Output::FlameTimed => registry
             .with(
                 standard_layer_unfiltered(writer)?
                     .with_span_events(FmtSpan::CLOSE)
                     .with_timer(UtcTime::rfc_3339())
                     .event_format(FormatEventFlame)
-                    .with_filter(filter),
+                    .with_filter(reloadable_filter(filter)),
             )
             .init(),
 
-        Output::IceTimed => Registry::default()
+        Output::IceTimed => registry
             .with(
                 standard_layer_unfiltered(writer)?
                     .with_span_events(FmtSpan::CLOSE)
                     .with_timer(UtcTime::rfc_3339())
                     .event_format(FormatEventIce)
-                    .with_filter(filter),
+                    .with_filter(reloadable_filter(filter)),
             )
             .init(),
 
-        Output::Compact => Registry::default()
+        Output::Compact => registry
             .with(
                 standard_layer_unfiltered(writer)?
                     .compact()
-                    .with_filter(filter),
+                    .with_filter(reloadable_filter(filter)),
             )
             .init(),
 
-        Output::None => (),
+        Output::None => {
+            if exports_spans {
+                registry.init()
+            }
+        }
     };
     Ok(())
 }
@@ -351,5 +406,11 @@ pub mod errors {
         TracingFlame,
         #[error(transparent)]
         BadDirective(#[from] tracing_subscriber::filter::ParseError),
+        #[error("Invalid otlp endpoint, expected an http or https URL: {0}")]
+        InvalidOtlpEndpoint(String),
+        #[error("The log filter can't be changed, as logging was not set up with init_fmt")]
+        FilterNotReloadable,
+        #[error(transparent)]
+        Reload(#[from] tracing_subscriber::reload::Error),
     }
 }
@@ -0,0 +1,663 @@
+//! Export spans to an OpenTelemetry collector using OTLP over HTTP.
+//!
+//! The [`OtlpLayer`] turns every span which passes its filter into an
+//! OpenTelemetry span, and sends them in batches to the `/v1/traces`
+//! endpoint of the collector, JSON encoded.
+//!
+//! Spans which have a `dna_hash`, `agent` or `app_id` field, and all the
+//! spans inside them, are tagged with those values. Spans are grouped into
+//! one OpenTelemetry resource per DNA, with the DNA hash as the
+//! `holochain.dna_hash` resource attribute, so that the activity of each
+//! DNA can be told apart in the collector.
+
+use std::sync::mpsc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde_json::{json, Value};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::filter::{EnvFilter, Filtered};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+use crate::errors::TracingError;
+
+/// The spans which are sent to the collector in one request, at most.
+const MAX_BATCH_SIZE: usize = 512;
+
+/// How long finished spans are held before they are sent.
+const BATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// The finished spans which can be waiting to be sent, at most.
+/// Any more are dropped.
+const MAX_QUEUED_SPANS: usize = 8192;
+
+/// The events which are recorded on one span, at most.
+const MAX_EVENTS_PER_SPAN: usize = 128;
+
+/// The filter used when no filter is configured.
+const DEFAULT_FILTER: &str = "info";
+
+/// Where and how to export spans.
+#[derive(Debug, Clone)]
+pub struct OtlpConfig {
+    /// The base URL of the collector, e.g. `http://localhost:4318`, using
+    /// either `http` or `https`.
+    /// If it has no path, `/v1/traces` is used.
+    pub endpoint: String,
+    /// The `service.name` to report the spans under.
+    pub service_name: String,
+    /// Which spans to export, in the same format as `RUST_LOG`.
+    /// Defaults to `info`.
+    pub filter: Option<String>,
+}
+
+impl OtlpConfig {
+    /// Build a layer which exports spans according to this config.
+    pub fn layer<S>(&self) -> Result<Filtered<OtlpLayer, EnvFilter, S>, TracingError>
+    where
+        S: Subscriber + for<'span> LookupSpan<'span>,
+    {
+        let filter = EnvFilter::try_new(self.filter.as_deref().unwrap_or(DEFAULT_FILTER))?;
+        let exporter = HttpExporter::new(&self.endpoint)?;
+        Ok(OtlpLayer::spawn(self.service_name.clone(), exporter).with_filter(filter))
+    }
+}
+
+/// A [`Layer`] which exports spans to an OpenTelemetry collector.
+pub struct OtlpLayer {
+    tx: mpsc::SyncSender<FinishedSpan>,
+}
+
+impl OtlpLayer {
+    fn spawn(service_name: String, exporter: impl Export) -> Self {
+        let (tx, rx) = mpsc::sync_channel(MAX_QUEUED_SPANS);
+        std::thread::Builder::new()
+            .name("otlp-export".to_string())
+            .spawn(move || export_loop(rx, service_name, exporter))
+            .expect("Failed to spawn otlp export thread");
+        Self { tx }
+    }
+}
+
+/// The holochain context a span is running in, which is passed down to the
+/// spans inside it.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct HcContext {
+    dna_hash: Option<String>,
+    agent: Option<String>,
+    app_id: Option<String>,
+}
+
+impl HcContext {
+    fn set(&mut self, name: &str, value: &AttributeValue) {
+        let field = match name {
+            "dna_hash" => &mut self.dna_hash,
+            "agent" => &mut self.agent,
+            "app_id" => &mut self.app_id,
+            _ => return,
+        };
+        *field = Some(value.to_string());
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum AttributeValue {
+    String(String),
+    Bool(bool),
+    Int(i64),
+    Double(f64),
+}
+
+impl std::fmt::Display for AttributeValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::String(s) => f.write_str(s),
+            Self::Bool(b) => b.fmt(f),
+            Self::Int(i) => i.fmt(f),
+            Self::Double(d) => d.fmt(f),
+        }
+    }
+}
+
+impl AttributeValue {
+    fn to_json(&self) -> Value {
+        match self {
+            // 64 bit integers are encoded as strings in OTLP JSON
+            Self::Int(i) => json!({ "intValue": i.to_string() }),
+            Self::String(s) => json!({ "stringValue": s }),
+            Self::Bool(b) => json!({ "boolValue": b }),
+            Self::Double(d) => json!({ "doubleValue": d }),
+        }
+    }
+}
+
+#[derive(Default)]
+struct FieldVisitor(Vec<(&'static str, AttributeValue)>);
+
+impl Visit for FieldVisitor {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.0.push((field.name(), AttributeValue::Double(value)));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.0.push((field.name(), AttributeValue::Int(value)));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        let value = match i64::try_from(value) {
+            Ok(i) => AttributeValue::Int(i),
+            Err(_) => AttributeValue::String(value.to_string()),
+        };
+        self.0.push((field.name(), value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.0.push((field.name(), AttributeValue::Bool(value)));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0
+            .push((field.name(), AttributeValue::String(value.to_string())));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn std::fmt::Debug) {
+        self.0
+            .push((field.name(), AttributeValue::String(format!("{:?}", value))));
+    }
+}
+
+struct SpanEvent {
+    name: String,
+    time: SystemTime,
+    attributes: Vec<(&'static str, AttributeValue)>,
+}
+
+/// The state of an open span, kept in the span's extensions.
+struct SpanData {
+    trace_id: [u8; 16],
+    span_id: [u8; 8],
+    parent_span_id: Option<[u8; 8]>,
+    start: SystemTime,
+    attributes: Vec<(&'static str, AttributeValue)>,
+    context: HcContext,
+    events: Vec<SpanEvent>,
+    links: Vec<([u8; 16], [u8; 8])>,
+    is_error: bool,
+    /// When the span was last exited. A span can stay open after its work
+    /// is done while other spans refer to it, so this is used as its end.
+    last_exit: Option<SystemTime>,
+}
+
+impl SpanData {
+    fn record(&mut self, fields: FieldVisitor) {
+        for (name, value) in fields.0 {
+            self.context.set(name, &value);
+            self.attributes.retain(|(n, _)| *n != name);
+            self.attributes.push((name, value));
+        }
+    }
+}
+
+/// A closed span, ready to be exported.
+struct FinishedSpan {
+    name: &'static str,
+    target: &'static str,
+    end: SystemTime,
+    data: SpanData,
+}
+
+impl<S> Layer<S> for OtlpLayer
+where
+    S: Subscriber + for<'span> LookupSpan<'span>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let parent = span.parent().and_then(|parent| {
+            parent
+                .extensions()
+                .get::<SpanData>()
+                .map(|p| (p.trace_id, p.span_id, p.context.clone()))
+        });
+        let (trace_id, parent_span_id, context) = match parent {
+            Some((trace_id, span_id, context)) => (trace_id, Some(span_id), context),
+            None => (rand::random(), None, HcContext::default()),
+        };
+
+        let mut data = SpanData {
+            trace_id,
+            span_id: rand::random(),
+            parent_span_id,
+            start: SystemTime::now(),
+            attributes: Vec::new(),
+            context,
+            events: Vec::new(),
+            links: Vec::new(),
+            is_error: false,
+            last_exit: None,
+        };
+        let mut fields = FieldVisitor::default();
+        attrs.record(&mut fields);
+        data.record(fields);
+        span.extensions_mut().insert(data);
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut fields = FieldVisitor::default();
+        values.record(&mut fields);
+        let mut extensions = span.extensions_mut();
+        if let Some(data) = extensions.get_mut::<SpanData>() {
+            data.record(fields);
+        }
+    }
+
+    fn on_follows_from(&self, id: &Id, follows: &Id, ctx: Context<'_, S>) {
+        let (Some(span), Some(follows)) = (ctx.span(id), ctx.span(follows)) else {
+            return;
+        };
+        let link = follows
+            .extensions()
+            .get::<SpanData>()
+            .map(|f| (f.trace_id, f.span_id));
+        let mut extensions = span.extensions_mut();
+        if let (Some(link), Some(data)) = (link, extensions.get_mut::<SpanData>()) {
+            data.links.push(link);
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.event_span(event) else {
+            return;
+        };
+        let mut extensions = span.extensions_mut();
+        let Some(data) = extensions.get_mut::<SpanData>() else {
+            return;
+        };
+        if *event.metadata().level() == Level::ERROR {
+            data.is_error = true;
+        }
+        if data.events.len() >= MAX_EVENTS_PER_SPAN {
+            return;
+        }
+
+        let mut fields = FieldVisitor::default();
+        event.record(&mut fields);
+        let name = match fields.0.iter().position(|(n, _)| *n == "message") {
+            Some(i) => fields.0.remove(i).1.to_string(),
+            None => event.metadata().name().to_string(),
+        };
+        fields.0.push((
+            "level",
+            AttributeValue::String(event.metadata().level().to_string()),
+        ));
+        data.events.push(SpanEvent {
+            name,
+            time: SystemTime::now(),
+            attributes: fields.0,
+        });
+    }
+
+    fn on_exit(&self, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut extensions = span.extensions_mut();
+        if let Some(data) = extensions.get_mut::<SpanData>() {
+            data.last_exit = Some(SystemTime::now());
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(&id) else { return };
+        let Some(data) = span.extensions_mut().remove::<SpanData>() else {
+            return;
+        };
+        // If the exporter can't keep up, drop the span rather than blocking.
+        let _ = self.tx.try_send(FinishedSpan {
+            name: span.metadata().name(),
+            target: span.metadata().target(),
+            end: data.last_exit.unwrap_or_else(SystemTime::now),
+            data,
+        });
+    }
+}
+
+/// Somewhere to send encoded batches of spans.
+trait Export: 'static + Send {
+    fn export(&mut self, body: Vec<u8>) -> std::io::Result<()>;
+}
+
+fn export_loop(rx: mpsc::Receiver<FinishedSpan>, service_name: String, mut exporter: impl Export) {
+    let mut batch = Vec::new();
+    let mut last_export = Instant::now();
+    loop {
+        let closed = match rx.recv_timeout(BATCH_INTERVAL) {
+            Ok(span) => {
+                batch.push(span);
+                false
+            }
+            Err(mpsc::RecvTimeoutError::Timeout) => false,
+            Err(mpsc::RecvTimeoutError::Disconnected) => true,
+        };
+
+        let due = batch.len() >= MAX_BATCH_SIZE || last_export.elapsed() >= BATCH_INTERVAL;
+        if !batch.is_empty() && (due || closed) {
+            let body = encode_spans(&service_name, &batch).to_string().into_bytes();
+            if let Err(e) = exporter.export(body) {
+                tracing::warn!(?e, "Failed to export spans to the otlp collector");
+            }
+            batch.clear();
+            last_export = Instant::now();
+        }
+        if closed {
+            break;
+        }
+    }
+}
+
+/// Encode spans as an OTLP `ExportTraceServiceRequest`.
+fn encode_spans(service_name: &str, spans: &[FinishedSpan]) -> Value {
+    let mut by_dna: Vec<(Option<&str>, Vec<Value>)> = Vec::new();
+    for span in spans {
+        let dna_hash = span.data.context.dna_hash.as_deref();
+        let encoded = encode_span(span);
+        match by_dna.iter_mut().find(|(d, _)| *d == dna_hash) {
+            Some((_, group)) => group.push(encoded),
+            None => by_dna.push((dna_hash, vec![encoded])),
+        }
+    }
+
+    let resource_spans: Vec<Value> = by_dna
+        .into_iter()
+        .map(|(dna_hash, spans)| {
+            let mut resource = vec![attribute(
+                "service.name",
+                &AttributeValue::String(service_name.to_string()),
+            )];
+            if let Some(dna_hash) = dna_hash {
+                resource.push(attribute(
+                    "holochain.dna_hash",
+                    &AttributeValue::String(dna_hash.to_string()),
+                ));
+            }
+            json!({
+                "resource": { "attributes": resource },
+                "scopeSpans": [{
+                    "scope": { "name": "holochain_trace" },
+                    "spans": spans,
+                }],
+            })
+        })
+        .collect();
+
+    json!({ "resourceSpans": resource_spans })
+}
+
+fn encode_span(span: &FinishedSpan) -> Value {
+    let data = &span.data;
+    let mut attributes = vec![attribute(
+        "code.namespace",
+        &AttributeValue::String(span.target.to_string()),
+    )];
+    for (key, value) in [
+        ("agent", &data.context.agent),
+        ("app_id", &data.context.app_id),
+    ] {
+        if let Some(value) = value {
+            attributes.push(attribute(
+                &format!("holochain.{}", key),
+                &AttributeValue::String(value.clone()),
+            ));
+        }
+    }
+    attributes.extend(data.attributes.iter().map(|(k, v)| attribute(k, v)));
+
+    let events: Vec<Value> = data
+        .events
+        .iter()
+        .map(|event| {
+            json!({
+                "name": event.name,
+                "timeUnixNano": unix_nanos(event.time),
+                "attributes": event.attributes.iter().map(|(k, v)| attribute(k, v)).collect::<Vec<_>>(),
+            })
+        })
+        .collect();
+
+    let links: Vec<Value> = data
+        .links
+        .iter()
+        .map(|(trace_id, span_id)| json!({ "traceId": hex(trace_id), "spanId": hex(span_id) }))
+        .collect();
+
+    json!({
+        "traceId": hex(&data.trace_id),
+        "spanId": hex(&data.span_id),
+        "parentSpanId": data.parent_span_id.map(|p| hex(&p)).unwrap_or_default(),
+        "name": span.name,
+        // SPAN_KIND_INTERNAL
+        "kind": 1,
+        "startTimeUnixNano": unix_nanos(data.start),
+        "endTimeUnixNano": unix_nanos(span.end),
+        "attributes": attributes,
+        "events": events,
+        "links": links,
+        // STATUS_CODE_ERROR if an error was logged in the span, otherwise STATUS_CODE_UNSET
+        "status": { "code": if data.is_error { 2 } else { 0 } },
+    })
+}
+
+fn attribute(key: &str, value: &AttributeValue) -> Value {
+    json!({ "key": key, "value": value.to_json() })
+}
+
+fn unix_nanos(time: SystemTime) -> String {
+    time.duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_nanos()
+        .to_string()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    use std::fmt::Write;
+    bytes.iter().fold(String::new(), |mut out, b| {
+        let _ = write!(out, "{:02x}", b);
+        out
+    })
+}
+
+/// Posts batches of spans to a collector's OTLP/HTTP endpoint.
+struct HttpExporter {
+    url: reqwest::Url,
+    /// Built by the export thread on the first export, since a blocking
+    /// client can't be created or dropped inside an async runtime.
+    client: Option<reqwest::blocking::Client>,
+}
+
+impl HttpExporter {
+    fn new(endpoint: &str) -> Result<Self, TracingError> {
+        let invalid = || TracingError::InvalidOtlpEndpoint(endpoint.to_string());
+        let mut url = reqwest::Url::parse(endpoint).map_err(|_| invalid())?;
+        if !matches!(url.scheme(), "http" | "https") || url.host_str().is_none() {
+            return Err(invalid());
+        }
+        let path = match url.path().trim_end_matches('/') {
+            "" => "/v1/traces".to_string(),
+            path => path.to_string(),
+        };
+        url.set_path(&path);
+        Ok(Self { url, client: None })
+    }
+}
+
+impl Export for HttpExporter {
+    fn export(&mut self, body: Vec<u8>) -> std::io::Result<()> {
+        let client = match &mut self.client {
+            Some(client) => client,
+            client => client.insert(
+                reqwest::blocking::Client::builder()
+                    .connect_timeout(Duration::from_secs(5))
+                    .timeout(Duration::from_secs(10))
+                    .build()
+                    .map_err(std::io::Error::other)?,
+            ),
+        };
+        client
+            .post(self.url.clone())
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .body(body)
+            .send()
+            .and_then(reqwest::blocking::Response::error_for_status)
+            .map_err(std::io::Error::other)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    #[derive(Clone, Default)]
+    struct TestExporter(Arc<Mutex<Vec<Value>>>);
+
+    impl Export for TestExporter {
+        fn export(&mut self, body: Vec<u8>) -> std::io::Result<()> {
+            self.0
+                .lock()
+                .unwrap()
+                .push(serde_json::from_slice(&body).unwrap());
+            Ok(())
+        }
+    }
+
+    fn spans_by_name(requests: &[Value]) -> Vec<(Value, Value)> {
+        let mut spans = Vec::new();
+        for request in requests {
+            for resource in request["resourceSpans"].as_array().unwrap() {
+                for span in resource["scopeSpans"][0]["spans"].as_array().unwrap() {
+                    spans.push((resource["resource"].clone(), span.clone()));
+                }
+            }
+        }
+        spans
+    }
+
+    fn attribute_value<'a>(attributes: &'a Value, key: &str) -> Option<&'a Value> {
+        attributes
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|a| a["key"] == key)
+            .map(|a| &a["value"])
+    }
+
+    #[test]
+    fn exports_spans_with_holochain_context() {
+        let exporter = TestExporter::default();
+        let layer = OtlpLayer::spawn("test".to_string(), exporter.clone());
+        let subscriber = tracing_subscriber::registry().with(layer);
+
+        tracing::subscriber::with_default(subscriber, || {
+            let app = tracing::info_span!("app_request", app_id = "my_app");
+            let _app = app.enter();
+            let call = tracing::info_span!("call_zome", dna_hash = "uhC0k", agent = "uhCAk");
+            let _call = call.enter();
+            tracing::error!(zome = "foo", "zome call failed");
+
+            let workflow = tracing::info_span!(parent: None, "workflow", workflow = "publish");
+            workflow.follows_from(&call);
+        });
+
+        // wait for the exporter to flush the batch
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while exporter.0.lock().unwrap().is_empty() {
+            assert!(Instant::now() < deadline, "spans were not exported");
+            std::thread::sleep(Duration::from_millis(50));
+        }
+        let spans = spans_by_name(&exporter.0.lock().unwrap());
+        let find = |name: &str| spans.iter().find(|(_, s)| s["name"] == name).unwrap();
+        let (app_resource, app) = find("app_request");
+        let (call_resource, call) = find("call_zome");
+        let (_, workflow) = find("workflow");
+
+        assert_eq!(call["traceId"], app["traceId"]);
+        assert_eq!(call["parentSpanId"], app["spanId"]);
+        assert_eq!(app["parentSpanId"], "");
+        assert_ne!(workflow["traceId"], call["traceId"]);
+        assert_eq!(workflow["links"][0]["spanId"], call["spanId"]);
+
+        assert_eq!(
+            attribute_value(&call_resource["attributes"], "holochain.dna_hash").unwrap()
+                ["stringValue"],
+            "uhC0k"
+        );
+        assert!(attribute_value(&app_resource["attributes"], "holochain.dna_hash").is_none());
+        assert_eq!(
+            attribute_value(&call["attributes"], "holochain.app_id").unwrap()["stringValue"],
+            "my_app"
+        );
+        assert_eq!(call["status"]["code"], 2);
+        assert_eq!(call["events"][0]["name"], "zome call failed");
+        assert_eq!(app["status"]["code"], 0);
+    }
+
+    #[test]
+    fn parses_endpoints() {
+        let url = |endpoint: &str| HttpExporter::new(endpoint).unwrap().url.to_string();
+        assert_eq!(
+            "http://localhost:4318/v1/traces",
+            url("http://localhost:4318")
+        );
+        assert_eq!(
+            "https://collector/custom/traces",
+            url("https://collector/custom/traces/")
+        );
+        assert_eq!("http://[::1]:4318/v1/traces", url("http://[::1]:4318"));
+        assert!(HttpExporter::new("ftp://localhost:4318").is_err());
+        assert!(HttpExporter::new("localhost:4318").is_err());
+        assert!(HttpExporter::new("http://:4318").is_err());
+    }
+
+    #[test]
+    fn posts_spans_to_the_collector() {
+        use std::io::{BufRead, BufReader, Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let endpoint = format!("http://{}", listener.local_addr().unwrap());
+        let collector = std::thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request_line = String::new();
+            reader.read_line(&mut request_line).unwrap();
+            let mut content_length = 0;
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+                if let Some((name, value)) = header.split_once(':') {
+                    if name.eq_ignore_ascii_case("content-length") {
+                        content_length = value.trim().parse().unwrap();
+                    }
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            reader
+                .get_mut()
+                .write_all(b"HTTP/1.1 200 OK\r\ncontent-length: 0\r\n\r\n")
+                .unwrap();
+            (request_line, body)
+        });
+
+        let mut exporter = HttpExporter::new(&endpoint).unwrap();
+        exporter.export(b"{}".to_vec()).unwrap();
+        let (request_line, body) = collector.join().unwrap();
+        assert_eq!("POST /v1/traces HTTP/1.1", request_line.trim());
+        assert_eq!(b"{}".to_vec(), body);
+    }
+}
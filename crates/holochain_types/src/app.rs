@@ -23,6 +23,7 @@ use holochain_util::ffs;
 use holochain_zome_types::cell::CloneId;
 use holochain_zome_types::prelude::*;
 use indexmap::IndexMap;
+pub use kitsune_p2p_types::config::NetworkProfile;
 use std::{collections::HashMap, path::PathBuf};
 
 /// The unique identifier for an installed app in this conductor
@@ -196,6 +197,22 @@ pub struct InstallAppPayload {
     /// Use only in situations where you know that this is a throwaway key!
     #[serde(default)]
     pub allow_throwaway_random_agent_key: bool,
+
+    /// Optional: restricts the network infrastructure which the DNAs of this app may use,
+    /// e.g. to specific bootstrap and signal servers or to the local network, so that
+    /// the traffic of a privacy-sensitive app never touches public infrastructure.
+    ///
+    /// The profile applies to every app which uses the same DNAs, and cells of an app
+    /// whose profile doesn't allow the network config of the conductor don't join the network.
+    #[serde(default)]
+    pub network_profile: Option<NetworkProfile>,
+
+    /// Optional: install the cells of this app as pure clients of the network, e.g. on
+    /// mobile devices. Their storage arc is held at zero, they aren't authorities for
+    /// any data, the data they fetch is cached for longer, and they don't send
+    /// validation receipts for the ops they hold.
+    #[serde(default)]
+    pub client_only: bool,
 }
 
 /// Alias
@@ -463,6 +480,15 @@ pub struct InstalledAppCommon {
 
     /// The timestamp when this app was installed
     pub installed_at: Timestamp,
+
+    /// The network profile which the app was installed with.
+    #[serde(default)]
+    pub network_profile: Option<NetworkProfile>,
+
+    /// Whether the cells of the app are pure clients of the network.
+    /// See [`InstallAppPayload::client_only`].
+    #[serde(default)]
+    pub client_only: bool,
 }
 
 impl InstalledAppCommon {
@@ -488,6 +514,8 @@ impl InstalledAppCommon {
             role_assignments,
             manifest,
             installed_at,
+            network_profile: None,
+            client_only: false,
         })
     }
 
@@ -889,6 +917,8 @@ impl InstalledAppCommon {
             role_assignments,
             manifest,
             installed_at: Timestamp::now(),
+            network_profile: None,
+            client_only: false,
         })
     }
 
@@ -45,6 +45,33 @@ impl AppBundle {
         self.0
     }
 
+    /// Check the publisher's signature on this bundle.
+    ///
+    /// Returns the key of the publisher if the bundle is signed, or `None` if
+    /// it isn't. A signature which doesn't match the contents of the bundle,
+    /// e.g. because the bundle was altered after signing, is an error.
+    pub async fn verify_signature(&self) -> AppBundleResult<Option<AgentPubKey>> {
+        let Some(signature) = self.signature() else {
+            return Ok(None);
+        };
+        let signer = AgentPubKey::try_from_raw_39(signature.signer.clone())
+            .map_err(|e| AppBundleError::InvalidSignature(e.to_string()))?;
+        let signature = Signature(signature.signature.as_slice().try_into().map_err(|_| {
+            AppBundleError::InvalidSignature("the signature has the wrong length".to_string())
+        })?);
+        let valid = signer
+            .verify_signature_raw(&signature, self.signing_bytes()?.into())
+            .await
+            .map_err(|e| AppBundleError::InvalidSignature(e.to_string()))?;
+        if valid {
+            Ok(Some(signer))
+        } else {
+            Err(AppBundleError::InvalidSignature(format!(
+                "the signature by {signer} does not match the contents of the bundle"
+            )))
+        }
+    }
+
     /// Look up every installed_hash of every role, getting the DnaFiles from the DnaStore
     pub fn get_all_dnas_from_store(&self, dna_store: &impl DnaStore) -> HashMap<DnaHash, DnaFile> {
         self.manifest()
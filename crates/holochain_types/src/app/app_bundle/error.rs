@@ -20,6 +20,12 @@ pub enum AppBundleError {
 
     #[error(transparent)]
     FfsIoError(#[from] ffs::IoError),
+
+    #[error("The app bundle signature is invalid: {0}")]
+    InvalidSignature(String),
+
+    #[error("The app bundle was rejected by the conductor's signing policy: {0}")]
+    UntrustedPublisher(String),
 }
 
 pub type AppBundleResult<T> = Result<T, AppBundleError>;
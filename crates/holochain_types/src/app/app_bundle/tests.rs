@@ -71,3 +71,72 @@ async fn provisioning_1_create() {
     };
     assert_eq!(resolution, expected);
 }
+
+/// Test that a signed bundle verifies, and that tampering with it is detected
+#[tokio::test]
+async fn bundle_signature() {
+    let (bundle, _) = app_bundle_fixture(DnaModifiersOpt::none()).await;
+    assert_eq!(bundle.verify_signature().await.unwrap(), None);
+
+    let keystore = holochain_keystore::spawn_test_keystore().await.unwrap();
+    let publisher = AgentPubKey::new_random(&keystore).await.unwrap();
+    let signature = publisher
+        .sign_raw(&keystore, bundle.signing_bytes().unwrap().into())
+        .await
+        .unwrap();
+    let signed = AppBundle::from(
+        bundle
+            .into_inner()
+            .with_signature(mr_bundle::BundleSignature {
+                signer: publisher.get_raw_39().to_vec(),
+                signature: signature.0.to_vec(),
+            }),
+    );
+    assert_eq!(signed.verify_signature().await.unwrap(), Some(publisher));
+
+    let decoded = AppBundle::decode(&signed.encode().unwrap()).unwrap();
+    let signature = decoded.signature().unwrap().clone();
+    let mut manifest = decoded.manifest().clone();
+    manifest.set_network_seed("tampered".to_string());
+    let tampered = AppBundle::from(
+        decoded
+            .into_inner()
+            .update_manifest(manifest)
+            .unwrap()
+            .with_signature(signature),
+    );
+    matches::assert_matches!(
+        tampered.verify_signature().await,
+        Err(AppBundleError::InvalidSignature(_))
+    );
+}
+
+/// A signature doesn't cover DNAs which aren't in the bundle, so a signed bundle
+/// which locates a DNA by path is rejected
+#[tokio::test]
+async fn bundle_signature_does_not_cover_unbundled_dnas() {
+    let manifest = app_manifest_fixture(
+        Some(DnaLocation::Path(PathBuf::from("/dnas/unbundled.dna"))),
+        DnaHash::from_raw_32(vec![1; 32]),
+        DnaModifiersOpt::none(),
+    )
+    .await;
+    let keystore = holochain_keystore::spawn_test_keystore().await.unwrap();
+    let publisher = AgentPubKey::new_random(&keystore).await.unwrap();
+    let signed = AppBundle::from(
+        mr_bundle::Bundle::new_unchecked(manifest.into(), vec![])
+            .unwrap()
+            .with_signature(mr_bundle::BundleSignature {
+                signer: publisher.get_raw_39().to_vec(),
+                signature: vec![0; 64],
+            }),
+    );
+    matches::assert_matches!(
+        signed.verify_signature().await,
+        Err(AppBundleError::MrBundleError(
+            mr_bundle::error::MrBundleError::BundleError(
+                mr_bundle::error::BundleError::UnbundledResourceInSignedBundle(_)
+            )
+        ))
+    );
+}
@@ -114,6 +114,14 @@ impl AppManifest {
         }
     }
 
+    /// Whether the content of blocked agents is filtered out of the results
+    /// of gets and link queries made by this app
+    pub fn filter_blocked_agents(&self) -> bool {
+        match self {
+            Self::V1(manifest) => manifest.filter_blocked_agents,
+        }
+    }
+
     /// Derive a manifest from a list of InstalledCells, filling in some values
     /// with defaults.
     pub fn from_legacy(cells: impl Iterator<Item = InstalledCell>) -> Self {
@@ -138,6 +146,7 @@ impl AppManifest {
             description: Some("Generated by `fn new_legacy`".into()),
             roles,
             allow_deferred_memproofs: false,
+            filter_blocked_agents: false,
         }
         .into()
     }
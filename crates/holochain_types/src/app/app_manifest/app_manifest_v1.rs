@@ -47,6 +47,13 @@ pub struct AppManifestV1 {
     #[serde(default)]
     #[builder(default)]
     pub allow_deferred_memproofs: bool,
+
+    /// Declares that the content of agents which this conductor has blocked
+    /// is left out of the results of gets and link queries made by the app's zomes,
+    /// so that zomes don't each have to check their results against the block list.
+    #[serde(default)]
+    #[builder(default)]
+    pub filter_blocked_agents: bool,
 }
 
 /// Description of an app "role" defined by this app.
@@ -201,6 +208,7 @@ impl AppManifestV1 {
             roles,
             description: _,
             allow_deferred_memproofs: _,
+            filter_blocked_agents: _,
         } = self;
         let roles = roles
             .into_iter()
@@ -300,6 +308,7 @@ pub mod tests {
             description: Some("Serialization roundtrip test".to_string()),
             roles,
             allow_deferred_memproofs: false,
+            filter_blocked_agents: false,
         }
     }
 
@@ -0,0 +1,21 @@
+//! The conductor's audit log of administrative actions.
+
+use holochain_zome_types::prelude::Timestamp;
+
+/// An admin interface request which changed the state of the conductor.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AuditLogEntry {
+    /// The position of the entry in the log, which only ever grows.
+    pub id: u64,
+    /// When the request was handled.
+    pub timestamp: Timestamp,
+    /// The connection which made the request, as the address of the client
+    /// and the origin it connected from.
+    pub connection: String,
+    /// The type of the request, e.g. `install_app`.
+    pub action: String,
+    /// What the request acted on, e.g. the id of the app which was installed.
+    pub target: Option<String>,
+    /// The error which the request failed with, if it failed.
+    pub error: Option<String>,
+}
@@ -0,0 +1,96 @@
+//! Progress of a cell which joined an existing DHT in getting the ops of its storage arc.
+//!
+//! The number of ops in the arc isn't known until they have been gossiped about, so the
+//! total is estimated as the ops which the conductor holds or knows that it needs to fetch.
+//! The estimate grows as gossip finds more ops, and backfill is only complete once gossip
+//! has completed a round without leaving anything to fetch or integrate.
+
+use holochain_zome_types::cell::CellId;
+
+/// How far a cell is with getting the ops of its storage arc.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct BackfillProgress {
+    /// The cell.
+    pub cell_id: CellId,
+    /// The ops in the storage arc of the cell's agent which have been integrated.
+    pub integrated_ops: u64,
+    /// The ops in the storage arc which have been received but not integrated yet.
+    pub pending_ops: u64,
+    /// The ops which the conductor is fetching for the DNA, for all of its local agents.
+    pub ops_to_fetch: u64,
+    /// The estimated number of ops in the storage arc.
+    pub estimated_total_ops: u64,
+    /// The gossip rounds which were completed for the DNA since the cell was started.
+    pub gossip_rounds: u32,
+    /// How many ops were integrated per minute recently.
+    pub ops_per_minute: u64,
+    /// The estimated number of seconds until the remaining ops are integrated,
+    /// if ops are being integrated.
+    pub eta_secs: Option<u64>,
+    /// Whether gossip has completed a round and there is nothing left to fetch or integrate.
+    pub complete: bool,
+}
+
+impl BackfillProgress {
+    /// Estimate the progress from the op counts, the gossip rounds since the cell was
+    /// started, and the recent integration rate.
+    pub fn new(
+        cell_id: CellId,
+        integrated_ops: u64,
+        pending_ops: u64,
+        ops_to_fetch: u64,
+        gossip_rounds: u32,
+        ops_per_minute: u64,
+    ) -> Self {
+        let remaining = pending_ops + ops_to_fetch;
+        let eta_secs = if remaining == 0 {
+            Some(0)
+        } else if ops_per_minute > 0 {
+            Some(remaining.saturating_mul(60).div_ceil(ops_per_minute))
+        } else {
+            None
+        };
+        Self {
+            cell_id,
+            integrated_ops,
+            pending_ops,
+            ops_to_fetch,
+            estimated_total_ops: integrated_ops + remaining,
+            gossip_rounds,
+            ops_per_minute,
+            eta_secs,
+            complete: remaining == 0 && gossip_rounds > 0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use holo_hash::{AgentPubKey, DnaHash};
+
+    #[test]
+    fn backfill_is_complete_after_gossip_with_nothing_left() {
+        let cell_id = CellId::new(
+            DnaHash::from_raw_32(vec![1; 32]),
+            AgentPubKey::from_raw_32(vec![2; 32]),
+        );
+
+        // Nothing is known to be missing before gossip has run.
+        let progress = BackfillProgress::new(cell_id.clone(), 0, 0, 0, 0, 0);
+        assert!(!progress.complete);
+
+        let progress = BackfillProgress::new(cell_id.clone(), 100, 20, 100, 1, 60);
+        assert_eq!(220, progress.estimated_total_ops);
+        assert_eq!(Some(120), progress.eta_secs);
+        assert!(!progress.complete);
+
+        // Without a rate there is no ETA.
+        let progress = BackfillProgress::new(cell_id.clone(), 100, 20, 100, 1, 0);
+        assert_eq!(None, progress.eta_secs);
+
+        let progress = BackfillProgress::new(cell_id, 220, 0, 0, 2, 60);
+        assert_eq!(Some(0), progress.eta_secs);
+        assert!(progress.complete);
+    }
+}
@@ -0,0 +1,237 @@
+//! The clock which the timestamps of new actions are taken from: the system clock,
+//! with an optional correction for how far it is estimated to be from the clocks of
+//! peers.
+//!
+//! Skewed clocks break the validation of timestamps, e.g. an action which is
+//! timestamped before the action it follows, so a conductor can estimate its skew
+//! with a [`ClockSkewEstimator`] and correct its [`Clock`] for it.
+
+use holo_hash::AgentPubKey;
+use holochain_zome_types::prelude::Timestamp;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// The system clock plus a correction, which is zero unless it's set.
+///
+/// Each conductor has its own clock, which it hands to the source chains of its
+/// cells. Clones share the correction, so setting it on one sets it on all of them.
+#[derive(Clone, Debug, Default)]
+pub struct Clock {
+    correction_micros: Arc<AtomicI64>,
+}
+
+impl Clock {
+    /// The system time, with the correction added.
+    pub fn now(&self) -> Timestamp {
+        Timestamp::from_micros(
+            Timestamp::now()
+                .as_micros()
+                .saturating_add(self.correction()),
+        )
+    }
+
+    /// Add this many microseconds to the system time from now on, which may be negative.
+    pub fn set_correction(&self, micros: i64) {
+        self.correction_micros.store(micros, Ordering::Relaxed);
+    }
+
+    /// The microseconds which are added to the system time.
+    pub fn correction(&self) -> i64 {
+        self.correction_micros.load(Ordering::Relaxed)
+    }
+}
+
+/// Skews which are within this many microseconds of the median are never outliers,
+/// as the latency of the network alone spreads the skews of peers that much.
+const MIN_SPREAD_MICROS: i64 = 1_000_000;
+
+/// Skews which are further from the median than this many times the median absolute
+/// deviation of the skews are outliers.
+const OUTLIER_SPREADS: i64 = 3;
+
+/// Estimates how far the system clock is from the clocks of peers, from timestamps
+/// which peers signed just before sending them, such as the agent infos which they
+/// publish when they come online or refresh them.
+///
+/// Such a timestamp is at most the latency of the network behind the clock of its
+/// signer when it's received, but it may be much older if it was passed on by other
+/// peers. So the freshest timestamp of each peer within `window` is taken as its
+/// skew. The skews which are far from the median of them all, by their median
+/// absolute deviation, are dropped as outliers, and the estimate is the median of
+/// the rest, capped at `max_skew` either way. That way peers with wrong clocks can't
+/// move the estimate unless they are the majority, and even then only so far.
+pub struct ClockSkewEstimator {
+    window: Duration,
+    min_peers: usize,
+    max_skew_micros: i64,
+    peers: HashMap<AgentPubKey, PeerSkew>,
+}
+
+/// The freshest timestamp which a peer signed, relative to the system clock.
+struct PeerSkew {
+    micros: i64,
+    observed_at: Instant,
+}
+
+impl ClockSkewEstimator {
+    /// Estimate the skew from the timestamps of the last `window`, once there are
+    /// timestamps from at least `min_peers` peers which aren't outliers. The estimate
+    /// is at most `max_skew` either way.
+    pub fn new(window: Duration, min_peers: usize, max_skew: Duration) -> Self {
+        Self {
+            window,
+            min_peers: min_peers.max(1),
+            max_skew_micros: i64::try_from(max_skew.as_micros()).unwrap_or(i64::MAX),
+            peers: HashMap::new(),
+        }
+    }
+
+    /// Take into account a timestamp which `peer` signed, which was received now.
+    pub fn observe(&mut self, peer: AgentPubKey, signed_at: Timestamp) {
+        let micros = signed_at
+            .as_micros()
+            .saturating_sub(Timestamp::now().as_micros());
+        let now = Instant::now();
+        let window = self.window;
+        self.peers
+            .entry(peer)
+            .and_modify(|skew| {
+                if micros > skew.micros || now.duration_since(skew.observed_at) > window {
+                    *skew = PeerSkew {
+                        micros,
+                        observed_at: now,
+                    };
+                }
+            })
+            .or_insert(PeerSkew {
+                micros,
+                observed_at: now,
+            });
+    }
+
+    /// How many peers the estimate is made from.
+    pub fn peers(&mut self) -> usize {
+        self.expire();
+        self.peers.len()
+    }
+
+    /// How many microseconds the clocks of peers are estimated to be ahead of the
+    /// system clock, or behind it if negative. `None` until enough peers are known.
+    pub fn estimate(&mut self) -> Option<i64> {
+        self.expire();
+        if self.peers.len() < self.min_peers {
+            return None;
+        }
+        let mut skews: Vec<i64> = self.peers.values().map(|skew| skew.micros).collect();
+        skews.sort_unstable();
+        let middle = median(&skews);
+
+        let mut deviations: Vec<i64> = skews
+            .iter()
+            .map(|skew| skew.saturating_sub(middle).saturating_abs())
+            .collect();
+        deviations.sort_unstable();
+        let max_deviation = median(&deviations)
+            .max(MIN_SPREAD_MICROS)
+            .saturating_mul(OUTLIER_SPREADS);
+        skews.retain(|skew| skew.saturating_sub(middle).saturating_abs() <= max_deviation);
+        if skews.len() < self.min_peers {
+            return None;
+        }
+
+        Some(median(&skews).clamp(-self.max_skew_micros, self.max_skew_micros))
+    }
+
+    /// Forget the peers which signed nothing within the window.
+    fn expire(&mut self) {
+        let window = self.window;
+        self.peers
+            .retain(|_, skew| skew.observed_at.elapsed() <= window);
+    }
+}
+
+/// The median of sorted values, of which there is at least one.
+fn median(sorted: &[i64]) -> i64 {
+    let mid = sorted.len() / 2;
+    if sorted.len() % 2 == 0 {
+        sorted[mid - 1] / 2 + sorted[mid] / 2
+    } else {
+        sorted[mid]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn peer(i: u8) -> AgentPubKey {
+        AgentPubKey::from_raw_36(vec![i; 36])
+    }
+
+    fn in_secs(secs: i64) -> Timestamp {
+        Timestamp::from_micros(Timestamp::now().as_micros() + secs * 1_000_000)
+    }
+
+    #[test]
+    fn skew_is_the_median_of_the_freshest_timestamps() {
+        let mut estimator =
+            ClockSkewEstimator::new(Duration::from_secs(60), 3, Duration::from_secs(600));
+        estimator.observe(peer(1), in_secs(60));
+        estimator.observe(peer(2), in_secs(58));
+        // - Not enough peers yet.
+        assert_eq!(None, estimator.estimate());
+
+        // - An old timestamp which was passed on doesn't replace a fresher one.
+        estimator.observe(peer(1), in_secs(-600));
+        estimator.observe(peer(3), in_secs(-600));
+        estimator.observe(peer(3), in_secs(61));
+        // - A peer with a wrong clock doesn't move the estimate.
+        estimator.observe(peer(4), in_secs(-3600));
+        estimator.observe(peer(5), in_secs(59));
+
+        let skew = estimator.estimate().unwrap();
+        assert!((58_000_000..=60_000_000).contains(&skew), "{}", skew);
+        assert_eq!(5, estimator.peers());
+    }
+
+    #[test]
+    fn outliers_do_not_move_the_estimate() {
+        let mut estimator =
+            ClockSkewEstimator::new(Duration::from_secs(60), 3, Duration::from_secs(600));
+        estimator.observe(peer(1), in_secs(10));
+        estimator.observe(peer(2), in_secs(11));
+        estimator.observe(peer(3), in_secs(12));
+        // - Both are on the same side, so they would move the median to 12 seconds.
+        estimator.observe(peer(4), in_secs(200));
+        estimator.observe(peer(5), in_secs(300));
+
+        let skew = estimator.estimate().unwrap();
+        assert!((10_900_000..=11_000_000).contains(&skew), "{}", skew);
+    }
+
+    #[test]
+    fn estimate_is_capped() {
+        let mut estimator =
+            ClockSkewEstimator::new(Duration::from_secs(60), 3, Duration::from_secs(600));
+        for i in 0..3 {
+            estimator.observe(peer(i), in_secs(-3600));
+        }
+
+        assert_eq!(Some(-600_000_000), estimator.estimate());
+    }
+
+    #[test]
+    fn corrections_are_added_to_the_system_time() {
+        let clock = Clock::default();
+        let other_clock = Clock::default();
+        clock.clone().set_correction(-3_600_000_000);
+        let corrected = clock.now();
+        let skew = Timestamp::now().as_micros() - corrected.as_micros();
+        assert!((3_600_000_000..3_601_000_000).contains(&skew), "{}", skew);
+
+        // - Each clock has its own correction.
+        assert_eq!(0, other_clock.correction());
+    }
+}
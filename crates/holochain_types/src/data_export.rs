@@ -0,0 +1,244 @@
+//! Exports of the data which an agent authored in a cell, in a portable JSON form
+//! which doesn't need Holochain to be read, e.g. for data portability requests.
+//!
+//! Unlike a raw export of the source chain, hashes are written as base64 strings and
+//! app entries are decoded from the MessagePack which the HDK serializes them with.
+
+use crate::prelude::*;
+use holo_hash::{ActionHashB64, AgentPubKeyB64, AnyLinkableHashB64, DnaHashB64, EntryHashB64};
+use std::collections::HashSet;
+
+/// The data which an agent authored in a cell.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct AuthoredDataExport {
+    /// The DNA of the cell.
+    pub dna_hash: DnaHashB64,
+    /// The agent whose data this is.
+    pub agent: AgentPubKeyB64,
+    /// When the data was exported.
+    pub exported_at: Timestamp,
+    /// Every action of the agent's source chain, in order.
+    pub actions: Vec<ExportedAction>,
+    /// The app entries which the agent created or updated.
+    ///
+    /// Capability grants and claims are left out, as they hold secrets.
+    pub entries: Vec<ExportedEntry>,
+    /// The links which the agent created.
+    pub links: Vec<ExportedLink>,
+}
+
+/// An action of the agent's source chain.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ExportedAction {
+    /// The hash of the action.
+    pub hash: ActionHashB64,
+    /// The position of the action in the source chain.
+    pub seq: u32,
+    /// When the action was authored.
+    pub timestamp: Timestamp,
+    /// The type of the action, e.g. `Create`.
+    pub action_type: String,
+    /// The entry which the action created or updated.
+    pub entry_hash: Option<EntryHashB64>,
+    /// The action which this action updates or deletes, for updates, deletes and deleted links.
+    pub original_action: Option<ActionHashB64>,
+}
+
+/// An app entry which the agent created or updated.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ExportedEntry {
+    /// The hash of the entry.
+    pub hash: EntryHashB64,
+    /// The action which created or updated the entry.
+    pub action_hash: ActionHashB64,
+    /// The zome and entry type which the entry was defined with.
+    pub entry_def: AppEntryDef,
+    /// The decoded content of the entry, unless it isn't valid MessagePack or has
+    /// map keys which aren't strings.
+    pub content: Option<serde_json::Value>,
+    /// The serialized content of the entry, if it couldn't be decoded.
+    pub bytes: Option<Vec<u8>>,
+}
+
+/// A link which the agent created.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ExportedLink {
+    /// The hash of the action which created the link.
+    pub create_link_hash: ActionHashB64,
+    /// The base of the link.
+    pub base: AnyLinkableHashB64,
+    /// The target of the link.
+    pub target: AnyLinkableHashB64,
+    /// The zome which defined the link type.
+    pub zome_index: ZomeIndex,
+    /// The type of the link within its zome.
+    pub link_type: LinkType,
+    /// The tag of the link.
+    pub tag: Vec<u8>,
+    /// When the link was created.
+    pub timestamp: Timestamp,
+    /// Whether the agent deleted the link again.
+    pub deleted: bool,
+}
+
+impl AuthoredDataExport {
+    /// Export the records of an agent's source chain, which are in chain order.
+    pub fn new(
+        cell_id: &CellId,
+        exported_at: Timestamp,
+        records: impl IntoIterator<Item = Record>,
+    ) -> Self {
+        let mut actions = Vec::new();
+        let mut entries = Vec::new();
+        let mut links = Vec::new();
+        let mut deleted_links = HashSet::new();
+        for record in records {
+            let action_hash = record.action_address().clone();
+            let action = record.action();
+            let original_action = match action {
+                Action::Update(update) => Some(update.original_action_address.clone()),
+                Action::Delete(delete) => Some(delete.deletes_address.clone()),
+                Action::DeleteLink(delete_link) => {
+                    deleted_links.insert(delete_link.link_add_address.clone());
+                    Some(delete_link.link_add_address.clone())
+                }
+                _ => None,
+            };
+            actions.push(ExportedAction {
+                hash: action_hash.clone().into(),
+                seq: action.action_seq(),
+                timestamp: action.timestamp(),
+                action_type: action.action_type().to_string(),
+                entry_hash: action.entry_hash().cloned().map(Into::into),
+                original_action: original_action.map(Into::into),
+            });
+            if let Action::CreateLink(create_link) = action {
+                links.push(ExportedLink {
+                    create_link_hash: action_hash.clone().into(),
+                    base: create_link.base_address.clone().into(),
+                    target: create_link.target_address.clone().into(),
+                    zome_index: create_link.zome_index,
+                    link_type: create_link.link_type,
+                    tag: create_link.tag.0.clone(),
+                    timestamp: create_link.timestamp,
+                    deleted: false,
+                });
+            }
+            let entry_def = match action.entry_type() {
+                Some(EntryType::App(entry_def)) => entry_def.clone(),
+                _ => continue,
+            };
+            let (Some(entry_hash), Some(entry)) = (action.entry_hash(), record.entry().as_option())
+            else {
+                continue;
+            };
+            let bytes = match entry {
+                Entry::App(bytes) | Entry::CounterSign(_, bytes) => bytes.bytes(),
+                _ => continue,
+            };
+            let content: Option<serde_json::Value> = holochain_serialized_bytes::decode(bytes).ok();
+            entries.push(ExportedEntry {
+                hash: entry_hash.clone().into(),
+                action_hash: action_hash.into(),
+                entry_def,
+                bytes: content.is_none().then(|| bytes.to_vec()),
+                content,
+            });
+        }
+        for link in &mut links {
+            link.deleted = deleted_links.contains(&ActionHash::from(link.create_link_hash.clone()));
+        }
+        Self {
+            dna_hash: cell_id.dna_hash().clone().into(),
+            agent: cell_id.agent_pubkey().clone().into(),
+            exported_at,
+            actions,
+            entries,
+            links,
+        }
+    }
+
+    /// Encode the export as pretty printed JSON.
+    pub fn to_json(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixt::CreateFixturator;
+    use crate::fixt::CreateLinkFixturator;
+    use crate::fixt::DeleteLinkFixturator;
+    use ::fixt::prelude::*;
+
+    #[derive(serde::Serialize, serde::Deserialize, SerializedBytes, Debug)]
+    struct Post {
+        title: String,
+    }
+
+    fn record(action: Action, entry: Option<Entry>) -> Record {
+        Record::new(
+            SignedActionHashed::with_presigned(
+                ActionHashed::from_content_sync(action),
+                Signature([0; 64]),
+            ),
+            entry,
+        )
+    }
+
+    #[test]
+    fn authored_data_is_exported_with_decoded_entries() {
+        let entry = Entry::app(
+            Post {
+                title: "hello".into(),
+            }
+            .try_into()
+            .unwrap(),
+        )
+        .unwrap();
+        let mut create = fixt!(Create);
+        create.entry_type = EntryType::App(AppEntryDef::new(
+            0.into(),
+            0.into(),
+            EntryVisibility::Public,
+        ));
+        create.entry_hash = EntryHash::with_data_sync(&entry);
+        // Fixturated hashes don't have valid locations, so they can't be parsed again.
+        let mut create_link = fixt!(CreateLink);
+        create_link.base_address = EntryHash::from_raw_32(vec![1; 32]).into();
+        create_link.target_address = EntryHash::from_raw_32(vec![2; 32]).into();
+        let create_link = record(Action::CreateLink(create_link), None);
+        let mut delete_link = fixt!(DeleteLink);
+        delete_link.link_add_address = create_link.action_address().clone();
+
+        let export = AuthoredDataExport::new(
+            &CellId::new(
+                DnaHash::from_raw_32(vec![3; 32]),
+                AgentPubKey::from_raw_32(vec![4; 32]),
+            ),
+            Timestamp::now(),
+            [
+                record(Action::Create(create), Some(entry)),
+                create_link,
+                record(Action::DeleteLink(delete_link), None),
+            ],
+        );
+        assert_eq!(3, export.actions.len());
+        assert_eq!(1, export.entries.len());
+        assert_eq!(
+            Some(serde_json::json!({ "title": "hello" })),
+            export.entries[0].content
+        );
+        assert_eq!(None, export.entries[0].bytes);
+        assert_eq!(1, export.links.len());
+        assert!(export.links[0].deleted);
+
+        // Hashes are written as base64 strings.
+        let json: serde_json::Value = serde_json::from_str(&export.to_json().unwrap()).unwrap();
+        assert_eq!(
+            serde_json::json!(export.actions[1].hash.to_string()),
+            json["links"][0]["create_link_hash"]
+        );
+    }
+}
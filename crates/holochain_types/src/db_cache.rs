@@ -1,10 +1,12 @@
 //! # Database Cache
 //! This is an in-memory cache that is used to store the state of the DHT database.
 
+use crate::chain::MustGetAgentActivityResponse;
 use crate::dht_op::ChainOpType;
 use crate::share::RwShare;
 use holo_hash::*;
 use holochain_sqlite::prelude::*;
+use holochain_zome_types::chain::ChainFilter;
 use rusqlite::named_params;
 use std::collections::HashMap;
 use std::ops::RangeInclusive;
@@ -17,6 +19,12 @@ mod tests;
 mod error;
 pub use error::*;
 
+mod hot_hashes;
+pub use hot_hashes::*;
+
+mod query_cache;
+pub use query_cache::*;
+
 #[derive(Clone)]
 /// This cache allows us to track selected database queries that
 /// are too slow to run frequently.
@@ -28,10 +36,22 @@ pub struct DhtDbQueryCache {
     dht_db: DbRead<DbKindDht>,
     /// The cache of agent activity queries.
     activity: Arc<tokio::sync::OnceCell<ActivityCache>>,
+    /// The chain subsets which were found for `must_get_agent_activity` requests.
+    chain_subsets: ChainSubsetCache,
+    /// The results of common cascade queries.
+    queries: CascadeQueryCache,
+    /// The hashes which are often read from the network.
+    hot_hashes: HotHashes,
 }
 
 type ActivityCache = RwShare<HashMap<Arc<AgentPubKey>, ActivityState>>;
 
+type ChainSubsetCache =
+    RwShare<HashMap<Arc<AgentPubKey>, HashMap<ChainFilter, MustGetAgentActivityResponse>>>;
+
+/// The most chain subsets which are cached at once, across all authors.
+const MAX_CACHED_CHAIN_SUBSETS: usize = 1000;
+
 #[derive(Default, Debug, Clone, PartialEq, Eq)]
 /// The state of an authors activity according to this authority.
 pub struct ActivityState {
@@ -75,9 +95,71 @@ impl DhtDbQueryCache {
         Self {
             dht_db,
             activity: Default::default(),
+            chain_subsets: Default::default(),
+            queries: Default::default(),
+            hot_hashes: Default::default(),
         }
     }
 
+    /// The hashes which are often read from the network for the cells of this DNA,
+    /// and which of them have been prefetched.
+    pub fn hot_hashes(&self) -> &HotHashes {
+        &self.hot_hashes
+    }
+
+    /// The results of common cascade queries on this database, and the data
+    /// authored by the cells of its DNA.
+    pub fn queries(&self) -> &CascadeQueryCache {
+        &self.queries
+    }
+
+    /// Get the chain subset which was cached for a `must_get_agent_activity` request,
+    /// so that it isn't rebuilt or fetched again for every op of the author being validated.
+    pub fn get_chain_subset(
+        &self,
+        author: &AgentPubKey,
+        filter: &ChainFilter,
+    ) -> Option<MustGetAgentActivityResponse> {
+        self.chain_subsets.share_ref(|subsets| {
+            subsets
+                .get(author)
+                .and_then(|subsets| subsets.get(filter))
+                .cloned()
+        })
+    }
+
+    /// Cache the response to a `must_get_agent_activity` request.
+    ///
+    /// Only complete chain subsets are cached, as any other response may change
+    /// once more of the author's chain is held. The subsets of an author are
+    /// dropped when more of their activity is integrated.
+    pub fn put_chain_subset(
+        &self,
+        author: AgentPubKey,
+        filter: ChainFilter,
+        response: &MustGetAgentActivityResponse,
+    ) {
+        if !matches!(response, MustGetAgentActivityResponse::Activity { .. }) {
+            return;
+        }
+        self.chain_subsets.share_mut(|subsets| {
+            if subsets.values().map(HashMap::len).sum::<usize>() >= MAX_CACHED_CHAIN_SUBSETS {
+                subsets.clear();
+            }
+            subsets
+                .entry(Arc::new(author))
+                .or_default()
+                .insert(filter, response.clone());
+        })
+    }
+
+    /// Drop the cached chain subsets of an author, as their chain has grown.
+    fn invalidate_chain_subsets(&self, author: &AgentPubKey) {
+        self.chain_subsets.share_mut(|subsets| {
+            subsets.remove(author);
+        })
+    }
+
     /// Lazily initiate the activity cache.
     #[cfg_attr(feature = "instrument", tracing::instrument(skip_all))]
     async fn get_or_try_init(&self) -> DatabaseResult<&ActivityCache> {
@@ -197,6 +279,12 @@ impl DhtDbQueryCache {
         }))
     }
 
+    /// Build the activity cache from the DHT database now, rather than at its first use.
+    pub async fn init(&self) -> DatabaseResult<()> {
+        self.get_or_try_init().await?;
+        Ok(())
+    }
+
     /// Is the SourceChain empty for this [`AgentPubKey`]?
     pub async fn is_chain_empty(&self, author: &AgentPubKey) -> DatabaseResult<bool> {
         Ok(self.get_or_try_init().await?.share_ref(|activity| {
@@ -216,6 +304,8 @@ impl DhtDbQueryCache {
 
             // For each authors activity run the activity check then update the activity state.
             for (author, seq_range) in integrated_activity {
+                self.invalidate_chain_subsets(&author);
+
                 let prev_bounds = activity.get_mut(author.as_ref());
 
                 // Set the new bounds to the start of this range for the check.
@@ -284,12 +374,29 @@ impl DhtDbQueryCache {
         .await
     }
 
+    /// Forget the activity of an author after `seq`, e.g. because their chain was rolled
+    /// back to it and the ops of the later actions were deleted, so that new activity
+    /// after `seq` can be integrated again.
+    pub async fn roll_back_activity(&self, author: &AgentPubKey, seq: u32) -> DatabaseResult<()> {
+        self.invalidate_chain_subsets(author);
+        self.get_or_try_init().await?.share_mut(|activity| {
+            if let Some(state) = activity.get_mut(author) {
+                state.bounds.integrated = state.bounds.integrated.map(|i| i.min(seq));
+                state.bounds.ready_to_integrate =
+                    state.bounds.ready_to_integrate.map(|r| r.min(seq));
+                state.awaiting_deps.retain(|s| *s <= seq);
+            }
+        });
+        Ok(())
+    }
+
     /// Add an author's activity.
     async fn new_activity_inner(
         &self,
         agent: &AgentPubKey,
         new_bounds: ActivityBounds,
     ) -> DbCacheResult<()> {
+        self.invalidate_chain_subsets(agent);
         self.get_or_try_init()
             .await?
             .share_mut(|activity| update_activity(activity, agent, &new_bounds))
@@ -0,0 +1,167 @@
+//! The hashes of records which are often read from the network, because no cell
+//! of the DNA on this conductor is an authority for them.
+//!
+//! The hottest of these hashes are prefetched in the background, so that they can
+//! be read from the cache database until the next prefetch, without waiting on the network.
+//!
+//! The DNAs of client-only cells, which hold no data themselves, can also keep every
+//! record they fetch fresh in the cache database for a while.
+
+use holo_hash::*;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// The most hashes whose reads are counted at once.
+pub const MAX_TRACKED_HOT_HASHES: usize = 10_000;
+
+/// How many times a hash must be read from the network between two prefetches
+/// for it to be prefetched.
+pub const MIN_HOT_HASH_READS: u32 = 2;
+
+/// The hashes of records which are read from the network, and those which
+/// have been prefetched.
+#[derive(Clone, Default)]
+pub struct HotHashes(Arc<parking_lot::Mutex<HotHashesState>>);
+
+#[derive(Default)]
+struct HotHashesState {
+    /// How many times each hash was read since the last prefetch.
+    reads: HashMap<AnyDhtHash, u32>,
+    /// Until when each prefetched hash can be read from the cache database.
+    prefetched: HashMap<AnyDhtHash, Instant>,
+    /// How long each fetched hash can be read from the cache database, if at all.
+    fetched_fresh_for: Option<Duration>,
+    /// Until when each fetched hash can be read from the cache database.
+    fetched: HashMap<AnyDhtHash, Instant>,
+}
+
+impl HotHashes {
+    /// Count a read of a hash which had to go to the network, or which was
+    /// answered by a prefetch.
+    pub fn record_read(&self, hash: &AnyDhtHash) {
+        let mut state = self.0.lock();
+        if let Some(reads) = state.reads.get_mut(hash) {
+            *reads += 1;
+        } else if state.reads.len() < MAX_TRACKED_HOT_HASHES {
+            state.reads.insert(hash.clone(), 1);
+        }
+    }
+
+    /// Whether a hash was prefetched, or fetched, recently enough to be read from the
+    /// cache database.
+    pub fn is_prefetched(&self, hash: &AnyDhtHash) -> bool {
+        let state = self.0.lock();
+        let now = Instant::now();
+        state
+            .prefetched
+            .get(hash)
+            .into_iter()
+            .chain(state.fetched.get(hash))
+            .any(|fresh_until| now < *fresh_until)
+    }
+
+    /// Keep every hash which is fetched from the network fresh in the cache database
+    /// for this long, or stop doing so with `None`.
+    pub fn set_fetched_fresh_for(&self, fresh_for: Option<Duration>) {
+        let mut state = self.0.lock();
+        state.fetched_fresh_for = fresh_for;
+        if fresh_for.is_none() {
+            state.fetched.clear();
+        }
+    }
+
+    /// Mark a hash as fetched from the network, so that it is read from the cache
+    /// database for a while if [`HotHashes::set_fetched_fresh_for`] was set.
+    pub fn record_fetch(&self, hash: &AnyDhtHash) {
+        let mut state = self.0.lock();
+        let Some(fresh_for) = state.fetched_fresh_for else {
+            return;
+        };
+        let now = Instant::now();
+        if state.fetched.len() >= MAX_TRACKED_HOT_HASHES {
+            state.fetched.retain(|_, fresh_until| now < *fresh_until);
+        }
+        if state.fetched.len() < MAX_TRACKED_HOT_HASHES || state.fetched.contains_key(hash) {
+            state.fetched.insert(hash.clone(), now + fresh_for);
+        }
+    }
+
+    /// Take the `count` hashes which were read most often since this was last called,
+    /// and start counting their reads again.
+    ///
+    /// Prefetched hashes which are no longer among the hottest are forgotten, so that
+    /// they are read from the network again.
+    pub fn take_hottest(&self, count: usize) -> Vec<AnyDhtHash> {
+        let mut state = self.0.lock();
+        let mut reads: Vec<_> = state
+            .reads
+            .drain()
+            .filter(|(_, reads)| *reads >= MIN_HOT_HASH_READS)
+            .collect();
+        reads.sort_unstable_by(|a, b| b.1.cmp(&a.1));
+        reads.truncate(count);
+        let hottest: Vec<_> = reads.into_iter().map(|(hash, _)| hash).collect();
+        state.prefetched.retain(|hash, _| hottest.contains(hash));
+        hottest
+    }
+
+    /// Mark a hash as prefetched, so that it is read from the cache database until `fresh_until`.
+    pub fn set_prefetched(&self, hash: AnyDhtHash, fresh_until: Instant) {
+        self.0.lock().prefetched.insert(hash, fresh_until);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::prelude::*;
+    use holo_hash::fixt::*;
+    use std::time::Duration;
+
+    #[test]
+    fn the_hottest_hashes_are_prefetched() {
+        let hot_hashes = HotHashes::default();
+        let hottest: AnyDhtHash = fixt!(ActionHash).into();
+        let warm: AnyDhtHash = fixt!(EntryHash).into();
+        let cold: AnyDhtHash = fixt!(ActionHash).into();
+        for _ in 0..3 {
+            hot_hashes.record_read(&hottest);
+        }
+        for _ in 0..2 {
+            hot_hashes.record_read(&warm);
+        }
+        hot_hashes.record_read(&cold);
+
+        assert_eq!(vec![hottest.clone()], hot_hashes.take_hottest(1));
+        // The reads are counted again from zero.
+        assert!(hot_hashes.take_hottest(1).is_empty());
+
+        hot_hashes.set_prefetched(hottest.clone(), Instant::now() + Duration::from_secs(60));
+        assert!(hot_hashes.is_prefetched(&hottest));
+        hot_hashes.set_prefetched(warm.clone(), Instant::now());
+        assert!(!hot_hashes.is_prefetched(&warm));
+
+        // A prefetched hash which is no longer read isn't prefetched any more.
+        hot_hashes.take_hottest(1);
+        assert!(!hot_hashes.is_prefetched(&hottest));
+    }
+
+    #[test]
+    fn fetched_hashes_are_fresh_only_when_enabled() {
+        let hot_hashes = HotHashes::default();
+        let hash: AnyDhtHash = fixt!(ActionHash).into();
+        hot_hashes.record_fetch(&hash);
+        assert!(!hot_hashes.is_prefetched(&hash));
+
+        hot_hashes.set_fetched_fresh_for(Some(Duration::from_secs(60)));
+        hot_hashes.record_fetch(&hash);
+        assert!(hot_hashes.is_prefetched(&hash));
+        // - Fetched hashes aren't forgotten when the hottest are taken.
+        hot_hashes.take_hottest(1);
+        assert!(hot_hashes.is_prefetched(&hash));
+
+        hot_hashes.set_fetched_fresh_for(None);
+        assert!(!hot_hashes.is_prefetched(&hash));
+    }
+}
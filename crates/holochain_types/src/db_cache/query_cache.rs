@@ -0,0 +1,233 @@
+//! The results of common cascade queries, kept in memory so that apps which read
+//! the same records and links over and over don't run the same SQL every time.
+//!
+//! A result is dropped from the cache once any op on its basis is integrated,
+//! authored or fetched from the network.
+
+use crate::link::WireLinkKey;
+use holo_hash::*;
+use holochain_zome_types::prelude::*;
+use lru::LruCache;
+use std::collections::HashSet;
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+
+/// The most query results which are cached at once, for all the cells of a DNA.
+pub const MAX_CACHED_QUERIES: usize = 1000;
+
+/// A cascade query whose result can be cached.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum CachedQuery {
+    /// The live record for an action or entry hash.
+    Record(AnyDhtHash),
+    /// The live links for a link key.
+    Links(WireLinkKey),
+}
+
+impl CachedQuery {
+    /// The basis of the ops which the result of this query depends on.
+    pub fn basis(&self) -> AnyLinkableHash {
+        match self {
+            Self::Record(hash) => hash.clone().into(),
+            Self::Links(key) => key.base.clone(),
+        }
+    }
+}
+
+/// A query, along with the data of the cell which it was run for.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct CachedQueryKey {
+    /// The cell whose authored data the query read.
+    pub cell_id: Arc<CellId>,
+    /// The agent whose private entries the query could read.
+    pub private_data: Option<Arc<AgentPubKey>>,
+    /// The query.
+    pub query: CachedQuery,
+}
+
+/// The result of a [`CachedQuery`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CachedResult {
+    /// The result of a [`CachedQuery::Record`].
+    Record(Option<Record>),
+    /// The result of a [`CachedQuery::Links`].
+    Links(Vec<Link>),
+}
+
+impl From<Option<Record>> for CachedResult {
+    fn from(record: Option<Record>) -> Self {
+        Self::Record(record)
+    }
+}
+
+impl From<Vec<Link>> for CachedResult {
+    fn from(links: Vec<Link>) -> Self {
+        Self::Links(links)
+    }
+}
+
+impl TryFrom<CachedResult> for Option<Record> {
+    type Error = CachedResult;
+
+    fn try_from(result: CachedResult) -> Result<Self, Self::Error> {
+        match result {
+            CachedResult::Record(record) => Ok(record),
+            result => Err(result),
+        }
+    }
+}
+
+impl TryFrom<CachedResult> for Vec<Link> {
+    type Error = CachedResult;
+
+    fn try_from(result: CachedResult) -> Result<Self, Self::Error> {
+        match result {
+            CachedResult::Links(links) => Ok(links),
+            result => Err(result),
+        }
+    }
+}
+
+/// The least recently used query results of the cells of a DNA.
+#[derive(Clone)]
+pub struct CascadeQueryCache(Arc<parking_lot::Mutex<QueryCacheState>>);
+
+struct QueryCacheState {
+    results: LruCache<CachedQueryKey, CachedResult>,
+    /// Counts the invalidations, so that a result which was read from the databases
+    /// before an invalidation isn't put in the cache after it.
+    generation: u64,
+}
+
+impl Default for CascadeQueryCache {
+    fn default() -> Self {
+        Self::new(MAX_CACHED_QUERIES)
+    }
+}
+
+impl CascadeQueryCache {
+    /// Create a cache which holds up to `capacity` results.
+    pub fn new(capacity: usize) -> Self {
+        Self(Arc::new(parking_lot::Mutex::new(QueryCacheState {
+            results: LruCache::new(NonZeroUsize::new(capacity).unwrap_or(NonZeroUsize::MIN)),
+            generation: 0,
+        })))
+    }
+
+    /// The current generation of the cache, which must be read before a query
+    /// is run on the databases and passed to [`CascadeQueryCache::put`] with its result.
+    pub fn generation(&self) -> u64 {
+        self.0.lock().generation
+    }
+
+    /// Get the cached result of a query.
+    pub fn get(&self, key: &CachedQueryKey) -> Option<CachedResult> {
+        self.0.lock().results.get(key).cloned()
+    }
+
+    /// Cache the result of a query, unless the cache was invalidated since `generation`.
+    pub fn put(&self, key: CachedQueryKey, result: CachedResult, generation: u64) {
+        let mut state = self.0.lock();
+        if state.generation == generation {
+            state.results.put(key, result);
+        }
+    }
+
+    /// Drop the results of the queries on these bases, as ops on them have been written.
+    pub fn invalidate(&self, bases: impl IntoIterator<Item = AnyLinkableHash>) {
+        let bases: HashSet<_> = bases.into_iter().collect();
+        if bases.is_empty() {
+            return;
+        }
+        let mut state = self.0.lock();
+        state.generation += 1;
+        let invalidated: Vec<_> = state
+            .results
+            .iter()
+            .filter(|(key, _)| bases.contains(&key.query.basis()))
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in invalidated {
+            state.results.pop(&key);
+        }
+    }
+
+    /// Drop all the cached results.
+    pub fn clear(&self) {
+        let mut state = self.0.lock();
+        state.generation += 1;
+        state.results.clear();
+    }
+
+    /// The number of cached results.
+    pub fn len(&self) -> usize {
+        self.0.lock().results.len()
+    }
+
+    /// Whether there are no cached results.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::prelude::*;
+
+    fn key(query: CachedQuery) -> CachedQueryKey {
+        CachedQueryKey {
+            cell_id: Arc::new(fixt!(CellId)),
+            private_data: None,
+            query,
+        }
+    }
+
+    #[test]
+    fn results_are_invalidated_by_basis() {
+        let cache = CascadeQueryCache::default();
+        let record_hash: AnyDhtHash = fixt!(ActionHash).into();
+        let record = key(CachedQuery::Record(record_hash.clone()));
+        let links = key(CachedQuery::Links(WireLinkKey {
+            base: fixt!(AnyLinkableHash),
+            type_query: LinkTypeFilter::Dependencies(vec![]),
+            tag: None,
+            after: None,
+            before: None,
+            author: None,
+        }));
+
+        let generation = cache.generation();
+        cache.put(record.clone(), CachedResult::Record(None), generation);
+        cache.put(links.clone(), CachedResult::Links(vec![]), generation);
+        assert_eq!(Some(CachedResult::Record(None)), cache.get(&record));
+
+        cache.invalidate([record_hash.into()]);
+        assert_eq!(None, cache.get(&record));
+        assert_eq!(Some(CachedResult::Links(vec![])), cache.get(&links));
+
+        // A result read before the invalidation isn't cached.
+        cache.put(record.clone(), CachedResult::Record(None), generation);
+        assert_eq!(None, cache.get(&record));
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn least_recently_used_results_are_evicted() {
+        let cache = CascadeQueryCache::new(2);
+        let keys: Vec<_> = (0..3)
+            .map(|_| key(CachedQuery::Record(fixt!(ActionHash).into())))
+            .collect();
+        let generation = cache.generation();
+        cache.put(keys[0].clone(), CachedResult::Record(None), generation);
+        cache.put(keys[1].clone(), CachedResult::Record(None), generation);
+        cache.get(&keys[0]);
+        cache.put(keys[2].clone(), CachedResult::Record(None), generation);
+
+        assert!(cache.get(&keys[0]).is_some());
+        assert!(cache.get(&keys[1]).is_none());
+        assert!(cache.get(&keys[2]).is_some());
+    }
+}
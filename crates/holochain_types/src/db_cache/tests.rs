@@ -177,3 +177,40 @@ fn find_consecutive_test(mut awaiting_deps: Vec<u32>) -> (Option<u32>, Vec<u32>)
     let r = find_consecutive(&mut awaiting_deps);
     (r, awaiting_deps)
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn activity_after_a_rollback_can_be_integrated_again() {
+    let dna_hash = Arc::new(DnaHash::from_raw_36(vec![0; 36]));
+    let db = DbWrite::new(None, DbKindDht(dna_hash), Default::default(), None).unwrap();
+    let cache = DhtDbQueryCache::new(db.into());
+    let author = AgentPubKey::from_raw_36(vec![1; 36]);
+    for seq in 0..=4 {
+        cache
+            .set_activity_to_integrated(&author, Some(seq))
+            .await
+            .unwrap();
+    }
+    cache
+        .set_activity_ready_to_integrate(&author, Some(6))
+        .await
+        .unwrap();
+
+    cache.roll_back_activity(&author, 2).await.unwrap();
+
+    // - The activity after the rollback is forgotten, so new activity follows on from it.
+    assert!(cache
+        .set_activity_to_integrated(&author, Some(4))
+        .await
+        .is_err());
+    cache
+        .set_activity_to_integrated(&author, Some(3))
+        .await
+        .unwrap();
+    let state = cache
+        .get_state()
+        .await
+        .share_ref(|activity| activity.get(&author).cloned())
+        .unwrap();
+    assert_eq!(Some(3), state.integrated);
+    assert!(state.awaiting_deps.is_empty());
+}
@@ -20,7 +20,11 @@ use kitsune_p2p_dht::Loc;
 use serde::Deserialize;
 use serde::Serialize;
 
+mod archive;
+mod epoch;
 mod error;
+pub use archive::*;
+pub use epoch::*;
 pub use error::*;
 
 #[cfg(test)]
@@ -0,0 +1,156 @@
+//! Archives of integrated ops, for archival nodes and cold-storage backups of the data of a DNA.
+
+use super::*;
+use std::collections::HashMap;
+
+/// The ops of a DNA which were integrated before a point in time, in a compact form
+/// which stores each action and entry once, however many ops were produced from it.
+///
+/// Importing an archive validates its ops again, so an archive doesn't need to be trusted.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, SerializedBytes)]
+pub struct DhtOpArchive {
+    /// The DNA which the ops belong to.
+    pub dna_hash: DnaHash,
+    /// The ops in the archive were integrated before this time.
+    pub integrated_before: Timestamp,
+    records: Vec<ArchivedRecord>,
+    warrants: Vec<WarrantOp>,
+}
+
+/// An action along with its entry and the types of the archived ops produced from it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct ArchivedRecord {
+    action: SignedAction,
+    entry: Option<Entry>,
+    op_types: Vec<ChainOpType>,
+}
+
+impl DhtOpArchive {
+    /// Archive ops of a DNA which were integrated before `integrated_before`.
+    pub fn new(
+        dna_hash: DnaHash,
+        integrated_before: Timestamp,
+        ops: impl IntoIterator<Item = DhtOp>,
+    ) -> Self {
+        let mut records: Vec<ArchivedRecord> = Vec::new();
+        let mut record_indexes: HashMap<ActionHash, usize> = HashMap::new();
+        let mut warrants = Vec::new();
+        for op in ops {
+            match op {
+                DhtOp::ChainOp(op) => {
+                    let action = op.signed_action();
+                    let entry = op.entry().into_option().cloned();
+                    let index = *record_indexes
+                        .entry(ActionHash::with_data_sync(action.action()))
+                        .or_insert_with(|| {
+                            records.push(ArchivedRecord {
+                                action,
+                                entry: None,
+                                op_types: Vec::new(),
+                            });
+                            records.len() - 1
+                        });
+                    let record = &mut records[index];
+                    if record.entry.is_none() {
+                        record.entry = entry;
+                    }
+                    record.op_types.push(op.get_type());
+                }
+                DhtOp::WarrantOp(warrant) => warrants.push(*warrant),
+            }
+        }
+        Self {
+            dna_hash,
+            integrated_before,
+            records,
+            warrants,
+        }
+    }
+
+    /// The number of ops in the archive.
+    pub fn len(&self) -> usize {
+        self.records
+            .iter()
+            .map(|record| record.op_types.len())
+            .sum::<usize>()
+            + self.warrants.len()
+    }
+
+    /// Whether the archive has no ops.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The ops in the archive, which are produced again from the archived actions and entries.
+    pub fn into_ops(self) -> DhtOpResult<Vec<DhtOp>> {
+        let mut ops = Vec::with_capacity(self.len());
+        for record in self.records {
+            for op_type in record.op_types {
+                ops.push(
+                    ChainOp::from_type(op_type, record.action.clone(), record.entry.clone())?
+                        .into(),
+                );
+            }
+        }
+        ops.extend(self.warrants.into_iter().map(DhtOp::from));
+        Ok(ops)
+    }
+
+    /// Encode the archive, e.g. to write it to a file.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, SerializedBytesError> {
+        holochain_serialized_bytes::encode(self)
+    }
+
+    /// Decode an archive encoded with [`DhtOpArchive::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, SerializedBytesError> {
+        holochain_serialized_bytes::decode(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fixt::CreateFixturator;
+    use crate::fixt::CreateLinkFixturator;
+    use crate::fixt::EntryFixturator;
+    use ::fixt::prelude::*;
+    use holo_hash::fixt::DnaHashFixturator;
+
+    fn record(action: Action, entry: Option<Entry>) -> Record {
+        Record::new(
+            SignedActionHashed::with_presigned(
+                ActionHashed::from_content_sync(action),
+                Signature([0; 64]),
+            ),
+            entry,
+        )
+    }
+
+    #[test]
+    fn archived_ops_are_produced_again() {
+        let entry = fixt!(Entry);
+        let mut create = fixt!(Create);
+        create.entry_type = EntryType::App(AppEntryDef::new(
+            0.into(),
+            0.into(),
+            EntryVisibility::Public,
+        ));
+        create.entry_hash = EntryHash::with_data_sync(&entry);
+        let ops: Vec<DhtOp> = [
+            record(Action::Create(create), Some(entry)),
+            record(Action::CreateLink(fixt!(CreateLink)), None),
+        ]
+        .iter()
+        .flat_map(|record| produce_ops_from_record(record).unwrap())
+        .map(DhtOp::from)
+        .collect();
+
+        let archive = DhtOpArchive::new(fixt!(DnaHash), Timestamp::now(), ops.clone());
+        assert_eq!(ops.len(), archive.len());
+        // Each action is stored once.
+        assert_eq!(2, archive.records.len());
+
+        let archive = DhtOpArchive::from_bytes(&archive.to_bytes().unwrap()).unwrap();
+        assert_eq!(ops, archive.into_ops().unwrap());
+    }
+}
@@ -0,0 +1,51 @@
+//! Epochs of DHT ops, which partition the ops of a DNA by the time they were authored
+//! so that old epochs can be archived and deleted wholesale.
+
+use super::*;
+use holochain_sqlite::sql::sql_cell::DHT_OP_EPOCH_MICROS;
+
+/// The epoch which an op authored at `timestamp` belongs to.
+///
+/// This rounds towards zero, in the same way as the `epoch` column of the DHT database.
+pub fn dht_op_epoch(timestamp: Timestamp) -> i64 {
+    timestamp.as_micros() / DHT_OP_EPOCH_MICROS
+}
+
+/// The earliest time which an epoch covers.
+pub fn dht_op_epoch_start(epoch: i64) -> Timestamp {
+    Timestamp::from_micros(epoch.saturating_mul(DHT_OP_EPOCH_MICROS))
+}
+
+/// The ops which a DHT database holds for an epoch.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DhtEpochSummary {
+    /// The epoch, see [`dht_op_epoch`].
+    pub epoch: i64,
+    /// The earliest time which the epoch covers.
+    pub starts_at: Timestamp,
+    /// How many ops of the epoch are held.
+    pub op_count: u64,
+    /// Whether the epoch has been archived, after which its ops aren't taken in again.
+    pub archived: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ops_are_put_in_the_epoch_they_were_authored_in() {
+        assert_eq!(0, dht_op_epoch(Timestamp::from_micros(0)));
+        assert_eq!(
+            0,
+            dht_op_epoch(Timestamp::from_micros(DHT_OP_EPOCH_MICROS - 1))
+        );
+        assert_eq!(
+            3,
+            dht_op_epoch(Timestamp::from_micros(3 * DHT_OP_EPOCH_MICROS))
+        );
+        for epoch in [0, 1, 700] {
+            assert_eq!(epoch, dht_op_epoch(dht_op_epoch_start(epoch)));
+        }
+    }
+}
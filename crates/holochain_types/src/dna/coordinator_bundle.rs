@@ -45,14 +45,18 @@ impl CoordinatorBundle {
         let coordinator = hash_bytes(self.manifest().zomes.iter().cloned(), &mut resources).await?;
         let coordinator_zomes = coordinator
             .iter()
-            .map(|(zome_name, hash, _, dependencies, preserialized_path)| {
-                let zome_def = ZomeDef::Wasm(WasmZome {
-                    wasm_hash: hash.clone(),
-                    dependencies: dependencies.clone(),
-                    preserialized_path: preserialized_path.clone(),
-                });
-                (zome_name.clone(), zome_def.into())
-            })
+            .zip(&self.manifest().zomes)
+            .map(
+                |((zome_name, hash, _, dependencies, preserialized_path), manifest)| {
+                    let zome_def = ZomeDef::Wasm(WasmZome {
+                        wasm_hash: hash.clone(),
+                        dependencies: dependencies.clone(),
+                        init_dependencies: manifest.init_dependencies(),
+                        preserialized_path: preserialized_path.clone(),
+                    });
+                    (zome_name.clone(), zome_def.into())
+                },
+            )
             .collect();
         let wasms = coordinator
             .into_iter()
@@ -63,13 +63,13 @@ impl DnaBundle {
 
     async fn inner_maps(&self) -> DnaResult<(IntegrityZomes, CoordinatorZomes, WasmMap)> {
         let mut resources = self.resolve_all_cloned().await?;
-        let data = match &self.manifest().0 {
+        let (data, coordinator_manifests) = match &self.manifest().0 {
             DnaManifest::V1(manifest) => {
                 let integrity =
                     hash_bytes(manifest.integrity.zomes.iter().cloned(), &mut resources).await?;
                 let coordinator =
                     hash_bytes(manifest.coordinator.zomes.iter().cloned(), &mut resources).await?;
-                [integrity, coordinator]
+                ([integrity, coordinator], &manifest.coordinator.zomes)
             }
         };
 
@@ -79,6 +79,7 @@ impl DnaBundle {
                 let zome_def = ZomeDef::Wasm(WasmZome {
                     wasm_hash: hash.clone(),
                     dependencies: dependencies.clone(),
+                    init_dependencies: Default::default(),
                     preserialized_path: dylib_path.clone(),
                 });
                 (zome_name.clone(), zome_def.into())
@@ -86,14 +87,18 @@ impl DnaBundle {
             .collect();
         let coordinator_zomes = data[1]
             .iter()
-            .map(|(zome_name, hash, _, dependencies, dylib_path)| {
-                let zome_def = ZomeDef::Wasm(WasmZome {
-                    wasm_hash: hash.clone(),
-                    dependencies: dependencies.clone(),
-                    preserialized_path: dylib_path.clone(),
-                });
-                (zome_name.clone(), zome_def.into())
-            })
+            .zip(coordinator_manifests)
+            .map(
+                |((zome_name, hash, _, dependencies, dylib_path), manifest)| {
+                    let zome_def = ZomeDef::Wasm(WasmZome {
+                        wasm_hash: hash.clone(),
+                        dependencies: dependencies.clone(),
+                        init_dependencies: manifest.init_dependencies(),
+                        preserialized_path: dylib_path.clone(),
+                    });
+                    (zome_name.clone(), zome_def.into())
+                },
+            )
             .collect();
         let code: BTreeMap<_, _> = data
             .into_iter()
@@ -133,6 +138,10 @@ impl DnaBundle {
                         .into_iter()
                         .map(Into::into)
                         .collect(),
+                    size_limits: manifest.integrity.size_limits.clone(),
+                    required_validation_receipts: manifest.required_validation_receipts,
+                    interactive_types: manifest.interactive_types.clone(),
+                    fulltext_entry_types: manifest.fulltext_entry_types.clone(),
                 };
 
                 let original_hash = DnaHash::with_data_sync(&dna_def);
@@ -142,8 +151,9 @@ impl DnaBundle {
         }
     }
 
-    /// Build a bundle from a DnaFile. Useful for tests.
-    #[cfg(feature = "test_utils")]
+    /// Build a bundle from a DnaFile, e.g. to export a DNA which a conductor holds.
+    ///
+    /// The modifiers of the DNA are written to the manifest, so the bundle has the same DNA hash.
     pub fn from_dna_file(dna_file: DnaFile) -> DnaResult<Self> {
         let DnaFile { dna, code, .. } = dna_file;
         let manifest = Self::manifest_from_dna_def(dna.into_content())?;
@@ -154,7 +164,6 @@ impl DnaBundle {
         DnaBundle::new(manifest.try_into()?, resources, PathBuf::from("."))
     }
 
-    #[cfg(feature = "test_utils")]
     fn manifest_from_dna_def(dna_def: DnaDef) -> DnaResult<DnaManifest> {
         let integrity = dna_def
             .integrity_zomes
@@ -176,6 +185,7 @@ impl DnaBundle {
                         location: Location::Bundled(PathBuf::from(filename)),
                         dylib: None,
                         dependencies: Some(dependencies),
+                        init_dependencies: None,
                     }
                 })
             })
@@ -191,6 +201,13 @@ impl DnaBundle {
                     .cloned()
                     .map(|name| ZomeDependency { name })
                     .collect();
+                let init_dependencies: Vec<_> = zome
+                    .as_any_zome_def()
+                    .init_dependencies()
+                    .iter()
+                    .cloned()
+                    .map(|name| ZomeDependency { name })
+                    .collect();
                 zome.wasm_hash(&name).ok().map(|hash| {
                     let hash = WasmHashB64::from(hash);
                     let filename = format!("{}", hash);
@@ -200,6 +217,8 @@ impl DnaBundle {
                         location: Location::Bundled(PathBuf::from(filename)),
                         dylib: None,
                         dependencies: Some(dependencies),
+                        init_dependencies: (!init_dependencies.is_empty())
+                            .then_some(init_dependencies),
                     }
                 })
             })
@@ -217,9 +236,13 @@ impl DnaBundle {
                 })?),
                 origin_time: dna_def.modifiers.origin_time.into(),
                 zomes: integrity,
+                size_limits: dna_def.size_limits,
             },
             coordinator: CoordinatorManifest { zomes: coordinator },
             lineage,
+            required_validation_receipts: dna_def.required_validation_receipts,
+            interactive_types: dna_def.interactive_types,
+            fulltext_entry_types: dna_def.fulltext_entry_types,
         }
         .into())
     }
@@ -286,6 +309,7 @@ mod tests {
                         location: mr_bundle::Location::Bundled(path1.clone()),
                         dylib: None,
                         dependencies: Default::default(),
+                        init_dependencies: Default::default(),
                     },
                     ZomeManifest {
                         name: "zome2".into(),
@@ -294,11 +318,25 @@ mod tests {
                         location: mr_bundle::Location::Bundled(path2.clone()),
                         dylib: None,
                         dependencies: Default::default(),
+                        init_dependencies: Default::default(),
                     },
                 ],
+                size_limits: Default::default(),
             },
             coordinator: CoordinatorManifest { zomes: vec![] },
             lineage,
+            required_validation_receipts: Some(3),
+            interactive_types: InteractiveTypes {
+                entry_types: vec![ZomeTypeIndex {
+                    zome: "zome1".into(),
+                    index: 0,
+                }],
+                link_types: vec![],
+            },
+            fulltext_entry_types: vec![ZomeTypeIndex {
+                zome: "zome1".into(),
+                index: 1,
+            }],
         };
         let resources = vec![(path1, wasm1.into()), (path2, wasm2.into())];
 
@@ -330,6 +368,15 @@ mod tests {
             .0;
         assert_eq!(dna_file.dna_def().integrity_zomes.len(), 2);
         assert_eq!(dna_file.code().len(), 2);
+        assert_eq!(Some(3), dna_file.dna_def().required_validation_receipts);
+        assert_eq!(
+            manifest.interactive_types,
+            dna_file.dna_def().interactive_types
+        );
+        assert_eq!(
+            manifest.fulltext_entry_types,
+            dna_file.dna_def().fulltext_entry_types
+        );
 
         // - Check that properties and UUID can be overridden
         let properties: YamlProperties = serde_yaml::Value::from(42).into();
@@ -34,6 +34,7 @@ async fn test_update_coordinators() {
             CoordinatorZomeDef::from(ZomeDef::Wasm(WasmZome {
                 wasm_hash: WasmHash::with_data(&dna_wasms[2]).await,
                 dependencies: vec!["b".into()],
+                init_dependencies: Default::default(),
                 preserialized_path: None,
             })),
         ),
@@ -42,6 +43,7 @@ async fn test_update_coordinators() {
             CoordinatorZomeDef::from(ZomeDef::Wasm(WasmZome {
                 wasm_hash: WasmHash::with_data(&dna_wasms[3]).await,
                 dependencies: vec!["b".into(), "a".into()],
+                init_dependencies: Default::default(),
                 preserialized_path: None,
             })),
         ),
@@ -67,6 +69,7 @@ async fn test_update_coordinators() {
         CoordinatorZomeDef::from(ZomeDef::Wasm(WasmZome {
             wasm_hash: WasmHash::with_data(&new_dna_wasms[0]).await,
             dependencies: vec!["b".into()],
+            init_dependencies: Default::default(),
             preserialized_path: None,
         })),
     )];
@@ -99,6 +102,7 @@ async fn test_update_coordinators() {
         CoordinatorZomeDef::from(ZomeDef::Wasm(WasmZome {
             wasm_hash: WasmHash::with_data(&new_dna_wasms[0]).await,
             dependencies: vec!["a".into()],
+            init_dependencies: Default::default(),
             preserialized_path: None,
         })),
     )];
@@ -139,6 +143,7 @@ async fn test_update_coordinators() {
             CoordinatorZomeDef::from(ZomeDef::Wasm(WasmZome {
                 wasm_hash: WasmHash::with_data(&new_dna_wasms[0]).await,
                 dependencies: vec!["a".into()],
+                init_dependencies: Default::default(),
                 preserialized_path: None,
             })),
         ),
@@ -147,6 +152,7 @@ async fn test_update_coordinators() {
             CoordinatorZomeDef::from(ZomeDef::Wasm(WasmZome {
                 wasm_hash: WasmHash::with_data(&new_dna_wasms[1]).await,
                 dependencies: vec!["a".into()],
+                init_dependencies: Default::default(),
                 preserialized_path: None,
             })),
         ),
@@ -155,6 +161,7 @@ async fn test_update_coordinators() {
             CoordinatorZomeDef::from(ZomeDef::Wasm(WasmZome {
                 wasm_hash: WasmHash::with_data(&new_dna_wasms[2]).await,
                 dependencies: vec!["a".into()],
+                init_dependencies: Default::default(),
                 preserialized_path: None,
             })),
         ),
@@ -163,6 +170,7 @@ async fn test_update_coordinators() {
             CoordinatorZomeDef::from(ZomeDef::Wasm(WasmZome {
                 wasm_hash: WasmHash::with_data(&new_dna_wasms[3]).await,
                 dependencies: vec!["a".into()],
+                init_dependencies: Default::default(),
                 preserialized_path: None,
             })),
         ),
@@ -235,6 +243,7 @@ async fn test_update_coordinators_checks_deps() {
             CoordinatorZomeDef::from(ZomeDef::Wasm(WasmZome {
                 wasm_hash: WasmHash::with_data(&dna_wasms[2]).await,
                 dependencies: vec!["b".into()],
+                init_dependencies: Default::default(),
                 preserialized_path: None,
             })),
         ),
@@ -243,6 +252,7 @@ async fn test_update_coordinators_checks_deps() {
             CoordinatorZomeDef::from(ZomeDef::Wasm(WasmZome {
                 wasm_hash: WasmHash::with_data(&dna_wasms[3]).await,
                 dependencies: vec!["b".into(), "a".into()],
+                init_dependencies: Default::default(),
                 preserialized_path: None,
             })),
         ),
@@ -266,6 +276,7 @@ async fn test_update_coordinators_checks_deps() {
         CoordinatorZomeDef::from(ZomeDef::Wasm(WasmZome {
             wasm_hash: WasmHash::with_data(&new_dna_wasms[0]).await,
             dependencies: vec!["z".into()],
+            init_dependencies: Default::default(),
             preserialized_path: None,
         })),
     )];
@@ -285,6 +296,7 @@ async fn test_update_coordinators_checks_deps() {
         CoordinatorZomeDef::from(ZomeDef::Wasm(WasmZome {
             wasm_hash: WasmHash::with_data(&new_dna_wasms[0]).await,
             dependencies: vec!["z".into()],
+            init_dependencies: Default::default(),
             preserialized_path: None,
         })),
     )];
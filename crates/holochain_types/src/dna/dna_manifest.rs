@@ -57,11 +57,20 @@ impl DnaManifest {
     ) -> Self {
         DnaManifestCurrent::new(
             name,
-            IntegrityManifest::new(network_seed, properties, origin_time, integrity_zomes),
+            IntegrityManifest {
+                network_seed,
+                properties,
+                origin_time,
+                zomes: integrity_zomes,
+                size_limits: Default::default(),
+            },
             CoordinatorManifest {
                 zomes: coordinator_zomes,
             },
             lineage.into_iter().map(Into::into).collect(),
+            None,
+            Default::default(),
+            Default::default(),
         )
         .into()
     }
@@ -142,6 +151,7 @@ mod tests {
                 origin_time: HumanTimestamp::Micros(Timestamp::now()),
                 properties: None,
                 zomes: vec![],
+                size_limits: Default::default(),
             })
             .build()
             .unwrap()
@@ -107,6 +107,47 @@ pub struct DnaManifestV1 {
     #[serde(default)]
     #[builder(default)]
     pub lineage: Vec<DnaHashB64>,
+
+    /// How many validation receipts an op authored on this DNA needs before Holochain
+    /// stops republishing it. Overrides the `required_validations` of entry defs.
+    ///
+    /// Defaults to the conductor's `default_required_validation_receipts`.
+    /// Does not affect the [`DnaHash`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    pub required_validation_receipts: Option<u8>,
+
+    /// The entry and link types whose ops are validated ahead of the ops of other types,
+    /// because users are waiting to see them, e.g.:
+    ///
+    /// ```yaml
+    /// interactive_types:
+    ///   entry_types:
+    ///     - zome: posts_integrity
+    ///       index: 0
+    ///   link_types:
+    ///     - zome: posts_integrity
+    ///       index: 1
+    /// ```
+    ///
+    /// Does not affect the [`DnaHash`].
+    #[serde(default, skip_serializing_if = "InteractiveTypes::is_empty")]
+    #[builder(default)]
+    pub interactive_types: InteractiveTypes,
+
+    /// The entry types whose content is indexed, so that the entries of those types
+    /// which a cell holds can be found with `query_local_fulltext`, e.g.:
+    ///
+    /// ```yaml
+    /// fulltext_entry_types:
+    ///   - zome: posts_integrity
+    ///     index: 0
+    /// ```
+    ///
+    /// Does not affect the [`DnaHash`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[builder(default)]
+    pub fulltext_entry_types: Vec<ZomeTypeIndex>,
 }
 
 impl DnaManifestV1 {
@@ -148,6 +189,12 @@ pub struct IntegrityManifest {
     /// The order is significant: it determines initialization order.
     /// The integrity zome manifests.
     pub zomes: Vec<ZomeManifest>,
+
+    /// The largest entries and link tags which can be committed to this DNA, in bytes.
+    /// Commits and ops received from other agents which exceed these limits are rejected.
+    #[serde(default)]
+    #[builder(default)]
+    pub size_limits: PayloadSizeLimits,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Default)]
@@ -177,6 +224,11 @@ pub struct ZomeManifest {
     /// are used in the zome.
     pub dependencies: Option<Vec<ZomeDependency>>,
 
+    /// The zomes whose init callbacks must run before this zome's.
+    /// Only used for coordinator zomes.
+    #[serde(default)]
+    pub init_dependencies: Option<Vec<ZomeDependency>>,
+
     /// DEPRECATED: Bundling precompiled and preserialized wasm for iOS is deprecated. Please use the wasm interpreter instead.
     ///
     /// The location of the wasm dylib for this zome
@@ -202,4 +254,13 @@ impl ZomeManifest {
     pub fn location(&self) -> &ZomeLocation {
         &self.location
     }
+
+    /// The names of the zomes whose init callbacks must run before this zome's.
+    pub fn init_dependencies(&self) -> Vec<ZomeName> {
+        self.init_dependencies
+            .iter()
+            .flatten()
+            .map(|d| d.name.clone())
+            .collect()
+    }
 }
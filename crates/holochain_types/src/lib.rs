@@ -16,10 +16,14 @@ pub mod access;
 pub mod action;
 pub mod activity;
 pub mod app;
+pub mod audit_log;
 pub mod autonomic;
+pub mod backfill;
 pub mod chain;
+pub mod clock;
 pub mod combinators;
 pub mod countersigning;
+pub mod data_export;
 pub mod db;
 pub mod db_cache;
 pub mod dht_op;
@@ -29,8 +33,10 @@ pub mod link;
 mod macros;
 pub mod metadata;
 pub mod prelude;
+pub mod publication_proof;
 pub mod rate_limit;
 pub mod record;
+pub mod redundancy;
 pub mod share;
 pub mod signal;
 #[warn(missing_docs)]
@@ -13,7 +13,7 @@ use crate::dht_op::DhtOpResult;
 use crate::dht_op::RenderedOp;
 use crate::dht_op::RenderedOps;
 
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, SerializedBytes)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, SerializedBytes)]
 /// Link key for sending across the wire for get links requests.
 pub struct WireLinkKey {
     /// Base the links are on.
@@ -257,17 +257,26 @@ pub struct WireLinkQuery {
 }
 
 /// Response type for a `WireLinkQuery`.
+///
+/// Only the action hash and timestamp of each matched link are sent,
+/// so that responses from several authorities can be merged without
+/// transferring the links themselves.
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, SerializedBytes)]
-pub struct CountLinksResponse(Vec<ActionHash>);
+pub struct CountLinksResponse(Vec<(ActionHash, Timestamp)>);
 
 impl CountLinksResponse {
-    /// Create a new response from the action hashes of the matched links
-    pub fn new(create_link_actions: Vec<ActionHash>) -> Self {
+    /// Create a new response from the action hashes and timestamps of the matched links
+    pub fn new(create_link_actions: Vec<(ActionHash, Timestamp)>) -> Self {
         CountLinksResponse(create_link_actions)
     }
 
     /// Get the action hashes of the matched links
     pub fn create_link_actions(&self) -> Vec<ActionHash> {
-        self.0.clone()
+        self.0.iter().map(|(hash, _)| hash.clone()).collect()
+    }
+
+    /// Get the action hashes and timestamps of the matched links
+    pub fn into_create_link_timestamps(self) -> Vec<(ActionHash, Timestamp)> {
+        self.0
     }
 }
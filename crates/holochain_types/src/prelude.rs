@@ -11,10 +11,13 @@ pub use crate::access::*;
 pub use crate::action::*;
 pub use crate::activity::*;
 pub use crate::app::*;
+pub use crate::audit_log::*;
 pub use crate::autonomic::*;
+pub use crate::backfill::*;
 pub use crate::chain::*;
 pub use crate::combinators::*;
 pub use crate::countersigning::*;
+pub use crate::data_export::*;
 pub use crate::db::*;
 pub use crate::db_cache::*;
 pub use crate::dht_op::*;
@@ -23,7 +26,9 @@ pub use crate::dna::*;
 pub use crate::entry::*;
 pub use crate::link::*;
 pub use crate::metadata::*;
+pub use crate::publication_proof::*;
 pub use crate::record::*;
+pub use crate::redundancy::*;
 pub use crate::signal::*;
 pub use crate::validation_receipt::*;
 pub use crate::warrant::*;
@@ -0,0 +1,231 @@
+//! Proofs that an action was published, which a third party can check offline, e.g.
+//! for audits: the signed action, the ops which were produced from it and the
+//! validation receipts which the authorities of the ops signed.
+//!
+//! A [`PublicationProof`] holds nothing which needs Holochain or the network to be
+//! checked. [`PublicationProof::verify`] recomputes the hashes of the action and its
+//! ops and checks every signature, so the only thing which the verifier has to be
+//! told is the DNA which the action is expected to have been published in.
+
+use crate::prelude::*;
+use thiserror::Error;
+
+/// An action, the ops which were produced from it, and the validation receipts which
+/// were collected for them.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct PublicationProof {
+    /// The DNA which the action was published in.
+    pub dna_hash: DnaHash,
+    /// The action, signed by its author.
+    pub action: SignedActionHashed,
+    /// The ops of the action, with their receipts.
+    pub ops: Vec<PublishedOp>,
+}
+
+/// An op of the action of a [`PublicationProof`].
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PublishedOp {
+    /// The type of the op.
+    pub op_type: ChainOpType,
+    /// The hash of the op.
+    pub op_hash: DhtOpHash,
+    /// The validation receipts which the authorities of the op signed.
+    pub receipts: Vec<SignedValidationReceipt>,
+}
+
+/// What a [`PublicationProof`] proves, once it's been verified.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifiedPublication {
+    /// The action which was published.
+    pub action_hash: ActionHash,
+    /// The author of the action.
+    pub author: AgentPubKey,
+    /// Each op of the action, with the validators which signed a receipt for it and
+    /// the status which they validated it with.
+    pub ops: Vec<(ChainOpType, Vec<(AgentPubKey, ValidationStatus)>)>,
+}
+
+impl VerifiedPublication {
+    /// The number of distinct validators which signed a receipt saying that an op of
+    /// the action is valid.
+    pub fn valid_receipt_count(&self) -> usize {
+        self.ops
+            .iter()
+            .flat_map(|(_, validators)| validators)
+            .filter(|(_, status)| *status == ValidationStatus::Valid)
+            .map(|(validator, _)| validator)
+            .collect::<std::collections::HashSet<_>>()
+            .len()
+    }
+}
+
+/// Why a [`PublicationProof`] doesn't prove what it claims to.
+#[derive(Error, Debug, PartialEq)]
+pub enum PublicationProofError {
+    /// The proof is for another DNA.
+    #[error("The proof is for DNA {0}, not the expected one")]
+    WrongDna(DnaHash),
+    /// The action doesn't have the hash which the proof gives for it.
+    #[error("The action doesn't have the hash {0}")]
+    WrongActionHash(ActionHash),
+    /// The action isn't signed by its author.
+    #[error("The action {0} isn't signed by its author")]
+    InvalidActionSignature(ActionHash),
+    /// An op isn't one which the action produces, or doesn't have the hash which the
+    /// proof gives for it.
+    #[error("The op {0} isn't an op of the action")]
+    WrongOp(DhtOpHash),
+    /// A receipt is for another op, or isn't signed by each of its validators.
+    #[error("A receipt for the op {0} isn't signed by its validators")]
+    InvalidReceipt(DhtOpHash),
+}
+
+impl PublicationProof {
+    /// Check the hashes and signatures of the proof, and that it's for `dna_hash`.
+    pub async fn verify(
+        &self,
+        dna_hash: &DnaHash,
+    ) -> Result<VerifiedPublication, PublicationProofError> {
+        if &self.dna_hash != dna_hash {
+            return Err(PublicationProofError::WrongDna(self.dna_hash.clone()));
+        }
+        let action_hash = self.action.action_address().clone();
+        if ActionHash::with_data_sync(self.action.action()) != action_hash {
+            return Err(PublicationProofError::WrongActionHash(action_hash));
+        }
+        if self.action.verify_signature().await.is_err() {
+            return Err(PublicationProofError::InvalidActionSignature(action_hash));
+        }
+
+        let op_types = action_to_op_types(self.action.action());
+        let mut ops = Vec::with_capacity(self.ops.len());
+        for op in &self.ops {
+            let wrong_op = || PublicationProofError::WrongOp(op.op_hash.clone());
+            if !op_types.contains(&op.op_type) {
+                return Err(wrong_op());
+            }
+            let (_, op_hash) = ChainOpUniqueForm::op_hash(op.op_type, self.action.action().clone())
+                .map_err(|_| wrong_op())?;
+            if op_hash != op.op_hash {
+                return Err(wrong_op());
+            }
+
+            let mut validators = Vec::new();
+            for signed in &op.receipts {
+                let receipt = &signed.receipt;
+                let invalid = || PublicationProofError::InvalidReceipt(op.op_hash.clone());
+                if receipt.dht_op_hash != op.op_hash
+                    || receipt.validators.len() != signed.validators_signatures.len()
+                {
+                    return Err(invalid());
+                }
+                for (validator, signature) in
+                    receipt.validators.iter().zip(&signed.validators_signatures)
+                {
+                    if !matches!(
+                        validator.verify_signature(signature, receipt.clone()).await,
+                        Ok(true)
+                    ) {
+                        return Err(invalid());
+                    }
+                    validators.push((validator.clone(), receipt.validation_status));
+                }
+            }
+            ops.push((op.op_type, validators));
+        }
+
+        Ok(VerifiedPublication {
+            action_hash,
+            author: self.action.action().author().clone(),
+            ops,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::prelude::*;
+    use holochain_keystore::MetaLairClient;
+
+    async fn proof(keystore: &MetaLairClient) -> PublicationProof {
+        let author = AgentPubKey::new_random(keystore).await.unwrap();
+        let validator = AgentPubKey::new_random(keystore).await.unwrap();
+        let mut create = fixt!(Create);
+        create.author = author;
+        let action = SignedActionHashed::sign(
+            keystore,
+            ActionHashed::from_content_sync(Action::Create(create)),
+        )
+        .await
+        .unwrap();
+        let (_, op_hash) =
+            ChainOpUniqueForm::op_hash(ChainOpType::StoreRecord, action.action().clone()).unwrap();
+        let receipt = ValidationReceipt {
+            dht_op_hash: op_hash.clone(),
+            validation_status: ValidationStatus::Valid,
+            validators: vec![validator],
+            when_integrated: Timestamp::now(),
+        }
+        .sign(keystore)
+        .await
+        .unwrap()
+        .unwrap();
+        PublicationProof {
+            dna_hash: fixt!(DnaHash),
+            action,
+            ops: vec![PublishedOp {
+                op_type: ChainOpType::StoreRecord,
+                op_hash,
+                receipts: vec![receipt],
+            }],
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn proofs_are_verified_offline() {
+        let keystore = holochain_keystore::spawn_test_keystore().await.unwrap();
+        let proof = proof(&keystore).await;
+        let dna_hash = proof.dna_hash.clone();
+
+        let verified = proof.verify(&dna_hash).await.unwrap();
+        assert_eq!(proof.action.action_address(), &verified.action_hash);
+        assert_eq!(1, verified.valid_receipt_count());
+
+        // - A proof for another DNA.
+        matches::assert_matches!(
+            proof.verify(&fixt!(DnaHash)).await,
+            Err(PublicationProofError::WrongDna(_))
+        );
+
+        // - An op which the action doesn't produce.
+        let mut wrong_op = proof.clone();
+        wrong_op.ops[0].op_type = ChainOpType::RegisterAddLink;
+        matches::assert_matches!(
+            wrong_op.verify(&dna_hash).await,
+            Err(PublicationProofError::WrongOp(_))
+        );
+
+        // - A receipt which was changed after it was signed.
+        let mut forged = proof.clone();
+        forged.ops[0].receipts[0].receipt.validation_status = ValidationStatus::Rejected;
+        matches::assert_matches!(
+            forged.verify(&dna_hash).await,
+            Err(PublicationProofError::InvalidReceipt(_))
+        );
+
+        // - An action which was changed after it was signed.
+        let mut tampered = proof;
+        let mut action = tampered.action.action().clone();
+        if let Action::Create(create) = &mut action {
+            create.action_seq += 1;
+        }
+        let signature = tampered.action.signature().clone();
+        tampered.action =
+            SignedActionHashed::with_presigned(ActionHashed::from_content_sync(action), signature);
+        matches::assert_matches!(
+            tampered.verify(&dna_hash).await,
+            Err(PublicationProofError::InvalidActionSignature(_))
+        );
+    }
+}
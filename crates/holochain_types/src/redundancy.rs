@@ -0,0 +1,41 @@
+//! Estimates of how many authorities hold each region of the DHT.
+//!
+//! The DHT is split into [`REDUNDANCY_REGION_COUNT`] regions of equal size, and the
+//! redundancy of a region is the number of agents whose storage arc covers all of it.
+
+/// How many regions of equal size the DHT is split into when estimating redundancy.
+pub const REDUNDANCY_REGION_COUNT: u32 = 16;
+
+/// The estimated number of authorities which hold a region of the DHT.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RegionRedundancy {
+    /// The first location of the region.
+    pub start_loc: u32,
+    /// The last location of the region, inclusive.
+    pub end_loc: u32,
+    /// How many agents are known to hold the whole region, including local agents.
+    pub estimated_redundancy: u32,
+}
+
+/// The first and last location of each of the [`REDUNDANCY_REGION_COUNT`] regions.
+pub fn redundancy_regions() -> impl Iterator<Item = (u32, u32)> {
+    let size = (u32::MAX as u64 + 1) / REDUNDANCY_REGION_COUNT as u64;
+    (0..REDUNDANCY_REGION_COUNT as u64)
+        .map(move |i| ((i * size) as u32, ((i + 1) * size - 1) as u32))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regions_cover_the_dht() {
+        let regions: Vec<_> = redundancy_regions().collect();
+        assert_eq!(REDUNDANCY_REGION_COUNT as usize, regions.len());
+        assert_eq!(0, regions[0].0);
+        assert_eq!(u32::MAX, regions.last().unwrap().1);
+        for pair in regions.windows(2) {
+            assert_eq!(pair[0].1 + 1, pair[1].0);
+        }
+    }
+}
@@ -3,7 +3,9 @@
 //! - App-defined signals are produced via the `emit_signal` host function.
 //! - System-defined signals are produced in various places in the system
 
+use crate::backfill::BackfillProgress;
 use crate::impl_from;
+use crate::redundancy::RegionRedundancy;
 use holochain_serialized_bytes::prelude::*;
 use holochain_zome_types::prelude::*;
 
@@ -39,6 +41,17 @@ pub enum SystemSignal {
     SuccessfulCountersigning(EntryHash),
     /// A countersigning session has been abandoned.
     AbandonedCountersigning(EntryHash),
+    /// A region of the DHT which a local agent of the DNA holds is estimated to be held
+    /// by fewer authorities than the conductor's `min_region_redundancy`.
+    LowRedundancy {
+        /// The DNA whose DHT the region is in.
+        dna_hash: DnaHash,
+        /// The region and its estimated redundancy.
+        region: RegionRedundancy,
+    },
+    /// How far a cell which joined the DHT is with getting the ops of its storage arc.
+    /// Emitted periodically until backfill is complete, and once more when it is.
+    BackfillProgress(BackfillProgress),
 }
 
 impl_from! {
@@ -53,6 +53,10 @@ pub fn fake_dna_zomes_named(
         integrity_zomes: Vec::new(),
         coordinator_zomes: Vec::new(),
         lineage: Default::default(),
+        size_limits: Default::default(),
+        required_validation_receipts: None,
+        interactive_types: Default::default(),
+        fulltext_entry_types: Default::default(),
     };
     tokio_helper::block_forever_on(async move {
         let mut wasm_code = Vec::new();
@@ -64,6 +68,7 @@ pub fn fake_dna_zomes_named(
                 ZomeDef::Wasm(WasmZome {
                     wasm_hash,
                     dependencies: Default::default(),
+                    init_dependencies: Default::default(),
                     preserialized_path: None,
                 })
                 .into(),
@@ -32,6 +32,18 @@ pub fn pw_get() -> Result<sodoken::BufRead> {
         .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))
 }
 
+/// Capture an additional passphrase from the user, e.g. to encrypt a backup.
+/// Unlike [pw_get] this is not cached, so each call captures a new
+/// passphrase. If piped, this reads the next line of stdin, so call [pw_get]
+/// first if both are needed.
+pub fn pw_get_other(prompt: &str) -> Result<sodoken::BufRead> {
+    if get_piped() {
+        read_piped_passphrase()
+    } else {
+        read_interactive_passphrase(prompt)
+    }
+}
+
 fn vec_to_locked(mut pass_tmp: Vec<u8>) -> Result<sodoken::BufRead> {
     match sodoken::BufWrite::new_mem_locked(pass_tmp.len()) {
         Err(e) => {
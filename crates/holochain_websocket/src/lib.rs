@@ -14,7 +14,8 @@ use tokio::select;
 use tokio_tungstenite::tungstenite::handshake::client::Request;
 use tokio_tungstenite::tungstenite::handshake::server::{Callback, ErrorResponse, Response};
 use tokio_tungstenite::tungstenite::http::{HeaderMap, HeaderValue, StatusCode};
-use tokio_tungstenite::tungstenite::protocol::Message;
+use tokio_tungstenite::tungstenite::protocol::frame::coding::CloseCode;
+use tokio_tungstenite::tungstenite::protocol::{CloseFrame, Message};
 
 #[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes)]
 #[serde(rename_all = "snake_case", tag = "type")]
@@ -128,6 +129,11 @@ impl WireMessage {
 }
 
 /// Websocket configuration struct.
+///
+/// There is no setting for compression: `permessage-deflate` is never
+/// negotiated, as tungstenite 0.21 can't receive compressed frames.
+// TODO: offer `permessage-deflate` once tokio-tungstenite is upgraded to a
+//       version which supports it.
 #[derive(Clone, Debug)]
 pub struct WebsocketConfig {
     /// Seconds after which the lib will stop tracking individual request ids.
@@ -135,9 +141,16 @@ pub struct WebsocketConfig {
     pub default_request_timeout: std::time::Duration,
 
     /// Maximum total message size of a websocket message. [default = 64M]
+    ///
+    /// The connection is closed with a [CloseCode::Size] close frame if a
+    /// larger message is received.
     pub max_message_size: usize,
 
     /// Maximum websocket frame size. [default = 16M]
+    ///
+    /// Messages are sent as a single frame, so this also limits the size of
+    /// the messages which can be sent. Sending a larger message fails with
+    /// [WebsocketError::MessageTooLarge] and leaves the connection open.
     pub max_frame_size: usize,
 
     /// Allowed origins access control for a [WebsocketListener].
@@ -162,6 +175,11 @@ impl WebsocketConfig {
         allowed_origins: Some(AllowedOrigins::Any),
     };
 
+    /// The largest message which can be sent.
+    pub(crate) fn max_send_size(&self) -> usize {
+        self.max_message_size.min(self.max_frame_size)
+    }
+
     /// Internal convert to tungstenite config.
     pub(crate) fn as_tungstenite(
         &self,
@@ -244,6 +262,14 @@ pub enum WebsocketError {
     /// A websocket error from the underlying tungstenite library.
     #[error("Websocket error: {0}")]
     Websocket(#[from] tokio_tungstenite::tungstenite::Error),
+    /// A message was larger than the maximum size allowed by the [WebsocketConfig].
+    #[error("Message of {size} bytes is larger than the maximum of {max} bytes")]
+    MessageTooLarge {
+        /// The size of the message.
+        size: usize,
+        /// The maximum size of a message.
+        max: usize,
+    },
     /// A timeout occurred.
     #[error("Timeout")]
     Timeout(#[from] tokio::time::error::Elapsed),
@@ -271,6 +297,41 @@ struct WsCore {
     pub recv: WsRecvSync,
     pub rmap: RMap,
     pub timeout: std::time::Duration,
+    pub max_send_size: usize,
+}
+
+impl WsCore {
+    /// Send a message, unless it is larger than the largest message which can be sent.
+    async fn send(&self, msg: Message) -> WebsocketResult<()> {
+        use futures::sink::SinkExt;
+        let size = msg.len();
+        if size > self.max_send_size {
+            return Err(WebsocketError::MessageTooLarge {
+                size,
+                max: self.max_send_size,
+            });
+        }
+        self.send.lock().await.send(msg).await?;
+        Ok(())
+    }
+
+    /// Close the connection after receiving a message which was too large, telling the remote
+    /// why with a close frame.
+    async fn reject_too_large(&self, size: usize, max: usize) -> WebsocketError {
+        use futures::sink::SinkExt;
+        let err = WebsocketError::MessageTooLarge { size, max };
+        let close = Message::Close(Some(CloseFrame {
+            code: CloseCode::Size,
+            reason: err.to_string().into(),
+        }));
+        if let Err(e) = self.send.lock().await.send(close).await {
+            tracing::debug!(
+                ?e,
+                "Failed to send close frame for a message which was too large"
+            );
+        }
+        err
+    }
 }
 
 #[derive(Clone)]
@@ -300,6 +361,13 @@ impl WsCoreSync {
                 // That's a client issue and not a connection issue.
                 Err(e)
             }
+            Err(e @ WebsocketError::MessageTooLarge { .. }) => {
+                // Don't close the connection when a message is too large to
+                // send, nothing was sent so the connection is still usable.
+                // Messages which are too large to receive close the connection
+                // when they are received.
+                Err(e)
+            }
             Err(err) => {
                 self.close();
                 Err(err)
@@ -344,13 +412,41 @@ impl WebsocketRespond {
         SerializedBytes: TryFrom<S, Error = SerializedBytesError>,
     {
         tracing::trace!(?s, %self.id, "OutResponse");
-        use futures::sink::SinkExt;
         self.core
             .exec(move |_, core| async move {
                 tokio::time::timeout(core.timeout, async {
                     let s = WireMessage::response(self.id, s)?;
-                    core.send.lock().await.send(s).await?;
-                    Ok(())
+                    core.send(s).await
+                })
+                .await?
+            })
+            .await
+    }
+
+    /// Respond to an incoming request, or respond with the result of
+    /// `too_large` if the response is larger than the largest message which
+    /// can be sent.
+    pub async fn respond_or<S, T, F>(self, s: S, too_large: F) -> WebsocketResult<()>
+    where
+        S: std::fmt::Debug,
+        SerializedBytes: TryFrom<S, Error = SerializedBytesError>,
+        T: std::fmt::Debug,
+        SerializedBytes: TryFrom<T, Error = SerializedBytesError>,
+        F: FnOnce(WebsocketError) -> T + Send,
+    {
+        tracing::trace!(?s, %self.id, "OutResponse");
+        self.core
+            .exec(move |_, core| async move {
+                tokio::time::timeout(core.timeout, async {
+                    let s = WireMessage::response(self.id, s)?;
+                    match core.send(s).await {
+                        Err(e @ WebsocketError::MessageTooLarge { .. }) => {
+                            tracing::warn!(?e, %self.id, "Response is too large to send");
+                            let s = WireMessage::response(self.id, too_large(e))?;
+                            core.send(s).await
+                        }
+                        r => r,
+                    }
                 })
                 .await?
             })
@@ -383,6 +479,7 @@ pub struct WebsocketReceiver(
     WsCoreSync,
     std::net::SocketAddr,
     tokio::task::JoinHandle<()>,
+    Option<String>,
 );
 
 impl Drop for WebsocketReceiver {
@@ -393,7 +490,7 @@ impl Drop for WebsocketReceiver {
 }
 
 impl WebsocketReceiver {
-    fn new(core: WsCoreSync, addr: std::net::SocketAddr) -> Self {
+    fn new(core: WsCoreSync, addr: std::net::SocketAddr, origin: Option<String>) -> Self {
         let core2 = core.clone();
         let ping_task = tokio::task::spawn(async move {
             loop {
@@ -416,7 +513,7 @@ impl WebsocketReceiver {
                 }
             }
         });
-        Self(core, addr, ping_task)
+        Self(core, addr, ping_task, origin)
     }
 
     /// Peer address.
@@ -424,6 +521,13 @@ impl WebsocketReceiver {
         self.1
     }
 
+    /// The `Origin` header sent by the remote when it connected.
+    ///
+    /// This is only known for connections accepted by a [WebsocketListener].
+    pub fn origin(&self) -> Option<&str> {
+        self.3.as_deref()
+    }
+
     /// Receive the next message.
     pub async fn recv<D>(&mut self) -> WebsocketResult<ReceiveMessage<D>>
     where
@@ -450,7 +554,7 @@ impl WebsocketReceiver {
             if let Some(result) = self
                 .0
                 .exec(move |core_sync, core| async move {
-                    let msg = core
+                    let msg = match core
                         .recv
                         .lock()
                         .await
@@ -458,7 +562,19 @@ impl WebsocketReceiver {
                         .await
                         .ok_or::<WebsocketError>(WebsocketError::Other(
                             "ReceiverClosed".to_string(),
-                        ))??;
+                        ))? {
+                        Err(tokio_tungstenite::tungstenite::Error::Capacity(
+                            tokio_tungstenite::tungstenite::error::CapacityError::MessageTooLong {
+                                size,
+                                max_size,
+                            },
+                        )) => {
+                            let err = core.reject_too_large(size, max_size).await;
+                            core_sync.close();
+                            return Err(err);
+                        }
+                        msg => msg?,
+                    };
                     let msg = match msg {
                         Message::Text(s) => s.into_bytes(),
                         Message::Binary(b) => b,
@@ -467,6 +583,12 @@ impl WebsocketReceiver {
                             return Ok(None);
                         }
                         Message::Pong(_) => return Ok(None),
+                        Message::Close(Some(frame)) if frame.code == CloseCode::Size => {
+                            return Err(WebsocketError::Close(format!(
+                                "The remote closed the connection because a message was too large: {}",
+                                frame.reason
+                            )));
+                        }
                         Message::Close(frame) => {
                             return Err(WebsocketError::Close(format!("{frame:?}")));
                         }
@@ -535,13 +657,11 @@ impl WebsocketSender {
         S: std::fmt::Debug,
         SerializedBytes: TryFrom<S, Error = SerializedBytesError>,
     {
-        use futures::sink::SinkExt;
         self.0
             .exec(move |_, core| async move {
                 tokio::time::timeout(timeout, async {
                     let s = WireMessage::authenticate(s)?;
-                    core.send.lock().await.send(s).await?;
-                    Ok(())
+                    core.send(s).await
                 })
                 .await?
             })
@@ -573,8 +693,6 @@ impl WebsocketSender {
     {
         let timeout_at = tokio::time::Instant::now() + timeout;
 
-        use futures::sink::SinkExt;
-
         let (s, id) = WireMessage::request(s)?;
 
         /// Drop helper to remove our response callback if we timeout.
@@ -599,7 +717,7 @@ impl WebsocketSender {
 
                 tokio::time::timeout_at(timeout_at, async move {
                     // send the actual message
-                    core.send.lock().await.send(s).await?;
+                    core.send(s).await?;
 
                     Ok(drop)
                 })
@@ -640,25 +758,33 @@ impl WebsocketSender {
         S: std::fmt::Debug,
         SerializedBytes: TryFrom<S, Error = SerializedBytesError>,
     {
-        use futures::sink::SinkExt;
         self.0
             .exec(move |_, core| async move {
                 tokio::time::timeout(timeout, async {
                     let s = WireMessage::signal(s)?;
-                    core.send.lock().await.send(s).await?;
-                    Ok(())
+                    core.send(s).await
                 })
                 .await?
             })
             .await
     }
+
+    /// Close the connection.
+    ///
+    /// Outstanding requests made on this connection fail, and the receiver side stops
+    /// receiving messages.
+    pub fn close(&self) {
+        self.0.close();
+    }
 }
 
 fn split(
     stream: WsStream,
-    timeout: std::time::Duration,
+    config: &WebsocketConfig,
     peer_addr: std::net::SocketAddr,
+    origin: Option<String>,
 ) -> WebsocketResult<(WebsocketSender, WebsocketReceiver)> {
+    let timeout = config.default_request_timeout;
     let (sink, stream) = futures::stream::StreamExt::split(stream);
 
     // Q: Why do we split the parts only to seemingly put them back together?
@@ -671,6 +797,7 @@ fn split(
         recv: Arc::new(tokio::sync::Mutex::new(stream)),
         rmap: RMap::default(),
         timeout,
+        max_send_size: config.max_send_size(),
     };
 
     let core_send = WsCoreSync(Arc::new(std::sync::Mutex::new(Some(core))));
@@ -678,7 +805,7 @@ fn split(
 
     Ok((
         WebsocketSender(core_send, timeout),
-        WebsocketReceiver::new(core_recv, peer_addr),
+        WebsocketReceiver::new(core_recv, peer_addr, origin),
     ))
 }
 
@@ -696,7 +823,7 @@ pub async fn connect(
         Some(config.as_tungstenite()),
     )
     .await?;
-    split(stream, config.default_request_timeout, peer_addr)
+    split(stream, &config, peer_addr, None)
 }
 
 /// A request to connect to a websocket server.
@@ -801,10 +928,36 @@ impl TcpListener for DualStackListener {
     }
 }
 
+/// The origins a [WebsocketListener] accepts connections from,
+/// which can be changed while it is listening.
+#[derive(Clone)]
+pub struct AllowedOriginsHandle(Arc<std::sync::RwLock<Arc<AllowedOrigins>>>);
+
+impl AllowedOriginsHandle {
+    fn new(allowed_origins: AllowedOrigins) -> Self {
+        Self(Arc::new(std::sync::RwLock::new(Arc::new(allowed_origins))))
+    }
+
+    /// The origins new connections are accepted from.
+    pub fn get(&self) -> AllowedOrigins {
+        self.current().as_ref().clone()
+    }
+
+    /// Accept new connections from these origins instead.
+    /// Connections which are already open are not affected.
+    pub fn set(&self, allowed_origins: AllowedOrigins) {
+        *self.0.write().unwrap() = Arc::new(allowed_origins);
+    }
+
+    fn current(&self) -> Arc<AllowedOrigins> {
+        self.0.read().unwrap().clone()
+    }
+}
+
 /// A Holochain websocket listener.
 pub struct WebsocketListener {
     config: Arc<WebsocketConfig>,
-    access_control: Arc<AllowedOrigins>,
+    access_control: AllowedOriginsHandle,
     listener: Box<dyn TcpListener>,
 }
 
@@ -817,9 +970,10 @@ impl Drop for WebsocketListener {
 impl WebsocketListener {
     /// Bind a new websocket listener.
     pub async fn bind(config: Arc<WebsocketConfig>, addr: impl ToSocketAddrs) -> Result<Self> {
-        let access_control = Arc::new(config.allowed_origins.clone().ok_or_else(|| {
-            Error::other("WebsocketListener requires allowed_origins to be set in the config")
-        })?);
+        let access_control =
+            AllowedOriginsHandle::new(config.allowed_origins.clone().ok_or_else(|| {
+                Error::other("WebsocketListener requires allowed_origins to be set in the config")
+            })?);
 
         let listener = tokio::net::TcpListener::bind(addr).await?;
 
@@ -854,9 +1008,10 @@ impl WebsocketListener {
         addr_v4: SocketAddrV4,
         addr_v6: SocketAddrV6,
     ) -> Result<Self> {
-        let access_control = Arc::new(config.allowed_origins.clone().ok_or_else(|| {
-            Error::other("WebsocketListener requires allowed_origins to be set in the config")
-        })?);
+        let access_control =
+            AllowedOriginsHandle::new(config.allowed_origins.clone().ok_or_else(|| {
+                Error::other("WebsocketListener requires allowed_origins to be set in the config")
+            })?);
 
         let addr_v6: SocketAddr = addr_v6.into();
         let mut addr_v4: SocketAddr = addr_v4.into();
@@ -942,25 +1097,35 @@ impl WebsocketListener {
         self.listener.local_addrs()
     }
 
+    /// Get a handle to change the origins this listener accepts connections from.
+    pub fn allowed_origins(&self) -> AllowedOriginsHandle {
+        self.access_control.clone()
+    }
+
     /// Accept an incoming connection.
     pub async fn accept(&self) -> WebsocketResult<(WebsocketSender, WebsocketReceiver)> {
         let (stream, addr) = self.listener.accept().await?;
         tracing::debug!(?addr, "Accept Incoming Websocket Connection");
+        let origin = Arc::new(std::sync::Mutex::new(None));
         let stream = tokio_tungstenite::accept_hdr_async_with_config(
             stream,
             ConnectCallback {
-                allowed_origin: self.access_control.clone(),
+                allowed_origin: self.access_control.current(),
+                origin: origin.clone(),
             },
             Some(self.config.as_tungstenite()),
         )
         .await
         .map_err(Error::other)?;
-        split(stream, self.config.default_request_timeout, addr)
+        let origin = origin.lock().unwrap().take();
+        split(stream, &self.config, addr, origin)
     }
 }
 
 struct ConnectCallback {
     allowed_origin: Arc<AllowedOrigins>,
+    /// The origin of an accepted connection request.
+    origin: Arc<std::sync::Mutex<Option<String>>>,
 }
 
 impl Callback for ConnectCallback {
@@ -981,6 +1146,7 @@ impl Callback for ConnectCallback {
         {
             Some(origin) => {
                 if self.allowed_origin.is_allowed(origin) {
+                    *self.origin.lock().unwrap() = Some(origin.to_string());
                     Ok(response)
                 } else {
                     tracing::warn!("Rejecting websocket connection request with disallowed `Origin` header: {:?}", request);
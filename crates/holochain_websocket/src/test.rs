@@ -407,3 +407,215 @@ async fn handle_client_close() {
         .expect("Error joining the signal sender task")
         .expect("Other error than WebsocketClosed while sending signals");
 }
+
+#[derive(Debug, serde::Serialize, serde::Deserialize, SerializedBytes, PartialEq)]
+enum SizedMsg {
+    Small,
+    Large(#[serde(with = "serde_bytes")] Vec<u8>),
+}
+
+fn large_msg() -> SizedMsg {
+    SizedMsg::Large(vec![0; 4096])
+}
+
+fn config_with_max_size(mut config: WebsocketConfig, max: usize) -> Arc<WebsocketConfig> {
+    config.max_message_size = max;
+    config.max_frame_size = max;
+    Arc::new(config)
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn sending_too_large_message_keeps_connection_open() {
+    holochain_trace::test_run();
+
+    let l = WebsocketListener::bind(Arc::new(WebsocketConfig::LISTENER_DEFAULT), "localhost:0")
+        .await
+        .unwrap();
+    let addr = l.local_addrs().unwrap()[0];
+
+    let l_task = tokio::task::spawn(async move {
+        let (_send, mut recv) = l.accept().await.unwrap();
+        match recv.recv::<SizedMsg>().await.unwrap() {
+            ReceiveMessage::Request(SizedMsg::Small, res) => {
+                res.respond(SizedMsg::Small).await.unwrap();
+            }
+            oth => panic!("unexpected: {oth:?}"),
+        }
+    });
+
+    let (send, mut recv) = connect(
+        config_with_max_size(WebsocketConfig::CLIENT_DEFAULT, 1024),
+        addr,
+    )
+    .await
+    .unwrap();
+    let r_task =
+        tokio::task::spawn(async move { while let Ok(_r) = recv.recv::<SizedMsg>().await {} });
+
+    match send.signal(large_msg()).await {
+        Err(WebsocketError::MessageTooLarge { size, max }) => {
+            assert!(size > 4096);
+            assert_eq!(1024, max);
+        }
+        oth => panic!("unexpected: {oth:?}"),
+    }
+
+    // The connection can still be used for messages which aren't too large.
+    let res: SizedMsg = send.request(SizedMsg::Small).await.unwrap();
+    assert_eq!(SizedMsg::Small, res);
+
+    l_task.await.unwrap();
+    r_task.abort();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn receiving_too_large_message_closes_connection_with_reason() {
+    holochain_trace::test_run();
+
+    let l = WebsocketListener::bind(
+        config_with_max_size(WebsocketConfig::LISTENER_DEFAULT, 1024),
+        "localhost:0",
+    )
+    .await
+    .unwrap();
+    let addr = l.local_addrs().unwrap()[0];
+
+    let l_task = tokio::task::spawn(async move {
+        let (_send, mut recv) = l.accept().await.unwrap();
+        match recv.recv::<SizedMsg>().await {
+            Err(WebsocketError::MessageTooLarge { size, max }) => {
+                assert!(size > 4096);
+                assert_eq!(1024, max);
+            }
+            oth => panic!("unexpected: {oth:?}"),
+        }
+    });
+
+    let (send, mut recv) = connect(Arc::new(WebsocketConfig::CLIENT_DEFAULT), addr)
+        .await
+        .unwrap();
+    send.signal(large_msg()).await.unwrap();
+
+    match recv.recv::<SizedMsg>().await {
+        Err(WebsocketError::Close(reason)) => {
+            assert!(reason.contains("too large"), "{reason}");
+        }
+        oth => panic!("unexpected: {oth:?}"),
+    }
+
+    l_task.await.unwrap();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn respond_or_sends_fallback_for_too_large_response() {
+    holochain_trace::test_run();
+
+    let l = WebsocketListener::bind(
+        config_with_max_size(WebsocketConfig::LISTENER_DEFAULT, 1024),
+        "localhost:0",
+    )
+    .await
+    .unwrap();
+    let addr = l.local_addrs().unwrap()[0];
+
+    let l_task = tokio::task::spawn(async move {
+        let (_send, mut recv) = l.accept().await.unwrap();
+        match recv.recv::<SizedMsg>().await.unwrap() {
+            ReceiveMessage::Request(SizedMsg::Small, res) => {
+                res.respond_or(large_msg(), |e| {
+                    assert!(matches!(e, WebsocketError::MessageTooLarge { .. }));
+                    SizedMsg::Small
+                })
+                .await
+                .unwrap();
+            }
+            oth => panic!("unexpected: {oth:?}"),
+        }
+    });
+
+    let (send, mut recv) = connect(Arc::new(WebsocketConfig::CLIENT_DEFAULT), addr)
+        .await
+        .unwrap();
+    let r_task =
+        tokio::task::spawn(async move { while let Ok(_r) = recv.recv::<SizedMsg>().await {} });
+
+    let res: SizedMsg = send.request(SizedMsg::Small).await.unwrap();
+    assert_eq!(SizedMsg::Small, res);
+
+    l_task.await.unwrap();
+    r_task.abort();
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn accepted_connection_reports_origin_and_can_be_closed() {
+    holochain_trace::test_run();
+
+    let l = WebsocketListener::bind(Arc::new(WebsocketConfig::LISTENER_DEFAULT), "localhost:0")
+        .await
+        .unwrap();
+    let addr = l.local_addrs().unwrap()[0];
+
+    let l_task = tokio::task::spawn(async move {
+        let (send, recv) = l.accept().await.unwrap();
+        assert_eq!(Some("test-origin"), recv.origin());
+        send.close();
+
+        // The receiver must be kept until the connection is closed.
+        recv
+    });
+
+    let request = ConnectRequest::new(addr)
+        .try_set_header("Origin", "test-origin")
+        .unwrap();
+    let (_send, mut recv) = connect(Arc::new(WebsocketConfig::CLIENT_DEFAULT), request)
+        .await
+        .unwrap();
+    assert_eq!(None, recv.origin());
+
+    let _recv = l_task.await.unwrap();
+
+    let res = tokio::time::timeout(std::time::Duration::from_secs(5), recv.recv::<SizedMsg>())
+        .await
+        .expect("Timeout waiting for the connection to close");
+    assert!(res.is_err());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn allowed_origins_can_be_changed_while_listening() {
+    holochain_trace::test_run();
+
+    let mut config = WebsocketConfig::LISTENER_DEFAULT;
+    config.allowed_origins = Some(AllowedOrigins::Origins(
+        ["http://example.com".to_string()].into_iter().collect(),
+    ));
+    let l = WebsocketListener::bind(Arc::new(config), "localhost:0")
+        .await
+        .unwrap();
+    let addr = l.local_addrs().unwrap()[0];
+    let allowed_origins = l.allowed_origins();
+
+    let request = || {
+        ConnectRequest::new(addr)
+            .try_set_header("Origin", "http://other.org")
+            .unwrap()
+    };
+
+    let (accepted, connected) = tokio::join!(
+        l.accept(),
+        connect(Arc::new(WebsocketConfig::CLIENT_DEFAULT), request())
+    );
+    assert!(accepted.is_err());
+    assert!(connected.is_err());
+
+    allowed_origins.set(AllowedOrigins::Origins(
+        ["http://other.org".to_string()].into_iter().collect(),
+    ));
+    assert!(allowed_origins.get().is_allowed("http://other.org"));
+
+    let (accepted, connected) = tokio::join!(
+        l.accept(),
+        connect(Arc::new(WebsocketConfig::CLIENT_DEFAULT), request())
+    );
+    assert_eq!(Some("http://other.org"), accepted.unwrap().1.origin());
+    connected.unwrap();
+}
@@ -7,6 +7,10 @@ pub struct GetAgentActivityInput {
     pub agent_pubkey: holo_hash::AgentPubKey,
     pub chain_query_filter: crate::query::ChainQueryFilter,
     pub activity_request: crate::query::ActivityRequest,
+    /// Only return a window of the agent's activity.
+    /// If this is `None` then all matching activity is returned.
+    #[serde(default)]
+    pub page: Option<AgentActivityPage>,
 }
 
 impl GetAgentActivityInput {
@@ -20,8 +24,103 @@ impl GetAgentActivityInput {
             agent_pubkey,
             chain_query_filter,
             activity_request,
+            page: None,
         }
     }
+
+    /// Only return the given window of the agent's activity.
+    pub fn with_page(mut self, page: AgentActivityPage) -> Self {
+        self.page = Some(page);
+        self
+    }
+}
+
+/// A window onto an agent's activity.
+///
+/// Following a long chain by repeatedly asking for all of its activity means
+/// transferring the whole chain every time. Instead, ask for a page of
+/// activity, then ask for the next page with `after_seq` set to the highest
+/// sequence number returned so far. The [`HighestObserved`] action in the
+/// response tells you whether there is more to fetch.
+///
+/// Pages are applied by the authority after the [`ChainQueryFilter`], and
+/// all set bounds must hold for an action to be returned.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AgentActivityPage {
+    /// Only return actions with a sequence number greater than this.
+    pub after_seq: Option<u32>,
+    /// Only return actions with a sequence number less than or equal to this.
+    pub until_seq: Option<u32>,
+    /// Only return actions authored at or after this time.
+    pub since: Option<Timestamp>,
+    /// Only return actions authored before this time.
+    pub before: Option<Timestamp>,
+    /// Return at most this many valid actions, and at most this many
+    /// rejected actions, starting from the lowest sequence number.
+    pub limit: Option<u32>,
+}
+
+impl AgentActivityPage {
+    /// Create a page that returns at most `limit` actions.
+    pub fn new(limit: u32) -> Self {
+        Self {
+            limit: Some(limit),
+            ..Default::default()
+        }
+    }
+
+    /// Only return actions after this sequence number.
+    pub fn after_seq(mut self, seq: u32) -> Self {
+        self.after_seq = Some(seq);
+        self
+    }
+
+    /// Only return actions up to and including this sequence number.
+    pub fn until_seq(mut self, seq: u32) -> Self {
+        self.until_seq = Some(seq);
+        self
+    }
+
+    /// Only return actions authored in `[since, before)`.
+    pub fn time_range(mut self, since: Option<Timestamp>, before: Option<Timestamp>) -> Self {
+        self.since = since;
+        self.before = before;
+        self
+    }
+
+    /// Apply every bound of this page to actions sorted by ascending sequence number.
+    pub fn apply<T: ActionHashedContainer>(&self, actions: Vec<T>) -> Vec<T> {
+        let actions = if self.since.is_some() || self.before.is_some() {
+            actions
+                .into_iter()
+                .filter(|a| {
+                    let timestamp = a.action().timestamp();
+                    self.since.map_or(true, |since| timestamp >= since)
+                        && self.before.map_or(true, |before| timestamp < before)
+                })
+                .collect()
+        } else {
+            actions
+        };
+        self.apply_seq_bounds(actions)
+    }
+
+    /// Apply the sequence bounds and limit of this page, but not the time
+    /// range, to actions sorted by ascending sequence number.
+    ///
+    /// This is for when only the sequence numbers of actions are known.
+    pub fn apply_seq_bounds<T: ActionSequenceAndHash>(&self, actions: Vec<T>) -> Vec<T> {
+        let limit = self.limit.map_or(usize::MAX, |l| l as usize);
+        actions
+            .into_iter()
+            .filter(|a| {
+                let seq = a.action_seq();
+                self.after_seq.map_or(true, |after| seq > after)
+                    && self.until_seq.map_or(true, |until| seq <= until)
+            })
+            .take(limit)
+            .collect()
+    }
 }
 
 /// Query arguments for the deterministic version of GetAgentActivity
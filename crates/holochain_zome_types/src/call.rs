@@ -16,6 +16,9 @@ pub enum CallTargetCell {
 pub enum CallTarget {
     NetworkAgent(AgentPubKey),
     ConductorCell(CallTargetCell),
+    /// The cell of a remote agent in any DNA, which the conductor calls through the
+    /// cell which the app of the calling cell has in that DNA, as the same agent.
+    NetworkCell(CellId),
 }
 
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
@@ -65,6 +68,20 @@ impl Call {
     }
 }
 
+/// A read-only source chain query against another cell of the calling agent
+/// on the same conductor.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct QueryCellInput {
+    pub target: CallTargetCell,
+    pub filter: ChainQueryFilter,
+}
+
+impl QueryCellInput {
+    pub fn new(target: CallTargetCell, filter: ChainQueryFilter) -> Self {
+        Self { target, filter }
+    }
+}
+
 #[allow(missing_docs)]
 pub trait CallbackResult: Sized {
     /// if a callback result is definitive we should halt any further iterations over remaining
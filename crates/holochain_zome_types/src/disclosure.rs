@@ -0,0 +1,53 @@
+//! Proofs about private entries which don't reveal their content
+
+use crate::prelude::*;
+
+/// Input to `create_private_entry_proof`.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct PrivateEntryProofInput {
+    /// The hash of a private entry on the calling agent's source chain.
+    pub entry_hash: EntryHash,
+    /// Bytes chosen by whoever asked for the proof, which are signed into it so that
+    /// the proof can't be replayed in answer to another request.
+    pub challenge: Bytes,
+}
+
+/// A proof that an agent authored a private entry, which discloses the entry's hash
+/// and type but not its content.
+///
+/// The proof is made of the signed action which created the entry, which is public anyway,
+/// and a signature by the same agent over the action hash and a challenge.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct PrivateEntryProof {
+    /// The signed action which created the private entry.
+    pub signed_action: SignedActionHashed,
+    /// The challenge from the [`PrivateEntryProofInput`].
+    pub challenge: Bytes,
+    /// The signature of the action's author over [`PrivateEntryProof::challenge_data`].
+    pub challenge_signature: Signature,
+}
+
+impl PrivateEntryProof {
+    /// The data which is signed by the author to answer a challenge about an action.
+    pub fn challenge_data(
+        action_hash: &ActionHash,
+        challenge: &Bytes,
+    ) -> Result<Vec<u8>, SerializedBytesError> {
+        holochain_serialized_bytes::encode(&(action_hash, challenge))
+    }
+
+    /// The agent which authored the entry.
+    pub fn author(&self) -> &AgentPubKey {
+        self.signed_action.action().author()
+    }
+
+    /// The hash of the entry, if the action created one.
+    pub fn entry_hash(&self) -> Option<&EntryHash> {
+        self.signed_action.action().entry_hash()
+    }
+
+    /// The type of the entry, if the action created one.
+    pub fn entry_type(&self) -> Option<&EntryType> {
+        self.signed_action.action().entry_type()
+    }
+}
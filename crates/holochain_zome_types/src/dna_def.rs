@@ -72,6 +72,104 @@ pub struct DnaDef {
     #[serde(default)]
     #[cfg_attr(feature = "full-dna-def", builder(default))]
     pub lineage: HashSet<DnaHash>,
+
+    /// Limits on the size of entries and link tags which can be committed to this DNA.
+    ///
+    /// The limits are included in the DNA hash computation, so that every agent
+    /// in the network validates with the same limits.
+    #[serde(default)]
+    #[cfg_attr(feature = "full-dna-def", builder(default))]
+    pub size_limits: PayloadSizeLimits,
+
+    /// How many validation receipts an op authored on this DNA needs before it stops
+    /// being republished. This applies to every op, including the ops of app entries
+    /// whose entry def sets `required_validations`.
+    ///
+    /// If this isn't set, entry defs and then the conductor's default decide.
+    /// Does not affect the [`DnaHash`], since it only changes what an author does with
+    /// their own ops.
+    #[serde(default)]
+    #[cfg_attr(feature = "full-dna-def", builder(default))]
+    pub required_validation_receipts: Option<u8>,
+
+    /// The entry and link types whose ops are validated ahead of the ops of other types.
+    ///
+    /// Does not affect the [`DnaHash`], since it only changes the order in which each
+    /// conductor works through the ops it holds.
+    #[serde(default)]
+    #[cfg_attr(feature = "full-dna-def", builder(default))]
+    pub interactive_types: InteractiveTypes,
+
+    /// The entry types whose content is indexed for full-text queries of the data which
+    /// a cell holds locally.
+    ///
+    /// Does not affect the [`DnaHash`], since it only changes what each conductor
+    /// indexes of the entries it holds.
+    #[serde(default)]
+    #[cfg_attr(feature = "full-dna-def", builder(default))]
+    pub fulltext_entry_types: Vec<ZomeTypeIndex>,
+}
+
+/// App-defined limits on the size of the data which can be committed to a DNA, in bytes.
+///
+/// A limit which isn't set, or which is larger than the limit Holochain applies to every DNA,
+/// is replaced by Holochain's limit.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct PayloadSizeLimits {
+    /// The largest app entry which can be committed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_entry_size: Option<u32>,
+
+    /// The largest link tag which can be committed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_link_tag_size: Option<u32>,
+}
+
+impl PayloadSizeLimits {
+    /// Whether no limits are set, so only Holochain's limits apply.
+    pub fn is_unset(&self) -> bool {
+        self.max_entry_size.is_none() && self.max_link_tag_size.is_none()
+    }
+}
+
+/// The entry and link types of a DNA whose data users are waiting to see, so that their
+/// ops are validated ahead of the ops of other types, such as agent activity. Without
+/// them, fresh data waits behind everything which arrived before it, which is a long
+/// wait while a cell is catching up with the DHT.
+///
+/// A type is given by the name of the integrity zome which defines it and its index in
+/// that zome, which is the position of its variant in the zome's entry or link types.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct InteractiveTypes {
+    /// The entry types whose creates and updates are interactive.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub entry_types: Vec<ZomeTypeIndex>,
+
+    /// The link types whose created links are interactive.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub link_types: Vec<ZomeTypeIndex>,
+}
+
+/// An entry or link type of an integrity zome, by its index in the zome.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+#[serde(rename_all = "snake_case", deny_unknown_fields)]
+pub struct ZomeTypeIndex {
+    /// The name of the integrity zome which defines the type.
+    pub zome: ZomeName,
+    /// The index of the type in the zome.
+    pub index: u8,
+}
+
+impl InteractiveTypes {
+    /// Whether no types are interactive, so ops are validated in the order they arrived.
+    pub fn is_empty(&self) -> bool {
+        self.entry_types.is_empty() && self.link_types.is_empty()
+    }
 }
 
 #[cfg(feature = "full-dna-def")]
@@ -80,6 +178,14 @@ pub struct DnaDef {
 struct DnaDefHash<'a> {
     modifiers: &'a DnaModifiers,
     integrity_zomes: &'a IntegrityZomes,
+    // Skipped when unset so that the hashes of DNAs without limits don't change.
+    #[serde(skip_serializing_if = "size_limits_unset")]
+    size_limits: &'a PayloadSizeLimits,
+}
+
+#[cfg(feature = "full-dna-def")]
+fn size_limits_unset(size_limits: &&PayloadSizeLimits) -> bool {
+    size_limits.is_unset()
 }
 
 #[cfg(feature = "test_utils")]
@@ -113,6 +219,99 @@ impl DnaDef {
                     .map(|(n, def)| (n, def.as_any_zome_def())),
             )
     }
+
+    /// Get all zomes in the order their `init` callbacks should run.
+    ///
+    /// Integrity zomes come first in declaration order, followed by the
+    /// coordinator zomes. A coordinator zome runs after every zome it lists in
+    /// its init dependencies, otherwise declaration order is preserved.
+    pub fn init_order(&self) -> Result<Vec<(&ZomeName, &ZomeDef)>, crate::zome::ZomeError> {
+        let mut ordered: Vec<(&ZomeName, &ZomeDef)> = self
+            .integrity_zomes
+            .iter()
+            .map(|(n, def)| (n, def.as_any_zome_def()))
+            .collect();
+        for (name, def) in self.coordinator_zomes.iter() {
+            if let Some(missing) = def
+                .as_any_zome_def()
+                .init_dependencies()
+                .iter()
+                .find(|dep| !self.all_zomes().any(|(n, _)| n == *dep))
+            {
+                return Err(crate::zome::ZomeError::InitDependencyNotFound(
+                    name.clone(),
+                    missing.clone(),
+                ));
+            }
+        }
+
+        let mut remaining: Vec<(&ZomeName, &ZomeDef)> = self
+            .coordinator_zomes
+            .iter()
+            .map(|(n, def)| (n, def.as_any_zome_def()))
+            .collect();
+        while !remaining.is_empty() {
+            let next = remaining.iter().position(|(_, def)| {
+                def.init_dependencies()
+                    .iter()
+                    .all(|dep| ordered.iter().any(|(n, _)| *n == dep))
+            });
+            match next {
+                Some(i) => ordered.push(remaining.remove(i)),
+                None => {
+                    return Err(crate::zome::ZomeError::InitDependencyCycle(
+                        remaining.into_iter().map(|(n, _)| n.clone()).collect(),
+                    ))
+                }
+            }
+        }
+        Ok(ordered)
+    }
+
+    /// Whether the ops of an action are of one of the [`InteractiveTypes`] of this DNA.
+    ///
+    /// Deleted entries and links don't carry their types, so only creates and updates
+    /// of entries and created links can be interactive.
+    pub fn is_interactive(&self, action: &Action) -> bool {
+        if self.interactive_types.is_empty() {
+            return false;
+        }
+        let (types, zome_index, index) = match action {
+            Action::CreateLink(create_link) => (
+                &self.interactive_types.link_types,
+                create_link.zome_index,
+                create_link.link_type.0,
+            ),
+            _ => match action.entry_type() {
+                Some(EntryType::App(app_entry_def)) => (
+                    &self.interactive_types.entry_types,
+                    app_entry_def.zome_index,
+                    app_entry_def.entry_index.0,
+                ),
+                _ => return false,
+            },
+        };
+        let Some((zome_name, _)) = self.integrity_zomes.get(zome_index.0 as usize) else {
+            return false;
+        };
+        types
+            .iter()
+            .any(|ty| ty.index == index && &ty.zome == zome_name)
+    }
+
+    /// Whether the content of entries of an app entry type is indexed for full-text
+    /// queries, because it's one of the [`DnaDef::fulltext_entry_types`].
+    pub fn is_fulltext_indexed(&self, app_entry_def: &AppEntryDef) -> bool {
+        let Some((zome_name, _)) = self
+            .integrity_zomes
+            .get(app_entry_def.zome_index.0 as usize)
+        else {
+            return false;
+        };
+        self.fulltext_entry_types
+            .iter()
+            .any(|ty| ty.index == app_entry_def.entry_index.0 && &ty.zome == zome_name)
+    }
 }
 
 #[cfg(feature = "full-dna-def")]
@@ -295,6 +494,7 @@ impl HashableContent for DnaDef {
         let hash = DnaDefHash {
             modifiers: &self.modifiers,
             integrity_zomes: &self.integrity_zomes,
+            size_limits: &self.size_limits,
         };
         HashableContentBytes::Content(
             holochain_serialized_bytes::UnsafeBytes::from(
@@ -344,4 +544,117 @@ mod tests {
 
         assert_eq!(mods.update(opt), expected);
     }
+
+    fn dna_with_init_dependencies(coordinators: Vec<(&str, Vec<&str>)>) -> DnaDef {
+        let zome_def = |i: u8| ZomeDef::from_hash(WasmHash::from_raw_32(vec![i; 32]));
+        let coordinator_zomes = coordinators
+            .into_iter()
+            .enumerate()
+            .map(|(i, (name, deps))| {
+                let mut def = CoordinatorZomeDef::from(zome_def(i as u8 + 1));
+                for dep in deps {
+                    def.set_init_dependency(dep);
+                }
+                (name.into(), def)
+            })
+            .collect();
+        DnaDef {
+            name: "init order".to_string(),
+            modifiers: DnaModifiers {
+                network_seed: "seed".into(),
+                properties: ().try_into().unwrap(),
+                origin_time: Timestamp::HOLOCHAIN_EPOCH,
+                quantum_time: STANDARD_QUANTUM_TIME,
+            },
+            integrity_zomes: vec![("integrity".into(), zome_def(0).into())],
+            coordinator_zomes,
+            lineage: Default::default(),
+            size_limits: Default::default(),
+            required_validation_receipts: None,
+            interactive_types: Default::default(),
+            fulltext_entry_types: Default::default(),
+        }
+    }
+
+    #[test]
+    fn init_order_respects_dependencies() {
+        let dna = dna_with_init_dependencies(vec![
+            ("a", vec!["c"]),
+            ("b", vec![]),
+            ("c", vec!["integrity", "b"]),
+        ]);
+        let order: Vec<_> = dna
+            .init_order()
+            .unwrap()
+            .into_iter()
+            .map(|(n, _)| n.0.to_string())
+            .collect();
+        assert_eq!(order, vec!["integrity", "b", "c", "a"]);
+    }
+
+    #[test]
+    fn init_order_rejects_cycles_and_unknown_zomes() {
+        let dna =
+            dna_with_init_dependencies(vec![("a", vec!["b"]), ("b", vec!["a"]), ("c", vec![])]);
+        assert!(matches!(
+            dna.init_order(),
+            Err(ZomeError::InitDependencyCycle(zomes)) if zomes == vec!["a".into(), "b".into()]
+        ));
+
+        let dna = dna_with_init_dependencies(vec![("a", vec!["missing"])]);
+        assert!(matches!(
+            dna.init_order(),
+            Err(ZomeError::InitDependencyNotFound(..))
+        ));
+    }
+
+    #[test]
+    fn interactive_types_are_matched_by_zome_and_index() {
+        let mut dna = dna_with_init_dependencies(vec![]);
+        let create = |entry_index: u8| {
+            Action::Create(Create {
+                author: AgentPubKey::from_raw_36(vec![0; 36]),
+                timestamp: Timestamp::HOLOCHAIN_EPOCH,
+                action_seq: 4,
+                prev_action: ActionHash::from_raw_36(vec![0; 36]),
+                entry_type: EntryType::App(AppEntryDef::new(
+                    entry_index.into(),
+                    0.into(),
+                    EntryVisibility::Public,
+                )),
+                entry_hash: EntryHash::from_raw_36(vec![0; 36]),
+                weight: Default::default(),
+            })
+        };
+        assert!(!dna.is_interactive(&create(1)));
+
+        dna.interactive_types.entry_types.push(ZomeTypeIndex {
+            zome: "integrity".into(),
+            index: 1,
+        });
+        assert!(dna.is_interactive(&create(1)));
+        assert!(!dna.is_interactive(&create(0)));
+    }
+
+    #[test]
+    fn fulltext_entry_types_are_matched_by_zome_and_index() {
+        let mut dna = dna_with_init_dependencies(vec![]);
+        let entry_def = |entry_index: u8, zome_index: u8| {
+            AppEntryDef::new(
+                entry_index.into(),
+                zome_index.into(),
+                EntryVisibility::Public,
+            )
+        };
+        assert!(!dna.is_fulltext_indexed(&entry_def(1, 0)));
+
+        dna.fulltext_entry_types.push(ZomeTypeIndex {
+            zome: "integrity".into(),
+            index: 1,
+        });
+        assert!(dna.is_fulltext_indexed(&entry_def(1, 0)));
+        assert!(!dna.is_fulltext_indexed(&entry_def(0, 0)));
+        // - There is no second integrity zome.
+        assert!(!dna.is_fulltext_indexed(&entry_def(1, 1)));
+    }
 }
@@ -75,6 +75,16 @@ impl GetOptions {
             strategy: GetStrategy::Local,
         }
     }
+    /// Fetch the latest metadata from `authorities` authorities at once,
+    /// even if the current agent is an authority for this hash,
+    /// and merge all of their responses.
+    ///
+    /// Fails if fewer than `authorities` authorities respond.
+    pub fn quorum(authorities: u8) -> Self {
+        Self {
+            strategy: GetStrategy::Quorum(authorities),
+        }
+    }
 }
 
 impl Default for GetOptions {
@@ -96,6 +106,13 @@ pub enum GetStrategy {
     /// Gets the action/entry and its metadata from local databases only.
     /// No network call is made.
     Local,
+    /// Fetch the latest metadata from the given number of authorities at once,
+    /// even if the current agent is an authority for this hash,
+    /// and merge all of their responses with the locally held metadata.
+    ///
+    /// This gives the freshest view of the data, at the cost of waiting for
+    /// several authorities to respond. Fails if fewer authorities respond.
+    Quorum(u8),
 }
 
 /// Zome input to create an entry.
@@ -144,6 +161,70 @@ impl AsRef<crate::Entry> for CreateInput {
     }
 }
 
+/// Zome input to split a blob which is too large for one entry into [`Chunk`] entries,
+/// and create them along with a [`ChunkManifest`] entry which they're read from.
+#[derive(PartialEq, Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct CreateChunkedInput {
+    /// The type of the chunk entries.
+    pub chunk_location: EntryDefLocation,
+    /// The type of the manifest entry.
+    pub manifest_location: EntryDefLocation,
+    /// The visibility of the chunk and manifest entries.
+    pub entry_visibility: EntryVisibility,
+    /// The blob.
+    #[serde(with = "serde_bytes")]
+    pub bytes: Vec<u8>,
+    /// The size of every chunk but the last, at most [`MAX_CHUNK_SIZE`].
+    pub chunk_size: u32,
+    /// ChainTopBehaviour for the writes.
+    pub chain_top_ordering: ChainTopOrdering,
+}
+
+impl CreateChunkedInput {
+    /// Constructor, with chunks of [`DEFAULT_CHUNK_SIZE`].
+    pub fn new(
+        chunk_location: impl Into<EntryDefLocation>,
+        manifest_location: impl Into<EntryDefLocation>,
+        entry_visibility: EntryVisibility,
+        bytes: Vec<u8>,
+    ) -> Self {
+        Self {
+            chunk_location: chunk_location.into(),
+            manifest_location: manifest_location.into(),
+            entry_visibility,
+            bytes,
+            chunk_size: DEFAULT_CHUNK_SIZE,
+            chain_top_ordering: ChainTopOrdering::default(),
+        }
+    }
+}
+
+/// Zome input to read a blob which was split into chunks, or a range of its bytes.
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+pub struct GetChunkedInput {
+    /// The hash of the manifest entry, or of the action which created it.
+    pub manifest_hash: holo_hash::AnyDhtHash,
+    /// The bytes to read, or the whole blob if this is `None`. Only the chunks which
+    /// hold these bytes are fetched, so that a large blob can be read piece by piece.
+    pub range: Option<std::ops::Range<u64>>,
+    /// Options for getting the manifest and its chunks.
+    pub get_options: crate::entry::GetOptions,
+}
+
+impl GetChunkedInput {
+    /// Constructor, to read the whole blob.
+    pub fn new(
+        manifest_hash: impl Into<holo_hash::AnyDhtHash>,
+        get_options: crate::entry::GetOptions,
+    ) -> Self {
+        Self {
+            manifest_hash: manifest_hash.into(),
+            range: None,
+            get_options,
+        }
+    }
+}
+
 /// Zome input for get and get_details calls.
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct GetInput {
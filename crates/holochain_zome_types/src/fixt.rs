@@ -743,6 +743,10 @@ fixturator!(
             .next()
             .unwrap(),
         lineage: Default::default(),
+        size_limits: Default::default(),
+        required_validation_receipts: None,
+        interactive_types: Default::default(),
+        fulltext_entry_types: Default::default(),
     };
 
     curve Unpredictable DnaDef {
@@ -767,6 +771,10 @@ fixturator!(
             .unwrap(),
         // TODO: non-empty lineage
         lineage: Default::default(),
+        size_limits: Default::default(),
+        required_validation_receipts: None,
+        interactive_types: Default::default(),
+        fulltext_entry_types: Default::default(),
     };
 
     curve Predictable DnaDef {
@@ -791,6 +799,10 @@ fixturator!(
             .unwrap(),
         // TODO: non-empty lineage
         lineage: Default::default(),
+        size_limits: Default::default(),
+        required_validation_receipts: None,
+        interactive_types: Default::default(),
+        fulltext_entry_types: Default::default(),
     };
 );
 
@@ -0,0 +1,36 @@
+//! Full-text queries of the entries which a cell holds
+
+use crate::prelude::*;
+
+/// A full-text query of the entries of the full-text entry types of a DNA, made with
+/// `query_local_fulltext`.
+///
+/// Only the entries which the conductor holds in its own DHT database are searched,
+/// so the results depend on which part of the DHT the cell is an authority for.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct FullTextQuery {
+    /// The query in the [FTS5 query syntax](https://www.sqlite.org/fts5.html#full_text_query_syntax),
+    /// e.g. `"peer AND network*"`.
+    pub query: String,
+    /// The maximum number of entries to return.
+    pub limit: u32,
+}
+
+impl FullTextQuery {
+    /// The number of entries which are returned unless a limit is set.
+    pub const DEFAULT_LIMIT: u32 = 20;
+
+    /// Constructor with the [`FullTextQuery::DEFAULT_LIMIT`].
+    pub fn new(query: impl Into<String>) -> Self {
+        Self {
+            query: query.into(),
+            limit: Self::DEFAULT_LIMIT,
+        }
+    }
+
+    /// Set the maximum number of entries to return.
+    pub fn with_limit(mut self, limit: u32) -> Self {
+        self.limit = limit;
+        self
+    }
+}
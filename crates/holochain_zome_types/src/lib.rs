@@ -15,7 +15,7 @@ pub mod action;
 pub mod agent_activity;
 #[allow(missing_docs)]
 pub mod block;
-pub mod bytes;
+pub use holochain_integrity_types::bytes;
 #[allow(missing_docs)]
 pub mod call;
 pub mod capability;
@@ -26,10 +26,12 @@ pub mod clone;
 pub mod countersigning;
 #[allow(missing_docs)]
 pub mod crdt;
+pub mod disclosure;
 pub mod dna_def;
 pub mod entry;
 #[allow(missing_docs)]
 pub mod entry_def;
+pub mod fulltext;
 pub mod genesis;
 #[allow(missing_docs)]
 pub mod hash;
@@ -41,9 +43,12 @@ pub mod judged;
 #[allow(missing_docs)]
 pub mod link;
 pub mod metadata;
+pub mod metric;
 #[allow(missing_docs)]
 pub mod op;
+pub mod outbox;
 pub mod prelude;
+pub mod presence;
 #[cfg(feature = "properties")]
 pub mod properties;
 pub mod query;
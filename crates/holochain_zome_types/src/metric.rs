@@ -0,0 +1,41 @@
+//! App-defined metrics
+
+use crate::prelude::*;
+
+/// A measurement emitted by an app via `emit_metric`.
+///
+/// The conductor records it alongside its own metrics, tagged with the app, DNA,
+/// agent and zome that emitted it, so apps can observe domain-level activity
+/// without building their own telemetry channel.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct AppMetric {
+    /// The name of the metric, e.g. `"messages_sent"`, which is recorded as the
+    /// `hc.app.<name>` histogram.
+    ///
+    /// It must start with a letter and contain only ASCII letters, digits, `_` and `.`.
+    pub name: String,
+    /// The measured value.
+    pub value: f64,
+    /// Extra key/value pairs to record with the measurement.
+    ///
+    /// The keys `app`, `dna`, `agent` and `zome` are reserved for the
+    /// conductor and cannot be used.
+    pub tags: Vec<(String, String)>,
+}
+
+impl AppMetric {
+    /// Constructor without any tags.
+    pub fn new(name: impl Into<String>, value: f64) -> Self {
+        Self {
+            name: name.into(),
+            value,
+            tags: Vec::new(),
+        }
+    }
+
+    /// Add a tag to record with the measurement.
+    pub fn with_tag(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.tags.push((key.into(), value.into()));
+        self
+    }
+}
@@ -0,0 +1,83 @@
+//! Remote calls and signals which are queued by the conductor until the
+//! recipient can be reached.
+//!
+//! Unlike [`crate::call::Call`] and [`crate::signal::RemoteSignal`], a queued message
+//! to an agent who is offline isn't lost. The conductor keeps it until it is delivered
+//! or its TTL runs out, and retries whenever the agent comes back online.
+//!
+//! Messages are delivered at least once. An attempt which times out is retried even
+//! if the recipient handled it, so a message may be handled more than once.
+
+use crate::prelude::*;
+use holo_hash::AgentPubKey;
+
+/// A message to send to another agent, which is queued until it is delivered
+/// or `ttl_ms` has passed.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct QueuedRemoteMessage {
+    /// The agent to send the message to.
+    pub agent: AgentPubKey,
+    /// The message.
+    pub message: RemoteMessage,
+    /// How long, in milliseconds, the conductor keeps trying to deliver the message.
+    pub ttl_ms: u64,
+}
+
+/// A message which can be queued for another agent.
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum RemoteMessage {
+    /// A signal, which is received by the `recv_remote_signal` function of the
+    /// same zome of the recipient.
+    Signal(ExternIO),
+    /// A call to a zome function of the recipient.
+    Call {
+        /// The zome to call.
+        zome_name: ZomeName,
+        /// The function to call.
+        fn_name: FunctionName,
+        /// The secret of the capability to call the function with.
+        cap_secret: Option<CapSecret>,
+        /// The payload of the call.
+        payload: ExternIO,
+    },
+}
+
+/// The identifier of a [`QueuedRemoteMessage`], which is used to look up its delivery.
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, serde::Serialize, serde::Deserialize,
+)]
+pub struct OutboxMessageId(pub u64);
+
+/// The delivery of a [`QueuedRemoteMessage`].
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub enum OutboxDelivery {
+    /// The message hasn't been delivered yet, and will be retried.
+    Pending {
+        /// How many attempts have been made.
+        attempts: u32,
+        /// Why the last attempt failed.
+        last_error: Option<String>,
+    },
+    /// The recipient handled the message.
+    Delivered {
+        /// The response to a [`RemoteMessage::Call`]. Signals have no response.
+        response: Option<ExternIO>,
+        /// How many attempts were made, including the successful one.
+        attempts: u32,
+    },
+    /// The recipient was reached but didn't accept the message, e.g. because it hasn't
+    /// granted access to the function. The message isn't retried.
+    Rejected(String),
+    /// The message couldn't be delivered before its TTL ran out.
+    Expired {
+        /// How many attempts were made.
+        attempts: u32,
+    },
+}
+
+impl OutboxDelivery {
+    /// Whether the message will still be retried.
+    pub fn is_pending(&self) -> bool {
+        matches!(self, Self::Pending { .. })
+    }
+}
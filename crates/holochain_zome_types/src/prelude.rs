@@ -11,9 +11,11 @@ pub use crate::chain::*;
 pub use crate::clone::*;
 pub use crate::countersigning::*;
 pub use crate::crdt::*;
+pub use crate::disclosure::*;
 pub use crate::dna_def::*;
 pub use crate::entry::*;
 pub use crate::entry_def::*;
+pub use crate::fulltext::*;
 pub use crate::genesis::*;
 pub use crate::hash::*;
 pub use crate::info::*;
@@ -21,7 +23,10 @@ pub use crate::init::*;
 pub use crate::judged::*;
 pub use crate::link::*;
 pub use crate::metadata::*;
+pub use crate::metric::*;
 pub use crate::op::*;
+pub use crate::outbox::*;
+pub use crate::presence::*;
 #[cfg(feature = "properties")]
 pub use crate::properties::*;
 pub use crate::query::ChainQueryFilter as QueryFilter;
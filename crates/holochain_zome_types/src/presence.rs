@@ -0,0 +1,47 @@
+//! The liveness of agents, which they announce to their neighborhood on the DHT.
+
+use crate::timestamp::Timestamp;
+use holo_hash::AgentPubKey;
+
+/// When an agent was last known to be online.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct AgentPresence {
+    /// The agent.
+    pub agent: AgentPubKey,
+    /// When the agent last announced that it was online.
+    pub last_seen: Timestamp,
+}
+
+impl AgentPresence {
+    /// Whether the agent was seen within `window` of `now`.
+    ///
+    /// Agents announce their presence every minute while they are online, so a window
+    /// of a few minutes tells whether an agent is online now.
+    pub fn seen_within(&self, now: Timestamp, window: std::time::Duration) -> bool {
+        let since = now
+            .as_micros()
+            .saturating_sub(self.last_seen.as_micros())
+            .max(0);
+        since as u128 <= window.as_micros()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn presence_is_judged_by_the_window() {
+        let presence = AgentPresence {
+            agent: AgentPubKey::from_raw_36(vec![0; 36]),
+            last_seen: Timestamp::from_micros(10_000_000),
+        };
+        let window = Duration::from_secs(60);
+        assert!(presence.seen_within(Timestamp::from_micros(10_000_000), window));
+        assert!(presence.seen_within(Timestamp::from_micros(70_000_000), window));
+        assert!(!presence.seen_within(Timestamp::from_micros(70_000_001), window));
+        // An announcement from a clock which is ahead still counts.
+        assert!(presence.seen_within(Timestamp::from_micros(0), window));
+    }
+}
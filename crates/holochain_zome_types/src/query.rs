@@ -106,6 +106,30 @@ pub struct LinkQuery {
     pub author: Option<AgentPubKey>,
 }
 
+/// The creation times of the links matching a [`LinkQuery`], without the links themselves.
+///
+/// This is much cheaper to fetch from authorities than the links when only the number of
+/// links or how recently they were created is needed.
+#[derive(
+    serde::Serialize, serde::Deserialize, SerializedBytes, Default, PartialEq, Clone, Debug,
+)]
+pub struct LinksSummary {
+    /// When each of the links was created, oldest first.
+    pub timestamps: Vec<Timestamp>,
+}
+
+impl LinksSummary {
+    /// The number of links.
+    pub fn count(&self) -> usize {
+        self.timestamps.len()
+    }
+
+    /// When the most recent link was created, if there are any links.
+    pub fn latest(&self) -> Option<Timestamp> {
+        self.timestamps.last().copied()
+    }
+}
+
 #[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
 /// An agents chain records returned from a agent_activity_query
 pub struct AgentActivity {
@@ -118,8 +142,8 @@ pub struct AgentActivity {
     /// The highest chain action that has
     /// been observed by this authority.
     pub highest_observed: Option<HighestObserved>,
-    /// Warrants about this AgentActivity.
-    /// Placeholder for future.
+    /// The valid warrants against this agent which are known to the authorities
+    /// of its activity, or to this conductor.
     pub warrants: Vec<Warrant>,
 }
 
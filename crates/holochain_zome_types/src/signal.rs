@@ -29,3 +29,78 @@ pub struct RemoteSignal {
     /// The signal to send.
     pub signal: ExternIO,
 }
+
+/// Remote signal many agents and wait for each of them to acknowledge receipt.
+///
+/// Unlike [`RemoteSignal`], which is fire-and-forget, each recipient is sent
+/// the signal individually and the outcome for every agent is reported back
+/// as a [`RemoteSignalDelivery`].
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize, SerializedBytes)]
+pub struct AckedRemoteSignal {
+    /// Agents to send the signal to.
+    pub agents: Vec<AgentPubKey>,
+    /// The signal to send.
+    pub signal: ExternIO,
+    /// How many times delivery to an agent is retried after the first attempt
+    /// times out or the agent cannot be reached.
+    pub retries: u8,
+    /// How long to wait for each attempt to be acknowledged.
+    /// If not set the host default is used.
+    pub timeout_ms: Option<u64>,
+}
+
+impl AckedRemoteSignal {
+    /// Constructor with no retries and the host default timeout.
+    pub fn new(agents: Vec<AgentPubKey>, signal: ExternIO) -> Self {
+        Self {
+            agents,
+            signal,
+            retries: 0,
+            timeout_ms: None,
+        }
+    }
+
+    /// Retry delivery this many times after the first failed attempt.
+    pub fn with_retries(mut self, retries: u8) -> Self {
+        self.retries = retries;
+        self
+    }
+
+    /// Wait this long for each attempt to be acknowledged.
+    pub fn with_timeout_ms(mut self, timeout_ms: u64) -> Self {
+        self.timeout_ms = Some(timeout_ms);
+        self
+    }
+}
+
+/// The outcome of delivering an [`AckedRemoteSignal`] to a single agent.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum RemoteSignalDeliveryStatus {
+    /// The recipient handled the signal.
+    Delivered,
+    /// The recipient was reached but did not accept the signal,
+    /// e.g. because it has not granted access to `recv_remote_signal`.
+    Rejected(String),
+    /// No acknowledgement was received before the timeout elapsed.
+    TimedOut,
+    /// The recipient could not be reached over the network.
+    Unreachable(String),
+}
+
+/// Delivery report for one recipient of an [`AckedRemoteSignal`].
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct RemoteSignalDelivery {
+    /// The agent the signal was sent to.
+    pub agent: AgentPubKey,
+    /// The status of the final attempt.
+    pub status: RemoteSignalDeliveryStatus,
+    /// How many attempts were made, including the first.
+    pub attempts: u8,
+}
+
+impl RemoteSignalDelivery {
+    /// Was the signal delivered to this agent?
+    pub fn is_delivered(&self) -> bool {
+        matches!(self.status, RemoteSignalDeliveryStatus::Delivered)
+    }
+}
@@ -99,6 +99,11 @@ impl CoordinatorZome {
     pub fn set_dependency(&mut self, zome_name: impl Into<ZomeName>) {
         self.def.set_dependency(zome_name);
     }
+
+    /// Require the init callback of another zome to run before this zome's.
+    pub fn set_init_dependency(&mut self, zome_name: impl Into<ZomeName>) {
+        self.def.set_init_dependency(zome_name);
+    }
 }
 
 impl From<(ZomeName, ZomeDef)> for Zome {
@@ -155,6 +160,13 @@ pub struct WasmZome {
     /// The zome dependencies
     pub dependencies: Vec<ZomeName>,
 
+    /// The zomes whose init callbacks must run before this zome's.
+    ///
+    /// Not serialized when empty, so that zomes without init dependencies
+    /// keep the same hash.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub init_dependencies: Vec<ZomeName>,
+
     /// DEPRECATED: Bundling precompiled and preserialized wasm for iOS is deprecated. Please use the wasm interpreter instead.
     ///
     /// The path to a preserialized wasmer module used as a "dynamic library" (dylib).
@@ -188,6 +200,7 @@ pub enum ZomeDef {
     Inline {
         inline_zome: self::inline_zome::DynInlineZome,
         dependencies: Vec<ZomeName>,
+        init_dependencies: Vec<ZomeName>,
     },
 }
 
@@ -240,6 +253,20 @@ impl CoordinatorZomeDef {
             ZomeDef::Inline { dependencies, .. } => dependencies.push(zome_name.into()),
         }
     }
+
+    /// Require the init callback of another zome to run before this zome's.
+    pub fn set_init_dependency(&mut self, zome_name: impl Into<ZomeName>) {
+        match &mut self.0 {
+            ZomeDef::Wasm(WasmZome {
+                init_dependencies, ..
+            }) => init_dependencies.push(zome_name.into()),
+
+            #[cfg(feature = "full-dna-def")]
+            ZomeDef::Inline {
+                init_dependencies, ..
+            } => init_dependencies.push(zome_name.into()),
+        }
+    }
 }
 
 #[cfg(feature = "full-dna-def")]
@@ -248,6 +275,7 @@ impl From<InlineIntegrityZome> for ZomeDef {
         Self::Inline {
             inline_zome: inline_zome::DynInlineZome(Arc::new(iz)),
             dependencies: Default::default(),
+            init_dependencies: Default::default(),
         }
     }
 }
@@ -258,6 +286,7 @@ impl From<InlineIntegrityZome> for IntegrityZomeDef {
         Self(ZomeDef::Inline {
             inline_zome: inline_zome::DynInlineZome(Arc::new(iz)),
             dependencies: Default::default(),
+            init_dependencies: Default::default(),
         })
     }
 }
@@ -268,6 +297,7 @@ impl From<crate::prelude::InlineCoordinatorZome> for ZomeDef {
         Self::Inline {
             inline_zome: inline_zome::DynInlineZome(Arc::new(iz)),
             dependencies: Default::default(),
+            init_dependencies: Default::default(),
         }
     }
 }
@@ -278,6 +308,7 @@ impl From<crate::prelude::InlineCoordinatorZome> for CoordinatorZomeDef {
         Self(ZomeDef::Inline {
             inline_zome: inline_zome::DynInlineZome(Arc::new(iz)),
             dependencies: Default::default(),
+            init_dependencies: Default::default(),
         })
     }
 }
@@ -306,6 +337,20 @@ impl ZomeDef {
             ZomeDef::Inline { dependencies, .. } => &dependencies[..],
         }
     }
+
+    /// Get the zomes whose `init` callbacks must run before this zome's.
+    pub fn init_dependencies(&self) -> &[ZomeName] {
+        match self {
+            ZomeDef::Wasm(WasmZome {
+                init_dependencies, ..
+            }) => &init_dependencies[..],
+
+            #[cfg(feature = "full-dna-def")]
+            ZomeDef::Inline {
+                init_dependencies, ..
+            } => &init_dependencies[..],
+        }
+    }
 }
 
 impl IntegrityZomeDef {
@@ -359,6 +404,7 @@ impl WasmZome {
         Self {
             wasm_hash,
             dependencies: Default::default(),
+            init_dependencies: Default::default(),
             preserialized_path: None,
         }
     }
@@ -370,6 +416,7 @@ impl ZomeDef {
         Self::Wasm(WasmZome {
             wasm_hash,
             dependencies: Default::default(),
+            init_dependencies: Default::default(),
             preserialized_path: None,
         })
     }
@@ -11,6 +11,14 @@ pub enum ZomeError {
     #[error("Accessed a zome expecting to find a WasmZome, but found other type. Zome name: {0}")]
     NonWasmZome(ZomeName),
 
+    /// A zome declared an init dependency on a zome that is not in the DNA
+    #[error("Zome {0} declares an init dependency on zome {1}, which does not exist in this DNA")]
+    InitDependencyNotFound(ZomeName, ZomeName),
+
+    /// The declared init dependencies between zomes form a cycle
+    #[error("The init dependencies between these zomes form a cycle: {0:?}")]
+    InitDependencyCycle(Vec<ZomeName>),
+
     /// SerializedBytesError (can occur during DnaDef::update_modifiers)
     #[error(transparent)]
     SerializedBytesError(#[from] holochain_serialized_bytes::SerializedBytesError),
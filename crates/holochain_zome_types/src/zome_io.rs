@@ -68,9 +68,22 @@ wasm_io_types! {
     // Returns ActionHash of the newly created record.
     fn create (zt::entry::CreateInput) -> holo_hash::ActionHash;
 
+    // Create many records with one signing round-trip to the keystore.
+    fn create_entries (Vec<zt::entry::CreateInput>) -> Vec<holo_hash::ActionHash>;
+
+    // Split a blob into chunk entries and create them with the manifest entry which
+    // lists them. Returns the ActionHash of the manifest.
+    fn create_chunked (zt::entry::CreateChunkedInput) -> holo_hash::ActionHash;
+
+    // Prove authorship of a private entry without revealing its content.
+    fn create_private_entry_proof (zt::disclosure::PrivateEntryProofInput) -> zt::disclosure::PrivateEntryProof;
+
     // Create a link between two entries.
     fn create_link (zt::link::CreateLinkInput) -> holo_hash::ActionHash;
 
+    // Create many links with one signing round-trip to the keystore.
+    fn create_links (Vec<zt::link::CreateLinkInput>) -> Vec<holo_hash::ActionHash>;
+
     fn create_x25519_keypair(()) -> zt::x_salsa20_poly1305::x25519::X25519PubKey;
 
     // The debug host import takes a TraceMsg to output wherever the host wants to display it.
@@ -83,12 +96,21 @@ wasm_io_types! {
     // Delete a record.
     fn delete (zt::entry::DeleteInput) -> holo_hash::ActionHash;
 
+    // Pseudo-random bytes seeded by the op being validated, the same for every validator.
+    fn deterministic_random_bytes (u32) -> zt::bytes::Bytes;
+
+    // Record an app-defined measurement in the conductor's metrics.
+    fn emit_metric (zt::metric::AppMetric) -> ();
+
     // Action hash of the newly committed record.
     // Emit a Signal::App to subscribers on the interface
     fn emit_signal (zt::signal::AppSignal) -> ();
 
     fn get_agent_activity (zt::agent_activity::GetAgentActivityInput) -> zt::query::AgentActivity;
 
+    // When an agent last announced its presence to its neighborhood.
+    fn get_agent_presence (AgentPubKey) -> Option<zt::presence::AgentPresence>;
+
     // DPKI
     fn get_agent_key_lineage (AgentPubKey) -> Vec<AgentPubKey>;
 
@@ -101,9 +123,16 @@ wasm_io_types! {
 
     fn count_links(zt::query::LinkQuery) -> usize;
 
+    // Get the creation times of links without the links themselves.
+    fn get_links_summary(zt::query::LinkQuery) -> zt::query::LinksSummary;
+
     // Attempt to get a live entry from the cascade.
     fn get (Vec<zt::entry::GetInput>) -> Vec<Option<zt::record::Record>>;
 
+    // Read a blob, or a range of it, from its chunk entries, which are fetched in
+    // parallel and checked against the hashes in its manifest.
+    fn get_chunked (zt::entry::GetChunkedInput) -> Option<zt::bytes::Bytes>;
+
     // Hash data on the host.
     fn hash (zt::hash::HashInput) -> zt::hash::HashOutput;
 
@@ -115,23 +144,47 @@ wasm_io_types! {
     // Retreive a record from the DHT or short circuit.
     fn must_get_valid_record (zt::entry::MustGetValidRecordInput) -> zt::record::Record;
 
+    // Same as must_get_valid_record, with control over how long to wait for the network.
+    fn must_get_valid_record_with_options (zt::entry::MustGetValidRecordWithOptionsInput) -> zt::record::Record;
+
     // Retreive a entry from the DHT or short circuit.
     fn must_get_entry (zt::entry::MustGetEntryInput) -> zt::entry::EntryHashed;
 
+    // Same as must_get_entry, with control over how long to wait for the network.
+    fn must_get_entry_with_options (zt::entry::MustGetEntryWithOptionsInput) -> zt::entry::EntryHashed;
+
     // Retrieve an action from the DHT or short circuit.
     fn must_get_action (zt::entry::MustGetActionInput) -> zt::prelude::SignedActionHashed;
 
+    // Same as must_get_action, with control over how long to wait for the network.
+    fn must_get_action_with_options (zt::entry::MustGetActionWithOptionsInput) -> zt::prelude::SignedActionHashed;
+
     fn must_get_agent_activity (zt::chain::MustGetAgentActivityInput) -> Vec<zt::op::RegisterAgentActivity>;
 
     // Query the source chain for data.
     fn query (zt::query::ChainQueryFilter) -> Vec<crate::prelude::Record>;
 
+    // Query the source chain of another cell of the same agent.
+    fn query_cell (zt::call::QueryCellInput) -> Vec<crate::prelude::Record>;
+
+    // Query the text of the entries held locally.
+    fn query_local_fulltext (zt::fulltext::FullTextQuery) -> Vec<holo_hash::EntryHash>;
+
     // the length of random bytes to create
     fn random_bytes (u32) -> zt::bytes::Bytes;
 
     // Remotely signal many agents without waiting for responses
     fn send_remote_signal (zt::signal::RemoteSignal) -> ();
 
+    // Remotely signal many agents and report delivery status per agent
+    fn send_remote_signal_acked (zt::signal::AckedRemoteSignal) -> Vec<zt::signal::RemoteSignalDelivery>;
+
+    // Queue a remote call or signal until the recipient can be reached
+    fn queue_remote_message (zt::outbox::QueuedRemoteMessage) -> zt::outbox::OutboxMessageId;
+
+    // Look up the delivery of queued remote messages
+    fn outbox_delivery (Vec<zt::outbox::OutboxMessageId>) -> Vec<Option<zt::outbox::OutboxDelivery>>;
+
     // Schedule a schedulable function if it is not already.
     #[cfg(feature = "unstable-functions")]
     fn schedule (String) -> ();
@@ -152,6 +205,9 @@ wasm_io_types! {
     // Current system time, in the opinion of the host, as a `Timestamp`.
     fn sys_time (()) -> zt::timestamp::Timestamp;
 
+    // The timestamp of the action being validated.
+    fn validation_timestamp (()) -> zt::timestamp::Timestamp;
+
     // Same as  but also takes the ActionHash of the updated record.
     fn update (zt::entry::UpdateInput) -> holo_hash::ActionHash;
 
@@ -161,6 +217,9 @@ wasm_io_types! {
 
     fn verify_signature (zt::signature::VerifySignature) -> bool;
 
+    // Check the signatures of a proof about a private entry.
+    fn verify_private_entry_proof (zt::disclosure::PrivateEntryProof) -> bool;
+
     fn x_salsa20_poly1305_shared_secret_create_random(
         Option<zt::x_salsa20_poly1305::key_ref::XSalsa20Poly1305KeyRef>
     ) -> zt::x_salsa20_poly1305::key_ref::XSalsa20Poly1305KeyRef;
@@ -232,8 +291,21 @@ pub enum ZomeCallAuthorization {
     Authorized,
     BadSignature,
     BadCapGrant,
-    BadNonce(String),
+    BadNonce(NonceRejection),
     BlockedProvenance,
+    BadDelegation(String),
+}
+
+/// Why the nonce of a zome call was rejected.
+#[derive(PartialEq, Eq, Clone, Copy, Debug, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NonceRejection {
+    /// A call with the same nonce from the same provenance was already accepted.
+    Duplicate,
+    /// The call expired, according to the callee's clock.
+    Expired,
+    /// The call expires further in the future than the callee allows.
+    Future,
 }
 
 impl std::fmt::Display for ZomeCallAuthorization {
@@ -315,3 +387,162 @@ impl ZomeCallUnsigned {
         Ok(holo_hash::encode::blake2b_256(&holochain_serialized_bytes::encode(&self)?).into())
     }
 }
+
+/// The most delegations that the delegation chain of a zome call may have.
+pub const MAX_ZOME_CALL_DELEGATIONS: usize = 4;
+
+/// The right to sign zome calls on behalf of another agent, which that agent signed.
+///
+/// Delegations form a chain: the first is signed by the provenance of the call, each
+/// next one by the delegate of the one before it, and the call itself by the delegate
+/// of the last one. Every delegation of the chain must allow the call, and a chain
+/// has at most [`MAX_ZOME_CALL_DELEGATIONS`] delegations.
+///
+/// Each delegation is backed by a cap grant on the source chain of the called cell,
+/// which is assigned to the delegate. A delegation is only accepted while its grant
+/// is live, so deleting the grant revokes the delegation.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ZomeCallDelegation {
+    /// The key which may sign calls, or delegate signing them further.
+    pub delegate: AgentPubKey,
+    /// The cell which calls may be made to.
+    pub cell_id: CellId,
+    /// The functions which may be called.
+    pub functions: GrantedFunctions,
+    /// The secret of the cap grant which backs this delegation.
+    pub cap_secret: CapSecret,
+    /// Time after which this delegation MUST NOT be accepted.
+    pub expires_at: Timestamp,
+    /// The delegator's signature of [`ZomeCallDelegation::data_to_sign`].
+    pub signature: Signature,
+}
+
+impl ZomeCallDelegation {
+    /// Prepare the canonical bytes which a delegator signs, so that a delegation is
+    /// always signed and verified in the same way.
+    pub fn data_to_sign(
+        delegate: &AgentPubKey,
+        cell_id: &CellId,
+        functions: &GrantedFunctions,
+        cap_secret: &CapSecret,
+        expires_at: Timestamp,
+    ) -> Result<std::sync::Arc<[u8]>, SerializedBytesError> {
+        Ok(
+            holo_hash::encode::blake2b_256(&holochain_serialized_bytes::encode(&(
+                "zome_call_delegation",
+                delegate,
+                cell_id,
+                functions,
+                cap_secret,
+                expires_at,
+            ))?)
+            .into(),
+        )
+    }
+
+    /// Whether this delegation allows a call to a function of a cell at `now`.
+    pub fn allows(
+        &self,
+        cell_id: &CellId,
+        zome_name: &ZomeName,
+        fn_name: &FunctionName,
+        now: Timestamp,
+    ) -> bool {
+        now < self.expires_at
+            && &self.cell_id == cell_id
+            && match &self.functions {
+                GrantedFunctions::All => true,
+                GrantedFunctions::Listed(functions) => {
+                    functions.contains(&(zome_name.clone(), fn_name.clone()))
+                }
+            }
+    }
+
+    /// Check that a chain isn't too long and that every delegation of it allows a
+    /// call at `now`, and return the key which must have signed the call, which is
+    /// the last delegate.
+    ///
+    /// This doesn't check the signatures or the cap grants of the delegations.
+    pub fn allowed_signer<'a>(
+        chain: &'a [ZomeCallDelegation],
+        call: &ZomeCallUnsigned,
+        now: Timestamp,
+    ) -> Result<&'a AgentPubKey, String> {
+        let Some(last) = chain.last() else {
+            return Err("The delegation chain is empty".to_string());
+        };
+        if chain.len() > MAX_ZOME_CALL_DELEGATIONS {
+            return Err(format!(
+                "The delegation chain has {} delegations, but at most {MAX_ZOME_CALL_DELEGATIONS} are allowed",
+                chain.len()
+            ));
+        }
+        for (i, delegation) in chain.iter().enumerate() {
+            if !delegation.allows(&call.cell_id, &call.zome_name, &call.fn_name, now) {
+                return Err(format!(
+                    "Delegation {i} of the chain doesn't allow this call to {}/{}",
+                    call.zome_name, call.fn_name
+                ));
+            }
+        }
+        Ok(&last.delegate)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::prelude::*;
+    use holo_hash::fixt::AgentPubKeyFixturator;
+    use holo_hash::fixt::DnaHashFixturator;
+
+    fn delegation(cell_id: &CellId, functions: GrantedFunctions) -> ZomeCallDelegation {
+        ZomeCallDelegation {
+            delegate: fixt!(AgentPubKey),
+            cell_id: cell_id.clone(),
+            functions,
+            cap_secret: [0; CAP_SECRET_BYTES].into(),
+            expires_at: Timestamp::from_micros(100),
+            signature: Signature([0; 64]),
+        }
+    }
+
+    #[test]
+    fn every_delegation_must_allow_the_call() {
+        let cell_id = CellId::new(fixt!(DnaHash), fixt!(AgentPubKey));
+        let call = ZomeCallUnsigned {
+            provenance: fixt!(AgentPubKey),
+            cell_id: cell_id.clone(),
+            zome_name: "zome".into(),
+            fn_name: "fn".into(),
+            cap_secret: None,
+            payload: ExternIO::encode(()).unwrap(),
+            nonce: [0; 32].into(),
+            expires_at: Timestamp::from_micros(100),
+        };
+        let now = Timestamp::from_micros(0);
+        let all = delegation(&cell_id, GrantedFunctions::All);
+        let listed = delegation(
+            &cell_id,
+            GrantedFunctions::Listed([("zome".into(), "fn".into())].into()),
+        );
+        let chain = [all.clone(), listed.clone()];
+        assert_eq!(
+            Ok(&listed.delegate),
+            ZomeCallDelegation::allowed_signer(&chain, &call, now)
+        );
+
+        let other_fn = delegation(
+            &cell_id,
+            GrantedFunctions::Listed([("zome".into(), "other".into())].into()),
+        );
+        assert!(ZomeCallDelegation::allowed_signer(&[all.clone(), other_fn], &call, now).is_err());
+        // Expired delegations don't allow any call.
+        assert!(
+            ZomeCallDelegation::allowed_signer(&chain, &call, Timestamp::from_micros(100)).is_err()
+        );
+        assert!(ZomeCallDelegation::allowed_signer(&[], &call, now).is_err());
+        let too_long = vec![all; MAX_ZOME_CALL_DELEGATIONS + 1];
+        assert!(ZomeCallDelegation::allowed_signer(&too_long, &call, now).is_err());
+    }
+}
@@ -7,10 +7,14 @@ use std::convert::TryInto;
 use std::sync::OnceLock;
 use url2::Url2;
 
+mod service;
+pub use service::*;
+
 pub mod prelude {
     pub use kitsune_p2p_bootstrap::error::*;
 
     pub use super::{now, now_once, proxy_list, put, random, BootstrapNet};
+    pub use super::{BootstrapService, DynBootstrapService, HttpBootstrap, LocalBootstrap};
 }
 
 /// The "net" flag / bucket to use when talking to the bootstrap server.
@@ -0,0 +1,201 @@
+//! The bootstrap service as a trait, so that nodes can find each other through something
+//! other than a remote bootstrap server, e.g. a [`LocalBootstrap`] in tests.
+//!
+//! Tests which need a real bootstrap server over HTTP can run one in process with
+//! `kitsune_p2p_bootstrap::run`, which is what holochain's `SweetLocalRendezvous` and
+//! `hc sandbox local-network` do, and talk to it with an [`HttpBootstrap`].
+
+use crate::BootstrapNet;
+use futures::future::BoxFuture;
+use futures::FutureExt;
+use kitsune_p2p_bin_data::{KitsuneAgent, KitsuneSpace};
+use kitsune_p2p_bootstrap::error::BootstrapClientResult;
+use kitsune_p2p_types::agent_info::AgentInfoSigned;
+use kitsune_p2p_types::bootstrap::RandomQuery;
+use rand::seq::IteratorRandom;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use url2::Url2;
+
+/// Where nodes put their agent infos, and find the agent infos of other nodes.
+pub trait BootstrapService: 'static + Send + Sync + std::fmt::Debug {
+    /// Put a signed agent info, replacing any which was signed earlier for the same agent.
+    fn put(&self, agent_info_signed: AgentInfoSigned) -> BoxFuture<'_, BootstrapClientResult<()>>;
+
+    /// Get up to `query.limit` unexpired agent infos of `query.space`, at random.
+    fn random(
+        &self,
+        query: RandomQuery,
+    ) -> BoxFuture<'_, BootstrapClientResult<Vec<AgentInfoSigned>>>;
+}
+
+/// Trait object bootstrap service.
+pub type DynBootstrapService = Arc<dyn BootstrapService>;
+
+/// The bootstrap service of a remote bootstrap server, over HTTP.
+#[derive(Debug, Clone)]
+pub struct HttpBootstrap {
+    url: Url2,
+    net: BootstrapNet,
+}
+
+impl HttpBootstrap {
+    /// Talk to the bootstrap server at `url`.
+    pub fn new(url: Url2, net: BootstrapNet) -> Self {
+        Self { url, net }
+    }
+}
+
+impl BootstrapService for HttpBootstrap {
+    fn put(&self, agent_info_signed: AgentInfoSigned) -> BoxFuture<'_, BootstrapClientResult<()>> {
+        crate::put(Some(self.url.clone()), agent_info_signed, self.net).boxed()
+    }
+
+    fn random(
+        &self,
+        query: RandomQuery,
+    ) -> BoxFuture<'_, BootstrapClientResult<Vec<AgentInfoSigned>>> {
+        crate::random(Some(self.url.clone()), query, self.net).boxed()
+    }
+}
+
+type AgentMap = HashMap<Arc<KitsuneAgent>, AgentInfoSigned>;
+
+/// A bootstrap service which only lives in this process, for nodes which run in the
+/// same process to find each other without a bootstrap server. Clones share the
+/// agent infos.
+#[derive(Debug, Clone, Default)]
+pub struct LocalBootstrap(Arc<Mutex<HashMap<Arc<KitsuneSpace>, AgentMap>>>);
+
+impl LocalBootstrap {
+    /// An empty bootstrap service.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl BootstrapService for LocalBootstrap {
+    fn put(&self, agent_info_signed: AgentInfoSigned) -> BoxFuture<'_, BootstrapClientResult<()>> {
+        let mut spaces = self.0.lock().unwrap();
+        let agents = spaces.entry(agent_info_signed.space.clone()).or_default();
+        match agents.get(&agent_info_signed.agent) {
+            Some(existing) if existing.signed_at_ms >= agent_info_signed.signed_at_ms => (),
+            _ => {
+                agents.insert(agent_info_signed.agent.clone(), agent_info_signed);
+            }
+        }
+        async move { Ok(()) }.boxed()
+    }
+
+    fn random(
+        &self,
+        query: RandomQuery,
+    ) -> BoxFuture<'_, BootstrapClientResult<Vec<AgentInfoSigned>>> {
+        let now = std::time::UNIX_EPOCH
+            .elapsed()
+            .map(|now| now.as_millis() as u64);
+        let infos = now.map(|now| {
+            self.0
+                .lock()
+                .unwrap()
+                .get(&query.space)
+                .map(|agents| {
+                    agents
+                        .values()
+                        .filter(|info| info.expires_at_ms > now)
+                        .cloned()
+                        .choose_multiple(&mut rand::thread_rng(), query.limit.0 as usize)
+                })
+                .unwrap_or_default()
+        });
+        async move { Ok(infos?) }.boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::fixt::prelude::*;
+    use kitsune_p2p_bin_data::fixt::*;
+    use kitsune_p2p_bin_data::KitsuneSignature;
+    use kitsune_p2p_types::dht::Arq;
+    use std::net::SocketAddr;
+
+    async fn agent_info(space: Arc<KitsuneSpace>, signed_at_ms: u64) -> AgentInfoSigned {
+        AgentInfoSigned::sign(
+            space,
+            Arc::new(fixt!(KitsuneAgent, Unpredictable)),
+            Arq::new(12, 0u32.into(), 0u32.into()),
+            vec![],
+            signed_at_ms,
+            signed_at_ms + 1000 * 60 * 20,
+            |_| async move { Ok(Arc::new(KitsuneSignature(vec![0; 64]))) },
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn local_bootstrap_shares_agent_infos_between_clones() {
+        let now = std::time::UNIX_EPOCH.elapsed().unwrap().as_millis() as u64;
+        let space = Arc::new(fixt!(KitsuneSpace, Unpredictable));
+        let alice = agent_info(space.clone(), now).await;
+        let bob = agent_info(space.clone(), now).await;
+        let expired = agent_info(space.clone(), 0).await;
+
+        let bootstrap = LocalBootstrap::new();
+        for info in [&alice, &bob, &expired] {
+            bootstrap.put(info.clone()).await.unwrap();
+        }
+        let query = |limit: u32| RandomQuery {
+            space: space.clone(),
+            limit: limit.into(),
+        };
+
+        // - A clone sees what was put to the original, but not expired agent infos.
+        let mut random = bootstrap.clone().random(query(10)).await.unwrap();
+        random.sort_by(|a, b| a.agent.partial_cmp(&b.agent).unwrap());
+        let mut expected = vec![alice.clone(), bob];
+        expected.sort_by(|a, b| a.agent.partial_cmp(&b.agent).unwrap());
+        assert_eq!(expected, random);
+        assert_eq!(1, bootstrap.random(query(1)).await.unwrap().len());
+
+        // - Other spaces are empty.
+        assert!(bootstrap
+            .random(RandomQuery {
+                space: Arc::new(fixt!(KitsuneSpace, Unpredictable)),
+                limit: 10.into(),
+            })
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn http_bootstrap_talks_to_a_bootstrap_server() {
+        let (driver, addr, shutdown) =
+            kitsune_p2p_bootstrap::run("127.0.0.1:0".parse::<SocketAddr>().unwrap(), vec![])
+                .await
+                .unwrap();
+        tokio::spawn(driver);
+        let bootstrap: DynBootstrapService = Arc::new(HttpBootstrap::new(
+            url2::url2!("http://{:?}", addr),
+            BootstrapNet::Tx5,
+        ));
+
+        let now = std::time::UNIX_EPOCH.elapsed().unwrap().as_millis() as u64;
+        let space = Arc::new(fixt!(KitsuneSpace, Unpredictable));
+        let alice = agent_info(space.clone(), now).await;
+        bootstrap.put(alice.clone()).await.unwrap();
+        let random = bootstrap
+            .random(RandomQuery {
+                space,
+                limit: 10.into(),
+            })
+            .await
+            .unwrap();
+        assert_eq!(vec![alice], random);
+
+        shutdown();
+    }
+}
@@ -659,6 +659,56 @@ pub fn approximate_arq(dim: impl SpaceDim, strat: &ArqStrat, start: Loc, len: u6
     }
 }
 
+/// A storage arc which a local agent is pinned to, rather than letting it be sized by
+/// the network conditions.
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArqPin {
+    /// Hold the whole DHT, e.g. for an archival node.
+    Full,
+    /// Hold nothing, e.g. for a client which only reads and writes.
+    Empty,
+    /// Hold the locations from `start` to `end` inclusive, wrapping around the DHT if
+    /// `end` is before `start`.
+    Range {
+        /// The first location held.
+        start: u32,
+        /// The last location held.
+        end: u32,
+    },
+}
+
+impl ArqPin {
+    /// The arq of an agent at `loc` which this pin gives. A range is rounded out to the
+    /// nearest quantized arq which holds all of it.
+    pub fn to_arq(&self, dim: impl SpaceDim, strat: &ArqStrat, loc: Loc) -> Arq {
+        let dim = dim.get();
+        match self {
+            Self::Full => Arq::new_full_max(dim, strat, loc),
+            Self::Empty => Arq::new_empty(dim, loc),
+            Self::Range { start, end } => {
+                // The start of an arq falls on its chunks, so the length is counted
+                // from the start of the chunk which `start` is in.
+                let chunks = |power: u8| {
+                    let chunk_width = dim.quantum as u64 * pow2(power) as u64;
+                    let len = *start as u64 % chunk_width + end.wrapping_sub(*start) as u64 + 1;
+                    len.min(U32_LEN).div_ceil(chunk_width)
+                };
+                let mut power = dim.min_power();
+                while chunks(power) > strat.max_chunks() as u64 && power < dim.max_power(strat) {
+                    power += 1;
+                }
+                let count = chunks(power);
+                if is_full(dim, power, count as u32) {
+                    Arq::new_full_max(dim, strat, loc)
+                } else {
+                    Arq::new(power, Loc::from(*start), (count as u32).into())
+                }
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -786,6 +836,30 @@ mod tests {
         );
     }
 
+    #[test_case(0, u32::MAX)]
+    #[test_case(1000, 999)]
+    #[test_case(0, 5000)]
+    #[test_case(u32::MAX - 100_000, 100_000)]
+    #[test_case(2u32.pow(30), 2u32.pow(31))]
+    fn pinned_ranges_are_held(start: u32, end: u32) {
+        let topo = Topology::standard_epoch_full();
+        let strat = ArqStrat::default();
+        let arq = ArqPin::Range { start, end }.to_arq(&topo, &strat, 0.into());
+        let arc = arq.to_dht_arc_range(&topo);
+        assert!(arc.contains(Loc::from(start)));
+        assert!(arc.contains(Loc::from(end)));
+        assert!(arq.absolute_length(&topo) > end.wrapping_sub(start) as u64);
+        assert!(*arq.count <= strat.max_chunks());
+    }
+
+    #[test]
+    fn pinned_full_and_empty_arqs() {
+        let topo = Topology::standard_epoch_full();
+        let strat = ArqStrat::default();
+        assert!(ArqPin::Full.to_arq(&topo, &strat, 42.into()).is_full(&topo));
+        assert!(ArqPin::Empty.to_arq(&topo, &strat, 42.into()).is_empty());
+    }
+
     proptest::proptest! {
 
         #[test]
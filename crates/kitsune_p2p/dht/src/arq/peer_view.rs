@@ -1,7 +1,7 @@
-use kitsune_p2p_dht_arc::DhtArc;
+use kitsune_p2p_dht_arc::{DhtArc, DhtArcRange};
 use num_traits::Zero;
 
-use crate::spacetime::{SpaceOffset, Topology};
+use crate::spacetime::{SpaceDim, SpaceOffset, Topology};
 
 use super::{is_full, Arq, ArqClamping, ArqStrat};
 
@@ -26,6 +26,14 @@ impl PeerView {
             Self::Quantized(v) => v.update_arq(arq),
         }
     }
+
+    /// The segments of the arq, from the one which the fewest peers in this view
+    /// cover to the one which the most cover.
+    pub fn coverage_gaps(&self, arq: &Arq) -> Vec<CoverageGap> {
+        match self {
+            Self::Quantized(v) => v.coverage_gaps(arq),
+        }
+    }
 }
 
 /// The Quantized PeerView
@@ -111,6 +119,19 @@ impl PeerViewQ {
             / 2f64.powf(32.0)
     }
 
+    /// The segments of the arq, from the one which the fewest peers in this view
+    /// cover to the one which the most cover.
+    ///
+    /// A peer with exactly this arq is taken to be the agent whose arq it is,
+    /// and isn't counted.
+    pub fn coverage_gaps(&self, arq: &Arq) -> Vec<CoverageGap> {
+        let mut peers = self.peers.clone();
+        if let Some(i) = peers.iter().position(|peer| peer == arq) {
+            peers.remove(i);
+        }
+        coverage_gaps(self.topo.space, arq, peers.iter())
+    }
+
     /// Mutate the arq to its ideal target
     pub fn update_arq(&self, arq: &mut Arq) -> bool {
         let topo = &self.topo;
@@ -404,6 +425,41 @@ pub fn actual_coverage<'a, P: Iterator<Item = &'a Arq>>(topo: &Topology, peers:
         .sum()
 }
 
+/// A segment of an arq, and the number of peers which cover some of it.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct CoverageGap {
+    /// The locations of the segment.
+    pub segment: DhtArcRange,
+    /// The number of peers whose arqs overlap the segment.
+    pub num_peers: usize,
+}
+
+/// The segments of `arq`, from the one which the fewest `peers` cover to the one
+/// which the most cover. Segments which are covered by as many peers are kept in
+/// their order along the arq.
+pub fn coverage_gaps<'a, P: Iterator<Item = &'a Arq>>(
+    dim: impl SpaceDim,
+    arq: &Arq,
+    peers: P,
+) -> Vec<CoverageGap> {
+    let peers: Vec<_> = peers
+        .filter(|peer| !peer.is_empty())
+        .map(|peer| peer.to_dht_arc_range(dim))
+        .collect();
+    let mut gaps: Vec<_> = arq
+        .to_bounds(dim)
+        .segments()
+        .map(|segment| {
+            let (start, end) = segment.loc_bounds(dim);
+            let segment = DhtArcRange::from_bounds(start, end);
+            let num_peers = peers.iter().filter(|peer| peer.overlaps(&segment)).count();
+            CoverageGap { segment, num_peers }
+        })
+        .collect();
+    gaps.sort_by_key(|gap| gap.num_peers);
+    gaps
+}
+
 /// Statistics about the power levels of all arqs in a view.
 /// Used to make inferences about what your neighbors are up to.
 #[derive(Debug, Clone)]
@@ -419,8 +475,6 @@ mod tests {
 
     use std::convert::identity;
 
-    use kitsune_p2p_dht_arc::DhtArcRange;
-
     use crate::arq::{pow2, print_arqs};
     use crate::spacetime::Topology;
     use crate::ArqBounds;
@@ -462,6 +516,46 @@ mod tests {
         assert_eq!(get(make_arq(&topo, pow, 0x10, 0x20)), vec![b]);
     }
 
+    #[test]
+    fn test_coverage_gaps() {
+        let topo = Topology::unit_zero();
+        let pow = 24;
+        let arq = make_arq(&topo, pow, 0, 4);
+        // The peers cover the segments 2, 3, 2 and 0 times.
+        let peers = vec![
+            arq,
+            make_arq(&topo, pow, 0, 2),
+            make_arq(&topo, pow, 0xf0, 3),
+            make_arq(&topo, pow, 1, 3),
+        ];
+        let view = PeerViewQ::new(topo.clone(), ArqStrat::default(), peers);
+        let segment =
+            |i: u32| DhtArcRange::from_bounds(pow2(pow) * i, (pow2(pow) * (i + 1)).wrapping_sub(1));
+
+        // - The agent's own arq isn't counted, and the least covered segment comes first.
+        assert_eq!(
+            view.coverage_gaps(&arq),
+            vec![
+                CoverageGap {
+                    segment: segment(3),
+                    num_peers: 0,
+                },
+                CoverageGap {
+                    segment: segment(0),
+                    num_peers: 2,
+                },
+                CoverageGap {
+                    segment: segment(2),
+                    num_peers: 2,
+                },
+                CoverageGap {
+                    segment: segment(1),
+                    num_peers: 3,
+                },
+            ]
+        );
+    }
+
     #[test]
     fn test_coverage() {
         let topo = Topology::unit_zero();
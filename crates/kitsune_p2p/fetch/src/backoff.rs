@@ -11,6 +11,9 @@ pub struct FetchBackoff {
     backoff: FibonacciBackoff,
     current_wait: Duration,
     started_wait_at: Instant,
+    /// How many more delays the backoff will wait for after the current one.
+    #[cfg(test)]
+    remaining_waits: usize,
     expired: bool,
 }
 
@@ -28,6 +31,8 @@ impl FetchBackoff {
             current_wait: backoff.next().expect("At least one backoff period"),
             started_wait_at: Instant::now(),
             backoff,
+            #[cfg(test)]
+            remaining_waits: BACKOFF_RETRY_COUNT - 1,
             expired: false,
         }
     }
@@ -53,11 +58,27 @@ impl FetchBackoff {
         self.expired
     }
 
+    /// Whether the current delay has passed, without starting the next one.
+    #[cfg(test)]
+    pub(crate) fn wait_elapsed(&self) -> bool {
+        self.started_wait_at.elapsed() >= self.current_wait
+    }
+
+    /// How many more delays the backoff will wait for after the current one.
+    #[cfg(test)]
+    pub(crate) fn remaining_waits(&self) -> usize {
+        self.remaining_waits
+    }
+
     fn advance(&mut self) {
         match self.backoff.next() {
             Some(d) => {
                 self.current_wait = d;
                 self.started_wait_at = Instant::now();
+                #[cfg(test)]
+                {
+                    self.remaining_waits -= 1;
+                }
             }
             None => {
                 self.expired = true;
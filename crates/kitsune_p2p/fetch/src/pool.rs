@@ -26,6 +26,9 @@ use crate::{
 mod pool_reader;
 pub use pool_reader::*;
 
+#[cfg(test)]
+mod model;
+
 /// A FetchPool tracks a set of [`FetchKey`]s (op hashes) to be fetched,
 /// each of which can have multiple sources associated with it.
 ///
@@ -183,6 +186,16 @@ impl FetchPool {
             .share_mut(|s| s.get_batch(self.config.clone()).clone())
     }
 
+    /// Get a list of the next items to be fetched, leaving out the items of spaces which
+    /// can't take in more ops for now. Those items are kept in the pool for later.
+    pub fn get_items_to_fetch_except(
+        &self,
+        skip_space: impl Fn(&KSpace) -> bool,
+    ) -> Vec<(FetchKey, KSpace, FetchSource, Option<FetchContext>)> {
+        self.state
+            .share_mut(|s| s.get_batch_except(self.config.clone(), skip_space))
+    }
+
     /// Get the current size of the fetch pool. This is the number of outstanding items
     /// and may be different to the size of response from `get_items_to_fetch` because it
     /// ignores retry delays.
@@ -253,6 +266,17 @@ impl State {
     pub fn get_batch(
         &mut self,
         config: Arc<dyn FetchPoolConfig>,
+    ) -> Vec<(FetchKey, KSpace, FetchSource, Option<FetchContext>)> {
+        self.get_batch_except(config, |_| false)
+    }
+
+    /// Get a batch of items to fetch, leaving out the items of the spaces for which
+    /// `skip_space` is true. Skipped items aren't marked as requested, so their
+    /// sources aren't blamed for not responding to them.
+    pub fn get_batch_except(
+        &mut self,
+        config: Arc<dyn FetchPoolConfig>,
+        skip_space: impl Fn(&KSpace) -> bool,
     ) -> Vec<(FetchKey, KSpace, FetchSource, Option<FetchContext>)> {
         let batch_size = config.fetch_batch_size();
 
@@ -271,6 +295,10 @@ impl State {
                 None => continue,
             };
 
+            if skip_space(&item.space) {
+                continue;
+            }
+
             // Check for a pending response on this item
             let should_fetch_item = match &item.pending_response {
                 Some(pending_response) => {
@@ -628,6 +656,26 @@ mod tests {
         assert_eq!(3, q.get_batch(cfg.clone()).len());
     }
 
+    #[tokio::test(start_paused = true)]
+    async fn skipped_spaces_are_not_fetched() {
+        let cfg = Arc::new(TestFetchConfig(1, 10));
+        let mut q = State {
+            queue: [(
+                test_key_op(1),
+                item(cfg.clone(), test_sources(0..=0), test_ctx(1)),
+            )]
+            .into_iter()
+            .collect(),
+            sources: [(test_source(0), SourceState::default())]
+                .into_iter()
+                .collect(),
+        };
+
+        assert!(q.get_batch_except(cfg.clone(), |_| true).is_empty());
+        // - The skipped item isn't waiting for a response, so it's fetched right away.
+        assert_eq!(1, q.get_batch(cfg.clone()).len());
+    }
+
     #[tokio::test(start_paused = true)]
     async fn uses_all_sources() {
         let cfg = Arc::new(TestFetchConfig(1, 10));
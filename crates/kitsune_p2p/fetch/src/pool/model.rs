@@ -0,0 +1,621 @@
+//! A model of the fetch pool, for checking its behaviour.
+//!
+//! The model describes the state of the pool with small values which can be compared and
+//! hashed: items and sources are numbered, and a source backoff is reduced to the number of
+//! delays it has left and whether the current delay has passed. Time moves forward in one of
+//! two steps, either by the item retry delay, or by long enough for every source backoff delay
+//! to pass.
+//!
+//! [`ModelState::project`] maps the real pool [`State`] onto the model, so that random
+//! sequences of actions can be applied to both and compared after every step. Because the model
+//! agrees with the real pool, [`explore`] can then visit every state of a small instance of the
+//! model and check invariants on each of them, including that every item in the pool is
+//! eventually fetched or dropped, whether or not its sources respond.
+
+use std::collections::{BTreeMap, HashSet, VecDeque};
+
+use kitsune_p2p_types::bin_types::KitsuneBinType;
+
+use super::*;
+use crate::backoff::BACKOFF_RETRY_COUNT;
+use crate::source::{SourceCurrentState, NUM_PROBE_ATTEMPTS};
+
+/// An item, numbered by the first byte of its op hash.
+type Key = u8;
+
+/// A source, numbered by the first byte of its agent key.
+type Src = u8;
+
+/// The limits which the pool is configured with.
+#[derive(Clone, Debug)]
+struct ModelConfig {
+    batch_size: usize,
+    timeout_threshold: usize,
+    backoff_waits: usize,
+    probe_attempts: u32,
+}
+
+impl ModelConfig {
+    /// The limits which the real pool uses with this config.
+    fn from_pool_config(config: &dyn FetchPoolConfig) -> Self {
+        Self {
+            batch_size: config.fetch_batch_size(),
+            timeout_threshold: config.source_unavailable_timeout_threshold(),
+            backoff_waits: BACKOFF_RETRY_COUNT,
+            probe_attempts: NUM_PROBE_ATTEMPTS,
+        }
+    }
+}
+
+/// Something which can happen to the pool.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Action {
+    /// An item is pushed with a source to fetch it from.
+    Push(Key, Src),
+    /// A batch of items to fetch is taken from the pool.
+    GetBatch,
+    /// An item has been fetched, whether or not it was requested.
+    Remove(Key),
+    /// The item retry delay passes, so every pending request has timed out.
+    RetryDelayElapses,
+    /// Enough time passes for the current delay of every source backoff to pass.
+    BackoffDelaysElapse,
+    /// Sources are checked, and expired sources are dropped.
+    CheckSources,
+}
+
+/// The state of the pool.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+struct ModelState {
+    /// The items which are waiting to be fetched, in queue order.
+    queue: Vec<(Key, ModelItem)>,
+    /// The position in the queue where the next batch starts.
+    queue_index: usize,
+    /// Every source which is known to the pool.
+    sources: BTreeMap<Src, ModelSource>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct ModelItem {
+    /// The sources to fetch this item from, in the order they are tried.
+    sources: Vec<Src>,
+    /// The position of the source to try next.
+    source_index: usize,
+    /// The request for this item which is waiting for a response.
+    pending: Option<ModelPending>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct ModelPending {
+    source: Src,
+    timed_out: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum ModelSource {
+    Available { timeouts: usize },
+    Backoff(ModelBackoff),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct ModelBackoff {
+    /// How many more delays the backoff will wait for after the current one.
+    remaining_waits: usize,
+    /// Whether the current delay has passed.
+    wait_elapsed: bool,
+    expired: bool,
+    /// How many more probes are allowed before waiting for the backoff again.
+    probes: u32,
+}
+
+impl ModelBackoff {
+    fn new(config: &ModelConfig) -> Self {
+        Self {
+            remaining_waits: config.backoff_waits - 1,
+            wait_elapsed: false,
+            expired: false,
+            probes: 0,
+        }
+    }
+
+    fn is_ready(&mut self, config: &ModelConfig) -> bool {
+        let backoff_ready = if self.expired || !self.wait_elapsed {
+            false
+        } else {
+            if self.remaining_waits > 0 {
+                self.remaining_waits -= 1;
+                self.wait_elapsed = false;
+            } else {
+                self.expired = true;
+            }
+            true
+        };
+
+        if backoff_ready {
+            self.probes = config.probe_attempts - 1;
+            true
+        } else if self.probes > 0 {
+            self.probes -= 1;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+impl ModelSource {
+    fn should_use(&mut self, config: &ModelConfig) -> bool {
+        match self {
+            Self::Available { .. } => true,
+            Self::Backoff(backoff) => backoff.is_ready(config),
+        }
+    }
+
+    fn is_valid(&mut self, config: &ModelConfig) -> bool {
+        match self {
+            Self::Available { timeouts } if *timeouts > config.timeout_threshold => {
+                *self = Self::Backoff(ModelBackoff::new(config));
+                true
+            }
+            Self::Available { .. } => true,
+            Self::Backoff(backoff) => !backoff.expired,
+        }
+    }
+
+    fn record_timeout(&mut self) {
+        if let Self::Available { timeouts } = self {
+            *timeouts += 1;
+        }
+    }
+
+    fn record_response(&mut self) {
+        if let Self::Backoff(_) = self {
+            *self = Self::Available { timeouts: 0 };
+        }
+    }
+}
+
+impl ModelItem {
+    fn next_source(
+        &mut self,
+        sources: &mut BTreeMap<Src, ModelSource>,
+        config: &ModelConfig,
+    ) -> Option<Src> {
+        for _ in 0..self.sources.len() {
+            let index = self.source_index;
+            self.source_index = (self.source_index + 1) % self.sources.len();
+
+            if let Some(source) = self.sources.get(index) {
+                if sources
+                    .get_mut(source)
+                    .map_or(false, |state| state.should_use(config))
+                {
+                    return Some(*source);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+impl ModelState {
+    /// Map the real state of the pool onto the model. Items and sources must have been created
+    /// with [`test_key_op`](crate::test_utils::test_key_op) and
+    /// [`test_source`](crate::test_utils::test_source).
+    fn project(state: &State, config: &dyn FetchPoolConfig) -> Self {
+        let queue = state
+            .queue
+            .iter()
+            .map(|(key, item)| {
+                let pending = item.pending_response.as_ref().map(|pending| ModelPending {
+                    source: project_source(&pending.source),
+                    timed_out: pending.when.elapsed() > config.item_retry_delay(),
+                });
+                let item = ModelItem {
+                    sources: item.sources.iter().map(project_source).collect(),
+                    source_index: item.sources.index(),
+                    pending,
+                };
+                (project_key(key), item)
+            })
+            .collect();
+
+        let sources = state
+            .sources
+            .iter()
+            .map(|(source, state)| {
+                let state = match state.current_state() {
+                    SourceCurrentState::Available(timeouts) => ModelSource::Available {
+                        timeouts: *timeouts,
+                    },
+                    SourceCurrentState::Backoff(backoff) => ModelSource::Backoff(ModelBackoff {
+                        remaining_waits: backoff.backoff().remaining_waits(),
+                        wait_elapsed: backoff.backoff().wait_elapsed(),
+                        expired: backoff.backoff().is_expired(),
+                        probes: backoff.probes(),
+                    }),
+                };
+                (project_source(source), state)
+            })
+            .collect();
+
+        Self {
+            queue,
+            queue_index: state.queue.index(),
+            sources,
+        }
+    }
+
+    /// Apply an action, returning the items to fetch and their sources if the action takes a
+    /// batch from the pool.
+    fn apply(&mut self, config: &ModelConfig, action: Action) -> Vec<(Key, Src)> {
+        match action {
+            Action::Push(key, source) => self.push(key, source),
+            Action::GetBatch => return self.get_batch(config),
+            Action::Remove(key) => self.remove(key),
+            Action::RetryDelayElapses => self.time_out_pending(),
+            Action::BackoffDelaysElapse => {
+                self.time_out_pending();
+                for state in self.sources.values_mut() {
+                    if let ModelSource::Backoff(backoff) = state {
+                        backoff.wait_elapsed = true;
+                    }
+                }
+            }
+            Action::CheckSources => self.check_sources(config),
+        }
+        vec![]
+    }
+
+    fn push(&mut self, key: Key, source: Src) {
+        self.sources
+            .entry(source)
+            .or_insert(ModelSource::Available { timeouts: 0 });
+
+        match self.queue.iter_mut().find(|(k, _)| *k == key) {
+            Some((_, item)) => {
+                if !item.sources.contains(&source) {
+                    item.sources.push(source);
+                }
+            }
+            None => self.queue.push((
+                key,
+                ModelItem {
+                    sources: vec![source],
+                    source_index: 0,
+                    pending: None,
+                },
+            )),
+        }
+    }
+
+    fn get_batch(&mut self, config: &ModelConfig) -> Vec<(Key, Src)> {
+        let mut to_fetch = vec![];
+        for _ in 0..self.queue.len() {
+            if to_fetch.len() >= config.batch_size {
+                break;
+            }
+
+            let index = self.queue_index;
+            self.queue_index = (self.queue_index + 1) % self.queue.len();
+            let Some((key, item)) = self.queue.get_mut(index) else {
+                continue;
+            };
+
+            let should_fetch_item = match &item.pending {
+                Some(pending) if pending.timed_out => {
+                    if let Some(state) = self.sources.get_mut(&pending.source) {
+                        state.record_timeout();
+                    }
+                    true
+                }
+                Some(_) => false,
+                None => true,
+            };
+
+            if should_fetch_item {
+                item.pending = None;
+                if let Some(source) = item.next_source(&mut self.sources, config) {
+                    item.pending = Some(ModelPending {
+                        source,
+                        timed_out: false,
+                    });
+                    to_fetch.push((*key, source));
+                }
+            }
+        }
+        to_fetch
+    }
+
+    fn remove(&mut self, key: Key) {
+        if let Some(index) = self.queue.iter().position(|(k, _)| *k == key) {
+            let (_, item) = self.queue.swap_remove(index);
+            if let Some(pending) = item.pending {
+                if let Some(state) = self.sources.get_mut(&pending.source) {
+                    state.record_response();
+                }
+            }
+        }
+    }
+
+    fn time_out_pending(&mut self) {
+        for (_, item) in self.queue.iter_mut() {
+            if let Some(pending) = &mut item.pending {
+                pending.timed_out = true;
+            }
+        }
+    }
+
+    fn check_sources(&mut self, config: &ModelConfig) {
+        self.sources.retain(|_, state| state.is_valid(config));
+
+        let keys: Vec<_> = self.queue.iter().map(|(key, _)| *key).collect();
+        for key in keys {
+            let index = self
+                .queue
+                .iter()
+                .position(|(k, _)| *k == key)
+                .expect("Iterating keys");
+            let sources = &self.sources;
+            let item = &mut self.queue[index].1;
+            item.sources.retain(|source| sources.contains_key(source));
+            if item.sources.is_empty() {
+                self.queue.swap_remove(index);
+            } else {
+                item.source_index %= item.sources.len();
+            }
+        }
+    }
+
+    /// Check the properties which must hold in every state.
+    fn check_invariants(&self) -> Result<(), String> {
+        let mut keys = HashSet::new();
+        for (key, item) in &self.queue {
+            if !keys.insert(key) {
+                return Err(format!("item {key} is in the queue twice"));
+            }
+            if item.sources.is_empty() {
+                return Err(format!("item {key} has no sources"));
+            }
+            if item.source_index >= item.sources.len() {
+                return Err(format!("item {key} has an invalid source index"));
+            }
+            if item.sources.iter().collect::<HashSet<_>>().len() != item.sources.len() {
+                return Err(format!("item {key} has a duplicate source"));
+            }
+            if let Some(source) = item
+                .sources
+                .iter()
+                .find(|source| !self.sources.contains_key(source))
+            {
+                return Err(format!("item {key} has unknown source {source}"));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check that every item is eventually fetched or dropped, both when every request is
+    /// answered and when none are.
+    fn check_eventually_resolved(&self, config: &ModelConfig) -> Result<(), String> {
+        for respond in [true, false] {
+            let mut state = self.clone();
+            let mut rounds = 0;
+            while !state.queue.is_empty() {
+                if rounds == MAX_RESOLVE_ROUNDS {
+                    return Err(format!(
+                        "items were not resolved after {rounds} rounds (respond = {respond}): {state:?}"
+                    ));
+                }
+                state.apply(config, Action::RetryDelayElapses);
+                state.apply(config, Action::BackoffDelaysElapse);
+                for (key, _) in state.apply(config, Action::GetBatch) {
+                    if respond {
+                        state.apply(config, Action::Remove(key));
+                    }
+                }
+                state.apply(config, Action::CheckSources);
+                rounds += 1;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// How many rounds of fetching an item may take to be fetched or dropped, beyond which it is
+/// considered to be starved.
+const MAX_RESOLVE_ROUNDS: usize = 1000;
+
+fn project_key(key: &FetchKey) -> Key {
+    match key {
+        FetchKey::Op(hash) => hash.get_bytes()[0],
+    }
+}
+
+fn project_source(source: &FetchSource) -> Src {
+    match source {
+        FetchSource::Agent(agent) => agent.get_bytes()[0],
+    }
+}
+
+/// Every action on up to `num_keys` items and `num_sources` sources.
+fn all_actions(num_keys: u8, num_sources: u8) -> Vec<Action> {
+    let mut actions = vec![
+        Action::GetBatch,
+        Action::RetryDelayElapses,
+        Action::BackoffDelaysElapse,
+        Action::CheckSources,
+    ];
+    for key in 0..num_keys {
+        actions.push(Action::Remove(key));
+        for source in 0..num_sources {
+            actions.push(Action::Push(key, source));
+        }
+    }
+    actions
+}
+
+/// Visit every state which can be reached from an empty pool in at most `depth` actions,
+/// checking the invariants and that every item is eventually resolved in each of them.
+/// Returns the number of states visited, or the actions which lead to a failing state.
+fn explore(
+    config: &ModelConfig,
+    num_keys: u8,
+    num_sources: u8,
+    depth: usize,
+) -> Result<usize, (Vec<Action>, String)> {
+    let actions = all_actions(num_keys, num_sources);
+    let mut seen = HashSet::new();
+    let mut frontier = VecDeque::from([(ModelState::default(), vec![])]);
+    seen.insert(ModelState::default());
+
+    while let Some((state, trace)) = frontier.pop_front() {
+        state
+            .check_invariants()
+            .and_then(|_| state.check_eventually_resolved(config))
+            .map_err(|e| (trace.clone(), e))?;
+
+        if trace.len() == depth {
+            continue;
+        }
+        for action in &actions {
+            let mut next = state.clone();
+            next.apply(config, *action);
+            if seen.insert(next.clone()) {
+                let mut trace = trace.clone();
+                trace.push(*action);
+                frontier.push_back((next, trace));
+            }
+        }
+    }
+
+    Ok(seen.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::*;
+    use rand::rngs::StdRng;
+    use rand::{Rng, SeedableRng};
+
+    struct ModelFetchConfig;
+
+    impl FetchPoolConfig for ModelFetchConfig {
+        fn item_retry_delay(&self) -> Duration {
+            Duration::from_secs(1)
+        }
+
+        // Much longer than the item retry delay, so that backoff delays only pass when the
+        // model says they do.
+        fn source_retry_delay(&self) -> Duration {
+            Duration::from_secs(10_000)
+        }
+
+        fn merge_fetch_contexts(&self, a: u32, b: u32) -> u32 {
+            a | b
+        }
+
+        fn fetch_batch_size(&self) -> usize {
+            2
+        }
+
+        fn source_unavailable_timeout_threshold(&self) -> usize {
+            2
+        }
+    }
+
+    fn random_action(rng: &mut StdRng) -> Action {
+        match rng.gen_range(0..20) {
+            0..=5 => Action::Push(rng.gen_range(0..4), rng.gen_range(0..3)),
+            6..=10 => Action::GetBatch,
+            11..=12 => Action::Remove(rng.gen_range(0..4)),
+            13..=16 => Action::RetryDelayElapses,
+            17 => Action::BackoffDelaysElapse,
+            _ => Action::CheckSources,
+        }
+    }
+
+    async fn apply_to_pool(
+        state: &mut State,
+        config: Arc<dyn FetchPoolConfig>,
+        action: Action,
+    ) -> Vec<(Key, Src)> {
+        match action {
+            Action::Push(key, source) => {
+                state.push(&*config, test_req_op(key, None, test_source(source)))
+            }
+            Action::GetBatch => {
+                return state
+                    .get_batch(config)
+                    .iter()
+                    .map(|(key, _, source, _)| (project_key(key), project_source(source)))
+                    .collect()
+            }
+            Action::Remove(key) => {
+                state.remove(&test_key_op(key));
+            }
+            Action::RetryDelayElapses => {
+                tokio::time::advance(config.item_retry_delay() + Duration::from_millis(1)).await
+            }
+            Action::BackoffDelaysElapse => {
+                // The longest delay, including jitter, is less than 10 times the initial delay,
+                // so this is enough for every current delay to pass
+                tokio::time::advance(config.source_retry_delay() * 20).await
+            }
+            Action::CheckSources => state.check_sources(config),
+        }
+        vec![]
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn model_matches_fetch_pool() {
+        let config: Arc<dyn FetchPoolConfig> = Arc::new(ModelFetchConfig);
+        let model_config = ModelConfig::from_pool_config(&*config);
+        let mut rng = StdRng::seed_from_u64(0);
+        let mut saw_backoff = false;
+
+        for _ in 0..100 {
+            let mut state = State::default();
+            let mut model = ModelState::default();
+            let mut trace = vec![];
+
+            for _ in 0..100 {
+                let action = random_action(&mut rng);
+                trace.push(action);
+
+                let batch = apply_to_pool(&mut state, config.clone(), action).await;
+                let model_batch = model.apply(&model_config, action);
+                assert_eq!(batch, model_batch, "after {trace:?}");
+                assert_eq!(
+                    ModelState::project(&state, &*config),
+                    model,
+                    "after {trace:?}"
+                );
+
+                saw_backoff |= model
+                    .sources
+                    .values()
+                    .any(|source| matches!(source, ModelSource::Backoff(_)));
+            }
+        }
+
+        // The random actions should have been enough to put some sources on a backoff
+        assert!(saw_backoff);
+    }
+
+    #[test]
+    fn explore_small_pool() {
+        let config = ModelConfig {
+            batch_size: 1,
+            timeout_threshold: 1,
+            backoff_waits: 2,
+            probe_attempts: 2,
+        };
+
+        match explore(&config, 2, 2, 8) {
+            Ok(states) => assert!(states > 1000, "only visited {states} states"),
+            Err((trace, e)) => panic!("{e}\nafter {trace:?}"),
+        }
+    }
+}
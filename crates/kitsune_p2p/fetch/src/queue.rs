@@ -50,6 +50,12 @@ where
         self.inner.get_index_mut(fetch_index)
     }
 
+    /// The position of the entry which will be returned by the next call to [`MapQueue::front`].
+    #[cfg(test)]
+    pub(crate) fn index(&self) -> usize {
+        self.index
+    }
+
     pub(crate) fn entry(&mut self, key: K) -> Entry<K, V> {
         self.inner.entry(key)
     }
@@ -7,7 +7,7 @@ use crate::{backoff::FetchBackoff, FetchConfig};
 /// The number of times to probe a source between backoff attempts. This needs to be enough to reasonably allow
 /// a source which might be slow to respond to one or two requests to respond to at least one of the probes but
 /// not so high that it wastes time for this node trying to talk to a source that is not responding.
-const NUM_PROBE_ATTEMPTS: u32 = 10;
+pub(crate) const NUM_PROBE_ATTEMPTS: u32 = 10;
 
 /// A source to fetch from: either a node, or an agent on a node
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -58,6 +58,12 @@ impl Sources {
         None
     }
 
+    /// The position of the source which will be considered next.
+    #[cfg(test)]
+    pub(crate) fn index(&self) -> usize {
+        self.index
+    }
+
     pub(crate) fn add(&mut self, source: FetchSource) {
         self.inner.insert(source);
     }
@@ -109,6 +115,12 @@ impl SourceState {
         }
     }
 
+    /// The current state of the source.
+    #[cfg(test)]
+    pub(crate) fn current_state(&self) -> &SourceCurrentState {
+        &self.current_state
+    }
+
     /// Notify the state that a request to this source has timed out.
     pub fn record_timeout(&mut self) {
         if let SourceCurrentState::Available(num_timeouts) = &mut self.current_state {
@@ -182,6 +194,18 @@ impl FetchSourceBackoff {
     fn is_expired(&self) -> bool {
         self.backoff.is_expired()
     }
+
+    /// The underlying backoff.
+    #[cfg(test)]
+    pub(crate) fn backoff(&self) -> &FetchBackoff {
+        &self.backoff
+    }
+
+    /// How many more probes are allowed before waiting for the backoff again.
+    #[cfg(test)]
+    pub(crate) fn probes(&self) -> u32 {
+        self.probes
+    }
 }
 
 #[cfg(test)]
@@ -34,6 +34,7 @@ use tokio::time::Instant;
 
 pub use self::bandwidth::BandwidthThrottle;
 use self::ops::OpsBatchQueue;
+use self::rng::GossipRng;
 use self::state_map::RoundStateMap;
 use self::store::AgentInfoSession;
 use crate::metrics::MetricsSync;
@@ -55,8 +56,12 @@ pub mod test_utils;
 mod accept;
 mod agents;
 mod bloom;
+mod handshake;
 mod initiate;
 mod ops;
+#[cfg(any(test, feature = "test_utils"))]
+mod rng;
+mod round_model;
 mod state_map;
 mod store;
 
@@ -104,6 +109,9 @@ pub struct ShardedGossip {
     pub(crate) state: Share<ShardedGossipState>,
     /// Bandwidth for incoming and outgoing gossip.
     bandwidth: Arc<BandwidthThrottle>,
+    /// Bandwidth for the incoming and outgoing historical gossip of this space,
+    /// if its schedule limits it.
+    schedule_bandwidth: Option<BandwidthThrottle>,
 }
 
 impl std::fmt::Debug for ShardedGossip {
@@ -159,6 +167,24 @@ impl ShardedGossip {
         let state = Default::default();
 
         let tuning_params = config.tuning_params.clone();
+        let rng = GossipRng::new(tuning_params.danger_gossip_seed(), gossip_type);
+
+        // Recent gossip always runs.
+        let schedule = match gossip_type {
+            GossipType::Recent => None,
+            GossipType::Historical => config.historical_gossip_schedule.clone(),
+        };
+        let schedule_bandwidth = schedule
+            .as_ref()
+            .filter(|s| s.max_inbound_kbps.is_some() || s.max_outbound_kbps.is_some())
+            .map(|s| {
+                let mbps = |kbps: Option<u32>| kbps.map_or(0.0, |kbps| kbps as f64 / 1000.0);
+                BandwidthThrottle::new(
+                    mbps(s.max_inbound_kbps),
+                    mbps(s.max_outbound_kbps),
+                    tuning_params.gossip_burst_ratio,
+                )
+            });
 
         let this = Arc::new(Self {
             ep_hnd,
@@ -170,9 +196,13 @@ impl ShardedGossip {
                 inner: Share::new(ShardedGossipLocalState::new(metrics)),
                 gossip_type,
                 closing: AtomicBool::new(false),
+                pause: parking_lot::Mutex::new(GossipPause::default()),
+                schedule,
+                rng,
                 fetch_pool,
             },
             bandwidth,
+            schedule_bandwidth,
         });
 
         let mut refresh_agent_list_timer = std::time::Instant::now();
@@ -197,7 +227,7 @@ impl ShardedGossip {
                     this.run_one_iteration(&mut agent_info_session).await;
                     this.stats(&mut stats);
 
-                    tokio::time::sleep(GOSSIP_LOOP_INTERVAL).await;
+                    tokio::time::sleep(this.gossip.loop_interval()).await;
                 }
                 KitsuneResult::Ok(())
             }
@@ -265,6 +295,20 @@ impl ShardedGossip {
         let timeout = self.gossip.tuning_params.implicit_timeout();
 
         self.bandwidth.outgoing_bytes(bytes).await;
+        if let Some(bandwidth) = &self.schedule_bandwidth {
+            bandwidth.outgoing_bytes(bytes).await;
+        }
+        crate::metrics::METRIC_GOSSIP_BYTE_COUNT.add(
+            bytes as u64,
+            &[
+                opentelemetry_api::KeyValue::new("space", format!("{:?}", self.gossip.space)),
+                opentelemetry_api::KeyValue::new(
+                    "gossip_type",
+                    format!("{:?}", self.gossip.gossip_type),
+                ),
+                opentelemetry_api::KeyValue::new("direction", "send"),
+            ],
+        );
 
         let con = match how.clone() {
             HowToConnect::Con(con, remote_url) => {
@@ -328,6 +372,9 @@ impl ShardedGossip {
 
         if let Some((con, remote_url, msg, bytes)) = incoming {
             self.bandwidth.incoming_bytes(bytes).await;
+            if let Some(bandwidth) = &self.schedule_bandwidth {
+                bandwidth.incoming_bytes(bytes).await;
+            }
             let variant_type = msg
                 .variant_type()
                 .to_string()
@@ -461,6 +508,12 @@ pub struct ShardedGossipLocal {
     host_api: HostApiLegacy,
     inner: Share<ShardedGossipLocalState>,
     closing: AtomicBool,
+    /// Whether rounds are currently initiated and accepted.
+    pause: parking_lot::Mutex<GossipPause>,
+    /// When new rounds may be initiated and accepted, if only at some times.
+    schedule: Option<HistoricalGossipSchedule>,
+    /// The source of randomness for scheduling.
+    rng: GossipRng,
     fetch_pool: FetchPool,
 }
 
@@ -494,6 +547,9 @@ pub struct ShardedGossipLocalState {
     /// Metrics that track remote node states and help guide
     /// the next node to gossip with.
     metrics: MetricsSync,
+    /// Checks every round against the round model.
+    #[cfg(any(test, feature = "test_utils"))]
+    round_recorder: round_model::RoundRecorder,
 }
 
 impl ShardedGossipLocalState {
@@ -523,6 +579,14 @@ impl ShardedGossipLocalState {
             vec![]
         };
         let r = self.round_map.remove(state_key);
+        if let Some(r) = &r {
+            let event = if error {
+                RoundEvent::Failed
+            } else {
+                RoundEvent::Finished
+            };
+            self.record_round_event_with(state_key, event, r);
+        }
         let mut metrics = self.metrics.write();
         if let Some(r) = &r {
             if error {
@@ -538,6 +602,25 @@ impl ShardedGossipLocalState {
         r
     }
 
+    /// Record an event for the round with a node, if the round is running.
+    fn record_round_event(&mut self, key: &NodeCert, event: RoundEvent) {
+        #[cfg(any(test, feature = "test_utils"))]
+        if let Some(state) = self.round_map.peek(key) {
+            self.round_recorder.record(key, event, state);
+        }
+        #[cfg(not(any(test, feature = "test_utils")))]
+        let _ = (key, event);
+    }
+
+    /// Record an event for the round with a node, given the state of the round after the
+    /// event.
+    fn record_round_event_with(&mut self, key: &NodeCert, event: RoundEvent, state: &RoundState) {
+        #[cfg(any(test, feature = "test_utils"))]
+        self.round_recorder.record(key, event, state);
+        #[cfg(not(any(test, feature = "test_utils")))]
+        let _ = (key, event, state);
+    }
+
     fn check_tgt_expired(&mut self, gossip_type: GossipType, round_timeout: Duration) {
         if let Some((remote_agent_list, cert, when_initiated)) = self
             .initiate_tgt
@@ -692,6 +775,28 @@ pub struct RoundState {
     pub(crate) id: String,
 }
 
+/// Something which changes the state of a gossip round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RoundEvent {
+    /// The round was started by initiating or accepting gossip.
+    Started,
+    /// The next batch of op blooms was sent.
+    SentOpBlooms,
+    /// The last op bloom, or region set, was received from the remote node.
+    ReceivedFinalOpBloom,
+    /// The remote node's region set was diffed against ours and the missing regions were
+    /// queued.
+    QueuedRegions,
+    /// The remote node sent every missing op for one of our op blooms, or our region set.
+    ReceivedAllMissingOps,
+    /// The round was removed because it finished.
+    Finished,
+    /// The round was removed because of an error.
+    Failed,
+    /// The round was removed because it timed out.
+    TimedOut,
+}
+
 /// Our region diff and their region diff
 pub type RegionDiffs = Option<(Vec<Region>, Vec<Region>)>;
 
@@ -746,6 +851,24 @@ impl ShardedGossipLocal {
     /// The number of bloom filters we want to send in a single gossip iteration.
     const UPPER_BLOOM_BOUND: usize = 10;
 
+    /// How long to wait between gossip loop iterations.
+    /// With a seeded RNG the interval is jittered by up to half either way,
+    /// so that different seeds give different interleavings between nodes.
+    /// Whether the schedule of the gossip lets new rounds start now.
+    fn is_scheduled(&self) -> bool {
+        self.schedule
+            .as_ref()
+            .map_or(true, |schedule| schedule.is_open(SystemTime::now()))
+    }
+
+    fn loop_interval(&self) -> Duration {
+        if !self.rng.is_seeded() {
+            return GOSSIP_LOOP_INTERVAL;
+        }
+        let jitter: f64 = self.rng.with(|rng| rand::Rng::gen(rng));
+        GOSSIP_LOOP_INTERVAL / 2 + GOSSIP_LOOP_INTERVAL.mul_f64(jitter)
+    }
+
     /// Calculate the time range for a gossip round.
     fn calculate_time_range(&self) -> TimeWindow {
         const NOW: Duration = Duration::from_secs(0);
@@ -815,10 +938,11 @@ impl ShardedGossipLocal {
     fn update_state_if_active(&self, key: NodeCert, state: RoundState) -> KitsuneResult<()> {
         self.inner.share_mut(|i, _| {
             if i.round_map.round_exists(&key) {
-                if state.is_finished() {
+                let finished = state.is_finished();
+                i.round_map.insert(key.clone(), state);
+                i.record_round_event(&key, RoundEvent::SentOpBlooms);
+                if finished {
                     i.remove_state(&key, self.gossip_type, false);
-                } else {
-                    i.round_map.insert(key, state);
                 }
             }
             Ok(())
@@ -838,6 +962,7 @@ impl ShardedGossipLocal {
                     state.is_finished()
                 })
                 .unwrap_or(true);
+            i.record_round_event(state_id, RoundEvent::ReceivedFinalOpBloom);
             if finished {
                 Ok(i.remove_state(state_id, self.gossip_type, false))
             } else {
@@ -855,11 +980,13 @@ impl ShardedGossipLocal {
                 state.has_pending_historical_op_data = false;
                 state.is_finished()
             };
-            if i.round_map
+            let finished = i
+                .round_map
                 .get_mut(state_id)
                 .map(remove_state)
-                .unwrap_or(true)
-            {
+                .unwrap_or(true);
+            i.record_round_event(state_id, RoundEvent::ReceivedAllMissingOps);
+            if finished {
                 Ok(i.remove_state(state_id, self.gossip_type, false))
             } else {
                 Ok(i.round_map.get(state_id).cloned())
@@ -899,16 +1026,31 @@ impl ShardedGossipLocal {
                 intervals,
                 id,
                 agent_list,
+                agent_proofs,
             }) => {
-                self.incoming_initiate(peer_cert, intervals, id, agent_list, agent_info_session)
-                    .await?
+                self.incoming_initiate(
+                    peer_cert,
+                    intervals,
+                    id,
+                    agent_list,
+                    agent_proofs,
+                    agent_info_session,
+                )
+                .await?
             }
             ShardedGossipWire::Accept(Accept {
                 intervals,
                 agent_list,
+                agent_proofs,
             }) => {
-                self.incoming_accept(peer_cert, intervals, agent_list, agent_info_session)
-                    .await?
+                self.incoming_accept(
+                    peer_cert,
+                    intervals,
+                    agent_list,
+                    agent_proofs,
+                    agent_info_session,
+                )
+                .await?
             }
             ShardedGossipWire::Agents(Agents { filter }) => {
                 if let Some(state) = self.get_state(&peer_cert)? {
@@ -1096,6 +1238,7 @@ impl ShardedGossipLocal {
             .share_mut(|i, _| {
                 for (cert, ref r) in i.round_map.take_timed_out_rounds() {
                     tracing::warn!("The node {:?} has timed out its gossip round", cert);
+                    i.record_round_event_with(&cert, RoundEvent::TimedOut, r);
                     let mut metrics = i.metrics.write();
                     metrics.record_error(&r.remote_agent_list, self.gossip_type.into());
                     metrics.complete_current_round(&cert, true);
@@ -1105,6 +1248,14 @@ impl ShardedGossipLocal {
             .ok();
     }
 
+    /// The gossip rounds which didn't match the round model.
+    #[cfg(any(test, feature = "test_utils"))]
+    pub fn round_model_failures(&self) -> Vec<String> {
+        self.inner
+            .share_mut(|i, _| Ok(i.round_recorder.failures()))
+            .unwrap_or_default()
+    }
+
     fn show_local_agents(&self) -> HashSet<Arc<KitsuneAgent>> {
         self.inner
             .share_mut(|i, _| Ok(i.local_agents.clone()))
@@ -1227,6 +1378,9 @@ kitsune_p2p_types::write_codec_enum! {
             id.1: u32,
             /// List of active local agents represented by this node.
             agent_list.2: Vec<AgentInfoSigned>,
+            /// The signature of each agent of the `agent_list` over the handshake,
+            /// proving that this node represents it.
+            agent_proofs.3: Vec<Arc<KitsuneSignature>>,
         },
 
         /// Accept an incoming round of gossip from a remote node
@@ -1236,6 +1390,9 @@ kitsune_p2p_types::write_codec_enum! {
             intervals.0: Vec<ArqBounds>,
             /// List of active local agents represented by this node.
             agent_list.1: Vec<AgentInfoSigned>,
+            /// The signature of each agent of the `agent_list` over the handshake,
+            /// proving that this node represents it.
+            agent_proofs.2: Vec<Arc<KitsuneSignature>>,
         },
 
         /// Send Agent Info Bloom
@@ -1330,7 +1487,22 @@ impl AsGossipModule for ShardedGossip {
         use kitsune_p2p_types::codec::*;
         let (bytes, gossip) =
             ShardedGossipWire::decode_ref(&gossip_data).map_err(KitsuneError::other)?;
+        crate::metrics::METRIC_GOSSIP_BYTE_COUNT.add(
+            bytes as u64,
+            &[
+                opentelemetry_api::KeyValue::new("space", format!("{:?}", self.gossip.space)),
+                opentelemetry_api::KeyValue::new(
+                    "gossip_type",
+                    format!("{:?}", self.gossip.gossip_type),
+                ),
+                opentelemetry_api::KeyValue::new("direction", "recv"),
+            ],
+        );
         let new_initiate = matches!(gossip, ShardedGossipWire::Initiate(_));
+        let refused = new_initiate
+            && (*self.gossip.pause.lock() == GossipPause::All
+                || !self.gossip.is_scheduled()
+                || self.gossip.host_api.is_overloaded(&self.gossip.space));
         self.state.share_mut(move |i, _| {
             let overloaded = i.incoming.len() > 20;
             if overloaded {
@@ -1339,8 +1511,8 @@ impl AsGossipModule for ShardedGossip {
                     i.incoming.len()
                 );
             }
-            // If we are overloaded then return busy to any new initiates.
-            if overloaded && new_initiate {
+            // If we are overloaded or paused then return busy to any new initiates.
+            if (overloaded || refused) && new_initiate {
                 i.push_outgoing([(
                     con.peer_id(),
                     HowToConnect::Con(con, remote_url),
@@ -1384,6 +1556,11 @@ impl AsGossipModule for ShardedGossip {
             Ok(())
         });
     }
+
+    fn set_pause(&self, pause: GossipPause) {
+        tracing::info!(space = ?self.gossip.space, gossip_type = ?self.gossip.gossip_type, ?pause, "Setting gossip pause");
+        *self.gossip.pause.lock() = pause;
+    }
 }
 
 struct ShardedRecentGossipFactory {
@@ -1,5 +1,6 @@
 use kitsune_p2p_types::dht::{spacetime::SpaceDimension, ArqBounds};
 
+use super::handshake::HandshakeRole;
 use super::*;
 
 impl ShardedGossipLocal {
@@ -11,9 +12,10 @@ impl ShardedGossipLocal {
         peer_cert: NodeCert,
         remote_arq_set: Vec<ArqBounds>,
         remote_agent_list: Vec<AgentInfoSigned>,
+        agent_proofs: Vec<Arc<KitsuneSignature>>,
         agent_info_session: &mut AgentInfoSession,
     ) -> KitsuneResult<Vec<ShardedGossipWire>> {
-        let (local_agents, when_initiated, accept_is_from_target) =
+        let (local_agents, when_initiated, accept_is_from_target, tie_break) =
             self.inner.share_mut(|i, _| {
                 let accept_is_from_target = i
                     .initiate_tgt
@@ -21,10 +23,12 @@ impl ShardedGossipLocal {
                     .map(|tgt| tgt.cert == peer_cert)
                     .unwrap_or(false);
                 let when_initiated = i.initiate_tgt.as_ref().and_then(|i| i.when_initiated);
+                let tie_break = i.initiate_tgt.as_ref().map(|tgt| tgt.tie_break);
                 Ok((
                     i.local_agents.clone(),
                     when_initiated,
                     accept_is_from_target,
+                    tie_break,
                 ))
             })?;
 
@@ -49,6 +53,24 @@ impl ShardedGossipLocal {
             return Ok(vec![ShardedGossipWire::no_agents()]);
         }
 
+        // Only gossip with the agents which the remote node proved that it represents,
+        // for the id of our initiate.
+        let remote_agent_list = self
+            .verified_remote_agents(
+                remote_agent_list,
+                agent_proofs,
+                HandshakeRole::Accept,
+                tie_break.unwrap_or_default(),
+                agent_info_session,
+            )
+            .await?;
+        if remote_agent_list.is_empty() {
+            self.remove_target(&peer_cert, true)?;
+            return Ok(vec![ShardedGossipWire::error(
+                "None of the agents of the accept could be verified".to_string(),
+            )]);
+        }
+
         // Get the local intervals.
         let local_agent_arqs: Vec<_> = agent_info_session
             .local_agent_arqs()
@@ -80,11 +102,14 @@ impl ShardedGossipLocal {
             // a stale accept comes in for the same peer cert?
             // Maybe we need to check timestamps on messages or have unique round ids?
 
-            let mut metrics = inner.metrics.write();
-            metrics.update_current_round(&peer_cert, self.gossip_type.into(), &state);
-            metrics.record_initiate(&remote_agent_list, self.gossip_type.into());
+            {
+                let mut metrics = inner.metrics.write();
+                metrics.update_current_round(&peer_cert, self.gossip_type.into(), &state);
+                metrics.record_initiate(&remote_agent_list, self.gossip_type.into());
+            }
 
             inner.round_map.insert(peer_cert.clone(), state);
+            inner.record_round_event(&peer_cert, RoundEvent::Started);
             Ok(())
         })?;
         Ok(gossip)
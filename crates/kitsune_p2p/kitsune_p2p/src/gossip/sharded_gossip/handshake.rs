@@ -0,0 +1,111 @@
+use super::*;
+
+/// Which side of the handshake signed it, so that the proofs of an initiate
+/// can't be passed off as those of an accept or the other way around.
+#[derive(Debug, Clone, Copy)]
+pub(super) enum HandshakeRole {
+    Initiate,
+    Accept,
+}
+
+impl ShardedGossipLocal {
+    /// The payload which the agents of a node sign to prove to the node at `to_url`
+    /// that they're represented by the node they are gossiping through.
+    ///
+    /// The payload is tied to the round by the initiator's id, and to the node
+    /// which it's sent to by its url, so that a node can't relay the proofs it was
+    /// sent to claim agents which it doesn't control.
+    fn handshake_payload(&self, role: HandshakeRole, id: u32, to_url: &str) -> Vec<u8> {
+        let mut payload = b"kitsune-gossip-handshake".to_vec();
+        payload.extend_from_slice(&self.space.0);
+        payload.push(match self.gossip_type {
+            GossipType::Recent => 0,
+            GossipType::Historical => 1,
+        });
+        payload.push(match role {
+            HandshakeRole::Initiate => 0,
+            HandshakeRole::Accept => 1,
+        });
+        payload.extend_from_slice(&id.to_le_bytes());
+        payload.extend_from_slice(to_url.as_bytes());
+        payload
+    }
+
+    /// Have each of the local agents sign the handshake for `to_url`, in the order of
+    /// `agents`.
+    pub(super) async fn sign_handshake(
+        &self,
+        agents: &[AgentInfoSigned],
+        role: HandshakeRole,
+        id: u32,
+        to_url: &str,
+    ) -> KitsuneResult<Vec<Arc<KitsuneSignature>>> {
+        let data = Arc::new(self.handshake_payload(role, id, to_url));
+        let mut proofs = Vec::with_capacity(agents.len());
+        for info in agents {
+            let signature = self
+                .host_api
+                .legacy
+                .sign_network_data(SignNetworkDataEvt {
+                    space: self.space.clone(),
+                    agent: info.agent.clone(),
+                    data: data.clone(),
+                })
+                .await
+                .map_err(KitsuneError::other)?;
+            proofs.push(Arc::new(signature));
+        }
+        Ok(proofs)
+    }
+
+    /// Only keep the remote agents whose proof shows that they signed the handshake
+    /// for one of the urls of this node.
+    pub(super) async fn verified_remote_agents(
+        &self,
+        remote_agent_list: Vec<AgentInfoSigned>,
+        agent_proofs: Vec<Arc<KitsuneSignature>>,
+        role: HandshakeRole,
+        id: u32,
+        agent_info_session: &AgentInfoSession,
+    ) -> KitsuneResult<Vec<AgentInfoSigned>> {
+        let local_urls: HashSet<_> = agent_info_session
+            .get_local_agents()
+            .iter()
+            .flat_map(|info| info.url_list.iter().map(|url| url.as_str().to_string()))
+            .collect();
+        let payloads: Vec<_> = local_urls
+            .iter()
+            .map(|url| self.handshake_payload(role, id, url))
+            .collect();
+
+        let mut verified = Vec::with_capacity(remote_agent_list.len());
+        let num_claimed = remote_agent_list.len();
+        for (info, signature) in remote_agent_list.into_iter().zip(agent_proofs) {
+            let mut is_valid = false;
+            for payload in &payloads {
+                if self
+                    .host_api
+                    .verify_network_data(info.agent.clone(), payload.clone(), signature.clone())
+                    .await
+                    .map_err(KitsuneError::other)?
+                {
+                    is_valid = true;
+                    break;
+                }
+            }
+            if is_valid {
+                verified.push(info);
+            } else {
+                tracing::warn!(agent = ?info.agent, "A gossip partner claimed an agent which didn't sign the handshake");
+            }
+        }
+        if verified.len() < num_claimed {
+            tracing::warn!(
+                claimed = num_claimed,
+                verified = verified.len(),
+                "Only some of the agents of a gossip partner were verified"
+            );
+        }
+        Ok(verified)
+    }
+}
@@ -1,3 +1,4 @@
+use super::handshake::HandshakeRole;
 use super::*;
 use crate::metrics::{GENERATE_OP_BLOOMS_TIME, GENERATE_OP_REGION_SET_TIME};
 use kitsune_p2p_types::dht::{arq::ArqSet, ArqBounds};
@@ -10,6 +11,15 @@ impl ShardedGossipLocal {
         &self,
         agent_info_session: &mut AgentInfoSession,
     ) -> KitsuneResult<Option<Outgoing>> {
+        // Paused gossip doesn't start any new rounds.
+        if *self.pause.lock() != GossipPause::Running {
+            return Ok(None);
+        }
+        // Nor does gossip outside of the windows of its schedule.
+        if !self.is_scheduled() {
+            return Ok(None);
+        }
+
         // Get local agents
         let (has_target, local_agents) = self.inner.share_mut(|i, _| {
             i.check_tgt_expired(self.gossip_type, self.tuning_params.gossip_round_timeout());
@@ -47,14 +57,16 @@ impl ShardedGossipLocal {
             url,
         }) = remote_agent
         {
-            let id = rand::thread_rng().gen();
+            let id = self.rng.with(|rng| rng.gen());
+
+            // Prove to the remote node that we represent our agents.
+            let agent_list = agent_info_session.get_local_agents().to_vec();
+            let agent_proofs = self
+                .sign_handshake(&agent_list, HandshakeRole::Initiate, id, url.as_str())
+                .await?;
 
             // TODO Why send both the agents and the intervals? The agents contain their arcs
-            let gossip = ShardedGossipWire::initiate(
-                intervals,
-                id,
-                agent_info_session.get_local_agents().to_vec(),
-            );
+            let gossip = ShardedGossipWire::initiate(intervals, id, agent_list, agent_proofs);
 
             let tgt = ShardedGossipTarget {
                 remote_agent_list: agent_info_list,
@@ -84,6 +96,7 @@ impl ShardedGossipLocal {
         remote_arqs: Vec<ArqBounds>,
         remote_id: u32,
         remote_agent_list: Vec<AgentInfoSigned>,
+        agent_proofs: Vec<Arc<KitsuneSignature>>,
         agent_info_session: &mut AgentInfoSession,
     ) -> KitsuneResult<Vec<ShardedGossipWire>> {
         let (local_agents, same_as_target, already_in_progress) =
@@ -135,10 +148,42 @@ impl ShardedGossipLocal {
             .map(|arc| arc.to_bounds_std())
             .collect();
 
+        // Only gossip with the agents which the remote node proved that it represents.
+        let remote_agent_list = self
+            .verified_remote_agents(
+                remote_agent_list,
+                agent_proofs,
+                HandshakeRole::Initiate,
+                remote_id,
+                agent_info_session,
+            )
+            .await?;
+        let Some(remote_url) = remote_agent_list
+            .iter()
+            .find_map(|info| info.url_list.first().cloned())
+        else {
+            return Ok(vec![ShardedGossipWire::error(
+                "None of the agents of the initiate could be verified".to_string(),
+            )]);
+        };
+
+        // Prove to the remote node that we represent our agents, for its id.
         let agent_list = agent_info_session.get_local_agents().to_vec();
+        let agent_proofs = self
+            .sign_handshake(
+                &agent_list,
+                HandshakeRole::Accept,
+                remote_id,
+                remote_url.as_str(),
+            )
+            .await?;
 
         // Send the intervals back as the accept message.
-        let mut gossip = vec![ShardedGossipWire::accept(local_arqs.clone(), agent_list)];
+        let mut gossip = vec![ShardedGossipWire::accept(
+            local_arqs.clone(),
+            agent_list,
+            agent_proofs,
+        )];
 
         // Generate the bloom filters and new state.
         let state = self
@@ -166,6 +211,7 @@ impl ShardedGossipLocal {
             }
 
             inner.round_map.insert(peer_cert.clone(), state);
+            inner.record_round_event(&peer_cert, RoundEvent::Started);
 
             // If this is the target then we should clear the when initiated timeout.
             if let Some(tgt) = inner.initiate_tgt.as_mut() {
@@ -2,6 +2,8 @@ use std::cmp::Ordering;
 
 use super::*;
 use crate::metrics::*;
+use kitsune_p2p_types::dht::spacetime::SpaceDimension;
+use kitsune_p2p_types::dht_arc::DhtArcRange;
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 /// A remote node we can connect to.
@@ -90,25 +92,137 @@ impl ShardedGossipLocal {
             }
         }
 
-        let remote_nodes = remote_nodes.into_values().collect();
+        // Agent infos are only gossiped in recent gossip, where gossiping with the nodes
+        // nearest to the parts of our arcs which the fewest peers cover finds the agents
+        // there sooner.
+        let gaps = match self.gossip_type {
+            GossipType::Recent => {
+                let now = std::time::UNIX_EPOCH
+                    .elapsed()
+                    .expect("Your system clock is set before UNIX epoch")
+                    .as_millis() as u64;
+                coverage_gaps(
+                    agent_info_session.get_local_agents(),
+                    agent_info_session
+                        .get_agents()
+                        .iter()
+                        .filter(|a| a.expires_at_ms > now && !local_agents.contains(&a.agent)),
+                )
+            }
+            GossipType::Historical => Vec::new(),
+        };
+
+        let mut remote_nodes: Vec<Node> = remote_nodes.into_values().collect();
+        // Start from the same order every time, so that a seeded RNG makes the same choice.
+        if self.rng.is_seeded() {
+            remote_nodes.sort_unstable_by(|a, b| a.cert.as_slice().cmp(b.cert.as_slice()));
+        }
+        // Nodes whose agents are all deprioritized are only gossiped with when
+        // there is no other node to gossip with.
+        let (deprioritized, remote_nodes): (Vec<_>, Vec<_>) =
+            remote_nodes.into_iter().partition(|node| {
+                node.agent_info_list
+                    .iter()
+                    .all(|info| self.host_api.is_deprioritized(&self.space, &info.agent))
+            });
         let tuning_params = self.tuning_params.clone();
         // We could clone the metrics store out of the lock here but I don't think
         // the next_remote_node will be that slow so we can just choose the next node inline.
         self.inner.share_mut(|i, _| {
-            let node = next_remote_node(remote_nodes, &i.metrics, tuning_params);
+            let node = next_remote_node(
+                remote_nodes,
+                &gaps,
+                &i.metrics,
+                tuning_params.clone(),
+                &self.rng,
+            )
+            .or_else(|| {
+                next_remote_node(deprioritized, &gaps, &i.metrics, tuning_params, &self.rng)
+            });
             Ok(node)
         })
     }
 }
 
+/// The segments of the arcs of the local agents which the fewest remote agents cover,
+/// unless every segment is covered by as many.
+fn coverage_gaps<'a>(
+    local_agents: &[AgentInfoSigned],
+    remote_agents: impl Iterator<Item = &'a AgentInfoSigned>,
+) -> Vec<DhtArcRange> {
+    let remote_arqs: Vec<_> = remote_agents.map(|info| info.storage_arq).collect();
+    let gaps: Vec<_> = local_agents
+        .iter()
+        .flat_map(|info| {
+            kitsune_p2p_types::dht::arq::coverage_gaps(
+                SpaceDimension::standard(),
+                &info.storage_arq,
+                remote_arqs.iter(),
+            )
+        })
+        .collect();
+    let fewest = gaps.iter().map(|gap| gap.num_peers).min();
+    let most = gaps.iter().map(|gap| gap.num_peers).max();
+    match (fewest, most) {
+        (Some(fewest), Some(most)) if fewest < most => gaps
+            .into_iter()
+            .filter(|gap| gap.num_peers == fewest)
+            .map(|gap| gap.segment)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Move the nodes with the agents which come the closest to any of the `gaps` to the
+/// front, keeping the order of the nodes otherwise.
+fn prioritize_coverage_gaps(remote_nodes: Vec<Node>, gaps: &[DhtArcRange]) -> Vec<Node> {
+    if gaps.is_empty() {
+        return remote_nodes;
+    }
+    let distance = |node: &Node| {
+        node.agent_info_list
+            .iter()
+            .map(|info| info.storage_arc().inner())
+            .flat_map(|arc| {
+                gaps.iter().map(move |gap| {
+                    if arc.overlaps(gap) {
+                        return 0;
+                    }
+                    match gap.to_bounds_grouped() {
+                        Some((start, end)) => {
+                            std::cmp::min(arc.dist(start.as_u32()), arc.dist(end.as_u32()))
+                        }
+                        None => u32::MAX,
+                    }
+                })
+            })
+            .min()
+            .unwrap_or(u32::MAX)
+    };
+    let distances: Vec<_> = remote_nodes.iter().map(distance).collect();
+    let closest = match distances.iter().min() {
+        Some(closest) => *closest,
+        None => return remote_nodes,
+    };
+    let (mut nearest, rest): (Vec<_>, Vec<_>) = remote_nodes
+        .into_iter()
+        .zip(distances)
+        .partition(|(_, distance)| *distance == closest);
+    nearest.extend(rest);
+    nearest.into_iter().map(|(node, _)| node).collect()
+}
+
 /// Find the next remote node to sync with.
+///
+/// The nodes nearest to the `gaps` in the arcs of the local agents are tried first.
 fn next_remote_node(
     mut remote_nodes: Vec<Node>,
+    gaps: &[DhtArcRange],
     metrics: &MetricsSync,
     tuning_params: KitsuneP2pTuningParams,
+    rng: &GossipRng,
 ) -> Option<Node> {
     use rand::prelude::*;
-    let mut rng = thread_rng();
 
     // Sort the nodes by longest time since we last successfully gossiped with them.
     // Randomly break ties between nodes we haven't successfully gossiped with.
@@ -126,7 +240,7 @@ fn next_remote_node(
             (None, Some(_)) => Ordering::Less,
             // Randomly break ties.
             (None, None) => {
-                if rng.gen() {
+                if rng.with(|rng| rng.gen()) {
                     Ordering::Less
                 } else {
                     Ordering::Greater
@@ -135,6 +249,8 @@ fn next_remote_node(
         }
     });
 
+    let remote_nodes = prioritize_coverage_gaps(remote_nodes, gaps);
+
     let forced_initiate = metrics.read().forced_initiate();
 
     remote_nodes
@@ -243,14 +359,36 @@ mod tests {
 
         let r = next_remote_node(
             remote_nodes.clone(),
+            &[],
             &Default::default(),
             tuning_params_no_delay(),
+            &GossipRng::default(),
         );
 
         // - That node is chosen.
         assert_eq!(r, remote_nodes.first().cloned());
     }
 
+    #[test]
+    /// Test that the node nearest to a gap in the coverage of our arcs
+    /// is chosen before the other nodes we haven't talked to.
+    fn next_remote_node_prefers_coverage_gaps() {
+        let remote_nodes = create_remote_nodes(10);
+        let near_gap = remote_nodes[7].clone();
+        let gap = near_gap.agent_info_list[0].storage_arc().inner();
+
+        for _ in 0..10 {
+            let r = next_remote_node(
+                remote_nodes.clone(),
+                &[gap],
+                &Default::default(),
+                tuning_params_no_delay(),
+                &GossipRng::default(),
+            );
+            assert_eq!(r, Some(near_gap.clone()));
+        }
+    }
+
     /// Test that given N remote nodes we choose the one
     /// we talked to the least recently.
     #[test_case(1)]
@@ -287,7 +425,13 @@ mod tests {
         // - Push the last node back into the remote nodes.
         remote_nodes.push(last);
 
-        let r = next_remote_node(remote_nodes.clone(), &metrics, tuning_params_no_delay());
+        let r = next_remote_node(
+            remote_nodes.clone(),
+            &[],
+            &metrics,
+            tuning_params_no_delay(),
+            &GossipRng::default(),
+        );
 
         // - Expect the last node to be chosen because it was the least recently gossiped with.
         assert_eq!(r, remote_nodes.last().cloned());
@@ -321,7 +465,13 @@ mod tests {
         // - Push the last node back into the remote nodes.
         remote_nodes.push(last);
 
-        let r = next_remote_node(remote_nodes.clone(), &metrics, tuning_params_no_delay());
+        let r = next_remote_node(
+            remote_nodes.clone(),
+            &[],
+            &metrics,
+            tuning_params_no_delay(),
+            &GossipRng::default(),
+        );
 
         // - Expect the last node to be chosen because it was never gossiped with.
         assert_eq!(r, remote_nodes.last().cloned());
@@ -358,8 +508,14 @@ mod tests {
         let mut chose_last = false;
         let mut chose_second_last = false;
         for _ in 0..100 {
-            let r =
-                next_remote_node(remote_nodes.clone(), &metrics, tuning_params_no_delay()).unwrap();
+            let r = next_remote_node(
+                remote_nodes.clone(),
+                &[],
+                &metrics,
+                tuning_params_no_delay(),
+                &GossipRng::default(),
+            )
+            .unwrap();
             if r == last {
                 chose_last = true;
             } else if r == second_last {
@@ -369,6 +525,30 @@ mod tests {
         assert!(chose_last && chose_second_last);
     }
 
+    #[test]
+    /// Test that a seeded RNG breaks ties the same way every time.
+    fn seeded_rng_breaks_ties_reproducibly() {
+        let remote_nodes = create_remote_nodes(10);
+        let metrics = MetricsSync::default();
+
+        let choose = |seed| {
+            let rng = GossipRng::new(Some(seed), GossipType::Recent);
+            (0..10)
+                .map(|_| {
+                    next_remote_node(
+                        remote_nodes.clone(),
+                        &[],
+                        &metrics,
+                        tuning_params_no_delay(),
+                        &rng,
+                    )
+                    .unwrap()
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(choose(7), choose(7));
+    }
+
     /// Test that given N remote nodes we never choose a current round.
     #[test_case(1)]
     #[test_case(2)]
@@ -391,7 +571,13 @@ mod tests {
                 .record_accept(&node.agent_info_list, GossipModuleType::ShardedRecent);
         }
 
-        let r = next_remote_node(remote_nodes.clone(), &metrics, tuning_params_no_delay());
+        let r = next_remote_node(
+            remote_nodes.clone(),
+            &[],
+            &metrics,
+            tuning_params_no_delay(),
+            &GossipRng::default(),
+        );
 
         // - Without the last node we expect no nodes to be chosen.
         assert!(r.is_none());
@@ -405,7 +591,13 @@ mod tests {
             .record_success(&last.agent_info_list, GossipModuleType::ShardedRecent);
         remote_nodes.push(last);
 
-        let r = next_remote_node(remote_nodes.clone(), &metrics, tuning_params_no_delay());
+        let r = next_remote_node(
+            remote_nodes.clone(),
+            &[],
+            &metrics,
+            tuning_params_no_delay(),
+            &GossipRng::default(),
+        );
 
         // - Now we expect the last node to be chosen.
         // (because we're using "no delay" for the tuning params)
@@ -432,9 +624,11 @@ mod tests {
 
         let r = next_remote_node(
             remote_nodes.clone(),
+            &[],
             &metrics,
             // - Set the tuning params to a delay in the future.
             tuning_params_delay(1000 * 60, 0),
+            &GossipRng::default(),
         );
 
         // - Expect no nodes to be chosen.
@@ -445,9 +639,11 @@ mod tests {
 
         let r = next_remote_node(
             remote_nodes.clone(),
+            &[],
             &metrics,
             // - Set the tuning params to a delay in the future.
             tuning_params_delay(1000 * 60, 0),
+            &GossipRng::default(),
         );
 
         // - Still no result.
@@ -455,9 +651,11 @@ mod tests {
 
         let r = next_remote_node(
             remote_nodes.clone(),
+            &[],
             &metrics,
             // - Set the tuning params to a 9 ms after the successful round.
             tuning_params_delay(9, 0),
+            &GossipRng::default(),
         );
 
         // - Now we should get a result.
@@ -475,9 +673,11 @@ mod tests {
 
         let r = next_remote_node(
             remote_nodes.clone(),
+            &[],
             &metrics,
             // - Set the tuning params to a delay in the future.
             tuning_params_delay(0, 1000 * 60),
+            &GossipRng::default(),
         );
 
         // - Expect no nodes to be chosen.
@@ -488,9 +688,11 @@ mod tests {
 
         let r = next_remote_node(
             remote_nodes.clone(),
+            &[],
             &metrics,
             // - Set the tuning params to a delay in the future.
             tuning_params_delay(0, 1000 * 60),
+            &GossipRng::default(),
         );
 
         // - Still no result.
@@ -498,9 +700,11 @@ mod tests {
 
         let r = next_remote_node(
             remote_nodes.clone(),
+            &[],
             &metrics,
             // - Set the tuning params to a 9 ms after an error round.
             tuning_params_delay(1000 * 60, 9),
+            &GossipRng::default(),
         );
 
         // - Now we should get a result.
@@ -545,9 +749,11 @@ mod tests {
 
         let r = next_remote_node(
             remote_nodes.clone(),
+            &[],
             &metrics,
             // - Set the tuning params to a delay in the future.
             tuning_params_delay(1000 * 60, 0),
+            &GossipRng::default(),
         );
 
         // - Expect no nodes to be chosen.
@@ -558,9 +764,11 @@ mod tests {
 
         let r = next_remote_node(
             remote_nodes.clone(),
+            &[],
             &metrics,
             // - Set the tuning params to a delay in the future.
             tuning_params_delay(1000 * 60, 0),
+            &GossipRng::default(),
         );
 
         // - Expect the last node to be chosen because it was successfully gossiped
@@ -578,9 +786,11 @@ mod tests {
 
         let r = next_remote_node(
             remote_nodes.clone(),
+            &[],
             &metrics,
             // - Set the tuning params to a delay in the future.
             tuning_params_delay(1000 * 60, 0),
+            &GossipRng::default(),
         );
 
         // - Now the first node is the least recently gossiped with.
@@ -597,9 +807,11 @@ mod tests {
 
         let r = next_remote_node(
             remote_nodes.clone(),
+            &[],
             &metrics,
             // - Set the tuning params to a delay in the future.
             tuning_params_delay(1000 * 60, 0),
+            &GossipRng::default(),
         );
         // - Force initiate only forces 2 nodes so now we expect no nodes
         // to be chosen because they are all more recent then the tuning params delay.
@@ -628,9 +840,11 @@ mod tests {
 
         let r = next_remote_node(
             remote_nodes.clone(),
+            &[],
             &metrics,
             // - Set the tuning params to a delay in the future.
             tuning_params_delay(1000 * 60, 0),
+            &GossipRng::default(),
         );
 
         // - Now we expect node 1 to be chosen (unless there is only one node).
@@ -647,9 +861,11 @@ mod tests {
 
         let r = next_remote_node(
             remote_nodes.clone(),
+            &[],
             &metrics,
             // - Set the tuning params to a delay in the future.
             tuning_params_delay(1000 * 60, 0),
+            &GossipRng::default(),
         );
 
         // - Now we expect node 2 to be chosen (unless there is only one node).
@@ -666,9 +882,11 @@ mod tests {
 
         let r = next_remote_node(
             remote_nodes.clone(),
+            &[],
             &metrics,
             // - Set the tuning params to a delay in the future.
             tuning_params_delay(1000 * 60, 0),
+            &GossipRng::default(),
         );
 
         // - We expect no nodes to be chosen because the forced initiate has run out.
@@ -679,9 +897,11 @@ mod tests {
 
         let r = next_remote_node(
             remote_nodes.clone(),
+            &[],
             &metrics,
             // - Set the tuning params to a delay in the future.
             tuning_params_delay(1000 * 60, 0),
+            &GossipRng::default(),
         );
 
         // - Now we expect node 3 to be chosen (unless there is only one or two nodes).
@@ -701,9 +921,11 @@ mod tests {
 
         let r = next_remote_node(
             remote_nodes.clone(),
+            &[],
             &metrics,
             // - Set the tuning params to a delay in the future.
             tuning_params_delay(1000 * 60, 0),
+            &GossipRng::default(),
         );
 
         // - Now we expect node 4 to be chosen (unless there is only one or two nodes).
@@ -720,9 +942,11 @@ mod tests {
 
         let r = next_remote_node(
             remote_nodes.clone(),
+            &[],
             &metrics,
             // - Set the tuning params to a delay in the future.
             tuning_params_delay(1000 * 60, 0),
+            &GossipRng::default(),
         );
 
         // - We expect the 5 node to be chosen because the forced initiate was reset.
@@ -739,9 +963,11 @@ mod tests {
 
         let r = next_remote_node(
             remote_nodes.clone(),
+            &[],
             &metrics,
             // - Set the tuning params to a delay in the future.
             tuning_params_delay(1000 * 60, 0),
+            &GossipRng::default(),
         );
 
         // - Now the reset has run out we get no nodes.
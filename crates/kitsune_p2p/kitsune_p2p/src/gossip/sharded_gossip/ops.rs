@@ -123,6 +123,7 @@ impl ShardedGossipLocal {
                         GossipModuleType::ShardedHistorical,
                         round,
                     );
+                    i.record_round_event(peer_cert, RoundEvent::QueuedRegions);
                 } else {
                     tracing::warn!(
                         "attempting to queue_incoming_regions for round with no cert: {:?}",
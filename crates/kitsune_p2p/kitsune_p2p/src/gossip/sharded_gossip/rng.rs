@@ -0,0 +1,61 @@
+//! The source of randomness for gossip scheduling.
+
+use super::*;
+use rand::rngs::StdRng;
+use rand::{RngCore, SeedableRng};
+
+/// Draws the random values which gossip scheduling depends on: the tie-breaks between
+/// partners, the tie-break values of initiated rounds and the jitter of the gossip loop.
+///
+/// When the `danger_gossip_seed` tuning param is set the values come from an RNG seeded
+/// with it, so that a failing network test can be re-run with the same interleaving.
+#[derive(Default)]
+pub(crate) struct GossipRng(Option<parking_lot::Mutex<StdRng>>);
+
+impl GossipRng {
+    /// Seed the RNG of a gossip module if a seed is set.
+    /// The recent and historical modules of a node get different streams.
+    pub(crate) fn new(seed: Option<u64>, gossip_type: GossipType) -> Self {
+        Self(seed.map(|seed| {
+            let stream = match gossip_type {
+                GossipType::Recent => 0,
+                GossipType::Historical => 1,
+            };
+            parking_lot::Mutex::new(StdRng::seed_from_u64(seed.wrapping_add(stream)))
+        }))
+    }
+
+    /// Whether the values are drawn from a seeded RNG.
+    pub(crate) fn is_seeded(&self) -> bool {
+        self.0.is_some()
+    }
+
+    /// Draw values from the seeded RNG, or the thread RNG if there is no seed.
+    pub(crate) fn with<R>(&self, f: impl FnOnce(&mut dyn RngCore) -> R) -> R {
+        match &self.0 {
+            Some(rng) => f(&mut *rng.lock()),
+            None => f(&mut rand::thread_rng()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn same_seed_draws_same_values() {
+        let draw =
+            |rng: &GossipRng| rng.with(|rng| (0..10).map(|_| rng.gen()).collect::<Vec<u32>>());
+        let a = GossipRng::new(Some(42), GossipType::Recent);
+        let b = GossipRng::new(Some(42), GossipType::Recent);
+        assert!(a.is_seeded());
+        assert_eq!(draw(&a), draw(&b));
+        assert_ne!(
+            draw(&GossipRng::new(Some(42), GossipType::Recent)),
+            draw(&GossipRng::new(Some(42), GossipType::Historical))
+        );
+        assert!(!GossipRng::default().is_seeded());
+    }
+}
@@ -0,0 +1,511 @@
+//! A model of a single gossip round, checked against the rounds which real gossip runs.
+//!
+//! [`RoundRecorder`] keeps the history of every round while it is running: each
+//! [`RoundEvent`] which changed the round, along with the parts of the [`RoundState`] which
+//! decide when the round is finished. When a round ends, [`to_actions`] converts its history
+//! into a sequence of [`RoundAction`]s, which are applied to a [`RoundMachine`]. The round is
+//! checked after every event, so a change to the round state which the model doesn't expect, or
+//! a round which is removed as finished before the model agrees that it is, is recorded as a
+//! failure.
+//!
+//! Since every round run by a [`ShardedGossipLocal`] with `test_utils` enabled is checked, any
+//! gossip test exercises the model, not only the tests written for it.
+
+use super::*;
+
+/// The parts of a [`RoundState`] which decide when the round is finished.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct RoundFlags {
+    /// Whether the round is for historical gossip.
+    pub historical: bool,
+    pub num_expected_op_blooms: u16,
+    pub received_all_incoming_op_blooms: bool,
+    pub regions_are_queued: bool,
+    pub has_pending_historical_op_data: bool,
+    /// Whether there are more op blooms to send in a later batch.
+    pub bloom_batch_pending: bool,
+    /// Whether there are missing ops queued to send to the remote node.
+    pub ops_queued: bool,
+}
+
+impl RoundFlags {
+    /// Project the state of a real round onto the model.
+    pub fn new(state: &RoundState) -> Self {
+        Self {
+            historical: state.region_set_sent.is_some(),
+            num_expected_op_blooms: state.num_expected_op_blooms,
+            received_all_incoming_op_blooms: state.received_all_incoming_op_blooms,
+            regions_are_queued: state.regions_are_queued,
+            has_pending_historical_op_data: state.has_pending_historical_op_data,
+            bloom_batch_pending: state.bloom_batch_cursor.is_some(),
+            ops_queued: !state.ops_batch_queue.is_empty(),
+        }
+    }
+
+    /// Mirrors [`RoundState::is_finished`].
+    pub fn is_finished(&self) -> bool {
+        self.num_expected_op_blooms == 0
+            && !self.has_pending_historical_op_data
+            && self.received_all_incoming_op_blooms
+            && self.regions_are_queued
+            && !self.bloom_batch_pending
+            && !self.ops_queued
+    }
+}
+
+/// Something which happens to a gossip round.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum RoundAction {
+    /// The round starts, after sending its first batch of op blooms for recent gossip, or its
+    /// region set for historical gossip.
+    Start {
+        historical: bool,
+        op_blooms: u16,
+        more_batched: bool,
+    },
+    /// The round was already running, in this state, when it was first recorded.
+    Resume(RoundFlags),
+    /// Another batch of op blooms is sent, after the remote node has responded to every op bloom
+    /// in the previous batch.
+    SendOpBlooms { op_blooms: u16, more_batched: bool },
+    /// The final op bloom, or the region set for historical gossip, is received.
+    ReceiveFinalOpBloom,
+    /// The remote node's region set is diffed against ours and the missing regions are queued.
+    QueueRegions,
+    /// The remote node has sent every missing op for one of our op blooms, or for our region
+    /// set.
+    ReceiveAllMissingOps,
+    /// Missing ops are queued to be sent to the remote node, or the queue is emptied.
+    QueueOps(bool),
+    /// The round is removed because it is finished.
+    Finish,
+    /// The round is removed because of an error.
+    Fail,
+    /// The round is removed because it timed out.
+    TimeOut,
+}
+
+/// Whether a round has started or ended.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum RoundPhase {
+    #[default]
+    NotStarted,
+    Running,
+    Ended,
+}
+
+/// The model of a single gossip round.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub(crate) struct RoundMachine {
+    flags: RoundFlags,
+    phase: RoundPhase,
+}
+
+impl RoundMachine {
+    /// Apply an action, or return why the action can't happen in this state.
+    pub fn apply(&mut self, action: &RoundAction) -> Result<(), String> {
+        let flags = &mut self.flags;
+        match (self.phase, action) {
+            (RoundPhase::NotStarted, RoundAction::Start { .. } | RoundAction::Resume(_)) => {}
+            (RoundPhase::NotStarted, _) => return Err("the round has not started".to_string()),
+            (RoundPhase::Running, RoundAction::Start { .. } | RoundAction::Resume(_)) => {
+                return Err("the round has already started".to_string())
+            }
+            (RoundPhase::Running, _) => {}
+            (RoundPhase::Ended, _) => return Err("the round has already ended".to_string()),
+        }
+
+        match action {
+            RoundAction::Start {
+                historical,
+                op_blooms,
+                more_batched,
+            } => {
+                self.phase = RoundPhase::Running;
+                *flags = RoundFlags {
+                    historical: *historical,
+                    num_expected_op_blooms: *op_blooms,
+                    bloom_batch_pending: *more_batched,
+                    // Recent gossip has no regions to queue, and historical gossip has op data
+                    // to receive for its region set
+                    regions_are_queued: !historical,
+                    has_pending_historical_op_data: *historical,
+                    ..Default::default()
+                };
+                if *historical && (*op_blooms > 0 || *more_batched) {
+                    return Err("historical gossip sent op blooms".to_string());
+                }
+            }
+            RoundAction::Resume(resumed) => {
+                self.phase = RoundPhase::Running;
+                *flags = resumed.clone();
+            }
+            RoundAction::SendOpBlooms {
+                op_blooms,
+                more_batched,
+            } => {
+                if !flags.bloom_batch_pending {
+                    return Err("op blooms were sent with no batch pending".to_string());
+                }
+                if flags.num_expected_op_blooms > 0 {
+                    return Err(
+                        "op blooms were sent before every op bloom in the previous batch had a response"
+                            .to_string(),
+                    );
+                }
+                flags.num_expected_op_blooms = *op_blooms;
+                flags.bloom_batch_pending = *more_batched;
+            }
+            RoundAction::ReceiveFinalOpBloom => {
+                flags.received_all_incoming_op_blooms = true;
+            }
+            RoundAction::QueueRegions => {
+                if !flags.historical {
+                    return Err("regions were queued for recent gossip".to_string());
+                }
+                flags.regions_are_queued = true;
+            }
+            RoundAction::ReceiveAllMissingOps => {
+                if flags.num_expected_op_blooms == 0 && !flags.has_pending_historical_op_data {
+                    return Err("missing ops were received with none expected".to_string());
+                }
+                flags.num_expected_op_blooms = flags.num_expected_op_blooms.saturating_sub(1);
+                // There is only ever one batch of op regions
+                flags.has_pending_historical_op_data = false;
+            }
+            RoundAction::QueueOps(queued) => {
+                flags.ops_queued = *queued;
+            }
+            RoundAction::Finish => {
+                if !flags.is_finished() {
+                    return Err("the round was removed as finished before it finished".to_string());
+                }
+                self.phase = RoundPhase::Ended;
+            }
+            RoundAction::Fail | RoundAction::TimeOut => {
+                self.phase = RoundPhase::Ended;
+            }
+        }
+        Ok(())
+    }
+
+    /// Check the recorded history of a round against the model.
+    pub fn check_history(history: &[RecordedEvent]) -> Result<(), String> {
+        let mut machine = Self::default();
+        for (i, (action, expected)) in to_actions(history).into_iter().enumerate() {
+            machine
+                .apply(&action)
+                .and_then(|_| match expected {
+                    Some(flags) if flags != machine.flags => Err(format!(
+                        "the round is {flags:?} but the model expects {:?}",
+                        machine.flags
+                    )),
+                    _ => Ok(()),
+                })
+                .map_err(|e| format!("{e}, at action {i} ({action:?}) of {history:#?}"))?;
+        }
+        Ok(())
+    }
+}
+
+/// An event recorded for a round, with the state of the round after it.
+#[derive(Clone, Debug)]
+pub(crate) struct RecordedEvent {
+    pub event: RoundEvent,
+    pub flags: RoundFlags,
+}
+
+/// Convert the history of a round into the actions which the model takes, each with the
+/// state of the round which the model should be in after it, if known.
+///
+/// Changes to the missing ops queue aren't recorded as events since the queue is shared by
+/// every copy of the round state, so they are found by comparing the queue before and after
+/// each event.
+pub(crate) fn to_actions(history: &[RecordedEvent]) -> Vec<(RoundAction, Option<RoundFlags>)> {
+    let mut actions = Vec::new();
+    let mut ops_queued = false;
+
+    for (i, RecordedEvent { event, flags }) in history.iter().enumerate() {
+        let action = match (i, event) {
+            (_, RoundEvent::Started) => RoundAction::Start {
+                historical: flags.historical,
+                op_blooms: flags.num_expected_op_blooms,
+                more_batched: flags.bloom_batch_pending,
+            },
+            // A round which was already running when its first event was recorded is
+            // resumed in the model
+            (0, _) => RoundAction::Resume(RoundFlags {
+                ops_queued: false,
+                ..flags.clone()
+            }),
+            (_, RoundEvent::SentOpBlooms) => RoundAction::SendOpBlooms {
+                op_blooms: flags.num_expected_op_blooms,
+                more_batched: flags.bloom_batch_pending,
+            },
+            (_, RoundEvent::ReceivedFinalOpBloom) => RoundAction::ReceiveFinalOpBloom,
+            (_, RoundEvent::QueuedRegions) => RoundAction::QueueRegions,
+            (_, RoundEvent::ReceivedAllMissingOps) => RoundAction::ReceiveAllMissingOps,
+            (_, RoundEvent::Finished) => RoundAction::Finish,
+            (_, RoundEvent::Failed) => RoundAction::Fail,
+            (_, RoundEvent::TimedOut) => RoundAction::TimeOut,
+        };
+
+        // The queue may have changed at any point before this event, so it's changed in the
+        // model just before the event, unless that would start the round with queued ops.
+        if i == 0 {
+            actions.push((action, None));
+            if flags.ops_queued {
+                actions.push((RoundAction::QueueOps(true), None));
+            }
+        } else {
+            if flags.ops_queued != ops_queued {
+                actions.push((RoundAction::QueueOps(flags.ops_queued), None));
+            }
+            actions.push((action, None));
+        }
+        ops_queued = flags.ops_queued;
+        if let Some((_, expected)) = actions.last_mut() {
+            *expected = Some(flags.clone());
+        }
+    }
+
+    actions
+}
+
+/// Records the history of every running round, and checks each round against the model when
+/// it ends.
+#[derive(Default)]
+pub(crate) struct RoundRecorder {
+    rounds: HashMap<NodeCert, (String, Vec<RecordedEvent>)>,
+    failures: Vec<String>,
+}
+
+impl RoundRecorder {
+    /// Record an event for the round with a node, given the state of the round after the event.
+    pub fn record(&mut self, key: &NodeCert, event: RoundEvent, state: &RoundState) {
+        // A round with a new id replaces the round which was running with the node, which
+        // may have been removed without an event, such as when it finished while being
+        // checked for a timeout.
+        if self
+            .rounds
+            .get(key)
+            .is_some_and(|(running, _)| *running != state.id)
+        {
+            if let Some((_, history)) = self.rounds.remove(key) {
+                self.check(&history);
+            }
+        }
+
+        let recorded = RecordedEvent {
+            event,
+            flags: RoundFlags::new(state),
+        };
+        self.rounds
+            .entry(key.clone())
+            .or_insert_with(|| (state.id.clone(), Vec::new()))
+            .1
+            .push(recorded);
+
+        if matches!(
+            event,
+            RoundEvent::Finished | RoundEvent::Failed | RoundEvent::TimedOut
+        ) {
+            if let Some((_, history)) = self.rounds.remove(key) {
+                self.check(&history);
+            }
+        }
+    }
+
+    /// The failures found when checking rounds against the model.
+    pub fn failures(&self) -> Vec<String> {
+        self.failures.clone()
+    }
+
+    fn check(&mut self, history: &[RecordedEvent]) {
+        if let Err(e) = RoundMachine::check_history(history) {
+            tracing::error!("Gossip round doesn't match the round model: {}", e);
+            self.failures.push(e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn recorded(event: RoundEvent, flags: RoundFlags) -> RecordedEvent {
+        RecordedEvent { event, flags }
+    }
+
+    fn started(num_expected_op_blooms: u16) -> RoundFlags {
+        RoundFlags {
+            num_expected_op_blooms,
+            regions_are_queued: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn recent_round_matches_model() {
+        let received_bloom = RoundFlags {
+            received_all_incoming_op_blooms: true,
+            ..started(1)
+        };
+        let received_ops = RoundFlags {
+            num_expected_op_blooms: 0,
+            ..received_bloom.clone()
+        };
+        let history = [
+            recorded(RoundEvent::Started, started(1)),
+            recorded(RoundEvent::ReceivedFinalOpBloom, received_bloom),
+            recorded(RoundEvent::ReceivedAllMissingOps, received_ops.clone()),
+            recorded(RoundEvent::Finished, received_ops),
+        ];
+
+        assert_eq!(
+            vec![
+                RoundAction::Start {
+                    historical: false,
+                    op_blooms: 1,
+                    more_batched: false,
+                },
+                RoundAction::ReceiveFinalOpBloom,
+                RoundAction::ReceiveAllMissingOps,
+                RoundAction::Finish,
+            ],
+            to_actions(&history)
+                .into_iter()
+                .map(|(action, _)| action)
+                .collect::<Vec<_>>()
+        );
+        RoundMachine::check_history(&history).unwrap();
+    }
+
+    #[test]
+    fn batched_round_matches_model() {
+        let first_batch = RoundFlags {
+            bloom_batch_pending: true,
+            ..started(2)
+        };
+        let received_bloom = RoundFlags {
+            received_all_incoming_op_blooms: true,
+            ..first_batch.clone()
+        };
+        let responded = RoundFlags {
+            num_expected_op_blooms: 0,
+            ..received_bloom.clone()
+        };
+        let second_batch = RoundFlags {
+            num_expected_op_blooms: 1,
+            bloom_batch_pending: false,
+            ..responded.clone()
+        };
+        let finished = RoundFlags {
+            num_expected_op_blooms: 0,
+            ..second_batch.clone()
+        };
+        let history = [
+            recorded(RoundEvent::Started, first_batch),
+            recorded(RoundEvent::ReceivedFinalOpBloom, received_bloom),
+            recorded(
+                RoundEvent::ReceivedAllMissingOps,
+                RoundFlags {
+                    num_expected_op_blooms: 1,
+                    ..responded.clone()
+                },
+            ),
+            recorded(RoundEvent::ReceivedAllMissingOps, responded),
+            recorded(RoundEvent::SentOpBlooms, second_batch),
+            recorded(RoundEvent::ReceivedAllMissingOps, finished.clone()),
+            recorded(RoundEvent::Finished, finished),
+        ];
+        RoundMachine::check_history(&history).unwrap();
+    }
+
+    #[test]
+    fn historical_round_matches_model() {
+        let started = RoundFlags {
+            historical: true,
+            has_pending_historical_op_data: true,
+            ..Default::default()
+        };
+        let received_regions = RoundFlags {
+            received_all_incoming_op_blooms: true,
+            ..started.clone()
+        };
+        let queued = RoundFlags {
+            regions_are_queued: true,
+            ..received_regions.clone()
+        };
+        let finished = RoundFlags {
+            has_pending_historical_op_data: false,
+            ..queued.clone()
+        };
+        let history = [
+            recorded(RoundEvent::Started, started),
+            recorded(RoundEvent::ReceivedFinalOpBloom, received_regions),
+            recorded(RoundEvent::QueuedRegions, queued),
+            recorded(RoundEvent::ReceivedAllMissingOps, finished.clone()),
+            recorded(RoundEvent::Finished, finished),
+        ];
+        RoundMachine::check_history(&history).unwrap();
+    }
+
+    #[test]
+    fn unfinished_round_removed_as_finished_fails() {
+        let history = [
+            recorded(RoundEvent::Started, started(1)),
+            recorded(RoundEvent::Finished, started(1)),
+        ];
+        assert!(RoundMachine::check_history(&history).is_err());
+    }
+
+    #[test]
+    fn unexpected_state_change_fails() {
+        // Receiving the final op bloom shouldn't change the number of expected op blooms
+        let history = [
+            recorded(RoundEvent::Started, started(1)),
+            recorded(
+                RoundEvent::ReceivedFinalOpBloom,
+                RoundFlags {
+                    received_all_incoming_op_blooms: true,
+                    ..started(2)
+                },
+            ),
+        ];
+        assert!(RoundMachine::check_history(&history).is_err());
+    }
+
+    #[test]
+    fn queued_ops_keep_round_running() {
+        let received = RoundFlags {
+            received_all_incoming_op_blooms: true,
+            ..started(0)
+        };
+        let queued = RoundFlags {
+            ops_queued: true,
+            ..received.clone()
+        };
+        // Finishing while ops are still queued must fail
+        let history = [
+            recorded(RoundEvent::Started, started(0)),
+            recorded(RoundEvent::ReceivedFinalOpBloom, queued.clone()),
+            recorded(RoundEvent::Finished, queued),
+        ];
+        assert!(RoundMachine::check_history(&history).is_err());
+
+        // But once the queue is empty the round can finish
+        let history = [
+            recorded(RoundEvent::Started, started(0)),
+            recorded(
+                RoundEvent::ReceivedFinalOpBloom,
+                RoundFlags {
+                    ops_queued: true,
+                    ..received.clone()
+                },
+            ),
+            recorded(RoundEvent::Finished, received),
+        ];
+        RoundMachine::check_history(&history).unwrap();
+    }
+}
@@ -43,6 +43,12 @@ impl RoundStateMap {
         self.map.get_mut(key)
     }
 
+    /// Get the state without touching it or checking if it has timed out.
+    #[cfg(any(test, feature = "test_utils"))]
+    pub(super) fn peek(&self, key: &NodeCert) -> Option<&RoundState> {
+        self.map.get(key)
+    }
+
     /// Remove the state.
     pub(super) fn remove(&mut self, key: &NodeCert) -> Option<RoundState> {
         self.map.remove(key)
@@ -6,6 +6,7 @@ use super::*;
 
 mod bloom;
 mod common;
+mod handshake;
 mod ops;
 mod test_two_nodes;
 
@@ -29,6 +30,9 @@ impl ShardedGossipLocal {
             host_api: host,
             inner: Share::new(inner),
             closing: std::sync::atomic::AtomicBool::new(false),
+            pause: parking_lot::Mutex::new(GossipPause::default()),
+            schedule: None,
+            rng: Default::default(),
             fetch_pool,
         }
     }
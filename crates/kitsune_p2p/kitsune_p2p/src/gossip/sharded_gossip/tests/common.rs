@@ -136,6 +136,30 @@ impl KitsuneHost for StandardResponsesHostApi {
     ) -> crate::KitsuneHostResult<Vec<OpHashSized>> {
         todo!()
     }
+
+    fn verify_network_data(
+        &self,
+        agent: Arc<KitsuneAgent>,
+        data: Vec<u8>,
+        signature: Arc<KitsuneSignature>,
+    ) -> crate::KitsuneHostResult<bool> {
+        box_fut(Ok(*signature == fake_signature(&agent, &data)))
+    }
+}
+
+/// A stand-in for the signature of `agent` over `data`, so that the test hosts
+/// can sign and check handshakes without holding real keys for their agents.
+pub fn fake_signature(agent: &KitsuneAgent, data: &[u8]) -> KitsuneSignature {
+    KitsuneSignature(
+        blake2b_simd::Params::new()
+            .hash_length(64)
+            .to_state()
+            .update(&agent.0)
+            .update(data)
+            .finalize()
+            .as_bytes()
+            .to_vec(),
+    )
 }
 
 // TODO: integrate with `HandlerBuilder`
@@ -191,6 +215,12 @@ async fn standard_responses(
     evt_handler
         .expect_handle_receive_ops()
         .returning(|_, _, _| Ok(async { Ok(()) }.boxed().into()));
+    evt_handler
+        .expect_handle_sign_network_data()
+        .returning(|input| {
+            let signature = fake_signature(&input.agent, &input.data);
+            Ok(async move { Ok(signature) }.boxed().into())
+        });
 
     (evt_handler, Arc::new(host_api))
 }
@@ -0,0 +1,201 @@
+use super::common::*;
+use super::*;
+use crate::gossip::sharded_gossip::handshake::HandshakeRole;
+
+/// Alice gossips with bob, and carol is a third node whose url alice's proofs
+/// must not be valid for.
+struct Handshake {
+    alice: ShardedGossipLocal,
+    alice_info: AgentInfoSigned,
+    carol_info: AgentInfoSigned,
+    bob: ShardedGossipLocal,
+    bob_session: AgentInfoSession,
+    bob_url: String,
+}
+
+impl Handshake {
+    async fn new() -> Self {
+        let agents = agents_with_infos(3).await;
+        let mut iter = agents.clone().into_iter();
+        let (alice_agent, alice_info) = iter.next().unwrap();
+        let (bob_agent, bob_info) = iter.next().unwrap();
+        let (_, carol_info) = iter.next().unwrap();
+
+        let alice = setup_standard_player(
+            ShardedGossipLocalState {
+                local_agents: maplit::hashset! { alice_agent },
+                ..Default::default()
+            },
+            agents.clone(),
+        )
+        .await;
+        let bob = setup_standard_player(
+            ShardedGossipLocalState {
+                local_agents: maplit::hashset! { bob_agent },
+                ..Default::default()
+            },
+            agents,
+        )
+        .await;
+        let bob_url = bob_info.url_list[0].as_str().to_string();
+
+        Self {
+            alice,
+            alice_info,
+            carol_info,
+            bob,
+            bob_session: AgentInfoSession::new(vec![bob_info], vec![]),
+            bob_url,
+        }
+    }
+
+    /// Have alice sign the handshake for `to_url` with each of `agents`.
+    async fn sign(
+        &self,
+        agents: &[AgentInfoSigned],
+        role: HandshakeRole,
+        id: u32,
+        to_url: &str,
+    ) -> Vec<Arc<KitsuneSignature>> {
+        self.alice
+            .sign_handshake(agents, role, id, to_url)
+            .await
+            .unwrap()
+    }
+
+    /// The agents which bob accepts from an initiate of round 1.
+    async fn verify(
+        &self,
+        agents: Vec<AgentInfoSigned>,
+        proofs: Vec<Arc<KitsuneSignature>>,
+    ) -> Vec<Arc<KitsuneAgent>> {
+        self.bob
+            .verified_remote_agents(
+                agents,
+                proofs,
+                HandshakeRole::Initiate,
+                1,
+                &self.bob_session,
+            )
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|info| info.agent.clone())
+            .collect()
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn signed_agents_are_verified() {
+    let h = Handshake::new().await;
+    let proofs = h
+        .sign(
+            &[h.alice_info.clone()],
+            HandshakeRole::Initiate,
+            1,
+            &h.bob_url,
+        )
+        .await;
+
+    let verified = h.verify(vec![h.alice_info.clone()], proofs).await;
+
+    assert_eq!(verified, vec![h.alice_info.agent.clone()]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn unsigned_agents_are_dropped() {
+    let h = Handshake::new().await;
+    // Alice only signs with its own agent but claims carol's agent as well.
+    let proofs = h
+        .sign(
+            &[h.alice_info.clone()],
+            HandshakeRole::Initiate,
+            1,
+            &h.bob_url,
+        )
+        .await;
+
+    let verified = h
+        .verify(vec![h.alice_info.clone(), h.carol_info.clone()], proofs)
+        .await;
+
+    assert_eq!(verified, vec![h.alice_info.agent.clone()]);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn forged_signatures_are_dropped() {
+    let h = Handshake::new().await;
+    let zeros = vec![Arc::new(KitsuneSignature(vec![0; 64]))];
+    assert!(h.verify(vec![h.alice_info.clone()], zeros).await.is_empty());
+
+    // A proof made by another agent doesn't prove alice's agent.
+    let carols_proof = h
+        .sign(
+            &[h.carol_info.clone()],
+            HandshakeRole::Initiate,
+            1,
+            &h.bob_url,
+        )
+        .await;
+    assert!(h
+        .verify(vec![h.alice_info.clone()], carols_proof)
+        .await
+        .is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn proofs_for_another_node_are_dropped() {
+    let h = Handshake::new().await;
+    // A proof which alice sent to carol can't be replayed to bob.
+    let carol_url = h.carol_info.url_list[0].as_str().to_string();
+    let proofs = h
+        .sign(
+            &[h.alice_info.clone()],
+            HandshakeRole::Initiate,
+            1,
+            &carol_url,
+        )
+        .await;
+
+    assert!(h
+        .verify(vec![h.alice_info.clone()], proofs)
+        .await
+        .is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn proofs_for_another_round_are_dropped() {
+    let h = Handshake::new().await;
+    let proofs = h
+        .sign(
+            &[h.alice_info.clone()],
+            HandshakeRole::Initiate,
+            2,
+            &h.bob_url,
+        )
+        .await;
+
+    assert!(h
+        .verify(vec![h.alice_info.clone()], proofs)
+        .await
+        .is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn proofs_for_the_other_role_are_dropped() {
+    let h = Handshake::new().await;
+    // The proofs of an accept can't be passed off as those of an initiate.
+    let proofs = h
+        .sign(
+            &[h.alice_info.clone()],
+            HandshakeRole::Accept,
+            1,
+            &h.bob_url,
+        )
+        .await;
+
+    assert!(h
+        .verify(vec![h.alice_info.clone()], proofs)
+        .await
+        .is_empty());
+}
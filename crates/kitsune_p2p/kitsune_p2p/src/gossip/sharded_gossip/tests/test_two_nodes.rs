@@ -136,6 +136,10 @@ async fn sharded_sanity_test() {
             Ok(())
         })
         .unwrap();
+
+    // - Both rounds match the round model.
+    assert_eq!(alice.round_model_failures(), Vec::<String>::new());
+    assert_eq!(bob.round_model_failures(), Vec::<String>::new());
 }
 
 #[tokio::test(flavor = "multi_thread")]
@@ -557,6 +561,10 @@ async fn no_data_still_finishes() {
             Ok(())
         })
         .unwrap();
+
+    // - Both rounds match the round model.
+    assert_eq!(alice.round_model_failures(), Vec::<String>::new());
+    assert_eq!(bob.round_model_failures(), Vec::<String>::new());
 }
 
 #[tokio::test(flavor = "multi_thread")]
@@ -864,3 +872,87 @@ async fn initiate_times_out() {
         })
         .unwrap();
 }
+
+#[tokio::test(flavor = "multi_thread")]
+/// This test checks that no round is initiated while gossip is paused,
+/// and that initiating works again once it is resumed.
+async fn paused_gossip_is_not_initiated() {
+    let agents = agents_with_infos(2).await;
+    let all_agents: Vec<AgentInfoSigned> = agents.iter().map(|x| x.1.clone()).collect();
+    let alice = setup_empty_player(
+        ShardedGossipLocalState {
+            local_agents: maplit::hashset!(agents[0].0.clone()),
+            ..Default::default()
+        },
+        agents.clone(),
+    )
+    .await;
+
+    // - Alice pauses gossip and doesn't initiate.
+    *alice.pause.lock() = GossipPause::Initiation;
+    let alice_initiate = alice
+        .try_initiate(&mut AgentInfoSession::new(
+            alice.query_agents_by_local_agents().await.unwrap(),
+            all_agents.clone(),
+        ))
+        .await
+        .unwrap();
+    assert!(alice_initiate.is_none());
+
+    // - Alice resumes gossip and initiates with bob.
+    *alice.pause.lock() = GossipPause::Running;
+    let alice_initiate = alice
+        .try_initiate(&mut AgentInfoSession::new(
+            alice.query_agents_by_local_agents().await.unwrap(),
+            all_agents.clone(),
+        ))
+        .await
+        .unwrap();
+    assert!(alice_initiate.is_some());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+/// This test checks that historical gossip isn't initiated outside of the
+/// windows of its schedule.
+async fn historical_gossip_is_only_initiated_in_its_windows() {
+    let agents = agents_with_infos(2).await;
+    let all_agents: Vec<AgentInfoSigned> = agents.iter().map(|x| x.1.clone()).collect();
+    let mut alice = setup_empty_player(
+        ShardedGossipLocalState {
+            local_agents: maplit::hashset!(agents[0].0.clone()),
+            ..Default::default()
+        },
+        agents.clone(),
+    )
+    .await;
+    let hour = (std::time::UNIX_EPOCH.elapsed().unwrap().as_secs() / 60 / 60 % 24) as u8;
+    let window = |start_hour: u8| HistoricalGossipSchedule {
+        windows: vec![GossipWindow {
+            start_hour,
+            end_hour: (start_hour + 1) % 24,
+        }],
+        ..Default::default()
+    };
+
+    // - The window has yet to open, so alice doesn't initiate.
+    alice.schedule = Some(window((hour + 1) % 24));
+    let alice_initiate = alice
+        .try_initiate(&mut AgentInfoSession::new(
+            alice.query_agents_by_local_agents().await.unwrap(),
+            all_agents.clone(),
+        ))
+        .await
+        .unwrap();
+    assert!(alice_initiate.is_none());
+
+    // - The window is open, so alice initiates with bob.
+    alice.schedule = Some(window(hour));
+    let alice_initiate = alice
+        .try_initiate(&mut AgentInfoSession::new(
+            alice.query_agents_by_local_agents().await.unwrap(),
+            all_agents.clone(),
+        ))
+        .await
+        .unwrap();
+    assert!(alice_initiate.is_some());
+}
@@ -5,7 +5,7 @@ use must_future::MustBoxFuture;
 use std::sync::Arc;
 
 use kitsune_p2p_types::{
-    bin_types::KitsuneSpace,
+    bin_types::{KitsuneAgent, KitsuneSignature, KitsuneSpace},
     dependencies::lair_keystore_api,
     dht::{
         region::{Region, RegionCoords},
@@ -105,6 +105,20 @@ pub trait KitsuneHost: 'static + Send + Sync + std::fmt::Debug {
         .into()
     }
 
+    /// Whether the host can't take in more ops for a space for now, e.g. because it's
+    /// behind on validating the ops it has. Ops aren't fetched for the space and new
+    /// gossip rounds are answered with busy until it can again.
+    fn is_overloaded(&self, _space: &KitsuneSpace) -> bool {
+        false
+    }
+
+    /// Whether gossip with an agent should only be initiated once there is no other
+    /// node to gossip with, e.g. because its key was revoked. A node is only put
+    /// behind the others if every agent on it is deprioritized.
+    fn is_deprioritized(&self, _space: &KitsuneSpace, _agent: &KitsuneAgent) -> bool {
+        false
+    }
+
     /// Do something whenever a batch of op hashes was received and stored in the FetchPool
     fn handle_op_hash_received(
         &self,
@@ -123,6 +137,17 @@ pub trait KitsuneHost: 'static + Send + Sync + std::fmt::Debug {
     ) {
     }
 
+    /// Check that `signature` is the signature of `agent` over `data`, e.g. over the
+    /// gossip handshake of a node which claims to represent the agent.
+    /// There is no default, because a host which accepted every signature couldn't
+    /// tell which agents its gossip partners really represent.
+    fn verify_network_data(
+        &self,
+        agent: Arc<KitsuneAgent>,
+        data: Vec<u8>,
+        signature: Arc<KitsuneSignature>,
+    ) -> KitsuneHostResult<bool>;
+
     /// Get the lair "tag" identifying the id seed to use for crypto signing.
     /// (this is currently only used in tx5/WebRTC if that feature is enabled.)
     fn lair_tag(&self) -> Option<Arc<str>> {
@@ -134,6 +159,12 @@ pub trait KitsuneHost: 'static + Send + Sync + std::fmt::Debug {
     fn lair_client(&self) -> Option<lair_keystore_api::LairClient> {
         None
     }
+
+    /// Get the bootstrap service to use instead of the bootstrap server of the
+    /// config, e.g. a [`kitsune_p2p_bootstrap_client::LocalBootstrap`] in tests.
+    fn bootstrap_service(&self) -> Option<kitsune_p2p_bootstrap_client::DynBootstrapService> {
+        None
+    }
 }
 
 /// Trait object for the host interface
@@ -145,6 +145,20 @@ pub trait KitsuneHostDefaultError: KitsuneHost + FetchPoolConfig {
         .into()))
     }
 
+    fn verify_network_data(
+        &self,
+        _agent: Arc<KitsuneAgent>,
+        _data: Vec<u8>,
+        _signature: Arc<KitsuneSignature>,
+    ) -> KitsuneHostResult<bool> {
+        box_fut(Err(format!(
+            "error for unimplemented KitsuneHost test behavior: method {} of {}",
+            "verify_network_data",
+            Self::NAME
+        )
+        .into()))
+    }
+
     fn merge_fetch_contexts(&self, _a: u32, _b: u32) -> u32 {
         0
     }
@@ -226,4 +240,13 @@ impl<T: KitsuneHostDefaultError> KitsuneHost for T {
     ) -> KitsuneHostResult<Vec<OpHashSized>> {
         KitsuneHostDefaultError::query_op_hashes_by_region(self, space, region)
     }
+
+    fn verify_network_data(
+        &self,
+        agent: Arc<KitsuneAgent>,
+        data: Vec<u8>,
+        signature: Arc<KitsuneSignature>,
+    ) -> KitsuneHostResult<bool> {
+        KitsuneHostDefaultError::verify_network_data(self, agent, data, signature)
+    }
 }
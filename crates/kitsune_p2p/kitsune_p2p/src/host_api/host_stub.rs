@@ -234,6 +234,15 @@ impl KitsuneHost for HostStub {
             KitsuneHost::check_op_data(&self.err, space, op_hash_list, context)
         }
     }
+
+    fn verify_network_data(
+        &self,
+        agent: Arc<KitsuneAgent>,
+        data: Vec<u8>,
+        signature: Arc<KitsuneSignature>,
+    ) -> KitsuneHostResult<bool> {
+        crate::test_util::verify_ed25519_network_data(&agent, data, &signature)
+    }
 }
 
 impl FetchPoolConfig for HostStub {
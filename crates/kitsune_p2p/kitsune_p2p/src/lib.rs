@@ -61,6 +61,8 @@ pub use host_api::*;
 
 pub use meta_net::PreflightUserData;
 
+pub mod switchboard;
+
 #[allow(missing_docs)]
 #[cfg(feature = "test_utils")]
 pub mod test_util;
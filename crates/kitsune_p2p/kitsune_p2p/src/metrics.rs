@@ -57,6 +57,30 @@ pub(crate) static GENERATE_OP_REGION_SET_TIME: Lazy<opentelemetry_api::metrics::
             .init()
     });
 
+pub(crate) static METRIC_GOSSIP_BYTE_COUNT: Lazy<opentelemetry_api::metrics::Counter<u64>> =
+    Lazy::new(|| {
+        opentelemetry_api::global::meter("kitsune")
+            .u64_counter("kitsune.gossip.byte.count")
+            .with_description("Bytes of gossip messages sent and received")
+            .with_unit(opentelemetry_api::metrics::Unit::new("By"))
+            .init()
+    });
+
+/// Report the number of items in the fetch pool whenever metrics are collected.
+pub(crate) fn register_fetch_pool_size_metric(fetch_pool: kitsune_p2p_fetch::FetchPool) {
+    let meter = opentelemetry_api::global::meter("kitsune");
+    let gauge = meter
+        .u64_observable_gauge("kitsune.fetch_pool.size")
+        .with_description("The number of items waiting to be fetched")
+        .init();
+    let registration_result = meter.register_callback(&[gauge.as_any()], move |observer| {
+        observer.observe_u64(&gauge, fetch_pool.len() as u64, &[])
+    });
+    if let Err(e) = registration_result {
+        tracing::error!("Failed to register callback for metric: {:?}", e);
+    }
+}
+
 /// how long historical metric records should be kept
 /// (currently set to 1 week)
 const HISTORICAL_RECORD_EXPIRE_DURATION_MICROS: i64 = 1000 * 1000 * 60 * 60 * 24 * 7;
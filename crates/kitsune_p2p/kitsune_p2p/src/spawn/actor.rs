@@ -12,7 +12,8 @@ use kitsune_p2p_bootstrap_client::BootstrapNet;
 use kitsune_p2p_fetch::*;
 use kitsune_p2p_types::agent_info::AgentInfoSigned;
 use kitsune_p2p_types::async_lazy::AsyncLazy;
-use kitsune_p2p_types::config::{KitsuneP2pConfig, TransportConfig};
+use kitsune_p2p_types::config::{KitsuneP2pConfig, NetworkProfile, TransportConfig};
+use kitsune_p2p_types::dht::arq::{ArqPin, CoverageGap};
 use kitsune_p2p_types::dht::Arq;
 use kitsune_p2p_types::*;
 use std::collections::hash_map::Entry;
@@ -130,6 +131,9 @@ pub(crate) struct KitsuneP2pActor {
         )>,
     >,
     config: Arc<KitsuneP2pConfig>,
+    /// The network profiles of the spaces which have one, which restrict
+    /// the config which the spaces are created with.
+    network_profiles: HashMap<Arc<KitsuneSpace>, NetworkProfile>,
     bootstrap_net: BootstrapNet,
     bandwidth_throttles: BandwidthThrottles,
     parallel_notify_permit: Arc<tokio::sync::Semaphore>,
@@ -159,6 +163,7 @@ impl KitsuneP2pActor {
 
         // TODO - use a real config
         let fetch_pool = FetchPool::new_bitwise_or();
+        crate::metrics::register_fetch_pool_size_metric(fetch_pool.clone());
 
         // Start a loop to handle our fetch queue fetch items.
         FetchTask::spawn(
@@ -192,6 +197,7 @@ impl KitsuneP2pActor {
             host_api: direct_host_api,
             spaces: HashMap::new(),
             config: Arc::new(config),
+            network_profiles: HashMap::new(),
             bootstrap_net,
             bandwidth_throttles,
             parallel_notify_permit,
@@ -213,6 +219,20 @@ pub(super) async fn create_meta_net(
     let mut bootstrap_net = None;
     let mut maybe_peer_url = None;
 
+    if let Some(TransportConfig::Switchboard { name }) = config.transport_pool.first() {
+        tracing::trace!(%name, "switchboard");
+        let (h, e, p) = MetaNet::new_switchboard(
+            config.tuning_params.clone(),
+            host.clone(),
+            crate::switchboard::Switchboard::named(name),
+        )?;
+        ep_hnd = Some(h);
+        ep_evt = Some(e);
+        // Agent infos on a switchboard are bootstrapped like those on tx5.
+        bootstrap_net = Some(BootstrapNet::Tx5);
+        maybe_peer_url = p;
+    }
+
     if ep_hnd.is_none() && config.is_tx5() {
         tracing::trace!("tx5");
         let mut tune: kitsune_p2p_types::config::tuning_params_struct::KitsuneP2pTuningParams =
@@ -232,6 +252,7 @@ pub(super) async fn create_meta_net(
                 tune.tx5_backend_module = "mem".to_string();
                 ("wss://fake.fake".to_string(), "{}".to_string())
             }
+            TransportConfig::Switchboard { .. } => unreachable!("The switchboard isn't tx5"),
         };
         let (h, e, p) = MetaNet::new_tx5(
             Arc::new(tune),
@@ -625,7 +646,12 @@ impl KitsuneP2pHandler for KitsuneP2pActor {
         let space2 = space.clone();
         let ep_hnd = self.ep_hnd.clone();
         let host = self.host_api.clone().api;
-        let config = Arc::clone(&self.config);
+        let config = match self.network_profiles.get(&space) {
+            Some(profile) if !self.spaces.contains_key(&space) => {
+                Arc::new(profile.restrict(&self.config)?)
+            }
+            _ => Arc::clone(&self.config),
+        };
         let bootstrap_net = self.bootstrap_net;
         let bandwidth_throttles = self.bandwidth_throttles.clone();
         let parallel_notify_permit = self.parallel_notify_permit.clone();
@@ -830,6 +856,13 @@ impl KitsuneP2pHandler for KitsuneP2pActor {
         Ok(results)
     }
 
+    fn handle_get_connection_stats(
+        &mut self,
+    ) -> KitsuneP2pHandlerResult<Vec<kitsune_p2p_types::metrics::PeerConnectionStats>> {
+        let stats = self.ep_hnd.connection_stats();
+        Ok(async move { Ok(stats?) }.boxed().into())
+    }
+
     fn handle_dump_network_stats(&mut self) -> KitsuneP2pHandlerResult<serde_json::Value> {
         let peer_fut_list = self
             .spaces
@@ -940,6 +973,78 @@ impl KitsuneP2pHandler for KitsuneP2pActor {
         .boxed()
         .into())
     }
+
+    fn handle_set_gossip_pause(
+        &mut self,
+        space: KSpace,
+        pause: GossipPause,
+    ) -> KitsuneP2pHandlerResult<()> {
+        let space_sender = match self.spaces.get_mut(&space) {
+            None => return Err(KitsuneP2pError::RoutingSpaceError(space)),
+            Some(space) => space.get(),
+        };
+        Ok(async move {
+            let (space_sender, _) = space_sender.await;
+            space_sender.set_gossip_pause(space, pause).await
+        }
+        .boxed()
+        .into())
+    }
+
+    fn handle_set_arq_pin(
+        &mut self,
+        space: KSpace,
+        agent: KAgent,
+        pin: Option<ArqPin>,
+    ) -> KitsuneP2pHandlerResult<()> {
+        let space_sender = match self.spaces.get_mut(&space) {
+            None => return Err(KitsuneP2pError::RoutingSpaceError(space)),
+            Some(space) => space.get(),
+        };
+        Ok(async move {
+            let (space_sender, _) = space_sender.await;
+            space_sender.set_arq_pin(space, agent, pin).await
+        }
+        .boxed()
+        .into())
+    }
+
+    fn handle_get_coverage_gaps(
+        &mut self,
+        space: KSpace,
+        agent: KAgent,
+    ) -> KitsuneP2pHandlerResult<Vec<CoverageGap>> {
+        let space_sender = match self.spaces.get_mut(&space) {
+            None => return Err(KitsuneP2pError::RoutingSpaceError(space)),
+            Some(space) => space.get(),
+        };
+        Ok(async move {
+            let (space_sender, _) = space_sender.await;
+            space_sender.get_coverage_gaps(space, agent).await
+        }
+        .boxed()
+        .into())
+    }
+
+    fn handle_set_network_profile(
+        &mut self,
+        space: KSpace,
+        profile: NetworkProfile,
+    ) -> KitsuneP2pHandlerResult<()> {
+        if self.network_profiles.get(&space) == Some(&profile) {
+            return unit_ok_fut();
+        }
+        if self.spaces.contains_key(&space) {
+            return Err(KitsuneP2pError::other(format!(
+                "Can't set the network profile of space {:?} which is already running",
+                space
+            )));
+        }
+        // Check the profile now rather than when the space is joined.
+        profile.restrict(&self.config)?;
+        self.network_profiles.insert(space, profile);
+        unit_ok_fut()
+    }
 }
 
 #[cfg(any(test, feature = "test_utils"))]
@@ -1055,6 +1160,19 @@ mod tests {
         meta_net.close(0, "test").await;
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn create_switchboard_meta_net() {
+        let config = KitsuneP2pConfig::switchboard("create-switchboard-meta-net");
+
+        let (meta_net, _, bootstrap_net) = test_create_meta_net(config).await.unwrap();
+        assert_eq!(BootstrapNet::Tx5, bootstrap_net);
+
+        let switchboard = crate::switchboard::Switchboard::named("create-switchboard-meta-net");
+        assert_eq!(1, switchboard.node_urls().len());
+        meta_net.close(0, "test").await;
+        assert!(switchboard.node_urls().is_empty());
+    }
+
     async fn test_create_meta_net(
         config: KitsuneP2pConfig,
     ) -> KitsuneP2pResult<(MetaNet, MetaNetEvtRecv, BootstrapNet)> {
@@ -27,7 +27,8 @@ impl FetchTask {
                     // Drop sources that aren't responding to fetch requests, and any items that have no remaining sources to fetch from.
                     fetch_pool.check_sources();
 
-                    let list = fetch_pool.get_items_to_fetch();
+                    // Leave the ops of spaces which the host can't take in for now in the pool.
+                    let list = fetch_pool.get_items_to_fetch_except(|space| host.is_overloaded(space));
 
                     for (key, space, source, context) in list {
                         let FetchKey::Op(op_hash) = &key;
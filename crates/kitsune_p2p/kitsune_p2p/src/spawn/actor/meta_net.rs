@@ -25,6 +25,7 @@ use kitsune_p2p_types::agent_info::AgentInfoSigned;
 use kitsune_p2p_types::codec::Codec;
 use kitsune_p2p_types::config::KitsuneP2pConfig;
 use kitsune_p2p_types::config::KitsuneP2pTuningParams;
+use kitsune_p2p_types::metrics::PeerConnectionStats;
 use kitsune_p2p_types::*;
 use opentelemetry_api::metrics::Histogram;
 use parking_lot::Mutex;
@@ -34,6 +35,9 @@ use tx5::PeerUrl;
 
 use crate::spawn::actor::UNAUTHORIZED_DISCONNECT_CODE;
 use crate::spawn::actor::UNAUTHORIZED_DISCONNECT_REASON;
+use crate::switchboard::{
+    node_id_from_url, Switchboard, SwitchboardDelivery, SwitchboardMsg, SwitchboardNode,
+};
 
 kitsune_p2p_types::write_codec_enum! {
     /// KitsuneP2p WebRTC wrapper enum.
@@ -318,6 +322,14 @@ pub enum MetaNetCon {
         tun: KitsuneP2pTuningParams,
     },
 
+    Switchboard {
+        host: HostApiLegacy,
+        node: Arc<SwitchboardNode>,
+        rem_url: String,
+        rem_id: NodeCert,
+        res: ResStore,
+    },
+
     #[cfg(test)]
     Test {
         state: Arc<parking_lot::RwLock<MetaNetConTest>>,
@@ -328,6 +340,18 @@ impl PartialEq for MetaNetCon {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (MetaNetCon::Tx5 { ep: a, .. }, MetaNetCon::Tx5 { ep: b, .. }) => Arc::ptr_eq(a, b),
+            (
+                MetaNetCon::Switchboard {
+                    node: a,
+                    rem_url: a_url,
+                    ..
+                },
+                MetaNetCon::Switchboard {
+                    node: b,
+                    rem_url: b_url,
+                    ..
+                },
+            ) => Arc::ptr_eq(a, b) && a_url == b_url,
             _ => false,
         }
     }
@@ -373,7 +397,7 @@ impl MetaNetCon {
 
     async fn wire_is_authorized(&self, payload: &wire::Wire, now: Timestamp) -> MetaNetAuth {
         match self {
-            MetaNetCon::Tx5 { host, .. } => {
+            MetaNetCon::Tx5 { host, .. } | MetaNetCon::Switchboard { host, .. } => {
                 nodespace_is_authorized(host, self.peer_id(), payload.maybe_space(), now).await
             }
             #[cfg(test)]
@@ -423,6 +447,13 @@ impl MetaNetCon {
                         }
                     }
 
+                    {
+                        if let MetaNetCon::Switchboard { node, rem_url, .. } = self {
+                            node.send(rem_url, SwitchboardMsg::Notify(payload.clone()))?;
+                            return Ok(());
+                        }
+                    }
+
                     return Err("invalid features".into());
                 }
                 MetaNetAuth::UnauthorizedIgnore => {
@@ -492,6 +523,29 @@ impl MetaNetCon {
                         }
                     }
 
+                    {
+                        if let MetaNetCon::Switchboard {
+                            node,
+                            rem_url,
+                            res: res_store,
+                            ..
+                        } = self
+                        {
+                            let (s, r) = tokio::sync::oneshot::channel();
+                            res_store.lock().insert(msg_id, s);
+
+                            let res_store = res_store.clone();
+                            tokio::task::spawn(async move {
+                                tokio::time::sleep(timeout.time_remaining()).await;
+                                res_store.lock().remove(&msg_id);
+                            });
+
+                            node.send(rem_url, SwitchboardMsg::Request(msg_id, payload.clone()))?;
+
+                            return r.await.map_err(|_| KitsuneError::other("timeout"));
+                        }
+                    }
+
                     return Err("invalid features".into());
                 }
                 MetaNetAuth::UnauthorizedIgnore => {
@@ -527,6 +581,12 @@ impl MetaNetCon {
             }
         }
 
+        {
+            if let MetaNetCon::Switchboard { rem_id, .. } = self {
+                return rem_id.clone();
+            }
+        }
+
         panic!("invalid features");
     }
 }
@@ -577,9 +637,121 @@ pub enum MetaNet {
         res: ResStore,
         tun: KitsuneP2pTuningParams,
     },
+
+    /// In-memory switchboard for tests
+    Switchboard {
+        host: HostApiLegacy,
+        node: Arc<SwitchboardNode>,
+        res: ResStore,
+    },
 }
 
 impl MetaNet {
+    /// Construct abstraction with an in-memory switchboard backend.
+    pub fn new_switchboard(
+        tuning_params: KitsuneP2pTuningParams,
+        host: HostApiLegacy,
+        switchboard: Switchboard,
+    ) -> KitsuneP2pResult<(Self, MetaNetEvtRecv, Option<String>)> {
+        let (mut evt_send, evt_recv) =
+            futures::channel::mpsc::channel(tuning_params.concurrent_limit_per_thread);
+
+        let (node, mut inbox) = switchboard.join();
+        let local_url = node.url().to_string();
+        tracing::info!(%local_url, "meta net startup switchboard");
+
+        let res_store: ResStore = Arc::new(Mutex::new(HashMap::new()));
+
+        // Only hold on to the node while a delivery is handled, so that it leaves the
+        // switchboard once this meta net and its connections are dropped.
+        let weak_node = Arc::downgrade(&node);
+        let res_store2 = res_store.clone();
+        let spawn_host = host.clone();
+        tokio::task::spawn(async move {
+            let mut connected = std::collections::HashSet::new();
+            while let Some(SwitchboardDelivery { from, msg }) = inbox.recv().await {
+                let Some(node) = weak_node.upgrade() else {
+                    break;
+                };
+                let rem_id = match node_id_from_url(&from) {
+                    Ok(rem_id) => rem_id,
+                    Err(err) => {
+                        tracing::error!(?err, "switchboard delivery from an invalid url");
+                        continue;
+                    }
+                };
+                let con = MetaNetCon::Switchboard {
+                    host: spawn_host.clone(),
+                    node: node.clone(),
+                    rem_url: from.clone(),
+                    rem_id,
+                    res: res_store2.clone(),
+                };
+
+                // Nodes on a switchboard are connected as soon as they first hear
+                // from each other.
+                if connected.insert(from.clone())
+                    && evt_send
+                        .send(MetaNetEvt::Connected {
+                            remote_url: from.clone(),
+                            con: con.clone(),
+                        })
+                        .await
+                        .is_err()
+                {
+                    break;
+                }
+
+                let evt = match msg {
+                    SwitchboardMsg::Notify(data) => {
+                        tracing::trace!(%from, ?data, "received notify");
+                        MetaNetEvt::Notify {
+                            remote_url: from,
+                            con,
+                            data,
+                        }
+                    }
+                    SwitchboardMsg::Request(msg_id, data) => {
+                        let rem_url = from.clone();
+                        let respond: Respond = Box::new(move |data| {
+                            let out: RespondFut = Box::pin(async move {
+                                let _ = node.send(&rem_url, SwitchboardMsg::Response(msg_id, data));
+                            });
+                            out
+                        });
+                        MetaNetEvt::Request {
+                            remote_url: from,
+                            con,
+                            data,
+                            respond,
+                        }
+                    }
+                    SwitchboardMsg::Response(msg_id, data) => {
+                        if let Some(s) = res_store2.lock().remove(&msg_id) {
+                            let _ = s.send(data);
+                        } else {
+                            tracing::debug!(%msg_id, "response mismatch");
+                        }
+                        continue;
+                    }
+                };
+                if evt_send.send(evt).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok((
+            MetaNet::Switchboard {
+                host,
+                node,
+                res: res_store,
+            },
+            evt_recv,
+            Some(local_url),
+        ))
+    }
+
     /// Construct abstraction with tx5 backend.
     pub async fn new_tx5(
         tuning_params: KitsuneP2pTuningParams,
@@ -918,10 +1090,23 @@ impl MetaNet {
             }
         }
 
+        {
+            if let MetaNet::Switchboard { node, .. } = self {
+                for url in node.peer_urls() {
+                    // Like a broadcast over tx5, this doesn't wait for delivery.
+                    let _ = node.send(&url, SwitchboardMsg::Notify(payload.clone()));
+                }
+                return Ok(());
+            }
+        }
+
         Err("invalid features".into())
     }
 
     pub async fn close(&self, code: u32, reason: &str) {
+        if let MetaNet::Switchboard { node, .. } = self {
+            node.leave();
+        }
 
         // TODO - currently no way to shutdown tx5
     }
@@ -961,6 +1146,18 @@ impl MetaNet {
             }
         }
 
+        {
+            if let MetaNet::Switchboard { host, node, res } = self {
+                return Ok(MetaNetCon::Switchboard {
+                    host: host.clone(),
+                    node: node.clone(),
+                    rem_id: node_id_from_url(&remote_url)?,
+                    rem_url: remote_url,
+                    res: res.clone(),
+                });
+            }
+        }
+
         Err("invalid features".into())
     }
 
@@ -983,8 +1180,55 @@ impl MetaNet {
             }
         }
 
+        {
+            if let MetaNet::Switchboard { node, .. } = self {
+                let stats = serde_json::json!({
+                    "backend": "switchboard",
+                    "url": node.url(),
+                });
+                return async move { Ok(stats) }.boxed();
+            }
+        }
+
         async move { Err("invalid features".into()) }.boxed()
     }
+
+    /// The statistics of each open connection to a remote peer.
+    ///
+    /// The transport doesn't report the ICE candidates, round trip times or
+    /// retransmissions of its WebRTC connections, so only whether a connection
+    /// is relayed and how much it has carried are known.
+    pub fn connection_stats(&self) -> KitsuneResult<Vec<PeerConnectionStats>> {
+        {
+            if let MetaNet::Tx5 { ep, .. } = self {
+                use base64::Engine;
+                return Ok(ep
+                    .get_stats()
+                    .connection_list
+                    .into_iter()
+                    .map(|con| PeerConnectionStats {
+                        peer_id: base64::engine::general_purpose::URL_SAFE_NO_PAD
+                            .encode(con.pub_key),
+                        opened_at: Timestamp::from_micros(con.opened_at_s as i64 * 1_000_000),
+                        relayed: !con.is_webrtc,
+                        send_message_count: con.send_message_count,
+                        send_bytes: con.send_bytes,
+                        recv_message_count: con.recv_message_count,
+                        recv_bytes: con.recv_bytes,
+                    })
+                    .collect());
+            }
+        }
+
+        {
+            if let MetaNet::Switchboard { .. } = self {
+                // Switchboard nodes deliver messages to each other without connections.
+                return Ok(Vec::new());
+            }
+        }
+
+        Err("invalid features".into())
+    }
 }
 
 #[cfg(test)]
@@ -1005,3 +1005,115 @@ async fn request_timeout() {
 
     nodes.shutdown().await;
 }
+
+/// Two meta nets on a new switchboard.
+async fn setup_2_switchboard_nodes(
+    test: Test,
+    name: &str,
+) -> (
+    KitsuneP2pTuningParams,
+    Switchboard,
+    MetaNet,
+    MetaNet,
+    String,
+) {
+    let tuning_params = Arc::new(config::tuning_params_struct::KitsuneP2pTuningParams::default());
+    let switchboard = Switchboard::named(name);
+    let (test, _, evt_sender) = test.spawn().await;
+    let host = HostApiLegacy {
+        api: Arc::new(test.clone()),
+        legacy: evt_sender,
+    };
+
+    let (send1, recv1, _) =
+        MetaNet::new_switchboard(tuning_params.clone(), host.clone(), switchboard.clone()).unwrap();
+    test.spawn_receiver(recv1);
+    let (send2, recv2, addr2) =
+        MetaNet::new_switchboard(tuning_params.clone(), host, switchboard.clone()).unwrap();
+    test.spawn_receiver(recv2);
+
+    (tuning_params, switchboard, send1, send2, addr2.unwrap())
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn switchboard_request() {
+    let mut test = Test::default();
+
+    test.recv = Arc::new(move |evt| {
+        if let MetaNetEvt::Request { respond, .. } = evt {
+            tokio::task::spawn(respond(wire::Wire::failure("world".into())));
+        }
+    });
+
+    let (tuning_params, _, send1, send2, addr2) =
+        setup_2_switchboard_nodes(test, "switchboard-request").await;
+
+    let con = send1
+        .get_connection(addr2.clone(), tuning_params.implicit_timeout())
+        .await
+        .unwrap();
+    assert_eq!(
+        crate::switchboard::node_id_from_url(&addr2).unwrap(),
+        con.peer_id()
+    );
+
+    let resp = con
+        .request(
+            &wire::Wire::failure("hello".into()),
+            tuning_params.implicit_timeout(),
+        )
+        .await
+        .unwrap();
+
+    assert!(matches!(
+        resp,
+        wire::Wire::Failure(wire::Failure {
+            reason,
+        }) if reason == "world",
+    ));
+
+    send1.close(0, "").await;
+    send2.close(0, "").await;
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn switchboard_partition_times_out_requests() {
+    let mut test = Test::default();
+
+    test.recv = Arc::new(move |evt| {
+        if let MetaNetEvt::Request { respond, .. } = evt {
+            tokio::task::spawn(respond(wire::Wire::failure("world".into())));
+        }
+    });
+
+    let (tuning_params, switchboard, send1, send2, addr2) =
+        setup_2_switchboard_nodes(test, "switchboard-partition").await;
+
+    let con = send1
+        .get_connection(addr2.clone(), tuning_params.implicit_timeout())
+        .await
+        .unwrap();
+
+    switchboard.partition([vec![addr2]]);
+    let resp = con
+        .request(
+            &wire::Wire::failure("hello".into()),
+            KitsuneTimeout::from_millis(100),
+        )
+        .await;
+    assert!(matches!(
+        resp,
+        Err(e) if format!("{e:?}").contains("timeout"),
+    ));
+
+    switchboard.heal();
+    con.request(
+        &wire::Wire::failure("hello".into()),
+        tuning_params.implicit_timeout(),
+    )
+    .await
+    .unwrap();
+
+    send1.close(0, "").await;
+    send2.close(0, "").await;
+}
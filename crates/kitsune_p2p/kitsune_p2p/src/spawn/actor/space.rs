@@ -2,17 +2,16 @@ use super::*;
 use crate::metrics::*;
 use crate::types::gossip::GossipModule;
 use ghost_actor::dependencies::tracing;
-use kitsune_p2p_bootstrap_client::BootstrapNet;
+use kitsune_p2p_bootstrap_client::{BootstrapNet, DynBootstrapService, HttpBootstrap};
 use kitsune_p2p_fetch::FetchPool;
 use kitsune_p2p_types::agent_info::AgentInfoSigned;
-use kitsune_p2p_types::config::KitsuneP2pConfig;
+use kitsune_p2p_types::config::{KitsuneP2pConfig, NetworkProfile};
 use kitsune_p2p_types::dht::arq::ArqSize;
 use kitsune_p2p_types::dht::prelude::*;
 use kitsune_p2p_types::dht_arc::{DhtArcRange, DhtArcSet};
 use kitsune_p2p_types::tx_utils::TxUrl;
 use std::collections::{HashMap, HashSet};
 use std::sync::atomic::AtomicBool;
-use url2::Url2;
 
 /// How often to record historical metrics
 /// (currently once per hour)
@@ -213,11 +212,12 @@ impl SpaceInternalHandler for Space {
         let mut agent_list = Vec::with_capacity(self.local_joined_agents.len());
         for agent in self.local_joined_agents.keys().cloned() {
             let arq = self.get_agent_arq(&agent);
-            agent_list.push((agent, arq));
+            let pinned = self.pinned_agents.contains(&agent);
+            agent_list.push((agent, arq, pinned));
         }
         let bootstrap_net = self.ro_inner.bootstrap_net;
         let evt_sender = self.host_api.legacy.clone();
-        let bootstrap_service = self.config.bootstrap_service.clone();
+        let bootstrap = self.ro_inner.bootstrap.clone();
         let expires_after = self.config.tuning_params.agent_info_expires_after_ms as u64;
         #[cfg(feature = "unstable-sharding")]
         let dynamic_arcs = self.config.tuning_params.gossip_dynamic_arcs;
@@ -227,7 +227,7 @@ impl SpaceInternalHandler for Space {
         Ok(async move {
             let urls = vec![TxUrl::try_from(local_url)?];
             let mut peer_data = Vec::with_capacity(agent_list.len());
-            for (agent, arq) in agent_list {
+            for (agent, arq, pinned) in agent_list {
                 let input = UpdateAgentInfoInput {
                     expires_after,
                     space: space.clone(),
@@ -238,8 +238,8 @@ impl SpaceInternalHandler for Space {
                     evt_sender: &evt_sender,
                     internal_sender: &internal_sender,
                     _mdns_handles: &mut mdns_handles,
-                    bootstrap_service: &bootstrap_service,
-                    dynamic_arcs,
+                    bootstrap: &bootstrap,
+                    dynamic_arcs: dynamic_arcs && !pinned,
                 };
                 peer_data.push(update_single_agent_info(input).await?);
             }
@@ -265,12 +265,13 @@ impl SpaceInternalHandler for Space {
         let mut mdns_handles = self.mdns_handles.clone();
         let evt_sender = self.host_api.legacy.clone();
         let internal_sender = self.i_s.clone();
-        let bootstrap_service = self.config.bootstrap_service.clone();
+        let bootstrap = self.ro_inner.bootstrap.clone();
         let expires_after = self.config.tuning_params.agent_info_expires_after_ms as u64;
         #[cfg(feature = "unstable-sharding")]
         let dynamic_arcs = self.config.tuning_params.gossip_dynamic_arcs;
         #[cfg(not(feature = "unstable-sharding"))]
         let dynamic_arcs = false;
+        let dynamic_arcs = dynamic_arcs && !self.pinned_agents.contains(&agent);
         let arc = self.get_agent_arq(&agent);
 
         Ok(async move {
@@ -285,7 +286,7 @@ impl SpaceInternalHandler for Space {
                 evt_sender: &evt_sender,
                 internal_sender: &internal_sender,
                 _mdns_handles: &mut mdns_handles,
-                bootstrap_service: &bootstrap_service,
+                bootstrap: &bootstrap,
                 dynamic_arcs,
             };
             let peer_data = vec![update_single_agent_info(input).await?];
@@ -669,7 +670,7 @@ struct UpdateAgentInfoInput<'borrow> {
     evt_sender: &'borrow futures::channel::mpsc::Sender<KitsuneP2pEvent>,
     internal_sender: &'borrow ghost_actor::GhostSender<SpaceInternal>,
     _mdns_handles: &'borrow mut HashMap<Vec<u8>, Arc<AtomicBool>>,
-    bootstrap_service: &'borrow Option<Url2>,
+    bootstrap: &'borrow Option<DynBootstrapService>,
     dynamic_arcs: bool,
 }
 
@@ -714,7 +715,7 @@ async fn update_single_agent_info(
         evt_sender,
         internal_sender,
         _mdns_handles,
-        bootstrap_service,
+        bootstrap,
         dynamic_arcs,
     } = input;
 
@@ -766,13 +767,8 @@ async fn update_single_agent_info(
     }
 
     // bootstrap stuff
-    {
-        kitsune_p2p_bootstrap_client::put(
-            bootstrap_service.clone(),
-            agent_info_signed.clone(),
-            bootstrap_net,
-        )
-        .await?;
+    if let Some(bootstrap) = bootstrap {
+        bootstrap.put(agent_info_signed.clone()).await?;
     }
     Ok(agent_info_signed)
 }
@@ -846,6 +842,7 @@ impl KitsuneP2pHandler for Space {
     ) -> KitsuneP2pHandlerResult<()> {
         self.local_joined_agents.remove(&agent);
         self.agent_arqs.remove(&agent);
+        self.pinned_agents.remove(&agent);
         self.update_metric_exchange_arcset();
         for module in self.gossip_mod.values() {
             module.local_agent_leave(agent.clone());
@@ -1225,6 +1222,22 @@ impl KitsuneP2pHandler for Space {
         unreachable!()
     }
 
+    fn handle_get_connection_stats(
+        &mut self,
+    ) -> KitsuneP2pHandlerResult<Vec<kitsune_p2p_types::metrics::PeerConnectionStats>> {
+        // call handled by parent actor and never delegated to spaces
+        unreachable!()
+    }
+
+    fn handle_set_network_profile(
+        &mut self,
+        _space: KSpace,
+        _profile: NetworkProfile,
+    ) -> KitsuneP2pHandlerResult<()> {
+        // call handled by parent actor and never delegated to spaces
+        unreachable!()
+    }
+
     fn handle_get_diagnostics(
         &mut self,
         _space: KSpace,
@@ -1235,6 +1248,65 @@ impl KitsuneP2pHandler for Space {
         };
         Ok(async move { Ok(diagnostics) }.boxed().into())
     }
+
+    fn handle_set_gossip_pause(
+        &mut self,
+        _space: KSpace,
+        pause: GossipPause,
+    ) -> KitsuneP2pHandlerResult<()> {
+        for module in self.gossip_mod.values() {
+            module.set_pause(pause);
+        }
+        unit_ok_fut()
+    }
+
+    fn handle_set_arq_pin(
+        &mut self,
+        _space: KSpace,
+        agent: KAgent,
+        pin: Option<ArqPin>,
+    ) -> KitsuneP2pHandlerResult<()> {
+        match pin {
+            Some(pin) => {
+                let arq = pin.to_arq(
+                    SpaceDimension::standard(),
+                    &self.config.tuning_params.to_arq_strat(),
+                    agent.get_loc(),
+                );
+                self.agent_arqs.insert(agent.clone(), arq);
+                self.pinned_agents.insert(agent.clone());
+                self.update_metric_exchange_arcset();
+            }
+            None => {
+                self.pinned_agents.remove(&agent);
+            }
+        }
+        // Peers gossip with the agent by the arc in its agent info.
+        let i_s = self.i_s.clone();
+        Ok(async move { i_s.update_single_agent_info(agent).await }
+            .boxed()
+            .into())
+    }
+
+    fn handle_get_coverage_gaps(
+        &mut self,
+        space: KSpace,
+        agent: KAgent,
+    ) -> KitsuneP2pHandlerResult<Vec<CoverageGap>> {
+        let arq = match self.agent_arqs.get(&agent) {
+            Some(arq) => *arq,
+            None => return Err(KitsuneP2pError::RoutingAgentError(agent)),
+        };
+        let evt_sender = self.host_api.legacy.clone();
+        Ok(async move {
+            let view = evt_sender
+                .query_peer_density(space, arq.to_dht_arc_std())
+                .await?;
+            Ok(view.coverage_gaps(&arq))
+        }
+        .boxed()
+        .into())
+    }
 }
 
 pub(crate) struct PendingDelegate {
@@ -1256,6 +1328,8 @@ pub(crate) struct SpaceReadOnlyInner {
     #[allow(dead_code)]
     pub(crate) config: Arc<KitsuneP2pConfig>,
     pub(crate) bootstrap_net: BootstrapNet,
+    /// Where agent infos are put and other agents are found, if anywhere.
+    pub(crate) bootstrap: Option<DynBootstrapService>,
     pub(crate) parallel_notify_permit: Arc<tokio::sync::Semaphore>,
     pub(crate) metrics: MetricsSync,
     pub(crate) metric_exchange: MetricExchangeSync,
@@ -1324,6 +1398,8 @@ pub(crate) struct Space {
     pub(crate) host_api: HostApiLegacy,
     pub(crate) local_joined_agents: HashMap<Arc<KitsuneAgent>, Option<AgentInfoSigned>>,
     pub(crate) agent_arqs: HashMap<Arc<KitsuneAgent>, Arq>,
+    /// The local agents whose arcs are pinned, so aren't resized by dynamic arcs.
+    pub(crate) pinned_agents: HashSet<Arc<KitsuneAgent>>,
     pub(crate) config: Arc<KitsuneP2pConfig>,
     mdns_handles: HashMap<Vec<u8>, Arc<AtomicBool>>,
     _mdns_listened_spaces: HashSet<String>,
@@ -1431,16 +1507,20 @@ impl Space {
             ),
         );
 
-        if config.bootstrap_service.is_some() {
+        // The host's bootstrap service takes the place of the bootstrap server of the config.
+        let bootstrap = host_api.bootstrap_service().or_else(|| {
+            config
+                .bootstrap_service
+                .clone()
+                .map(|url| Arc::new(HttpBootstrap::new(url, bootstrap_net)) as DynBootstrapService)
+        });
+        if let Some(bootstrap) = bootstrap.clone() {
             // spawn the periodic bootstrap pull
             BootstrapTask::spawn(
                 i_s.clone(),
                 host_api.legacy.clone(),
                 space.clone(),
-                // TODO: refactor to take just the Url. We know that nothing will
-                //       happen if this is None.
-                config.bootstrap_service.clone(),
-                bootstrap_net,
+                bootstrap,
                 config
                     .tuning_params
                     .bootstrap_check_delay_backoff_multiplier,
@@ -1456,6 +1536,7 @@ impl Space {
             ep_hnd,
             config: config.clone(),
             bootstrap_net,
+            bootstrap,
             parallel_notify_permit,
             metrics,
             metric_exchange,
@@ -1470,6 +1551,7 @@ impl Space {
             host_api,
             local_joined_agents: HashMap::new(),
             agent_arqs: HashMap::new(),
+            pinned_agents: HashSet::new(),
             config,
             mdns_handles: HashMap::new(),
             _mdns_listened_spaces: HashSet::new(),
@@ -1493,7 +1575,7 @@ impl Space {
         let space = self.space.clone();
         let bootstrap_net = self.ro_inner.bootstrap_net;
         let evt_sender = self.host_api.legacy.clone();
-        let bootstrap_service = self.config.bootstrap_service.clone();
+        let bootstrap = self.ro_inner.bootstrap.clone();
         let expires_after = self.config.tuning_params.agent_info_expires_after_ms as u64;
         let host = self.host_api.clone();
 
@@ -1536,14 +1618,8 @@ impl Space {
 
             // Push to the network as well
 
-            {
-                match kitsune_p2p_bootstrap_client::put(
-                    bootstrap_service.clone(),
-                    agent_info_signed,
-                    bootstrap_net,
-                )
-                .await
-                {
+            if let Some(bootstrap) = bootstrap {
+                match bootstrap.put(agent_info_signed).await {
                     Ok(_) => {
                         tracing::debug!("Successfully publish agent info to the bootstrap service");
                     }
@@ -1,17 +1,13 @@
 use crate::event::{KitsuneP2pEvent, KitsuneP2pEventSender, PutAgentInfoSignedEvt};
 use crate::spawn::actor::space::{SpaceInternal, SpaceInternalSender};
-use crate::{KitsuneP2pError, KitsuneP2pResult, KitsuneSpace};
+use crate::{KitsuneP2pError, KitsuneSpace};
 use futures::channel::mpsc::Sender;
-use futures::future::BoxFuture;
-use futures::FutureExt;
 use ghost_actor::{GhostControlSender, GhostError, GhostSender};
-use kitsune_p2p_bootstrap_client::BootstrapNet;
-use kitsune_p2p_types::agent_info::AgentInfoSigned;
+use kitsune_p2p_bootstrap_client::DynBootstrapService;
 use kitsune_p2p_types::bootstrap::RandomQuery;
 use parking_lot::RwLock;
 use std::sync::Arc;
 use std::time::Duration;
-use url2::Url2;
 
 const MAX_AGENTS_PER_QUERY: u32 = 8;
 
@@ -21,31 +17,12 @@ pub(super) struct BootstrapTask {
     max_delay: Duration,
 }
 
-// Trait for the bootstrap query to allow mocking in tests
-trait BootstrapService: Send {
-    fn random(&self, query: RandomQuery) -> BoxFuture<KitsuneP2pResult<Vec<AgentInfoSigned>>>;
-}
-
-struct DefaultBootstrapService {
-    url: Option<Url2>,
-    net: BootstrapNet,
-}
-
-impl BootstrapService for DefaultBootstrapService {
-    fn random(&self, query: RandomQuery) -> BoxFuture<KitsuneP2pResult<Vec<AgentInfoSigned>>> {
-        async move {
-            Ok(kitsune_p2p_bootstrap_client::random(self.url.clone(), query, self.net).await?)
-        }.boxed()
-    }
-}
-
 impl BootstrapTask {
     pub(super) fn spawn(
         internal_sender: GhostSender<SpaceInternal>,
         host_sender: Sender<KitsuneP2pEvent>,
         space: Arc<KitsuneSpace>,
-        bootstrap_service: Option<Url2>,
-        bootstrap_net: BootstrapNet,
+        bootstrap_service: DynBootstrapService,
         bootstrap_check_delay_backoff_multiplier: u32,
         mut bootstrap_max_delay_s: u32,
     ) -> Arc<RwLock<Self>> {
@@ -59,17 +36,12 @@ impl BootstrapTask {
             max_delay: Duration::from_secs(bootstrap_max_delay_s as u64),
         }));
 
-        let bootstrap_query = DefaultBootstrapService {
-            url: bootstrap_service,
-            net: bootstrap_net,
-        };
-
         BootstrapTask::spawn_inner(
             this,
             internal_sender,
             host_sender,
             space,
-            Box::new(bootstrap_query),
+            bootstrap_service,
             bootstrap_check_delay_backoff_multiplier,
         )
     }
@@ -79,7 +51,7 @@ impl BootstrapTask {
         internal_sender: GhostSender<SpaceInternal>,
         host_sender: Sender<KitsuneP2pEvent>,
         space: Arc<KitsuneSpace>,
-        bootstrap_query: Box<impl BootstrapService + Sync + 'static>,
+        bootstrap_query: DynBootstrapService,
         bootstrap_check_delay_backoff_multiplier: u32,
     ) -> Arc<RwLock<Self>> {
         let task_this = this.clone();
@@ -170,7 +142,7 @@ impl BootstrapTask {
 #[cfg(test)]
 mod tests {
     use crate::event::PutAgentInfoSignedEvt;
-    use crate::spawn::actor::space::bootstrap_task::{BootstrapService, BootstrapTask};
+    use crate::spawn::actor::space::bootstrap_task::BootstrapTask;
     use crate::spawn::actor::space::{
         KAgent, KBasis, KSpace, MaybeDelegate, OpHashList, Payload, SpaceInternal,
         SpaceInternalHandler, SpaceInternalHandlerResult, VecMXM, WireConHnd,
@@ -179,8 +151,7 @@ mod tests {
     use crate::spawn::actor::MetaNetCon;
     use crate::types::actor::BroadcastData;
     use crate::wire::Wire;
-    use crate::KitsuneP2pResult;
-    use crate::{GossipModuleType, KitsuneP2pError};
+    use crate::GossipModuleType;
     use ::fixt::prelude::*;
     use futures::channel::mpsc::channel;
     use futures::future::BoxFuture;
@@ -188,7 +159,9 @@ mod tests {
     use ghost_actor::actor_builder::GhostActorBuilder;
     use ghost_actor::{GhostControlHandler, GhostControlSender, GhostHandler, GhostSender};
     use kitsune_p2p_bin_data::fixt::*;
-    use kitsune_p2p_bootstrap_client::prelude::BootstrapClientError;
+    use kitsune_p2p_bootstrap_client::prelude::{
+        BootstrapClientError, BootstrapClientResult, BootstrapService,
+    };
     use kitsune_p2p_fetch::FetchContext;
     use kitsune_p2p_types::agent_info::AgentInfoSigned;
     use kitsune_p2p_types::bootstrap::RandomQuery;
@@ -533,7 +506,7 @@ mod tests {
             internal_sender.clone(),
             host_sender,
             Arc::new(space),
-            Box::new(TestBootstrapService::new(
+            Arc::new(TestBootstrapService::new(
                 agents,
                 bootstrap_every_other_call_fails,
             )),
@@ -684,6 +657,7 @@ mod tests {
         }
     }
 
+    #[derive(Debug)]
     struct TestBootstrapService {
         every_other_call_fails: bool,
         call_count: AtomicU32,
@@ -701,14 +675,24 @@ mod tests {
     }
 
     impl BootstrapService for TestBootstrapService {
-        fn random(&self, _query: RandomQuery) -> BoxFuture<KitsuneP2pResult<Vec<AgentInfoSigned>>> {
+        fn put(
+            &self,
+            _agent_info_signed: AgentInfoSigned,
+        ) -> BoxFuture<'_, BootstrapClientResult<()>> {
+            async move { Ok(()) }.boxed()
+        }
+
+        fn random(
+            &self,
+            _query: RandomQuery,
+        ) -> BoxFuture<'_, BootstrapClientResult<Vec<AgentInfoSigned>>> {
             let calls = self.call_count.fetch_add(1, Ordering::SeqCst);
 
             if self.every_other_call_fails && calls % 2 == 1 {
                 return async move {
-                    Err(KitsuneP2pError::Bootstrap(BootstrapClientError::Bootstrap(
+                    Err(BootstrapClientError::Bootstrap(
                         "TestBootstrapService error".to_string().into_boxed_str(),
-                    )))
+                    ))
                 }
                 .boxed();
             }
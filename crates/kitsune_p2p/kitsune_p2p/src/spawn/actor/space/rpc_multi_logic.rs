@@ -1,72 +1,148 @@
 use super::*;
-use futures::future::BoxFuture;
 
 pub(crate) async fn handle_rpc_multi(
     input: actor::RpcMulti,
     ro_inner: Arc<SpaceReadOnlyInner>,
     local_joined_agents: HashSet<Arc<KitsuneAgent>>,
 ) -> KitsuneP2pResult<Vec<actor::RpcMultiResponse>> {
-    handle_rpc_multi_as_single(input, ro_inner, local_joined_agents).await
+    if input.as_race {
+        handle_rpc_multi_as_single(input, ro_inner, local_joined_agents).await
+    } else {
+        handle_rpc_multi_as_all(input, ro_inner, local_joined_agents).await
+    }
 }
 
-pub(crate) async fn handle_rpc_multi_as_single(
+/// Make the request of a single remote agent.
+async fn make_req(
+    ro_inner: &SpaceReadOnlyInner,
+    space: &Arc<KitsuneSpace>,
+    payload: &[u8],
+    max_timeout: KitsuneTimeout,
+    con_hnd: MetaNetCon,
+    agent: Arc<KitsuneAgent>,
+) -> KitsuneP2pResult<Vec<actor::RpcMultiResponse>> {
+    let msg = wire::Wire::call(space.clone(), agent.clone(), payload.to_vec().into());
+
+    let start = tokio::time::Instant::now();
+
+    let res = con_hnd.request(&msg, max_timeout).await;
+
+    match res {
+        Ok(wire::Wire::CallResp(c)) => {
+            ro_inner
+                .metrics
+                .write()
+                .record_reachability_event(true, [&agent]);
+            ro_inner
+                .metrics
+                .write()
+                .record_latency_micros(start.elapsed().as_micros(), [&agent]);
+            Ok(vec![RpcMultiResponse {
+                agent: agent.clone(),
+                response: c.data.into(),
+            }])
+        }
+        oth => {
+            ro_inner
+                .metrics
+                .write()
+                .record_reachability_event(false, [&agent]);
+            ro_inner
+                .metrics
+                .write()
+                .record_latency_micros(start.elapsed().as_micros(), [&agent]);
+            tracing::warn!(?oth, "unexpected remote call result");
+            Err(format!("rpc_multi request failed: {:?}", oth).into())
+        }
+    }
+}
+
+/// Make the request of up to `max_remote_agent_count` remote agents near the basis
+/// at once, and return all the responses which were received.
+/// Falls back to a single request if there are no remote agents near the basis.
+pub(crate) async fn handle_rpc_multi_as_all(
     input: actor::RpcMulti,
     ro_inner: Arc<SpaceReadOnlyInner>,
     local_joined_agents: HashSet<Arc<KitsuneAgent>>,
 ) -> KitsuneP2pResult<Vec<actor::RpcMultiResponse>> {
+    let mut infos = discover::get_cached_remotes_near_basis(
+        ro_inner.clone(),
+        input.basis.get_loc(),
+        input.max_timeout,
+    )
+    .await
+    .unwrap_or_default();
+    if infos.is_empty() {
+        return handle_rpc_multi_as_single(input, ro_inner, local_joined_agents).await;
+    }
+    // The remotes are ordered by their distance to the basis.
+    infos.truncate(input.max_remote_agent_count.max(1) as usize);
+
     let RpcMulti {
         space,
-        basis,
         payload,
         max_timeout,
         ..
     } = input;
-
     let ro_inner = &ro_inner;
     let space = &space;
     let payload = &payload;
 
-    let make_req = move |con_hnd: MetaNetCon,
-                         agent: Arc<KitsuneAgent>|
-          -> BoxFuture<'_, KitsuneP2pResult<Vec<actor::RpcMultiResponse>>> {
-        async move {
-            let msg = wire::Wire::call(space.clone(), agent.clone(), payload.clone().into());
-
-            let start = tokio::time::Instant::now();
-
-            let res = con_hnd.request(&msg, max_timeout).await;
-
-            match res {
-                Ok(wire::Wire::CallResp(c)) => {
-                    ro_inner
-                        .metrics
-                        .write()
-                        .record_reachability_event(true, [&agent]);
-                    ro_inner
-                        .metrics
-                        .write()
-                        .record_latency_micros(start.elapsed().as_micros(), [&agent]);
-                    Ok(vec![RpcMultiResponse {
-                        agent: agent.clone(),
-                        response: c.data.into(),
-                    }])
-                }
-                oth => {
-                    ro_inner
-                        .metrics
-                        .write()
-                        .record_reachability_event(false, [&agent]);
-                    ro_inner
-                        .metrics
-                        .write()
-                        .record_latency_micros(start.elapsed().as_micros(), [&agent]);
-                    tracing::warn!(?oth, "unexpected remote call result");
-                    Err(format!("rpc_multi request failed: {:?}", oth).into())
-                }
+    let requests = infos.into_iter().map(|info| async move {
+        use discover::PeerDiscoverResult;
+
+        match discover::peer_connect(ro_inner.clone(), &info, max_timeout).await {
+            PeerDiscoverResult::OkRemote { con_hnd, .. } => {
+                make_req(
+                    ro_inner,
+                    space,
+                    payload,
+                    max_timeout,
+                    con_hnd,
+                    info.agent.clone(),
+                )
+                .await
             }
+            PeerDiscoverResult::OkShortcut => Err("remote peer is local".into()),
+            PeerDiscoverResult::Err(err) => Err(err),
         }
-        .boxed()
-    };
+    });
+
+    let mut out = Vec::new();
+    let mut errs = Vec::new();
+    for result in futures::future::join_all(requests).await {
+        match result {
+            Ok(res) => out.extend(res),
+            Err(err) => {
+                tracing::warn!(?err, "remote call error");
+                errs.push(err);
+            }
+        }
+    }
+    if out.is_empty() {
+        let error_msg = format!("rpc_multi failed to get results. Errors: {:?}", errs);
+        tracing::error!("{}", error_msg);
+        return Err(error_msg.into());
+    }
+    Ok(out)
+}
+
+pub(crate) async fn handle_rpc_multi_as_single(
+    input: actor::RpcMulti,
+    ro_inner: Arc<SpaceReadOnlyInner>,
+    local_joined_agents: HashSet<Arc<KitsuneAgent>>,
+) -> KitsuneP2pResult<Vec<actor::RpcMultiResponse>> {
+    let RpcMulti {
+        space,
+        basis,
+        payload,
+        max_timeout,
+        ..
+    } = input;
+
+    let ro_inner = &ro_inner;
+    let space = &space;
+    let payload = &payload;
 
     max_timeout
         .mix("rpc_multi", async move {
@@ -127,7 +203,16 @@ pub(crate) async fn handle_rpc_multi_as_single(
                             PeerDiscoverResult::OkRemote { con_hnd, .. } => con_hnd,
                         };
 
-                        match make_req(con_hnd, info.agent.clone()).await {
+                        match make_req(
+                            ro_inner,
+                            space,
+                            payload,
+                            max_timeout,
+                            con_hnd,
+                            info.agent.clone(),
+                        )
+                        .await
+                        {
                             Ok(res) => return Ok(res),
                             Err(err) => {
                                 tracing::warn!(?err, "remote call error");
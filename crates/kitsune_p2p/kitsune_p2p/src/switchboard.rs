@@ -0,0 +1,311 @@
+//! An in-memory network for nodes in the same process, with control over how the
+//! messages which they send each other are delivered.
+//!
+//! Nodes join a switchboard by name with [`TransportConfig::Switchboard`], and tests
+//! look up the same switchboard with [`Switchboard::named`] to delay, drop or partition
+//! messages between the nodes. Unlike [`TransportConfig::Mem`], no signal server or
+//! WebRTC connection is involved, so delivery only depends on what the test asks for.
+//!
+//! Connection preflight isn't run, so nodes have to learn about each other's agents from
+//! a bootstrap service or by having them injected.
+//!
+//! [`TransportConfig::Switchboard`]: kitsune_p2p_types::config::TransportConfig::Switchboard
+//! [`TransportConfig::Mem`]: kitsune_p2p_types::config::TransportConfig::Mem
+
+use crate::wire;
+use base64::Engine;
+use kitsune_p2p_bin_data::NodeCert;
+use kitsune_p2p_types::{KitsuneError, KitsuneResult};
+use once_cell::sync::Lazy;
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+
+/// The URL scheme of the nodes on a switchboard.
+pub const SWITCHBOARD_SCHEME: &str = "switchboard";
+
+/// Every switchboard in the process, by name.
+static SWITCHBOARDS: Lazy<Mutex<HashMap<String, Switchboard>>> = Lazy::new(Default::default);
+
+/// An in-memory network which the nodes in this process can join by name.
+#[derive(Clone)]
+pub struct Switchboard {
+    name: String,
+    state: Arc<Mutex<SwitchboardState>>,
+}
+
+#[derive(Default)]
+struct SwitchboardState {
+    nodes: HashMap<String, mpsc::UnboundedSender<SwitchboardDelivery>>,
+    delay: Duration,
+    drop_rate: f64,
+    /// The partition of each node which is in one. Nodes can only reach the nodes
+    /// in the same partition, and nodes which are in none can only reach each other.
+    partitions: HashMap<String, usize>,
+}
+
+impl std::fmt::Debug for Switchboard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Switchboard")
+            .field("name", &self.name)
+            .finish()
+    }
+}
+
+impl Switchboard {
+    /// Get the switchboard with this name, creating it if nodes haven't joined it yet.
+    ///
+    /// The name becomes the host of the URLs of the nodes, so it must be a valid
+    /// URL host, e.g. `my-test-1`.
+    pub fn named(name: &str) -> Self {
+        SWITCHBOARDS
+            .lock()
+            .entry(name.to_string())
+            .or_insert_with(|| Self {
+                name: name.to_string(),
+                state: Default::default(),
+            })
+            .clone()
+    }
+
+    /// The name of this switchboard.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The URLs of the nodes which are on this switchboard.
+    pub fn node_urls(&self) -> Vec<String> {
+        self.state.lock().nodes.keys().cloned().collect()
+    }
+
+    /// Delay every message by this long. Messages aren't delayed by default.
+    pub fn set_delay(&self, delay: Duration) {
+        self.state.lock().delay = delay;
+    }
+
+    /// Drop this share of the messages at random, between `0.0` and `1.0`.
+    /// Messages aren't dropped by default.
+    pub fn set_drop_rate(&self, drop_rate: f64) {
+        self.state.lock().drop_rate = drop_rate.clamp(0.0, 1.0);
+    }
+
+    /// Split the nodes into partitions which can't reach each other, each given by the
+    /// URLs of its nodes. Any earlier partitions are replaced.
+    ///
+    /// Nodes which aren't in any of the partitions, including nodes which join later,
+    /// can only reach each other.
+    pub fn partition<P, U>(&self, partitions: P)
+    where
+        P: IntoIterator<Item = U>,
+        U: IntoIterator<Item = String>,
+    {
+        let mut state = self.state.lock();
+        state.partitions = partitions
+            .into_iter()
+            .enumerate()
+            .flat_map(|(i, urls)| urls.into_iter().map(move |url| (url, i)))
+            .collect();
+    }
+
+    /// Remove all partitions, so that every node can reach every other node again.
+    pub fn heal(&self) {
+        self.state.lock().partitions.clear();
+    }
+
+    /// Join this switchboard as a new node, which receives the messages sent to it
+    /// from the returned receiver.
+    pub(crate) fn join(
+        &self,
+    ) -> (
+        Arc<SwitchboardNode>,
+        mpsc::UnboundedReceiver<SwitchboardDelivery>,
+    ) {
+        let id = NodeCert::from(Arc::new(rand::random::<[u8; 32]>()));
+        let url = node_url(&self.name, &id);
+        let (send, recv) = mpsc::unbounded_channel();
+        self.state.lock().nodes.insert(url.clone(), send);
+        let node = Arc::new(SwitchboardNode {
+            switchboard: self.clone(),
+            url,
+            id,
+        });
+        (node, recv)
+    }
+
+    /// Send a message between two nodes, unless it's dropped or the nodes are
+    /// partitioned, in which case it's lost as it would be on a real network.
+    /// Sending to a node which isn't on the switchboard is an error.
+    fn send(&self, from: &str, to: &str, msg: SwitchboardMsg) -> KitsuneResult<()> {
+        let state = self.state.lock();
+        let inbox = state
+            .nodes
+            .get(to)
+            .ok_or_else(|| KitsuneError::other(format!("No node at {} on the switchboard", to)))?
+            .clone();
+        if state.partitions.get(from) != state.partitions.get(to) {
+            tracing::trace!(%from, %to, "Switchboard message lost to a partition");
+            return Ok(());
+        }
+        if state.drop_rate > 0.0 && rand::Rng::gen_bool(&mut rand::thread_rng(), state.drop_rate) {
+            tracing::trace!(%from, %to, "Switchboard message dropped");
+            return Ok(());
+        }
+        let delivery = SwitchboardDelivery {
+            from: from.to_string(),
+            msg,
+        };
+        if state.delay.is_zero() {
+            // The node may have just left, in which case the message is lost.
+            let _ = inbox.send(delivery);
+        } else {
+            let delay = state.delay;
+            tokio::task::spawn(async move {
+                tokio::time::sleep(delay).await;
+                let _ = inbox.send(delivery);
+            });
+        }
+        Ok(())
+    }
+}
+
+/// A node on a switchboard, which leaves it when dropped.
+#[derive(Debug)]
+pub(crate) struct SwitchboardNode {
+    switchboard: Switchboard,
+    url: String,
+    id: NodeCert,
+}
+
+impl SwitchboardNode {
+    /// The URL at which other nodes reach this node.
+    pub(crate) fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Send a message to the node at `to`.
+    pub(crate) fn send(&self, to: &str, msg: SwitchboardMsg) -> KitsuneResult<()> {
+        self.switchboard.send(&self.url, to, msg)
+    }
+
+    /// The URLs of the other nodes on the switchboard.
+    pub(crate) fn peer_urls(&self) -> Vec<String> {
+        let mut urls = self.switchboard.node_urls();
+        urls.retain(|url| url != &self.url);
+        urls
+    }
+
+    /// Leave the switchboard, so that no more messages are delivered to this node.
+    pub(crate) fn leave(&self) {
+        self.switchboard.state.lock().nodes.remove(&self.url);
+    }
+}
+
+impl Drop for SwitchboardNode {
+    fn drop(&mut self) {
+        self.leave();
+    }
+}
+
+/// A message between two nodes on a switchboard.
+#[derive(Debug)]
+pub(crate) enum SwitchboardMsg {
+    /// A notification which doesn't need a response.
+    Notify(wire::Wire),
+    /// A request which expects a response with the same message id.
+    Request(u64, wire::Wire),
+    /// The response to a request.
+    Response(u64, wire::Wire),
+}
+
+/// A message which was delivered to a node.
+#[derive(Debug)]
+pub(crate) struct SwitchboardDelivery {
+    /// The URL of the node which sent the message.
+    pub from: String,
+    /// The message.
+    pub msg: SwitchboardMsg,
+}
+
+/// Node URLs have their id in the path, in the same form as tx5 URLs, so that
+/// gossip can tell the nodes apart by their URLs.
+fn node_url(name: &str, id: &NodeCert) -> String {
+    let id = base64::prelude::BASE64_URL_SAFE_NO_PAD.encode(id.as_slice());
+    format!("{}://{}/{}", SWITCHBOARD_SCHEME, name, id)
+}
+
+/// Get the id of the node at a switchboard URL.
+pub(crate) fn node_id_from_url(url: &str) -> KitsuneResult<NodeCert> {
+    let invalid = || KitsuneError::other(format!("Invalid switchboard url: {}", url));
+    let id = url
+        .strip_prefix(&format!("{}://", SWITCHBOARD_SCHEME))
+        .and_then(|rest| rest.rsplit_once('/'))
+        .map(|(_, id)| id)
+        .ok_or_else(invalid)?;
+    let id: [u8; 32] = base64::prelude::BASE64_URL_SAFE_NO_PAD
+        .decode(id)
+        .map_err(|_| invalid())?
+        .try_into()
+        .map_err(|_| invalid())?;
+    Ok(NodeCert::from(Arc::new(id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notify() -> SwitchboardMsg {
+        SwitchboardMsg::Notify(wire::Wire::failure("test".to_string()))
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn messages_are_delivered_unless_partitioned() {
+        let switchboard = Switchboard::named("delivered-unless-partitioned");
+        let (alice, _alice_recv) = switchboard.join();
+        let (bob, mut bob_recv) = switchboard.join();
+        assert_eq!(vec![alice.url().to_string()], bob.peer_urls());
+        assert_eq!(bob.id, node_id_from_url(bob.url()).unwrap());
+        // Gossip reads node ids from URLs as proxy URLs.
+        let digest = kitsune_p2p_types::tx_utils::ProxyUrl::from_full(bob.url())
+            .unwrap()
+            .digest()
+            .unwrap();
+        assert_eq!(bob.id, NodeCert::from(digest.0));
+
+        alice.send(bob.url(), notify()).unwrap();
+        assert_eq!(alice.url(), bob_recv.recv().await.unwrap().from);
+
+        switchboard.partition([vec![alice.url().to_string()]]);
+        alice.send(bob.url(), notify()).unwrap();
+        assert!(bob_recv.try_recv().is_err());
+
+        switchboard.heal();
+        alice.send(bob.url(), notify()).unwrap();
+        assert!(bob_recv.recv().await.is_some());
+
+        drop(bob);
+        assert!(alice.send(&node_url("x", &alice.id), notify()).is_err());
+        assert!(alice.peer_urls().is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn messages_can_be_dropped_and_delayed() {
+        let switchboard = Switchboard::named("dropped-and-delayed");
+        let (alice, _alice_recv) = switchboard.join();
+        let (bob, mut bob_recv) = switchboard.join();
+
+        switchboard.set_drop_rate(1.0);
+        alice.send(bob.url(), notify()).unwrap();
+        assert!(bob_recv.try_recv().is_err());
+
+        switchboard.set_drop_rate(0.0);
+        switchboard.set_delay(Duration::from_millis(100));
+        alice.send(bob.url(), notify()).unwrap();
+        assert!(bob_recv.try_recv().is_err());
+        tokio::time::timeout(Duration::from_secs(5), bob_recv.recv())
+            .await
+            .unwrap()
+            .unwrap();
+    }
+}
@@ -55,6 +55,26 @@ pub async fn spawn_handler<H: KitsuneP2pEventHandler + ghost_actor::GhostControl
     (tx, tokio::task::spawn(driver))
 }
 
+/// Check that `signature` is an ed25519 signature over `data` by the key which
+/// makes up the first 32 bytes of `agent`, as the test hosts sign network data.
+pub fn verify_ed25519_network_data(
+    agent: &KitsuneAgent,
+    data: Vec<u8>,
+    signature: &KitsuneSignature,
+) -> KitsuneHostResult<'static, bool> {
+    use kitsune_p2p_types::dependencies::lair_keystore_api::dependencies::sodoken;
+
+    let (Some(Ok(pub_key)), Ok(signature)) = (
+        agent.0.get(..32).map(<[u8; 32]>::try_from),
+        <[u8; 64]>::try_from(signature.0.as_slice()),
+    ) else {
+        return kitsune_p2p_types::box_fut(Ok(false));
+    };
+    async move { Ok(sodoken::sign::verify_detached(signature, data, pub_key).await?) }
+        .boxed()
+        .into()
+}
+
 pub fn hash_op_data(data: &[u8]) -> Arc<KitsuneOpHash> {
     Arc::new(KitsuneOpHash::new(
         blake2b_simd::Params::new()
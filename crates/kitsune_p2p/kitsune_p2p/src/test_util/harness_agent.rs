@@ -60,6 +60,15 @@ impl KitsuneHostDefaultError for HarnessHost {
     ) -> KitsuneHostResult<RegionSetLtcs> {
         box_fut(Ok(RegionSetLtcs::empty()))
     }
+
+    fn verify_network_data(
+        &self,
+        agent: Arc<KitsuneAgent>,
+        data: Vec<u8>,
+        signature: Arc<KitsuneSignature>,
+    ) -> KitsuneHostResult<bool> {
+        crate::test_util::verify_ed25519_network_data(&agent, data, &signature)
+    }
 }
 
 pub(crate) async fn spawn_test_agent(
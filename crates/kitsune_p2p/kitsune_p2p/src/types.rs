@@ -85,7 +85,7 @@ pub(crate) mod gossip;
 #[allow(missing_docs)]
 pub mod wire;
 
-pub use gossip::GossipModuleType;
+pub use gossip::{GossipModuleType, GossipPause};
 pub use kitsune_p2p_types::dht;
 pub use kitsune_p2p_types::dht_arc;
 
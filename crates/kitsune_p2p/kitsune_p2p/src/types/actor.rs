@@ -30,6 +30,11 @@ pub struct RpcMulti {
     /// but made any additional outgoing remote requests,
     /// we'll wait at least this long for additional responses.
     pub remote_request_grace_ms: u64,
+
+    /// If `true`, the first response received is returned.
+    /// Otherwise the request is made of up to `max_remote_agent_count`
+    /// remote agents at once, and all of their responses are returned.
+    pub as_race: bool,
 }
 
 impl RpcMulti {
@@ -48,6 +53,7 @@ impl RpcMulti {
             max_remote_agent_count: tuning_params.default_rpc_multi_remote_agent_count,
             max_timeout: tuning_params.implicit_timeout(),
             remote_request_grace_ms: tuning_params.default_rpc_multi_remote_request_grace_ms,
+            as_race: true,
         }
     }
 }
@@ -166,7 +172,29 @@ ghost_actor::ghost_chan! {
         /// dump network stats
         fn dump_network_stats() -> serde_json::Value;
 
+        /// Get the statistics of each open transport connection to a remote peer.
+        fn get_connection_stats() -> Vec<kitsune_p2p_types::metrics::PeerConnectionStats>;
+
         /// Get data for diagnostics
         fn get_diagnostics(space: KSpace) -> KitsuneDiagnostics;
+
+        /// Pause or resume gossip for a space.
+        fn set_gossip_pause(space: KSpace, pause: super::GossipPause) -> ();
+
+        /// Pin the storage arc of a local agent, so that it isn't resized by dynamic arcs,
+        /// and publish it. With `None`, the arc is sized by dynamic arcs again.
+        fn set_arq_pin(space: KSpace, agent: KAgent, pin: Option<crate::dht::arq::ArqPin>) -> ();
+
+        /// Get the segments of the storage arc of a local agent, from the one which the
+        /// fewest known peers cover to the one which the most cover.
+        fn get_coverage_gaps(space: KSpace, agent: KAgent) -> Vec<crate::dht::arq::CoverageGap>;
+
+        /// Restrict the network infrastructure which a space may use.
+        /// This must be set before the space is first joined, and fails
+        /// if the space is already running with another profile.
+        fn set_network_profile(
+            space: KSpace,
+            profile: kitsune_p2p_types::config::NetworkProfile,
+        ) -> ();
     }
 }
@@ -21,6 +21,19 @@ pub enum GossipModuleType {
     ShardedHistorical,
 }
 
+/// Whether gossip is paused for a space, so that it can be ruled in or out as the
+/// cause of an issue without restarting with gossip disabled.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum GossipPause {
+    /// Gossip runs as normal.
+    #[default]
+    Running,
+    /// No new rounds are initiated, but rounds which remote nodes initiate are still accepted.
+    Initiation,
+    /// No new rounds are initiated or accepted. Rounds which are in progress still finish.
+    All,
+}
+
 /// Represents an interchangeable gossip strategy module
 pub trait AsGossipModule: 'static + Send + Sync {
     fn close(&self);
@@ -33,6 +46,7 @@ pub trait AsGossipModule: 'static + Send + Sync {
     fn local_agent_join(&self, a: Arc<KitsuneAgent>);
     fn local_agent_leave(&self, a: Arc<KitsuneAgent>);
     fn new_integrated_data(&self) {}
+    fn set_pause(&self, pause: GossipPause);
 }
 
 #[derive(Clone)]
@@ -64,6 +78,11 @@ impl GossipModule {
     pub fn new_integrated_data(&self) {
         self.0.new_integrated_data();
     }
+
+    /// Pause or resume gossip.
+    pub fn set_pause(&self, pause: GossipPause) {
+        self.0.set_pause(pause);
+    }
 }
 
 impl std::fmt::Debug for GossipModule {
@@ -254,6 +254,15 @@ impl KitsuneHost for TestHost {
         async move { Ok(res) }.boxed().into()
     }
 
+    fn verify_network_data(
+        &self,
+        agent: Arc<kitsune_p2p_bin_data::KitsuneAgent>,
+        data: Vec<u8>,
+        signature: Arc<kitsune_p2p_bin_data::KitsuneSignature>,
+    ) -> kitsune_p2p::KitsuneHostResult<bool> {
+        kitsune_p2p::test_util::verify_ed25519_network_data(&agent, data, &signature)
+    }
+
     fn lair_tag(&self) -> Option<Arc<str>> {
         Some(self.tag.clone().into())
     }
@@ -2,6 +2,7 @@
 #![allow(missing_docs)]
 
 use crate::tx_utils::TxUrl;
+use crate::{KitsuneError, KitsuneResult};
 use url2::Url2;
 
 /// Fifteen minutes
@@ -344,6 +345,15 @@ pub mod tuning_params_struct {
         /// The default value is 60 * 5 s = five minutes.
         /// The minimum value is 60 s = one minute.
         bootstrap_max_delay_s: u32 = 60 * 5,
+
+        /// Seed the randomness of gossip scheduling: the choice between partners,
+        /// the tie-breaks of initiated rounds and the timing of the gossip loop.
+        /// A failing network test can be re-run with the same seeds to reproduce
+        /// the same interleaving, as long as each node is given its own seed.
+        ///
+        /// Do not set this except in testing environments.
+        /// `0` leaves gossip random. [Default: 0]
+        danger_gossip_seed: u64 = 0,
     }
 
     impl KitsuneP2pTuningParams {
@@ -358,6 +368,11 @@ pub mod tuning_params_struct {
             std::time::Duration::from_secs(self.danger_gossip_recent_threshold_secs)
         }
 
+        /// The seed for gossip scheduling, if gossip should be deterministic.
+        pub fn danger_gossip_seed(&self) -> Option<u64> {
+            (self.danger_gossip_seed != 0).then_some(self.danger_gossip_seed)
+        }
+
         /// get the tx5_ban_time_s param as a Duration.
         pub fn tx5_ban_time(&self) -> std::time::Duration {
             std::time::Duration::from_secs(self.tx5_ban_time_s as u64)
@@ -418,6 +433,11 @@ pub struct KitsuneP2pConfig {
     /// so that logs from multiple instances in the same process can be disambiguated.
     #[serde(default)]
     pub tracing_scope: Option<String>,
+
+    /// When and how fast historical gossip runs. It runs at any time, and only within
+    /// the limits of the tuning params, if this is `None`.
+    #[serde(default)]
+    pub historical_gossip_schedule: Option<HistoricalGossipSchedule>,
 }
 
 impl Default for KitsuneP2pConfig {
@@ -434,6 +454,15 @@ impl KitsuneP2pConfig {
             bootstrap_service: None,
             tuning_params: KitsuneP2pTuningParams::default(),
             tracing_scope: None,
+            historical_gossip_schedule: None,
+        }
+    }
+
+    /// Testing-only in-memory backend on the switchboard with the given name.
+    pub fn switchboard(name: impl Into<String>) -> Self {
+        Self {
+            transport_pool: vec![TransportConfig::Switchboard { name: name.into() }],
+            ..Self::mem()
         }
     }
 
@@ -448,6 +477,7 @@ impl KitsuneP2pConfig {
             bootstrap_service: None,
             tuning_params: KitsuneP2pTuningParams::default(),
             tracing_scope: None,
+            historical_gossip_schedule: None,
         }
     }
 }
@@ -502,4 +532,256 @@ pub enum TransportConfig {
     /// A transport that uses the local memory transport protocol
     /// (this is mainly for testing)
     Mem {},
+
+    /// An in-memory network shared by the nodes in this process which join the
+    /// switchboard with the same name, on which tests can delay, drop and partition
+    /// messages (this is only for testing)
+    Switchboard {
+        /// The name of the switchboard to join.
+        name: String,
+    },
+}
+
+/// Restrictions on the network infrastructure which a space may use, e.g. so that the
+/// traffic of a privacy-sensitive app never touches public infrastructure.
+///
+/// A profile is applied to the config of a space with [`NetworkProfile::restrict`]
+/// before the space is created. The transports are shared by all spaces, so a profile
+/// can't switch them over to another signal server, and joining a space fails instead
+/// if they use one which the profile doesn't allow.
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct NetworkProfile {
+    /// The bootstrap services which the space may use. The bootstrap service of the
+    /// config is used if it's one of them, and otherwise the first of them.
+    /// No bootstrap service is used if the list is empty, and any may be if it's `None`.
+    #[serde(default)]
+    pub bootstrap_services: Option<Vec<Url2>>,
+
+    /// The signal servers which the transports may be connected to.
+    /// Any may be if it's `None`.
+    #[serde(default)]
+    pub signal_servers: Option<Vec<String>>,
+
+    /// Only discover and reach peers on the local network: no bootstrap service is
+    /// used, and the signal server must be at a loopback or private address.
+    #[serde(default)]
+    pub lan_only: bool,
+
+    /// When and how fast the space runs historical gossip, in place of the schedule of
+    /// the config. Recent gossip isn't affected.
+    #[serde(default)]
+    pub historical_gossip: Option<HistoricalGossipSchedule>,
+}
+
+impl NetworkProfile {
+    /// The config which a space with this profile uses, given the config of the conductor.
+    /// Fails if the transports use a signal server which this profile doesn't allow.
+    pub fn restrict(&self, config: &KitsuneP2pConfig) -> KitsuneResult<KitsuneP2pConfig> {
+        for transport in &config.transport_pool {
+            let TransportConfig::WebRTC { signal_url, .. } = transport else {
+                // In-process transports don't use any infrastructure.
+                continue;
+            };
+            if let Some(signal_servers) = &self.signal_servers {
+                if !signal_servers.contains(signal_url) {
+                    return Err(KitsuneError::other(format!(
+                        "The network profile doesn't allow the signal server {}",
+                        signal_url
+                    )));
+                }
+            }
+            if self.lan_only && !is_local_url(signal_url) {
+                return Err(KitsuneError::other(format!(
+                    "The network profile is LAN only, but the signal server {} isn't on the local network",
+                    signal_url
+                )));
+            }
+        }
+
+        let mut config = config.clone();
+        if let Some(schedule) = &self.historical_gossip {
+            config.historical_gossip_schedule = Some(schedule.clone());
+        }
+        if self.lan_only {
+            config.bootstrap_service = None;
+        } else if let Some(bootstrap_services) = &self.bootstrap_services {
+            let allowed = config
+                .bootstrap_service
+                .as_ref()
+                .is_some_and(|url| bootstrap_services.contains(url));
+            if !allowed {
+                config.bootstrap_service = bootstrap_services.first().cloned();
+            }
+        }
+        Ok(config)
+    }
+}
+
+/// When and how fast historical gossip runs, e.g. so that a home server only backfills
+/// the history of a space at night, and doesn't compete with interactive traffic.
+///
+/// Outside of the windows no new historical rounds are initiated or accepted, but
+/// rounds which are in progress still finish. Recent gossip always runs.
+#[derive(Clone, Debug, Default, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct HistoricalGossipSchedule {
+    /// The windows in which historical gossip runs. It runs at any time if there are none.
+    #[serde(default)]
+    pub windows: Vec<GossipWindow>,
+
+    /// The most bandwidth which the historical gossip of a space may receive, in kilobits
+    /// per second. This is on top of `gossip_historic_inbound_target_mbps`, which limits
+    /// the historical gossip of all spaces together.
+    #[serde(default)]
+    pub max_inbound_kbps: Option<u32>,
+
+    /// The most bandwidth which the historical gossip of a space may send, in kilobits
+    /// per second. This is on top of `gossip_historic_outbound_target_mbps`, which limits
+    /// the historical gossip of all spaces together.
+    #[serde(default)]
+    pub max_outbound_kbps: Option<u32>,
+}
+
+impl HistoricalGossipSchedule {
+    /// Whether historical gossip may start new rounds at `now`.
+    pub fn is_open(&self, now: std::time::SystemTime) -> bool {
+        if self.windows.is_empty() {
+            return true;
+        }
+        let secs = now
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let hour = (secs / 60 / 60 % 24) as u8;
+        self.windows.iter().any(|window| window.contains(hour))
+    }
+}
+
+/// The hours of the day in which historical gossip runs, from the start of `start_hour`
+/// up to the start of `end_hour`, in UTC. A window whose end is before its start runs
+/// past midnight, e.g. from 22 to 6, and one whose end is its start lasts all day.
+#[derive(Clone, Copy, Debug, serde::Serialize, serde::Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct GossipWindow {
+    /// The hour, from 0 to 23, at which the window opens.
+    pub start_hour: u8,
+    /// The hour, from 0 to 23, at which the window closes.
+    pub end_hour: u8,
+}
+
+impl GossipWindow {
+    /// Whether the hour of the day, from 0 to 23, is in this window.
+    pub fn contains(&self, hour: u8) -> bool {
+        match self.start_hour.cmp(&self.end_hour) {
+            std::cmp::Ordering::Less => self.start_hour <= hour && hour < self.end_hour,
+            std::cmp::Ordering::Greater => self.start_hour <= hour || hour < self.end_hour,
+            std::cmp::Ordering::Equal => true,
+        }
+    }
+}
+
+/// Whether a URL is at a loopback, private or link local address.
+fn is_local_url(url: &str) -> bool {
+    let Ok(url) = Url2::try_parse(url) else {
+        return false;
+    };
+    match url.host() {
+        Some(url::Host::Ipv4(ip)) => ip.is_loopback() || ip.is_private() || ip.is_link_local(),
+        // Unique local addresses are fc00::/7 and link local addresses fe80::/10.
+        Some(url::Host::Ipv6(ip)) => {
+            ip.is_loopback()
+                || (ip.segments()[0] & 0xfe00) == 0xfc00
+                || (ip.segments()[0] & 0xffc0) == 0xfe80
+        }
+        Some(url::Host::Domain(domain)) => domain == "localhost" || domain.ends_with(".local"),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn webrtc_config(signal_url: &str) -> KitsuneP2pConfig {
+        KitsuneP2pConfig {
+            transport_pool: vec![TransportConfig::WebRTC {
+                signal_url: signal_url.to_string(),
+                webrtc_config: None,
+            }],
+            bootstrap_service: Some(Url2::parse("https://bootstrap.holo.host")),
+            ..KitsuneP2pConfig::mem()
+        }
+    }
+
+    #[test]
+    fn network_profile_restricts_bootstrap_and_signal_servers() {
+        let config = webrtc_config("wss://signal.holo.host");
+        assert_eq!(config, NetworkProfile::default().restrict(&config).unwrap());
+
+        let private = Url2::parse("https://bootstrap.example.com");
+        let profile = NetworkProfile {
+            bootstrap_services: Some(vec![private.clone()]),
+            ..Default::default()
+        };
+        assert_eq!(
+            Some(private),
+            profile.restrict(&config).unwrap().bootstrap_service
+        );
+
+        let profile = NetworkProfile {
+            signal_servers: Some(vec!["wss://signal.example.com".to_string()]),
+            ..Default::default()
+        };
+        assert!(profile.restrict(&config).is_err());
+    }
+
+    #[test]
+    fn lan_only_network_profile_needs_a_local_signal_server() {
+        let profile = NetworkProfile {
+            lan_only: true,
+            ..Default::default()
+        };
+        assert!(profile
+            .restrict(&webrtc_config("wss://signal.holo.host"))
+            .is_err());
+        for signal_url in [
+            "ws://127.0.0.1:5000",
+            "ws://192.168.1.10:5000",
+            "ws://[fd00::1]:5000",
+            "ws://signal.local:5000",
+        ] {
+            let config = profile.restrict(&webrtc_config(signal_url)).unwrap();
+            assert_eq!(None, config.bootstrap_service);
+        }
+        // - Peers in the same process are always local.
+        assert!(profile.restrict(&KitsuneP2pConfig::mem()).is_ok());
+    }
+
+    #[test]
+    fn historical_gossip_only_runs_in_its_windows() {
+        let at_hour =
+            |hour: u64| std::time::UNIX_EPOCH + std::time::Duration::from_secs(hour * 60 * 60);
+        let nightly = HistoricalGossipSchedule {
+            windows: vec![GossipWindow {
+                start_hour: 22,
+                end_hour: 6,
+            }],
+            ..Default::default()
+        };
+        assert!(nightly.is_open(at_hour(23)));
+        // - The window runs past midnight, on any day.
+        assert!(nightly.is_open(at_hour(24 * 100 + 5)));
+        assert!(!nightly.is_open(at_hour(6)));
+        assert!(!nightly.is_open(at_hour(12)));
+        assert!(HistoricalGossipSchedule::default().is_open(at_hour(12)));
+
+        // - A profile sets the schedule of its space.
+        let profile = NetworkProfile {
+            historical_gossip: Some(nightly.clone()),
+            ..Default::default()
+        };
+        let config = profile.restrict(&KitsuneP2pConfig::mem()).unwrap();
+        assert_eq!(Some(nightly), config.historical_gossip_schedule);
+    }
 }
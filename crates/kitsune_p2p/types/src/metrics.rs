@@ -251,7 +251,8 @@ pub fn init_sys_info_poll() {
 
                 let proc = system.process(pid).unwrap();
 
-                let mem = proc.memory();
+                // The memory is given in bytes.
+                let mem = proc.memory() / 1024;
                 USED_MEM_KB.store(mem, Ordering::Relaxed);
 
                 let cpu = (proc.cpu_usage() * 1000.0) as usize;
@@ -433,6 +434,26 @@ impl Tx2ApiMetrics {
     }
 }
 
+/// The statistics of the transport connection to a remote peer.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct PeerConnectionStats {
+    /// The id of the remote peer, which is the last segment of its peer URL.
+    pub peer_id: String,
+    /// When the connection was opened.
+    pub opened_at: Timestamp,
+    /// Whether messages are relayed through the signal server, because the connection
+    /// hasn't been upgraded to a direct WebRTC connection.
+    pub relayed: bool,
+    /// The messages sent over the connection.
+    pub send_message_count: u64,
+    /// The bytes sent over the connection.
+    pub send_bytes: u64,
+    /// The messages received over the connection.
+    pub recv_message_count: u64,
+    /// The bytes received over the connection.
+    pub recv_bytes: u64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::{get_sys_info, init_sys_info_poll, metric_task, TASK_COUNT};
@@ -46,7 +46,7 @@ impl ProxyUrl {
             };
         }
         let full = url2::try_url2!("{}", full).map_err(|_| err!("parse"))?;
-        if full.scheme() == "wss" || full.scheme() == "ws" {
+        if full.scheme() == "wss" || full.scheme() == "ws" || full.scheme() == "switchboard" {
             return Ok(Self {
                 full: full.clone(),
                 base: full,
@@ -142,8 +142,8 @@ impl ProxyUrl {
     /// Extract the cert digest from the url
     pub fn digest(&self) -> KitsuneResult<CertDigest> {
         let scheme = self.full.scheme();
-        if scheme == "wss" || scheme == "ws" {
-            // override for tx5
+        if scheme == "wss" || scheme == "ws" || scheme == "switchboard" {
+            // override for tx5 and the in-memory switchboard
             if let Some(mut i) = self.full.path_segments() {
                 if let Some(u) = i.next() {
                     let digest = base64::prelude::BASE64_URL_SAFE_NO_PAD
@@ -50,6 +50,18 @@ use hdi::prelude::*;
             &self,
             must_get_valid_record_input: MustGetValidRecordInput,
         ) -> ExternResult<Record>;
+        fn must_get_entry_with_options(
+            &self,
+            must_get_entry_input: MustGetEntryWithOptionsInput,
+        ) -> ExternResult<EntryHashed>;
+        fn must_get_action_with_options(
+            &self,
+            must_get_action_input: MustGetActionWithOptionsInput,
+        ) -> ExternResult<SignedActionHashed>;
+        fn must_get_valid_record_with_options(
+            &self,
+            must_get_valid_record_input: MustGetValidRecordWithOptionsInput,
+        ) -> ExternResult<Record>;
         fn must_get_agent_activity(
             &self,
             input: MustGetAgentActivityInput,
@@ -60,6 +72,9 @@ use hdi::prelude::*;
         // Info
         fn dna_info(&self, dna_info_input: ()) -> ExternResult<DnaInfo>;
         fn zome_info(&self, zome_info_input: ()) -> ExternResult<ZomeInfo>;
+        // Validation
+        fn deterministic_random_bytes(&self, number_of_bytes: u32) -> ExternResult<Bytes>;
+        fn validation_timestamp(&self, validation_timestamp_input: ()) -> ExternResult<Timestamp>;
         // Trace
         fn trace(&self, trace_msg: TraceMsg) -> ExternResult<()>;
         // XSalsa20Poly1305
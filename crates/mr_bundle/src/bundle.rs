@@ -46,6 +46,36 @@ where
     //        struct into two versions for each case.
     #[serde(skip)]
     root_dir: Option<PathBuf>,
+
+    /// A signature over [`Bundle::signing_bytes`], made by the publisher of
+    /// the bundle. Bundles without a signature encode exactly as they did
+    /// before signatures existed.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    signature: Option<BundleSignature>,
+}
+
+/// A publisher's signature over the contents of a [`Bundle`].
+///
+/// The key and signature are opaque bytes here, and it's up to the user of
+/// the bundle to decide which signing scheme they belong to.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "fuzzing", derive(arbitrary::Arbitrary))]
+pub struct BundleSignature {
+    /// The public key of the signer.
+    #[serde(with = "serde_bytes")]
+    pub signer: Vec<u8>,
+
+    /// The signature over [`Bundle::signing_bytes`].
+    #[serde(with = "serde_bytes")]
+    pub signature: Vec<u8>,
+}
+
+/// The part of a Bundle which is covered by its signature. This encodes
+/// exactly as the Bundle does without its signature.
+#[derive(Serialize)]
+struct SignedContent<'a, M> {
+    manifest: &'a M,
+    resources: &'a ResourceMap,
 }
 
 impl<M> Bundle<M>
@@ -104,6 +134,7 @@ where
             manifest,
             resources,
             root_dir,
+            signature: None,
         })
     }
 
@@ -112,8 +143,44 @@ where
         &self.manifest
     }
 
+    /// Accessor for the publisher's signature, if the bundle is signed
+    pub fn signature(&self) -> Option<&BundleSignature> {
+        self.signature.as_ref()
+    }
+
+    /// Return this Bundle with the given signature attached, replacing any
+    /// existing one. The signature is not checked.
+    pub fn with_signature(mut self, signature: BundleSignature) -> Self {
+        self.signature = Some(signature);
+        self
+    }
+
+    /// The bytes that a publisher signs, which are the canonical encoding of
+    /// the bundle without its signature, i.e. what [`Bundle::encode`] compresses.
+    /// Decoding a signed bundle checks that its bytes are in the canonical
+    /// encoding, so the signature covers the bytes of the bundle file.
+    ///
+    /// Resources located by path or url are not in those bytes, so they could be
+    /// swapped without invalidating the signature. Only bundles whose resources
+    /// are all bundled can be signed.
+    pub fn signing_bytes(&self) -> MrBundleResult<Vec<u8>> {
+        if let Some(location) = self
+            .manifest
+            .locations()
+            .into_iter()
+            .find(|location| !matches!(location, Location::Bundled(_)))
+        {
+            return Err(BundleError::UnbundledResourceInSignedBundle(location).into());
+        }
+        Ok(rmp_serde::to_vec_named(&SignedContent {
+            manifest: &self.manifest,
+            resources: &self.resources,
+        })?)
+    }
+
     /// Return a new Bundle with an updated manifest, subject to the same
     /// validation constraints as creating a new Bundle from scratch.
+    /// Any signature is dropped, since it would no longer match.
     pub fn update_manifest(self, manifest: M) -> MrBundleResult<Self> {
         Self::from_parts(manifest, self.resources, self.root_dir)
     }
@@ -181,7 +248,11 @@ where
 
     /// Decode bytes produced by [`encode`](Bundle::encode)
     pub fn decode(bytes: &[u8]) -> MrBundleResult<Self> {
-        crate::decode(bytes)
+        let (bundle, bytes): (Self, _) = crate::encoding::decode_with_bytes(bytes)?;
+        if bundle.signature.is_some() && rmp_serde::to_vec_named(&bundle)? != bytes {
+            return Err(BundleError::NonCanonicalSignedBundle.into());
+        }
+        Ok(bundle)
     }
 
     /// Given that the Manifest is located at the given absolute `path`, find
@@ -269,4 +340,82 @@ mod tests {
             Err(MrBundleError::BundleError(BundleError::BundledPathNotInManifest(path))) if path == PathBuf::from("3.thing")
         );
     }
+
+    #[test]
+    fn bundle_signature_roundtrip() {
+        let manifest = TestManifest(vec![Location::Bundled("1.thing".into())]);
+        let unsigned =
+            Bundle::new_unchecked(manifest, vec![("1.thing".into(), vec![1].into())]).unwrap();
+        let unsigned_bytes = unsigned.encode().unwrap();
+        let signing_bytes = unsigned.signing_bytes().unwrap();
+
+        let signature = BundleSignature {
+            signer: vec![1; 32],
+            signature: vec![2; 64],
+        };
+        let signed = unsigned.with_signature(signature.clone());
+        assert_eq!(signed.signing_bytes().unwrap(), signing_bytes);
+
+        let decoded: Bundle<TestManifest> = Bundle::decode(&signed.encode().unwrap()).unwrap();
+        assert_eq!(decoded.signature(), Some(&signature));
+
+        let decoded: Bundle<TestManifest> = Bundle::decode(&unsigned_bytes).unwrap();
+        assert_eq!(decoded.signature(), None);
+    }
+
+    #[test]
+    fn signing_bytes_are_the_unsigned_bundle_bytes() {
+        let manifest = TestManifest(vec![Location::Bundled("1.thing".into())]);
+        let bundle =
+            Bundle::new_unchecked(manifest, vec![("1.thing".into(), vec![1].into())]).unwrap();
+        let (_, bytes): (Bundle<TestManifest>, _) =
+            crate::encoding::decode_with_bytes(&bundle.encode().unwrap()).unwrap();
+        assert_eq!(bundle.signing_bytes().unwrap(), bytes);
+    }
+
+    #[test]
+    fn bundles_with_unbundled_resources_cant_be_signed() {
+        let location = Location::Url("https://example.com/2.thing".into());
+        let manifest = TestManifest(vec![Location::Bundled("1.thing".into()), location.clone()]);
+        let bundle =
+            Bundle::new_unchecked(manifest, vec![("1.thing".into(), vec![1].into())]).unwrap();
+        matches::assert_matches!(
+            bundle.signing_bytes(),
+            Err(MrBundleError::BundleError(BundleError::UnbundledResourceInSignedBundle(l))) if l == location
+        );
+    }
+
+    #[test]
+    fn signed_bundles_must_be_canonically_encoded() {
+        let manifest = TestManifest(vec![Location::Bundled("1.thing".into())]);
+        let signed = Bundle::new_unchecked(manifest, vec![("1.thing".into(), vec![1].into())])
+            .unwrap()
+            .with_signature(BundleSignature {
+                signer: vec![1; 32],
+                signature: vec![2; 64],
+            });
+
+        assert_eq!(Bundle::decode(&signed.encode().unwrap()).unwrap(), signed);
+
+        // - The same bundle with its fields in another order decodes to the
+        //   same value, but isn't in the bytes which were signed.
+        #[derive(Serialize)]
+        struct Reordered<'a> {
+            signature: &'a BundleSignature,
+            resources: &'a ResourceMap,
+            manifest: &'a TestManifest,
+        }
+        let reordered = crate::encode(&Reordered {
+            signature: signed.signature().unwrap(),
+            resources: signed.bundled_resources(),
+            manifest: signed.manifest(),
+        })
+        .unwrap();
+        matches::assert_matches!(
+            Bundle::<TestManifest>::decode(&reordered),
+            Err(MrBundleError::BundleError(
+                BundleError::NonCanonicalSignedBundle
+            ))
+        );
+    }
 }
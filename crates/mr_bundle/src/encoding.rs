@@ -14,9 +14,18 @@ pub fn encode<T: serde::ser::Serialize>(data: &T) -> MrBundleResult<Vec<u8>> {
 
 /// Decompress and deserialize some bytes (inverse of `encode`)
 pub fn decode<T: serde::de::DeserializeOwned>(compressed: &[u8]) -> MrBundleResult<T> {
+    Ok(decode_with_bytes(compressed)?.0)
+}
+
+/// Decompress and deserialize some bytes, also returning the decompressed bytes
+pub(crate) fn decode_with_bytes<T: serde::de::DeserializeOwned>(
+    compressed: &[u8],
+) -> MrBundleResult<(T, Vec<u8>)> {
     let mut gz = flate2::read::GzDecoder::new(compressed);
     let mut bytes = Vec::new();
     gz.read_to_end(&mut bytes)?;
-    rmp_serde::from_slice(&bytes)
-        .map_err(|e| MrBundleError::MsgpackDecodeError(std::any::type_name::<T>().to_string(), e))
+    let data = rmp_serde::from_slice(&bytes).map_err(|e| {
+        MrBundleError::MsgpackDecodeError(std::any::type_name::<T>().to_string(), e)
+    })?;
+    Ok((data, bytes))
 }
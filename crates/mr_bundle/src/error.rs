@@ -52,6 +52,14 @@ pub enum BundleError {
         "Cannot use relative paths for local locations. The following local path is relative: {0}"
     )]
     RelativeLocalPath(std::path::PathBuf),
+
+    #[error(
+        "Only bundled resources can be signed, since the signature would not cover the resource at {0:?}"
+    )]
+    UnbundledResourceInSignedBundle(crate::Location),
+
+    #[error("The signed bundle is not in its canonical encoding, so its signature does not cover its bytes")]
+    NonCanonicalSignedBundle,
 }
 pub type BundleResult<T> = Result<T, BundleError>;
 
@@ -513,6 +513,7 @@ async fn path_to_def(path: PathBuf, dependencies: Vec<ZomeName>) -> ZomeDef {
     ZomeDef::Wasm(WasmZome {
         wasm_hash,
         dependencies,
+        init_dependencies: Default::default(),
         preserialized_path: None,
     })
 }
@@ -113,6 +113,17 @@ fn create_priv_msg(_: ()) -> ExternResult<ActionHash> {
     ))))
 }
 
+#[hdk_extern]
+fn prove_priv_msg(challenge: Bytes) -> ExternResult<PrivateEntryProof> {
+    let entry_hash = hash_entry(&crate::integrity::PrivMsg("Don't tell anyone".into()))?;
+    create_private_entry_proof(entry_hash, challenge)
+}
+
+#[hdk_extern]
+fn verify_priv_msg_proof(proof: PrivateEntryProof) -> ExternResult<bool> {
+    verify_private_entry_proof(proof)
+}
+
 #[hdk_extern]
 fn get_activity(
     input: holochain_test_wasm_common::AgentActivitySearch,